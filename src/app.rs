@@ -1,63 +1,134 @@
 //! Main egui/eframe application state and UI orchestration.
 
-use crate::config::AppConfig;
+use crate::calibration_report::CalibrationReport;
+use crate::config::{AppConfig, ConfigOverrides, DialogBackend, ExportBackup};
+use crate::error::{CurcatError, Preference};
+use crate::export::{ExportOptions, TimeExportFormat};
+use crate::fit::FitModel;
 use crate::i18n::{I18n, TextKey, UiLanguage};
 use crate::image::{
-    ImageFilters, ImageMeta, LoadedImage, apply_image_filters, describe_aspect_ratio,
-    flip_color_image_horizontal, flip_color_image_vertical, format_system_time,
-    human_readable_bytes, rotate_color_image_ccw, rotate_color_image_cw, total_pixel_count,
+    ImageFilters, ImageMeta, LoadedImage, Preprocessing, apply_image_filters, apply_preprocessing,
+    describe_aspect_ratio, flip_color_image_horizontal, flip_color_image_vertical,
+    format_system_time, human_readable_bytes, rotate_color_image_ccw, rotate_color_image_cw,
+    total_pixel_count,
 };
 use crate::image::{ImageTransformOp, ImageTransformRecord};
-use crate::interp::InterpAlgorithm;
-use crate::snap::{SnapFeatureSource, SnapThresholdKind};
+use crate::interp::{DEFAULT_SPLINE_SPAN, InterpAlgorithm, SampleGrid, SmoothingFilter};
+use crate::profiling::profile_function;
+use crate::snap::{ColorSampleMode, SNAP_SCORERS, SnapFeatureSource, SnapThresholdKind};
 use crate::types::{
     AngleDirection, AngleUnit, AxisMapping, AxisUnit, CoordSystem, PolarMapping, ScaleKind,
 };
 use egui::{Color32, Context, Key, Pos2, Vec2, pos2};
 
-use egui_file_dialog::{DialogState, FileDialog};
 use std::{
+    borrow::Cow,
     path::{Path, PathBuf},
-    time::Duration,
 };
 
+mod affine_calibration;
+mod auto_calibration;
 mod auto_trace;
 mod calibration;
+mod calibration_sandbox;
+mod chart_text;
 mod clipboard;
+mod config_overrides;
+mod config_reload;
 mod constants;
+mod continuity;
+mod crossings;
+mod curve_stats;
+mod display_scale;
+mod error_bars;
 mod export_helpers;
+mod export_history;
+mod export_jobs;
+mod export_profiles;
+mod export_range;
 mod export_state;
+mod export_validation;
+mod file_chooser;
+#[cfg(test)]
+mod fixtures;
+#[cfg(test)]
+mod harness;
+mod idle;
 mod image_loader;
 mod image_state;
 mod interaction;
+mod legend;
+mod marker_detection;
+mod perspective;
+mod plot_crop;
+mod point_editing;
+mod point_import;
+mod point_locks;
+mod point_selection;
 mod points;
 mod project_state;
+mod recalibration;
+mod sample_grid;
+mod scale_suggestion;
+mod selection;
+mod shortcuts;
+mod smith_calibration;
+mod smoothing;
+mod snap_area;
 mod snap_helpers;
 mod snap_state;
+mod stitching;
+mod surface_export;
+mod swatches;
 mod ui;
 mod ui_state;
+mod unsaved_changes;
+mod view_mode;
+mod zoom_region;
 
 pub use crate::util::safe_usize_to_f32;
+pub use auto_calibration::AutoCalibrationReview;
 pub use auto_trace::{AutoTraceConfig, AutoTraceDirection};
 pub use calibration::{
-    AxisCalUi, AxisValueField, CalIntSnapSticky, CalSnapEndpoint, CalSnapGuide, CalibrationState,
-    PickMode, PolarCalUi,
+    AffineCalUi, AffineRefUi, AxisCalUi, AxisTimeUi, AxisValueField, CalIntSnapSticky,
+    CalSnapEndpoint, CalSnapGuide, CalibrationState, ChartTextField, PickMode, PolarCalUi,
+    SmithCalUi,
 };
+pub use calibration_sandbox::{CalibrationSandbox, SandboxSummary};
+pub use config_reload::ConfigWatcher;
 pub use constants::*;
-pub use export_state::{ExportKind, ExportState, SAMPLE_COUNT_MIN};
+pub use curve_stats::CurveAnalysis;
+pub use error_bars::{ErrorBarMode, PendingErrorBar};
+pub use export_state::{
+    ExportJob, ExportJobResult, ExportKind, ExportState, SAMPLE_COUNT_MIN, SPLINE_SPAN_MAX,
+};
+use file_chooser::DialogPoll;
 pub use image_state::{
     ImageLoadRequest, ImageLoadResult, ImageState, PendingImageLimitPrompt, PendingImageMeta,
-    PendingImageTask, ZoomAnchor, ZoomIntent,
+    PendingImageTask, PendingSvgScalePrompt, ZoomAnchor, ZoomIntent,
 };
 pub use interaction::{AutoPlaceState, DragTarget, InteractionState, PrimaryPressInfo};
-pub use points::{PickedPoint, PointsState};
+pub use marker_detection::MarkerReview;
+pub use point_import::ImportedDataset;
+pub use point_selection::PointSelectionDrag;
+pub use points::{Curve, CurveSet, PickedPoint, PointFlag, PointsState};
 pub use project_state::ProjectState;
-pub use snap_state::{PointInputMode, SnapBuildJob, SnapState};
+pub use recalibration::RecalibrationReview;
+pub use sample_grid::parse_sample_xs;
+pub use scale_suggestion::ScaleSuggestion;
+pub use smoothing::{LOESS_ITERATIONS_MAX, SMOOTHING_MAX};
+pub use snap_state::{PointInputMode, SnapBuildJob, SnapQuery, SnapQueryKey, SnapState};
+pub use stitching::StitchState;
+pub use swatches::ColorSwatch;
 pub use ui_state::{NativeDialog, SidePanelPosition, StatusLevel, StatusMessage, UiState};
+pub use unsaved_changes::DeferredAction;
+pub use view_mode::VIEW_FLAG;
+pub use zoom_region::ZoomRegionDrag;
 /// Top-level application state for the Curcat UI.
 #[allow(clippy::struct_excessive_bools)]
 pub struct CurcatApp {
     config: AppConfig,
+    config_watcher: Option<ConfigWatcher>,
     image: ImageState,
     project: ProjectState,
     calibration: CalibrationState,
@@ -68,13 +139,6 @@ pub struct CurcatApp {
     ui: UiState,
 }
 
-enum DialogPoll {
-    Picked(PathBuf),
-    Cancelled,
-    Closed,
-    Open,
-}
-
 impl Default for CurcatApp {
     #[allow(clippy::too_many_lines)]
     fn default() -> Self {
@@ -90,14 +154,22 @@ impl Default for CurcatApp {
             .unwrap_or(Color32::from_rgb(236, 214, 96));
         Self {
             config,
+            config_watcher: None,
             image: ImageState {
                 image: None,
                 base_pixels: None,
                 filters: ImageFilters::default(),
+                preprocessing: Preprocessing::default(),
                 meta: None,
+                svg_scale_percent: None,
                 transform: ImageTransformRecord::identity(),
+                perspective_warps: Vec::new(),
+                perspective_corners: Vec::new(),
+                horizon_start: None,
+                crop_suggestion: None,
                 pan: Vec2::ZERO,
                 last_viewport_size: None,
+                last_image_rect: None,
                 last_pixels_per_point: 1.0,
                 skip_pan_sync_once: false,
                 pending_fit_on_load: false,
@@ -110,22 +182,38 @@ impl Default for CurcatApp {
             project: ProjectState {
                 pending_image_task: None,
                 pending_image_limit_prompt: None,
+                pending_svg_scale_prompt: None,
                 pending_project_apply: None,
                 pending_project_save: None,
                 project_prompt: None,
+                saved_fingerprint: None,
+                unsaved_prompt: None,
+                after_save: None,
+                close_confirmed: false,
+                document_path: None,
+                restore_prompt: None,
+                config_overrides: ConfigOverrides::default(),
+                swatches: Vec::new(),
                 title: None,
                 description: None,
+                axis_titles: Default::default(),
+                secondary_y_title: String::new(),
+                export_history: Vec::new(),
+                validation_rules: Vec::new(),
+                parameter_name: String::new(),
                 active_dialog: None,
                 last_project_dir: None,
                 last_project_path: None,
                 last_image_dir: None,
                 last_export_dir: None,
+                stitch: StitchState::default(),
             },
             calibration: CalibrationState {
                 pick_mode: PickMode::None,
                 pending_value_focus: None,
                 cal_x: AxisCalUi::new(AxisUnit::Float, ScaleKind::Linear),
                 cal_y: AxisCalUi::new(AxisUnit::Float, ScaleKind::Linear),
+                cal_secondary_y: AxisCalUi::new(AxisUnit::Float, ScaleKind::Linear),
                 polar_cal: PolarCalUi {
                     origin: None,
                     radius: AxisCalUi::new(AxisUnit::Float, ScaleKind::Linear),
@@ -133,6 +221,8 @@ impl Default for CurcatApp {
                     angle_unit: AngleUnit::Degrees,
                     angle_direction: AngleDirection::Cw,
                 },
+                affine_cal: AffineCalUi::new(),
+                smith_cal: SmithCalUi::new(),
                 coord_system: CoordSystem::Cartesian,
                 calibration_angle_snap: false,
                 snap_ext: true,
@@ -144,9 +234,14 @@ impl Default for CurcatApp {
                 drag_last_pixel: None,
                 snap_guides: [None; CAL_SNAP_GUIDE_SLOTS],
                 int_snap_sticky: None,
+                sandbox: None,
+                auto_review: None,
+                recalibration_review: None,
+                scale_suggestion: None,
             },
             points: PointsState {
                 points: Vec::new(),
+                curves: CurveSet::default(),
                 points_numeric_dirty: true,
                 cached_sorted_preview: Vec::new(),
                 cached_sorted_numeric: Vec::new(),
@@ -154,33 +249,88 @@ impl Default for CurcatApp {
                 sorted_numeric_dirty: true,
                 last_x_mapping: None,
                 last_y_mapping: None,
+                last_secondary_y_mapping: None,
                 last_polar_mapping: None,
+                last_smith_mapping: None,
                 last_coord_system: CoordSystem::Cartesian,
                 show_curve_segments: true,
+                show_point_confidence: false,
+                record_capture_times: false,
+                imported: None,
+                error_bar_mode: ErrorBarMode::Off,
+                pending_error_bar: None,
             },
             snap: SnapState {
                 point_input_mode: PointInputMode::Free,
                 contrast_search_radius: 12.0,
                 contrast_threshold: 12.0,
                 centerline_threshold: 40.0,
+                scorer_thresholds: SNAP_SCORERS
+                    .iter()
+                    .map(|entry| entry.default_threshold)
+                    .collect(),
                 snap_feature_source: SnapFeatureSource::LumaGradient,
                 snap_threshold_kind: SnapThresholdKind::Gradient,
                 snap_target_color: Color32::from_rgb(200, 60, 60),
                 snap_color_tolerance: 30.0,
+                color_sample_mode: ColorSampleMode::Pixel,
                 snap_maps: None,
+                curve_mask: None,
+                #[cfg(feature = "segmentation")]
+                pending_mask_job: None,
+                search_area: None,
+                search_area_draft: Vec::new(),
+                ignore_text: true,
+                suppress_grid: false,
+                bridge_gaps: false,
                 pending_snap_job: None,
                 snap_maps_dirty: true,
+                persist_next_snap_build: false,
                 snap_overlay_color: default_overlay_color,
                 snap_overlay_choices: default_overlay_choices,
                 snap_overlay_choice: 0,
+                last_query: None,
+                preview_searched_at: None,
             },
             export: ExportState {
                 sample_count: 200,
+                sample_count_follows_axis: true,
+                followed_axis_pixels: None,
+                sample_grid: SampleGrid::Even,
+                sample_xs_text: String::new(),
+                grid_range: None,
+                grid_step: None,
+                x_range: None,
                 export_kind: ExportKind::Interpolated,
                 interp_algorithm: InterpAlgorithm::Linear,
+                spline_span: DEFAULT_SPLINE_SPAN,
                 raw_include_distances: false,
                 raw_include_angles: false,
+                raw_include_steps: false,
+                raw_include_flags: false,
+                raw_include_capture_times: false,
+                raw_include_confidence: false,
                 polar_export_include_cartesian: false,
+                fit_model: FitModel::Polynomial,
+                fit_degree: 2,
+                include_fit_column: false,
+                column_templates: Vec::new(),
+                smoothing: 0,
+                smoothing_filter: SmoothingFilter::default(),
+                loess_iterations: 2,
+                step_breakpoints: false,
+                unstack_layers: false,
+                crossing_level: 0.0,
+                show_crossings: false,
+                area_bounds: None,
+                long_format: false,
+                decimals: ExportOptions::default().decimals,
+                significant_figures: None,
+                csv_delimiter: ExportOptions::default().csv_delimiter,
+                time_format: TimeExportFormat::Iso8601,
+                active_profile: None,
+                profile_name_draft: String::new(),
+                pending_export_job: None,
             },
             interaction: InteractionState {
                 auto_place_cfg,
@@ -188,6 +338,13 @@ impl Default for CurcatApp {
                 auto_trace_cfg: AutoTraceConfig::default(),
                 primary_press: None,
                 middle_pan_enabled: false,
+                selection: None,
+                point_selection: Vec::new(),
+                point_selection_drag: None,
+                zoom_region: None,
+                snap_area_drag: None,
+                x_range_drag: None,
+                marker_review: None,
             },
             ui: UiState {
                 language,
@@ -196,22 +353,52 @@ impl Default for CurcatApp {
                 info_window_open: false,
                 points_info_window_open: false,
                 image_filters_window_open: false,
+                preprocess_window_open: false,
                 auto_trace_window_open: false,
+                flag_review_window_open: false,
+                calibration_sandbox_window_open: false,
+                focus_dim_enabled: false,
+                loupe_enabled: false,
+                result_plot_open: false,
+                stitch_window_open: false,
+                chart_text_window_open: false,
+                export_history_window_open: false,
+                validation_window_open: false,
+                keymap_window_open: false,
+                keymap_recording: None,
+                view_only: false,
+                #[cfg(feature = "profiling")]
+                profiler: None,
+                validation_report: None,
+                flag_review_selected: None,
+                flag_comment_draft: String::new(),
+                swatch_name_draft: String::new(),
                 last_status: None,
                 status_copy_feedback_until: None,
+                toasts: Vec::new(),
+                parent_window: String::new(),
+                pixels_per_point: None,
+                last_activity: None,
+                window_title: String::new(),
+                idle: false,
             },
         }
     }
 }
 
 impl CurcatApp {
-    /// Create a new app and optionally queue an initial image load.
+    /// Create a new app and optionally queue an initial image or project load.
     pub fn new_with_initial_path(ctx: &Context, initial_path: Option<&Path>) -> Self {
         egui_extras::install_image_loaders(ctx);
         let mut app = Self::default();
-        if let Some(p) = initial_path {
+        app.start_config_watcher(ctx);
+        if let Some(p) = initial_path.filter(|p| clipboard::is_project_path(p)) {
+            app.handle_project_load(p.to_owned());
+        } else if let Some(p) = initial_path {
             app.remember_image_dir_from_path(p);
             app.start_loading_image_from_path(p.to_owned());
+        } else {
+            app.offer_session_restore();
         }
         app
     }
@@ -240,10 +427,34 @@ impl CurcatApp {
             return;
         }
         self.ui.language = language;
-        if let Err(err) = self.config.persist_ui_language(language) {
-            self.set_status_error(match self.ui.language {
-                UiLanguage::En => format!("Failed to save language preference: {err}"),
-                UiLanguage::Ru => format!("Не удалось сохранить выбранный язык интерфейса: {err}"),
+        if let Err(source) = self.config.persist_ui_language(language) {
+            self.report_error(&CurcatError::Preference {
+                preference: Preference::Language,
+                source,
+            });
+        }
+    }
+
+    pub(crate) fn set_dialog_backend(&mut self, backend: DialogBackend) {
+        if self.config.dialogs.backend == backend {
+            return;
+        }
+        if let Err(source) = self.config.persist_dialog_backend(backend) {
+            self.report_error(&CurcatError::Preference {
+                preference: Preference::DialogBackend,
+                source,
+            });
+        }
+    }
+
+    pub(crate) fn set_export_backup(&mut self, backup: ExportBackup) {
+        if self.config.export.backup == backup {
+            return;
+        }
+        if let Err(source) = self.config.persist_export_backup(backup) {
+            self.report_error(&CurcatError::Preference {
+                preference: Preference::ExportBackup,
+                source,
             });
         }
     }
@@ -268,9 +479,24 @@ impl CurcatApp {
         self.ui.last_status = Some(StatusMessage {
             text: msg.into(),
             level,
+            details: None,
+            created_at: std::time::Instant::now(),
+        });
+        self.ui.status_copy_feedback_until = None;
+    }
+
+    /// Show `err` in the status bar and as a notification, its source chain behind "Show details".
+    fn report_error(&mut self, err: &CurcatError) {
+        let text = err.user_message(self.ui.language);
+        let details = err.details();
+        self.ui.last_status = Some(StatusMessage {
+            text: text.clone(),
+            level: StatusLevel::Error,
+            details: Some(details.clone()),
             created_at: std::time::Instant::now(),
         });
         self.ui.status_copy_feedback_until = None;
+        self.push_toast_with_details(StatusLevel::Error, text, Some(details));
     }
 
     fn begin_pick_mode(&mut self, mode: PickMode) {
@@ -290,6 +516,7 @@ impl CurcatApp {
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     const fn pick_mode_label(&self, mode: PickMode) -> Option<&'static str> {
         match mode {
             PickMode::X1 => Some(match self.ui.language {
@@ -308,6 +535,14 @@ impl CurcatApp {
                 UiLanguage::En => "Picking Y2",
                 UiLanguage::Ru => "Выбор Y2",
             }),
+            PickMode::SecondaryY1 => Some(match self.ui.language {
+                UiLanguage::En => "Picking Y'1",
+                UiLanguage::Ru => "Выбор Y'1",
+            }),
+            PickMode::SecondaryY2 => Some(match self.ui.language {
+                UiLanguage::En => "Picking Y'2",
+                UiLanguage::Ru => "Выбор Y'2",
+            }),
             PickMode::Origin => Some(match self.ui.language {
                 UiLanguage::En => "Picking origin",
                 UiLanguage::Ru => "Выбор начала координат",
@@ -328,30 +563,70 @@ impl CurcatApp {
                 UiLanguage::En => "Picking A2",
                 UiLanguage::Ru => "Выбор A2",
             }),
+            PickMode::AffineRef(_) => Some(match self.ui.language {
+                UiLanguage::En => "Picking reference point",
+                UiLanguage::Ru => "Выбор опорной точки",
+            }),
+            PickMode::SmithCenter => Some(match self.ui.language {
+                UiLanguage::En => "Picking chart centre",
+                UiLanguage::Ru => "Выбор центра диаграммы",
+            }),
+            PickMode::SmithShort => Some(match self.ui.language {
+                UiLanguage::En => "Picking short-circuit point",
+                UiLanguage::Ru => "Выбор точки короткого замыкания",
+            }),
             PickMode::CurveColor => Some(match self.ui.language {
                 UiLanguage::En => "Pick curve color",
                 UiLanguage::Ru => "Выбор цвета кривой",
             }),
+            PickMode::LegendEntry => Some(match self.ui.language {
+                UiLanguage::En => "Legend: click the swatch of each curve",
+                UiLanguage::Ru => "Легенда: щёлкните образец каждой кривой",
+            }),
+            PickMode::ChartText(_) => Some(match self.ui.language {
+                UiLanguage::En => "Click the text to read",
+                UiLanguage::Ru => "Щёлкните текст для распознавания",
+            }),
+            PickMode::PerspectiveCorner => Some(match self.ui.language {
+                UiLanguage::En => "Perspective: click the four plot corners",
+                UiLanguage::Ru => "Перспектива: щёлкните четыре угла графика",
+            }),
+            PickMode::HorizonPoint => Some(match self.ui.language {
+                UiLanguage::En => "Level: click two points that should be horizontal",
+                UiLanguage::Ru => "Выравнивание: щёлкните две точки на будущей горизонтали",
+            }),
             PickMode::AutoTrace => Some(match self.ui.language {
                 UiLanguage::En => "Auto-trace: click start point",
                 UiLanguage::Ru => "Авто-трассировка: выберите стартовую точку",
             }),
+            PickMode::ZoomRegion => Some(match self.ui.language {
+                UiLanguage::En => "Zoom: drag a rectangle over the image",
+                UiLanguage::Ru => "Масштаб: обведите прямоугольник на изображении",
+            }),
+            PickMode::MarkerSample => Some(match self.ui.language {
+                UiLanguage::En => "Markers: click one marker of the series",
+                UiLanguage::Ru => "Маркеры: щёлкните один маркер серии",
+            }),
+            PickMode::MarkerReview => Some(match self.ui.language {
+                UiLanguage::En => "Markers: click false detections to drop them",
+                UiLanguage::Ru => "Маркеры: щёлкните ложные находки, чтобы убрать их",
+            }),
+            PickMode::SnapArea => Some(match self.ui.language {
+                UiLanguage::En => {
+                    "Snap area: drag a rectangle or click the corners, Enter to close"
+                }
+                UiLanguage::Ru => {
+                    "Область привязки: обведите прямоугольник или щёлкните углы, Enter — замкнуть"
+                }
+            }),
+            PickMode::SelectPoints => Some(match self.ui.language {
+                UiLanguage::En => "Select: drag a rectangle over points, or hold Alt for a lasso",
+                UiLanguage::Ru => "Выбор: обведите точки прямоугольником или, удерживая Alt, лассо",
+            }),
             PickMode::None => None,
         }
     }
 
-    fn poll_dialog(ctx: &Context, dialog: &mut FileDialog) -> DialogPoll {
-        dialog.update(ctx);
-        dialog.take_picked().map_or_else(
-            || match dialog.state() {
-                DialogState::Cancelled => DialogPoll::Cancelled,
-                DialogState::Closed => DialogPoll::Closed,
-                _ => DialogPoll::Open,
-            },
-            DialogPoll::Picked,
-        )
-    }
-
     fn reset_calibrations(&mut self) {
         self.calibration.cal_x.p1 = None;
         self.calibration.cal_x.p2 = None;
@@ -370,24 +645,41 @@ impl CurcatApp {
         self.calibration.polar_cal.angle.p2 = None;
         self.calibration.polar_cal.angle.v1_text.clear();
         self.calibration.polar_cal.angle.v2_text.clear();
+        self.calibration.affine_cal.points = vec![AffineRefUi::default(); AffineCalUi::MIN_POINTS];
+        self.calibration.smith_cal.center = None;
+        self.calibration.smith_cal.short = None;
         self.calibration.pick_mode = PickMode::None;
         self.calibration.pending_value_focus = None;
+        self.calibration.auto_review = None;
+        self.calibration.recalibration_review = None;
         self.clear_calibration_drag_runtime();
+        self.interaction.selection = self
+            .interaction
+            .selection
+            .filter(|target| matches!(target, DragTarget::CurvePoint(_)));
     }
 
     fn update_filtered_texture(&mut self) {
+        profile_function!();
         let Some(base) = self.image.base_pixels.as_ref() else {
             return;
         };
         let Some(image) = self.image.image.as_mut() else {
             return;
         };
-        let filtered = apply_image_filters(base, self.image.filters);
+        let preprocessing = self.image.preprocessing;
+        let working = if preprocessing.is_identity() {
+            Cow::Borrowed(base)
+        } else {
+            Cow::Owned(apply_preprocessing(base, preprocessing))
+        };
+        let filtered = apply_image_filters(&working, self.image.filters);
         image.replace_pixels(filtered);
     }
 
     fn after_image_pixels_changed(&mut self) {
         self.mark_snap_maps_dirty();
+        self.calibration.scale_suggestion = None;
         self.refresh_snap_overlay_palette();
         self.interaction.auto_place_state = AutoPlaceState::default();
         self.interaction.primary_press = None;
@@ -400,38 +692,64 @@ impl CurcatApp {
 
     fn reset_after_new_image(&mut self) {
         self.reset_calibrations();
-        self.points.points.clear();
-        self.mark_points_dirty();
+        // A chosen sample count was for the previous chart; this one's axis sets it again.
+        self.export.sample_count_follows_axis = true;
+        self.export.followed_axis_pixels = None;
+        self.reset_curves();
+        self.points.imported = None;
         self.image.touch_pan_active = false;
         self.image.touch_pan_last = None;
         self.image.pan = Vec2::ZERO;
+        self.snap.curve_mask = None;
+        #[cfg(feature = "segmentation")]
+        {
+            self.snap.pending_mask_job = None;
+        }
+        self.snap.search_area = None;
+        self.snap.search_area_draft.clear();
+        self.interaction.marker_review = None;
+        self.image.crop_suggestion = None;
         self.after_image_pixels_changed();
+        self.snap.persist_next_snap_build = true;
         self.image.zoom_target = self.image.zoom;
         self.image.zoom_intent = ZoomIntent::TargetPan(self.image.pan);
     }
 
     fn reset_after_image_transform(&mut self) {
+        self.calibration.auto_review = None;
+        self.calibration.recalibration_review = None;
         self.clear_calibration_drag_runtime();
         self.image.touch_pan_active = false;
         self.image.touch_pan_last = None;
+        self.snap.curve_mask = None;
+        #[cfg(feature = "segmentation")]
+        {
+            self.snap.pending_mask_job = None;
+        }
+        self.snap.search_area = None;
+        self.snap.search_area_draft.clear();
+        self.interaction.marker_review = None;
+        self.image.crop_suggestion = None;
         self.after_image_pixels_changed();
+        self.snap.persist_next_snap_build = true;
         self.image.zoom_target = self.image.zoom;
         self.image.zoom_intent = ZoomIntent::TargetPan(self.image.pan);
     }
 
-    fn set_loaded_image(&mut self, mut image: LoadedImage, meta: Option<ImageMeta>) {
-        let base_pixels = image.pixels.clone();
-        if !self.image.filters.is_identity() {
-            let filtered = apply_image_filters(&base_pixels, self.image.filters);
-            image.replace_pixels(filtered);
-        }
-        self.image.base_pixels = Some(base_pixels);
+    fn set_loaded_image(&mut self, image: LoadedImage, meta: Option<ImageMeta>) {
+        self.image.base_pixels = Some(image.pixels.clone());
         self.image.image = Some(image);
+        if !self.image.filters.is_identity() || !self.image.preprocessing.is_identity() {
+            self.update_filtered_texture();
+        }
         self.image.meta = meta;
         self.image.transform = ImageTransformRecord::identity();
+        self.image.perspective_warps.clear();
         self.image.pan = Vec2::ZERO;
         self.project.title = None;
         self.project.description = None;
+        self.project.axis_titles = Default::default();
+        self.project.secondary_y_title.clear();
         self.image.zoom = 1.0;
         self.image.zoom_target = 1.0;
         self.image.zoom_intent = ZoomIntent::TargetPan(self.image.pan);
@@ -439,6 +757,7 @@ impl CurcatApp {
     }
 
     fn apply_image_transform(&mut self, op: ImageTransformOp, status: Option<&str>) {
+        profile_function!();
         let Some(base) = self.image.base_pixels.as_mut() else {
             return;
         };
@@ -482,7 +801,11 @@ impl CurcatApp {
                 mapped.y.clamp(0.0, new_max_y),
             )
         };
+        self.map_image_positions(map_pos);
+    }
 
+    /// Move calibration anchors and curve points after the image pixels were remapped.
+    fn map_image_positions(&mut self, map_pos: impl Fn(Pos2) -> Pos2 + Copy) {
         self.calibration.cal_x.p1 = self.calibration.cal_x.p1.map(map_pos);
         self.calibration.cal_x.p2 = self.calibration.cal_x.p2.map(map_pos);
         self.calibration.cal_y.p1 = self.calibration.cal_y.p1.map(map_pos);
@@ -492,8 +815,19 @@ impl CurcatApp {
         self.calibration.polar_cal.radius.p2 = self.calibration.polar_cal.radius.p2.map(map_pos);
         self.calibration.polar_cal.angle.p1 = self.calibration.polar_cal.angle.p1.map(map_pos);
         self.calibration.polar_cal.angle.p2 = self.calibration.polar_cal.angle.p2.map(map_pos);
+        self.calibration.smith_cal.center = self.calibration.smith_cal.center.map(map_pos);
+        self.calibration.smith_cal.short = self.calibration.smith_cal.short.map(map_pos);
+        for point in &mut self.calibration.affine_cal.points {
+            point.pixel = point.pixel.map(map_pos);
+        }
 
-        for point in &mut self.points.points {
+        let parked = self
+            .points
+            .curves
+            .curves
+            .iter_mut()
+            .flat_map(|curve| curve.points.iter_mut());
+        for point in self.points.points.iter_mut().chain(parked) {
             point.pixel = map_pos(point.pixel);
         }
         self.mark_points_dirty();
@@ -738,16 +1072,49 @@ impl CurcatApp {
     }
 
     fn cartesian_mappings(&self) -> (Option<AxisMapping>, Option<AxisMapping>) {
+        match self.calibration.coord_system {
+            CoordSystem::Smith => return (None, None),
+            CoordSystem::Affine => {
+                return self
+                    .calibration
+                    .affine_cal
+                    .mapping()
+                    .map_or((None, None), |m| (Some(m.x), Some(m.y)));
+            }
+            CoordSystem::Cartesian | CoordSystem::Polar => {}
+        }
         (
             self.calibration.cal_x.mapping(),
             self.calibration.cal_y.mapping(),
         )
     }
 
+    /// Mapping of the secondary Y axis; Cartesian calibration only.
+    fn secondary_y_mapping(&self) -> Option<AxisMapping> {
+        if self.calibration.coord_system == CoordSystem::Cartesian {
+            self.calibration.cal_secondary_y.mapping()
+        } else {
+            None
+        }
+    }
+
     fn polar_mapping(&self) -> Option<PolarMapping> {
         self.calibration.polar_cal.mapping()
     }
 
+    /// Quality report of a complete Cartesian calibration.
+    ///
+    /// Uses the grid-scale suggestion only once it has been detected, so an export never waits
+    /// on line detection.
+    fn calibration_report(&self) -> Option<CalibrationReport> {
+        if self.calibration.coord_system != CoordSystem::Cartesian {
+            return None;
+        }
+        let (x, y) = self.cartesian_mappings();
+        let grid = self.calibration.scale_suggestion.unwrap_or_default();
+        Some(CalibrationReport::new(&x?, &y?, [grid.x, grid.y]))
+    }
+
     fn calibration_ready(&self) -> bool {
         match self.calibration.coord_system {
             CoordSystem::Cartesian => {
@@ -755,6 +1122,8 @@ impl CurcatApp {
                 x.is_some() && y.is_some()
             }
             CoordSystem::Polar => self.polar_mapping().is_some(),
+            CoordSystem::Affine => self.calibration.affine_cal.mapping().is_some(),
+            CoordSystem::Smith => self.smith_mapping().is_some(),
         }
     }
 
@@ -776,116 +1145,101 @@ impl CurcatApp {
             || a2_invalid
     }
 
-    const fn axis_labels(&self) -> (&'static str, &'static str) {
+    /// Names of the x/y columns; Cartesian and affine axes take their titles when set.
+    fn axis_labels(&self) -> (String, String) {
+        let titled = |idx: usize, fallback: &str| {
+            let title = self.project.axis_titles[idx].trim();
+            if title.is_empty() { fallback } else { title }.to_string()
+        };
         match self.calibration.coord_system {
-            CoordSystem::Cartesian => ("x", "y"),
-            CoordSystem::Polar => ("theta", "r"),
+            CoordSystem::Cartesian | CoordSystem::Affine => (titled(0, "x"), titled(1, "y")),
+            CoordSystem::Polar => ("theta".to_string(), "r".to_string()),
+            CoordSystem::Smith => ("r".to_string(), "x".to_string()),
         }
     }
 
-    fn trigger_shift_export_hotkey(&mut self, ctx: &Context, key: Key, action: fn(&mut Self)) {
-        if self.project.active_dialog.is_none()
-            && ctx.input(|i| i.key_pressed(key) && i.modifiers.command && i.modifiers.shift)
-        {
-            action(self);
-        }
+    /// Name of the y column of curves read against the secondary Y axis.
+    fn secondary_y_label(&self) -> String {
+        let title = self.project.secondary_y_title.trim();
+        if title.is_empty() { "y2" } else { title }.to_string()
     }
 }
 
 impl eframe::App for CurcatApp {
+    fn ui(&mut self, root_ui: &mut egui::Ui, frame: &mut eframe::Frame) {
+        if self.ui.parent_window.is_empty() {
+            self.ui.parent_window = crate::portal::parent_window_id(frame);
+        }
+        self.ui_frame(root_ui);
+        // System dialogs are parented to the main window, which only `eframe` hands out.
+        if let Some(dialog) = self.project.active_dialog.as_mut() {
+            dialog.chooser_mut().start_native(frame);
+        }
+    }
+}
+
+impl CurcatApp {
+    /// One full frame: hotkeys, background jobs, panels, windows and dialogs.
+    ///
+    /// Needs no `eframe::Frame`, so the test harness can drive it on a bare `egui::Context`.
     #[allow(clippy::too_many_lines)]
-    fn ui(&mut self, root_ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+    pub(crate) fn ui_frame(&mut self, root_ui: &mut egui::Ui) {
+        crate::profiling::new_frame();
+        profile_function!();
         let ctx = root_ui.ctx().clone();
-        let title = match self.ui.language {
-            UiLanguage::En => "Curcat — Graph Digitizer",
-            UiLanguage::Ru => "Curcat — Оцифровка графиков",
-        };
-        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.to_string()));
+        // Every viewport command asks for another frame, so only changes are sent.
+        let title = self.window_title();
+        if title != self.ui.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.ui.window_title = title;
+        }
+        self.follow_display_scale(&ctx);
+        self.track_idle(&ctx);
+        self.intercept_close_request(&ctx);
 
+        self.poll_config_watcher(&ctx);
         self.poll_image_loader(&ctx);
-        self.poll_project_save_job();
+        self.poll_stitch_job();
+        #[cfg(feature = "segmentation")]
+        self.poll_segmentation_job();
+        if self.poll_project_save_job()
+            && let Some(action) = self.project.after_save.take()
+        {
+            self.run_deferred_action(&ctx, action);
+        }
         self.poll_snap_build_job();
+        self.poll_export_job(&ctx);
+        self.follow_axis_sample_count();
         // Global hotkeys (ignored while typing in text fields)
         let wants_kb = ctx.egui_wants_keyboard_input();
         if !wants_kb {
-            // Ctrl/Cmd + B: toggle side panel
-            if ctx.input(|i| i.key_pressed(Key::B) && i.modifiers.command) {
-                self.ui.side_open = !self.ui.side_open;
-            }
-            // Ctrl/Cmd + O: open image
-            if self.project.active_dialog.is_none()
-                && ctx.input(|i| i.key_pressed(Key::O) && i.modifiers.command)
-            {
-                self.open_image_dialog();
-            }
-            if self.project.active_dialog.is_none()
-                && ctx.input(|i| i.key_pressed(Key::P) && i.modifiers.command && i.modifiers.shift)
-            {
-                self.open_project_dialog();
-            }
-            if self.project.active_dialog.is_none()
-                && self.image.meta.as_ref().and_then(|m| m.path()).is_some()
-                && ctx.input(|i| i.key_pressed(Key::S) && i.modifiers.command)
-            {
-                self.save_project_dialog();
-            }
-            // Ctrl/Cmd + V: paste image from clipboard
-            if self.project.active_dialog.is_none()
-                && ctx.input(|i| i.key_pressed(Key::V) && i.modifiers.command)
-            {
-                self.paste_image_from_clipboard(&ctx);
-            }
-            // Ctrl/Cmd + Shift + [C/J/R/E/H/X/M]: export formats.
-            for (key, action) in [
-                (Key::C, Self::start_export_csv as fn(&mut Self)),
-                (Key::J, Self::start_export_json as fn(&mut Self)),
-                (Key::R, Self::start_export_ron as fn(&mut Self)),
-                (Key::E, Self::start_export_xlsx as fn(&mut Self)),
-                (Key::H, Self::start_export_html as fn(&mut Self)),
-                (Key::X, Self::start_export_xml as fn(&mut Self)),
-                (Key::M, Self::start_export_markdown as fn(&mut Self)),
-            ] {
-                self.trigger_shift_export_hotkey(&ctx, key, action);
-            }
-            // Ctrl/Cmd + Shift + D: clear all points
-            if ctx.input(|i| i.key_pressed(Key::D) && i.modifiers.command && i.modifiers.shift) {
-                self.clear_all_points();
-            }
-            // Ctrl/Cmd + I: show image info
-            if self.image.image.is_some()
-                && ctx.input(|i| i.key_pressed(Key::I) && i.modifiers.command)
-            {
-                self.ui.info_window_open = true;
-            }
-            // Ctrl/Cmd + Shift + F: image filters
-            if ctx.input(|i| i.key_pressed(Key::F) && i.modifiers.command && i.modifiers.shift) {
-                self.ui.image_filters_window_open = true;
-            }
-            // Ctrl/Cmd + Shift + T: auto-trace
-            if ctx.input(|i| i.key_pressed(Key::T) && i.modifiers.command && i.modifiers.shift) {
-                self.ui.auto_trace_window_open = true;
-            }
-            // Ctrl/Cmd + F: fit view to viewport
-            if self.image.image.is_some()
-                && ctx.input(|i| i.key_pressed(Key::F) && i.modifiers.command && !i.modifiers.shift)
-            {
-                self.fit_image_to_viewport();
-            }
-            // Ctrl/Cmd + R: reset view (zoom 100%, pan origin)
-            if self.image.image.is_some()
-                && ctx.input(|i| i.key_pressed(Key::R) && i.modifiers.command && !i.modifiers.shift)
+            self.handle_shortcut_keys(&ctx);
+        }
+        if !wants_kb && !self.view_only() {
+            // Arrows: nudge the selected point or calibration handle
+            self.handle_nudge_keys(&ctx);
+            // Delete: remove the points selected together
+            if !self.interaction.point_selection.is_empty()
+                && ctx.input(|i| i.key_pressed(Key::Delete))
             {
-                self.reset_view();
-            }
-            // Ctrl/Cmd + Z: undo
-            if ctx.input(|i| i.key_pressed(Key::Z) && i.modifiers.command) {
-                self.undo_last_point();
+                self.delete_selected_points();
             }
         }
 
-        // Esc: cancel active pick mode
+        // Esc: cancel active pick mode, a zoom-region drag and the selection
         if ctx.input(|i| i.key_pressed(Key::Escape)) {
             self.cancel_pick_mode();
+            self.interaction.selection = None;
+            self.interaction.point_selection.clear();
+            self.interaction.zoom_region = None;
+            self.interaction.marker_review = None;
+            self.snap.search_area_draft.clear();
+        }
+        // Enter: close the snap-area polygon being clicked
+        if self.calibration.pick_mode == PickMode::SnapArea
+            && ctx.input(|i| i.key_pressed(Key::Enter))
+        {
+            self.finish_snap_area_polygon();
         }
 
         let needs_open_hint = self.image.image.is_none();
@@ -895,13 +1249,21 @@ impl eframe::App for CurcatApp {
                     || ui::common::axis_needs_attention(&self.calibration.cal_y)
             }
             CoordSystem::Polar => self.polar_needs_attention(),
+            CoordSystem::Affine => self.calibration.affine_cal.mapping().is_none(),
+            CoordSystem::Smith => self.smith_mapping().is_none(),
         };
         if needs_open_hint || needs_cal_hint {
-            ctx.request_repaint_after(Duration::from_millis(16));
+            self.request_attention_frame(&ctx);
         }
 
         egui::Panel::top("top").show_inside(root_ui, |ui| self.ui_top(ui));
         egui::Panel::bottom("status").show_inside(root_ui, |ui| self.ui_status_bar(ui));
+        egui::Panel::bottom("result_plot")
+            .resizable(true)
+            .default_size(220.0)
+            .show_animated_inside(root_ui, self.ui.result_plot_open, |ui| {
+                self.ui_result_plot_panel(ui);
+            });
         let side_panel = match self.ui.side_position {
             SidePanelPosition::Left => egui::Panel::left("side"),
             SidePanelPosition::Right => egui::Panel::right("side"),
@@ -915,17 +1277,33 @@ impl eframe::App for CurcatApp {
         egui::CentralPanel::default().show_inside(root_ui, |ui| self.ui_central_image(&ctx, ui));
         self.ui_image_info_window(&ctx);
         self.ui_image_filters_window(&ctx);
+        self.ui_preprocess_window(&ctx);
         self.ui_auto_trace_window(&ctx);
         self.ui_points_info_window(&ctx);
+        self.ui_flag_review_window(&ctx);
+        self.ui_calibration_sandbox_window(&ctx);
+        self.ui_stitch_window(&ctx);
+        self.ui_chart_text_window(&ctx);
+        self.ui_export_history_window(&ctx);
+        self.ui_validation_window(&ctx);
+        self.ui_keymap_window(&ctx);
+        #[cfg(feature = "profiling")]
+        self.ui_profiler_window(&ctx);
         self.ui_project_prompt(&ctx);
+        self.ui_unsaved_changes_prompt(&ctx);
+        self.ui_session_restore_prompt(&ctx);
         self.ui_image_limits_prompt(&ctx);
+        self.ui_svg_scale_prompt(&ctx);
+        self.ui_plot_crop_prompt(&ctx);
+        self.ui_toasts(&ctx);
 
         let mut close_dialog = false;
         let mut picked_export_path: Option<PathBuf> = None;
+        let mut export_job_path: Option<PathBuf> = None;
 
         if let Some(dialog_state) = self.project.active_dialog.as_mut() {
             match dialog_state {
-                NativeDialog::Open(dialog) => match Self::poll_dialog(&ctx, dialog) {
+                NativeDialog::Open(dialog) => match dialog.poll(&ctx) {
                     DialogPoll::Picked(path) => {
                         self.start_loading_image_from_path(path);
                         close_dialog = true;
@@ -940,7 +1318,32 @@ impl eframe::App for CurcatApp {
                     DialogPoll::Closed => close_dialog = true,
                     DialogPoll::Open => {}
                 },
-                NativeDialog::OpenProject(dialog) => match Self::poll_dialog(&ctx, dialog) {
+                NativeDialog::OpenCurveMask(dialog) => match dialog.poll(&ctx) {
+                    DialogPoll::Picked(path) => {
+                        self.load_curve_mask_from_path(&path);
+                        close_dialog = true;
+                    }
+                    DialogPoll::Cancelled | DialogPoll::Closed => close_dialog = true,
+                    DialogPoll::Open => {}
+                },
+                #[cfg(feature = "segmentation")]
+                NativeDialog::OpenSegmentationModel(dialog) => match dialog.poll(&ctx) {
+                    DialogPoll::Picked(path) => {
+                        self.run_segmentation_model(path);
+                        close_dialog = true;
+                    }
+                    DialogPoll::Cancelled | DialogPoll::Closed => close_dialog = true,
+                    DialogPoll::Open => {}
+                },
+                NativeDialog::OpenStitchScans(dialog) => match dialog.poll_multiple(&ctx) {
+                    DialogPoll::Picked(paths) => {
+                        self.add_stitch_parts(paths);
+                        close_dialog = true;
+                    }
+                    DialogPoll::Cancelled | DialogPoll::Closed => close_dialog = true,
+                    DialogPoll::Open => {}
+                },
+                NativeDialog::OpenProject(dialog) => match dialog.poll(&ctx) {
                     DialogPoll::Picked(path) => {
                         self.handle_project_load(path);
                         close_dialog = true;
@@ -955,7 +1358,7 @@ impl eframe::App for CurcatApp {
                     DialogPoll::Closed => close_dialog = true,
                     DialogPoll::Open => {}
                 },
-                NativeDialog::SaveProject(dialog) => match Self::poll_dialog(&ctx, dialog) {
+                NativeDialog::SaveProject(dialog) => match dialog.poll(&ctx) {
                     DialogPoll::Picked(path) => {
                         self.handle_project_save(&path);
                         close_dialog = true;
@@ -965,53 +1368,65 @@ impl eframe::App for CurcatApp {
                             UiLanguage::En => "Project save canceled.",
                             UiLanguage::Ru => "Сохранение проекта отменено.",
                         });
+                        self.project.after_save = None;
+                        close_dialog = true;
+                    }
+                    DialogPoll::Closed => {
+                        self.project.after_save = None;
                         close_dialog = true;
                     }
-                    DialogPoll::Closed => close_dialog = true,
                     DialogPoll::Open => {}
                 },
-                NativeDialog::SaveExport {
-                    dialog,
-                    payload,
-                    format,
-                } => {
-                    let format = *format;
-                    let format_label = format.label();
-                    match Self::poll_dialog(&ctx, dialog) {
-                        DialogPoll::Picked(path) => {
-                            picked_export_path = Some(path.clone());
-                            match format.export(&path, payload) {
-                                Ok(()) => {
-                                    self.set_status(self.i18n().format_exported(format_label));
-                                }
-                                Err(e) => {
-                                    self.set_status_error(
-                                        self.i18n().format_export_failed(format_label, &e),
-                                    );
-                                }
-                            }
-                            close_dialog = true;
-                        }
-                        DialogPoll::Cancelled => {
-                            self.set_status(match self.ui.language {
-                                UiLanguage::En => "Export canceled.",
-                                UiLanguage::Ru => "Экспорт отменён.",
-                            });
-                            close_dialog = true;
-                        }
-                        DialogPoll::Closed => close_dialog = true,
-                        DialogPoll::Open => {}
+                NativeDialog::SaveExport { dialog, .. }
+                | NativeDialog::SaveSurface { dialog, .. } => match dialog.poll(&ctx) {
+                    DialogPoll::Picked(path) => {
+                        picked_export_path = Some(path.clone());
+                        export_job_path = Some(path);
+                        close_dialog = true;
                     }
-                }
+                    DialogPoll::Cancelled => {
+                        self.set_status(match self.ui.language {
+                            UiLanguage::En => "Export canceled.",
+                            UiLanguage::Ru => "Экспорт отменён.",
+                        });
+                        close_dialog = true;
+                    }
+                    DialogPoll::Closed => close_dialog = true,
+                    DialogPoll::Open => {}
+                },
             }
         }
 
+        let backend_error = self
+            .project
+            .active_dialog
+            .as_mut()
+            .and_then(|dialog| dialog.chooser_mut().take_backend_error());
+        if let Some(details) = backend_error {
+            let text = self.i18n().text(TextKey::FileDialogFailed).to_string();
+            self.set_status_warn(text.clone());
+            self.push_toast_with_details(StatusLevel::Warn, text, Some(details));
+        }
+
         if let Some(path) = picked_export_path {
             self.remember_export_dir_from_path(&path);
         }
 
         if close_dialog {
-            self.project.active_dialog = None;
+            match (export_job_path, self.project.active_dialog.take()) {
+                (Some(path), Some(NativeDialog::SaveExport { curves, format, .. })) => {
+                    self.start_export_job(path, curves, format);
+                }
+                (
+                    Some(path),
+                    Some(NativeDialog::SaveSurface {
+                        surface, format, ..
+                    }),
+                ) => {
+                    self.write_surface(&path, &surface, format);
+                }
+                _ => {}
+            }
         }
     }
 }
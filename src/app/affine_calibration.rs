@@ -0,0 +1,103 @@
+//! Affine calibration: reference points that fit a full 2-D mapping for skewed scans.
+
+use super::{AffineCalUi, AffineRefUi, CurcatApp, PickMode};
+use crate::i18n::UiLanguage;
+
+impl CurcatApp {
+    /// Place reference point `idx` at `pixel` and leave pick mode.
+    pub(crate) fn pick_affine_reference(&mut self, idx: usize, pixel: egui::Pos2) {
+        self.calibration.pick_mode = PickMode::None;
+        let pixel = self.snap_pixel_if_requested(pixel);
+        let Some(point) = self.calibration.affine_cal.points.get_mut(idx) else {
+            return;
+        };
+        point.pixel = Some(pixel);
+        self.mark_points_dirty();
+        self.set_status(self.i18n().format_picked(&format!("P{}", idx + 1)));
+    }
+
+    pub(crate) fn add_affine_reference(&mut self) {
+        self.calibration
+            .affine_cal
+            .points
+            .push(AffineRefUi::default());
+    }
+
+    /// Drop reference point `idx`, keeping at least the minimum number of rows.
+    pub(crate) fn remove_affine_reference(&mut self, idx: usize) {
+        let points = &mut self.calibration.affine_cal.points;
+        if points.len() <= AffineCalUi::MIN_POINTS || idx >= points.len() {
+            return;
+        }
+        points.remove(idx);
+        // Row indices shift, so a pending pick would land on the wrong point.
+        if matches!(self.calibration.pick_mode, PickMode::AffineRef(_)) {
+            self.calibration.pick_mode = PickMode::None;
+        }
+        self.clear_calibration_drag_runtime();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Reference point removed.",
+            UiLanguage::Ru => "Опорная точка удалена.",
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::harness::Harness;
+    use crate::types::CoordSystem;
+    use egui::Pos2;
+    use egui::accesskit::Role;
+
+    /// Pixel of chart point `(x, y)` on a scan rotated by 0.15 rad with a slanted Y axis.
+    #[allow(clippy::cast_possible_truncation)]
+    fn scan_pixel(x: f64, y: f64) -> Pos2 {
+        let (sin, cos) = 0.15f64.sin_cos();
+        let (u, v) = (30.0f64.mul_add(x, 8.0 * y), -25.0 * y);
+        Pos2::new(
+            (cos.mul_add(u, -(sin * v)) + 60.0) as f32,
+            (sin.mul_add(u, cos * v) + 260.0) as f32,
+        )
+    }
+
+    #[test]
+    fn reference_points_calibrate_a_rotated_scan() {
+        let mut harness = Harness::with_blank_image(480, 360);
+        harness.app.calibration.coord_system = CoordSystem::Affine;
+        harness.run();
+        for (idx, (x, y)) in [(0.0, 0.0), (10.0, 0.0), (0.0, 8.0)]
+            .into_iter()
+            .enumerate()
+        {
+            harness
+                .app
+                .begin_pick_mode(crate::app::PickMode::AffineRef(idx));
+            harness.click_image_pixel(scan_pixel(x, y));
+            let point = &mut harness.app.calibration.affine_cal.points[idx];
+            assert!(point.pixel.is_some(), "P{} placed", idx + 1);
+            point.x_text = x.to_string();
+            point.y_text = y.to_string();
+        }
+        assert!(harness.app.calibration_ready());
+
+        harness.click_image_pixel(scan_pixel(6.0, 5.0));
+        let point = harness.app.points.points.last().expect("picked point");
+        let (x, y) = (point.x_numeric.expect("x"), point.y_numeric.expect("y"));
+        // Screen clicks land on whole logical pixels, so allow about a pixel of slack.
+        assert!((x - 6.0).abs() < 0.1, "x = {x}");
+        assert!((y - 5.0).abs() < 0.1, "y = {y}");
+    }
+
+    #[test]
+    fn rows_can_be_added_and_removed_down_to_three() {
+        let mut harness = Harness::with_blank_image(200, 200);
+        harness.app.calibration.coord_system = CoordSystem::Affine;
+        harness.run();
+        harness.click(Role::Button, "Add point");
+        assert_eq!(harness.app.calibration.affine_cal.points.len(), 4);
+        harness.app.remove_affine_reference(0);
+        harness.app.remove_affine_reference(0);
+        assert_eq!(harness.app.calibration.affine_cal.points.len(), 3);
+    }
+}
@@ -0,0 +1,153 @@
+//! Tick-label auto-calibration: fill the axes from an OCR proposal and let the user confirm it.
+
+use super::{AxisCalUi, CurcatApp, PickMode};
+use crate::i18n::UiLanguage;
+use crate::ocr::{self, AxisProposal};
+use crate::types::CoordSystem;
+
+/// Proposal applied to the axes and waiting for confirmation.
+#[derive(Debug, Clone)]
+pub struct AutoCalibrationReview {
+    /// `None` when no axis with readable labels was found.
+    pub(super) x_confidence: Option<f32>,
+    pub(super) y_confidence: Option<f32>,
+    /// Axis calibrations from before the proposal, restored on discard.
+    previous: (AxisCalUi, AxisCalUi),
+}
+
+fn apply_proposal(cal: &mut AxisCalUi, proposal: &AxisProposal) {
+    cal.unit = proposal.unit;
+    cal.scale = proposal.scale;
+    cal.p1 = Some(proposal.p1);
+    cal.p2 = Some(proposal.p2);
+    cal.v1_text.clone_from(&proposal.v1_text);
+    cal.v2_text.clone_from(&proposal.v2_text);
+}
+
+impl CurcatApp {
+    /// Read the tick labels of the current image and fill the cartesian axes with the result.
+    pub(crate) fn auto_calibrate_from_ticks(&mut self) {
+        let Some(image) = self.image.image.as_ref() else {
+            return;
+        };
+        if self.calibration.coord_system != CoordSystem::Cartesian {
+            return;
+        }
+        let proposal = ocr::propose_calibration(&image.pixels);
+        if proposal.x.is_none() && proposal.y.is_none() {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "No axis with readable tick labels found.",
+                UiLanguage::Ru => "Не найдено осей с читаемыми подписями делений.",
+            });
+            return;
+        }
+        // A repeated run keeps the calibration from before the first proposal.
+        let previous = self.calibration.auto_review.take().map_or_else(
+            || {
+                (
+                    self.calibration.cal_x.clone(),
+                    self.calibration.cal_y.clone(),
+                )
+            },
+            |review| review.previous,
+        );
+        if let Some(x) = &proposal.x {
+            apply_proposal(&mut self.calibration.cal_x, x);
+        }
+        if let Some(y) = &proposal.y {
+            apply_proposal(&mut self.calibration.cal_y, y);
+        }
+        self.calibration.auto_review = Some(AutoCalibrationReview {
+            x_confidence: proposal.x.as_ref().map(|x| x.confidence),
+            y_confidence: proposal.y.as_ref().map(|y| y.confidence),
+            previous,
+        });
+        self.calibration.pick_mode = PickMode::None;
+        self.calibration.pending_value_focus = None;
+        self.clear_calibration_drag_runtime();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Calibration proposed from tick labels. Check it, then accept.",
+            UiLanguage::Ru => "Калибровка предложена по подписям делений. Проверьте и подтвердите.",
+        });
+    }
+
+    /// Keep the proposed calibration.
+    pub(crate) fn accept_auto_calibration(&mut self) {
+        if self.calibration.auto_review.take().is_some() {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => "Proposed calibration accepted.",
+                UiLanguage::Ru => "Предложенная калибровка принята.",
+            });
+        }
+    }
+
+    /// Restore the axes from before the proposal.
+    pub(crate) fn discard_auto_calibration(&mut self) {
+        let Some(review) = self.calibration.auto_review.take() else {
+            return;
+        };
+        (self.calibration.cal_x, self.calibration.cal_y) = review.previous;
+        self.clear_calibration_drag_runtime();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Proposed calibration discarded.",
+            UiLanguage::Ru => "Предложенная калибровка отменена.",
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::fixtures::{ChartFixture, ChartSpec, unique_temp_dir};
+    use crate::app::harness::Harness;
+    use egui::accesskit::Role;
+
+    fn labelled_chart_harness() -> (ChartFixture, Harness, std::path::PathBuf) {
+        let fixture = ChartFixture::render(ChartSpec::labelled_sine());
+        let dir = unique_temp_dir("auto_calibration");
+        let mut harness = fixture.open(&dir).expect("open fixture project");
+        harness.app.reset_calibrations();
+        (fixture, harness, dir)
+    }
+
+    #[test]
+    fn proposal_from_tick_labels_matches_the_ground_truth() {
+        let (fixture, mut harness, dir) = labelled_chart_harness();
+        harness.click(Role::Button, "Auto-calibrate");
+        let review = harness
+            .app
+            .calibration
+            .auto_review
+            .as_ref()
+            .expect("review");
+        assert!(review.x_confidence.is_some_and(|c| c > 0.8));
+        assert!(review.y_confidence.is_some_and(|c| c > 0.8));
+
+        let x_mapping = harness.app.calibration.cal_x.mapping().expect("x mapping");
+        let y_mapping = harness.app.calibration.cal_y.mapping().expect("y mapping");
+        for (x, y) in [(1.0, 0.5), (5.0, 3.0), (9.0, 5.5)] {
+            let pixel = fixture.pixel_of(x, y);
+            assert!((x_mapping.numeric_at(pixel).expect("x") - x).abs() < 0.02);
+            assert!((y_mapping.numeric_at(pixel).expect("y") - y).abs() < 0.02);
+        }
+
+        harness.click(Role::Button, "Accept");
+        assert!(harness.app.calibration.auto_review.is_none());
+        assert!(harness.app.calibration_ready());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn discarding_restores_the_previous_calibration() {
+        let (_fixture, mut harness, dir) = labelled_chart_harness();
+        harness.app.auto_calibrate_from_ticks();
+        harness.app.auto_calibrate_from_ticks();
+        assert!(harness.app.calibration_ready());
+        harness.app.discard_auto_calibration();
+        assert!(harness.app.calibration.auto_review.is_none());
+        assert!(harness.app.calibration.cal_x.p1.is_none());
+        assert!(harness.app.calibration.cal_y.v2_text.is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{CurcatApp, PickedPoint};
+use super::CurcatApp;
 use crate::i18n::UiLanguage;
 use crate::snap::SnapBehavior;
 use crate::types::CoordSystem;
@@ -85,13 +85,12 @@ impl CurcatApp {
         }
         let Some(behavior) = self.current_snap_behavior() else {
             self.set_status(match self.ui.language {
-                UiLanguage::En => "Auto-trace requires snapping (Contrast/Centerline).",
-                UiLanguage::Ru => {
-                    "Для авто-трассировки нужен режим привязки (Contrast/Centerline)."
-                }
+                UiLanguage::En => "Auto-trace requires a snap mode.",
+                UiLanguage::Ru => "Для авто-трассировки нужен режим привязки.",
             });
             return;
         };
+        let behavior = behavior.as_ref();
         let cfg = self.interaction.auto_trace_cfg.sanitized();
         let size = self.image.image.as_ref().map_or([0, 0], |img| img.size);
         let axis_dir = self
@@ -163,10 +162,18 @@ impl CurcatApp {
         }
 
         for p in &deduped {
-            self.points.points.push(PickedPoint::new(*p));
+            let point = self.new_snapped_point(*p);
+            self.points.points.push(point);
         }
         self.mark_points_dirty();
-        self.set_status(self.i18n().format_auto_trace_added(deduped.len()));
+        let added = self.i18n().format_auto_trace_added(deduped.len());
+        let jumps = self.flag_continuity_jumps();
+        if jumps == 0 {
+            self.set_status(added);
+        } else {
+            let flagged = self.i18n().format_continuity_jumps(jumps);
+            self.set_status_warn(format!("{added} {flagged}"));
+        }
     }
 
     fn auto_trace_direction(
@@ -175,7 +182,7 @@ impl CurcatApp {
         axis_dir: Vec2,
         dir_sign: f32,
         size: [usize; 2],
-        behavior: SnapBehavior,
+        behavior: &dyn SnapBehavior,
         cfg: AutoTraceConfig,
     ) -> Vec<Pos2> {
         if size[0] == 0 || size[1] == 0 {
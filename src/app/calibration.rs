@@ -1,7 +1,8 @@
 use super::interaction::DragTarget;
 use crate::types::{
-    AngleDirection, AngleUnit, AxisMapping, AxisUnit, AxisValue, CoordSystem, PolarMapping,
-    PolarMappingParams, ScaleKind, parse_axis_value,
+    AffineMapping, AffineMappingError, AffineReference, AngleDirection, AngleUnit, AxisMapping,
+    AxisUnit, AxisValue, CoordSystem, PolarMapping, PolarMappingParams, ScaleKind, SmithMapping,
+    SmithMappingError, TimeInput, parse_axis_value_with, parse_utc_offset,
 };
 use egui::Pos2;
 use std::cell::RefCell;
@@ -33,13 +34,47 @@ pub enum PickMode {
     X2,
     Y1,
     Y2,
+    SecondaryY1,
+    SecondaryY2,
     Origin,
     R1,
     R2,
     A1,
     A2,
+    /// Reference point `n` of the affine calibration.
+    AffineRef(usize),
+    /// Centre of the Smith chart, where the impedance matches the reference.
+    SmithCenter,
+    /// Short-circuit point of the Smith chart, the left end of its real axis.
+    SmithShort,
+    /// Next plot corner of a perspective warp.
+    PerspectiveCorner,
+    /// Next end of a line that should be horizontal.
+    HorizonPoint,
     CurveColor,
+    /// Legend swatches to seed curves from, until canceled.
+    LegendEntry,
+    /// Chart title or axis title to read into the project.
+    ChartText(ChartTextField),
     AutoTrace,
+    /// Rectangle to zoom to, dragged without holding Ctrl.
+    ZoomRegion,
+    /// Rectangle dragged, or polygon corners clicked, to confine snapping to.
+    SnapArea,
+    /// Rectangle dragged, or lasso drawn with Alt, around points to select.
+    SelectPoints,
+    /// Sample scatter marker whose look-alikes are detected.
+    MarkerSample,
+    /// Detected markers, clicked to drop false ones until accepted or discarded.
+    MarkerReview,
+}
+
+/// Chart text that OCR can read into the project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartTextField {
+    Title,
+    XAxisTitle,
+    YAxisTitle,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +83,8 @@ pub enum AxisValueField {
     X2,
     Y1,
     Y2,
+    SecondaryY1,
+    SecondaryY2,
     R1,
     R2,
     A1,
@@ -60,7 +97,11 @@ pub struct CalibrationState {
     pub(super) pending_value_focus: Option<AxisValueField>,
     pub(super) cal_x: AxisCalUi,
     pub(super) cal_y: AxisCalUi,
+    /// Second value scale of dual-axis charts; only curves assigned to it read against it.
+    pub(super) cal_secondary_y: AxisCalUi,
     pub(super) polar_cal: PolarCalUi,
+    pub(super) affine_cal: AffineCalUi,
+    pub(super) smith_cal: SmithCalUi,
     pub(super) coord_system: CoordSystem,
     pub(super) calibration_angle_snap: bool,
     pub(super) snap_ext: bool,
@@ -72,6 +113,39 @@ pub struct CalibrationState {
     pub(super) drag_last_pixel: Option<Pos2>,
     pub(super) snap_guides: [Option<CalSnapGuide>; super::constants::CAL_SNAP_GUIDE_SLOTS],
     pub(super) int_snap_sticky: Option<CalIntSnapSticky>,
+    /// What-if draft edited in the calibration sandbox window.
+    pub(super) sandbox: Option<super::CalibrationSandbox>,
+    /// Tick-label proposal applied to the axes and not yet confirmed.
+    pub(super) auto_review: Option<super::AutoCalibrationReview>,
+    /// Fit to the pasted reference applied to the axes and not yet confirmed.
+    pub(super) recalibration_review: Option<super::RecalibrationReview>,
+    /// Grid-spacing scale suggestion for the current image; `None` until first asked for.
+    pub(super) scale_suggestion: Option<super::ScaleSuggestion>,
+}
+
+/// Entered format and UTC offset for the `DateTime` values of one axis.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AxisTimeUi {
+    /// strptime-style pattern; empty uses the built-in formats only.
+    pub(super) pattern: String,
+    /// UTC offset of values typed without one; empty is UTC.
+    pub(super) offset_text: String,
+}
+
+impl AxisTimeUi {
+    /// Parsing settings, or `None` while the offset does not parse.
+    pub(super) fn input(&self) -> Option<TimeInput> {
+        Some(TimeInput {
+            pattern: self.pattern.trim().to_string(),
+            offset: parse_utc_offset(&self.offset_text)?,
+        })
+    }
+}
+
+/// Parse `text` for an axis with the given time settings; nothing parses while the offset
+/// is invalid.
+fn parse_with_time(text: &str, unit: AxisUnit, time: &AxisTimeUi) -> Option<AxisValue> {
+    parse_axis_value_with(text, unit, &time.input()?)
 }
 
 #[derive(Debug, Clone)]
@@ -82,6 +156,10 @@ pub struct AxisCalUi {
     pub(super) p2: Option<Pos2>,
     pub(super) v1_text: String,
     pub(super) v2_text: String,
+    /// Declared reversed: values fall to the right on X or upward on Y, as on depth axes.
+    pub(super) inverted: bool,
+    /// How `DateTime` values are typed; unused on float axes.
+    pub(super) time: AxisTimeUi,
     parse_cache_v1: RefCell<ParsedAxisValueCache>,
     parse_cache_v2: RefCell<ParsedAxisValueCache>,
 }
@@ -90,6 +168,7 @@ pub struct AxisCalUi {
 struct ParsedAxisValueCache {
     unit: AxisUnit,
     text: String,
+    time: AxisTimeUi,
     value: Option<AxisValue>,
 }
 
@@ -98,16 +177,21 @@ impl ParsedAxisValueCache {
         Self {
             unit,
             text: String::new(),
+            time: AxisTimeUi {
+                pattern: String::new(),
+                offset_text: String::new(),
+            },
             value: None,
         }
     }
 
-    fn get_or_parse(&mut self, text: &str, unit: AxisUnit) -> Option<AxisValue> {
-        if self.unit != unit || self.text != text {
+    fn get_or_parse(&mut self, text: &str, unit: AxisUnit, time: &AxisTimeUi) -> Option<AxisValue> {
+        if self.unit != unit || self.text != text || self.time != *time {
             self.unit = unit;
             self.text.clear();
             self.text.push_str(text);
-            self.value = parse_axis_value(text, unit);
+            self.time.clone_from(time);
+            self.value = parse_with_time(text, unit, time);
         }
         self.value.clone()
     }
@@ -133,20 +217,25 @@ impl AxisCalUi {
             p2,
             v1_text,
             v2_text,
+            inverted: false,
+            time: AxisTimeUi {
+                pattern: String::new(),
+                offset_text: String::new(),
+            },
             parse_cache_v1: RefCell::new(ParsedAxisValueCache::new(unit)),
             parse_cache_v2: RefCell::new(ParsedAxisValueCache::new(unit)),
         }
     }
 
     fn parsed_values(&self) -> (Option<AxisValue>, Option<AxisValue>) {
-        let v1 = self
-            .parse_cache_v1
-            .borrow_mut()
-            .get_or_parse(&self.v1_text, self.unit);
-        let v2 = self
-            .parse_cache_v2
-            .borrow_mut()
-            .get_or_parse(&self.v2_text, self.unit);
+        let v1 =
+            self.parse_cache_v1
+                .borrow_mut()
+                .get_or_parse(&self.v1_text, self.unit, &self.time);
+        let v2 =
+            self.parse_cache_v2
+                .borrow_mut()
+                .get_or_parse(&self.v2_text, self.unit, &self.time);
         (v1, v2)
     }
 
@@ -156,6 +245,49 @@ impl AxisCalUi {
         AxisMapping::try_new(p1, p2, v1?, v2?, self.scale, self.unit).ok()
     }
 
+    /// Whether the values grow right-to-left on an X axis or top-to-bottom on a Y axis, once
+    /// points and values are set.
+    ///
+    /// Unless the axis is marked [`Self::inverted`], usually a sign of swapped entries; it is
+    /// still a valid (reversed-axis) calibration.
+    pub(super) fn runs_reversed(&self, is_x: bool) -> Option<bool> {
+        let (p1, p2) = (self.p1?, self.p2?);
+        let (v1, v2) = self.parsed_values();
+        let value_delta = v2?.to_scalar_seconds() - v1?.to_scalar_seconds();
+        let pixel_delta = if is_x { p2.x - p1.x } else { p1.y - p2.y };
+        (pixel_delta.abs() > f32::EPSILON && value_delta != 0.0)
+            .then(|| value_delta * f64::from(pixel_delta) < 0.0)
+    }
+
+    /// Whether the direction of the values disagrees with the [`Self::inverted`] flag.
+    pub(super) fn direction_mismatch(&self, is_x: bool) -> bool {
+        self.runs_reversed(is_x)
+            .is_some_and(|reversed| reversed != self.inverted)
+    }
+
+    /// Picked endpoints ordered from the lower value to the higher one.
+    pub(super) fn increasing_ends(&self) -> Option<(Pos2, Pos2)> {
+        let (p1, p2) = (self.p1?, self.p2?);
+        let (v1, v2) = self.parsed_values();
+        if v2?.to_scalar_seconds() >= v1?.to_scalar_seconds() {
+            Some((p1, p2))
+        } else {
+            Some((p2, p1))
+        }
+    }
+
+    /// Exchange the two value entries, keeping the picked pixels.
+    pub(super) const fn swap_values(&mut self) {
+        std::mem::swap(&mut self.v1_text, &mut self.v2_text);
+    }
+
+    /// Exchange the two endpoints whole: pixels, values and their parse caches.
+    pub(super) const fn swap_endpoints(&mut self) {
+        std::mem::swap(&mut self.p1, &mut self.p2);
+        std::mem::swap(&mut self.v1_text, &mut self.v2_text);
+        std::mem::swap(&mut self.parse_cache_v1, &mut self.parse_cache_v2);
+    }
+
     pub(super) fn value_invalid_flags(&self) -> (bool, bool) {
         let (v1, v2) = self.parsed_values();
         let invalid_pair = if let (Some(a), Some(b)) = (&v1, &v2) {
@@ -243,3 +375,102 @@ impl PolarCalUi {
         .ok()
     }
 }
+
+/// Reference points for the affine calibration mode, three or more.
+#[derive(Debug, Clone)]
+pub struct AffineCalUi {
+    pub(super) x_unit: AxisUnit,
+    pub(super) x_scale: ScaleKind,
+    pub(super) y_unit: AxisUnit,
+    pub(super) y_scale: ScaleKind,
+    pub(super) x_time: AxisTimeUi,
+    pub(super) y_time: AxisTimeUi,
+    pub(super) points: Vec<AffineRefUi>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AffineRefUi {
+    pub(super) pixel: Option<Pos2>,
+    pub(super) x_text: String,
+    pub(super) y_text: String,
+}
+
+impl AffineCalUi {
+    /// Fewest reference points that pin down an affine mapping.
+    pub(super) const MIN_POINTS: usize = 3;
+
+    pub(super) fn new() -> Self {
+        Self {
+            x_unit: AxisUnit::Float,
+            x_scale: ScaleKind::Linear,
+            y_unit: AxisUnit::Float,
+            y_scale: ScaleKind::Linear,
+            x_time: AxisTimeUi::default(),
+            y_time: AxisTimeUi::default(),
+            points: vec![AffineRefUi::default(); Self::MIN_POINTS],
+        }
+    }
+
+    /// Fit over the complete rows; rows missing a pixel or a value are left out.
+    pub(super) fn fit(&self) -> Result<AffineMapping, AffineMappingError> {
+        let refs: Vec<AffineReference> = self
+            .points
+            .iter()
+            .filter_map(|p| {
+                Some(AffineReference {
+                    pixel: p.pixel?,
+                    x: parse_with_time(&p.x_text, self.x_unit, &self.x_time)?,
+                    y: parse_with_time(&p.y_text, self.y_unit, &self.y_time)?,
+                })
+            })
+            .collect();
+        AffineMapping::fit(
+            &refs,
+            (self.x_scale, self.x_unit),
+            (self.y_scale, self.y_unit),
+        )
+    }
+
+    pub(super) fn mapping(&self) -> Option<AffineMapping> {
+        self.fit().ok()
+    }
+}
+
+/// Calibration of the Smith chart mode: chart centre, short-circuit point and `Z0`.
+#[derive(Debug, Clone)]
+pub struct SmithCalUi {
+    pub(super) center: Option<Pos2>,
+    pub(super) short: Option<Pos2>,
+    /// Reference impedance in ohms, as typed.
+    pub(super) reference_text: String,
+}
+
+impl SmithCalUi {
+    pub(super) const DEFAULT_REFERENCE: &'static str = "50";
+
+    pub(super) fn new() -> Self {
+        Self {
+            center: None,
+            short: None,
+            reference_text: Self::DEFAULT_REFERENCE.to_string(),
+        }
+    }
+
+    /// The typed reference impedance, or `None` when it does not parse as a number.
+    pub(super) fn reference_ohms(&self) -> Option<f64> {
+        self.reference_text.trim().replace(',', ".").parse().ok()
+    }
+
+    /// `None` while a point is missing or the reference does not parse.
+    pub(super) fn fit(&self) -> Option<Result<SmithMapping, SmithMappingError>> {
+        Some(SmithMapping::try_new(
+            self.center?,
+            self.short?,
+            self.reference_ohms()?,
+        ))
+    }
+
+    pub(super) fn mapping(&self) -> Option<SmithMapping> {
+        self.fit()?.ok()
+    }
+}
@@ -0,0 +1,261 @@
+//! What-if calibration sandbox: draft calibration values evaluated against the picked points.
+
+use super::{AxisCalUi, CurcatApp, PolarCalUi};
+use crate::i18n::UiLanguage;
+use crate::interp::XYPoint;
+use crate::types::{AxisMapping, CoordSystem, PolarMapping};
+use egui::Pos2;
+
+/// Draft calibration values; pixels, units and scales always come from the live calibration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationSandbox {
+    pub(super) coord_system: CoordSystem,
+    /// `[X1, X2, Y1, Y2]` for cartesian, `[R1, R2, A1, A2]` for polar calibration.
+    pub(super) values: [String; 4],
+}
+
+/// Extremes and endpoints of the picked points under one calibration, as axis scalars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SandboxSummary {
+    pub(super) x_min: f64,
+    pub(super) x_max: f64,
+    pub(super) y_min: f64,
+    pub(super) y_max: f64,
+    /// First point in placement order (the first raw export row).
+    pub(super) first: XYPoint,
+    /// Last point in placement order.
+    pub(super) last: XYPoint,
+}
+
+impl SandboxSummary {
+    fn from_points(points: impl IntoIterator<Item = XYPoint>) -> Option<Self> {
+        let mut iter = points.into_iter();
+        let first = iter.next()?;
+        let mut summary = Self {
+            x_min: first.x,
+            x_max: first.x,
+            y_min: first.y,
+            y_max: first.y,
+            first,
+            last: first,
+        };
+        for p in iter {
+            summary.x_min = summary.x_min.min(p.x);
+            summary.x_max = summary.x_max.max(p.x);
+            summary.y_min = summary.y_min.min(p.y);
+            summary.y_max = summary.y_max.max(p.y);
+            summary.last = p;
+        }
+        Some(summary)
+    }
+}
+
+/// Live or draft calibration resolved into mappings.
+enum SandboxMappings {
+    Cartesian(AxisMapping, AxisMapping),
+    Polar(PolarMapping),
+}
+
+impl SandboxMappings {
+    fn value_at(&self, pixel: Pos2) -> Option<XYPoint> {
+        let (x, y) = match self {
+            Self::Cartesian(xm, ym) => (xm.numeric_at(pixel)?, ym.numeric_at(pixel)?),
+            Self::Polar(pm) => (pm.angle_at(pixel)?, pm.radius_at(pixel)?),
+        };
+        (x.is_finite() && y.is_finite()).then_some(XYPoint { x, y })
+    }
+}
+
+fn with_values(cal: &AxisCalUi, v1: &str, v2: &str) -> AxisCalUi {
+    let mut draft = AxisCalUi::with_values(
+        cal.unit,
+        cal.scale,
+        cal.p1,
+        cal.p2,
+        v1.to_string(),
+        v2.to_string(),
+    );
+    draft.time.clone_from(&cal.time);
+    draft
+}
+
+impl CurcatApp {
+    /// Draft seeded from the live calibration values.
+    pub(crate) fn calibration_sandbox_seed(&self) -> CalibrationSandbox {
+        let cal = &self.calibration;
+        let values = match cal.coord_system {
+            CoordSystem::Cartesian => [
+                cal.cal_x.v1_text.clone(),
+                cal.cal_x.v2_text.clone(),
+                cal.cal_y.v1_text.clone(),
+                cal.cal_y.v2_text.clone(),
+            ],
+            CoordSystem::Polar => [
+                cal.polar_cal.radius.v1_text.clone(),
+                cal.polar_cal.radius.v2_text.clone(),
+                cal.polar_cal.angle.v1_text.clone(),
+                cal.polar_cal.angle.v2_text.clone(),
+            ],
+            // Reference points and Smith charts have no fixed value slots; the sandbox does
+            // not draft them.
+            CoordSystem::Affine | CoordSystem::Smith => Default::default(),
+        };
+        CalibrationSandbox {
+            coord_system: cal.coord_system,
+            values,
+        }
+    }
+
+    /// Keep the draft in step with the live coordinate system; a switch starts a fresh draft.
+    pub(crate) fn sync_calibration_sandbox(&mut self) {
+        let stale = self
+            .calibration
+            .sandbox
+            .as_ref()
+            .is_none_or(|s| s.coord_system != self.calibration.coord_system);
+        if stale {
+            self.calibration.sandbox = Some(self.calibration_sandbox_seed());
+        }
+    }
+
+    fn sandbox_mappings(&self, draft: Option<&CalibrationSandbox>) -> Option<SandboxMappings> {
+        let cal = &self.calibration;
+        match cal.coord_system {
+            CoordSystem::Cartesian => {
+                let (x, y) = draft.map_or_else(
+                    || (cal.cal_x.clone(), cal.cal_y.clone()),
+                    |d| {
+                        (
+                            with_values(&cal.cal_x, &d.values[0], &d.values[1]),
+                            with_values(&cal.cal_y, &d.values[2], &d.values[3]),
+                        )
+                    },
+                );
+                Some(SandboxMappings::Cartesian(x.mapping()?, y.mapping()?))
+            }
+            CoordSystem::Polar => {
+                let polar = draft.map_or_else(
+                    || cal.polar_cal.clone(),
+                    |d| PolarCalUi {
+                        radius: with_values(&cal.polar_cal.radius, &d.values[0], &d.values[1]),
+                        angle: with_values(&cal.polar_cal.angle, &d.values[2], &d.values[3]),
+                        ..cal.polar_cal.clone()
+                    },
+                );
+                polar.mapping().map(SandboxMappings::Polar)
+            }
+            CoordSystem::Affine | CoordSystem::Smith => None,
+        }
+    }
+
+    /// Summary of the picked points under the live calibration (`None` draft) or a draft.
+    ///
+    /// Returns `None` when that calibration is incomplete or no point maps to finite values.
+    pub(crate) fn calibration_sandbox_summary(
+        &self,
+        draft: Option<&CalibrationSandbox>,
+    ) -> Option<SandboxSummary> {
+        let mappings = self.sandbox_mappings(draft)?;
+        SandboxSummary::from_points(
+            self.points
+                .points
+                .iter()
+                .filter_map(|p| mappings.value_at(p.pixel)),
+        )
+    }
+
+    /// Copy the draft values into the live calibration.
+    pub(crate) fn apply_calibration_sandbox(&mut self) {
+        let Some(draft) = self.calibration.sandbox.clone() else {
+            return;
+        };
+        if draft.coord_system != self.calibration.coord_system
+            || self.sandbox_mappings(Some(&draft)).is_none()
+        {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "Draft calibration is incomplete; nothing applied.",
+                UiLanguage::Ru => "Черновая калибровка неполна; изменения не применены.",
+            });
+            return;
+        }
+        let [v1, v2, v3, v4] = draft.values;
+        let (first, second) = match draft.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine | CoordSystem::Smith => {
+                (&mut self.calibration.cal_x, &mut self.calibration.cal_y)
+            }
+            CoordSystem::Polar => (
+                &mut self.calibration.polar_cal.radius,
+                &mut self.calibration.polar_cal.angle,
+            ),
+        };
+        first.v1_text = v1;
+        first.v2_text = v2;
+        second.v1_text = v3;
+        second.v2_text = v4;
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Sandbox calibration values applied.",
+            UiLanguage::Ru => "Значения калибровки из песочницы применены.",
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::PickedPoint;
+
+    fn calibrated_app() -> CurcatApp {
+        let mut app = CurcatApp::default();
+        app.calibration.coord_system = CoordSystem::Cartesian;
+        app.calibration.cal_x.p1 = Some(Pos2::new(0.0, 0.0));
+        app.calibration.cal_x.p2 = Some(Pos2::new(100.0, 0.0));
+        app.calibration.cal_x.v1_text = "0".to_string();
+        app.calibration.cal_x.v2_text = "10".to_string();
+        app.calibration.cal_y.p1 = Some(Pos2::new(0.0, 100.0));
+        app.calibration.cal_y.p2 = Some(Pos2::new(0.0, 0.0));
+        app.calibration.cal_y.v1_text = "0".to_string();
+        app.calibration.cal_y.v2_text = "10".to_string();
+        app.points.points = vec![
+            PickedPoint::new(Pos2::new(50.0, 20.0)),
+            PickedPoint::new(Pos2::new(10.0, 90.0)),
+        ];
+        app
+    }
+
+    #[test]
+    fn sandbox_summary_reflects_draft_values_without_touching_calibration() {
+        let mut app = calibrated_app();
+        app.sync_calibration_sandbox();
+        let live = app.calibration_sandbox_summary(None).expect("live summary");
+        assert!((live.x_max - 5.0).abs() < 1e-4);
+        assert!((live.first.y - 8.0).abs() < 1e-4);
+
+        let draft = app.calibration.sandbox.as_mut().expect("draft");
+        draft.values[1] = "1000".to_string();
+        let draft = app.calibration.sandbox.clone();
+        let what_if = app
+            .calibration_sandbox_summary(draft.as_ref())
+            .expect("draft summary");
+        assert!((what_if.x_min - 100.0).abs() < 1e-4);
+        assert!((what_if.x_max - 500.0).abs() < 1e-4);
+        assert!((what_if.last.y - live.last.y).abs() < 1e-4);
+        assert_eq!(app.calibration.cal_x.v2_text, "10");
+
+        app.apply_calibration_sandbox();
+        assert_eq!(app.calibration.cal_x.v2_text, "1000");
+    }
+
+    #[test]
+    fn sandbox_rejects_incomplete_draft() {
+        let mut app = calibrated_app();
+        app.sync_calibration_sandbox();
+        if let Some(draft) = app.calibration.sandbox.as_mut() {
+            draft.values[2] = "abc".to_string();
+        }
+        let draft = app.calibration.sandbox.clone();
+        assert!(app.calibration_sandbox_summary(draft.as_ref()).is_none());
+        app.apply_calibration_sandbox();
+        assert_eq!(app.calibration.cal_y.v1_text, "0");
+    }
+}
@@ -0,0 +1,48 @@
+//! Chart title and axis titles read off the image by OCR into the project, where the
+//! axis titles name the exported x/y columns and the title heads report-style exports.
+
+use super::{ChartTextField, CurcatApp};
+use crate::i18n::UiLanguage;
+use crate::ocr::{CaptionOrientation, read_caption};
+use egui::Pos2;
+
+impl CurcatApp {
+    /// Read the caption line under `pixel` into `field`.
+    ///
+    /// Y axis titles are tried bottom to top first, then as a horizontal line.
+    pub(crate) fn read_chart_text(&mut self, field: ChartTextField, pixel: Pos2) {
+        let orientations: &[CaptionOrientation] = match field {
+            ChartTextField::Title | ChartTextField::XAxisTitle => &[CaptionOrientation::Horizontal],
+            ChartTextField::YAxisTitle => &[
+                CaptionOrientation::BottomToTop,
+                CaptionOrientation::Horizontal,
+            ],
+        };
+        let text = self
+            .image
+            .image
+            .as_ref()
+            .zip(self.image_pixel_coords(pixel))
+            .and_then(|(image, (x, y))| {
+                orientations
+                    .iter()
+                    .find_map(|&orientation| read_caption(&image.pixels, x, y, orientation))
+            });
+        let Some(text) = text else {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "No legible text at the clicked point.",
+                UiLanguage::Ru => "В указанном месте не найден читаемый текст.",
+            });
+            return;
+        };
+        match field {
+            ChartTextField::Title => self.project.title = Some(text.clone()),
+            ChartTextField::XAxisTitle => self.project.axis_titles[0].clone_from(&text),
+            ChartTextField::YAxisTitle => self.project.axis_titles[1].clone_from(&text),
+        }
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Read \"{text}\" from the chart."),
+            UiLanguage::Ru => format!("С графика прочитано «{text}»."),
+        });
+    }
+}
@@ -1,6 +1,29 @@
-use super::CurcatApp;
+use super::{CurcatApp, PickMode};
+use crate::i18n::UiLanguage;
+use crate::project::CalibrationRecord;
 use arboard::{Clipboard, Error as ClipboardError};
 use egui::Context;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Clipboard text form of a calibration; the named field keeps other JSON from pasting as one.
+#[derive(Serialize, Deserialize)]
+struct CalibrationClip {
+    curcat_calibration: CalibrationRecord,
+}
+
+fn encode_calibration(record: CalibrationRecord) -> String {
+    serde_json::to_string_pretty(&CalibrationClip {
+        curcat_calibration: record,
+    })
+    .unwrap_or_default()
+}
+
+fn decode_calibration(text: &str) -> Option<CalibrationRecord> {
+    serde_json::from_str::<CalibrationClip>(text.trim())
+        .ok()
+        .map(|clip| clip.curcat_calibration)
+}
 
 struct ClipboardCapture {
     width: usize,
@@ -8,28 +31,167 @@ struct ClipboardCapture {
     rgba: Vec<u8>,
 }
 
+/// Something on the clipboard that a loader can open, in the order it is looked for.
+enum ClipboardContent {
+    /// A file copied in Explorer/Finder/a file manager, or a local path or `file://` URL as text.
+    File(PathBuf),
+    Bitmap(ClipboardCapture),
+    /// An image inlined as a `data:` URL.
+    EncodedImage(Vec<u8>),
+}
+
+/// What clipboard text points at, if it names an image or project.
+#[derive(Debug, PartialEq, Eq)]
+enum ClipboardLocation {
+    File(PathBuf),
+    Inline(Vec<u8>),
+    /// An `http(s)` URL, which is not fetched.
+    Remote,
+}
+
+fn parse_clipboard_location(text: &str) -> Option<ClipboardLocation> {
+    // A copied URL may come with surrounding whitespace or as the first of several lines.
+    let text = text.trim().lines().next()?.trim();
+    if text.starts_with("data:") {
+        let url = data_url::DataUrl::process(text).ok()?;
+        if url.mime_type().type_ != "image" {
+            return None;
+        }
+        return url
+            .decode_to_vec()
+            .ok()
+            .map(|(bytes, _)| ClipboardLocation::Inline(bytes));
+    }
+    if let Ok(url) = url::Url::parse(text) {
+        match url.scheme() {
+            "file" => return url.to_file_path().ok().map(ClipboardLocation::File),
+            "http" | "https" => return Some(ClipboardLocation::Remote),
+            // A Windows drive letter parses as a one-letter scheme; treat it as a path below.
+            scheme if scheme.len() > 1 => return None,
+            _ => {}
+        }
+    }
+    let path = PathBuf::from(text.trim_matches('"'));
+    (path.is_absolute() && path.is_file()).then_some(ClipboardLocation::File(path))
+}
+
+pub(super) fn is_project_path(path: &std::path::Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("curcat"))
+}
+
 impl CurcatApp {
     pub(crate) fn paste_image_from_clipboard(&mut self, _ctx: &Context) {
         self.project.pending_image_task = None;
         self.project.pending_image_limit_prompt = None;
+        self.project.pending_svg_scale_prompt = None;
 
-        match capture_clipboard_image() {
-            Ok(captured) => {
+        match capture_clipboard_content() {
+            Ok(ClipboardContent::File(path)) if is_project_path(&path) => {
+                self.handle_project_load(path);
+            }
+            Ok(ClipboardContent::File(path)) => self.start_loading_image_from_path(path),
+            Ok(ClipboardContent::Bitmap(captured)) => {
                 self.start_loading_image_from_clipboard(
                     captured.width,
                     captured.height,
                     captured.rgba,
                 );
             }
+            Ok(ClipboardContent::EncodedImage(bytes)) => {
+                self.start_loading_image_from_bytes(None, bytes, None);
+            }
+            Err(err) => self.set_status_error(err),
+        }
+    }
+
+    /// Paste a two-column table as a reference dataset (see `point_import`).
+    pub(crate) fn paste_points_from_clipboard(&mut self) {
+        let text = Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .map_err(|err| format_clipboard_error(err, "text"));
+        match text {
+            Ok(text) => self.set_imported_dataset_from_text(&text),
+            Err(err) => self.set_status_error(err),
+        }
+    }
+
+    /// Put the whole calibration on the clipboard for pasting into another project or window.
+    pub(crate) fn copy_calibration_to_clipboard(&mut self, ctx: &Context) {
+        ctx.copy_text(encode_calibration(self.calibration_record()));
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Calibration copied to the clipboard.",
+            UiLanguage::Ru => "Калибровка скопирована в буфер обмена.",
+        });
+    }
+
+    pub(crate) fn paste_calibration_from_clipboard(&mut self) {
+        let text = Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .map_err(|err| format_clipboard_error(err, "text"));
+        match text {
+            Ok(text) => self.paste_calibration_text(&text),
             Err(err) => self.set_status_error(err),
         }
     }
+
+    pub(crate) fn paste_calibration_text(&mut self, text: &str) {
+        let Some(record) = decode_calibration(text) else {
+            self.set_status_error(match self.ui.language {
+                UiLanguage::En => "Paste failed: the clipboard does not hold a copied calibration.",
+                UiLanguage::Ru => {
+                    "Не удалось вставить: в буфере обмена нет скопированной калибровки."
+                }
+            });
+            return;
+        };
+        self.apply_calibration_record(&record);
+        self.calibration.pick_mode = PickMode::None;
+        self.calibration.pending_value_focus = None;
+        self.clear_calibration_drag_runtime();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Calibration pasted from the clipboard.",
+            UiLanguage::Ru => "Калибровка вставлена из буфера обмена.",
+        });
+    }
 }
 
-fn capture_clipboard_image() -> Result<ClipboardCapture, String> {
-    let mut clipboard = Clipboard::new().map_err(format_clipboard_error)?;
-    let data = clipboard.get_image().map_err(format_clipboard_error)?;
+/// Copied files win over a bitmap, since file managers may also put the file's icon on the
+/// clipboard; text is tried last for a path or an image URL.
+fn capture_clipboard_content() -> Result<ClipboardContent, String> {
+    let mut clipboard = Clipboard::new().map_err(|err| format_clipboard_error(err, "an image"))?;
+    if let Some(path) = clipboard
+        .get()
+        .file_list()
+        .ok()
+        .and_then(|paths| paths.into_iter().next())
+    {
+        return Ok(ClipboardContent::File(path));
+    }
+    let image_err = match clipboard.get_image() {
+        Ok(data) => return capture_bitmap(data).map(ClipboardContent::Bitmap),
+        Err(err) => err,
+    };
+    match clipboard
+        .get_text()
+        .ok()
+        .and_then(|text| parse_clipboard_location(&text))
+    {
+        Some(ClipboardLocation::File(path)) => Ok(ClipboardContent::File(path)),
+        Some(ClipboardLocation::Inline(bytes)) => Ok(ClipboardContent::EncodedImage(bytes)),
+        Some(ClipboardLocation::Remote) => Err(
+            "Paste failed: web addresses are not downloaded; save the image and paste the file."
+                .to_string(),
+        ),
+        None => Err(format_clipboard_error(
+            image_err,
+            "an image or an image file",
+        )),
+    }
+}
 
+fn capture_bitmap(data: arboard::ImageData<'_>) -> Result<ClipboardCapture, String> {
     if data.width == 0 || data.height == 0 {
         return Err("Paste failed: clipboard image is empty.".to_string());
     }
@@ -52,10 +214,11 @@ fn capture_clipboard_image() -> Result<ClipboardCapture, String> {
     })
 }
 
-fn format_clipboard_error(err: ClipboardError) -> String {
+/// `missing` names the requested content (e.g. "an image") for the not-available message.
+fn format_clipboard_error(err: ClipboardError, missing: &str) -> String {
     match err {
         ClipboardError::ContentNotAvailable => {
-            "Paste failed: clipboard does not contain an image.".to_string()
+            format!("Paste failed: clipboard does not contain {missing}.")
         }
         ClipboardError::ClipboardNotSupported => {
             "Paste failed: clipboard access is not supported in this environment.".to_string()
@@ -64,7 +227,7 @@ fn format_clipboard_error(err: ClipboardError) -> String {
             "Paste failed: clipboard is busy; try again in a moment.".to_string()
         }
         ClipboardError::ConversionFailure => {
-            "Paste failed: clipboard image could not be converted.".to_string()
+            "Paste failed: clipboard contents could not be converted.".to_string()
         }
         ClipboardError::Unknown { description } => {
             format!("Paste failed: {description}")
@@ -74,3 +237,65 @@ fn format_clipboard_error(err: ClipboardError) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copied_calibration_pastes_back_unchanged() {
+        let mut source = CurcatApp::default();
+        source.calibration.cal_x.p1 = Some(egui::pos2(10.0, 90.0));
+        source.calibration.cal_x.p2 = Some(egui::pos2(190.0, 90.0));
+        source.calibration.cal_x.v1_text = "1".to_string();
+        source.calibration.cal_x.v2_text = "1000".to_string();
+        source.calibration.cal_x.scale = crate::types::ScaleKind::Log10;
+        let text = encode_calibration(source.calibration_record());
+
+        let mut target = CurcatApp::default();
+        target.paste_calibration_text(&text);
+        let cal = &target.calibration.cal_x;
+        assert_eq!(cal.p2, Some(egui::pos2(190.0, 90.0)));
+        assert_eq!(cal.v2_text, "1000");
+        assert_eq!(cal.scale, crate::types::ScaleKind::Log10);
+        assert_eq!(
+            target.calibration.coord_system,
+            source.calibration.coord_system
+        );
+    }
+
+    #[test]
+    fn clipboard_text_naming_an_image_is_located() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("curcat_clipboard_location.png");
+        std::fs::write(&path, b"png").expect("write file");
+        assert_eq!(
+            parse_clipboard_location(&path.display().to_string()),
+            Some(ClipboardLocation::File(path.clone()))
+        );
+        let file_url = url::Url::from_file_path(&path).expect("file url");
+        assert_eq!(
+            parse_clipboard_location(&format!("  {file_url}\n")),
+            Some(ClipboardLocation::File(path.clone()))
+        );
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            parse_clipboard_location("data:image/png;base64,iVBORw=="),
+            Some(ClipboardLocation::Inline(vec![0x89, b'P', b'N', b'G']))
+        );
+        assert_eq!(
+            parse_clipboard_location("https://example.com/chart.png"),
+            Some(ClipboardLocation::Remote)
+        );
+        assert!(parse_clipboard_location("data:text/plain,hello").is_none());
+        assert!(parse_clipboard_location("x,y\n1,2").is_none());
+        assert!(parse_clipboard_location("/no/such/file.png").is_none());
+    }
+
+    #[test]
+    fn other_clipboard_text_is_not_a_calibration() {
+        assert!(decode_calibration("x,y\n1,2").is_none());
+        assert!(decode_calibration("{\"coord_system\": \"Cartesian\"}").is_none());
+    }
+}
@@ -0,0 +1,129 @@
+//! Per-project overrides of the global config: effective styles and the project toggles.
+
+use super::CurcatApp;
+use crate::config::{AutoPlaceConfig, ConfigOverrides, CrosshairStyle, PointStyle, StrokeStyle};
+use crate::export::ExportFormat;
+
+impl CurcatApp {
+    pub(crate) fn curve_line_style(&self) -> &StrokeStyle {
+        self.project
+            .config_overrides
+            .curve_line
+            .as_ref()
+            .unwrap_or(&self.config.curve_line)
+    }
+
+    pub(crate) fn curve_point_style(&self) -> &PointStyle {
+        self.project
+            .config_overrides
+            .curve_points
+            .as_ref()
+            .unwrap_or(&self.config.curve_points)
+    }
+
+    pub(crate) fn crosshair_style(&self) -> &CrosshairStyle {
+        self.project
+            .config_overrides
+            .crosshair
+            .as_ref()
+            .unwrap_or(&self.config.crosshair)
+    }
+
+    pub(crate) fn attention_highlight_style(&self) -> &StrokeStyle {
+        self.project
+            .config_overrides
+            .attention_highlight
+            .as_ref()
+            .unwrap_or(&self.config.attention_highlight)
+    }
+
+    pub(crate) fn effective_auto_place(&self) -> AutoPlaceConfig {
+        self.project
+            .config_overrides
+            .auto_place
+            .map_or_else(|| self.config.auto_place(), |cfg| cfg.sanitized())
+    }
+
+    /// Adopt the overrides of an opened project (or none for a plain image).
+    pub(crate) fn set_config_overrides(&mut self, overrides: ConfigOverrides) {
+        if let Some(profile) = &overrides.export {
+            self.load_export_settings(profile);
+            // A named profile would otherwise absorb these settings on the next export.
+            self.export.active_profile = None;
+        }
+        self.project.config_overrides = overrides;
+        self.interaction.auto_place_cfg = self.effective_auto_place();
+    }
+
+    /// Pin the current overlay styles to the project, or fall back to the global config.
+    pub(crate) fn set_style_overrides(&mut self, enabled: bool) {
+        let source = enabled.then(|| self.config.clone());
+        self.project
+            .config_overrides
+            .set_styles_from(source.as_ref());
+    }
+
+    pub(crate) fn set_auto_place_override(&mut self, enabled: bool) {
+        self.project.config_overrides.auto_place = enabled.then(|| self.config.auto_place());
+        self.interaction.auto_place_cfg = self.effective_auto_place();
+    }
+
+    pub(crate) fn set_export_override(&mut self, enabled: bool) {
+        self.project.config_overrides.export = enabled.then(|| self.export_settings_snapshot());
+    }
+
+    /// Overrides to write into a project; the export settings are taken as they are now.
+    pub(crate) fn config_overrides_for_save(&self) -> ConfigOverrides {
+        let mut overrides = self.project.config_overrides.clone();
+        if overrides.export.is_some() {
+            overrides.export = Some(self.export_settings_snapshot());
+        }
+        overrides
+    }
+
+    fn export_settings_snapshot(&self) -> crate::config::ExportProfile {
+        let format = self
+            .active_export_profile()
+            .map_or(ExportFormat::Csv, |profile| profile.format);
+        self.export_profile_snapshot(String::new(), format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_overrides_win_over_global_config_until_dropped() {
+        let mut app = CurcatApp::default();
+        let global_thickness = app.config.curve_line.thickness;
+
+        let mut overrides = ConfigOverrides::default();
+        let mut line = app.config.curve_line.clone();
+        line.thickness = global_thickness + 3.0;
+        overrides.set_styles_from(Some(&app.config));
+        overrides.curve_line = Some(line);
+        let mut auto_place = app.config.auto_place;
+        auto_place.distance_min = 7.5;
+        overrides.auto_place = Some(auto_place);
+        let mut export = app.export_settings_snapshot();
+        export.precision = 2;
+        overrides.export = Some(export);
+        app.set_config_overrides(overrides);
+
+        assert!((app.curve_line_style().thickness - (global_thickness + 3.0)).abs() < 1e-6);
+        assert!((app.interaction.auto_place_cfg.distance_min - 7.5).abs() < 1e-6);
+        assert_eq!(app.export.decimals, 2);
+        assert_eq!(app.export.active_profile, None);
+
+        app.set_style_overrides(false);
+        app.set_auto_place_override(false);
+        assert!((app.curve_line_style().thickness - global_thickness).abs() < 1e-6);
+        assert!(
+            (app.interaction.auto_place_cfg.distance_min - app.config.auto_place().distance_min)
+                .abs()
+                < 1e-6
+        );
+        assert!(!app.project.config_overrides.has_styles());
+    }
+}
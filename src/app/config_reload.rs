@@ -0,0 +1,144 @@
+//! Live reload of `curcat.toml`: a file watcher plus applying the re-read settings.
+
+use super::CurcatApp;
+use crate::config::AppConfig;
+use crate::i18n::UiLanguage;
+use notify::{Event, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+/// Editors save in several steps (truncate, write, rename); wait for the burst to settle.
+const CONFIG_RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Watches the directory of the config file, since editors often replace the file itself.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    rx: Receiver<notify::Result<Event>>,
+    reload_due: Option<Instant>,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Start watching `path`; `None` when its directory does not exist or cannot be watched.
+    pub fn start(ctx: &egui::Context, path: PathBuf) -> Option<Self> {
+        let dir = path.parent().filter(|dir| dir.is_dir())?.to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+            ctx.request_repaint();
+        })
+        .ok()?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive).ok()?;
+        Some(Self {
+            path,
+            rx,
+            reload_due: None,
+            _watcher: watcher,
+        })
+    }
+
+    /// Drain watcher events; returns true once edits have settled and a reload is due.
+    fn poll(&mut self, ctx: &egui::Context) -> bool {
+        let now = Instant::now();
+        for event in self.rx.try_iter() {
+            let touches_config = event.is_ok_and(|event| {
+                !event.kind.is_access() && event.paths.iter().any(|p| same_file_name(p, &self.path))
+            });
+            if touches_config {
+                self.reload_due = Some(now + CONFIG_RELOAD_DEBOUNCE);
+            }
+        }
+        match self.reload_due {
+            Some(due) if due <= now => {
+                self.reload_due = None;
+                true
+            }
+            Some(due) => {
+                ctx.request_repaint_after(due - now);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+fn same_file_name(a: &Path, b: &Path) -> bool {
+    a.file_name().is_some() && a.file_name() == b.file_name()
+}
+
+impl CurcatApp {
+    pub(crate) fn start_config_watcher(&mut self, ctx: &egui::Context) {
+        self.config_watcher =
+            AppConfig::watched_path().and_then(|path| ConfigWatcher::start(ctx, path));
+    }
+
+    /// Re-read the config once the watched file changed.
+    pub(crate) fn poll_config_watcher(&mut self, ctx: &egui::Context) {
+        let Some(watcher) = self.config_watcher.as_mut() else {
+            return;
+        };
+        if !watcher.poll(ctx) {
+            return;
+        }
+        let path = watcher.path.clone();
+        match AppConfig::load_from(&path) {
+            // A deleted config keeps the current settings until a new file appears.
+            Ok(None) => {}
+            Ok(Some(cfg)) => {
+                if self.apply_reloaded_config(cfg) {
+                    self.set_status(match self.ui.language {
+                        UiLanguage::En => {
+                            format!("Configuration reloaded from {}.", path.display())
+                        }
+                        UiLanguage::Ru => {
+                            format!("Конфигурация перечитана из {}.", path.display())
+                        }
+                    });
+                }
+            }
+            Err(err) => self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Config not reloaded: {err:#}"),
+                UiLanguage::Ru => format!("Конфигурация не перечитана: {err:#}"),
+            }),
+        }
+    }
+
+    /// Swap in `cfg`; returns false when nothing changed (e.g. the app's own writes).
+    ///
+    /// Styles, limits and export settings are read from the config on use, so replacing it
+    /// is enough; the auto-place parameters are cached and refreshed here. Project overrides
+    /// keep winning over the reloaded values.
+    pub(crate) fn apply_reloaded_config(&mut self, cfg: AppConfig) -> bool {
+        if self.config.same_settings(&cfg) {
+            return false;
+        }
+        if let Some(language) = cfg.ui_language() {
+            self.ui.language = language;
+        }
+        self.config = cfg;
+        self.interaction.auto_place_cfg = self.effective_auto_place();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reloaded_config_replaces_styles_and_cached_parameters() {
+        let mut app = CurcatApp::default();
+        assert!(!app.apply_reloaded_config(app.config.clone()));
+
+        let mut cfg = app.config.clone();
+        cfg.curve_line.thickness = 5.5;
+        cfg.auto_place.distance_min = 9.0;
+        cfg.ui.language = Some(UiLanguage::Ru);
+        assert!(app.apply_reloaded_config(cfg));
+        assert!((app.config.curve_line.thickness - 5.5).abs() < f32::EPSILON);
+        assert!((app.interaction.auto_place_cfg.distance_min - 9.0).abs() < f32::EPSILON);
+        assert_eq!(app.ui.language, UiLanguage::Ru);
+    }
+}
@@ -0,0 +1,183 @@
+//! Continuity check for digitized curves: flags points where the curve jumps.
+//!
+//! Points are ordered by pixel x and each vertical step is compared with the
+//! median of the steps around it, so the test adapts to steep and flat parts of
+//! the curve alike and does not depend on the calibration.
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+use egui::Pos2;
+use std::cmp::Ordering;
+
+/// A step this many times larger than its neighbors counts as a jump.
+const JUMP_RATIO: f32 = 4.0;
+/// Steps below this many pixels never count as jumps, however flat the neighbors are.
+const MIN_JUMP_PX: f32 = 3.0;
+/// Neighboring steps on each side used for the reference median.
+const NEIGHBOR_STEPS: usize = 3;
+
+/// A point that starts a suspicious jump in the sorted curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContinuityJump {
+    /// Index into the checked points.
+    pub(crate) index: usize,
+    /// Vertical step into the point, in pixels.
+    pub(crate) delta_y: f32,
+    /// Step relative to the median of the neighboring steps.
+    pub(crate) ratio: f32,
+}
+
+/// Find points where the curve jumps relative to its neighbors.
+///
+/// A lone spike (a big step out and straight back) flags only the spike point.
+pub fn find_continuity_jumps(pixels: &[Pos2]) -> Vec<ContinuityJump> {
+    let mut order: Vec<usize> = (0..pixels.len()).collect();
+    order.sort_by(|&a, &b| {
+        pixels[a]
+            .x
+            .partial_cmp(&pixels[b].x)
+            .unwrap_or(Ordering::Equal)
+    });
+    let steps: Vec<f32> = order
+        .windows(2)
+        .map(|pair| pixels[pair[1]].y - pixels[pair[0]].y)
+        .collect();
+    if steps.len() < 2 {
+        return Vec::new();
+    }
+
+    let jump_ratio = |idx: usize| {
+        let lo = idx.saturating_sub(NEIGHBOR_STEPS);
+        let hi = (idx + NEIGHBOR_STEPS + 1).min(steps.len());
+        let mut around: Vec<f32> = (lo..hi)
+            .filter(|&j| j != idx)
+            .map(|j| steps[j].abs())
+            .collect();
+        around.sort_by(f32::total_cmp);
+        let reference = around[around.len() / 2].max(MIN_JUMP_PX / JUMP_RATIO);
+        let step = steps[idx].abs();
+        (step >= MIN_JUMP_PX && step > JUMP_RATIO * reference).then_some(step / reference)
+    };
+
+    let mut jumps = Vec::new();
+    let mut idx = 0;
+    while idx < steps.len() {
+        let Some(ratio) = jump_ratio(idx) else {
+            idx += 1;
+            continue;
+        };
+        jumps.push(ContinuityJump {
+            index: order[idx + 1],
+            delta_y: steps[idx],
+            ratio,
+        });
+        // The way back down from a spike is part of the same jump.
+        let returns = steps
+            .get(idx + 1)
+            .is_some_and(|&next| next * steps[idx] < 0.0 && jump_ratio(idx + 1).is_some());
+        idx += if returns { 2 } else { 1 };
+    }
+    jumps
+}
+
+impl CurcatApp {
+    /// Flag the active curve's suspicious jumps for review; returns how many were found.
+    ///
+    /// Re-running the check does not stack duplicate comments on points it flagged before.
+    pub(crate) fn flag_continuity_jumps(&mut self) -> usize {
+        let pixels: Vec<Pos2> = self.points.points.iter().map(|p| p.pixel).collect();
+        let jumps = find_continuity_jumps(&pixels);
+        for jump in &jumps {
+            let comment = match self.ui.language {
+                UiLanguage::En => format!(
+                    "Jump: Δy {:.1} px ({:.1}× neighbors)",
+                    jump.delta_y.abs(),
+                    jump.ratio
+                ),
+                UiLanguage::Ru => format!(
+                    "Скачок: Δy {:.1} px ({:.1}× соседних)",
+                    jump.delta_y.abs(),
+                    jump.ratio
+                ),
+            };
+            let already = self.points.points[jump.index]
+                .flag
+                .as_ref()
+                .is_some_and(|flag| flag.comments.contains(&comment));
+            if !already {
+                self.add_point_flag_comment(jump.index, &comment);
+            }
+        }
+        jumps.len()
+    }
+
+    /// Run the continuity check from the UI and report the result.
+    pub(crate) fn check_curve_continuity(&mut self) {
+        let found = self.flag_continuity_jumps();
+        if found == 0 {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => "No suspicious jumps found.",
+                UiLanguage::Ru => "Подозрительных скачков не найдено.",
+            });
+            return;
+        }
+        self.ui.flag_review_window_open = true;
+        self.set_status_warn(self.i18n().format_continuity_jumps(found));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::pos2;
+
+    fn wave(n: usize) -> Vec<Pos2> {
+        (0..n)
+            .map(|i| {
+                let x = crate::util::safe_usize_to_f32(i) * 5.0;
+                pos2(x, 40.0f32.mul_add((x * 0.05).sin(), 100.0))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn smooth_curves_have_no_jumps() {
+        assert!(find_continuity_jumps(&wave(60)).is_empty());
+        // Picking order does not matter.
+        let mut shuffled = wave(30);
+        shuffled.reverse();
+        assert!(find_continuity_jumps(&shuffled).is_empty());
+        assert!(find_continuity_jumps(&[pos2(0.0, 0.0), pos2(1.0, 90.0)]).is_empty());
+    }
+
+    #[test]
+    fn spikes_and_steps_are_flagged_once() {
+        let mut points = wave(40);
+        points[12].y += 60.0;
+        for p in &mut points[30..] {
+            p.y -= 80.0;
+        }
+        points.swap(0, 30);
+
+        let jumps = find_continuity_jumps(&points);
+        let indices: Vec<usize> = jumps.iter().map(|j| j.index).collect();
+        assert_eq!(indices, vec![12, 0], "{jumps:?}");
+        assert!(jumps[0].delta_y > 0.0 && jumps[1].delta_y < 0.0);
+        assert!(jumps.iter().all(|j| j.ratio > JUMP_RATIO));
+    }
+
+    #[test]
+    fn rechecking_does_not_duplicate_comments() {
+        let mut app = CurcatApp::default();
+        let mut points = wave(20);
+        points[8].y += 50.0;
+        for p in points {
+            app.push_curve_point_snapped(p);
+        }
+        assert_eq!(app.flag_continuity_jumps(), 1);
+        assert_eq!(app.flag_continuity_jumps(), 1);
+        let flag = app.points.points[8].flag.as_ref().expect("flagged");
+        assert_eq!(flag.comments.len(), 1);
+        assert_eq!(app.flagged_point_count(), 1);
+    }
+}
@@ -0,0 +1,66 @@
+//! Level crossings: where each curve passes through a chosen y level, reported in the side
+//! panel, marked over the image and exported as rows.
+
+use super::{CurcatApp, PickedPoint};
+use crate::export::ExportTextColumn;
+use crate::interp::{LevelCrossing, XYPoint, level_crossings};
+use egui::{Color32, Rect, Stroke, pos2};
+
+/// Half the diagonal of a crossing marker, in screen points.
+const MARKER_REACH: f32 = 5.0;
+
+impl CurcatApp {
+    /// Crossings of the export's crossing level by the curve through `points`.
+    ///
+    /// The curve is [`Self::analysed_curve`], so the report and the exported rows agree;
+    /// between samples it is taken as straight.
+    pub(crate) fn curve_level_crossings(&self, points: &[PickedPoint]) -> Vec<LevelCrossing> {
+        level_crossings(&self.analysed_curve(points), self.export.crossing_level)
+    }
+
+    /// Export rows of the crossings of `points` inside the export range, at the level, with a
+    /// `direction` column.
+    pub(super) fn crossing_rows(
+        &self,
+        points: &[PickedPoint],
+    ) -> (Vec<XYPoint>, Vec<ExportTextColumn>) {
+        let mut crossings = self.curve_level_crossings(points);
+        crossings.retain(|c| self.in_export_range(c.x));
+        let rows = crossings
+            .iter()
+            .map(|c| XYPoint {
+                x: c.x,
+                y: self.export.crossing_level,
+            })
+            .collect();
+        let direction = crossings
+            .iter()
+            .map(|c| Some(if c.rising { "rising" } else { "falling" }.to_string()))
+            .collect();
+        (rows, vec![ExportTextColumn::new("direction", direction)])
+    }
+
+    /// Diamonds on the level crossings of every curve over the image drawn at `rect`.
+    pub(crate) fn draw_level_crossings(&self, painter: &egui::Painter, rect: Rect) {
+        if !self.export.show_crossings {
+            return;
+        }
+        let zoom = self.image.zoom;
+        let halo = Stroke::new(3.0_f32, Color32::from_black_alpha(140));
+        for (idx, curve) in self.points.curves.curves.iter().enumerate() {
+            let (rows, _) = self.crossing_rows(self.curve_points(idx));
+            let stroke = Stroke::new(1.5_f32, self.curve_point_color(idx));
+            for pixel in self.rows_to_pixels(&rows, curve.y_axis) {
+                let c = rect.min + pixel.to_vec2() * zoom;
+                let diamond = vec![
+                    pos2(c.x, c.y - MARKER_REACH),
+                    pos2(c.x + MARKER_REACH, c.y),
+                    pos2(c.x, c.y + MARKER_REACH),
+                    pos2(c.x - MARKER_REACH, c.y),
+                ];
+                painter.add(egui::Shape::closed_line(diamond.clone(), halo));
+                painter.add(egui::Shape::closed_line(diamond, stroke));
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Live statistics of the active curve for the side panel: the y extremes, the mean, the arc
+//! length and the area under the curve between two x bounds.
+
+use super::{CurcatApp, PickedPoint};
+use crate::interp::{CurveStats, XYPoint, area_under, curve_stats};
+
+/// What the statistics section shows for one curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveAnalysis {
+    pub stats: CurveStats,
+    /// x bounds of [`Self::area`]: the chosen ones, else the curve's whole span.
+    pub bounds: [f64; 2],
+    /// `None` when the bounds miss the curve.
+    pub area: Option<f64>,
+}
+
+impl CurcatApp {
+    /// The curve the interpolated export would write for `points`, smoothing included; the
+    /// statistics and the level crossings are read off it, so they agree with the export.
+    pub(crate) fn analysed_curve(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        self.smoothed_rows(self.build_interpolated_samples(points))
+    }
+
+    /// Statistics of the curve through `points`; `None` while it has no calibrated sample.
+    pub(crate) fn curve_analysis(&self, points: &[PickedPoint]) -> Option<CurveAnalysis> {
+        let samples = self.analysed_curve(points);
+        let stats = curve_stats(&samples)?;
+        let bounds = self.export.area_bounds.unwrap_or_else(|| {
+            let xs = samples.iter().map(|p| p.x).filter(|x| x.is_finite());
+            xs.fold([f64::INFINITY, f64::NEG_INFINITY], |[lo, hi], x| {
+                [lo.min(x), hi.max(x)]
+            })
+        });
+        Some(CurveAnalysis {
+            stats,
+            bounds,
+            area: area_under(&samples, bounds[0], bounds[1]),
+        })
+    }
+}
@@ -0,0 +1,58 @@
+//! Moving the window between monitors with different scale factors.
+//!
+//! egui measures everything in points, so a new `pixels_per_point` changes how many points
+//! the viewport holds while remembered sizes (panels, windows, the scroll area) are still
+//! those of the old scale. The frame that notices the change is thrown away and laid out
+//! again, and the image keeps the pixel under the viewport centre where it was.
+
+use super::{CurcatApp, ZoomIntent};
+use egui::{Context, Vec2};
+
+/// Scroll offset that keeps the content at the viewport centre in place when a viewport of
+/// `viewport` points becomes `viewport * ratio`.
+fn rescaled_pan(pan: Vec2, viewport: Vec2, ratio: f32) -> Vec2 {
+    let centre = pan + viewport * 0.5;
+    (centre - viewport * ratio * 0.5).max(Vec2::ZERO)
+}
+
+impl CurcatApp {
+    /// Notice a scale factor change and relayout at the new scale in the same frame.
+    pub(crate) fn follow_display_scale(&mut self, ctx: &Context) {
+        let ppp = ctx.pixels_per_point();
+        let Some(previous) = self.ui.pixels_per_point.replace(ppp) else {
+            return;
+        };
+        if (ppp - previous).abs() <= f32::EPSILON {
+            return;
+        }
+        // The window keeps its physical size, so its size in points scales inversely.
+        let ratio = previous / ppp;
+        if let Some(viewport) = self.image.last_viewport_size {
+            self.image.pan = rescaled_pan(self.image.pan, viewport, ratio);
+            self.image.last_viewport_size = Some(viewport * ratio);
+            self.image.zoom_intent = ZoomIntent::TargetPan(self.image.pan);
+            self.image.skip_pan_sync_once = true;
+        }
+        self.image.last_image_rect = None;
+        ctx.request_discard("display scale factor changed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pan_keeps_the_viewport_centre_across_a_scale_change() {
+        // 100% → 150%: a 900×600 point viewport shows 600×400 points.
+        let pan = rescaled_pan(Vec2::new(300.0, 200.0), Vec2::new(900.0, 600.0), 2.0 / 3.0);
+        assert!((pan - Vec2::new(450.0, 300.0)).length() < 1e-3, "{pan:?}");
+        // Back to 100%, the view returns to where it was; offsets never go negative.
+        let pan = rescaled_pan(pan, Vec2::new(600.0, 400.0), 1.5);
+        assert!((pan - Vec2::new(300.0, 200.0)).length() < 1e-3, "{pan:?}");
+        assert_eq!(
+            rescaled_pan(Vec2::ZERO, Vec2::new(600.0, 400.0), 1.5),
+            Vec2::ZERO
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Error bar picking: after a point is placed, the next click(s) set its bar ends.
+//!
+//! Bars are stored as pixel positions on the point and turned into `y_err_lo` /
+//! `y_err_hi` distances alongside the numeric cache, so they follow calibration
+//! changes like the point itself.
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+use egui::Pos2;
+
+/// How placed points pick up error bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorBarMode {
+    /// Points are placed without bars.
+    #[default]
+    Off,
+    /// Two more clicks set the lower and upper ends independently.
+    Asymmetric,
+    /// One more click sets one end; the other mirrors it through the point.
+    Symmetric,
+}
+
+impl ErrorBarMode {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Asymmetric, Self::Symmetric];
+}
+
+/// A point of the active curve still waiting for its bar ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingErrorBar {
+    /// Index of the point in the active curve.
+    pub(crate) index: usize,
+    /// First end of an asymmetric bar, once clicked.
+    pub(crate) first_end: Option<Pos2>,
+}
+
+impl CurcatApp {
+    /// Place a curve point, or an end of the pending point's error bar.
+    pub(crate) fn pick_point_or_error_bar(&mut self, pixel: Pos2) {
+        if let Some(pending) = self.points.pending_error_bar.take()
+            && pending.index < self.points.points.len()
+        {
+            self.pick_error_bar_end(pending, pixel);
+            return;
+        }
+        self.push_curve_point(pixel);
+        if self.points.error_bar_mode == ErrorBarMode::Off {
+            return;
+        }
+        self.points.pending_error_bar = Some(PendingErrorBar {
+            index: self.points.points.len() - 1,
+            first_end: None,
+        });
+        self.set_status(match (self.ui.language, self.points.error_bar_mode) {
+            (UiLanguage::En, ErrorBarMode::Symmetric) => {
+                "Click one end of the error bar; the other end mirrors it."
+            }
+            (UiLanguage::En, _) => "Click the first end of the error bar.",
+            (UiLanguage::Ru, ErrorBarMode::Symmetric) => {
+                "Щёлкните по одному концу планки погрешности; второй отразится симметрично."
+            }
+            (UiLanguage::Ru, _) => "Щёлкните по первому концу планки погрешности.",
+        });
+    }
+
+    fn pick_error_bar_end(&mut self, pending: PendingErrorBar, end: Pos2) {
+        let center = self.points.points[pending.index].pixel;
+        let ends = match (self.points.error_bar_mode, pending.first_end) {
+            (ErrorBarMode::Symmetric, _) => [end, center + (center - end)],
+            (_, Some(first)) => [first, end],
+            (_, None) => {
+                self.points.pending_error_bar = Some(PendingErrorBar {
+                    first_end: Some(end),
+                    ..pending
+                });
+                self.set_status(match self.ui.language {
+                    UiLanguage::En => "Click the other end of the error bar.",
+                    UiLanguage::Ru => "Щёлкните по второму концу планки погрешности.",
+                });
+                return;
+            }
+        };
+        self.points.points[pending.index].error_bar = Some(ends);
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Error bar set.",
+            UiLanguage::Ru => "Планка погрешности задана.",
+        });
+    }
+
+    /// Drop a half-picked error bar, e.g. when the points it refers to change.
+    pub(crate) const fn cancel_pending_error_bar(&mut self) {
+        self.points.pending_error_bar = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::pos2;
+
+    #[test]
+    fn asymmetric_bars_take_two_clicks_and_symmetric_bars_one() {
+        let mut app = CurcatApp::default();
+        app.points.error_bar_mode = ErrorBarMode::Asymmetric;
+        app.pick_point_or_error_bar(pos2(50.0, 50.0));
+        app.pick_point_or_error_bar(pos2(50.0, 40.0));
+        assert!(app.points.points[0].error_bar.is_none());
+        app.pick_point_or_error_bar(pos2(50.0, 70.0));
+        assert_eq!(
+            app.points.points[0].error_bar,
+            Some([pos2(50.0, 40.0), pos2(50.0, 70.0)])
+        );
+
+        app.points.error_bar_mode = ErrorBarMode::Symmetric;
+        app.pick_point_or_error_bar(pos2(80.0, 30.0));
+        app.pick_point_or_error_bar(pos2(80.0, 24.0));
+        assert_eq!(app.points.points.len(), 2);
+        assert_eq!(
+            app.points.points[1].error_bar,
+            Some([pos2(80.0, 24.0), pos2(80.0, 36.0)])
+        );
+        assert!(app.points.pending_error_bar.is_none());
+    }
+
+    #[test]
+    fn removing_the_pending_point_places_a_new_point_instead() {
+        let mut app = CurcatApp::default();
+        app.points.error_bar_mode = ErrorBarMode::Symmetric;
+        app.pick_point_or_error_bar(pos2(10.0, 10.0));
+        app.undo_last_point();
+        app.pick_point_or_error_bar(pos2(20.0, 20.0));
+        assert_eq!(app.points.points.len(), 1);
+        assert!(app.points.points[0].error_bar.is_none());
+        assert!(app.points.pending_error_bar.is_some());
+    }
+}
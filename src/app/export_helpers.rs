@@ -1,15 +1,27 @@
 //! Helpers for formatting and preparing export payloads.
 
-use super::CurcatApp;
-use crate::export::{ExportExtraColumn, ExportPayload, sequential_distances, turning_angles};
+use super::{CurcatApp, PickedPoint};
+use crate::export::{
+    ExportExtraColumn, ExportPayload, ExportTextColumn, PARAMETER_COLUMN_HEADER,
+    Y_AXIS_COLUMN_HEADER, axis_steps, sequential_distances, turning_angles,
+};
+use crate::fit::{CurveFit, fit_curve};
 use crate::i18n::UiLanguage;
-use crate::interp::{XYPoint, auto_sample_count, interpolate_sorted};
-use crate::types::{AngleUnit, AxisUnit, CoordSystem};
+use crate::interp::{
+    InterpAlgorithm, XYPoint, auto_sample_count, even_grid, interpolate_on_grid,
+    interpolate_sorted, smoothing_spline, step_breakpoints, subtract_below,
+};
+use crate::profiling::profile_function;
+use crate::spectrum::amplitude_spectrum;
+use crate::types::{AngleUnit, AxisUnit, CoordSystem, ScaleKind, YAxis};
+
+/// Smoothing-spline weight of a point with no snap confidence, placed or moved by hand; a sure
+/// snap weighs ten times as much.
+const MIN_CONFIDENCE_WEIGHT: f64 = 0.1;
 
 impl CurcatApp {
-    pub(crate) fn collect_numeric_points_in_order(&self) -> Vec<XYPoint> {
-        self.points
-            .points
+    pub(crate) fn collect_numeric_points_in_order(points: &[PickedPoint]) -> Vec<XYPoint> {
+        points
             .iter()
             .filter_map(|p| match (p.x_numeric, p.y_numeric) {
                 (Some(x), Some(y)) => Some(XYPoint { x, y }),
@@ -18,14 +30,180 @@ impl CurcatApp {
             .collect()
     }
 
-    pub(crate) fn build_interpolated_samples(&mut self) -> Vec<XYPoint> {
-        let sample_count = self.export.sample_count;
-        let algo = self.export.interp_algorithm;
-        let nums = self.sorted_numeric_points_cache();
+    /// Flag annotations aligned with [`Self::collect_numeric_points_in_order`].
+    pub(crate) fn collect_flag_annotations_in_order(points: &[PickedPoint]) -> Vec<Option<String>> {
+        points
+            .iter()
+            .filter(|p| p.x_numeric.is_some() && p.y_numeric.is_some())
+            .map(|p| p.flag.as_ref().map(super::PointFlag::annotation))
+            .collect()
+    }
+
+    /// Capture times aligned with exported raw points, as UTC ISO-8601 with milliseconds.
+    pub(crate) fn collect_capture_times_in_order(points: &[PickedPoint]) -> Vec<Option<String>> {
+        points
+            .iter()
+            .filter(|p| p.x_numeric.is_some() && p.y_numeric.is_some())
+            .map(|p| {
+                p.captured_at
+                    .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())
+            })
+            .collect()
+    }
+
+    /// Calibrated points of `points` sorted by x, for interpolation by
+    /// [`Self::node_interp_algorithm`].
+    ///
+    /// The smoothing spline is solved here, while each point's snap confidence is at hand to
+    /// weight it, and its nodes are returned in place of the points.
+    pub(crate) fn interpolation_nodes(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        let mut weighted: Vec<(XYPoint, f64)> = points
+            .iter()
+            .filter_map(|p| match (p.x_numeric, p.y_numeric) {
+                (Some(x), Some(y)) => {
+                    let confidence = f64::from(p.confidence.clamp(0.0, 1.0));
+                    let weight =
+                        (1.0 - MIN_CONFIDENCE_WEIGHT).mul_add(confidence, MIN_CONFIDENCE_WEIGHT);
+                    Some((XYPoint { x, y }, weight))
+                }
+                _ => None,
+            })
+            .collect();
+        weighted.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+        let (nums, weights): (Vec<XYPoint>, Vec<f64>) = weighted.into_iter().unzip();
+        if self.export.interp_algorithm != InterpAlgorithm::SmoothingSpline {
+            return nums;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let span = self.export.spline_span as f64;
+        smoothing_spline(&nums, &weights, span)
+    }
+
+    /// Algorithm that joins [`Self::interpolation_nodes`]: the natural cubic through the nodes
+    /// of a smoothing spline is the spline itself.
+    pub(crate) const fn node_interp_algorithm(&self) -> InterpAlgorithm {
+        match self.export.interp_algorithm {
+            InterpAlgorithm::SmoothingSpline => InterpAlgorithm::NaturalCubic,
+            algo => algo,
+        }
+    }
+
+    /// Interpolated samples of `points` with the configured count and algorithm.
+    pub(crate) fn build_interpolated_samples(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        let nums = self.interpolation_nodes(points);
         if nums.len() < 2 {
             return Vec::new();
         }
-        interpolate_sorted(nums, sample_count, algo)
+        interpolate_sorted(
+            &nums,
+            self.export.sample_count,
+            self.node_interp_algorithm(),
+        )
+    }
+
+    /// [`Self::build_interpolated_samples`] spread over the export range instead of the whole
+    /// curve.
+    fn interpolated_samples_in_range(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        if self.export.x_range.is_none() {
+            return self.build_interpolated_samples(points);
+        }
+        let nums = self.interpolation_nodes(points);
+        if nums.len() < 2 {
+            return Vec::new();
+        }
+        let Some([lo, hi]) = self.clip_to_export_range(nums[0].x, nums[nums.len() - 1].x) else {
+            return Vec::new();
+        };
+        let xs = even_grid(lo, hi, self.export.sample_count);
+        interpolate_on_grid(&nums, &xs, self.node_interp_algorithm())
+    }
+
+    /// Rows of the interpolated export: the samples of the sample grid, or the exact corners of
+    /// the step-hold curve when step breakpoints are on, inside the export range.
+    pub(crate) fn interpolated_export_rows(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        let breakpoints = self.export.step_breakpoints
+            && self.export.interp_algorithm == InterpAlgorithm::StepHold;
+        let grid = if breakpoints {
+            None
+        } else {
+            let Some(mut xs) = self.sample_grid_xs(points) else {
+                return self.interpolated_samples_in_range(points);
+            };
+            xs.retain(|&x| self.in_export_range(x));
+            Some(xs)
+        };
+        let nums = self.interpolation_nodes(points);
+        grid.map_or_else(
+            || self.step_breakpoints_in_range(&nums, step_breakpoints(&nums)),
+            |xs| interpolate_on_grid(&nums, &xs, self.node_interp_algorithm()),
+        )
+    }
+
+    /// `rows` less the curve through `below`, the boundary of the layer underneath, when
+    /// layers are unstacked; unchanged otherwise.
+    fn unstacked(&self, rows: Vec<XYPoint>, below: Option<&[PickedPoint]>) -> Vec<XYPoint> {
+        let Some(below) = below else {
+            return rows;
+        };
+        let lower = self.interpolation_nodes(below);
+        subtract_below(&rows, &lower, self.node_interp_algorithm())
+    }
+
+    /// Fit the configured model to the calibrated points of `points`.
+    pub(crate) fn fit_points(&self, points: &[PickedPoint]) -> Option<CurveFit> {
+        let nums = Self::collect_numeric_points_in_order(points);
+        fit_curve(&nums, self.export.fit_model, self.export.fit_degree)
+    }
+
+    fn build_fitted_samples(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        let Some(fit) = self.fit_points(points) else {
+            return Vec::new();
+        };
+        let (x_min, x_max) = Self::collect_numeric_points_in_order(points)
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                (lo.min(p.x), hi.max(p.x))
+            });
+        // The model is fitted to every point but only sampled inside the export range.
+        let Some([x_min, x_max]) = self.clip_to_export_range(x_min, x_max) else {
+            return Vec::new();
+        };
+        let Some(mut xs) = self.sample_grid_xs(points) else {
+            return fit.sample(x_min, x_max, self.export.sample_count);
+        };
+        // Like the interpolated rows, the model is not extrapolated past the picked points.
+        xs.retain(|x| (x_min..=x_max).contains(x));
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+        xs.into_iter()
+            .map(|x| XYPoint { x, y: fit.eval(x) })
+            .collect()
+    }
+
+    /// Default the sample count to one sample per pixel of the calibrated X axis whenever that
+    /// span changes, until the count is chosen some other way.
+    ///
+    /// Denser samples carry nothing the image does not; sparser ones drop detail it has.
+    pub(crate) fn follow_axis_sample_count(&mut self) {
+        if !self.export.sample_count_follows_axis
+            || self.calibration.coord_system != CoordSystem::Cartesian
+        {
+            return;
+        }
+        let Some(mapping) = self.calibration.cal_x.mapping() else {
+            return;
+        };
+        // A length is non-negative and far below `usize::MAX`.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let pixels = (mapping.p2 - mapping.p1).length().round() as usize;
+        if self.export.followed_axis_pixels == Some(pixels) {
+            return;
+        }
+        self.export.followed_axis_pixels = Some(pixels);
+        self.export.sample_count = pixels.clamp(
+            super::SAMPLE_COUNT_MIN,
+            self.config.export.samples_max_sanitized(),
+        );
     }
 
     pub(crate) fn auto_tune_sample_count(&mut self) {
@@ -43,6 +221,22 @@ impl CurcatApp {
                         "Завершите калибровку начала, радиуса и угла перед автоподбором семплов."
                     }
                 },
+                CoordSystem::Affine => match self.ui.language {
+                    UiLanguage::En => {
+                        "Place at least three reference points before auto-tuning samples."
+                    }
+                    UiLanguage::Ru => {
+                        "Задайте как минимум три опорные точки перед автоподбором семплов."
+                    }
+                },
+                CoordSystem::Smith => match self.ui.language {
+                    UiLanguage::En => {
+                        "Pick the chart centre and short-circuit point before auto-tuning samples."
+                    }
+                    UiLanguage::Ru => {
+                        "Выберите центр диаграммы и точку короткого замыкания перед автоподбором семплов."
+                    }
+                },
             });
             return;
         }
@@ -74,25 +268,58 @@ impl CurcatApp {
         let suggested =
             auto_sample_count(nums, algo, min_samples, max_samples, rel_tol, ref_samples);
         self.export.sample_count = suggested;
+        self.export.sample_count_follows_axis = false;
         self.set_status(self.i18n().format_sample_count_tuned(suggested));
     }
 
-    pub(crate) fn build_export_payload(&mut self) -> Result<ExportPayload, &'static str> {
+    /// One payload per curve that has data, named after the curve, in curve order.
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn build_export_curves(
+        &mut self,
+    ) -> Result<Vec<(String, ExportPayload)>, &'static str> {
+        profile_function!();
         if !self.calibration_ready() {
             return Err(match self.calibration.coord_system {
                 CoordSystem::Cartesian => "Complete both axis calibrations before export.",
                 CoordSystem::Polar => {
                     "Complete origin, radius, and angle calibration before export."
                 }
+                CoordSystem::Affine => "Place at least three reference points before export.",
+                CoordSystem::Smith => {
+                    "Pick the Smith chart centre and short-circuit point and enter a positive \
+                     reference impedance before export."
+                }
             });
         }
 
+        let dual_axis = self.uses_secondary_y_axis();
+        if self.calibration.coord_system == CoordSystem::Cartesian
+            && self.inverted_axis_disagrees(dual_axis)
+        {
+            return Err("An axis marked Inverted has values growing the usual way; \
+                 swap its values or clear Inverted before export.");
+        }
+
         let (x_mapping, y_mapping) = self.cartesian_mappings();
         let polar_mapping = self.polar_mapping();
-        let (x_label, y_label) = self.axis_labels();
+        let smith_mapping = (self.calibration.coord_system == CoordSystem::Smith)
+            .then(|| self.smith_mapping())
+            .flatten();
+        let spectrum = self.export.export_kind == super::ExportKind::Spectrum;
+        let (x_label, y_label) = if spectrum {
+            ("frequency".to_string(), "amplitude".to_string())
+        } else {
+            self.axis_labels()
+        };
+        let secondary_y_unit = self.secondary_y_mapping().map(|mapping| mapping.unit);
+        if dual_axis && secondary_y_unit.is_none() {
+            return Err("Complete the secondary Y axis calibration before export.");
+        }
 
         let (x_unit, y_unit, angle_unit) = match self.calibration.coord_system {
-            CoordSystem::Cartesian => {
+            // A spectrum is amplitude over frequency whatever the axes hold.
+            _ if spectrum => (AxisUnit::Float, AxisUnit::Float, None),
+            CoordSystem::Cartesian | CoordSystem::Affine => {
                 let x_unit = x_mapping
                     .as_ref()
                     .map(|mapping| mapping.unit)
@@ -103,6 +330,7 @@ impl CurcatApp {
                     .ok_or("Complete both axis calibrations before export.")?;
                 (x_unit, y_unit, None)
             }
+            CoordSystem::Smith => (AxisUnit::Float, AxisUnit::Float, None),
             CoordSystem::Polar => (
                 AxisUnit::Float,
                 AxisUnit::Float,
@@ -119,59 +347,230 @@ impl CurcatApp {
             polar_mapping.as_ref(),
         );
 
-        match self.export.export_kind {
-            super::ExportKind::Interpolated => {
-                let data = self.build_interpolated_samples();
-                if data.is_empty() {
-                    Err("Nothing to export. Add data points first.")
-                } else {
-                    let mut extra_columns = Vec::new();
-                    if self.calibration.coord_system == CoordSystem::Polar
-                        && self.export.polar_export_include_cartesian
-                        && let Some(unit) = angle_unit
-                    {
-                        extra_columns.extend(Self::polar_cartesian_columns(&data, unit));
-                    }
-                    Ok(ExportPayload {
-                        points: data,
-                        x_unit,
-                        y_unit,
-                        x_label: x_label.to_string(),
-                        y_label: y_label.to_string(),
-                        coord_system: self.calibration.coord_system,
-                        angle_unit,
-                        extra_columns,
-                    })
+        let calibration_report = (!spectrum).then(|| self.calibration_report()).flatten();
+        let parameter_header = self.parameter_column_header();
+        let mut curves = Vec::new();
+        for (idx, curve) in self.points.curves.curves.iter().enumerate() {
+            // Curves stack bottom first: each one's layer sits on the previous curve.
+            let below = (self.export.unstack_layers && idx > 0).then(|| self.curve_points(idx - 1));
+            let (data, mut extra_columns, mut text_columns) =
+                self.curve_export_rows(self.curve_points(idx), curve.y_axis, below);
+            if data.is_empty() {
+                continue;
+            }
+            if self.calibration.coord_system == CoordSystem::Polar
+                && self.export.polar_export_include_cartesian
+                && let Some(unit) = angle_unit
+            {
+                extra_columns.extend(Self::polar_cartesian_columns(&data, unit));
+            }
+            if !spectrum && let Some(smith) = &smith_mapping {
+                extra_columns.extend(Self::smith_columns(&data, smith.reference_ohms));
+            }
+            if let Some(header) = &parameter_header {
+                extra_columns.push(Self::parameter_column(header, curve, data.len()));
+            }
+            let (y_unit, y_label) = match (curve.y_axis, secondary_y_unit) {
+                (YAxis::Secondary, Some(unit)) if dual_axis && !spectrum => {
+                    (unit, self.secondary_y_label())
                 }
+                _ => (y_unit, y_label.clone()),
+            };
+            if dual_axis {
+                let axis = Some(curve.y_axis.label().to_string());
+                text_columns.push(ExportTextColumn::new(
+                    Y_AXIS_COLUMN_HEADER,
+                    vec![axis; data.len()],
+                ));
             }
+            let mut payload = ExportPayload {
+                points: data,
+                x_unit,
+                y_unit,
+                x_label: x_label.clone(),
+                y_label,
+                title: self.project.title.clone(),
+                calibration_report,
+                coord_system: self.calibration.coord_system,
+                angle_unit,
+                extra_columns,
+                text_columns,
+                options: self.export_options(),
+            };
+            payload.apply_time_format(self.export.time_format);
+            curves.push((curve.name.clone(), payload));
+        }
+        if curves.is_empty() && self.export.export_kind == super::ExportKind::Crossings {
+            Err("No curve crosses the crossing level.")
+        } else if curves.is_empty() {
+            Err("Nothing to export. Add data points first.")
+        } else {
+            Ok(curves)
+        }
+    }
+
+    /// Header of the family parameter column; `None` when no curve has a parameter value.
+    pub(super) fn parameter_column_header(&self) -> Option<String> {
+        if !self
+            .points
+            .curves
+            .curves
+            .iter()
+            .any(|c| c.parameter().is_some())
+        {
+            return None;
+        }
+        let name = self.project.parameter_name.trim();
+        Some(if name.is_empty() {
+            PARAMETER_COLUMN_HEADER.to_string()
+        } else {
+            name.to_string()
+        })
+    }
+
+    /// `rows` copies of the curve's family parameter, empty if it has none.
+    fn parameter_column(header: &str, curve: &super::Curve, rows: usize) -> ExportExtraColumn {
+        ExportExtraColumn::new(header, vec![curve.parameter(); rows])
+    }
+
+    /// Whether an axis declared reversed has values that actually grow the usual way, which
+    /// would export the data mirrored.
+    fn inverted_axis_disagrees(&self, dual_axis: bool) -> bool {
+        let calibration = &self.calibration;
+        let disagrees =
+            |cal: &super::AxisCalUi, is_x: bool| cal.inverted && cal.direction_mismatch(is_x);
+        disagrees(&calibration.cal_x, true)
+            || disagrees(&calibration.cal_y, false)
+            || (dual_axis && disagrees(&calibration.cal_secondary_y, false))
+    }
+
+    /// Rows of one curve for the chosen export kind, with their extra and text columns.
+    ///
+    /// With `below`, the points of the curve underneath, the rows are the layer between the
+    /// two curves; level crossings stay on the curve itself.
+    fn curve_export_rows(
+        &self,
+        points: &[PickedPoint],
+        y_axis: YAxis,
+        below: Option<&[PickedPoint]>,
+    ) -> (Vec<XYPoint>, Vec<ExportExtraColumn>, Vec<ExportTextColumn>) {
+        let (data, mut extra_columns, text_columns) = match self.export.export_kind {
+            super::ExportKind::Interpolated => (
+                self.smoothed_rows(self.unstacked(self.interpolated_export_rows(points), below)),
+                Vec::new(),
+                Vec::new(),
+            ),
             super::ExportKind::RawPoints => {
-                let data = self.collect_numeric_points_in_order();
-                if data.is_empty() {
-                    Err("Nothing to export. Add data points first.")
-                } else {
-                    let mut extras = self.build_raw_extra_columns(&data);
-                    if self.calibration.coord_system == CoordSystem::Polar
-                        && self.export.polar_export_include_cartesian
-                        && let Some(unit) = angle_unit
-                    {
-                        extras.extend(Self::polar_cartesian_columns(&data, unit));
-                    }
-                    Ok(ExportPayload {
-                        points: data,
-                        x_unit,
-                        y_unit,
-                        x_label: x_label.to_string(),
-                        y_label: y_label.to_string(),
-                        coord_system: self.calibration.coord_system,
-                        angle_unit,
-                        extra_columns: extras,
-                    })
+                let points = &*self.points_in_export_range(points);
+                let data = self.smoothed_rows(
+                    self.unstacked(Self::collect_numeric_points_in_order(points), below),
+                );
+                let mut extras = self.build_raw_extra_columns(&data, y_axis);
+                extras.extend(Self::error_bar_columns(points));
+                if self.export.raw_include_confidence {
+                    extras.push(Self::confidence_column(points));
                 }
+                (data, extras, self.build_raw_text_columns(points))
+            }
+            super::ExportKind::Fitted => (
+                self.unstacked(self.build_fitted_samples(points), below),
+                Vec::new(),
+                Vec::new(),
+            ),
+            super::ExportKind::Spectrum => {
+                let (data, phase) = Self::spectrum_rows(&self.smoothed_rows(
+                    self.unstacked(self.interpolated_samples_in_range(points), below),
+                ));
+                (data, phase, Vec::new())
+            }
+            super::ExportKind::Crossings => {
+                let (data, direction) = self.crossing_rows(points);
+                (data, Vec::new(), direction)
             }
+        };
+        if self.export.include_fit_column
+            && matches!(
+                self.export.export_kind,
+                super::ExportKind::Interpolated | super::ExportKind::RawPoints
+            )
+            && let Some(fit) = self.fit_points(points)
+        {
+            let values = data.iter().map(|p| Some(fit.eval(p.x))).collect();
+            extra_columns.push(ExportExtraColumn::new("fit", values));
+        }
+        extra_columns.extend(self.template_columns(&data));
+        (data, extra_columns, text_columns)
+    }
+
+    /// Frequency and amplitude rows of `samples` with the phase column; empty when the curve
+    /// has too few samples for a spectrum.
+    fn spectrum_rows(samples: &[XYPoint]) -> (Vec<XYPoint>, Vec<ExportExtraColumn>) {
+        let Ok(bins) = amplitude_spectrum(samples) else {
+            return (Vec::new(), Vec::new());
+        };
+        let rows = bins
+            .iter()
+            .map(|bin| XYPoint {
+                x: bin.frequency,
+                y: bin.amplitude,
+            })
+            .collect();
+        let phase = bins.iter().map(|bin| Some(bin.phase_deg)).collect();
+        (rows, vec![ExportExtraColumn::new("phase_deg", phase)])
+    }
+
+    /// Selected config column templates computed over `data`; broken ones are left out.
+    fn template_columns(&self, data: &[XYPoint]) -> Vec<ExportExtraColumn> {
+        self.config
+            .export
+            .columns
+            .iter()
+            .filter(|template| self.export.column_templates.contains(&template.name))
+            .filter_map(|template| {
+                let values = template.values(data).ok()?;
+                Some(ExportExtraColumn::new(template.name.trim(), values))
+            })
+            .collect()
+    }
+
+    fn build_raw_text_columns(&self, points: &[PickedPoint]) -> Vec<ExportTextColumn> {
+        let mut text_columns = Vec::new();
+        if self.export.raw_include_flags {
+            text_columns.push(ExportTextColumn::new(
+                "flag",
+                Self::collect_flag_annotations_in_order(points),
+            ));
+        }
+        if self.export.raw_include_capture_times {
+            text_columns.push(ExportTextColumn::new(
+                "captured_at",
+                Self::collect_capture_times_in_order(points),
+            ));
+        }
+        text_columns
+    }
+
+    /// Scales of the axes a curve read against `y_axis` is exported in.
+    fn export_axis_scales(&self, y_axis: YAxis) -> (ScaleKind, ScaleKind) {
+        if self.calibration.coord_system == CoordSystem::Polar {
+            return (ScaleKind::Linear, self.calibration.polar_cal.radius.scale);
         }
+        let (x_mapping, y_mapping) = self.cartesian_mappings();
+        let y_mapping = match y_axis {
+            YAxis::Secondary if self.uses_secondary_y_axis() => self.secondary_y_mapping(),
+            _ => y_mapping,
+        };
+        let scale = |mapping: Option<super::AxisMapping>| {
+            mapping.map_or(ScaleKind::Linear, |mapping| mapping.scale)
+        };
+        (scale(x_mapping), scale(y_mapping))
     }
 
-    fn build_raw_extra_columns(&self, raw_points: &[XYPoint]) -> Vec<ExportExtraColumn> {
+    fn build_raw_extra_columns(
+        &self,
+        raw_points: &[XYPoint],
+        y_axis: YAxis,
+    ) -> Vec<ExportExtraColumn> {
         let mut extras = Vec::new();
         if self.export.raw_include_distances {
             extras.push(ExportExtraColumn::new(
@@ -185,9 +584,47 @@ impl CurcatApp {
                 turning_angles(raw_points),
             ));
         }
+        if self.export.raw_include_steps {
+            let (x_scale, y_scale) = self.export_axis_scales(y_axis);
+            extras.extend(axis_steps(raw_points, x_scale, y_scale));
+        }
         extras
     }
 
+    /// `y_err_lo` / `y_err_hi` aligned with exported raw points; none if no point has a bar.
+    fn error_bar_columns(points: &[PickedPoint]) -> Vec<ExportExtraColumn> {
+        let errors: Vec<Option<[f64; 2]>> = points
+            .iter()
+            .filter(|p| p.x_numeric.is_some() && p.y_numeric.is_some())
+            .map(|p| p.y_error)
+            .collect();
+        if errors.iter().all(Option::is_none) {
+            return Vec::new();
+        }
+        vec![
+            ExportExtraColumn::new(
+                "y_err_lo",
+                errors.iter().map(|e| e.map(|[lo, _]| lo)).collect(),
+            ),
+            ExportExtraColumn::new(
+                "y_err_hi",
+                errors.iter().map(|e| e.map(|[_, hi]| hi)).collect(),
+            ),
+        ]
+    }
+
+    /// Snap confidence aligned with exported raw points.
+    fn confidence_column(points: &[PickedPoint]) -> ExportExtraColumn {
+        ExportExtraColumn::new(
+            "confidence",
+            points
+                .iter()
+                .filter(|p| p.x_numeric.is_some() && p.y_numeric.is_some())
+                .map(|p| Some(f64::from(p.confidence)))
+                .collect(),
+        )
+    }
+
     fn polar_cartesian_columns(
         points: &[XYPoint],
         angle_unit: AngleUnit,
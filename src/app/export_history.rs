@@ -0,0 +1,118 @@
+//! Export history of the current project: recording finished exports, reopening their files
+//! and writing them again with the same settings.
+
+use super::CurcatApp;
+use crate::config::ExportProfile;
+use crate::i18n::UiLanguage;
+use crate::project::ExportHistoryRecord;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Oldest entries are dropped beyond this many.
+const MAX_EXPORT_HISTORY: usize = 100;
+
+impl CurcatApp {
+    /// Remember a finished export of `rows` data rows written to `path` with `settings`.
+    pub(crate) fn record_export(&mut self, path: PathBuf, settings: ExportProfile, rows: usize) {
+        let history = &mut self.project.export_history;
+        history.push(ExportHistoryRecord {
+            path,
+            settings,
+            rows,
+            exported_at_ms: DateTime::<Utc>::from(SystemTime::now()).timestamp_millis(),
+        });
+        let excess = history.len().saturating_sub(MAX_EXPORT_HISTORY);
+        history.drain(..excess);
+    }
+
+    /// Write history entry `idx` again to its path, with the settings it was made with.
+    ///
+    /// The export section keeps its current settings.
+    pub(crate) fn repeat_export(&mut self, idx: usize) {
+        let Some(record) = self.project.export_history.get(idx).cloned() else {
+            return;
+        };
+        let format = record.settings.format;
+        let current = self.export_profile_snapshot(String::new(), format);
+        let follows_axis = self.export.sample_count_follows_axis;
+        self.load_export_settings(&record.settings);
+        match self.build_export_curves() {
+            Ok(curves) => {
+                if self.validate_export(&curves) {
+                    self.start_export_job(record.path, curves, format);
+                }
+            }
+            Err(msg) => self.set_status_warn(msg),
+        }
+        self.load_export_settings(&current);
+        self.export.sample_count_follows_axis = follows_axis;
+    }
+
+    /// Open the file of history entry `idx` in its default application.
+    pub(crate) fn open_exported_file(&mut self, idx: usize) {
+        let Some(path) = self.export_history_path(idx) else {
+            return;
+        };
+        self.open_with_desktop(&path);
+    }
+
+    /// Open the folder holding the file of history entry `idx`.
+    pub(crate) fn open_export_folder(&mut self, idx: usize) {
+        let Some(path) = self.export_history_path(idx) else {
+            return;
+        };
+        match path.parent().filter(|dir| dir.is_dir()) {
+            Some(dir) => self.open_with_desktop(dir),
+            None => self.set_status_warn(match self.ui.language {
+                UiLanguage::En => format!("Folder of {} no longer exists.", path.display()),
+                UiLanguage::Ru => format!("Папка файла {} больше не существует.", path.display()),
+            }),
+        }
+    }
+
+    /// Path of history entry `idx` if the file is still there; warns otherwise.
+    fn export_history_path(&mut self, idx: usize) -> Option<PathBuf> {
+        let path = self.project.export_history.get(idx)?.path.clone();
+        if path.exists() {
+            return Some(path);
+        }
+        self.set_status_warn(match self.ui.language {
+            UiLanguage::En => format!("{} no longer exists.", path.display()),
+            UiLanguage::Ru => format!("Файл {} больше не существует.", path.display()),
+        });
+        None
+    }
+
+    fn open_with_desktop(&mut self, target: &Path) {
+        if let Err(err) = desktop_open_command(target).spawn() {
+            self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Failed to open {}: {err}", target.display()),
+                UiLanguage::Ru => format!("Не удалось открыть {}: {err}", target.display()),
+            });
+        }
+    }
+}
+
+/// Command that opens `target` with the desktop's default handler.
+fn desktop_open_command(target: &Path) -> Command {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let mut command = Command::new(program);
+    command.arg(target);
+    command
+}
+
+/// `2026-10-14 09:30 UTC` for an entry's timestamp.
+pub fn format_export_time(exported_at_ms: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(exported_at_ms).map_or_else(
+        || "—".to_string(),
+        |time| time.format("%Y-%m-%d %H:%M UTC").to_string(),
+    )
+}
@@ -0,0 +1,236 @@
+use super::{CurcatApp, ExportJob, ExportJobResult, StatusLevel};
+use crate::error::CurcatError;
+use crate::export::{self, ExportCancelled, ExportFormat, ExportPayload, ExportProgress};
+use crate::i18n::UiLanguage;
+use crate::profiling::profile_scope;
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use egui::Context;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, SystemTime};
+
+/// How often the UI refreshes the progress bar while an export is running.
+const EXPORT_PROGRESS_REPAINT: Duration = Duration::from_millis(100);
+
+impl CurcatApp {
+    pub(crate) const fn export_job_running(&self) -> bool {
+        self.export.pending_export_job.is_some()
+    }
+
+    /// Write the named curve payloads on the rayon pool so large exports keep the window
+    /// responsive.
+    pub(crate) fn start_export_job(
+        &mut self,
+        path: PathBuf,
+        curves: Vec<(String, ExportPayload)>,
+        format: ExportFormat,
+    ) {
+        if self.export_job_running() {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "Another export is still running.",
+                UiLanguage::Ru => "Предыдущий экспорт ещё выполняется.",
+            });
+            return;
+        }
+        let rows = curves.iter().map(|(_, payload)| payload.points.len()).sum();
+        let long_format = self.export.long_format;
+        let settings = self.export_profile_snapshot(String::new(), format);
+        let progress = Arc::new(ExportProgress::default());
+        let worker_progress = Arc::clone(&progress);
+        let worker_path = path.clone();
+        let backup = self
+            .config
+            .export
+            .backup
+            .backup_path(&path, DateTime::<Utc>::from(SystemTime::now()))
+            .filter(|_| path.is_file());
+        let worker_backup = backup.clone();
+        let (tx, rx) = mpsc::channel();
+        rayon::spawn(move || {
+            profile_scope!("export_job");
+            let displaced = match &worker_backup {
+                Some(target) => match move_to_backup(&worker_path, target) {
+                    Ok(displaced) => displaced,
+                    Err(err) => {
+                        let _ = tx.send(ExportJobResult::Failed(err));
+                        return;
+                    }
+                },
+                None => None,
+            };
+            let result = match format {
+                ExportFormat::Csv => {
+                    export::export_curves_to_csv_streamed(&worker_path, &curves, &worker_progress)
+                        .map_err(|err| (!err.is::<ExportCancelled>()).then_some(err))
+                }
+                // One stacked table instead of a sheet or object per curve.
+                _ if long_format => export::merge_curve_payloads(curves)
+                    .and_then(|payload| format.export(&worker_path, &payload))
+                    .map_err(Some),
+                _ => format.export_curves(&worker_path, curves).map_err(Some),
+            };
+            // Nothing was written, so the file that was backed up goes back in its place and
+            // the older backup back under its name; only a finished export replaces it.
+            let result = match (result, &worker_backup) {
+                (Err(err), Some(target)) => {
+                    match restore_backup(&worker_path, target, displaced.as_deref()) {
+                        Ok(()) => Err(err),
+                        Err(restore) => Err(Some(err.map_or(restore, |err| {
+                            err.context(format!(
+                                "the previous file is kept as {}",
+                                target.display()
+                            ))
+                        }))),
+                    }
+                }
+                (result, _) => {
+                    if let Some(aside) = &displaced {
+                        let _ = std::fs::remove_file(aside);
+                    }
+                    result
+                }
+            };
+            let _ = tx.send(match result {
+                Ok(()) => ExportJobResult::Finished,
+                Err(None) => ExportJobResult::Cancelled,
+                Err(Some(err)) => ExportJobResult::Failed(err),
+            });
+        });
+        self.export.pending_export_job = Some(ExportJob {
+            rx,
+            progress,
+            format,
+            path,
+            settings,
+            rows,
+            backup,
+        });
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Exporting {}…", format.label()),
+            UiLanguage::Ru => format!("Экспорт {}…", format.label()),
+        });
+    }
+
+    /// Ask the running export to stop at its next chunk boundary.
+    pub(crate) fn cancel_export_job(&self) {
+        if let Some(job) = &self.export.pending_export_job {
+            job.progress.cancel();
+        }
+    }
+
+    pub(crate) fn poll_export_job(&mut self, ctx: &Context) {
+        let Some(job) = self.export.pending_export_job.take() else {
+            return;
+        };
+        let format_label = job.format.label();
+        match job.rx.try_recv() {
+            Ok(ExportJobResult::Finished) => {
+                let mut msg = match self.ui.language {
+                    UiLanguage::En => format!("{format_label} saved to {}", job.path.display()),
+                    UiLanguage::Ru => {
+                        format!("{format_label} сохранён в {}", job.path.display())
+                    }
+                };
+                if let Some(backup) = &job.backup {
+                    let note = match self.ui.language {
+                        UiLanguage::En => format!("previous file kept as {}", backup.display()),
+                        UiLanguage::Ru => format!("прежний файл сохранён как {}", backup.display()),
+                    };
+                    msg = format!("{msg}; {note}");
+                }
+                self.set_status(self.i18n().format_exported(format_label));
+                self.push_toast(StatusLevel::Info, msg);
+                self.record_export(job.path, job.settings, job.rows);
+            }
+            Ok(ExportJobResult::Cancelled) => {
+                self.set_status(match self.ui.language {
+                    UiLanguage::En => format!(
+                        "{format_label} export cancelled; {} was not written.",
+                        job.path.display()
+                    ),
+                    UiLanguage::Ru => format!(
+                        "Экспорт {format_label} отменён; файл {} не записан.",
+                        job.path.display()
+                    ),
+                });
+            }
+            Ok(ExportJobResult::Failed(source)) => {
+                self.report_error(&CurcatError::Export {
+                    format: format_label,
+                    source,
+                });
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                self.export.pending_export_job = Some(job);
+                ctx.request_repaint_after(EXPORT_PROGRESS_REPAINT);
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.set_status_error(
+                    self.i18n()
+                        .format_export_failed(format_label, "worker disconnected"),
+                );
+            }
+        }
+    }
+}
+
+/// Move the file an export is about to overwrite to `backup`. An older file at `backup` is
+/// set aside rather than deleted, and its new name returned, so it can come back if the
+/// export does not complete.
+fn move_to_backup(path: &Path, backup: &Path) -> anyhow::Result<Option<PathBuf>> {
+    // Windows will not rename onto an existing file, so an older `.bak` moves first.
+    let displaced = if backup.is_file() {
+        let aside = displaced_backup_path(backup);
+        std::fs::rename(backup, &aside)
+            .with_context(|| format!("cannot replace {}", backup.display()))?;
+        Some(aside)
+    } else {
+        None
+    };
+    if let Err(err) = std::fs::rename(path, backup) {
+        if let Some(aside) = &displaced {
+            let _ = std::fs::rename(aside, backup);
+        }
+        return Err(anyhow::Error::from(err).context(format!(
+            "cannot back up {} as {}",
+            path.display(),
+            backup.display()
+        )));
+    }
+    Ok(displaced)
+}
+
+/// Hidden name beside `backup` that holds the older backup while an export runs.
+fn displaced_backup_path(backup: &Path) -> PathBuf {
+    let name = backup
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    backup.with_file_name(format!(".{name}.curcat-old"))
+}
+
+/// Put the file moved to `backup` back at `path` after an export that did not complete,
+/// replacing whatever the export left there, and return the older backup to `backup`.
+fn restore_backup(path: &Path, backup: &Path, displaced: Option<&Path>) -> anyhow::Result<()> {
+    if path.is_file() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("cannot remove the partial {}", path.display()))?;
+    }
+    std::fs::rename(backup, path).with_context(|| {
+        format!(
+            "cannot restore {} from {}",
+            path.display(),
+            backup.display()
+        )
+    })?;
+    if let Some(aside) = displaced {
+        std::fs::rename(aside, backup).with_context(|| {
+            format!(
+                "cannot restore {} from {}",
+                backup.display(),
+                aside.display()
+            )
+        })?;
+    }
+    Ok(())
+}
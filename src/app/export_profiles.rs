@@ -0,0 +1,168 @@
+//! Named export profiles: applying and saving them from the export section.
+
+use super::CurcatApp;
+use crate::config::ExportProfile;
+use crate::export::{ExportFormat, ExportOptions};
+use crate::fit::MAX_POLY_DEGREE;
+use crate::i18n::UiLanguage;
+
+impl CurcatApp {
+    pub(crate) const fn export_options(&self) -> ExportOptions {
+        ExportOptions {
+            decimals: self.export.decimals,
+            csv_delimiter: self.export.csv_delimiter,
+            significant_figures: self.export.significant_figures,
+        }
+    }
+
+    pub(super) fn export_profile_snapshot(
+        &self,
+        name: String,
+        format: ExportFormat,
+    ) -> ExportProfile {
+        ExportProfile {
+            name,
+            format,
+            precision: self.export.decimals,
+            significant_figures: self.export.significant_figures,
+            delimiter: char::from(self.export.csv_delimiter),
+            time_format: self.export.time_format,
+            kind: self.export.export_kind,
+            interpolation: self.export.interp_algorithm,
+            spline_span: self.export.spline_span,
+            samples: self.export.sample_count,
+            include_distances: self.export.raw_include_distances,
+            include_angles: self.export.raw_include_angles,
+            include_steps: self.export.raw_include_steps,
+            include_flags: self.export.raw_include_flags,
+            include_capture_times: self.export.raw_include_capture_times,
+            include_confidence: self.export.raw_include_confidence,
+            include_cartesian: self.export.polar_export_include_cartesian,
+            fit_model: self.export.fit_model,
+            fit_degree: self.export.fit_degree,
+            include_fit: self.export.include_fit_column,
+            smoothing: self.export.smoothing,
+            smoothing_filter: self.export.smoothing_filter,
+            loess_iterations: self.export.loess_iterations,
+            step_breakpoints: self.export.step_breakpoints,
+            unstack_layers: self.export.unstack_layers,
+            columns: self.export.column_templates.clone(),
+        }
+    }
+
+    pub(crate) fn active_export_profile(&self) -> Option<&ExportProfile> {
+        self.export
+            .active_profile
+            .and_then(|idx| self.config.export.profiles.get(idx))
+    }
+
+    /// Load the settings of profile `idx` into the export section and make it active.
+    pub(crate) fn apply_export_profile(&mut self, idx: usize) {
+        let Some(profile) = self.config.export.profiles.get(idx).cloned() else {
+            return;
+        };
+        self.load_export_settings(&profile);
+        self.export.active_profile = Some(idx);
+        self.export.profile_name_draft = profile.name;
+    }
+
+    /// Copy the settings of `profile` into the export section (name and format excluded).
+    pub(super) fn load_export_settings(&mut self, profile: &ExportProfile) {
+        self.export.decimals = profile.precision.min(ExportOptions::MAX_DECIMALS);
+        self.export.significant_figures = profile
+            .significant_figures
+            .map(|digits| digits.clamp(1, ExportOptions::MAX_DECIMALS));
+        self.export.csv_delimiter = profile.delimiter_byte();
+        self.export.time_format = profile.time_format;
+        self.export.export_kind = profile.kind;
+        self.export.interp_algorithm = profile.interpolation;
+        self.export.spline_span = profile.spline_span.clamp(1, super::SPLINE_SPAN_MAX);
+        self.export.sample_count = profile.samples.clamp(
+            super::SAMPLE_COUNT_MIN,
+            self.config.export.samples_max_sanitized(),
+        );
+        self.export.sample_count_follows_axis = false;
+        self.export.raw_include_distances = profile.include_distances;
+        self.export.raw_include_angles = profile.include_angles;
+        self.export.raw_include_steps = profile.include_steps;
+        self.export.raw_include_flags = profile.include_flags;
+        self.export.raw_include_capture_times = profile.include_capture_times;
+        self.export.raw_include_confidence = profile.include_confidence;
+        self.export.polar_export_include_cartesian = profile.include_cartesian;
+        self.export.fit_model = profile.fit_model;
+        self.export.fit_degree = profile.fit_degree.clamp(1, MAX_POLY_DEGREE);
+        self.export.include_fit_column = profile.include_fit;
+        self.export.smoothing = profile.smoothing.min(super::SMOOTHING_MAX);
+        self.export.smoothing_filter = profile.smoothing_filter;
+        self.export.loess_iterations = profile.loess_iterations.min(super::LOESS_ITERATIONS_MAX);
+        self.export.step_breakpoints = profile.step_breakpoints;
+        self.export.unstack_layers = profile.unstack_layers;
+        self.export.column_templates.clone_from(&profile.columns);
+    }
+
+    /// Store the current settings under `name`; an existing profile with that name is replaced.
+    pub(crate) fn save_export_profile_as(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        let format = self
+            .active_export_profile()
+            .map_or(ExportFormat::Csv, |profile| profile.format);
+        let snapshot = self.export_profile_snapshot(name.to_string(), format);
+        let mut profiles = self.config.export.profiles.clone();
+        let idx = if let Some(idx) = profiles.iter().position(|p| p.name == name) {
+            profiles[idx] = snapshot;
+            idx
+        } else {
+            profiles.push(snapshot);
+            profiles.len() - 1
+        };
+        self.export.active_profile = Some(idx);
+        if self.persist_export_profiles(profiles) {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => format!("Export profile \"{name}\" saved."),
+                UiLanguage::Ru => format!("Профиль экспорта «{name}» сохранён."),
+            });
+        }
+    }
+
+    pub(crate) fn delete_active_export_profile(&mut self) {
+        let Some(idx) = self.export.active_profile.take() else {
+            return;
+        };
+        let mut profiles = self.config.export.profiles.clone();
+        if idx >= profiles.len() {
+            return;
+        }
+        let removed = profiles.remove(idx);
+        self.export.profile_name_draft.clear();
+        if self.persist_export_profiles(profiles) {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => format!("Export profile \"{}\" deleted.", removed.name),
+                UiLanguage::Ru => format!("Профиль экспорта «{}» удалён.", removed.name),
+            });
+        }
+    }
+
+    /// Whether the export section has drifted from the active profile since it was applied or
+    /// saved; only "Save profile" writes the change to curcat.toml.
+    pub(crate) fn active_export_profile_modified(&self) -> bool {
+        self.active_export_profile().is_some_and(|active| {
+            *active != self.export_profile_snapshot(active.name.clone(), active.format)
+        })
+    }
+
+    fn persist_export_profiles(&mut self, profiles: Vec<ExportProfile>) -> bool {
+        match self.config.persist_export_profiles(profiles) {
+            Ok(()) => true,
+            Err(err) => {
+                self.set_status_error(match self.ui.language {
+                    UiLanguage::En => format!("Failed to save export profiles: {err}"),
+                    UiLanguage::Ru => format!("Не удалось сохранить профили экспорта: {err}"),
+                });
+                false
+            }
+        }
+    }
+}
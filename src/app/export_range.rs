@@ -0,0 +1,227 @@
+//! Export range: two x bounds the export is clamped to, edited in the side panel and dragged
+//! over the image, for points picked past the region of interest.
+//!
+//! Interpolation still runs over every picked point, so the rows at the bounds are the curve's
+//! own values rather than the ends of a shorter curve.
+
+use super::sample_grid::x_range;
+use super::{CurcatApp, POINT_HIT_RADIUS, PickMode, PickedPoint};
+use crate::interp::{InterpAlgorithm, XYPoint, interpolate_on_grid};
+use crate::types::{AxisMapping, CoordSystem};
+use egui::{Color32, Pos2, Rect, Stroke, Vec2};
+use std::borrow::Cow;
+
+const RANGE_COLOR: Color32 = Color32::from_rgb(250, 190, 90);
+
+/// A bound drawn across the image: the point where it meets the x axis line and the unit
+/// direction along it, in image pixels.
+struct BoundLine {
+    foot: Pos2,
+    along: Vec2,
+}
+
+impl BoundLine {
+    /// Pixel distance of `pixel` from the line.
+    fn distance(&self, pixel: Pos2) -> f32 {
+        let normal = Vec2::new(self.along.y, -self.along.x);
+        (pixel - self.foot).dot(normal).abs()
+    }
+}
+
+/// Line of constant `x` on `mapping`: points take their x from the projection onto the axis, so
+/// it runs perpendicular to the axis line.
+fn bound_line(mapping: &AxisMapping, x: f64) -> Option<BoundLine> {
+    let axis = mapping.p2 - mapping.p1;
+    if axis.length_sq() <= f32::EPSILON {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let t = mapping.t_of_numeric(x)? as f32;
+    Some(BoundLine {
+        foot: mapping.p1 + axis * t,
+        along: axis.normalized().rot90(),
+    })
+}
+
+impl CurcatApp {
+    /// The export range in ascending order; `None` exports the whole curve.
+    pub(crate) fn export_x_bounds(&self) -> Option<[f64; 2]> {
+        self.export
+            .x_range
+            .map(|[a, b]| if a <= b { [a, b] } else { [b, a] })
+    }
+
+    pub(crate) fn in_export_range(&self, x: f64) -> bool {
+        self.export_x_bounds()
+            .is_none_or(|[lo, hi]| (lo..=hi).contains(&x))
+    }
+
+    /// `[lo, hi]` cut to the export range; `None` when the two do not overlap.
+    pub(crate) fn clip_to_export_range(&self, lo: f64, hi: f64) -> Option<[f64; 2]> {
+        let [lo, hi] = self
+            .export_x_bounds()
+            .map_or([lo, hi], |[from, to]| [lo.max(from), hi.min(to)]);
+        (lo <= hi).then_some([lo, hi])
+    }
+
+    /// The calibrated points of `points` inside the export range, in order.
+    pub(crate) fn points_in_export_range<'a>(
+        &self,
+        points: &'a [PickedPoint],
+    ) -> Cow<'a, [PickedPoint]> {
+        if self.export.x_range.is_none() {
+            return Cow::Borrowed(points);
+        }
+        Cow::Owned(
+            points
+                .iter()
+                .filter(|p| p.x_numeric.is_some_and(|x| self.in_export_range(x)))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Corners of the step-hold curve through sorted `nums` inside the export range, opened and
+    /// closed by the held values at the bounds.
+    pub(super) fn step_breakpoints_in_range(
+        &self,
+        nums: &[XYPoint],
+        breakpoints: Vec<XYPoint>,
+    ) -> Vec<XYPoint> {
+        let Some([lo, hi]) = self.export_x_bounds() else {
+            return breakpoints;
+        };
+        let held = |x: f64| interpolate_on_grid(nums, &[x], InterpAlgorithm::StepHold);
+        let mut rows: Vec<XYPoint> = breakpoints
+            .into_iter()
+            .filter(|p| (lo..=hi).contains(&p.x))
+            .collect();
+        if rows.first().is_none_or(|p| p.x > lo) {
+            rows.splice(0..0, held(lo));
+        }
+        if rows.last().is_none_or(|p| p.x < hi) {
+            rows.extend(held(hi));
+        }
+        rows
+    }
+
+    /// The export range over the x span of the active curve, as a starting point to narrow.
+    pub(crate) fn default_export_range(&self) -> Option<[f64; 2]> {
+        let points = self.curve_points(self.points.curves.active);
+        x_range(&Self::collect_numeric_points_in_order(points))
+    }
+
+    /// X axis the range bounds are dragged along; only cartesian and affine charts have one.
+    fn export_range_axis(&self) -> Option<AxisMapping> {
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => self.cartesian_mappings().0,
+            CoordSystem::Polar | CoordSystem::Smith => None,
+        }
+    }
+
+    /// Follow a drag of an export range bound over the image.
+    ///
+    /// `press` and `pointer` are image pixels. A drag that starts on a bound line moves that
+    /// bound; returns `true` while it does and on the frame it is released, so the gesture does
+    /// not also place points.
+    pub(crate) fn track_export_range_drag(
+        &mut self,
+        response: &egui::Response,
+        press: Option<Pos2>,
+        pointer: Option<Pos2>,
+    ) -> bool {
+        let (Some(range), Some(axis)) = (self.export.x_range, self.export_range_axis()) else {
+            self.interaction.x_range_drag = None;
+            return false;
+        };
+        let reach = POINT_HIT_RADIUS / self.image.zoom;
+        let bound_near = |pixel: Pos2| {
+            range
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, &x)| Some((idx, bound_line(&axis, x)?.distance(pixel))))
+                .filter(|&(_, distance)| distance <= reach)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(idx, _)| idx)
+        };
+        // Shift + drag stays with the points and calibration handles.
+        let shift = response.ctx.input(|i| i.modifiers.shift);
+        if self.calibration.pick_mode == PickMode::None
+            && self.interaction.x_range_drag.is_none()
+            && !shift
+        {
+            if pointer.and_then(bound_near).is_some() && response.hovered() {
+                response
+                    .ctx
+                    .set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+            }
+            if response.drag_started_by(egui::PointerButton::Primary)
+                && self.interaction.zoom_region.is_none()
+                && let Some(start) = press
+            {
+                self.interaction.x_range_drag = bound_near(start);
+            }
+        }
+        let Some(idx) = self.interaction.x_range_drag else {
+            return false;
+        };
+        response
+            .ctx
+            .set_cursor_icon(egui::CursorIcon::ResizeHorizontal);
+        if let Some(x) = pointer.and_then(|pixel| axis.numeric_at(pixel))
+            && let Some(range) = self.export.x_range.as_mut()
+        {
+            range[idx] = x;
+        }
+        if !response.dragged_by(egui::PointerButton::Primary) {
+            self.interaction.x_range_drag = None;
+        }
+        true
+    }
+
+    /// The export range bounds over the image drawn at `rect`, with the parts of the image
+    /// outside them dimmed.
+    pub(crate) fn draw_export_range(&self, painter: &egui::Painter, rect: Rect) {
+        let (Some([lo, hi]), Some(axis)) = (self.export_x_bounds(), self.export_range_axis())
+        else {
+            return;
+        };
+        let zoom = self.image.zoom;
+        let to_screen = |pixel: Pos2| rect.min + pixel.to_vec2() * zoom;
+        let painter = painter.with_clip_rect(painter.clip_rect().intersect(rect));
+        // Far enough to leave the image in any direction.
+        let reach = rect.size().length() / zoom;
+        let direction = axis.p2 - axis.p1;
+        if direction.length_sq() <= f32::EPSILON {
+            return;
+        }
+        // Towards growing x, which on a reversed axis is towards its first calibration point.
+        let growing = axis
+            .t_of_numeric(hi)
+            .zip(axis.t_of_numeric(lo))
+            .is_none_or(|(t_hi, t_lo)| t_hi >= t_lo);
+        let outward = if growing {
+            direction.normalized()
+        } else {
+            -direction.normalized()
+        };
+        let shade = Color32::from_black_alpha(90);
+        let stroke = Stroke::new(1.5_f32, RANGE_COLOR);
+        for (x, away) in [(lo, -outward), (hi, outward)] {
+            let Some(line) = bound_line(&axis, x) else {
+                continue;
+            };
+            let a = line.foot - line.along * reach;
+            let b = line.foot + line.along * reach;
+            let outside = vec![
+                to_screen(a),
+                to_screen(b),
+                to_screen(b + away * reach),
+                to_screen(a + away * reach),
+            ];
+            painter.add(egui::Shape::convex_polygon(outside, shade, Stroke::NONE));
+            painter.line_segment([to_screen(a), to_screen(b)], stroke);
+            painter.circle_filled(to_screen(line.foot), 4.0, RANGE_COLOR);
+        }
+    }
+}
@@ -1,18 +1,112 @@
-use crate::interp::InterpAlgorithm;
+use crate::config::ExportProfile;
+use crate::export::{ExportFormat, ExportProgress, TimeExportFormat};
+use crate::fit::FitModel;
+use crate::interp::{InterpAlgorithm, SampleGrid, SmoothingFilter};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc::Receiver;
 
 pub const SAMPLE_COUNT_MIN: usize = 10;
+/// Widest smoothing-spline span the slider offers, in point spacings.
+pub const SPLINE_SPAN_MAX: usize = 25;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ExportKind {
     Interpolated,
     RawPoints,
+    /// The fitted model sampled evenly over the curve's x range.
+    Fitted,
+    /// Amplitude and phase over frequency of the evenly resampled curve.
+    Spectrum,
+    /// X positions where the evenly resampled curve crosses the crossing level.
+    Crossings,
 }
 
+pub enum ExportJobResult {
+    Finished,
+    Cancelled,
+    Failed(anyhow::Error),
+}
+
+/// Export running on a worker thread; `progress` is shared with the writer.
+pub struct ExportJob {
+    pub(super) rx: Receiver<ExportJobResult>,
+    pub(super) progress: Arc<ExportProgress>,
+    pub(super) format: ExportFormat,
+    pub(super) path: PathBuf,
+    /// Settings the payloads were built with, for the export history.
+    pub(super) settings: ExportProfile,
+    pub(super) rows: usize,
+    /// Where the file being overwritten was moved, if it was backed up.
+    pub(super) backup: Option<PathBuf>,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct ExportState {
     pub(super) sample_count: usize,
+    /// `sample_count` still tracks the X axis' pixel span; cleared once it is set by hand, by
+    /// `Auto` or from a profile.
+    pub(super) sample_count_follows_axis: bool,
+    /// Pixel span of the X axis `sample_count` was last defaulted from.
+    pub(super) followed_axis_pixels: Option<usize>,
+    /// X positions of interpolated and fitted samples.
+    pub(super) sample_grid: SampleGrid,
+    /// X values of [`SampleGrid::List`], as typed.
+    pub(super) sample_xs_text: String,
+    /// Start and stop x of the uniform and log-spaced grids; `None` spans the curve.
+    pub(super) grid_range: Option<[f64; 2]>,
+    /// Step of [`SampleGrid::Uniform`]; `None` is a tenth of the range.
+    pub(super) grid_step: Option<f64>,
+    /// X bounds the export is clamped to, in either order; `None` exports the whole curve.
+    pub(super) x_range: Option<[f64; 2]>,
     pub(super) export_kind: ExportKind,
     pub(super) interp_algorithm: InterpAlgorithm,
+    /// Span of [`InterpAlgorithm::SmoothingSpline`], in point spacings.
+    pub(super) spline_span: usize,
     pub(super) raw_include_distances: bool,
     pub(super) raw_include_angles: bool,
+    /// Add `dx`, `dy`, `step` and `slope` in axis units, in decades on log axes.
+    pub(super) raw_include_steps: bool,
+    pub(super) raw_include_flags: bool,
+    pub(super) raw_include_capture_times: bool,
+    /// Add a `confidence` column with the snap confidence of each point.
+    pub(super) raw_include_confidence: bool,
     pub(super) polar_export_include_cartesian: bool,
+    pub(super) fit_model: FitModel,
+    /// Polynomial degree; ignored by the other models.
+    pub(super) fit_degree: usize,
+    /// Add a `fit` column with the model evaluated at each exported x.
+    pub(super) include_fit_column: bool,
+    /// Names of the config column templates added to the export.
+    pub(super) column_templates: Vec<String>,
+    /// Smoothing radius applied to exported rows, in rows on each side; 0 is off.
+    pub(super) smoothing: usize,
+    pub(super) smoothing_filter: SmoothingFilter,
+    /// Robust reweighting passes of the LOESS filter.
+    pub(super) loess_iterations: usize,
+    /// Export the corners of a step-hold curve instead of even samples.
+    pub(super) step_breakpoints: bool,
+    /// Export each curve less the curve before it, as layers of a stacked chart.
+    pub(super) unstack_layers: bool,
+    /// Y level whose crossings are reported, in each curve's own y units.
+    pub(super) crossing_level: f64,
+    /// Mark the level crossings over the image.
+    pub(super) show_crossings: bool,
+    /// x bounds of the area under the curve; `None` integrates over the whole curve.
+    pub(super) area_bounds: Option<[f64; 2]>,
+    /// Write all curves as one stacked table even where the format could keep them apart.
+    pub(super) long_format: bool,
+    /// Fractional digits written for float values.
+    pub(super) decimals: u8,
+    /// When set, float columns are formatted by significant figures instead of `decimals`.
+    pub(super) significant_figures: Option<u8>,
+    pub(super) csv_delimiter: u8,
+    /// How `DateTime` columns are written.
+    pub(super) time_format: TimeExportFormat,
+    /// Index into `config.export.profiles` that receives settings after each export.
+    pub(super) active_profile: Option<usize>,
+    pub(super) profile_name_draft: String,
+    pub(super) pending_export_job: Option<ExportJob>,
 }
@@ -0,0 +1,51 @@
+//! Project validation rules checked against the rows an export is about to write.
+
+use super::CurcatApp;
+use crate::export::ExportPayload;
+use crate::i18n::UiLanguage;
+use crate::validation::{RuleSeverity, check_rules};
+
+impl CurcatApp {
+    /// Check `curves` against the project's rules before they are exported.
+    ///
+    /// Violations are listed in the validation window. Returns `false` when a blocking rule
+    /// failed and the export must not go ahead.
+    pub(crate) fn validate_export(&mut self, curves: &[(String, ExportPayload)]) -> bool {
+        let rules = &self.project.validation_rules;
+        if rules.is_empty() {
+            self.ui.validation_report = None;
+            return true;
+        }
+        let named: Vec<_> = curves
+            .iter()
+            .map(|(name, payload)| (name.as_str(), payload.points.as_slice()))
+            .collect();
+        let violations = check_rules(rules, &named);
+        if violations.is_empty() {
+            self.ui.validation_report = Some(violations);
+            return true;
+        }
+        let blocked = violations
+            .iter()
+            .any(|v| rules[v.rule].severity == RuleSeverity::Block);
+        let count = violations.len();
+        self.ui.validation_report = Some(violations);
+        self.ui.validation_window_open = true;
+        if blocked {
+            self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Export blocked: {count} rows break validation rules."),
+                UiLanguage::Ru => {
+                    format!("Экспорт остановлен: {count} строк нарушают правила проверки.")
+                }
+            });
+        } else {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => format!("{count} rows break validation rules; exporting anyway."),
+                UiLanguage::Ru => {
+                    format!("{count} строк нарушают правила проверки; экспорт продолжается.")
+                }
+            });
+        }
+        !blocked
+    }
+}
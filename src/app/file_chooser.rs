@@ -0,0 +1,304 @@
+//! File dialogs behind one interface: the built-in browser, the system dialog or the desktop
+//! portal.
+
+use crate::config::{DialogBackend, DialogsConfig};
+use crate::native_dialog;
+use crate::portal::{self, FileRequest, PendingRequest};
+use egui::Context;
+use egui_file_dialog::{DialogState, FileDialog};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// System and portal dialogs are other windows; check for their answer at this pace meanwhile.
+const EXTERNAL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What the user is asked to choose.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogMode {
+    PickFile,
+    PickMultiple,
+    Save { default_name: String },
+}
+
+/// A dialog described independently of the backend that shows it.
+#[derive(Debug, Clone)]
+pub struct DialogSpec {
+    pub title: String,
+    pub mode: DialogMode,
+    /// Filter label and its extensions; the first filter is preselected.
+    pub filters: Vec<(String, Vec<&'static str>)>,
+    pub initial_dir: Option<PathBuf>,
+}
+
+impl DialogSpec {
+    /// A save dialog offering one `*.ext` filter per extension.
+    pub fn save(
+        title: &str,
+        default_name: &str,
+        extensions: &[&'static str],
+        initial_dir: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            title: title.to_string(),
+            mode: DialogMode::Save {
+                default_name: default_name.to_string(),
+            },
+            filters: extensions
+                .iter()
+                .map(|ext| (format!("*.{ext}"), vec![*ext]))
+                .collect(),
+            initial_dir,
+        }
+    }
+
+    fn builtin(&self) -> FileDialog {
+        let mut dialog = FileDialog::new().title(&self.title);
+        let first_label = self.filters.first().map(|(label, _)| label.as_str());
+        if let DialogMode::Save { default_name } = &self.mode {
+            dialog = dialog.default_file_name(default_name);
+            for (label, extensions) in &self.filters {
+                dialog = dialog.add_save_extension(label, extensions[0]);
+            }
+            if let Some(label) = first_label {
+                dialog = dialog.default_save_extension(label);
+            }
+        } else {
+            for (label, extensions) in &self.filters {
+                dialog = dialog.add_file_filter_extensions(label, extensions.clone());
+            }
+            if let Some(label) = first_label {
+                dialog = dialog.default_file_filter(label);
+            }
+        }
+        if let Some(dir) = self.initial_dir.clone() {
+            dialog = dialog.initial_directory(dir);
+        }
+        match self.mode {
+            DialogMode::PickFile => dialog.pick_file(),
+            DialogMode::PickMultiple => dialog.pick_multiple(),
+            DialogMode::Save { .. } => dialog.save_file(),
+        }
+        dialog
+    }
+
+    fn file_request(&self, parent_window: &str) -> FileRequest {
+        FileRequest {
+            title: self.title.clone(),
+            save: matches!(self.mode, DialogMode::Save { .. }),
+            multiple: self.mode == DialogMode::PickMultiple,
+            filters: self
+                .filters
+                .iter()
+                .map(|(label, extensions)| {
+                    let extensions = extensions.iter().map(ToString::to_string).collect();
+                    (label.clone(), extensions)
+                })
+                .collect(),
+            current_name: match &self.mode {
+                DialogMode::Save { default_name } => Some(default_name.clone()),
+                _ => None,
+            },
+            current_folder: self.initial_dir.clone(),
+            parent_window: parent_window.to_string(),
+        }
+    }
+
+    /// Give a saved file without an extension the preselected one, as the built-in dialog does.
+    fn with_save_extension(&self, mut path: PathBuf) -> PathBuf {
+        if matches!(self.mode, DialogMode::Save { .. })
+            && path.extension().is_none()
+            && let Some((_, extensions)) = self.filters.first()
+        {
+            path.set_extension(extensions[0]);
+        }
+        path
+    }
+}
+
+/// Result of checking an open dialog once per frame.
+pub enum DialogPoll<T> {
+    Picked(T),
+    Cancelled,
+    Closed,
+    Open,
+}
+
+/// An open file dialog.
+#[derive(Debug)]
+pub enum FileChooser {
+    Builtin {
+        dialog: Box<FileDialog>,
+        /// Why the system or portal dialog this one replaces failed, until the app has reported it.
+        backend_error: Option<String>,
+    },
+    /// A system dialog; `request` is `None` until [`FileChooser::start_native`] shows it.
+    Native {
+        request: Option<PendingRequest>,
+        spec: DialogSpec,
+    },
+    Portal {
+        request: PendingRequest,
+        spec: DialogSpec,
+    },
+}
+
+impl FileChooser {
+    /// Show `spec` with the backend `config` selects; a portal dialog is made a child of
+    /// `parent_window` (see [`portal::parent_window_id`]).
+    ///
+    /// A system dialog only waits here: it needs the main window itself as its parent, so
+    /// [`Self::start_native`] shows it.
+    pub fn open(spec: DialogSpec, config: &DialogsConfig, parent_window: &str) -> Self {
+        match config.resolved_backend() {
+            DialogBackend::Portal => Self::Portal {
+                request: portal::start(spec.file_request(parent_window)),
+                spec,
+            },
+            DialogBackend::Native => Self::Native {
+                request: None,
+                spec,
+            },
+            DialogBackend::Auto | DialogBackend::Builtin => Self::Builtin {
+                dialog: Box::new(spec.builtin()),
+                backend_error: None,
+            },
+        }
+    }
+
+    /// Show a waiting system dialog over `parent`; any other dialog is left as it is.
+    pub fn start_native(&mut self, parent: &(impl HasWindowHandle + HasDisplayHandle)) {
+        if let Self::Native {
+            request: request @ None,
+            spec,
+        } = self
+        {
+            *request = Some(native_dialog::start(&spec.file_request(""), parent));
+        }
+    }
+
+    /// The system or portal failure this dialog fell back from, once.
+    pub const fn take_backend_error(&mut self) -> Option<String> {
+        match self {
+            Self::Builtin { backend_error, .. } => backend_error.take(),
+            Self::Native { .. } | Self::Portal { .. } => None,
+        }
+    }
+
+    /// Draw the dialog (built-in backend) and report a single picked path.
+    pub fn poll(&mut self, ctx: &Context) -> DialogPoll<PathBuf> {
+        match self.poll_multiple(ctx) {
+            DialogPoll::Picked(paths) => paths
+                .into_iter()
+                .next()
+                .map_or(DialogPoll::Cancelled, DialogPoll::Picked),
+            DialogPoll::Cancelled => DialogPoll::Cancelled,
+            DialogPoll::Closed => DialogPoll::Closed,
+            DialogPoll::Open => DialogPoll::Open,
+        }
+    }
+
+    /// Draw the dialog (built-in backend) and report every picked path.
+    pub fn poll_multiple(&mut self, ctx: &Context) -> DialogPoll<Vec<PathBuf>> {
+        match self {
+            Self::Builtin { dialog, .. } => {
+                dialog.update(ctx);
+                if let Some(paths) = dialog.take_picked_multiple() {
+                    return DialogPoll::Picked(paths);
+                }
+                if let Some(path) = dialog.take_picked() {
+                    return DialogPoll::Picked(vec![path]);
+                }
+                match dialog.state() {
+                    DialogState::Cancelled => DialogPoll::Cancelled,
+                    DialogState::Closed => DialogPoll::Closed,
+                    _ => DialogPoll::Open,
+                }
+            }
+            Self::Native { request: None, .. } => {
+                ctx.request_repaint();
+                DialogPoll::Open
+            }
+            Self::Native {
+                request: Some(request),
+                spec,
+            }
+            | Self::Portal { request, spec } => match request.try_outcome() {
+                None => {
+                    ctx.request_repaint_after(EXTERNAL_POLL_INTERVAL);
+                    DialogPoll::Open
+                }
+                Some(Ok(Some(paths))) => DialogPoll::Picked(
+                    paths
+                        .into_iter()
+                        .map(|path| spec.with_save_extension(path))
+                        .collect(),
+                ),
+                Some(Ok(None)) => DialogPoll::Cancelled,
+                Some(Err(err)) => {
+                    // No portal on this desktop, or no system dialog in this build: show the
+                    // same dialog in-app instead.
+                    *self = Self::Builtin {
+                        dialog: Box::new(spec.builtin()),
+                        backend_error: Some(format!("{err:#}")),
+                    };
+                    self.poll_multiple(ctx)
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portal_requests_carry_the_dialog_spec() {
+        let spec = DialogSpec::save(
+            "Export Arrow",
+            "curve.arrow",
+            &["arrow", "feather"],
+            Some(PathBuf::from("/data/out")),
+        );
+        let request = spec.file_request("x11:2c00004");
+        assert!(request.save && !request.multiple);
+        assert_eq!(request.parent_window, "x11:2c00004");
+        assert_eq!(request.current_name.as_deref(), Some("curve.arrow"));
+        assert_eq!(
+            request.filters,
+            [
+                ("*.arrow".to_string(), vec!["arrow".to_string()]),
+                ("*.feather".to_string(), vec!["feather".to_string()]),
+            ]
+        );
+        assert_eq!(
+            spec.with_save_extension(PathBuf::from("/data/out/run")),
+            PathBuf::from("/data/out/run.arrow")
+        );
+        assert_eq!(
+            spec.with_save_extension(PathBuf::from("/data/out/run.feather")),
+            PathBuf::from("/data/out/run.feather")
+        );
+    }
+
+    #[test]
+    fn system_dialogs_wait_for_the_main_window() {
+        let config = DialogsConfig {
+            backend: DialogBackend::Native,
+            ..DialogsConfig::default()
+        };
+        let spec = DialogSpec::save("Save Project", "chart.curcat", &["curcat"], None);
+        let mut chooser = FileChooser::open(spec, &config, "x11:2c00004");
+        if !native_dialog::available() {
+            assert!(matches!(chooser, FileChooser::Builtin { .. }));
+            return;
+        }
+        assert!(matches!(chooser, FileChooser::Native { request: None, .. }));
+        assert!(matches!(
+            chooser.poll(&Context::default()),
+            DialogPoll::Open
+        ));
+        assert_eq!(chooser.take_backend_error(), None);
+    }
+}
@@ -0,0 +1,362 @@
+//! Deterministic synthetic charts with known ground-truth curves, plus matching projects.
+//!
+//! A fixture renders axes, a light grid and one or more curves of exact thickness into a
+//! `ColorImage`, then writes it as a PNG next to a `.curcat` project whose calibration maps
+//! the plot area onto the chart's value ranges. Accuracy tests open the project like a user
+//! would and compare what snapping, tracing and export produce against the true curves.
+
+use super::harness::Harness;
+use crate::config::ConfigOverrides;
+use crate::image::ImageTransformRecord;
+use crate::ocr;
+use crate::project::{
+    self, AffineCalibrationRecord, AxisCalibrationRecord, CalibrationRecord, CurveRecord,
+    InvertedAxesRecord, PolarCalibrationRecord, ProjectPayload, SmithCalibrationRecord,
+    TimeInputsRecord,
+};
+use crate::types::{AxisUnit, CoordSystem, ScaleKind, YAxis};
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, ColorImage, Pos2, Rect, pos2};
+use std::path::{Path, PathBuf};
+
+const BACKGROUND: Color32 = Color32::WHITE;
+const GRID: Color32 = Color32::from_gray(225);
+const AXIS: Color32 = Color32::BLACK;
+
+/// One curve drawn into a fixture chart.
+#[derive(Clone, Copy)]
+pub struct FixtureCurve {
+    pub color: Color32,
+    /// Stroke width in image pixels, measured across the curve.
+    pub thickness: f32,
+    pub f: fn(f64) -> f64,
+}
+
+/// Layout of a fixture chart: image size, plot area and the value ranges it spans.
+#[derive(Clone)]
+pub struct ChartSpec {
+    pub size: [usize; 2],
+    /// Plot area in image pixels; the axes run along its left and bottom edges.
+    pub plot: Rect,
+    pub x_range: (f64, f64),
+    pub y_range: (f64, f64),
+    /// Grid lines per axis inside the plot area.
+    pub grid_lines: usize,
+    /// Draw outward ticks at the plot edges and grid lines, labelled with their values.
+    pub tick_labels: bool,
+    pub curves: Vec<FixtureCurve>,
+}
+
+impl ChartSpec {
+    /// 640×400 chart of `y = 3 + 2 sin(x)` over `0..=10`, drawn 3 px wide in red.
+    pub fn sine() -> Self {
+        Self {
+            size: [640, 400],
+            plot: Rect::from_min_max(pos2(60.0, 30.0), pos2(610.0, 350.0)),
+            x_range: (0.0, 10.0),
+            y_range: (0.0, 6.0),
+            grid_lines: 5,
+            tick_labels: false,
+            curves: vec![FixtureCurve {
+                color: Color32::from_rgb(220, 40, 40),
+                thickness: 3.0,
+                f: |x| 2.0f64.mul_add(x.sin(), 3.0),
+            }],
+        }
+    }
+
+    /// [`Self::sine`] drawn only 1 px wide.
+    pub fn thin_sine() -> Self {
+        let mut spec = Self::sine();
+        spec.curves[0].thickness = 1.0;
+        spec
+    }
+
+    /// [`Self::sine`] with labelled ticks every 2.5 on x and every 1.5 on y.
+    pub fn labelled_sine() -> Self {
+        let mut spec = Self::sine();
+        spec.grid_lines = 3;
+        spec.tick_labels = true;
+        spec
+    }
+
+    /// [`Self::sine`] with a blue straight line crossing it, to check color selectivity.
+    pub fn sine_and_line() -> Self {
+        let mut spec = Self::sine();
+        spec.curves.push(FixtureCurve {
+            color: Color32::from_rgb(40, 80, 220),
+            thickness: 2.0,
+            f: |x| 0.5f64.mul_add(x, 0.5),
+        });
+        spec
+    }
+}
+
+pub struct ChartFixture {
+    pub spec: ChartSpec,
+    pub image: ColorImage,
+}
+
+impl ChartFixture {
+    pub fn render(spec: ChartSpec) -> Self {
+        let [width, height] = spec.size;
+        let mut fixture = Self {
+            spec,
+            image: ColorImage::new([width, height], vec![BACKGROUND; width * height]),
+        };
+        let mut image = std::mem::take(&mut fixture.image);
+        fixture.draw_frame(&mut image);
+        for curve in &fixture.spec.curves {
+            fixture.draw_curve(&mut image, *curve);
+        }
+        fixture.image = image;
+        fixture
+    }
+
+    /// Continuous image position of a chart value.
+    pub fn pixel_of(&self, x: f64, y: f64) -> Pos2 {
+        let (x0, x1) = self.spec.x_range;
+        let (y0, y1) = self.spec.y_range;
+        let plot = self.spec.plot;
+        #[allow(clippy::cast_possible_truncation)]
+        pos2(
+            (((x - x0) / (x1 - x0)) as f32).mul_add(plot.width(), plot.left()),
+            (((y - y0) / (y1 - y0)) as f32).mul_add(-plot.height(), plot.bottom()),
+        )
+    }
+
+    /// Chart value of a continuous image position.
+    pub fn value_of(&self, pixel: Pos2) -> (f64, f64) {
+        let (x0, x1) = self.spec.x_range;
+        let (y0, y1) = self.spec.y_range;
+        let plot = self.spec.plot;
+        let tx = f64::from((pixel.x - plot.left()) / plot.width());
+        let ty = f64::from((plot.bottom() - pixel.y) / plot.height());
+        ((x1 - x0).mul_add(tx, x0), (y1 - y0).mul_add(ty, y0))
+    }
+
+    /// True `y` of curve `idx` at `x`.
+    pub fn truth(&self, idx: usize, x: f64) -> f64 {
+        (self.spec.curves[idx].f)(x)
+    }
+
+    /// Calibration that maps the plot's corners onto the value ranges.
+    pub fn calibration(&self) -> CalibrationRecord {
+        let (x0, x1) = self.spec.x_range;
+        let (y0, y1) = self.spec.y_range;
+        let axis = |p1: Pos2, p2: Pos2, v1: f64, v2: f64| AxisCalibrationRecord {
+            unit: AxisUnit::Float,
+            scale: ScaleKind::Linear,
+            p1: Some([p1.x, p1.y]),
+            p2: Some([p2.x, p2.y]),
+            v1_text: v1.to_string(),
+            v2_text: v2.to_string(),
+        };
+        CalibrationRecord {
+            coord_system: CoordSystem::Cartesian,
+            x: axis(self.pixel_of(x0, y0), self.pixel_of(x1, y0), x0, x1),
+            y: axis(self.pixel_of(x0, y0), self.pixel_of(x0, y1), y0, y1),
+            secondary_y: AxisCalibrationRecord::default(),
+            inverted_axes: InvertedAxesRecord::default(),
+            polar: PolarCalibrationRecord::default(),
+            affine: AffineCalibrationRecord::default(),
+            smith: SmithCalibrationRecord::default(),
+            time_inputs: TimeInputsRecord::default(),
+            calibration_angle_snap: false,
+            show_calibration_segments: true,
+        }
+    }
+
+    /// Write `chart.png` and a calibrated, point-free `chart.curcat` into `dir`.
+    ///
+    /// Returns the project path.
+    pub fn write_project(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let image_path = dir.join("chart.png");
+        let [width, height] = self.image.size;
+        let rgba = self
+            .image
+            .pixels
+            .iter()
+            .flat_map(Color32::to_array)
+            .collect();
+        let buffer =
+            image::RgbaImage::from_raw(u32::try_from(width)?, u32::try_from(height)?, rgba)
+                .ok_or_else(|| anyhow::anyhow!("fixture pixel buffer has the wrong size"))?;
+        buffer.save(&image_path)?;
+
+        let project_path = dir.join("chart.curcat");
+        let payload = ProjectPayload {
+            absolute_image_path: image_path.clone(),
+            relative_image_path: Some(PathBuf::from("chart.png")),
+            image_crc32: project::compute_image_crc32(&image_path)?,
+            perspective: Vec::new(),
+            transform: ImageTransformRecord::identity(),
+            calibration: self.calibration(),
+            curves: vec![CurveRecord {
+                name: "Curve 1".to_string(),
+                color: None,
+                points: Vec::new(),
+                y_axis: YAxis::Primary,
+                parameter_text: String::new(),
+                locked: false,
+            }],
+            active_curve: 0,
+            zoom: 1.0,
+            pan: [0.0, 0.0],
+            title: Some("Synthetic chart".to_string()),
+            description: None,
+            config_overrides: ConfigOverrides::default(),
+            swatches: Vec::new(),
+            svg_scale_percent: None,
+            axis_titles: Default::default(),
+            secondary_y_title: String::new(),
+            export_history: Vec::new(),
+            validation_rules: Vec::new(),
+            parameter_name: String::new(),
+        };
+        project::save_project(&project_path, &payload)?;
+        Ok(project_path)
+    }
+
+    /// Write the project into `dir` and open it in a fresh harness.
+    pub fn open(&self, dir: &Path) -> anyhow::Result<Harness> {
+        let project = self.write_project(dir)?;
+        Ok(Harness::with_project(&project))
+    }
+
+    /// Grid lines inside the plot area, then the left and bottom axes over them.
+    fn draw_frame(&self, image: &mut ColorImage) {
+        let plot = self.spec.plot;
+        let divisions = safe_usize_to_f32(self.spec.grid_lines + 1);
+        for step in 1..=self.spec.grid_lines {
+            let t = safe_usize_to_f32(step) / divisions;
+            let x = plot.left() + t * plot.width();
+            let y = plot.top() + t * plot.height();
+            fill_rect(
+                image,
+                Rect::from_x_y_ranges(x..=x + 1.0, plot.y_range()),
+                GRID,
+            );
+            fill_rect(
+                image,
+                Rect::from_x_y_ranges(plot.x_range(), y..=y + 1.0),
+                GRID,
+            );
+        }
+        let (left, bottom) = (plot.left(), plot.bottom());
+        fill_rect(
+            image,
+            Rect::from_x_y_ranges(left - 1.0..=left + 1.0, plot.top()..=bottom + 1.0),
+            AXIS,
+        );
+        fill_rect(
+            image,
+            Rect::from_x_y_ranges(left - 1.0..=plot.right(), bottom - 1.0..=bottom + 1.0),
+            AXIS,
+        );
+        if self.spec.tick_labels {
+            self.draw_tick_labels(image);
+        }
+    }
+
+    /// Outward ticks with their values written in the OCR template font, like a plotting
+    /// library would place them: centered below the x ticks, right-aligned left of the y ticks.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn draw_tick_labels(&self, image: &mut ColorImage) {
+        const SCALE: usize = 2;
+        let plot = self.spec.plot;
+        let (x0, x1) = self.spec.x_range;
+        let (y0, y1) = self.spec.y_range;
+        let divisions = self.spec.grid_lines + 1;
+        for step in 0..=divisions {
+            let x_value = (x1 - x0).mul_add(step as f64 / divisions as f64, x0);
+            let x = self.pixel_of(x_value, y0).x;
+            fill_rect(
+                image,
+                Rect::from_x_y_ranges(x - 1.0..=x + 1.0, plot.bottom() + 1.0..=plot.bottom() + 6.0),
+                AXIS,
+            );
+            let text = x_value.to_string();
+            let width = ocr::text_width(&text, SCALE) as f32;
+            let origin = [
+                (x - width * 0.5).round() as usize,
+                plot.bottom() as usize + 10,
+            ];
+            ocr::stamp_text(image, &text, origin, SCALE, AXIS);
+
+            let y_value = (y1 - y0).mul_add(step as f64 / divisions as f64, y0);
+            let y = self.pixel_of(x0, y_value).y;
+            fill_rect(
+                image,
+                Rect::from_x_y_ranges(plot.left() - 7.0..=plot.left() - 1.0, y - 1.0..=y + 1.0),
+                AXIS,
+            );
+            let text = y_value.to_string();
+            let width = ocr::text_width(&text, SCALE) as f32;
+            let origin = [
+                (plot.left() - 11.0 - width).round() as usize,
+                (y - 7.0).round() as usize,
+            ];
+            ocr::stamp_text(image, &text, origin, SCALE, AXIS);
+        }
+    }
+
+    /// Paint every pixel whose center lies within half the thickness of the curve.
+    ///
+    /// The distance uses the local slope, so steep parts keep the same stroke width.
+    fn draw_curve(&self, image: &mut ColorImage, curve: FixtureCurve) {
+        let [width, height] = image.size;
+        let plot = self.spec.plot;
+        let half = curve.thickness * 0.5;
+        let curve_y = |px: f32| {
+            let (x, _) = self.value_of(pos2(px, 0.0));
+            self.pixel_of(x, (curve.f)(x)).y
+        };
+        for col in 0..width {
+            let cx = safe_usize_to_f32(col) + 0.5;
+            if cx < plot.left() || cx > plot.right() {
+                continue;
+            }
+            let y = curve_y(cx);
+            let slope = (curve_y(cx + 0.5) - curve_y(cx - 0.5)).abs();
+            let reach = half * slope.hypot(1.0) + 1.0;
+            for row in 0..height {
+                let cy = safe_usize_to_f32(row) + 0.5;
+                if (cy - y).abs() > reach {
+                    continue;
+                }
+                if (cy - y).abs() / slope.hypot(1.0) <= half {
+                    image.pixels[row * width + col] = curve.color;
+                }
+            }
+        }
+    }
+}
+
+/// Fresh, not yet created directory under the system temp dir.
+pub fn unique_temp_dir(label: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    std::env::temp_dir().join(format!("curcat_fixture_{label}_{nanos}"))
+}
+
+fn fill_rect(image: &mut ColorImage, rect: Rect, color: Color32) {
+    let [width, height] = image.size;
+    for row in 0..height {
+        for col in 0..width {
+            let center = pos2(safe_usize_to_f32(col) + 0.5, safe_usize_to_f32(row) + 0.5);
+            if rect.contains(center) {
+                image.pixels[row * width + col] = color;
+            }
+        }
+    }
+}
+
+mod benchmark;
+mod tests;
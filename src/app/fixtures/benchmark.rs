@@ -0,0 +1,218 @@
+//! Accuracy benchmark: auto-trace every fixture chart with every snap configuration and report
+//! the RMS error against the ground truth, for the traced points and for each interpolation.
+//!
+//! The report test measures rather than asserts, so it is ignored by default; run it with
+//! `cargo test accuracy_benchmark -- --ignored --nocapture` before and after changing
+//! `snap` or `interp` and compare the tables.
+
+use super::{ChartFixture, ChartSpec, unique_temp_dir};
+use crate::app::{AutoTraceDirection, ExportKind, PointInputMode};
+use crate::interp::InterpAlgorithm;
+use crate::snap::SnapFeatureSource;
+use std::fmt::Write as _;
+
+/// Benchmark chart: report label and spec constructor.
+type ChartCase = (&'static str, fn() -> ChartSpec);
+
+const CHARTS: [ChartCase; 3] = [
+    ("sine 3px", ChartSpec::sine),
+    ("sine 1px", ChartSpec::thin_sine),
+    ("sine + line", ChartSpec::sine_and_line),
+];
+
+#[derive(Debug, Clone, Copy)]
+struct SnapConfig {
+    label: &'static str,
+    mode: PointInputMode,
+    feature_source: SnapFeatureSource,
+}
+
+const SNAP_CONFIGS: [SnapConfig; 5] = [
+    SnapConfig {
+        label: "centerline",
+        mode: PointInputMode::CenterlineSnap,
+        feature_source: SnapFeatureSource::LumaGradient,
+    },
+    SnapConfig {
+        label: "contrast/luma",
+        mode: PointInputMode::ContrastSnap,
+        feature_source: SnapFeatureSource::LumaGradient,
+    },
+    SnapConfig {
+        label: "contrast/color",
+        mode: PointInputMode::ContrastSnap,
+        feature_source: SnapFeatureSource::ColorMatch,
+    },
+    SnapConfig {
+        label: "contrast/hybrid",
+        mode: PointInputMode::ContrastSnap,
+        feature_source: SnapFeatureSource::Hybrid,
+    },
+    SnapConfig {
+        label: "ridge",
+        mode: PointInputMode::Scorer(0),
+        feature_source: SnapFeatureSource::LumaGradient,
+    },
+];
+
+/// Result of tracing one chart with one snap configuration.
+#[derive(Debug, Clone)]
+struct BenchRow {
+    chart: &'static str,
+    snap: &'static str,
+    points: usize,
+    /// Share of the chart's x range between the first and last traced point.
+    coverage: f64,
+    trace_rms: Option<f64>,
+    /// Export RMS per [`InterpAlgorithm::ALL`] entry.
+    export_rms: [Option<f64>; InterpAlgorithm::ALL.len()],
+}
+
+fn rms(errors: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = errors.fold((0.0, 0_u32), |(sum, count), err| {
+        (err.mul_add(err, sum), count + 1)
+    });
+    (count > 0).then(|| (sum / f64::from(count)).sqrt())
+}
+
+fn run_benchmark() -> Vec<BenchRow> {
+    let mut rows = Vec::new();
+    for (chart, spec) in CHARTS {
+        let fixture = ChartFixture::render(spec());
+        let dir = unique_temp_dir("benchmark");
+        let mut harness = fixture.open(&dir).expect("open fixture project");
+        let app = &mut harness.app;
+        app.interaction.auto_trace_cfg.direction = AutoTraceDirection::Both;
+        app.snap.snap_target_color = fixture.spec.curves[0].color;
+        let (x0, x1) = fixture.spec.x_range;
+        let mid = (x0 + x1) * 0.5;
+        let start = fixture.pixel_of(mid, fixture.truth(0, mid));
+
+        for config in SNAP_CONFIGS {
+            let app = &mut harness.app;
+            app.clear_all_points();
+            app.snap.point_input_mode = config.mode;
+            app.snap.snap_feature_source = config.feature_source;
+            app.mark_snap_maps_dirty();
+            harness.wait_for_snap_maps();
+            let app = &mut harness.app;
+            app.auto_trace_from(start);
+
+            let values: Vec<(f64, f64)> = app
+                .points
+                .points
+                .iter()
+                .map(|p| fixture.value_of(p.pixel))
+                .collect();
+            let (lo, hi) = values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(x, _)| {
+                    (lo.min(x), hi.max(x))
+                });
+            let coverage = if values.is_empty() {
+                0.0
+            } else {
+                (hi - lo) / (x1 - x0)
+            };
+            let trace_rms = rms(values.iter().map(|&(x, y)| y - fixture.truth(0, x)));
+
+            let mut export_rms = [None; InterpAlgorithm::ALL.len()];
+            app.export.export_kind = ExportKind::Interpolated;
+            for (slot, algorithm) in export_rms.iter_mut().zip(InterpAlgorithm::ALL) {
+                app.export.interp_algorithm = algorithm;
+                *slot = app.build_export_curves().ok().and_then(|curves| {
+                    rms(curves[0]
+                        .1
+                        .points
+                        .iter()
+                        .map(|p| p.y - fixture.truth(0, p.x)))
+                });
+            }
+
+            rows.push(BenchRow {
+                chart,
+                snap: config.label,
+                points: values.len(),
+                coverage,
+                trace_rms,
+                export_rms,
+            });
+        }
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    rows
+}
+
+fn format_report(rows: &[BenchRow]) -> String {
+    let fmt_rms = |value: Option<f64>| value.map_or_else(|| "—".to_string(), |v| format!("{v:.4}"));
+    let mut out = String::from("| chart | snap | points | coverage | trace RMS |");
+    for algorithm in InterpAlgorithm::ALL {
+        let _ = write!(out, " {algorithm:?} RMS |");
+    }
+    out.push_str("\n|---|---|---:|---:|---:|");
+    out.push_str(&"---:|".repeat(InterpAlgorithm::ALL.len()));
+    for row in rows {
+        let _ = write!(
+            out,
+            "\n| {} | {} | {} | {:.1}% | {} |",
+            row.chart,
+            row.snap,
+            row.points,
+            row.coverage * 100.0,
+            fmt_rms(row.trace_rms)
+        );
+        for value in row.export_rms {
+            let _ = write!(out, " {} |", fmt_rms(value));
+        }
+    }
+    out
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rms_of_known_errors() {
+        assert_eq!(rms(std::iter::empty()), None);
+        let value = rms([3.0, -4.0].into_iter()).expect("rms");
+        assert!((value - 12.5_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn report_has_one_line_per_configuration() {
+        let rows = vec![BenchRow {
+            chart: "sine 3px",
+            snap: "centerline",
+            points: 12,
+            coverage: 0.985,
+            trace_rms: Some(0.012_34),
+            export_rms: [
+                Some(0.02),
+                None,
+                Some(0.015),
+                Some(0.011),
+                Some(0.012),
+                Some(0.013),
+            ],
+        }];
+        let report = format_report(&rows);
+        let lines: Vec<_> = report.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "| chart | snap | points | coverage | trace RMS | Linear RMS | StepHold RMS | NaturalCubic RMS | Pchip RMS | Akima RMS | SmoothingSpline RMS |"
+        );
+        assert_eq!(
+            lines[2],
+            "| sine 3px | centerline | 12 | 98.5% | 0.0123 | 0.0200 | — | 0.0150 | 0.0110 | 0.0120 | 0.0130 |"
+        );
+    }
+
+    #[test]
+    #[ignore = "prints the accuracy report; run with --ignored --nocapture"]
+    fn accuracy_benchmark() {
+        let rows = run_benchmark();
+        assert_eq!(rows.len(), CHARTS.len() * SNAP_CONFIGS.len());
+        println!("{}", format_report(&rows));
+    }
+}
@@ -0,0 +1,173 @@
+use super::*;
+use crate::app::{AutoTraceDirection, ExportKind, PointInputMode};
+use egui::vec2;
+
+/// Open the fixture's project in a harness and snap to curve `idx`.
+fn open_fixture(label: &str, fixture: &ChartFixture, idx: usize) -> (Harness, PathBuf) {
+    let dir = unique_temp_dir(label);
+    let mut harness = fixture.open(&dir).expect("open fixture project");
+    harness.app.snap.point_input_mode = PointInputMode::CenterlineSnap;
+    harness.app.snap.snap_target_color = fixture.spec.curves[idx].color;
+    harness.app.mark_snap_maps_dirty();
+    harness.wait_for_snap_maps();
+    (harness, dir)
+}
+
+/// Largest |y - f(x)| over `pixels`, in chart units.
+fn max_error(fixture: &ChartFixture, idx: usize, pixels: impl Iterator<Item = Pos2>) -> f64 {
+    pixels
+        .map(|pixel| {
+            let (x, y) = fixture.value_of(pixel);
+            (y - fixture.truth(idx, x)).abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn fixture_rendering_is_deterministic_and_follows_the_curve() {
+    let fixture = ChartFixture::render(ChartSpec::sine_and_line());
+    let again = ChartFixture::render(ChartSpec::sine_and_line());
+    assert_eq!(fixture.image.pixels, again.image.pixels);
+
+    for step in 1..20 {
+        let x = f64::from(step) * 0.5;
+        let on_curve = fixture.pixel_of(x, fixture.truth(0, x));
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (col, row) = (on_curve.x as usize, on_curve.y as usize);
+        let color = fixture.image.pixels[row * fixture.image.size[0] + col];
+        assert!(
+            [fixture.spec.curves[0].color, fixture.spec.curves[1].color].contains(&color),
+            "no curve pixel at x = {x}"
+        );
+    }
+    let (x, y) = fixture.value_of(fixture.pixel_of(2.5, 4.0));
+    assert!((x - 2.5).abs() < 1e-4 && (y - 4.0).abs() < 1e-4);
+}
+
+#[test]
+fn generated_project_opens_with_ground_truth_calibration() {
+    let fixture = ChartFixture::render(ChartSpec::sine());
+    let (harness, dir) = open_fixture("calibration", &fixture, 0);
+    let x_mapping = harness.app.calibration.cal_x.mapping().expect("x mapping");
+    let y_mapping = harness.app.calibration.cal_y.mapping().expect("y mapping");
+    for (x, y) in [(0.0, 0.0), (2.5, 1.5), (10.0, 6.0)] {
+        let pixel = fixture.pixel_of(x, y);
+        assert!((x_mapping.numeric_at(pixel).expect("x") - x).abs() < 1e-4);
+        assert!((y_mapping.numeric_at(pixel).expect("y") - y).abs() < 1e-4);
+    }
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn centerline_snap_lands_on_the_ground_truth_curve() {
+    let fixture = ChartFixture::render(ChartSpec::sine_and_line());
+    let (mut harness, dir) = open_fixture("snap", &fixture, 0);
+    let snapped: Vec<Pos2> = (1..20)
+        .map(|step| {
+            let x = f64::from(step) * 0.5;
+            let hint = fixture.pixel_of(x, fixture.truth(0, x)) + vec2(0.0, 5.0);
+            harness
+                .app
+                .compute_snap_candidate(hint)
+                .unwrap_or_else(|| panic!("no snap candidate near x = {x}"))
+        })
+        .collect();
+    let err = max_error(&fixture, 0, snapped.into_iter());
+    assert!(err < 0.05, "snap error {err} exceeds the regression bound");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn auto_trace_covers_the_curve_and_exports_ground_truth_values() {
+    let fixture = ChartFixture::render(ChartSpec::sine());
+    let (mut harness, dir) = open_fixture("trace", &fixture, 0);
+    harness.app.interaction.auto_trace_cfg.direction = AutoTraceDirection::Both;
+    harness
+        .app
+        .auto_trace_from(fixture.pixel_of(5.0, fixture.truth(0, 5.0)));
+    let traced: Vec<Pos2> = harness.app.points.points.iter().map(|p| p.pixel).collect();
+    assert!(
+        traced.len() > 20,
+        "trace found only {} points",
+        traced.len()
+    );
+    let (first, _) = fixture.value_of(traced[0]);
+    let (last, _) = fixture.value_of(traced[traced.len() - 1]);
+    let (lo, hi) = (first.min(last), first.max(last));
+    assert!(lo < 0.5 && hi > 9.5, "trace covered only {lo}..{hi}");
+    let err = max_error(&fixture, 0, traced.into_iter());
+    assert!(err < 0.05, "trace error {err} exceeds the regression bound");
+
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let samples = &curves[0].1.points;
+    assert!(!samples.is_empty());
+    let export_err = samples
+        .iter()
+        .map(|p| (p.y - fixture.truth(0, p.x)).abs())
+        .fold(0.0, f64::max);
+    assert!(
+        export_err < 0.05,
+        "export error {export_err} exceeds the regression bound"
+    );
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn grid_suppression_keeps_contrast_snap_off_dark_grid_lines() {
+    let mut fixture = ChartFixture::render(ChartSpec::sine());
+    for pixel in &mut fixture.image.pixels {
+        if *pixel == GRID {
+            *pixel = Color32::from_gray(90);
+        }
+    }
+    let (mut harness, dir) = open_fixture("grid", &fixture, 0);
+    harness.app.snap.point_input_mode = PointInputMode::ContrastSnap;
+    let plot = fixture.spec.plot;
+    let grid_rows: Vec<f32> = (1..=fixture.spec.grid_lines)
+        .map(|step| (safe_usize_to_f32(step) / 6.0).mul_add(plot.height(), plot.top()) + 0.5)
+        .collect();
+    // Click on a grid line wherever the curve passes a few pixels from it.
+    let hints: Vec<Pos2> = (1..200)
+        .filter_map(|step| {
+            let x = f64::from(step) * 0.05;
+            let on_curve = fixture.pixel_of(x, fixture.truth(0, x));
+            grid_rows
+                .iter()
+                .find(|row| (3.0..=8.0).contains(&(**row - on_curve.y).abs()))
+                .map(|row| pos2(on_curve.x, *row))
+        })
+        .collect();
+    assert!(
+        hints.len() > 10,
+        "only {} hints near grid lines",
+        hints.len()
+    );
+
+    // Contrast snap lands on a stroke edge, so count snaps with no curve ink next to them.
+    let curve = fixture.spec.curves[0].color;
+    let [width, height] = fixture.image.size;
+    let near_curve = |pixel: Pos2| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (col, row) = (pixel.x as usize, pixel.y as usize);
+        (row.saturating_sub(1)..(row + 2).min(height)).any(|y| {
+            (col.saturating_sub(1)..(col + 2).min(width))
+                .any(|x| fixture.image.pixels[y * width + x] == curve)
+        })
+    };
+    let off_curve = |harness: &mut Harness| {
+        hints
+            .iter()
+            .filter_map(|hint| harness.app.compute_snap_candidate(*hint))
+            .filter(|snapped| !near_curve(*snapped))
+            .count()
+    };
+    let unsuppressed = off_curve(&mut harness);
+    harness.app.snap.suppress_grid = true;
+    harness.app.mark_snap_maps_dirty();
+    harness.wait_for_snap_maps();
+    let suppressed = off_curve(&mut harness);
+    assert!(unsuppressed > 0, "the dark grid no longer attracts snaps");
+    assert_eq!(suppressed, 0, "{suppressed} snaps still land off the curve");
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,281 @@
+//! Headless harness that drives full `CurcatApp` frames with scripted input.
+//!
+//! Widgets are found through the AccessKit tree egui emits each frame (by label), and image
+//! clicks are aimed at image pixels through the rect the central panel drew the image in.
+
+use super::CurcatApp;
+use crate::util::safe_usize_to_f32;
+use egui::accesskit::{Action, ActionRequest, NodeId, Role, TreeId};
+use egui::{Event, Modifiers, PointerButton, Pos2, RawInput, Rect, vec2};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const SCREEN_SIZE: [f32; 2] = [1400.0, 900.0];
+const FRAME_SECONDS: f64 = 1.0 / 60.0;
+/// Upper bound for waiting on background work such as image decoding.
+const BACKGROUND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Widget found in the last frame's accessibility tree.
+#[derive(Debug, Clone)]
+struct WidgetNode {
+    id: NodeId,
+    role: Role,
+    label: String,
+}
+
+pub struct Harness {
+    pub app: CurcatApp,
+    ctx: egui::Context,
+    time: f64,
+    events: Vec<Event>,
+    modifiers: Modifiers,
+    nodes: Vec<WidgetNode>,
+    /// Delay until the frame the last one asked for; `Duration::MAX` when it asked for none.
+    repaint_delay: Duration,
+}
+
+impl Harness {
+    /// Fresh app with default settings and one frame already run.
+    pub fn new() -> Self {
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        egui_extras::install_image_loaders(&ctx);
+        let mut harness = Self {
+            app: CurcatApp::default(),
+            ctx,
+            time: 0.0,
+            events: Vec::new(),
+            modifiers: Modifiers::NONE,
+            nodes: Vec::new(),
+            repaint_delay: Duration::MAX,
+        };
+        harness.run();
+        harness
+    }
+
+    /// Fresh app showing a solid white image of the given size, fitted to the viewport.
+    pub fn with_blank_image(width: usize, height: usize) -> Self {
+        let mut harness = Self::new();
+        harness.app.start_loading_image_from_clipboard(
+            width,
+            height,
+            vec![255; width * height * 4],
+        );
+        harness.run_until(|app| app.image.image.is_some());
+        harness.settle();
+        harness
+    }
+
+    /// Fresh app that opened `project` and finished loading its image.
+    pub fn with_project(project: &Path) -> Self {
+        let mut harness = Self::new();
+        harness.app.load_project_from(project.to_path_buf());
+        harness.run_until(|app| {
+            app.image.image.is_some() && app.project.pending_project_apply.is_none()
+        });
+        harness.settle();
+        harness
+    }
+
+    /// Block until the snap maps for the current target color are built.
+    pub fn wait_for_snap_maps(&mut self) {
+        let started = Instant::now();
+        while self.app.snap.snap_maps.is_none() {
+            assert!(
+                started.elapsed() < BACKGROUND_TIMEOUT,
+                "harness timed out waiting for snap maps"
+            );
+            self.app.ensure_snap_maps();
+            std::thread::sleep(Duration::from_millis(2));
+        }
+    }
+
+    /// Run one frame with the queued input.
+    pub fn run(&mut self) {
+        self.time += FRAME_SECONDS;
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, SCREEN_SIZE.into())),
+            time: Some(self.time),
+            predicted_dt: 1.0 / 60.0,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            ..RawInput::default()
+        };
+        let app = &mut self.app;
+        let output = self.ctx.run_ui(input, |ui| app.ui_frame(ui));
+        self.repaint_delay = output
+            .viewport_output
+            .get(&egui::ViewportId::ROOT)
+            .map_or(Duration::MAX, |viewport| viewport.repaint_delay);
+        if let Some(update) = output.platform_output.accesskit_update {
+            self.nodes = update
+                .nodes
+                .iter()
+                .filter_map(|(id, node)| {
+                    Some(WidgetNode {
+                        id: *id,
+                        role: node.role(),
+                        label: node.label()?.to_string(),
+                    })
+                })
+                .collect();
+        }
+    }
+
+    pub fn run_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.run();
+        }
+    }
+
+    /// Run frames until `done` holds; panics after [`BACKGROUND_TIMEOUT`].
+    pub fn run_until(&mut self, done: impl Fn(&CurcatApp) -> bool) {
+        let started = Instant::now();
+        while !done(&self.app) {
+            assert!(
+                started.elapsed() < BACKGROUND_TIMEOUT,
+                "harness timed out waiting for the app"
+            );
+            self.run();
+            std::thread::sleep(Duration::from_millis(2));
+        }
+    }
+
+    /// Run frames until zoom animations and layout stop moving the image.
+    pub fn settle(&mut self) {
+        let mut previous = None;
+        for _ in 0..240 {
+            self.run();
+            let rect = self.app.image.last_image_rect;
+            if rect.is_some() && rect == previous {
+                return;
+            }
+            previous = rect;
+        }
+    }
+
+    /// Whether the last frame had a `role` widget labelled exactly `label`.
+    pub fn has_widget(&self, role: Role, label: &str) -> bool {
+        self.find(role, label).is_some()
+    }
+
+    fn find(&self, role: Role, label: &str) -> Option<&WidgetNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.role == role && node.label == label)
+    }
+
+    /// Activate a widget the way a screen reader would, then let the app react.
+    pub fn click(&mut self, role: Role, label: &str) {
+        let Some(node) = self.find(role, label) else {
+            let labels: Vec<_> = self.nodes.iter().map(|n| n.label.as_str()).collect();
+            panic!("no {role:?} labelled {label:?}; labels: {labels:?}");
+        };
+        let target_node = node.id;
+        self.events
+            .push(Event::AccessKitActionRequest(ActionRequest {
+                action: Action::Click,
+                target_tree: TreeId::ROOT,
+                target_node,
+                data: None,
+            }));
+        self.run_frames(2);
+    }
+
+    /// Press and release the primary button at a screen position.
+    pub fn click_at(&mut self, pos: Pos2) {
+        self.hover(pos);
+        self.push_button(pos, PointerButton::Primary, true);
+        self.run();
+        self.push_button(pos, PointerButton::Primary, false);
+        self.run_frames(2);
+    }
+
+    /// Drag the primary button from `from` to `to` in a few moves with `modifiers` held.
+    pub fn drag(&mut self, from: Pos2, to: Pos2, modifiers: Modifiers) {
+        self.drag_through(&[from, to], modifiers);
+    }
+
+    /// Drag with the primary button along the corners of `path`.
+    pub fn drag_through(&mut self, path: &[Pos2], modifiers: Modifiers) {
+        let (Some(&from), Some(&to)) = (path.first(), path.last()) else {
+            return;
+        };
+        self.modifiers = modifiers;
+        self.hover(from);
+        self.push_button(from, PointerButton::Primary, true);
+        self.run();
+        for leg in path.windows(2) {
+            for step in 1..=4 {
+                self.events.push(Event::PointerMoved(
+                    leg[0].lerp(leg[1], 0.25 * safe_usize_to_f32(step)),
+                ));
+                self.run();
+            }
+        }
+        self.push_button(to, PointerButton::Primary, false);
+        self.run();
+        self.modifiers = Modifiers::NONE;
+        self.run_frames(2);
+    }
+
+    fn hover(&mut self, pos: Pos2) {
+        self.events.push(Event::PointerMoved(pos));
+        self.run();
+    }
+
+    /// Screen position of an image pixel in the last frame.
+    pub fn image_pixel_to_screen(&self, pixel: Pos2) -> Pos2 {
+        let rect = self
+            .app
+            .image
+            .last_image_rect
+            .expect("no image drawn in the last frame");
+        let image = self.app.image.image.as_ref().expect("no image loaded");
+        let scale = vec2(
+            rect.width() / safe_usize_to_f32(image.size[0]),
+            rect.height() / safe_usize_to_f32(image.size[1]),
+        );
+        rect.min + pixel.to_vec2() * scale
+    }
+
+    /// Click the center of an image pixel.
+    pub fn click_image_pixel(&mut self, pixel: Pos2) {
+        let pos = self.image_pixel_to_screen(pixel + vec2(0.5, 0.5));
+        self.click_at(pos);
+    }
+
+    /// Type text into the focused widget.
+    pub fn type_text(&mut self, text: &str) {
+        self.events.push(Event::Text(text.to_string()));
+        self.run_frames(2);
+    }
+
+    /// Press and release `key` with `modifiers` held.
+    pub fn press_key(&mut self, key: egui::Key, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+        for pressed in [true, false] {
+            self.events.push(Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers,
+            });
+        }
+        self.run();
+        self.modifiers = Modifiers::NONE;
+        self.run();
+    }
+
+    fn push_button(&mut self, pos: Pos2, button: PointerButton, pressed: bool) {
+        self.events.push(Event::PointerButton {
+            pos,
+            button,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+}
+
+mod tests;
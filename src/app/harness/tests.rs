@@ -0,0 +1,2000 @@
+use super::*;
+use crate::app::{
+    ChartTextField, DragTarget, ErrorBarMode, ExportKind, PickMode, ScaleSuggestion, StatusLevel,
+};
+use crate::config::{ColumnTemplate, ExportBackup};
+use crate::export::{
+    ColumnMetric, ExportExtraColumn, ExportFormat, SurfaceFormat, TimeExportFormat,
+};
+use crate::fit::FitModel;
+use crate::i18n::TextKey;
+use crate::interp::{InterpAlgorithm, SampleGrid, SmoothingFilter};
+use crate::keymap::{ShortcutAction, parse_shortcut};
+use crate::types::{AxisUnit, ScaleKind, YAxis};
+use crate::validation::RuleSeverity;
+use egui::{Color32, Key, Modifiers, Pos2, pos2, vec2};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "expected {expected}, got {actual}"
+    );
+}
+
+/// Screen round trips at a fractional fit zoom cost a few thousandths of a pixel.
+fn assert_pixel(actual: Pos2, expected: Pos2) {
+    assert!(
+        actual.distance(expected) < 1e-2,
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+/// Pick one calibration point through its side-panel button and type its value.
+fn calibrate_point(harness: &mut Harness, name: &str, pixel: Pos2, value: &str) {
+    harness.click(Role::Button, &format!("Pick {name}"));
+    harness.click_image_pixel(pixel);
+    // Picking moves the keyboard focus into the point's value field.
+    harness.type_text(value);
+}
+
+/// 200×100 image with x = 0..10 over pixels 20..180 and y = 0..8 over pixels 90..10.
+fn calibrated_harness() -> Harness {
+    let mut harness = Harness::with_blank_image(200, 100);
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "10");
+    calibrate_point(&mut harness, "Y1", pos2(5.0, 90.0), "0");
+    calibrate_point(&mut harness, "Y2", pos2(5.0, 10.0), "8");
+    harness
+}
+
+#[test]
+fn calibration_is_entered_through_pick_buttons_and_value_fields() {
+    let mut harness = Harness::with_blank_image(200, 100);
+    assert!(harness.has_widget(Role::Button, "Pick X1"));
+
+    harness.click(Role::Button, "Pick X1");
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::X1);
+    harness.click_image_pixel(pos2(20.0, 95.0));
+    let p1 = harness.app.calibration.cal_x.p1.expect("X1 picked");
+    assert_pixel(p1, pos2(20.5, 95.5));
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+    harness.type_text("0");
+    assert_eq!(harness.app.calibration.cal_x.v1_text, "0");
+    assert!(harness.app.calibration.cal_x.mapping().is_none());
+
+    let mut harness = calibrated_harness();
+    assert_eq!(harness.app.calibration.cal_x.v2_text, "10");
+    assert_eq!(harness.app.calibration.cal_y.v2_text, "8");
+    assert!(harness.app.calibration_ready());
+    // Escape leaves a pick mode without touching the calibration.
+    harness.click(Role::Button, "Pick Y2");
+    harness.press_key(Key::Escape, Modifiers::NONE);
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+    assert!(harness.app.calibration_ready());
+}
+
+#[test]
+fn clicks_place_points_at_calibrated_values_at_any_zoom() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(100.0, 50.0));
+
+    // Ctrl+R resets the view to 100%, so the same pixel lands elsewhere on screen.
+    let fitted_zoom = harness.app.image.zoom;
+    harness.press_key(Key::R, Modifiers::COMMAND);
+    harness.settle();
+    assert!((harness.app.image.zoom - 1.0).abs() < 1e-3);
+    assert!(
+        (fitted_zoom - 1.0).abs() > 1e-3,
+        "fit should have zoomed the image"
+    );
+    harness.click_image_pixel(pos2(60.0, 30.0));
+
+    let points = &harness.app.points.points;
+    assert_eq!(points.len(), 2);
+    assert_pixel(points[0].pixel, pos2(100.5, 50.5));
+    assert_pixel(points[1].pixel, pos2(60.5, 30.5));
+    let x_mapping = harness.app.calibration.cal_x.mapping().expect("x mapping");
+    let y_mapping = harness.app.calibration.cal_y.mapping().expect("y mapping");
+    let values: Vec<_> = points
+        .iter()
+        .map(|p| {
+            (
+                x_mapping.numeric_at(p.pixel).expect("x value"),
+                y_mapping.numeric_at(p.pixel).expect("y value"),
+            )
+        })
+        .collect();
+    assert_close(values[0].0, 5.0);
+    assert_close(values[0].1, 4.0);
+    assert_close(values[1].0, 2.5);
+    assert_close(values[1].1, 6.0);
+}
+
+#[test]
+fn placed_points_export_as_calibrated_csv_rows() {
+    let mut harness = calibrated_harness();
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.export_kind = ExportKind::RawPoints;
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert_eq!(curves.len(), 1);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_harness_{nanos}.csv"));
+    ExportFormat::Csv
+        .export_curves(&path, curves)
+        .expect("write csv");
+
+    let text = std::fs::read_to_string(&path).expect("read csv");
+    let _ = std::fs::remove_file(&path);
+    let rows: Vec<Vec<f64>> = text
+        .lines()
+        .skip(1)
+        .map(|line| {
+            line.split(',')
+                .take(2)
+                .map(|cell| cell.parse().expect("numeric cell"))
+                .collect()
+        })
+        .collect();
+    assert_eq!(rows.len(), 3);
+    for (row, expected) in rows.iter().zip([(2.5, 6.0), (5.0, 4.0), (7.5, 2.0)]) {
+        assert_close(row[0], expected.0);
+        assert_close(row[1], expected.1);
+    }
+}
+
+#[test]
+fn fitted_export_samples_the_model_with_a_fit_column_alongside() {
+    let mut harness = calibrated_harness();
+    // Points on the line y = 7.5 - 0.8·x in calibrated units.
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.fit_model = FitModel::Polynomial;
+    harness.app.export.fit_degree = 1;
+    harness.app.export.sample_count = 11;
+    harness.app.export.export_kind = ExportKind::Fitted;
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let samples = &curves[0].1.points;
+    assert_eq!(samples.len(), 11);
+    assert_close(samples[0].x, 2.5);
+    assert_close(samples[10].x, 7.5);
+    assert_close(samples[5].y, 4.0);
+
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    harness.app.export.include_fit_column = true;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let fit = curves[0]
+        .1
+        .extra_columns
+        .iter()
+        .find(|c| c.header == "fit")
+        .expect("fit column");
+    assert_eq!(fit.values.len(), 3);
+    assert_close(fit.values[1].expect("fit value"), 4.0);
+}
+
+#[test]
+fn sample_count_defaults_to_the_pixel_span_of_the_x_axis() {
+    let mut harness = Harness::with_blank_image(200, 100);
+    assert_eq!(harness.app.export.sample_count, 200);
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "10");
+    harness.run();
+    assert_eq!(harness.app.export.sample_count, 160);
+
+    // Recalibrating keeps it in step, until a profile or the slider picks a count.
+    calibrate_point(&mut harness, "X2", pos2(120.0, 95.0), "10");
+    harness.run();
+    assert_eq!(harness.app.export.sample_count, 100);
+    harness.app.apply_export_profile(0);
+    let chosen = harness.app.export.sample_count;
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "10");
+    harness.run();
+    assert_eq!(harness.app.export.sample_count, chosen);
+}
+
+#[test]
+fn a_new_image_follows_its_own_axis_after_a_chosen_sample_count() {
+    let mut harness = calibrated_harness();
+    harness.run();
+    assert_eq!(harness.app.export.sample_count, 160);
+    for x in [40.0, 100.0, 160.0] {
+        harness.click_image_pixel(pos2(x, 50.0));
+    }
+    harness.app.auto_tune_sample_count();
+    assert!(!harness.app.export.sample_count_follows_axis);
+
+    harness
+        .app
+        .start_loading_image_from_clipboard(300, 100, vec![255; 300 * 100 * 4]);
+    harness.run();
+    harness.click(Role::Button, "Discard");
+    harness.run_until(|app| {
+        app.image
+            .image
+            .as_ref()
+            .is_some_and(|img| img.size[0] == 300)
+    });
+    harness.settle();
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(260.0, 95.0), "10");
+    harness.run();
+    assert_eq!(harness.app.export.sample_count, 240);
+}
+
+#[test]
+fn interpolated_and_fitted_exports_sample_a_user_x_grid() {
+    let mut harness = calibrated_harness();
+    // Points on the line y = 8 - 0.8·x in calibrated units, x from 2.5 to 7.5.
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    harness.app.export.sample_grid = SampleGrid::List;
+    harness.app.export.sample_xs_text = "6, 3 20; 4.5".to_string();
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let xs: Vec<f64> = curves[0].1.points.iter().map(|p| p.x).collect();
+    assert_eq!(
+        xs,
+        vec![3.0, 4.5, 6.0],
+        "sorted, without the x past the curve"
+    );
+    assert_close(curves[0].1.points[1].y, 4.4);
+
+    harness.app.export.sample_grid = SampleGrid::Uniform;
+    harness.app.export.grid_range = Some([3.0, 7.0]);
+    harness.app.export.grid_step = Some(2.0);
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let xs: Vec<f64> = curves[0].1.points.iter().map(|p| p.x).collect();
+    assert_eq!(xs, vec![3.0, 5.0, 7.0]);
+
+    harness.app.export.fit_model = FitModel::Polynomial;
+    harness.app.export.fit_degree = 1;
+    harness.app.export.export_kind = ExportKind::Fitted;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let samples = &curves[0].1.points;
+    assert_eq!(samples.len(), 3);
+    assert_close(samples[2].y, 2.4);
+
+    harness.app.export.sample_grid = SampleGrid::List;
+    harness.app.export.sample_xs_text = "3, x".to_string();
+    let curves = harness.app.build_export_curves();
+    assert!(curves.map_or(true, |curves| curves[0].1.points.is_empty()));
+}
+
+#[test]
+fn export_range_clamps_every_kind_and_drags_on_the_image() {
+    let mut harness = calibrated_harness();
+    // Points on the line y = 8 - 0.8·x, x from 2.5 to 7.5.
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.click(Role::CheckBox, "Limit X range");
+    assert_eq!(harness.app.export.x_range, Some([2.5, 7.5]));
+    harness.app.export.x_range = Some([6.0, 4.0]);
+    harness.app.export.sample_count = 3;
+
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let rows = &curves[0].1.points;
+    assert_eq!(rows.len(), 3);
+    for (row, (x, y)) in rows.iter().zip([(4.0, 4.8), (5.0, 4.0), (6.0, 3.2)]) {
+        assert_close(row.x, x);
+        assert_close(row.y, y);
+    }
+
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert_eq!(curves[0].1.points.len(), 1);
+    // The click lands within f32 rounding of the pixel center, depending on the layout.
+    let clicked_x = curves[0].1.points[0].x;
+    assert_close(clicked_x, 5.0);
+
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    harness.app.export.interp_algorithm = InterpAlgorithm::StepHold;
+    harness.app.export.step_breakpoints = true;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let xs: Vec<f64> = curves[0].1.points.iter().map(|p| p.x).collect();
+    // The inner corner copies the clicked point's x exactly.
+    assert_eq!(
+        xs,
+        vec![4.0, clicked_x, clicked_x, 6.0],
+        "held values open and close the range"
+    );
+
+    harness.app.export.fit_model = FitModel::Polynomial;
+    harness.app.export.fit_degree = 1;
+    harness.app.export.export_kind = ExportKind::Fitted;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let samples = &curves[0].1.points;
+    assert_close(samples[0].x, 4.0);
+    assert_close(samples[2].x, 6.0);
+
+    // The bound at x = 6 crosses the image at pixel 116; dragging it to 132 moves it to 7.
+    let from = harness.image_pixel_to_screen(pos2(116.0, 40.0));
+    let to = harness.image_pixel_to_screen(pos2(132.0, 40.0));
+    harness.drag(from, to, Modifiers::NONE);
+    let [lo, hi] = harness.app.export.x_range.expect("range kept");
+    // Within the sub-pixel rounding of the pointer.
+    assert!((lo - 7.0).abs() < 0.05, "bound moved to {lo}");
+    assert_close(hi, 4.0);
+    assert_eq!(
+        harness.app.points.points.len(),
+        3,
+        "the drag places no point"
+    );
+
+    harness.click(Role::CheckBox, "Limit X range");
+    assert!(harness.app.export.x_range.is_none());
+}
+
+#[test]
+fn smoothing_spline_export_is_pulled_less_by_unsure_points() {
+    let mut harness = calibrated_harness();
+    // Level at y = 4 but for a stray click at y = 7.
+    for (idx, px) in [30.0, 50.0, 70.0, 90.0, 110.0, 130.0, 150.0, 170.0]
+        .into_iter()
+        .enumerate()
+    {
+        let py = if idx == 3 { 20.0 } else { 50.0 };
+        harness.click_image_pixel(pos2(px, py));
+    }
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    harness.app.export.interp_algorithm = InterpAlgorithm::SmoothingSpline;
+    harness.app.export.spline_span = 2;
+    let peak = |harness: &mut Harness| {
+        let curves = harness.app.build_export_curves().expect("export payload");
+        curves[0]
+            .1
+            .points
+            .iter()
+            .map(|p| p.y)
+            .fold(f64::MIN, f64::max)
+    };
+
+    for point in &mut harness.app.points.points {
+        point.confidence = 0.9;
+    }
+    let sure = peak(&mut harness);
+    // The stray point was placed by hand.
+    harness.app.points.points[3].confidence = 0.0;
+    let unsure = peak(&mut harness);
+    assert!(sure < 7.0, "the spline smooths the spike: {sure}");
+    assert!(unsure - 4.0 < 0.5 * (sure - 4.0), "{unsure} vs {sure}");
+
+    harness.app.export.interp_algorithm = InterpAlgorithm::NaturalCubic;
+    assert!(peak(&mut harness) > 6.9, "interpolation passes through it");
+}
+
+#[test]
+fn selected_column_templates_are_added_to_every_export_kind() {
+    let mut harness = calibrated_harness();
+    for pixel in [pos2(40.0, 50.0), pos2(80.0, 30.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.config.export.columns = vec![
+        ColumnTemplate {
+            name: "sum".to_string(),
+            expression: "x + y".to_string(),
+            ..ColumnTemplate::default()
+        },
+        ColumnTemplate {
+            name: "unused".to_string(),
+            metric: Some(ColumnMetric::Distance),
+            ..ColumnTemplate::default()
+        },
+    ];
+    harness.app.export.column_templates = vec!["sum".to_string()];
+
+    for kind in [ExportKind::RawPoints, ExportKind::Interpolated] {
+        harness.app.export.export_kind = kind;
+        let curves = harness.app.build_export_curves().expect("export payload");
+        let payload = &curves[0].1;
+        let headers: Vec<_> = payload
+            .extra_columns
+            .iter()
+            .map(|c| c.header.as_str())
+            .collect();
+        assert_eq!(headers, ["sum"]);
+        for (point, value) in payload.points.iter().zip(&payload.extra_columns[0].values) {
+            assert_close(value.expect("sum value"), point.x + point.y);
+        }
+    }
+
+    let profile = harness
+        .app
+        .export_profile_snapshot("Sums".to_string(), ExportFormat::Csv);
+    assert_eq!(profile.columns, ["sum"]);
+}
+
+#[test]
+fn export_smoothing_is_previewed_and_only_written_on_export() {
+    let mut harness = calibrated_harness();
+    // y alternates 1, 5, 1, 5, 1 over x = 1..5.
+    for (column, row) in [
+        (36.0, 80.0),
+        (52.0, 40.0),
+        (68.0, 80.0),
+        (84.0, 40.0),
+        (100.0, 80.0),
+    ] {
+        harness.click_image_pixel(pos2(column, row));
+    }
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    harness.app.export.smoothing = 1;
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let ys: Vec<f64> = curves[0].1.points.iter().map(|p| p.y).collect();
+    for (y, expected) in ys.iter().zip([1.0, 7.0 / 3.0, 11.0 / 3.0, 7.0 / 3.0, 1.0]) {
+        assert_close(*y, expected);
+    }
+    let points = harness.app.points.points.clone();
+    assert_eq!(harness.app.smoothing_preview_rows(&points).len(), 5);
+    let series = harness.app.result_plot_series();
+    assert_eq!(series[0].smoothed.len(), 5);
+    // The picks themselves keep their values.
+    assert_close(points[1].y_numeric.expect("y value"), 5.0);
+    harness.run();
+
+    // A three-row median keeps alternating values in place but the preview follows it.
+    harness.app.export.smoothing_filter = SmoothingFilter::Median;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let ys: Vec<f64> = curves[0].1.points.iter().map(|p| p.y).collect();
+    for (y, expected) in ys.iter().zip([1.0, 1.0, 5.0, 1.0, 1.0]) {
+        assert_close(*y, expected);
+    }
+    assert_close(harness.app.smoothing_preview_rows(&points)[1].y, 1.0);
+    let profile = harness
+        .app
+        .export_profile_snapshot("median".to_string(), ExportFormat::Csv);
+    assert_eq!(profile.smoothing_filter, SmoothingFilter::Median);
+
+    // LOESS over three rows pulls the alternating values towards each other, symmetrically.
+    harness.app.export.smoothing_filter = SmoothingFilter::Loess;
+    harness.app.export.loess_iterations = 0;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let ys: Vec<f64> = curves[0].1.points.iter().map(|p| p.y).collect();
+    assert!(ys[1] < 4.0 && ys[2] > 1.5, "{ys:?}");
+    assert_close(ys[1], ys[3]);
+    assert_close(ys[0], ys[4]);
+    let profile = harness
+        .app
+        .export_profile_snapshot("loess".to_string(), ExportFormat::Csv);
+    assert_eq!(profile.smoothing_filter, SmoothingFilter::Loess);
+    assert_eq!(profile.loess_iterations, 0);
+
+    harness.app.export.smoothing = 0;
+    assert!(harness.app.smoothing_preview_rows(&points).is_empty());
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert_close(curves[0].1.points[1].y, 5.0);
+}
+
+#[test]
+fn clicked_error_bars_export_as_y_err_columns() {
+    let mut harness = calibrated_harness();
+    harness.app.points.error_bar_mode = ErrorBarMode::Asymmetric;
+    // Point at y = 4 with bar ends at y = 5 and y = 2.
+    for pixel in [pos2(60.0, 50.0), pos2(60.0, 40.0), pos2(60.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.points.error_bar_mode = ErrorBarMode::Off;
+    harness.click_image_pixel(pos2(100.0, 30.0));
+    assert_eq!(harness.app.points.points.len(), 2);
+
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let column = |header: &str| {
+        curves[0]
+            .1
+            .extra_columns
+            .iter()
+            .find(|c| c.header == header)
+            .map(|c| c.values.clone())
+            .expect("error bar column")
+    };
+    let (lo, hi) = (column("y_err_lo"), column("y_err_hi"));
+    assert_close(lo[0].expect("lower error"), 2.0);
+    assert_close(hi[0].expect("upper error"), 1.0);
+    assert!(lo[1].is_none() && hi[1].is_none());
+}
+
+#[test]
+fn result_plot_follows_picked_points() {
+    let mut harness = calibrated_harness();
+    harness.app.ui.result_plot_open = true;
+    harness.settle();
+    assert!(harness.app.result_plot_series().is_empty());
+
+    harness.click_image_pixel(pos2(60.0, 50.0));
+    let series = harness.app.result_plot_series();
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].points.len(), 1);
+    assert!(series[0].samples.is_empty());
+    assert_close(series[0].points[0][0], 2.5);
+    assert_close(series[0].points[0][1], 4.0);
+
+    harness.click_image_pixel(pos2(100.0, 30.0));
+    let series = harness.app.result_plot_series();
+    assert_eq!(series[0].points.len(), 2);
+    assert_eq!(series[0].samples.len(), harness.app.export.sample_count);
+}
+
+#[test]
+fn swapping_endpoints_keeps_the_mapping() {
+    let mut harness = calibrated_harness();
+    let (p1, p2) = (
+        harness.app.calibration.cal_x.p1,
+        harness.app.calibration.cal_x.p2,
+    );
+    harness.click(Role::Button, "Swap X1 ↔ X2");
+
+    let cal = &harness.app.calibration.cal_x;
+    assert_eq!((cal.p1, cal.p2), (p2, p1));
+    assert_eq!(cal.v1_text, "10");
+    assert_eq!(cal.v2_text, "0");
+    assert!(!cal.direction_mismatch(true));
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    let point = &harness.app.points.points[0];
+    assert_close(point.x_numeric.expect("x"), 5.0);
+    assert_close(point.y_numeric.expect("y"), 4.0);
+}
+
+#[test]
+fn x_calibration_copies_to_y_for_square_plots() {
+    let mut harness = Harness::with_blank_image(200, 200);
+    calibrate_point(&mut harness, "X1", pos2(20.0, 180.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 180.0), "10");
+    assert!(harness.app.calibration.cal_y.mapping().is_none());
+    harness.click(Role::Button, "X → Y");
+
+    let origin = harness.app.calibration.cal_x.p1.expect("X1");
+    let cal_y = &harness.app.calibration.cal_y;
+    assert_eq!(cal_y.p1, Some(origin));
+    assert_pixel(cal_y.p2.expect("Y2"), origin - egui::vec2(0.0, 160.0));
+    assert_eq!(
+        (cal_y.v1_text.as_str(), cal_y.v2_text.as_str()),
+        ("0", "10")
+    );
+    let mapping = cal_y.mapping().expect("Y mapping");
+    assert_close(
+        mapping
+            .numeric_at(origin - egui::vec2(0.0, 40.0))
+            .expect("y value"),
+        2.5,
+    );
+    assert!(harness.app.calibration_ready());
+}
+
+#[test]
+fn swapped_axis_values_are_flagged_and_fixed_in_one_click() {
+    let mut harness = calibrated_harness();
+    assert!(!harness.has_widget(Role::Button, "Swap values"));
+
+    harness.app.calibration.cal_y.swap_values();
+    harness.settle();
+    assert!(harness.app.calibration.cal_y.direction_mismatch(false));
+    assert!(!harness.app.calibration.cal_x.direction_mismatch(true));
+    harness.click(Role::Button, "Swap values");
+
+    assert!(!harness.app.calibration.cal_y.direction_mismatch(false));
+    assert_eq!(harness.app.calibration.cal_y.v1_text, "0");
+    assert_eq!(harness.app.calibration.cal_y.v2_text, "8");
+    assert!(!harness.has_widget(Role::Button, "Swap values"));
+}
+
+#[test]
+fn legend_swatches_seed_named_curves() {
+    let entries = [
+        (30, Color32::from_rgb(220, 40, 40), "Data"),
+        (80, Color32::from_rgb(40, 90, 220), "Fit"),
+    ];
+    let entry = |(y, color, label): (usize, Color32, &str)| {
+        let [r, g, b, _] = color.to_array();
+        format!(
+            r#"<rect x="20" y="{y}" width="36" height="4" fill="rgb({r},{g},{b})"/>
+            <text x="64" y="{}" font-family="DejaVu Sans" font-size="24">{label}</text>"#,
+            y + 10
+        )
+    };
+    let image = crate::image::render_svg(&format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="240" height="120">
+            <rect width="100%" height="100%" fill="white"/>{}{}
+        </svg>"#,
+        entry(entries[0]),
+        entry(entries[1]),
+    ));
+    let [width, height] = image.size;
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.settle();
+
+    harness.click(Role::Button, "From legend");
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::LegendEntry);
+    harness.click_image_pixel(pos2(24.0, 32.0));
+    harness.click_image_pixel(pos2(24.0, 82.0));
+    harness.press_key(Key::Escape, Modifiers::NONE);
+
+    let curves = &harness.app.points.curves.curves;
+    let names: Vec<&str> = curves.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["Data", "Fit"], "the blank first curve is reused");
+    assert_eq!(curves[1].color, Some(entries[1].1));
+    assert_eq!(harness.app.snap.snap_target_color, entries[1].1);
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+}
+
+#[test]
+fn chart_and_axis_titles_are_read_into_the_project() {
+    // The title is set right of the chart text window, which covers the top left corner.
+    let image = crate::image::render_svg(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="240">
+            <rect width="100%" height="100%" fill="white"/>
+            <g font-family="DejaVu Sans" font-size="24">
+                <text x="220" y="34">Temperature</text>
+                <text x="160" y="220">Time, s</text>
+                <text transform="translate(34 200) rotate(-90)">Voltage, mV</text>
+            </g>
+        </svg>"#,
+    );
+    let [width, height] = image.size;
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.app.ui.chart_text_window_open = true;
+    harness.settle();
+
+    // The first "Read" button belongs to the title row.
+    harness.click(Role::Button, "Read");
+    assert_eq!(
+        harness.app.calibration.pick_mode,
+        PickMode::ChartText(ChartTextField::Title)
+    );
+    harness.click_image_pixel(pos2(226.0, 26.0));
+    assert_eq!(harness.app.project.title.as_deref(), Some("Temperature"));
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+
+    harness.app.calibration.pick_mode = PickMode::ChartText(ChartTextField::XAxisTitle);
+    harness.click_image_pixel(pos2(166.0, 212.0));
+    harness.app.calibration.pick_mode = PickMode::ChartText(ChartTextField::YAxisTitle);
+    harness.click_image_pixel(pos2(26.0, 194.0));
+    assert_eq!(
+        harness.app.axis_labels(),
+        ("Time, s".to_string(), "Voltage, mV".to_string())
+    );
+}
+
+#[test]
+fn svg_opens_at_the_chosen_scale_and_reopens_at_it() {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let dir = std::env::temp_dir().join(format!("curcat_harness_svg_{nanos}"));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let svg_path = dir.join("chart.svg");
+    std::fs::write(
+        &svg_path,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="80"><path d="M10 70 L110 10" stroke="#000"/></svg>"##,
+    )
+    .expect("write svg");
+
+    let mut harness = Harness::new();
+    harness.app.start_loading_image_from_path(svg_path);
+    harness.settle();
+    assert!(harness.app.image.image.is_none(), "waits for the scale");
+    harness.click(Role::Button, "300%");
+    harness.click(Role::Button, "Import");
+    harness.run_until(|app| app.image.image.is_some());
+    assert_eq!(harness.app.image.image.as_ref().unwrap().size, [360, 240]);
+
+    let project_path = dir.join("chart.curcat");
+    harness.app.handle_project_save(&project_path);
+    harness.run_until(|app| app.project.pending_project_save.is_none());
+    let reopened = Harness::with_project(&project_path);
+    assert_eq!(reopened.app.image.svg_scale_percent, Some(300));
+    assert_eq!(reopened.app.image.image.as_ref().unwrap().size, [360, 240]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn preprocessing_whitens_scan_paper_and_toggles_back() {
+    let (width, height) = (160, 80);
+    let mut image = egui::ColorImage::new(
+        [width, height],
+        vec![Color32::from_rgb(214, 205, 182); width * height],
+    );
+    for x in 0..width {
+        image.pixels[40 * width + x] = Color32::from_rgb(40, 30, 25);
+    }
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.app.ui.preprocess_window_open = true;
+    harness.settle();
+
+    let pixel = |harness: &Harness, x: usize, y: usize| {
+        harness.app.image.image.as_ref().unwrap().pixels.pixels[y * width + x]
+    };
+    harness.click(Role::CheckBox, "Apply preprocessing");
+    assert_eq!(pixel(&harness, 20, 10), Color32::WHITE);
+    assert!(pixel(&harness, 20, 40).r() < 80, "the line stays dark");
+    assert_eq!(
+        harness.app.image.base_pixels.as_ref().unwrap().pixels,
+        image.pixels,
+        "the original pixels are kept"
+    );
+
+    harness.click(Role::CheckBox, "Apply preprocessing");
+    assert_eq!(pixel(&harness, 20, 10), image.pixels[10 * width + 20]);
+}
+
+#[test]
+fn finished_exports_are_recorded_and_repeat_with_their_settings() {
+    let mut harness = calibrated_harness();
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_history_{nanos}.csv"));
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    harness
+        .app
+        .start_export_job(path.clone(), curves, ExportFormat::Csv);
+    harness.run_until(|app| app.project.export_history.len() == 1);
+    let record = &harness.app.project.export_history[0];
+    assert_eq!(record.path, path);
+    assert_eq!(record.rows, 3);
+    assert_eq!(record.settings.format, ExportFormat::Csv);
+    assert_eq!(record.settings.kind, ExportKind::RawPoints);
+
+    // Repeating uses the recorded kind and leaves the current one alone.
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    std::fs::remove_file(&path).expect("remove first export");
+    harness.app.repeat_export(0);
+    harness.run_until(|app| app.project.export_history.len() == 2);
+    assert_eq!(harness.app.export.export_kind, ExportKind::Interpolated);
+    assert_eq!(
+        harness.app.project.export_history[1].settings.kind,
+        ExportKind::RawPoints
+    );
+    let text = std::fs::read_to_string(&path).expect("read repeated csv");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(text.lines().skip(1).count(), 3);
+}
+
+#[test]
+fn curves_on_the_secondary_y_axis_export_against_its_scale() {
+    let mut harness = calibrated_harness();
+    harness.click(Role::Button, "Secondary Y axis (Y')");
+    // Y' = 100..500 over the same pixel span as Y = 0..8.
+    calibrate_point(&mut harness, "Y'1", pos2(195.0, 90.0), "100");
+    calibrate_point(&mut harness, "Y'2", pos2(195.0, 10.0), "500");
+    harness.click_image_pixel(pos2(60.0, 50.0));
+    harness.app.add_curve();
+    harness.click_image_pixel(pos2(60.0, 50.0));
+    harness.app.set_curve_y_axis(1, YAxis::Secondary);
+    harness.app.export.export_kind = ExportKind::RawPoints;
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert_close(curves[0].1.points[0].y, 4.0);
+    assert_close(curves[1].1.points[0].y, 300.0);
+    assert_eq!(curves[1].1.y_label, "y2");
+    let axis_of = |idx: usize| {
+        curves[idx]
+            .1
+            .text_columns
+            .iter()
+            .find(|c| c.header == "y_axis")
+            .map(|c| c.values.clone())
+            .expect("y_axis column")
+    };
+    assert_eq!(axis_of(0), vec![Some("primary".to_string())]);
+    assert_eq!(axis_of(1), vec![Some("secondary".to_string())]);
+
+    // Without a complete Y' calibration the export is refused.
+    harness.app.calibration.cal_secondary_y.v2_text.clear();
+    assert!(harness.app.build_export_curves().is_err());
+}
+
+#[test]
+fn axis_names_head_csv_columns_and_key_json_rows() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(60.0, 50.0));
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    harness.app.project.axis_titles = ["Time [s]".to_string(), "Temperature [°C]".to_string()];
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let dir = std::env::temp_dir();
+    let csv = dir.join(format!("curcat_axis_names_{nanos}.csv"));
+    let json = dir.join(format!("curcat_axis_names_{nanos}.json"));
+
+    for (path, format) in [(&csv, ExportFormat::Csv), (&json, ExportFormat::Json)] {
+        let curves = harness.app.build_export_curves().expect("export payload");
+        let done = harness.app.project.export_history.len() + 1;
+        harness.app.start_export_job(path.clone(), curves, format);
+        harness.run_until(|app| app.project.export_history.len() == done);
+    }
+    let csv_text = std::fs::read_to_string(&csv).expect("read csv");
+    let json_text = std::fs::read_to_string(&json).expect("read json");
+    let _ = std::fs::remove_file(&csv);
+    let _ = std::fs::remove_file(&json);
+    assert_eq!(csv_text.lines().next(), Some("Time [s],Temperature [°C]"));
+    let root: serde_json::Value = serde_json::from_str(&json_text).expect("parse json");
+    let row = &root["points"][0];
+    assert!(row["Time [s]"].is_number(), "{json_text}");
+    assert!(row["Temperature [°C]"].is_number(), "{json_text}");
+
+    // The secondary axis has a name of its own; cleared names fall back to x / y / y2.
+    harness.click(Role::Button, "Secondary Y axis (Y')");
+    calibrate_point(&mut harness, "Y'1", pos2(195.0, 90.0), "100");
+    calibrate_point(&mut harness, "Y'2", pos2(195.0, 10.0), "500");
+    harness.app.add_curve();
+    harness.click_image_pixel(pos2(60.0, 50.0));
+    harness.app.set_curve_y_axis(1, YAxis::Secondary);
+    harness.app.project.secondary_y_title = "Pressure [kPa]".to_string();
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert_eq!(curves[0].1.y_label, "Temperature [°C]");
+    assert_eq!(curves[1].1.y_label, "Pressure [kPa]");
+    harness.app.project.axis_titles = Default::default();
+    harness.app.project.secondary_y_title = "  ".to_string();
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert_eq!(curves[0].1.x_label, "x");
+    assert_eq!(curves[1].1.y_label, "y2");
+}
+
+#[test]
+fn overwriting_an_export_keeps_the_previous_file_as_a_backup() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(60.0, 30.0));
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    harness.app.config.export.backup = ExportBackup::Bak;
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_backup_{nanos}.csv"));
+    let backup = path.with_file_name(format!("curcat_backup_{nanos}.csv.bak"));
+    std::fs::write(&path, "older export\n").expect("write previous export");
+    std::fs::write(&backup, "oldest export\n").expect("write previous backup");
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    harness
+        .app
+        .start_export_job(path.clone(), curves, ExportFormat::Csv);
+    harness.run_until(|app| !app.export_job_running());
+    let text = std::fs::read_to_string(&path).expect("read new export");
+    let kept = std::fs::read_to_string(&backup).expect("read backup");
+    let leftovers = std::fs::read_dir(std::env::temp_dir())
+        .expect("list temp dir")
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.contains(&format!("curcat_backup_{nanos}")) && name.ends_with("curcat-old")
+        })
+        .count();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(&backup);
+    assert_eq!(text.lines().skip(1).count(), 1);
+    assert_eq!(kept, "older export\n");
+    assert_eq!(leftovers, 0);
+}
+
+#[test]
+fn exports_leave_the_active_profile_to_save_profile() {
+    let mut harness = calibrated_harness();
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.apply_export_profile(0);
+    let applied = harness.app.config.export.profiles[0].clone();
+    assert!(!harness.app.active_export_profile_modified());
+
+    harness.app.export.decimals = if applied.precision == 2 { 3 } else { 2 };
+    assert!(harness.app.active_export_profile_modified());
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_profile_{nanos}.csv"));
+    let curves = harness.app.build_export_curves().expect("export payload");
+    harness
+        .app
+        .start_export_job(path.clone(), curves, ExportFormat::Csv);
+    harness.run_until(|app| !app.export_job_running());
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(harness.app.config.export.profiles[0], applied);
+    assert!(harness.app.active_export_profile_modified());
+}
+
+#[test]
+fn a_failed_export_puts_the_backed_up_file_back() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(60.0, 30.0));
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    harness.app.config.export.backup = ExportBackup::Bak;
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_restore_{nanos}.csv"));
+    let backup = path.with_file_name(format!("curcat_restore_{nanos}.csv.bak"));
+    std::fs::write(&path, "older export\n").expect("write previous export");
+    std::fs::write(&backup, "oldest export\n").expect("write previous backup");
+
+    // Curves with different columns cannot share one CSV table, so the write fails.
+    let mut curves = harness.app.build_export_curves().expect("export payload");
+    let mut other = curves[0].clone();
+    other.0 = "Other".to_string();
+    other
+        .1
+        .extra_columns
+        .push(ExportExtraColumn::new("extra", vec![None]));
+    curves.push(other);
+    harness
+        .app
+        .start_export_job(path.clone(), curves, ExportFormat::Csv);
+    harness.run_until(|app| !app.export_job_running());
+    let text = std::fs::read_to_string(&path).expect("previous export restored");
+    let kept = std::fs::read_to_string(&backup).expect("previous backup restored");
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(&backup);
+    assert_eq!(text, "older export\n");
+    assert_eq!(kept, "oldest export\n");
+}
+
+#[test]
+fn inverted_axes_are_declared_and_checked_before_export() {
+    // Depth chart: X = 10..0 over pixels 20..180, so values fall to the right.
+    let mut harness = Harness::with_blank_image(200, 100);
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "10");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "0");
+    calibrate_point(&mut harness, "Y1", pos2(5.0, 90.0), "0");
+    calibrate_point(&mut harness, "Y2", pos2(5.0, 10.0), "8");
+    assert!(harness.has_widget(Role::Button, "Swap values"));
+
+    harness.app.calibration.cal_x.inverted = true;
+    harness.settle();
+    assert!(!harness.has_widget(Role::Button, "Swap values"));
+    for pixel in [pos2(60.0, 30.0), pos2(100.0, 50.0), pos2(140.0, 70.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    harness.app.export.sample_count = 3;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let xs: Vec<f64> = curves[0].1.points.iter().map(|p| p.x).collect();
+    assert_eq!(xs.len(), 3);
+    assert_close(xs[0], 2.5);
+    assert_close(xs[2], 7.5);
+    assert_close(curves[0].1.points[0].y, 2.0);
+
+    // Marked inverted, but the values were then entered the usual way round.
+    harness.app.calibration.cal_x.swap_values();
+    harness.settle();
+    assert!(harness.app.calibration.cal_x.direction_mismatch(true));
+    assert!(harness.app.build_export_curves().is_err());
+    harness.click(Role::Button, "Swap values");
+    assert!(harness.app.build_export_curves().is_ok());
+}
+
+#[test]
+fn validation_rules_block_or_warn_before_export() {
+    let mut harness = calibrated_harness();
+    // y = -0.5 below the Y1 line.
+    harness.click_image_pixel(pos2(60.0, 95.0));
+    harness.click_image_pixel(pos2(100.0, 30.0));
+    harness.app.export.export_kind = ExportKind::RawPoints;
+
+    assert!(harness.has_widget(Role::Button, "Validation rules (0)"));
+    harness.app.ui.validation_window_open = true;
+    harness.settle();
+    harness.click(Role::Button, "Add rule");
+    assert_eq!(harness.app.project.validation_rules.len(), 1);
+
+    harness.app.start_export(ExportFormat::Csv);
+    harness.settle();
+    assert!(harness.app.project.active_dialog.is_none());
+    let report = harness.app.ui.validation_report.clone().expect("report");
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].row, 0);
+    assert_close(report[0].value, -0.5);
+    assert!(harness.app.ui.validation_window_open);
+
+    harness.app.project.validation_rules[0].severity = RuleSeverity::Warn;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    assert!(harness.app.validate_export(&curves));
+    assert_eq!(
+        harness.app.ui.validation_report.as_ref().map(Vec::len),
+        Some(1)
+    );
+}
+
+#[test]
+fn calibration_is_fitted_to_a_pasted_reference_and_can_be_reverted() {
+    // The X ticks were picked 4 px outside where 0 and 10 really are.
+    let mut harness = calibrated_harness();
+    let (x1, x2) = (
+        harness.app.calibration.cal_x.p1.expect("x1"),
+        harness.app.calibration.cal_x.p2.expect("x2"),
+    );
+    harness
+        .app
+        .set_imported_dataset_from_text("x\ty\n2.5\t2\n5\t4\n7.5\t6\n");
+    for pixel in [pos2(62.0, 70.0), pos2(100.0, 50.0), pos2(138.0, 30.0)] {
+        harness.click_image_pixel(pixel);
+    }
+
+    harness.click(Role::Button, "Fit calibration to reference");
+    let review = harness
+        .app
+        .calibration
+        .recalibration_review
+        .clone()
+        .expect("review");
+    assert_eq!(review.pairs, 3);
+    assert!(review.rms_before[0] > 0.1);
+    assert!(review.rms_after[0] < 1e-3 && review.rms_after[1] < 1e-3);
+    let cal_x = &harness.app.calibration.cal_x;
+    assert_pixel(cal_x.p1.expect("x1"), x1 + vec2(4.0, 0.0));
+    assert_pixel(cal_x.p2.expect("x2"), x2 - vec2(4.0, 0.0));
+    assert_eq!(cal_x.v2_text, "10");
+
+    harness.settle();
+    harness.click(Role::Button, "Revert");
+    assert!(harness.app.calibration.recalibration_review.is_none());
+    assert_eq!(harness.app.calibration.cal_x.p1, Some(x1));
+}
+
+#[test]
+fn point_list_edits_reorders_inserts_and_deletes_rows() {
+    let mut harness = calibrated_harness();
+    for pixel in [pos2(36.0, 80.0), pos2(68.0, 60.0), pos2(164.0, 20.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.ui.points_info_window_open = true;
+    harness.settle();
+    harness.click(Role::Button, "2");
+    assert_eq!(harness.app.selected_point(), Some(1));
+
+    // Typed values move the point to where it reads them; calibration pixels are centers.
+    assert!(harness.app.set_point_values(0, 5.0, 4.0));
+    assert_pixel(harness.app.points.points[0].pixel, pos2(100.5, 50.5));
+    harness.app.nudge_point(0, vec2(0.5, -1.0));
+    assert_pixel(harness.app.points.points[0].pixel, pos2(101.0, 49.5));
+
+    // The selection follows its point through inserts, moves and deletes.
+    harness.app.insert_point_after(0);
+    assert_eq!(harness.app.points.points.len(), 4);
+    assert_eq!(harness.app.selected_point(), Some(1));
+    let inserted = harness.app.points.points[1].pixel;
+    let second = harness.app.points.points[2].pixel;
+    harness.app.move_point_row(1, false);
+    assert_eq!(harness.app.points.points[2].pixel, inserted);
+    assert_eq!(harness.app.selected_point(), Some(2));
+    harness.app.delete_point_row(1);
+    assert_eq!(harness.app.points.points[1].pixel, inserted);
+    assert_eq!(harness.app.selected_point(), Some(1));
+    assert!(!harness.app.points.points.iter().any(|p| p.pixel == second));
+    harness.settle();
+}
+
+#[test]
+fn locked_points_and_curves_cannot_be_moved_or_removed() {
+    let mut harness = calibrated_harness();
+    for pixel in [pos2(40.0, 50.0), pos2(80.0, 40.0), pos2(120.0, 30.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.toggle_point_lock(1);
+    let locked = harness.app.points.points[1].pixel;
+
+    // Shift-drag leaves the locked point where it is but still moves the others. The first
+    // leg is short so the drag starts over the point.
+    let shift_drag = |harness: &mut Harness, pixel: Pos2| {
+        let from = harness.image_pixel_to_screen(pixel);
+        let path = [from, from + vec2(8.0, 0.0), from + vec2(8.0, 40.0)];
+        harness.drag_through(&path, Modifiers::SHIFT);
+    };
+    shift_drag(&mut harness, locked);
+    assert_eq!(harness.app.points.points[1].pixel, locked);
+    let status = harness.app.ui.last_status.as_ref().expect("lock warning");
+    assert_eq!(status.level, StatusLevel::Warn);
+    let free = harness.app.points.points[0].pixel;
+    shift_drag(&mut harness, free);
+    assert!(harness.app.points.points[0].pixel.y > free.y + 5.0);
+
+    harness.app.nudge_point(1, vec2(3.0, 0.0));
+    harness.app.delete_point_row(1);
+    assert_eq!(harness.app.points.points.len(), 3);
+    assert_eq!(harness.app.points.points[1].pixel, locked);
+
+    // A bulk delete skips it and leaves it selected.
+    harness.app.select_points(vec![0, 1]);
+    harness.app.delete_selected_points();
+    assert_eq!(harness.app.points.points.len(), 2);
+    assert_eq!(harness.app.points.points[0].pixel, locked);
+    assert_eq!(harness.app.interaction.point_selection, [0]);
+
+    // A locked curve keeps all of its points but still takes new ones.
+    harness.app.toggle_point_lock(0);
+    harness.app.toggle_curve_lock(0);
+    let last = harness.app.points.points[1].pixel;
+    harness.app.nudge_point(1, vec2(3.0, 0.0));
+    harness.app.undo_last_point();
+    assert_eq!(harness.app.points.points.len(), 2);
+    assert_eq!(harness.app.points.points[1].pixel, last);
+    harness.click_image_pixel(pos2(150.0, 20.0));
+    assert_eq!(harness.app.points.points.len(), 3);
+    harness.app.toggle_curve_lock(0);
+    harness.app.undo_last_point();
+    assert_eq!(harness.app.points.points.len(), 2);
+}
+
+#[test]
+fn arrow_keys_nudge_the_selected_point_or_calibration_handle() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    let start = harness.app.points.points[0].pixel;
+    harness.app.interaction.selection = Some(DragTarget::CurvePoint(0));
+    harness.press_key(Key::ArrowRight, Modifiers::NONE);
+    harness.press_key(Key::ArrowDown, Modifiers::SHIFT);
+    harness.press_key(Key::ArrowLeft, Modifiers::ALT);
+    assert_pixel(harness.app.points.points[0].pixel, start + vec2(0.9, 10.0));
+
+    // A whole axis line moves both of its ends and leaves the points in place.
+    let cal_x = harness.app.calibration.cal_x.clone();
+    harness.app.interaction.selection = Some(DragTarget::CalXLine);
+    harness.press_key(Key::ArrowUp, Modifiers::NONE);
+    let moved = &harness.app.calibration.cal_x;
+    assert_pixel(
+        moved.p1.expect("x1"),
+        cal_x.p1.expect("x1") - vec2(0.0, 1.0),
+    );
+    assert_pixel(
+        moved.p2.expect("x2"),
+        cal_x.p2.expect("x2") - vec2(0.0, 1.0),
+    );
+    assert_pixel(harness.app.points.points[0].pixel, start + vec2(0.9, 10.0));
+
+    harness.press_key(Key::Escape, Modifiers::NONE);
+    assert!(harness.app.interaction.selection.is_none());
+    harness.press_key(Key::ArrowUp, Modifiers::NONE);
+    assert_pixel(
+        harness.app.calibration.cal_x.p1.expect("x1"),
+        cal_x.p1.expect("x1") - vec2(0.0, 1.0),
+    );
+}
+
+#[test]
+fn curve_family_parameters_export_as_a_column_and_a_long_table() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(60.0, 30.0));
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    harness.app.points.curves.curves[0].parameter_text = "25".to_string();
+    harness.app.add_curve();
+    harness.click_image_pixel(pos2(140.0, 70.0));
+    harness.app.points.curves.curves[1].parameter_text = "50.5".to_string();
+    harness.app.project.parameter_name = "T".to_string();
+    harness.app.export.export_kind = ExportKind::RawPoints;
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let parameters: Vec<_> = curves
+        .iter()
+        .map(|(_, payload)| {
+            let column = payload
+                .extra_columns
+                .iter()
+                .find(|c| c.header == "T")
+                .expect("parameter column");
+            column.values.clone()
+        })
+        .collect();
+    assert_eq!(parameters, [vec![Some(25.0); 2], vec![Some(50.5)]]);
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_family_{nanos}.json"));
+    harness.app.export.long_format = true;
+    harness
+        .app
+        .start_export_job(path.clone(), curves, ExportFormat::Json);
+    harness.run_until(|app| !app.export_job_running());
+    let text = std::fs::read_to_string(&path).expect("read export");
+    let _ = std::fs::remove_file(&path);
+    let root: serde_json::Value = serde_json::from_str(&text).expect("json");
+    // A long table has no per-curve index, only rows carrying curve and parameter.
+    assert!(root.get("curves").is_none());
+    let rows = root["points"].as_array().expect("points");
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2]["curve"], "Curve 2");
+    assert_close(rows[2]["T"].as_f64().expect("parameter"), 50.5);
+}
+
+#[test]
+fn curve_family_surface_grids_parameter_curves_over_x() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(36.0, 80.0));
+    harness.click_image_pixel(pos2(164.0, 20.0));
+    harness.app.points.curves.curves[0].parameter_text = "50".to_string();
+    harness.app.add_curve();
+    harness.click_image_pixel(pos2(68.0, 60.0));
+    harness.click_image_pixel(pos2(132.0, 40.0));
+    harness.app.points.curves.curves[1].parameter_text = "25".to_string();
+    harness.app.project.parameter_name = "T".to_string();
+    harness.app.export.sample_count = 11;
+    harness.settle();
+    assert!(harness.has_widget(Role::Button, "NetCDF"));
+
+    let surface = harness.app.build_surface().expect("surface");
+    assert_eq!(surface.parameters, [25.0, 50.0]);
+    assert_eq!(surface.x.len(), 11);
+    // The shorter curve has no value where only the longer one reaches.
+    assert!(surface.values[0][0].is_none());
+    assert!(surface.values[1].iter().all(Option::is_some));
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let path = std::env::temp_dir().join(format!("curcat_surface_{nanos}.csv"));
+    harness
+        .app
+        .write_surface(&path, &surface, SurfaceFormat::CsvMatrix);
+    let text = std::fs::read_to_string(&path).expect("read surface");
+    let _ = std::fs::remove_file(&path);
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("T \\ x,"));
+    assert!(lines[1].starts_with("25,,"));
+}
+
+#[test]
+fn ctrl_drag_zooms_to_the_region_and_fit_width_fills_the_viewport() {
+    let mut harness = calibrated_harness();
+    let viewport = harness.app.image.last_viewport_size.expect("viewport");
+    let (a, b) = (pos2(20.0, 15.0), pos2(180.0, 85.0));
+    let from = harness.image_pixel_to_screen(a);
+    let to = harness.image_pixel_to_screen(b);
+    harness.drag(from, to, Modifiers::CTRL);
+    harness.settle();
+    assert!(
+        harness.app.points.points.is_empty(),
+        "the drag placed points"
+    );
+    let span = harness.image_pixel_to_screen(b) - harness.image_pixel_to_screen(a);
+    let fill = (span.x / viewport.x).max(span.y / viewport.y);
+    assert_close(f64::from(fill), 0.98);
+    let zoomed = harness.app.image.zoom;
+
+    // Armed from the zoom menu, a plain drag frames a region and ends the mode.
+    harness.app.begin_pick_mode(PickMode::ZoomRegion);
+    let from = harness.image_pixel_to_screen(pos2(60.0, 30.0));
+    let to = harness.image_pixel_to_screen(pos2(120.0, 60.0));
+    harness.drag(from, to, Modifiers::NONE);
+    harness.settle();
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+    assert!(harness.app.image.zoom > zoomed);
+    assert!(harness.app.points.points.is_empty());
+
+    // Leave the Y2 value field, which still holds keyboard focus, then fit the width.
+    harness.press_key(Key::Escape, Modifiers::NONE);
+    harness.press_key(Key::W, Modifiers::COMMAND);
+    harness.settle();
+    let width = harness.image_pixel_to_screen(pos2(200.0, 0.0)).x
+        - harness.image_pixel_to_screen(pos2(0.0, 0.0)).x;
+    assert_close(f64::from(width / viewport.x), 0.98);
+}
+
+#[test]
+fn snapping_stays_inside_the_snap_area() {
+    let (width, height) = (200, 100);
+    let mut image = egui::ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+    for y in 0..height {
+        image.pixels[y * width + 60] = Color32::BLACK;
+        image.pixels[y * width + 150] = Color32::BLACK;
+    }
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.app.snap.point_input_mode = crate::app::PointInputMode::ContrastSnap;
+    harness.settle();
+    harness.app.ensure_snap_maps();
+    harness.run_until(|app| app.snap.snap_maps.is_some());
+    assert!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(147.0, 50.0))
+            .is_some()
+    );
+
+    // A dragged rectangle around the left line.
+    harness.click(Role::Button, "Snap area");
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::SnapArea);
+    let from = harness.image_pixel_to_screen(pos2(20.0, 10.0));
+    let to = harness.image_pixel_to_screen(pos2(100.0, 90.0));
+    harness.drag(from, to, Modifiers::NONE);
+    harness.settle();
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+    assert_eq!(harness.app.snap.search_area.as_ref().map(Vec::len), Some(4));
+    assert!(harness.app.points.points.is_empty());
+    assert!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(147.0, 50.0))
+            .is_none()
+    );
+    assert!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(57.0, 50.0))
+            .is_some()
+    );
+
+    // Clicked corners closed with Enter replace it with a triangle around the right line.
+    harness.click(Role::Button, "Snap area");
+    for corner in [pos2(120.0, 10.0), pos2(190.0, 50.0), pos2(120.0, 90.0)] {
+        harness.click_image_pixel(corner);
+    }
+    assert!(
+        harness
+            .app
+            .snap
+            .search_area
+            .as_ref()
+            .is_some_and(|a| a.len() == 4)
+    );
+    harness.press_key(Key::Enter, Modifiers::NONE);
+    assert_eq!(harness.app.snap.search_area.as_ref().map(Vec::len), Some(3));
+    assert!(harness.app.points.points.is_empty());
+    assert!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(57.0, 50.0))
+            .is_none()
+    );
+    assert!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(147.0, 50.0))
+            .is_some()
+    );
+
+    harness.click(Role::Button, "Clear area");
+    assert!(harness.app.snap.search_area.is_none());
+    assert!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(57.0, 50.0))
+            .is_some()
+    );
+}
+
+#[test]
+fn points_selected_by_rectangle_or_lasso_are_edited_together() {
+    let mut harness = calibrated_harness();
+    for pixel in [
+        pos2(40.0, 50.0),
+        pos2(60.0, 40.0),
+        pos2(80.0, 50.0),
+        pos2(140.0, 30.0),
+    ] {
+        harness.click_image_pixel(pixel);
+    }
+    let pixels = |harness: &Harness| -> Vec<Pos2> {
+        harness.app.points.points.iter().map(|p| p.pixel).collect()
+    };
+    let before = pixels(&harness);
+
+    // A rectangle around the first two points; the arrow keys move both.
+    harness.click(Role::Button, "Select points");
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::SelectPoints);
+    let from = harness.image_pixel_to_screen(pos2(30.0, 30.0));
+    let to = harness.image_pixel_to_screen(pos2(70.0, 60.0));
+    harness.drag(from, to, Modifiers::NONE);
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+    assert_eq!(harness.app.interaction.point_selection, [0, 1]);
+    assert_eq!(harness.app.points.points.len(), 4);
+    harness.press_key(Key::ArrowRight, Modifiers::SHIFT);
+    let after = pixels(&harness);
+    for idx in 0..4 {
+        let shift = if idx < 2 { 10.0 } else { 0.0 };
+        assert_pixel(after[idx], before[idx] + vec2(shift, 0.0));
+    }
+
+    // They move to a new curve, in order, leaving the other two.
+    harness.app.add_curve();
+    harness.app.select_curve(0);
+    harness.app.select_points(vec![0, 1]);
+    harness.app.move_selected_points_to_curve(1);
+    assert!(harness.app.interaction.point_selection.is_empty());
+    assert_eq!(harness.app.points.points.len(), 2);
+    let moved: Vec<Pos2> = harness
+        .app
+        .curve_points(1)
+        .iter()
+        .map(|p| p.pixel)
+        .collect();
+    assert_eq!(moved, after[..2]);
+
+    // An Alt lasso around the one at (140, 30) catches it alone; Delete removes it.
+    harness.app.begin_point_selection_pick();
+    let lasso = [
+        pos2(120.0, 10.0),
+        pos2(170.0, 20.0),
+        pos2(150.0, 60.0),
+        pos2(115.0, 40.0),
+    ]
+    .map(|pixel| harness.image_pixel_to_screen(pixel));
+    harness.drag_through(&lasso, Modifiers::ALT);
+    assert_eq!(harness.app.interaction.point_selection, [1]);
+    harness.press_key(Key::Delete, Modifiers::NONE);
+    assert!(harness.app.interaction.point_selection.is_empty());
+    assert_eq!(pixels(&harness), [after[2]]);
+}
+
+#[test]
+fn snapped_points_carry_a_confidence_that_is_exported() {
+    let (width, height) = (200, 100);
+    let mut image = egui::ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+    for y in 0..height {
+        image.pixels[y * width + 60] = Color32::BLACK;
+    }
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.app.snap.point_input_mode = crate::app::PointInputMode::ContrastSnap;
+    harness.settle();
+    harness.app.ensure_snap_maps();
+    harness.run_until(|app| app.snap.snap_maps.is_some());
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "10");
+    calibrate_point(&mut harness, "Y1", pos2(5.0, 90.0), "0");
+    calibrate_point(&mut harness, "Y2", pos2(5.0, 10.0), "8");
+
+    harness.click_image_pixel(pos2(58.0, 50.0));
+    harness.app.snap.point_input_mode = crate::app::PointInputMode::Free;
+    harness.click_image_pixel(pos2(120.0, 40.0));
+    let confidences: Vec<f32> = harness
+        .app
+        .points
+        .points
+        .iter()
+        .map(|p| p.confidence)
+        .collect();
+    assert_eq!(confidences.len(), 2);
+    assert!(confidences[0] > 0.5, "{confidences:?}");
+    assert!(confidences[1].abs() < f32::EPSILON, "{confidences:?}");
+
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    harness.app.export.raw_include_confidence = true;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let column = curves[0]
+        .1
+        .extra_columns
+        .iter()
+        .find(|c| c.header == "confidence")
+        .expect("confidence column");
+    let values: Vec<f64> = column.values.iter().map(|v| v.expect("value")).collect();
+    assert_eq!(values.len(), 2);
+    assert_close(values[0], f64::from(confidences[0]));
+    assert_close(values[1], 0.0);
+}
+
+#[test]
+fn snap_queries_reuse_the_last_search_for_cursor_jitter() {
+    let (width, height) = (120, 100);
+    let mut image = egui::ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+    for y in 0..height {
+        image.pixels[y * width + 60] = Color32::BLACK;
+    }
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.app.snap.point_input_mode = crate::app::PointInputMode::ContrastSnap;
+    harness.settle();
+    harness.app.ensure_snap_maps();
+    harness.run_until(|app| app.snap.snap_maps.is_some());
+
+    let snapped = harness.app.compute_snap_candidate(pos2(57.0, 50.0));
+    assert!(snapped.is_some_and(|p| (p.x - 60.0).abs() <= 1.0));
+    // Plant a marker result: only a reused query can return it.
+    let marker = Some(pos2(1.0, 1.0));
+    if let Some(query) = harness.app.snap.last_query.as_mut() {
+        query.result = marker;
+    }
+    assert_eq!(harness.app.compute_snap_candidate(pos2(57.3, 50.2)), marker);
+    assert_eq!(
+        harness
+            .app
+            .compute_snap_candidate(pos2(57.0, 53.0))
+            .map(|p| p.x),
+        snapped.map(|p| p.x)
+    );
+
+    for change in [
+        |app: &mut crate::app::CurcatApp| app.snap.contrast_threshold += 1.0,
+        |app: &mut crate::app::CurcatApp| app.snap.contrast_search_radius += 1.0,
+        crate::app::CurcatApp::clear_snap_area,
+    ] {
+        harness.app.compute_snap_candidate(pos2(57.0, 50.0));
+        if let Some(query) = harness.app.snap.last_query.as_mut() {
+            query.result = marker;
+        }
+        change(&mut harness.app);
+        assert_ne!(harness.app.compute_snap_candidate(pos2(57.0, 50.0)), marker);
+    }
+}
+
+#[test]
+fn detected_scatter_markers_are_reviewed_before_becoming_points() {
+    let (width, height) = (200, 100);
+    let red = Color32::from_rgb(220, 40, 40);
+    let mut image = egui::ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+    for (cx, cy) in [(52, 80), (84, 50), (116, 30), (148, 60)] {
+        for y in cy - 3..=cy + 3 {
+            for x in cx - 3..=cx + 3 {
+                image.pixels[y * width + x] = red;
+            }
+        }
+    }
+    // A legend swatch of the same color is not marker-shaped.
+    for x in 150..190 {
+        image.pixels[12 * width + x] = red;
+    }
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.settle();
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "10");
+    calibrate_point(&mut harness, "Y1", pos2(5.0, 90.0), "0");
+    calibrate_point(&mut harness, "Y2", pos2(5.0, 10.0), "8");
+    harness.app.ui.auto_trace_window_open = true;
+    harness.settle();
+
+    harness.click(Role::Button, "Detect markers");
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::MarkerSample);
+    harness.click_image_pixel(pos2(85.0, 51.0));
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::MarkerReview);
+    let found = harness
+        .app
+        .interaction
+        .marker_review
+        .as_ref()
+        .map(|r| r.candidates.len());
+    assert_eq!(found, Some(4));
+    assert!(harness.app.points.points.is_empty());
+
+    // A click on a candidate drops it; one beside every candidate changes nothing.
+    harness.click_image_pixel(pos2(116.0, 30.0));
+    harness.click_image_pixel(pos2(100.0, 90.0));
+    let found = harness
+        .app
+        .interaction
+        .marker_review
+        .as_ref()
+        .map(|r| r.candidates.len());
+    assert_eq!(found, Some(3));
+    assert!(harness.app.points.points.is_empty());
+
+    harness.click(Role::Button, "Add points");
+    harness.run();
+    assert!(harness.app.interaction.marker_review.is_none());
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+    let values: Vec<(f64, f64)> = harness
+        .app
+        .points
+        .points
+        .iter()
+        .map(|p| {
+            (
+                p.x_numeric.unwrap_or(f64::NAN),
+                p.y_numeric.unwrap_or(f64::NAN),
+            )
+        })
+        .collect();
+    // Pixel p is at x = (p - 20) / 16 and y = (90 - p) / 10.
+    let expected = [(2.0, 1.0), (4.0, 4.0), (8.0, 3.0)];
+    assert_eq!(values.len(), expected.len());
+    for ((x, y), (ex, ey)) in values.into_iter().zip(expected) {
+        assert_close(x, ex);
+        assert_close(y, ey);
+    }
+}
+
+#[test]
+fn spectrum_export_writes_frequency_amplitude_and_phase() {
+    let mut harness = calibrated_harness();
+    // A flat line at y = 4 from x = 0.625 to x = 9.375.
+    for pixel in [pos2(30.0, 50.0), pos2(100.0, 50.0), pos2(170.0, 50.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.sample_count = 200;
+    harness.app.export.export_kind = ExportKind::Spectrum;
+
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let payload = &curves[0].1;
+    assert_eq!(payload.x_label, "frequency");
+    assert_eq!(payload.y_label, "amplitude");
+    assert_eq!(payload.points.len(), 101);
+    // 200 samples 8.75 / 199 apart span 200 steps.
+    assert_close(payload.points[1].x, 199.0 / (200.0 * 8.75));
+    assert_close(payload.points[0].y, 4.0);
+    assert!(payload.points[1..].iter().all(|p| p.y.abs() < 1e-6));
+    let phase = payload
+        .extra_columns
+        .iter()
+        .find(|c| c.header == "phase_deg")
+        .expect("phase column");
+    assert_eq!(phase.values.len(), 101);
+}
+
+#[test]
+fn level_crossings_are_reported_and_exported() {
+    let mut harness = calibrated_harness();
+    // A V from (0, 6) down to (5, 1) and back up to (10, 6).
+    for pixel in [pos2(20.0, 30.0), pos2(100.0, 80.0), pos2(180.0, 30.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.crossing_level = 3.0;
+    harness.app.export.show_crossings = true;
+    harness.run();
+
+    let crossings = harness
+        .app
+        .curve_level_crossings(&harness.app.points.points.clone());
+    assert_eq!(crossings.len(), 2);
+    assert_close(crossings[0].x, 3.0);
+    assert!(!crossings[0].rising);
+    assert_close(crossings[1].x, 7.0);
+    assert!(crossings[1].rising);
+
+    harness.app.export.export_kind = ExportKind::Crossings;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let payload = &curves[0].1;
+    let rows: Vec<(f64, f64)> = payload.points.iter().map(|p| (p.x, p.y)).collect();
+    assert_eq!(rows.len(), 2);
+    for ((x, y), ex) in rows.into_iter().zip([3.0, 7.0]) {
+        assert_close(x, ex);
+        assert_close(y, 3.0);
+    }
+    let direction = payload
+        .text_columns
+        .iter()
+        .find(|c| c.header == "direction")
+        .expect("direction column");
+    assert_eq!(
+        direction.values,
+        [Some("falling".to_string()), Some("rising".to_string())]
+    );
+
+    harness.app.export.crossing_level = 9.0;
+    assert!(harness.app.build_export_curves().is_err());
+}
+
+#[test]
+fn curve_statistics_read_the_interpolated_curve() {
+    let mut harness = calibrated_harness();
+    // The V from (0, 6) down to (5, 1) and back up to (10, 6), sampled through its vertex.
+    for pixel in [pos2(20.0, 30.0), pos2(100.0, 80.0), pos2(180.0, 30.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.sample_count = 201;
+    harness.run();
+
+    let points = harness.app.points.points.clone();
+    let analysis = harness.app.curve_analysis(&points).expect("statistics");
+    assert_close(analysis.stats.min.x, 5.0);
+    assert_close(analysis.stats.min.y, 1.0);
+    assert_close(analysis.stats.max.x, 0.0);
+    assert_close(analysis.stats.max.y, 6.0);
+    assert_close(analysis.stats.mean, 3.5);
+    assert_close(analysis.stats.arc_length, 2.0 * 50f64.sqrt());
+    assert_close(analysis.bounds[0], 0.0);
+    assert_close(analysis.bounds[1], 10.0);
+    assert_close(analysis.area.expect("area"), 35.0);
+
+    harness.app.export.area_bounds = Some([0.0, 5.0]);
+    harness.run();
+    let half = harness.app.curve_analysis(&points).expect("statistics");
+    assert_close(half.area.expect("area"), 17.5);
+    harness.app.export.area_bounds = Some([20.0, 30.0]);
+    assert!(
+        harness
+            .app
+            .curve_analysis(&points)
+            .is_some_and(|a| a.area.is_none())
+    );
+
+    harness.click(Role::Button, "Whole curve");
+    assert!(harness.app.export.area_bounds.is_none());
+}
+
+#[test]
+fn stacked_step_curves_export_their_breakpoints_layer_by_layer() {
+    let mut harness = calibrated_harness();
+    // Bottom boundary 1 → 3 and top boundary 4 → 7, both stepping up at x = 5.
+    for pixel in [pos2(20.0, 80.0), pos2(100.0, 60.0), pos2(180.0, 60.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.add_curve();
+    for pixel in [pos2(20.0, 50.0), pos2(100.0, 20.0), pos2(180.0, 20.0)] {
+        harness.click_image_pixel(pixel);
+    }
+    harness.app.export.export_kind = ExportKind::Interpolated;
+    harness.app.export.interp_algorithm = InterpAlgorithm::StepHold;
+    harness.app.export.step_breakpoints = true;
+
+    let rows_of = |harness: &mut Harness, idx: usize| -> Vec<(f64, f64)> {
+        let curves = harness.app.build_export_curves().expect("export payload");
+        curves[idx].1.points.iter().map(|p| (p.x, p.y)).collect()
+    };
+    let assert_rows = |rows: Vec<(f64, f64)>, expected: [(f64, f64); 4]| {
+        assert_eq!(rows.len(), expected.len(), "{rows:?}");
+        for ((x, y), (ex, ey)) in rows.into_iter().zip(expected) {
+            assert_close(x, ex);
+            assert_close(y, ey);
+        }
+    };
+    assert_rows(
+        rows_of(&mut harness, 1),
+        [(0.0, 4.0), (5.0, 4.0), (5.0, 7.0), (10.0, 7.0)],
+    );
+
+    harness.app.export.unstack_layers = true;
+    assert_rows(
+        rows_of(&mut harness, 0),
+        [(0.0, 1.0), (5.0, 1.0), (5.0, 3.0), (10.0, 3.0)],
+    );
+    assert_rows(
+        rows_of(&mut harness, 1),
+        [(0.0, 3.0), (5.0, 3.0), (5.0, 4.0), (10.0, 4.0)],
+    );
+}
+
+#[test]
+fn custom_time_format_and_offset_feed_numeric_time_exports() {
+    let mut harness = calibrated_harness();
+    let cal_x = &mut harness.app.calibration.cal_x;
+    cal_x.unit = AxisUnit::DateTime;
+    cal_x.time.pattern = "%d/%m/%Y %I:%M %p".to_string();
+    cal_x.time.offset_text = "+02:00".to_string();
+    // 2024-01-01 and 2024-01-11, 00:00 UTC.
+    cal_x.v1_text = "01/01/2024 02:00 AM".to_string();
+    cal_x.v2_text = "11/01/2024 02:00 AM".to_string();
+    assert!(harness.app.calibration_ready());
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    harness.app.export.export_kind = ExportKind::RawPoints;
+
+    let first_x = |harness: &mut Harness| {
+        let curves = harness.app.build_export_curves().expect("export payload");
+        let payload = &curves[0].1;
+        (payload.x_unit, payload.points[0].x)
+    };
+    let (unit, unix_midpoint) = first_x(&mut harness);
+    assert_eq!(unit, AxisUnit::DateTime);
+    assert!((unix_midpoint - 1_704_499_200.0).abs() < 1.0);
+
+    harness.app.export.time_format = TimeExportFormat::UnixSeconds;
+    let (unit, seconds) = first_x(&mut harness);
+    assert_eq!(unit, AxisUnit::Float);
+    assert!((seconds - 1_704_499_200.0).abs() < 1.0);
+
+    // 2024-01-06 is day 45297 in Excel.
+    harness.app.export.time_format = TimeExportFormat::ExcelSerial;
+    let (_, serial) = first_x(&mut harness);
+    assert_close(serial, 45_297.0);
+
+    harness.app.calibration.cal_x.time.offset_text = "+2h".to_string();
+    assert!(!harness.app.calibration_ready());
+}
+
+#[test]
+fn grid_spacing_suggests_a_log_scale_for_x() {
+    let (width, height) = (300, 200);
+    let mut image = egui::ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+    let grid = Color32::from_gray(150);
+    // Two decades of log rulings across, evenly spaced rulings down.
+    for decade in [0.0, 1.0] {
+        for digit in 1..=9u8 {
+            let x = (130.0f32.mul_add(decade + f32::from(digit).log10(), 20.0)).round();
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let x = x as usize;
+            for y in 0..height {
+                image.pixels[y * width + x] = grid;
+            }
+        }
+    }
+    for y in (20..height).step_by(30) {
+        for x in 0..width {
+            image.pixels[y * width + x] = grid;
+        }
+    }
+    let mut harness = Harness::new();
+    let rgba = image.pixels.iter().flat_map(Color32::to_array).collect();
+    harness
+        .app
+        .start_loading_image_from_clipboard(width, height, rgba);
+    harness.run_until(|app| app.image.image.is_some());
+    harness.settle();
+
+    assert_eq!(
+        harness.app.calibration.scale_suggestion,
+        Some(ScaleSuggestion {
+            x: Some(ScaleKind::Log10),
+            y: Some(ScaleKind::Linear),
+        })
+    );
+    assert!(!harness.has_widget(Role::Button, "Grid suggests: Linear"));
+    harness.click(Role::Button, "Grid suggests: Log10");
+    assert_eq!(harness.app.calibration.cal_x.scale, ScaleKind::Log10);
+    assert_eq!(harness.app.calibration.cal_y.scale, ScaleKind::Linear);
+    assert!(!harness.has_widget(Role::Button, "Grid suggests: Log10"));
+}
+
+#[test]
+fn attention_blink_stops_scheduling_frames_once_the_window_idles() {
+    // Without an image the open hint blinks.
+    let mut harness = Harness::new();
+    harness.run();
+    assert!(!harness.app.ui.idle);
+    assert!(harness.repaint_delay <= Duration::from_millis(50));
+
+    harness.run_frames(6 * 60);
+    assert!(harness.app.ui.idle);
+    assert_eq!(harness.repaint_delay, Duration::MAX);
+
+    harness.events.push(Event::PointerMoved(pos2(700.0, 450.0)));
+    harness.run();
+    assert!(!harness.app.ui.idle);
+    assert!(harness.repaint_delay <= Duration::from_millis(50));
+
+    harness.app.config.ui.power_saving = false;
+    harness.run_frames(6 * 60);
+    assert!(!harness.app.ui.idle);
+}
+
+#[test]
+fn failed_project_load_explains_the_cause_and_folds_the_details() {
+    let mut harness = Harness::new();
+    let path = std::env::temp_dir().join("curcat-missing-project.curcat");
+    let _ = std::fs::remove_file(&path);
+    harness.app.load_project_from(path);
+    harness.run();
+
+    let status = harness.app.ui.last_status.as_ref().expect("error status");
+    assert_eq!(status.level, StatusLevel::Error);
+    assert!(
+        status
+            .text
+            .starts_with("Failed to load project: the file or folder does not exist"),
+        "{}",
+        status.text
+    );
+    let details = status.details.clone().expect("error details");
+    assert!(details.contains("Failed to read project"), "{details}");
+    assert_eq!(harness.app.ui.toasts.len(), 1);
+
+    // The status bar has its own copy button; the toast's shows with its details.
+    let copy_buttons = |harness: &Harness| {
+        harness
+            .nodes
+            .iter()
+            .filter(|node| node.role == Role::Button && node.label == "Copy details")
+            .count()
+    };
+    assert_eq!(copy_buttons(&harness), 1);
+    harness.click(Role::Button, "Show details");
+    harness.settle();
+    assert_eq!(copy_buttons(&harness), 2);
+    // Opening the details is not the click that dismisses the toast.
+    assert_eq!(harness.app.ui.toasts.len(), 1);
+}
+
+#[test]
+fn rebound_shortcuts_replace_the_defaults_and_show_in_tooltips() {
+    let mut harness = calibrated_harness();
+    // The click also takes the keyboard focus off the value field.
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    let side_open = harness.app.ui.side_open;
+    harness.press_key(Key::B, Modifiers::COMMAND);
+    assert_eq!(harness.app.ui.side_open, !side_open);
+
+    let keymap = &mut harness.app.config.keymap;
+    keymap.rebind(ShortcutAction::ToggleSidePanel, parse_shortcut("Alt+B"));
+    keymap.rebind(ShortcutAction::PickX1, parse_shortcut("X"));
+    keymap.rebind(ShortcutAction::Undo, None);
+    harness.press_key(Key::B, Modifiers::COMMAND);
+    assert_eq!(harness.app.ui.side_open, !side_open);
+    harness.press_key(Key::B, Modifiers::ALT);
+    assert_eq!(harness.app.ui.side_open, side_open);
+
+    harness.press_key(Key::X, Modifiers::NONE);
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::X1);
+    // Extra modifiers make it another shortcut.
+    harness.press_key(Key::X, Modifiers::SHIFT);
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::X1);
+    harness.press_key(Key::X, Modifiers::NONE);
+    assert_eq!(harness.app.calibration.pick_mode, PickMode::None);
+
+    harness.press_key(Key::Z, Modifiers::COMMAND);
+    assert_eq!(harness.app.points.points.len(), 1);
+    assert_eq!(
+        harness.app.hover_with_shortcut(
+            TextKey::ToggleSidePanelHover,
+            ShortcutAction::ToggleSidePanel
+        ),
+        "Toggle side panel and set position (Alt+B)"
+    );
+    assert_eq!(
+        harness
+            .app
+            .hover_with_shortcut(TextKey::UndoHover, ShortcutAction::Undo),
+        "Undo last point"
+    );
+}
+
+#[test]
+fn view_only_mode_keeps_navigation_and_blocks_edits() {
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    assert_eq!(harness.app.points.points.len(), 1);
+    harness.app.enter_view_mode();
+    assert!(!harness.app.has_unsaved_changes());
+    assert!(harness.app.window_title().contains("view only"));
+
+    harness.click_image_pixel(pos2(140.0, 60.0));
+    harness.press_key(Key::Z, Modifiers::COMMAND);
+    harness.press_key(Key::D, Modifiers::COMMAND | Modifiers::SHIFT);
+    harness.press_key(Key::Delete, Modifiers::NONE);
+    assert_eq!(harness.app.points.points.len(), 1);
+
+    let side_open = harness.app.ui.side_open;
+    harness.press_key(Key::B, Modifiers::COMMAND);
+    assert_eq!(harness.app.ui.side_open, !side_open);
+}
+
+#[test]
+fn a_finished_calibration_reports_its_quality_in_the_panel_and_exports() {
+    let mut harness = Harness::with_blank_image(200, 100);
+    calibrate_point(&mut harness, "X1", pos2(20.0, 95.0), "0");
+    calibrate_point(&mut harness, "X2", pos2(180.0, 95.0), "10");
+    assert!(!harness.has_widget(Role::Button, "Calibration quality"));
+
+    let mut harness = calibrated_harness();
+    harness.click_image_pixel(pos2(100.0, 50.0));
+    assert!(harness.has_widget(Role::Button, "Calibration quality"));
+    harness.app.export.export_kind = ExportKind::RawPoints;
+    let curves = harness.app.build_export_curves().expect("export payload");
+    let report = curves[0].1.calibration_report.expect("calibration report");
+    assert_close(report.axis_angle_deg, 90.0);
+    assert_close(report.x.pixels_per_unit, 16.0);
+    assert_close(report.y.pixels_per_unit, 10.0);
+    assert_close(report.y.quantization_error, 0.05);
+}
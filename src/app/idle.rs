@@ -0,0 +1,46 @@
+//! Repaint pacing for an idle window.
+//!
+//! egui repaints on input by itself; animations ask for the frames in between. The blinking
+//! attention outlines are the one animation with no end of its own: they run for as long as
+//! no image is open or the calibration is incomplete. After a spell without input, or while
+//! the window is in the background, they hold a steady colour and stop scheduling frames.
+
+use super::CurcatApp;
+use egui::{Context, InputState};
+use std::time::Duration;
+
+/// Seconds without input after which the window counts as idle.
+const IDLE_AFTER_SECS: f64 = 5.0;
+/// Frame interval of the attention blink; its period of about three seconds needs no more
+/// than 30 frames a second.
+const ATTENTION_FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+/// Whether the frame's input is the user doing something.
+fn has_activity(input: &InputState) -> bool {
+    !input.events.is_empty() || input.pointer.is_moving() || input.pointer.any_down()
+}
+
+impl CurcatApp {
+    /// Note the frame's input and decide whether the window is idle.
+    pub(crate) fn track_idle(&mut self, ctx: &Context) {
+        let (now, active, focused) = ctx.input(|i| {
+            (
+                i.time,
+                has_activity(i),
+                i.viewport().focused.unwrap_or(true),
+            )
+        });
+        let last = match self.ui.last_activity {
+            Some(last) if !active => last,
+            _ => *self.ui.last_activity.insert(now),
+        };
+        self.ui.idle = self.config.ui.power_saving && (!focused || now - last >= IDLE_AFTER_SECS);
+    }
+
+    /// Schedule the next frame of the attention blink unless the window is idle.
+    pub(crate) fn request_attention_frame(&self, ctx: &Context) {
+        if !self.ui.idle {
+            ctx.request_repaint_after(ATTENTION_FRAME_INTERVAL);
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use super::{
-    CurcatApp, ImageLoadRequest, ImageLoadResult, PendingImageLimitPrompt, PendingImageMeta,
-    PendingImageTask,
+    CurcatApp, DeferredAction, ImageLoadRequest, ImageLoadResult, PendingImageLimitPrompt,
+    PendingImageMeta, PendingImageTask, PendingSvgScalePrompt,
 };
+use crate::cache::{DecodeCache, ImageCacheKey};
+use crate::config::ConfigOverrides;
+use crate::error::CurcatError;
 use crate::i18n::UiLanguage;
 use crate::image::{
     ImageDecodeOptions, ImageLoadOutcome, ImageLoadPolicy, LoadedImage, decode_image_from_bytes,
     decode_image_from_bytes_with_options, decode_image_from_clipboard_rgba,
     decode_image_from_clipboard_rgba_with_options, decode_image_from_path,
-    decode_image_from_path_with_options,
+    decode_image_from_path_with_options, is_svg_path, probe_svg_size,
 };
 use egui::{ColorImage, Context};
 use std::path::Path;
@@ -15,6 +18,8 @@ use std::sync::mpsc::{self, TryRecvError};
 use std::thread;
 
 const SVG_VIEWPORT_QUALITY_SCALE: f32 = 1.0;
+pub const SVG_SCALE_RANGE: std::ops::RangeInclusive<u32> = 10..=1_600;
+const SVG_SCALE_STEP: u32 = 25;
 
 impl CurcatApp {
     pub(crate) fn start_loading_image_from_path(&mut self, path: std::path::PathBuf) {
@@ -23,12 +28,7 @@ impl CurcatApp {
         }
         self.remember_image_dir_from_path(&path);
         let meta = PendingImageMeta::Path { path: path.clone() };
-        self.start_image_load(
-            ImageLoadRequest::Path(path),
-            meta,
-            ImageLoadPolicy::AskUser,
-            self.current_decode_options(),
-        );
+        self.start_image_load_unless_unsaved(ImageLoadRequest::Path(path), meta);
     }
 
     pub(crate) fn start_loading_image_from_bytes(
@@ -45,12 +45,7 @@ impl CurcatApp {
             byte_len: bytes.len(),
             last_modified,
         };
-        self.start_image_load(
-            ImageLoadRequest::Bytes(bytes),
-            meta,
-            ImageLoadPolicy::AskUser,
-            self.current_decode_options(),
-        );
+        self.start_image_load_unless_unsaved(ImageLoadRequest::Bytes(bytes), meta);
     }
 
     pub(crate) fn start_loading_image_from_clipboard(
@@ -65,18 +60,108 @@ impl CurcatApp {
         let meta = PendingImageMeta::Clipboard {
             byte_len: rgba.len(),
         };
-        self.start_image_load(
+        self.start_image_load_unless_unsaved(
             ImageLoadRequest::ClipboardRgba {
                 width,
                 height,
                 rgba,
             },
             meta,
+        );
+    }
+
+    /// Start a user-initiated load, or hold it behind the unsaved-changes prompt.
+    fn start_image_load_unless_unsaved(
+        &mut self,
+        request: ImageLoadRequest,
+        meta: PendingImageMeta,
+    ) {
+        if let Some(DeferredAction::LoadImage { request, meta }) =
+            self.defer_for_unsaved_changes(DeferredAction::LoadImage { request, meta })
+        {
+            self.start_user_image_load(request, meta);
+        }
+    }
+
+    /// Start a load the user asked for; SVG sources first ask for their raster scale.
+    ///
+    /// A project being applied replays the scale it was saved with instead, so its
+    /// points land on the same pixels.
+    pub(crate) fn start_user_image_load(
+        &mut self,
+        request: ImageLoadRequest,
+        meta: PendingImageMeta,
+    ) {
+        if let Some(plan) = self.project.pending_project_apply.as_ref() {
+            let options = plan.payload.svg_scale_percent.map_or_else(
+                || self.current_decode_options(),
+                |percent| ImageDecodeOptions {
+                    svg_scale_percent: Some(percent),
+                    ..ImageDecodeOptions::default()
+                },
+            );
+            self.start_image_load(request, meta, ImageLoadPolicy::AskUser, options);
+            return;
+        }
+        let Some(source_size) = svg_source_size(&request) else {
+            let options = self.current_decode_options();
+            self.start_image_load(request, meta, ImageLoadPolicy::AskUser, options);
+            return;
+        };
+        let label = meta.description();
+        self.project.pending_svg_scale_prompt = Some(PendingSvgScalePrompt {
+            request,
+            meta,
+            source_size,
+            scale_percent: self.suggested_svg_scale_percent(source_size),
+        });
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("{label} is a vector image. Choose its raster scale."),
+            UiLanguage::Ru => {
+                format!("{label} — векторное изображение. Выберите масштаб растеризации.")
+            }
+        });
+    }
+
+    pub(crate) fn confirm_svg_scale(&mut self) {
+        let Some(prompt) = self.project.pending_svg_scale_prompt.take() else {
+            return;
+        };
+        let options = ImageDecodeOptions {
+            svg_scale_percent: Some(prompt.scale_percent),
+            ..ImageDecodeOptions::default()
+        };
+        self.start_image_load(
+            prompt.request,
+            prompt.meta,
             ImageLoadPolicy::AskUser,
-            self.current_decode_options(),
+            options,
         );
     }
 
+    pub(crate) fn cancel_svg_scale_prompt(&mut self) {
+        if self.project.pending_svg_scale_prompt.take().is_some() {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "SVG import canceled.",
+                UiLanguage::Ru => "Импорт SVG отменён.",
+            });
+        }
+    }
+
+    /// Smallest scale, in whole steps, at which the SVG covers the viewport.
+    fn suggested_svg_scale_percent(&self, source_size: [u32; 2]) -> u32 {
+        let Some([min_w, min_h]) = self.current_decode_options().svg_min_render_size else {
+            return 100;
+        };
+        let percent_for =
+            |target: u32, side: u32| (u64::from(target) * 100).div_ceil(u64::from(side.max(1)));
+        let needed = percent_for(min_w, source_size[0]).max(percent_for(min_h, source_size[1]));
+        let stepped = needed.div_ceil(u64::from(SVG_SCALE_STEP)) * u64::from(SVG_SCALE_STEP);
+        u32::try_from(stepped)
+            .unwrap_or(u32::MAX)
+            .clamp(100, *SVG_SCALE_RANGE.end())
+    }
+
     pub(crate) fn retry_image_load_with_policy(&mut self, policy: ImageLoadPolicy) {
         let Some(prompt) = self.project.pending_image_limit_prompt.take() else {
             return;
@@ -95,7 +180,7 @@ impl CurcatApp {
         }
     }
 
-    fn start_image_load(
+    pub(super) fn start_image_load(
         &mut self,
         request: ImageLoadRequest,
         meta: PendingImageMeta,
@@ -106,6 +191,7 @@ impl CurcatApp {
         let cfg = self.config.clone();
         let (tx, rx) = mpsc::channel();
         self.project.pending_image_limit_prompt = None;
+        self.project.pending_svg_scale_prompt = None;
 
         thread::spawn(move || {
             let msg = decode_request(&cfg, request, policy, decode_options);
@@ -126,10 +212,25 @@ impl CurcatApp {
         };
         match task.rx.try_recv() {
             Ok(ImageLoadResult::Success(color)) => {
+                self.image.svg_scale_percent = task.decode_options.svg_scale_percent;
+                let pasted = matches!(task.meta, PendingImageMeta::Clipboard { .. })
+                    && self.project.pending_project_apply.is_none();
                 let meta = task.meta.into_image_meta();
                 let loaded_path = meta.path().map(Path::to_path_buf);
+                if self.project.pending_project_apply.is_none() {
+                    self.project.document_path = None;
+                    self.set_config_overrides(ConfigOverrides::default());
+                    self.project.swatches.clear();
+                    self.project.export_history.clear();
+                    self.project.validation_rules.clear();
+                    self.project.parameter_name.clear();
+                }
                 self.finish_loaded_color_image(ctx, color, meta);
                 self.apply_project_if_ready(loaded_path.as_deref());
+                self.mark_document_saved();
+                if pasted {
+                    self.suggest_plot_crop(true);
+                }
             }
             Ok(ImageLoadResult::NeedsLimitDecision { request, info }) => {
                 let label = task.meta.description();
@@ -148,11 +249,10 @@ impl CurcatApp {
                     }
                 });
             }
-            Ok(ImageLoadResult::Error(err)) => {
-                let label = task.meta.description();
-                self.set_status_error(match self.ui.language {
-                    UiLanguage::En => format!("Failed to load {label}: {err}"),
-                    UiLanguage::Ru => format!("Не удалось загрузить {label}: {err}"),
+            Ok(ImageLoadResult::Error(source)) => {
+                self.report_error(&CurcatError::ImageLoad {
+                    label: task.meta.description(),
+                    source,
                 });
                 self.project.pending_project_apply = None;
                 self.project.pending_image_limit_prompt = None;
@@ -213,7 +313,7 @@ impl CurcatApp {
         false
     }
 
-    fn current_decode_options(&self) -> ImageDecodeOptions {
+    pub(super) fn current_decode_options(&self) -> ImageDecodeOptions {
         let Some(viewport) = self.image.last_viewport_size else {
             return ImageDecodeOptions::default();
         };
@@ -227,7 +327,20 @@ impl CurcatApp {
         let min_h = ceil_f32_to_u32(viewport.y * ppp * SVG_VIEWPORT_QUALITY_SCALE);
         ImageDecodeOptions {
             svg_min_render_size: Some([min_w, min_h]),
+            ..ImageDecodeOptions::default()
+        }
+    }
+}
+
+/// Intrinsic size of the requested image if it is an SVG.
+fn svg_source_size(request: &ImageLoadRequest) -> Option<[u32; 2]> {
+    match request {
+        ImageLoadRequest::Path(path) if is_svg_path(path) => {
+            let bytes = std::fs::read(path).ok()?;
+            probe_svg_size(&bytes, Some(path))
         }
+        ImageLoadRequest::Bytes(bytes) => probe_svg_size(bytes, None),
+        ImageLoadRequest::Path(_) | ImageLoadRequest::ClipboardRgba { .. } => None,
     }
 }
 
@@ -239,11 +352,24 @@ fn decode_request(
 ) -> ImageLoadResult {
     match request {
         ImageLoadRequest::Path(path) => {
+            let cached = DecodeCache::from_config(cfg).and_then(|cache| {
+                let key = ImageCacheKey::for_file(&path, cfg, policy, decode_options).ok()?;
+                Some((cache, key))
+            });
+            if let Some(image) = cached
+                .as_ref()
+                .and_then(|(cache, key)| cache.load_image(*key))
+            {
+                return ImageLoadResult::Success(image);
+            }
             let outcome = if decode_options == ImageDecodeOptions::default() {
                 decode_image_from_path(cfg, &path, policy)
             } else {
                 decode_image_from_path_with_options(cfg, &path, policy, decode_options)
             };
+            if let (Some((cache, key)), Ok(ImageLoadOutcome::Ready(image))) = (&cached, &outcome) {
+                cache.store_image(*key, image);
+            }
             map_outcome(ImageLoadRequest::Path(path), outcome)
         }
         ImageLoadRequest::Bytes(bytes) => {
@@ -309,6 +435,6 @@ fn map_outcome(
         Ok(ImageLoadOutcome::NeedsLimitDecision(info)) => {
             ImageLoadResult::NeedsLimitDecision { request, info }
         }
-        Err(err) => ImageLoadResult::Error(err.to_string()),
+        Err(err) => ImageLoadResult::Error(err),
     }
 }
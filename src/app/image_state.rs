@@ -1,8 +1,8 @@
 use crate::image::{
     ImageDecodeOptions, ImageFilters, ImageLimitInfo, ImageLoadPolicy, ImageMeta,
-    ImageTransformRecord, LoadedImage,
+    ImageTransformRecord, LoadedImage, PerspectiveWarpRecord, Preprocessing,
 };
-use egui::{ColorImage, Pos2, Vec2};
+use egui::{ColorImage, Pos2, Rect, Vec2};
 use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
 use std::time::SystemTime;
@@ -41,7 +41,7 @@ pub enum ImageLoadResult {
         request: ImageLoadRequest,
         info: ImageLimitInfo,
     },
-    Error(String),
+    Error(anyhow::Error),
 }
 
 pub struct PendingImageLimitPrompt {
@@ -51,6 +51,15 @@ pub struct PendingImageLimitPrompt {
     pub(super) decode_options: ImageDecodeOptions,
 }
 
+/// SVG waiting for the user to choose the scale it is rasterized at.
+pub struct PendingSvgScalePrompt {
+    pub(super) request: ImageLoadRequest,
+    pub(super) meta: PendingImageMeta,
+    /// Intrinsic size of the SVG document in pixels.
+    pub(super) source_size: [u32; 2],
+    pub(super) scale_percent: u32,
+}
+
 #[derive(Clone)]
 pub enum PendingImageMeta {
     Path {
@@ -111,10 +120,25 @@ pub struct ImageState {
     pub(super) image: Option<LoadedImage>,
     pub(super) base_pixels: Option<ColorImage>,
     pub(super) filters: ImageFilters,
+    /// Scan cleanup applied to the base pixels before the display filters.
+    pub(super) preprocessing: Preprocessing,
     pub(super) meta: Option<ImageMeta>,
+    /// Scale the loaded SVG was rasterized at; saved so a project reload matches its pixels.
+    pub(super) svg_scale_percent: Option<u32>,
     pub(super) transform: ImageTransformRecord,
+    /// Perspective warps applied since the image was loaded, oldest first.
+    pub(super) perspective_warps: Vec<PerspectiveWarpRecord>,
+    /// Plot corners clicked so far while picking a perspective warp.
+    pub(super) perspective_corners: Vec<Pos2>,
+    /// First end of the horizon line while leveling the image.
+    pub(super) horizon_start: Option<Pos2>,
+    /// Crop offered around the plot frame found in the image, in image pixels, corner pixels
+    /// included.
+    pub(super) crop_suggestion: Option<Rect>,
     pub(super) pan: Vec2,
     pub(super) last_viewport_size: Option<Vec2>,
+    /// Screen rect of the displayed image in the last frame.
+    pub(super) last_image_rect: Option<Rect>,
     pub(super) last_pixels_per_point: f32,
     pub(super) skip_pan_sync_once: bool,
     pub(super) pending_fit_on_load: bool,
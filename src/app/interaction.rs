@@ -1,4 +1,5 @@
 use super::auto_trace::AutoTraceConfig;
+use super::{MarkerReview, PointSelectionDrag, ZoomRegionDrag};
 use crate::config::AutoPlaceConfig;
 use egui::Pos2;
 use std::time::Instant;
@@ -28,6 +29,20 @@ pub struct InteractionState {
     pub(super) auto_trace_cfg: AutoTraceConfig,
     pub(super) primary_press: Option<PrimaryPressInfo>,
     pub(super) middle_pan_enabled: bool,
+    /// Point or calibration handle the arrow keys nudge.
+    pub(super) selection: Option<DragTarget>,
+    /// Points of the active curve selected together for bulk edits, in ascending order.
+    pub(super) point_selection: Vec<usize>,
+    /// Rectangle or lasso of a point selection being dragged.
+    pub(super) point_selection_drag: Option<PointSelectionDrag>,
+    /// Rectangle of a zoom-to-region drag in progress.
+    pub(super) zoom_region: Option<ZoomRegionDrag>,
+    /// Corners of a snap-area rectangle being dragged, in image pixels.
+    pub(super) snap_area_drag: Option<[Pos2; 2]>,
+    /// Index of the export range bound being dragged.
+    pub(super) x_range_drag: Option<usize>,
+    /// Detected scatter markers not yet added as points.
+    pub(super) marker_review: Option<MarkerReview>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,9 +54,14 @@ pub enum DragTarget {
     CalY1,
     CalY2,
     CalYLine,
+    CalSecondaryY1,
+    CalSecondaryY2,
     PolarOrigin,
     PolarR1,
     PolarR2,
     PolarA1,
     PolarA2,
+    AffineRef(usize),
+    SmithCenter,
+    SmithShort,
 }
@@ -0,0 +1,58 @@
+//! Curves seeded from legend entries: the swatch color becomes the curve color and the
+//! label next to it, read by OCR, the curve name (and so its name in exports).
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+use crate::ocr;
+use egui::Pos2;
+
+impl CurcatApp {
+    /// Set up a curve from the legend swatch under `pixel`.
+    ///
+    /// A blank active curve is reused, otherwise a new curve is added. Snapping follows the
+    /// swatch color, so the curve can be traced right away.
+    pub(crate) fn seed_curve_from_legend(&mut self, pixel: Pos2) {
+        let Some(color) = self.sample_image_color(pixel) else {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "Unable to pick color at cursor.",
+                UiLanguage::Ru => "Не удалось выбрать цвет под курсором.",
+            });
+            return;
+        };
+        let label = self
+            .image
+            .image
+            .as_ref()
+            .zip(self.image_pixel_coords(pixel))
+            .and_then(|(image, (x, y))| ocr::read_legend_label(&image.pixels, x, y));
+
+        if !self.active_curve_is_blank() {
+            self.add_curve();
+        }
+        let idx = self.points.curves.active;
+        let curve = &mut self.points.curves.curves[idx];
+        curve.color = Some(color);
+        if let Some(label) = &label {
+            curve.name.clone_from(label);
+        }
+        let name = curve.name.clone();
+        self.snap.snap_target_color = color;
+        self.mark_snap_maps_dirty();
+
+        if label.is_some() {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => format!("Curve \"{name}\" added from the legend."),
+                UiLanguage::Ru => format!("Кривая «{name}» добавлена из легенды."),
+            });
+        } else {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => format!(
+                    "{name} takes the legend color, but its label could not be read; rename it in the curves list."
+                ),
+                UiLanguage::Ru => format!(
+                    "{name} получила цвет из легенды, но подпись не распознана; переименуйте её в списке кривых."
+                ),
+            });
+        }
+    }
+}
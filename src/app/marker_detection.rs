@@ -0,0 +1,118 @@
+//! Scatter digitizing: every marker shaped and colored like a clicked one becomes a point,
+//! after a review in which false detections are clicked away.
+
+use super::{CurcatApp, POINT_HIT_RADIUS, PickMode};
+use crate::i18n::UiLanguage;
+use crate::snap::{detect_markers, sample_marker};
+use egui::{Color32, Pos2, Rect, Stroke};
+
+const CANDIDATE_COLOR: Color32 = Color32::from_rgb(255, 200, 60);
+
+/// Marker centroids waiting to be added as points.
+#[derive(Debug, Clone, Default)]
+pub struct MarkerReview {
+    pub(super) candidates: Vec<Pos2>,
+}
+
+impl CurcatApp {
+    /// Find the markers like the one under `pixel` and start reviewing them.
+    ///
+    /// Detection follows the marker's color within the snap color tolerance and stays inside
+    /// the snap area when one is set.
+    pub(crate) fn detect_markers_like(&mut self, pixel: Pos2) {
+        self.calibration.pick_mode = PickMode::None;
+        let (Some(color), Some((x, y))) = (
+            self.sample_image_color(pixel),
+            self.image_pixel_coords(pixel),
+        ) else {
+            return;
+        };
+        let Some(image) = self.image.image.as_ref() else {
+            return;
+        };
+        let tolerance = self.snap.snap_color_tolerance;
+        let area = self.snap.search_area.as_deref().unwrap_or_default();
+        let candidates = sample_marker(&image.pixels, x, y, color, tolerance)
+            .map(|shape| detect_markers(&image.pixels, color, tolerance, shape, area))
+            .unwrap_or_default();
+        if candidates.is_empty() {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "No marker found under the click.",
+                UiLanguage::Ru => "Под щелчком не найдено маркера.",
+            });
+            return;
+        }
+        let count = candidates.len();
+        self.interaction.marker_review = Some(MarkerReview { candidates });
+        self.calibration.pick_mode = PickMode::MarkerReview;
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!(
+                "Found {count} markers. Click false ones to drop them, then add the points."
+            ),
+            UiLanguage::Ru => format!(
+                "Найдено маркеров: {count}. Щёлкните ложные, чтобы убрать их, затем добавьте точки."
+            ),
+        });
+    }
+
+    /// Drop the candidate nearest to `pixel` if the click is on it.
+    pub(crate) fn drop_marker_candidate_near(&mut self, pixel: Pos2) {
+        let reach = POINT_HIT_RADIUS / self.image.zoom.max(f32::EPSILON);
+        let Some(review) = self.interaction.marker_review.as_mut() else {
+            return;
+        };
+        let nearest = review
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(idx, candidate)| (idx, candidate.distance(pixel)))
+            .filter(|&(_, dist)| dist <= reach)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((idx, _)) = nearest {
+            review.candidates.remove(idx);
+        }
+    }
+
+    /// Add the reviewed markers to the active curve, ordered by image x.
+    pub(crate) fn accept_detected_markers(&mut self) {
+        let Some(review) = self.interaction.marker_review.take() else {
+            return;
+        };
+        if self.calibration.pick_mode == PickMode::MarkerReview {
+            self.calibration.pick_mode = PickMode::None;
+        }
+        for &pixel in &review.candidates {
+            let point = self.new_snapped_point(pixel);
+            self.points.points.push(point);
+        }
+        self.mark_points_dirty();
+        let count = review.candidates.len();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Added {count} points from markers."),
+            UiLanguage::Ru => format!("Добавлено точек по маркерам: {count}."),
+        });
+    }
+
+    pub(crate) fn discard_detected_markers(&mut self) {
+        if self.interaction.marker_review.take().is_some()
+            && self.calibration.pick_mode == PickMode::MarkerReview
+        {
+            self.calibration.pick_mode = PickMode::None;
+        }
+    }
+
+    /// Rings around the candidates under review over the image drawn at `rect`.
+    pub(crate) fn draw_marker_candidates(&self, painter: &egui::Painter, rect: Rect) {
+        let Some(review) = &self.interaction.marker_review else {
+            return;
+        };
+        let zoom = self.image.zoom;
+        let halo = Stroke::new(3.5_f32, Color32::from_black_alpha(140));
+        let stroke = Stroke::new(1.5_f32, CANDIDATE_COLOR);
+        for candidate in &review.candidates {
+            let center = rect.min + candidate.to_vec2() * zoom;
+            painter.circle_stroke(center, 7.0, halo);
+            painter.circle_stroke(center, 7.0, stroke);
+        }
+    }
+}
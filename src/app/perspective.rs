@@ -0,0 +1,221 @@
+//! Perspective correction: four plot corners picked on a photo warp it upright.
+//!
+//! Leveling by a two-point horizon is the same warp with a rotated rectangle as
+//! the quad, so it is replayed from projects like any other warp.
+
+use super::{CurcatApp, PickMode};
+use crate::i18n::UiLanguage;
+use crate::image::{
+    ImageTransformRecord, PerspectiveWarpRecord, level_quad, order_quad_corners, warp_perspective,
+};
+use crate::util::safe_usize_to_f32;
+use egui::{Pos2, pos2};
+
+/// Steeper horizons are left to the 90° rotation buttons.
+const MAX_LEVEL_TILT_DEG: f32 = 45.0;
+/// Tilts below this are already level.
+const MIN_LEVEL_TILT_DEG: f32 = 0.01;
+
+/// Tilt of the line through `a` and `b` from the horizontal, in (-90°, 90°] radians.
+fn horizon_tilt(a: Pos2, b: Pos2) -> f32 {
+    let d = b - a;
+    let tilt = d.y.atan2(d.x);
+    if tilt > std::f32::consts::FRAC_PI_2 {
+        tilt - std::f32::consts::PI
+    } else if tilt <= -std::f32::consts::FRAC_PI_2 {
+        tilt + std::f32::consts::PI
+    } else {
+        tilt
+    }
+}
+
+impl CurcatApp {
+    pub(crate) fn begin_perspective_pick(&mut self) {
+        self.image.perspective_corners.clear();
+        self.begin_pick_mode(PickMode::PerspectiveCorner);
+    }
+
+    /// Record the next plot corner; the fourth one applies the warp.
+    pub(crate) fn pick_perspective_corner(&mut self, pixel: Pos2) {
+        self.image.perspective_corners.push(pixel);
+        let picked = self.image.perspective_corners.len();
+        if picked < 4 {
+            self.set_status(self.i18n().format_picked(&format!("C{picked}")));
+            return;
+        }
+        self.calibration.pick_mode = PickMode::None;
+        let corners = std::mem::take(&mut self.image.perspective_corners);
+        let Ok(corners) = <[Pos2; 4]>::try_from(corners) else {
+            return;
+        };
+        if self.apply_perspective_warp(order_quad_corners(corners)) {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => "Perspective corrected.",
+                UiLanguage::Ru => "Перспектива исправлена.",
+            });
+        } else {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => {
+                    "The corners do not form a convex quadrilateral; image left as is."
+                }
+                UiLanguage::Ru => {
+                    "Углы не образуют выпуклый четырёхугольник; изображение не изменено."
+                }
+            });
+        }
+    }
+
+    pub(crate) fn begin_horizon_pick(&mut self) {
+        self.image.horizon_start = None;
+        self.begin_pick_mode(PickMode::HorizonPoint);
+    }
+
+    /// Record an end of the horizon line; the second one rotates the image level.
+    pub(crate) fn pick_horizon_point(&mut self, pixel: Pos2) {
+        let Some(start) = self.image.horizon_start.take() else {
+            self.image.horizon_start = Some(pixel);
+            self.set_status(self.i18n().format_picked("H1"));
+            return;
+        };
+        self.calibration.pick_mode = PickMode::None;
+        if start.distance(pixel) < 1.0 {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "The two horizon points coincide; image left as is.",
+                UiLanguage::Ru => "Точки горизонта совпадают; изображение не изменено.",
+            });
+            return;
+        }
+        let tilt = horizon_tilt(start, pixel);
+        let degrees = tilt.to_degrees();
+        if degrees.abs() > MAX_LEVEL_TILT_DEG {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => format!(
+                    "The line is tilted by {degrees:.1}°; use the 90° rotation for steep angles."
+                ),
+                UiLanguage::Ru => format!(
+                    "Линия наклонена на {degrees:.1}°; для больших углов используйте поворот на 90°."
+                ),
+            });
+            return;
+        }
+        if degrees.abs() < MIN_LEVEL_TILT_DEG {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => "The line is already level.",
+                UiLanguage::Ru => "Линия уже горизонтальна.",
+            });
+            return;
+        }
+        let Some(size) = self.image.base_pixels.as_ref().map(|base| base.size) else {
+            return;
+        };
+        if self.apply_perspective_warp(level_quad(size, tilt)) {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => format!("Image rotated by {:.2}° to level.", -degrees),
+                UiLanguage::Ru => format!("Изображение повёрнуто на {:.2}°.", -degrees),
+            });
+        }
+    }
+
+    /// Warp the base pixels so `quad` (top-left, top-right, bottom-right,
+    /// bottom-left) fills the image; `false` when the quad is degenerate.
+    pub(super) fn apply_perspective_warp(&mut self, quad: [Pos2; 4]) -> bool {
+        let Some(base) = self.image.base_pixels.as_ref() else {
+            return false;
+        };
+        let Some((warped, to_source)) = warp_perspective(base, quad) else {
+            return false;
+        };
+        let Some(to_output) = to_source.inverse() else {
+            return false;
+        };
+        let max_x = safe_usize_to_f32(warped.size[0] - 1);
+        let max_y = safe_usize_to_f32(warped.size[1] - 1);
+        self.image.base_pixels = Some(warped);
+        self.map_image_positions(|pos| {
+            to_output.apply(pos).map_or(pos, |mapped| {
+                pos2(mapped.x.clamp(0.0, max_x), mapped.y.clamp(0.0, max_y))
+            })
+        });
+        self.update_filtered_texture();
+        self.image.perspective_warps.push(PerspectiveWarpRecord {
+            prior: self.image.transform,
+            corners: quad.map(|p| [p.x, p.y]),
+        });
+        self.image.transform = ImageTransformRecord::identity();
+        self.reset_after_image_transform();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::horizon_tilt;
+    use crate::app::PickMode;
+    use crate::app::harness::Harness;
+    use egui::pos2;
+
+    #[test]
+    fn horizon_tilt_ignores_click_order() {
+        let (a, b) = (pos2(10.0, 50.0), pos2(110.0, 40.0));
+        let tilt = horizon_tilt(a, b);
+        assert!((tilt - horizon_tilt(b, a)).abs() < 1e-6);
+        assert!(
+            (tilt.to_degrees() + 5.71).abs() < 0.01,
+            "{}",
+            tilt.to_degrees()
+        );
+    }
+
+    #[test]
+    fn two_horizon_clicks_rotate_the_image_and_its_points_level() {
+        let mut harness = Harness::with_blank_image(240, 160);
+        // Ends of an X axis drawn 4° off level, and a point on it.
+        let (left, right) = (pos2(40.0, 120.0), pos2(200.0, 108.81));
+        harness.app.calibration.cal_x.p1 = Some(left);
+        harness.app.calibration.cal_x.p2 = Some(right);
+        harness.app.begin_horizon_pick();
+        harness.click_image_pixel(right);
+        harness.click_image_pixel(left);
+        harness.run();
+
+        let app = &harness.app;
+        assert_eq!(app.calibration.pick_mode, PickMode::None);
+        assert_eq!(app.image.perspective_warps.len(), 1);
+        assert_eq!(app.image.image.as_ref().expect("image").size, [240, 160]);
+        let (p1, p2) = (
+            app.calibration.cal_x.p1.expect("X1"),
+            app.calibration.cal_x.p2.expect("X2"),
+        );
+        assert!((p1.y - p2.y).abs() < 0.1, "{p1:?} {p2:?}");
+        assert!((p1.distance(p2) - left.distance(right)).abs() < 0.1);
+    }
+
+    #[test]
+    fn four_corner_clicks_warp_the_image_and_its_points() {
+        let mut harness = Harness::with_blank_image(320, 240);
+        harness.app.calibration.cal_x.p1 = Some(pos2(160.0, 120.0));
+        harness.app.begin_perspective_pick();
+        // Clicked out of order; the warp sorts them.
+        for corner in [
+            pos2(280.0, 200.0),
+            pos2(40.0, 30.0),
+            pos2(290.0, 20.0),
+            pos2(30.0, 210.0),
+        ] {
+            harness.click_image_pixel(corner);
+        }
+        harness.run();
+
+        let app = &harness.app;
+        assert_eq!(app.calibration.pick_mode, PickMode::None);
+        assert_eq!(app.image.perspective_warps.len(), 1);
+        let [x, y] = app.image.perspective_warps[0].corners[0];
+        assert!(pos2(x, y).distance(pos2(40.0, 30.0)) < 1.0);
+        let size = app.image.image.as_ref().expect("image").size;
+        assert_eq!(size, [251, 181]);
+        // The quad's centre stays near the middle of the straightened image.
+        let anchor = app.calibration.cal_x.p1.expect("anchor kept");
+        assert!((anchor.x - 125.0).abs() < 8.0, "x = {}", anchor.x);
+        assert!((anchor.y - 90.0).abs() < 8.0, "y = {}", anchor.y);
+    }
+}
@@ -0,0 +1,149 @@
+//! Plot crop: a pasted screenshot of a whole window is offered a crop to the chart in it, so
+//! the toolbars and panels around the chart need no external tool to cut away.
+//!
+//! The crop is applied as an upright perspective warp. Its corners are whole pixels, so the
+//! warp copies pixels unchanged, and projects replay it like any other warp.
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+use crate::snap::detect_plot_frame;
+use egui::{Color32, Pos2, Rect, Stroke, pos2};
+
+const CROP_COLOR: Color32 = Color32::from_rgb(120, 200, 255);
+
+impl CurcatApp {
+    /// Look for a plot frame in the image and offer the crop around it.
+    ///
+    /// `quiet` skips the status when nothing is found, as after a paste that was not a
+    /// screenshot.
+    pub(crate) fn suggest_plot_crop(&mut self, quiet: bool) {
+        let Some(base) = self.image.base_pixels.as_ref() else {
+            return;
+        };
+        self.image.crop_suggestion = detect_plot_frame(base).map(|found| found.crop);
+        if self.image.crop_suggestion.is_none() && !quiet {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "No plot frame found to crop to.",
+                UiLanguage::Ru => "Рамка графика для обрезки не найдена.",
+            });
+        }
+    }
+
+    /// Crop the image to the suggested rect; points and calibration move with it.
+    pub(crate) fn accept_plot_crop(&mut self) {
+        let Some(crop) = self.image.crop_suggestion.take() else {
+            return;
+        };
+        let quad = [
+            crop.left_top(),
+            crop.right_top(),
+            crop.right_bottom(),
+            crop.left_bottom(),
+        ];
+        if !self.apply_perspective_warp(quad) {
+            return;
+        }
+        let Some([width, height]) = self.image.base_pixels.as_ref().map(|base| base.size) else {
+            return;
+        };
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Image cropped to the plot: {width}×{height} px."),
+            UiLanguage::Ru => format!("Изображение обрезано по графику: {width}×{height} пикс."),
+        });
+    }
+
+    pub(crate) const fn dismiss_plot_crop(&mut self) {
+        self.image.crop_suggestion = None;
+    }
+
+    /// The suggested crop over the image drawn at `rect`, with the parts it drops dimmed.
+    pub(crate) fn draw_plot_crop(&self, painter: &egui::Painter, rect: Rect) {
+        let Some(crop) = self.image.crop_suggestion else {
+            return;
+        };
+        let zoom = self.image.zoom;
+        let to_screen = |pixel: Pos2| rect.min + pixel.to_vec2() * zoom;
+        // Pixel centres to the outer edges of the corner pixels.
+        let kept = Rect::from_min_max(
+            to_screen(crop.min),
+            to_screen(crop.max + egui::vec2(1.0, 1.0)),
+        );
+        let shade = Color32::from_black_alpha(120);
+        for dropped in [
+            Rect::from_min_max(rect.min, pos2(rect.max.x, kept.min.y)),
+            Rect::from_min_max(pos2(rect.min.x, kept.max.y), rect.max),
+            Rect::from_min_max(pos2(rect.min.x, kept.min.y), pos2(kept.min.x, kept.max.y)),
+            Rect::from_min_max(pos2(kept.max.x, kept.min.y), pos2(rect.max.x, kept.max.y)),
+        ] {
+            painter.rect_filled(dropped, 0.0, shade);
+        }
+        painter.rect_stroke(
+            kept,
+            0.0,
+            Stroke::new(1.5_f32, CROP_COLOR),
+            egui::StrokeKind::Outside,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::harness::Harness;
+    use egui::accesskit::Role;
+    use egui::{Rect, pos2};
+
+    /// RGBA of a window screenshot: a toolbar band above a white figure with a framed plot
+    /// from (120, 80) to (420, 290) and a block of tick labels under it.
+    fn screenshot_rgba(width: usize, height: usize) -> Vec<u8> {
+        let mut rgba = vec![255; width * height * 4];
+        let mut fill = |xs: std::ops::Range<usize>, ys: std::ops::Range<usize>, gray: u8| {
+            for y in ys {
+                for x in xs.clone() {
+                    let idx = (y * width + x) * 4;
+                    rgba[idx..idx + 3].fill(gray);
+                }
+            }
+        };
+        fill(0..width, 0..30, 70);
+        fill(120..121, 80..291, 0);
+        fill(420..421, 80..291, 0);
+        fill(120..421, 80..81, 0);
+        fill(120..421, 290..291, 0);
+        fill(200..340, 300..310, 0);
+        rgba
+    }
+
+    #[test]
+    fn a_pasted_screenshot_is_offered_a_crop_that_moves_the_points() {
+        let (width, height) = (480, 360);
+        let mut harness = Harness::new();
+        harness.app.start_loading_image_from_clipboard(
+            width,
+            height,
+            screenshot_rgba(width, height),
+        );
+        harness.run_until(|app| app.image.image.is_some());
+        harness.settle();
+        assert_eq!(
+            harness.app.image.crop_suggestion,
+            Some(Rect::from_min_max(pos2(116.0, 76.0), pos2(424.0, 313.0)))
+        );
+
+        harness.app.calibration.cal_x.p1 = Some(pos2(120.0, 290.0));
+        harness.click(Role::Button, "Crop image");
+        harness.run();
+
+        let app = &harness.app;
+        assert_eq!(app.image.crop_suggestion, None);
+        assert_eq!(app.image.perspective_warps.len(), 1);
+        assert_eq!(app.image.image.as_ref().expect("image").size, [309, 238]);
+        let corner = app.calibration.cal_x.p1.expect("X1 kept");
+        assert!(corner.distance(pos2(4.0, 214.0)) < 0.01, "{corner:?}");
+    }
+
+    #[test]
+    fn a_blank_paste_gets_no_crop_prompt() {
+        let harness = Harness::with_blank_image(200, 100);
+        assert_eq!(harness.app.image.crop_suggestion, None);
+    }
+}
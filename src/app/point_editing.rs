@@ -0,0 +1,131 @@
+//! Row operations of the point list editor: typed values, pixel nudges, reordering,
+//! deletion and insertion between neighbours, all on the active curve.
+
+use super::{CurcatApp, DragTarget};
+use crate::types::{AxisMapping, AxisUnit, CoordSystem, YAxis};
+use egui::{Pos2, Vec2};
+
+impl CurcatApp {
+    /// Mappings the active curve is read with: X and its own Y axis.
+    pub(crate) fn active_curve_mappings(&self) -> (Option<AxisMapping>, Option<AxisMapping>) {
+        let (x, y) = self.cartesian_mappings();
+        let curves = &self.points.curves;
+        match curves.curves[curves.active].y_axis {
+            YAxis::Primary => (x, y),
+            YAxis::Secondary => (x, self.secondary_y_mapping()),
+        }
+    }
+
+    /// Image position that reads as (`x`, `y`) on the active curve; angle and radius in polar,
+    /// normalized resistance and reactance on a Smith chart.
+    fn pixel_for_values(&self, x: f64, y: f64) -> Option<Pos2> {
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
+                let (Some(xm), Some(ym)) = self.active_curve_mappings() else {
+                    return None;
+                };
+                AxisMapping::pixel_at(&xm, &ym, x, y)
+            }
+            CoordSystem::Polar => self.polar_mapping()?.pixel_at(x, y),
+            CoordSystem::Smith => self.smith_mapping()?.pixel_at(x, y),
+        }
+    }
+
+    /// Units of the two values the point list shows; polar and Smith values are plain floats.
+    pub(crate) fn point_value_units(&self) -> [AxisUnit; 2] {
+        if matches!(
+            self.calibration.coord_system,
+            CoordSystem::Polar | CoordSystem::Smith
+        ) {
+            return [AxisUnit::Float; 2];
+        }
+        <[_; 2]>::from(self.active_curve_mappings())
+            .map(|mapping| mapping.map_or(AxisUnit::Float, |m| m.unit))
+    }
+
+    /// Move point `idx` to where it reads (`x`, `y`); returns `false` when no pixel does.
+    pub(crate) fn set_point_values(&mut self, idx: usize, x: f64, y: f64) -> bool {
+        let Some(pixel) = self.pixel_for_values(x, y) else {
+            return false;
+        };
+        self.set_point_pixel(idx, pixel);
+        true
+    }
+
+    /// Shift point `idx` by `delta` image pixels.
+    pub(crate) fn nudge_point(&mut self, idx: usize, delta: Vec2) {
+        if let Some(point) = self.points.points.get(idx) {
+            self.set_point_pixel(idx, point.pixel + delta);
+        }
+    }
+
+    fn set_point_pixel(&mut self, idx: usize, pixel: Pos2) {
+        if self.point_locked(idx) {
+            self.warn_point_locked();
+            return;
+        }
+        let Some(point) = self.points.points.get_mut(idx) else {
+            return;
+        };
+        // The error bar travels with its point.
+        let delta = pixel - point.pixel;
+        point.pixel = pixel;
+        point.confidence = 0.0;
+        if let Some(ends) = &mut point.error_bar {
+            *ends = ends.map(|end| end + delta);
+        }
+        self.mark_points_dirty();
+    }
+
+    /// Swap point `idx` with its neighbour above (`up`) or below; selections follow the points.
+    pub(crate) fn move_point_row(&mut self, idx: usize, up: bool) {
+        let other = if up {
+            idx.checked_sub(1)
+        } else {
+            Some(idx + 1)
+        };
+        let Some(other) = other.filter(|&other| other < self.points.points.len()) else {
+            return;
+        };
+        self.points.points.swap(idx, other);
+        let swap = |selected: Option<usize>| match selected {
+            Some(s) if s == idx => Some(other),
+            Some(s) if s == other => Some(idx),
+            keep => keep,
+        };
+        self.ui.flag_review_selected = swap(self.ui.flag_review_selected);
+        self.map_selected_point(|idx| swap(Some(idx)));
+        self.mark_points_dirty();
+    }
+
+    pub(crate) fn delete_point_row(&mut self, idx: usize) {
+        if idx >= self.points.points.len() {
+            return;
+        }
+        if self.point_locked(idx) {
+            self.warn_point_locked();
+            return;
+        }
+        self.points.points.remove(idx);
+        self.shift_selection_after_removal(idx);
+        self.cancel_pending_error_bar();
+        self.mark_points_dirty();
+    }
+
+    /// Insert a point halfway between `idx` and the next one and select it.
+    pub(crate) fn insert_point_after(&mut self, idx: usize) {
+        let (Some(a), Some(b)) = (self.points.points.get(idx), self.points.points.get(idx + 1))
+        else {
+            return;
+        };
+        let pixel = a.pixel.lerp(b.pixel, 0.5);
+        let point = self.new_picked_point(pixel);
+        self.points.points.insert(idx + 1, point);
+        self.ui.flag_review_selected = self
+            .ui
+            .flag_review_selected
+            .map(|s| if s > idx { s + 1 } else { s });
+        self.interaction.selection = Some(DragTarget::CurvePoint(idx + 1));
+        self.mark_points_dirty();
+    }
+}
@@ -0,0 +1,276 @@
+//! Pasted reference datasets: external (x, y) tables drawn through the inverse calibration.
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+use crate::interp::XYPoint;
+use crate::types::{AxisMapping, AxisUnit, CoordSystem, parse_axis_value};
+use egui::Pos2;
+
+/// Points pasted from the clipboard, kept in data coordinates so they follow calibration edits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedDataset {
+    pub(super) points: Vec<XYPoint>,
+    /// Data rows that could not be parsed (a leading header row is not counted).
+    pub(super) skipped_rows: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableDelimiter {
+    Tab,
+    Semicolon,
+    Comma,
+    Whitespace,
+}
+
+impl TableDelimiter {
+    fn detect(line: &str) -> Self {
+        if line.contains('\t') {
+            Self::Tab
+        } else if line.contains(';') {
+            Self::Semicolon
+        } else if line.contains(',') {
+            Self::Comma
+        } else {
+            Self::Whitespace
+        }
+    }
+
+    fn split(self, line: &str) -> Vec<&str> {
+        match self {
+            Self::Tab => line.split('\t').collect(),
+            Self::Semicolon => line.split(';').collect(),
+            Self::Comma => line.split(',').collect(),
+            Self::Whitespace => line.split_whitespace().collect(),
+        }
+    }
+}
+
+/// Parse the first two columns of a CSV/TSV table into data-space points.
+///
+/// The delimiter is picked from the first non-empty line (tab, `;`, `,`, then whitespace).
+/// With tab or `;` delimiters a decimal comma is accepted, as spreadsheets in many locales
+/// copy `1,5` rather than `1.5`. A first row that does not parse is treated as a header.
+pub(super) fn parse_point_table(
+    text: &str,
+    x_unit: AxisUnit,
+    y_unit: AxisUnit,
+) -> Result<ImportedDataset, String> {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    let Some(first) = lines.next() else {
+        return Err("Clipboard text is empty.".to_string());
+    };
+    let delimiter = TableDelimiter::detect(first);
+    let decimal_comma = matches!(delimiter, TableDelimiter::Tab | TableDelimiter::Semicolon);
+    let parse_cell = |cell: &str, unit: AxisUnit| {
+        let cell = cell.trim().trim_matches('"').trim();
+        let value = if decimal_comma && unit == AxisUnit::Float {
+            parse_axis_value(&cell.replace(',', "."), unit)
+        } else {
+            parse_axis_value(cell, unit)
+        };
+        value
+            .map(|v| v.to_scalar_seconds())
+            .filter(|v| v.is_finite())
+    };
+    let parse_row = |line: &str| {
+        let cells = delimiter.split(line);
+        let (x, y) = (cells.first()?, cells.get(1)?);
+        Some(XYPoint {
+            x: parse_cell(x, x_unit)?,
+            y: parse_cell(y, y_unit)?,
+        })
+    };
+
+    let mut points = Vec::new();
+    let mut skipped_rows = 0;
+    if let Some(point) = parse_row(first) {
+        points.push(point);
+    }
+    for line in lines {
+        match parse_row(line) {
+            Some(point) => points.push(point),
+            None => skipped_rows += 1,
+        }
+    }
+    if points.is_empty() {
+        return Err("Clipboard text has no rows with two numeric columns.".to_string());
+    }
+    Ok(ImportedDataset {
+        points,
+        skipped_rows,
+    })
+}
+
+impl CurcatApp {
+    /// Units the pasted columns are parsed in: the calibrated axes, (angle, radius) for polar,
+    /// or normalized (resistance, reactance) on a Smith chart.
+    pub(crate) const fn import_axis_units(&self) -> (AxisUnit, AxisUnit) {
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian => (self.calibration.cal_x.unit, self.calibration.cal_y.unit),
+            CoordSystem::Polar | CoordSystem::Smith => (AxisUnit::Float, AxisUnit::Float),
+            CoordSystem::Affine => (
+                self.calibration.affine_cal.x_unit,
+                self.calibration.affine_cal.y_unit,
+            ),
+        }
+    }
+
+    pub(crate) fn set_imported_dataset_from_text(&mut self, text: &str) {
+        if self.image.image.is_none() {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "Load an image before pasting points.",
+                UiLanguage::Ru => "Сначала загрузите изображение, затем вставляйте точки.",
+            });
+            return;
+        }
+        let (x_unit, y_unit) = self.import_axis_units();
+        let dataset = match parse_point_table(text, x_unit, y_unit) {
+            Ok(dataset) => dataset,
+            Err(err) => {
+                self.set_status_error(format!("Paste failed: {err}"));
+                return;
+            }
+        };
+        let count = dataset.points.len();
+        let skipped = dataset.skipped_rows;
+        self.points.imported = Some(dataset);
+        let calibrated = self.calibration_ready();
+        let msg = match (self.ui.language, calibrated) {
+            (UiLanguage::En, true) => {
+                format!("Pasted {count} reference points ({skipped} rows skipped).")
+            }
+            (UiLanguage::En, false) => format!(
+                "Pasted {count} reference points ({skipped} rows skipped); finish calibration to see them."
+            ),
+            (UiLanguage::Ru, true) => {
+                format!("Вставлено опорных точек: {count} (пропущено строк: {skipped}).")
+            }
+            (UiLanguage::Ru, false) => format!(
+                "Вставлено опорных точек: {count} (пропущено строк: {skipped}); завершите калибровку, чтобы их увидеть."
+            ),
+        };
+        if calibrated {
+            self.set_status(msg);
+        } else {
+            self.set_status_warn(msg);
+        }
+    }
+
+    /// Image-space positions of the pasted points under the current calibration.
+    pub(crate) fn imported_point_pixels(&self) -> Vec<Pos2> {
+        let Some(dataset) = &self.points.imported else {
+            return Vec::new();
+        };
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
+                let (Some(xm), Some(ym)) = self.cartesian_mappings() else {
+                    return Vec::new();
+                };
+                dataset
+                    .points
+                    .iter()
+                    .filter_map(|p| AxisMapping::pixel_at(&xm, &ym, p.x, p.y))
+                    .collect()
+            }
+            CoordSystem::Polar => {
+                let Some(pm) = self.polar_mapping() else {
+                    return Vec::new();
+                };
+                dataset
+                    .points
+                    .iter()
+                    .filter_map(|p| pm.pixel_at(p.x, p.y))
+                    .collect()
+            }
+            CoordSystem::Smith => {
+                let Some(sm) = self.smith_mapping() else {
+                    return Vec::new();
+                };
+                dataset
+                    .points
+                    .iter()
+                    .filter_map(|p| sm.pixel_at(p.x, p.y))
+                    .collect()
+            }
+        }
+    }
+
+    /// Turn the pasted reference into regular picked points so the curve can be continued.
+    ///
+    /// Points that land outside the image are dropped, since they could not be edited.
+    pub(crate) fn append_imported_points(&mut self) {
+        if self.points.imported.is_none() {
+            return;
+        }
+        let Some([w, h]) = self.image.image.as_ref().map(|img| img.size) else {
+            return;
+        };
+        let (w, h) = (super::safe_usize_to_f32(w), super::safe_usize_to_f32(h));
+        let pixels = self.imported_point_pixels();
+        if pixels.is_empty() {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "Finish calibration before adding pasted points.",
+                UiLanguage::Ru => "Завершите калибровку, чтобы добавить вставленные точки.",
+            });
+            return;
+        }
+        let total = self.points.imported.as_ref().map_or(0, |d| d.points.len());
+        let mut added = 0;
+        for pixel in pixels {
+            if (0.0..=w).contains(&pixel.x) && (0.0..=h).contains(&pixel.y) {
+                let point = self.new_picked_point(pixel);
+                self.points.points.push(point);
+                added += 1;
+            }
+        }
+        self.points.imported = None;
+        self.mark_points_dirty();
+        let dropped = total - added;
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Added {added} pasted points ({dropped} outside the image)."),
+            UiLanguage::Ru => {
+                format!("Добавлено вставленных точек: {added} (вне изображения: {dropped}).")
+            }
+        });
+    }
+
+    pub(crate) fn clear_imported_points(&mut self) {
+        if self.points.imported.take().is_some() {
+            self.set_status(match self.ui.language {
+                UiLanguage::En => "Pasted reference points cleared.",
+                UiLanguage::Ru => "Вставленные опорные точки убраны.",
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_point_table_skips_header_and_accepts_tsv_decimal_comma() {
+        let text = "x\ty\n1,5\t2\n\n3\t4,25\tignored\nbad\trow\n";
+        let dataset = parse_point_table(text, AxisUnit::Float, AxisUnit::Float).expect("parsed");
+        assert_eq!(
+            dataset.points,
+            vec![XYPoint { x: 1.5, y: 2.0 }, XYPoint { x: 3.0, y: 4.25 }]
+        );
+        assert_eq!(dataset.skipped_rows, 1);
+    }
+
+    #[test]
+    fn parse_point_table_reads_quoted_csv_and_datetime_axis() {
+        let text = "\"2024-01-02 00:00:00\",\"10\"\n2024-01-03,20\n";
+        let dataset = parse_point_table(text, AxisUnit::DateTime, AxisUnit::Float).expect("parsed");
+        assert_eq!(dataset.points.len(), 2);
+        assert!((dataset.points[1].x - dataset.points[0].x - 86_400.0).abs() < 1.0e-6);
+        assert_eq!(dataset.skipped_rows, 0);
+    }
+
+    #[test]
+    fn parse_point_table_rejects_text_without_numeric_rows() {
+        assert!(parse_point_table("", AxisUnit::Float, AxisUnit::Float).is_err());
+        assert!(parse_point_table("a b\nc d", AxisUnit::Float, AxisUnit::Float).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+//! Point locks: points, or whole curves, kept from being dragged, nudged or deleted by a slip
+//! of Shift-drag or Delete-click while the points around them are still being edited.
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+
+impl CurcatApp {
+    /// Whether point `idx` of the active curve is locked, by itself or with its curve.
+    pub(crate) fn point_locked(&self, idx: usize) -> bool {
+        let curves = &self.points.curves;
+        curves.curves[curves.active].locked || self.points.points.get(idx).is_some_and(|p| p.locked)
+    }
+
+    pub(crate) fn toggle_point_lock(&mut self, idx: usize) {
+        if let Some(point) = self.points.points.get_mut(idx) {
+            point.locked = !point.locked;
+        }
+    }
+
+    pub(crate) fn toggle_curve_lock(&mut self, idx: usize) {
+        if let Some(curve) = self.points.curves.curves.get_mut(idx) {
+            curve.locked = !curve.locked;
+        }
+    }
+
+    /// Status for an edit refused because it would move or remove a locked point.
+    pub(crate) fn warn_point_locked(&mut self) {
+        self.set_status_warn(match self.ui.language {
+            UiLanguage::En => "The point is locked; unlock it in the point list or curves panel.",
+            UiLanguage::Ru => {
+                "Точка заблокирована; снимите блокировку в списке точек или в панели кривых."
+            }
+        });
+    }
+}
@@ -0,0 +1,222 @@
+//! Point selection: points of the active curve gathered by a dragged rectangle or a freehand
+//! lasso, then deleted, nudged or moved to another curve together.
+
+use super::{CurcatApp, PickMode};
+use crate::i18n::UiLanguage;
+use crate::snap::inside_polygon;
+use egui::{Color32, Pos2, Rect, Stroke, Vec2, pos2};
+
+/// Screen size below which a drag counts as a click that clears the selection.
+const MIN_SELECTION_SCREEN: f32 = 6.0;
+/// Screen distance the pointer travels before the lasso gets another corner.
+const LASSO_STEP_SCREEN: f32 = 3.0;
+const SELECTION_COLOR: Color32 = Color32::from_rgb(90, 170, 255);
+
+/// Outline dragged so far to select points, in image pixels.
+#[derive(Debug, Clone)]
+pub struct PointSelectionDrag {
+    /// Pointer path of a lasso; a rectangle keeps its start and the pointer.
+    pub(super) path: Vec<Pos2>,
+    pub(super) lasso: bool,
+}
+
+impl PointSelectionDrag {
+    fn outline(&self) -> Vec<Pos2> {
+        if self.lasso {
+            return self.path.clone();
+        }
+        let (Some(&a), Some(&b)) = (self.path.first(), self.path.last()) else {
+            return Vec::new();
+        };
+        vec![a, pos2(b.x, a.y), b, pos2(a.x, b.y)]
+    }
+}
+
+impl CurcatApp {
+    /// Start selecting points: drag a rectangle, or hold Alt and draw a lasso.
+    pub(crate) fn begin_point_selection_pick(&mut self) {
+        self.begin_pick_mode(PickMode::SelectPoints);
+    }
+
+    /// Select points `indices` of the active curve together, replacing any selection.
+    pub(crate) fn select_points(&mut self, mut indices: Vec<usize>) {
+        indices.sort_unstable();
+        indices.dedup();
+        indices.retain(|&idx| idx < self.points.points.len());
+        self.interaction.selection = None;
+        self.interaction.point_selection = indices;
+    }
+
+    pub(crate) fn point_is_selected(&self, idx: usize) -> bool {
+        self.selected_point() == Some(idx)
+            || self.interaction.point_selection.binary_search(&idx).is_ok()
+    }
+
+    /// Follow a selection drag while points are being selected.
+    ///
+    /// `press` and `pointer` are image pixels; `lasso` is read when the drag starts. Returns
+    /// `true` while the outline is dragged and on the frame it is released, so the gesture does
+    /// not also count as a click.
+    pub(crate) fn track_point_selection_drag(
+        &mut self,
+        response: &egui::Response,
+        lasso: bool,
+        press: Option<Pos2>,
+        pointer: Option<Pos2>,
+    ) -> bool {
+        if self.calibration.pick_mode != PickMode::SelectPoints {
+            self.interaction.point_selection_drag = None;
+            return false;
+        }
+        if response.drag_started_by(egui::PointerButton::Primary)
+            && self.interaction.zoom_region.is_none()
+            && let Some(start) = press
+        {
+            self.interaction.point_selection_drag = Some(PointSelectionDrag {
+                path: vec![start, pointer.unwrap_or(start)],
+                lasso,
+            });
+        }
+        let zoom = self.image.zoom;
+        let Some(drag) = self.interaction.point_selection_drag.as_mut() else {
+            return false;
+        };
+        if let Some(end) = pointer {
+            let far = drag
+                .path
+                .iter()
+                .nth_back(1)
+                .is_none_or(|&prev| prev.distance(end) * zoom >= LASSO_STEP_SCREEN);
+            if drag.lasso && far {
+                drag.path.push(end);
+            } else if let Some(last) = drag.path.last_mut() {
+                *last = end;
+            }
+        }
+        if response.dragged_by(egui::PointerButton::Primary) {
+            return true;
+        }
+        let outline = self
+            .interaction
+            .point_selection_drag
+            .take()
+            .map(|drag| drag.outline())
+            .unwrap_or_default();
+        let extent = Rect::from_points(&outline).size() * zoom;
+        if extent.max_elem() < MIN_SELECTION_SCREEN {
+            self.select_points(Vec::new());
+            return true;
+        }
+        let picked: Vec<usize> = self
+            .points
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| inside_polygon(&outline, p.pixel))
+            .map(|(idx, _)| idx)
+            .collect();
+        let count = picked.len();
+        self.select_points(picked);
+        self.calibration.pick_mode = PickMode::None;
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Selected {count} points."),
+            UiLanguage::Ru => format!("Выбрано точек: {count}."),
+        });
+        true
+    }
+
+    /// Take the unlocked points out of the selection; locked ones stay selected.
+    fn take_unlocked_selection(&mut self) -> Vec<usize> {
+        let (locked, unlocked) = std::mem::take(&mut self.interaction.point_selection)
+            .into_iter()
+            .partition(|&idx| self.point_locked(idx));
+        self.interaction.point_selection = locked;
+        unlocked
+    }
+
+    /// Status `done` of a bulk edit, with a warning when locked points were left selected.
+    fn report_bulk_edit(&mut self, done: String) {
+        let kept = self.interaction.point_selection.len();
+        if kept == 0 {
+            self.set_status(done);
+            return;
+        }
+        self.set_status_warn(match self.ui.language {
+            UiLanguage::En => format!("{done} {kept} locked points were kept."),
+            UiLanguage::Ru => format!("{done} Заблокированные точки оставлены: {kept}."),
+        });
+    }
+
+    /// Delete the selected points of the active curve, except locked ones.
+    pub(crate) fn delete_selected_points(&mut self) {
+        let selected = self.take_unlocked_selection();
+        for &idx in selected.iter().rev() {
+            self.points.points.remove(idx);
+            self.shift_selection_after_removal(idx);
+        }
+        if !selected.is_empty() {
+            self.cancel_pending_error_bar();
+            self.mark_points_dirty();
+        }
+        let count = selected.len();
+        self.report_bulk_edit(match self.ui.language {
+            UiLanguage::En => format!("Deleted {count} points."),
+            UiLanguage::Ru => format!("Удалено точек: {count}."),
+        });
+    }
+
+    /// Shift the selected points by `delta` image pixels.
+    pub(crate) fn nudge_selected_points(&mut self, delta: Vec2) {
+        for idx in self.interaction.point_selection.clone() {
+            self.nudge_point(idx, delta);
+        }
+    }
+
+    /// Move the selected points to the end of curve `target`, keeping their order; locked
+    /// points stay.
+    pub(crate) fn move_selected_points_to_curve(&mut self, target: usize) {
+        if target == self.points.curves.active || target >= self.points.curves.len() {
+            return;
+        }
+        let selected = self.take_unlocked_selection();
+        let mut moved = Vec::with_capacity(selected.len());
+        for &idx in selected.iter().rev() {
+            moved.push(self.points.points.remove(idx));
+            self.shift_selection_after_removal(idx);
+        }
+        moved.reverse();
+        let curve = &mut self.points.curves.curves[target];
+        curve.points.extend(moved);
+        let name = curve.name.clone();
+        self.cancel_pending_error_bar();
+        self.mark_points_dirty();
+        let count = selected.len();
+        self.report_bulk_edit(match self.ui.language {
+            UiLanguage::En => format!("Moved {count} points to “{name}”."),
+            UiLanguage::Ru => format!("Точек перенесено в «{name}»: {count}."),
+        });
+    }
+
+    /// The selection outline being dragged over the image drawn at `rect`.
+    pub(crate) fn draw_point_selection_drag(&self, painter: &egui::Painter, rect: Rect) {
+        let Some(drag) = &self.interaction.point_selection_drag else {
+            return;
+        };
+        let zoom = self.image.zoom;
+        let outline: Vec<Pos2> = drag
+            .outline()
+            .into_iter()
+            .map(|pixel| rect.min + pixel.to_vec2() * zoom)
+            .collect();
+        let stroke = Stroke::new(1.5_f32, SELECTION_COLOR);
+        if drag.lasso {
+            painter.add(egui::Shape::closed_line(outline, stroke));
+        } else {
+            painter.add(egui::Shape::convex_polygon(
+                outline,
+                SELECTION_COLOR.gamma_multiply(0.15),
+                stroke,
+            ));
+        }
+    }
+}
@@ -1,14 +1,54 @@
-use super::{AxisMapping, CurcatApp};
+use super::{AxisMapping, CurcatApp, ErrorBarMode, ImportedDataset, PendingErrorBar};
 use crate::interp::XYPoint;
-use crate::types::{CoordSystem, PolarMapping};
-use egui::Pos2;
+use crate::types::{
+    AxisUnit, AxisValue, CoordSystem, PolarMapping, SmithMapping, YAxis, parse_axis_value,
+};
+use chrono::{DateTime, Utc};
+use egui::{Color32, Pos2, Stroke};
 use std::cmp::Ordering;
+use std::time::SystemTime;
+
+/// Review flag attached to a point, with an ordered thread of comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PointFlag {
+    pub(super) comments: Vec<String>,
+}
+
+impl PointFlag {
+    /// Single-line summary used for export annotations and tooltips.
+    pub(super) fn annotation(&self) -> String {
+        let joined = self
+            .comments
+            .iter()
+            .map(|c| c.trim())
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>()
+            .join("; ");
+        if joined.is_empty() {
+            "flagged".to_string()
+        } else {
+            joined
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct PickedPoint {
     pub(super) pixel: Pos2,
     pub(super) x_numeric: Option<f64>,
     pub(super) y_numeric: Option<f64>,
+    pub(super) flag: Option<PointFlag>,
+    /// Wall-clock time the point was placed, kept only while capture recording is on.
+    pub(super) captured_at: Option<DateTime<Utc>>,
+    /// Pixel positions of the two error bar ends, if picked.
+    pub(super) error_bar: Option<[Pos2; 2]>,
+    /// Distances from the point's Y value down to the lower and up to the upper bar end.
+    pub(super) y_error: Option<[f64; 2]>,
+    /// How surely the point lies on the curve, from 0 to 1, judged by the snap features where
+    /// it was placed; 0 for points placed or moved by hand.
+    pub(super) confidence: f32,
+    /// Kept from dragging, nudging and deletion until unlocked.
+    pub(super) locked: bool,
 }
 
 impl PickedPoint {
@@ -17,13 +57,114 @@ impl PickedPoint {
             pixel,
             x_numeric: None,
             y_numeric: None,
+            flag: None,
+            captured_at: None,
+            error_bar: None,
+            y_error: None,
+            confidence: 0.0,
+            locked: false,
+        }
+    }
+}
+
+/// Colors handed out to added curves, in order; the first curve follows the configured styles.
+const CURVE_PALETTE: [Color32; 8] = [
+    Color32::from_rgb(66, 135, 245),
+    Color32::from_rgb(245, 166, 35),
+    Color32::from_rgb(155, 89, 182),
+    Color32::from_rgb(26, 188, 156),
+    Color32::from_rgb(231, 76, 60),
+    Color32::from_rgb(241, 196, 15),
+    Color32::from_rgb(52, 73, 94),
+    Color32::from_rgb(236, 112, 180),
+];
+
+/// One named series digitized from the image.
+#[derive(Debug, Clone)]
+pub struct Curve {
+    pub(super) name: String,
+    /// Color of the curve's points and segments; `None` follows the configured styles.
+    pub(super) color: Option<Color32>,
+    /// Points while the curve is inactive; empty for the active curve (see [`CurveSet`]).
+    pub(super) points: Vec<PickedPoint>,
+    /// Y axis the curve's values are read against.
+    pub(super) y_axis: YAxis,
+    /// Family parameter as typed, e.g. the temperature the curve was measured at.
+    pub(super) parameter_text: String,
+    /// Locks all of the curve's points at once.
+    pub(super) locked: bool,
+}
+
+impl Curve {
+    pub(super) fn new(name: impl Into<String>, color: Option<Color32>) -> Self {
+        Self {
+            name: name.into(),
+            color,
+            points: Vec::new(),
+            y_axis: YAxis::Primary,
+            parameter_text: String::new(),
+            locked: false,
+        }
+    }
+
+    /// Parsed family parameter; `None` when the text is empty or not a number.
+    pub(super) fn parameter(&self) -> Option<f64> {
+        match parse_axis_value(&self.parameter_text, AxisUnit::Float)? {
+            AxisValue::Float(value) => Some(value),
+            AxisValue::DateTime(_) => None,
         }
     }
 }
 
+/// Curves of the document, one of which receives new points.
+///
+/// The active curve's points live in [`PointsState::points`], so picking, dragging, flags and
+/// undo keep working on a single vector; switching curves swaps the vectors.
+#[derive(Debug, Clone)]
+pub struct CurveSet {
+    pub(super) curves: Vec<Curve>,
+    pub(super) active: usize,
+}
+
+impl Default for CurveSet {
+    fn default() -> Self {
+        Self {
+            curves: vec![Curve::new(Self::default_name(0), None)],
+            active: 0,
+        }
+    }
+}
+
+impl CurveSet {
+    fn default_name(idx: usize) -> String {
+        format!("Curve {}", idx + 1)
+    }
+
+    fn is_default_name(name: &str) -> bool {
+        name.strip_prefix("Curve ")
+            .is_some_and(|n| n.parse::<usize>().is_ok())
+    }
+
+    pub(super) const fn len(&self) -> usize {
+        self.curves.len()
+    }
+
+    /// First free `Curve N` name, so deleting a curve does not produce duplicate names.
+    fn next_name(&self) -> String {
+        // `len + 1` candidates cannot all be taken by `len` curves.
+        let len = self.curves.len();
+        (len..=2 * len)
+            .map(Self::default_name)
+            .find(|name| self.curves.iter().all(|c| &c.name != name))
+            .unwrap_or_default()
+    }
+}
+
 #[allow(clippy::struct_excessive_bools)]
 pub struct PointsState {
+    /// Points of the active curve.
     pub(super) points: Vec<PickedPoint>,
+    pub(super) curves: CurveSet,
     pub(super) points_numeric_dirty: bool,
     pub(super) cached_sorted_preview: Vec<(f64, Pos2)>,
     pub(super) cached_sorted_numeric: Vec<XYPoint>,
@@ -31,9 +172,20 @@ pub struct PointsState {
     pub(super) sorted_numeric_dirty: bool,
     pub(super) last_x_mapping: Option<AxisMapping>,
     pub(super) last_y_mapping: Option<AxisMapping>,
+    pub(super) last_secondary_y_mapping: Option<AxisMapping>,
     pub(super) last_polar_mapping: Option<PolarMapping>,
+    pub(super) last_smith_mapping: Option<SmithMapping>,
     pub(super) last_coord_system: CoordSystem,
     pub(super) show_curve_segments: bool,
+    /// Fade point markers by their snap confidence.
+    pub(super) show_point_confidence: bool,
+    /// Stamp newly placed points with the current wall-clock time.
+    pub(super) record_capture_times: bool,
+    /// Reference dataset pasted from the clipboard; drawn but not exported until appended.
+    pub(super) imported: Option<ImportedDataset>,
+    pub(super) error_bar_mode: ErrorBarMode,
+    /// Point placed in an error bar mode whose bar ends are still being clicked.
+    pub(super) pending_error_bar: Option<PendingErrorBar>,
 }
 
 impl CurcatApp {
@@ -50,45 +202,244 @@ impl CurcatApp {
         y_mapping: Option<&AxisMapping>,
         polar_mapping: Option<&PolarMapping>,
     ) {
+        let secondary_y_mapping = self.secondary_y_mapping();
+        let smith_mapping = self.smith_mapping();
         let mapping_changed = match coord_system {
-            CoordSystem::Cartesian => {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
                 self.points.last_coord_system != coord_system
                     || self.points.last_x_mapping.as_ref() != x_mapping
                     || self.points.last_y_mapping.as_ref() != y_mapping
+                    || self.points.last_secondary_y_mapping != secondary_y_mapping
             }
             CoordSystem::Polar => {
                 self.points.last_coord_system != coord_system
                     || self.points.last_polar_mapping.as_ref() != polar_mapping
             }
+            CoordSystem::Smith => {
+                self.points.last_coord_system != coord_system
+                    || self.points.last_smith_mapping != smith_mapping
+            }
         };
 
         if mapping_changed {
             self.points.last_coord_system = coord_system;
             self.points.last_x_mapping = x_mapping.cloned();
             self.points.last_y_mapping = y_mapping.cloned();
+            self.points
+                .last_secondary_y_mapping
+                .clone_from(&secondary_y_mapping);
             self.points.last_polar_mapping = polar_mapping.cloned();
+            self.points.last_smith_mapping.clone_from(&smith_mapping);
             self.mark_points_dirty();
         }
 
         if self.points.points_numeric_dirty {
-            match coord_system {
-                CoordSystem::Cartesian => {
-                    for p in &mut self.points.points {
-                        p.x_numeric = x_mapping.and_then(|xm| xm.numeric_at(p.pixel));
-                        p.y_numeric = y_mapping.and_then(|ym| ym.numeric_at(p.pixel));
-                    }
-                }
-                CoordSystem::Polar => {
-                    for p in &mut self.points.points {
-                        p.x_numeric = polar_mapping.and_then(|pm| pm.angle_at(p.pixel));
-                        p.y_numeric = polar_mapping.and_then(|pm| pm.radius_at(p.pixel));
-                    }
+            let curves = &mut self.points.curves;
+            let active = curves.active;
+            for (idx, curve) in curves.curves.iter_mut().enumerate() {
+                let points = if idx == active {
+                    &mut self.points.points
+                } else {
+                    &mut curve.points
+                };
+                let y_mapping = match curve.y_axis {
+                    YAxis::Primary => y_mapping,
+                    YAxis::Secondary => secondary_y_mapping.as_ref(),
+                };
+                for p in points {
+                    Self::update_point_numeric(
+                        p,
+                        coord_system,
+                        x_mapping,
+                        y_mapping,
+                        polar_mapping,
+                        smith_mapping.as_ref(),
+                    );
                 }
             }
             self.points.points_numeric_dirty = false;
         }
     }
 
+    fn update_point_numeric(
+        p: &mut PickedPoint,
+        coord_system: CoordSystem,
+        x_mapping: Option<&AxisMapping>,
+        y_mapping: Option<&AxisMapping>,
+        polar_mapping: Option<&PolarMapping>,
+        smith_mapping: Option<&SmithMapping>,
+    ) {
+        match coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
+                p.x_numeric = x_mapping.and_then(|xm| xm.numeric_at(p.pixel));
+                p.y_numeric = y_mapping.and_then(|ym| ym.numeric_at(p.pixel));
+            }
+            CoordSystem::Polar => {
+                p.x_numeric = polar_mapping.and_then(|pm| pm.angle_at(p.pixel));
+                p.y_numeric = polar_mapping.and_then(|pm| pm.radius_at(p.pixel));
+            }
+            CoordSystem::Smith => {
+                let z = smith_mapping.and_then(|sm| sm.impedance_at(p.pixel));
+                p.x_numeric = z.map(|(r, _)| r);
+                p.y_numeric = z.map(|(_, x)| x);
+            }
+        }
+        p.y_error = p.error_bar.and_then(|ends| {
+            let y = p.y_numeric?;
+            let value_at = |end: Pos2| match coord_system {
+                CoordSystem::Cartesian | CoordSystem::Affine => {
+                    y_mapping.and_then(|ym| ym.numeric_at(end))
+                }
+                CoordSystem::Polar => polar_mapping.and_then(|pm| pm.radius_at(end)),
+                CoordSystem::Smith => smith_mapping
+                    .and_then(|sm| sm.impedance_at(end))
+                    .map(|(_, x)| x),
+            };
+            let (a, b) = (value_at(ends[0])?, value_at(ends[1])?);
+            Some([(y - a.min(b)).max(0.0), (a.max(b) - y).max(0.0)])
+        });
+    }
+
+    /// Points of curve `idx`, wherever they are kept.
+    pub(crate) fn curve_points(&self, idx: usize) -> &[PickedPoint] {
+        if idx == self.points.curves.active {
+            &self.points.points
+        } else {
+            self.points
+                .curves
+                .curves
+                .get(idx)
+                .map_or(&[], |curve| curve.points.as_slice())
+        }
+    }
+
+    /// Point color of curve `idx`: its own color or the configured point style.
+    pub(crate) fn curve_point_color(&self, idx: usize) -> Color32 {
+        self.points
+            .curves
+            .curves
+            .get(idx)
+            .and_then(|curve| curve.color)
+            .unwrap_or_else(|| self.curve_point_style().color32())
+    }
+
+    /// Segment stroke of curve `idx`: the configured line style in the curve's color.
+    pub(crate) fn curve_line_stroke(&self, idx: usize) -> Stroke {
+        let mut stroke = self.curve_line_style().stroke();
+        if let Some(color) = self.points.curves.curves.get(idx).and_then(|c| c.color) {
+            stroke.color = color;
+        }
+        stroke
+    }
+
+    /// Make curve `idx` receive new points.
+    pub(crate) fn select_curve(&mut self, idx: usize) {
+        let curves = &mut self.points.curves;
+        if idx == curves.active || idx >= curves.len() {
+            return;
+        }
+        curves.curves[curves.active].points = std::mem::take(&mut self.points.points);
+        self.points.points = std::mem::take(&mut curves.curves[idx].points);
+        curves.active = idx;
+        self.ui.flag_review_selected = None;
+        self.clear_point_selection();
+        self.cancel_pending_error_bar();
+        self.mark_points_dirty();
+    }
+
+    /// Append an empty curve with the next palette color and make it active.
+    pub(crate) fn add_curve(&mut self) {
+        let curves = &mut self.points.curves;
+        let color = CURVE_PALETTE[(curves.len() - 1) % CURVE_PALETTE.len()];
+        let curve = Curve::new(curves.next_name(), Some(color));
+        curves.curves.push(curve);
+        self.select_curve(self.points.curves.len() - 1);
+    }
+
+    /// Whether the active curve has no points and still carries an automatic `Curve N` name.
+    pub(crate) fn active_curve_is_blank(&self) -> bool {
+        let curves = &self.points.curves;
+        self.points.points.is_empty()
+            && CurveSet::is_default_name(&curves.curves[curves.active].name)
+    }
+
+    /// Delete curve `idx` with its points; the last remaining curve cannot be removed.
+    pub(crate) fn remove_curve(&mut self, idx: usize) -> bool {
+        let curves = &mut self.points.curves;
+        if curves.len() <= 1 || idx >= curves.len() {
+            return false;
+        }
+        curves.curves.remove(idx);
+        if idx == curves.active {
+            curves.active = idx.min(curves.len() - 1);
+            self.points.points = std::mem::take(&mut curves.curves[curves.active].points);
+            self.ui.flag_review_selected = None;
+            self.clear_point_selection();
+            self.cancel_pending_error_bar();
+        } else if idx < curves.active {
+            curves.active -= 1;
+        }
+        self.mark_points_dirty();
+        true
+    }
+
+    /// Whether any curve is read against the secondary Y axis of a Cartesian calibration.
+    pub(crate) fn uses_secondary_y_axis(&self) -> bool {
+        self.calibration.coord_system == CoordSystem::Cartesian
+            && self
+                .points
+                .curves
+                .curves
+                .iter()
+                .any(|curve| curve.y_axis == YAxis::Secondary)
+    }
+
+    /// Read curve `idx` against `axis` from now on.
+    pub(crate) fn set_curve_y_axis(&mut self, idx: usize, axis: YAxis) {
+        if let Some(curve) = self.points.curves.curves.get_mut(idx) {
+            curve.y_axis = axis;
+            self.mark_points_dirty();
+        }
+    }
+
+    /// Trim a curve name after editing; an emptied name falls back to a free `Curve N`.
+    pub(crate) fn normalize_curve_name(&mut self, idx: usize) {
+        let curves = &mut self.points.curves;
+        let Some(curve) = curves.curves.get_mut(idx) else {
+            return;
+        };
+        let trimmed = curve.name.trim().to_string();
+        curves.curves[idx].name = if trimmed.is_empty() {
+            curves.next_name()
+        } else {
+            trimmed
+        };
+    }
+
+    /// Drop every curve and start over with a single empty one.
+    pub(crate) fn reset_curves(&mut self) {
+        self.points.points.clear();
+        self.points.curves = CurveSet::default();
+        self.ui.flag_review_selected = None;
+        self.clear_point_selection();
+        self.cancel_pending_error_bar();
+        self.mark_points_dirty();
+    }
+
+    /// Replace all curves, e.g. from a project; `active` is clamped to the list.
+    pub(crate) fn set_curves(&mut self, mut curves: Vec<Curve>, active: usize) {
+        if curves.is_empty() {
+            curves.push(CurveSet::default().curves.remove(0));
+        }
+        let active = active.min(curves.len() - 1);
+        self.points.points = std::mem::take(&mut curves[active].points);
+        self.points.curves = CurveSet { curves, active };
+        self.ui.flag_review_selected = None;
+        self.clear_point_selection();
+        self.cancel_pending_error_bar();
+        self.mark_points_dirty();
+    }
+
     pub(crate) fn sorted_preview_segments(&mut self) -> &[(f64, Pos2)] {
         if self.points.sorted_preview_dirty {
             self.points.cached_sorted_preview.clear();
@@ -121,14 +472,32 @@ impl CurcatApp {
         &self.points.cached_sorted_numeric
     }
 
+    /// New point at `pixel`, stamped with the capture time when recording is enabled.
+    pub(crate) fn new_picked_point(&self, pixel: Pos2) -> PickedPoint {
+        let mut point = PickedPoint::new(pixel);
+        if self.points.record_capture_times {
+            point.captured_at = Some(DateTime::<Utc>::from(SystemTime::now()));
+        }
+        point
+    }
+
+    /// New point at `pixel` with the confidence of a snap there in the current input mode.
+    pub(crate) fn new_snapped_point(&mut self, pixel: Pos2) -> PickedPoint {
+        let mut point = self.new_picked_point(pixel);
+        point.confidence = self.snap_confidence(pixel);
+        point
+    }
+
     pub(crate) fn push_curve_point(&mut self, pixel_hint: Pos2) {
         let resolved = self.resolve_curve_pick(pixel_hint);
-        self.points.points.push(PickedPoint::new(resolved));
+        let point = self.new_snapped_point(resolved);
+        self.points.points.push(point);
         self.mark_points_dirty();
     }
 
     pub(crate) fn push_curve_point_snapped(&mut self, snapped: Pos2) {
-        self.points.points.push(PickedPoint::new(snapped));
+        let point = self.new_snapped_point(snapped);
+        self.points.points.push(point);
         self.mark_points_dirty();
     }
 
@@ -138,12 +507,102 @@ impl CurcatApp {
 
     pub(crate) fn clear_all_points(&mut self) {
         self.points.points.clear();
+        self.ui.flag_review_selected = None;
+        self.clear_point_selection();
+        self.cancel_pending_error_bar();
         self.mark_points_dirty();
     }
 
+    pub(crate) fn toggle_point_flag(&mut self, idx: usize) -> Option<bool> {
+        let point = self.points.points.get_mut(idx)?;
+        if point.flag.take().is_some() {
+            Some(false)
+        } else {
+            point.flag = Some(PointFlag::default());
+            Some(true)
+        }
+    }
+
+    pub(crate) fn add_point_flag_comment(&mut self, idx: usize, comment: &str) -> bool {
+        let trimmed = comment.trim();
+        if trimmed.is_empty() {
+            return false;
+        }
+        let Some(point) = self.points.points.get_mut(idx) else {
+            return false;
+        };
+        point
+            .flag
+            .get_or_insert_with(PointFlag::default)
+            .comments
+            .push(trimmed.to_string());
+        true
+    }
+
+    pub(crate) fn flagged_point_count(&self) -> usize {
+        self.points
+            .points
+            .iter()
+            .filter(|p| p.flag.is_some())
+            .count()
+    }
+
     pub(crate) fn undo_last_point(&mut self) {
+        if let Some(last) = self.points.points.len().checked_sub(1)
+            && self.point_locked(last)
+        {
+            self.warn_point_locked();
+            return;
+        }
         if self.points.points.pop().is_some() {
+            self.shift_selection_after_removal(self.points.points.len());
+            self.cancel_pending_error_bar();
             self.mark_points_dirty();
         }
     }
+
+    /// Keep the review and point selections on the same point after `removed` is deleted.
+    pub(crate) fn shift_selection_after_removal(&mut self, removed: usize) {
+        let shift = |selected: Option<usize>| match selected {
+            Some(idx) if idx == removed => None,
+            Some(idx) if idx > removed => Some(idx - 1),
+            other => other,
+        };
+        self.ui.flag_review_selected = shift(self.ui.flag_review_selected);
+        self.map_selected_point(|idx| shift(Some(idx)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_curves_keeps_each_curves_points() {
+        let mut app = CurcatApp::default();
+        app.push_curve_point_snapped(Pos2::new(1.0, 1.0));
+        app.add_curve();
+        assert_eq!(app.points.curves.active, 1);
+        assert!(app.points.points.is_empty());
+        app.push_curve_point_snapped(Pos2::new(2.0, 2.0));
+        app.push_curve_point_snapped(Pos2::new(3.0, 3.0));
+
+        app.select_curve(0);
+        assert_eq!(app.points.points.len(), 1);
+        assert_eq!(app.curve_points(1).len(), 2);
+        assert_eq!(app.points.curves.curves[1].name, "Curve 2");
+        assert!(app.points.curves.curves[1].color.is_some());
+
+        // Removing the active curve activates its neighbour and brings its points along.
+        assert!(app.remove_curve(0));
+        assert_eq!(app.points.curves.active, 0);
+        assert_eq!(app.points.points.len(), 2);
+        assert!(!app.remove_curve(0));
+
+        app.add_curve();
+        assert_eq!(app.points.curves.curves[1].name, "Curve 3");
+        app.points.curves.curves[1].name = "  ".to_string();
+        app.normalize_curve_name(1);
+        assert_eq!(app.points.curves.curves[1].name, "Curve 3");
+    }
 }
@@ -1,12 +1,17 @@
 use super::{
-    AxisCalUi, CurcatApp, MAX_ZOOM, MIN_ZOOM, NativeDialog, PendingImageLimitPrompt,
-    PendingImageTask, PickMode, PickedPoint, PolarCalUi, ZoomIntent,
+    AffineCalUi, AffineRefUi, AxisCalUi, AxisTimeUi, ColorSwatch, CurcatApp, Curve, DeferredAction,
+    MAX_ZOOM, MIN_ZOOM, NativeDialog, PendingImageLimitPrompt, PendingImageTask,
+    PendingSvgScalePrompt, PickMode, PickedPoint, PointFlag, PolarCalUi, SmithCalUi, StitchState,
+    ZoomIntent,
 };
+use crate::config::ConfigOverrides;
+use crate::error::CurcatError;
 use crate::i18n::UiLanguage;
 use crate::image::ImageTransformRecord;
 use crate::project;
 use crate::types::{AxisUnit, ScaleKind};
-use egui::{Pos2, Vec2};
+use crate::validation::ValidationRule;
+use egui::{Color32, Pos2, Vec2};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 
@@ -14,22 +19,20 @@ use std::sync::mpsc::{self, Receiver, TryRecvError};
 pub(super) struct ProjectSaveRequest {
     pub(super) target_path: PathBuf,
     pub(super) image_path: PathBuf,
-    pub(super) transform: ImageTransformRecord,
-    pub(super) calibration: project::CalibrationRecord,
-    pub(super) points: Vec<project::PointRecord>,
-    pub(super) zoom: f32,
-    pub(super) pan: [f32; 2],
-    pub(super) title: Option<String>,
-    pub(super) description: Option<String>,
+    /// Document state; the worker fills in the image path and checksum fields.
+    pub(super) payload: project::ProjectPayload,
 }
 
 pub(super) struct PendingProjectSave {
     pub(super) rx: Receiver<ProjectSaveResult>,
+    pub(super) path: PathBuf,
+    /// Document fingerprint at the time the save request was built.
+    pub(super) fingerprint: u64,
 }
 
 pub(super) enum ProjectSaveResult {
     Success,
-    Error(String),
+    Error(anyhow::Error),
 }
 
 #[derive(Debug)]
@@ -49,48 +52,60 @@ pub(super) struct ProjectLoadPrompt {
 pub struct ProjectState {
     pub(super) pending_image_task: Option<PendingImageTask>,
     pub(super) pending_image_limit_prompt: Option<PendingImageLimitPrompt>,
+    pub(super) pending_svg_scale_prompt: Option<PendingSvgScalePrompt>,
     pub(super) pending_project_apply: Option<ProjectApplyPlan>,
     pub(super) pending_project_save: Option<PendingProjectSave>,
     pub(super) project_prompt: Option<ProjectLoadPrompt>,
+    /// Fingerprint of the document as last loaded or saved; see `has_unsaved_changes`.
+    pub(super) saved_fingerprint: Option<u64>,
+    /// Action waiting on the save / discard / cancel prompt.
+    pub(super) unsaved_prompt: Option<DeferredAction>,
+    /// Action to resume once the project save started from that prompt succeeds.
+    pub(super) after_save: Option<DeferredAction>,
+    /// Set once the user agreed to close, so the next close request goes through.
+    pub(super) close_confirmed: bool,
+    /// Project file the current document was loaded from or last saved to.
+    pub(super) document_path: Option<PathBuf>,
+    /// Last session's project, offered for reopening at startup.
+    pub(super) restore_prompt: Option<PathBuf>,
+    /// Config values the current document carries; stored in its project file.
+    pub(super) config_overrides: ConfigOverrides,
+    /// Named colors of the current document; stored in its project file.
+    pub(super) swatches: Vec<ColorSwatch>,
     pub(super) title: Option<String>,
     pub(super) description: Option<String>,
+    /// X and Y axis titles; when set they name the exported x/y columns.
+    pub(super) axis_titles: [String; 2],
+    /// Secondary Y axis title; when set it names the column of curves read against that axis.
+    pub(super) secondary_y_title: String,
+    /// Exports made from the current document, oldest first; stored in its project file.
+    pub(super) export_history: Vec<project::ExportHistoryRecord>,
+    /// Checks run on exported rows before export; stored in its project file.
+    pub(super) validation_rules: Vec<ValidationRule>,
+    /// Header of the exported curve family parameter; empty uses `parameter`.
+    pub(super) parameter_name: String,
     pub(super) active_dialog: Option<NativeDialog>,
     pub(super) last_project_dir: Option<PathBuf>,
     pub(super) last_project_path: Option<PathBuf>,
     pub(super) last_image_dir: Option<PathBuf>,
     pub(super) last_export_dir: Option<PathBuf>,
+    /// Scans queued in the stitching window.
+    pub(super) stitch: StitchState,
 }
 
-fn perform_project_save(request: ProjectSaveRequest) -> Result<(), String> {
+fn perform_project_save(request: ProjectSaveRequest) -> anyhow::Result<()> {
     let ProjectSaveRequest {
         target_path,
         image_path,
-        transform,
-        calibration,
-        points,
-        zoom,
-        pan,
-        title,
-        description,
+        mut payload,
     } = request;
     let absolute_image_path = std::fs::canonicalize(&image_path).unwrap_or(image_path);
-    let image_crc32 =
-        project::compute_image_crc32(&absolute_image_path).map_err(|err| err.to_string())?;
-    let relative_image_path = project::make_relative_image_path(&target_path, &absolute_image_path)
-        .or_else(|| absolute_image_path.file_name().map(PathBuf::from));
-    let payload = project::ProjectPayload {
-        absolute_image_path,
-        relative_image_path,
-        image_crc32,
-        transform,
-        calibration,
-        points,
-        zoom,
-        pan,
-        title,
-        description,
-    };
-    project::save_project(&target_path, &payload).map_err(|err| err.to_string())
+    payload.image_crc32 = project::compute_image_crc32(&absolute_image_path)?;
+    payload.relative_image_path =
+        project::make_relative_image_path(&target_path, &absolute_image_path)
+            .or_else(|| absolute_image_path.file_name().map(PathBuf::from));
+    payload.absolute_image_path = absolute_image_path;
+    project::save_project(&target_path, &payload)
 }
 
 impl CurcatApp {
@@ -137,6 +152,24 @@ impl CurcatApp {
         )
     }
 
+    fn point_from_record(p: &project::PointRecord) -> PickedPoint {
+        PickedPoint {
+            pixel: Pos2::new(p.pixel[0], p.pixel[1]),
+            x_numeric: p.x_numeric,
+            y_numeric: p.y_numeric,
+            flag: p.flag.as_ref().map(|flag| PointFlag {
+                comments: flag.comments.clone(),
+            }),
+            captured_at: p
+                .captured_at_ms
+                .and_then(chrono::DateTime::from_timestamp_millis),
+            error_bar: p.error_bar.map(|ends| ends.map(|[x, y]| Pos2::new(x, y))),
+            y_error: None,
+            confidence: p.confidence,
+            locked: p.locked,
+        }
+    }
+
     fn polar_to_record(polar: &PolarCalUi) -> project::PolarCalibrationRecord {
         project::PolarCalibrationRecord {
             origin: polar.origin.map(|p| [p.x, p.y]),
@@ -162,6 +195,142 @@ impl CurcatApp {
         }
     }
 
+    fn affine_to_record(affine: &AffineCalUi) -> project::AffineCalibrationRecord {
+        project::AffineCalibrationRecord {
+            x_unit: affine.x_unit,
+            x_scale: affine.x_scale,
+            y_unit: affine.y_unit,
+            y_scale: affine.y_scale,
+            points: affine
+                .points
+                .iter()
+                .map(|point| project::AffineReferenceRecord {
+                    pixel: point.pixel.map(|p| [p.x, p.y]),
+                    x_text: point.x_text.clone(),
+                    y_text: point.y_text.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn affine_from_record(record: &project::AffineCalibrationRecord) -> AffineCalUi {
+        let mut points: Vec<AffineRefUi> = record
+            .points
+            .iter()
+            .map(|point| AffineRefUi {
+                pixel: point.pixel.map(|p| Pos2::new(p[0], p[1])),
+                x_text: point.x_text.clone(),
+                y_text: point.y_text.clone(),
+            })
+            .collect();
+        if points.len() < AffineCalUi::MIN_POINTS {
+            points.resize_with(AffineCalUi::MIN_POINTS, AffineRefUi::default);
+        }
+        AffineCalUi {
+            x_unit: record.x_unit,
+            // Log scales only apply to float axes.
+            x_scale: if record.x_unit == AxisUnit::Float {
+                record.x_scale
+            } else {
+                ScaleKind::Linear
+            },
+            y_unit: record.y_unit,
+            y_scale: if record.y_unit == AxisUnit::Float {
+                record.y_scale
+            } else {
+                ScaleKind::Linear
+            },
+            x_time: AxisTimeUi::default(),
+            y_time: AxisTimeUi::default(),
+            points,
+        }
+    }
+
+    fn smith_to_record(smith: &SmithCalUi) -> project::SmithCalibrationRecord {
+        project::SmithCalibrationRecord {
+            center: smith.center.map(|p| [p.x, p.y]),
+            short: smith.short.map(|p| [p.x, p.y]),
+            reference_text: smith.reference_text.clone(),
+        }
+    }
+
+    fn smith_from_record(record: &project::SmithCalibrationRecord) -> SmithCalUi {
+        SmithCalUi {
+            center: record.center.map(|p| Pos2::new(p[0], p[1])),
+            short: record.short.map(|p| Pos2::new(p[0], p[1])),
+            reference_text: if record.reference_text.is_empty() {
+                SmithCalUi::DEFAULT_REFERENCE.to_string()
+            } else {
+                record.reference_text.clone()
+            },
+        }
+    }
+
+    fn time_to_record(time: &AxisTimeUi) -> project::AxisTimeRecord {
+        project::AxisTimeRecord {
+            pattern: time.pattern.clone(),
+            offset_text: time.offset_text.clone(),
+        }
+    }
+
+    fn time_from_record(record: &project::AxisTimeRecord) -> AxisTimeUi {
+        AxisTimeUi {
+            pattern: record.pattern.clone(),
+            offset_text: record.offset_text.clone(),
+        }
+    }
+
+    /// Calibration of every coordinate system as stored in projects.
+    pub(crate) fn calibration_record(&self) -> project::CalibrationRecord {
+        project::CalibrationRecord {
+            coord_system: self.calibration.coord_system,
+            x: Self::axis_to_record(&self.calibration.cal_x),
+            y: Self::axis_to_record(&self.calibration.cal_y),
+            polar: Self::polar_to_record(&self.calibration.polar_cal),
+            affine: Self::affine_to_record(&self.calibration.affine_cal),
+            calibration_angle_snap: self.calibration.calibration_angle_snap,
+            show_calibration_segments: self.calibration.show_calibration_segments,
+            secondary_y: Self::axis_to_record(&self.calibration.cal_secondary_y),
+            inverted_axes: project::InvertedAxesRecord {
+                x: self.calibration.cal_x.inverted,
+                y: self.calibration.cal_y.inverted,
+                secondary_y: self.calibration.cal_secondary_y.inverted,
+            },
+            smith: Self::smith_to_record(&self.calibration.smith_cal),
+            time_inputs: project::TimeInputsRecord {
+                x: Self::time_to_record(&self.calibration.cal_x.time),
+                y: Self::time_to_record(&self.calibration.cal_y.time),
+                secondary_y: Self::time_to_record(&self.calibration.cal_secondary_y.time),
+                affine_x: Self::time_to_record(&self.calibration.affine_cal.x_time),
+                affine_y: Self::time_to_record(&self.calibration.affine_cal.y_time),
+            },
+        }
+    }
+
+    /// Replace the calibration with `record`; the caller refreshes point values.
+    pub(crate) fn apply_calibration_record(&mut self, record: &project::CalibrationRecord) {
+        self.calibration.auto_review = None;
+        self.calibration.recalibration_review = None;
+        self.calibration.cal_x = Self::axis_from_record(&record.x);
+        self.calibration.cal_y = Self::axis_from_record(&record.y);
+        self.calibration.cal_secondary_y = Self::axis_from_record(&record.secondary_y);
+        self.calibration.cal_x.inverted = record.inverted_axes.x;
+        self.calibration.cal_y.inverted = record.inverted_axes.y;
+        self.calibration.cal_secondary_y.inverted = record.inverted_axes.secondary_y;
+        self.calibration.polar_cal = Self::polar_from_record(&record.polar);
+        self.calibration.affine_cal = Self::affine_from_record(&record.affine);
+        self.calibration.smith_cal = Self::smith_from_record(&record.smith);
+        let times = &record.time_inputs;
+        self.calibration.cal_x.time = Self::time_from_record(&times.x);
+        self.calibration.cal_y.time = Self::time_from_record(&times.y);
+        self.calibration.cal_secondary_y.time = Self::time_from_record(&times.secondary_y);
+        self.calibration.affine_cal.x_time = Self::time_from_record(&times.affine_x);
+        self.calibration.affine_cal.y_time = Self::time_from_record(&times.affine_y);
+        self.calibration.coord_system = record.coord_system;
+        self.calibration.calibration_angle_snap = record.calibration_angle_snap;
+        self.calibration.show_calibration_segments = record.show_calibration_segments;
+    }
+
     fn build_project_save_request(
         &mut self,
         target_path: &Path,
@@ -184,37 +353,69 @@ impl CurcatApp {
             polar_mapping.as_ref(),
         );
 
-        let points = self
-            .points
+        Ok(ProjectSaveRequest {
+            target_path: target_path.to_path_buf(),
+            image_path,
+            payload: self.project_payload(),
+        })
+    }
+
+    /// Everything a project file stores about the current document, with the image path and
+    /// checksum fields left empty.
+    pub(super) fn project_payload(&self) -> project::ProjectPayload {
+        let curves = self
             .points
+            .curves
+            .curves
             .iter()
-            .map(|p| project::PointRecord {
-                pixel: [p.pixel.x, p.pixel.y],
-                x_numeric: p.x_numeric,
-                y_numeric: p.y_numeric,
+            .enumerate()
+            .map(|(idx, curve)| project::CurveRecord {
+                name: curve.name.clone(),
+                color: curve.color.map(|c| c.to_array()),
+                points: self
+                    .curve_points(idx)
+                    .iter()
+                    .map(|p| project::PointRecord {
+                        pixel: [p.pixel.x, p.pixel.y],
+                        x_numeric: p.x_numeric,
+                        y_numeric: p.y_numeric,
+                        flag: p.flag.as_ref().map(|flag| project::PointFlagRecord {
+                            comments: flag.comments.clone(),
+                        }),
+                        captured_at_ms: p.captured_at.map(|t| t.timestamp_millis()),
+                        error_bar: p.error_bar.map(|ends| ends.map(|end| [end.x, end.y])),
+                        confidence: p.confidence,
+                        locked: p.locked,
+                    })
+                    .collect(),
+                y_axis: curve.y_axis,
+                parameter_text: curve.parameter_text.clone(),
+                locked: curve.locked,
             })
             .collect();
 
-        let calibration = project::CalibrationRecord {
-            coord_system: self.calibration.coord_system,
-            x: Self::axis_to_record(&self.calibration.cal_x),
-            y: Self::axis_to_record(&self.calibration.cal_y),
-            polar: Self::polar_to_record(&self.calibration.polar_cal),
-            calibration_angle_snap: self.calibration.calibration_angle_snap,
-            show_calibration_segments: self.calibration.show_calibration_segments,
-        };
-
-        Ok(ProjectSaveRequest {
-            target_path: target_path.to_path_buf(),
-            image_path,
+        project::ProjectPayload {
+            absolute_image_path: PathBuf::new(),
+            relative_image_path: None,
+            image_crc32: 0,
+            perspective: self.image.perspective_warps.clone(),
             transform: self.image.transform,
-            calibration,
-            points,
+            calibration: self.calibration_record(),
+            curves,
+            active_curve: self.points.curves.active,
             zoom: self.image.zoom,
             pan: [self.image.pan.x, self.image.pan.y],
             title: self.project.title.clone(),
             description: self.project.description.clone(),
-        })
+            config_overrides: self.config_overrides_for_save(),
+            swatches: self.swatches_to_records(),
+            svg_scale_percent: self.image.svg_scale_percent,
+            axis_titles: self.project.axis_titles.clone(),
+            secondary_y_title: self.project.secondary_y_title.clone(),
+            export_history: self.project.export_history.clone(),
+            validation_rules: self.project.validation_rules.clone(),
+            parameter_name: self.project.parameter_name.clone(),
+        }
     }
 
     pub(super) fn handle_project_save(&mut self, path: &Path) {
@@ -229,14 +430,15 @@ impl CurcatApp {
         self.project.last_project_dir = path.parent().map(Path::to_path_buf);
         match self.build_project_save_request(path) {
             Ok(request) => self.start_project_save_job(request),
-            Err(err) => self.set_status_error(match self.ui.language {
-                UiLanguage::En => format!("Project save failed: {err}"),
-                UiLanguage::Ru => format!("Ошибка сохранения проекта: {err}"),
-            }),
+            Err(source) => {
+                self.project.after_save = None;
+                self.report_error(&CurcatError::ProjectSave { source });
+            }
         }
     }
 
     fn start_project_save_job(&mut self, request: ProjectSaveRequest) {
+        let path = request.target_path.clone();
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
             let result = match perform_project_save(request) {
@@ -245,54 +447,68 @@ impl CurcatApp {
             };
             let _ = tx.send(result);
         });
-        self.project.pending_project_save = Some(PendingProjectSave { rx });
+        self.project.pending_project_save = Some(PendingProjectSave {
+            rx,
+            path,
+            fingerprint: self.document_fingerprint(),
+        });
         self.set_status(match self.ui.language {
             UiLanguage::En => "Saving project…",
             UiLanguage::Ru => "Сохранение проекта…",
         });
     }
 
-    pub(super) fn poll_project_save_job(&mut self) {
+    /// Returns `true` on the frame a save finishes successfully.
+    pub(super) fn poll_project_save_job(&mut self) -> bool {
         let Some(job) = self.project.pending_project_save.take() else {
-            return;
+            return false;
         };
         match job.rx.try_recv() {
             Ok(ProjectSaveResult::Success) => {
+                self.project.saved_fingerprint = Some(job.fingerprint);
+                self.remember_document_project(job.path);
                 self.set_status(match self.ui.language {
                     UiLanguage::En => "Project saved.",
                     UiLanguage::Ru => "Проект сохранён.",
                 });
+                return true;
             }
-            Ok(ProjectSaveResult::Error(err)) => {
-                self.set_status_error(match self.ui.language {
-                    UiLanguage::En => format!("Project save failed: {err}"),
-                    UiLanguage::Ru => format!("Ошибка сохранения проекта: {err}"),
-                });
+            Ok(ProjectSaveResult::Error(source)) => {
+                self.project.after_save = None;
+                self.report_error(&CurcatError::ProjectSave { source });
             }
             Err(TryRecvError::Empty) => {
                 self.project.pending_project_save = Some(job);
             }
             Err(TryRecvError::Disconnected) => {
+                self.project.after_save = None;
                 self.set_status_error(match self.ui.language {
                     UiLanguage::En => "Project save failed: worker disconnected.",
                     UiLanguage::Ru => "Ошибка сохранения проекта: рабочий поток отключился.",
                 });
             }
         }
+        false
     }
 
     pub(super) fn handle_project_load(&mut self, path: PathBuf) {
+        if let Some(DeferredAction::LoadProject(path)) =
+            self.defer_for_unsaved_changes(DeferredAction::LoadProject(path))
+        {
+            self.load_project_from(path);
+        }
+    }
+
+    pub(super) fn load_project_from(&mut self, path: PathBuf) {
         self.project.project_prompt = None;
         self.project.pending_image_limit_prompt = None;
+        self.project.pending_svg_scale_prompt = None;
         self.project.pending_project_apply = None;
         self.project.last_project_dir = path.parent().map(Path::to_path_buf);
         self.project.last_project_path = Some(path.clone());
         match project::load_project(&path) {
             Ok(outcome) => self.handle_loaded_project(path, outcome),
-            Err(err) => self.set_status_error(match self.ui.language {
-                UiLanguage::En => format!("Failed to load project: {err}"),
-                UiLanguage::Ru => format!("Не удалось загрузить проект: {err}"),
-            }),
+            Err(source) => self.report_error(&CurcatError::ProjectLoad { path, source }),
         }
     }
 
@@ -323,6 +539,7 @@ impl CurcatApp {
         let image_path = plan.image.path.clone();
         self.project.project_prompt = None;
         self.project.pending_image_limit_prompt = None;
+        self.project.pending_svg_scale_prompt = None;
         let status = {
             let source_label =
                 Self::project_source_label(self.ui.language, plan.image.source, false);
@@ -378,8 +595,15 @@ impl CurcatApp {
         self.project.project_prompt = None;
         self.project.pending_project_apply = None;
 
-        // Reapply transforms on freshly loaded image.
+        // Reapply warps and transforms on freshly loaded image.
         self.image.transform = ImageTransformRecord::identity();
+        self.image.perspective_warps.clear();
+        for warp in &plan.payload.perspective {
+            for op in warp.prior.replay_operations() {
+                self.apply_image_transform(op, None);
+            }
+            self.apply_perspective_warp(warp.corners.map(|[x, y]| Pos2::new(x, y)));
+        }
         let ops = plan.payload.transform.replay_operations();
         for op in ops {
             self.apply_image_transform(op, None);
@@ -394,17 +618,30 @@ impl CurcatApp {
         self.project
             .description
             .clone_from(&plan.payload.description);
+        self.project
+            .axis_titles
+            .clone_from(&plan.payload.axis_titles);
+        self.project
+            .secondary_y_title
+            .clone_from(&plan.payload.secondary_y_title);
+        self.project
+            .export_history
+            .clone_from(&plan.payload.export_history);
+        self.project
+            .validation_rules
+            .clone_from(&plan.payload.validation_rules);
+        self.project
+            .parameter_name
+            .clone_from(&plan.payload.parameter_name);
+        self.set_config_overrides(plan.payload.config_overrides.clone());
+        self.set_swatches_from_records(&plan.payload.swatches);
 
-        self.calibration.cal_x = Self::axis_from_record(&plan.payload.calibration.x);
-        self.calibration.cal_y = Self::axis_from_record(&plan.payload.calibration.y);
-        self.calibration.polar_cal = Self::polar_from_record(&plan.payload.calibration.polar);
-        self.calibration.coord_system = plan.payload.calibration.coord_system;
-        self.calibration.calibration_angle_snap = plan.payload.calibration.calibration_angle_snap;
-        self.calibration.show_calibration_segments =
-            plan.payload.calibration.show_calibration_segments;
+        self.apply_calibration_record(&plan.payload.calibration);
         self.points.last_x_mapping = None;
         self.points.last_y_mapping = None;
+        self.points.last_secondary_y_mapping = None;
         self.points.last_polar_mapping = None;
+        self.points.last_smith_mapping = None;
         self.points.last_coord_system = self.calibration.coord_system;
         self.calibration.pick_mode = PickMode::None;
         self.calibration.pending_value_focus = None;
@@ -412,24 +649,31 @@ impl CurcatApp {
         self.image.touch_pan_active = false;
         self.image.touch_pan_last = None;
 
-        self.points.points = plan
+        let curves = plan
             .payload
-            .points
+            .curves
             .iter()
-            .map(|p| PickedPoint {
-                pixel: Pos2::new(p.pixel[0], p.pixel[1]),
-                x_numeric: p.x_numeric,
-                y_numeric: p.y_numeric,
+            .map(|record| Curve {
+                name: record.name.clone(),
+                color: record
+                    .color
+                    .map(|[r, g, b, a]| Color32::from_rgba_premultiplied(r, g, b, a)),
+                points: record.points.iter().map(Self::point_from_record).collect(),
+                y_axis: record.y_axis,
+                parameter_text: record.parameter_text.clone(),
+                locked: record.locked,
             })
             .collect();
-        self.mark_points_dirty();
+        self.set_curves(curves, plan.payload.active_curve);
+        self.ui.flag_comment_draft.clear();
         self.mark_snap_maps_dirty();
         self.refresh_snap_overlay_palette();
 
         if let Some(parent) = plan.project_path.parent() {
             self.project.last_project_dir = Some(parent.to_path_buf());
         }
-        self.project.last_project_path = Some(plan.project_path);
+        self.project.last_project_path = Some(plan.project_path.clone());
+        self.remember_document_project(plan.project_path);
         self.remember_image_dir_from_path(&plan.image.path);
 
         if plan.image.checksum_matches {
@@ -455,6 +699,27 @@ impl CurcatApp {
         }
     }
 
+    /// Record `path` as the current document's project and as the one to restore next launch.
+    fn remember_document_project(&mut self, path: PathBuf) {
+        self.project.document_path = Some(path.clone());
+        if let Err(err) = self.config.persist_last_project(Some(path)) {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => format!("Failed to remember the last project: {err}"),
+                UiLanguage::Ru => format!("Не удалось запомнить последний проект: {err}"),
+            });
+        }
+    }
+
+    /// Offer reopening the last session's project when it still exists.
+    pub(super) fn offer_session_restore(&mut self) {
+        self.project.restore_prompt = self
+            .config
+            .ui
+            .last_project
+            .clone()
+            .filter(|path| path.is_file());
+    }
+
     pub(super) fn project_warning_text(&self, warn: &project::ProjectWarning) -> String {
         match warn {
             project::ProjectWarning::MissingImage {
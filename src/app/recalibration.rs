@@ -0,0 +1,161 @@
+//! Recalibration against a pasted reference dataset: slide the axis points so the picked
+//! points of the active curve read the reference values, then keep or revert the result.
+
+use super::{AxisCalUi, CurcatApp};
+use crate::i18n::UiLanguage;
+use crate::interp::XYPoint;
+use crate::types::{AxisMapping, CoordSystem};
+use egui::Pos2;
+
+/// Fitted calibration waiting for confirmation.
+#[derive(Debug, Clone)]
+pub struct RecalibrationReview {
+    /// Picked points matched to a reference point.
+    pub(super) pairs: usize,
+    /// RMS residual on X and Y against the reference, before and after the fit.
+    pub(super) rms_before: [f64; 2],
+    pub(super) rms_after: [f64; 2],
+    /// Axis calibrations from before the first fit, restored on revert.
+    previous: (AxisCalUi, AxisCalUi),
+}
+
+/// RMS of the X and Y differences between what `pairs` read and their reference values.
+fn residual_rms(x: &AxisMapping, y: &AxisMapping, pairs: &[(Pos2, XYPoint)]) -> [f64; 2] {
+    let mut sums = [0.0, 0.0];
+    for (pixel, reference) in pairs {
+        let dx = x.numeric_at(*pixel).map_or(0.0, |v| v - reference.x);
+        let dy = y.numeric_at(*pixel).map_or(0.0, |v| v - reference.y);
+        sums[0] = dx.mul_add(dx, sums[0]);
+        sums[1] = dy.mul_add(dy, sums[1]);
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let n = pairs.len().max(1) as f64;
+    sums.map(|sum| (sum / n).sqrt())
+}
+
+impl CurcatApp {
+    /// Picked points of the active curve, each with the pasted reference point drawn nearest
+    /// to it under the current calibration.
+    fn reference_pairs(&self, x: &AxisMapping, y: &AxisMapping) -> Vec<(Pos2, XYPoint)> {
+        let Some(dataset) = &self.points.imported else {
+            return Vec::new();
+        };
+        let references: Vec<(Pos2, XYPoint)> = dataset
+            .points
+            .iter()
+            .filter_map(|p| Some((AxisMapping::pixel_at(x, y, p.x, p.y)?, *p)))
+            .collect();
+        self.points
+            .points
+            .iter()
+            .filter_map(|picked| {
+                references
+                    .iter()
+                    .min_by(|a, b| {
+                        a.0.distance_sq(picked.pixel)
+                            .total_cmp(&b.0.distance_sq(picked.pixel))
+                    })
+                    .map(|&(_, reference)| (picked.pixel, reference))
+            })
+            .collect()
+    }
+
+    /// Least-squares fit of the Cartesian axes to the pasted reference.
+    ///
+    /// Each axis keeps its values and moves its two points along the axis line; an axis the
+    /// matched references do not spread along is left as it is.
+    pub(crate) fn recalibrate_to_reference(&mut self) {
+        if self.calibration.coord_system != CoordSystem::Cartesian {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "Fitting to a reference works on Cartesian calibration only.",
+                UiLanguage::Ru => {
+                    "Подгонка по опорным данным работает только в декартовой калибровке."
+                }
+            });
+            return;
+        }
+        let (Some(x), Some(y)) = self.cartesian_mappings() else {
+            return;
+        };
+        let pairs = self.reference_pairs(&x, &y);
+        let samples = |pick: fn(&XYPoint) -> f64| -> Vec<(Pos2, f64)> {
+            pairs.iter().map(|(pixel, r)| (*pixel, pick(r))).collect()
+        };
+        let fitted_x = x.refit_to_samples(&samples(|r| r.x));
+        let fitted_y = y.refit_to_samples(&samples(|r| r.y));
+        if pairs.len() < 2 || (fitted_x.is_none() && fitted_y.is_none()) {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => {
+                    "Pick at least two points of the curve where the pasted reference is known."
+                }
+                UiLanguage::Ru => {
+                    "Поставьте на кривой хотя бы две точки там, где известны опорные значения."
+                }
+            });
+            return;
+        }
+
+        // A repeated fit keeps the calibration from before the first one.
+        let previous = self.calibration.recalibration_review.take().map_or_else(
+            || {
+                (
+                    self.calibration.cal_x.clone(),
+                    self.calibration.cal_y.clone(),
+                )
+            },
+            |review| review.previous,
+        );
+        for (cal, fitted) in [
+            (&mut self.calibration.cal_x, &fitted_x),
+            (&mut self.calibration.cal_y, &fitted_y),
+        ] {
+            if let Some(mapping) = fitted {
+                cal.p1 = Some(mapping.p1);
+                cal.p2 = Some(mapping.p2);
+            }
+        }
+        let rms_before = residual_rms(&x, &y, &pairs);
+        let rms_after = residual_rms(
+            fitted_x.as_ref().unwrap_or(&x),
+            fitted_y.as_ref().unwrap_or(&y),
+            &pairs,
+        );
+        self.calibration.recalibration_review = Some(RecalibrationReview {
+            pairs: pairs.len(),
+            rms_before,
+            rms_after,
+            previous,
+        });
+        self.clear_calibration_drag_runtime();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!(
+                "Calibration fitted to {} reference points. Check it, then keep or revert.",
+                pairs.len()
+            ),
+            UiLanguage::Ru => format!(
+                "Калибровка подогнана по опорным точкам: {}. Проверьте, затем оставьте или откатите.",
+                pairs.len()
+            ),
+        });
+    }
+
+    /// Keep the fitted calibration.
+    pub(crate) fn keep_recalibration(&mut self) {
+        self.calibration.recalibration_review = None;
+    }
+
+    /// Restore the axes from before the fit.
+    pub(crate) fn revert_recalibration(&mut self) {
+        let Some(review) = self.calibration.recalibration_review.take() else {
+            return;
+        };
+        (self.calibration.cal_x, self.calibration.cal_y) = review.previous;
+        self.clear_calibration_drag_runtime();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Fitted calibration reverted.",
+            UiLanguage::Ru => "Подогнанная калибровка отменена.",
+        });
+    }
+}
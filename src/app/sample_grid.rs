@@ -0,0 +1,109 @@
+//! User sample grids of the interpolated and fitted exports: typed x values, a fixed step or
+//! log spacing, instead of a sample count spread evenly over the curve.
+
+use super::{CurcatApp, PickedPoint};
+use crate::interp::{SampleGrid, XYPoint, log_grid, uniform_grid};
+use crate::types::{AxisUnit, CoordSystem, parse_axis_value};
+
+/// Fraction of the grid range a default uniform step covers.
+const DEFAULT_STEP_FRACTION: f64 = 0.1;
+
+/// The x values in `text`, in `unit`; `Err` holds the first entry that does not parse.
+///
+/// Entries are separated by commas, semicolons or line breaks, and on numeric axes also by
+/// spaces; date-times keep their spaces.
+pub fn parse_sample_xs(text: &str, unit: AxisUnit) -> Result<Vec<f64>, String> {
+    let separators: &[char] = match unit {
+        AxisUnit::Float => &[',', ';', '\n', ' ', '\t'],
+        AxisUnit::DateTime => &[',', ';', '\n'],
+    };
+    text.split(separators)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            parse_axis_value(entry, unit)
+                .map(|value| value.to_scalar_seconds())
+                .ok_or_else(|| entry.to_string())
+        })
+        .collect()
+}
+
+/// Smallest and largest x of `nums`; `None` without points.
+pub(super) fn x_range(nums: &[XYPoint]) -> Option<[f64; 2]> {
+    nums.iter().fold(None, |range, p| match range {
+        None => Some([p.x, p.x]),
+        Some([lo, hi]) => Some([lo.min(p.x), hi.max(p.x)]),
+    })
+}
+
+impl CurcatApp {
+    /// Unit of exported x values: the x axis in cartesian charts, plain numbers otherwise.
+    pub(crate) fn export_x_unit(&self) -> AxisUnit {
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => self
+                .active_curve_mappings()
+                .0
+                .map_or(AxisUnit::Float, |mapping| mapping.unit),
+            CoordSystem::Polar | CoordSystem::Smith => AxisUnit::Float,
+        }
+    }
+
+    /// Start and stop x of the uniform and log-spaced grids: as set, or the x range of `points`.
+    pub(crate) fn grid_range(&self, points: &[PickedPoint]) -> Option<[f64; 2]> {
+        self.export
+            .grid_range
+            .or_else(|| x_range(&Self::collect_numeric_points_in_order(points)))
+    }
+
+    /// Step of the uniform grid over `range`.
+    pub(crate) fn grid_step(&self, range: [f64; 2]) -> f64 {
+        self.export
+            .grid_step
+            .unwrap_or_else(|| (range[1] - range[0]).abs() * DEFAULT_STEP_FRACTION)
+    }
+
+    /// The x values the export samples `points` at; `None` for evenly spread samples.
+    ///
+    /// A list that does not parse gives no x values, so nothing is exported until it is fixed.
+    pub(crate) fn sample_grid_xs(&self, points: &[PickedPoint]) -> Option<Vec<f64>> {
+        let max = self.config.export.samples_max_sanitized();
+        match self.export.sample_grid {
+            SampleGrid::Even => None,
+            SampleGrid::List => Some(
+                parse_sample_xs(&self.export.sample_xs_text, self.export_x_unit())
+                    .map(|mut xs| {
+                        xs.truncate(max);
+                        xs
+                    })
+                    .unwrap_or_default(),
+            ),
+            SampleGrid::Uniform => Some(self.grid_range(points).map_or_else(Vec::new, |range| {
+                uniform_grid(range[0], range[1], self.grid_step(range), max)
+            })),
+            SampleGrid::LogSpaced => Some(self.grid_range(points).map_or_else(Vec::new, |range| {
+                log_grid(range[0], range[1], self.export.sample_count)
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_lists_split_numbers_on_spaces_but_not_dates() {
+        assert_eq!(
+            parse_sample_xs("1, 2.5;3\n 4  5", AxisUnit::Float),
+            Ok(vec![1.0, 2.5, 3.0, 4.0, 5.0])
+        );
+        assert_eq!(
+            parse_sample_xs("1, two, 3", AxisUnit::Float),
+            Err("two".to_string())
+        );
+        let dates = parse_sample_xs("2024-01-01 00:00; 2024-01-02 00:00", AxisUnit::DateTime)
+            .expect("dates parse");
+        assert_eq!(dates.len(), 2);
+        assert!((dates[1] - dates[0] - 86_400.0).abs() < 1.0e-6);
+    }
+}
@@ -0,0 +1,37 @@
+//! Scale-kind suggestion: read Linear or Log10 for each cartesian axis from the spacing of the
+//! image's grid lines.
+
+use super::CurcatApp;
+use crate::snap::{detect_grid_lines, spacing_scale};
+use crate::types::ScaleKind;
+
+/// Scales the grid lines of the current image suggest; `None` where the spacing is unclear.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScaleSuggestion {
+    /// From the vertical lines.
+    pub x: Option<ScaleKind>,
+    /// From the horizontal lines.
+    pub y: Option<ScaleKind>,
+}
+
+impl CurcatApp {
+    /// Suggestion for the current image, detected on first use and kept until the pixels change.
+    pub(crate) fn scale_suggestion(&mut self) -> ScaleSuggestion {
+        if let Some(suggestion) = self.calibration.scale_suggestion {
+            return suggestion;
+        }
+        let suggestion = self
+            .image
+            .image
+            .as_ref()
+            .map_or_else(ScaleSuggestion::default, |image| {
+                let lines = detect_grid_lines(&image.pixels);
+                ScaleSuggestion {
+                    x: spacing_scale(&lines.vertical),
+                    y: spacing_scale(&lines.horizontal),
+                }
+            });
+        self.calibration.scale_suggestion = Some(suggestion);
+        suggestion
+    }
+}
@@ -0,0 +1,133 @@
+//! The selected curve point or calibration handle and its arrow-key nudging.
+//!
+//! Points selected together (see `point_selection`) are re-pointed and nudged alongside.
+
+use super::{CurcatApp, DragTarget};
+use egui::{Context, Key, Pos2, Vec2, vec2};
+
+/// Nudge step in image pixels; Shift makes it coarse and Alt fine.
+const NUDGE_STEP: f32 = 1.0;
+const NUDGE_STEP_COARSE: f32 = 10.0;
+const NUDGE_STEP_FINE: f32 = 0.1;
+
+impl CurcatApp {
+    /// Index of the selected point of the active curve.
+    pub(crate) const fn selected_point(&self) -> Option<usize> {
+        match self.interaction.selection {
+            Some(DragTarget::CurvePoint(idx)) => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// Re-point the point selections with `f`; a selected calibration handle is kept.
+    pub(crate) fn map_selected_point(&mut self, f: impl Fn(usize) -> Option<usize>) {
+        if let Some(idx) = self.selected_point() {
+            self.interaction.selection = f(idx).map(DragTarget::CurvePoint);
+        }
+        let mut selected: Vec<usize> = self
+            .interaction
+            .point_selection
+            .drain(..)
+            .filter_map(&f)
+            .collect();
+        selected.sort_unstable();
+        self.interaction.point_selection = selected;
+    }
+
+    pub(crate) fn clear_point_selection(&mut self) {
+        self.map_selected_point(|_| None);
+    }
+
+    /// Placed calibration points `target` stands for: both ends of a whole axis line.
+    fn handle_slots(&mut self, target: DragTarget) -> Vec<&mut Option<Pos2>> {
+        let cal = &mut self.calibration;
+        match target {
+            DragTarget::CalX1 => vec![&mut cal.cal_x.p1],
+            DragTarget::CalX2 => vec![&mut cal.cal_x.p2],
+            DragTarget::CalXLine => vec![&mut cal.cal_x.p1, &mut cal.cal_x.p2],
+            DragTarget::CalY1 => vec![&mut cal.cal_y.p1],
+            DragTarget::CalY2 => vec![&mut cal.cal_y.p2],
+            DragTarget::CalYLine => vec![&mut cal.cal_y.p1, &mut cal.cal_y.p2],
+            DragTarget::CalSecondaryY1 => vec![&mut cal.cal_secondary_y.p1],
+            DragTarget::CalSecondaryY2 => vec![&mut cal.cal_secondary_y.p2],
+            DragTarget::PolarOrigin => vec![&mut cal.polar_cal.origin],
+            DragTarget::PolarR1 => vec![&mut cal.polar_cal.radius.p1],
+            DragTarget::PolarR2 => vec![&mut cal.polar_cal.radius.p2],
+            DragTarget::PolarA1 => vec![&mut cal.polar_cal.angle.p1],
+            DragTarget::PolarA2 => vec![&mut cal.polar_cal.angle.p2],
+            DragTarget::AffineRef(idx) => cal
+                .affine_cal
+                .points
+                .get_mut(idx)
+                .map(|point| &mut point.pixel)
+                .into_iter()
+                .collect(),
+            DragTarget::SmithCenter => vec![&mut cal.smith_cal.center],
+            DragTarget::SmithShort => vec![&mut cal.smith_cal.short],
+            DragTarget::CurvePoint(_) => Vec::new(),
+        }
+    }
+
+    /// Image positions of the selected calibration handle, for drawing its highlight.
+    pub(crate) fn selected_handle_pixels(&mut self) -> Vec<Pos2> {
+        let Some(target) = self.interaction.selection else {
+            return Vec::new();
+        };
+        self.handle_slots(target)
+            .into_iter()
+            .filter_map(|slot| *slot)
+            .collect()
+    }
+
+    /// Shift the selection by `delta` image pixels; snapping is left out on purpose.
+    pub(crate) fn nudge_selection(&mut self, delta: Vec2) {
+        let Some(target) = self.interaction.selection else {
+            self.nudge_selected_points(delta);
+            return;
+        };
+        if let DragTarget::CurvePoint(idx) = target {
+            self.nudge_point(idx, delta);
+            return;
+        }
+        let mut moved = false;
+        for pixel in self.handle_slots(target).into_iter().flatten() {
+            *pixel += delta;
+            moved = true;
+        }
+        if moved {
+            self.clear_calibration_drag_runtime();
+            self.mark_points_dirty();
+        }
+    }
+
+    /// Arrow keys nudge the selection by 1 px, 10 px with Shift and 0.1 px with Alt.
+    pub(crate) fn handle_nudge_keys(&mut self, ctx: &Context) {
+        if self.interaction.selection.is_none() && self.interaction.point_selection.is_empty() {
+            return;
+        }
+        let delta = ctx.input(|i| {
+            if i.modifiers.command {
+                return Vec2::ZERO;
+            }
+            let step = if i.modifiers.shift {
+                NUDGE_STEP_COARSE
+            } else if i.modifiers.alt {
+                NUDGE_STEP_FINE
+            } else {
+                NUDGE_STEP
+            };
+            [
+                (Key::ArrowLeft, vec2(-step, 0.0)),
+                (Key::ArrowRight, vec2(step, 0.0)),
+                (Key::ArrowUp, vec2(0.0, -step)),
+                (Key::ArrowDown, vec2(0.0, step)),
+            ]
+            .into_iter()
+            .filter(|(key, _)| i.key_pressed(*key))
+            .fold(Vec2::ZERO, |sum, (_, delta)| sum + delta)
+        });
+        if delta != Vec2::ZERO {
+            self.nudge_selection(delta);
+        }
+    }
+}
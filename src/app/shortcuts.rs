@@ -0,0 +1,131 @@
+//! Keyboard shortcuts: the bound keys of `[keymap]` dispatched to their actions, and the
+//! bindings shown next to the buttons and in their tooltips.
+
+use super::{CurcatApp, PickMode};
+use crate::error::{CurcatError, Preference};
+use crate::i18n::TextKey;
+use crate::keymap::{KeymapConfig, ShortcutAction, format_shortcut};
+use crate::types::CoordSystem;
+use egui::Context;
+
+impl CurcatApp {
+    /// Binding of `action` as written in the config; empty when it has none.
+    pub(crate) fn shortcut_label(&self, action: ShortcutAction) -> String {
+        self.config
+            .keymap
+            .shortcut(action)
+            .map(format_shortcut)
+            .unwrap_or_default()
+    }
+
+    /// Tooltip `key` followed by the binding of `action`, when it has one.
+    pub(crate) fn hover_with_shortcut(&self, key: TextKey, action: ShortcutAction) -> String {
+        let hover = self.t(key);
+        let label = self.shortcut_label(action);
+        if label.is_empty() {
+            hover.to_string()
+        } else {
+            format!("{hover} ({label})")
+        }
+    }
+
+    /// Replace the keymap and save it to the config file.
+    pub(crate) fn set_keymap(&mut self, keymap: KeymapConfig) {
+        if self.config.keymap == keymap {
+            return;
+        }
+        if let Err(source) = self.config.persist_keymap(keymap) {
+            self.report_error(&CurcatError::Preference {
+                preference: Preference::Keymap,
+                source,
+            });
+        }
+    }
+
+    /// Run the actions whose shortcuts were pressed this frame.
+    ///
+    /// Modifiers must match exactly, so `Ctrl+V` and `Ctrl+Shift+V` stay apart. Nothing runs
+    /// while a new binding is being recorded.
+    pub(crate) fn handle_shortcut_keys(&mut self, ctx: &Context) {
+        if self.ui.keymap_recording.is_some() {
+            return;
+        }
+        let keymap = &self.config.keymap;
+        let pressed: Vec<ShortcutAction> = ctx.input(|i| {
+            ShortcutAction::ALL
+                .into_iter()
+                .filter(|&action| {
+                    keymap.shortcut(action).is_some_and(|shortcut| {
+                        i.key_pressed(shortcut.logical_key)
+                            && i.modifiers.matches_exact(shortcut.modifiers)
+                    })
+                })
+                .collect()
+        });
+        for action in pressed {
+            self.run_shortcut(ctx, action);
+        }
+    }
+
+    fn run_shortcut(&mut self, ctx: &Context, action: ShortcutAction) {
+        if !self.shortcut_allowed(action) {
+            return;
+        }
+        let no_dialog = self.project.active_dialog.is_none();
+        let has_image = self.image.image.is_some();
+        match action {
+            ShortcutAction::ToggleSidePanel => self.ui.side_open = !self.ui.side_open,
+            ShortcutAction::OpenImage if no_dialog => self.open_image_dialog(),
+            ShortcutAction::PasteImage if no_dialog => self.paste_image_from_clipboard(ctx),
+            ShortcutAction::PastePoints if no_dialog => self.paste_points_from_clipboard(),
+            ShortcutAction::OpenProject if no_dialog => self.open_project_dialog(),
+            ShortcutAction::SaveProject
+                if no_dialog && self.image.meta.as_ref().and_then(|m| m.path()).is_some() =>
+            {
+                self.save_project_dialog();
+            }
+            ShortcutAction::ExportCsv if no_dialog => self.start_export_csv(),
+            ShortcutAction::ExportJson if no_dialog => self.start_export_json(),
+            ShortcutAction::ExportRon if no_dialog => self.start_export_ron(),
+            ShortcutAction::ExportExcel if no_dialog => self.start_export_xlsx(),
+            ShortcutAction::ExportOds if no_dialog => self.start_export_ods(),
+            ShortcutAction::ExportArrow if no_dialog => self.start_export_arrow(),
+            ShortcutAction::ExportParquet if no_dialog => self.start_export_parquet(),
+            ShortcutAction::ExportSql if no_dialog => self.start_export_sql(),
+            ShortcutAction::ExportNetCdf if no_dialog => self.start_export_netcdf(),
+            ShortcutAction::ExportHtml if no_dialog => self.start_export_html(),
+            ShortcutAction::ExportXml if no_dialog => self.start_export_xml(),
+            ShortcutAction::ExportMarkdown if no_dialog => self.start_export_markdown(),
+            ShortcutAction::Undo => self.undo_last_point(),
+            ShortcutAction::ClearPoints => self.clear_all_points(),
+            ShortcutAction::ImageInfo if has_image => self.ui.info_window_open = true,
+            ShortcutAction::ImageFilters => self.ui.image_filters_window_open = true,
+            ShortcutAction::AutoTrace => self.ui.auto_trace_window_open = true,
+            ShortcutAction::FitView if has_image => self.fit_image_to_viewport(),
+            ShortcutAction::FitWidth if has_image => self.fit_image_width(),
+            ShortcutAction::ResetView if has_image => self.reset_view(),
+            ShortcutAction::PickX1 => self.toggle_pick_shortcut(PickMode::X1),
+            ShortcutAction::PickX2 => self.toggle_pick_shortcut(PickMode::X2),
+            ShortcutAction::PickY1 => self.toggle_pick_shortcut(PickMode::Y1),
+            ShortcutAction::PickY2 => self.toggle_pick_shortcut(PickMode::Y2),
+            ShortcutAction::SelectPoints => self.toggle_pick_shortcut(PickMode::SelectPoints),
+            _ => {}
+        }
+    }
+
+    /// Start picking `mode` like its button does, or cancel it when it is already active.
+    fn toggle_pick_shortcut(&mut self, mode: PickMode) {
+        if self.calibration.pick_mode == mode {
+            self.cancel_pick_mode();
+            return;
+        }
+        let available = if mode == PickMode::SelectPoints {
+            !self.points.points.is_empty()
+        } else {
+            self.image.image.is_some() && self.calibration.coord_system == CoordSystem::Cartesian
+        };
+        if available {
+            self.begin_pick_mode(mode);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Smith chart calibration: the chart centre, its short-circuit point and the reference
+//! impedance map picked pixels to normalized impedance for RF plots.
+
+use super::{CurcatApp, PickMode};
+use crate::export::ExportExtraColumn;
+use crate::interp::XYPoint;
+use crate::types::{SmithMapping, reflection_of};
+use egui::Pos2;
+
+impl CurcatApp {
+    pub(crate) fn smith_mapping(&self) -> Option<SmithMapping> {
+        self.calibration.smith_cal.mapping()
+    }
+
+    /// Place the Smith point the current pick mode asks for at `pixel` and leave pick mode.
+    pub(crate) fn pick_smith_point(&mut self, pixel: Pos2) {
+        let mode = self.calibration.pick_mode;
+        self.calibration.pick_mode = PickMode::None;
+        let pixel = self.snap_pixel_if_requested(pixel);
+        let cal = &mut self.calibration.smith_cal;
+        let name = match mode {
+            PickMode::SmithCenter => {
+                cal.center = Some(pixel);
+                "Z0"
+            }
+            PickMode::SmithShort => {
+                cal.short = Some(pixel);
+                "SC"
+            }
+            _ => return,
+        };
+        self.mark_points_dirty();
+        self.set_status(self.i18n().format_picked(name));
+    }
+
+    /// Impedance in ohms and the reflection coefficient for exported `(r, x)` rows.
+    pub(super) fn smith_columns(points: &[XYPoint], reference_ohms: f64) -> Vec<ExportExtraColumn> {
+        let mut resistance = Vec::with_capacity(points.len());
+        let mut reactance = Vec::with_capacity(points.len());
+        let mut magnitude = Vec::with_capacity(points.len());
+        let mut angle = Vec::with_capacity(points.len());
+        for p in points {
+            let finite = p.x.is_finite() && p.y.is_finite();
+            resistance.push(finite.then_some(p.x * reference_ohms));
+            reactance.push(finite.then_some(p.y * reference_ohms));
+            let gamma = reflection_of(p.x, p.y);
+            magnitude.push(gamma.map(|(re, im)| re.hypot(im)));
+            angle.push(gamma.map(|(re, im)| im.atan2(re).to_degrees()));
+        }
+        vec![
+            ExportExtraColumn::new("R_ohm", resistance),
+            ExportExtraColumn::new("X_ohm", reactance),
+            ExportExtraColumn::new("gamma_mag", magnitude),
+            ExportExtraColumn::new("gamma_deg", angle),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::harness::Harness;
+    use crate::app::{ExportKind, PickMode};
+    use crate::types::CoordSystem;
+    use egui::pos2;
+
+    #[test]
+    fn picked_points_read_as_impedance_and_export_in_ohms() {
+        let mut harness = Harness::with_blank_image(300, 300);
+        harness.app.calibration.coord_system = CoordSystem::Smith;
+        harness.run();
+        harness.app.begin_pick_mode(PickMode::SmithCenter);
+        harness.click_image_pixel(pos2(150.0, 150.0));
+        harness.app.begin_pick_mode(PickMode::SmithShort);
+        harness.click_image_pixel(pos2(50.0, 150.0));
+        assert!(harness.app.calibration_ready());
+
+        // Γ = 0.2 + 0.4j is z = 1 + 1j, and Γ = 0.6 is z = 4, on a chart of radius 100 px.
+        harness.click_image_pixel(pos2(170.0, 110.0));
+        harness.click_image_pixel(pos2(210.0, 150.0));
+        let picked: Vec<(f64, f64)> = harness
+            .app
+            .points
+            .points
+            .iter()
+            .map(|p| (p.x_numeric.expect("r"), p.y_numeric.expect("x")))
+            .collect();
+        for ((r, x), (want_r, want_x)) in picked.iter().zip([(1.0, 1.0), (4.0, 0.0)]) {
+            assert!((r - want_r).abs() < 0.05, "r = {r}");
+            assert!((x - want_x).abs() < 0.05, "x = {x}");
+        }
+
+        harness.app.export.export_kind = ExportKind::RawPoints;
+        let curves = harness.app.build_export_curves().expect("export payload");
+        let payload = &curves[0].1;
+        let column = |header: &str| {
+            payload
+                .extra_columns
+                .iter()
+                .find(|c| c.header == header)
+                .map(|c| {
+                    c.values
+                        .iter()
+                        .map(|v| v.expect(header))
+                        .collect::<Vec<_>>()
+                })
+                .expect(header)
+        };
+        let resistance = column("R_ohm");
+        assert!((resistance[0] - 50.0).abs() < 2.0 && (resistance[1] - 200.0).abs() < 2.0);
+        assert!((column("X_ohm")[0] - 50.0).abs() < 2.0);
+        let magnitude = column("gamma_mag");
+        assert!((magnitude[1] - 0.6).abs() < 0.01, "|Γ| = {}", magnitude[1]);
+        assert!(column("gamma_deg")[1].abs() < 0.5);
+    }
+}
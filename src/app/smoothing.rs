@@ -0,0 +1,98 @@
+//! Export smoothing: a moving average, Savitzky–Golay, median or LOESS filter over the exported
+//! rows, previewed over the image and in the result plot while the picked points stay as
+//! they are.
+
+use super::{CurcatApp, ExportKind, PickedPoint};
+use crate::interp::{XYPoint, smooth};
+use crate::types::{AxisMapping, CoordSystem, YAxis};
+use egui::{Color32, Pos2, Rect, Stroke};
+
+/// Largest smoothing radius the slider offers, in rows on each side.
+pub const SMOOTHING_MAX: usize = 25;
+/// Most robust reweighting passes LOESS offers.
+pub const LOESS_ITERATIONS_MAX: usize = 6;
+
+impl CurcatApp {
+    /// `rows` through the export's smoothing filter and radius; unchanged when smoothing is off.
+    pub(crate) fn smoothed_rows(&self, rows: Vec<XYPoint>) -> Vec<XYPoint> {
+        match self.export.smoothing {
+            0 => rows,
+            radius => smooth(
+                &rows,
+                self.export.smoothing_filter,
+                radius,
+                self.export.loess_iterations,
+            ),
+        }
+    }
+
+    /// The smoothed rows the export would write for `points`; empty when there is nothing to
+    /// preview: smoothing is off, or the export writes the fitted model.
+    pub(crate) fn smoothing_preview_rows(&self, points: &[PickedPoint]) -> Vec<XYPoint> {
+        if self.export.smoothing == 0 {
+            return Vec::new();
+        }
+        let rows = match self.export.export_kind {
+            ExportKind::Interpolated => self.interpolated_export_rows(points),
+            ExportKind::Spectrum | ExportKind::Crossings => self.build_interpolated_samples(points),
+            ExportKind::RawPoints => Self::collect_numeric_points_in_order(points),
+            ExportKind::Fitted => return Vec::new(),
+        };
+        self.smoothed_rows(rows)
+    }
+
+    /// Image positions of `rows` read against `y_axis`; rows no pixel shows are skipped.
+    pub(super) fn rows_to_pixels(&self, rows: &[XYPoint], y_axis: YAxis) -> Vec<Pos2> {
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
+                let (x_mapping, y_mapping) = self.cartesian_mappings();
+                let y_mapping = if y_axis == YAxis::Secondary && self.uses_secondary_y_axis() {
+                    self.secondary_y_mapping()
+                } else {
+                    y_mapping
+                };
+                let (Some(xm), Some(ym)) = (x_mapping, y_mapping) else {
+                    return Vec::new();
+                };
+                rows.iter()
+                    .filter_map(|p| AxisMapping::pixel_at(&xm, &ym, p.x, p.y))
+                    .collect()
+            }
+            CoordSystem::Polar => {
+                let Some(pm) = self.polar_mapping() else {
+                    return Vec::new();
+                };
+                rows.iter().filter_map(|p| pm.pixel_at(p.x, p.y)).collect()
+            }
+            CoordSystem::Smith => {
+                let Some(sm) = self.smith_mapping() else {
+                    return Vec::new();
+                };
+                rows.iter().filter_map(|p| sm.pixel_at(p.x, p.y)).collect()
+            }
+        }
+    }
+
+    /// Smoothed curves over the image drawn at `rect`, in each curve's colour on a dark halo.
+    pub(crate) fn draw_smoothing_preview(&self, painter: &egui::Painter, rect: Rect) {
+        if self.export.smoothing == 0 {
+            return;
+        }
+        let zoom = self.image.zoom;
+        for (idx, curve) in self.points.curves.curves.iter().enumerate() {
+            let rows = self.smoothing_preview_rows(self.curve_points(idx));
+            let screen: Vec<Pos2> = self
+                .rows_to_pixels(&rows, curve.y_axis)
+                .iter()
+                .map(|p| rect.min + p.to_vec2() * zoom)
+                .collect();
+            if screen.len() < 2 {
+                continue;
+            }
+            let halo = Stroke::new(4.0_f32, Color32::from_black_alpha(110));
+            painter.add(egui::Shape::line(screen.clone(), halo));
+            let stroke = Stroke::new(2.0_f32, self.curve_point_color(idx));
+            painter.add(egui::Shape::line(screen, stroke));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Snap area: a rectangle or polygon over the plot that snapping and auto-trace stay inside,
+//! so legends, axis labels and annotations around it never attract the snapper.
+
+use super::{CurcatApp, POINT_HIT_RADIUS, PickMode};
+use crate::i18n::UiLanguage;
+use egui::{Color32, Pos2, Rect, Stroke, pos2};
+
+/// Screen size below which a dragged rectangle counts as a click rather than an area.
+const MIN_AREA_SCREEN: f32 = 8.0;
+const AREA_COLOR: Color32 = Color32::from_rgb(120, 220, 170);
+
+impl CurcatApp {
+    /// Start marking a snap area: drag a rectangle, or click the corners of a polygon.
+    pub(crate) fn begin_snap_area_pick(&mut self) {
+        self.snap.search_area_draft.clear();
+        self.begin_pick_mode(PickMode::SnapArea);
+    }
+
+    /// Confine snapping to the image polygon `vertices`.
+    pub(crate) fn set_snap_area(&mut self, vertices: Vec<Pos2>) {
+        self.snap.search_area = Some(vertices);
+        self.snap.search_area_draft.clear();
+        self.calibration.pick_mode = PickMode::None;
+        self.attach_snap_overlays();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Snapping is limited to the marked area.",
+            UiLanguage::Ru => "Привязка ограничена отмеченной областью.",
+        });
+    }
+
+    pub(crate) fn clear_snap_area(&mut self) {
+        self.snap.search_area = None;
+        self.snap.search_area_draft.clear();
+        self.attach_snap_overlays();
+    }
+
+    /// Add a polygon corner; a click on the first corner closes a polygon of three or more.
+    pub(crate) fn pick_snap_area_vertex(&mut self, pixel: Pos2) {
+        let closes =
+            self.snap.search_area_draft.len() >= 3
+                && self.snap.search_area_draft.first().is_some_and(|first| {
+                    first.distance(pixel) * self.image.zoom <= POINT_HIT_RADIUS
+                });
+        if closes {
+            self.finish_snap_area_polygon();
+        } else {
+            self.snap.search_area_draft.push(pixel);
+        }
+    }
+
+    /// Close the polygon clicked so far, as Enter does.
+    pub(crate) fn finish_snap_area_polygon(&mut self) {
+        if self.snap.search_area_draft.len() < 3 {
+            self.set_status_warn(match self.ui.language {
+                UiLanguage::En => "A snap area needs three or more corners.",
+                UiLanguage::Ru => "Для области привязки нужно не меньше трёх углов.",
+            });
+            return;
+        }
+        let vertices = std::mem::take(&mut self.snap.search_area_draft);
+        self.set_snap_area(vertices);
+    }
+
+    /// Follow a rectangle drag while the snap area is being marked.
+    ///
+    /// `press` and `pointer` are image pixels. Returns `true` while the rectangle is dragged and
+    /// on the frame it is released, so the gesture does not also add a polygon corner.
+    pub(crate) fn track_snap_area_drag(
+        &mut self,
+        response: &egui::Response,
+        press: Option<Pos2>,
+        pointer: Option<Pos2>,
+    ) -> bool {
+        if self.calibration.pick_mode != PickMode::SnapArea {
+            self.interaction.snap_area_drag = None;
+            return false;
+        }
+        if response.drag_started_by(egui::PointerButton::Primary)
+            && self.interaction.zoom_region.is_none()
+            && let Some(start) = press
+        {
+            self.interaction.snap_area_drag = Some([start, pointer.unwrap_or(start)]);
+        }
+        let Some(drag) = self.interaction.snap_area_drag.as_mut() else {
+            return false;
+        };
+        if let Some(end) = pointer {
+            drag[1] = end;
+        }
+        if response.dragged_by(egui::PointerButton::Primary) {
+            return true;
+        }
+        let [a, b] = *drag;
+        self.interaction.snap_area_drag = None;
+        if ((b - a).abs() * self.image.zoom).min_elem() >= MIN_AREA_SCREEN {
+            let area = Rect::from_two_pos(a, b);
+            self.set_snap_area(vec![
+                area.left_top(),
+                area.right_top(),
+                area.right_bottom(),
+                area.left_bottom(),
+            ]);
+        }
+        true
+    }
+
+    /// Outline of the snap area over the image drawn at `rect`, with the corners or rectangle
+    /// of one being marked.
+    pub(crate) fn draw_snap_area(&self, painter: &egui::Painter, rect: Rect, hover: Option<Pos2>) {
+        let zoom = self.image.zoom;
+        let to_screen = |pixel: &Pos2| rect.min + pixel.to_vec2() * zoom;
+        let stroke = Stroke::new(1.5_f32, AREA_COLOR);
+        if let Some(area) = &self.snap.search_area {
+            let outline: Vec<Pos2> = area.iter().map(to_screen).collect();
+            painter.add(egui::Shape::closed_line(outline, stroke));
+        }
+        if self.calibration.pick_mode != PickMode::SnapArea {
+            return;
+        }
+        if let Some([a, b]) = self.interaction.snap_area_drag {
+            let (a, b) = (to_screen(&a), to_screen(&b));
+            let outline = vec![a, pos2(b.x, a.y), b, pos2(a.x, b.y)];
+            painter.add(egui::Shape::convex_polygon(
+                outline,
+                AREA_COLOR.gamma_multiply(0.15),
+                stroke,
+            ));
+            return;
+        }
+        let mut draft: Vec<Pos2> = self.snap.search_area_draft.iter().map(to_screen).collect();
+        for &corner in &draft {
+            painter.circle_filled(corner, 3.5, AREA_COLOR);
+        }
+        if !draft.is_empty()
+            && let Some(hover) = hover
+        {
+            draft.push(to_screen(&hover));
+        }
+        painter.add(egui::Shape::line(draft, stroke));
+    }
+}
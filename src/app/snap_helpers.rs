@@ -1,31 +1,73 @@
 //! Helpers for snap-map creation, color analysis, and snapping workflow.
 
-use super::{CurcatApp, PointInputMode, SnapBuildJob, safe_usize_to_f32};
+use super::{CurcatApp, PointInputMode, SnapBuildJob, SnapQuery, SnapQueryKey, safe_usize_to_f32};
+use crate::cache::{DecodeCache, SnapCacheKey};
 use crate::i18n::UiLanguage;
-use crate::snap::{SnapBehavior, SnapMapCache, derive_snap_overlay_palette};
+use crate::ocr::detect_text_regions;
+use crate::snap::{
+    CenterlineScorer, ContrastScorer, CurveMask, SNAP_SCORERS, SnapBehavior, SnapMapCache,
+    derive_snap_overlay_palette, sample_color_area, suppress_grid_lines,
+};
 use egui::{Color32, ColorImage, Pos2, Vec2};
+use std::borrow::Cow;
+use std::path::Path;
 use std::sync::mpsc::{self, TryRecvError};
 use std::thread;
 
 /// Square size (in pixels) used for the snap color swatch preview.
 pub const SNAP_SWATCH_SIZE: f32 = 22.0;
+/// Pointer moves shorter than this (in image pixels) reuse the last snap search.
+pub const SNAP_QUERY_JITTER: f32 = 0.5;
 
 impl CurcatApp {
     /// Return the active snap behavior based on the current input mode.
-    pub(crate) const fn current_snap_behavior(&self) -> Option<SnapBehavior> {
+    pub(crate) fn current_snap_behavior(&self) -> Option<Box<dyn SnapBehavior>> {
         match self.snap.point_input_mode {
             PointInputMode::Free => None,
-            PointInputMode::ContrastSnap => Some(SnapBehavior::Contrast {
+            PointInputMode::ContrastSnap => Some(Box::new(ContrastScorer {
                 feature_source: self.snap.snap_feature_source,
                 threshold_kind: self.snap.snap_threshold_kind,
                 threshold: self.snap.contrast_threshold,
-            }),
-            PointInputMode::CenterlineSnap => Some(SnapBehavior::Centerline {
+            })),
+            PointInputMode::CenterlineSnap => Some(Box::new(CenterlineScorer {
                 threshold: self.snap.centerline_threshold,
-            }),
+            })),
+            PointInputMode::Scorer(idx) => {
+                let entry = SNAP_SCORERS.get(idx)?;
+                let threshold = self
+                    .snap
+                    .scorer_thresholds
+                    .get(idx)
+                    .copied()
+                    .unwrap_or(entry.default_threshold);
+                Some((entry.build)(threshold))
+            }
         }
     }
 
+    /// Parameters of a snap search in the current input mode; `None` in free placement.
+    pub(crate) fn current_snap_query_key(&self) -> Option<SnapQueryKey> {
+        let threshold = match self.snap.point_input_mode {
+            PointInputMode::Free => return None,
+            PointInputMode::ContrastSnap => self.snap.contrast_threshold,
+            PointInputMode::CenterlineSnap => self.snap.centerline_threshold,
+            PointInputMode::Scorer(idx) => self
+                .snap
+                .scorer_thresholds
+                .get(idx)
+                .copied()
+                .or_else(|| SNAP_SCORERS.get(idx).map(|entry| entry.default_threshold))?,
+        };
+        Some(SnapQueryKey {
+            mode: self.snap.point_input_mode,
+            threshold,
+            feature_source: self.snap.snap_feature_source,
+            threshold_kind: self.snap.snap_threshold_kind,
+            radius: self.snap.contrast_search_radius,
+            bridge_gaps: self.snap.bridge_gaps,
+        })
+    }
+
     /// Default overlay palette used when the image analysis yields no colors.
     ///
     /// The set favors high-contrast tones that remain visible over most charts.
@@ -79,6 +121,7 @@ impl CurcatApp {
         self.snap.snap_maps_dirty = true;
         self.snap.snap_maps = None;
         self.snap.pending_snap_job = None;
+        self.snap.last_query = None;
     }
 
     /// Kick off a background job that builds snap maps for the current image.
@@ -89,15 +132,43 @@ impl CurcatApp {
         let Some(image) = &self.image.image else {
             self.snap.snap_maps_dirty = false;
             self.snap.snap_maps = None;
+            self.snap.last_query = None;
             return;
         };
         let color_image = image.pixels.clone();
         let overlay_color = self.snap.snap_target_color;
         let tolerance = self.snap.snap_color_tolerance;
+        let suppress_grid = self.snap.suppress_grid;
+        let disk_cache = DecodeCache::from_config(&self.config);
+        let persist = std::mem::take(&mut self.snap.persist_next_snap_build);
+        let ignore_text = self.snap.ignore_text;
         let (tx, rx) = mpsc::channel();
         // Build the cache off-thread to avoid blocking the UI while scanning pixels.
         thread::spawn(move || {
-            let result = SnapMapCache::build(&color_image, overlay_color, tolerance);
+            let snap_image = if suppress_grid {
+                Cow::Owned(suppress_grid_lines(&color_image))
+            } else {
+                Cow::Borrowed(&color_image)
+            };
+            let key = SnapCacheKey::new(&snap_image, overlay_color, tolerance);
+            let cached = disk_cache
+                .as_ref()
+                .and_then(|disk_cache| disk_cache.load_snap_maps(key));
+            let mut result = cached.or_else(|| {
+                let built = SnapMapCache::build(&snap_image, overlay_color, tolerance);
+                // Only maps for freshly loaded pixels are worth the disk space; color and
+                // tolerance tweaks would otherwise write a new pyramid per slider step.
+                if persist
+                    && let Some(disk_cache) = &disk_cache
+                    && let Some(maps) = &built
+                {
+                    disk_cache.store_snap_maps(key, maps);
+                }
+                built
+            });
+            if ignore_text && let Some(maps) = result.as_mut() {
+                maps.set_exclusions(&detect_text_regions(&color_image));
+            }
             let _ = tx.send(result);
         });
         self.snap.pending_snap_job = Some(SnapBuildJob { rx });
@@ -112,12 +183,14 @@ impl CurcatApp {
         match job.rx.try_recv() {
             Ok(result) => {
                 self.snap.snap_maps = result;
+                self.attach_snap_overlays();
             }
             Err(TryRecvError::Empty) => {
                 self.snap.pending_snap_job = Some(job);
             }
             Err(TryRecvError::Disconnected) => {
                 self.snap.snap_maps = None;
+                self.snap.last_query = None;
             }
         }
     }
@@ -134,6 +207,90 @@ impl CurcatApp {
         self.poll_snap_build_job();
     }
 
+    /// Re-apply the curve mask and snap area, which the maps do not cache.
+    pub(super) fn attach_snap_overlays(&mut self) {
+        self.snap.last_query = None;
+        if let Some(maps) = self.snap.snap_maps.as_mut() {
+            maps.set_curve_mask(self.snap.curve_mask.as_ref());
+            maps.set_search_area(self.snap.search_area.as_deref().unwrap_or_default());
+        }
+    }
+
+    /// Load a segmentation mask for the current image and use it as a snap plane.
+    pub(crate) fn load_curve_mask_from_path(&mut self, path: &Path) {
+        let Some(size) = self.image.image.as_ref().map(|img| img.size) else {
+            return;
+        };
+        match CurveMask::load(path, size) {
+            Ok(mask) => {
+                self.snap.curve_mask = Some(mask);
+                self.attach_snap_overlays();
+                self.set_status(match self.ui.language {
+                    UiLanguage::En => "Curve mask loaded.",
+                    UiLanguage::Ru => "Маска кривой загружена.",
+                });
+            }
+            Err(err) => self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Curve mask not loaded: {err:#}"),
+                UiLanguage::Ru => format!("Маска кривой не загружена: {err:#}"),
+            }),
+        }
+    }
+
+    /// Run the ONNX model at `path` on the current image off-thread; its mask arrives
+    /// through [`Self::poll_segmentation_job`].
+    #[cfg(feature = "segmentation")]
+    pub(crate) fn run_segmentation_model(&mut self, path: std::path::PathBuf) {
+        let Some(image) = &self.image.image else {
+            return;
+        };
+        let pixels = image.pixels.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(
+                crate::snap::SegmentationModel::load(&path).and_then(|model| model.run(&pixels)),
+            );
+        });
+        self.snap.pending_mask_job = Some(rx);
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Running the segmentation model…",
+            UiLanguage::Ru => "Модель сегментации работает…",
+        });
+    }
+
+    /// Take the mask of a finished segmentation run, if any.
+    #[cfg(feature = "segmentation")]
+    pub(crate) fn poll_segmentation_job(&mut self) {
+        let Some(rx) = &self.snap.pending_mask_job else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("model run ended")),
+        };
+        self.snap.pending_mask_job = None;
+        match result {
+            Ok(mask) => {
+                self.snap.curve_mask = Some(mask);
+                self.attach_snap_overlays();
+                self.set_status(match self.ui.language {
+                    UiLanguage::En => "Curve mask computed.",
+                    UiLanguage::Ru => "Маска кривой построена.",
+                });
+            }
+            Err(err) => self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Segmentation model failed: {err:#}"),
+                UiLanguage::Ru => format!("Модель сегментации не сработала: {err:#}"),
+            }),
+        }
+    }
+
+    pub(crate) fn clear_curve_mask(&mut self) {
+        self.snap.curve_mask = None;
+        self.attach_snap_overlays();
+    }
+
     /// Return a snapped pixel location if the current input mode requests it.
     pub(crate) fn snap_pixel_if_requested(&mut self, pixel_hint: Pos2) -> Pos2 {
         self.compute_snap_candidate(pixel_hint)
@@ -141,9 +298,42 @@ impl CurcatApp {
     }
 
     /// Compute the best snap candidate based on the current input mode.
+    ///
+    /// With gap bridging on, a click between the dashes of a dashed curve still snaps. A hint
+    /// within [`SNAP_QUERY_JITTER`] of the last search with the same parameters reuses its
+    /// result, so the hover preview and the click that follows it agree.
     pub(crate) fn compute_snap_candidate(&mut self, pixel_hint: Pos2) -> Option<Pos2> {
         let behavior = self.current_snap_behavior()?;
-        self.find_snap_point_with_radius(pixel_hint, self.snap.contrast_search_radius, behavior)
+        let key = self.current_snap_query_key()?;
+        // Readying the maps may attach a finished build, which drops the cached query.
+        self.ready_snap_maps()?;
+        if let Some(query) = self.snap.last_query
+            && query.key == key
+            && query.pixel.distance(pixel_hint) < SNAP_QUERY_JITTER
+        {
+            return query.result;
+        }
+        let cache = self.snap.snap_maps.as_ref()?;
+        let result = if key.bridge_gaps {
+            cache.find_point_across_gaps(pixel_hint, key.radius, behavior.as_ref())
+        } else {
+            cache.find_point(pixel_hint, key.radius, behavior.as_ref())
+        };
+        self.snap.last_query = Some(SnapQuery {
+            pixel: pixel_hint,
+            key,
+            result,
+        });
+        result
+    }
+
+    /// Confidence of a point at `pixel` in the current input mode; 0 in free placement.
+    pub(crate) fn snap_confidence(&mut self, pixel: Pos2) -> f32 {
+        let Some(behavior) = self.current_snap_behavior() else {
+            return 0.0;
+        };
+        self.ready_snap_maps()
+            .map_or(0.0, |maps| maps.confidence_at(pixel, behavior.as_ref()))
     }
 
     /// Find a snap point within a radius using the specified snap behavior.
@@ -151,8 +341,14 @@ impl CurcatApp {
         &mut self,
         pixel_hint: Pos2,
         radius: f32,
-        behavior: SnapBehavior,
+        behavior: &dyn SnapBehavior,
     ) -> Option<Pos2> {
+        self.ready_snap_maps()?
+            .find_point(pixel_hint, radius, behavior)
+    }
+
+    /// Snap maps for the current image, built on the spot if no background build is running.
+    fn ready_snap_maps(&mut self) -> Option<&SnapMapCache> {
         self.ensure_snap_maps();
         if self.snap.snap_maps.is_none()
             && self.snap.pending_snap_job.is_none()
@@ -162,16 +358,33 @@ impl CurcatApp {
             let color_image = image.pixels.clone();
             let overlay_color = self.snap.snap_target_color;
             let tolerance = self.snap.snap_color_tolerance;
-            self.snap.snap_maps = SnapMapCache::build(&color_image, overlay_color, tolerance);
+            let snap_image = if self.snap.suppress_grid {
+                Cow::Owned(suppress_grid_lines(&color_image))
+            } else {
+                Cow::Borrowed(&color_image)
+            };
+            self.snap.snap_maps = SnapMapCache::build(&snap_image, overlay_color, tolerance);
+            if self.snap.ignore_text
+                && let Some(maps) = self.snap.snap_maps.as_mut()
+            {
+                maps.set_exclusions(&detect_text_regions(&color_image));
+            }
+            self.attach_snap_overlays();
             self.snap.pending_snap_job = None;
             self.snap.snap_maps_dirty = false;
         }
-        let cache = self.snap.snap_maps.as_ref()?;
-        cache.find_point(pixel_hint, radius, behavior)
+        self.snap.snap_maps.as_ref()
     }
 
-    /// Sample a pixel color, clamping the position to the image bounds.
+    /// Sample the color under `pixel` with the picker's area mode, clamping to the image bounds.
     pub(crate) fn sample_image_color(&self, pixel: Pos2) -> Option<Color32> {
+        let image = self.image.image.as_ref()?;
+        let (x, y) = self.image_pixel_coords(pixel)?;
+        sample_color_area(&image.pixels, x, y, self.snap.color_sample_mode)
+    }
+
+    /// Integer pixel under `pixel`, rounded and clamped to the image bounds.
+    pub(crate) fn image_pixel_coords(&self, pixel: Pos2) -> Option<(usize, usize)> {
         let image = self.image.image.as_ref()?;
         let [w, h] = image.pixels.size;
         if w == 0 || h == 0 {
@@ -187,10 +400,7 @@ impl CurcatApp {
                 coord.round().clamp(0.0, max) as usize
             }
         };
-        let x = clamp_coord(pixel.x, w);
-        let y = clamp_coord(pixel.y, h);
-        let idx = y * w + x;
-        image.pixels.pixels.get(idx).copied()
+        Some((clamp_coord(pixel.x, w), clamp_coord(pixel.y, h)))
     }
 
     /// Pick a curve color from the image and invalidate snap maps accordingly.
@@ -1,5 +1,5 @@
-use crate::snap::{SnapFeatureSource, SnapMapCache, SnapThresholdKind};
-use egui::Color32;
+use crate::snap::{ColorSampleMode, CurveMask, SnapFeatureSource, SnapMapCache, SnapThresholdKind};
+use egui::{Color32, Pos2};
 use std::sync::mpsc::Receiver;
 
 pub struct SnapBuildJob {
@@ -11,21 +11,67 @@ pub enum PointInputMode {
     Free,
     ContrastSnap,
     CenterlineSnap,
+    /// Entry of [`crate::snap::SNAP_SCORERS`] by index.
+    Scorer(usize),
 }
 
+/// Everything besides the pointer that decides where a snap search lands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapQueryKey {
+    pub(super) mode: PointInputMode,
+    pub(super) threshold: f32,
+    pub(super) feature_source: SnapFeatureSource,
+    pub(super) threshold_kind: SnapThresholdKind,
+    pub(super) radius: f32,
+    pub(super) bridge_gaps: bool,
+}
+
+/// The last snap search and where it landed.
+#[derive(Debug, Clone, Copy)]
+pub struct SnapQuery {
+    pub(super) pixel: Pos2,
+    pub(super) key: SnapQueryKey,
+    pub(super) result: Option<Pos2>,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct SnapState {
     pub(super) point_input_mode: PointInputMode,
     pub(super) contrast_search_radius: f32,
     pub(super) contrast_threshold: f32,
     pub(super) centerline_threshold: f32,
+    /// Threshold of each registered scorer, parallel to [`crate::snap::SNAP_SCORERS`].
+    pub(super) scorer_thresholds: Vec<f32>,
     pub(super) snap_feature_source: SnapFeatureSource,
     pub(super) snap_threshold_kind: SnapThresholdKind,
     pub(super) snap_target_color: Color32,
     pub(super) snap_color_tolerance: f32,
+    pub(super) color_sample_mode: ColorSampleMode,
     pub(super) snap_maps: Option<SnapMapCache>,
+    /// Segmentation mask for the current image geometry, attached to every snap map build.
+    pub(super) curve_mask: Option<CurveMask>,
+    /// Segmentation model running on the current image; its mask replaces `curve_mask`.
+    #[cfg(feature = "segmentation")]
+    pub(super) pending_mask_job: Option<Receiver<anyhow::Result<CurveMask>>>,
+    /// Image polygon snapping and auto-trace stay inside; `None` searches the whole image.
+    pub(super) search_area: Option<Vec<Pos2>>,
+    /// Corners of a snap-area polygon clicked so far.
+    pub(super) search_area_draft: Vec<Pos2>,
+    /// Leave detected text (tick labels, titles) out of the snap search.
+    pub(super) ignore_text: bool,
+    /// Fade horizontal and vertical grid lines in the pixels the snap maps are built from.
+    pub(super) suppress_grid: bool,
+    /// Let a click in the gap of a dashed or dotted curve land on the line through its dashes.
+    pub(super) bridge_gaps: bool,
     pub(super) pending_snap_job: Option<SnapBuildJob>,
     pub(super) snap_maps_dirty: bool,
+    /// Write the next built maps to the disk cache; set for fresh pixels, not for tweaks.
+    pub(super) persist_next_snap_build: bool,
     pub(super) snap_overlay_color: Color32,
     pub(super) snap_overlay_choices: Vec<Color32>,
     pub(super) snap_overlay_choice: usize,
+    /// Reused while the cursor jitters in place; cleared whenever the maps or overlays change.
+    pub(super) last_query: Option<SnapQuery>,
+    /// Input time of the last search the hover preview ran, for debouncing fast motion.
+    pub(super) preview_searched_at: Option<f64>,
 }
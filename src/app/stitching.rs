@@ -0,0 +1,136 @@
+//! Stitching several overlapping scans into one working image.
+//!
+//! The scans are decoded and aligned on a worker thread; the result is written as a PNG
+//! next to the first scan and then opened like any other image, so it can be calibrated,
+//! digitized and saved as a project as usual.
+
+use super::CurcatApp;
+use crate::config::AppConfig;
+use crate::i18n::UiLanguage;
+use crate::image::{ImageLoadOutcome, ImageLoadPolicy, StitchMode, decode_image_from_path};
+use anyhow::Context as _;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// Scans queued for stitching and the running job, if any.
+#[derive(Default)]
+pub struct StitchState {
+    /// Scans in stitching order; each must overlap the one before it.
+    pub(crate) parts: Vec<PathBuf>,
+    pub(crate) mode: StitchMode,
+    pub(crate) job: Option<Receiver<Result<PathBuf, String>>>,
+}
+
+impl CurcatApp {
+    /// Queue picked scans, sorted by file name, behind the ones already listed.
+    pub(crate) fn add_stitch_parts(&mut self, mut paths: Vec<PathBuf>) {
+        paths.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+        for path in paths {
+            if !self.project.stitch.parts.contains(&path) {
+                self.project.stitch.parts.push(path);
+            }
+        }
+        if let Some(first) = self.project.stitch.parts.first().cloned() {
+            self.remember_image_dir_from_path(&first);
+        }
+        self.ui.stitch_window_open = true;
+    }
+
+    pub(crate) const fn move_stitch_part(&mut self, from: usize, to: usize) {
+        let parts = self.project.stitch.parts.as_mut_slice();
+        if from < parts.len() && to < parts.len() {
+            parts.swap(from, to);
+        }
+    }
+
+    pub(crate) fn start_stitch_job(&mut self) {
+        if self.project.stitch.parts.len() < 2 || self.project.stitch.job.is_some() {
+            return;
+        }
+        let parts = self.project.stitch.parts.clone();
+        let mode = self.project.stitch.mode;
+        let cfg = self.config.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = stitch_to_file(&cfg, &parts, mode).map_err(|err| format!("{err:#}"));
+            let _ = tx.send(result);
+        });
+        self.project.stitch.job = Some(rx);
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Stitching scans…",
+            UiLanguage::Ru => "Склейка сканов…",
+        });
+    }
+
+    pub(crate) fn poll_stitch_job(&mut self) {
+        let Some(rx) = self.project.stitch.job.as_ref() else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("worker disconnected".to_string()),
+        };
+        self.project.stitch.job = None;
+        match result {
+            Ok(path) => {
+                self.project.stitch.parts.clear();
+                self.ui.stitch_window_open = false;
+                self.start_loading_image_from_path(path);
+            }
+            Err(err) => self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Stitching failed: {err}"),
+                UiLanguage::Ru => format!("Склейка не удалась: {err}"),
+            }),
+        }
+    }
+}
+
+/// Decode, stitch and save the scans; returns the path of the stitched PNG.
+fn stitch_to_file(cfg: &AppConfig, parts: &[PathBuf], mode: StitchMode) -> anyhow::Result<PathBuf> {
+    let images = parts
+        .iter()
+        .map(|path| {
+            // Scans keep their own resolution; the stitched result goes through the limits.
+            match decode_image_from_path(cfg, path, ImageLoadPolicy::IgnoreConfigWithHardCap)? {
+                ImageLoadOutcome::Ready(image) => Ok(image),
+                ImageLoadOutcome::NeedsLimitDecision(_) => {
+                    anyhow::bail!("{} exceeds the image size limits", path.display())
+                }
+            }
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let stitched = crate::image::stitch_images(&images, mode)?;
+    let out = stitched_path(&parts[0]);
+    let [width, height] = stitched.size;
+    let rgba = image::RgbaImage::from_raw(
+        u32::try_from(width)?,
+        u32::try_from(height)?,
+        stitched.as_raw().to_vec(),
+    )
+    .context("stitched image buffer has the wrong size")?;
+    rgba.save(&out)
+        .with_context(|| format!("Failed to write {}", out.display()))?;
+    Ok(out)
+}
+
+/// `scan-1.png` → `scan-1-stitched.png` in the same directory.
+fn stitched_path(first: &Path) -> PathBuf {
+    let stem = first
+        .file_stem()
+        .map_or_else(|| "scan".into(), |s| s.to_string_lossy());
+    first.with_file_name(format!("{stem}-stitched.png"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stitched_image_is_written_next_to_the_first_scan() {
+        assert_eq!(
+            stitched_path(Path::new("/scans/strip-01.tif")),
+            Path::new("/scans/strip-01-stitched.png")
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Surface export of a curve family: the curves that carry a parameter value, gridded over
+//! x and the parameter.
+
+use super::{CurcatApp, StatusLevel};
+use crate::error::CurcatError;
+use crate::export::{PARAMETER_COLUMN_HEADER, Surface, SurfaceFormat};
+use crate::i18n::UiLanguage;
+use crate::types::YAxis;
+use std::path::Path;
+
+impl CurcatApp {
+    /// Curves whose parameter text reads as a number.
+    pub(crate) fn parameter_curve_count(&self) -> usize {
+        self.points
+            .curves
+            .curves
+            .iter()
+            .filter(|curve| curve.parameter().is_some())
+            .count()
+    }
+
+    /// Grid the parameter curves with the export's sample count and interpolation.
+    pub(crate) fn build_surface(&mut self) -> Result<Surface, String> {
+        if !self.calibration_ready() {
+            return Err("Complete the calibration before export.".to_string());
+        }
+        let (x_mapping, y_mapping) = self.cartesian_mappings();
+        let polar_mapping = self.polar_mapping();
+        self.ensure_point_numeric_cache(
+            self.calibration.coord_system,
+            x_mapping.as_ref(),
+            y_mapping.as_ref(),
+            polar_mapping.as_ref(),
+        );
+
+        let curves = &self.points.curves.curves;
+        let on_secondary =
+            |idx: usize| self.uses_secondary_y_axis() && curves[idx].y_axis == YAxis::Secondary;
+        let members: Vec<usize> = (0..curves.len())
+            .filter(|&idx| curves[idx].parameter().is_some())
+            .collect();
+        let secondary = members.first().is_some_and(|&idx| on_secondary(idx));
+        if members.iter().any(|&idx| on_secondary(idx) != secondary) {
+            return Err("The curves of a surface must be read against the same Y axis.".into());
+        }
+        let family = members
+            .iter()
+            .filter_map(|&idx| {
+                let points = self.interpolation_nodes(self.curve_points(idx));
+                Some((curves[idx].parameter()?, points))
+            })
+            .collect();
+        let mut surface = Surface::assemble(
+            family,
+            self.export.sample_count,
+            self.node_interp_algorithm(),
+        )
+        .map_err(|err| err.to_string())?;
+
+        let (x_label, y_label) = self.axis_labels();
+        surface.x_unit = x_mapping.map_or(surface.x_unit, |mapping| mapping.unit);
+        surface.x_label = x_label;
+        surface.y_label = if secondary {
+            self.secondary_y_label()
+        } else {
+            y_label
+        };
+        surface.parameter_label = self
+            .parameter_column_header()
+            .unwrap_or_else(|| PARAMETER_COLUMN_HEADER.to_string());
+        surface.title.clone_from(&self.project.title);
+        surface.options = self.export_options();
+        Ok(surface)
+    }
+
+    /// Write `surface` right away; the grid is small next to a full point export.
+    pub(crate) fn write_surface(&mut self, path: &Path, surface: &Surface, format: SurfaceFormat) {
+        let label = format.label();
+        match format.export(path, surface) {
+            Ok(()) => {
+                self.set_status(self.i18n().format_exported(label));
+                self.push_toast(
+                    StatusLevel::Info,
+                    match self.ui.language {
+                        UiLanguage::En => format!("{label} saved to {}", path.display()),
+                        UiLanguage::Ru => format!("{label} сохранён в {}", path.display()),
+                    },
+                );
+            }
+            Err(source) => {
+                let _ = std::fs::remove_file(path);
+                self.report_error(&CurcatError::Export {
+                    format: label,
+                    source,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Per-project library of named colors for snap targets and curve colors.
+
+use super::CurcatApp;
+use crate::project;
+use egui::Color32;
+
+/// Named color saved with the project, e.g. "Series A red".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorSwatch {
+    pub name: String,
+    pub color: Color32,
+}
+
+impl CurcatApp {
+    /// Save `color` under `name`, replacing the color of a swatch with the same name.
+    ///
+    /// Returns `false` when the trimmed name is empty.
+    pub(crate) fn add_swatch(&mut self, name: &str, color: Color32) -> bool {
+        let name = name.trim();
+        if name.is_empty() {
+            return false;
+        }
+        let swatches = &mut self.project.swatches;
+        if let Some(existing) = swatches.iter_mut().find(|s| s.name == name) {
+            existing.color = color;
+        } else {
+            swatches.push(ColorSwatch {
+                name: name.to_string(),
+                color,
+            });
+        }
+        true
+    }
+
+    pub(crate) fn remove_swatch(&mut self, idx: usize) {
+        if idx < self.project.swatches.len() {
+            self.project.swatches.remove(idx);
+        }
+    }
+
+    /// Use a swatch as the color snap target.
+    pub(crate) fn apply_swatch_to_snap_target(&mut self, color: Color32) {
+        if self.snap.snap_target_color != color {
+            self.snap.snap_target_color = color;
+            self.mark_snap_maps_dirty();
+        }
+    }
+
+    pub(crate) fn swatches_to_records(&self) -> Vec<project::SwatchRecord> {
+        self.project
+            .swatches
+            .iter()
+            .map(|s| project::SwatchRecord {
+                name: s.name.clone(),
+                color: s.color.to_array(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn set_swatches_from_records(&mut self, records: &[project::SwatchRecord]) {
+        self.project.swatches = records
+            .iter()
+            .map(|r| {
+                let [red, green, blue, alpha] = r.color;
+                ColorSwatch {
+                    name: r.name.clone(),
+                    color: Color32::from_rgba_premultiplied(red, green, blue, alpha),
+                }
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_a_swatch_under_an_existing_name_replaces_its_color() {
+        let mut app = CurcatApp::default();
+        assert!(!app.add_swatch("   ", Color32::RED));
+        assert!(app.add_swatch(" Series A red ", Color32::RED));
+        assert!(app.add_swatch("Series B", Color32::BLUE));
+        assert!(app.add_swatch("Series A red", Color32::DARK_RED));
+        assert_eq!(
+            app.project.swatches,
+            vec![
+                ColorSwatch {
+                    name: "Series A red".to_string(),
+                    color: Color32::DARK_RED,
+                },
+                ColorSwatch {
+                    name: "Series B".to_string(),
+                    color: Color32::BLUE,
+                },
+            ]
+        );
+
+        let records = app.swatches_to_records();
+        app.remove_swatch(0);
+        assert_eq!(app.project.swatches.len(), 1);
+        app.set_swatches_from_records(&records);
+        assert_eq!(app.project.swatches.len(), 2);
+        assert_eq!(app.project.swatches[0].color, Color32::DARK_RED);
+    }
+}
@@ -1,12 +1,31 @@
 pub mod auto_trace;
 pub mod center;
+pub mod chart_text;
 pub mod common;
 pub mod dialogs;
+pub mod export_history;
 pub mod filters;
+pub mod flags;
+pub mod focus;
 pub mod icons;
 pub mod image_limits;
 pub mod info;
+pub mod keymap;
+pub mod loupe;
+pub mod magnifier;
+pub mod plot_crop;
+pub mod point_table;
+pub mod preprocess;
+#[cfg(feature = "profiling")]
+pub mod profiler;
 pub mod project;
+pub mod result_plot;
+pub mod sandbox;
 pub mod side;
 pub mod stats;
+pub mod stitch;
+pub mod svg_import;
+pub mod swatches;
+pub mod toasts;
 pub mod top;
+pub mod validation;
@@ -17,6 +17,8 @@ impl CurcatApp {
             .collapsible(false)
             .show(ctx, |ui| {
                 self.ui_auto_trace_section(ui);
+                ui.separator();
+                self.ui_marker_detection_section(ui);
             });
         self.ui.auto_trace_window_open = open;
     }
@@ -28,10 +30,7 @@ impl CurcatApp {
         let has_image = self.image.image.is_some();
         let calibrated = self.calibration_ready();
         let cartesian = matches!(self.calibration.coord_system, CoordSystem::Cartesian);
-        let snap_ok = matches!(
-            self.snap.point_input_mode,
-            PointInputMode::ContrastSnap | PointInputMode::CenterlineSnap
-        );
+        let snap_ok = !matches!(self.snap.point_input_mode, PointInputMode::Free);
         let can_trace = has_image && calibrated && cartesian && snap_ok;
         let trace_hint = if !has_image {
             i18n.text(TextKey::LoadImageFirst)
@@ -125,4 +124,44 @@ impl CurcatApp {
                 .clamping(egui::SliderClamping::Always),
         );
     }
+
+    fn ui_marker_detection_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::DetectMarkersIntro)).small());
+        let can_detect = self.image.image.is_some() && self.calibration_ready();
+        let hint = if self.image.image.is_none() {
+            i18n.text(TextKey::LoadImageFirst)
+        } else if can_detect {
+            i18n.text(TextKey::DetectMarkersHover)
+        } else {
+            i18n.text(TextKey::CompleteCalibrationBeforeTracing)
+        };
+        if ui
+            .add_enabled(
+                can_detect,
+                egui::Button::new(i18n.text(TextKey::DetectMarkers)),
+            )
+            .on_hover_text(hint)
+            .clicked()
+        {
+            self.interaction.marker_review = None;
+            self.begin_pick_mode(PickMode::MarkerSample);
+        }
+        let Some(review) = &self.interaction.marker_review else {
+            return;
+        };
+        ui.label(i18n.format_markers_found(review.candidates.len()));
+        ui.horizontal(|ui| {
+            if ui
+                .button(i18n.text(TextKey::AddMarkerPoints))
+                .on_hover_text(i18n.text(TextKey::AddMarkerPointsHover))
+                .clicked()
+            {
+                self.accept_detected_markers();
+            }
+            if ui.button(i18n.text(TextKey::DiscardProposal)).clicked() {
+                self.discard_detected_markers();
+            }
+        });
+    }
 }
@@ -1,18 +1,48 @@
+use super::super::clipboard::is_project_path;
 use super::super::{
     AutoPlaceState, AxisValueField, CalIntSnapSticky, CalSnapEndpoint, CalSnapGuide, CurcatApp,
-    DragTarget, PickMode, PointInputMode, PrimaryPressInfo, safe_usize_to_f32,
+    DragTarget, PickMode, PickedPoint, PointInputMode, PrimaryPressInfo, safe_usize_to_f32,
 };
 use super::icons;
 
 use crate::i18n::TextKey;
-use crate::types::{AxisMapping, AxisValue, CoordSystem, PolarMapping};
+use crate::keymap::ShortcutAction;
+use crate::profiling::profile_function;
+use crate::types::{AxisMapping, AxisValue, CoordSystem, FloatFormat, PolarMapping, ScaleKind};
 use egui::{Color32, CornerRadius, Key, PointerButton, Pos2, Sense, Vec2, pos2};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 const LIGHT_DRAG_CLICK_DIST: f32 = 20.0;
+/// Ring around the selected point or calibration handle.
+const SELECTION_RING_COLOR: Color32 = Color32::from_rgb(90, 170, 255);
+/// Opacity of a point marker with no snap confidence, so hand-placed points stay visible.
+const MIN_CONFIDENCE_OPACITY: f32 = 0.3;
 const LIGHT_DRAG_CLICK_MAX_DURATION: Duration = Duration::from_millis(400);
 
+/// Marker opacity of a point with snap `confidence`.
+fn confidence_opacity(confidence: f32) -> f32 {
+    (1.0 - MIN_CONFIDENCE_OPACITY).mul_add(confidence.clamp(0.0, 1.0), MIN_CONFIDENCE_OPACITY)
+}
+
+/// Small padlock centred at `center`, marking a locked point or curve.
+fn draw_padlock(painter: &egui::Painter, center: Pos2, color: Color32) {
+    let body = egui::Rect::from_center_size(center + Vec2::new(0.0, 1.5), Vec2::new(7.0, 5.0));
+    let outline = egui::Stroke::new(1.0_f32, Color32::from_black_alpha(160));
+    painter.rect_filled(body.expand(1.0), 1.5, outline.color);
+    painter.rect_filled(body, 1.0, color);
+    let shackle: Vec<Pos2> = (0..=8_u8)
+        .map(|step| {
+            let angle = std::f32::consts::PI * (1.0 + f32::from(step) / 8.0);
+            body.center_top() + Vec2::angled(angle) * 2.3
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        shackle,
+        egui::Stroke::new(1.4_f32, color),
+    ));
+}
+
 fn is_soft_primary_click(
     press: &PrimaryPressInfo,
     release_pos: Option<Pos2>,
@@ -33,9 +63,22 @@ fn is_soft_primary_click(
     dist <= LIGHT_DRAG_CLICK_DIST && elapsed <= LIGHT_DRAG_CLICK_MAX_DURATION
 }
 
-fn format_overlay_value(value: &AxisValue) -> String {
+/// Snap radius (in image pixels) from which the hover preview skips searches during fast motion.
+const SNAP_DEBOUNCE_MIN_RADIUS: f32 = 24.0;
+/// Pointer speed (points per second) above which the hover preview is debounced.
+const SNAP_DEBOUNCE_SPEED: f32 = 600.0;
+/// Shortest gap between hover preview searches while the pointer moves fast.
+const SNAP_DEBOUNCE_INTERVAL: f64 = 0.05;
+
+/// Significant figures of the axis span shown in hover labels.
+const OVERLAY_SIGNIFICANT_FIGURES: usize = 4;
+
+/// Hover label text: enough digits to resolve the axis `span`, scientific for extreme values.
+fn format_overlay_value(value: &AxisValue, span: f64) -> String {
     match value {
-        AxisValue::Float(v) => format!("{v:.3}"),
+        AxisValue::Float(v) => {
+            FloatFormat::adaptive(span, *v, OVERLAY_SIGNIFICANT_FIGURES).format_trimmed(*v)
+        }
         AxisValue::DateTime(_) => value.format(),
     }
 }
@@ -366,6 +409,7 @@ struct PointerState {
     primary_released: bool,
     delete_down: bool,
     ctrl_pressed: bool,
+    alt_pressed: bool,
     press_origin: Option<Pos2>,
     latest_pos: Option<Pos2>,
 }
@@ -379,6 +423,7 @@ impl PointerState {
             primary_released: i.pointer.button_released(PointerButton::Primary),
             delete_down: i.key_down(Key::Delete),
             ctrl_pressed: i.modifiers.ctrl,
+            alt_pressed: i.modifiers.alt,
             press_origin: i.pointer.press_origin(),
             latest_pos: i.pointer.latest_pos(),
         })
@@ -402,6 +447,8 @@ enum CalTarget {
     X2,
     Y1,
     Y2,
+    SecondaryY1,
+    SecondaryY2,
     Origin,
     R1,
     R2,
@@ -416,6 +463,8 @@ impl CalTarget {
             Self::X2 => "X2",
             Self::Y1 => "Y1",
             Self::Y2 => "Y2",
+            Self::SecondaryY1 => "Y'1",
+            Self::SecondaryY2 => "Y'2",
             Self::Origin => "Origin",
             Self::R1 => "R1",
             Self::R2 => "R2",
@@ -430,6 +479,8 @@ impl CalTarget {
             Self::X2 => Some(AxisValueField::X2),
             Self::Y1 => Some(AxisValueField::Y1),
             Self::Y2 => Some(AxisValueField::Y2),
+            Self::SecondaryY1 => Some(AxisValueField::SecondaryY1),
+            Self::SecondaryY2 => Some(AxisValueField::SecondaryY2),
             Self::R1 => Some(AxisValueField::R1),
             Self::R2 => Some(AxisValueField::R2),
             Self::A1 => Some(AxisValueField::A1),
@@ -444,12 +495,19 @@ impl CalTarget {
             DragTarget::CalX2 => Some(Self::X2),
             DragTarget::CalY1 => Some(Self::Y1),
             DragTarget::CalY2 => Some(Self::Y2),
+            DragTarget::CalSecondaryY1 => Some(Self::SecondaryY1),
+            DragTarget::CalSecondaryY2 => Some(Self::SecondaryY2),
             DragTarget::PolarOrigin => Some(Self::Origin),
             DragTarget::PolarR1 => Some(Self::R1),
             DragTarget::PolarR2 => Some(Self::R2),
             DragTarget::PolarA1 => Some(Self::A1),
             DragTarget::PolarA2 => Some(Self::A2),
-            DragTarget::CalXLine | DragTarget::CalYLine | DragTarget::CurvePoint(_) => None,
+            DragTarget::CalXLine
+            | DragTarget::CalYLine
+            | DragTarget::CurvePoint(_)
+            | DragTarget::AffineRef(_)
+            | DragTarget::SmithCenter
+            | DragTarget::SmithShort => None,
         }
     }
 
@@ -459,6 +517,8 @@ impl CalTarget {
             PickMode::X2 => Some(Self::X2),
             PickMode::Y1 => Some(Self::Y1),
             PickMode::Y2 => Some(Self::Y2),
+            PickMode::SecondaryY1 => Some(Self::SecondaryY1),
+            PickMode::SecondaryY2 => Some(Self::SecondaryY2),
             PickMode::Origin => Some(Self::Origin),
             PickMode::R1 => Some(Self::R1),
             PickMode::R2 => Some(Self::R2),
@@ -583,6 +643,10 @@ impl CurcatApp {
 
         match action {
             DropAction::None => {}
+            DropAction::LoadPath(path) if is_project_path(&path) => self.handle_project_load(path),
+            DropAction::LoadPath(_) | DropAction::LoadBytes { .. } if self.view_only() => {
+                self.warn_view_only();
+            }
             DropAction::LoadPath(path) => {
                 if cfg!(debug_assertions) {
                     eprintln!("[DnD] Loading from path: {}", path.display());
@@ -746,11 +810,39 @@ impl CurcatApp {
         }
     }
 
-    fn compute_snap_preview(&mut self, pointer_pixel: Option<Pos2>) -> Option<Pos2> {
+    /// Snap target under the pointer. With a large radius and a fast pointer, searches run at
+    /// most every [`SNAP_DEBOUNCE_INTERVAL`] and the last target stands in between; a repaint
+    /// is scheduled so the preview catches up once the pointer slows down.
+    fn compute_snap_preview(
+        &mut self,
+        ctx: &egui::Context,
+        pointer_pixel: Option<Pos2>,
+    ) -> Option<Pos2> {
         if !matches!(self.snap.point_input_mode, PointInputMode::Free)
-            && !matches!(self.calibration.pick_mode, PickMode::CurveColor)
+            && !matches!(
+                self.calibration.pick_mode,
+                PickMode::CurveColor
+                    | PickMode::LegendEntry
+                    | PickMode::ChartText(_)
+                    | PickMode::MarkerSample
+                    | PickMode::MarkerReview
+                    | PickMode::SelectPoints
+            )
             && let Some(pixel) = pointer_pixel
         {
+            let (now, speed) = ctx.input(|i| (i.time, i.pointer.velocity().length()));
+            if self.snap.contrast_search_radius >= SNAP_DEBOUNCE_MIN_RADIUS
+                && speed > SNAP_DEBOUNCE_SPEED
+                && let Some(searched_at) = self.snap.preview_searched_at
+                && now - searched_at < SNAP_DEBOUNCE_INTERVAL
+                && let Some(query) = self.snap.last_query
+                && Some(query.key) == self.current_snap_query_key()
+            {
+                let wait = SNAP_DEBOUNCE_INTERVAL - (now - searched_at);
+                ctx.request_repaint_after(Duration::from_secs_f64(wait.max(0.0)));
+                return query.result;
+            }
+            self.snap.preview_searched_at = Some(now);
             self.compute_snap_candidate(pixel)
         } else {
             None
@@ -763,9 +855,14 @@ impl CurcatApp {
             CalTarget::X2 => Some(CartesianEndpointId::X2),
             CalTarget::Y1 => Some(CartesianEndpointId::Y1),
             CalTarget::Y2 => Some(CartesianEndpointId::Y2),
-            CalTarget::Origin | CalTarget::R1 | CalTarget::R2 | CalTarget::A1 | CalTarget::A2 => {
-                None
-            }
+            // The endpoint snaps line up with the X and Y axis pair only.
+            CalTarget::SecondaryY1
+            | CalTarget::SecondaryY2
+            | CalTarget::Origin
+            | CalTarget::R1
+            | CalTarget::R2
+            | CalTarget::A1
+            | CalTarget::A2 => None,
         }
     }
 
@@ -1261,6 +1358,8 @@ impl CurcatApp {
             CalTarget::X2 => self.calibration.cal_x.p1,
             CalTarget::Y1 => self.calibration.cal_y.p2,
             CalTarget::Y2 => self.calibration.cal_y.p1,
+            CalTarget::SecondaryY1 => self.calibration.cal_secondary_y.p2,
+            CalTarget::SecondaryY2 => self.calibration.cal_secondary_y.p1,
             CalTarget::R1 | CalTarget::R2 | CalTarget::A1 | CalTarget::A2 => {
                 self.calibration.polar_cal.origin
             }
@@ -1390,6 +1489,9 @@ impl CurcatApp {
                 self.calibration.cal_y.p2 = Some(snapped);
                 *y_mapping = self.calibration.cal_y.mapping();
             }
+            // Point values pick up the new secondary mapping in `ensure_point_numeric_cache`.
+            CalTarget::SecondaryY1 => self.calibration.cal_secondary_y.p1 = Some(snapped),
+            CalTarget::SecondaryY2 => self.calibration.cal_secondary_y.p2 = Some(snapped),
             CalTarget::Origin => {
                 self.calibration.polar_cal.origin = Some(snapped);
                 *polar_mapping = self.calibration.polar_cal.mapping();
@@ -1421,6 +1523,103 @@ impl CurcatApp {
         }
     }
 
+    /// Curve point or calibration handle nearest to screen position `pos`, within hit range.
+    fn handle_near_screen(&self, pos: Pos2, rect: egui::Rect) -> Option<DragTarget> {
+        let mut best: Option<(DragTarget, f32)> = None;
+        let mut consider = |target: DragTarget, screen: Pos2| {
+            let dist = pos.distance(screen);
+            if dist <= super::super::POINT_HIT_RADIUS
+                && best.as_ref().is_none_or(|(_, best_dist)| dist < *best_dist)
+            {
+                best = Some((target, dist));
+            }
+        };
+
+        for (idx, point) in self.points.points.iter().enumerate() {
+            let screen = rect.min + point.pixel.to_vec2() * self.image.zoom;
+            consider(DragTarget::CurvePoint(idx), screen);
+        }
+
+        match self.calibration.coord_system {
+            CoordSystem::Cartesian => {
+                for (target, maybe_pixel) in [
+                    (DragTarget::CalX1, self.calibration.cal_x.p1),
+                    (DragTarget::CalX2, self.calibration.cal_x.p2),
+                    (DragTarget::CalY1, self.calibration.cal_y.p1),
+                    (DragTarget::CalY2, self.calibration.cal_y.p2),
+                    (
+                        DragTarget::CalSecondaryY1,
+                        self.calibration.cal_secondary_y.p1,
+                    ),
+                    (
+                        DragTarget::CalSecondaryY2,
+                        self.calibration.cal_secondary_y.p2,
+                    ),
+                ] {
+                    if let Some(pixel) = maybe_pixel {
+                        let screen = rect.min + pixel.to_vec2() * self.image.zoom;
+                        consider(target, screen);
+                    }
+                }
+                if let (Some(p1), Some(p2)) = (self.calibration.cal_x.p1, self.calibration.cal_x.p2)
+                {
+                    let a = rect.min + p1.to_vec2() * self.image.zoom;
+                    let b = rect.min + p2.to_vec2() * self.image.zoom;
+                    if let Some(dist) = line_drag_hit_distance(pos, a, b)
+                        && best.as_ref().is_none_or(|(_, best_dist)| dist < *best_dist)
+                    {
+                        best = Some((DragTarget::CalXLine, dist));
+                    }
+                }
+                if let (Some(p1), Some(p2)) = (self.calibration.cal_y.p1, self.calibration.cal_y.p2)
+                {
+                    let a = rect.min + p1.to_vec2() * self.image.zoom;
+                    let b = rect.min + p2.to_vec2() * self.image.zoom;
+                    if let Some(dist) = line_drag_hit_distance(pos, a, b)
+                        && best.as_ref().is_none_or(|(_, best_dist)| dist < *best_dist)
+                    {
+                        best = Some((DragTarget::CalYLine, dist));
+                    }
+                }
+            }
+            CoordSystem::Polar => {
+                for (target, maybe_pixel) in [
+                    (DragTarget::PolarOrigin, self.calibration.polar_cal.origin),
+                    (DragTarget::PolarR1, self.calibration.polar_cal.radius.p1),
+                    (DragTarget::PolarR2, self.calibration.polar_cal.radius.p2),
+                    (DragTarget::PolarA1, self.calibration.polar_cal.angle.p1),
+                    (DragTarget::PolarA2, self.calibration.polar_cal.angle.p2),
+                ] {
+                    if let Some(pixel) = maybe_pixel {
+                        let screen = rect.min + pixel.to_vec2() * self.image.zoom;
+                        consider(target, screen);
+                    }
+                }
+            }
+            CoordSystem::Affine => {
+                for (idx, point) in self.calibration.affine_cal.points.iter().enumerate() {
+                    if let Some(pixel) = point.pixel {
+                        let screen = rect.min + pixel.to_vec2() * self.image.zoom;
+                        consider(DragTarget::AffineRef(idx), screen);
+                    }
+                }
+            }
+            CoordSystem::Smith => {
+                for (target, maybe_pixel) in [
+                    (DragTarget::SmithCenter, self.calibration.smith_cal.center),
+                    (DragTarget::SmithShort, self.calibration.smith_cal.short),
+                ] {
+                    if let Some(pixel) = maybe_pixel {
+                        let screen = rect.min + pixel.to_vec2() * self.image.zoom;
+                        consider(target, screen);
+                    }
+                }
+            }
+        }
+
+        best.map(|(target, _)| target)
+    }
+
     fn draw_calibration_overlay(&self, painter: &egui::Painter, rect: egui::Rect) {
         if !self.calibration.show_calibration_segments {
             return;
@@ -1428,6 +1627,8 @@ impl CurcatApp {
         match self.calibration.coord_system {
             CoordSystem::Cartesian => self.draw_cartesian_calibration_overlay(painter, rect),
             CoordSystem::Polar => self.draw_polar_calibration_overlay(painter, rect),
+            CoordSystem::Affine => self.draw_affine_calibration_overlay(painter, rect),
+            CoordSystem::Smith => self.draw_smith_calibration_overlay(painter, rect),
         }
     }
 
@@ -1450,6 +1651,18 @@ impl CurcatApp {
         }
     }
 
+    fn draw_handle_selection(&mut self, painter: &egui::Painter, rect: egui::Rect) {
+        let zoom = self.image.zoom;
+        let radius = Self::calibration_style().point_outer_radius + 4.0;
+        for pixel in self.selected_handle_pixels() {
+            painter.circle_stroke(
+                rect.min + pixel.to_vec2() * zoom,
+                radius,
+                egui::Stroke::new(2.0_f32, SELECTION_RING_COLOR),
+            );
+        }
+    }
+
     fn draw_cal_line(
         painter: &egui::Painter,
         rect: egui::Rect,
@@ -1466,6 +1679,71 @@ impl CurcatApp {
         painter.line_segment(line, style.stroke);
     }
 
+    /// Arrowhead on the `low`→`high` calibration line, pointing the way its values grow.
+    fn draw_cal_arrow(
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        zoom: f32,
+        style: &CalOverlayStyle,
+        low: Pos2,
+        high: Pos2,
+    ) {
+        const ARROW_SIZE: f32 = 9.0;
+        let start = rect.min + low.to_vec2() * zoom;
+        let end = rect.min + high.to_vec2() * zoom;
+        let span = end - start;
+        if span.length() < ARROW_SIZE * 4.0 {
+            return;
+        }
+        let dir = span.normalized();
+        let tip = start + span * 0.8;
+        let back = tip - dir * ARROW_SIZE;
+        let side = dir.rot90() * (ARROW_SIZE * 0.55);
+        painter.add(egui::Shape::convex_polygon(
+            vec![tip, back + side, back - side],
+            style.stroke.color,
+            egui::Stroke::new(1.0_f32, style.outline.color),
+        ));
+    }
+
+    /// Decade ticks of a log axis along its calibration line across the image, longest at the
+    /// powers of ten; they should sit on the printed ticks when the chart really is log.
+    fn draw_log_decade_ticks(
+        painter: &egui::Painter,
+        rect: egui::Rect,
+        zoom: f32,
+        style: &CalOverlayStyle,
+        mapping: &AxisMapping,
+    ) {
+        // Below this many screen points per decade only the powers of ten are drawn.
+        const MIN_DECADE_SCREEN: f32 = 40.0;
+        let span = (mapping.p2 - mapping.p1) * zoom;
+        let decades = (mapping.v2.to_scalar_seconds() / mapping.v1.to_scalar_seconds())
+            .log10()
+            .abs();
+        if span.length() <= f32::EPSILON || decades <= f64::EPSILON {
+            return;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let decade_screen = span.length() / decades as f32;
+        let normal = span.normalized().rot90();
+        let bounds = egui::Rect::from_min_size(Pos2::ZERO, rect.size() / zoom);
+        let halo = egui::Stroke::new(3.0_f32, style.outline.color);
+        let stroke = egui::Stroke::new(1.0_f32, style.stroke.color);
+        for (point, digit) in mapping.log_decade_ticks(bounds) {
+            let reach = match digit {
+                1 => 8.0,
+                _ if decade_screen < MIN_DECADE_SCREEN => continue,
+                5 => 5.5,
+                _ => 3.5,
+            };
+            let center = rect.min + point.to_vec2() * zoom;
+            let tick = [center - normal * reach, center + normal * reach];
+            painter.line_segment(tick, halo);
+            painter.line_segment(tick, stroke);
+        }
+    }
+
     fn draw_dashed_segment(painter: &egui::Painter, start: Pos2, end: Pos2, stroke: egui::Stroke) {
         let dir = end - start;
         let len = dir.length();
@@ -1543,6 +1821,8 @@ impl CurcatApp {
         };
         let x_normal = calc_label_normal(self.calibration.cal_x.p1, self.calibration.cal_x.p2);
         let y_normal = calc_label_normal(self.calibration.cal_y.p1, self.calibration.cal_y.p2);
+        let secondary = &self.calibration.cal_secondary_y;
+        let secondary_normal = calc_label_normal(secondary.p1, secondary.p2);
         let draw_cal_point = |point: Pos2, label: &str, normal: Option<Vec2>, flip_side: bool| {
             let screen = Self::draw_cal_point_base(painter, rect, self.image.zoom, &style, point);
             let dir = normal.unwrap_or(default_dir);
@@ -1604,6 +1884,24 @@ impl CurcatApp {
             draw_cal_line(p1, p2);
             draw_cal_length_label(p1, p2);
         }
+        if let Some(p1) = secondary.p1
+            && let Some(p2) = secondary.p2
+        {
+            draw_cal_line(p1, p2);
+            draw_cal_length_label(p1, p2);
+        }
+        for cal in [&self.calibration.cal_x, &self.calibration.cal_y, secondary] {
+            if cal.inverted
+                && let Some((low, high)) = cal.increasing_ends()
+            {
+                Self::draw_cal_arrow(painter, rect, self.image.zoom, &style, low, high);
+            }
+            if cal.scale == ScaleKind::Log10
+                && let Some(mapping) = cal.mapping()
+            {
+                Self::draw_log_decade_ticks(painter, rect, self.image.zoom, &style, &mapping);
+            }
+        }
         self.draw_calibration_snap_guides(painter, rect);
         if let Some(p) = self.calibration.cal_x.p1 {
             draw_cal_point(p, "X1", x_normal, false);
@@ -1617,6 +1915,98 @@ impl CurcatApp {
         if let Some(p) = self.calibration.cal_y.p2 {
             draw_cal_point(p, "Y2", y_normal, true);
         }
+        if let Some(p) = secondary.p1 {
+            draw_cal_point(p, "Y'1", secondary_normal, false);
+        }
+        if let Some(p) = secondary.p2 {
+            draw_cal_point(p, "Y'2", secondary_normal, true);
+        }
+    }
+
+    fn draw_affine_calibration_overlay(&self, painter: &egui::Painter, rect: egui::Rect) {
+        let style = Self::calibration_style();
+        for (idx, point) in self.calibration.affine_cal.points.iter().enumerate() {
+            let Some(pixel) = point.pixel else {
+                continue;
+            };
+            let screen = Self::draw_cal_point_base(painter, rect, self.image.zoom, &style, pixel);
+            let galley = painter.layout_no_wrap(
+                format!("P{}", idx + 1),
+                style.label_font.clone(),
+                style.stroke.color,
+            );
+            let label_pos = screen + Vec2::new(8.0, -8.0);
+            painter.galley(
+                label_pos + Vec2::splat(1.0),
+                galley.clone(),
+                style.label_shadow,
+            );
+            painter.galley(label_pos, galley, style.stroke.color);
+        }
+    }
+
+    /// Outer circle, real axis and the `r = 1` circle of the Smith chart being calibrated.
+    fn draw_smith_calibration_overlay(&self, painter: &egui::Painter, rect: egui::Rect) {
+        let style = Self::calibration_style();
+        let zoom = self.image.zoom;
+        let smith = &self.calibration.smith_cal;
+        if let (Some(center), Some(short)) = (smith.center, smith.short) {
+            let open = center + (center - short);
+            let radius = center.distance(short) * zoom;
+            let to_screen = |p: Pos2| rect.min + p.to_vec2() * zoom;
+            for (middle, r) in [(center, radius), (center.lerp(open, 0.5), radius * 0.5)] {
+                painter.circle_stroke(to_screen(middle), r, style.outline);
+                painter.circle_stroke(to_screen(middle), r, style.stroke);
+            }
+            Self::draw_cal_line(painter, rect, zoom, &style, short, open);
+        }
+        for (pixel, label) in [(smith.center, "Z0"), (smith.short, "SC")] {
+            let Some(pixel) = pixel else {
+                continue;
+            };
+            let screen = Self::draw_cal_point_base(painter, rect, zoom, &style, pixel);
+            let galley = painter.layout_no_wrap(
+                label.to_owned(),
+                style.label_font.clone(),
+                style.stroke.color,
+            );
+            let label_pos = screen + Vec2::new(8.0, -8.0);
+            painter.galley(
+                label_pos + Vec2::splat(1.0),
+                galley.clone(),
+                style.label_shadow,
+            );
+            painter.galley(label_pos, galley, style.stroke.color);
+        }
+    }
+
+    /// Corners clicked so far for a pending perspective warp, joined in click
+    /// order, or the first end of a horizon line.
+    fn draw_perspective_overlay(&self, painter: &egui::Painter, rect: egui::Rect) {
+        let (corners, prefix) = match self.calibration.pick_mode {
+            PickMode::PerspectiveCorner => (self.image.perspective_corners.as_slice(), "C"),
+            PickMode::HorizonPoint => (self.image.horizon_start.as_slice(), "H"),
+            _ => return,
+        };
+        let style = Self::calibration_style();
+        for pair in corners.windows(2) {
+            Self::draw_cal_line(painter, rect, self.image.zoom, &style, pair[0], pair[1]);
+        }
+        for (idx, &pixel) in corners.iter().enumerate() {
+            let screen = Self::draw_cal_point_base(painter, rect, self.image.zoom, &style, pixel);
+            let galley = painter.layout_no_wrap(
+                format!("{prefix}{}", idx + 1),
+                style.label_font.clone(),
+                style.stroke.color,
+            );
+            let label_pos = screen + Vec2::new(8.0, -8.0);
+            painter.galley(
+                label_pos + Vec2::splat(1.0),
+                galley.clone(),
+                style.label_shadow,
+            );
+            painter.galley(label_pos, galley, style.stroke.color);
+        }
     }
 
     fn draw_polar_calibration_overlay(&self, painter: &egui::Painter, rect: egui::Rect) {
@@ -1676,9 +2066,66 @@ impl CurcatApp {
         point_radius: f32,
         point_color: Color32,
     ) {
+        let faded = |color: Color32, p: &PickedPoint| {
+            if self.points.show_point_confidence {
+                color.gamma_multiply(confidence_opacity(p.confidence))
+            } else {
+                color
+            }
+        };
+        let active = self.points.curves.active;
+        let lock_offset = Vec2::new(-7.0, -8.0);
+        for idx in (0..self.points.curves.len()).filter(|&idx| idx != active) {
+            let color = self.curve_point_color(idx);
+            for p in self.curve_points(idx) {
+                let screen = rect.min + p.pixel.to_vec2() * self.image.zoom;
+                painter.circle_filled(screen, point_radius, faded(color, p));
+            }
+        }
+        // A locked curve carries one padlock, by its last point.
+        for (idx, curve) in self.points.curves.curves.iter().enumerate() {
+            if curve.locked
+                && let Some(last) = self.curve_points(idx).last()
+            {
+                let screen = rect.min + last.pixel.to_vec2() * self.image.zoom;
+                draw_padlock(painter, screen + lock_offset, self.curve_point_color(idx));
+            }
+        }
+        let flag_color = Color32::from_rgb(255, 170, 60);
         for (idx, p) in self.points.points.iter().enumerate() {
             let screen = rect.min + p.pixel.to_vec2() * self.image.zoom;
-            painter.circle_filled(screen, point_radius, point_color);
+            if let Some(ends) = p.error_bar {
+                let stroke = egui::Stroke::new(1.5_f32, point_color);
+                let ends = ends.map(|end| rect.min + end.to_vec2() * self.image.zoom);
+                painter.line_segment(ends, stroke);
+                let cap = (ends[1] - ends[0]).normalized().rot90() * (point_radius + 2.0);
+                for end in ends {
+                    painter.line_segment([end - cap, end + cap], stroke);
+                }
+            }
+            painter.circle_filled(screen, point_radius, faded(point_color, p));
+            if self.point_is_selected(idx) {
+                painter.circle_stroke(
+                    screen,
+                    point_radius + 5.0,
+                    egui::Stroke::new(2.0_f32, SELECTION_RING_COLOR),
+                );
+            }
+            if p.flag.is_some() {
+                let width = if self.ui.flag_review_selected == Some(idx) {
+                    2.5_f32
+                } else {
+                    1.5_f32
+                };
+                painter.circle_stroke(
+                    screen,
+                    point_radius + 3.0,
+                    egui::Stroke::new(width, flag_color),
+                );
+            }
+            if p.locked {
+                draw_padlock(painter, screen + lock_offset, Color32::WHITE);
+            }
             painter.text(
                 screen + Vec2::new(6.0, -6.0),
                 egui::Align2::LEFT_TOP,
@@ -1697,10 +2144,16 @@ impl CurcatApp {
         snap_preview: Option<Pos2>,
         point_radius: f32,
     ) {
-        if !matches!(
-            self.snap.point_input_mode,
-            PointInputMode::ContrastSnap | PointInputMode::CenterlineSnap
-        ) || matches!(self.calibration.pick_mode, PickMode::CurveColor)
+        if matches!(self.snap.point_input_mode, PointInputMode::Free)
+            || matches!(
+                self.calibration.pick_mode,
+                PickMode::CurveColor
+                    | PickMode::LegendEntry
+                    | PickMode::ChartText(_)
+                    | PickMode::MarkerSample
+                    | PickMode::MarkerReview
+                    | PickMode::SelectPoints
+            )
         {
             return;
         }
@@ -1729,8 +2182,23 @@ impl CurcatApp {
         if !self.points.show_curve_segments {
             return;
         }
-        let stroke_curve = self.config.curve_line.stroke();
         let zoom = self.image.zoom;
+        let active = self.points.curves.active;
+        for idx in (0..self.points.curves.len()).filter(|&idx| idx != active) {
+            let mut segments: Vec<(f64, Pos2)> = self
+                .curve_points(idx)
+                .iter()
+                .filter_map(|p| p.x_numeric.map(|x| (x, p.pixel)))
+                .collect();
+            segments.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let stroke = self.curve_line_stroke(idx);
+            for win in segments.windows(2) {
+                let a = rect.min + win[0].1.to_vec2() * zoom;
+                let b = rect.min + win[1].1.to_vec2() * zoom;
+                painter.line_segment([a, b], stroke);
+            }
+        }
+        let stroke_curve = self.curve_line_stroke(active);
         let preview_segments = self.sorted_preview_segments();
         if preview_segments.len() >= 2 {
             for win in preview_segments.windows(2) {
@@ -1741,6 +2209,27 @@ impl CurcatApp {
         }
     }
 
+    /// Pasted reference points: hollow markers joined by a dashed line, kept distinct from picks.
+    fn draw_imported_overlay(&self, painter: &egui::Painter, rect: egui::Rect, point_radius: f32) {
+        let pixels = self.imported_point_pixels();
+        if pixels.is_empty() {
+            return;
+        }
+        let color = Color32::from_rgb(90, 200, 250);
+        let stroke = egui::Stroke::new(1.2_f32, color);
+        let zoom = self.image.zoom;
+        let screen: Vec<Pos2> = pixels
+            .iter()
+            .map(|p| rect.min + p.to_vec2() * zoom)
+            .collect();
+        for win in screen.windows(2) {
+            Self::draw_dashed_segment(painter, win[0], win[1], stroke);
+        }
+        for pos in screen {
+            painter.circle_stroke(pos, point_radius.max(2.5), stroke);
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
     fn draw_navigator_minimap(
         &mut self,
@@ -1917,7 +2406,7 @@ impl CurcatApp {
             return;
         };
 
-        let crosshair_color = self.config.crosshair.color32();
+        let crosshair_color = self.crosshair_style().color32();
         let stroke = egui::Stroke::new(1.0_f32, crosshair_color);
         match self.calibration.coord_system {
             CoordSystem::Cartesian => {
@@ -1930,6 +2419,14 @@ impl CurcatApp {
                     stroke,
                 );
             }
+            CoordSystem::Affine => {
+                // Follow the skewed grid: each line keeps one axis value constant.
+                let reach = rect.size().length();
+                for mapping in [x_mapping, y_mapping].into_iter().flatten() {
+                    let dir = (mapping.p2 - mapping.p1).normalized().rot90() * reach;
+                    painter.line_segment([pos - dir, pos + dir], stroke);
+                }
+            }
             CoordSystem::Polar => {
                 if let Some(origin) = self.calibration.polar_cal.origin {
                     let origin_screen = rect.min + origin.to_vec2() * self.image.zoom;
@@ -1941,6 +2438,22 @@ impl CurcatApp {
                     }
                 }
             }
+            CoordSystem::Smith => {
+                // The constant-resistance circle through the pointer.
+                if let Some(mapping) = self.smith_mapping()
+                    && let Some((r, _)) = mapping.impedance_at(pixel)
+                    && r > -1.0
+                    && let Some(middle) = mapping.pixel_at(r.mul_add(2.0, 1.0), 0.0)
+                {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let radius = (mapping.radius() / (r + 1.0)) as f32 * self.image.zoom;
+                    painter.circle_stroke(
+                        rect.min + middle.to_vec2() * self.image.zoom,
+                        radius,
+                        stroke,
+                    );
+                }
+            }
         }
 
         let font = egui::FontId::proportional(12.0);
@@ -1975,11 +2488,12 @@ impl CurcatApp {
         };
 
         match self.calibration.coord_system {
-            CoordSystem::Cartesian => {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
                 if let Some(xmap) = x_mapping
                     && let Some(value) = xmap.value_at(pixel)
                 {
-                    let text = format_overlay_value(&value);
+                    let span = xmap.display_span(value.to_scalar_seconds());
+                    let text = format_overlay_value(&value, span);
                     let galley = painter.layout_no_wrap(text, font.clone(), text_color);
                     let size = galley.size();
                     let total = size + padding * 2.0;
@@ -2000,7 +2514,8 @@ impl CurcatApp {
                 if let Some(ymap) = y_mapping
                     && let Some(value) = ymap.value_at(pixel)
                 {
-                    let text = format_overlay_value(&value);
+                    let span = ymap.display_span(value.to_scalar_seconds());
+                    let text = format_overlay_value(&value, span);
                     let galley = painter.layout_no_wrap(text, font, text_color);
                     let size = galley.size();
                     let total = size + padding * 2.0;
@@ -2040,24 +2555,44 @@ impl CurcatApp {
                         let r_center = origin_screen + dir * (radial_len * 0.5);
 
                         if let Some(angle) = mapping.angle_at(pixel) {
-                            let text = format!("{angle:.3}");
+                            let text = format_overlay_value(
+                                &AxisValue::Float(angle),
+                                mapping.angle_display_span(),
+                            );
                             draw_label_centered(theta_center, text, font.clone());
                         }
                         if let Some(radius) = mapping.radius_at(pixel) {
-                            let text = format!("{radius:.3}");
+                            let text = format_overlay_value(
+                                &AxisValue::Float(radius),
+                                mapping.radius_display_span(radius),
+                            );
                             draw_label_centered(r_center, text, font);
                         }
                     }
                 }
             }
+            CoordSystem::Smith => {
+                if let Some((r, x)) = self
+                    .smith_mapping()
+                    .and_then(|mapping| mapping.impedance_at(pixel))
+                {
+                    let r_text = format_overlay_value(&AxisValue::Float(r), 1.0);
+                    let x_text = format_overlay_value(&AxisValue::Float(x.abs()), 1.0);
+                    let sign = if x < 0.0 { '−' } else { '+' };
+                    let text = format!("z = {r_text} {sign} j{x_text}");
+                    draw_label_centered(pos + Vec2::new(0.0, -28.0), text, font);
+                }
+            }
         }
 
         let badge_offset = Vec2::new(18.0, -18.0);
         let badge_anchor = pos + badge_offset;
         let badge_radius = 12.0;
         let showed_color_badge = {
-            if matches!(self.calibration.pick_mode, PickMode::CurveColor)
-                && let Some(sampled) = self.sample_image_color(pixel)
+            if matches!(
+                self.calibration.pick_mode,
+                PickMode::CurveColor | PickMode::LegendEntry | PickMode::MarkerSample
+            ) && let Some(sampled) = self.sample_image_color(pixel)
             {
                 let [r, g, b, _] = sampled.to_array();
                 let badge_color = Color32::from_rgb(r, g, b);
@@ -2067,6 +2602,16 @@ impl CurcatApp {
                     badge_radius,
                     egui::Stroke::new(1.0_f32, Color32::from_gray(30)),
                 );
+                let i18n = self.i18n();
+                let preview = format!(
+                    "#{r:02X}{g:02X}{b:02X} · {}\n{}",
+                    i18n.color_sample_mode_label(self.snap.color_sample_mode),
+                    i18n.text(TextKey::ClickToUseColor),
+                );
+                // Above the badge, clear of the magnifier below-right of the cursor.
+                let preview_center = badge_anchor - Vec2::new(0.0, badge_radius + 22.0);
+                draw_label_centered(preview_center, preview, egui::FontId::proportional(12.0));
+                self.draw_color_magnifier(painter, pos, pixel);
                 true
             } else {
                 false
@@ -2105,18 +2650,18 @@ impl CurcatApp {
 
     #[allow(clippy::too_many_lines)]
     pub(crate) fn ui_central_image(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        profile_function!();
         self.image.last_pixels_per_point = ctx.pixels_per_point().max(1.0);
         self.image.last_viewport_size = Some(ui.available_size());
+        self.image.last_image_rect = None;
         self.apply_pending_fit_on_load();
         self.handle_drag_and_drop(ui);
 
         if let Some(img) = self.image.image.as_ref() {
-            let mut x_mapping = self.calibration.cal_x.mapping();
-            let mut y_mapping = self.calibration.cal_y.mapping();
+            let (mut x_mapping, mut y_mapping) = self.cartesian_mappings();
             let mut polar_mapping = self.polar_mapping();
             let mut pending_zoom: Option<f32> = None;
             let mut pending_zoom_anchor: Option<Pos2> = None;
-            let mut image_screen_rect: Option<egui::Rect> = None;
             let mut image_base_size: Option<Vec2> = None;
             // Take a snapshot of the texture handle and size to avoid borrowing self.image in the UI closure
             let (tex_id, img_size) = (img.texture.id(), img.size);
@@ -2137,7 +2682,7 @@ impl CurcatApp {
                 let image = egui::Image::new((tex_id, display_size));
                 let response = self.add_centered_image(ui, image, display_size);
                 let rect = response.rect;
-                image_screen_rect = Some(rect);
+                self.image.last_image_rect = Some(rect);
                 image_base_size = Some(base_size);
                 let painter = ui.painter_at(rect);
 
@@ -2183,108 +2728,79 @@ impl CurcatApp {
                     pointer_pos,
                     hover_pos,
                 );
-                let snap_preview = self.compute_snap_preview(pointer_pixel);
+                let zoom_region_active = self.track_zoom_region(
+                    &response,
+                    pointer_state.ctrl_pressed && !pointer_state.shift_pressed,
+                    pointer_state.press_origin.map(&to_pixel),
+                    pointer_pixel,
+                );
+                let snap_area_active = self.track_snap_area_drag(
+                    &response,
+                    pointer_state.press_origin.map(&to_pixel),
+                    pointer_pixel,
+                );
+                let point_selection_active = self.track_point_selection_drag(
+                    &response,
+                    pointer_state.alt_pressed,
+                    pointer_state.press_origin.map(&to_pixel),
+                    pointer_pixel,
+                );
+                let x_range_active = self.track_export_range_drag(
+                    &response,
+                    pointer_state.press_origin.map(&to_pixel),
+                    pointer_pixel,
+                );
+                let snap_preview = self.compute_snap_preview(ctx, pointer_pixel);
                 let calibrated = match self.calibration.coord_system {
-                    CoordSystem::Cartesian => {
+                    CoordSystem::Cartesian | CoordSystem::Affine => {
                         x_mapping.is_some() && y_mapping.is_some()
                     }
                     CoordSystem::Polar => polar_mapping.is_some(),
+                    CoordSystem::Smith => self.smith_mapping().is_some(),
                 };
                 let auto_place_pointer_pixel = if primary_gesture.pointer_over_image {
                     pointer_pixel
                 } else {
                     None
                 };
-                let suppress_primary_click = self.auto_place_tick(
-                    auto_place_pointer_pixel,
-                    primary_gesture,
-                    pointer_state.shift_pressed,
-                    pointer_state.delete_down,
-                    calibrated,
-                );
+                // Ctrl + drag frames a zoom region and a drag on an export range bound moves it,
+                // instead of auto-placing points.
+                // View-only mode keeps the gestures that navigate and drops those that edit.
+                let editable = !self.view_only();
+                let suppress_primary_click = !editable
+                    || self.auto_place_tick(
+                        auto_place_pointer_pixel,
+                        primary_gesture,
+                        pointer_state.shift_pressed || pointer_state.ctrl_pressed || x_range_active,
+                        pointer_state.delete_down,
+                        calibrated,
+                    )
+                    || zoom_region_active
+                    || snap_area_active
+                    || point_selection_active
+                    || x_range_active;
 
                 if primary_gesture.down && primary_gesture.started_in_image {
                     ui.ctx().request_repaint_after(Duration::from_millis(16));
                 }
 
-                if pointer_state.shift_pressed
+                if editable
+                    && pointer_state.shift_pressed
                     && response.drag_started_by(PointerButton::Primary)
                     && let Some(pos) = pointer_pos
                 {
-                    let mut best: Option<(DragTarget, f32)> = None;
-                    let mut consider = |target: DragTarget, screen: Pos2| {
-                        let dist = pos.distance(screen);
-                        if dist <= super::super::POINT_HIT_RADIUS
-                            && best.as_ref().is_none_or(|(_, best_dist)| dist < *best_dist)
-                        {
-                            best = Some((target, dist));
-                        }
-                    };
-
-                    for (idx, point) in self.points.points.iter().enumerate() {
-                        let screen = rect.min + point.pixel.to_vec2() * self.image.zoom;
-                        consider(DragTarget::CurvePoint(idx), screen);
-                    }
-
-                    match self.calibration.coord_system {
-                        CoordSystem::Cartesian => {
-                            for (target, maybe_pixel) in [
-                                (DragTarget::CalX1, self.calibration.cal_x.p1),
-                                (DragTarget::CalX2, self.calibration.cal_x.p2),
-                                (DragTarget::CalY1, self.calibration.cal_y.p1),
-                                (DragTarget::CalY2, self.calibration.cal_y.p2),
-                            ] {
-                                if let Some(pixel) = maybe_pixel {
-                                    let screen = rect.min + pixel.to_vec2() * self.image.zoom;
-                                    consider(target, screen);
-                                }
-                            }
-                            if let (Some(p1), Some(p2)) =
-                                (self.calibration.cal_x.p1, self.calibration.cal_x.p2)
-                            {
-                                let a = rect.min + p1.to_vec2() * self.image.zoom;
-                                let b = rect.min + p2.to_vec2() * self.image.zoom;
-                                if let Some(dist) = line_drag_hit_distance(pos, a, b)
-                                    && best
-                                        .as_ref()
-                                        .is_none_or(|(_, best_dist)| dist < *best_dist)
-                                {
-                                    best = Some((DragTarget::CalXLine, dist));
-                                }
-                            }
-                            if let (Some(p1), Some(p2)) =
-                                (self.calibration.cal_y.p1, self.calibration.cal_y.p2)
-                            {
-                                let a = rect.min + p1.to_vec2() * self.image.zoom;
-                                let b = rect.min + p2.to_vec2() * self.image.zoom;
-                                if let Some(dist) = line_drag_hit_distance(pos, a, b)
-                                    && best
-                                        .as_ref()
-                                        .is_none_or(|(_, best_dist)| dist < *best_dist)
-                                {
-                                    best = Some((DragTarget::CalYLine, dist));
-                                }
-                            }
-                        }
-                        CoordSystem::Polar => {
-                            for (target, maybe_pixel) in [
-                                (DragTarget::PolarOrigin, self.calibration.polar_cal.origin),
-                                (DragTarget::PolarR1, self.calibration.polar_cal.radius.p1),
-                                (DragTarget::PolarR2, self.calibration.polar_cal.radius.p2),
-                                (DragTarget::PolarA1, self.calibration.polar_cal.angle.p1),
-                                (DragTarget::PolarA2, self.calibration.polar_cal.angle.p2),
-                            ] {
-                                if let Some(pixel) = maybe_pixel {
-                                    let screen = rect.min + pixel.to_vec2() * self.image.zoom;
-                                    consider(target, screen);
-                                }
-                            }
-                        }
+                    let mut picked = self.handle_near_screen(pos, rect);
+                    if let Some(DragTarget::CurvePoint(idx)) = picked
+                        && self.point_locked(idx)
+                    {
+                        self.warn_point_locked();
+                        picked = None;
                     }
-
-                    let picked = best.map(|(target, _)| target);
                     self.calibration.dragging_handle = picked;
                     self.calibration.drag_last_pixel = picked.map(|_| to_pixel(pos));
+                    if picked.is_some() {
+                        self.interaction.selection = picked;
+                    }
                 }
 
                 if let Some(target) = self.calibration.dragging_handle {
@@ -2293,7 +2809,14 @@ impl CurcatApp {
                         match target {
                             DragTarget::CurvePoint(idx) => {
                                 if let Some(point) = self.points.points.get_mut(idx) {
+                                    let shift = pixel - point.pixel;
                                     point.pixel = pixel;
+                                    point.confidence = 0.0;
+                                    if let Some(ends) = &mut point.error_bar {
+                                        for end in ends {
+                                            *end += shift;
+                                        }
+                                    }
                                     self.mark_points_dirty();
                                 }
                             }
@@ -2323,6 +2846,18 @@ impl CurcatApp {
                                     }
                                 }
                             }
+                            DragTarget::AffineRef(idx) => {
+                                if let Some(point) = self.calibration.affine_cal.points.get_mut(idx) {
+                                    point.pixel = Some(pixel);
+                                    (x_mapping, y_mapping) = self.cartesian_mappings();
+                                }
+                            }
+                            DragTarget::SmithCenter => {
+                                self.calibration.smith_cal.center = Some(pixel);
+                            }
+                            DragTarget::SmithShort => {
+                                self.calibration.smith_cal.short = Some(pixel);
+                            }
                             _ => {
                                 if let Some(cal_target) = CalTarget::from_drag(target) {
                                     self.apply_calibration_point(
@@ -2342,12 +2877,21 @@ impl CurcatApp {
                     if !pointer_state.shift_pressed || !pointer_state.primary_down {
                         self.clear_calibration_drag_runtime();
                     }
-                } else if response.clicked_by(PointerButton::Secondary)
+                } else if editable
+                    && response.clicked_by(PointerButton::Secondary)
                     && matches!(self.calibration.pick_mode, PickMode::None)
                     && let Some(pos) = pointer_pos
                 {
                     let image_origin = rect.min;
                     self.remove_point_near_screen(pos, image_origin);
+                } else if editable
+                    && pointer_state.shift_pressed
+                    && response.clicked_by(PointerButton::Primary)
+                    && let Some(pos) = pointer_pos
+                {
+                    // Shift + click picks what the arrow keys nudge; empty space deselects.
+                    self.interaction.selection = self.handle_near_screen(pos, rect);
+                    self.interaction.point_selection.clear();
                 } else if primary_gesture.clicked
                     && !suppress_primary_click
                     && !pointer_state.shift_pressed
@@ -2356,13 +2900,18 @@ impl CurcatApp {
                     if pointer_state.delete_down {
                         let image_origin = rect.min;
                         self.remove_point_near_screen(pos, image_origin);
+                    } else if pointer_state.alt_pressed
+                        && matches!(self.calibration.pick_mode, PickMode::None)
+                    {
+                        let image_origin = rect.min;
+                        self.toggle_flag_near_screen(pos, image_origin);
                     } else {
                         let pixel = to_pixel(pos);
                         let pick_mode = self.calibration.pick_mode;
                         match pick_mode {
                             PickMode::None => {
                                 if calibrated {
-                                    self.push_curve_point(pixel);
+                                    self.pick_point_or_error_bar(pixel);
                                 } else {
                                     self.set_status_warn(match self.calibration.coord_system {
                                         CoordSystem::Cartesian => {
@@ -2381,6 +2930,22 @@ impl CurcatApp {
                                                     "Калибровка неполная: задайте начало, радиус и угол перед установкой точек.",
                                             }
                                         }
+                                        CoordSystem::Affine => {
+                                            match self.ui.language {
+                                                crate::i18n::UiLanguage::En =>
+                                                    "Calibration incomplete: place at least three reference points before picking points.",
+                                                crate::i18n::UiLanguage::Ru =>
+                                                    "Калибровка неполная: задайте как минимум три опорные точки перед установкой точек.",
+                                            }
+                                        }
+                                        CoordSystem::Smith => {
+                                            match self.ui.language {
+                                                crate::i18n::UiLanguage::En =>
+                                                    "Calibration incomplete: place the chart centre and the short-circuit point before picking points.",
+                                                crate::i18n::UiLanguage::Ru =>
+                                                    "Калибровка неполная: задайте центр диаграммы и точку короткого замыкания перед установкой точек.",
+                                            }
+                                        }
                                     });
                                 }
                             }
@@ -2388,10 +2953,36 @@ impl CurcatApp {
                                 self.pick_curve_color_at(pixel);
                                 self.calibration.pick_mode = PickMode::None;
                             }
+                            PickMode::LegendEntry => self.seed_curve_from_legend(pixel),
+                            PickMode::ChartText(field) => {
+                                self.read_chart_text(field, pixel);
+                                self.calibration.pick_mode = PickMode::None;
+                            }
                             PickMode::AutoTrace => {
                                 self.auto_trace_from(pixel);
                                 self.calibration.pick_mode = PickMode::None;
                             }
+                            PickMode::SnapArea => self.pick_snap_area_vertex(pixel),
+                            PickMode::SelectPoints => self.select_points(Vec::new()),
+                            PickMode::MarkerSample => self.detect_markers_like(pixel),
+                            PickMode::MarkerReview => self.drop_marker_candidate_near(pixel),
+                            PickMode::AffineRef(idx) => {
+                                self.pick_affine_reference(idx, pixel);
+                                (x_mapping, y_mapping) = self.cartesian_mappings();
+                            }
+                            PickMode::SmithCenter | PickMode::SmithShort => {
+                                self.pick_smith_point(pixel);
+                            }
+                            PickMode::PerspectiveCorner => {
+                                self.pick_perspective_corner(pixel);
+                                (x_mapping, y_mapping) = self.cartesian_mappings();
+                                polar_mapping = self.polar_mapping();
+                            }
+                            PickMode::HorizonPoint => {
+                                self.pick_horizon_point(pixel);
+                                (x_mapping, y_mapping) = self.cartesian_mappings();
+                                polar_mapping = self.polar_mapping();
+                            }
                             _ => {
                                 if let Some(cal_target) = CalTarget::from_pick_mode(pick_mode) {
                                     self.apply_calibration_point(
@@ -2415,14 +3006,25 @@ impl CurcatApp {
                     y_mapping.as_ref(),
                     polar_mapping.as_ref(),
                 );
+                self.draw_focus_dim(&painter, rect, hover_pos_only);
                 self.draw_calibration_overlay(&painter, rect);
-
-                let point_style = &self.config.curve_points;
-                let point_color = point_style.color32();
-                let point_radius = point_style.radius();
+                self.draw_handle_selection(&painter, rect);
+                self.draw_perspective_overlay(&painter, rect);
+                self.draw_zoom_region(&painter, rect);
+                self.draw_snap_area(&painter, rect, hover_pixel);
+                self.draw_point_selection_drag(&painter, rect);
+                self.draw_export_range(&painter, rect);
+                self.draw_plot_crop(&painter, rect);
+                self.draw_marker_candidates(&painter, rect);
+
+                let point_color = self.curve_point_color(self.points.curves.active);
+                let point_radius = self.curve_point_style().radius();
                 self.draw_points_overlay(&painter, rect, point_radius, point_color);
                 self.draw_snap_overlay(&painter, rect, pointer_pixel, snap_preview, point_radius);
                 self.draw_curve_preview(&painter, rect);
+                self.draw_smoothing_preview(&painter, rect);
+                self.draw_level_crossings(&painter, rect);
+                self.draw_imported_overlay(&painter, rect, point_radius);
                 self.draw_crosshair_overlay(
                     ui,
                     &painter,
@@ -2436,6 +3038,7 @@ impl CurcatApp {
                     pointer_state.shift_pressed,
                     pointer_state.ctrl_pressed,
                 );
+                self.draw_loupe(ui, hover_pos_only, hover_pixel);
             });
             if self.image.skip_pan_sync_once {
                 self.image.skip_pan_sync_once = false;
@@ -2451,7 +3054,9 @@ impl CurcatApp {
                     self.set_zoom_about_viewport_center(next_zoom);
                 }
             }
-            if let (Some(image_rect), Some(image_size)) = (image_screen_rect, image_base_size) {
+            if let (Some(image_rect), Some(image_size)) =
+                (self.image.last_image_rect, image_base_size)
+            {
                 self.draw_navigator_minimap(
                     ui,
                     tex_id,
@@ -2474,7 +3079,10 @@ impl CurcatApp {
             });
         } else {
             ui.centered_and_justified(|ui| {
-                ui.label(self.t(TextKey::DropHint));
+                ui.label(format!(
+                    "{}.",
+                    self.hover_with_shortcut(TextKey::DropHint, ShortcutAction::PasteImage)
+                ));
             });
         }
     }
@@ -2499,10 +3107,16 @@ impl CurcatApp {
         if matches!(self.calibration.pick_mode, PickMode::AutoTrace) {
             return Some(CursorBadge::Icon(icons::ICON_AUTO_TRACE, Color32::WHITE));
         }
+        if matches!(self.calibration.pick_mode, PickMode::ZoomRegion) {
+            return Some(CursorBadge::Icon(icons::ICON_ZOOM, Color32::WHITE));
+        }
         if self.interaction.auto_place_state.active {
             return Some(CursorBadge::Icon(icons::ICON_AUTO_PLACE, Color32::WHITE));
         }
-        if matches!(self.calibration.pick_mode, PickMode::CurveColor) {
+        if matches!(
+            self.calibration.pick_mode,
+            PickMode::CurveColor | PickMode::LegendEntry | PickMode::MarkerSample
+        ) {
             return Some(CursorBadge::Icon(icons::ICON_PICK_COLOR, Color32::WHITE));
         }
         if delete_down {
@@ -2523,16 +3137,39 @@ impl CurcatApp {
             PickMode::X2 => Some(CursorBadge::Text("X2", Color32::from_rgb(190, 225, 255))),
             PickMode::Y1 => Some(CursorBadge::Text("Y1", Color32::from_rgb(200, 255, 200))),
             PickMode::Y2 => Some(CursorBadge::Text("Y2", Color32::from_rgb(200, 255, 200))),
+            PickMode::SecondaryY1 => {
+                Some(CursorBadge::Text("Y'1", Color32::from_rgb(255, 200, 240)))
+            }
+            PickMode::SecondaryY2 => {
+                Some(CursorBadge::Text("Y'2", Color32::from_rgb(255, 200, 240)))
+            }
             PickMode::Origin => Some(CursorBadge::Text("O", Color32::from_rgb(255, 230, 180))),
             PickMode::R1 => Some(CursorBadge::Text("R1", Color32::from_rgb(255, 210, 160))),
             PickMode::R2 => Some(CursorBadge::Text("R2", Color32::from_rgb(255, 210, 160))),
             PickMode::A1 => Some(CursorBadge::Text("A1", Color32::from_rgb(200, 210, 255))),
             PickMode::A2 => Some(CursorBadge::Text("A2", Color32::from_rgb(200, 210, 255))),
+            PickMode::AffineRef(_) => {
+                Some(CursorBadge::Text("P", Color32::from_rgb(235, 200, 255)))
+            }
+            PickMode::SmithCenter => {
+                Some(CursorBadge::Text("Z0", Color32::from_rgb(190, 240, 220)))
+            }
+            PickMode::SmithShort => Some(CursorBadge::Text("SC", Color32::from_rgb(190, 240, 220))),
+            PickMode::PerspectiveCorner => {
+                Some(CursorBadge::Text("C", Color32::from_rgb(255, 235, 170)))
+            }
+            PickMode::HorizonPoint => {
+                Some(CursorBadge::Text("H", Color32::from_rgb(255, 235, 170)))
+            }
             _ => None,
         }
     }
 
-    fn remove_point_near_screen(&mut self, pointer: Pos2, image_origin: Pos2) -> bool {
+    pub(crate) fn point_index_near_screen(
+        &self,
+        pointer: Pos2,
+        image_origin: Pos2,
+    ) -> Option<usize> {
         let mut best: Option<(usize, f32)> = None;
         for (idx, point) in self.points.points.iter().enumerate() {
             let screen = image_origin + point.pixel.to_vec2() * self.image.zoom;
@@ -2543,8 +3180,18 @@ impl CurcatApp {
                 best = Some((idx, dist));
             }
         }
-        if let Some((idx, _)) = best {
+        best.map(|(idx, _)| idx)
+    }
+
+    fn remove_point_near_screen(&mut self, pointer: Pos2, image_origin: Pos2) -> bool {
+        if let Some(idx) = self.point_index_near_screen(pointer, image_origin) {
+            if self.point_locked(idx) {
+                self.warn_point_locked();
+                return false;
+            }
             self.points.points.remove(idx);
+            self.shift_selection_after_removal(idx);
+            self.cancel_pending_error_bar();
             self.mark_points_dirty();
             self.set_status(match self.ui.language {
                 crate::i18n::UiLanguage::En => "Point removed.",
@@ -0,0 +1,78 @@
+use crate::app::{ChartTextField, CurcatApp, PickMode};
+use crate::i18n::TextKey;
+use egui::RichText;
+
+impl CurcatApp {
+    pub(crate) fn ui_chart_text_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.chart_text_window_open {
+            return;
+        }
+        let mut open = self.ui.chart_text_window_open;
+        egui::Window::new(self.t(TextKey::ChartTextWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| self.ui_chart_text_section(ui));
+        self.ui.chart_text_window_open = open && self.ui.chart_text_window_open;
+        if !self.ui.chart_text_window_open
+            && matches!(self.calibration.pick_mode, PickMode::ChartText(_))
+        {
+            self.cancel_pick_mode();
+        }
+    }
+
+    fn ui_chart_text_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::ChartTextIntro)).small());
+        ui.add_space(4.0);
+
+        let mut title = self.project.title.clone().unwrap_or_default();
+        let mut pick = None;
+        egui::Grid::new("chart_text_grid")
+            .num_columns(3)
+            .show(ui, |ui| {
+                let fields = [
+                    (ChartTextField::Title, TextKey::ChartTitle),
+                    (ChartTextField::XAxisTitle, TextKey::XAxisTitle),
+                    (ChartTextField::YAxisTitle, TextKey::YAxisTitle),
+                ];
+                for (field, key) in fields {
+                    ui.label(i18n.text(key));
+                    let text = match field {
+                        ChartTextField::Title => &mut title,
+                        ChartTextField::XAxisTitle => &mut self.project.axis_titles[0],
+                        ChartTextField::YAxisTitle => &mut self.project.axis_titles[1],
+                    };
+                    ui.add(egui::TextEdit::singleline(text).desired_width(220.0));
+                    let picking = self.calibration.pick_mode == PickMode::ChartText(field);
+                    if ui
+                        .add(egui::Button::selectable(
+                            picking,
+                            i18n.text(TextKey::ReadChartText),
+                        ))
+                        .on_hover_text(i18n.text(TextKey::ReadChartTextHover))
+                        .clicked()
+                    {
+                        pick = Some((field, picking));
+                    }
+                    ui.end_row();
+                }
+            });
+        self.project.title = Some(title).filter(|t| !t.trim().is_empty());
+
+        ui.label(i18n.text(TextKey::ChartDescription));
+        let mut description = self.project.description.clone().unwrap_or_default();
+        ui.add(
+            egui::TextEdit::multiline(&mut description)
+                .desired_rows(3)
+                .desired_width(f32::INFINITY),
+        );
+        self.project.description = Some(description).filter(|d| !d.trim().is_empty());
+
+        match pick {
+            Some((_, true)) => self.cancel_pick_mode(),
+            Some((field, false)) => self.begin_pick_mode(PickMode::ChartText(field)),
+            None => {}
+        }
+    }
+}
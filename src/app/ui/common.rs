@@ -57,9 +57,12 @@ pub fn side_section_card_collapsible(
 }
 
 impl CurcatApp {
-    /// Compute a pulsing highlight color based on the UI time.
+    /// Compute a pulsing highlight color based on the UI time; steady while the window idles.
     #[allow(clippy::cast_possible_truncation)]
-    pub(crate) fn attention_color(ctx: &egui::Context, base: Color32) -> Color32 {
+    pub(crate) fn attention_color(&self, ctx: &egui::Context, base: Color32) -> Color32 {
+        if self.ui.idle {
+            return base;
+        }
         let [r, g, b, a] = base.to_array();
         let base_alpha = f32::from(a) / 255.0;
         let time = ctx.input(|i| i.time) as f32;
@@ -81,8 +84,8 @@ impl CurcatApp {
         if !active || !ui.is_rect_visible(rect) {
             return;
         }
-        let mut stroke = self.config.attention_highlight.stroke();
-        stroke.color = Self::attention_color(ui.ctx(), stroke.color);
+        let mut stroke = self.attention_highlight_style().stroke();
+        stroke.color = self.attention_color(ui.ctx(), stroke.color);
         ui.painter().rect_stroke(
             rect.expand(super::super::ATTENTION_OUTLINE_PAD),
             CornerRadius::ZERO,
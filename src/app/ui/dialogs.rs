@@ -1,31 +1,68 @@
+use super::super::file_chooser::{DialogMode, DialogSpec, FileChooser};
 use super::super::{CurcatApp, NativeDialog};
-use crate::export::ExportFormat;
+use crate::export::{ExportFormat, SurfaceFormat};
 use crate::i18n::UiLanguage;
-use egui_file_dialog::FileDialog;
-use std::path::Path;
+use std::path::PathBuf;
 
 impl CurcatApp {
     pub(crate) fn open_image_dialog(&mut self) {
-        let mut dialog = self.make_open_dialog(self.project.last_image_dir.as_deref());
-        dialog.pick_file();
+        let dialog = self.file_chooser(self.image_open_spec(DialogMode::PickFile));
         self.project.active_dialog = Some(NativeDialog::Open(dialog));
     }
 
+    pub(crate) fn open_stitch_scans_dialog(&mut self) {
+        let dialog = self.file_chooser(self.image_open_spec(DialogMode::PickMultiple));
+        self.project.active_dialog = Some(NativeDialog::OpenStitchScans(dialog));
+    }
+
     pub(crate) fn open_project_dialog(&mut self) {
-        let mut dialog = FileDialog::new()
-            .title(self.t(crate::i18n::TextKey::OpenProjectDialogTitle))
-            .add_file_filter_extensions(
-                self.t(crate::i18n::TextKey::CurcatProjectFilterLabel),
+        let dialog = self.file_chooser(DialogSpec {
+            title: self
+                .t(crate::i18n::TextKey::OpenProjectDialogTitle)
+                .to_string(),
+            mode: DialogMode::PickFile,
+            filters: vec![(
+                self.t(crate::i18n::TextKey::CurcatProjectFilterLabel)
+                    .to_string(),
                 vec!["curcat"],
-            )
-            .default_file_filter(self.t(crate::i18n::TextKey::CurcatProjectFilterLabel));
-        if let Some(dir) = self.project.last_project_dir.as_deref() {
-            dialog = dialog.initial_directory(dir.to_path_buf());
-        }
-        dialog.pick_file();
+            )],
+            initial_dir: self.project_dialog_dir(),
+        });
         self.project.active_dialog = Some(NativeDialog::OpenProject(dialog));
     }
 
+    pub(crate) fn open_curve_mask_dialog(&mut self) {
+        let dialog = self.file_chooser(DialogSpec {
+            title: self
+                .t(crate::i18n::TextKey::OpenCurveMaskDialogTitle)
+                .to_string(),
+            mode: DialogMode::PickFile,
+            filters: vec![(
+                self.t(crate::i18n::TextKey::ImageFilterAll).to_string(),
+                vec!["png", "bmp", "tiff", "tif", "pgm", "webp"],
+            )],
+            initial_dir: self.image_dialog_dir(),
+        });
+        self.project.active_dialog = Some(NativeDialog::OpenCurveMask(dialog));
+    }
+
+    #[cfg(feature = "segmentation")]
+    pub(crate) fn open_segmentation_model_dialog(&mut self) {
+        let dialog = self.file_chooser(DialogSpec {
+            title: self
+                .t(crate::i18n::TextKey::OpenSegmentationModelDialogTitle)
+                .to_string(),
+            mode: DialogMode::PickFile,
+            filters: vec![(
+                self.t(crate::i18n::TextKey::SegmentationModelFilter)
+                    .to_string(),
+                vec!["onnx"],
+            )],
+            initial_dir: self.image_dialog_dir(),
+        });
+        self.project.active_dialog = Some(NativeDialog::OpenSegmentationModel(dialog));
+    }
+
     pub(crate) fn save_project_dialog(&mut self) {
         let default_name = self
             .project
@@ -33,13 +70,12 @@ impl CurcatApp {
             .as_ref()
             .and_then(|p| p.file_name().map(|s| s.to_string_lossy().into_owned()))
             .unwrap_or_else(|| self.t(crate::i18n::TextKey::DefaultProjectName).to_string());
-        let mut dialog = Self::make_save_dialog(
+        let dialog = self.file_chooser(DialogSpec::save(
             self.t(crate::i18n::TextKey::SaveProjectDialogTitle),
             &default_name,
             &["curcat"],
-            self.project.last_project_dir.as_deref(),
-        );
-        dialog.save_file();
+            self.project_dialog_dir(),
+        ));
         self.project.active_dialog = Some(NativeDialog::SaveProject(dialog));
     }
 
@@ -51,6 +87,26 @@ impl CurcatApp {
         self.start_export(ExportFormat::Xlsx);
     }
 
+    pub(crate) fn start_export_ods(&mut self) {
+        self.start_export(ExportFormat::Ods);
+    }
+
+    pub(crate) fn start_export_arrow(&mut self) {
+        self.start_export(ExportFormat::Arrow);
+    }
+
+    pub(crate) fn start_export_parquet(&mut self) {
+        self.start_export(ExportFormat::Parquet);
+    }
+
+    pub(crate) fn start_export_sql(&mut self) {
+        self.start_export(ExportFormat::Sql);
+    }
+
+    pub(crate) fn start_export_netcdf(&mut self) {
+        self.start_export(ExportFormat::NetCdf);
+    }
+
     pub(crate) fn start_export_json(&mut self) {
         self.start_export(ExportFormat::Json);
     }
@@ -72,22 +128,25 @@ impl CurcatApp {
     }
 
     pub(crate) fn start_export(&mut self, format: ExportFormat) {
-        match self.build_export_payload() {
-            Ok(payload) => {
+        match self.build_export_curves() {
+            Ok(curves) if !self.validate_export(&curves) => {}
+            Ok(curves) => {
                 let dialog_title = match self.ui.language {
                     UiLanguage::En => format!("Export {}", format.label()),
                     UiLanguage::Ru => format!("Экспорт {}", format.label()),
                 };
-                let mut dialog = Self::make_save_dialog(
+                let extensions: Vec<&str> = std::iter::once(format.extension())
+                    .chain(format.alternate_extension())
+                    .collect();
+                let dialog = self.file_chooser(DialogSpec::save(
                     &dialog_title,
                     format.default_filename(),
-                    &[format.extension()],
-                    self.project.last_export_dir.as_deref(),
-                );
-                dialog.save_file();
+                    &extensions,
+                    self.export_dialog_dir(),
+                ));
                 self.project.active_dialog = Some(NativeDialog::SaveExport {
                     dialog,
-                    payload,
+                    curves,
                     format,
                 });
             }
@@ -95,55 +154,84 @@ impl CurcatApp {
         }
     }
 
-    pub(crate) fn make_open_dialog(&self, initial_dir: Option<&Path>) -> FileDialog {
-        // Keep in sync with enabled `image` crate features + SVG/SVGZ via resvg.
-        // Add separate presets for frequent formats.
-        let mut dialog = FileDialog::new()
-            .title(self.t(crate::i18n::TextKey::OpenImageDialogTitle))
-            // Combined filter
-            .add_file_filter_extensions(
-                self.t(crate::i18n::TextKey::ImageFilterAll),
-                vec![
-                    "png", "jpg", "jpeg", "gif", "bmp", "webp", "ico", "tga", "tiff", "tif", "pnm",
-                    "pbm", "pgm", "ppm", "hdr", "dds", "svg", "svgz",
-                ],
-            )
-            // Individual format presets
-            .add_file_filter_extensions("PNG", vec!["png"])
-            .add_file_filter_extensions("JPEG/JPG", vec!["jpg", "jpeg"])
-            .add_file_filter_extensions("BMP", vec!["bmp"])
-            .add_file_filter_extensions("TIFF", vec!["tiff", "tif"])
-            .add_file_filter_extensions("SVG/SVGZ", vec!["svg", "svgz"])
-            .default_file_filter(self.t(crate::i18n::TextKey::ImageFilterAll));
-        if let Some(dir) = initial_dir {
-            dialog = dialog.initial_directory(dir.to_path_buf());
-        }
-        dialog
-    }
-
-    pub(crate) fn make_save_dialog(
-        title: &str,
-        default_name: &str,
-        extensions: &[&str],
-        initial_dir: Option<&Path>,
-    ) -> FileDialog {
-        let mut dialog = FileDialog::new()
-            .title(title)
-            .default_file_name(default_name);
-        let mut first_label: Option<String> = None;
-        for ext in extensions {
-            let label = format!("*.{ext}");
-            if first_label.is_none() {
-                first_label = Some(label.clone());
+    /// Assemble the curve family surface and ask where to write it.
+    pub(crate) fn start_surface_export(&mut self, format: SurfaceFormat) {
+        match self.build_surface() {
+            Ok(surface) => {
+                let dialog_title = match self.ui.language {
+                    UiLanguage::En => format!("Export {}", format.label()),
+                    UiLanguage::Ru => format!("Экспорт {}", format.label()),
+                };
+                let dialog = self.file_chooser(DialogSpec::save(
+                    &dialog_title,
+                    format.default_filename(),
+                    &[format.extension()],
+                    self.export_dialog_dir(),
+                ));
+                self.project.active_dialog = Some(NativeDialog::SaveSurface {
+                    dialog,
+                    surface,
+                    format,
+                });
             }
-            dialog = dialog.add_save_extension(&label, ext);
-        }
-        if let Some(label) = first_label.as_deref() {
-            dialog = dialog.default_save_extension(label);
+            Err(msg) => self.set_status_warn(msg),
         }
-        if let Some(dir) = initial_dir {
-            dialog = dialog.initial_directory(dir.to_path_buf());
+    }
+
+    /// Open `spec` with the dialog backend chosen in `[dialogs]`.
+    fn file_chooser(&self, spec: DialogSpec) -> FileChooser {
+        FileChooser::open(spec, &self.config.dialogs, &self.ui.parent_window)
+    }
+
+    /// Where image dialogs start: the last image folder, else the configured default.
+    fn image_dialog_dir(&self) -> Option<PathBuf> {
+        self.project
+            .last_image_dir
+            .clone()
+            .or_else(|| self.config.dialogs.image_dir())
+    }
+
+    /// Where export dialogs start: the last export folder, else the configured default.
+    pub(super) fn export_dialog_dir(&self) -> Option<PathBuf> {
+        self.project
+            .last_export_dir
+            .clone()
+            .or_else(|| self.config.dialogs.export_dir())
+    }
+
+    /// Where project dialogs start: the last project folder, else the configured default.
+    fn project_dialog_dir(&self) -> Option<PathBuf> {
+        self.project
+            .last_project_dir
+            .clone()
+            .or_else(|| self.config.dialogs.project_dir())
+    }
+
+    fn image_open_spec(&self, mode: DialogMode) -> DialogSpec {
+        // Keep in sync with enabled `image` crate features + SVG/SVGZ via resvg.
+        // Add separate presets for frequent formats.
+        DialogSpec {
+            title: self
+                .t(crate::i18n::TextKey::OpenImageDialogTitle)
+                .to_string(),
+            mode,
+            filters: vec![
+                // Combined filter
+                (
+                    self.t(crate::i18n::TextKey::ImageFilterAll).to_string(),
+                    vec![
+                        "png", "jpg", "jpeg", "gif", "bmp", "webp", "ico", "tga", "tiff", "tif",
+                        "pnm", "pbm", "pgm", "ppm", "hdr", "dds", "svg", "svgz",
+                    ],
+                ),
+                // Individual format presets
+                ("PNG".to_string(), vec!["png"]),
+                ("JPEG/JPG".to_string(), vec!["jpg", "jpeg"]),
+                ("BMP".to_string(), vec!["bmp"]),
+                ("TIFF".to_string(), vec!["tiff", "tif"]),
+                ("SVG/SVGZ".to_string(), vec!["svg", "svgz"]),
+            ],
+            initial_dir: self.image_dialog_dir(),
         }
-        dialog
     }
 }
@@ -0,0 +1,124 @@
+use crate::app::export_history::format_export_time;
+use crate::app::{CurcatApp, ExportKind};
+use crate::i18n::{I18n, TextKey};
+use crate::project::ExportHistoryRecord;
+use egui::RichText;
+
+/// What a history row asks for.
+enum HistoryAction {
+    OpenFile(usize),
+    OpenFolder(usize),
+    Repeat(usize),
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_export_history_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.export_history_window_open {
+            return;
+        }
+        let mut open = self.ui.export_history_window_open;
+        egui::Window::new(self.t(TextKey::ExportHistoryWindow))
+            .open(&mut open)
+            .resizable(true)
+            .collapsible(false)
+            .default_width(520.0)
+            .show(ctx, |ui| self.ui_export_history_section(ui));
+        self.ui.export_history_window_open = open;
+    }
+
+    fn ui_export_history_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        if self.project.export_history.is_empty() {
+            ui.label(RichText::new(i18n.text(TextKey::ExportHistoryEmpty)).small());
+            return;
+        }
+        let can_repeat = self.calibration_ready() && !self.export_job_running();
+        let mut action = None;
+        egui::ScrollArea::vertical()
+            .max_height(320.0)
+            .show(ui, |ui| {
+                egui::Grid::new("export_history_grid")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(i18n.text(TextKey::ExportedAt)).strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ExportFormat)).strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ExportedFile)).strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ExportedRows)).strong());
+                        ui.label("");
+                        ui.end_row();
+                        // Newest first.
+                        let history = &self.project.export_history;
+                        for (idx, record) in history.iter().enumerate().rev() {
+                            if let Some(clicked) = history_row(ui, i18n, idx, record, can_repeat) {
+                                action = Some(clicked);
+                            }
+                        }
+                    });
+            });
+        ui.add_space(4.0);
+        if ui.button(i18n.text(TextKey::ClearExportHistory)).clicked() {
+            self.project.export_history.clear();
+        }
+        match action {
+            Some(HistoryAction::OpenFile(idx)) => self.open_exported_file(idx),
+            Some(HistoryAction::OpenFolder(idx)) => self.open_export_folder(idx),
+            Some(HistoryAction::Repeat(idx)) => self.repeat_export(idx),
+            None => {}
+        }
+    }
+}
+
+/// One row of the history grid; returns the action of a clicked button.
+fn history_row(
+    ui: &mut egui::Ui,
+    i18n: I18n,
+    idx: usize,
+    record: &ExportHistoryRecord,
+    can_repeat: bool,
+) -> Option<HistoryAction> {
+    let settings = &record.settings;
+    let kind = match settings.kind {
+        ExportKind::Interpolated => i18n.text(TextKey::InterpolatedCurve),
+        ExportKind::RawPoints => i18n.text(TextKey::RawPickedPoints),
+        ExportKind::Fitted => i18n.text(TextKey::FittedCurve),
+        ExportKind::Spectrum => i18n.text(TextKey::SpectrumExport),
+        ExportKind::Crossings => i18n.text(TextKey::CrossingsExport),
+    };
+    ui.label(format_export_time(record.exported_at_ms));
+    ui.label(settings.format.label()).on_hover_text(kind);
+    let name = record.path.file_name().map_or_else(
+        || record.path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    ui.label(name)
+        .on_hover_text(record.path.display().to_string());
+    ui.label(record.rows.to_string());
+    let mut action = None;
+    ui.horizontal(|ui| {
+        if ui
+            .small_button(i18n.text(TextKey::OpenExportedFile))
+            .clicked()
+        {
+            action = Some(HistoryAction::OpenFile(idx));
+        }
+        if ui
+            .small_button(i18n.text(TextKey::OpenExportFolder))
+            .clicked()
+        {
+            action = Some(HistoryAction::OpenFolder(idx));
+        }
+        if ui
+            .add_enabled(
+                can_repeat,
+                egui::Button::new(i18n.text(TextKey::RepeatExport)).small(),
+            )
+            .on_hover_text(i18n.text(TextKey::RepeatExportHover))
+            .clicked()
+        {
+            action = Some(HistoryAction::Repeat(idx));
+        }
+    });
+    ui.end_row();
+    action
+}
@@ -0,0 +1,191 @@
+use crate::app::CurcatApp;
+use crate::i18n::TextKey;
+use egui::RichText;
+
+enum FlagReviewAction {
+    CheckContinuity,
+    Select(usize),
+    AddComment(usize),
+    Unflag(usize),
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_flag_review_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.flag_review_window_open {
+            return;
+        }
+        if self
+            .ui
+            .flag_review_selected
+            .is_some_and(|idx| self.points.points.get(idx).is_none_or(|p| p.flag.is_none()))
+        {
+            self.ui.flag_review_selected = None;
+        }
+
+        let mut open = self.ui.flag_review_window_open;
+        let mut action: Option<FlagReviewAction> = None;
+        let i18n = self.i18n();
+        egui::Window::new(i18n.text(TextKey::FlaggedPoints))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                if ui
+                    .add_enabled(
+                        self.points.points.len() > 2,
+                        egui::Button::new(i18n.text(TextKey::CheckContinuity)),
+                    )
+                    .on_hover_text(i18n.text(TextKey::CheckContinuityHover))
+                    .clicked()
+                {
+                    action = Some(FlagReviewAction::CheckContinuity);
+                }
+                let flagged: Vec<usize> = self
+                    .points
+                    .points
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, p)| p.flag.as_ref().map(|_| idx))
+                    .collect();
+                if flagged.is_empty() {
+                    ui.label(RichText::new(i18n.text(TextKey::NoFlaggedPoints)).weak());
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .id_salt("flag_review_list")
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for &idx in &flagged {
+                            let Some(flag) = self.points.points[idx].flag.as_ref() else {
+                                continue;
+                            };
+                            let selected = self.ui.flag_review_selected == Some(idx);
+                            let label = format!("#{} — {}", idx + 1, flag.annotation());
+                            let resp = ui.add(egui::Button::selectable(selected, label).truncate());
+                            if resp.clicked() {
+                                action = Some(FlagReviewAction::Select(idx));
+                            }
+                        }
+                    });
+
+                ui.separator();
+                let Some(idx) = self.ui.flag_review_selected else {
+                    ui.label(RichText::new(i18n.text(TextKey::SelectFlaggedPointHint)).weak());
+                    return;
+                };
+                if let Some(detail_action) = self.ui_flag_review_details(ui, idx) {
+                    action = Some(detail_action);
+                }
+            });
+        self.ui.flag_review_window_open = open;
+
+        match action {
+            Some(FlagReviewAction::CheckContinuity) => self.check_curve_continuity(),
+            Some(FlagReviewAction::Select(idx)) => {
+                if self.ui.flag_review_selected != Some(idx) {
+                    self.ui.flag_comment_draft.clear();
+                }
+                self.ui.flag_review_selected = Some(idx);
+            }
+            Some(FlagReviewAction::AddComment(idx)) => {
+                let draft = std::mem::take(&mut self.ui.flag_comment_draft);
+                self.add_point_flag_comment(idx, &draft);
+            }
+            Some(FlagReviewAction::Unflag(idx)) => {
+                if let Some(point) = self.points.points.get_mut(idx) {
+                    point.flag = None;
+                }
+                self.ui.flag_review_selected = None;
+                self.ui.flag_comment_draft.clear();
+            }
+            None => {}
+        }
+    }
+
+    fn ui_flag_review_details(
+        &mut self,
+        ui: &mut egui::Ui,
+        idx: usize,
+    ) -> Option<FlagReviewAction> {
+        let i18n = self.i18n();
+        let mut action = None;
+        let point = &self.points.points[idx];
+        ui.heading(format!("#{}", idx + 1));
+        ui.label(
+            RichText::new(format!("px ({:.1}, {:.1})", point.pixel.x, point.pixel.y))
+                .monospace()
+                .weak(),
+        );
+        if let Some(flag) = point.flag.as_ref() {
+            if flag.comments.is_empty() {
+                ui.label(RichText::new(i18n.text(TextKey::NoCommentsYet)).weak());
+            }
+            for comment in &flag.comments {
+                ui.label(format!("• {comment}"));
+            }
+        }
+        ui.add_space(4.0);
+        let edit = ui.add(
+            egui::TextEdit::singleline(&mut self.ui.flag_comment_draft)
+                .hint_text(i18n.text(TextKey::CommentHint))
+                .desired_width(f32::INFINITY),
+        );
+        let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        ui.horizontal(|ui| {
+            let has_text = !self.ui.flag_comment_draft.trim().is_empty();
+            let add_clicked = ui
+                .add_enabled(has_text, egui::Button::new(i18n.text(TextKey::AddComment)))
+                .clicked();
+            if (add_clicked || submitted) && has_text {
+                action = Some(FlagReviewAction::AddComment(idx));
+            }
+            if ui
+                .button(i18n.text(TextKey::Unflag))
+                .on_hover_text(i18n.text(TextKey::UnflagHover))
+                .clicked()
+            {
+                action = Some(FlagReviewAction::Unflag(idx));
+            }
+        });
+        action
+    }
+
+    /// Toggle the review flag of the point nearest to a click; flagged points open the review window.
+    pub(crate) fn toggle_flag_near_screen(
+        &mut self,
+        pointer: egui::Pos2,
+        image_origin: egui::Pos2,
+    ) {
+        let Some(idx) = self.point_index_near_screen(pointer, image_origin) else {
+            return;
+        };
+        match self.toggle_point_flag(idx) {
+            Some(true) => {
+                self.ui.flag_review_window_open = true;
+                self.ui.flag_review_selected = Some(idx);
+                self.ui.flag_comment_draft.clear();
+                self.set_status(match self.ui.language {
+                    crate::i18n::UiLanguage::En => {
+                        format!("Point #{} flagged for review.", idx + 1)
+                    }
+                    crate::i18n::UiLanguage::Ru => {
+                        format!("Точка #{} отмечена для проверки.", idx + 1)
+                    }
+                });
+            }
+            Some(false) => {
+                if self.ui.flag_review_selected == Some(idx) {
+                    self.ui.flag_review_selected = None;
+                }
+                self.set_status(match self.ui.language {
+                    crate::i18n::UiLanguage::En => format!("Flag removed from point #{}.", idx + 1),
+                    crate::i18n::UiLanguage::Ru => {
+                        format!("Отметка снята с точки #{}.", idx + 1)
+                    }
+                });
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Focus mode: dims the image outside the cursor neighbourhood and the traced curve.
+
+use crate::app::CurcatApp;
+use egui::{Color32, Mesh, Pos2, Rect};
+
+/// Screen-space grid step of the dimming mesh; the falloff is interpolated between vertices.
+const FOCUS_GRID_STEP: f32 = 12.0;
+/// Screen radius around the cursor that stays fully visible.
+const FOCUS_CURSOR_RADIUS: f32 = 110.0;
+/// Screen half-width of the visible band along picked points and the curve through them.
+const FOCUS_TRACE_RADIUS: f32 = 22.0;
+/// Share of each radius that is fully clear before the fade to the dimmed level starts.
+const FOCUS_CLEAR_FRACTION: f32 = 0.55;
+/// Dark overlay alpha; blending toward gray also washes out colours, not just brightness.
+const FOCUS_DIM_ALPHA: f32 = 185.0;
+const FOCUS_DIM_GRAY: u8 = 18;
+
+/// Per-vertex visibility (`0` dimmed, `1` clear) over a regular grid covering `rect`.
+struct FocusField {
+    origin: Pos2,
+    cols: usize,
+    rows: usize,
+    clear: Vec<f32>,
+}
+
+impl FocusField {
+    fn new(rect: Rect) -> Self {
+        let cols = grid_cells(rect.width());
+        let rows = grid_cells(rect.height());
+        Self {
+            origin: rect.min,
+            cols,
+            rows,
+            clear: vec![0.0; (cols + 1) * (rows + 1)],
+        }
+    }
+
+    fn vertex(&self, col: usize, row: usize) -> Pos2 {
+        #[allow(clippy::cast_precision_loss)]
+        let offset = egui::vec2(col as f32, row as f32) * FOCUS_GRID_STEP;
+        self.origin + offset
+    }
+
+    /// Clear the band within `radius` of the segment `a`–`b` (a disc when `a == b`).
+    fn reveal_segment(&mut self, a: Pos2, b: Pos2, radius: f32) {
+        let bounds = Rect::from_two_pos(a, b).expand(radius);
+        let (Some((c0, c1)), Some((r0, r1))) = (
+            grid_span(bounds.min.x, bounds.max.x, self.origin.x, self.cols),
+            grid_span(bounds.min.y, bounds.max.y, self.origin.y, self.rows),
+        ) else {
+            return;
+        };
+        let clear_radius = radius * FOCUS_CLEAR_FRACTION;
+        for row in r0..=r1 {
+            for col in c0..=c1 {
+                let dist = distance_to_segment(self.vertex(col, row), a, b);
+                if dist >= radius {
+                    continue;
+                }
+                let value = if dist <= clear_radius {
+                    1.0
+                } else {
+                    1.0 - (dist - clear_radius) / (radius - clear_radius)
+                };
+                let slot = &mut self.clear[row * (self.cols + 1) + col];
+                *slot = slot.max(value);
+            }
+        }
+    }
+
+    fn into_mesh(self) -> Mesh {
+        let mut mesh = Mesh::default();
+        for row in 0..=self.rows {
+            for col in 0..=self.cols {
+                let clear = self.clear[row * (self.cols + 1) + col];
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let alpha = (FOCUS_DIM_ALPHA * (1.0 - clear)).round() as u8;
+                mesh.colored_vertex(
+                    self.vertex(col, row),
+                    Color32::from_rgba_unmultiplied(
+                        FOCUS_DIM_GRAY,
+                        FOCUS_DIM_GRAY,
+                        FOCUS_DIM_GRAY,
+                        alpha,
+                    ),
+                );
+            }
+        }
+        let stride = u32::try_from(self.cols + 1).unwrap_or(u32::MAX);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let (Ok(row), Ok(col)) = (u32::try_from(row), u32::try_from(col)) else {
+                    continue;
+                };
+                let top_left = row * stride + col;
+                let bottom_left = top_left + stride;
+                mesh.add_triangle(top_left, top_left + 1, bottom_left);
+                mesh.add_triangle(top_left + 1, bottom_left + 1, bottom_left);
+            }
+        }
+        mesh
+    }
+}
+
+fn grid_cells(extent: f32) -> usize {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let cells = (extent / FOCUS_GRID_STEP).ceil().max(1.0) as usize;
+    cells
+}
+
+/// Grid index range touched by `[min, max]` along one axis.
+fn grid_span(min: f32, max: f32, origin: f32, cells: usize) -> Option<(usize, usize)> {
+    let lo = ((min - origin) / FOCUS_GRID_STEP).floor().max(0.0);
+    #[allow(clippy::cast_precision_loss)]
+    let hi = ((max - origin) / FOCUS_GRID_STEP).ceil().min(cells as f32);
+    if hi < lo {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((lo as usize, hi as usize))
+}
+
+fn distance_to_segment(p: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.length_sq();
+    if len_sq <= f32::EPSILON {
+        return p.distance(a);
+    }
+    let t = ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0);
+    p.distance(a + ab * t)
+}
+
+/// Dimming overlay for `visible` (screen space), clear around `cursor`, each of `spots`, and
+/// along the `trace` polyline.
+fn focus_dim_mesh(visible: Rect, cursor: Option<Pos2>, spots: &[Pos2], trace: &[Pos2]) -> Mesh {
+    let mut field = FocusField::new(visible);
+    for &spot in spots {
+        field.reveal_segment(spot, spot, FOCUS_TRACE_RADIUS);
+    }
+    for win in trace.windows(2) {
+        field.reveal_segment(win[0], win[1], FOCUS_TRACE_RADIUS);
+    }
+    if let Some(cursor) = cursor {
+        field.reveal_segment(cursor, cursor, FOCUS_CURSOR_RADIUS);
+    }
+    field.into_mesh()
+}
+
+impl CurcatApp {
+    /// Paint the focus-mode overlay over the visible part of the image at `rect`.
+    pub(crate) fn draw_focus_dim(
+        &mut self,
+        painter: &egui::Painter,
+        rect: Rect,
+        hover_pos: Option<Pos2>,
+    ) {
+        if !self.ui.focus_dim_enabled {
+            return;
+        }
+        let visible = painter.clip_rect().intersect(rect);
+        if !visible.is_positive() {
+            return;
+        }
+        let zoom = self.image.zoom;
+        let to_screen = |pixel: Pos2| rect.min + pixel.to_vec2() * zoom;
+        let spots: Vec<Pos2> = self
+            .points
+            .points
+            .iter()
+            .map(|p| to_screen(p.pixel))
+            .collect();
+        // The band follows the preview curve only while it is drawn.
+        let trace: Vec<Pos2> = if self.points.show_curve_segments {
+            self.sorted_preview_segments()
+                .iter()
+                .map(|(_, pixel)| to_screen(*pixel))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        painter.add(focus_dim_mesh(visible, hover_pos, &spots, &trace));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::pos2;
+
+    fn alpha_at(mesh: &Mesh, pos: Pos2) -> u8 {
+        mesh.vertices
+            .iter()
+            .find(|v| v.pos.distance(pos) < 0.5)
+            .map(|v| v.color.a())
+            .expect("vertex at position")
+    }
+
+    #[test]
+    fn focus_mesh_clears_cursor_and_trace_and_dims_elsewhere() {
+        let visible = Rect::from_min_size(pos2(0.0, 0.0), egui::vec2(600.0, 240.0));
+        let trace = [pos2(0.0, 192.0), pos2(600.0, 192.0)];
+        let mesh = focus_dim_mesh(visible, Some(pos2(120.0, 60.0)), &[], &trace);
+
+        assert_eq!(mesh.vertices.len(), 51 * 21);
+        assert_eq!(alpha_at(&mesh, pos2(120.0, 60.0)), 0);
+        assert_eq!(alpha_at(&mesh, pos2(480.0, 192.0)), 0);
+        let far = alpha_at(&mesh, pos2(480.0, 60.0));
+        assert!(far > 150, "far alpha {far}");
+        let fade = alpha_at(&mesh, pos2(120.0, 156.0));
+        assert!(fade > 0 && fade < far, "fade alpha {fade}");
+    }
+}
@@ -16,6 +16,9 @@ pub enum Icon {
     RotateCw,
     FlipH,
     FlipV,
+    Perspective,
+    Level,
+    Crop,
     Fit,
     ResetView,
     Clear,
@@ -24,9 +27,14 @@ pub enum Icon {
     ExportJson,
     ExportRon,
     ExportXlsx,
+    ExportOds,
+    ExportArrow,
+    ExportParquet,
+    ExportSql,
     ExportHtml,
     ExportXml,
     ExportMarkdown,
+    ExportNetCdf,
     PickPoint,
     AutoPlace,
     AutoTrace,
@@ -39,8 +47,16 @@ pub enum Icon {
     Close,
     OpenImage,
     PasteImage,
+    StitchScans,
     LoadProject,
     SaveProject,
+    Flag,
+    Sandbox,
+    FocusDim,
+    ResultPlot,
+    Preprocess,
+    Lock,
+    Unlock,
 }
 
 pub const BUTTON_ICON_SIZE: f32 = 14.0;
@@ -56,6 +72,9 @@ pub const ICON_ROTATE_CCW: Icon = Icon::RotateCcw;
 pub const ICON_ROTATE_CW: Icon = Icon::RotateCw;
 pub const ICON_FLIP_H: Icon = Icon::FlipH;
 pub const ICON_FLIP_V: Icon = Icon::FlipV;
+pub const ICON_PERSPECTIVE: Icon = Icon::Perspective;
+pub const ICON_LEVEL: Icon = Icon::Level;
+pub const ICON_CROP: Icon = Icon::Crop;
 pub const ICON_FIT: Icon = Icon::Fit;
 pub const ICON_RESET_VIEW: Icon = Icon::ResetView;
 pub const ICON_CLEAR: Icon = Icon::Clear;
@@ -64,9 +83,14 @@ pub const ICON_EXPORT_CSV: Icon = Icon::ExportCsv;
 pub const ICON_EXPORT_JSON: Icon = Icon::ExportJson;
 pub const ICON_EXPORT_RON: Icon = Icon::ExportRon;
 pub const ICON_EXPORT_XLSX: Icon = Icon::ExportXlsx;
+pub const ICON_EXPORT_ODS: Icon = Icon::ExportOds;
+pub const ICON_EXPORT_ARROW: Icon = Icon::ExportArrow;
+pub const ICON_EXPORT_PARQUET: Icon = Icon::ExportParquet;
+pub const ICON_EXPORT_SQL: Icon = Icon::ExportSql;
 pub const ICON_EXPORT_HTML: Icon = Icon::ExportHtml;
 pub const ICON_EXPORT_XML: Icon = Icon::ExportXml;
 pub const ICON_EXPORT_MARKDOWN: Icon = Icon::ExportMarkdown;
+pub const ICON_EXPORT_NETCDF: Icon = Icon::ExportNetCdf;
 pub const ICON_PICK_POINT: Icon = Icon::PickPoint;
 pub const ICON_AUTO_PLACE: Icon = Icon::AutoPlace;
 pub const ICON_AUTO_TRACE: Icon = Icon::AutoTrace;
@@ -79,8 +103,16 @@ pub const ICON_PRESET_PIXELS: Icon = Icon::PresetPixels;
 pub const ICON_CLOSE: Icon = Icon::Close;
 pub const ICON_OPEN_IMAGE: Icon = Icon::OpenImage;
 pub const ICON_PASTE_IMAGE: Icon = Icon::PasteImage;
+pub const ICON_STITCH_SCANS: Icon = Icon::StitchScans;
 pub const ICON_LOAD_PROJECT: Icon = Icon::LoadProject;
 pub const ICON_SAVE_PROJECT: Icon = Icon::SaveProject;
+pub const ICON_FLAG: Icon = Icon::Flag;
+pub const ICON_SANDBOX: Icon = Icon::Sandbox;
+pub const ICON_FOCUS_DIM: Icon = Icon::FocusDim;
+pub const ICON_RESULT_PLOT: Icon = Icon::ResultPlot;
+pub const ICON_PREPROCESS: Icon = Icon::Preprocess;
+pub const ICON_LOCK: Icon = Icon::Lock;
+pub const ICON_UNLOCK: Icon = Icon::Unlock;
 
 /// Вернуть монохромную иконку фиксированного размера.
 pub fn image(icon: Icon, size: f32) -> Image<'static> {
@@ -106,6 +138,11 @@ const fn source(icon: Icon) -> ImageSource<'static> {
         }
         Icon::FlipH => egui::include_image!("../../../assets/icons/tabler/flip-horizontal.svg"),
         Icon::FlipV => egui::include_image!("../../../assets/icons/tabler/flip-vertical.svg"),
+        Icon::Perspective => {
+            egui::include_image!("../../../assets/icons/tabler/perspective.svg")
+        }
+        Icon::Level => egui::include_image!("../../../assets/icons/tabler/angle.svg"),
+        Icon::Crop => egui::include_image!("../../../assets/icons/tabler/crop.svg"),
         Icon::Fit => egui::include_image!("../../../assets/icons/tabler/maximize.svg"),
         Icon::ResetView => egui::include_image!("../../../assets/icons/tabler/zoom-reset.svg"),
         Icon::Clear | Icon::DeletePoint => {
@@ -118,12 +155,19 @@ const fn source(icon: Icon) -> ImageSource<'static> {
         Icon::ExportJson | Icon::ExportXml => {
             egui::include_image!("../../../assets/icons/tabler/braces.svg")
         }
-        Icon::ExportRon | Icon::ExportHtml | Icon::ExportMarkdown => {
+        Icon::ExportRon
+        | Icon::ExportHtml
+        | Icon::ExportMarkdown
+        | Icon::ExportNetCdf
+        | Icon::ExportArrow
+        | Icon::ExportParquet
+        | Icon::ExportSql => {
             egui::include_image!("../../../assets/icons/tabler/file-code.svg")
         }
         Icon::ExportXlsx => {
             egui::include_image!("../../../assets/icons/tabler/file-type-xls.svg")
         }
+        Icon::ExportOds => egui::include_image!("../../../assets/icons/tabler/table.svg"),
         Icon::PickPoint => egui::include_image!("../../../assets/icons/tabler/crosshair.svg"),
         Icon::AutoPlace => egui::include_image!("../../../assets/icons/tabler/point.svg"),
         Icon::AutoTrace => egui::include_image!("../../../assets/icons/tabler/route-2.svg"),
@@ -137,9 +181,19 @@ const fn source(icon: Icon) -> ImageSource<'static> {
         Icon::Close => egui::include_image!("../../../assets/icons/tabler/x.svg"),
         Icon::OpenImage => egui::include_image!("../../../assets/icons/tabler/photo.svg"),
         Icon::PasteImage => egui::include_image!("../../../assets/icons/tabler/clipboard.svg"),
+        Icon::StitchScans => {
+            egui::include_image!("../../../assets/icons/tabler/layout-columns.svg")
+        }
         Icon::LoadProject => egui::include_image!("../../../assets/icons/tabler/folder-open.svg"),
         Icon::SaveProject => {
             egui::include_image!("../../../assets/icons/tabler/device-floppy.svg")
         }
+        Icon::Flag => egui::include_image!("../../../assets/icons/tabler/flag.svg"),
+        Icon::Sandbox => egui::include_image!("../../../assets/icons/tabler/flask.svg"),
+        Icon::FocusDim => egui::include_image!("../../../assets/icons/tabler/contrast.svg"),
+        Icon::ResultPlot => egui::include_image!("../../../assets/icons/tabler/chart-line.svg"),
+        Icon::Preprocess => egui::include_image!("../../../assets/icons/tabler/wand.svg"),
+        Icon::Lock => egui::include_image!("../../../assets/icons/tabler/lock.svg"),
+        Icon::Unlock => egui::include_image!("../../../assets/icons/tabler/lock-open.svg"),
     }
 }
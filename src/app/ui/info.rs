@@ -32,7 +32,7 @@ impl CurcatApp {
             .ui
             .last_status
             .as_ref()
-            .map(|status| (status.text.clone(), status.level));
+            .map(|status| (status.text.clone(), status.level, status.details.clone()));
         let copied_feedback_active = self
             .ui
             .status_copy_feedback_until
@@ -54,9 +54,9 @@ impl CurcatApp {
                     );
                     Self::status_bar_separator(ui);
                     Self::draw_mode_chip(ui, &mode_label, mode_color, &status_font);
-                    if let Some((status_text, status_level)) = status_snapshot.as_ref() {
+                    if let Some((status_text, status_level, details)) = status_snapshot.as_ref() {
                         Self::status_bar_separator(ui);
-                        ui.add(
+                        let status_resp = ui.add(
                             egui::Label::new(
                                 RichText::new(status_text.as_str())
                                     .font(status_font.clone())
@@ -64,6 +64,9 @@ impl CurcatApp {
                             )
                             .truncate(),
                         );
+                        if let Some(details) = details {
+                            status_resp.on_hover_text(RichText::new(details).monospace());
+                        }
 
                         if *status_level == StatusLevel::Error {
                             ui.add_space(6.0);
@@ -86,7 +89,10 @@ impl CurcatApp {
                                 .on_hover_text(hover)
                                 .clicked()
                             {
-                                copy_error_text = Some(status_text.clone());
+                                copy_error_text = Some(details.as_ref().map_or_else(
+                                    || status_text.clone(),
+                                    |details| format!("{status_text}\n\n{details}"),
+                                ));
                             }
                         }
 
@@ -259,7 +265,8 @@ impl CurcatApp {
         let i18n = self.i18n();
         egui::Window::new(i18n.text(TextKey::PointsInfoWindow))
             .open(&mut open)
-            .resizable(false)
+            .resizable(true)
+            .default_width(460.0)
             .show(ctx, |ui| {
                 let total = self.points.points.len();
                 ui.heading(i18n.text(TextKey::Points));
@@ -278,11 +285,15 @@ impl CurcatApp {
                 if calibrated != total {
                     ui.label(RichText::new(i18n.format_calibrated_pairs(calibrated)).weak());
                 }
+                let flagged = self.flagged_point_count();
+                if flagged > 0 {
+                    ui.label(i18n.format_flagged_points(flagged));
+                }
 
                 ui.add_space(6.0);
                 ui.heading(i18n.text(TextKey::Ranges));
                 match self.calibration.coord_system {
-                    crate::types::CoordSystem::Cartesian => {
+                    crate::types::CoordSystem::Cartesian | crate::types::CoordSystem::Affine => {
                         self.render_axis_stats(
                             ui,
                             i18n.text(TextKey::XAxis),
@@ -310,6 +321,18 @@ impl CurcatApp {
                             polar_mapping.as_ref(),
                         );
                     }
+                    crate::types::CoordSystem::Smith => {
+                        self.render_smith_axis_stats(
+                            ui,
+                            i18n.text(TextKey::Resistance),
+                            AxisKind::X,
+                        );
+                        self.render_smith_axis_stats(
+                            ui,
+                            i18n.text(TextKey::Reactance),
+                            AxisKind::Y,
+                        );
+                    }
                 }
 
                 ui.add_space(6.0);
@@ -319,13 +342,16 @@ impl CurcatApp {
                 ui.add_space(6.0);
                 ui.heading(i18n.text(TextKey::Geometry));
                 self.render_geometry_stats(ui);
+
+                ui.add_space(6.0);
+                self.ui_point_table(ui);
             });
         self.ui.points_info_window_open = open;
     }
 }
 
 impl CurcatApp {
-    const fn status_ttl(level: StatusLevel) -> Option<Duration> {
+    pub(super) const fn status_ttl(level: StatusLevel) -> Option<Duration> {
         match level {
             StatusLevel::Info => Some(Self::STATUS_INFO_TTL),
             StatusLevel::Warn => Some(Self::STATUS_WARN_TTL),
@@ -355,7 +381,7 @@ impl CurcatApp {
         }
     }
 
-    const fn status_color(level: StatusLevel) -> Color32 {
+    pub(super) const fn status_color(level: StatusLevel) -> Color32 {
         match level {
             StatusLevel::Info => Color32::from_gray(200),
             StatusLevel::Warn => Color32::from_rgb(242, 194, 102),
@@ -364,11 +390,12 @@ impl CurcatApp {
     }
 
     fn cursor_mode_chip(&self, ctx: &egui::Context) -> (String, Color32) {
-        let (delete_down, shift_pressed, ctrl_pressed) = ctx.input(|i| {
+        let (delete_down, shift_pressed, ctrl_pressed, alt_pressed) = ctx.input(|i| {
             (
                 i.key_down(egui::Key::Delete),
                 i.modifiers.shift,
                 i.modifiers.ctrl,
+                i.modifiers.alt,
             )
         });
         if let Some(pick_mode) = self.pick_mode_chip() {
@@ -385,6 +412,16 @@ impl CurcatApp {
                 ),
             };
         }
+        if alt_pressed && !self.points.points.is_empty() {
+            return match self.ui.language {
+                crate::i18n::UiLanguage::En => {
+                    ("Flag point".to_string(), Color32::from_rgb(255, 170, 60))
+                }
+                crate::i18n::UiLanguage::Ru => {
+                    ("Отметка точки".to_string(), Color32::from_rgb(255, 170, 60))
+                }
+            };
+        }
         if delete_down {
             return match self.ui.language {
                 crate::i18n::UiLanguage::En => {
@@ -423,6 +460,7 @@ impl CurcatApp {
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     fn pick_mode_chip(&self) -> Option<(String, Color32)> {
         match self.calibration.pick_mode {
             PickMode::None => None,
@@ -458,6 +496,22 @@ impl CurcatApp {
                     ("Выбор Y2".to_string(), Color32::from_rgb(200, 255, 200))
                 }
             }),
+            PickMode::SecondaryY1 => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => {
+                    ("Pick Y'1".to_string(), Color32::from_rgb(255, 200, 240))
+                }
+                crate::i18n::UiLanguage::Ru => {
+                    ("Выбор Y'1".to_string(), Color32::from_rgb(255, 200, 240))
+                }
+            }),
+            PickMode::SecondaryY2 => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => {
+                    ("Pick Y'2".to_string(), Color32::from_rgb(255, 200, 240))
+                }
+                crate::i18n::UiLanguage::Ru => {
+                    ("Выбор Y'2".to_string(), Color32::from_rgb(255, 200, 240))
+                }
+            }),
             PickMode::Origin => Some(match self.ui.language {
                 crate::i18n::UiLanguage::En => {
                     ("Pick origin".to_string(), Color32::from_rgb(255, 230, 180))
@@ -498,6 +552,62 @@ impl CurcatApp {
                     ("Выбор A2".to_string(), Color32::from_rgb(200, 210, 255))
                 }
             }),
+            PickMode::AffineRef(idx) => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    format!("Pick P{}", idx + 1),
+                    Color32::from_rgb(235, 200, 255),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    format!("Выбор P{}", idx + 1),
+                    Color32::from_rgb(235, 200, 255),
+                ),
+            }),
+            PickMode::SmithCenter => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Pick chart centre".to_string(),
+                    Color32::from_rgb(190, 240, 220),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    "Выбор центра диаграммы".to_string(),
+                    Color32::from_rgb(190, 240, 220),
+                ),
+            }),
+            PickMode::SmithShort => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Pick short circuit".to_string(),
+                    Color32::from_rgb(190, 240, 220),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    "Выбор короткого замыкания".to_string(),
+                    Color32::from_rgb(190, 240, 220),
+                ),
+            }),
+            PickMode::PerspectiveCorner => {
+                let next = self.image.perspective_corners.len() + 1;
+                Some(match self.ui.language {
+                    crate::i18n::UiLanguage::En => (
+                        format!("Pick corner {next}/4"),
+                        Color32::from_rgb(255, 235, 170),
+                    ),
+                    crate::i18n::UiLanguage::Ru => (
+                        format!("Выбор угла {next}/4"),
+                        Color32::from_rgb(255, 235, 170),
+                    ),
+                })
+            }
+            PickMode::HorizonPoint => {
+                let next = usize::from(self.image.horizon_start.is_some()) + 1;
+                Some(match self.ui.language {
+                    crate::i18n::UiLanguage::En => (
+                        format!("Pick horizon point {next}/2"),
+                        Color32::from_rgb(255, 235, 170),
+                    ),
+                    crate::i18n::UiLanguage::Ru => (
+                        format!("Выбор точки горизонта {next}/2"),
+                        Color32::from_rgb(255, 235, 170),
+                    ),
+                })
+            }
             PickMode::CurveColor => Some(match self.ui.language {
                 crate::i18n::UiLanguage::En => (
                     "Pick curve color".to_string(),
@@ -508,6 +618,26 @@ impl CurcatApp {
                     Color32::from_rgb(255, 210, 160),
                 ),
             }),
+            PickMode::LegendEntry => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Pick legend swatches".to_string(),
+                    Color32::from_rgb(255, 210, 160),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    "Выбор образцов легенды".to_string(),
+                    Color32::from_rgb(255, 210, 160),
+                ),
+            }),
+            PickMode::ChartText(_) => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Pick chart text".to_string(),
+                    Color32::from_rgb(255, 210, 160),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    "Выбор текста графика".to_string(),
+                    Color32::from_rgb(255, 210, 160),
+                ),
+            }),
             PickMode::AutoTrace => Some(match self.ui.language {
                 crate::i18n::UiLanguage::En => (
                     "Auto-trace pick".to_string(),
@@ -518,6 +648,44 @@ impl CurcatApp {
                     Color32::from_rgb(215, 215, 255),
                 ),
             }),
+            PickMode::ZoomRegion => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Zoom to region".to_string(),
+                    Color32::from_rgb(190, 225, 255),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    "Масштаб по области".to_string(),
+                    Color32::from_rgb(190, 225, 255),
+                ),
+            }),
+            PickMode::MarkerSample | PickMode::MarkerReview => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Detect markers".to_string(),
+                    Color32::from_rgb(255, 225, 150),
+                ),
+                crate::i18n::UiLanguage::Ru => (
+                    "Поиск маркеров".to_string(),
+                    Color32::from_rgb(255, 225, 150),
+                ),
+            }),
+            PickMode::SnapArea => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => {
+                    ("Snap area".to_string(), Color32::from_rgb(170, 240, 200))
+                }
+                crate::i18n::UiLanguage::Ru => (
+                    "Область привязки".to_string(),
+                    Color32::from_rgb(170, 240, 200),
+                ),
+            }),
+            PickMode::SelectPoints => Some(match self.ui.language {
+                crate::i18n::UiLanguage::En => (
+                    "Select points".to_string(),
+                    Color32::from_rgb(180, 210, 255),
+                ),
+                crate::i18n::UiLanguage::Ru => {
+                    ("Выбор точек".to_string(), Color32::from_rgb(180, 210, 255))
+                }
+            }),
         }
     }
 
@@ -586,6 +754,38 @@ impl CurcatApp {
         }
     }
 
+    fn render_affine_calibration_stats(&self, ui: &mut egui::Ui) {
+        let placed = self
+            .calibration
+            .affine_cal
+            .points
+            .iter()
+            .filter(|p| p.pixel.is_some())
+            .count();
+        ui.label(self.i18n().format_reference_points(placed));
+        match self.calibration.affine_cal.fit() {
+            Ok(mapping) => {
+                ui.label(self.i18n().format_affine_fit_error(mapping.rms_residual));
+            }
+            Err(_) => {
+                ui.label(RichText::new(self.t(TextKey::AffineFitMissing)).weak());
+            }
+        }
+    }
+
+    fn render_smith_calibration_stats(&self, ui: &mut egui::Ui) {
+        if let Some(mapping) = self.smith_mapping() {
+            ui.label(format!(
+                "{}: {:.1} px",
+                self.t(TextKey::SmithChartRadius),
+                mapping.radius()
+            ));
+            ui.label(format!("Z0 = {} Ω", mapping.reference_ohms));
+        } else {
+            ui.label(RichText::new(self.t(TextKey::SmithFitMissing)).weak());
+        }
+    }
+
     fn render_calibration_stats(&self, ui: &mut egui::Ui, polar_mapping: Option<&PolarMapping>) {
         match self.calibration.coord_system {
             CoordSystem::Cartesian => {
@@ -613,6 +813,8 @@ impl CurcatApp {
                     );
                 }
             }
+            CoordSystem::Affine => self.render_affine_calibration_stats(ui),
+            CoordSystem::Smith => self.render_smith_calibration_stats(ui),
             CoordSystem::Polar => {
                 if let Some(origin) = self.calibration.polar_cal.origin {
                     ui.label(self.i18n().format_origin_coords(origin.x, origin.y));
@@ -711,6 +913,17 @@ impl CurcatApp {
         }
     }
 
+    fn render_smith_axis_stats(&self, ui: &mut egui::Ui, label: &str, axis: AxisKind) {
+        if let Some(range) = self.axis_numeric_range(axis) {
+            let min = AxisValue::Float(range.min).format();
+            let max = AxisValue::Float(range.max).format();
+            let span = AxisValue::Float(range.span()).format();
+            ui.label(self.i18n().format_axis_range(label, &min, &max, &span));
+        } else {
+            ui.label(format!("{label}: {}", self.t(TextKey::NoData)));
+        }
+    }
+
     fn render_geometry_stats(&self, ui: &mut egui::Ui) {
         if let Some((xr, yr)) = self.pixel_bounds() {
             ui.label(
@@ -0,0 +1,175 @@
+use crate::app::CurcatApp;
+use crate::i18n::{I18n, TextKey};
+use crate::keymap::{ShortcutAction, binding_modifiers, format_shortcut};
+use egui::{Color32, Key, KeyboardShortcut, RichText};
+
+const CONFLICT_COLOR: Color32 = Color32::from_rgb(230, 120, 60);
+
+impl CurcatApp {
+    pub(crate) fn ui_keymap_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.keymap_window_open {
+            self.ui.keymap_recording = None;
+            return;
+        }
+        self.record_shortcut(ctx);
+        let mut open = self.ui.keymap_window_open;
+        egui::Window::new(self.t(TextKey::KeymapWindow))
+            .open(&mut open)
+            .resizable(true)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| self.ui_keymap_rows(ui));
+        self.ui.keymap_window_open = open;
+    }
+
+    /// Take the next key press as the binding being recorded; Esc cancels.
+    fn record_shortcut(&mut self, ctx: &egui::Context) {
+        let Some(action) = self.ui.keymap_recording else {
+            return;
+        };
+        let pressed = ctx.input_mut(|i| {
+            let (key, modifiers) = i.events.iter().find_map(|event| match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => Some((*key, *modifiers)),
+                _ => None,
+            })?;
+            // Keep the press from also reaching the focused button.
+            i.consume_key(modifiers, key);
+            Some(KeyboardShortcut::new(binding_modifiers(modifiers), key))
+        });
+        let Some(shortcut) = pressed else {
+            return;
+        };
+        self.ui.keymap_recording = None;
+        if shortcut != KeyboardShortcut::new(egui::Modifiers::NONE, Key::Escape) {
+            let mut keymap = self.config.keymap.clone();
+            keymap.rebind(action, Some(shortcut));
+            self.set_keymap(keymap);
+        }
+    }
+
+    fn ui_keymap_rows(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::KeymapIntro)).small());
+        ui.add_space(4.0);
+        let mut keymap = self.config.keymap.clone();
+        egui::ScrollArea::vertical()
+            .max_height(420.0)
+            .show(ui, |ui| {
+                egui::Grid::new("keymap_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for action in ShortcutAction::ALL {
+                            ui.label(action_label(i18n, action));
+                            let recording = self.ui.keymap_recording == Some(action);
+                            let shortcut = keymap.shortcut(action);
+                            let conflicts = keymap.conflicts(action);
+                            let text = if recording {
+                                RichText::new(i18n.text(TextKey::KeymapRecording)).italics()
+                            } else if let Some(shortcut) = shortcut {
+                                let text = RichText::new(format_shortcut(shortcut)).monospace();
+                                if conflicts.is_empty() {
+                                    text
+                                } else {
+                                    text.color(CONFLICT_COLOR)
+                                }
+                            } else {
+                                RichText::new(i18n.text(TextKey::KeymapUnbound)).weak()
+                            };
+                            let mut hover = i18n.text(TextKey::KeymapRecordHover).to_string();
+                            if !conflicts.is_empty() {
+                                let names: Vec<String> = conflicts
+                                    .iter()
+                                    .map(|&other| action_label(i18n, other))
+                                    .collect();
+                                hover = format!(
+                                    "{hover}\n{}: {}",
+                                    i18n.text(TextKey::KeymapConflict),
+                                    names.join(", ")
+                                );
+                            }
+                            if ui
+                                .add(egui::Button::selectable(recording, text))
+                                .on_hover_text(hover)
+                                .clicked()
+                            {
+                                self.ui.keymap_recording = (!recording).then_some(action);
+                            }
+                            if ui
+                                .add_enabled(shortcut.is_some(), egui::Button::new("✖").small())
+                                .on_hover_text(i18n.text(TextKey::KeymapClearHover))
+                                .clicked()
+                            {
+                                keymap.rebind(action, None);
+                            }
+                            if ui
+                                .add_enabled(
+                                    shortcut != action.default_shortcut(),
+                                    egui::Button::new("↺").small(),
+                                )
+                                .on_hover_text(i18n.text(TextKey::KeymapResetHover))
+                                .clicked()
+                            {
+                                keymap.rebind(action, action.default_shortcut());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        ui.add_space(4.0);
+        if ui
+            .add_enabled(
+                !keymap.bindings.is_empty(),
+                egui::Button::new(i18n.text(TextKey::KeymapResetAll)),
+            )
+            .clicked()
+        {
+            keymap.bindings.clear();
+            self.ui.keymap_recording = None;
+        }
+        self.set_keymap(keymap);
+    }
+}
+
+/// Name of `action` in the shortcut list, without the ellipsis of its menu entry.
+fn action_label(i18n: I18n, action: ShortcutAction) -> String {
+    let key = match action {
+        ShortcutAction::ToggleSidePanel => TextKey::ShortcutToggleSidePanel,
+        ShortcutAction::OpenImage => TextKey::OpenImage,
+        ShortcutAction::PasteImage => TextKey::PasteImage,
+        ShortcutAction::PastePoints => TextKey::PastePoints,
+        ShortcutAction::OpenProject => TextKey::LoadProject,
+        ShortcutAction::SaveProject => TextKey::SaveProject,
+        ShortcutAction::ExportCsv => TextKey::ExportCsv,
+        ShortcutAction::ExportJson => TextKey::ExportJson,
+        ShortcutAction::ExportRon => TextKey::ExportRon,
+        ShortcutAction::ExportExcel => TextKey::ExportExcel,
+        ShortcutAction::ExportOds => TextKey::ExportOds,
+        ShortcutAction::ExportArrow => TextKey::ExportArrow,
+        ShortcutAction::ExportParquet => TextKey::ExportParquet,
+        ShortcutAction::ExportSql => TextKey::ExportSql,
+        ShortcutAction::ExportNetCdf => TextKey::ExportNetCdf,
+        ShortcutAction::ExportHtml => TextKey::ExportHtml,
+        ShortcutAction::ExportXml => TextKey::ExportXml,
+        ShortcutAction::ExportMarkdown => TextKey::ExportMarkdown,
+        ShortcutAction::Undo => TextKey::Undo,
+        ShortcutAction::ClearPoints => TextKey::ClearPoints,
+        ShortcutAction::ImageInfo => TextKey::ImageInfo,
+        ShortcutAction::ImageFilters => TextKey::Filters,
+        ShortcutAction::AutoTrace => TextKey::AutoTrace,
+        ShortcutAction::FitView => TextKey::Fit,
+        ShortcutAction::FitWidth => TextKey::FitWidth,
+        ShortcutAction::ResetView => TextKey::ResetView,
+        ShortcutAction::PickX1 => TextKey::ShortcutPickX1,
+        ShortcutAction::PickX2 => TextKey::ShortcutPickX2,
+        ShortcutAction::PickY1 => TextKey::ShortcutPickY1,
+        ShortcutAction::PickY2 => TextKey::ShortcutPickY2,
+        ShortcutAction::SelectPoints => TextKey::SelectPoints,
+    };
+    i18n.text(key).trim_end_matches('…').to_string()
+}
@@ -0,0 +1,161 @@
+//! Loupe: a zoomed circular inset of the image pixels around the cursor while picking.
+
+use crate::app::{CurcatApp, PickMode};
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, ColorImage, Mesh, Pos2, Rect, Stroke, pos2, vec2};
+
+/// Loupe scale relative to the current zoom.
+const LOUPE_MAGNIFICATION: f32 = 6.0;
+/// Screen radius of the inset.
+const LOUPE_RADIUS: f32 = 64.0;
+/// Screen gap between the cursor and the nearest edge of the inset.
+const LOUPE_OFFSET: f32 = 28.0;
+/// Half-length of the crosshair gap kept clear over the hovered pixel.
+const LOUPE_CROSSHAIR_GAP: f32 = 4.0;
+const LOUPE_FRAME_COLOR: Color32 = Color32::from_gray(30);
+const LOUPE_CROSSHAIR_COLOR: Color32 = Color32::from_rgb(255, 64, 64);
+
+/// One quad per image pixel within `radius` of `center`, which shows image position `pixel`
+/// with each image pixel `cell` screen points wide.
+fn loupe_mesh(pixels: &ColorImage, pixel: Pos2, center: Pos2, radius: f32, cell: f32) -> Mesh {
+    let mut mesh = Mesh::default();
+    let [width, height] = pixels.size;
+    // Cell corners reach a half diagonal past their centres.
+    let reach = cell.mul_add(std::f32::consts::FRAC_1_SQRT_2, radius);
+    let span = (reach / cell).ceil();
+    let lo = |v: f32| (v - span).floor().max(0.0);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (x0, y0) = (lo(pixel.x) as usize, lo(pixel.y) as usize);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (x1, y1) = (
+        ((pixel.x + span).ceil() as usize).min(width),
+        ((pixel.y + span).ceil() as usize).min(height),
+    );
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let min = center + (pos2(safe_usize_to_f32(x), safe_usize_to_f32(y)) - pixel) * cell;
+            let cell_rect = Rect::from_min_size(min, vec2(cell, cell));
+            if cell_rect.center().distance(center) > reach {
+                continue;
+            }
+            let [r, g, b, _] = pixels.pixels[y * width + x].to_array();
+            mesh.add_colored_rect(cell_rect, Color32::from_rgb(r, g, b));
+        }
+    }
+    mesh
+}
+
+/// Centre of the inset: up and to the right of `cursor`, flipped to stay inside `bounds`.
+fn loupe_center(cursor: Pos2, bounds: Rect) -> Pos2 {
+    let shift = LOUPE_OFFSET + LOUPE_RADIUS;
+    let x = if cursor.x + shift + LOUPE_RADIUS > bounds.max.x {
+        cursor.x - shift
+    } else {
+        cursor.x + shift
+    };
+    let y = if cursor.y - shift - LOUPE_RADIUS < bounds.min.y {
+        cursor.y + shift
+    } else {
+        cursor.y - shift
+    };
+    pos2(x, y)
+}
+
+impl CurcatApp {
+    /// Paint the loupe for the cursor at `hover_pos` over image position `hover_pixel`.
+    pub(crate) fn draw_loupe(
+        &self,
+        ui: &egui::Ui,
+        hover_pos: Option<Pos2>,
+        hover_pixel: Option<Pos2>,
+    ) {
+        // The eyedropper brings its own magnifier.
+        if !self.ui.loupe_enabled || self.calibration.pick_mode == PickMode::CurveColor {
+            return;
+        }
+        let (Some(cursor), Some(pixel), Some(image)) =
+            (hover_pos, hover_pixel, self.image.image.as_ref())
+        else {
+            return;
+        };
+        // The scroll viewport rather than the image rect, so the inset can overhang the image.
+        let painter = ui.painter();
+        let center = loupe_center(cursor, ui.clip_rect());
+        let cell = self.image.zoom * LOUPE_MAGNIFICATION;
+        painter.circle_filled(center, LOUPE_RADIUS, LOUPE_FRAME_COLOR);
+        painter.add(loupe_mesh(&image.pixels, pixel, center, LOUPE_RADIUS, cell));
+
+        // Outline of the pixel a click lands in, and a crosshair through the exact position.
+        let cell_min = center + (pos2(pixel.x.floor(), pixel.y.floor()) - pixel) * cell;
+        painter.rect_stroke(
+            Rect::from_min_size(cell_min, vec2(cell, cell)),
+            0.0,
+            Stroke::new(1.0_f32, LOUPE_CROSSHAIR_COLOR),
+            egui::StrokeKind::Outside,
+        );
+        let stroke = Stroke::new(1.0_f32, LOUPE_CROSSHAIR_COLOR);
+        for dir in [
+            vec2(1.0, 0.0),
+            vec2(-1.0, 0.0),
+            vec2(0.0, 1.0),
+            vec2(0.0, -1.0),
+        ] {
+            painter.line_segment(
+                [
+                    center + dir * LOUPE_CROSSHAIR_GAP.max(cell * 0.5),
+                    center + dir * LOUPE_RADIUS,
+                ],
+                stroke,
+            );
+        }
+        // A wide bezel hides the stair-stepped cell edges around the circle.
+        let bezel = cell.mul_add(0.75, 2.0);
+        painter.circle_stroke(
+            center,
+            LOUPE_RADIUS + bezel * 0.5,
+            Stroke::new(bezel, LOUPE_FRAME_COLOR),
+        );
+        painter.circle_stroke(
+            center,
+            LOUPE_RADIUS + bezel,
+            Stroke::new(1.0_f32, Color32::from_gray(200)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loupe_mesh_enlarges_pixels_around_the_hovered_one() {
+        let mut pixels = ColorImage::filled([40, 40], Color32::BLACK);
+        pixels.pixels[12 * 40 + 10] = Color32::RED;
+        let center = pos2(100.0, 100.0);
+        // Pixel (10, 12) sits under the exact position, offset into its upper-left quarter.
+        let mesh = loupe_mesh(&pixels, pos2(10.25, 12.25), center, 30.0, 8.0);
+
+        let red: Vec<Pos2> = mesh
+            .vertices
+            .iter()
+            .filter(|v| v.color == Color32::RED)
+            .map(|v| v.pos)
+            .collect();
+        assert_eq!(red.len(), 4);
+        let bounds = Rect::from_points(&red);
+        assert_eq!(bounds.min, pos2(98.0, 98.0));
+        assert_eq!(bounds.size(), vec2(8.0, 8.0));
+        // Cells beyond the circle are left out: far fewer than the bounding square's.
+        let quads = mesh.vertices.len() / 4;
+        assert!(quads < 11 * 11 && quads > 30, "quads {quads}");
+    }
+
+    #[test]
+    fn loupe_stays_inside_the_visible_area() {
+        let bounds = Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0));
+        let center = loupe_center(pos2(400.0, 300.0), bounds);
+        assert!(center.x > 400.0 && center.y < 300.0);
+        let corner = loupe_center(pos2(790.0, 10.0), bounds);
+        assert!(corner.x < 790.0 && corner.y > 10.0);
+    }
+}
@@ -0,0 +1,154 @@
+//! Eyedropper magnifier shown next to the cursor while picking the curve color.
+
+use crate::app::CurcatApp;
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, ColorImage, Pos2, Rect, Stroke, StrokeKind, Vec2, pos2, vec2};
+
+/// Image pixels shown on each side of the center pixel.
+const MAGNIFIER_RADIUS: usize = 5;
+/// Screen size of one magnified pixel.
+const MAGNIFIER_CELL: f32 = 9.0;
+/// Gap between the cursor and the magnifier.
+const MAGNIFIER_OFFSET: f32 = 28.0;
+/// Height reserved under the grid for the RGB/hex caption.
+const MAGNIFIER_CAPTION: f32 = 34.0;
+
+/// Colors of the `(2r + 1)²` pixels around `(cx, cy)`, row by row; `None` outside the image.
+fn pixel_grid(image: &ColorImage, cx: usize, cy: usize, radius: usize) -> Vec<Option<Color32>> {
+    let [w, h] = image.size;
+    let side = 2 * radius + 1;
+    let mut cells = Vec::with_capacity(side * side);
+    for row in 0..side {
+        for col in 0..side {
+            let x = (cx + col).checked_sub(radius).filter(|&x| x < w);
+            let y = (cy + row).checked_sub(radius).filter(|&y| y < h);
+            cells.push(x.zip(y).map(|(x, y)| image.pixels[y * w + x]));
+        }
+    }
+    cells
+}
+
+/// Place a `size` box below-right of the cursor, flipping sides where it would leave `clip`.
+fn magnifier_origin(cursor: Pos2, size: Vec2, clip: Rect) -> Pos2 {
+    let x = if cursor.x + MAGNIFIER_OFFSET + size.x <= clip.right() {
+        cursor.x + MAGNIFIER_OFFSET
+    } else {
+        cursor.x - MAGNIFIER_OFFSET - size.x
+    };
+    let y = if cursor.y + MAGNIFIER_OFFSET + size.y <= clip.bottom() {
+        cursor.y + MAGNIFIER_OFFSET
+    } else {
+        cursor.y - MAGNIFIER_OFFSET - size.y
+    };
+    pos2(x.max(clip.left()), y.max(clip.top()))
+}
+
+impl CurcatApp {
+    /// Magnified pixel grid around `pixel` with the center pixel and sample window outlined.
+    pub(crate) fn draw_color_magnifier(&self, painter: &egui::Painter, cursor: Pos2, pixel: Pos2) {
+        let Some(image) = self.image.image.as_ref() else {
+            return;
+        };
+        let Some((cx, cy)) = self.image_pixel_coords(pixel) else {
+            return;
+        };
+        let side = 2 * MAGNIFIER_RADIUS + 1;
+        let grid_size = safe_usize_to_f32(side) * MAGNIFIER_CELL;
+        let box_size = vec2(grid_size, grid_size + MAGNIFIER_CAPTION);
+        let origin = magnifier_origin(cursor, box_size, painter.clip_rect());
+        let frame = Rect::from_min_size(origin, box_size);
+        painter.rect_filled(
+            frame.expand(2.0),
+            3.0,
+            Color32::from_rgba_unmultiplied(20, 20, 20, 230),
+        );
+
+        let cells = pixel_grid(&image.pixels, cx, cy, MAGNIFIER_RADIUS);
+        let cell_rect = |col: usize, row: usize, span: usize| {
+            let min =
+                origin + vec2(safe_usize_to_f32(col), safe_usize_to_f32(row)) * MAGNIFIER_CELL;
+            Rect::from_min_size(min, Vec2::splat(safe_usize_to_f32(span) * MAGNIFIER_CELL))
+        };
+        for (idx, cell) in cells.iter().enumerate() {
+            let color = cell.map_or(Color32::from_gray(45), |c| {
+                let [r, g, b, _] = c.to_array();
+                Color32::from_rgb(r, g, b)
+            });
+            painter.rect_filled(cell_rect(idx % side, idx / side, 1), 0.0, color);
+        }
+
+        let half = self.snap.color_sample_mode.half_window();
+        if half > 0 {
+            let window = cell_rect(
+                MAGNIFIER_RADIUS - half,
+                MAGNIFIER_RADIUS - half,
+                2 * half + 1,
+            );
+            painter.rect_stroke(
+                window,
+                0.0,
+                Stroke::new(1.0_f32, Color32::from_rgb(255, 210, 60)),
+                StrokeKind::Inside,
+            );
+        }
+        let center = cell_rect(MAGNIFIER_RADIUS, MAGNIFIER_RADIUS, 1);
+        painter.rect_stroke(
+            center,
+            0.0,
+            Stroke::new(1.0_f32, Color32::BLACK),
+            StrokeKind::Inside,
+        );
+        painter.rect_stroke(
+            center,
+            0.0,
+            Stroke::new(1.0_f32, Color32::WHITE),
+            StrokeKind::Outside,
+        );
+
+        if let Some(center_color) = cells[side * MAGNIFIER_RADIUS + MAGNIFIER_RADIUS] {
+            let [r, g, b, _] = center_color.to_array();
+            painter.text(
+                origin + vec2(grid_size * 0.5, grid_size + 4.0),
+                egui::Align2::CENTER_TOP,
+                format!("RGB {r}, {g}, {b}\n#{r:02X}{g:02X}{b:02X}"),
+                egui::FontId::monospace(11.0),
+                Color32::WHITE,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_grid_pads_outside_the_image() {
+        let pixels = (0..9u8).map(Color32::from_gray).collect();
+        let image = ColorImage::new([3, 3], pixels);
+        let grid = pixel_grid(&image, 0, 0, 1);
+        assert_eq!(grid.len(), 9);
+        assert_eq!(&grid[..4], &[None, None, None, None]);
+        assert_eq!(grid[4], Some(Color32::from_gray(0)));
+        assert_eq!(grid[8], Some(Color32::from_gray(4)));
+    }
+
+    #[test]
+    fn magnifier_flips_away_from_clip_edges() {
+        let clip = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 300.0));
+        let size = vec2(100.0, 130.0);
+        let inside = magnifier_origin(pos2(50.0, 50.0), size, clip);
+        assert_eq!(
+            inside,
+            pos2(50.0 + MAGNIFIER_OFFSET, 50.0 + MAGNIFIER_OFFSET)
+        );
+        let corner = magnifier_origin(pos2(390.0, 290.0), size, clip);
+        assert_eq!(
+            corner,
+            pos2(
+                390.0 - MAGNIFIER_OFFSET - 100.0,
+                290.0 - MAGNIFIER_OFFSET - 130.0
+            )
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use super::super::CurcatApp;
+use crate::i18n::TextKey;
+
+impl CurcatApp {
+    /// Prompt to crop the image to the plot frame found in it; anchored at the top so the
+    /// dimmed preview stays in view.
+    pub(crate) fn ui_plot_crop_prompt(&mut self, ctx: &egui::Context) {
+        let Some(crop) = self.image.crop_suggestion else {
+            return;
+        };
+        let i18n = self.i18n();
+        let size = crop.size() + egui::vec2(1.0, 1.0);
+
+        let mut confirm = false;
+        let mut dismiss = false;
+        let mut open = true;
+
+        egui::Window::new(i18n.text(TextKey::PlotCropWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 48.0))
+            .show(ctx, |ui| {
+                ui.label(i18n.text(TextKey::PlotCropIntro));
+                ui.add_space(6.0);
+                ui.label(format!(
+                    "{}: {}x{} px",
+                    i18n.text(TextKey::PlotCropSize),
+                    size.x,
+                    size.y
+                ));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(i18n.text(TextKey::KeepWholeImage)).clicked() {
+                        dismiss = true;
+                    }
+                    if ui.button(i18n.text(TextKey::PlotCropAccept)).clicked() {
+                        confirm = true;
+                    }
+                });
+            });
+
+        if confirm {
+            self.accept_plot_crop();
+        } else if dismiss || !open {
+            self.dismiss_plot_crop();
+        }
+    }
+}
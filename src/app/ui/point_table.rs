@@ -0,0 +1,259 @@
+use super::icons;
+use crate::app::{CurcatApp, DragTarget, PickedPoint};
+use crate::i18n::{I18n, TextKey, UiLanguage};
+use crate::types::{AxisUnit, AxisValue, CoordSystem, parse_axis_value};
+use egui::{RichText, Vec2, vec2};
+
+/// What a point-list row asks for.
+#[derive(Clone, Copy)]
+enum RowAction {
+    Select(usize),
+    SetValues(usize, f64, f64),
+    Nudge(usize, Vec2),
+    Move(usize, bool),
+    Insert(usize),
+    Delete(usize),
+    ToggleLock(usize),
+}
+
+/// Per-column editing setup shared by all rows.
+#[derive(Clone, Copy)]
+struct ValueColumn {
+    unit: AxisUnit,
+    speed: f64,
+}
+
+impl ValueColumn {
+    /// Drag speed of about a five-hundredth of the values' spread.
+    fn new(unit: AxisUnit, values: impl Iterator<Item = f64>) -> Self {
+        let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+        let span = max - min;
+        let speed = if span.is_finite() && span > 0.0 {
+            span / 500.0
+        } else {
+            0.01
+        };
+        Self { unit, speed }
+    }
+
+    /// Drag editor of `value`; returns whether it was changed.
+    fn edit(self, ui: &mut egui::Ui, enabled: bool, value: &mut f64) -> bool {
+        let unit = self.unit;
+        ui.add_enabled(
+            enabled,
+            egui::DragValue::new(value)
+                .speed(self.speed)
+                .custom_formatter(move |v, _| {
+                    AxisValue::from_scalar_seconds(unit, v)
+                        .map_or_else(|| v.to_string(), |value| value.format())
+                })
+                .custom_parser(move |text| {
+                    parse_axis_value(text, unit).map(|value| value.to_scalar_seconds())
+                }),
+        )
+        .changed()
+    }
+}
+
+impl CurcatApp {
+    /// Editable list of the active curve's points, shown in the points info window.
+    pub(crate) fn ui_point_table(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.heading(i18n.text(TextKey::PointList));
+        ui.label(RichText::new(i18n.text(TextKey::PointListHint)).small());
+        let [x_unit, y_unit] = self.point_value_units();
+        let points = &self.points.points;
+        let columns = [
+            ValueColumn::new(x_unit, points.iter().filter_map(|p| p.x_numeric)),
+            ValueColumn::new(y_unit, points.iter().filter_map(|p| p.y_numeric)),
+        ];
+        let headers = match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => ["X", "Y"],
+            CoordSystem::Polar => [i18n.text(TextKey::Angle), i18n.text(TextKey::Radius)],
+            CoordSystem::Smith => ["r", "x"],
+        };
+        let selected = self.selected_point();
+        let curve_locked = self.points.curves.curves[self.points.curves.active].locked;
+        let editable = !self.view_only();
+        let mut action = None;
+        egui::ScrollArea::vertical()
+            .max_height(320.0)
+            .show(ui, |ui| {
+                ui.add_enabled_ui(editable, |ui| {
+                    egui::Grid::new("point_table_grid")
+                        .num_columns(7)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("#").strong());
+                            ui.label(RichText::new(headers[0]).strong());
+                            ui.label(RichText::new(headers[1]).strong());
+                            ui.label(RichText::new(i18n.text(TextKey::PixelX)).strong());
+                            ui.label(RichText::new(i18n.text(TextKey::PixelY)).strong());
+                            ui.label("");
+                            ui.label("");
+                            ui.end_row();
+                            let last = points.len().saturating_sub(1);
+                            for (idx, point) in points.iter().enumerate() {
+                                let row = point_row(
+                                    ui,
+                                    i18n,
+                                    idx,
+                                    point,
+                                    columns,
+                                    selected,
+                                    last,
+                                    curve_locked,
+                                );
+                                if row.is_some() {
+                                    action = row;
+                                }
+                            }
+                        });
+                });
+            });
+        self.apply_row_action(action);
+    }
+
+    fn apply_row_action(&mut self, action: Option<RowAction>) {
+        match action {
+            Some(RowAction::Select(idx)) => {
+                self.interaction.selection =
+                    (self.selected_point() != Some(idx)).then_some(DragTarget::CurvePoint(idx));
+                self.interaction.point_selection.clear();
+            }
+            Some(RowAction::SetValues(idx, x, y)) => {
+                if !self.set_point_values(idx, x, y) {
+                    self.set_status_warn(match self.ui.language {
+                        UiLanguage::En => "No image position reads these values.",
+                        UiLanguage::Ru => {
+                            "Эти значения не соответствуют ни одной точке изображения."
+                        }
+                    });
+                }
+            }
+            Some(RowAction::Nudge(idx, delta)) => self.nudge_point(idx, delta),
+            Some(RowAction::Move(idx, up)) => self.move_point_row(idx, up),
+            Some(RowAction::Insert(idx)) => self.insert_point_after(idx),
+            Some(RowAction::Delete(idx)) => self.delete_point_row(idx),
+            Some(RowAction::ToggleLock(idx)) => self.toggle_point_lock(idx),
+            None => {}
+        }
+    }
+}
+
+/// One row of the point list; returns the action of an edited field or clicked button.
+///
+/// Values and pixels of a locked point, by itself or with `curve_locked`, are read-only.
+#[allow(clippy::too_many_arguments)]
+fn point_row(
+    ui: &mut egui::Ui,
+    i18n: I18n,
+    idx: usize,
+    point: &PickedPoint,
+    columns: [ValueColumn; 2],
+    selected: Option<usize>,
+    last: usize,
+    curve_locked: bool,
+) -> Option<RowAction> {
+    let editable = !point.locked && !curve_locked;
+    let mut action = ui
+        .add(egui::Button::selectable(
+            selected == Some(idx),
+            (idx + 1).to_string(),
+        ))
+        .clicked()
+        .then_some(RowAction::Select(idx));
+    if let (Some(mut x), Some(mut y)) = (point.x_numeric, point.y_numeric) {
+        let x_changed = columns[0].edit(ui, editable, &mut x);
+        let y_changed = columns[1].edit(ui, editable, &mut y);
+        if x_changed || y_changed {
+            action = Some(RowAction::SetValues(idx, x, y));
+        }
+    } else {
+        ui.label("—");
+        ui.label("—");
+    }
+    for axis in [0, 1] {
+        let old = point.pixel[axis];
+        let mut value = old;
+        if ui
+            .add_enabled(
+                editable,
+                egui::DragValue::new(&mut value)
+                    .speed(0.1)
+                    .fixed_decimals(1),
+            )
+            .changed()
+        {
+            let mut delta = vec2(0.0, 0.0);
+            delta[axis] = value - old;
+            action = Some(RowAction::Nudge(idx, delta));
+        }
+    }
+    ui.horizontal(|ui| {
+        let buttons = [
+            (
+                "⬆",
+                TextKey::MovePointUp,
+                idx > 0,
+                RowAction::Move(idx, true),
+            ),
+            (
+                "⬇",
+                TextKey::MovePointDown,
+                idx < last,
+                RowAction::Move(idx, false),
+            ),
+            (
+                "+",
+                TextKey::InsertPointAfter,
+                idx < last,
+                RowAction::Insert(idx),
+            ),
+            (
+                "✖",
+                TextKey::DeletePointRow,
+                editable,
+                RowAction::Delete(idx),
+            ),
+        ];
+        for (icon, hover, enabled, clicked) in buttons {
+            if ui
+                .add_enabled(enabled, egui::Button::new(icon).small())
+                .on_hover_text(i18n.text(hover))
+                .clicked()
+            {
+                action = Some(clicked);
+            }
+        }
+    });
+    let locked = point.locked || curve_locked;
+    let hover = if curve_locked {
+        TextKey::PointLockedByCurveHover
+    } else {
+        TextKey::LockPointHover
+    };
+    let icon = if locked {
+        icons::ICON_LOCK
+    } else {
+        icons::ICON_UNLOCK
+    };
+    if ui
+        .add_enabled(
+            !curve_locked,
+            egui::Button::image(icons::image(icon, icons::INLINE_ICON_SIZE))
+                .selected(locked)
+                .small()
+                .image_tint_follows_text_color(true),
+        )
+        .on_hover_text(i18n.text(hover))
+        .on_disabled_hover_text(i18n.text(hover))
+        .clicked()
+    {
+        action = Some(RowAction::ToggleLock(idx));
+    }
+    ui.end_row();
+    action
+}
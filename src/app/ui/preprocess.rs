@@ -0,0 +1,132 @@
+use crate::app::CurcatApp;
+use crate::i18n::TextKey;
+use crate::image::{Binarization, Preprocessing};
+use egui::RichText;
+
+impl CurcatApp {
+    pub(crate) fn ui_preprocess_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.preprocess_window_open {
+            return;
+        }
+
+        let mut open = self.ui.preprocess_window_open;
+        egui::Window::new(self.t(TextKey::PreprocessWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                self.ui_preprocess_section(ui);
+            });
+        self.ui.preprocess_window_open = open;
+    }
+
+    fn ui_preprocess_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::PreprocessIntro)).small());
+        ui.add_space(4.0);
+
+        let has_image = self.image.image.is_some();
+        let settings = &mut self.image.preprocessing;
+        let mut changed = false;
+
+        ui.add_enabled_ui(has_image, |ui| {
+            ui.spacing_mut().slider_width = 150.0;
+            changed |= ui
+                .checkbox(&mut settings.enabled, i18n.text(TextKey::PreprocessEnabled))
+                .on_hover_text(i18n.text(TextKey::PreprocessEnabledHover))
+                .changed();
+
+            ui.add_enabled_ui(settings.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .checkbox(
+                            &mut settings.flatten_background,
+                            i18n.text(TextKey::FlattenBackground),
+                        )
+                        .on_hover_text(i18n.text(TextKey::FlattenBackgroundHover))
+                        .changed();
+                    changed |= ui
+                        .add_enabled(
+                            settings.flatten_background,
+                            egui::Slider::new(&mut settings.background_cell, 8..=128)
+                                .text(i18n.text(TextKey::BackgroundCell)),
+                        )
+                        .changed();
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(i18n.text(TextKey::Binarization));
+                    let label = |mode: Binarization| match mode {
+                        Binarization::Off => i18n.text(TextKey::BinarizationOff),
+                        Binarization::Global => i18n.text(TextKey::BinarizationGlobal),
+                        Binarization::Adaptive => i18n.text(TextKey::BinarizationAdaptive),
+                    };
+                    egui::ComboBox::from_id_salt("binarization_mode")
+                        .selected_text(label(settings.binarization))
+                        .show_ui(ui, |ui| {
+                            for mode in [
+                                Binarization::Off,
+                                Binarization::Global,
+                                Binarization::Adaptive,
+                            ] {
+                                let option = ui.selectable_value(
+                                    &mut settings.binarization,
+                                    mode,
+                                    label(mode),
+                                );
+                                changed |= option.changed();
+                            }
+                        });
+                });
+                match settings.binarization {
+                    Binarization::Off => {}
+                    Binarization::Global => {
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(&mut settings.threshold, 0.0..=1.0)
+                                    .text(i18n.text(TextKey::Level)),
+                            )
+                            .changed();
+                    }
+                    Binarization::Adaptive => {
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(&mut settings.window_radius, 2..=64)
+                                    .text(i18n.text(TextKey::AdaptiveWindow)),
+                            )
+                            .changed();
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(&mut settings.offset, 0.0..=0.3)
+                                    .text(i18n.text(TextKey::AdaptiveOffset)),
+                            )
+                            .on_hover_text(i18n.text(TextKey::AdaptiveOffsetHover))
+                            .changed();
+                    }
+                }
+                if settings.binarization != Binarization::Off {
+                    changed |= ui
+                        .checkbox(
+                            &mut settings.keep_ink_color,
+                            i18n.text(TextKey::KeepInkColor),
+                        )
+                        .on_hover_text(i18n.text(TextKey::KeepInkColorHover))
+                        .changed();
+                }
+            });
+
+            if ui.button(i18n.text(TextKey::ResetPreprocessing)).clicked() {
+                *settings = Preprocessing::default();
+                changed = true;
+            }
+        });
+
+        if !has_image {
+            ui.label(RichText::new(i18n.text(TextKey::LoadImageToPreprocess)).small());
+        }
+
+        if changed && has_image {
+            self.apply_filters_to_loaded_image();
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Profiler window of `--features profiling` builds: the scopes of the latest or slowest
+//! recorded frame per thread, and a `.puffin` trace file users can attach to a report.
+//!
+//! The window draws the frames itself; `puffin_egui` lags behind the egui release in use.
+
+use crate::app::CurcatApp;
+use crate::i18n::{I18n, TextKey, UiLanguage};
+use crate::profiling::puffin::{
+    self, FrameData, GlobalFrameView, MergeScope, ScopeCollection, UnpackedFrameData,
+    merge_scopes_for_thread,
+};
+use egui::RichText;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Indent of each nesting level in the scope table, in points.
+const SCOPE_INDENT: f32 = 12.0;
+
+/// Frames collected while the window is open.
+pub struct ProfilerWindow {
+    view: GlobalFrameView,
+    /// Shown instead of live frames while paused.
+    paused: Option<Arc<FrameData>>,
+    /// Show the slowest recent frame rather than the latest one.
+    slowest: bool,
+}
+
+impl ProfilerWindow {
+    fn new() -> Self {
+        puffin::set_scopes_on(true);
+        Self {
+            view: GlobalFrameView::default(),
+            paused: None,
+            slowest: false,
+        }
+    }
+
+    fn shown_frame(&self) -> Option<Arc<FrameData>> {
+        if let Some(frame) = &self.paused {
+            return Some(Arc::clone(frame));
+        }
+        let view = self.view.lock();
+        if self.slowest {
+            view.slowest_frames_chronological()
+                .max_by_key(|frame| frame.duration_ns())
+                .cloned()
+        } else {
+            view.latest_frame()
+        }
+    }
+}
+
+fn millis(ns: puffin::NanoSecond) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let ns = ns as f64;
+    ns / 1.0e6
+}
+
+/// One line of the scope table.
+struct ScopeRow {
+    depth: u8,
+    name: String,
+    data: String,
+    millis: f64,
+    calls: usize,
+}
+
+/// Flatten `merged` depth-first, so the table can be drawn without holding the view lock.
+fn scope_rows(
+    scopes: &ScopeCollection,
+    merged: &[MergeScope<'_>],
+    depth: u8,
+    rows: &mut Vec<ScopeRow>,
+) {
+    for scope in merged {
+        rows.push(ScopeRow {
+            depth,
+            name: scopes
+                .fetch_by_id(&scope.id)
+                .map_or_else(|| "?".to_string(), |details| details.name().to_string()),
+            data: scope.data.to_string(),
+            millis: millis(scope.duration_per_frame_ns),
+            calls: scope.num_pieces,
+        });
+        scope_rows(scopes, &scope.children, depth.saturating_add(1), rows);
+    }
+}
+
+/// Scope rows of every thread in `frame`, by thread name.
+fn frame_scope_rows(
+    view: &GlobalFrameView,
+    frame: &Arc<UnpackedFrameData>,
+) -> Vec<(String, Vec<ScopeRow>)> {
+    let view = view.lock();
+    let frames = [Arc::clone(frame)];
+    frame
+        .thread_streams
+        .keys()
+        .filter_map(|thread| {
+            let merged = merge_scopes_for_thread(view.scope_collection(), &frames, thread).ok()?;
+            let mut rows = Vec::new();
+            scope_rows(view.scope_collection(), &merged, 0, &mut rows);
+            Some((thread.name.clone(), rows))
+        })
+        .collect()
+}
+
+impl CurcatApp {
+    pub(crate) fn toggle_profiler_window(&mut self) {
+        self.ui.profiler = match self.ui.profiler.take() {
+            Some(_) => None,
+            None => Some(ProfilerWindow::new()),
+        };
+    }
+
+    pub(crate) fn ui_profiler_window(&mut self, ctx: &egui::Context) {
+        if self.ui.profiler.is_none() {
+            return;
+        }
+        let i18n = self.i18n();
+        let mut open = true;
+        let mut save = false;
+        egui::Window::new(i18n.text(TextKey::Profiler))
+            .open(&mut open)
+            .resizable(true)
+            .collapsible(false)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                if let Some(window) = self.ui.profiler.as_mut() {
+                    save = profiler_section(ui, i18n, window);
+                }
+            });
+        if !open {
+            self.ui.profiler = None;
+        }
+        if save {
+            self.save_profiler_trace();
+        }
+        if self
+            .ui
+            .profiler
+            .as_ref()
+            .is_some_and(|w| w.paused.is_none())
+        {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Write the recorded frames to the export folder, for `puffin_viewer`.
+    fn save_profiler_trace(&mut self) {
+        let Some(window) = &self.ui.profiler else {
+            return;
+        };
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let path = self
+            .export_dialog_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(format!("curcat-{stamp}.puffin"));
+        let written = std::fs::File::create(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|mut file| window.view.lock().write(&mut file));
+        match written {
+            Ok(()) => self.set_status(match self.ui.language {
+                UiLanguage::En => format!("Profiler trace saved to {}", path.display()),
+                UiLanguage::Ru => format!("Трасса профилировщика сохранена в {}", path.display()),
+            }),
+            Err(err) => self.set_status_error(match self.ui.language {
+                UiLanguage::En => format!("Profiler trace not saved: {err:#}"),
+                UiLanguage::Ru => format!("Трасса профилировщика не сохранена: {err:#}"),
+            }),
+        }
+    }
+}
+
+/// Controls and scope table; returns whether a trace save was requested.
+fn profiler_section(ui: &mut egui::Ui, i18n: I18n, window: &mut ProfilerWindow) -> bool {
+    let mut save = false;
+    ui.horizontal(|ui| {
+        let mut recording = puffin::are_scopes_on();
+        if ui
+            .checkbox(&mut recording, i18n.text(TextKey::ProfilerRecord))
+            .on_hover_text(i18n.text(TextKey::ProfilerRecordHover))
+            .changed()
+        {
+            puffin::set_scopes_on(recording);
+        }
+        let mut paused = window.paused.is_some();
+        if ui
+            .checkbox(&mut paused, i18n.text(TextKey::ProfilerPause))
+            .changed()
+        {
+            window.paused = if paused { window.shown_frame() } else { None };
+        }
+        ui.checkbox(
+            &mut window.slowest,
+            i18n.text(TextKey::ProfilerSlowestFrame),
+        )
+        .on_hover_text(i18n.text(TextKey::ProfilerSlowestFrameHover));
+        save = ui
+            .button(i18n.text(TextKey::ProfilerSaveTrace))
+            .on_hover_text(i18n.text(TextKey::ProfilerSaveTraceHover))
+            .clicked();
+    });
+    ui.separator();
+
+    let Some(frame) = window.shown_frame() else {
+        ui.label(RichText::new(i18n.text(TextKey::ProfilerNoFrames)).small());
+        return save;
+    };
+    let Ok(unpacked) = frame.unpacked() else {
+        ui.label(RichText::new(i18n.text(TextKey::ProfilerNoFrames)).small());
+        return save;
+    };
+    ui.label(format!(
+        "#{} · {:.2} ms",
+        frame.frame_index(),
+        millis(frame.duration_ns())
+    ));
+    let threads = frame_scope_rows(&window.view, &unpacked);
+    egui::ScrollArea::vertical()
+        .max_height(360.0)
+        .show(ui, |ui| {
+            for (thread, rows) in &threads {
+                ui.label(RichText::new(thread).strong());
+                egui::Grid::new(("profiler_scopes", thread))
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(i18n.text(TextKey::ProfilerScope)).strong());
+                        ui.label(RichText::new("ms").strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ProfilerCalls)).strong());
+                        ui.end_row();
+                        for row in rows {
+                            ui.horizontal(|ui| {
+                                ui.add_space(f32::from(row.depth) * SCOPE_INDENT);
+                                let label = ui.label(&row.name);
+                                if !row.data.is_empty() {
+                                    label.on_hover_text(&row.data);
+                                }
+                            });
+                            ui.label(format!("{:.2}", row.millis));
+                            ui.label(row.calls.to_string());
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(4.0);
+            }
+        });
+    save
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::harness::Harness;
+
+    #[test]
+    fn open_window_records_the_ui_frame_scopes() {
+        let mut harness = Harness::new();
+        harness.app.toggle_profiler_window();
+        harness.run_frames(4);
+        let window = harness.app.ui.profiler.as_ref().expect("profiler open");
+        assert!(puffin::are_scopes_on());
+        let frames: Vec<_> = window
+            .view
+            .lock()
+            .recent_frames()
+            .filter_map(|frame| frame.unpacked().ok())
+            .collect();
+        assert!(frames.iter().any(|frame| {
+            frame_scope_rows(&window.view, frame)
+                .iter()
+                .flat_map(|(_, rows)| rows)
+                .any(|row| row.name.ends_with("ui_frame") && row.depth == 0)
+        }));
+
+        harness.app.toggle_profiler_window();
+        assert!(harness.app.ui.profiler.is_none());
+    }
+}
@@ -48,3 +48,93 @@ impl CurcatApp {
         }
     }
 }
+
+impl CurcatApp {
+    pub(crate) fn ui_unsaved_changes_prompt(&mut self, ctx: &egui::Context) {
+        if self.project.unsaved_prompt.is_none() {
+            return;
+        }
+        let can_save = self.image.meta.as_ref().and_then(|m| m.path()).is_some();
+        let mut save = false;
+        let mut discard = false;
+        let mut cancel = false;
+        let mut open = true;
+        egui::Window::new(self.t(TextKey::UnsavedChangesWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(self.t(TextKey::UnsavedChangesIntro));
+                if !can_save {
+                    ui.label(egui::RichText::new(self.t(TextKey::UnsavedChangesCannotSave)).weak());
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    save = ui
+                        .add_enabled(can_save, egui::Button::new(self.t(TextKey::SaveProject)))
+                        .clicked();
+                    discard = ui
+                        .button(self.t(TextKey::DiscardChanges))
+                        .on_hover_text(self.t(TextKey::DiscardChangesHover))
+                        .clicked();
+                    cancel = ui.button(self.t(TextKey::Cancel)).clicked();
+                });
+            });
+
+        if save {
+            if let Some(action) = self.project.unsaved_prompt.take() {
+                self.save_before_deferred_action(action);
+            }
+        } else if discard {
+            if let Some(action) = self.project.unsaved_prompt.take() {
+                self.run_deferred_action(ctx, action);
+            }
+        } else if cancel || !open {
+            self.project.unsaved_prompt = None;
+        }
+    }
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_session_restore_prompt(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.project.restore_prompt.as_ref() else {
+            return;
+        };
+        // Any image or project opened in the meantime answers the question.
+        if self.image.image.is_some() || self.project.pending_image_task.is_some() {
+            self.project.restore_prompt = None;
+            return;
+        }
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let mut reopen = false;
+        let mut dismiss = false;
+        let mut open = true;
+        egui::Window::new(self.t(TextKey::RestoreSessionWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(self.t(TextKey::RestoreSessionIntro));
+                ui.label(egui::RichText::new(name).strong())
+                    .on_hover_text(path.display().to_string());
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    reopen = ui.button(self.t(TextKey::ReopenProject)).clicked();
+                    dismiss = ui.button(self.t(TextKey::StartEmpty)).clicked();
+                });
+            });
+
+        if reopen {
+            if let Some(path) = self.project.restore_prompt.take() {
+                self.handle_project_load(path);
+            }
+        } else if dismiss || !open {
+            self.project.restore_prompt = None;
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Live plot of the digitized data in calibrated coordinates.
+//!
+//! Each curve shows its picked points and the interpolated samples the export
+//! would write, so mistakes show up before anything is exported. With export
+//! smoothing on, the smoothed rows are drawn dashed on top.
+
+use crate::app::CurcatApp;
+use crate::i18n::TextKey;
+use crate::profiling::profile_function;
+use egui::{Color32, RichText};
+use egui_plot::{Legend, Line, LineStyle, Plot, PlotPoints, Points};
+
+/// Marker radius of picked points, in screen pixels.
+const POINT_RADIUS: f32 = 3.0;
+
+/// Plotted data of one curve.
+pub struct ResultPlotSeries {
+    pub(crate) name: String,
+    pub(crate) color: Color32,
+    pub(crate) points: Vec<[f64; 2]>,
+    pub(crate) samples: Vec<[f64; 2]>,
+    /// Smoothed export rows; empty while smoothing is off.
+    pub(crate) smoothed: Vec<[f64; 2]>,
+}
+
+impl CurcatApp {
+    /// Calibrated points and interpolated samples of every curve that has any.
+    pub(crate) fn result_plot_series(&mut self) -> Vec<ResultPlotSeries> {
+        let (x_mapping, y_mapping) = self.cartesian_mappings();
+        let polar_mapping = self.polar_mapping();
+        self.ensure_point_numeric_cache(
+            self.calibration.coord_system,
+            x_mapping.as_ref(),
+            y_mapping.as_ref(),
+            polar_mapping.as_ref(),
+        );
+        (0..self.points.curves.curves.len())
+            .filter_map(|idx| {
+                let curve_points = self.curve_points(idx);
+                let points: Vec<[f64; 2]> = Self::collect_numeric_points_in_order(curve_points)
+                    .iter()
+                    .map(|p| [p.x, p.y])
+                    .collect();
+                if points.is_empty() {
+                    return None;
+                }
+                let samples = self
+                    .build_interpolated_samples(curve_points)
+                    .iter()
+                    .map(|p| [p.x, p.y])
+                    .collect();
+                let smoothed = self
+                    .smoothing_preview_rows(curve_points)
+                    .iter()
+                    .map(|p| [p.x, p.y])
+                    .collect();
+                Some(ResultPlotSeries {
+                    name: self.points.curves.curves[idx].name.clone(),
+                    color: self.curve_point_color(idx),
+                    points,
+                    samples,
+                    smoothed,
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn ui_result_plot_panel(&mut self, ui: &mut egui::Ui) {
+        profile_function!();
+        if !self.calibration_ready() {
+            ui.centered_and_justified(|ui| {
+                ui.label(RichText::new(self.t(TextKey::ResultPlotNeedsCalibration)).weak());
+            });
+            return;
+        }
+        let series = self.result_plot_series();
+        let (x_label, y_label) = self.axis_labels();
+        Plot::new("result_plot")
+            .legend(Legend::default())
+            .x_axis_label(x_label)
+            .y_axis_label(y_label)
+            .show(ui, |plot_ui| {
+                for curve in series {
+                    if !curve.samples.is_empty() {
+                        plot_ui.line(
+                            Line::new(curve.name.as_str(), PlotPoints::from(curve.samples))
+                                .color(curve.color),
+                        );
+                    }
+                    if !curve.smoothed.is_empty() {
+                        plot_ui.line(
+                            Line::new(curve.name.as_str(), PlotPoints::from(curve.smoothed))
+                                .color(curve.color)
+                                .style(LineStyle::dashed_dense()),
+                        );
+                    }
+                    plot_ui.points(
+                        Points::new(curve.name.as_str(), PlotPoints::from(curve.points))
+                            .color(curve.color)
+                            .radius(POINT_RADIUS),
+                    );
+                }
+            });
+    }
+}
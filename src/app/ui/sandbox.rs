@@ -0,0 +1,187 @@
+use super::icons;
+use crate::app::{CurcatApp, SandboxSummary};
+use crate::i18n::TextKey;
+use crate::interp::XYPoint;
+use crate::types::{AxisUnit, AxisValue, CoordSystem};
+use egui::{Color32, RichText};
+
+const CHANGED_VALUE_COLOR: Color32 = Color32::from_rgb(250, 190, 90);
+
+fn format_scalar(unit: AxisUnit, value: f64) -> String {
+    AxisValue::from_scalar_seconds(unit, value).map_or_else(|| "—".to_string(), |v| v.format())
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_calibration_sandbox_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.calibration_sandbox_window_open {
+            self.calibration.sandbox = None;
+            return;
+        }
+        self.sync_calibration_sandbox();
+
+        let mut open = self.ui.calibration_sandbox_window_open;
+        let mut apply = false;
+        let mut reset = false;
+        let i18n = self.i18n();
+        egui::Window::new(i18n.text(TextKey::CalibrationSandbox))
+            .open(&mut open)
+            .resizable(false)
+            .default_width(340.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(i18n.text(TextKey::CalibrationSandboxIntro)).small());
+                ui.add_space(4.0);
+                let live = self.calibration_sandbox_seed();
+                let names = match live.coord_system {
+                    CoordSystem::Cartesian => ["X1", "X2", "Y1", "Y2"],
+                    CoordSystem::Polar => ["R1", "R2", "A1", "A2"],
+                    CoordSystem::Affine | CoordSystem::Smith => {
+                        ui.label(
+                            RichText::new(i18n.text(TextKey::SandboxAffineUnsupported)).weak(),
+                        );
+                        return;
+                    }
+                };
+                if let Some(draft) = self.calibration.sandbox.as_mut() {
+                    egui::Grid::new("calibration_sandbox_values")
+                        .num_columns(3)
+                        .spacing([8.0, 4.0])
+                        .show(ui, |ui| {
+                            for ((name, value), live_value) in
+                                names.iter().zip(&mut draft.values).zip(&live.values)
+                            {
+                                ui.label(*name);
+                                ui.add(
+                                    egui::TextEdit::singleline(value)
+                                        .hint_text(live_value.as_str())
+                                        .desired_width(120.0),
+                                );
+                                if value != live_value {
+                                    ui.label(
+                                        RichText::new(live_value.as_str()).weak().strikethrough(),
+                                    );
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                ui.separator();
+                let draft = self.calibration.sandbox.clone();
+                let current = self.calibration_sandbox_summary(None);
+                let what_if = self.calibration_sandbox_summary(draft.as_ref());
+                if self.points.points.is_empty() {
+                    ui.label(RichText::new(i18n.text(TextKey::AddPointsToSeeStats)).weak());
+                } else {
+                    self.ui_calibration_sandbox_summary(ui, current, what_if);
+                }
+
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    let changed = draft.is_some_and(|d| d != live);
+                    let can_apply = changed && what_if.is_some();
+                    apply = ui
+                        .add_enabled(
+                            can_apply,
+                            egui::Button::new(i18n.text(TextKey::ApplySandbox)),
+                        )
+                        .on_hover_text(i18n.text(TextKey::ApplySandboxHover))
+                        .clicked();
+                    reset = ui
+                        .add_enabled(
+                            changed,
+                            egui::Button::image_and_text(
+                                icons::image(icons::ICON_UNDO, icons::BUTTON_ICON_SIZE),
+                                i18n.text(TextKey::ResetSandbox),
+                            )
+                            .image_tint_follows_text_color(true),
+                        )
+                        .on_hover_text(i18n.text(TextKey::ResetSandboxHover))
+                        .clicked();
+                });
+            });
+        self.ui.calibration_sandbox_window_open = open;
+
+        if apply {
+            self.apply_calibration_sandbox();
+        }
+        if reset || apply {
+            self.calibration.sandbox = Some(self.calibration_sandbox_seed());
+        }
+    }
+
+    fn ui_calibration_sandbox_summary(
+        &self,
+        ui: &mut egui::Ui,
+        current: Option<SandboxSummary>,
+        what_if: Option<SandboxSummary>,
+    ) {
+        let i18n = self.i18n();
+        let (x_unit, y_unit) = match self.calibration.coord_system {
+            CoordSystem::Cartesian => (self.calibration.cal_x.unit, self.calibration.cal_y.unit),
+            CoordSystem::Polar | CoordSystem::Smith => (AxisUnit::Float, AxisUnit::Float),
+            CoordSystem::Affine => (
+                self.calibration.affine_cal.x_unit,
+                self.calibration.affine_cal.y_unit,
+            ),
+        };
+        let (x_name, y_name) = match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => ("X", "Y"),
+            CoordSystem::Polar => ("A", "R"),
+            CoordSystem::Smith => ("r", "x"),
+        };
+        let point = |p: XYPoint| {
+            format!(
+                "({}, {})",
+                format_scalar(x_unit, p.x),
+                format_scalar(y_unit, p.y)
+            )
+        };
+        let cells = |s: &SandboxSummary| {
+            [
+                format_scalar(x_unit, s.x_min),
+                format_scalar(x_unit, s.x_max),
+                format_scalar(y_unit, s.y_min),
+                format_scalar(y_unit, s.y_max),
+                point(s.first),
+                point(s.last),
+            ]
+        };
+        let labels = [
+            format!("{x_name} min"),
+            format!("{x_name} max"),
+            format!("{y_name} min"),
+            format!("{y_name} max"),
+            i18n.text(TextKey::FirstPoint).to_string(),
+            i18n.text(TextKey::LastPoint).to_string(),
+        ];
+        let now = current.as_ref().map(cells);
+        let then = what_if.as_ref().map(cells);
+        let missing = i18n.text(TextKey::NoData);
+        egui::Grid::new("calibration_sandbox_summary")
+            .num_columns(3)
+            .striped(true)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("");
+                ui.label(RichText::new(i18n.text(TextKey::SandboxCurrent)).strong());
+                ui.label(RichText::new(i18n.text(TextKey::SandboxWhatIf)).strong());
+                ui.end_row();
+                for (idx, label) in labels.iter().enumerate() {
+                    let now_text = now.as_ref().map_or(missing, |c| c[idx].as_str());
+                    let then_text = then.as_ref().map_or(missing, |c| c[idx].as_str());
+                    ui.label(label);
+                    ui.label(RichText::new(now_text).monospace());
+                    let rich = RichText::new(then_text).monospace();
+                    ui.label(if then_text == now_text {
+                        rich
+                    } else {
+                        rich.color(CHANGED_VALUE_COLOR)
+                    });
+                    ui.end_row();
+                }
+            });
+        if what_if.is_none() {
+            ui.label(RichText::new(i18n.text(TextKey::SandboxDraftInvalid)).weak());
+        }
+    }
+}
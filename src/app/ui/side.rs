@@ -1,6 +1,15 @@
-//! Side panel UI: calibration, snapping, and export controls.
+//! Side panel UI: calibration, snapping, curves, and export controls.
 
+mod affine;
 mod axis_input;
 mod calibration;
+mod calibration_report;
+mod crossings;
+mod curve_stats;
+mod curves;
 mod export;
+mod export_range;
+mod fit;
 mod point_input;
+mod sample_grid;
+mod smith;
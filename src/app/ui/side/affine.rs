@@ -0,0 +1,177 @@
+use super::super::icons;
+use super::axis_input::{render_axis_name_row, render_time_input_rows, sanitize_axis_text};
+use super::calibration::{axis_unit_label, scale_kind_label};
+use crate::app::{AffineCalUi, CurcatApp, PickMode};
+use crate::i18n::{TextKey, UiLanguage};
+use crate::types::{AffineMappingError, AxisMappingError, AxisUnit, ScaleKind};
+use egui::{Color32, RichText};
+
+const fn fit_error_key(err: AffineMappingError) -> TextKey {
+    match err {
+        AffineMappingError::TooFewPoints => TextKey::AffineTooFewPoints,
+        AffineMappingError::CollinearPoints => TextKey::AffineCollinearPoints,
+        AffineMappingError::ParallelAxes => TextKey::AffineParallelAxes,
+        AffineMappingError::Axis(AxisMappingError::EqualValues) => TextKey::AffineEqualValues,
+        AffineMappingError::Axis(_) => TextKey::AffineInvalidValues,
+    }
+}
+
+/// Unit and scale pickers for one axis of the affine calibration.
+fn axis_row(
+    ui: &mut egui::Ui,
+    lang: UiLanguage,
+    name: &str,
+    unit: &mut AxisUnit,
+    scale: &mut ScaleKind,
+) {
+    ui.horizontal(|ui| {
+        ui.label(format!("{name}:"));
+        egui::ComboBox::from_id_salt(format!("affine_{name}_unit_combo"))
+            .selected_text(axis_unit_label(lang, *unit))
+            .show_ui(ui, |ui| {
+                for option in [AxisUnit::Float, AxisUnit::DateTime] {
+                    ui.selectable_value(unit, option, axis_unit_label(lang, option));
+                }
+            });
+        let allow_log = matches!(unit, AxisUnit::Float);
+        egui::ComboBox::from_id_salt(format!("affine_{name}_scale_combo"))
+            .selected_text(scale_kind_label(lang, *scale))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    scale,
+                    ScaleKind::Linear,
+                    scale_kind_label(lang, ScaleKind::Linear),
+                );
+                if allow_log {
+                    ui.selectable_value(
+                        scale,
+                        ScaleKind::Log10,
+                        scale_kind_label(lang, ScaleKind::Log10),
+                    );
+                }
+            });
+        if !allow_log {
+            *scale = ScaleKind::Linear;
+        }
+    });
+}
+
+impl CurcatApp {
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn ui_affine_calibration(&mut self, ui: &mut egui::Ui) {
+        let lang = self.ui.language;
+        let i18n = self.i18n();
+        let has_image = self.image.image.is_some();
+        {
+            let cal = &mut self.calibration.affine_cal;
+            let (x_unit, y_unit) = (cal.x_unit, cal.y_unit);
+            axis_row(ui, lang, "X", &mut cal.x_unit, &mut cal.x_scale);
+            render_axis_name_row(ui, i18n, "x", &mut self.project.axis_titles[0]);
+            if cal.x_unit == AxisUnit::DateTime {
+                render_time_input_rows(ui, i18n, "affine_x_time", &mut cal.x_time);
+            }
+            axis_row(ui, lang, "Y", &mut cal.y_unit, &mut cal.y_scale);
+            render_axis_name_row(ui, i18n, "y", &mut self.project.axis_titles[1]);
+            if cal.y_unit == AxisUnit::DateTime {
+                render_time_input_rows(ui, i18n, "affine_y_time", &mut cal.y_time);
+            }
+            for point in &mut cal.points {
+                if cal.x_unit != x_unit {
+                    sanitize_axis_text(&mut point.x_text, cal.x_unit);
+                }
+                if cal.y_unit != y_unit {
+                    sanitize_axis_text(&mut point.y_text, cal.y_unit);
+                }
+            }
+        }
+        ui.add_space(4.0);
+
+        let mut requested_pick = None;
+        let mut remove = None;
+        let mut attention = Vec::new();
+        let collapsing = egui::CollapsingHeader::new(self.t(TextKey::ReferencePoints))
+            .default_open(true)
+            .show(ui, |ui| {
+                let can_remove = self.calibration.affine_cal.points.len() > AffineCalUi::MIN_POINTS;
+                let pick_mode = self.calibration.pick_mode;
+                let pick_hover = self.t(TextKey::PickReferencePointHover);
+                let remove_hover = self.t(TextKey::RemoveReferencePointHover);
+                egui::Grid::new("affine_reference_points")
+                    .num_columns(5)
+                    .spacing([6.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.label("");
+                        ui.label(RichText::new("X").strong());
+                        ui.label(RichText::new("Y").strong());
+                        ui.end_row();
+                        for (idx, point) in
+                            self.calibration.affine_cal.points.iter_mut().enumerate()
+                        {
+                            ui.label(format!("P{}", idx + 1));
+                            let picking = pick_mode == PickMode::AffineRef(idx);
+                            let pick = ui
+                                .add_enabled(
+                                    has_image,
+                                    egui::Button::image(icons::image(
+                                        icons::ICON_PICK_POINT,
+                                        icons::BUTTON_ICON_SIZE,
+                                    ))
+                                    .selected(picking)
+                                    .image_tint_follows_text_color(true),
+                                )
+                                .on_hover_text(point.pixel.map_or_else(
+                                    || pick_hover.to_string(),
+                                    |p| format!("{pick_hover}\n@ ({:.1}, {:.1})", p.x, p.y),
+                                ));
+                            if pick.clicked() {
+                                requested_pick = Some(PickMode::AffineRef(idx));
+                            }
+                            attention.push((pick.rect, point.pixel.is_none()));
+                            for text in [&mut point.x_text, &mut point.y_text] {
+                                ui.add(egui::TextEdit::singleline(text).desired_width(72.0));
+                            }
+                            if can_remove
+                                && ui.small_button("✖").on_hover_text(remove_hover).clicked()
+                            {
+                                remove = Some(idx);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if ui
+                    .button(self.t(TextKey::AddReferencePoint))
+                    .on_hover_text(self.t(TextKey::AddReferencePointHover))
+                    .clicked()
+                {
+                    self.add_affine_reference();
+                }
+            });
+        collapsing
+            .header_response
+            .on_hover_text(self.t(TextKey::ReferencePointsHover));
+
+        if let Some(mode) = requested_pick {
+            self.begin_pick_mode(mode);
+        }
+        if let Some(idx) = remove {
+            self.remove_affine_reference(idx);
+        }
+        for (rect, active) in attention {
+            self.paint_attention_outline_if(ui, rect, active);
+        }
+
+        match self.calibration.affine_cal.fit() {
+            Ok(mapping) => {
+                ui.label(
+                    RichText::new(self.i18n().format_affine_fit_error(mapping.rms_residual))
+                        .color(Color32::GREEN),
+                )
+                .on_hover_text(self.t(TextKey::AffineFitHover));
+            }
+            Err(err) => {
+                ui.label(RichText::new(self.t(fit_error_key(err))).color(Color32::GRAY));
+            }
+        }
+    }
+}
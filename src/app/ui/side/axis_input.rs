@@ -1,6 +1,6 @@
 use super::super::icons;
-use crate::app::{AxisValueField, CurcatApp, PickMode};
-use crate::i18n::UiLanguage;
+use crate::app::{AxisTimeUi, AxisValueField, CurcatApp, PickMode};
+use crate::i18n::{I18n, TextKey, UiLanguage};
 use crate::types::AxisUnit;
 use egui::{
     Pos2, Rect, Response, RichText, TextBuffer, TextEdit,
@@ -29,12 +29,9 @@ const fn axis_char_allowed(unit: AxisUnit, ch: char) -> bool {
                 || matches!(ch, 'e' | 'E')
                 || matches!(ch, 'n' | 'N' | 'a' | 'A' | 'i' | 'I' | 'f' | 'F')
         }
+        // Letters and commas for custom formats such as `5 Mar 2024, 10:30 PM`.
         AxisUnit::DateTime => {
-            ch.is_ascii_digit()
-                || matches!(
-                    ch,
-                    '-' | '/' | '.' | ':' | ' ' | 'T' | 't' | '+' | 'Z' | 'z'
-                )
+            ch.is_ascii_alphanumeric() || matches!(ch, '-' | '/' | '.' | ':' | ' ' | '+' | ',')
         }
     }
 }
@@ -96,6 +93,63 @@ impl TextBuffer for AxisFilteredText<'_> {
     }
 }
 
+/// Input format and UTC offset fields of a `DateTime` axis; `id` keeps the edits of several
+/// axes apart.
+pub(super) fn render_time_input_rows(
+    ui: &mut egui::Ui,
+    i18n: I18n,
+    id: &str,
+    time: &mut AxisTimeUi,
+) {
+    ui.push_id(id, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::TimeInputFormat))
+                .on_hover_text(i18n.text(TextKey::TimeInputFormatHover));
+            ui.add(
+                TextEdit::singleline(&mut time.pattern)
+                    .hint_text(i18n.text(TextKey::TimeInputFormatAuto))
+                    .desired_width(120.0),
+            )
+            .on_hover_text(i18n.text(TextKey::TimeInputFormatHover));
+        });
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::UtcOffset))
+                .on_hover_text(i18n.text(TextKey::UtcOffsetHover));
+            let invalid = time.input().is_none();
+            let mut edit = TextEdit::singleline(&mut time.offset_text)
+                .hint_text("+00:00")
+                .desired_width(64.0);
+            if invalid {
+                edit = edit.text_color(ui.visuals().error_fg_color);
+            }
+            ui.add(edit).on_hover_text(i18n.text(if invalid {
+                TextKey::UtcOffsetInvalid
+            } else {
+                TextKey::UtcOffsetHover
+            }));
+        });
+    });
+}
+
+/// Export name of an axis; `default` is shown as the hint and used while `name` is empty.
+pub(super) fn render_axis_name_row(
+    ui: &mut egui::Ui,
+    i18n: I18n,
+    default: &str,
+    name: &mut String,
+) {
+    let hover = i18n.text(TextKey::AxisNameHover);
+    ui.horizontal(|ui| {
+        ui.label(i18n.text(TextKey::AxisName)).on_hover_text(hover);
+        ui.add(
+            TextEdit::singleline(name)
+                .hint_text(default)
+                .desired_width(140.0),
+        )
+        .on_hover_text(hover);
+    });
+}
+
 pub(super) struct CalRowResult {
     pub(super) value_rect: Option<Rect>,
     pub(super) pick_rect: Option<Rect>,
@@ -1,9 +1,12 @@
 use super::super::common::{side_section_card_collapsible, toggle_switch};
 use super::super::icons;
-use super::axis_input::sanitize_axis_text;
+use super::axis_input::{render_axis_name_row, render_time_input_rows, sanitize_axis_text};
 use crate::app::{AxisCalUi, AxisValueField, CurcatApp, PickMode, safe_usize_to_f32};
 use crate::i18n::{TextKey, UiLanguage};
-use crate::types::{AngleDirection, AngleUnit, AxisUnit, AxisValue, CoordSystem, ScaleKind};
+use crate::profiling::profile_function;
+use crate::types::{
+    AngleDirection, AngleUnit, AxisUnit, AxisValue, CoordSystem, FloatFormat, ScaleKind,
+};
 use egui::containers::menu::MenuButton;
 use egui::{Color32, Pos2, Rect, RichText};
 
@@ -89,6 +92,65 @@ impl CalibrationQuadrant {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CartesianAxisKind {
+    X,
+    Y,
+    /// Second value scale of a dual-axis chart.
+    SecondaryY,
+}
+
+impl CartesianAxisKind {
+    const fn endpoint_names(self) -> (&'static str, &'static str) {
+        match self {
+            Self::X => ("X1", "X2"),
+            Self::Y => ("Y1", "Y2"),
+            Self::SecondaryY => ("Y'1", "Y'2"),
+        }
+    }
+
+    const fn pick_modes(self) -> (PickMode, PickMode) {
+        match self {
+            Self::X => (PickMode::X1, PickMode::X2),
+            Self::Y => (PickMode::Y1, PickMode::Y2),
+            Self::SecondaryY => (PickMode::SecondaryY1, PickMode::SecondaryY2),
+        }
+    }
+
+    const fn value_fields(self) -> (AxisValueField, AxisValueField) {
+        match self {
+            Self::X => (AxisValueField::X1, AxisValueField::X2),
+            Self::Y => (AxisValueField::Y1, AxisValueField::Y2),
+            Self::SecondaryY => (AxisValueField::SecondaryY1, AxisValueField::SecondaryY2),
+        }
+    }
+
+    /// Export column name used while the axis has no title of its own.
+    const fn default_name(self) -> &'static str {
+        match self {
+            Self::X => "x",
+            Self::Y => "y",
+            Self::SecondaryY => "y2",
+        }
+    }
+
+    const fn title_key(self) -> TextKey {
+        match self {
+            Self::X => TextKey::XAxis,
+            Self::Y => TextKey::YAxis,
+            Self::SecondaryY => TextKey::SecondaryYAxis,
+        }
+    }
+
+    const fn hover_key(self) -> TextKey {
+        match self {
+            Self::X => TextKey::XAxisCalibrationHover,
+            Self::Y => TextKey::YAxisCalibrationHover,
+            Self::SecondaryY => TextKey::SecondaryYAxisCalibrationHover,
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 enum PolarAxisKind {
     Radius,
@@ -120,7 +182,7 @@ impl PolarAxisKind {
     }
 }
 
-const fn axis_unit_label(lang: UiLanguage, unit: AxisUnit) -> &'static str {
+pub(super) const fn axis_unit_label(lang: UiLanguage, unit: AxisUnit) -> &'static str {
     match (lang, unit) {
         (UiLanguage::En, AxisUnit::Float) => "Float",
         (UiLanguage::En, AxisUnit::DateTime) => "DateTime",
@@ -129,7 +191,7 @@ const fn axis_unit_label(lang: UiLanguage, unit: AxisUnit) -> &'static str {
     }
 }
 
-const fn scale_kind_label(lang: UiLanguage, scale: ScaleKind) -> &'static str {
+pub(super) const fn scale_kind_label(lang: UiLanguage, scale: ScaleKind) -> &'static str {
     match (lang, scale) {
         (UiLanguage::En, ScaleKind::Linear) => "Linear",
         (UiLanguage::En, ScaleKind::Log10) => "Log10",
@@ -175,7 +237,11 @@ impl CalibrationUiState {
 impl CurcatApp {
     #[allow(clippy::too_many_lines)]
     pub(crate) fn ui_side_calibration(&mut self, ui: &mut egui::Ui) {
+        profile_function!();
         let i18n = self.i18n();
+        // Fit, statistics, crossings and export only read the points, so view-only mode
+        // leaves just those sections usable.
+        let editable = !self.view_only();
         ui.spacing_mut().item_spacing.y = 6.0;
         ui.add_space(2.0);
         side_section_card_collapsible(
@@ -183,7 +249,17 @@ impl CurcatApp {
             "side_section_point_input",
             i18n.text(TextKey::PointInput),
             |ui| {
-                self.ui_point_input_section(ui);
+                ui.add_enabled_ui(editable, |ui| self.ui_point_input_section(ui));
+            },
+        );
+        ui.add_space(10.0);
+
+        side_section_card_collapsible(
+            ui,
+            "side_section_curves",
+            i18n.text(TextKey::Curves),
+            |ui| {
+                ui.add_enabled_ui(editable, |ui| self.ui_curves_section(ui));
             },
         );
         ui.add_space(10.0);
@@ -193,83 +269,199 @@ impl CurcatApp {
             "side_section_calibration",
             i18n.text(TextKey::Calibration),
             |ui| {
-                ui.horizontal(|ui| {
-                    ui.label(i18n.text(TextKey::CoordinateSystem))
-                        .on_hover_text(i18n.text(TextKey::CoordinateSystemHover));
-                    let mut system = self.calibration.coord_system;
-                    let resp = egui::ComboBox::from_id_salt("coord_system_combo")
-                        .selected_text(match system {
-                            CoordSystem::Cartesian => i18n.text(TextKey::Cartesian),
-                            CoordSystem::Polar => i18n.text(TextKey::Polar),
-                        })
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(
-                                &mut system,
-                                CoordSystem::Cartesian,
-                                i18n.text(TextKey::Cartesian),
+                ui.add_enabled_ui(editable, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(i18n.text(TextKey::CoordinateSystem))
+                            .on_hover_text(i18n.text(TextKey::CoordinateSystemHover));
+                        let mut system = self.calibration.coord_system;
+                        let resp = egui::ComboBox::from_id_salt("coord_system_combo")
+                            .selected_text(match system {
+                                CoordSystem::Cartesian => i18n.text(TextKey::Cartesian),
+                                CoordSystem::Polar => i18n.text(TextKey::Polar),
+                                CoordSystem::Affine => i18n.text(TextKey::Affine),
+                                CoordSystem::Smith => i18n.text(TextKey::SmithChart),
+                            })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut system,
+                                    CoordSystem::Cartesian,
+                                    i18n.text(TextKey::Cartesian),
+                                );
+                                ui.selectable_value(
+                                    &mut system,
+                                    CoordSystem::Polar,
+                                    i18n.text(TextKey::Polar),
+                                );
+                                ui.selectable_value(
+                                    &mut system,
+                                    CoordSystem::Affine,
+                                    i18n.text(TextKey::Affine),
+                                );
+                                ui.selectable_value(
+                                    &mut system,
+                                    CoordSystem::Smith,
+                                    i18n.text(TextKey::SmithChart),
+                                );
+                            });
+                        resp.response
+                            .on_hover_text(i18n.text(TextKey::CoordSystemForCalibrationExport));
+                        if system != self.calibration.coord_system {
+                            self.calibration.coord_system = system;
+                            self.mark_points_dirty();
+                            self.calibration.pick_mode = PickMode::None;
+                            self.calibration.pending_value_focus = None;
+                            self.calibration.auto_review = None;
+                            self.calibration.recalibration_review = None;
+                            self.clear_calibration_snap_runtime();
+                            self.set_status(match system {
+                                CoordSystem::Cartesian => match self.ui.language {
+                                    UiLanguage::En => "Switched to Cartesian calibration.",
+                                    UiLanguage::Ru => "Переключено на декартову калибровку.",
+                                },
+                                CoordSystem::Polar => match self.ui.language {
+                                    UiLanguage::En => "Switched to Polar calibration.",
+                                    UiLanguage::Ru => "Переключено на полярную калибровку.",
+                                },
+                                CoordSystem::Affine => match self.ui.language {
+                                    UiLanguage::En => "Switched to affine calibration.",
+                                    UiLanguage::Ru => "Переключено на аффинную калибровку.",
+                                },
+                                CoordSystem::Smith => match self.ui.language {
+                                    UiLanguage::En => "Switched to Smith chart calibration.",
+                                    UiLanguage::Ru => {
+                                        "Переключено на калибровку по диаграмме Смита."
+                                    }
+                                },
+                            });
+                        }
+                    });
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        let cartesian =
+                            matches!(self.calibration.coord_system, CoordSystem::Cartesian);
+                        self.ui_calibration_snap_menu(ui, cartesian);
+                        ui.add_space(8.0);
+                        let has_image = self.image.image.is_some();
+                        if cartesian {
+                            self.ui_quadrant_preset_menu(
+                                ui,
+                                CalibrationPresetKind::Unit,
+                                has_image,
                             );
-                            ui.selectable_value(
-                                &mut system,
-                                CoordSystem::Polar,
-                                i18n.text(TextKey::Polar),
+                            self.ui_quadrant_preset_menu(
+                                ui,
+                                CalibrationPresetKind::Pixels,
+                                has_image,
                             );
-                        });
-                    resp.response
-                        .on_hover_text(i18n.text(TextKey::CoordSystemForCalibrationExport));
-                    if system != self.calibration.coord_system {
-                        self.calibration.coord_system = system;
-                        self.mark_points_dirty();
-                        self.calibration.pick_mode = PickMode::None;
-                        self.calibration.pending_value_focus = None;
-                        self.clear_calibration_snap_runtime();
-                        self.set_status(match system {
-                            CoordSystem::Cartesian => match self.ui.language {
-                                UiLanguage::En => "Switched to Cartesian calibration.",
-                                UiLanguage::Ru => "Переключено на декартову калибровку.",
-                            },
-                            CoordSystem::Polar => match self.ui.language {
-                                UiLanguage::En => "Switched to Polar calibration.",
-                                UiLanguage::Ru => "Переключено на полярную калибровку.",
-                            },
-                        });
-                    }
-                });
-                ui.separator();
-                ui.horizontal(|ui| {
-                    let cartesian = matches!(self.calibration.coord_system, CoordSystem::Cartesian);
-                    self.ui_calibration_snap_menu(ui, cartesian);
-                    ui.add_space(8.0);
-                    let has_image = self.image.image.is_some();
-                    if cartesian {
-                        self.ui_quadrant_preset_menu(ui, CalibrationPresetKind::Unit, has_image);
-                        self.ui_quadrant_preset_menu(ui, CalibrationPresetKind::Pixels, has_image);
-                    }
-                });
-                ui.separator();
-
-                match self.calibration.coord_system {
-                    CoordSystem::Cartesian => {
-                        self.axis_cal_group(ui, true);
+                            ui.add_space(8.0);
+                            let resp = ui
+                                .add_enabled(
+                                    has_image,
+                                    egui::Button::new(i18n.text(TextKey::AutoCalibrate)),
+                                )
+                                .on_hover_text(i18n.text(TextKey::AutoCalibrateHover));
+                            if resp.clicked() {
+                                self.auto_calibrate_from_ticks();
+                            }
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(i18n.text(TextKey::CopyCalibration))
+                            .on_hover_text(i18n.text(TextKey::CopyCalibrationHover))
+                            .clicked()
+                        {
+                            self.copy_calibration_to_clipboard(ui.ctx());
+                        }
+                        if ui
+                            .button(i18n.text(TextKey::PasteCalibration))
+                            .on_hover_text(i18n.text(TextKey::PasteCalibrationHover))
+                            .clicked()
+                        {
+                            self.paste_calibration_from_clipboard();
+                        }
+                        if matches!(self.calibration.coord_system, CoordSystem::Cartesian) {
+                            let resp = ui
+                                .add_enabled(
+                                    self.calibration.cal_x.mapping().is_some(),
+                                    egui::Button::new(i18n.text(TextKey::CopyXToY)),
+                                )
+                                .on_hover_text(i18n.text(TextKey::CopyXToYHover));
+                            if resp.clicked() {
+                                self.copy_x_calibration_to_y();
+                            }
+                        }
+                    });
+                    ui.separator();
+                    if self.calibration.auto_review.is_some() {
+                        self.ui_auto_calibration_review(ui);
                         ui.separator();
-                        self.axis_cal_group(ui, false);
                     }
-                    CoordSystem::Polar => {
-                        self.ui_polar_origin_row(ui);
-                        ui.separator();
-                        self.polar_axis_group(ui, PolarAxisKind::Radius);
+                    if self.calibration.recalibration_review.is_some() {
+                        self.ui_recalibration_review(ui);
                         ui.separator();
-                        self.polar_axis_group(ui, PolarAxisKind::Angle);
                     }
-                }
 
-                ui.separator();
-                ui.horizontal(|ui| {
-                    toggle_switch(ui, &mut self.calibration.show_calibration_segments)
-                        .on_hover_text(i18n.text(TextKey::ShowCalibrationOverlayHover));
-                    ui.add_space(4.0);
-                    ui.label(i18n.text(TextKey::ShowCalibrationOverlay))
-                        .on_hover_text(i18n.text(TextKey::ShowCalibrationOverlayHover));
+                    match self.calibration.coord_system {
+                        CoordSystem::Cartesian => {
+                            self.axis_cal_group(ui, CartesianAxisKind::X);
+                            ui.separator();
+                            self.axis_cal_group(ui, CartesianAxisKind::Y);
+                            ui.separator();
+                            self.axis_cal_group(ui, CartesianAxisKind::SecondaryY);
+                        }
+                        CoordSystem::Polar => {
+                            self.ui_polar_origin_row(ui);
+                            ui.separator();
+                            self.polar_axis_group(ui, PolarAxisKind::Radius);
+                            ui.separator();
+                            self.polar_axis_group(ui, PolarAxisKind::Angle);
+                        }
+                        CoordSystem::Affine => self.ui_affine_calibration(ui),
+                        CoordSystem::Smith => self.ui_smith_calibration(ui),
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        toggle_switch(ui, &mut self.calibration.show_calibration_segments)
+                            .on_hover_text(i18n.text(TextKey::ShowCalibrationOverlayHover));
+                        ui.add_space(4.0);
+                        ui.label(i18n.text(TextKey::ShowCalibrationOverlay))
+                            .on_hover_text(i18n.text(TextKey::ShowCalibrationOverlayHover));
+                    });
                 });
+                // Read-only, so it stays legible in view-only mode.
+                self.ui_calibration_report(ui);
+            },
+        );
+        ui.add_space(10.0);
+
+        side_section_card_collapsible(
+            ui,
+            "side_section_fit",
+            i18n.text(TextKey::CurveFitSection),
+            |ui| {
+                self.ui_fit_section(ui);
+            },
+        );
+        ui.add_space(10.0);
+
+        side_section_card_collapsible(
+            ui,
+            "side_section_curve_stats",
+            i18n.text(TextKey::CurveStatsSection),
+            |ui| {
+                self.ui_curve_stats_section(ui);
+            },
+        );
+        ui.add_space(10.0);
+
+        side_section_card_collapsible(
+            ui,
+            "side_section_crossings",
+            i18n.text(TextKey::LevelCrossingsSection),
+            |ui| {
+                self.ui_crossings_section(ui);
             },
         );
         ui.add_space(10.0);
@@ -315,6 +507,101 @@ impl CurcatApp {
         });
     }
 
+    /// Residuals before and after the pending fit to the pasted reference, with keep and revert.
+    fn ui_recalibration_review(&mut self, ui: &mut egui::Ui) {
+        let Some(review) = self.calibration.recalibration_review.as_ref() else {
+            return;
+        };
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::FittedToReference)).strong())
+            .on_hover_text(i18n.text(TextKey::FittedToReferenceHover));
+        ui.label(
+            RichText::new(i18n.format_reference_pairs(review.pairs))
+                .small()
+                .weak(),
+        );
+        for (idx, name) in ["X", "Y"].into_iter().enumerate() {
+            let (before, after) = (review.rms_before[idx], review.rms_after[idx]);
+            let color = if after < before {
+                Color32::GREEN
+            } else {
+                Color32::GRAY
+            };
+            ui.label(
+                RichText::new(format!(
+                    "{name} {}: {} → {}",
+                    i18n.text(TextKey::RmsResidual),
+                    FloatFormat::adaptive(before, before, 3).format_trimmed(before),
+                    FloatFormat::adaptive(before, after, 3).format_trimmed(after),
+                ))
+                .color(color),
+            );
+        }
+        ui.horizontal(|ui| {
+            if ui.button(i18n.text(TextKey::KeepFit)).clicked() {
+                self.keep_recalibration();
+            }
+            if ui
+                .button(i18n.text(TextKey::RevertFit))
+                .on_hover_text(i18n.text(TextKey::RevertFitHover))
+                .clicked()
+            {
+                self.revert_recalibration();
+            }
+        });
+    }
+
+    /// Confidence per axis for the pending tick-label proposal, with accept and discard.
+    fn ui_auto_calibration_review(&mut self, ui: &mut egui::Ui) {
+        let Some(review) = self.calibration.auto_review.as_ref() else {
+            return;
+        };
+        let i18n = self.i18n();
+        let hover = i18n.text(TextKey::ProposedCalibrationHover);
+        ui.label(RichText::new(i18n.text(TextKey::ProposedCalibration)).strong())
+            .on_hover_text(hover);
+        ui.horizontal(|ui| {
+            for (name, confidence) in [("X", review.x_confidence), ("Y", review.y_confidence)] {
+                let (text, color) = confidence.map_or_else(
+                    || {
+                        (
+                            format!("{name}: {}", i18n.text(TextKey::AxisNotFound)),
+                            Color32::GRAY,
+                        )
+                    },
+                    |c| {
+                        let color = if c >= 0.8 {
+                            Color32::GREEN
+                        } else if c >= 0.5 {
+                            Color32::from_rgb(255, 170, 60)
+                        } else {
+                            Color32::from_rgb(255, 110, 110)
+                        };
+                        (format!("{name}: {:.0}%", c * 100.0), color)
+                    },
+                );
+                ui.label(RichText::new(text).color(color))
+                    .on_hover_text(hover);
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui
+                .button(i18n.text(TextKey::AcceptProposal))
+                .on_hover_text(i18n.text(TextKey::AcceptProposalHover))
+                .clicked()
+            {
+                self.accept_auto_calibration();
+            }
+            if ui
+                .button(i18n.text(TextKey::DiscardProposal))
+                .on_hover_text(i18n.text(TextKey::DiscardProposalHover))
+                .clicked()
+            {
+                self.discard_auto_calibration();
+            }
+        });
+    }
+
     fn ui_calibration_snap_toggle(ui: &mut egui::Ui, enabled: &mut bool, label: &str, hover: &str) {
         toggle_switch(ui, enabled).on_hover_text(hover);
         ui.add_space(2.0);
@@ -614,28 +901,44 @@ impl CurcatApp {
         (p1_invalid, p2_invalid)
     }
 
+    const fn cartesian_axis_cal(&self, axis: CartesianAxisKind) -> &AxisCalUi {
+        match axis {
+            CartesianAxisKind::X => &self.calibration.cal_x,
+            CartesianAxisKind::Y => &self.calibration.cal_y,
+            CartesianAxisKind::SecondaryY => &self.calibration.cal_secondary_y,
+        }
+    }
+
+    const fn cartesian_axis_cal_mut(&mut self, axis: CartesianAxisKind) -> &mut AxisCalUi {
+        match axis {
+            CartesianAxisKind::X => &mut self.calibration.cal_x,
+            CartesianAxisKind::Y => &mut self.calibration.cal_y,
+            CartesianAxisKind::SecondaryY => &mut self.calibration.cal_secondary_y,
+        }
+    }
+
+    /// Grid-spacing scale suggestion for `axis`; only the primary cartesian axes get one.
+    fn suggested_axis_scale(&mut self, axis: CartesianAxisKind) -> Option<ScaleKind> {
+        if self.calibration.coord_system != CoordSystem::Cartesian {
+            return None;
+        }
+        let suggestion = self.scale_suggestion();
+        match axis {
+            CartesianAxisKind::X => suggestion.x,
+            CartesianAxisKind::Y => suggestion.y,
+            CartesianAxisKind::SecondaryY => None,
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
-    pub(crate) fn axis_cal_group(&mut self, ui: &mut egui::Ui, is_x: bool) {
-        let (label, p1_mode, p2_mode, p1_name, p2_name) = if is_x {
-            (
-                self.t(TextKey::XAxis),
-                PickMode::X1,
-                PickMode::X2,
-                "X1",
-                "X2",
-            )
-        } else {
-            (
-                self.t(TextKey::YAxis),
-                PickMode::Y1,
-                PickMode::Y2,
-                "Y1",
-                "Y2",
-            )
-        };
+    fn axis_cal_group(&mut self, ui: &mut egui::Ui, axis: CartesianAxisKind) {
+        let label = self.t(axis.title_key());
+        let (p1_mode, p2_mode) = axis.pick_modes();
+        let (p1_name, p2_name) = axis.endpoint_names();
+        let (p1_field, p2_field) = axis.value_fields();
 
         let collapsing = egui::CollapsingHeader::new(label)
-            .default_open(true)
+            .default_open(axis != CartesianAxisKind::SecondaryY)
             .show(ui, |ui| {
                 ui.push_id(label, |ui| {
                     let mut ui_state =
@@ -648,28 +951,35 @@ impl CurcatApp {
                         let scale_label = self.t(TextKey::Scale);
                         let scale_hover = self.t(TextKey::ScaleHover);
                         let axis_scale_hover = self.t(TextKey::AxisScaleHover);
-                        let cal = if is_x {
-                            &mut self.calibration.cal_x
-                        } else {
-                            &mut self.calibration.cal_y
+                        let suggests_label = self.t(TextKey::GridSuggestsScale);
+                        let suggests_hover = self.t(TextKey::GridSuggestsScaleHover);
+                        let language = self.ui.language;
+                        let i18n = self.i18n();
+                        let suggested = self.suggested_axis_scale(axis);
+                        let name = match axis {
+                            CartesianAxisKind::X => &mut self.project.axis_titles[0],
+                            CartesianAxisKind::Y => &mut self.project.axis_titles[1],
+                            CartesianAxisKind::SecondaryY => &mut self.project.secondary_y_title,
                         };
+                        render_axis_name_row(ui, i18n, axis.default_name(), name);
+                        let cal = self.cartesian_axis_cal_mut(axis);
                         let previous_unit = cal.unit;
                         ui.horizontal(|ui| {
                             ui.label(unit_label).on_hover_text(unit_hover);
                             let mut unit = cal.unit;
                             let unit_ir =
                                 egui::ComboBox::from_id_salt(format!("{label}_unit_combo"))
-                                    .selected_text(axis_unit_label(self.ui.language, unit))
+                                    .selected_text(axis_unit_label(language, unit))
                                     .show_ui(ui, |ui| {
                                         ui.selectable_value(
                                             &mut unit,
                                             AxisUnit::Float,
-                                            axis_unit_label(self.ui.language, AxisUnit::Float),
+                                            axis_unit_label(language, AxisUnit::Float),
                                         );
                                         ui.selectable_value(
                                             &mut unit,
                                             AxisUnit::DateTime,
-                                            axis_unit_label(self.ui.language, AxisUnit::DateTime),
+                                            axis_unit_label(language, AxisUnit::DateTime),
                                         );
                                     });
                             unit_ir.response.on_hover_text(axis_value_type_hover);
@@ -681,25 +991,37 @@ impl CurcatApp {
                             let allow_log = matches!(cal.unit, AxisUnit::Float);
                             let scale_ir =
                                 egui::ComboBox::from_id_salt(format!("{label}_scale_combo"))
-                                    .selected_text(scale_kind_label(self.ui.language, scale))
+                                    .selected_text(scale_kind_label(language, scale))
                                     .show_ui(ui, |ui| {
                                         ui.selectable_value(
                                             &mut scale,
                                             ScaleKind::Linear,
-                                            scale_kind_label(self.ui.language, ScaleKind::Linear),
+                                            scale_kind_label(language, ScaleKind::Linear),
                                         );
                                         if allow_log {
                                             ui.selectable_value(
                                                 &mut scale,
                                                 ScaleKind::Log10,
-                                                scale_kind_label(
-                                                    self.ui.language,
-                                                    ScaleKind::Log10,
-                                                ),
+                                                scale_kind_label(language, ScaleKind::Log10),
                                             );
                                         }
                                     });
                             scale_ir.response.on_hover_text(axis_scale_hover);
+                            if let Some(suggested) = suggested.filter(|&suggested| {
+                                suggested != scale && (allow_log || suggested == ScaleKind::Linear)
+                            }) {
+                                let text = format!(
+                                    "{suggests_label}: {}",
+                                    scale_kind_label(language, suggested)
+                                );
+                                if ui
+                                    .small_button(text)
+                                    .on_hover_text(suggests_hover)
+                                    .clicked()
+                                {
+                                    scale = suggested;
+                                }
+                            }
                             if !allow_log && matches!(scale, ScaleKind::Log10) {
                                 scale = ScaleKind::Linear;
                             }
@@ -709,23 +1031,18 @@ impl CurcatApp {
                             sanitize_axis_text(&mut cal.v1_text, cal.unit);
                             sanitize_axis_text(&mut cal.v2_text, cal.unit);
                         }
+                        if cal.unit == AxisUnit::DateTime {
+                            render_time_input_rows(ui, i18n, label, &mut cal.time);
+                        }
 
                         let _ = Self::render_axis_rows(
                             ui,
-                            self.ui.language,
+                            language,
                             cal,
                             p1_name,
                             p2_name,
-                            if is_x {
-                                AxisValueField::X1
-                            } else {
-                                AxisValueField::Y1
-                            },
-                            if is_x {
-                                AxisValueField::X2
-                            } else {
-                                AxisValueField::Y2
-                            },
+                            p1_field,
+                            p2_field,
                             p1_mode,
                             p2_mode,
                             &mut ui_state,
@@ -733,6 +1050,9 @@ impl CurcatApp {
 
                         mapping_ready = cal.mapping().is_some();
                     }
+                    self.ui_axis_endpoint_swap(ui, axis);
+                    self.ui_axis_inverted_toggle(ui, axis);
+                    self.ui_axis_direction_warning(ui, axis);
                     self.finish_calibration_panel(
                         ui,
                         ui_state,
@@ -744,10 +1064,122 @@ impl CurcatApp {
                     );
                 });
             });
-        collapsing.header_response.on_hover_text(if is_x {
-            self.t(TextKey::XAxisCalibrationHover)
-        } else {
-            self.t(TextKey::YAxisCalibrationHover)
+        collapsing
+            .header_response
+            .on_hover_text(self.t(axis.hover_key()));
+    }
+
+    /// Reuse the X calibration for Y, as on plots with equal scales on both axes.
+    ///
+    /// Picked Y points are kept; missing ones are placed at X1 and at the X1→X2
+    /// span turned upward, so a square plot is calibrated in one step.
+    pub(crate) fn copy_x_calibration_to_y(&mut self) {
+        let cal_x = &self.calibration.cal_x;
+        let (Some(p1), Some(p2)) = (cal_x.p1, cal_x.p2) else {
+            return;
+        };
+        let cal_y = &mut self.calibration.cal_y;
+        cal_y.unit = cal_x.unit;
+        cal_y.scale = cal_x.scale;
+        cal_y.v1_text.clone_from(&cal_x.v1_text);
+        cal_y.v2_text.clone_from(&cal_x.v2_text);
+        if cal_y.p1.is_none() || cal_y.p2.is_none() {
+            let span = p2 - p1;
+            cal_y.p1 = Some(p1);
+            cal_y.p2 = Some(p1 + egui::vec2(span.y, -span.x));
+        }
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "Copied X calibration to Y.",
+            UiLanguage::Ru => "Калибровка X скопирована в Y.",
+        });
+    }
+
+    fn ui_axis_endpoint_swap(&mut self, ui: &mut egui::Ui, axis: CartesianAxisKind) {
+        let i18n = self.i18n();
+        let (p1, p2) = axis.endpoint_names();
+        let cal = self.cartesian_axis_cal_mut(axis);
+        let clicked = ui
+            .add_enabled(
+                cal.p1.is_some() || cal.p2.is_some(),
+                egui::Button::new(i18n.format_swap_endpoints(p1, p2)).small(),
+            )
+            .on_hover_text(i18n.text(TextKey::SwapEndpointsHover))
+            .clicked();
+        if !clicked {
+            return;
+        }
+        cal.swap_endpoints();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Swapped {p1} and {p2}."),
+            UiLanguage::Ru => format!("Точки {p1} и {p2} поменяны местами."),
+        });
+    }
+
+    /// The "Inverted" flag, with an arrow showing which way the entered values grow.
+    fn ui_axis_inverted_toggle(&mut self, ui: &mut egui::Ui, axis: CartesianAxisKind) {
+        let i18n = self.i18n();
+        let is_x = axis == CartesianAxisKind::X;
+        let cal = self.cartesian_axis_cal_mut(axis);
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut cal.inverted, i18n.text(TextKey::InvertedAxis))
+                .on_hover_text(i18n.text(TextKey::InvertedAxisHover));
+            if let Some(reversed) = cal.runs_reversed(is_x) {
+                let arrow = match (is_x, reversed) {
+                    (true, false) => "→",
+                    (true, true) => "←",
+                    (false, false) => "↑",
+                    (false, true) => "↓",
+                };
+                let text = format!("{} {arrow}", i18n.text(TextKey::ValuesGrow));
+                ui.label(RichText::new(text).small().weak());
+            }
+        });
+    }
+
+    /// Warn about an axis whose values run against its declared direction and offer to swap
+    /// them.
+    fn ui_axis_direction_warning(&mut self, ui: &mut egui::Ui, axis: CartesianAxisKind) {
+        let is_x = axis == CartesianAxisKind::X;
+        let cal = self.cartesian_axis_cal(axis);
+        if !cal.direction_mismatch(is_x) {
+            return;
+        }
+        let (warning, hover) = match (cal.inverted, is_x) {
+            (false, true) => (TextKey::XAxisLooksInverted, TextKey::AxisLooksInvertedHover),
+            (false, false) => (TextKey::YAxisLooksInverted, TextKey::AxisLooksInvertedHover),
+            (true, true) => (
+                TextKey::XAxisMarkedInverted,
+                TextKey::AxisMarkedInvertedHover,
+            ),
+            (true, false) => (
+                TextKey::YAxisMarkedInverted,
+                TextKey::AxisMarkedInvertedHover,
+            ),
+        };
+        let i18n = self.i18n();
+        let mut swap = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new(i18n.text(warning)).color(Color32::from_rgb(255, 170, 60)))
+                .on_hover_text(i18n.text(hover));
+            swap = ui
+                .button(i18n.text(TextKey::SwapAxisValues))
+                .on_hover_text(i18n.text(TextKey::SwapAxisValuesHover))
+                .clicked();
+        });
+        if swap {
+            self.swap_axis_values(axis);
+        }
+    }
+
+    fn swap_axis_values(&mut self, axis: CartesianAxisKind) {
+        let (p1, p2) = axis.endpoint_names();
+        self.cartesian_axis_cal_mut(axis).swap_values();
+        self.mark_points_dirty();
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Swapped {p1} and {p2} values."),
+            UiLanguage::Ru => format!("Значения {p1} и {p2} поменяны местами."),
         });
     }
 
@@ -0,0 +1,108 @@
+use super::calibration::scale_kind_label;
+use crate::app::CurcatApp;
+use crate::calibration_report::{AxisReport, CalibrationReport, ReportValue};
+use crate::i18n::{I18n, TextKey, UiLanguage};
+use crate::types::ScaleKind;
+use egui::{Color32, RichText};
+
+const WARN_COLOR: Color32 = Color32::from_rgb(230, 160, 60);
+
+impl CurcatApp {
+    /// Quality figures of the finished Cartesian calibration; nothing until both axes are set.
+    pub(super) fn ui_calibration_report(&self, ui: &mut egui::Ui) {
+        let Some(report) = self.calibration_report() else {
+            return;
+        };
+        let i18n = self.i18n();
+        ui.separator();
+        let collapsing = egui::CollapsingHeader::new(i18n.text(TextKey::CalibrationQuality))
+            .default_open(true)
+            .show(ui, |ui| ui_report_grid(ui, self.ui.language, &report));
+        collapsing
+            .header_response
+            .on_hover_text(i18n.text(TextKey::CalibrationQualityHover));
+    }
+}
+
+fn ui_report_grid(ui: &mut egui::Ui, lang: UiLanguage, report: &CalibrationReport) {
+    let i18n = I18n::new(lang);
+    let angle = RichText::new(format!("{}°", number(report.axis_angle_deg))).monospace();
+    let skewed = report.is_skewed();
+    ui.horizontal(|ui| {
+        ui.label(i18n.text(TextKey::AxisAngle))
+            .on_hover_text(i18n.text(TextKey::AxisAngleHover));
+        ui.label(if skewed {
+            angle.color(WARN_COLOR)
+        } else {
+            angle
+        });
+    });
+    if skewed {
+        ui.label(
+            RichText::new(i18n.text(TextKey::AxisAngleSkewed))
+                .small()
+                .color(WARN_COLOR),
+        );
+    }
+    egui::Grid::new("calibration_report_grid")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label(RichText::new("X").strong());
+            ui.label(RichText::new("Y").strong());
+            ui.end_row();
+
+            ui.label(i18n.text(TextKey::QualityScale));
+            for axis in [&report.x, &report.y] {
+                scale_cell(ui, lang, axis);
+            }
+            ui.end_row();
+
+            ui.label(i18n.text(TextKey::QualityResolution))
+                .on_hover_text(i18n.text(TextKey::QualityResolutionHover));
+            for axis in [&report.x, &report.y] {
+                let unit = match axis.scale {
+                    ScaleKind::Linear => i18n.text(TextKey::PixelsPerUnit),
+                    ScaleKind::Log10 => i18n.text(TextKey::PixelsPerDecade),
+                };
+                ui.label(
+                    RichText::new(format!("{} {unit}", number(axis.pixels_per_unit))).monospace(),
+                );
+            }
+            ui.end_row();
+
+            ui.label(i18n.text(TextKey::QualityError))
+                .on_hover_text(i18n.text(TextKey::QualityErrorHover));
+            for axis in [&report.x, &report.y] {
+                let error = match axis.scale {
+                    ScaleKind::Linear => format!("±{}", number(axis.quantization_error)),
+                    ScaleKind::Log10 => format!("±{} %", number(axis.quantization_error * 100.0)),
+                };
+                ui.label(RichText::new(error).monospace());
+            }
+            ui.end_row();
+        });
+}
+
+/// Scale of `axis`, flagged when the grid lines suggest the other one.
+fn scale_cell(ui: &mut egui::Ui, lang: UiLanguage, axis: &AxisReport) {
+    let label = scale_kind_label(lang, axis.scale);
+    match axis.grid_scale {
+        Some(grid) if axis.scale_disagrees() => {
+            ui.label(RichText::new(format!("{label} ⚠")).color(WARN_COLOR))
+                .on_hover_text(format!(
+                    "{} {}",
+                    I18n::new(lang).text(TextKey::GridSuggestsScale),
+                    scale_kind_label(lang, grid)
+                ));
+        }
+        _ => {
+            ui.label(label);
+        }
+    }
+}
+
+fn number(value: f64) -> String {
+    ReportValue::Number(value).to_text()
+}
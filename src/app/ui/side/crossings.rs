@@ -0,0 +1,76 @@
+use crate::app::CurcatApp;
+use crate::app::ui::stats::AxisKind;
+use crate::i18n::TextKey;
+use crate::types::{AxisUnit, AxisValue, CoordSystem};
+use egui::RichText;
+
+impl CurcatApp {
+    /// Level editor, marker toggle and the active curve's crossings of the level.
+    pub(crate) fn ui_crossings_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        self.ui_crossing_level(ui);
+        ui.checkbox(
+            &mut self.export.show_crossings,
+            i18n.text(TextKey::ShowCrossings),
+        )
+        .on_hover_text(i18n.text(TextKey::ShowCrossingsHover));
+
+        if !self.calibration_ready() {
+            ui.label(RichText::new(i18n.text(TextKey::FitNeedsCalibration)).weak());
+            return;
+        }
+        let (x_mapping, y_mapping) = self.cartesian_mappings();
+        let polar_mapping = self.polar_mapping();
+        self.ensure_point_numeric_cache(
+            self.calibration.coord_system,
+            x_mapping.as_ref(),
+            y_mapping.as_ref(),
+            polar_mapping.as_ref(),
+        );
+        let x_unit = match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
+                x_mapping.map_or(AxisUnit::Float, |mapping| mapping.unit)
+            }
+            CoordSystem::Polar | CoordSystem::Smith => AxisUnit::Float,
+        };
+        let crossings = self.curve_level_crossings(&self.points.points);
+        if crossings.is_empty() {
+            ui.label(RichText::new(i18n.text(TextKey::NoCrossings)).weak());
+            return;
+        }
+        egui::Grid::new("level_crossings_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (idx, crossing) in crossings.iter().enumerate() {
+                    let x = AxisValue::from_scalar_seconds(x_unit, crossing.x)
+                        .map_or_else(|| format!("{:.6}", crossing.x), |v| v.format());
+                    let direction = if crossing.rising {
+                        TextKey::CrossingRising
+                    } else {
+                        TextKey::CrossingFalling
+                    };
+                    ui.label(RichText::new(format!("{}", idx + 1)).weak());
+                    ui.label(RichText::new(x).monospace());
+                    ui.label(i18n.text(direction));
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Drag editor of the crossing level, stepping by a fraction of the points' y range.
+    pub(crate) fn ui_crossing_level(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let speed = self
+            .axis_numeric_range(AxisKind::Y)
+            .map(|range| range.span() / 500.0)
+            .filter(|speed| speed.is_finite() && *speed > 0.0)
+            .unwrap_or(0.01);
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::CrossingLevel))
+                .on_hover_text(i18n.text(TextKey::CrossingLevelHover));
+            ui.add(egui::DragValue::new(&mut self.export.crossing_level).speed(speed))
+                .on_hover_text(i18n.text(TextKey::CrossingLevelHover));
+        });
+    }
+}
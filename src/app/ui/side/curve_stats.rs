@@ -0,0 +1,141 @@
+use super::fit::format_coefficient;
+use crate::app::{CurcatApp, CurveAnalysis};
+use crate::i18n::TextKey;
+use crate::types::{AxisUnit, AxisValue, CoordSystem};
+use egui::RichText;
+
+/// `value` in `unit`: dates and durations as such, plain numbers via [`format_coefficient`].
+fn format_axis_value(unit: AxisUnit, value: f64) -> String {
+    match unit {
+        AxisUnit::Float => format_coefficient(value),
+        AxisUnit::DateTime => AxisValue::from_scalar_seconds(unit, value)
+            .map_or_else(|| format_coefficient(value), |v| v.format()),
+    }
+}
+
+impl CurcatApp {
+    /// Extremes, mean, arc length, area between x bounds and level crossings of the active curve.
+    pub(crate) fn ui_curve_stats_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        if !self.calibration_ready() {
+            ui.label(RichText::new(i18n.text(TextKey::StatsNeedCalibration)).weak());
+            return;
+        }
+        let (x_mapping, y_mapping) = self.cartesian_mappings();
+        let polar_mapping = self.polar_mapping();
+        self.ensure_point_numeric_cache(
+            self.calibration.coord_system,
+            x_mapping.as_ref(),
+            y_mapping.as_ref(),
+            polar_mapping.as_ref(),
+        );
+        let (x_unit, y_unit) = match self.calibration.coord_system {
+            CoordSystem::Cartesian | CoordSystem::Affine => {
+                let (x, y) = self.active_curve_mappings();
+                (
+                    x.map_or(AxisUnit::Float, |mapping| mapping.unit),
+                    y.map_or(AxisUnit::Float, |mapping| mapping.unit),
+                )
+            }
+            CoordSystem::Polar | CoordSystem::Smith => (AxisUnit::Float, AxisUnit::Float),
+        };
+        let Some(analysis) = self.curve_analysis(&self.points.points) else {
+            ui.label(RichText::new(i18n.text(TextKey::AddPointsToSeeStats)).weak());
+            return;
+        };
+        let stats = analysis.stats;
+        egui::Grid::new("curve_stats_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (label, point) in [
+                    (TextKey::CurveMinimum, stats.min),
+                    (TextKey::CurveMaximum, stats.max),
+                ] {
+                    ui.label(i18n.text(label));
+                    ui.label(
+                        RichText::new(format!(
+                            "{} @ {}",
+                            format_axis_value(y_unit, point.y),
+                            format_axis_value(x_unit, point.x)
+                        ))
+                        .monospace(),
+                    );
+                    ui.end_row();
+                }
+                ui.label(i18n.text(TextKey::CurveMean))
+                    .on_hover_text(i18n.text(TextKey::CurveMeanHover));
+                ui.label(RichText::new(format_axis_value(y_unit, stats.mean)).monospace());
+                ui.end_row();
+                ui.label(i18n.text(TextKey::ArcLength))
+                    .on_hover_text(i18n.text(TextKey::ArcLengthHover));
+                ui.label(RichText::new(format_coefficient(stats.arc_length)).monospace());
+                ui.end_row();
+            });
+
+        ui.add_space(4.0);
+        self.ui_area_under_curve(ui, &analysis);
+
+        // The level is the one of the level crossings section.
+        let crossings = self.curve_level_crossings(&self.points.points);
+        let text = if crossings.is_empty() {
+            RichText::new(i18n.text(TextKey::NoCrossings)).weak()
+        } else {
+            let xs: Vec<String> = crossings
+                .iter()
+                .map(|c| format_axis_value(x_unit, c.x))
+                .collect();
+            RichText::new(format!(
+                "{} {}: {}",
+                i18n.text(TextKey::CrossingsAtLevel),
+                format_axis_value(y_unit, self.export.crossing_level),
+                xs.join(", ")
+            ))
+        };
+        ui.label(text)
+            .on_hover_text(i18n.text(TextKey::CrossingsAtLevelHover));
+    }
+
+    /// Editors of the x bounds and the area under the curve between them.
+    fn ui_area_under_curve(&mut self, ui: &mut egui::Ui, analysis: &CurveAnalysis) {
+        let i18n = self.i18n();
+        let [mut from, mut to] = analysis.bounds;
+        let span = (to - from).abs();
+        let speed = if span.is_finite() && span > 0.0 {
+            span / 500.0
+        } else {
+            0.01
+        };
+        let mut bounds_changed = false;
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::AreaBounds))
+                .on_hover_text(i18n.text(TextKey::AreaBoundsHover));
+            bounds_changed |= ui
+                .add(egui::DragValue::new(&mut from).speed(speed))
+                .changed();
+            ui.label("–");
+            bounds_changed |= ui.add(egui::DragValue::new(&mut to).speed(speed)).changed();
+            if ui
+                .add_enabled(
+                    self.export.area_bounds.is_some(),
+                    egui::Button::new(i18n.text(TextKey::WholeCurve)),
+                )
+                .on_hover_text(i18n.text(TextKey::WholeCurveHover))
+                .clicked()
+            {
+                self.export.area_bounds = None;
+            }
+        });
+        if bounds_changed {
+            self.export.area_bounds = Some([from, to]);
+        }
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::AreaUnderCurve))
+                .on_hover_text(i18n.text(TextKey::AreaUnderCurveHover));
+            match analysis.area {
+                Some(area) => ui.label(RichText::new(format_coefficient(area)).monospace()),
+                None => ui.label(RichText::new(i18n.text(TextKey::AreaOutsideCurve)).weak()),
+            };
+        });
+    }
+}
@@ -0,0 +1,247 @@
+use super::super::icons;
+use crate::app::{CurcatApp, PickMode};
+use crate::export::PARAMETER_COLUMN_HEADER;
+use crate::i18n::TextKey;
+use crate::types::{CoordSystem, YAxis};
+use egui::{Color32, RichText};
+
+enum CurveAction {
+    Select(usize),
+    Recolor(usize, Color32),
+    SetYAxis(usize, YAxis),
+    ToggleLock(usize),
+    Remove(usize),
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_curves_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let mut action = None;
+        let can_remove = self.points.curves.len() > 1;
+        let cartesian = self.calibration.coord_system == CoordSystem::Cartesian;
+        for idx in 0..self.points.curves.len() {
+            ui.horizontal(|ui| {
+                let active = idx == self.points.curves.active;
+                if ui
+                    .radio(active, "")
+                    .on_hover_text(i18n.text(TextKey::ActiveCurveHover))
+                    .clicked()
+                {
+                    action = Some(CurveAction::Select(idx));
+                }
+                let mut color = self.curve_point_color(idx);
+                let color_resp = ui
+                    .color_edit_button_srgba(&mut color)
+                    .on_hover_text(i18n.text(TextKey::CurveColorSwatchHover));
+                if color_resp.changed() {
+                    action = Some(CurveAction::Recolor(idx, color));
+                }
+                color_resp.context_menu(|ui| {
+                    if let Some(picked) = self.ui_swatch_menu_contents(ui, color) {
+                        action = Some(CurveAction::Recolor(idx, picked));
+                    }
+                });
+                let name = &mut self.points.curves.curves[idx].name;
+                let name_resp = ui
+                    .add(egui::TextEdit::singleline(name).desired_width(120.0))
+                    .on_hover_text(i18n.text(TextKey::CurveNameHover));
+                if name_resp.lost_focus() {
+                    self.normalize_curve_name(idx);
+                }
+                self.ui_curve_parameter(ui, idx);
+                if cartesian {
+                    let secondary = self.points.curves.curves[idx].y_axis == YAxis::Secondary;
+                    let toggle = ui
+                        .add(egui::Button::selectable(secondary, "Y'").small())
+                        .on_hover_text(i18n.text(TextKey::CurveOnSecondaryYHover));
+                    if toggle.clicked() {
+                        let axis = if secondary {
+                            YAxis::Primary
+                        } else {
+                            YAxis::Secondary
+                        };
+                        action = Some(CurveAction::SetYAxis(idx, axis));
+                    }
+                }
+                let locked = self.points.curves.curves[idx].locked;
+                let icon = if locked {
+                    icons::ICON_LOCK
+                } else {
+                    icons::ICON_UNLOCK
+                };
+                let lock = ui
+                    .add(
+                        egui::Button::image(icons::image(icon, icons::INLINE_ICON_SIZE))
+                            .selected(locked)
+                            .frame(false)
+                            .image_tint_follows_text_color(true),
+                    )
+                    .on_hover_text(i18n.text(TextKey::LockCurveHover));
+                if lock.clicked() {
+                    action = Some(CurveAction::ToggleLock(idx));
+                }
+                let count = self.curve_points(idx).len();
+                ui.label(RichText::new(count.to_string()).weak())
+                    .on_hover_text(i18n.text(TextKey::CurvePointCountHover));
+                let remove = ui
+                    .add_enabled(
+                        can_remove,
+                        egui::Button::image(icons::image(
+                            icons::ICON_CLOSE,
+                            icons::INLINE_ICON_SIZE,
+                        ))
+                        .frame(false)
+                        .image_tint_follows_text_color(true),
+                    )
+                    .on_hover_text(i18n.text(TextKey::RemoveCurveHover));
+                if remove.clicked() {
+                    action = Some(CurveAction::Remove(idx));
+                }
+            });
+        }
+        self.ui_curve_list_buttons(ui);
+        self.ui_point_selection_controls(ui);
+        self.ui_parameter_name(ui);
+
+        match action {
+            Some(CurveAction::Select(idx)) => self.select_curve(idx),
+            Some(CurveAction::Recolor(idx, color)) => {
+                self.points.curves.curves[idx].color = Some(color);
+            }
+            Some(CurveAction::SetYAxis(idx, axis)) => self.set_curve_y_axis(idx, axis),
+            Some(CurveAction::ToggleLock(idx)) => self.toggle_curve_lock(idx),
+            Some(CurveAction::Remove(idx)) => {
+                self.remove_curve(idx);
+            }
+            None => {}
+        }
+    }
+
+    /// "Add curve" and "From legend" below the curve list.
+    fn ui_curve_list_buttons(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.horizontal(|ui| {
+            if ui
+                .button(i18n.text(TextKey::AddCurve))
+                .on_hover_text(i18n.text(TextKey::AddCurveHover))
+                .clicked()
+            {
+                self.add_curve();
+            }
+            let picking = self.calibration.pick_mode == PickMode::LegendEntry;
+            let from_legend = ui
+                .add_enabled(
+                    self.image.image.is_some(),
+                    egui::Button::selectable(picking, i18n.text(TextKey::CurvesFromLegend)),
+                )
+                .on_hover_text(i18n.text(TextKey::CurvesFromLegendHover));
+            if from_legend.clicked() {
+                if picking {
+                    self.cancel_pick_mode();
+                } else {
+                    self.begin_pick_mode(PickMode::LegendEntry);
+                }
+            }
+        });
+    }
+
+    /// "Select points" and the bulk edits of the points selected with it.
+    fn ui_point_selection_controls(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let picking = self.calibration.pick_mode == PickMode::SelectPoints;
+        let count = self.interaction.point_selection.len();
+        ui.horizontal(|ui| {
+            let select = ui
+                .add_enabled(
+                    !self.points.points.is_empty(),
+                    egui::Button::selectable(picking, i18n.text(TextKey::SelectPoints)),
+                )
+                .on_hover_text(i18n.text(TextKey::SelectPointsHover));
+            if select.clicked() {
+                if picking {
+                    self.cancel_pick_mode();
+                } else {
+                    self.begin_point_selection_pick();
+                }
+            }
+            if count > 0 {
+                ui.label(RichText::new(count.to_string()).weak())
+                    .on_hover_text(i18n.text(TextKey::SelectedPointCountHover));
+            }
+        });
+        if count == 0 {
+            return;
+        }
+        let mut target = None;
+        ui.horizontal(|ui| {
+            if ui
+                .button(i18n.text(TextKey::DeleteSelectedPoints))
+                .on_hover_text(i18n.text(TextKey::DeleteSelectedPointsHover))
+                .clicked()
+            {
+                self.delete_selected_points();
+            }
+            let active = self.points.curves.active;
+            ui.add_enabled_ui(self.points.curves.len() > 1, |ui| {
+                egui::ComboBox::from_id_salt("move_selected_points_combo")
+                    .selected_text(i18n.text(TextKey::MoveSelectedPointsTo))
+                    .show_ui(ui, |ui| {
+                        for (idx, curve) in self.points.curves.curves.iter().enumerate() {
+                            if idx != active && ui.selectable_label(false, &curve.name).clicked() {
+                                target = Some(idx);
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(i18n.text(TextKey::MoveSelectedPointsHover));
+            });
+            if ui.button(i18n.text(TextKey::ClearPointSelection)).clicked() {
+                self.interaction.point_selection.clear();
+            }
+        });
+        if let Some(idx) = target {
+            self.move_selected_points_to_curve(idx);
+        }
+    }
+
+    /// Header of the exported parameter column, offered once some curve has a parameter.
+    fn ui_parameter_name(&mut self, ui: &mut egui::Ui) {
+        let has_parameters = self
+            .points
+            .curves
+            .curves
+            .iter()
+            .any(|curve| !curve.parameter_text.trim().is_empty());
+        if !has_parameters {
+            return;
+        }
+        let i18n = self.i18n();
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::ParameterName));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.project.parameter_name)
+                    .desired_width(120.0)
+                    .hint_text(PARAMETER_COLUMN_HEADER),
+            )
+            .on_hover_text(i18n.text(TextKey::ParameterNameHover));
+        });
+    }
+
+    /// Family parameter field of curve `idx`, flagged when the text is not a number.
+    fn ui_curve_parameter(&mut self, ui: &mut egui::Ui, idx: usize) {
+        let i18n = self.i18n();
+        let curve = &mut self.points.curves.curves[idx];
+        let invalid = !curve.parameter_text.trim().is_empty() && curve.parameter().is_none();
+        let mut edit = egui::TextEdit::singleline(&mut curve.parameter_text)
+            .desired_width(48.0)
+            .hint_text(i18n.text(TextKey::CurveParameterHint));
+        if invalid {
+            edit = edit.text_color(ui.visuals().error_fg_color);
+        }
+        ui.add(edit).on_hover_text(i18n.text(if invalid {
+            TextKey::CurveParameterInvalid
+        } else {
+            TextKey::CurveParameterHover
+        }));
+    }
+}
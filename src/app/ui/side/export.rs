@@ -1,70 +1,306 @@
 use super::super::icons;
-use crate::app::{CurcatApp, ExportKind, SAMPLE_COUNT_MIN};
-use crate::export::ExportFormat;
+use crate::app::{
+    CurcatApp, ExportKind, LOESS_ITERATIONS_MAX, SAMPLE_COUNT_MIN, SMOOTHING_MAX, SPLINE_SPAN_MAX,
+};
+use crate::config::ExportBackup;
+use crate::export::{ExportFormat, ExportOptions, SurfaceFormat, TimeExportFormat};
 use crate::i18n::TextKey;
-use crate::interp::InterpAlgorithm;
+use crate::interp::{InterpAlgorithm, SmoothingFilter};
+use crate::keymap::ShortcutAction;
 
 type ExportButtonAction = (
     icons::Icon,
     TextKey,
-    &'static str,
+    ShortcutAction,
     ExportFormat,
     fn(&mut CurcatApp),
 );
 
-const EXPORT_BUTTON_ACTIONS: [ExportButtonAction; 7] = [
+const EXPORT_BUTTON_ACTIONS: [ExportButtonAction; 12] = [
     (
         icons::ICON_EXPORT_CSV,
         TextKey::ExportCsv,
-        "Ctrl+Shift+C",
+        ShortcutAction::ExportCsv,
         ExportFormat::Csv,
         CurcatApp::start_export_csv,
     ),
     (
         icons::ICON_EXPORT_JSON,
         TextKey::ExportJson,
-        "Ctrl+Shift+J",
+        ShortcutAction::ExportJson,
         ExportFormat::Json,
         CurcatApp::start_export_json,
     ),
     (
         icons::ICON_EXPORT_RON,
         TextKey::ExportRon,
-        "Ctrl+Shift+R",
+        ShortcutAction::ExportRon,
         ExportFormat::Ron,
         CurcatApp::start_export_ron,
     ),
     (
         icons::ICON_EXPORT_XLSX,
         TextKey::ExportExcel,
-        "Ctrl+Shift+E",
+        ShortcutAction::ExportExcel,
         ExportFormat::Xlsx,
         CurcatApp::start_export_xlsx,
     ),
+    (
+        icons::ICON_EXPORT_ODS,
+        TextKey::ExportOds,
+        ShortcutAction::ExportOds,
+        ExportFormat::Ods,
+        CurcatApp::start_export_ods,
+    ),
+    (
+        icons::ICON_EXPORT_ARROW,
+        TextKey::ExportArrow,
+        ShortcutAction::ExportArrow,
+        ExportFormat::Arrow,
+        CurcatApp::start_export_arrow,
+    ),
+    (
+        icons::ICON_EXPORT_PARQUET,
+        TextKey::ExportParquet,
+        ShortcutAction::ExportParquet,
+        ExportFormat::Parquet,
+        CurcatApp::start_export_parquet,
+    ),
+    (
+        icons::ICON_EXPORT_SQL,
+        TextKey::ExportSql,
+        ShortcutAction::ExportSql,
+        ExportFormat::Sql,
+        CurcatApp::start_export_sql,
+    ),
+    (
+        icons::ICON_EXPORT_NETCDF,
+        TextKey::ExportNetCdf,
+        ShortcutAction::ExportNetCdf,
+        ExportFormat::NetCdf,
+        CurcatApp::start_export_netcdf,
+    ),
     (
         icons::ICON_EXPORT_HTML,
         TextKey::ExportHtml,
-        "Ctrl+Shift+H",
+        ShortcutAction::ExportHtml,
         ExportFormat::Html,
         CurcatApp::start_export_html,
     ),
     (
         icons::ICON_EXPORT_XML,
         TextKey::ExportXml,
-        "Ctrl+Shift+X",
+        ShortcutAction::ExportXml,
         ExportFormat::Xml,
         CurcatApp::start_export_xml,
     ),
     (
         icons::ICON_EXPORT_MARKDOWN,
         TextKey::ExportMarkdown,
-        "Ctrl+Shift+M",
+        ShortcutAction::ExportMarkdown,
         ExportFormat::Markdown,
         CurcatApp::start_export_markdown,
     ),
 ];
 
+/// Digits preselected when significant-figure formatting is switched on.
+const DEFAULT_SIGNIFICANT_FIGURES: u8 = 6;
+
+const CSV_DELIMITERS: [(u8, TextKey); 4] = [
+    (b',', TextKey::DelimiterComma),
+    (b';', TextKey::DelimiterSemicolon),
+    (b'\t', TextKey::DelimiterTab),
+    (b'|', TextKey::DelimiterPipe),
+];
+
 impl CurcatApp {
+    fn ui_export_profile_controls(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let mut picked = None;
+        let modified = self.active_export_profile_modified();
+        let selected_text = self.active_export_profile().map_or_else(
+            || i18n.text(TextKey::NoExportProfile).to_string(),
+            |p| {
+                if modified {
+                    format!("{} *", p.name)
+                } else {
+                    p.name.clone()
+                }
+            },
+        );
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::ExportProfile))
+                .on_hover_text(i18n.text(TextKey::ExportProfileHover));
+            egui::ComboBox::from_id_salt("export_profile_combo")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for (idx, profile) in self.config.export.profiles.iter().enumerate() {
+                        let selected = self.export.active_profile == Some(idx);
+                        let label = format!("{} ({})", profile.name, profile.format.label());
+                        if ui.selectable_label(selected, label).clicked() {
+                            picked = Some(idx);
+                        }
+                    }
+                });
+        });
+        if let Some(idx) = picked {
+            self.apply_export_profile(idx);
+        }
+
+        let mut save = false;
+        let mut delete = false;
+        ui.horizontal(|ui| {
+            let edit = ui.add(
+                egui::TextEdit::singleline(&mut self.export.profile_name_draft)
+                    .hint_text(i18n.text(TextKey::ProfileNameHint))
+                    .desired_width(120.0),
+            );
+            let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let has_name = !self.export.profile_name_draft.trim().is_empty();
+            save = ui
+                .add_enabled(has_name, egui::Button::new(i18n.text(TextKey::SaveProfile)))
+                .on_hover_text(i18n.text(TextKey::SaveProfileHover))
+                .clicked()
+                || (submitted && has_name);
+            delete = ui
+                .add_enabled(
+                    self.export.active_profile.is_some(),
+                    egui::Button::new(i18n.text(TextKey::DeleteProfile)),
+                )
+                .on_hover_text(i18n.text(TextKey::DeleteProfileHover))
+                .clicked();
+        });
+        if save {
+            let name = self.export.profile_name_draft.clone();
+            self.save_export_profile_as(&name);
+        } else if delete {
+            self.delete_active_export_profile();
+        }
+    }
+
+    /// Checkboxes for the `[[export.columns]]` templates; nothing when none are configured.
+    fn ui_export_column_templates(&mut self, ui: &mut egui::Ui) {
+        if self.config.export.columns.is_empty() {
+            return;
+        }
+        let i18n = self.i18n();
+        ui.label(i18n.text(TextKey::ColumnTemplates))
+            .on_hover_text(i18n.text(TextKey::ColumnTemplatesHover));
+        for template in &self.config.export.columns {
+            let problem = template.problem();
+            let mut checked = self.export.column_templates.contains(&template.name);
+            let response = ui.add_enabled(
+                problem.is_none(),
+                egui::Checkbox::new(&mut checked, template.name.trim()),
+            );
+            let detail = template.metric.map_or_else(
+                || format!("= {}", template.expression),
+                |metric| metric.label().to_string(),
+            );
+            if let Some(problem) = problem {
+                response.on_disabled_hover_text(format!("{detail}\n{problem}"));
+                continue;
+            }
+            if response.on_hover_text(detail).changed() {
+                if checked {
+                    self.export.column_templates.push(template.name.clone());
+                } else {
+                    self.export
+                        .column_templates
+                        .retain(|name| *name != template.name);
+                }
+            }
+        }
+    }
+
+    fn ui_export_number_controls(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let use_significant = self.export.significant_figures.is_some();
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!use_significant, |ui| {
+                ui.label(i18n.text(TextKey::Decimals))
+                    .on_hover_text(i18n.text(TextKey::DecimalsHover));
+                ui.add(
+                    egui::DragValue::new(&mut self.export.decimals)
+                        .range(0..=ExportOptions::MAX_DECIMALS),
+                );
+            });
+        });
+        ui.horizontal(|ui| {
+            let mut enabled = use_significant;
+            if ui
+                .checkbox(&mut enabled, i18n.text(TextKey::SignificantFigures))
+                .on_hover_text(i18n.text(TextKey::SignificantFiguresHover))
+                .changed()
+            {
+                self.export.significant_figures = enabled.then_some(DEFAULT_SIGNIFICANT_FIGURES);
+            }
+            if let Some(digits) = self.export.significant_figures.as_mut() {
+                ui.add(egui::DragValue::new(digits).range(1..=ExportOptions::MAX_DECIMALS));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::CsvDelimiter))
+                .on_hover_text(i18n.text(TextKey::CsvDelimiterHover));
+            let current = CSV_DELIMITERS
+                .iter()
+                .find(|(byte, _)| *byte == self.export.csv_delimiter)
+                .map_or_else(
+                    || char::from(self.export.csv_delimiter).to_string(),
+                    |(_, key)| i18n.text(*key).to_string(),
+                );
+            egui::ComboBox::from_id_salt("csv_delimiter_combo")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (byte, key) in CSV_DELIMITERS {
+                        ui.selectable_value(&mut self.export.csv_delimiter, byte, i18n.text(key));
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::TimeExportFormat))
+                .on_hover_text(i18n.text(TextKey::TimeExportFormatHover));
+            let label = |format: TimeExportFormat| match format {
+                TimeExportFormat::Iso8601 => "ISO-8601",
+                TimeExportFormat::UnixSeconds => i18n.text(TextKey::TimeExportUnixSeconds),
+                TimeExportFormat::ExcelSerial => i18n.text(TextKey::TimeExportExcelSerial),
+            };
+            egui::ComboBox::from_id_salt("time_export_format_combo")
+                .selected_text(label(self.export.time_format))
+                .show_ui(ui, |ui| {
+                    for option in [
+                        TimeExportFormat::Iso8601,
+                        TimeExportFormat::UnixSeconds,
+                        TimeExportFormat::ExcelSerial,
+                    ] {
+                        ui.selectable_value(&mut self.export.time_format, option, label(option));
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::OverwriteBackup))
+                .on_hover_text(i18n.text(TextKey::OverwriteBackupHover));
+            let label = |backup: ExportBackup| match backup {
+                ExportBackup::Off => i18n.text(TextKey::OverwriteBackupOff),
+                ExportBackup::Bak => i18n.text(TextKey::OverwriteBackupBak),
+                ExportBackup::Timestamped => i18n.text(TextKey::OverwriteBackupTimestamped),
+            };
+            let mut backup = self.config.export.backup;
+            egui::ComboBox::from_id_salt("overwrite_backup_combo")
+                .selected_text(label(backup))
+                .show_ui(ui, |ui| {
+                    for option in [
+                        ExportBackup::Off,
+                        ExportBackup::Bak,
+                        ExportBackup::Timestamped,
+                    ] {
+                        ui.selectable_value(&mut backup, option, label(option));
+                    }
+                });
+            self.set_export_backup(backup);
+        });
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn export_action_button(
         &mut self,
@@ -89,15 +325,60 @@ impl CurcatApp {
         }
     }
 
+    pub(super) fn ui_export_sample_count(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(i18n.text(TextKey::Samples))
+            .on_hover_text(i18n.text(TextKey::SamplesHover));
+        ui.spacing_mut().slider_width = 150.0;
+        ui.horizontal(|ui| {
+            let max_samples = self.config.export.samples_max_sanitized();
+            self.export.sample_count = self
+                .export
+                .sample_count
+                .clamp(SAMPLE_COUNT_MIN, max_samples);
+            let sresp = ui.add(
+                egui::Slider::new(
+                    &mut self.export.sample_count,
+                    SAMPLE_COUNT_MIN..=max_samples,
+                )
+                .text(i18n.text(TextKey::Count)),
+            );
+            let slider_hint = match self.ui.language {
+                crate::i18n::UiLanguage::En => {
+                    format!("Higher values give a denser interpolated curve (max {max_samples})")
+                }
+                crate::i18n::UiLanguage::Ru => format!(
+                    "Чем больше значение, тем плотнее интерполированная кривая (макс {max_samples})"
+                ),
+            };
+            if sresp.changed() {
+                self.export.sample_count_follows_axis = false;
+            }
+            sresp.on_hover_text(slider_hint);
+            if ui
+                .button(i18n.text(TextKey::Auto))
+                .on_hover_text(i18n.text(TextKey::AutoSamplesHover))
+                .clicked()
+            {
+                self.auto_tune_sample_count();
+            }
+        });
+    }
+
     #[allow(clippy::too_many_lines)]
     pub(crate) fn ui_export_section(&mut self, ui: &mut egui::Ui) {
         let i18n = self.i18n();
         let has_points = !self.points.points.is_empty();
         let calibrated = self.calibration_ready();
-        let can_export = has_points && calibrated;
+        let can_export = has_points && calibrated && !self.export_job_running();
+        self.ui_export_profile_controls(ui);
+        ui.add_space(4.0);
         let export_kind_label = match self.export.export_kind {
             ExportKind::Interpolated => i18n.text(TextKey::InterpolatedCurve),
             ExportKind::RawPoints => i18n.text(TextKey::RawPickedPoints),
+            ExportKind::Fitted => i18n.text(TextKey::FittedCurve),
+            ExportKind::Spectrum => i18n.text(TextKey::SpectrumExport),
+            ExportKind::Crossings => i18n.text(TextKey::CrossingsExport),
         };
         egui::ComboBox::from_id_salt("export_kind_combo")
             .selected_text(export_kind_label)
@@ -114,11 +395,29 @@ impl CurcatApp {
                     i18n.text(TextKey::RawPickedPoints),
                 )
                 .on_hover_text(i18n.text(TextKey::RawPickedPointsHover));
+                ui.selectable_value(
+                    &mut self.export.export_kind,
+                    ExportKind::Fitted,
+                    i18n.text(TextKey::FittedCurve),
+                )
+                .on_hover_text(i18n.text(TextKey::FittedCurveHover));
+                ui.selectable_value(
+                    &mut self.export.export_kind,
+                    ExportKind::Spectrum,
+                    i18n.text(TextKey::SpectrumExport),
+                )
+                .on_hover_text(i18n.text(TextKey::SpectrumExportHover));
+                ui.selectable_value(
+                    &mut self.export.export_kind,
+                    ExportKind::Crossings,
+                    i18n.text(TextKey::CrossingsExport),
+                )
+                .on_hover_text(i18n.text(TextKey::CrossingsExportHover));
             });
         ui.add_space(4.0);
 
         match self.export.export_kind {
-            ExportKind::Interpolated => {
+            ExportKind::Interpolated | ExportKind::Spectrum | ExportKind::Crossings => {
                 ui.label(i18n.text(TextKey::Interpolation))
                     .on_hover_text(i18n.text(TextKey::InterpolationHover));
                 let combo = egui::ComboBox::from_id_salt("interp_algo_combo")
@@ -136,39 +435,31 @@ impl CurcatApp {
                     .response
                     .on_hover_text(i18n.text(TextKey::InterpolationAlgorithmHover));
 
-                ui.label(i18n.text(TextKey::Samples))
-                    .on_hover_text(i18n.text(TextKey::SamplesHover));
-                ui.spacing_mut().slider_width = 150.0;
-                ui.horizontal(|ui| {
-                    let max_samples = self.config.export.samples_max_sanitized();
-                    self.export.sample_count = self
-                        .export
-                        .sample_count
-                        .clamp(SAMPLE_COUNT_MIN, max_samples);
-                    let sresp = ui.add(
-                        egui::Slider::new(
-                            &mut self.export.sample_count,
-                            SAMPLE_COUNT_MIN..=max_samples,
-                        )
-                        .text(i18n.text(TextKey::Count)),
-                    );
-                    let slider_hint = match self.ui.language {
-                        crate::i18n::UiLanguage::En => format!(
-                            "Higher values give a denser interpolated curve (max {max_samples})"
-                        ),
-                        crate::i18n::UiLanguage::Ru => format!(
-                            "Чем больше значение, тем плотнее интерполированная кривая (макс {max_samples})"
-                        ),
-                    };
-                    sresp.on_hover_text(slider_hint);
-                    if ui
-                        .button(i18n.text(TextKey::Auto))
-                        .on_hover_text(i18n.text(TextKey::AutoSamplesHover))
-                        .clicked()
-                    {
-                        self.auto_tune_sample_count();
-                    }
-                });
+                if self.export.interp_algorithm == InterpAlgorithm::SmoothingSpline {
+                    ui.add(
+                        egui::Slider::new(&mut self.export.spline_span, 1..=SPLINE_SPAN_MAX)
+                            .text(i18n.text(TextKey::SplineSpan)),
+                    )
+                    .on_hover_text(i18n.text(TextKey::SplineSpanHover));
+                }
+
+                if self.export.export_kind == ExportKind::Interpolated {
+                    self.ui_export_sample_grid(ui);
+                } else {
+                    self.ui_export_sample_count(ui);
+                }
+                if self.export.export_kind == ExportKind::Interpolated
+                    && self.export.interp_algorithm == InterpAlgorithm::StepHold
+                {
+                    ui.checkbox(
+                        &mut self.export.step_breakpoints,
+                        i18n.text(TextKey::StepBreakpoints),
+                    )
+                    .on_hover_text(i18n.text(TextKey::StepBreakpointsHover));
+                }
+                if self.export.export_kind == ExportKind::Crossings {
+                    self.ui_crossing_level(ui);
+                }
             }
             ExportKind::RawPoints => {
                 ui.label(i18n.text(TextKey::ExtraColumns))
@@ -183,7 +474,90 @@ impl CurcatApp {
                     i18n.text(TextKey::IncludeAngleDeg),
                 );
                 ang.on_hover_text(i18n.text(TextKey::IncludeAngleDegHover));
+                let steps = ui.checkbox(
+                    &mut self.export.raw_include_steps,
+                    i18n.text(TextKey::IncludeAxisSteps),
+                );
+                steps.on_hover_text(i18n.text(TextKey::IncludeAxisStepsHover));
+                let flags = ui.checkbox(
+                    &mut self.export.raw_include_flags,
+                    i18n.text(TextKey::IncludeFlagAnnotations),
+                );
+                flags.on_hover_text(i18n.text(TextKey::IncludeFlagAnnotationsHover));
+                let times = ui.checkbox(
+                    &mut self.export.raw_include_capture_times,
+                    i18n.text(TextKey::IncludeCaptureTimes),
+                );
+                times.on_hover_text(i18n.text(TextKey::IncludeCaptureTimesHover));
+                let confidence = ui.checkbox(
+                    &mut self.export.raw_include_confidence,
+                    i18n.text(TextKey::IncludeConfidence),
+                );
+                confidence.on_hover_text(i18n.text(TextKey::IncludeConfidenceHover));
+            }
+            ExportKind::Fitted => {
+                ui.label(egui::RichText::new(i18n.fit_model_label(self.export.fit_model)).weak());
+                self.ui_export_sample_grid(ui);
+            }
+        }
+        self.ui_export_x_range(ui);
+        if matches!(
+            self.export.export_kind,
+            ExportKind::Interpolated | ExportKind::RawPoints
+        ) {
+            ui.checkbox(
+                &mut self.export.include_fit_column,
+                i18n.text(TextKey::IncludeFitColumn),
+            )
+            .on_hover_text(i18n.text(TextKey::IncludeFitColumnHover));
+        }
+        if self.export.export_kind != ExportKind::Fitted {
+            ui.horizontal(|ui| {
+                ui.label(i18n.text(TextKey::SmoothingFilter))
+                    .on_hover_text(i18n.text(TextKey::SmoothingFilterHover));
+                egui::ComboBox::from_id_salt("smoothing_filter_combo")
+                    .selected_text(i18n.smoothing_filter_label(self.export.smoothing_filter))
+                    .show_ui(ui, |ui| {
+                        for filter in SmoothingFilter::ALL {
+                            ui.selectable_value(
+                                &mut self.export.smoothing_filter,
+                                filter,
+                                i18n.smoothing_filter_label(filter),
+                            );
+                        }
+                    });
+            });
+            if self.export.smoothing_filter == SmoothingFilter::Loess {
+                ui.horizontal(|ui| {
+                    ui.label(i18n.text(TextKey::LoessIterations))
+                        .on_hover_text(i18n.text(TextKey::LoessIterationsHover));
+                    ui.add(
+                        egui::DragValue::new(&mut self.export.loess_iterations)
+                            .range(0..=LOESS_ITERATIONS_MAX),
+                    )
+                    .on_hover_text(i18n.text(TextKey::LoessIterationsHover));
+                });
             }
+            ui.add(
+                egui::Slider::new(&mut self.export.smoothing, 0..=SMOOTHING_MAX)
+                    .text(i18n.text(TextKey::Smoothing)),
+            )
+            .on_hover_text(i18n.text(TextKey::SmoothingHover));
+        }
+        self.ui_export_column_templates(ui);
+        if self.points.curves.len() > 1 && self.export.export_kind != ExportKind::Crossings {
+            ui.checkbox(
+                &mut self.export.unstack_layers,
+                i18n.text(TextKey::UnstackLayers),
+            )
+            .on_hover_text(i18n.text(TextKey::UnstackLayersHover));
+        }
+        if self.points.curves.len() > 1 {
+            ui.checkbox(
+                &mut self.export.long_format,
+                i18n.text(TextKey::LongFormatExport),
+            )
+            .on_hover_text(i18n.text(TextKey::LongFormatExportHover));
         }
 
         if matches!(
@@ -197,6 +571,8 @@ impl CurcatApp {
             cart.on_hover_text(i18n.text(TextKey::IncludeCartesianColumnsHover));
         }
 
+        self.ui_export_number_controls(ui);
+
         ui.separator();
         let coord_system = self.calibration.coord_system;
         let export_hint = |format_name: &str, shortcut: &str| -> String {
@@ -215,7 +591,17 @@ impl CurcatApp {
                         "{} {format_name}",
                         i18n.text(TextKey::CompleteCalibrationBeforeExportPolar)
                     ),
+                    crate::types::CoordSystem::Affine => format!(
+                        "{} {format_name}",
+                        i18n.text(TextKey::CompleteCalibrationBeforeExportAffine)
+                    ),
+                    crate::types::CoordSystem::Smith => format!(
+                        "{} {format_name}",
+                        i18n.text(TextKey::CompleteCalibrationBeforeExportSmith)
+                    ),
                 }
+            } else if shortcut.is_empty() {
+                format!("{} {format_name}", i18n.text(TextKey::ExportToFormat))
             } else {
                 format!(
                     "{} {format_name} ({shortcut})",
@@ -223,16 +609,117 @@ impl CurcatApp {
                 )
             }
         };
-        for (icon, text_key, shortcut, format, action) in EXPORT_BUTTON_ACTIONS {
+        for (icon, text_key, shortcut_action, format, action) in EXPORT_BUTTON_ACTIONS {
+            let shortcut = self.shortcut_label(shortcut_action);
             self.export_action_button(
                 ui,
                 can_export,
                 icon,
                 i18n.text(text_key),
-                shortcut,
-                &export_hint(format.label(), shortcut),
+                &shortcut,
+                &export_hint(format.label(), &shortcut),
                 action,
             );
         }
+        if let Some(format) = self.active_export_profile().map(|p| p.format) {
+            let label = format!(
+                "{} ({})",
+                i18n.text(TextKey::ExportWithProfile),
+                format.label()
+            );
+            let hint = if can_export {
+                i18n.text(TextKey::ExportWithProfileHover).to_string()
+            } else {
+                export_hint(format.label(), "")
+            };
+            if ui
+                .add_enabled(can_export, egui::Button::new(label))
+                .on_hover_text(hint)
+                .clicked()
+            {
+                self.start_export(format);
+            }
+        }
+        self.ui_surface_export(ui, calibrated && !self.export_job_running());
+        self.ui_export_job_progress(ui);
+        let history_label = format!(
+            "{} ({})",
+            i18n.text(TextKey::ExportHistory),
+            self.project.export_history.len()
+        );
+        let rules_label = format!(
+            "{} ({})",
+            i18n.text(TextKey::ValidationRules),
+            self.project.validation_rules.len()
+        );
+        ui.horizontal(|ui| {
+            if ui
+                .button(history_label)
+                .on_hover_text(i18n.text(TextKey::ExportHistoryHover))
+                .clicked()
+            {
+                self.ui.export_history_window_open = true;
+            }
+            if ui
+                .button(rules_label)
+                .on_hover_text(i18n.text(TextKey::ValidationRulesHover))
+                .clicked()
+            {
+                self.ui.validation_window_open = true;
+            }
+        });
+    }
+
+    /// Surface buttons, offered once two curves carry a parameter value.
+    fn ui_surface_export(&mut self, ui: &mut egui::Ui, enabled: bool) {
+        if self.parameter_curve_count() < 2 {
+            return;
+        }
+        let i18n = self.i18n();
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::SurfaceExport))
+                .on_hover_text(i18n.text(TextKey::SurfaceExportHover));
+            for (format, text) in [
+                (SurfaceFormat::CsvMatrix, TextKey::SurfaceCsvMatrix),
+                (SurfaceFormat::NetCdf, TextKey::SurfaceNetCdf),
+            ] {
+                if ui
+                    .add_enabled(enabled, egui::Button::new(i18n.text(text)))
+                    .on_hover_text(i18n.text(TextKey::SurfaceExportHover))
+                    .clicked()
+                {
+                    self.start_surface_export(format);
+                }
+            }
+        });
+    }
+
+    fn ui_export_job_progress(&self, ui: &mut egui::Ui) {
+        let Some(job) = self.export.pending_export_job.as_ref() else {
+            return;
+        };
+        let i18n = self.i18n();
+        let (done, total) = (job.progress.rows_done(), job.progress.rows_total());
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            if total == 0 {
+                // Only streamed formats report row counts.
+                ui.spinner();
+                ui.label(job.format.label());
+            } else {
+                ui.add(
+                    egui::ProgressBar::new(job.progress.fraction())
+                        .desired_width(160.0)
+                        .text(format!("{} {done}/{total}", job.format.label())),
+                );
+            }
+            if ui
+                .button(i18n.text(TextKey::Cancel))
+                .on_hover_text(i18n.text(TextKey::CancelExportHover))
+                .clicked()
+            {
+                self.cancel_export_job();
+            }
+        });
     }
 }
@@ -0,0 +1,50 @@
+use crate::app::CurcatApp;
+use crate::i18n::TextKey;
+use egui::RichText;
+
+impl CurcatApp {
+    /// Toggle and bound editors of the export range.
+    pub(super) fn ui_export_x_range(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let mut limited = self.export.x_range.is_some();
+        let default_range = self.default_export_range();
+        if ui
+            .add_enabled(
+                limited || default_range.is_some(),
+                egui::Checkbox::new(&mut limited, i18n.text(TextKey::ExportXRange)),
+            )
+            .on_hover_text(i18n.text(TextKey::ExportXRangeHover))
+            .clicked()
+        {
+            self.export.x_range = if limited { default_range } else { None };
+        }
+        let Some([mut from, mut to]) = self.export.x_range else {
+            return;
+        };
+        let span = (to - from).abs();
+        let speed = if span.is_finite() && span > 0.0 {
+            span / 500.0
+        } else {
+            0.01
+        };
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add(egui::DragValue::new(&mut from).speed(speed))
+                .changed();
+            ui.label("–");
+            changed |= ui.add(egui::DragValue::new(&mut to).speed(speed)).changed();
+        });
+        if changed {
+            self.export.x_range = Some([from, to]);
+        }
+        let misses =
+            default_range.is_some_and(|[lo, hi]| self.clip_to_export_range(lo, hi).is_none());
+        if misses {
+            ui.label(
+                RichText::new(i18n.text(TextKey::ExportXRangeMissesCurve))
+                    .color(ui.visuals().warn_fg_color),
+            );
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::app::CurcatApp;
+use crate::fit::{FitModel, MAX_POLY_DEGREE};
+use crate::i18n::TextKey;
+use egui::RichText;
+
+/// Fixed notation for everyday magnitudes, scientific otherwise.
+pub(super) fn format_coefficient(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude == 0.0 || (1.0e-3..1.0e6).contains(&magnitude) {
+        format!("{value:.6}")
+    } else {
+        format!("{value:.6e}")
+    }
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_fit_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::FitModelLabel))
+                .on_hover_text(i18n.text(TextKey::FitModelHover));
+            egui::ComboBox::from_id_salt("fit_model_combo")
+                .selected_text(i18n.fit_model_label(self.export.fit_model))
+                .show_ui(ui, |ui| {
+                    for model in FitModel::ALL {
+                        ui.selectable_value(
+                            &mut self.export.fit_model,
+                            model,
+                            i18n.fit_model_label(model),
+                        );
+                    }
+                });
+            if self.export.fit_model == FitModel::Polynomial {
+                ui.label(i18n.text(TextKey::PolynomialDegree))
+                    .on_hover_text(i18n.text(TextKey::PolynomialDegreeHover));
+                ui.add(
+                    egui::DragValue::new(&mut self.export.fit_degree).range(1..=MAX_POLY_DEGREE),
+                );
+            }
+        });
+        ui.label(
+            RichText::new(self.export.fit_model.equation(self.export.fit_degree))
+                .monospace()
+                .weak(),
+        );
+
+        if !self.calibration_ready() {
+            ui.label(RichText::new(i18n.text(TextKey::FitNeedsCalibration)).weak());
+            return;
+        }
+        let (x_mapping, y_mapping) = self.cartesian_mappings();
+        let polar_mapping = self.polar_mapping();
+        self.ensure_point_numeric_cache(
+            self.calibration.coord_system,
+            x_mapping.as_ref(),
+            y_mapping.as_ref(),
+            polar_mapping.as_ref(),
+        );
+        let Some(fit) = self.fit_points(&self.points.points) else {
+            ui.label(RichText::new(i18n.text(TextKey::FitUnavailable)).weak());
+            return;
+        };
+        egui::Grid::new("fit_coefficients_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (name, value) in fit.coefficient_names().iter().zip(&fit.coefficients) {
+                    ui.label(RichText::new(name).monospace());
+                    ui.label(RichText::new(format_coefficient(*value)).monospace());
+                    ui.end_row();
+                }
+                ui.label(RichText::new("R²").monospace());
+                ui.label(RichText::new(format!("{:.6}", fit.r_squared)).monospace());
+                ui.end_row();
+            });
+    }
+}
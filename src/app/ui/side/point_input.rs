@@ -1,8 +1,11 @@
 use super::super::common::toggle_switch;
+use super::super::icons;
 use crate::app::snap_helpers::SNAP_SWATCH_SIZE;
-use crate::app::{CurcatApp, PickMode, PointInputMode};
-use crate::i18n::TextKey;
-use crate::snap::{SnapFeatureSource, SnapThresholdKind};
+use crate::app::{CurcatApp, ErrorBarMode, PickMode, PointInputMode};
+use crate::i18n::{TextKey, UiLanguage};
+use crate::keymap::ShortcutAction;
+use crate::snap::{ColorSampleMode, SNAP_SCORERS, SnapFeatureSource, SnapThresholdKind};
+use crate::types::CoordSystem;
 use egui::{Color32, CornerRadius, RichText, StrokeKind, Vec2};
 
 impl CurcatApp {
@@ -13,6 +16,9 @@ impl CurcatApp {
             PointInputMode::Free => i18n.text(TextKey::Free),
             PointInputMode::ContrastSnap => i18n.text(TextKey::ContrastSnap),
             PointInputMode::CenterlineSnap => i18n.text(TextKey::CenterlineSnap),
+            PointInputMode::Scorer(idx) => SNAP_SCORERS
+                .get(idx)
+                .map_or("", |entry| i18n.snap_scorer_label(entry.id)),
         };
         egui::ComboBox::from_id_salt("point_input_mode_combo")
             .selected_text(mode_label)
@@ -35,6 +41,14 @@ impl CurcatApp {
                     i18n.text(TextKey::CenterlineSnap),
                 )
                 .on_hover_text(i18n.text(TextKey::CenterlineSnapHover));
+                for (idx, entry) in SNAP_SCORERS.iter().enumerate() {
+                    ui.selectable_value(
+                        &mut self.snap.point_input_mode,
+                        PointInputMode::Scorer(idx),
+                        i18n.snap_scorer_label(entry.id),
+                    )
+                    .on_hover_text(i18n.snap_scorer_hover(entry.id));
+                }
             });
 
         match self.snap.point_input_mode {
@@ -113,11 +127,33 @@ impl CurcatApp {
                     ui.label(RichText::new(i18n.text(TextKey::BestResultsColorSample)).small());
                 });
             }
+            PointInputMode::Scorer(idx) => {
+                self.ui_snap_radius_slider(ui);
+                self.ui_snap_overlay_color_selector(ui);
+                self.ui_curve_color_controls(ui);
+                if let (Some(entry), Some(threshold)) = (
+                    SNAP_SCORERS.get(idx),
+                    self.snap.scorer_thresholds.get_mut(idx),
+                ) {
+                    ui.add_space(4.0);
+                    ui.spacing_mut().slider_width = 150.0;
+                    ui.add(
+                        egui::Slider::new(threshold, entry.threshold_range.clone())
+                            .text(i18n.text(TextKey::Threshold))
+                            .clamping(egui::SliderClamping::Always),
+                    )
+                    .on_hover_text(i18n.text(TextKey::ThresholdHigherHint));
+                }
+                if SNAP_SCORERS
+                    .get(idx)
+                    .is_some_and(|entry| entry.needs_curve_mask)
+                {
+                    self.ui_curve_mask_controls(ui);
+                }
+            }
         }
-        if matches!(
-            self.snap.point_input_mode,
-            PointInputMode::ContrastSnap | PointInputMode::CenterlineSnap
-        ) {
+        if !matches!(self.snap.point_input_mode, PointInputMode::Free) {
+            self.ui_snap_area_controls(ui);
             ui.scope(|ui| {
                 ui.style_mut().spacing.item_spacing.x = 4.0;
                 ui.label(RichText::new(i18n.text(TextKey::PreviewCircleHint)).small());
@@ -131,6 +167,210 @@ impl CurcatApp {
             ui.label(i18n.text(TextKey::ShowPointConnections))
                 .on_hover_text(i18n.text(TextKey::ShowPointConnectionsHover));
         });
+        ui.horizontal(|ui| {
+            toggle_switch(ui, &mut self.points.show_point_confidence)
+                .on_hover_text(i18n.text(TextKey::ShowPointConfidenceHover));
+            ui.add_space(4.0);
+            ui.label(i18n.text(TextKey::ShowPointConfidence))
+                .on_hover_text(i18n.text(TextKey::ShowPointConfidenceHover));
+        });
+        ui.horizontal(|ui| {
+            toggle_switch(ui, &mut self.points.record_capture_times)
+                .on_hover_text(i18n.text(TextKey::RecordCaptureTimesHover));
+            ui.add_space(4.0);
+            ui.label(i18n.text(TextKey::RecordCaptureTimes))
+                .on_hover_text(i18n.text(TextKey::RecordCaptureTimesHover));
+        });
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::ErrorBars))
+                .on_hover_text(i18n.text(TextKey::ErrorBarsHover));
+            let before = self.points.error_bar_mode;
+            egui::ComboBox::from_id_salt("error_bar_mode_combo")
+                .selected_text(i18n.error_bar_mode_label(self.points.error_bar_mode))
+                .show_ui(ui, |ui| {
+                    for mode in ErrorBarMode::ALL {
+                        ui.selectable_value(
+                            &mut self.points.error_bar_mode,
+                            mode,
+                            i18n.error_bar_mode_label(mode),
+                        );
+                    }
+                });
+            if self.points.error_bar_mode != before {
+                self.cancel_pending_error_bar();
+            }
+        });
+        ui.add_space(6.0);
+        self.ui_pasted_points_controls(ui);
+    }
+
+    fn ui_pasted_points_controls(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let has_image = self.image.image.is_some();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    has_image,
+                    egui::Button::image_and_text(
+                        icons::image(icons::ICON_PASTE_IMAGE, icons::BUTTON_ICON_SIZE),
+                        i18n.text(TextKey::PastePoints),
+                    )
+                    .image_tint_follows_text_color(true),
+                )
+                .on_hover_text(
+                    self.hover_with_shortcut(
+                        TextKey::PastePointsHover,
+                        ShortcutAction::PastePoints,
+                    ),
+                )
+                .clicked()
+            {
+                self.paste_points_from_clipboard();
+            }
+        });
+        let Some(count) = self.points.imported.as_ref().map(|d| d.points.len()) else {
+            return;
+        };
+        ui.label(match self.ui.language {
+            UiLanguage::En => format!("Pasted reference: {count} points"),
+            UiLanguage::Ru => format!("Вставленные данные: {count} точек"),
+        });
+        ui.horizontal(|ui| {
+            let calibrated = self.calibration_ready();
+            if ui
+                .add_enabled(
+                    calibrated,
+                    egui::Button::new(i18n.text(TextKey::AppendPastedPoints)),
+                )
+                .on_hover_text(i18n.text(TextKey::AppendPastedPointsHover))
+                .on_disabled_hover_text(i18n.text(TextKey::AppendPastedPointsDisabledHover))
+                .clicked()
+            {
+                self.append_imported_points();
+            }
+            if ui
+                .button(i18n.text(TextKey::ClearPastedPoints))
+                .on_hover_text(i18n.text(TextKey::ClearPastedPointsHover))
+                .clicked()
+            {
+                self.clear_imported_points();
+            }
+        });
+        let can_fit = self.calibration.coord_system == CoordSystem::Cartesian
+            && self.calibration_ready()
+            && self.points.points.len() >= 2;
+        if ui
+            .add_enabled(
+                can_fit,
+                egui::Button::new(i18n.text(TextKey::FitCalibrationToReference)),
+            )
+            .on_hover_text(i18n.text(TextKey::FitCalibrationToReferenceHover))
+            .on_disabled_hover_text(i18n.text(TextKey::FitCalibrationToReferenceDisabledHover))
+            .clicked()
+        {
+            self.recalibrate_to_reference();
+        }
+    }
+
+    fn ui_curve_mask_controls(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    self.image.image.is_some(),
+                    egui::Button::new(i18n.text(TextKey::LoadCurveMask)),
+                )
+                .on_hover_text(i18n.text(TextKey::LoadCurveMaskHover))
+                .clicked()
+            {
+                self.open_curve_mask_dialog();
+            }
+            #[cfg(feature = "segmentation")]
+            {
+                let running = self.snap.pending_mask_job.is_some();
+                if ui
+                    .add_enabled(
+                        self.image.image.is_some() && !running,
+                        egui::Button::new(i18n.text(TextKey::RunSegmentationModel)),
+                    )
+                    .on_hover_text(i18n.text(TextKey::RunSegmentationModelHover))
+                    .clicked()
+                {
+                    self.open_segmentation_model_dialog();
+                }
+                if running {
+                    ui.spinner();
+                }
+            }
+            if ui
+                .add_enabled(
+                    self.snap.curve_mask.is_some(),
+                    egui::Button::new(i18n.text(TextKey::ClearCurveMask)),
+                )
+                .clicked()
+            {
+                self.clear_curve_mask();
+            }
+        });
+        if self.snap.curve_mask.is_none() {
+            ui.label(
+                RichText::new(i18n.text(TextKey::CurveMaskMissing))
+                    .small()
+                    .weak(),
+            );
+        }
+        if ui
+            .checkbox(
+                &mut self.snap.ignore_text,
+                i18n.text(TextKey::IgnoreTextLabels),
+            )
+            .on_hover_text(i18n.text(TextKey::IgnoreTextLabelsHover))
+            .changed()
+        {
+            self.mark_snap_maps_dirty();
+        }
+        if ui
+            .checkbox(
+                &mut self.snap.suppress_grid,
+                i18n.text(TextKey::SuppressGridLines),
+            )
+            .on_hover_text(i18n.text(TextKey::SuppressGridLinesHover))
+            .changed()
+        {
+            self.mark_snap_maps_dirty();
+        }
+        ui.checkbox(
+            &mut self.snap.bridge_gaps,
+            i18n.text(TextKey::BridgeDashGaps),
+        )
+        .on_hover_text(i18n.text(TextKey::BridgeDashGapsHover));
+    }
+
+    fn ui_snap_area_controls(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    self.image.image.is_some(),
+                    egui::Button::new(i18n.text(TextKey::MarkSnapArea)),
+                )
+                .on_hover_text(i18n.text(TextKey::MarkSnapAreaHover))
+                .clicked()
+            {
+                self.begin_snap_area_pick();
+            }
+            if ui
+                .add_enabled(
+                    self.snap.search_area.is_some(),
+                    egui::Button::new(i18n.text(TextKey::ClearSnapArea)),
+                )
+                .clicked()
+            {
+                self.clear_snap_area();
+            }
+        });
     }
 
     fn ui_snap_radius_slider(&mut self, ui: &mut egui::Ui) {
@@ -166,6 +406,31 @@ impl CurcatApp {
             {
                 self.begin_pick_mode(PickMode::CurveColor);
             }
+            let current = self.snap.snap_target_color;
+            let picked = ui
+                .menu_button(i18n.text(TextKey::Swatches), |ui| {
+                    self.ui_swatch_menu_contents(ui, current)
+                })
+                .inner
+                .flatten();
+            if let Some(color) = picked {
+                self.apply_swatch_to_snap_target(color);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::ColorSampling))
+                .on_hover_text(i18n.text(TextKey::ColorSamplingHover));
+            egui::ComboBox::from_id_salt("color_sample_mode")
+                .selected_text(i18n.color_sample_mode_label(self.snap.color_sample_mode))
+                .show_ui(ui, |ui| {
+                    for variant in ColorSampleMode::ALL {
+                        ui.selectable_value(
+                            &mut self.snap.color_sample_mode,
+                            variant,
+                            i18n.color_sample_mode_label(variant),
+                        );
+                    }
+                });
         });
         let tol_resp = ui
             .add(
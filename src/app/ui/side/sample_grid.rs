@@ -0,0 +1,133 @@
+use crate::app::{CurcatApp, parse_sample_xs};
+use crate::i18n::TextKey;
+use crate::interp::SampleGrid;
+use egui::RichText;
+
+impl CurcatApp {
+    /// Sample grid picker of the interpolated and fitted exports, with the editors of the
+    /// chosen grid.
+    pub(super) fn ui_export_sample_grid(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::SampleGridLabel))
+                .on_hover_text(i18n.text(TextKey::SampleGridHover));
+            egui::ComboBox::from_id_salt("sample_grid_combo")
+                .selected_text(i18n.sample_grid_label(self.export.sample_grid))
+                .show_ui(ui, |ui| {
+                    for grid in SampleGrid::ALL {
+                        ui.selectable_value(
+                            &mut self.export.sample_grid,
+                            grid,
+                            i18n.sample_grid_label(grid),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(i18n.text(TextKey::SampleGridHover));
+        });
+        match self.export.sample_grid {
+            SampleGrid::Even => {
+                self.ui_export_sample_count(ui);
+                return;
+            }
+            SampleGrid::List => {
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.export.sample_xs_text)
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("0, 0.5, 1, 2, 5"),
+                )
+                .on_hover_text(i18n.text(TextKey::SampleXsHover));
+                if let Err(entry) =
+                    parse_sample_xs(&self.export.sample_xs_text, self.export_x_unit())
+                {
+                    ui.label(
+                        RichText::new(i18n.format_unreadable_sample_x(&entry))
+                            .color(ui.visuals().error_fg_color),
+                    );
+                    return;
+                }
+            }
+            SampleGrid::Uniform | SampleGrid::LogSpaced => self.ui_grid_range(ui),
+        }
+        if self.export.sample_grid == SampleGrid::LogSpaced {
+            self.ui_export_sample_count(ui);
+        }
+        self.ui_grid_summary(ui);
+    }
+
+    /// Start, stop and (for the uniform grid) step editors.
+    fn ui_grid_range(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let points = self.curve_points(self.points.curves.active);
+        let Some([mut from, mut to]) = self.grid_range(points) else {
+            return;
+        };
+        let mut step = self.grid_step([from, to]);
+        let span = (to - from).abs();
+        let speed = if span.is_finite() && span > 0.0 {
+            span / 500.0
+        } else {
+            0.01
+        };
+        let mut range_changed = false;
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::GridFrom));
+            range_changed |= ui
+                .add(egui::DragValue::new(&mut from).speed(speed))
+                .changed();
+            ui.label(i18n.text(TextKey::GridTo));
+            range_changed |= ui.add(egui::DragValue::new(&mut to).speed(speed)).changed();
+            if ui
+                .add_enabled(
+                    self.export.grid_range.is_some(),
+                    egui::Button::new(i18n.text(TextKey::GridCurveRange)),
+                )
+                .on_hover_text(i18n.text(TextKey::GridCurveRangeHover))
+                .clicked()
+            {
+                self.export.grid_range = None;
+            }
+        });
+        if range_changed {
+            self.export.grid_range = Some([from, to]);
+        }
+        if self.export.sample_grid == SampleGrid::Uniform {
+            ui.horizontal(|ui| {
+                ui.label(i18n.text(TextKey::GridStep));
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut step)
+                            .speed(speed)
+                            .range(f64::MIN_POSITIVE..=f64::INFINITY),
+                    )
+                    .changed()
+                {
+                    self.export.grid_step = Some(step);
+                }
+            });
+        } else if !(from > 0.0 && to > 0.0) {
+            ui.label(
+                RichText::new(i18n.text(TextKey::GridLogNeedsPositive))
+                    .color(ui.visuals().warn_fg_color),
+            );
+        }
+    }
+
+    /// How many samples the grid gives on the active curve.
+    fn ui_grid_summary(&self, ui: &mut egui::Ui) {
+        let points = self.curve_points(self.points.curves.active);
+        let Some(xs) = self.sample_grid_xs(points) else {
+            return;
+        };
+        let nums = Self::collect_numeric_points_in_order(points);
+        let (lo, hi) = nums
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+                (lo.min(p.x), hi.max(p.x))
+            });
+        let outside = xs.iter().filter(|x| !(lo..=hi).contains(*x)).count();
+        let samples = self.interpolated_export_rows(points).len();
+        ui.label(RichText::new(self.i18n().format_grid_samples(samples, outside)).weak());
+    }
+}
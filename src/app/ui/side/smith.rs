@@ -0,0 +1,111 @@
+use super::super::icons;
+use crate::app::{CurcatApp, PickMode};
+use crate::i18n::TextKey;
+use crate::types::SmithMappingError;
+use egui::{Color32, RichText};
+
+const fn fit_error_key(err: SmithMappingError) -> TextKey {
+    match err {
+        SmithMappingError::ZeroRadius => TextKey::SmithZeroRadius,
+        SmithMappingError::NonPositiveReference | SmithMappingError::NonFiniteInput => {
+            TextKey::SmithBadReference
+        }
+    }
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_smith_calibration(&mut self, ui: &mut egui::Ui) {
+        let has_image = self.image.image.is_some();
+        let pick_mode = self.calibration.pick_mode;
+        let mut requested_pick = None;
+        let mut attention = Vec::new();
+        let mut reference_changed = false;
+        egui::Grid::new("smith_calibration_points")
+            .num_columns(2)
+            .spacing([6.0, 4.0])
+            .show(ui, |ui| {
+                let smith = &self.calibration.smith_cal;
+                for (mode, placed, label, hover) in [
+                    (
+                        PickMode::SmithCenter,
+                        smith.center,
+                        TextKey::SmithCenter,
+                        TextKey::PickSmithCenterHover,
+                    ),
+                    (
+                        PickMode::SmithShort,
+                        smith.short,
+                        TextKey::SmithShort,
+                        TextKey::PickSmithShortHover,
+                    ),
+                ] {
+                    ui.label(self.t(label));
+                    let hover = self.t(hover);
+                    let pick = ui
+                        .add_enabled(
+                            has_image,
+                            egui::Button::image(icons::image(
+                                icons::ICON_PICK_POINT,
+                                icons::BUTTON_ICON_SIZE,
+                            ))
+                            .selected(pick_mode == mode)
+                            .image_tint_follows_text_color(true),
+                        )
+                        .on_hover_text(placed.map_or_else(
+                            || hover.to_string(),
+                            |p| format!("{hover}\n@ ({:.1}, {:.1})", p.x, p.y),
+                        ));
+                    if pick.clicked() {
+                        requested_pick = Some(mode);
+                    }
+                    attention.push((pick.rect, placed.is_none()));
+                    ui.end_row();
+                }
+                ui.label(self.t(TextKey::ReferenceImpedance))
+                    .on_hover_text(self.t(TextKey::ReferenceImpedanceHover));
+                reference_changed = ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.calibration.smith_cal.reference_text)
+                            .desired_width(72.0),
+                    )
+                    .changed();
+                ui.end_row();
+            });
+
+        if let Some(mode) = requested_pick {
+            self.begin_pick_mode(mode);
+        }
+        if reference_changed {
+            self.mark_points_dirty();
+        }
+        for (rect, active) in attention {
+            self.paint_attention_outline_if(ui, rect, active);
+        }
+
+        let cal = &self.calibration.smith_cal;
+        let status =
+            if cal.center.is_some() && cal.short.is_some() && cal.reference_ohms().is_none() {
+                Some(Err(SmithMappingError::NonPositiveReference))
+            } else {
+                cal.fit()
+            };
+        match status {
+            Some(Ok(mapping)) => {
+                ui.label(
+                    RichText::new(format!(
+                        "{}: {:.1} px",
+                        self.t(TextKey::SmithChartRadius),
+                        mapping.radius()
+                    ))
+                    .color(Color32::GREEN),
+                );
+            }
+            Some(Err(err)) => {
+                ui.label(RichText::new(self.t(fit_error_key(err))).color(Color32::GRAY));
+            }
+            None => {
+                ui.label(RichText::new(self.t(TextKey::SmithFitMissing)).color(Color32::GRAY));
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::app::CurcatApp;
+use crate::i18n::TextKey;
+use crate::image::StitchMode;
+use egui::RichText;
+
+enum StitchPartAction {
+    MoveUp(usize),
+    MoveDown(usize),
+    Remove(usize),
+}
+
+impl CurcatApp {
+    pub(crate) fn ui_stitch_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.stitch_window_open {
+            return;
+        }
+        let mut open = self.ui.stitch_window_open;
+        egui::Window::new(self.t(TextKey::StitchScansWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| self.ui_stitch_section(ui));
+        self.ui.stitch_window_open = open && self.ui.stitch_window_open;
+    }
+
+    fn ui_stitch_section(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::StitchScansIntro)).small());
+        ui.add_space(4.0);
+
+        let running = self.project.stitch.job.is_some();
+        let count = self.project.stitch.parts.len();
+        let mut action = None;
+        egui::Grid::new("stitch_parts_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (idx, path) in self.project.stitch.parts.iter().enumerate() {
+                    let name = path.file_name().map_or_else(
+                        || path.display().to_string(),
+                        |s| s.to_string_lossy().into(),
+                    );
+                    ui.label(format!("{}.", idx + 1));
+                    ui.label(name).on_hover_text(path.display().to_string());
+                    ui.horizontal(|ui| {
+                        ui.add_enabled_ui(!running, |ui| {
+                            if ui.add_enabled(idx > 0, egui::Button::new("↑")).clicked() {
+                                action = Some(StitchPartAction::MoveUp(idx));
+                            }
+                            if ui
+                                .add_enabled(idx + 1 < count, egui::Button::new("↓"))
+                                .clicked()
+                            {
+                                action = Some(StitchPartAction::MoveDown(idx));
+                            }
+                            if ui.button("✖").clicked() {
+                                action = Some(StitchPartAction::Remove(idx));
+                            }
+                        });
+                    });
+                    ui.end_row();
+                }
+            });
+        match action {
+            Some(StitchPartAction::MoveUp(idx)) => self.move_stitch_part(idx, idx - 1),
+            Some(StitchPartAction::MoveDown(idx)) => self.move_stitch_part(idx, idx + 1),
+            Some(StitchPartAction::Remove(idx)) => {
+                self.project.stitch.parts.remove(idx);
+            }
+            None => {}
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(i18n.text(TextKey::StitchAlignment))
+                .on_hover_text(i18n.text(TextKey::StitchAlignmentHover));
+            egui::ComboBox::from_id_salt("stitch_mode_combo")
+                .selected_text(i18n.stitch_mode_label(self.project.stitch.mode))
+                .show_ui(ui, |ui| {
+                    for mode in StitchMode::ALL {
+                        ui.selectable_value(
+                            &mut self.project.stitch.mode,
+                            mode,
+                            i18n.stitch_mode_label(mode),
+                        );
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!running, egui::Button::new(i18n.text(TextKey::AddScans)))
+                .clicked()
+            {
+                self.open_stitch_scans_dialog();
+            }
+            if ui
+                .add_enabled(
+                    !running && count >= 2,
+                    egui::Button::new(i18n.text(TextKey::StitchAction)),
+                )
+                .on_hover_text(i18n.text(TextKey::StitchActionHover))
+                .clicked()
+            {
+                self.start_stitch_job();
+            }
+            if running {
+                ui.spinner();
+            }
+        });
+    }
+}
@@ -0,0 +1,75 @@
+use super::super::CurcatApp;
+use super::super::image_loader::SVG_SCALE_RANGE;
+use crate::i18n::TextKey;
+use crate::image::svg_scaled_size;
+
+const SVG_SCALE_PRESETS: [u32; 4] = [100, 200, 300, 400];
+
+impl CurcatApp {
+    pub(crate) fn ui_svg_scale_prompt(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = self.project.pending_svg_scale_prompt.as_ref() else {
+            return;
+        };
+        let i18n = self.i18n();
+        let source_label = prompt.meta.description();
+        let [source_w, source_h] = prompt.source_size;
+        let mut scale = prompt.scale_percent;
+
+        let mut confirm = false;
+        let mut cancel = false;
+        let mut open = true;
+
+        egui::Window::new(i18n.text(TextKey::SvgImportWindow))
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(i18n.text(TextKey::SvgImportIntro));
+                ui.add_space(6.0);
+                ui.label(format!(
+                    "{}: {source_label} — {source_w}x{source_h} px",
+                    i18n.text(TextKey::SvgIntrinsicSize)
+                ));
+                ui.horizontal(|ui| {
+                    ui.label(i18n.text(TextKey::SvgRasterScale));
+                    ui.add(
+                        egui::DragValue::new(&mut scale)
+                            .range(SVG_SCALE_RANGE)
+                            .speed(5)
+                            .suffix("%"),
+                    );
+                    for preset in SVG_SCALE_PRESETS {
+                        if ui
+                            .selectable_label(scale == preset, format!("{preset}%"))
+                            .clicked()
+                        {
+                            scale = preset;
+                        }
+                    }
+                });
+                let [out_w, out_h] = svg_scaled_size([source_w, source_h], scale);
+                ui.label(format!(
+                    "{}: {out_w}x{out_h} px",
+                    i18n.text(TextKey::SvgRasterSize)
+                ));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(i18n.text(TextKey::Cancel)).clicked() {
+                        cancel = true;
+                    }
+                    if ui.button(i18n.text(TextKey::ImportSvg)).clicked() {
+                        confirm = true;
+                    }
+                });
+            });
+
+        if let Some(prompt) = self.project.pending_svg_scale_prompt.as_mut() {
+            prompt.scale_percent = scale;
+        }
+        if confirm {
+            self.confirm_svg_scale();
+        } else if cancel || !open {
+            self.cancel_svg_scale_prompt();
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use super::icons;
+use crate::app::CurcatApp;
+use crate::i18n::TextKey;
+use egui::{Color32, Sense, Stroke, StrokeKind, vec2};
+
+const SWATCH_CHIP_SIZE: f32 = 14.0;
+
+enum SwatchAction {
+    Apply(Color32),
+    Remove(usize),
+    Save,
+}
+
+fn swatch_chip(ui: &mut egui::Ui, color: Color32) {
+    let (rect, _) =
+        ui.allocate_exact_size(vec2(SWATCH_CHIP_SIZE, SWATCH_CHIP_SIZE), Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, color);
+    ui.painter().rect_stroke(
+        rect,
+        2.0,
+        Stroke::new(1.0_f32, ui.visuals().weak_text_color()),
+        StrokeKind::Inside,
+    );
+}
+
+impl CurcatApp {
+    /// Swatch list plus "save `current` as…"; returns the swatch the user picked.
+    ///
+    /// Meant for a menu or context menu body; closes it once a swatch is chosen.
+    pub(crate) fn ui_swatch_menu_contents(
+        &mut self,
+        ui: &mut egui::Ui,
+        current: Color32,
+    ) -> Option<Color32> {
+        let i18n = self.i18n();
+        let mut action = None;
+        if self.project.swatches.is_empty() {
+            ui.label(egui::RichText::new(i18n.text(TextKey::NoSwatches)).weak());
+        }
+        for (idx, swatch) in self.project.swatches.iter().enumerate() {
+            ui.horizontal(|ui| {
+                swatch_chip(ui, swatch.color);
+                if ui
+                    .selectable_label(swatch.color == current, &swatch.name)
+                    .on_hover_text(i18n.text(TextKey::ApplySwatchHover))
+                    .clicked()
+                {
+                    action = Some(SwatchAction::Apply(swatch.color));
+                }
+                if ui
+                    .add(
+                        egui::Button::image(icons::image(
+                            icons::ICON_CLOSE,
+                            icons::INLINE_ICON_SIZE,
+                        ))
+                        .frame(false)
+                        .image_tint_follows_text_color(true),
+                    )
+                    .on_hover_text(i18n.text(TextKey::RemoveSwatchHover))
+                    .clicked()
+                {
+                    action = Some(SwatchAction::Remove(idx));
+                }
+            });
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            swatch_chip(ui, current);
+            let name_resp = ui.add(
+                egui::TextEdit::singleline(&mut self.ui.swatch_name_draft)
+                    .hint_text(i18n.text(TextKey::SwatchNameHint))
+                    .desired_width(120.0),
+            );
+            let submitted = name_resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let can_save = !self.ui.swatch_name_draft.trim().is_empty();
+            let save = ui
+                .add_enabled(can_save, egui::Button::new(i18n.text(TextKey::SaveSwatch)))
+                .on_hover_text(i18n.text(TextKey::SaveSwatchHover));
+            if save.clicked() || (submitted && can_save) {
+                action = Some(SwatchAction::Save);
+            }
+        });
+
+        match action? {
+            SwatchAction::Apply(color) => {
+                ui.close();
+                Some(color)
+            }
+            SwatchAction::Remove(idx) => {
+                self.remove_swatch(idx);
+                None
+            }
+            SwatchAction::Save => {
+                let name = std::mem::take(&mut self.ui.swatch_name_draft);
+                self.add_swatch(&name, current);
+                None
+            }
+        }
+    }
+}
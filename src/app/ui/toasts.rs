@@ -0,0 +1,94 @@
+use super::super::{CurcatApp, StatusLevel, StatusMessage};
+use crate::i18n::TextKey;
+use egui::{Align2, RichText};
+use std::time::Instant;
+
+/// Older toasts are dropped once this many are on screen.
+const MAX_TOASTS: usize = 4;
+
+impl CurcatApp {
+    /// Show a transient notification above the status bar (errors stay until clicked).
+    pub(crate) fn push_toast(&mut self, level: StatusLevel, msg: impl Into<String>) {
+        self.push_toast_with_details(level, msg, None);
+    }
+
+    /// A toast with `details` folded under "Show details", for the error chain behind `msg`.
+    pub(crate) fn push_toast_with_details(
+        &mut self,
+        level: StatusLevel,
+        msg: impl Into<String>,
+        details: Option<String>,
+    ) {
+        if self.ui.toasts.len() >= MAX_TOASTS {
+            self.ui.toasts.remove(0);
+        }
+        self.ui.toasts.push(StatusMessage {
+            text: msg.into(),
+            level,
+            details,
+            created_at: Instant::now(),
+        });
+    }
+
+    pub(crate) fn ui_toasts(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        self.ui.toasts.retain(|toast| {
+            Self::status_ttl(toast.level)
+                .is_none_or(|ttl| now.saturating_duration_since(toast.created_at) < ttl)
+        });
+        if self.ui.toasts.is_empty() {
+            return;
+        }
+        if let Some(remaining) = self
+            .ui
+            .toasts
+            .iter()
+            .filter_map(|toast| {
+                Self::status_ttl(toast.level)?
+                    .checked_sub(now.saturating_duration_since(toast.created_at))
+            })
+            .min()
+        {
+            ctx.request_repaint_after(remaining);
+        }
+
+        let i18n = self.i18n();
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -36.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ctx, |ui| {
+                for (idx, toast) in self.ui.toasts.iter().enumerate().rev() {
+                    let resp = egui::Frame::popup(ui.style())
+                        .show(ui, |ui| {
+                            ui.set_max_width(320.0);
+                            ui.label(
+                                RichText::new(&toast.text).color(Self::status_color(toast.level)),
+                            );
+                            if let Some(details) = &toast.details {
+                                egui::CollapsingHeader::new(
+                                    RichText::new(i18n.text(TextKey::ShowDetails)).small(),
+                                )
+                                .id_salt(("toast_details", toast.created_at))
+                                .show(ui, |ui| {
+                                    ui.label(RichText::new(details).small().monospace());
+                                    if ui.small_button(i18n.text(TextKey::CopyDetails)).clicked() {
+                                        ui.ctx().copy_text(details.clone());
+                                    }
+                                });
+                            }
+                        })
+                        .response
+                        .interact(egui::Sense::click());
+                    if resp.clicked() {
+                        dismissed = Some(idx);
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+        if let Some(idx) = dismissed {
+            self.ui.toasts.remove(idx);
+        }
+    }
+}
@@ -1,7 +1,9 @@
-use super::super::CurcatApp;
+use super::super::{CurcatApp, PickMode};
 use super::common::toggle_switch;
 use super::icons;
+use crate::config::DialogBackend;
 use crate::i18n::{TextKey, UiLanguage};
+use crate::keymap::ShortcutAction;
 use egui::containers::menu::MenuButton;
 
 impl CurcatApp {
@@ -23,8 +25,9 @@ impl CurcatApp {
             self.ui_side_toggle(ui);
             Self::top_bar_separator(ui);
 
+            let editable = !self.view_only();
             self.ui_appearance_menu(ui, has_image);
-            self.ui_transform_buttons(ui, has_image);
+            self.ui_transform_buttons(ui, has_image && editable);
 
             let has_points = !self.points.points.is_empty();
             self.ui_zoom_controls(ui);
@@ -33,7 +36,7 @@ impl CurcatApp {
             self.ui_middle_pan_toggle(ui);
             Self::top_bar_separator(ui);
 
-            self.ui_point_edit_buttons(ui, has_points);
+            self.ui_point_edit_buttons(ui, has_points && editable);
             ui.add_space(2.0);
         });
         ui.add_space(1.0);
@@ -70,23 +73,28 @@ impl CurcatApp {
         response.on_hover_text(self.t(TextKey::LanguageSwitcherHover));
     }
 
+    #[allow(clippy::too_many_lines)]
     fn ui_file_menu(&mut self, ui: &mut egui::Ui, can_save_project: bool) -> egui::Response {
         let button = egui::Button::image_and_text(
             icons::image(icons::ICON_MENU, icons::BUTTON_ICON_SIZE),
             self.t(TextKey::File),
         )
         .image_tint_follows_text_color(true);
+        let editable = !self.view_only();
         let (response, _) = MenuButton::from_button(button).ui(ui, |ui| {
             if ui
-                .add(
+                .add_enabled(
+                    editable,
                     egui::Button::image_and_text(
                         icons::image(icons::ICON_OPEN_IMAGE, icons::BUTTON_ICON_SIZE),
                         self.t(TextKey::OpenImage),
                     )
                     .image_tint_follows_text_color(true)
-                    .shortcut_text("Ctrl+O"),
+                    .shortcut_text(self.shortcut_label(ShortcutAction::OpenImage)),
+                )
+                .on_hover_text(
+                    self.hover_with_shortcut(TextKey::OpenImageHover, ShortcutAction::OpenImage),
                 )
-                .on_hover_text(self.t(TextKey::OpenImageHover))
                 .clicked()
             {
                 self.open_image_dialog();
@@ -94,21 +102,62 @@ impl CurcatApp {
             }
 
             if ui
-                .add(
+                .add_enabled(
+                    editable,
                     egui::Button::image_and_text(
                         icons::image(icons::ICON_PASTE_IMAGE, icons::BUTTON_ICON_SIZE),
                         self.t(TextKey::PasteImage),
                     )
                     .image_tint_follows_text_color(true)
-                    .shortcut_text("Ctrl+V"),
+                    .shortcut_text(self.shortcut_label(ShortcutAction::PasteImage)),
+                )
+                .on_hover_text(
+                    self.hover_with_shortcut(TextKey::PasteImageHover, ShortcutAction::PasteImage),
                 )
-                .on_hover_text(self.t(TextKey::PasteImageHover))
                 .clicked()
             {
                 self.paste_image_from_clipboard(ui.ctx());
                 ui.close();
             }
 
+            if ui
+                .add_enabled(
+                    editable,
+                    egui::Button::image_and_text(
+                        icons::image(icons::ICON_STITCH_SCANS, icons::BUTTON_ICON_SIZE),
+                        self.t(TextKey::StitchScans),
+                    )
+                    .image_tint_follows_text_color(true),
+                )
+                .on_hover_text(self.t(TextKey::StitchScansHover))
+                .clicked()
+            {
+                self.ui.stitch_window_open = true;
+                ui.close();
+            }
+
+            if ui
+                .add_enabled(
+                    editable && self.image.image.is_some(),
+                    egui::Button::image_and_text(
+                        icons::image(icons::ICON_PASTE_IMAGE, icons::BUTTON_ICON_SIZE),
+                        self.t(TextKey::PastePoints),
+                    )
+                    .image_tint_follows_text_color(true)
+                    .shortcut_text(self.shortcut_label(ShortcutAction::PastePoints)),
+                )
+                .on_hover_text(
+                    self.hover_with_shortcut(
+                        TextKey::PastePointsHover,
+                        ShortcutAction::PastePoints,
+                    ),
+                )
+                .clicked()
+            {
+                self.paste_points_from_clipboard();
+                ui.close();
+            }
+
             ui.separator();
 
             if ui
@@ -118,9 +167,14 @@ impl CurcatApp {
                         self.t(TextKey::LoadProject),
                     )
                     .image_tint_follows_text_color(true)
-                    .shortcut_text("Ctrl+Shift+P"),
+                    .shortcut_text(self.shortcut_label(ShortcutAction::OpenProject)),
+                )
+                .on_hover_text(
+                    self.hover_with_shortcut(
+                        TextKey::LoadProjectHover,
+                        ShortcutAction::OpenProject,
+                    ),
                 )
-                .on_hover_text(self.t(TextKey::LoadProjectHover))
                 .clicked()
             {
                 self.open_project_dialog();
@@ -129,24 +183,126 @@ impl CurcatApp {
 
             if ui
                 .add_enabled(
-                    can_save_project,
+                    editable && can_save_project,
                     egui::Button::image_and_text(
                         icons::image(icons::ICON_SAVE_PROJECT, icons::BUTTON_ICON_SIZE),
                         self.t(TextKey::SaveProject),
                     )
                     .image_tint_follows_text_color(true)
-                    .shortcut_text("Ctrl+S"),
+                    .shortcut_text(self.shortcut_label(ShortcutAction::SaveProject)),
+                )
+                .on_hover_text(
+                    self.hover_with_shortcut(
+                        TextKey::SaveProjectHover,
+                        ShortcutAction::SaveProject,
+                    ),
                 )
-                .on_hover_text(self.t(TextKey::SaveProjectHover))
                 .clicked()
             {
                 self.save_project_dialog();
                 ui.close();
             }
+
+            ui.add_enabled_ui(editable && self.image.image.is_some(), |ui| {
+                ui.menu_button(self.t(TextKey::ProjectSettings), |ui| {
+                    self.ui_project_settings_menu(ui);
+                })
+                .response
+                .on_hover_text(self.t(TextKey::ProjectSettingsHover));
+            });
+
+            ui.separator();
+            if ui
+                .button(self.t(TextKey::KeyboardShortcuts))
+                .on_hover_text(self.t(TextKey::KeyboardShortcutsHover))
+                .clicked()
+            {
+                self.ui.keymap_window_open = true;
+                ui.close();
+            }
+            self.ui_dialog_backend_menu(ui);
+            #[cfg(feature = "profiling")]
+            if ui
+                .button(self.t(TextKey::Profiler))
+                .on_hover_text(self.t(TextKey::ProfilerHover))
+                .clicked()
+            {
+                self.toggle_profiler_window();
+                ui.close();
+            }
         });
         response
     }
 
+    /// Builds with neither system nor portal dialogs have nothing to switch.
+    fn ui_dialog_backend_menu(&mut self, ui: &mut egui::Ui) {
+        let choices = DialogBackend::choices();
+        if choices.len() < 2 {
+            return;
+        }
+        let i18n = self.i18n();
+        let label = |backend: DialogBackend| match backend {
+            DialogBackend::Auto => i18n.text(TextKey::FileDialogsAuto),
+            DialogBackend::Builtin => i18n.text(TextKey::FileDialogsBuiltin),
+            DialogBackend::Native => i18n.text(TextKey::FileDialogsNative),
+            DialogBackend::Portal => i18n.text(TextKey::FileDialogsPortal),
+        };
+        // A backend this build lacks shows as the one standing in for it.
+        let current = if choices.contains(&self.config.dialogs.backend) {
+            self.config.dialogs.backend
+        } else {
+            self.config.dialogs.resolved_backend()
+        };
+        let mut backend = current;
+        ui.menu_button(i18n.text(TextKey::FileDialogs), |ui| {
+            for option in choices {
+                ui.selectable_value(&mut backend, option, label(option));
+            }
+        })
+        .response
+        .on_hover_text(i18n.text(TextKey::FileDialogsHover));
+        if backend != current {
+            self.set_dialog_backend(backend);
+        }
+    }
+
+    fn ui_project_settings_menu(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let mut styles = self.project.config_overrides.has_styles();
+        if ui
+            .checkbox(&mut styles, i18n.text(TextKey::KeepStylesInProject))
+            .on_hover_text(i18n.text(TextKey::KeepStylesInProjectHover))
+            .changed()
+        {
+            self.set_style_overrides(styles);
+        }
+        let mut auto_place = self.project.config_overrides.auto_place.is_some();
+        if ui
+            .checkbox(&mut auto_place, i18n.text(TextKey::KeepAutoPlaceInProject))
+            .on_hover_text(i18n.text(TextKey::KeepAutoPlaceInProjectHover))
+            .changed()
+        {
+            self.set_auto_place_override(auto_place);
+        }
+        let mut export = self.project.config_overrides.export.is_some();
+        if ui
+            .checkbox(&mut export, i18n.text(TextKey::KeepExportInProject))
+            .on_hover_text(i18n.text(TextKey::KeepExportInProjectHover))
+            .changed()
+        {
+            self.set_export_override(export);
+        }
+        ui.separator();
+        if ui
+            .button(i18n.text(TextKey::ChartTextMenu))
+            .on_hover_text(i18n.text(TextKey::ChartTextMenuHover))
+            .clicked()
+        {
+            self.ui.chart_text_window_open = true;
+            ui.close();
+        }
+    }
+
     fn ui_side_toggle(&mut self, ui: &mut egui::Ui) {
         let side_label = if self.ui.side_open {
             self.t(TextKey::HideSide)
@@ -158,7 +314,7 @@ impl CurcatApp {
             side_label,
         )
         .image_tint_follows_text_color(true)
-        .shortcut_text("Ctrl+B");
+        .shortcut_text(self.shortcut_label(ShortcutAction::ToggleSidePanel));
         let (response, _) = MenuButton::from_button(button).ui(ui, |ui| {
             let toggle_label = if self.ui.side_open {
                 self.t(TextKey::HideSidePanel)
@@ -187,7 +343,32 @@ impl CurcatApp {
                 ui.close();
             }
         });
-        response.on_hover_text(self.t(TextKey::ToggleSidePanelHover));
+        response.on_hover_text(self.hover_with_shortcut(
+            TextKey::ToggleSidePanelHover,
+            ShortcutAction::ToggleSidePanel,
+        ));
+    }
+
+    /// Overlays that help place points by hand: focus dimming and the loupe.
+    fn ui_picking_aid_toggles(&mut self, ui: &mut egui::Ui) {
+        let focus_label = self.t(TextKey::FocusMode);
+        let focus_hover = self.t(TextKey::FocusModeHover);
+        let loupe_label = self.t(TextKey::Loupe);
+        let loupe_hover = self.t(TextKey::LoupeHover);
+        Self::ui_toggle_menu_item(
+            ui,
+            &mut self.ui.focus_dim_enabled,
+            icons::ICON_FOCUS_DIM,
+            focus_label,
+            focus_hover,
+        );
+        Self::ui_toggle_menu_item(
+            ui,
+            &mut self.ui.loupe_enabled,
+            icons::ICON_ZOOM,
+            loupe_label,
+            loupe_hover,
+        );
     }
 
     fn ui_appearance_menu(&mut self, ui: &mut egui::Ui, has_image: bool) {
@@ -203,12 +384,24 @@ impl CurcatApp {
             .ui(ui, |ui| {
                 let points_label = self.t(TextKey::PointsStats);
                 let points_hover = self.t(TextKey::PointsStatsHover);
+                let flags_label = self.t(TextKey::FlaggedPoints);
+                let flags_hover = self.t(TextKey::FlaggedPointsHover);
+                let plot_label = self.t(TextKey::ResultPlot);
+                let plot_hover = self.t(TextKey::ResultPlotHover);
+                let sandbox_label = self.t(TextKey::CalibrationSandbox);
+                let sandbox_hover = self.t(TextKey::CalibrationSandboxHover);
                 let filters_label = self.t(TextKey::Filters);
-                let filters_hover = self.t(TextKey::FiltersHover);
+                let filters_hover =
+                    self.hover_with_shortcut(TextKey::FiltersHover, ShortcutAction::ImageFilters);
+                let preprocess_label = self.t(TextKey::Preprocess);
+                let preprocess_hover = self.t(TextKey::PreprocessHover);
                 let trace_label = self.t(TextKey::AutoTrace);
-                let trace_hover = self.t(TextKey::AutoTraceHover);
+                let trace_hover =
+                    self.hover_with_shortcut(TextKey::AutoTraceHover, ShortcutAction::AutoTrace);
                 let info_label = self.t(TextKey::ImageInfo);
-                let info_hover = self.t(TextKey::ImageInfoHover);
+                let info_hover =
+                    self.hover_with_shortcut(TextKey::ImageInfoHover, ShortcutAction::ImageInfo);
+                let editable = !self.view_only();
 
                 Self::ui_toggle_menu_item(
                     ui,
@@ -220,19 +413,58 @@ impl CurcatApp {
 
                 Self::ui_toggle_menu_item(
                     ui,
-                    &mut self.ui.image_filters_window_open,
-                    icons::ICON_FILTERS,
-                    filters_label,
-                    filters_hover,
+                    &mut self.ui.result_plot_open,
+                    icons::ICON_RESULT_PLOT,
+                    plot_label,
+                    plot_hover,
                 );
 
-                Self::ui_toggle_menu_item(
-                    ui,
-                    &mut self.ui.auto_trace_window_open,
-                    icons::ICON_AUTO_TRACE,
-                    trace_label,
-                    trace_hover,
-                );
+                // These windows edit the document, so view-only mode leaves them shut.
+                ui.add_enabled_ui(editable, |ui| {
+                    Self::ui_toggle_menu_item(
+                        ui,
+                        &mut self.ui.flag_review_window_open,
+                        icons::ICON_FLAG,
+                        flags_label,
+                        flags_hover,
+                    );
+
+                    Self::ui_toggle_menu_item(
+                        ui,
+                        &mut self.ui.calibration_sandbox_window_open,
+                        icons::ICON_SANDBOX,
+                        sandbox_label,
+                        sandbox_hover,
+                    );
+                });
+
+                self.ui_picking_aid_toggles(ui);
+
+                ui.add_enabled_ui(editable, |ui| {
+                    Self::ui_toggle_menu_item(
+                        ui,
+                        &mut self.ui.image_filters_window_open,
+                        icons::ICON_FILTERS,
+                        filters_label,
+                        &filters_hover,
+                    );
+
+                    Self::ui_toggle_menu_item(
+                        ui,
+                        &mut self.ui.preprocess_window_open,
+                        icons::ICON_PREPROCESS,
+                        preprocess_label,
+                        preprocess_hover,
+                    );
+
+                    Self::ui_toggle_menu_item(
+                        ui,
+                        &mut self.ui.auto_trace_window_open,
+                        icons::ICON_AUTO_TRACE,
+                        trace_label,
+                        &trace_hover,
+                    );
+                });
 
                 ui.add_enabled_ui(has_image || self.ui.info_window_open, |ui| {
                     Self::ui_toggle_menu_item(
@@ -240,7 +472,7 @@ impl CurcatApp {
                         &mut self.ui.info_window_open,
                         icons::ICON_INFO,
                         info_label,
-                        info_hover,
+                        &info_hover,
                     );
                 });
             });
@@ -269,7 +501,7 @@ impl CurcatApp {
         });
     }
 
-    fn ui_transform_buttons(&mut self, ui: &mut egui::Ui, has_image: bool) {
+    fn ui_transform_buttons(&mut self, ui: &mut egui::Ui, enabled: bool) {
         let info_hover = |ui: &mut egui::Ui, action: &str, title: &str| {
             ui.label(title);
             ui.label(action);
@@ -280,7 +512,7 @@ impl CurcatApp {
                            action: &str,
                            title: &str| {
             ui.add_enabled(
-                has_image,
+                enabled,
                 egui::Button::image_and_text(icons::image(icon, icons::BUTTON_ICON_SIZE), label)
                     .image_tint_follows_text_color(true),
             )
@@ -331,6 +563,39 @@ impl CurcatApp {
         {
             self.flip_image(false);
         }
+        if info_button(
+            ui,
+            icons::ICON_PERSPECTIVE,
+            self.t(TextKey::Perspective),
+            self.t(TextKey::PerspectiveAction),
+            self.t(TextKey::PerspectiveTogether),
+        )
+        .clicked()
+        {
+            self.begin_perspective_pick();
+        }
+        if info_button(
+            ui,
+            icons::ICON_LEVEL,
+            self.t(TextKey::LevelImage),
+            self.t(TextKey::LevelAction),
+            self.t(TextKey::LevelTogether),
+        )
+        .clicked()
+        {
+            self.begin_horizon_pick();
+        }
+        if info_button(
+            ui,
+            icons::ICON_CROP,
+            self.t(TextKey::CropToPlot),
+            self.t(TextKey::CropToPlotAction),
+            self.t(TextKey::CropToPlotTogether),
+        )
+        .clicked()
+        {
+            self.suggest_plot_crop(false);
+        }
     }
 
     fn ui_zoom_controls(&mut self, ui: &mut egui::Ui) {
@@ -347,14 +612,48 @@ impl CurcatApp {
                             self.t(TextKey::Fit),
                         )
                         .image_tint_follows_text_color(true)
-                        .shortcut_text("Ctrl+F"),
+                        .shortcut_text(self.shortcut_label(ShortcutAction::FitView)),
+                    )
+                    .on_hover_text(
+                        self.hover_with_shortcut(TextKey::FitHover, ShortcutAction::FitView),
                     )
-                    .on_hover_text(self.t(TextKey::FitHover))
                     .clicked()
                 {
                     self.fit_image_to_viewport();
                     ui.close();
                 }
+                if ui
+                    .add(
+                        egui::Button::image_and_text(
+                            icons::image(icons::ICON_FIT, icons::BUTTON_ICON_SIZE),
+                            self.t(TextKey::FitWidth),
+                        )
+                        .image_tint_follows_text_color(true)
+                        .shortcut_text(self.shortcut_label(ShortcutAction::FitWidth)),
+                    )
+                    .on_hover_text(
+                        self.hover_with_shortcut(TextKey::FitWidthHover, ShortcutAction::FitWidth),
+                    )
+                    .clicked()
+                {
+                    self.fit_image_width();
+                    ui.close();
+                }
+                if ui
+                    .add(
+                        egui::Button::image_and_text(
+                            icons::image(icons::ICON_ZOOM, icons::BUTTON_ICON_SIZE),
+                            self.t(TextKey::ZoomToRegion),
+                        )
+                        .image_tint_follows_text_color(true)
+                        .shortcut_text("Ctrl+drag"),
+                    )
+                    .on_hover_text(self.t(TextKey::ZoomToRegionHover))
+                    .clicked()
+                {
+                    self.begin_pick_mode(PickMode::ZoomRegion);
+                    ui.close();
+                }
                 if ui
                     .add(
                         egui::Button::image_and_text(
@@ -362,9 +661,14 @@ impl CurcatApp {
                             self.t(TextKey::ResetView),
                         )
                         .image_tint_follows_text_color(true)
-                        .shortcut_text("Ctrl+R"),
+                        .shortcut_text(self.shortcut_label(ShortcutAction::ResetView)),
+                    )
+                    .on_hover_text(
+                        self.hover_with_shortcut(
+                            TextKey::ResetViewHover,
+                            ShortcutAction::ResetView,
+                        ),
                     )
-                    .on_hover_text(self.t(TextKey::ResetViewHover))
                     .clicked()
                 {
                     self.reset_view();
@@ -396,7 +700,7 @@ impl CurcatApp {
         }
     }
 
-    fn ui_point_edit_buttons(&mut self, ui: &mut egui::Ui, has_points: bool) {
+    fn ui_point_edit_buttons(&mut self, ui: &mut egui::Ui, enabled: bool) {
         let button_height = ui.spacing().interact_size.y;
         let action_width = match self.ui.language {
             UiLanguage::En => 162.0,
@@ -404,32 +708,34 @@ impl CurcatApp {
         };
         let resp_clear = ui
             .add_enabled(
-                has_points,
+                enabled,
                 egui::Button::image_and_text(
                     icons::image(icons::ICON_CLEAR, icons::BUTTON_ICON_SIZE),
                     self.t(TextKey::ClearPoints),
                 )
                 .image_tint_follows_text_color(true)
-                .shortcut_text("Ctrl+Shift+D")
+                .shortcut_text(self.shortcut_label(ShortcutAction::ClearPoints))
                 .min_size(egui::vec2(action_width, button_height)),
             )
-            .on_hover_text(self.t(TextKey::ClearPointsHover));
+            .on_hover_text(
+                self.hover_with_shortcut(TextKey::ClearPointsHover, ShortcutAction::ClearPoints),
+            );
         if resp_clear.clicked() {
             self.clear_all_points();
         }
         ui.add_space(4.0);
         let resp_undo = ui
             .add_enabled(
-                has_points,
+                enabled,
                 egui::Button::image_and_text(
                     icons::image(icons::ICON_UNDO, icons::BUTTON_ICON_SIZE),
                     self.t(TextKey::Undo),
                 )
                 .image_tint_follows_text_color(true)
-                .shortcut_text("Ctrl+Z")
+                .shortcut_text(self.shortcut_label(ShortcutAction::Undo))
                 .min_size(egui::vec2(action_width, button_height)),
             )
-            .on_hover_text(self.t(TextKey::UndoHover));
+            .on_hover_text(self.hover_with_shortcut(TextKey::UndoHover, ShortcutAction::Undo));
         if resp_undo.clicked() {
             self.undo_last_point();
         }
@@ -0,0 +1,175 @@
+use crate::app::CurcatApp;
+use crate::i18n::{I18n, TextKey};
+use crate::validation::{RuleCheck, RuleColumn, RuleSeverity, ValidationRule, Violation};
+use egui::RichText;
+
+/// Violation rows listed before the rest are summarized.
+const MAX_LISTED_VIOLATIONS: usize = 100;
+
+impl CurcatApp {
+    pub(crate) fn ui_validation_window(&mut self, ctx: &egui::Context) {
+        if !self.ui.validation_window_open {
+            return;
+        }
+        let mut open = self.ui.validation_window_open;
+        egui::Window::new(self.t(TextKey::ValidationWindow))
+            .open(&mut open)
+            .resizable(true)
+            .collapsible(false)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                self.ui_validation_rules(ui);
+                ui.separator();
+                self.ui_validation_report(ui);
+            });
+        self.ui.validation_window_open = open;
+    }
+
+    fn ui_validation_rules(&mut self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        ui.label(RichText::new(i18n.text(TextKey::ValidationIntro)).small());
+        ui.add_space(4.0);
+        let mut changed = false;
+        let mut remove = None;
+        egui::Grid::new("validation_rules_grid")
+            .num_columns(4)
+            .show(ui, |ui| {
+                for (idx, rule) in self.project.validation_rules.iter_mut().enumerate() {
+                    changed |= rule_row(ui, i18n, idx, rule);
+                    if ui
+                        .small_button("✖")
+                        .on_hover_text(i18n.text(TextKey::RemoveValidationRule))
+                        .clicked()
+                    {
+                        remove = Some(idx);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(idx) = remove {
+            self.project.validation_rules.remove(idx);
+            changed = true;
+        }
+        if ui.button(i18n.text(TextKey::AddValidationRule)).clicked() {
+            self.project
+                .validation_rules
+                .push(ValidationRule::default());
+            changed = true;
+        }
+        // Rule indices of an old report no longer match the list.
+        if changed {
+            self.ui.validation_report = None;
+        }
+    }
+
+    fn ui_validation_report(&self, ui: &mut egui::Ui) {
+        let i18n = self.i18n();
+        let Some(violations) = self.ui.validation_report.as_ref() else {
+            ui.label(RichText::new(i18n.text(TextKey::ValidationNotRun)).small());
+            return;
+        };
+        if violations.is_empty() {
+            ui.label(RichText::new(i18n.text(TextKey::ValidationPassed)).small());
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .max_height(260.0)
+            .show(ui, |ui| {
+                egui::Grid::new("validation_report_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(i18n.text(TextKey::ViolationCurve)).strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ViolationRow)).strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ViolationValue)).strong());
+                        ui.label(RichText::new(i18n.text(TextKey::ViolationRule)).strong());
+                        ui.end_row();
+                        for violation in violations.iter().take(MAX_LISTED_VIOLATIONS) {
+                            self.violation_row(ui, violation);
+                        }
+                    });
+            });
+        let hidden = violations.len().saturating_sub(MAX_LISTED_VIOLATIONS);
+        if hidden > 0 {
+            ui.label(
+                RichText::new(format!("{} {hidden}", i18n.text(TextKey::MoreViolations))).small(),
+            );
+        }
+    }
+
+    fn violation_row(&self, ui: &mut egui::Ui, violation: &Violation) {
+        ui.label(&violation.curve);
+        ui.label((violation.row + 1).to_string());
+        ui.label(format!("{:.6}", violation.value));
+        match self.project.validation_rules.get(violation.rule) {
+            Some(rule) if rule.severity == RuleSeverity::Block => {
+                ui.colored_label(ui.visuals().error_fg_color, rule.summary());
+            }
+            Some(rule) => {
+                ui.colored_label(ui.visuals().warn_fg_color, rule.summary());
+            }
+            None => {
+                ui.label("—");
+            }
+        }
+        ui.end_row();
+    }
+}
+
+/// Column, check and severity editors of rule `idx`; returns whether anything changed.
+fn rule_row(ui: &mut egui::Ui, i18n: I18n, idx: usize, rule: &mut ValidationRule) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(("validation_rule_column", idx))
+        .width(40.0)
+        .selected_text(rule.column.label())
+        .show_ui(ui, |ui| {
+            for column in [RuleColumn::X, RuleColumn::Y] {
+                changed |= ui
+                    .selectable_value(&mut rule.column, column, column.label())
+                    .changed();
+            }
+        });
+    ui.horizontal(|ui| {
+        let bound = match rule.check {
+            RuleCheck::AtLeast(bound) | RuleCheck::AtMost(bound) => bound,
+            RuleCheck::StrictlyIncreasing | RuleCheck::StrictlyDecreasing => 0.0,
+        };
+        let label = |check: RuleCheck| match check {
+            RuleCheck::AtLeast(_) => i18n.text(TextKey::RuleAtLeast),
+            RuleCheck::AtMost(_) => i18n.text(TextKey::RuleAtMost),
+            RuleCheck::StrictlyIncreasing => i18n.text(TextKey::RuleStrictlyIncreasing),
+            RuleCheck::StrictlyDecreasing => i18n.text(TextKey::RuleStrictlyDecreasing),
+        };
+        egui::ComboBox::from_id_salt(("validation_rule_check", idx))
+            .selected_text(label(rule.check))
+            .show_ui(ui, |ui| {
+                for check in [
+                    RuleCheck::AtLeast(bound),
+                    RuleCheck::AtMost(bound),
+                    RuleCheck::StrictlyIncreasing,
+                    RuleCheck::StrictlyDecreasing,
+                ] {
+                    changed |= ui
+                        .selectable_value(&mut rule.check, check, label(check))
+                        .changed();
+                }
+            });
+        if let RuleCheck::AtLeast(bound) | RuleCheck::AtMost(bound) = &mut rule.check {
+            changed |= ui.add(egui::DragValue::new(bound).speed(0.1)).changed();
+        }
+    });
+    let severity_label = |severity: RuleSeverity| match severity {
+        RuleSeverity::Block => i18n.text(TextKey::RuleBlocks),
+        RuleSeverity::Warn => i18n.text(TextKey::RuleWarns),
+    };
+    egui::ComboBox::from_id_salt(("validation_rule_severity", idx))
+        .selected_text(severity_label(rule.severity))
+        .show_ui(ui, |ui| {
+            for severity in [RuleSeverity::Block, RuleSeverity::Warn] {
+                changed |= ui
+                    .selectable_value(&mut rule.severity, severity, severity_label(severity))
+                    .changed();
+            }
+        });
+    changed
+}
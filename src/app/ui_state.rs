@@ -1,6 +1,8 @@
-use crate::export::{ExportFormat, ExportPayload};
+use super::file_chooser::FileChooser;
+use crate::export::{ExportFormat, ExportPayload, Surface, SurfaceFormat};
 use crate::i18n::UiLanguage;
-use egui_file_dialog::FileDialog;
+use crate::keymap::ShortcutAction;
+use crate::validation::Violation;
 use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +21,8 @@ pub enum StatusLevel {
 pub struct StatusMessage {
     pub(super) text: String,
     pub(super) level: StatusLevel,
+    /// Source chain of the error behind the message, shown on request.
+    pub(super) details: Option<String>,
     pub(super) created_at: Instant,
 }
 
@@ -30,19 +34,89 @@ pub struct UiState {
     pub(super) info_window_open: bool,
     pub(super) points_info_window_open: bool,
     pub(super) image_filters_window_open: bool,
+    /// Window with background flattening and binarization of scans.
+    pub(super) preprocess_window_open: bool,
     pub(super) auto_trace_window_open: bool,
+    pub(super) flag_review_window_open: bool,
+    pub(super) calibration_sandbox_window_open: bool,
+    /// Dim the image away from the cursor and the picked points.
+    pub(super) focus_dim_enabled: bool,
+    /// Magnified inset of the pixels around the cursor.
+    pub(super) loupe_enabled: bool,
+    /// Bottom panel plotting the digitized data in calibrated coordinates.
+    pub(super) result_plot_open: bool,
+    /// Window listing the scans to stitch into one image.
+    pub(super) stitch_window_open: bool,
+    /// Window editing the chart title, axis titles and description.
+    pub(super) chart_text_window_open: bool,
+    /// Window listing the exports made from the current project.
+    pub(super) export_history_window_open: bool,
+    /// Window editing the project's validation rules.
+    pub(super) validation_window_open: bool,
+    /// Window rebinding the keyboard shortcuts.
+    pub(super) keymap_window_open: bool,
+    /// Action whose new shortcut is taken from the next key press.
+    pub(super) keymap_recording: Option<ShortcutAction>,
+    /// Opened with `--view`: edits are off, navigation and export stay.
+    pub(super) view_only: bool,
+    /// Open profiler window with the frames it has collected.
+    #[cfg(feature = "profiling")]
+    pub(super) profiler: Option<super::ui::profiler::ProfilerWindow>,
+    /// Rows that broke a rule at the last export attempt.
+    pub(super) validation_report: Option<Vec<Violation>>,
+    pub(super) flag_review_selected: Option<usize>,
+    pub(super) flag_comment_draft: String,
+    /// Name typed for the next saved color swatch.
+    pub(super) swatch_name_draft: String,
     pub(super) last_status: Option<StatusMessage>,
     pub(super) status_copy_feedback_until: Option<Instant>,
+    /// Transient notifications stacked above the status bar, oldest first.
+    pub(super) toasts: Vec<StatusMessage>,
+    /// Portal identifier of the main window, parent of the desktop file dialogs.
+    pub(super) parent_window: String,
+    /// Scale factor the last frame was laid out at; `None` before the first frame.
+    pub(super) pixels_per_point: Option<f32>,
+    /// Input time of the last frame with user input; `None` before the first frame.
+    pub(super) last_activity: Option<f64>,
+    /// No recent input or the window is unfocused: attention outlines stop blinking.
+    pub(super) idle: bool,
+    /// Title last sent to the main window.
+    pub(super) window_title: String,
 }
 
 #[derive(Debug)]
 pub enum NativeDialog {
-    Open(FileDialog),
-    OpenProject(FileDialog),
-    SaveProject(FileDialog),
+    Open(FileChooser),
+    OpenProject(FileChooser),
+    OpenCurveMask(FileChooser),
+    #[cfg(feature = "segmentation")]
+    OpenSegmentationModel(FileChooser),
+    OpenStitchScans(FileChooser),
+    SaveProject(FileChooser),
     SaveExport {
-        dialog: FileDialog,
-        payload: ExportPayload,
+        dialog: FileChooser,
+        curves: Vec<(String, ExportPayload)>,
         format: ExportFormat,
     },
+    SaveSurface {
+        dialog: FileChooser,
+        surface: Surface,
+        format: SurfaceFormat,
+    },
+}
+
+impl NativeDialog {
+    pub const fn chooser_mut(&mut self) -> &mut FileChooser {
+        match self {
+            Self::Open(dialog)
+            | Self::OpenProject(dialog)
+            | Self::OpenCurveMask(dialog)
+            | Self::OpenStitchScans(dialog)
+            | Self::SaveProject(dialog)
+            | Self::SaveExport { dialog, .. }
+            | Self::SaveSurface { dialog, .. } => dialog,
+            #[cfg(feature = "segmentation")]
+            Self::OpenSegmentationModel(dialog) => dialog,
+        }
+    }
 }
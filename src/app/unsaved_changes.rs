@@ -0,0 +1,232 @@
+//! Unsaved-changes tracking: document fingerprint, window title and the save-before prompt.
+
+use super::{CurcatApp, ImageLoadRequest, PendingImageMeta};
+use crate::i18n::UiLanguage;
+use crate::project;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Work that would replace the current document, parked until the user decides about the
+/// unsaved changes.
+pub enum DeferredAction {
+    LoadImage {
+        request: ImageLoadRequest,
+        meta: PendingImageMeta,
+    },
+    LoadProject(PathBuf),
+    Close,
+}
+
+impl CurcatApp {
+    /// Hash of the project payload that a save would write, minus the view (zoom and pan)
+    /// and the numeric values cached from the calibration.
+    ///
+    /// Cheap enough to recompute every frame, so edits need no explicit bookkeeping, and
+    /// every field a project stores counts as an edit without being listed here.
+    pub(crate) fn document_fingerprint(&self) -> u64 {
+        let mut payload = self.project_payload();
+        payload.zoom = 0.0;
+        payload.pan = [0.0; 2];
+        for point in payload
+            .curves
+            .iter_mut()
+            .flat_map(|curve| &mut curve.points)
+        {
+            point.x_numeric = None;
+            point.y_numeric = None;
+        }
+        let mut state = DefaultHasher::new();
+        project::encode_payload(&payload).ok().hash(&mut state);
+        state.finish()
+    }
+
+    /// Whether the loaded image carries edits that were not saved to a project.
+    ///
+    /// Never in view-only mode, where nothing can be saved.
+    pub(crate) fn has_unsaved_changes(&self) -> bool {
+        !self.view_only()
+            && self.image.image.is_some()
+            && self.project.saved_fingerprint != Some(self.document_fingerprint())
+    }
+
+    /// Treat the current state as the last saved one.
+    pub(crate) fn mark_document_saved(&mut self) {
+        self.project.saved_fingerprint = Some(self.document_fingerprint());
+    }
+
+    /// `chart.png — modified — Curcat` style title for the main window.
+    pub(crate) fn window_title(&self) -> String {
+        let Some(name) = self
+            .image
+            .meta
+            .as_ref()
+            .map(crate::image::ImageMeta::display_name)
+        else {
+            return match self.ui.language {
+                UiLanguage::En => "Curcat — Graph Digitizer",
+                UiLanguage::Ru => "Curcat — Оцифровка графиков",
+            }
+            .to_string();
+        };
+        if self.view_only() {
+            match self.ui.language {
+                UiLanguage::En => format!("{name} — view only — Curcat"),
+                UiLanguage::Ru => format!("{name} — просмотр — Curcat"),
+            }
+        } else if self.has_unsaved_changes() {
+            match self.ui.language {
+                UiLanguage::En => format!("{name} — modified — Curcat"),
+                UiLanguage::Ru => format!("{name} — изменён — Curcat"),
+            }
+        } else {
+            format!("{name} — Curcat")
+        }
+    }
+
+    /// Whether `action` has to wait for the unsaved-changes prompt; parks it if so.
+    ///
+    /// The image load that belongs to a project being applied is never held back: the
+    /// prompt already ran when that project was picked.
+    pub(crate) fn defer_for_unsaved_changes(
+        &mut self,
+        action: DeferredAction,
+    ) -> Option<DeferredAction> {
+        if self.project.pending_project_apply.is_some() || !self.has_unsaved_changes() {
+            return Some(action);
+        }
+        self.project.unsaved_prompt = Some(action);
+        None
+    }
+
+    pub(crate) fn run_deferred_action(&mut self, ctx: &egui::Context, action: DeferredAction) {
+        match action {
+            DeferredAction::LoadImage { request, meta } => {
+                self.start_user_image_load(request, meta);
+            }
+            DeferredAction::LoadProject(path) => self.load_project_from(path),
+            DeferredAction::Close => {
+                self.project.close_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+    }
+
+    /// Keep the window open while unsaved changes wait for a decision.
+    pub(crate) fn intercept_close_request(&mut self, ctx: &egui::Context) {
+        if !ctx.input(|i| i.viewport().close_requested())
+            || self.project.close_confirmed
+            || !self.has_unsaved_changes()
+        {
+            return;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        self.project.unsaved_prompt = Some(DeferredAction::Close);
+    }
+
+    /// Save the project first; the parked action resumes once the save succeeds.
+    ///
+    /// A document that already belongs to a project file is saved in place, otherwise the
+    /// save dialog opens.
+    pub(crate) fn save_before_deferred_action(&mut self, action: DeferredAction) {
+        self.project.after_save = Some(action);
+        match self.project.document_path.clone() {
+            Some(path) => self.handle_project_save(&path),
+            None => self.save_project_dialog(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::PickedPoint;
+    use egui::Pos2;
+
+    #[test]
+    fn fingerprint_tracks_document_edits_but_not_the_view() {
+        let mut app = CurcatApp::default();
+        let clean = app.document_fingerprint();
+
+        app.image.zoom *= 2.0;
+        app.image.pan.x += 10.0;
+        assert_eq!(app.document_fingerprint(), clean);
+
+        app.points
+            .points
+            .push(PickedPoint::new(Pos2::new(4.0, 2.0)));
+        let with_point = app.document_fingerprint();
+        assert_ne!(with_point, clean);
+
+        app.calibration.cal_x.v2_text = "100".to_string();
+        assert_ne!(app.document_fingerprint(), with_point);
+
+        app.points.points.clear();
+        app.calibration
+            .cal_x
+            .v2_text
+            .clone_from(&CurcatApp::default().calibration.cal_x.v2_text);
+        assert_eq!(app.document_fingerprint(), clean);
+    }
+
+    #[test]
+    fn cached_point_values_are_not_edits() {
+        let mut app = CurcatApp::default();
+        app.points
+            .points
+            .push(PickedPoint::new(Pos2::new(4.0, 2.0)));
+        let stale = app.document_fingerprint();
+        app.points.points[0].x_numeric = Some(1.5);
+        app.points.points[0].y_numeric = Some(-3.0);
+        assert_eq!(app.document_fingerprint(), stale);
+    }
+
+    #[test]
+    fn fingerprint_tracks_affine_reference_points() {
+        let mut app = CurcatApp::default();
+        let clean = app.document_fingerprint();
+        app.calibration.affine_cal.points[0].pixel = Some(Pos2::new(12.0, 30.0));
+        let placed = app.document_fingerprint();
+        assert_ne!(placed, clean);
+        app.calibration.affine_cal.points[0].x_text = "2.5".to_string();
+        assert_ne!(app.document_fingerprint(), placed);
+    }
+
+    #[test]
+    fn fingerprint_tracks_the_smith_calibration() {
+        let mut app = CurcatApp::default();
+        let clean = app.document_fingerprint();
+        app.calibration.smith_cal.center = Some(Pos2::new(50.0, 50.0));
+        let centred = app.document_fingerprint();
+        assert_ne!(centred, clean);
+        app.calibration.smith_cal.reference_text = "75".to_string();
+        assert_ne!(app.document_fingerprint(), centred);
+    }
+
+    #[test]
+    fn fingerprint_tracks_point_locks() {
+        let mut app = CurcatApp::default();
+        app.points
+            .points
+            .push(PickedPoint::new(Pos2::new(4.0, 2.0)));
+        let unlocked = app.document_fingerprint();
+        app.points.points[0].locked = true;
+        assert_ne!(app.document_fingerprint(), unlocked);
+    }
+
+    #[test]
+    fn fingerprint_tracks_curve_locks() {
+        let mut app = CurcatApp::default();
+        let unlocked = app.document_fingerprint();
+        app.points.curves.curves[0].locked = true;
+        assert_ne!(app.document_fingerprint(), unlocked);
+    }
+
+    #[test]
+    fn fingerprint_tracks_the_svg_scale() {
+        let mut app = CurcatApp::default();
+        let clean = app.document_fingerprint();
+        app.image.svg_scale_percent = Some(200);
+        assert_ne!(app.document_fingerprint(), clean);
+    }
+}
@@ -0,0 +1,62 @@
+//! View-only mode: a project opened with `--view` for colleagues who only inspect it or
+//! export it again, with every edit of points, curves, calibration and the image turned off.
+//!
+//! Navigation (zoom, pan, the side-panel toggle), the read-only windows and the export panel
+//! keep working. Nothing can be saved, so the document is never reported as modified.
+
+use super::CurcatApp;
+use crate::i18n::UiLanguage;
+use crate::keymap::ShortcutAction;
+
+/// Command-line flag that opens the given project view-only.
+pub const VIEW_FLAG: &str = "--view";
+
+impl CurcatApp {
+    /// Turn editing off for the rest of the session.
+    pub fn enter_view_mode(&mut self) {
+        self.ui.view_only = true;
+        self.calibration.pick_mode = super::PickMode::None;
+        self.set_status(match self.ui.language {
+            UiLanguage::En => "View-only mode: editing is off; navigation and export work.",
+            UiLanguage::Ru => "Режим просмотра: правка отключена, доступны навигация и экспорт.",
+        });
+    }
+
+    pub(crate) const fn view_only(&self) -> bool {
+        self.ui.view_only
+    }
+
+    /// Whether `action` may run; in view-only mode only navigation and export do.
+    pub(crate) const fn shortcut_allowed(&self, action: ShortcutAction) -> bool {
+        !self.ui.view_only
+            || matches!(
+                action,
+                ShortcutAction::ToggleSidePanel
+                    | ShortcutAction::OpenProject
+                    | ShortcutAction::ExportCsv
+                    | ShortcutAction::ExportJson
+                    | ShortcutAction::ExportRon
+                    | ShortcutAction::ExportExcel
+                    | ShortcutAction::ExportOds
+                    | ShortcutAction::ExportArrow
+                    | ShortcutAction::ExportParquet
+                    | ShortcutAction::ExportSql
+                    | ShortcutAction::ExportNetCdf
+                    | ShortcutAction::ExportHtml
+                    | ShortcutAction::ExportXml
+                    | ShortcutAction::ExportMarkdown
+                    | ShortcutAction::ImageInfo
+                    | ShortcutAction::FitView
+                    | ShortcutAction::FitWidth
+                    | ShortcutAction::ResetView
+            )
+    }
+
+    /// Status for a dropped image refused in view-only mode.
+    pub(crate) fn warn_view_only(&mut self) {
+        self.set_status_warn(match self.ui.language {
+            UiLanguage::En => "View-only mode: only projects can be opened.",
+            UiLanguage::Ru => "Режим просмотра: открывать можно только проекты.",
+        });
+    }
+}
@@ -0,0 +1,132 @@
+//! View commands that frame part of the image: fit width and zoom to a dragged rectangle.
+
+use super::{CurcatApp, MAX_ZOOM, MIN_ZOOM, PickMode};
+use crate::i18n::UiLanguage;
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, Pos2, Rect, Stroke, StrokeKind, Vec2, pos2};
+
+/// Share of the viewport a framed extent fills, as for fitting the whole image.
+const FRAME_MARGIN: f32 = 0.98;
+/// Screen size below which a dragged rectangle counts as a slip rather than a region.
+const MIN_REGION_SCREEN: f32 = 8.0;
+const REGION_COLOR: Color32 = Color32::from_rgb(90, 170, 255);
+
+/// Rectangle dragged so far, in image pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct ZoomRegionDrag {
+    pub(super) start: Pos2,
+    pub(super) end: Pos2,
+}
+
+impl CurcatApp {
+    fn image_base_size(&self) -> Option<Vec2> {
+        let [w, h] = self.image.image.as_ref()?.size;
+        (w > 0 && h > 0).then(|| Vec2::new(safe_usize_to_f32(w), safe_usize_to_f32(h)))
+    }
+
+    /// Scroll offset that puts image `pixel` at the viewport centre at `zoom`, kept in range.
+    fn pan_centering(pixel: Pos2, zoom: f32, viewport: Vec2, base_size: Vec2) -> Vec2 {
+        let display = base_size * zoom;
+        let pad = Self::center_padding(viewport, display);
+        let pan = pixel.to_vec2() * zoom + pad - viewport * 0.5;
+        let max = (display - viewport).max(Vec2::ZERO);
+        pan.clamp(Vec2::ZERO, max)
+    }
+
+    /// Zoom so the image width fills the viewport, keeping the row at its centre.
+    pub(crate) fn fit_image_width(&mut self) {
+        let (Some(base_size), Some(viewport)) =
+            (self.image_base_size(), self.image.last_viewport_size)
+        else {
+            return;
+        };
+        let zoom = (viewport.x.max(1.0) / base_size.x * FRAME_MARGIN).clamp(MIN_ZOOM, MAX_ZOOM);
+        let old_pad = Self::center_padding(viewport, base_size * self.image.zoom);
+        let center_y = ((viewport.y.mul_add(0.5, self.image.pan.y) - old_pad.y) / self.image.zoom)
+            .clamp(0.0, base_size.y);
+        let pan = Self::pan_centering(pos2(base_size.x * 0.5, center_y), zoom, viewport, base_size);
+        self.set_zoom_to_pan_target(zoom, pan);
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Fit width: {:.0}%", zoom * 100.0),
+            UiLanguage::Ru => format!("По ширине: {:.0}%", zoom * 100.0),
+        });
+    }
+
+    /// Zoom and scroll so the image rectangle spanned by `a` and `b` fills the viewport.
+    pub(crate) fn zoom_to_region(&mut self, a: Pos2, b: Pos2) {
+        let (Some(base_size), Some(viewport)) =
+            (self.image_base_size(), self.image.last_viewport_size)
+        else {
+            return;
+        };
+        let region = Rect::from_two_pos(a, b);
+        let size = region.size().max(Vec2::splat(1.0));
+        let zoom = ((viewport.x / size.x).min(viewport.y / size.y) * FRAME_MARGIN)
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+        let pan = Self::pan_centering(region.center(), zoom, viewport, base_size);
+        self.set_zoom_to_pan_target(zoom, pan);
+        self.set_status(match self.ui.language {
+            UiLanguage::En => format!("Zoomed to region: {:.0}%", zoom * 100.0),
+            UiLanguage::Ru => format!("Масштаб по области: {:.0}%", zoom * 100.0),
+        });
+    }
+
+    /// Follow a zoom-region drag: Ctrl + drag, or a plain drag while the mode is armed.
+    ///
+    /// `press` and `pointer` are image pixels. Returns `true` while a region is being dragged
+    /// and on the frame it is released, so the gesture neither places nor auto-places points.
+    pub(crate) fn track_zoom_region(
+        &mut self,
+        response: &egui::Response,
+        ctrl: bool,
+        press: Option<Pos2>,
+        pointer: Option<Pos2>,
+    ) -> bool {
+        let armed = self.calibration.pick_mode == PickMode::ZoomRegion;
+        if response.drag_started_by(egui::PointerButton::Primary)
+            && (ctrl || armed)
+            && let Some(start) = press
+        {
+            self.interaction.zoom_region = Some(ZoomRegionDrag {
+                start,
+                end: pointer.unwrap_or(start),
+            });
+        }
+        let Some(drag) = self.interaction.zoom_region.as_mut() else {
+            return false;
+        };
+        if let Some(end) = pointer {
+            drag.end = end;
+        }
+        if response.dragged_by(egui::PointerButton::Primary) {
+            return true;
+        }
+        let drag = *drag;
+        self.interaction.zoom_region = None;
+        let screen = (drag.end - drag.start).abs() * self.image.zoom;
+        if screen.min_elem() >= MIN_REGION_SCREEN {
+            self.zoom_to_region(drag.start, drag.end);
+            if armed {
+                self.calibration.pick_mode = PickMode::None;
+            }
+        }
+        true
+    }
+
+    /// Outline of the region being dragged over the image drawn at `rect`.
+    pub(crate) fn draw_zoom_region(&self, painter: &egui::Painter, rect: Rect) {
+        let Some(drag) = self.interaction.zoom_region else {
+            return;
+        };
+        let zoom = self.image.zoom;
+        let to_screen = |pixel: Pos2| rect.min + pixel.to_vec2() * zoom;
+        let region = Rect::from_two_pos(to_screen(drag.start), to_screen(drag.end));
+        painter.rect_filled(region, 0.0, REGION_COLOR.gamma_multiply(0.15));
+        painter.rect_stroke(
+            region,
+            0.0,
+            Stroke::new(1.5_f32, REGION_COLOR),
+            StrokeKind::Inside,
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! On-disk cache of decoded images and snap-map pyramids, keyed by image CRC32.
+//!
+//! Entries live in the platform cache directory and are plain files: a short header followed
+//! by an LZ4 block. Anything unreadable is treated as a miss and removed, so the cache never
+//! has to be consistent with the application version that wrote it.
+
+use crate::config::AppConfig;
+use crate::image::{ImageDecodeOptions, ImageLoadPolicy};
+use crate::snap::SnapMapCache;
+use crc32fast::Hasher;
+use directories::ProjectDirs;
+use egui::{Color32, ColorImage};
+use lz4_flex::block::{compress_prepend_size, decompress_size_prepended};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Magic signature prefix for cache entries.
+const CACHE_MAGIC: &[u8; 6] = b"CCACHE";
+/// Bumped whenever the layout of cached images or snap maps changes.
+const CACHE_VERSION: u32 = 1;
+const CACHE_EXTENSION: &str = "bin";
+const IMAGE_PREFIX: &str = "image";
+const SNAP_PREFIX: &str = "snap";
+
+/// Identifies a decoded image: source file contents plus every decode parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageCacheKey {
+    source_crc32: u32,
+    params_crc32: u32,
+}
+
+impl ImageCacheKey {
+    /// Key for decoding `path` with the given limits, policy and options.
+    pub fn for_file(
+        path: &Path,
+        cfg: &AppConfig,
+        policy: ImageLoadPolicy,
+        options: ImageDecodeOptions,
+    ) -> anyhow::Result<Self> {
+        let source_crc32 = crate::project::compute_image_crc32(path)?;
+        let limits = cfg.effective_image_limits();
+        let mut params = Hasher::new();
+        params.update(&limits.image_dim.to_le_bytes());
+        params.update(&limits.total_pixels.to_le_bytes());
+        params.update(&limits.alloc_bytes.to_le_bytes());
+        params.update(&[match policy {
+            ImageLoadPolicy::AskUser => 0,
+            ImageLoadPolicy::AutoscaleToConfig => 1,
+            ImageLoadPolicy::IgnoreConfigWithHardCap => 2,
+        }]);
+        match options.svg_min_render_size {
+            Some([w, h]) => {
+                params.update(&[1]);
+                params.update(&w.to_le_bytes());
+                params.update(&h.to_le_bytes());
+            }
+            None => params.update(&[0]),
+        }
+        if let Some(percent) = options.svg_scale_percent {
+            params.update(&[2]);
+            params.update(&percent.to_le_bytes());
+        }
+        Ok(Self {
+            source_crc32,
+            params_crc32: params.finalize(),
+        })
+    }
+
+    fn file_name(self) -> String {
+        entry_name(IMAGE_PREFIX, self.source_crc32, self.params_crc32)
+    }
+}
+
+/// Identifies snap maps: the pixels they were built from plus target color and tolerance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapCacheKey {
+    pixels_crc32: u32,
+    params_crc32: u32,
+}
+
+impl SnapCacheKey {
+    /// Key for snap maps of `image`; hashing pixels covers transforms and filters alike.
+    pub fn new(image: &ColorImage, target: Color32, tolerance: f32) -> Self {
+        let mut pixels = Hasher::new();
+        pixels.update(&dimension_bytes(image.size));
+        for color in &image.pixels {
+            pixels.update(&color.to_array());
+        }
+        let mut params = Hasher::new();
+        params.update(&target.to_array());
+        params.update(&tolerance.to_bits().to_le_bytes());
+        Self {
+            pixels_crc32: pixels.finalize(),
+            params_crc32: params.finalize(),
+        }
+    }
+
+    fn file_name(self) -> String {
+        entry_name(SNAP_PREFIX, self.pixels_crc32, self.params_crc32)
+    }
+}
+
+fn entry_name(prefix: &str, crc32: u32, params: u32) -> String {
+    format!("{prefix}-{crc32:08x}-{params:08x}.{CACHE_EXTENSION}")
+}
+
+fn dimension_bytes(size: [usize; 2]) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&(size[0] as u64).to_le_bytes());
+    bytes[8..].copy_from_slice(&(size[1] as u64).to_le_bytes());
+    bytes
+}
+
+/// Cache directory with a size budget; cheap to clone into worker threads.
+#[derive(Debug, Clone)]
+pub struct DecodeCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl DecodeCache {
+    pub const fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// Cache configured by `[cache]`, or `None` when disabled or no cache directory exists.
+    ///
+    /// Portable mode keeps the cache beside the executable.
+    pub fn from_config(cfg: &AppConfig) -> Option<Self> {
+        if !cfg.cache.enabled {
+            return None;
+        }
+        let cache_dir = crate::portable::cache_dir().or_else(|| {
+            ProjectDirs::from("dev", "Curcat", "Curcat").map(|dirs| dirs.cache_dir().to_path_buf())
+        })?;
+        Some(Self::new(
+            cache_dir.join("decoded"),
+            cfg.cache.max_size_bytes_sanitized(),
+        ))
+    }
+
+    pub fn load_image(&self, key: ImageCacheKey) -> Option<ColorImage> {
+        let bytes = self.read_entry(&key.file_name())?;
+        let decoded = decode_color_image(&bytes);
+        if decoded.is_none() {
+            self.discard_entry(&key.file_name());
+        }
+        decoded
+    }
+
+    pub fn store_image(&self, key: ImageCacheKey, image: &ColorImage) {
+        self.write_entry(&key.file_name(), &encode_color_image(image));
+    }
+
+    pub fn load_snap_maps(&self, key: SnapCacheKey) -> Option<SnapMapCache> {
+        let bytes = self.read_entry(&key.file_name())?;
+        let decoded = SnapMapCache::from_bytes(&bytes);
+        if decoded.is_none() {
+            self.discard_entry(&key.file_name());
+        }
+        decoded
+    }
+
+    pub fn store_snap_maps(&self, key: SnapCacheKey, maps: &SnapMapCache) {
+        self.write_entry(&key.file_name(), &maps.to_bytes());
+    }
+
+    /// Payload of an entry; a hit refreshes its modification time for eviction.
+    fn read_entry(&self, name: &str) -> Option<Vec<u8>> {
+        let path = self.dir.join(name);
+        let bytes = fs::read(&path).ok()?;
+        let Some(payload) = unwrap_entry(&bytes) else {
+            self.discard_entry(name);
+            return None;
+        };
+        if let Ok(file) = fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(payload)
+    }
+
+    /// Best effort: a cache that cannot be written just stays cold.
+    fn write_entry(&self, name: &str, payload: &[u8]) {
+        let compressed = compress_prepend_size(payload);
+        let entry_len = (CACHE_MAGIC.len() + 4 + compressed.len()) as u64;
+        // One oversized entry would evict everything else and still not fit comfortably.
+        if entry_len > self.max_bytes / 2 {
+            return;
+        }
+        let mut buffer = Vec::with_capacity(CACHE_MAGIC.len() + 4 + compressed.len());
+        buffer.extend_from_slice(CACHE_MAGIC);
+        buffer.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+        buffer.extend_from_slice(&compressed);
+        if crate::project::write_atomic(&self.dir.join(name), &buffer).is_ok() {
+            self.evict_to_budget(name);
+        }
+    }
+
+    fn discard_entry(&self, name: &str) {
+        let _ = fs::remove_file(self.dir.join(name));
+    }
+
+    /// Drop least recently used entries until the directory fits the budget; `keep` survives.
+    fn evict_to_budget(&self, keep: &str) {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut entries: Vec<(SystemTime, u64, PathBuf)> = read_dir
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .path()
+                    .extension()
+                    .is_some_and(|ext| ext == CACHE_EXTENSION)
+            })
+            .filter_map(|entry| {
+                let meta = entry.metadata().ok()?;
+                let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                Some((modified, meta.len(), entry.path()))
+            })
+            .collect();
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        entries.sort_by_key(|(modified, _, _)| *modified);
+        for (_, len, path) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            if path.file_name().is_some_and(|name| name == keep) {
+                continue;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(len);
+            }
+        }
+    }
+}
+
+fn unwrap_entry(bytes: &[u8]) -> Option<Vec<u8>> {
+    let rest = bytes.strip_prefix(CACHE_MAGIC.as_slice())?;
+    let (version, compressed) = rest.split_first_chunk::<4>()?;
+    if u32::from_le_bytes(*version) != CACHE_VERSION {
+        return None;
+    }
+    decompress_size_prepended(compressed).ok()
+}
+
+/// `width`, `height` as little-endian `u32`, then premultiplied RGBA bytes.
+fn encode_color_image(image: &ColorImage) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + image.pixels.len() * 4);
+    for side in image.size {
+        out.extend_from_slice(&u32::try_from(side).unwrap_or(u32::MAX).to_le_bytes());
+    }
+    for color in &image.pixels {
+        out.extend_from_slice(&color.to_array());
+    }
+    out
+}
+
+fn decode_color_image(bytes: &[u8]) -> Option<ColorImage> {
+    let (width, rest) = bytes.split_first_chunk::<4>()?;
+    let (height, rgba) = rest.split_first_chunk::<4>()?;
+    let width = usize::try_from(u32::from_le_bytes(*width)).ok()?;
+    let height = usize::try_from(u32::from_le_bytes(*height)).ok()?;
+    if width.checked_mul(height)?.checked_mul(4)? != rgba.len() {
+        return None;
+    }
+    Some(ColorImage::from_rgba_premultiplied([width, height], rgba))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache_dir(tag: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        std::env::temp_dir().join(format!("curcat_cache_{tag}_{}_{nanos}", std::process::id()))
+    }
+
+    fn gradient_image(width: usize, height: usize) -> ColorImage {
+        let pixels = (0..width * height)
+            .map(|i| {
+                let v = u8::try_from(i % 256).unwrap_or(0);
+                Color32::from_rgba_premultiplied(v, v / 2, 255 - v, 255)
+            })
+            .collect();
+        ColorImage::new([width, height], pixels)
+    }
+
+    #[test]
+    fn cache_roundtrips_images_and_snap_maps() {
+        let dir = temp_cache_dir("roundtrip");
+        let cache = DecodeCache::new(dir.clone(), 64 * 1024 * 1024);
+        let image = gradient_image(37, 21);
+        let image_key = ImageCacheKey {
+            source_crc32: 0xDEAD_BEEF,
+            params_crc32: 7,
+        };
+        assert!(cache.load_image(image_key).is_none());
+        cache.store_image(image_key, &image);
+        let restored = cache.load_image(image_key).expect("cached image");
+        assert_eq!(restored.size, image.size);
+        assert_eq!(restored.pixels, image.pixels);
+
+        let target = Color32::from_rgb(200, 60, 60);
+        let snap_key = SnapCacheKey::new(&image, target, 30.0);
+        assert_ne!(snap_key, SnapCacheKey::new(&image, target, 31.0));
+        let maps = SnapMapCache::build(&image, target, 30.0).expect("maps");
+        cache.store_snap_maps(snap_key, &maps);
+        let restored = cache.load_snap_maps(snap_key).expect("cached maps");
+        assert_eq!(restored.to_bytes(), maps.to_bytes());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn corrupt_entries_are_dropped() {
+        let dir = temp_cache_dir("corrupt");
+        let cache = DecodeCache::new(dir.clone(), 64 * 1024 * 1024);
+        let key = ImageCacheKey {
+            source_crc32: 1,
+            params_crc32: 2,
+        };
+        cache.store_image(key, &gradient_image(4, 4));
+        let path = dir.join(key.file_name());
+        let mut bytes = fs::read(&path).expect("entry");
+        bytes.truncate(bytes.len() / 2);
+        fs::write(&path, bytes).expect("truncate entry");
+
+        assert!(cache.load_image(key).is_none());
+        assert!(!path.exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,258 @@
+//! Calibration quality report for cartesian charts: how square the axes are, how many pixels
+//! an axis unit spans, and how far a point picked half a pixel off would read.
+//!
+//! The report is shown under the calibration once both axes are complete and written into the
+//! metadata of every export, so a data file says how precisely it could be read.
+
+use crate::types::{AxisMapping, FloatFormat, ScaleKind};
+
+/// Departure from a right angle between the axes, in degrees, above which the chart is
+/// reported as skewed.
+pub const SKEW_TOLERANCE_DEG: f64 = 0.5;
+
+/// Significant figures of the numbers written into export metadata.
+const METADATA_SIGNIFICANT_FIGURES: usize = 4;
+
+/// Quality figures of a complete X/Y calibration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationReport {
+    /// Angle between the X and Y calibration lines in degrees, 0..=90; 90 on a square chart.
+    pub axis_angle_deg: f64,
+    pub x: AxisReport,
+    pub y: AxisReport,
+}
+
+/// Quality figures of one calibrated axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisReport {
+    pub scale: ScaleKind,
+    /// Pixels per axis unit on linear axes, per decade on log axes.
+    pub pixels_per_unit: f64,
+    /// Reading error of a point half a pixel off along the axis: in axis units on linear
+    /// axes, as a fraction of the value on log axes.
+    pub quantization_error: f64,
+    /// Scale the spacing of the image's grid lines suggests, when it is clear.
+    pub grid_scale: Option<ScaleKind>,
+}
+
+/// Value of one metadata entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportValue {
+    Number(f64),
+    Text(&'static str),
+}
+
+impl ReportValue {
+    /// The value as written into text metadata; numbers keep four significant figures.
+    pub fn to_text(self) -> String {
+        match self {
+            Self::Number(value) => FloatFormat::adaptive(0.0, value, METADATA_SIGNIFICANT_FIGURES)
+                .format_trimmed(value),
+            Self::Text(text) => text.to_string(),
+        }
+    }
+}
+
+impl CalibrationReport {
+    /// Report on the `x` and `y` mappings, with the scales the grid lines suggest for each.
+    pub fn new(x: &AxisMapping, y: &AxisMapping, grid_scales: [Option<ScaleKind>; 2]) -> Self {
+        let dx = x.p2 - x.p1;
+        let dy = y.p2 - y.p1;
+        let cos = f64::from(dx.dot(dy).abs()) / f64::from(dx.length() * dy.length());
+        Self {
+            axis_angle_deg: cos.clamp(0.0, 1.0).acos().to_degrees(),
+            x: AxisReport::new(x, grid_scales[0]),
+            y: AxisReport::new(y, grid_scales[1]),
+        }
+    }
+
+    /// Whether the axes are further than [`SKEW_TOLERANCE_DEG`] from a right angle.
+    pub fn is_skewed(&self) -> bool {
+        90.0 - self.axis_angle_deg > SKEW_TOLERANCE_DEG
+    }
+
+    /// Entries written into export metadata, in a fixed order.
+    ///
+    /// Log axes name their figures per decade and relative, so a reader never mistakes them
+    /// for linear ones.
+    pub fn metadata(&self) -> Vec<(&'static str, ReportValue)> {
+        let mut pairs = vec![("axis_angle_deg", ReportValue::Number(self.axis_angle_deg))];
+        for (axis, keys) in [(&self.x, AxisKeys::X), (&self.y, AxisKeys::Y)] {
+            pairs.push((keys.scale, ReportValue::Text(scale_label(axis.scale))));
+            let (resolution, error) = match axis.scale {
+                ScaleKind::Linear => (keys.pixels_per_unit, keys.quantization_error),
+                ScaleKind::Log10 => (keys.pixels_per_decade, keys.relative_error),
+            };
+            pairs.push((resolution, ReportValue::Number(axis.pixels_per_unit)));
+            pairs.push((error, ReportValue::Number(axis.quantization_error)));
+            if let Some(grid) = axis.grid_scale {
+                pairs.push((keys.grid_scale, ReportValue::Text(scale_label(grid))));
+            }
+        }
+        pairs
+    }
+}
+
+impl AxisReport {
+    fn new(mapping: &AxisMapping, grid_scale: Option<ScaleKind>) -> Self {
+        let length = f64::from((mapping.p2 - mapping.p1).length());
+        let v1 = mapping.v1.to_scalar_seconds();
+        let v2 = mapping.v2.to_scalar_seconds();
+        let (pixels_per_unit, quantization_error) = match mapping.scale {
+            ScaleKind::Linear => {
+                let span = (v2 - v1).abs();
+                (length / span, 0.5 * span / length)
+            }
+            ScaleKind::Log10 => {
+                let decades = (v2.log10() - v1.log10()).abs();
+                (length / decades, 10_f64.powf(0.5 * decades / length) - 1.0)
+            }
+        };
+        Self {
+            scale: mapping.scale,
+            pixels_per_unit,
+            quantization_error,
+            grid_scale,
+        }
+    }
+
+    /// Whether the grid lines suggest the other scale.
+    pub fn scale_disagrees(&self) -> bool {
+        self.grid_scale.is_some_and(|grid| grid != self.scale)
+    }
+}
+
+/// Metadata keys of one axis.
+struct AxisKeys {
+    scale: &'static str,
+    pixels_per_unit: &'static str,
+    pixels_per_decade: &'static str,
+    quantization_error: &'static str,
+    relative_error: &'static str,
+    grid_scale: &'static str,
+}
+
+impl AxisKeys {
+    const X: Self = Self {
+        scale: "x_scale",
+        pixels_per_unit: "x_pixels_per_unit",
+        pixels_per_decade: "x_pixels_per_decade",
+        quantization_error: "x_quantization_error",
+        relative_error: "x_quantization_error_relative",
+        grid_scale: "x_grid_scale",
+    };
+    const Y: Self = Self {
+        scale: "y_scale",
+        pixels_per_unit: "y_pixels_per_unit",
+        pixels_per_decade: "y_pixels_per_decade",
+        quantization_error: "y_quantization_error",
+        relative_error: "y_quantization_error_relative",
+        grid_scale: "y_grid_scale",
+    };
+}
+
+const fn scale_label(scale: ScaleKind) -> &'static str {
+    match scale {
+        ScaleKind::Linear => "linear",
+        ScaleKind::Log10 => "log10",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AxisUnit, AxisValue};
+    use egui::pos2;
+
+    fn mapping(p1: egui::Pos2, p2: egui::Pos2, v: [f64; 2], scale: ScaleKind) -> AxisMapping {
+        AxisMapping::try_new(
+            p1,
+            p2,
+            AxisValue::Float(v[0]),
+            AxisValue::Float(v[1]),
+            scale,
+            AxisUnit::Float,
+        )
+        .expect("mapping")
+    }
+
+    #[test]
+    fn square_linear_axes_report_their_resolution() {
+        let x = mapping(
+            pos2(0.0, 100.0),
+            pos2(200.0, 100.0),
+            [0.0, 10.0],
+            ScaleKind::Linear,
+        );
+        let y = mapping(
+            pos2(0.0, 100.0),
+            pos2(0.0, 0.0),
+            [0.0, 50.0],
+            ScaleKind::Linear,
+        );
+        let report = CalibrationReport::new(&x, &y, [Some(ScaleKind::Linear), None]);
+        assert!((report.axis_angle_deg - 90.0).abs() < 1e-9);
+        assert!(!report.is_skewed());
+        assert!((report.x.pixels_per_unit - 20.0).abs() < 1e-9);
+        assert!((report.x.quantization_error - 0.025).abs() < 1e-9);
+        assert!((report.y.pixels_per_unit - 2.0).abs() < 1e-9);
+        assert!((report.y.quantization_error - 0.25).abs() < 1e-9);
+        assert!(!report.x.scale_disagrees());
+
+        let keys: Vec<&str> = report.metadata().iter().map(|(key, _)| *key).collect();
+        assert_eq!(
+            keys,
+            [
+                "axis_angle_deg",
+                "x_scale",
+                "x_pixels_per_unit",
+                "x_quantization_error",
+                "x_grid_scale",
+                "y_scale",
+                "y_pixels_per_unit",
+                "y_quantization_error",
+            ]
+        );
+        assert_eq!(
+            ReportValue::Number(report.y.quantization_error).to_text(),
+            "0.25"
+        );
+    }
+
+    #[test]
+    fn skewed_log_axes_report_per_decade_and_relative_error() {
+        let x = mapping(
+            pos2(0.0, 100.0),
+            pos2(300.0, 95.0),
+            [1.0, 1000.0],
+            ScaleKind::Log10,
+        );
+        let y = mapping(
+            pos2(0.0, 100.0),
+            pos2(0.0, 0.0),
+            [0.0, 1.0],
+            ScaleKind::Linear,
+        );
+        let report = CalibrationReport::new(&x, &y, [Some(ScaleKind::Linear), None]);
+        assert!(report.is_skewed());
+        assert!((report.axis_angle_deg - 89.045).abs() < 1e-3);
+        let length = f64::from(pos2(300.0, 95.0).distance(pos2(0.0, 100.0)));
+        assert!((report.x.pixels_per_unit - length / 3.0).abs() < 1e-9);
+        let relative = 10_f64.powf(1.5 / length) - 1.0;
+        assert!((report.x.quantization_error - relative).abs() < 1e-12);
+        assert!(report.x.scale_disagrees());
+
+        let metadata = report.metadata();
+        assert!(metadata.contains(&("x_scale", ReportValue::Text("log10"))));
+        assert!(
+            metadata
+                .iter()
+                .any(|(key, _)| *key == "x_pixels_per_decade")
+        );
+        assert!(
+            metadata
+                .iter()
+                .any(|(key, _)| *key == "x_quantization_error_relative")
+        );
+    }
+}
@@ -1,8 +1,15 @@
+use anyhow::Context as _;
 use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::app::ExportKind;
+use crate::export::{ColumnMetric, ExportFormat, TimeExportFormat};
+use crate::fit::FitModel;
 use crate::i18n::UiLanguage;
+use crate::interp::{DEFAULT_SPLINE_SPAN, InterpAlgorithm, SmoothingFilter};
+use crate::keymap::KeymapConfig;
+use chrono::{DateTime, Utc};
 use directories::{BaseDirs, ProjectDirs};
 use egui::{Color32, Stroke};
 use serde::{
@@ -193,6 +200,184 @@ impl CrosshairStyle {
     }
 }
 
+/// Named set of export settings (`[[export.profiles]]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ExportProfile {
+    pub name: String,
+    pub format: ExportFormat,
+    /// Fractional digits for float values.
+    pub precision: u8,
+    /// Significant figures per column; overrides `precision` when set.
+    pub significant_figures: Option<u8>,
+    /// CSV field separator (single ASCII character; `"\t"` for tabs).
+    pub delimiter: char,
+    /// How `DateTime` columns are written.
+    pub time_format: TimeExportFormat,
+    pub kind: ExportKind,
+    pub interpolation: InterpAlgorithm,
+    /// Span of the smoothing spline, in point spacings.
+    pub spline_span: usize,
+    pub samples: usize,
+    pub include_distances: bool,
+    pub include_angles: bool,
+    pub include_steps: bool,
+    pub include_flags: bool,
+    pub include_capture_times: bool,
+    pub include_confidence: bool,
+    pub include_cartesian: bool,
+    pub fit_model: FitModel,
+    pub fit_degree: usize,
+    pub include_fit: bool,
+    /// Smoothing radius of the exported rows; 0 leaves them as they are.
+    pub smoothing: usize,
+    pub smoothing_filter: SmoothingFilter,
+    /// Robust reweighting passes of the LOESS filter.
+    pub loess_iterations: usize,
+    /// Corners of step-hold curves instead of even samples.
+    pub step_breakpoints: bool,
+    /// Each curve less the one before it, as layers of a stacked chart.
+    pub unstack_layers: bool,
+    /// Names of the `[[export.columns]]` templates added to the export.
+    pub columns: Vec<String>,
+}
+
+impl Default for ExportProfile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            format: ExportFormat::Csv,
+            precision: 6,
+            significant_figures: None,
+            delimiter: ',',
+            time_format: TimeExportFormat::Iso8601,
+            kind: ExportKind::Interpolated,
+            interpolation: InterpAlgorithm::Linear,
+            spline_span: DEFAULT_SPLINE_SPAN,
+            samples: 200,
+            include_distances: false,
+            include_angles: false,
+            include_steps: false,
+            include_flags: false,
+            include_capture_times: false,
+            include_confidence: false,
+            include_cartesian: false,
+            fit_model: FitModel::Polynomial,
+            fit_degree: 2,
+            include_fit: false,
+            smoothing: 0,
+            smoothing_filter: SmoothingFilter::MovingAverage,
+            loess_iterations: 2,
+            step_breakpoints: false,
+            unstack_layers: false,
+            columns: Vec::new(),
+        }
+    }
+}
+
+impl ExportProfile {
+    /// Delimiter as a single byte; non-ASCII values fall back to a comma.
+    pub fn delimiter_byte(&self) -> u8 {
+        u8::try_from(self.delimiter)
+            .ok()
+            .filter(u8::is_ascii)
+            .unwrap_or(b',')
+    }
+
+    fn builtin() -> Vec<Self> {
+        vec![
+            Self {
+                name: "Excel".to_string(),
+                format: ExportFormat::Xlsx,
+                ..Self::default()
+            },
+            Self {
+                name: "MATLAB".to_string(),
+                format: ExportFormat::Csv,
+                precision: 9,
+                ..Self::default()
+            },
+        ]
+    }
+}
+
+/// Reusable extra export column (`[[export.columns]]`): a built-in metric or a formula.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnTemplate {
+    /// Column header, also how export profiles refer to the template.
+    pub name: String,
+    /// Built-in metric; when set, `expression` is ignored.
+    pub metric: Option<ColumnMetric>,
+    /// Formula over `x`, `y`, `i`, `n`, `dx` and `dy`, e.g. `"y / x"`.
+    pub expression: String,
+}
+
+/// Config values a project file can carry; set fields win over the global config.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigOverrides {
+    pub curve_line: Option<StrokeStyle>,
+    pub curve_points: Option<PointStyle>,
+    pub crosshair: Option<CrosshairStyle>,
+    pub attention_highlight: Option<StrokeStyle>,
+    pub auto_place: Option<AutoPlaceConfig>,
+    /// Export settings restored when the project is opened (the name is not used).
+    pub export: Option<ExportProfile>,
+}
+
+impl ConfigOverrides {
+    /// Whether the overlay styles are overridden (they are kept or dropped together).
+    pub const fn has_styles(&self) -> bool {
+        self.curve_line.is_some()
+            || self.curve_points.is_some()
+            || self.crosshair.is_some()
+            || self.attention_highlight.is_some()
+    }
+
+    /// Take all overlay styles from `cfg`, or drop them.
+    pub fn set_styles_from(&mut self, cfg: Option<&AppConfig>) {
+        self.curve_line = cfg.map(|cfg| cfg.curve_line.clone());
+        self.curve_points = cfg.map(|cfg| cfg.curve_points.clone());
+        self.crosshair = cfg.map(|cfg| cfg.crosshair.clone());
+        self.attention_highlight = cfg.map(|cfg| cfg.attention_highlight.clone());
+    }
+}
+
+/// What happens to a file an export is about to overwrite (`[export] backup`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportBackup {
+    #[default]
+    Off,
+    /// Moved to `name.ext.bak`, replacing the previous backup.
+    Bak,
+    /// Moved to `name.YYYYMMDD-HHMMSS.ext` (UTC), so every overwritten version is kept.
+    Timestamped,
+}
+
+impl ExportBackup {
+    /// Where the existing file at `path` goes before an export written at `now` replaces it.
+    pub fn backup_path(self, path: &Path, now: DateTime<Utc>) -> Option<PathBuf> {
+        let name = path.file_name()?.to_string_lossy();
+        let backup = match self {
+            Self::Off => return None,
+            Self::Bak => format!("{name}.bak"),
+            Self::Timestamped => {
+                let stamp = now.format("%Y%m%d-%H%M%S");
+                match path.extension() {
+                    Some(ext) => {
+                        let stem = path.file_stem()?.to_string_lossy();
+                        format!("{stem}.{stamp}.{}", ext.to_string_lossy())
+                    }
+                    None => format!("{name}.{stamp}"),
+                }
+            }
+        };
+        Some(path.with_file_name(backup))
+    }
+}
+
 /// Parameters controlling export and auto-sampling.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -200,6 +385,9 @@ pub struct ExportConfig {
     pub samples_max: u32,
     pub auto_rel_tolerance: f32,
     pub auto_ref_samples: u32,
+    pub profiles: Vec<ExportProfile>,
+    pub backup: ExportBackup,
+    pub columns: Vec<ColumnTemplate>,
 }
 
 impl Default for ExportConfig {
@@ -208,6 +396,9 @@ impl Default for ExportConfig {
             samples_max: 10_000,
             auto_rel_tolerance: 0.005,
             auto_ref_samples: 2048,
+            profiles: ExportProfile::builtin(),
+            backup: ExportBackup::Off,
+            columns: Vec::new(),
         }
     }
 }
@@ -238,10 +429,106 @@ impl ExportConfig {
 }
 
 /// Root application configuration loaded from TOML.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UiConfig {
     pub language: Option<UiLanguage>,
+    /// Project that was open last; offered for restoring on the next launch.
+    pub last_project: Option<PathBuf>,
+    /// Stop blinking attention outlines while the window is idle or in the background.
+    pub power_saving: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            language: None,
+            last_project: None,
+            power_saving: true,
+        }
+    }
+}
+
+/// Which file dialog implementation to show (`[dialogs] backend`).
+///
+/// Backends the build or platform lacks fall back to the built-in dialog: the portal is
+/// Linux and BSD only, and the system dialogs need the `native-dialogs` feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DialogBackend {
+    /// The desktop portal inside Flatpak/Snap sandboxes, the built-in browser elsewhere.
+    #[default]
+    Auto,
+    Builtin,
+    /// The operating system's own dialogs through `rfd`.
+    Native,
+    Portal,
+}
+
+impl DialogBackend {
+    /// Backends this build can show, in menu order; `Auto` only where it can pick the portal.
+    pub fn choices() -> Vec<Self> {
+        let portal = crate::portal::available();
+        let mut choices = Vec::new();
+        if portal {
+            choices.push(Self::Auto);
+        }
+        choices.push(Self::Builtin);
+        if crate::native_dialog::available() {
+            choices.push(Self::Native);
+        }
+        if portal {
+            choices.push(Self::Portal);
+        }
+        choices
+    }
+}
+
+/// File dialog backend and the folders dialogs start in (`[dialogs]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DialogsConfig {
+    pub backend: DialogBackend,
+    /// Start folders until a file is picked during the session; `~` means the home folder.
+    pub image_dir: Option<PathBuf>,
+    pub export_dir: Option<PathBuf>,
+    pub project_dir: Option<PathBuf>,
+}
+
+impl DialogsConfig {
+    /// The backend dialogs actually use: never `Auto`, and never one this build lacks, so
+    /// `backend = "portal"` in a config shared with Windows or macOS keeps the built-in dialog there.
+    pub fn resolved_backend(&self) -> DialogBackend {
+        let portal = crate::portal::available();
+        match self.backend {
+            DialogBackend::Auto if portal && crate::portal::sandboxed() => DialogBackend::Portal,
+            DialogBackend::Portal if portal => DialogBackend::Portal,
+            DialogBackend::Native if crate::native_dialog::available() => DialogBackend::Native,
+            _ => DialogBackend::Builtin,
+        }
+    }
+
+    /// Configured start folder for images, with `~` expanded.
+    pub fn image_dir(&self) -> Option<PathBuf> {
+        self.image_dir.as_deref().map(expand_home)
+    }
+
+    /// Configured start folder for exports, with `~` expanded.
+    pub fn export_dir(&self) -> Option<PathBuf> {
+        self.export_dir.as_deref().map(expand_home)
+    }
+
+    /// Configured start folder for projects, with `~` expanded.
+    pub fn project_dir(&self) -> Option<PathBuf> {
+        self.project_dir.as_deref().map(expand_home)
+    }
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), BaseDirs::new()) {
+        (Ok(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => path.to_path_buf(),
+    }
 }
 
 /// Root application configuration loaded from TOML.
@@ -258,6 +545,10 @@ pub struct AppConfig {
     pub export: ExportConfig,
     pub auto_place: AutoPlaceConfig,
     pub ui: UiConfig,
+    pub cache: CacheConfig,
+    pub dialogs: DialogsConfig,
+    pub window: WindowConfig,
+    pub keymap: KeymapConfig,
 }
 
 impl Default for AppConfig {
@@ -276,6 +567,10 @@ impl Default for AppConfig {
             export: ExportConfig::default(),
             auto_place: AutoPlaceConfig::default(),
             ui: UiConfig::default(),
+            cache: CacheConfig::default(),
+            dialogs: DialogsConfig::default(),
+            window: WindowConfig::default(),
+            keymap: KeymapConfig::default(),
         }
     }
 }
@@ -296,6 +591,33 @@ impl AppConfig {
         Self::default()
     }
 
+    /// Read and parse one config file, keeping I/O and TOML errors apart from a missing file.
+    pub fn load_from(path: &Path) -> anyhow::Result<Option<Self>> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        let cfg = toml::from_str::<Self>(&contents)
+            .map_err(|err| anyhow::anyhow!("Failed to parse {}: {err}", path.display()))?;
+        Ok(Some(cfg))
+    }
+
+    /// File worth watching for edits: the first existing candidate, else where saves go.
+    pub fn watched_path() -> Option<PathBuf> {
+        Self::candidate_paths()
+            .into_iter()
+            .find(|path| path.is_file())
+            .or_else(Self::save_path)
+    }
+
+    /// Whether two configs would serialize to the same TOML.
+    pub fn same_settings(&self, other: &Self) -> bool {
+        toml::to_string(self).ok() == toml::to_string(other).ok()
+    }
+
     /// Sanitized multiplier for panning speed.
     pub const fn pan_speed_factor(&self) -> f32 {
         self.pan_speed.clamp(0.01, 50.0)
@@ -322,6 +644,39 @@ impl AppConfig {
         self.save_to_default_path().map(|_| ())
     }
 
+    /// Persist the file dialog backend (`[dialogs] backend = ...`) and keep it in memory.
+    pub fn persist_dialog_backend(&mut self, backend: DialogBackend) -> std::io::Result<()> {
+        self.dialogs.backend = backend;
+        self.save_to_default_path().map(|_| ())
+    }
+
+    /// Persist the last open project (`[ui] last_project = ...`) and keep it in memory.
+    pub fn persist_last_project(&mut self, path: Option<PathBuf>) -> std::io::Result<()> {
+        if self.ui.last_project == path {
+            return Ok(());
+        }
+        self.ui.last_project = path;
+        self.save_to_default_path().map(|_| ())
+    }
+
+    /// Persist the overwrite backup mode (`[export] backup = ...`) and keep it in memory.
+    pub fn persist_export_backup(&mut self, backup: ExportBackup) -> std::io::Result<()> {
+        self.export.backup = backup;
+        self.save_to_default_path().map(|_| ())
+    }
+
+    /// Persist the export profile list to disk and keep it in memory.
+    pub fn persist_export_profiles(&mut self, profiles: Vec<ExportProfile>) -> std::io::Result<()> {
+        self.export.profiles = profiles;
+        self.save_to_default_path().map(|_| ())
+    }
+
+    /// Persist rebound keyboard shortcuts (`[keymap]`) and keep them in memory.
+    pub fn persist_keymap(&mut self, keymap: KeymapConfig) -> std::io::Result<()> {
+        self.keymap = keymap;
+        self.save_to_default_path().map(|_| ())
+    }
+
     fn save_to_default_path(&self) -> std::io::Result<PathBuf> {
         let path = Self::save_path().ok_or_else(|| {
             std::io::Error::new(
@@ -339,6 +694,9 @@ impl AppConfig {
     }
 
     fn candidate_paths() -> Vec<PathBuf> {
+        if let Some(root) = crate::portable::root() {
+            return vec![root.join(CONFIG_FILE_NAME)];
+        }
         let mut paths = Vec::new();
 
         if let Ok(exe_path) = std::env::current_exe()
@@ -359,6 +717,9 @@ impl AppConfig {
     }
 
     fn save_path() -> Option<PathBuf> {
+        if let Some(root) = crate::portable::root() {
+            return Some(root.join(CONFIG_FILE_NAME));
+        }
         if let Some(proj_dirs) = ProjectDirs::from("dev", "Curcat", "Curcat") {
             return Some(proj_dirs.config_dir().join(CONFIG_FILE_NAME));
         }
@@ -371,6 +732,73 @@ impl AppConfig {
     }
 }
 
+/// Main window size on first launch and whether its geometry is remembered (`[window]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+    /// Inner size in points used until a geometry has been remembered.
+    pub width: f32,
+    pub height: f32,
+    /// Restore the size, position and maximized state of the last run.
+    pub remember: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            width: 1200.0,
+            height: 800.0,
+            remember: true,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// Smallest inner size the window can be shrunk to, in points.
+    pub const MIN_SIZE: [f32; 2] = [800.0, 600.0];
+    const MAX_SIDE: f32 = 16_384.0;
+
+    /// Configured size, kept between the minimum window size and a sane upper bound.
+    pub fn size_sanitized(&self) -> [f32; 2] {
+        let side = |value: f32, min: f32| {
+            if value.is_finite() {
+                value.clamp(min, Self::MAX_SIDE)
+            } else {
+                min
+            }
+        };
+        [
+            side(self.width, Self::MIN_SIZE[0]),
+            side(self.height, Self::MIN_SIZE[1]),
+        ]
+    }
+}
+
+/// On-disk cache of decoded images and snap maps (`[cache]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// Size budget in MiB; least recently used entries are evicted beyond it.
+    pub max_size_mb: u32,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_size_mb: 2048,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Size budget in bytes, clamped to 64 MiB .. 64 GiB.
+    pub fn max_size_bytes_sanitized(&self) -> u64 {
+        u64::from(self.max_size_mb.clamp(64, 65_536)) * 1024 * 1024
+    }
+}
+
 /// Limits for image decoding to guard against resource abuse.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -540,10 +968,15 @@ mod tests {
             r#"
                 [ui]
                 language = "ru"
+                last_project = "/data/charts/run.curcat"
             "#,
         )
         .unwrap();
         assert_eq!(cfg.ui.language, Some(UiLanguage::Ru));
+        assert_eq!(
+            cfg.ui.last_project.as_deref(),
+            Some(std::path::Path::new("/data/charts/run.curcat"))
+        );
     }
 
     #[test]
@@ -554,4 +987,154 @@ mod tests {
         assert!(text.contains("[ui]"));
         assert!(text.contains("language = \"en\""));
     }
+
+    #[test]
+    fn parses_export_profiles_table_array() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+                [[export.profiles]]
+                name = "Semicolon CSV"
+                format = "csv"
+                precision = 3
+                delimiter = ";"
+                kind = "raw_points"
+                include_flags = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.export.profiles.len(), 1);
+        let profile = &cfg.export.profiles[0];
+        assert_eq!(profile.format, ExportFormat::Csv);
+        assert_eq!(profile.precision, 3);
+        assert_eq!(profile.delimiter_byte(), b';');
+        assert_eq!(profile.kind, ExportKind::RawPoints);
+        assert!(profile.include_flags);
+        assert_eq!(profile.interpolation, InterpAlgorithm::Linear);
+    }
+
+    #[test]
+    fn parses_export_column_templates() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+                [[export.columns]]
+                name = "ratio"
+                expression = "y / x"
+
+                [[export.columns]]
+                name = "s"
+                metric = "arc_length"
+
+                [[export.columns]]
+                name = "broken"
+                expression = "y /"
+
+                [[export.profiles]]
+                name = "With ratio"
+                columns = ["ratio"]
+            "#,
+        )
+        .unwrap();
+        let columns = &cfg.export.columns;
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns[0].metric, None);
+        assert_eq!(columns[1].metric, Some(ColumnMetric::ArcLength));
+        assert!(columns[0].problem().is_none() && columns[1].problem().is_none());
+        assert!(columns[2].problem().is_some());
+        assert_eq!(cfg.export.profiles[0].columns, ["ratio"]);
+        assert!(AppConfig::default().export.columns.is_empty());
+    }
+
+    #[test]
+    fn parses_dialog_backend_and_start_folders() {
+        let cfg: AppConfig = toml::from_str(
+            r#"
+                [dialogs]
+                backend = "portal"
+                export_dir = "/data/exports"
+                project_dir = "~/charts"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.dialogs.backend, DialogBackend::Portal);
+        assert_eq!(
+            cfg.dialogs.resolved_backend(),
+            if crate::portal::available() {
+                DialogBackend::Portal
+            } else {
+                DialogBackend::Builtin
+            }
+        );
+        assert_eq!(cfg.dialogs.image_dir(), None);
+        assert_eq!(
+            cfg.dialogs.export_dir().as_deref(),
+            Some(Path::new("/data/exports"))
+        );
+        let project_dir = cfg.dialogs.project_dir().unwrap();
+        assert!(project_dir.ends_with("charts"));
+        if let Some(dirs) = BaseDirs::new() {
+            assert_eq!(project_dir, dirs.home_dir().join("charts"));
+        }
+        assert_eq!(AppConfig::default().dialogs.backend, DialogBackend::Auto);
+        let native: DialogsConfig = toml::from_str(r#"backend = "native""#).unwrap();
+        assert_eq!(
+            native.resolved_backend() == DialogBackend::Native,
+            crate::native_dialog::available()
+        );
+        assert_eq!(
+            DialogBackend::choices().contains(&DialogBackend::Native),
+            crate::native_dialog::available()
+        );
+    }
+
+    #[test]
+    fn parses_window_size_and_keeps_it_above_the_minimum() {
+        let cfg: AppConfig = toml::from_str(
+            r"
+                [window]
+                width = 1600
+                height = 300
+                remember = false
+            ",
+        )
+        .unwrap();
+        let [width, height] = cfg.window.size_sanitized();
+        assert!((width - 1600.0).abs() < f32::EPSILON && (height - 600.0).abs() < f32::EPSILON);
+        assert!(!cfg.window.remember);
+        assert!(AppConfig::default().window.remember);
+    }
+
+    #[test]
+    fn export_backup_names_sit_beside_the_export() {
+        let cfg: AppConfig = toml::from_str("[export]\nbackup = \"timestamped\"").unwrap();
+        assert_eq!(cfg.export.backup, ExportBackup::Timestamped);
+        assert_eq!(AppConfig::default().export.backup, ExportBackup::Off);
+
+        let now = DateTime::<Utc>::from_timestamp(1_790_000_000, 0).unwrap();
+        let path = Path::new("/data/curves.csv");
+        assert_eq!(ExportBackup::Off.backup_path(path, now), None);
+        assert_eq!(
+            ExportBackup::Bak.backup_path(path, now),
+            Some(PathBuf::from("/data/curves.csv.bak"))
+        );
+        assert_eq!(
+            ExportBackup::Timestamped.backup_path(path, now),
+            Some(PathBuf::from("/data/curves.20260921-141320.csv"))
+        );
+        assert_eq!(
+            ExportBackup::Timestamped.backup_path(Path::new("/data/curves"), now),
+            Some(PathBuf::from("/data/curves.20260921-141320"))
+        );
+    }
+
+    #[test]
+    fn default_config_has_builtin_export_profiles() {
+        let cfg = AppConfig::default();
+        let names: Vec<_> = cfg
+            .export
+            .profiles
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["Excel", "MATLAB"]);
+    }
 }
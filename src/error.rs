@@ -0,0 +1,362 @@
+//! Failures the user is told about, with the low-level cause behind each one.
+//!
+//! [`CurcatError::user_message`] names the operation and what to do about the cause (a missing
+//! file, a full disk, a damaged image), while [`CurcatError::details`] keeps the whole source
+//! chain for the "show details" part of the notification and for bug reports.
+
+use crate::i18n::UiLanguage;
+use crate::image::FormatMismatch;
+use std::error::Error as _;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Preference whose write to the config file failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preference {
+    Language,
+    DialogBackend,
+    ExportBackup,
+    Keymap,
+}
+
+#[derive(Debug, Error)]
+pub enum CurcatError {
+    #[error("failed to load {label}")]
+    ImageLoad {
+        label: String,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to load project {}", path.display())]
+    ProjectLoad {
+        path: PathBuf,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("project save failed")]
+    ProjectSave {
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("{format} export failed")]
+    Export {
+        format: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to save the {preference:?} preference")]
+    Preference {
+        preference: Preference,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// What went wrong underneath an operation, as far as the user can act on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cause {
+    NotFound,
+    PermissionDenied,
+    StorageFull,
+    ReadOnly,
+    /// A file format the decoder does not handle.
+    Unsupported,
+    /// The bytes do not decode: a truncated download or a wrong extension.
+    Corrupt,
+    /// An image beyond the decoder's size limits.
+    TooLarge,
+    /// Contents other than the extension claims, or a container no decoder opens.
+    FormatMismatch,
+    Other,
+}
+
+impl Cause {
+    const fn from_io(kind: io::ErrorKind) -> Self {
+        match kind {
+            io::ErrorKind::NotFound => Self::NotFound,
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            io::ErrorKind::StorageFull | io::ErrorKind::QuotaExceeded => Self::StorageFull,
+            io::ErrorKind::ReadOnlyFilesystem => Self::ReadOnly,
+            io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof => Self::Corrupt,
+            io::ErrorKind::Unsupported => Self::Unsupported,
+            _ => Self::Other,
+        }
+    }
+
+    fn from_image(err: &image::ImageError) -> Self {
+        match err {
+            image::ImageError::Unsupported(_) => Self::Unsupported,
+            image::ImageError::Decoding(_) => Self::Corrupt,
+            image::ImageError::Limits(_) => Self::TooLarge,
+            image::ImageError::IoError(err) => Self::from_io(err.kind()),
+            image::ImageError::Parameter(_) | image::ImageError::Encoding(_) => Self::Other,
+        }
+    }
+
+    /// What to do about the cause; `None` when only the error text itself can tell.
+    const fn hint(self, lang: UiLanguage) -> Option<&'static str> {
+        Some(match (self, lang) {
+            (Self::NotFound, UiLanguage::En) => {
+                "the file or folder does not exist. Check whether it was moved or renamed."
+            }
+            (Self::NotFound, UiLanguage::Ru) => {
+                "файл или папка не найдены. Проверьте, не были ли они перемещены или переименованы."
+            }
+            (Self::PermissionDenied, UiLanguage::En) => {
+                "access denied. Check the file permissions or choose another folder."
+            }
+            (Self::PermissionDenied, UiLanguage::Ru) => {
+                "нет доступа. Проверьте права на файл или выберите другую папку."
+            }
+            (Self::StorageFull, UiLanguage::En) => "the disk is full. Free some space and retry.",
+            (Self::StorageFull, UiLanguage::Ru) => {
+                "на диске нет места. Освободите место и повторите попытку."
+            }
+            (Self::ReadOnly, UiLanguage::En) => {
+                "the location is read-only. Choose a folder you can write to."
+            }
+            (Self::ReadOnly, UiLanguage::Ru) => {
+                "место доступно только для чтения. Выберите папку, в которую можно писать."
+            }
+            (Self::Unsupported, UiLanguage::En) => {
+                "this format is not supported. Convert the file to PNG or JPEG first."
+            }
+            (Self::Unsupported, UiLanguage::Ru) => {
+                "формат не поддерживается. Сначала преобразуйте файл в PNG или JPEG."
+            }
+            (Self::Corrupt, UiLanguage::En) => {
+                "the file is damaged or is not what its extension says."
+            }
+            (Self::Corrupt, UiLanguage::Ru) => {
+                "файл повреждён или не соответствует своему расширению."
+            }
+            (Self::TooLarge, UiLanguage::En) => {
+                "the image is too large to decode. Reduce it in an image editor."
+            }
+            (Self::TooLarge, UiLanguage::Ru) => {
+                "изображение слишком большое для декодирования. Уменьшите его в графическом редакторе."
+            }
+            // The message names both formats, see `mismatch_message`.
+            (Self::FormatMismatch | Self::Other, _) => return None,
+        })
+    }
+}
+
+impl CurcatError {
+    /// The errors below this one, outermost first.
+    fn sources(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        std::iter::successors(self.source(), |&err| err.source())
+    }
+
+    /// The innermost error the user can act on, looking through contexts and wrappers.
+    pub fn cause(&self) -> Cause {
+        self.sources()
+            .filter_map(|err| {
+                if err.is::<FormatMismatch>() {
+                    return Some(Cause::FormatMismatch);
+                }
+                if let Some(err) = err.downcast_ref::<io::Error>() {
+                    return Some(Cause::from_io(err.kind()));
+                }
+                if let Some(err) = err.downcast_ref::<image::ImageError>() {
+                    return Some(Cause::from_image(err));
+                }
+                (err.is::<bincode::error::DecodeError>()
+                    || err.is::<lz4_flex::block::DecompressError>())
+                .then_some(Cause::Corrupt)
+            })
+            .find(|cause| *cause != Cause::Other)
+            .unwrap_or(Cause::Other)
+    }
+
+    /// The operation that failed, in the user's language.
+    fn operation(&self, lang: UiLanguage) -> String {
+        match (self, lang) {
+            (Self::ImageLoad { label, .. }, UiLanguage::En) => format!("Failed to load {label}"),
+            (Self::ImageLoad { label, .. }, UiLanguage::Ru) => {
+                format!("Не удалось загрузить {label}")
+            }
+            (Self::ProjectLoad { .. }, UiLanguage::En) => "Failed to load project".to_string(),
+            (Self::ProjectLoad { .. }, UiLanguage::Ru) => "Не удалось загрузить проект".to_string(),
+            (Self::ProjectSave { .. }, UiLanguage::En) => "Project save failed".to_string(),
+            (Self::ProjectSave { .. }, UiLanguage::Ru) => "Ошибка сохранения проекта".to_string(),
+            (Self::Export { format, .. }, UiLanguage::En) => format!("{format} export failed"),
+            (Self::Export { format, .. }, UiLanguage::Ru) => format!("Ошибка экспорта {format}"),
+            (Self::Preference { preference, .. }, UiLanguage::En) => {
+                let what = match preference {
+                    Preference::Language => "language",
+                    Preference::DialogBackend => "file dialog",
+                    Preference::ExportBackup => "export backup",
+                    Preference::Keymap => "keyboard shortcut",
+                };
+                format!("Failed to save {what} preference")
+            }
+            (Self::Preference { preference, .. }, UiLanguage::Ru) => match preference {
+                Preference::Language => "Не удалось сохранить выбранный язык интерфейса",
+                Preference::DialogBackend => "Не удалось сохранить выбор файловых диалогов",
+                Preference::ExportBackup => "Не удалось сохранить настройку копий экспорта",
+                Preference::Keymap => "Не удалось сохранить сочетания клавиш",
+            }
+            .to_string(),
+        }
+    }
+
+    /// One line for the status bar and the notification: the operation and what to do next.
+    ///
+    /// Causes without a hint fall back to the text of the error directly below the operation,
+    /// which is what these messages showed before they had hints.
+    pub fn user_message(&self, lang: UiLanguage) -> String {
+        let operation = self.operation(lang);
+        if let Some(mismatch) = self
+            .sources()
+            .find_map(|err| err.downcast_ref::<FormatMismatch>())
+        {
+            return format!("{operation}: {}", mismatch_message(mismatch, lang));
+        }
+        match self.cause().hint(lang) {
+            Some(hint) => format!("{operation}: {hint}"),
+            None => match self.source() {
+                Some(source) => format!("{operation}: {source}"),
+                None => operation,
+            },
+        }
+    }
+
+    /// The full source chain, one error per line, for the expandable details.
+    pub fn details(&self) -> String {
+        std::iter::once(self.to_string())
+            .chain(self.sources().map(ToString::to_string))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// What the file contains next to what its name claims, and how to get it open.
+fn mismatch_message(mismatch: &FormatMismatch, lang: UiLanguage) -> String {
+    let named = mismatch.named;
+    match (mismatch.actual, lang) {
+        (Some(actual), UiLanguage::En) if actual.supported => format!(
+            "the file is named as {} but contains {} ({}), which does not decode either. The file may be damaged.",
+            named.unwrap_or("an image"),
+            actual.name,
+            actual.mime
+        ),
+        (Some(actual), UiLanguage::Ru) if actual.supported => format!(
+            "файл назван как {}, но содержит {} ({}), который тоже не декодируется. Возможно, файл повреждён.",
+            named.unwrap_or("изображение"),
+            actual.name,
+            actual.mime
+        ),
+        (Some(actual), UiLanguage::En) => format!(
+            "the file contains {} ({}), which cannot be opened. Export or convert it to PNG first.",
+            actual.name, actual.mime
+        ),
+        (Some(actual), UiLanguage::Ru) => format!(
+            "файл содержит {} ({}), который не открывается. Сначала экспортируйте или преобразуйте его в PNG.",
+            actual.name, actual.mime
+        ),
+        (None, UiLanguage::En) => format!(
+            "the file is named as {} but its contents are not a known image format.",
+            named.unwrap_or("an image")
+        ),
+        (None, UiLanguage::Ru) => format!(
+            "файл назван как {}, но его содержимое не похоже на известный формат изображений.",
+            named.unwrap_or("изображение")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context as _;
+
+    fn export_error(source: anyhow::Error) -> CurcatError {
+        CurcatError::Export {
+            format: "CSV",
+            source,
+        }
+    }
+
+    #[test]
+    fn io_kinds_behind_contexts_map_to_actionable_causes() {
+        for (kind, cause) in [
+            (io::ErrorKind::NotFound, Cause::NotFound),
+            (io::ErrorKind::PermissionDenied, Cause::PermissionDenied),
+            (io::ErrorKind::StorageFull, Cause::StorageFull),
+            (io::ErrorKind::ReadOnlyFilesystem, Cause::ReadOnly),
+            (io::ErrorKind::Interrupted, Cause::Other),
+        ] {
+            let source = Err::<(), _>(io::Error::from(kind))
+                .context("Failed to create data.csv")
+                .unwrap_err();
+            assert_eq!(export_error(source).cause(), cause, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn user_message_prefers_the_hint_and_details_keep_the_chain() {
+        let source = Err::<(), _>(io::Error::from(io::ErrorKind::StorageFull))
+            .context("Failed to write data.csv")
+            .unwrap_err();
+        let err = export_error(source);
+        assert_eq!(
+            err.user_message(UiLanguage::En),
+            "CSV export failed: the disk is full. Free some space and retry."
+        );
+        let details = err.details();
+        let lines: Vec<&str> = details.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "CSV export failed");
+        assert_eq!(lines[1], "Failed to write data.csv");
+    }
+
+    #[test]
+    fn unknown_causes_show_the_error_text() {
+        let err = CurcatError::ProjectLoad {
+            path: PathBuf::from("plot.curcat"),
+            source: anyhow::anyhow!("Not a Curcat project file: magic signature mismatch"),
+        };
+        assert_eq!(err.cause(), Cause::Other);
+        assert_eq!(
+            err.user_message(UiLanguage::En),
+            "Failed to load project: Not a Curcat project file: magic signature mismatch"
+        );
+    }
+
+    #[test]
+    fn image_decode_errors_are_reported_as_damaged_files() {
+        let decode = image::load_from_memory_with_format(b"not a png", image::ImageFormat::Png)
+            .expect_err("garbage bytes do not decode");
+        let err = CurcatError::ImageLoad {
+            label: "scan.png".to_string(),
+            source: anyhow::Error::new(decode).context("Failed to decode scan.png"),
+        };
+        assert_eq!(err.cause(), Cause::Corrupt);
+    }
+
+    #[test]
+    fn format_mismatch_names_the_container_found() {
+        let source = FormatMismatch::explain(
+            Some(std::path::Path::new("scan.png")),
+            b"%PDF-1.7",
+            anyhow::anyhow!("Failed to read Png image dimensions"),
+        )
+        .expect("PDF named as PNG");
+        let err = CurcatError::ImageLoad {
+            label: "scan.png".to_string(),
+            source: anyhow::Error::new(source),
+        };
+        assert_eq!(err.cause(), Cause::FormatMismatch);
+        assert_eq!(
+            err.user_message(UiLanguage::En),
+            "Failed to load scan.png: the file contains PDF (application/pdf), which cannot be \
+             opened. Export or convert it to PNG first."
+        );
+        assert!(
+            err.details()
+                .contains("Failed to read Png image dimensions")
+        );
+    }
+}
@@ -1,16 +1,37 @@
-//! Export helpers for writing picked points to CSV, XLSX, JSON, RON, HTML, XML, and Markdown formats.
+//! Export helpers for writing picked points to CSV, XLSX, ODS, Arrow IPC, Parquet, SQL, `NetCDF`, JSON, RON, HTML, XML, and Markdown formats.
+//! Curve families can also be written as a gridded surface (CSV matrix or `NetCDF`).
 
+use crate::calibration_report::{CalibrationReport, ReportValue};
 use crate::interp::XYPoint;
-use crate::types::{AngleUnit, AxisUnit, AxisValue, CoordSystem};
+use crate::types::{AngleUnit, AxisUnit, AxisValue, CoordSystem, FloatFormat, ScaleKind};
 use chrono::{Datelike, Duration, Timelike};
 use maud::{DOCTYPE, html};
 use ron::ser::PrettyConfig;
-use rust_xlsxwriter::{ExcelDateTime, Format, Workbook, XlsxError};
-use serde::Serialize;
+use rust_xlsxwriter::{
+    ExcelDateTime, Format, Table, TableColumn, TableStyle, Workbook, Worksheet, XlsxError,
+};
 use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Number, Value};
 use std::collections::BTreeMap;
 use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+mod arrow;
+mod columns;
+mod netcdf;
+mod ods;
+mod parquet;
+mod sql;
+mod surface;
+
+pub use arrow::export_to_arrow;
+pub use columns::ColumnMetric;
+pub use netcdf::export_to_netcdf;
+pub use ods::export_to_ods;
+pub use parquet::export_to_parquet;
+pub use sql::export_to_sql;
+pub use surface::{Surface, SurfaceFormat};
 
 /// Export-ready dataset plus axis units and optional computed columns.
 #[derive(Debug, Clone)]
@@ -20,11 +41,209 @@ pub struct ExportPayload {
     pub y_unit: AxisUnit,
     pub x_label: String,
     pub y_label: String,
+    /// Chart title heading report-style exports (HTML, XML metadata).
+    pub title: Option<String>,
+    /// Quality figures of the calibration the points were read with, for the metadata.
+    pub calibration_report: Option<CalibrationReport>,
     pub coord_system: CoordSystem,
     pub angle_unit: Option<AngleUnit>,
     pub extra_columns: Vec<ExportExtraColumn>,
+    pub text_columns: Vec<ExportTextColumn>,
+    pub options: ExportOptions,
 }
 
+/// Output formatting knobs shared by all exporters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Fractional digits for float values (x/y are still trimmed of trailing zeros).
+    pub decimals: u8,
+    /// Field separator for CSV output.
+    pub csv_delimiter: u8,
+    /// Significant figures of each column's span; `None` keeps fixed `decimals`.
+    pub significant_figures: Option<u8>,
+}
+
+impl ExportOptions {
+    /// Upper bound for `decimals`; beyond this f64 output is noise.
+    pub const MAX_DECIMALS: u8 = 15;
+
+    const fn decimals(self) -> usize {
+        let clamped = if self.decimals > Self::MAX_DECIMALS {
+            Self::MAX_DECIMALS
+        } else {
+            self.decimals
+        };
+        clamped as usize
+    }
+
+    /// Float format for a column holding `values`.
+    ///
+    /// With significant figures the precision follows the column span, so a `0..1e-3` column
+    /// and a `0..5000` column both keep the same number of meaningful digits.
+    fn float_format(self, values: impl IntoIterator<Item = f64>) -> FloatFormat {
+        let Some(significant) = self.significant_figures else {
+            return FloatFormat::Fixed(self.decimals());
+        };
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut magnitude = 0.0_f64;
+        for v in values.into_iter().filter(|v| v.is_finite()) {
+            min = min.min(v);
+            max = max.max(v);
+            magnitude = magnitude.max(v.abs());
+        }
+        let span = if min <= max { max - min } else { 0.0 };
+        let significant = significant.clamp(1, Self::MAX_DECIMALS);
+        FloatFormat::adaptive(span, magnitude, usize::from(significant))
+    }
+}
+
+/// Float formats resolved once per payload: x, y, and one per extra column.
+struct ColumnFormats {
+    x: FloatFormat,
+    y: FloatFormat,
+    extras: Vec<FloatFormat>,
+}
+
+impl ColumnFormats {
+    fn resolve(payload: &ExportPayload) -> Self {
+        Self::resolve_stacked(&[payload])
+    }
+
+    /// Formats of `payloads` written one under another as a single table.
+    fn resolve_stacked(payloads: &[&ExportPayload]) -> Self {
+        let options = payloads.first().map(|p| p.options).unwrap_or_default();
+        let points = || payloads.iter().flat_map(|payload| &payload.points);
+        let extras = payloads.first().map_or(0, |p| p.extra_columns.len());
+        Self {
+            x: options.float_format(points().map(|p| p.x)),
+            y: options.float_format(points().map(|p| p.y)),
+            extras: (0..extras)
+                .map(|idx| {
+                    options.float_format(
+                        payloads
+                            .iter()
+                            .flat_map(|payload| &payload.extra_columns[idx].values)
+                            .flatten()
+                            .copied(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            decimals: 6,
+            csv_delimiter: b',',
+            significant_figures: None,
+        }
+    }
+}
+
+/// How `DateTime` columns are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeExportFormat {
+    /// `YYYY-MM-DD HH:MM:SS[.fraction]` in UTC, or the native date-time type of the format.
+    #[default]
+    Iso8601,
+    /// Seconds since 1970-01-01 00:00 UTC.
+    UnixSeconds,
+    /// Days since 1899-12-30 with the time of day as the fraction, as spreadsheets count.
+    ExcelSerial,
+}
+
+impl TimeExportFormat {
+    /// Excel serial day of the Unix epoch.
+    const EXCEL_UNIX_EPOCH: f64 = 25_569.0;
+    const SECONDS_PER_DAY: f64 = 86_400.0;
+
+    /// `seconds` since the Unix epoch as a number in this format; `None` for ISO-8601 text.
+    pub fn numeric(self, seconds: f64) -> Option<f64> {
+        match self {
+            Self::Iso8601 => None,
+            Self::UnixSeconds => Some(seconds),
+            Self::ExcelSerial => Some(seconds / Self::SECONDS_PER_DAY + Self::EXCEL_UNIX_EPOCH),
+        }
+    }
+}
+
+impl ExportPayload {
+    /// Turn `DateTime` x and y columns into plain numbers when `format` is numeric.
+    pub fn apply_time_format(&mut self, format: TimeExportFormat) {
+        if format == TimeExportFormat::Iso8601 {
+            return;
+        }
+        let convert = |v: f64| format.numeric(v).unwrap_or(v);
+        if self.x_unit == AxisUnit::DateTime {
+            self.x_unit = AxisUnit::Float;
+            for p in &mut self.points {
+                p.x = convert(p.x);
+            }
+        }
+        if self.y_unit == AxisUnit::DateTime {
+            self.y_unit = AxisUnit::Float;
+            for p in &mut self.points {
+                p.y = convert(p.y);
+            }
+        }
+    }
+}
+
+/// Rows written between progress updates and cancellation checks in streamed exports.
+const STREAM_CHUNK_ROWS: usize = 4096;
+
+/// Progress counters and cancellation flag shared with a background export.
+#[derive(Debug, Default)]
+pub struct ExportProgress {
+    rows_done: AtomicUsize,
+    rows_total: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl ExportProgress {
+    pub fn rows_done(&self) -> usize {
+        self.rows_done.load(Ordering::Relaxed)
+    }
+
+    pub fn rows_total(&self) -> usize {
+        self.rows_total.load(Ordering::Relaxed)
+    }
+
+    /// Completed share in `0.0..=1.0` (zero until the total is known).
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn fraction(&self) -> f32 {
+        let total = self.rows_total();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.rows_done() as f64 / total as f64).clamp(0.0, 1.0) as f32
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Error returned when a streamed export is cancelled through [`ExportProgress::cancel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportCancelled;
+
+impl std::fmt::Display for ExportCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("export cancelled")
+    }
+}
+
+impl std::error::Error for ExportCancelled {}
+
 /// Optional per-row numeric column aligned with the exported points.
 #[derive(Debug, Clone)]
 pub struct ExportExtraColumn {
@@ -42,16 +261,102 @@ impl ExportExtraColumn {
     }
 }
 
+/// Optional per-row text column (annotations, labels) placed after numeric extras.
+#[derive(Debug, Clone)]
+pub struct ExportTextColumn {
+    pub header: String,
+    pub values: Vec<Option<String>>,
+}
+
+impl ExportTextColumn {
+    /// Create a new text column with a header and row-aligned values.
+    pub fn new(header: impl Into<String>, values: Vec<Option<String>>) -> Self {
+        Self {
+            header: header.into(),
+            values,
+        }
+    }
+}
+
 impl ExportPayload {
     const fn row_count(&self) -> usize {
         self.points.len()
     }
+
+    /// Whether `other` stacks under `self`; Y labels may differ, see [`merge_curve_payloads`].
+    fn same_columns(&self, other: &Self) -> bool {
+        self.x_unit == other.x_unit
+            && self.y_unit == other.y_unit
+            && self.x_label == other.x_label
+            && self
+                .extra_columns
+                .iter()
+                .map(|c| &c.header)
+                .eq(other.extra_columns.iter().map(|c| &c.header))
+            && self
+                .text_columns
+                .iter()
+                .map(|c| &c.header)
+                .eq(other.text_columns.iter().map(|c| &c.header))
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Header of the text column naming each row's curve in merged multi-curve exports.
+pub const CURVE_COLUMN_HEADER: &str = "curve";
+
+/// Header of the text column naming the Y axis each row was read against on dual-axis charts.
+pub const Y_AXIS_COLUMN_HEADER: &str = "y_axis";
+
+/// Default header of the curve family parameter column, used when the project names none.
+pub const PARAMETER_COLUMN_HEADER: &str = "parameter";
+
+/// Stack named per-curve payloads into one table with a trailing [`CURVE_COLUMN_HEADER`] column.
+///
+/// A single curve is returned unchanged, so one-curve exports keep their layout. All payloads
+/// must share units and column headers, as they do when built for the same export. Curves read
+/// against different Y axes carry different Y labels; their merged column is plain `y`.
+pub fn merge_curve_payloads(curves: Vec<(String, ExportPayload)>) -> anyhow::Result<ExportPayload> {
+    let mut curves = curves.into_iter();
+    let Some((first_name, mut merged)) = curves.next() else {
+        anyhow::bail!("Nothing to export.");
+    };
+    let rest: Vec<_> = curves.collect();
+    if rest.is_empty() {
+        return Ok(merged);
+    }
+    let mut names = vec![Some(first_name); merged.row_count()];
+    for (name, payload) in rest {
+        if !merged.same_columns(&payload) {
+            anyhow::bail!("Curve '{name}' does not share the columns of the other curves.");
+        }
+        if payload.y_label != merged.y_label {
+            merged.y_label = "y".to_string();
+        }
+        names.extend(std::iter::repeat_n(Some(name), payload.row_count()));
+        merged.points.extend(payload.points);
+        for (dst, src) in merged.extra_columns.iter_mut().zip(payload.extra_columns) {
+            dst.values.extend(src.values);
+        }
+        for (dst, src) in merged.text_columns.iter_mut().zip(payload.text_columns) {
+            dst.values.extend(src.values);
+        }
+    }
+    merged
+        .text_columns
+        .push(ExportTextColumn::new(CURVE_COLUMN_HEADER, names));
+    Ok(merged)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ExportFormat {
     Csv,
     Xlsx,
+    Ods,
+    Arrow,
+    Parquet,
+    Sql,
+    NetCdf,
     Json,
     Ron,
     Html,
@@ -64,6 +369,11 @@ impl ExportFormat {
         match self {
             Self::Csv => "CSV",
             Self::Xlsx => "Excel",
+            Self::Ods => "ODS",
+            Self::Arrow => "Arrow IPC",
+            Self::Parquet => "Parquet",
+            Self::Sql => "SQL",
+            Self::NetCdf => "NetCDF",
             Self::Json => "JSON",
             Self::Ron => "RON",
             Self::Html => "HTML",
@@ -76,6 +386,11 @@ impl ExportFormat {
         match self {
             Self::Csv => "curve.csv",
             Self::Xlsx => "curve.xlsx",
+            Self::Ods => "curve.ods",
+            Self::Arrow => "curve.arrow",
+            Self::Parquet => "curve.parquet",
+            Self::Sql => "curve.sql",
+            Self::NetCdf => "curve.nc",
             Self::Json => "curve.json",
             Self::Ron => "curve.ron",
             Self::Html => "curve.html",
@@ -88,6 +403,11 @@ impl ExportFormat {
         match self {
             Self::Csv => "csv",
             Self::Xlsx => "xlsx",
+            Self::Ods => "ods",
+            Self::Arrow => "arrow",
+            Self::Parquet => "parquet",
+            Self::Sql => "sql",
+            Self::NetCdf => "nc",
             Self::Json => "json",
             Self::Ron => "ron",
             Self::Html => "html",
@@ -96,17 +416,55 @@ impl ExportFormat {
         }
     }
 
-    pub fn export(self, path: &std::path::Path, payload: &ExportPayload) -> Result<(), String> {
+    /// Other extension the save dialog offers for the same format.
+    pub const fn alternate_extension(self) -> Option<&'static str> {
+        match self {
+            Self::Arrow => Some("feather"),
+            _ => None,
+        }
+    }
+
+    pub fn export(self, path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
         match self {
-            Self::Csv => export_to_csv(path, payload).map_err(|e| e.to_string()),
-            Self::Xlsx => export_to_xlsx(path, payload).map_err(|e| e.to_string()),
-            Self::Json => export_to_json(path, payload).map_err(|e| e.to_string()),
-            Self::Ron => export_to_ron(path, payload).map_err(|e| e.to_string()),
-            Self::Html => export_to_html(path, payload).map_err(|e| e.to_string()),
-            Self::Xml => export_to_xml(path, payload).map_err(|e| e.to_string()),
-            Self::Markdown => export_to_markdown(path, payload).map_err(|e| e.to_string()),
+            Self::Csv => export_to_csv(path, payload),
+            Self::Xlsx => export_to_xlsx(path, payload).map_err(anyhow::Error::from),
+            Self::Ods => export_to_ods(path, payload),
+            Self::Arrow => export_to_arrow(path, payload),
+            Self::Parquet => export_to_parquet(path, payload),
+            Self::Sql => export_to_sql(path, payload),
+            Self::NetCdf => export_to_netcdf(path, payload),
+            Self::Json => export_to_json(path, payload),
+            Self::Ron => export_to_ron(path, payload),
+            Self::Html => export_to_html(path, payload),
+            Self::Xml => export_to_xml(path, payload),
+            Self::Markdown => export_to_markdown(path, payload),
         }
     }
+
+    /// Export several named curves: one sheet each in XLSX, a merged table elsewhere.
+    pub fn export_curves(
+        self,
+        path: &std::path::Path,
+        curves: Vec<(String, ExportPayload)>,
+    ) -> anyhow::Result<()> {
+        if curves.len() > 1 {
+            match self {
+                Self::Xlsx => {
+                    let datasets: Vec<(&str, &ExportPayload)> = curves
+                        .iter()
+                        .map(|(name, payload)| (name.as_str(), payload))
+                        .collect();
+                    return export_datasets_to_xlsx(path, &datasets).map_err(anyhow::Error::from);
+                }
+                Self::Json => {
+                    return export_curves_to_json(path, curves);
+                }
+                _ => {}
+            }
+        }
+        let payload = merge_curve_payloads(curves)?;
+        self.export(path, &payload)
+    }
 }
 
 /// Compute per-point distances to the previous point (first entry is `None`).
@@ -149,6 +507,52 @@ pub fn turning_angles(raw_points: &[XYPoint]) -> Vec<Option<f64>> {
     values
 }
 
+/// Steps to the previous point in axis terms: `dx`, `dy`, the Euclidean `step` and `slope`.
+///
+/// On a log axis the step is taken in decades (`dlog10_x` / `dlog10_y`), so on log-log axes
+/// `slope` is the local power-law exponent. Steps touching a non-positive log value are empty.
+pub fn axis_steps(
+    raw_points: &[XYPoint],
+    x_scale: ScaleKind,
+    y_scale: ScaleKind,
+) -> Vec<ExportExtraColumn> {
+    let axis = |scale: ScaleKind, v: f64| match scale {
+        ScaleKind::Linear => Some(v),
+        ScaleKind::Log10 => (v > 0.0).then(|| v.log10()),
+    };
+    let len = raw_points.len();
+    let (mut dx, mut dy) = (vec![None; len], vec![None; len]);
+    for i in 1..len {
+        let (prev, curr) = (&raw_points[i - 1], &raw_points[i]);
+        dx[i] = axis(x_scale, curr.x)
+            .zip(axis(x_scale, prev.x))
+            .map(|(a, b)| a - b);
+        dy[i] = axis(y_scale, curr.y)
+            .zip(axis(y_scale, prev.y))
+            .map(|(a, b)| a - b);
+    }
+    let step = dx
+        .iter()
+        .zip(&dy)
+        .map(|(dx, dy)| Some((*dx)?.hypot((*dy)?)))
+        .collect();
+    let slope = dx
+        .iter()
+        .zip(&dy)
+        .map(|(dx, dy)| Some((*dy)? / (*dx)?).filter(|s| s.is_finite()))
+        .collect();
+    let header = |scale: ScaleKind, axis: &str| match scale {
+        ScaleKind::Linear => format!("d{axis}"),
+        ScaleKind::Log10 => format!("dlog10_{axis}"),
+    };
+    vec![
+        ExportExtraColumn::new(header(x_scale, "x"), dx),
+        ExportExtraColumn::new(header(y_scale, "y"), dy),
+        ExportExtraColumn::new("step", step),
+        ExportExtraColumn::new("slope", slope),
+    ]
+}
+
 const XLSX_MAX_ROWS: u32 = 1_048_576;
 const XLSX_MAX_COLS: u16 = 16_384;
 
@@ -166,6 +570,18 @@ fn validate_extra_columns(payload: &ExportPayload) -> Result<(), String> {
             column.values.len()
         ));
     }
+    if let Some((index, column)) = payload
+        .text_columns
+        .iter()
+        .enumerate()
+        .find(|(_, col)| col.values.len() != expected_rows)
+    {
+        return Err(format!(
+            "Text column '{}' (index {index}) has {} rows, expected {expected_rows}.",
+            column.header,
+            column.values.len()
+        ));
+    }
     Ok(())
 }
 
@@ -178,39 +594,56 @@ fn build_tabular_export(payload: &ExportPayload) -> anyhow::Result<TabularExport
     if let Err(err) = validate_extra_columns(payload) {
         anyhow::bail!(err);
     }
-    let mut headers = vec![payload.x_label.clone(), payload.y_label.clone()];
-    headers.extend(payload.extra_columns.iter().map(|c| c.header.clone()));
-
+    let headers = tabular_headers(payload);
+    let formats = ColumnFormats::resolve(payload);
     let mut rows = Vec::with_capacity(payload.row_count());
     for row_idx in 0..payload.row_count() {
-        let p = &payload.points[row_idx];
-        let xv = axis_value_from_scalar_for_export(payload.x_unit, p.x, "x")?;
-        let yv = axis_value_from_scalar_for_export(payload.y_unit, p.y, "y")?;
-
-        let mut row = Vec::with_capacity(headers.len());
-        row.push(Some(xv.format()));
-        row.push(Some(yv.format()));
-        for col in &payload.extra_columns {
-            debug_assert_eq!(col.values.len(), payload.row_count());
-            let cell = col
-                .values
-                .get(row_idx)
-                .and_then(|v| *v)
-                .map(format_extra_value);
-            row.push(cell);
-        }
-        rows.push(row);
+        rows.push(tabular_row(payload, &formats, row_idx)?);
     }
 
     Ok(TabularExport { headers, rows })
 }
 
-fn format_extra_value(value: f64) -> String {
-    format!("{value:.6}")
+fn tabular_headers(payload: &ExportPayload) -> Vec<String> {
+    let mut headers = vec![payload.x_label.clone(), payload.y_label.clone()];
+    headers.extend(payload.extra_columns.iter().map(|c| c.header.clone()));
+    headers.extend(payload.text_columns.iter().map(|c| c.header.clone()));
+    headers
+}
+
+fn tabular_row(
+    payload: &ExportPayload,
+    formats: &ColumnFormats,
+    row_idx: usize,
+) -> anyhow::Result<Vec<Option<String>>> {
+    let p = &payload.points[row_idx];
+    let xv = axis_value_from_scalar_for_export(payload.x_unit, p.x, "x")?;
+    let yv = axis_value_from_scalar_for_export(payload.y_unit, p.y, "y")?;
+
+    let mut row = Vec::with_capacity(2 + payload.extra_columns.len() + payload.text_columns.len());
+    row.push(Some(xv.format_with(formats.x)));
+    row.push(Some(yv.format_with(formats.y)));
+    for (col, format) in payload.extra_columns.iter().zip(&formats.extras) {
+        debug_assert_eq!(col.values.len(), payload.row_count());
+        let cell = col
+            .values
+            .get(row_idx)
+            .and_then(|v| *v)
+            .map(|v| format.format(v));
+        row.push(cell);
+    }
+    for col in &payload.text_columns {
+        row.push(col.values.get(row_idx).cloned().flatten());
+    }
+    Ok(row)
 }
 
 fn metadata_pairs(payload: &ExportPayload) -> Vec<(&'static str, String)> {
-    let mut pairs = vec![
+    let mut pairs = Vec::new();
+    if let Some(title) = &payload.title {
+        pairs.push(("title", title.clone()));
+    }
+    pairs.extend([
         (
             "coord_system",
             coord_system_label(payload.coord_system).to_string(),
@@ -219,10 +652,18 @@ fn metadata_pairs(payload: &ExportPayload) -> Vec<(&'static str, String)> {
         ("y_unit", axis_unit_label(payload.y_unit).to_string()),
         ("x_label", payload.x_label.clone()),
         ("y_label", payload.y_label.clone()),
-    ];
+    ]);
     if let Some(unit) = payload.angle_unit {
         pairs.push(("angle_unit", angle_unit_label(unit).to_string()));
     }
+    if let Some(report) = &payload.calibration_report {
+        pairs.extend(
+            report
+                .metadata()
+                .into_iter()
+                .map(|(name, value)| (name, value.to_text())),
+        );
+    }
     pairs
 }
 
@@ -273,18 +714,104 @@ fn escape_markdown_cell(input: &str) -> String {
 
 /// Write the payload to CSV at the provided path.
 ///
-/// Floats are formatted with 6 fractional digits; `DateTime` values are emitted
-/// as formatted strings. Returns an error if any value is not representable.
+/// Floats are formatted with the configured fractional digits (6 by default);
+/// `DateTime` values are emitted as formatted strings. Returns an error if any
+/// value is not representable.
 pub fn export_to_csv(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
-    let table = build_tabular_export(payload)?;
-    let mut wtr = csv::Writer::from_path(path)?;
-    wtr.write_record(&table.headers)?;
+    export_to_csv_streamed(path, payload, &ExportProgress::default())
+}
+
+/// Write CSV row by row, publishing progress and honouring cancellation between chunks.
+///
+/// Rows are formatted on the fly, so memory use does not grow with the sample count. The
+/// file is written beside `path` and moved over it once complete, so a cancelled or failed
+/// export leaves the previous file untouched.
+pub fn export_to_csv_streamed(
+    path: &std::path::Path,
+    payload: &ExportPayload,
+    progress: &ExportProgress,
+) -> anyhow::Result<()> {
+    write_csv_rows(path, &[("", payload)], progress)
+}
 
-    for row in table.rows {
-        let record: Vec<String> = row.into_iter().map(Option::unwrap_or_default).collect();
-        wtr.write_record(record)?;
+/// [`export_to_csv_streamed`] for named curves stacked into one table.
+///
+/// The table is the one [`merge_curve_payloads`] would build, but every curve is written
+/// straight from its own rows instead of being copied into a merged payload first.
+pub fn export_curves_to_csv_streamed(
+    path: &std::path::Path,
+    curves: &[(String, ExportPayload)],
+    progress: &ExportProgress,
+) -> anyhow::Result<()> {
+    let curves: Vec<_> = curves
+        .iter()
+        .map(|(name, payload)| (name.as_str(), payload))
+        .collect();
+    write_csv_rows(path, &curves, progress)
+}
+
+fn write_csv_rows(
+    path: &std::path::Path,
+    curves: &[(&str, &ExportPayload)],
+    progress: &ExportProgress,
+) -> anyhow::Result<()> {
+    let Some(&(_, first)) = curves.first() else {
+        anyhow::bail!("Nothing to export.");
+    };
+    for &(name, payload) in curves {
+        if !first.same_columns(payload) {
+            anyhow::bail!("Curve '{name}' does not share the columns of the other curves.");
+        }
+        if let Err(err) = validate_extra_columns(payload) {
+            anyhow::bail!(err);
+        }
+    }
+    let mut headers = tabular_headers(first);
+    if curves
+        .iter()
+        .any(|(_, payload)| payload.y_label != first.y_label)
+    {
+        headers[1] = "y".to_string();
     }
-    wtr.flush()?;
+    // A single curve keeps its layout, as in `merge_curve_payloads`.
+    let named = curves.len() > 1;
+    if named {
+        headers.push(CURVE_COLUMN_HEADER.to_string());
+    }
+    let payloads: Vec<_> = curves.iter().map(|&(_, payload)| payload).collect();
+    let formats = ColumnFormats::resolve_stacked(&payloads);
+    let total = payloads.iter().map(|payload| payload.row_count()).sum();
+    progress.rows_total.store(total, Ordering::Relaxed);
+    progress.rows_done.store(0, Ordering::Relaxed);
+
+    crate::project::write_atomic_with(path, |file| {
+        let mut wtr = csv::WriterBuilder::new()
+            .delimiter(first.options.csv_delimiter)
+            .from_writer(file);
+        wtr.write_record(&headers)?;
+        let mut done = 0;
+        for &(name, payload) in curves {
+            for row_idx in 0..payload.row_count() {
+                if done % STREAM_CHUNK_ROWS == 0 {
+                    if progress.is_cancelled() {
+                        return Err(ExportCancelled.into());
+                    }
+                    progress.rows_done.store(done, Ordering::Relaxed);
+                }
+                for cell in tabular_row(payload, &formats, row_idx)? {
+                    wtr.write_field(cell.unwrap_or_default())?;
+                }
+                if named {
+                    wtr.write_field(name)?;
+                }
+                wtr.write_record(None::<&[u8]>)?;
+                done += 1;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    })?;
+    progress.rows_done.store(total, Ordering::Relaxed);
     Ok(())
 }
 
@@ -292,15 +819,16 @@ pub fn export_to_csv(path: &std::path::Path, payload: &ExportPayload) -> anyhow:
 pub fn export_to_html(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
     let table = build_tabular_export(payload)?;
     let metadata = metadata_pairs(payload);
+    let heading = payload.title.as_deref().unwrap_or("Curcat export");
     let doc = html! {
         (DOCTYPE)
         html lang="en" {
             head {
                 meta charset="utf-8";
-                title { "Curcat export" }
+                title { (heading) }
             }
             body {
-                h1 { "Curcat export" }
+                h1 { (heading) }
                 dl {
                     @for (name, value) in &metadata {
                         dt { (name) }
@@ -409,15 +937,59 @@ pub fn export_to_markdown(path: &std::path::Path, payload: &ExportPayload) -> an
 
 /// Write the payload to an Excel XLSX workbook at the provided path.
 ///
-/// The export respects Excel row/column limits, splitting data across sheets
-/// when needed. Non-finite numbers and unrepresentable datetimes return errors.
-#[allow(clippy::too_many_lines)]
+/// Equivalent to [`export_datasets_to_xlsx`] with a single dataset named `Data`.
 pub fn export_to_xlsx(path: &std::path::Path, payload: &ExportPayload) -> Result<(), XlsxError> {
+    export_datasets_to_xlsx(path, &[("Data", payload)])
+}
+
+/// Write each named dataset to its own worksheet as an Excel Table with a frozen header row.
+///
+/// Datasets longer than Excel's row limit continue on `"<name> 2"`, `"<name> 3"`, … sheets,
+/// each holding its own table. Non-finite numbers and unrepresentable datetimes return errors.
+pub fn export_datasets_to_xlsx(
+    path: &std::path::Path,
+    datasets: &[(&str, &ExportPayload)],
+) -> Result<(), XlsxError> {
+    let mut workbook = Workbook::new();
+    let mut sheet_names: Vec<String> = Vec::new();
+    let mut table_names: Vec<String> = Vec::new();
+    for (name, payload) in datasets {
+        write_xlsx_dataset(
+            &mut workbook,
+            name,
+            payload,
+            &mut sheet_names,
+            &mut table_names,
+        )?;
+    }
+    if datasets.is_empty() {
+        workbook.add_worksheet().set_name("Data")?;
+    }
+    workbook.save(path)
+}
+
+struct XlsxFormats {
+    /// Number format per float column: x, y, then extras.
+    numbers: Vec<Format>,
+    datetime: Format,
+    blank: Format,
+}
+
+fn write_xlsx_dataset(
+    workbook: &mut Workbook,
+    name: &str,
+    payload: &ExportPayload,
+    sheet_names: &mut Vec<String>,
+    table_names: &mut Vec<String>,
+) -> Result<(), XlsxError> {
     if let Err(err) = validate_extra_columns(payload) {
         return Err(XlsxError::ParameterError(err));
     }
-    let mut workbook = Workbook::new();
-    let total_columns = payload.extra_columns.len().saturating_add(2);
+    let total_columns = payload
+        .extra_columns
+        .len()
+        .saturating_add(payload.text_columns.len())
+        .saturating_add(2);
     let total_columns_u16 = u16::try_from(total_columns)
         .map_err(|_| XlsxError::ParameterError("XLSX export exceeds column index range.".into()))?;
     if total_columns_u16 > XLSX_MAX_COLS {
@@ -434,137 +1006,280 @@ pub fn export_to_xlsx(path: &std::path::Path, payload: &ExportPayload) -> Result
         total_rows.div_ceil(max_rows_per_sheet)
     };
 
-    // Keep parity with CSV/JSON (6 fractional digits).
-    let num_format = Format::new().set_num_format("0.000000");
-    let datetime_format = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss.000");
-    let blank_format = Format::new();
+    // Keep parity with CSV/JSON digits.
+    let column_formats = ColumnFormats::resolve(payload);
+    let formats = XlsxFormats {
+        numbers: [column_formats.x, column_formats.y]
+            .into_iter()
+            .chain(column_formats.extras)
+            .map(|f| Format::new().set_num_format(xlsx_number_format(f)))
+            .collect(),
+        datetime: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss.000"),
+        blank: Format::new(),
+    };
+    let headers = unique_table_headers(&tabular_headers(payload));
+    let columns: Vec<TableColumn> = headers
+        .iter()
+        .map(|header| TableColumn::new().set_header(header))
+        .collect();
 
     for sheet_index in 0..sheet_count {
-        let worksheet = workbook.add_worksheet();
-        let sheet_name = if sheet_index == 0 {
-            "Data".to_string()
+        let base = if sheet_index == 0 {
+            name.to_string()
         } else {
-            format!("Data {}", sheet_index + 1)
+            format!("{name} {}", sheet_index + 1)
         };
+        let sheet_name = unique_xlsx_name(&xlsx_sheet_name(&base), sheet_names, 31);
+        let worksheet = workbook.add_worksheet();
         worksheet.set_name(&sheet_name)?;
-
-        worksheet.write_string(0, 0, &payload.x_label)?;
-        worksheet.write_string(0, 1, &payload.y_label)?;
-        for (idx, col) in payload.extra_columns.iter().enumerate() {
-            let col_idx = u16::try_from(idx + 2)
+        for (col_idx, header) in headers.iter().enumerate() {
+            let col_idx = u16::try_from(col_idx)
                 .map_err(|_| XlsxError::ParameterError("XLSX column index overflow.".into()))?;
-            worksheet.write_string(0, col_idx, &col.header)?;
+            worksheet.write_string(0, col_idx, header)?;
         }
 
         let start = sheet_index * max_rows_per_sheet;
         let end = (start + max_rows_per_sheet).min(total_rows);
-        let slice = &payload.points[start..end];
-        for (row_offset, p) in slice.iter().enumerate() {
-            let row = u32::try_from(row_offset + 1)
+        write_xlsx_rows(worksheet, payload, start, end, &formats)?;
+        worksheet.set_freeze_panes(1, 0)?;
+
+        // Excel tables need at least one data row below the header.
+        if end > start {
+            let last_row = u32::try_from(end - start)
                 .map_err(|_| XlsxError::ParameterError("XLSX row index overflow.".into()))?;
-            match payload.x_unit {
-                AxisUnit::Float => {
-                    if !p.x.is_finite() {
+            let table_name = unique_xlsx_name(&xlsx_table_name(&base), table_names, 255);
+            let table = Table::new()
+                .set_name(table_name)
+                .set_columns(&columns)
+                .set_style(TableStyle::Medium2);
+            worksheet.add_table(0, 0, last_row, total_columns_u16 - 1, &table)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_xlsx_rows(
+    worksheet: &mut Worksheet,
+    payload: &ExportPayload,
+    start: usize,
+    end: usize,
+    formats: &XlsxFormats,
+) -> Result<(), XlsxError> {
+    let text_base = payload.extra_columns.len() + 2;
+    for (row_offset, p) in payload.points[start..end].iter().enumerate() {
+        let row = u32::try_from(row_offset + 1)
+            .map_err(|_| XlsxError::ParameterError("XLSX row index overflow.".into()))?;
+        write_xlsx_axis_cell(worksheet, row, 0, payload.x_unit, p.x, "x", formats)?;
+        write_xlsx_axis_cell(worksheet, row, 1, payload.y_unit, p.y, "y", formats)?;
+
+        for (col_idx, col) in payload.extra_columns.iter().enumerate() {
+            let col_num = u16::try_from(col_idx + 2)
+                .map_err(|_| XlsxError::ParameterError("XLSX column index overflow.".into()))?;
+            debug_assert_eq!(col.values.len(), payload.row_count());
+            match col.values.get(start + row_offset).and_then(|v| *v) {
+                Some(value) => {
+                    if !value.is_finite() {
                         return Err(XlsxError::ParameterError(format!(
-                            "XLSX export cannot represent non-finite x value {x}.",
-                            x = p.x
+                            "XLSX export cannot represent non-finite value {value}."
                         )));
                     }
-                    worksheet.write_number_with_format(row, 0, p.x, &num_format)?;
+                    let format = &formats.numbers[col_idx + 2];
+                    worksheet.write_number_with_format(row, col_num, value, format)?;
                 }
-                AxisUnit::DateTime => {
-                    let xv = axis_value_from_scalar_for_xlsx(payload.x_unit, p.x, "x")?;
-                    if let Some(excel_dt) = axis_value_to_excel_datetime(&xv) {
-                        worksheet.write_datetime_with_format(
-                            row,
-                            0,
-                            &excel_dt,
-                            &datetime_format,
-                        )?;
-                    } else {
-                        worksheet.write_string(row, 0, xv.format())?;
-                    }
+                None => {
+                    worksheet.write_blank(row, col_num, &formats.blank)?;
                 }
             }
+        }
 
-            match payload.y_unit {
-                AxisUnit::Float => {
-                    if !p.y.is_finite() {
-                        return Err(XlsxError::ParameterError(format!(
-                            "XLSX export cannot represent non-finite y value {y}.",
-                            y = p.y
-                        )));
-                    }
-                    worksheet.write_number_with_format(row, 1, p.y, &num_format)?;
+        for (idx, col) in payload.text_columns.iter().enumerate() {
+            let col_num = u16::try_from(text_base + idx)
+                .map_err(|_| XlsxError::ParameterError("XLSX column index overflow.".into()))?;
+            match col
+                .values
+                .get(start + row_offset)
+                .and_then(Option::as_deref)
+            {
+                Some(text) => {
+                    worksheet.write_string(row, col_num, text)?;
                 }
-                AxisUnit::DateTime => {
-                    let yv = axis_value_from_scalar_for_xlsx(payload.y_unit, p.y, "y")?;
-                    if let Some(excel_dt) = axis_value_to_excel_datetime(&yv) {
-                        worksheet.write_datetime_with_format(
-                            row,
-                            1,
-                            &excel_dt,
-                            &datetime_format,
-                        )?;
-                    } else {
-                        worksheet.write_string(row, 1, yv.format())?;
-                    }
+                None => {
+                    worksheet.write_blank(row, col_num, &formats.blank)?;
                 }
             }
+        }
+    }
+    Ok(())
+}
 
-            for (col_idx, col) in payload.extra_columns.iter().enumerate() {
-                let col_num = u16::try_from(col_idx + 2)
-                    .map_err(|_| XlsxError::ParameterError("XLSX column index overflow.".into()))?;
-                debug_assert_eq!(col.values.len(), payload.row_count());
-                match col.values.get(start + row_offset).and_then(|v| *v) {
-                    Some(value) => {
-                        if !value.is_finite() {
-                            return Err(XlsxError::ParameterError(format!(
-                                "XLSX export cannot represent non-finite value {value}."
-                            )));
-                        }
-                        worksheet.write_number_with_format(row, col_num, value, &num_format)?;
-                    }
-                    None => {
-                        worksheet.write_blank(row, col_num, &blank_format)?;
-                    }
-                }
+fn write_xlsx_axis_cell(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    unit: AxisUnit,
+    value: f64,
+    axis: &str,
+    formats: &XlsxFormats,
+) -> Result<(), XlsxError> {
+    match unit {
+        AxisUnit::Float => {
+            if !value.is_finite() {
+                return Err(XlsxError::ParameterError(format!(
+                    "XLSX export cannot represent non-finite {axis} value {value}."
+                )));
+            }
+            worksheet.write_number_with_format(
+                row,
+                col,
+                value,
+                &formats.numbers[usize::from(col)],
+            )?;
+        }
+        AxisUnit::DateTime => {
+            let av = axis_value_from_scalar_for_xlsx(unit, value, axis)?;
+            if let Some(excel_dt) = axis_value_to_excel_datetime(&av) {
+                worksheet.write_datetime_with_format(row, col, &excel_dt, &formats.datetime)?;
+            } else {
+                worksheet.write_string(row, col, av.format())?;
             }
         }
     }
+    Ok(())
+}
 
-    workbook.save(path)
+/// Replace characters Excel rejects in sheet names; blank names become `Data`.
+fn xlsx_sheet_name(base: &str) -> String {
+    let cleaned: String = base
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('\'');
+    if cleaned.is_empty() {
+        "Data".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Build a workbook-unique table identifier; the `Table_` prefix keeps it from parsing as a cell reference.
+fn xlsx_table_name(base: &str) -> String {
+    let mut name = String::from("Table_");
+    for c in base.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '.' {
+            name.push(c);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    name
+}
+
+/// Truncate `candidate` to `max_chars` and append `_n` until it is unused (case-insensitive).
+fn unique_xlsx_name(candidate: &str, used: &mut Vec<String>, max_chars: usize) -> String {
+    let truncate = |s: &str, limit: usize| s.chars().take(limit).collect::<String>();
+    let taken = |name: &str, used: &[String]| used.iter().any(|u| u.eq_ignore_ascii_case(name));
+    let mut name = truncate(candidate, max_chars);
+    let mut suffix = 2;
+    while taken(&name, used) {
+        let tail = format!("_{suffix}");
+        name = truncate(candidate, max_chars.saturating_sub(tail.len())) + &tail;
+        suffix += 1;
+    }
+    used.push(name.clone());
+    name
 }
 
+/// Excel tables require distinct, non-empty headers; duplicates get a numeric suffix.
+fn unique_table_headers(headers: &[String]) -> Vec<String> {
+    let mut used = Vec::with_capacity(headers.len());
+    headers
+        .iter()
+        .enumerate()
+        .map(|(idx, header)| {
+            let base = if header.trim().is_empty() {
+                format!("Column{}", idx + 1)
+            } else {
+                header.clone()
+            };
+            unique_xlsx_name(&base, &mut used, 255)
+        })
+        .collect()
+}
+
+/// Version of the JSON/RON export layout described by `assets/schema/export.schema.json`.
+///
+/// Bump it (and the schema's `const`) whenever a field is added, renamed, or changes meaning.
+pub const EXPORT_SCHEMA_VERSION: u32 = 3;
+
 /// Write the payload to JSON at the provided path.
 ///
-/// The output contains `x_unit`, `y_unit`, and a `points` array. Floats are
-/// rounded to 6 fractional digits; `DateTime` values are emitted as strings.
+/// The output contains `schema_version`, `x_unit`, `y_unit`, and a `points` array. Floats are
+/// rounded to the configured fractional digits; `DateTime` values are emitted as strings.
 pub fn export_to_json(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
+    write_json(path, json_document(payload)?)
+}
+
+/// Write several named curves to one JSON document.
+///
+/// Points of all curves share the `points` array and carry their curve name under
+/// [`CURVE_COLUMN_HEADER`]; the root `curves` array lists the names in export order.
+pub fn export_curves_to_json(
+    path: &std::path::Path,
+    curves: Vec<(String, ExportPayload)>,
+) -> anyhow::Result<()> {
+    let names: Vec<Value> = curves
+        .iter()
+        .map(|(name, _)| Value::String(name.clone()))
+        .collect();
+    let merged = merge_curve_payloads(curves)?;
+    let mut root = json_document(&merged)?;
+    root.insert("curves".to_string(), Value::Array(names));
+    write_json(path, root)
+}
+
+fn write_json(path: &std::path::Path, root: Map<String, Value>) -> anyhow::Result<()> {
+    let writer = BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer_pretty(writer, &Value::Object(root))?;
+    Ok(())
+}
+
+fn json_document(payload: &ExportPayload) -> anyhow::Result<Map<String, Value>> {
     if let Err(err) = validate_extra_columns(payload) {
         anyhow::bail!(err);
     }
+    let formats = ColumnFormats::resolve(payload);
     let mut points = Vec::with_capacity(payload.row_count());
     for row_idx in 0..payload.row_count() {
         let mut obj = Map::new();
         let p = &payload.points[row_idx];
         obj.insert(
             payload.x_label.clone(),
-            axis_value_to_json(payload.x_unit, p.x, &payload.x_label)?,
+            axis_value_to_json(payload.x_unit, p.x, &payload.x_label, formats.x)?,
         );
         obj.insert(
             payload.y_label.clone(),
-            axis_value_to_json(payload.y_unit, p.y, &payload.y_label)?,
+            axis_value_to_json(payload.y_unit, p.y, &payload.y_label, formats.y)?,
         );
-        for col in &payload.extra_columns {
+        for (col, format) in payload.extra_columns.iter().zip(&formats.extras) {
             debug_assert_eq!(col.values.len(), payload.row_count());
             let cell = col.values.get(row_idx).and_then(|v| *v);
-            obj.insert(col.header.clone(), optional_number_json(cell));
+            obj.insert(col.header.clone(), optional_number_json(cell, *format));
+        }
+        for col in &payload.text_columns {
+            let cell = col.values.get(row_idx).cloned().flatten();
+            obj.insert(col.header.clone(), cell.map_or(Value::Null, Value::String));
         }
         points.push(Value::Object(obj));
     }
 
     let mut root = Map::new();
+    root.insert(
+        "schema_version".to_string(),
+        Value::from(EXPORT_SCHEMA_VERSION),
+    );
     root.insert(
         "coord_system".to_string(),
         Value::String(coord_system_label(payload.coord_system).to_string()),
@@ -591,15 +1306,27 @@ pub fn export_to_json(path: &std::path::Path, payload: &ExportPayload) -> anyhow
             Value::String(angle_unit_label(unit).to_string()),
         );
     }
+    if let Some(report) = &payload.calibration_report {
+        let calibration = report
+            .metadata()
+            .into_iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    ReportValue::Number(value) => Value::from(value),
+                    ReportValue::Text(text) => Value::String(text.to_string()),
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+        root.insert("calibration".to_string(), Value::Object(calibration));
+    }
     root.insert("points".to_string(), Value::Array(points));
-
-    let writer = BufWriter::new(std::fs::File::create(path)?);
-    serde_json::to_writer_pretty(writer, &Value::Object(root))?;
-    Ok(())
+    Ok(root)
 }
 
 #[derive(Debug, Serialize)]
 struct RonExport {
+    schema_version: u32,
     coord_system: &'static str,
     x_unit: &'static str,
     y_unit: &'static str,
@@ -607,6 +1334,8 @@ struct RonExport {
     y_label: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     angle_unit: Option<&'static str>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    calibration: BTreeMap<&'static str, RonValue>,
     points: Vec<BTreeMap<String, RonValue>>,
 }
 
@@ -637,33 +1366,54 @@ pub fn export_to_ron(path: &std::path::Path, payload: &ExportPayload) -> anyhow:
     if let Err(err) = validate_extra_columns(payload) {
         anyhow::bail!(err);
     }
+    let formats = ColumnFormats::resolve(payload);
     let mut points = Vec::with_capacity(payload.row_count());
     for row_idx in 0..payload.row_count() {
         let mut row = BTreeMap::new();
         let p = &payload.points[row_idx];
         row.insert(
             payload.x_label.clone(),
-            axis_value_to_ron(payload.x_unit, p.x, &payload.x_label)?,
+            axis_value_to_ron(payload.x_unit, p.x, &payload.x_label, formats.x)?,
         );
         row.insert(
             payload.y_label.clone(),
-            axis_value_to_ron(payload.y_unit, p.y, &payload.y_label)?,
+            axis_value_to_ron(payload.y_unit, p.y, &payload.y_label, formats.y)?,
         );
-        for col in &payload.extra_columns {
+        for (col, format) in payload.extra_columns.iter().zip(&formats.extras) {
             debug_assert_eq!(col.values.len(), payload.row_count());
             let cell = col.values.get(row_idx).and_then(|v| *v);
-            row.insert(col.header.clone(), optional_number_ron(cell));
+            row.insert(col.header.clone(), optional_number_ron(cell, *format));
+        }
+        for col in &payload.text_columns {
+            let cell = col.values.get(row_idx).cloned().flatten();
+            row.insert(
+                col.header.clone(),
+                cell.map_or(RonValue::None, RonValue::String),
+            );
         }
         points.push(row);
     }
 
     let doc = RonExport {
+        schema_version: EXPORT_SCHEMA_VERSION,
         coord_system: coord_system_label(payload.coord_system),
         x_unit: axis_unit_label(payload.x_unit),
         y_unit: axis_unit_label(payload.y_unit),
         x_label: payload.x_label.clone(),
         y_label: payload.y_label.clone(),
         angle_unit: payload.angle_unit.map(angle_unit_label),
+        calibration: payload
+            .calibration_report
+            .iter()
+            .flat_map(CalibrationReport::metadata)
+            .map(|(name, value)| {
+                let value = match value {
+                    ReportValue::Number(value) => RonValue::Number(value),
+                    ReportValue::Text(text) => RonValue::String(text.to_string()),
+                };
+                (name, value)
+            })
+            .collect(),
         points,
     };
 
@@ -682,8 +1432,10 @@ const fn axis_unit_label(unit: AxisUnit) -> &'static str {
 
 const fn coord_system_label(system: CoordSystem) -> &'static str {
     match system {
-        CoordSystem::Cartesian => "cartesian",
+        // Affine calibration still yields plain x/y values.
+        CoordSystem::Cartesian | CoordSystem::Affine => "cartesian",
         CoordSystem::Polar => "polar",
+        CoordSystem::Smith => "smith",
     }
 }
 
@@ -698,13 +1450,14 @@ fn axis_value_to_json(
     unit: AxisUnit,
     scalar_seconds: f64,
     axis_label: &str,
+    format: FloatFormat,
 ) -> anyhow::Result<Value> {
     match unit {
         AxisUnit::Float => {
             if !scalar_seconds.is_finite() {
                 anyhow::bail!("Cannot export non-finite float value {scalar_seconds}.");
             }
-            Ok(rounded_number_json(scalar_seconds))
+            Ok(rounded_number_json(scalar_seconds, format))
         }
         AxisUnit::DateTime => {
             let value = axis_value_from_scalar_for_export(unit, scalar_seconds, axis_label)?;
@@ -717,13 +1470,14 @@ fn axis_value_to_ron(
     unit: AxisUnit,
     scalar_seconds: f64,
     axis_label: &str,
+    format: FloatFormat,
 ) -> anyhow::Result<RonValue> {
     match unit {
         AxisUnit::Float => {
             if !scalar_seconds.is_finite() {
                 anyhow::bail!("Cannot export non-finite float value {scalar_seconds}.");
             }
-            Ok(number_to_ron_value(scalar_seconds))
+            Ok(number_to_ron_value(scalar_seconds, format))
         }
         AxisUnit::DateTime => {
             let value = axis_value_from_scalar_for_export(unit, scalar_seconds, axis_label)?;
@@ -732,16 +1486,16 @@ fn axis_value_to_ron(
     }
 }
 
-fn optional_number_json(value: Option<f64>) -> Value {
-    value.map_or(Value::Null, rounded_number_json)
+fn optional_number_json(value: Option<f64>, format: FloatFormat) -> Value {
+    value.map_or(Value::Null, |v| rounded_number_json(v, format))
 }
 
-fn optional_number_ron(value: Option<f64>) -> RonValue {
-    value.map_or(RonValue::None, number_to_ron_value)
+fn optional_number_ron(value: Option<f64>, format: FloatFormat) -> RonValue {
+    value.map_or(RonValue::None, |v| number_to_ron_value(v, format))
 }
 
-fn number_to_ron_value(value: f64) -> RonValue {
-    let rounded = rounded_f64(value);
+fn number_to_ron_value(value: f64, format: FloatFormat) -> RonValue {
+    let rounded = format.round(value);
     if rounded.is_finite() {
         RonValue::Number(rounded)
     } else {
@@ -749,14 +1503,19 @@ fn number_to_ron_value(value: f64) -> RonValue {
     }
 }
 
-fn rounded_number_json(value: f64) -> Value {
-    // Keep parity with CSV output: 6 fractional digits, rounded.
-    let rounded = rounded_f64(value);
+fn rounded_number_json(value: f64, format: FloatFormat) -> Value {
+    // Keep parity with CSV output: same digits, rounded.
+    let rounded = format.round(value);
     Number::from_f64(rounded).map_or_else(|| Value::String(format!("{rounded}")), Value::Number)
 }
 
-fn rounded_f64(value: f64) -> f64 {
-    (value * 1_000_000.0).round() / 1_000_000.0
+fn xlsx_number_format(format: FloatFormat) -> String {
+    match format {
+        FloatFormat::Fixed(0) => "0".to_string(),
+        FloatFormat::Fixed(decimals) => format!("0.{}", "0".repeat(decimals)),
+        FloatFormat::Scientific(0) => "0E+00".to_string(),
+        FloatFormat::Scientific(digits) => format!("0.{}E+00", "0".repeat(digits)),
+    }
 }
 
 fn axis_value_to_excel_datetime(value: &AxisValue) -> Option<ExcelDateTime> {
@@ -801,9 +1560,13 @@ fn axis_value_from_scalar_for_xlsx(
     })
 }
 
+#[cfg(test)]
+mod schema_tests;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::AxisMapping;
     use ron::value::{Map, Value};
     use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -827,7 +1590,7 @@ mod tests {
         }
     }
 
-    fn temp_export_path(stem: &str, ext: &str) -> PathBuf {
+    pub(super) fn temp_export_path(stem: &str, ext: &str) -> PathBuf {
         std::env::temp_dir().join(format!(
             "curcat_{stem}_{}.{}",
             SystemTime::now()
@@ -852,12 +1615,16 @@ mod tests {
             y_unit: AxisUnit::Float,
             x_label: "X".to_string(),
             y_label: "Y".to_string(),
+            title: None,
+            calibration_report: None,
             coord_system: CoordSystem::Cartesian,
             angle_unit: None,
             extra_columns: vec![ExportExtraColumn::new(
                 "extra",
                 vec![None, Some(9.876_543_21)],
             )],
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
         };
 
         let path = temp_export_path("ron_export_test", "ron");
@@ -925,9 +1692,13 @@ mod tests {
             y_unit: AxisUnit::Float,
             x_label: "X".to_string(),
             y_label: "Y".to_string(),
+            title: None,
+            calibration_report: None,
             coord_system: CoordSystem::Cartesian,
             angle_unit: None,
             extra_columns: vec![ExportExtraColumn::new("extra", vec![Some(1.0)])],
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
         };
 
         let check_err = validate_extra_columns(&payload).expect_err("must reject mismatch");
@@ -947,9 +1718,13 @@ mod tests {
             y_unit: AxisUnit::Float,
             x_label: "x<&\"'>".to_string(),
             y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
             coord_system: CoordSystem::Cartesian,
             angle_unit: None,
             extra_columns: vec![ExportExtraColumn::new("extra<&\"'>", vec![None, Some(7.5)])],
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
         };
 
         let path = temp_export_path("html_export_test", "html");
@@ -976,16 +1751,47 @@ mod tests {
     }
 
     #[test]
-    fn export_xml_contains_metadata_points_and_escaping() {
+    fn export_html_is_headed_by_the_chart_title() {
         let payload = ExportPayload {
             points: vec![XYPoint { x: 1.0, y: 2.0 }],
             x_unit: AxisUnit::Float,
             y_unit: AxisUnit::Float,
-            x_label: "x\"line\nnext".to_string(),
+            x_label: "Time, s".to_string(),
             y_label: "y".to_string(),
+            title: Some("Step response".to_string()),
+            calibration_report: None,
             coord_system: CoordSystem::Cartesian,
             angle_unit: None,
-            extra_columns: vec![ExportExtraColumn::new("<extra&name>", vec![None])],
+            extra_columns: Vec::new(),
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
+        };
+
+        let path = temp_export_path("html_title_export_test", "html");
+        export_to_html(&path, &payload).expect("HTML export failed");
+        let text = std::fs::read_to_string(&path).expect("failed to read HTML output");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(text.contains("<h1>Step response</h1>"));
+        assert!(text.contains("<dt>title</dt><dd>Step response</dd>"));
+        assert!(text.contains("<th>Time, s</th>"));
+    }
+
+    #[test]
+    fn export_xml_contains_metadata_points_and_escaping() {
+        let payload = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.0 }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x\"line\nnext".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("<extra&name>", vec![None])],
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
         };
 
         let path = temp_export_path("xml_export_test", "xml");
@@ -1005,6 +1811,69 @@ mod tests {
         assert!(text.contains("<field name=\"&lt;extra&amp;name&gt;\"/>"));
     }
 
+    #[test]
+    fn calibration_report_is_written_into_the_metadata() {
+        let axis = |p2: egui::Pos2, v2: f64, scale: ScaleKind| {
+            AxisMapping::try_new(
+                egui::pos2(0.0, 100.0),
+                p2,
+                AxisValue::Float(1.0),
+                AxisValue::Float(v2),
+                scale,
+                AxisUnit::Float,
+            )
+            .expect("mapping")
+        };
+        let report = CalibrationReport::new(
+            &axis(egui::pos2(200.0, 100.0), 11.0, ScaleKind::Linear),
+            &axis(egui::pos2(0.0, 0.0), 100.0, ScaleKind::Log10),
+            [None, Some(ScaleKind::Log10)],
+        );
+        let payload = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.0 }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: Some(report),
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: Vec::new(),
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
+        };
+
+        let xml_path = temp_export_path("xml_calibration_report_test", "xml");
+        export_to_xml(&xml_path, &payload).expect("XML export failed");
+        let xml = std::fs::read_to_string(&xml_path).expect("failed to read XML output");
+        let _ = std::fs::remove_file(&xml_path);
+        for attr in [
+            "axis_angle_deg=\"90\"",
+            "x_scale=\"linear\"",
+            "x_pixels_per_unit=\"20\"",
+            "x_quantization_error=\"0.025\"",
+            "y_scale=\"log10\"",
+            "y_pixels_per_decade=\"50\"",
+            "y_grid_scale=\"log10\"",
+        ] {
+            assert!(xml.contains(attr), "{attr} missing from {xml}");
+        }
+
+        let json_path = temp_export_path("json_calibration_report_test", "json");
+        export_to_json(&json_path, &payload).expect("JSON export failed");
+        let json = std::fs::read_to_string(&json_path).expect("failed to read JSON output");
+        let _ = std::fs::remove_file(&json_path);
+        let parsed: serde_json::Value = serde_json::from_str(&json).expect("parse JSON");
+        let calibration = &parsed["calibration"];
+        assert_eq!(calibration["x_pixels_per_unit"], 20.0);
+        assert_eq!(calibration["y_scale"], "log10");
+        let relative = calibration["y_quantization_error_relative"]
+            .as_f64()
+            .expect("relative error");
+        assert!((relative - (10_f64.powf(0.01) - 1.0)).abs() < 1e-12);
+    }
+
     #[test]
     fn export_markdown_writes_table_and_escapes_special_symbols() {
         let payload = ExportPayload {
@@ -1013,9 +1882,13 @@ mod tests {
             y_unit: AxisUnit::Float,
             x_label: "x|\nhead".to_string(),
             y_label: "y\\head".to_string(),
+            title: None,
+            calibration_report: None,
             coord_system: CoordSystem::Cartesian,
             angle_unit: None,
             extra_columns: vec![ExportExtraColumn::new("c|d", vec![None, Some(5.1)])],
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
         };
 
         let path = temp_export_path("markdown_export_test", "md");
@@ -1026,4 +1899,789 @@ mod tests {
         let expected = "| x\\|<br>head | y\\\\head | c\\|d |\n| --- | --- | --- |\n| 1 | 2 |  |\n| 3 | 4 | 5.100000 |\n";
         assert_eq!(text, expected);
     }
+
+    #[test]
+    fn export_csv_and_json_include_text_columns_after_numeric_extras() {
+        let payload = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.0 }, XYPoint { x: 3.0, y: 4.0 }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("d", vec![None, Some(1.5)])],
+            text_columns: vec![ExportTextColumn::new(
+                "flag",
+                vec![Some("check, tick".to_string()), None],
+            )],
+            options: ExportOptions::default(),
+        };
+
+        let csv_path = temp_export_path("csv_text_column_test", "csv");
+        export_to_csv(&csv_path, &payload).expect("CSV export failed");
+        let csv_text = std::fs::read_to_string(&csv_path).expect("failed to read CSV output");
+        let _ = std::fs::remove_file(&csv_path);
+        assert_eq!(
+            csv_text,
+            "x,y,d,flag\n1,2,,\"check, tick\"\n3,4,1.500000,\n"
+        );
+
+        let json_path = temp_export_path("json_text_column_test", "json");
+        export_to_json(&json_path, &payload).expect("JSON export failed");
+        let json_text = std::fs::read_to_string(&json_path).expect("failed to read JSON output");
+        let _ = std::fs::remove_file(&json_path);
+        let parsed: serde_json::Value = serde_json::from_str(&json_text).expect("parse JSON");
+        assert_eq!(parsed["points"][0]["flag"], "check, tick");
+        assert!(parsed["points"][1]["flag"].is_null());
+    }
+
+    #[test]
+    fn export_csv_respects_decimals_and_delimiter() {
+        let payload = ExportPayload {
+            points: vec![XYPoint {
+                x: 1.234_567,
+                y: 2.5,
+            }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("d", vec![Some(0.123_456)])],
+            text_columns: Vec::new(),
+            options: ExportOptions {
+                decimals: 3,
+                csv_delimiter: b';',
+                significant_figures: None,
+            },
+        };
+
+        let path = temp_export_path("csv_options_test", "csv");
+        export_to_csv(&path, &payload).expect("CSV export failed");
+        let text = std::fs::read_to_string(&path).expect("failed to read CSV output");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text, "x;y;d\n1.235;2.5;0.123\n");
+        assert_eq!(xlsx_number_format(FloatFormat::Fixed(3)), "0.000");
+        assert_eq!(xlsx_number_format(FloatFormat::Fixed(0)), "0");
+        assert_eq!(xlsx_number_format(FloatFormat::Scientific(2)), "0.00E+00");
+    }
+
+    #[test]
+    fn export_csv_significant_figures_follow_column_span() {
+        let payload = ExportPayload {
+            points: vec![
+                XYPoint {
+                    x: 1000.123,
+                    y: 0.001_234_5,
+                },
+                XYPoint {
+                    x: 1999.87,
+                    y: 0.002_5,
+                },
+            ],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new(
+                "d",
+                vec![Some(2.5e7), Some(2.612e7)],
+            )],
+            text_columns: Vec::new(),
+            options: ExportOptions {
+                significant_figures: Some(3),
+                ..ExportOptions::default()
+            },
+        };
+
+        let path = temp_export_path("csv_significant_test", "csv");
+        export_to_csv(&path, &payload).expect("CSV export failed");
+        let text = std::fs::read_to_string(&path).expect("failed to read CSV output");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(text, "x,y,d\n1000,0.00123,2.500e7\n2000,0.0025,2.612e7\n");
+    }
+
+    #[test]
+    fn streamed_csv_reports_progress_and_keeps_the_previous_file_on_cancel() {
+        let payload = ExportPayload {
+            points: (0..10_000)
+                .map(|i| XYPoint {
+                    x: f64::from(i),
+                    y: f64::from(i) * 0.5,
+                })
+                .collect(),
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: Vec::new(),
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
+        };
+
+        let path = temp_export_path("csv_streamed_test", "csv");
+        let progress = ExportProgress::default();
+        export_to_csv_streamed(&path, &payload, &progress).expect("CSV export failed");
+        assert_eq!(progress.rows_total(), 10_000);
+        assert_eq!(progress.rows_done(), 10_000);
+        let text = std::fs::read_to_string(&path).expect("failed to read CSV output");
+        assert_eq!(text.lines().count(), 10_001);
+        assert_eq!(text.lines().last(), Some("9999,4999.5"));
+
+        let cancelled = ExportProgress::default();
+        cancelled.cancel();
+        let err = export_to_csv_streamed(&path, &payload, &cancelled).unwrap_err();
+        assert!(err.is::<ExportCancelled>());
+        let kept = std::fs::read_to_string(&path).expect("previous CSV kept");
+        assert_eq!(kept, text);
+        let _ = std::fs::remove_file(&path);
+
+        let fresh = temp_export_path("csv_streamed_cancel_test", "csv");
+        let err = export_to_csv_streamed(&fresh, &payload, &cancelled).unwrap_err();
+        assert!(err.is::<ExportCancelled>());
+        assert!(!fresh.exists());
+        let name = fresh.file_name().expect("file name").to_string_lossy();
+        let leftovers = std::fs::read_dir(fresh.parent().expect("temp dir"))
+            .expect("list temp dir")
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(&format!(".{name}"))
+            })
+            .count();
+        assert_eq!(leftovers, 0, "temp file left behind");
+    }
+
+    #[test]
+    fn streamed_csv_of_several_curves_matches_the_merged_table() {
+        let curve = |y_label: &str, offset: f64| ExportPayload {
+            points: (0..5)
+                .map(|i| XYPoint {
+                    x: f64::from(i),
+                    y: f64::from(i) + offset,
+                })
+                .collect(),
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: y_label.to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new(
+                "step",
+                (0..5).map(|i| Some(f64::from(i) * 0.25)).collect(),
+            )],
+            text_columns: Vec::new(),
+            options: ExportOptions {
+                significant_figures: Some(3),
+                ..ExportOptions::default()
+            },
+        };
+        let curves = vec![
+            ("Upper".to_string(), curve("Temperature", 1000.0)),
+            ("Lower".to_string(), curve("Pressure", 0.0)),
+        ];
+
+        let streamed_path = temp_export_path("csv_curves_streamed", "csv");
+        export_curves_to_csv_streamed(&streamed_path, &curves, &ExportProgress::default())
+            .expect("streamed CSV export failed");
+        let merged_path = temp_export_path("csv_curves_merged", "csv");
+        let merged = merge_curve_payloads(curves).expect("merge");
+        export_to_csv(&merged_path, &merged).expect("merged CSV export failed");
+
+        let streamed = std::fs::read_to_string(&streamed_path).expect("read streamed CSV");
+        let expected = std::fs::read_to_string(&merged_path).expect("read merged CSV");
+        let _ = std::fs::remove_file(&streamed_path);
+        let _ = std::fs::remove_file(&merged_path);
+        assert_eq!(streamed, expected);
+        assert_eq!(streamed.lines().next(), Some("x,y,step,curve"));
+        assert_eq!(streamed.lines().count(), 11);
+    }
+
+    #[test]
+    fn xlsx_sheet_and_table_names_are_sanitized_and_unique() {
+        let mut sheets = Vec::new();
+        assert_eq!(
+            unique_xlsx_name(&xlsx_sheet_name("a/b:c"), &mut sheets, 31),
+            "a_b_c"
+        );
+        assert_eq!(
+            unique_xlsx_name(&xlsx_sheet_name("A/B:C"), &mut sheets, 31),
+            "A_B_C_2"
+        );
+        assert_eq!(
+            unique_xlsx_name(&xlsx_sheet_name("  "), &mut sheets, 31),
+            "Data"
+        );
+        let long = "x".repeat(40);
+        let first = unique_xlsx_name(&long, &mut sheets, 31);
+        let second = unique_xlsx_name(&long, &mut sheets, 31);
+        assert_eq!(first.chars().count(), 31);
+        assert_eq!(second, format!("{}_2", "x".repeat(29)));
+
+        assert_eq!(xlsx_table_name("Curve 1 (raw)"), "Table_Curve_1_raw_");
+        assert_eq!(
+            unique_table_headers(&["x".into(), "X".into(), String::new()]),
+            ["x", "X_2", "Column3"]
+        );
+    }
+
+    #[test]
+    fn export_datasets_to_xlsx_writes_one_table_per_dataset() {
+        let payload = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.0 }, XYPoint { x: 3.0, y: 4.0 }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "x".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: Vec::new(),
+            text_columns: vec![ExportTextColumn::new(
+                "flag",
+                vec![None, Some("odd".into())],
+            )],
+            options: ExportOptions::default(),
+        };
+        let empty = ExportPayload {
+            points: Vec::new(),
+            text_columns: Vec::new(),
+            ..payload.clone()
+        };
+
+        let path = temp_export_path("xlsx_datasets_test", "xlsx");
+        export_datasets_to_xlsx(
+            &path,
+            &[("Curve", &payload), ("curve", &payload), ("Empty", &empty)],
+        )
+        .expect("XLSX export failed");
+        let bytes = std::fs::read(&path).expect("failed to read XLSX output");
+        let _ = std::fs::remove_file(&path);
+        assert!(bytes.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn export_curves_merges_rows_and_tags_them_with_the_curve_name() {
+        let curve = |points: Vec<XYPoint>, flags: Vec<Option<String>>| ExportPayload {
+            points,
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: Vec::new(),
+            text_columns: vec![ExportTextColumn::new("flag", flags)],
+            options: ExportOptions::default(),
+        };
+        let first = curve(
+            vec![XYPoint { x: 1.0, y: 2.0 }, XYPoint { x: 3.0, y: 4.0 }],
+            vec![None, Some("odd".into())],
+        );
+        let second = curve(vec![XYPoint { x: 5.0, y: 6.0 }], vec![None]);
+        let curves = vec![("Upper".to_string(), first), ("Lower".to_string(), second)];
+
+        let path = temp_export_path("curves_csv_test", "csv");
+        ExportFormat::Csv
+            .export_curves(&path, curves.clone())
+            .expect("CSV export failed");
+        let text = std::fs::read_to_string(&path).expect("failed to read CSV output");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            [
+                "x,y,flag,curve",
+                "1,2,,Upper",
+                "3,4,odd,Upper",
+                "5,6,,Lower"
+            ]
+        );
+
+        let path = temp_export_path("curves_xlsx_test", "xlsx");
+        ExportFormat::Xlsx
+            .export_curves(&path, curves.clone())
+            .expect("XLSX export failed");
+        let bytes = std::fs::read(&path).expect("failed to read XLSX output");
+        let _ = std::fs::remove_file(&path);
+        assert!(bytes.starts_with(b"PK"));
+
+        // A lone curve keeps the single-series layout.
+        let single = merge_curve_payloads(curves[..1].to_vec()).expect("merge");
+        assert_eq!(single.text_columns.len(), 1);
+
+        // Curves read against two Y axes stack under a generic Y header.
+        let mut dual_axis = curves.clone();
+        dual_axis[0].1.y_label = "T, K".to_string();
+        dual_axis[1].1.y_label = "p, kPa".to_string();
+        assert_eq!(merge_curve_payloads(dual_axis).expect("merge").y_label, "y");
+
+        let mut mismatched = curves;
+        mismatched[1].1.x_label = "other".to_string();
+        assert!(merge_curve_payloads(mismatched).is_err());
+    }
+
+    #[test]
+    fn export_ods_writes_typed_cells_with_stored_mimetype_first() {
+        let payload = ExportPayload {
+            points: vec![XYPoint {
+                x: 1_700_000_000.0,
+                y: 0.126,
+            }],
+            x_unit: AxisUnit::DateTime,
+            y_unit: AxisUnit::Float,
+            x_label: "t".to_string(),
+            y_label: "a<b".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("d", vec![None])],
+            text_columns: Vec::new(),
+            options: ExportOptions {
+                decimals: 2,
+                ..ExportOptions::default()
+            },
+        };
+
+        let path = temp_export_path("ods_test", "ods");
+        export_to_ods(&path, &payload).expect("ODS export failed");
+        let file = std::fs::File::open(&path).expect("failed to open ODS output");
+        let mut archive = zip::ZipArchive::new(file).expect("ODS is not a zip archive");
+        let mimetype = archive.by_index(0).expect("missing first entry");
+        assert_eq!(mimetype.name(), "mimetype");
+        assert_eq!(mimetype.compression(), zip::CompressionMethod::Stored);
+        drop(mimetype);
+        let mut content = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("content.xml").expect("missing content.xml"),
+            &mut content,
+        )
+        .expect("failed to read content.xml");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(content.contains("<table:table table:name=\"Data\">"));
+        assert!(content.contains("office:date-value=\"2023-11-14T22:13:20.000\""));
+        assert!(content.contains("office:value=\"0.126\"><text:p>0.13</text:p>"));
+        assert!(content.contains("<text:p>a&lt;b</text:p>"));
+        assert!(content.contains("number:decimal-places=\"2\""));
+    }
+
+    #[test]
+    fn export_arrow_writes_ipc_file_framing() {
+        let payload = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.0 }, XYPoint { x: 3.0, y: 4.0 }],
+            x_unit: AxisUnit::DateTime,
+            y_unit: AxisUnit::Float,
+            x_label: "t".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("d", vec![None, Some(0.5)])],
+            text_columns: vec![ExportTextColumn::new("flag", vec![Some("é".into()), None])],
+            options: ExportOptions::default(),
+        };
+
+        let path = temp_export_path("arrow_test", "arrow");
+        export_to_arrow(&path, &payload).expect("Arrow export failed");
+        let bytes = std::fs::read(&path).expect("failed to read Arrow output");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(&bytes[..8], b"ARROW1\0\0");
+        assert_eq!(&bytes[bytes.len() - 6..], b"ARROW1");
+        assert_eq!(&bytes[8..12], &[0xFF; 4]);
+        let footer_len =
+            u32::from_le_bytes(bytes[bytes.len() - 10..bytes.len() - 6].try_into().unwrap());
+        let footer_start = bytes.len() - 10 - footer_len as usize;
+        // End-of-stream marker sits right before the footer.
+        assert_eq!(
+            &bytes[footer_start - 8..footer_start],
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0]
+        );
+        assert_eq!(footer_len % 8, 0);
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.contains("flag"));
+        assert!(text.contains("coord_system"));
+    }
+
+    #[test]
+    fn export_parquet_writes_footer_and_schema() {
+        let payload = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.0 }, XYPoint { x: 3.0, y: 4.0 }],
+            x_unit: AxisUnit::DateTime,
+            y_unit: AxisUnit::Float,
+            x_label: "t".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("d", vec![None, Some(0.5)])],
+            text_columns: vec![ExportTextColumn::new("flag", vec![Some("é".into()), None])],
+            options: ExportOptions::default(),
+        };
+
+        let path = temp_export_path("parquet_test", "parquet");
+        export_to_parquet(&path, &payload).expect("Parquet export failed");
+        let bytes = std::fs::read(&path).expect("failed to read Parquet output");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(&bytes[..4], b"PAR1");
+        assert_eq!(&bytes[bytes.len() - 4..], b"PAR1");
+        let footer_len =
+            u32::from_le_bytes(bytes[bytes.len() - 8..bytes.len() - 4].try_into().unwrap());
+        let footer = &bytes[bytes.len() - 8 - footer_len as usize..bytes.len() - 8];
+        // `version: 1`, then the root and four column schema elements.
+        assert_eq!(&footer[..3], &[0x15, 0x02, 0x19]);
+        assert_eq!(footer[3], 0x5C);
+        let text = String::from_utf8_lossy(footer);
+        for name in ["schema", "t", "flag", "coord_system", "curcat version"] {
+            assert!(text.contains(name), "missing {name}");
+        }
+        // Present text values are stored as plain UTF-8 byte arrays.
+        let data = String::from_utf8_lossy(&bytes[..bytes.len() - 8 - footer_len as usize]);
+        assert!(data.contains('é'));
+    }
+
+    #[test]
+    fn export_datasets_to_sql_keeps_a_fixed_layout_and_escapes_literals() {
+        let base = ExportPayload {
+            points: vec![XYPoint { x: 1.0, y: 2.5 }, XYPoint { x: 2.0, y: 3.5 }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: vec![ExportExtraColumn::new("X", vec![Some(0.5), None])],
+            text_columns: vec![ExportTextColumn::new(
+                "note",
+                vec![Some("it's".to_string()), None],
+            )],
+            options: ExportOptions::default(),
+        };
+        let other = ExportPayload {
+            extra_columns: vec![ExportExtraColumn::new("dist", vec![Some(1.0), Some(2.0)])],
+            text_columns: Vec::new(),
+            ..base.clone()
+        };
+
+        let path = temp_export_path("sql_test", "sql");
+        sql::export_datasets_to_sql(&path, &[("Run A", &base), ("Run B", &other)])
+            .expect("SQL export failed");
+        let script = std::fs::read_to_string(&path).expect("failed to read SQL output");
+        let _ = std::fs::remove_file(&path);
+
+        // The columns of a dataset never reach the schema, so later exports can append.
+        assert!(script.contains("CREATE TABLE IF NOT EXISTS curcat_points (\n    dataset TEXT NOT NULL,\n    point INTEGER NOT NULL,\n    x REAL NOT NULL,\n    y REAL NOT NULL\n);"));
+        assert!(!script.contains("\"dist\""));
+        assert!(script.contains("('Run A', 0, 1, 2.5),"));
+        assert!(script.contains("('Run B', 1, 2, 3.5);"));
+        assert!(script.contains("('Run A', 0, 'X', 0.500000, NULL),"));
+        assert!(script.contains("('Run A', 0, 'note', NULL, 'it''s');"));
+        assert!(script.contains("('Run B', 1, 'dist', 2.000000, NULL);"));
+        assert!(script.contains("('Run A', 'coord_system', 'cartesian')"));
+        assert!(script.trim_end().ends_with("COMMIT;"));
+    }
+
+    #[test]
+    fn export_datasets_to_sql_stores_mixed_axis_units_as_text() {
+        let numbers = ExportPayload {
+            points: vec![XYPoint { x: 1.5, y: 2.0 }],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            title: None,
+            calibration_report: None,
+            coord_system: CoordSystem::Cartesian,
+            angle_unit: None,
+            extra_columns: Vec::new(),
+            text_columns: Vec::new(),
+            options: ExportOptions::default(),
+        };
+        let dates = ExportPayload {
+            x_unit: AxisUnit::DateTime,
+            points: vec![XYPoint { x: 0.0, y: 3.0 }],
+            ..numbers.clone()
+        };
+
+        let path = temp_export_path("sql_mixed_test", "sql");
+        sql::export_datasets_to_sql(&path, &[("Lab", &numbers), ("Log", &dates)])
+            .expect("SQL export failed");
+        let script = std::fs::read_to_string(&path).expect("failed to read SQL output");
+
+        assert!(script.contains("    x TEXT NOT NULL,\n    y REAL NOT NULL"));
+        // Numbers in a text column are quoted, so strict clients accept them.
+        assert!(script.contains("('Lab', 0, '1.5', 2);"));
+        assert!(script.contains("('Log', 0, '1970-01-01 00:00:00.000', 3);"));
+
+        // A failed export leaves the previous script in place, not half a new one.
+        let broken = ExportPayload {
+            points: vec![XYPoint {
+                x: 1.0,
+                y: f64::NAN,
+            }],
+            ..numbers
+        };
+        assert!(sql::export_to_sql(&path, &broken).is_err());
+        let kept = std::fs::read_to_string(&path).expect("failed to read SQL output");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(kept, script);
+    }
+
+    fn sample_surface() -> Surface {
+        let points = |pairs: &[(f64, f64)]| -> Vec<XYPoint> {
+            pairs.iter().map(|&(x, y)| XYPoint { x, y }).collect()
+        };
+        Surface::assemble(
+            vec![
+                (30.0, points(&[(0.0, 20.0), (5.0, 25.0)])),
+                (10.0, points(&[(0.0, 0.0), (10.0, 10.0)])),
+                (20.0, points(&[(5.0, 15.0), (10.0, 20.0)])),
+            ],
+            3,
+            crate::interp::InterpAlgorithm::Linear,
+        )
+        .expect("surface")
+    }
+
+    #[test]
+    fn surface_fills_gaps_across_the_parameter_only_between_curves() {
+        let surface = sample_surface();
+        assert_eq!(surface.x, [0.0, 5.0, 10.0]);
+        assert_eq!(surface.parameters, [10.0, 20.0, 30.0]);
+        assert_eq!(
+            surface.values,
+            [
+                vec![Some(0.0), Some(5.0), Some(10.0)],
+                // Below its own range, halfway between the neighbouring curves.
+                vec![Some(10.0), Some(15.0), Some(20.0)],
+                // Past its range with no curve above to interpolate from.
+                vec![Some(20.0), Some(25.0), None],
+            ]
+        );
+
+        let twin = Surface::assemble(
+            vec![
+                (
+                    1.0,
+                    vec![XYPoint { x: 0.0, y: 0.0 }, XYPoint { x: 1.0, y: 1.0 }],
+                ),
+                (
+                    1.0,
+                    vec![XYPoint { x: 0.0, y: 2.0 }, XYPoint { x: 1.0, y: 3.0 }],
+                ),
+            ],
+            3,
+            crate::interp::InterpAlgorithm::Linear,
+        );
+        assert!(twin.is_err());
+    }
+
+    #[test]
+    fn surface_csv_puts_parameters_down_and_x_across() {
+        let mut surface = sample_surface();
+        surface.parameter_label = "T".to_string();
+        surface.options.decimals = 1;
+        let path = temp_export_path("surface_test", "csv");
+        SurfaceFormat::CsvMatrix
+            .export(&path, &surface)
+            .expect("surface CSV export failed");
+        let text = std::fs::read_to_string(&path).expect("failed to read surface CSV");
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(
+            lines,
+            ["T \\ x,0,5,10", "10,0,5,10", "20,10,15,20", "30,20,25,"]
+        );
+    }
+
+    #[test]
+    fn export_netcdf_writes_cf_point_variables_and_padded_text() {
+        let payload = ExportPayload {
+            points: vec![
+                XYPoint { x: 10.0, y: 1.0 },
+                XYPoint { x: 20.0, y: 2.0 },
+                XYPoint { x: 30.0, y: 3.0 },
+            ],
+            x_unit: AxisUnit::Float,
+            y_unit: AxisUnit::Float,
+            x_label: "Angle".to_string(),
+            y_label: "Radius".to_string(),
+            title: Some("Rose".to_string()),
+            calibration_report: None,
+            coord_system: CoordSystem::Polar,
+            angle_unit: Some(AngleUnit::Degrees),
+            extra_columns: vec![ExportExtraColumn::new(
+                "distance",
+                vec![None, Some(1.5), Some(2.5)],
+            )],
+            text_columns: vec![ExportTextColumn::new(
+                "flag",
+                vec![Some("a".to_string()), Some("bcd".to_string()), None],
+            )],
+            options: ExportOptions::default(),
+        };
+        let path = temp_export_path("netcdf_test", "nc");
+        ExportFormat::NetCdf
+            .export(&path, &payload)
+            .expect("NetCDF export failed");
+        let bytes = std::fs::read(&path).expect("failed to read NetCDF output");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(&bytes[..8], b"CDF\x01\0\0\0\0");
+        // `point` and the text column's string length.
+        assert_eq!(&bytes[8..16], &[0, 0, 0, 0x0A, 0, 0, 0, 2]);
+        // Three double variables of three points, then 3 × 3 chars padded to 12 bytes.
+        let data_start = bytes.len() - (3 * 3 * 8 + 12);
+        let doubles: Vec<f64> = bytes[data_start..data_start + 72]
+            .chunks_exact(8)
+            .map(|chunk| f64::from_be_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(doubles[..6], [10.0, 20.0, 30.0, 1.0, 2.0, 3.0]);
+        assert!(doubles[6].is_nan());
+        assert_eq!(doubles[7..], [1.5, 2.5]);
+        assert_eq!(&bytes[data_start + 72..], b"a\0\0bcd\0\0\0\0\0\0");
+        let header = String::from_utf8_lossy(&bytes[..data_start]);
+        for needle in ["CF-1.8", "Rose", "degree", "coordinates", "flag_strlen"] {
+            assert!(header.contains(needle), "header lacks {needle}");
+        }
+    }
+
+    #[test]
+    fn surface_netcdf_writes_classic_header_and_big_endian_data() {
+        let mut surface = sample_surface();
+        surface.title = Some("Family".to_string());
+        let path = temp_export_path("surface_test", "nc");
+        SurfaceFormat::NetCdf
+            .export(&path, &surface)
+            .expect("NetCDF export failed");
+        let bytes = std::fs::read(&path).expect("failed to read NetCDF output");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(&bytes[..8], b"CDF\x01\0\0\0\0");
+        // Dimension list: tag, two entries, then `x` of length 3.
+        assert_eq!(&bytes[8..16], &[0, 0, 0, 0x0A, 0, 0, 0, 2]);
+        assert_eq!(&bytes[16..28], &[0, 0, 0, 1, b'x', 0, 0, 0, 0, 0, 0, 3]);
+        let data_start = bytes.len() - 8 * (3 + 3 + 9);
+        let doubles: Vec<f64> = bytes[data_start..]
+            .chunks_exact(8)
+            .map(|chunk| f64::from_be_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(doubles[..6], [0.0, 5.0, 10.0, 10.0, 20.0, 30.0]);
+        assert_eq!(doubles[6..9], [0.0, 5.0, 10.0]);
+        assert!(doubles[14].is_nan());
+        // The `x` variable's offset points at the first data byte.
+        let begin = u32::try_from(data_start).unwrap().to_be_bytes();
+        assert!(bytes[..data_start].windows(4).any(|w| w == begin));
+        let header = String::from_utf8_lossy(&bytes[..data_start]);
+        assert!(header.contains("_FillValue"));
+        assert!(header.contains("Family"));
+    }
+
+    #[test]
+    fn column_formulas_follow_precedence_and_see_the_previous_row() {
+        use columns::Formula;
+        let points = [
+            XYPoint { x: 1.0, y: 2.0 },
+            XYPoint { x: 2.0, y: 8.0 },
+            XYPoint { x: 4.0, y: 0.0 },
+        ];
+        let column = |source: &str| Formula::parse(source).unwrap().column(&points);
+        assert_eq!(column("-x^2 + 2 * 3"), [Some(5.0), Some(2.0), Some(-10.0)]);
+        assert_eq!(
+            column("2^3^2 % 100 + i"),
+            [Some(12.0), Some(13.0), Some(14.0)]
+        );
+        assert_eq!(column("dy / dx"), [None, Some(6.0), Some(-4.0)]);
+        assert_eq!(
+            column("max(x, y) / n"),
+            [Some(2.0 / 3.0), Some(8.0 / 3.0), Some(4.0 / 3.0)]
+        );
+        // Division by zero leaves the row empty.
+        assert_eq!(column("1 / y")[2], None);
+        let close = column("hypot(x, y) - sqrt(x*x + y*y) + cos(pi) + 1.5e1");
+        assert!(close.iter().all(|v| (v.unwrap() - 14.0).abs() < 1e-12));
+
+        for (source, message) in [
+            ("", "empty"),
+            ("x +", "ends too early"),
+            ("(x", "Expected ')'"),
+            ("z * 2", "Unknown name 'z'"),
+            ("foo(x)", "Unknown function 'foo'"),
+            ("atan2(y)", "Expected ','"),
+            ("x $ y", "Unexpected '$'"),
+            ("x y", "Unexpected 'y'"),
+        ] {
+            let err = Formula::parse(source).unwrap_err();
+            assert!(err.contains(message), "{source}: {err}");
+        }
+    }
+
+    #[test]
+    fn column_metrics_align_with_the_rows() {
+        let points = [
+            XYPoint { x: 0.0, y: 0.0 },
+            XYPoint { x: 3.0, y: 4.0 },
+            XYPoint { x: 3.0, y: 10.0 },
+        ];
+        assert_eq!(
+            ColumnMetric::ArcLength.values(&points),
+            [Some(0.0), Some(5.0), Some(11.0)]
+        );
+        assert_eq!(
+            ColumnMetric::Slope.values(&points),
+            [None, Some(4.0 / 3.0), None]
+        );
+        assert_eq!(ColumnMetric::Distance.values(&points)[1], Some(5.0));
+        assert!(ColumnMetric::Slope.values(&[]).is_empty());
+    }
+
+    #[test]
+    fn axis_steps_take_log_axes_in_decades() {
+        let points = [
+            XYPoint { x: 1.0, y: 10.0 },
+            XYPoint { x: 2.0, y: 1000.0 },
+            XYPoint { x: 2.0, y: 0.0 },
+        ];
+        let columns = axis_steps(&points, ScaleKind::Linear, ScaleKind::Log10);
+        let headers: Vec<_> = columns.iter().map(|c| c.header.as_str()).collect();
+        assert_eq!(headers, ["dx", "dlog10_y", "step", "slope"]);
+        assert_eq!(columns[0].values, [None, Some(1.0), Some(0.0)]);
+        // The last step reaches y = 0, which a log axis cannot show.
+        assert_eq!(columns[1].values[1], Some(2.0));
+        assert_eq!(columns[1].values[2], None);
+        assert_eq!(columns[2].values[1], Some(5.0_f64.sqrt()));
+        assert_eq!(columns[3].values, [None, Some(2.0), None]);
+
+        let log_log = [XYPoint { x: 10.0, y: 1.0 }, XYPoint { x: 100.0, y: 0.01 }];
+        let slope = &axis_steps(&log_log, ScaleKind::Log10, ScaleKind::Log10)[3];
+        assert!((slope.values[1].unwrap() + 2.0).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,449 @@
+//! Arrow IPC file (Feather v2) writer.
+//!
+//! Writes a single record batch with `Float64`/`Timestamp` axes, nullable `Float64` extra
+//! columns, and nullable `Utf8` text columns. The flatbuffer metadata is encoded by a tiny
+//! builder below instead of pulling in the full `arrow` crate for one exporter.
+
+use super::{ExportPayload, metadata_pairs, validate_extra_columns};
+use crate::types::AxisUnit;
+use std::io::{BufWriter, Write};
+
+const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+const CONTINUATION: u32 = 0xFFFF_FFFF;
+/// `MetadataVersion::V5`.
+const METADATA_VERSION: i16 = 4;
+const BODY_ALIGNMENT: usize = 8;
+
+// `MessageHeader` union tags.
+const HEADER_SCHEMA: u8 = 1;
+const HEADER_RECORD_BATCH: u8 = 3;
+// `Type` union tags.
+const TYPE_FLOATING_POINT: u8 = 3;
+const TYPE_UTF8: u8 = 5;
+const TYPE_TIMESTAMP: u8 = 10;
+const PRECISION_DOUBLE: i16 = 2;
+const TIME_UNIT_MICROSECOND: i16 = 2;
+
+/// Write the payload as an Arrow IPC file (`.arrow` / `.feather`).
+pub fn export_to_arrow(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
+    if let Err(err) = validate_extra_columns(payload) {
+        anyhow::bail!(err);
+    }
+    let columns = arrow_columns(payload)?;
+    let metadata = metadata_pairs(payload);
+
+    let mut body = Vec::new();
+    let mut nodes = Vec::with_capacity(columns.len());
+    let mut buffers = Vec::new();
+    for column in &columns {
+        nodes.push((payload.row_count(), column.null_count()));
+        for buffer in column.buffers() {
+            let offset = body.len();
+            body.extend_from_slice(&buffer);
+            pad_to(&mut body, BODY_ALIGNMENT);
+            buffers.push((offset, buffer.len()));
+        }
+    }
+
+    let mut file = BufWriter::new(std::fs::File::create(path)?);
+    let mut written = 0usize;
+    file.write_all(ARROW_MAGIC)?;
+    file.write_all(&[0, 0])?;
+    written += 8;
+
+    let schema_message = encode_message(HEADER_SCHEMA, 0, |fb| {
+        encode_schema(fb, &columns, &metadata)
+    });
+    written += write_encapsulated(&mut file, &schema_message, &[])?;
+
+    let batch_offset = written;
+    let batch_message = encode_message(HEADER_RECORD_BATCH, body.len(), |fb| {
+        encode_record_batch(fb, payload.row_count(), &nodes, &buffers)
+    });
+    let batch_len = write_encapsulated(&mut file, &batch_message, &body)?;
+    let batch_meta_len = batch_len - body.len();
+    // End-of-stream marker.
+    file.write_all(&CONTINUATION.to_le_bytes())?;
+    file.write_all(&0u32.to_le_bytes())?;
+
+    let mut fb = FlatBuilder::default();
+    let schema = encode_schema(&mut fb, &columns, &metadata);
+    let blocks = fb.struct_vector(8, &[block_bytes(batch_offset, batch_meta_len, body.len())]);
+    let no_blocks = fb.struct_vector(8, &[]);
+    let footer = fb.table(&[
+        (0, Field::I16(METADATA_VERSION)),
+        (1, Field::Offset(schema)),
+        (2, Field::Offset(no_blocks)),
+        (3, Field::Offset(blocks)),
+    ]);
+    let footer = fb.finish(footer);
+    file.write_all(&footer)?;
+    file.write_all(&u32::try_from(footer.len())?.to_le_bytes())?;
+    file.write_all(ARROW_MAGIC)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Column layout shared with the Parquet writer.
+pub(super) enum ArrowColumn {
+    Float64 {
+        name: String,
+        nullable: bool,
+        values: Vec<Option<f64>>,
+    },
+    TimestampMicros {
+        name: String,
+        values: Vec<i64>,
+    },
+    Utf8 {
+        name: String,
+        values: Vec<Option<String>>,
+    },
+}
+
+impl ArrowColumn {
+    pub(super) fn name(&self) -> &str {
+        match self {
+            Self::Float64 { name, .. }
+            | Self::TimestampMicros { name, .. }
+            | Self::Utf8 { name, .. } => name,
+        }
+    }
+
+    pub(super) const fn nullable(&self) -> bool {
+        match self {
+            Self::Float64 { nullable, .. } => *nullable,
+            Self::TimestampMicros { .. } => false,
+            Self::Utf8 { .. } => true,
+        }
+    }
+
+    fn null_count(&self) -> usize {
+        match self {
+            Self::Float64 { values, .. } => values.iter().filter(|v| v.is_none()).count(),
+            Self::TimestampMicros { .. } => 0,
+            Self::Utf8 { values, .. } => values.iter().filter(|v| v.is_none()).count(),
+        }
+    }
+
+    /// Body buffers in IPC order: validity bitmap, then offsets (for `Utf8`), then values.
+    fn buffers(&self) -> Vec<Vec<u8>> {
+        match self {
+            Self::Float64 { values, .. } => {
+                let data = values
+                    .iter()
+                    .flat_map(|v| v.unwrap_or_default().to_le_bytes())
+                    .collect();
+                vec![validity_bitmap(values.iter().map(Option::is_some)), data]
+            }
+            Self::TimestampMicros { values, .. } => {
+                let data = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+                vec![Vec::new(), data]
+            }
+            Self::Utf8 { values, .. } => {
+                let mut offsets = Vec::with_capacity((values.len() + 1) * 4);
+                let mut data = Vec::new();
+                offsets.extend_from_slice(&0i32.to_le_bytes());
+                for value in values {
+                    data.extend_from_slice(value.as_deref().unwrap_or_default().as_bytes());
+                    let end = i32::try_from(data.len()).unwrap_or(i32::MAX);
+                    offsets.extend_from_slice(&end.to_le_bytes());
+                }
+                vec![
+                    validity_bitmap(values.iter().map(Option::is_some)),
+                    offsets,
+                    data,
+                ]
+            }
+        }
+    }
+}
+
+pub(super) fn arrow_columns(payload: &ExportPayload) -> anyhow::Result<Vec<ArrowColumn>> {
+    let axis_column = |name: &str, unit: AxisUnit, values: Vec<f64>| match unit {
+        AxisUnit::Float => ArrowColumn::Float64 {
+            name: name.to_string(),
+            nullable: false,
+            values: values.into_iter().map(Some).collect(),
+        },
+        AxisUnit::DateTime => ArrowColumn::TimestampMicros {
+            name: name.to_string(),
+            values: values.into_iter().map(seconds_to_micros).collect(),
+        },
+    };
+    let mut columns = vec![
+        axis_column(
+            &payload.x_label,
+            payload.x_unit,
+            payload.points.iter().map(|p| p.x).collect(),
+        ),
+        axis_column(
+            &payload.y_label,
+            payload.y_unit,
+            payload.points.iter().map(|p| p.y).collect(),
+        ),
+    ];
+    for col in &payload.extra_columns {
+        columns.push(ArrowColumn::Float64 {
+            name: col.header.clone(),
+            nullable: true,
+            values: col.values.clone(),
+        });
+    }
+    for col in &payload.text_columns {
+        let total: usize = col.values.iter().flatten().map(String::len).sum();
+        if i32::try_from(total).is_err() {
+            anyhow::bail!("Arrow export: text column {} exceeds 2 GiB.", col.header);
+        }
+        columns.push(ArrowColumn::Utf8 {
+            name: col.header.clone(),
+            values: col.values.clone(),
+        });
+    }
+    Ok(columns)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn seconds_to_micros(seconds: f64) -> i64 {
+    (seconds * 1_000_000.0).round() as i64
+}
+
+/// LSB-first validity bitmap; empty when every slot is valid (Arrow allows omitting it).
+fn validity_bitmap(valid: impl ExactSizeIterator<Item = bool>) -> Vec<u8> {
+    let mut bits = vec![0u8; valid.len().div_ceil(8)];
+    let mut all_valid = true;
+    for (idx, is_valid) in valid.enumerate() {
+        if is_valid {
+            bits[idx / 8] |= 1 << (idx % 8);
+        } else {
+            all_valid = false;
+        }
+    }
+    if all_valid { Vec::new() } else { bits }
+}
+
+fn pad_to(buf: &mut Vec<u8>, alignment: usize) {
+    buf.resize(buf.len().next_multiple_of(alignment), 0);
+}
+
+/// Write `0xFFFFFFFF`, the padded metadata length, the metadata, and the body; returns bytes written.
+fn write_encapsulated(out: &mut impl Write, metadata: &[u8], body: &[u8]) -> anyhow::Result<usize> {
+    let padded = (metadata.len() + 8).next_multiple_of(BODY_ALIGNMENT) - 8;
+    out.write_all(&CONTINUATION.to_le_bytes())?;
+    out.write_all(&u32::try_from(padded)?.to_le_bytes())?;
+    out.write_all(metadata)?;
+    out.write_all(&vec![0; padded - metadata.len()])?;
+    out.write_all(body)?;
+    Ok(8 + padded + body.len())
+}
+
+fn encode_message(
+    header_type: u8,
+    body_len: usize,
+    header: impl FnOnce(&mut FlatBuilder) -> usize,
+) -> Vec<u8> {
+    let mut fb = FlatBuilder::default();
+    let header = header(&mut fb);
+    let message = fb.table(&[
+        (0, Field::I16(METADATA_VERSION)),
+        (1, Field::U8(header_type)),
+        (2, Field::Offset(header)),
+        (3, Field::I64(usize_to_i64(body_len))),
+    ]);
+    fb.finish(message)
+}
+
+fn encode_schema(
+    fb: &mut FlatBuilder,
+    columns: &[ArrowColumn],
+    metadata: &[(&'static str, String)],
+) -> usize {
+    let fields: Vec<usize> = columns.iter().map(|c| encode_field(fb, c)).collect();
+    let fields = fb.offset_vector(&fields);
+    let pairs: Vec<usize> = metadata
+        .iter()
+        .map(|(key, value)| {
+            let key = fb.string(key);
+            let value = fb.string(value);
+            fb.table(&[(0, Field::Offset(key)), (1, Field::Offset(value))])
+        })
+        .collect();
+    let pairs = fb.offset_vector(&pairs);
+    fb.table(&[(1, Field::Offset(fields)), (2, Field::Offset(pairs))])
+}
+
+fn encode_field(fb: &mut FlatBuilder, column: &ArrowColumn) -> usize {
+    let name = fb.string(column.name());
+    let (type_tag, arrow_type) = match column {
+        ArrowColumn::Float64 { .. } => (
+            TYPE_FLOATING_POINT,
+            fb.table(&[(0, Field::I16(PRECISION_DOUBLE))]),
+        ),
+        ArrowColumn::TimestampMicros { .. } => (
+            TYPE_TIMESTAMP,
+            fb.table(&[(0, Field::I16(TIME_UNIT_MICROSECOND))]),
+        ),
+        ArrowColumn::Utf8 { .. } => (TYPE_UTF8, fb.table(&[])),
+    };
+    // Readers expect a (possibly empty) children vector on every field.
+    let children = fb.offset_vector(&[]);
+    fb.table(&[
+        (0, Field::Offset(name)),
+        (1, Field::U8(u8::from(column.nullable()))),
+        (2, Field::U8(type_tag)),
+        (3, Field::Offset(arrow_type)),
+        (5, Field::Offset(children)),
+    ])
+}
+
+fn encode_record_batch(
+    fb: &mut FlatBuilder,
+    rows: usize,
+    nodes: &[(usize, usize)],
+    buffers: &[(usize, usize)],
+) -> usize {
+    let pair_bytes = |(a, b): (usize, usize)| {
+        let mut bytes = usize_to_i64(a).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&usize_to_i64(b).to_le_bytes());
+        bytes
+    };
+    let nodes: Vec<Vec<u8>> = nodes.iter().copied().map(pair_bytes).collect();
+    let buffers: Vec<Vec<u8>> = buffers.iter().copied().map(pair_bytes).collect();
+    let nodes = fb.struct_vector(8, &nodes);
+    let buffers = fb.struct_vector(8, &buffers);
+    fb.table(&[
+        (0, Field::I64(usize_to_i64(rows))),
+        (1, Field::Offset(nodes)),
+        (2, Field::Offset(buffers)),
+    ])
+}
+
+/// `Block { offset: long, metaDataLength: int, bodyLength: long }` with its 4 padding bytes.
+fn block_bytes(offset: usize, meta_len: usize, body_len: usize) -> Vec<u8> {
+    let mut bytes = usize_to_i64(offset).to_le_bytes().to_vec();
+    bytes.extend_from_slice(&i32::try_from(meta_len).unwrap_or(i32::MAX).to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&usize_to_i64(body_len).to_le_bytes());
+    bytes
+}
+
+fn usize_to_i64(value: usize) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+enum Field {
+    U8(u8),
+    I16(i16),
+    I64(i64),
+    /// Handle returned by a previous builder call.
+    Offset(usize),
+}
+
+/// Minimal back-to-front flatbuffer builder.
+///
+/// Objects are prepended, so every reference points forward as the format requires.
+/// Handles are distances from the end of the buffer, which stay valid while prepending.
+#[derive(Default)]
+struct FlatBuilder {
+    buf: Vec<u8>,
+}
+
+impl FlatBuilder {
+    fn prepend(&mut self, bytes: &[u8]) -> usize {
+        self.buf.splice(0..0, bytes.iter().copied());
+        self.buf.len()
+    }
+
+    /// Pad so that `additional` bytes prepended next end up `alignment`-aligned.
+    fn align(&mut self, additional: usize, alignment: usize) {
+        let misalignment = (self.buf.len() + additional) % alignment;
+        if misalignment != 0 {
+            self.prepend(&vec![0; alignment - misalignment]);
+        }
+    }
+
+    fn uoffset_to(&mut self, target: usize) -> usize {
+        self.align(4, 4);
+        let at = self.buf.len() + 4;
+        self.prepend(&u32::try_from(at - target).unwrap_or(u32::MAX).to_le_bytes())
+    }
+
+    fn string(&mut self, value: &str) -> usize {
+        self.align(value.len() + 1 + 4, 4);
+        self.prepend(&[0]);
+        self.prepend(value.as_bytes());
+        self.prepend(&u32::try_from(value.len()).unwrap_or(u32::MAX).to_le_bytes())
+    }
+
+    fn offset_vector(&mut self, targets: &[usize]) -> usize {
+        self.align(targets.len() * 4 + 4, 4);
+        for &target in targets.iter().rev() {
+            self.uoffset_to(target);
+        }
+        self.prepend(
+            &u32::try_from(targets.len())
+                .unwrap_or(u32::MAX)
+                .to_le_bytes(),
+        )
+    }
+
+    fn struct_vector(&mut self, alignment: usize, items: &[Vec<u8>]) -> usize {
+        let size: usize = items.iter().map(Vec::len).sum();
+        self.align(size, alignment);
+        for item in items.iter().rev() {
+            self.prepend(item);
+        }
+        self.align(4, 4);
+        self.prepend(&u32::try_from(items.len()).unwrap_or(u32::MAX).to_le_bytes())
+    }
+
+    fn table(&mut self, fields: &[(u16, Field)]) -> usize {
+        let table_end = self.buf.len();
+        let mut slots: Vec<(u16, usize)> = Vec::with_capacity(fields.len());
+        for (slot, field) in fields.iter().rev() {
+            let handle = match field {
+                Field::U8(v) => self.prepend(&[*v]),
+                Field::I16(v) => {
+                    self.align(2, 2);
+                    self.prepend(&v.to_le_bytes())
+                }
+                Field::I64(v) => {
+                    self.align(8, 8);
+                    self.prepend(&v.to_le_bytes())
+                }
+                Field::Offset(target) => self.uoffset_to(*target),
+            };
+            slots.push((*slot, handle));
+        }
+        self.align(4, 4);
+        let table = self.prepend(&[0; 4]);
+
+        let slot_count = fields.iter().map(|(slot, _)| slot + 1).max().unwrap_or(0);
+        let mut vtable = vec![0u16; 2 + usize::from(slot_count)];
+        vtable[0] = u16::try_from(vtable.len() * 2).unwrap_or(u16::MAX);
+        vtable[1] = u16::try_from(table - table_end).unwrap_or(u16::MAX);
+        for (slot, handle) in slots {
+            vtable[2 + usize::from(slot)] = u16::try_from(table - handle).unwrap_or(u16::MAX);
+        }
+        let bytes: Vec<u8> = vtable.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let vtable_handle = self.prepend(&bytes);
+
+        // soffset from the table to its vtable (the vtable sits just before it).
+        let soffset = i32::try_from(vtable_handle - table).unwrap_or(i32::MAX);
+        let at = self.buf.len() - table;
+        self.buf[at..at + 4].copy_from_slice(&soffset.to_le_bytes());
+        table
+    }
+
+    /// Prepend the root offset, padding the whole buffer to 8 bytes so end-relative alignment holds.
+    fn finish(mut self, root: usize) -> Vec<u8> {
+        self.align(4, 8);
+        let total = self.buf.len() + 4;
+        self.prepend(
+            &u32::try_from(total - root)
+                .unwrap_or(u32::MAX)
+                .to_le_bytes(),
+        );
+        self.buf
+    }
+}
@@ -0,0 +1,424 @@
+//! Column templates (`[[export.columns]]`): extra columns computed per exported row from a
+//! built-in metric or a formula.
+//!
+//! Formulas are plain arithmetic over the row: `x`, `y`, the row index `i`, the row count `n`
+//! and `dx` / `dy`, the step from the previous row (NaN on the first). `+ - * / % ^`, brackets,
+//! the constants `pi` and `e` and the usual functions are understood; rows where the result is
+//! not finite are left empty.
+
+use super::{sequential_distances, turning_angles};
+use crate::config::ColumnTemplate;
+use crate::interp::XYPoint;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnMetric {
+    /// Distance to the previous row.
+    Distance,
+    /// Distance along the curve from the first row.
+    ArcLength,
+    /// Turning angle at interior rows, degrees.
+    AngleDeg,
+    /// dy/dx to the previous row.
+    Slope,
+}
+
+impl ColumnMetric {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Distance => "distance",
+            Self::ArcLength => "arc_length",
+            Self::AngleDeg => "angle_deg",
+            Self::Slope => "slope",
+        }
+    }
+
+    pub fn values(self, points: &[XYPoint]) -> Vec<Option<f64>> {
+        match self {
+            Self::Distance => sequential_distances(points),
+            Self::ArcLength => {
+                let mut total = 0.0;
+                sequential_distances(points)
+                    .into_iter()
+                    .map(|step| {
+                        total += step.unwrap_or(0.0);
+                        Some(total)
+                    })
+                    .collect()
+            }
+            Self::AngleDeg => turning_angles(points),
+            Self::Slope => std::iter::once(None)
+                .chain(points.windows(2).map(|pair| {
+                    let slope = (pair[1].y - pair[0].y) / (pair[1].x - pair[0].x);
+                    slope.is_finite().then_some(slope)
+                }))
+                .take(points.len())
+                .collect(),
+        }
+    }
+}
+
+impl ColumnTemplate {
+    /// Values of the column for `points`, or why the template cannot be computed.
+    pub fn values(&self, points: &[XYPoint]) -> Result<Vec<Option<f64>>, String> {
+        if let Some(metric) = self.metric {
+            return Ok(metric.values(points));
+        }
+        let formula = Formula::parse(&self.expression)?;
+        Ok(formula.column(points))
+    }
+
+    /// Error in the template's formula, if it has one that does not parse.
+    pub fn problem(&self) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("The column has no name.".to_string());
+        }
+        if self.metric.is_some() {
+            return None;
+        }
+        Formula::parse(&self.expression).err()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Var {
+    X,
+    Y,
+    Index,
+    Count,
+    Dx,
+    Dy,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Num(f64),
+    Var(Var),
+    Neg(Box<Self>),
+    Bin(BinOp, Box<Self>, Box<Self>),
+    Call1(fn(f64) -> f64, Box<Self>),
+    Call2(fn(f64, f64) -> f64, Box<Self>, Box<Self>),
+}
+
+/// Values a formula sees for one row.
+struct Row {
+    x: f64,
+    y: f64,
+    index: f64,
+    count: f64,
+    dx: f64,
+    dy: f64,
+}
+
+impl Expr {
+    fn eval(&self, row: &Row) -> f64 {
+        match self {
+            Self::Num(value) => *value,
+            Self::Var(var) => match var {
+                Var::X => row.x,
+                Var::Y => row.y,
+                Var::Index => row.index,
+                Var::Count => row.count,
+                Var::Dx => row.dx,
+                Var::Dy => row.dy,
+            },
+            Self::Neg(inner) => -inner.eval(row),
+            Self::Bin(op, lhs, rhs) => {
+                let (a, b) = (lhs.eval(row), rhs.eval(row));
+                match op {
+                    BinOp::Add => a + b,
+                    BinOp::Sub => a - b,
+                    BinOp::Mul => a * b,
+                    BinOp::Div => a / b,
+                    BinOp::Rem => a % b,
+                    BinOp::Pow => a.powf(b),
+                }
+            }
+            Self::Call1(func, arg) => func(arg.eval(row)),
+            Self::Call2(func, a, b) => func(a.eval(row), b.eval(row)),
+        }
+    }
+}
+
+/// Parsed column formula.
+#[derive(Debug, Clone)]
+pub struct Formula(Expr);
+
+impl Formula {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err("The formula is empty.".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.expr()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("Unexpected {} in the formula.", token.describe()));
+        }
+        Ok(Self(expr))
+    }
+
+    /// The formula evaluated at each of `points`.
+    pub fn column(&self, points: &[XYPoint]) -> Vec<Option<f64>> {
+        #[allow(clippy::cast_precision_loss)]
+        let count = points.len() as f64;
+        points
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| {
+                let prev = idx.checked_sub(1).map(|prev| points[prev]);
+                #[allow(clippy::cast_precision_loss)]
+                let row = Row {
+                    x: p.x,
+                    y: p.y,
+                    index: idx as f64,
+                    count,
+                    dx: prev.map_or(f64::NAN, |prev| p.x - prev.x),
+                    dy: prev.map_or(f64::NAN, |prev| p.y - prev.y),
+                };
+                let value = self.0.eval(&row);
+                value.is_finite().then_some(value)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(char),
+    Open,
+    Close,
+    Comma,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Num(value) => format!("number {value}"),
+            Self::Ident(name) => format!("'{name}'"),
+            Self::Op(op) => format!("'{op}'"),
+            Self::Open => "'('".to_string(),
+            Self::Close => "')'".to_string(),
+            Self::Comma => "','".to_string(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            let mut prev = c;
+            while let Some(&(idx, c)) = chars.peek() {
+                let exponent_sign = (c == '+' || c == '-') && matches!(prev, 'e' | 'E');
+                if !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E') || exponent_sign) {
+                    break;
+                }
+                end = idx + c.len_utf8();
+                prev = c;
+                chars.next();
+            }
+            let text = &source[start..end];
+            let value = text
+                .parse()
+                .map_err(|_| format!("'{text}' is not a number."))?;
+            tokens.push(Token::Num(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(idx, c)) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = idx + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token::Ident(source[start..end].to_string()));
+        } else {
+            tokens.push(match c {
+                '+' | '-' | '*' | '/' | '%' | '^' => Token::Op(c),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                ',' => Token::Comma,
+                _ => return Err(format!("Unexpected '{c}' in the formula.")),
+            });
+            chars.next();
+        }
+    }
+    Ok(tokens)
+}
+
+fn function1(name: &str) -> Option<fn(f64) -> f64> {
+    Some(match name {
+        "abs" => f64::abs,
+        "sqrt" => f64::sqrt,
+        "exp" => f64::exp,
+        "ln" => f64::ln,
+        "log10" => f64::log10,
+        "sin" => f64::sin,
+        "cos" => f64::cos,
+        "tan" => f64::tan,
+        "asin" => f64::asin,
+        "acos" => f64::acos,
+        "atan" => f64::atan,
+        "floor" => f64::floor,
+        "ceil" => f64::ceil,
+        "round" => f64::round,
+        "degrees" => f64::to_degrees,
+        "radians" => f64::to_radians,
+        _ => return None,
+    })
+}
+
+fn function2(name: &str) -> Option<fn(f64, f64) -> f64> {
+    Some(match name {
+        "atan2" => f64::atan2,
+        "hypot" => f64::hypot,
+        "min" => f64::min,
+        "max" => f64::max,
+        "pow" => f64::powf,
+        _ => return None,
+    })
+}
+
+fn variable(name: &str) -> Option<Expr> {
+    Some(match name {
+        "x" => Expr::Var(Var::X),
+        "y" => Expr::Var(Var::Y),
+        "i" => Expr::Var(Var::Index),
+        "n" => Expr::Var(Var::Count),
+        "dx" => Expr::Var(Var::Dx),
+        "dy" => Expr::Var(Var::Dy),
+        "pi" => Expr::Num(std::f64::consts::PI),
+        "e" => Expr::Num(std::f64::consts::E),
+        _ => return None,
+    })
+}
+
+/// Recursive descent: sums, products, unary minus, then right-associative powers.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.eat(token) {
+            return Ok(());
+        }
+        let expected = token.describe();
+        Err(self.peek().map_or_else(
+            || format!("Expected {expected} at the end of the formula."),
+            |found| format!("Expected {expected} but found {}.", found.describe()),
+        ))
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op('+')) => BinOp::Add,
+                Some(Token::Op('-')) => BinOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Op('*')) => BinOp::Mul,
+                Some(Token::Op('/')) => BinOp::Div,
+                Some(Token::Op('%')) => BinOp::Rem,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            lhs = Expr::Bin(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat(&Token::Op('-')) {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::Op('+')) {
+            return self.unary();
+        }
+        let base = self.atom()?;
+        if self.eat(&Token::Op('^')) {
+            return Ok(Expr::Bin(
+                BinOp::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Num(value)) => Ok(Expr::Num(value)),
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                self.expect(&Token::Close)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::Open) => {
+                self.pos += 1;
+                let first = self.expr()?;
+                let call = if let Some(func) = function1(&name) {
+                    Expr::Call1(func, Box::new(first))
+                } else if let Some(func) = function2(&name) {
+                    self.expect(&Token::Comma)?;
+                    Expr::Call2(func, Box::new(first), Box::new(self.expr()?))
+                } else {
+                    return Err(format!("Unknown function '{name}'."));
+                };
+                self.expect(&Token::Close)?;
+                Ok(call)
+            }
+            Some(Token::Ident(name)) => {
+                variable(&name).ok_or_else(|| format!("Unknown name '{name}'."))
+            }
+            Some(token) => Err(format!("Unexpected {} in the formula.", token.describe())),
+            None => Err("The formula ends too early.".to_string()),
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! `NetCDF` classic (CDF-1) writer and the point export built on it.
+//!
+//! The file layout is emitted directly rather than through `libnetcdf`, so the export needs no
+//! system library: fixed-size dimensions, `double` and `char` variables, no record dimension.
+//! Point exports follow the CF conventions: `x` and `y` carry `long_name` and, where the unit is
+//! known, `units`; `y` names `x` as its coordinate.
+
+use super::{ExportPayload, angle_unit_label, coord_system_label, validate_extra_columns};
+use crate::types::{AngleUnit, AxisUnit, CoordSystem};
+use std::io::{BufWriter, Write};
+
+const CDF_MAGIC: &[u8; 4] = b"CDF\x01";
+// Header list tags and value types.
+const NC_DIMENSION: u32 = 0x0A;
+const NC_VARIABLE: u32 = 0x0B;
+const NC_ATTRIBUTE: u32 = 0x0C;
+const NC_CHAR: u32 = 2;
+const NC_DOUBLE: u32 = 6;
+/// Units of date-time values, which are stored as Unix seconds.
+pub(super) const EPOCH_UNITS: &str = "seconds since 1970-01-01 00:00:00";
+const CF_CONVENTIONS: &str = "CF-1.8";
+/// Names the point variables take; extra and text columns are renamed around them.
+const RESERVED_NAMES: [&str; 3] = ["point", "x", "y"];
+
+pub(super) enum Attribute<'a> {
+    Text(&'a str, &'a str),
+    Double(&'a str, f64),
+}
+
+pub(super) enum Values {
+    Double(Vec<f64>),
+    /// Bytes of a `char` array, row by row.
+    Char(Vec<u8>),
+}
+
+impl Values {
+    const fn nc_type(&self) -> u32 {
+        match self {
+            Self::Double(_) => NC_DOUBLE,
+            Self::Char(_) => NC_CHAR,
+        }
+    }
+
+    /// Bytes the variable takes in the data section, padded to a four-byte boundary.
+    const fn size(&self) -> usize {
+        match self {
+            Self::Double(values) => values.len() * 8,
+            Self::Char(bytes) => bytes.len().next_multiple_of(4),
+        }
+    }
+}
+
+pub(super) struct Variable<'a> {
+    pub(super) name: String,
+    /// Indices into the dimension list, slowest-varying first.
+    pub(super) dims: Vec<usize>,
+    pub(super) attributes: Vec<Attribute<'a>>,
+    pub(super) values: Values,
+}
+
+/// Write a classic file holding `variables` over the named fixed-size `dims`.
+pub(super) fn write_classic(
+    path: &std::path::Path,
+    dims: &[(String, usize)],
+    global: &[Attribute],
+    variables: &[Variable],
+) -> anyhow::Result<()> {
+    // Offsets do not change the header's size, so a first pass measures where data starts.
+    let data_start = encode_header(dims, global, variables, 0)?.len();
+    let header = encode_header(dims, global, variables, data_start)?;
+    let mut file = BufWriter::new(std::fs::File::create(path)?);
+    file.write_all(&header)?;
+    for variable in variables {
+        match &variable.values {
+            Values::Double(values) => {
+                for value in values {
+                    file.write_all(&value.to_be_bytes())?;
+                }
+            }
+            Values::Char(bytes) => {
+                file.write_all(bytes)?;
+                let padding = variable.values.size() - bytes.len();
+                file.write_all(&[0; 4][..padding])?;
+            }
+        }
+    }
+    file.flush()?;
+    Ok(())
+}
+
+fn encode_header(
+    dims: &[(String, usize)],
+    global: &[Attribute],
+    variables: &[Variable],
+    data_start: usize,
+) -> anyhow::Result<Vec<u8>> {
+    let mut out = CDF_MAGIC.to_vec();
+    // No record dimension, so no records.
+    put_u32(&mut out, 0);
+    put_u32(&mut out, NC_DIMENSION);
+    put_len(&mut out, dims.len())?;
+    for (name, len) in dims {
+        put_name(&mut out, name)?;
+        put_len(&mut out, *len)?;
+    }
+    put_attributes(&mut out, global)?;
+    put_u32(&mut out, NC_VARIABLE);
+    put_len(&mut out, variables.len())?;
+    let mut begin = data_start;
+    for variable in variables {
+        put_name(&mut out, &variable.name)?;
+        put_len(&mut out, variable.dims.len())?;
+        for dim in &variable.dims {
+            put_len(&mut out, *dim)?;
+        }
+        put_attributes(&mut out, &variable.attributes)?;
+        put_u32(&mut out, variable.values.nc_type());
+        let size = variable.values.size();
+        put_len(&mut out, size)?;
+        put_len(&mut out, begin)?;
+        begin += size;
+    }
+    Ok(out)
+}
+
+fn put_attributes(out: &mut Vec<u8>, attributes: &[Attribute]) -> anyhow::Result<()> {
+    if attributes.is_empty() {
+        // `ABSENT`: a zero tag and a zero count.
+        put_u32(out, 0);
+        put_u32(out, 0);
+        return Ok(());
+    }
+    put_u32(out, NC_ATTRIBUTE);
+    put_len(out, attributes.len())?;
+    for attribute in attributes {
+        match attribute {
+            Attribute::Text(name, text) => {
+                put_name(out, name)?;
+                put_u32(out, NC_CHAR);
+                put_padded(out, text.as_bytes())?;
+            }
+            Attribute::Double(name, value) => {
+                put_name(out, name)?;
+                put_u32(out, NC_DOUBLE);
+                put_u32(out, 1);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn put_name(out: &mut Vec<u8>, name: &str) -> anyhow::Result<()> {
+    put_padded(out, name.as_bytes())
+}
+
+/// Byte count, the bytes, and zero padding to the next four-byte boundary.
+fn put_padded(out: &mut Vec<u8>, bytes: &[u8]) -> anyhow::Result<()> {
+    put_len(out, bytes.len())?;
+    out.extend_from_slice(bytes);
+    out.resize(out.len().next_multiple_of(4), 0);
+    Ok(())
+}
+
+fn put_len(out: &mut Vec<u8>, value: usize) -> anyhow::Result<()> {
+    let value = u32::try_from(value)
+        .map_err(|_| anyhow::anyhow!("The data is too large for a NetCDF classic file."))?;
+    put_u32(out, value);
+    Ok(())
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// CF `units` of an axis: epoch seconds for date-times, the angle unit for polar angles.
+const fn cf_units(unit: AxisUnit, angle: Option<AngleUnit>) -> Option<&'static str> {
+    match (unit, angle) {
+        (AxisUnit::DateTime, _) => Some(EPOCH_UNITS),
+        (AxisUnit::Float, Some(AngleUnit::Degrees)) => Some("degree"),
+        (AxisUnit::Float, Some(AngleUnit::Radians)) => Some("radian"),
+        (AxisUnit::Float, None) => None,
+    }
+}
+
+/// A valid `NetCDF` name for `header` that no earlier variable or dimension took.
+fn variable_name(header: &str, used: &mut Vec<String>) -> String {
+    let mut base: String = header
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !base.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        base.insert_str(0, "v_");
+    }
+    let mut candidate = base.clone();
+    let mut suffix = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{base}_{suffix}");
+        suffix += 1;
+    }
+    used.push(candidate.clone());
+    candidate
+}
+
+/// Write the payload as a `NetCDF` classic file: `x` and `y` over the `point` dimension, extra
+/// columns as further `double` variables and text columns as `char` arrays.
+pub fn export_to_netcdf(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
+    if let Err(err) = validate_extra_columns(payload) {
+        anyhow::bail!(err);
+    }
+    let polar_angle = (payload.coord_system == CoordSystem::Polar)
+        .then_some(payload.angle_unit)
+        .flatten();
+    let mut x_attributes = vec![Attribute::Text("long_name", &payload.x_label)];
+    if let Some(units) = cf_units(payload.x_unit, polar_angle) {
+        x_attributes.push(Attribute::Text("units", units));
+    }
+    let mut y_attributes = vec![Attribute::Text("long_name", &payload.y_label)];
+    if let Some(units) = cf_units(payload.y_unit, None) {
+        y_attributes.push(Attribute::Text("units", units));
+    }
+    y_attributes.push(Attribute::Text("coordinates", "x"));
+
+    let mut used: Vec<String> = RESERVED_NAMES
+        .iter()
+        .map(|name| (*name).to_string())
+        .collect();
+    let mut dims = vec![("point".to_string(), payload.points.len())];
+    let mut variables = vec![
+        Variable {
+            name: "x".to_string(),
+            dims: vec![0],
+            attributes: x_attributes,
+            values: Values::Double(payload.points.iter().map(|p| p.x).collect()),
+        },
+        Variable {
+            name: "y".to_string(),
+            dims: vec![0],
+            attributes: y_attributes,
+            values: Values::Double(payload.points.iter().map(|p| p.y).collect()),
+        },
+    ];
+    for column in &payload.extra_columns {
+        variables.push(Variable {
+            name: variable_name(&column.header, &mut used),
+            dims: vec![0],
+            attributes: vec![
+                Attribute::Text("long_name", &column.header),
+                Attribute::Double("_FillValue", f64::NAN),
+                Attribute::Text("coordinates", "x"),
+            ],
+            values: Values::Double(
+                column
+                    .values
+                    .iter()
+                    .map(|v| v.unwrap_or(f64::NAN))
+                    .collect(),
+            ),
+        });
+    }
+    for column in &payload.text_columns {
+        let name = variable_name(&column.header, &mut used);
+        let width = column
+            .values
+            .iter()
+            .flatten()
+            .map(String::len)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut bytes = Vec::with_capacity(width * column.values.len());
+        for value in &column.values {
+            let text = value.as_deref().unwrap_or_default().as_bytes();
+            bytes.extend_from_slice(text);
+            bytes.resize(bytes.len() + width - text.len(), 0);
+        }
+        dims.push((variable_name(&format!("{name}_strlen"), &mut used), width));
+        variables.push(Variable {
+            name,
+            dims: vec![0, dims.len() - 1],
+            attributes: vec![Attribute::Text("long_name", &column.header)],
+            values: Values::Char(bytes),
+        });
+    }
+
+    let source = format!("curcat {}", env!("CARGO_PKG_VERSION"));
+    let mut global = vec![
+        Attribute::Text("Conventions", CF_CONVENTIONS),
+        Attribute::Text("source", &source),
+        Attribute::Text("coord_system", coord_system_label(payload.coord_system)),
+    ];
+    if let Some(unit) = polar_angle {
+        global.push(Attribute::Text("angle_unit", angle_unit_label(unit)));
+    }
+    if let Some(title) = &payload.title {
+        global.push(Attribute::Text("title", title));
+    }
+    write_classic(path, &dims, &global, &variables)
+}
@@ -0,0 +1,264 @@
+//! `OpenDocument` Spreadsheet (`.ods`) writer.
+//!
+//! Cells keep their types: floats are written as `float` values and `DateTime` axes as
+//! `date` values, so `LibreOffice` users get sortable dates without a CSV round-trip.
+
+use super::{
+    ColumnFormats, ExportPayload, XLSX_MAX_ROWS, axis_value_from_scalar_for_export,
+    escape_xml_attr, escape_xml_text, tabular_headers, unique_xlsx_name, validate_extra_columns,
+    xlsx_sheet_name,
+};
+use crate::types::{AxisUnit, AxisValue, FloatFormat};
+use std::io::Write;
+use zip::CompressionMethod;
+use zip::write::{SimpleFileOptions, ZipWriter};
+
+const ODS_MIMETYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+const MANIFEST_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.3">
+ <manifest:file-entry manifest:full-path="/" manifest:version="1.3" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/>
+ <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#;
+
+/// Write the payload to an ODS workbook at the provided path.
+pub fn export_to_ods(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
+    export_datasets_to_ods(path, &[("Data", payload)])
+}
+
+/// Write each named dataset to its own sheet of an ODS workbook.
+///
+/// Sheets follow the XLSX naming and row-splitting rules so both spreadsheet exports line up.
+pub fn export_datasets_to_ods(
+    path: &std::path::Path,
+    datasets: &[(&str, &ExportPayload)],
+) -> anyhow::Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut zip = ZipWriter::new(std::io::BufWriter::new(file));
+    // The mimetype entry must come first and stay uncompressed for format sniffing.
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(ODS_MIMETYPE.as_bytes())?;
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file("META-INF/manifest.xml", deflated)?;
+    zip.write_all(MANIFEST_XML.as_bytes())?;
+
+    zip.start_file("content.xml", deflated)?;
+    // Styles live before the body, so every column format is resolved up front.
+    let formats: Vec<ColumnFormats> = datasets
+        .iter()
+        .map(|(_, payload)| ColumnFormats::resolve(payload))
+        .collect();
+    let mut number_formats: Vec<FloatFormat> = Vec::new();
+    for format in formats
+        .iter()
+        .flat_map(|f| [f.x, f.y].into_iter().chain(f.extras.iter().copied()))
+    {
+        if !number_formats.contains(&format) {
+            number_formats.push(format);
+        }
+    }
+    write_content_prologue(&mut zip, &number_formats)?;
+    let mut sheet_names = Vec::new();
+    for ((name, payload), formats) in datasets.iter().zip(&formats) {
+        write_dataset(&mut zip, name, payload, formats, &mut sheet_names)?;
+    }
+    if datasets.is_empty() {
+        zip.write_all(b"<table:table table:name=\"Data\"/>")?;
+    }
+    zip.write_all(b"</office:spreadsheet></office:body></office:document-content>\n")?;
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+/// Cell style name for a float format; the matching data style swaps the `ce` prefix for `N`.
+fn number_style_name(format: FloatFormat) -> String {
+    match format {
+        FloatFormat::Fixed(decimals) => format!("ceF{decimals}"),
+        FloatFormat::Scientific(digits) => format!("ceE{digits}"),
+    }
+}
+
+fn write_content_prologue(
+    out: &mut impl Write,
+    number_formats: &[FloatFormat],
+) -> std::io::Result<()> {
+    out.write_all(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "\n",
+            r#"<office:document-content"#,
+            r#" xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0""#,
+            r#" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0""#,
+            r#" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0""#,
+            r#" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0""#,
+            r#" xmlns:number="urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0""#,
+            r#" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0""#,
+            r#" office:version="1.3">"#,
+            "<office:automatic-styles>",
+        )
+        .as_bytes(),
+    )?;
+    for &format in number_formats {
+        let cell_style = number_style_name(format);
+        let data_style = cell_style.replacen("ce", "N", 1);
+        match format {
+            FloatFormat::Fixed(decimals) => write!(
+                out,
+                "<number:number-style style:name=\"{data_style}\"><number:number number:decimal-places=\"{decimals}\" number:min-integer-digits=\"1\"/></number:number-style>"
+            )?,
+            FloatFormat::Scientific(digits) => write!(
+                out,
+                "<number:number-style style:name=\"{data_style}\"><number:scientific-number number:decimal-places=\"{digits}\" number:min-integer-digits=\"1\" number:min-exponent-digits=\"2\"/></number:number-style>"
+            )?,
+        }
+        write!(
+            out,
+            "<style:style style:name=\"{cell_style}\" style:family=\"table-cell\" style:data-style-name=\"{data_style}\"/>"
+        )?;
+    }
+    out.write_all(concat!(
+        r#"<number:date-style style:name="N1">"#,
+        r#"<number:year number:style="long"/><number:text>-</number:text>"#,
+        r#"<number:month number:style="long"/><number:text>-</number:text>"#,
+        r#"<number:day number:style="long"/><number:text> </number:text>"#,
+        r#"<number:hours number:style="long"/><number:text>:</number:text>"#,
+        r#"<number:minutes number:style="long"/><number:text>:</number:text>"#,
+        r#"<number:seconds number:style="long" number:decimal-places="3"/>"#,
+        "</number:date-style>",
+        r#"<style:style style:name="ceDate" style:family="table-cell" style:data-style-name="N1"/>"#,
+        r#"<style:style style:name="ceHead" style:family="table-cell"><style:text-properties fo:font-weight="bold"/></style:style>"#,
+        "</office:automatic-styles><office:body><office:spreadsheet>",
+    ).as_bytes())
+}
+
+fn write_dataset(
+    out: &mut impl Write,
+    name: &str,
+    payload: &ExportPayload,
+    formats: &ColumnFormats,
+    sheet_names: &mut Vec<String>,
+) -> anyhow::Result<()> {
+    if let Err(err) = validate_extra_columns(payload) {
+        anyhow::bail!(err);
+    }
+    let headers = tabular_headers(payload);
+    let max_rows_per_sheet = XLSX_MAX_ROWS.saturating_sub(1) as usize;
+    let total_rows = payload.row_count();
+    let sheet_count = total_rows.div_ceil(max_rows_per_sheet).max(1);
+
+    for sheet_index in 0..sheet_count {
+        let base = if sheet_index == 0 {
+            name.to_string()
+        } else {
+            format!("{name} {}", sheet_index + 1)
+        };
+        let sheet_name = unique_xlsx_name(&xlsx_sheet_name(&base), sheet_names, 31);
+        write!(
+            out,
+            "<table:table table:name=\"{}\"><table:table-column table:number-columns-repeated=\"{}\"/>",
+            escape_xml_attr(&sheet_name),
+            headers.len()
+        )?;
+        out.write_all(b"<table:table-header-rows><table:table-row>")?;
+        for header in &headers {
+            push_string_cell(out, Some(header), Some("ceHead"))?;
+        }
+        out.write_all(b"</table:table-row></table:table-header-rows>")?;
+
+        let start = sheet_index * max_rows_per_sheet;
+        let end = (start + max_rows_per_sheet).min(total_rows);
+        for row_idx in start..end {
+            write_row(out, payload, formats, row_idx)?;
+        }
+        out.write_all(b"</table:table>")?;
+    }
+    Ok(())
+}
+
+fn write_row(
+    out: &mut impl Write,
+    payload: &ExportPayload,
+    formats: &ColumnFormats,
+    row_idx: usize,
+) -> anyhow::Result<()> {
+    let p = &payload.points[row_idx];
+    out.write_all(b"<table:table-row>")?;
+    push_axis_cell(out, payload.x_unit, p.x, "x", formats.x)?;
+    push_axis_cell(out, payload.y_unit, p.y, "y", formats.y)?;
+    for (col, &format) in payload.extra_columns.iter().zip(&formats.extras) {
+        match col.values.get(row_idx).and_then(|v| *v) {
+            Some(value) if value.is_finite() => {
+                push_float_cell(out, value, &format.format(value), format)?;
+            }
+            Some(value) => anyhow::bail!("ODS export cannot represent non-finite value {value}."),
+            None => out.write_all(b"<table:table-cell/>")?,
+        }
+    }
+    for col in &payload.text_columns {
+        push_string_cell(
+            out,
+            col.values.get(row_idx).and_then(Option::as_deref),
+            None,
+        )?;
+    }
+    out.write_all(b"</table:table-row>")?;
+    Ok(())
+}
+
+fn push_axis_cell(
+    out: &mut impl Write,
+    unit: AxisUnit,
+    scalar: f64,
+    axis: &str,
+    format: FloatFormat,
+) -> anyhow::Result<()> {
+    match axis_value_from_scalar_for_export(unit, scalar, axis)? {
+        av @ AxisValue::Float(value) if value.is_finite() => {
+            push_float_cell(out, value, &av.format_with(format), format)?;
+        }
+        AxisValue::Float(value) => {
+            anyhow::bail!("ODS export cannot represent non-finite {axis} value {value}.")
+        }
+        dt @ AxisValue::DateTime(naive) => {
+            let iso = naive.format("%Y-%m-%dT%H:%M:%S%.3f");
+            write!(
+                out,
+                "<table:table-cell table:style-name=\"ceDate\" office:value-type=\"date\" office:date-value=\"{iso}\"><text:p>{}</text:p></table:table-cell>",
+                escape_xml_text(&dt.format())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn push_float_cell(
+    out: &mut impl Write,
+    value: f64,
+    display: &str,
+    format: FloatFormat,
+) -> std::io::Result<()> {
+    write!(
+        out,
+        "<table:table-cell table:style-name=\"{}\" office:value-type=\"float\" office:value=\"{value}\"><text:p>{}</text:p></table:table-cell>",
+        number_style_name(format),
+        escape_xml_text(display)
+    )
+}
+
+fn push_string_cell(
+    out: &mut impl Write,
+    text: Option<&str>,
+    style: Option<&str>,
+) -> std::io::Result<()> {
+    let style_attr = style.map_or_else(String::new, |s| format!(" table:style-name=\"{s}\""));
+    match text {
+        Some(text) => write!(
+            out,
+            "<table:table-cell{style_attr} office:value-type=\"string\"><text:p>{}</text:p></table:table-cell>",
+            escape_xml_text(text)
+        ),
+        None => write!(out, "<table:table-cell{style_attr}/>"),
+    }
+}
@@ -0,0 +1,353 @@
+//! Apache Parquet file writer.
+//!
+//! Writes one row group with one uncompressed, `PLAIN`-encoded data page per column, using
+//! the same column layout as the Arrow export: `DOUBLE`/`TIMESTAMP(µs)` axes, optional
+//! `DOUBLE` extra columns, and optional `BYTE_ARRAY` (UTF-8) text columns. The Thrift
+//! metadata is encoded by a small compact-protocol writer below.
+
+use super::arrow::{ArrowColumn, arrow_columns};
+use super::{ExportPayload, metadata_pairs, validate_extra_columns};
+use std::io::{BufWriter, Write};
+
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+const FORMAT_VERSION: i32 = 1;
+
+// `Type` values.
+const TYPE_INT64: i32 = 2;
+const TYPE_DOUBLE: i32 = 5;
+const TYPE_BYTE_ARRAY: i32 = 6;
+// `FieldRepetitionType` values.
+const REPETITION_REQUIRED: i32 = 0;
+const REPETITION_OPTIONAL: i32 = 1;
+/// `ConvertedType::UTF8`, kept for readers that predate logical types.
+const CONVERTED_UTF8: i32 = 0;
+// `LogicalType` union fields.
+const LOGICAL_STRING: i16 = 1;
+const LOGICAL_TIMESTAMP: i16 = 8;
+const TIME_UNIT_MICROS: i16 = 2;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+/// Write the payload as a Parquet file.
+pub fn export_to_parquet(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
+    if let Err(err) = validate_extra_columns(payload) {
+        anyhow::bail!(err);
+    }
+    let columns = arrow_columns(payload)?;
+    let rows = usize_to_i64(payload.row_count());
+
+    let mut file = BufWriter::new(std::fs::File::create(path)?);
+    file.write_all(PARQUET_MAGIC)?;
+    let mut offset = PARQUET_MAGIC.len();
+    let mut chunks = Vec::with_capacity(columns.len());
+    for column in &columns {
+        let data = page_data(column);
+        let header = page_header(payload.row_count(), data.len())?;
+        file.write_all(&header)?;
+        file.write_all(&data)?;
+        let size = header.len() + data.len();
+        chunks.push((offset, size));
+        offset += size;
+    }
+
+    let metadata = file_metadata(&columns, &chunks, rows, &metadata_pairs(payload));
+    file.write_all(&metadata)?;
+    file.write_all(&u32::try_from(metadata.len())?.to_le_bytes())?;
+    file.write_all(PARQUET_MAGIC)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Definition levels (for optional columns) followed by the `PLAIN` values of non-null slots.
+fn page_data(column: &ArrowColumn) -> Vec<u8> {
+    let mut out = Vec::new();
+    match column {
+        ArrowColumn::Float64 {
+            nullable, values, ..
+        } => {
+            if *nullable {
+                definition_levels(&mut out, values.iter().map(Option::is_some));
+            }
+            for value in values.iter().flatten() {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        ArrowColumn::TimestampMicros { values, .. } => {
+            for value in values {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        ArrowColumn::Utf8 { values, .. } => {
+            definition_levels(&mut out, values.iter().map(Option::is_some));
+            for value in values.iter().flatten() {
+                let len = u32::try_from(value.len()).unwrap_or(u32::MAX);
+                out.extend_from_slice(&len.to_le_bytes());
+                out.extend_from_slice(value.as_bytes());
+            }
+        }
+    }
+    out
+}
+
+/// Length-prefixed RLE runs of 1-bit definition levels (1 = present, 0 = null).
+fn definition_levels(out: &mut Vec<u8>, present: impl Iterator<Item = bool>) {
+    let mut runs = Vec::new();
+    let mut run: Option<(bool, u64)> = None;
+    for value in present {
+        run = match run {
+            Some((prev, len)) if prev == value => Some((prev, len + 1)),
+            Some((prev, len)) => {
+                write_rle_run(&mut runs, prev, len);
+                Some((value, 1))
+            }
+            None => Some((value, 1)),
+        };
+    }
+    if let Some((value, len)) = run {
+        write_rle_run(&mut runs, value, len);
+    }
+    out.extend_from_slice(&u32::try_from(runs.len()).unwrap_or(u32::MAX).to_le_bytes());
+    out.extend_from_slice(&runs);
+}
+
+fn write_rle_run(out: &mut Vec<u8>, value: bool, len: u64) {
+    write_varint(out, len << 1);
+    out.push(u8::from(value));
+}
+
+fn page_header(rows: usize, data_len: usize) -> anyhow::Result<Vec<u8>> {
+    let size = i32::try_from(data_len)?;
+    let mut w = CompactWriter::default();
+    w.i32(1, PAGE_DATA);
+    w.i32(2, size);
+    w.i32(3, size);
+    w.begin_struct(5);
+    w.i32(1, i32::try_from(rows)?);
+    w.i32(2, ENCODING_PLAIN);
+    w.i32(3, ENCODING_RLE);
+    w.i32(4, ENCODING_RLE);
+    w.end_struct();
+    Ok(w.finish())
+}
+
+fn file_metadata(
+    columns: &[ArrowColumn],
+    chunks: &[(usize, usize)],
+    rows: i64,
+    metadata: &[(&'static str, String)],
+) -> Vec<u8> {
+    let mut w = CompactWriter::default();
+    w.i32(1, FORMAT_VERSION);
+
+    w.list(2, CompactType::Struct, columns.len() + 1);
+    w.begin_element();
+    w.binary(4, b"schema");
+    w.i32(5, i32::try_from(columns.len()).unwrap_or(i32::MAX));
+    w.end_struct();
+    for column in columns {
+        w.begin_element();
+        schema_element(&mut w, column);
+        w.end_struct();
+    }
+
+    w.i64(3, rows);
+    w.list(4, CompactType::Struct, 1);
+    w.begin_element();
+    w.list(1, CompactType::Struct, columns.len());
+    for (column, &(offset, size)) in columns.iter().zip(chunks) {
+        w.begin_element();
+        w.i64(2, usize_to_i64(offset));
+        w.begin_struct(3);
+        w.i32(1, physical_type(column));
+        w.list(2, CompactType::I32, 2);
+        w.element_i32(ENCODING_PLAIN);
+        w.element_i32(ENCODING_RLE);
+        w.list(3, CompactType::Binary, 1);
+        w.element_binary(column.name().as_bytes());
+        w.i32(4, CODEC_UNCOMPRESSED);
+        w.i64(5, rows);
+        w.i64(6, usize_to_i64(size));
+        w.i64(7, usize_to_i64(size));
+        w.i64(9, usize_to_i64(offset));
+        w.end_struct();
+        w.end_struct();
+    }
+    let total: usize = chunks.iter().map(|&(_, size)| size).sum();
+    w.i64(2, usize_to_i64(total));
+    w.i64(3, rows);
+    w.end_struct();
+
+    w.list(5, CompactType::Struct, metadata.len());
+    for (key, value) in metadata {
+        w.begin_element();
+        w.binary(1, key.as_bytes());
+        w.binary(2, value.as_bytes());
+        w.end_struct();
+    }
+    w.binary(
+        6,
+        format!("curcat version {}", env!("CARGO_PKG_VERSION")).as_bytes(),
+    );
+    w.finish()
+}
+
+fn schema_element(w: &mut CompactWriter, column: &ArrowColumn) {
+    let repetition = if column.nullable() {
+        REPETITION_OPTIONAL
+    } else {
+        REPETITION_REQUIRED
+    };
+    w.i32(1, physical_type(column));
+    w.i32(3, repetition);
+    w.binary(4, column.name().as_bytes());
+    match column {
+        ArrowColumn::Float64 { .. } => {}
+        // Local (not UTC-adjusted) time, like the Arrow export; such timestamps have
+        // no `ConvertedType` equivalent.
+        ArrowColumn::TimestampMicros { .. } => {
+            w.begin_struct(10);
+            w.begin_struct(LOGICAL_TIMESTAMP);
+            w.bool(1, false);
+            w.begin_struct(2);
+            w.begin_struct(TIME_UNIT_MICROS);
+            w.end_struct();
+            w.end_struct();
+            w.end_struct();
+            w.end_struct();
+        }
+        ArrowColumn::Utf8 { .. } => {
+            w.i32(6, CONVERTED_UTF8);
+            w.begin_struct(10);
+            w.begin_struct(LOGICAL_STRING);
+            w.end_struct();
+            w.end_struct();
+        }
+    }
+}
+
+const fn physical_type(column: &ArrowColumn) -> i32 {
+    match column {
+        ArrowColumn::Float64 { .. } => TYPE_DOUBLE,
+        ArrowColumn::TimestampMicros { .. } => TYPE_INT64,
+        ArrowColumn::Utf8 { .. } => TYPE_BYTE_ARRAY,
+    }
+}
+
+fn usize_to_i64(value: usize) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+#[derive(Clone, Copy)]
+enum CompactType {
+    BoolTrue = 1,
+    BoolFalse = 2,
+    I32 = 5,
+    I64 = 6,
+    Binary = 8,
+    List = 9,
+    Struct = 12,
+}
+
+/// Thrift compact-protocol writer for the handful of shapes Parquet metadata needs.
+///
+/// Field ids are delta-encoded against the previous field of the same struct, so the
+/// writer keeps the last id of every struct it is nested in.
+#[derive(Default)]
+struct CompactWriter {
+    buf: Vec<u8>,
+    last_id: i16,
+    outer_ids: Vec<i16>,
+}
+
+impl CompactWriter {
+    fn field(&mut self, id: i16, kind: CompactType) {
+        if let Ok(delta @ 1..=15) = u8::try_from(id - self.last_id) {
+            self.buf.push((delta << 4) | kind as u8);
+        } else {
+            self.buf.push(kind as u8);
+            self.zigzag(i64::from(id));
+        }
+        self.last_id = id;
+    }
+
+    fn zigzag(&mut self, value: i64) {
+        #[allow(clippy::cast_sign_loss)]
+        let encoded = ((value << 1) ^ (value >> 63)) as u64;
+        write_varint(&mut self.buf, encoded);
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, CompactType::I32);
+        self.zigzag(i64::from(value));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, CompactType::I64);
+        self.zigzag(value);
+    }
+
+    fn bool(&mut self, id: i16, value: bool) {
+        let kind = if value {
+            CompactType::BoolTrue
+        } else {
+            CompactType::BoolFalse
+        };
+        self.field(id, kind);
+    }
+
+    fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field(id, CompactType::Binary);
+        self.element_binary(value);
+    }
+
+    fn list(&mut self, id: i16, element: CompactType, len: usize) {
+        self.field(id, CompactType::List);
+        if let Ok(short @ 0..15) = u8::try_from(len) {
+            self.buf.push((short << 4) | element as u8);
+        } else {
+            self.buf.push(0xF0 | element as u8);
+            write_varint(&mut self.buf, len as u64);
+        }
+    }
+
+    fn element_i32(&mut self, value: i32) {
+        self.zigzag(i64::from(value));
+    }
+
+    fn element_binary(&mut self, value: &[u8]) {
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    fn begin_struct(&mut self, id: i16) {
+        self.field(id, CompactType::Struct);
+        self.begin_element();
+    }
+
+    /// Start a struct that is a list element (no field header).
+    fn begin_element(&mut self) {
+        self.outer_ids.push(self.last_id);
+        self.last_id = 0;
+    }
+
+    fn end_struct(&mut self) {
+        self.buf.push(0);
+        self.last_id = self.outer_ids.pop().unwrap_or_default();
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.buf.push(0);
+        self.buf
+    }
+}
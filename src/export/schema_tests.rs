@@ -0,0 +1,353 @@
+//! Validates JSON exports against the shipped `assets/schema/export.schema.json`.
+//!
+//! Only the keywords the schema uses are implemented, which keeps the check dependency-free;
+//! an unknown keyword fails loudly so the schema cannot silently outgrow the validator.
+
+use super::tests::temp_export_path;
+use super::*;
+use crate::types::AxisMapping;
+use chrono::NaiveDateTime;
+
+const SCHEMA: &str = include_str!("../../assets/schema/export.schema.json");
+
+fn schema() -> Value {
+    serde_json::from_str(SCHEMA).expect("export schema is valid JSON")
+}
+
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .map_or(schema, |reference| {
+            let pointer = reference
+                .strip_prefix('#')
+                .expect("only local $ref is supported");
+            root.pointer(pointer)
+                .unwrap_or_else(|| panic!("unresolved $ref {reference}"))
+        })
+}
+
+fn type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        other => panic!("unsupported type {other}"),
+    }
+}
+
+fn validate(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = resolve(root, schema);
+    let Some(keywords) = schema.as_object() else {
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{path}: not allowed"));
+        }
+        return;
+    };
+    for (keyword, rule) in keywords {
+        match keyword.as_str() {
+            // Annotations, plus `then`/`else` which are applied by `if`.
+            "$schema" | "$id" | "$defs" | "$ref" | "title" | "description" | "then" | "else" => {}
+            "type" => {
+                let allowed: Vec<&str> = match rule {
+                    Value::String(name) => vec![name.as_str()],
+                    Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                    other => panic!("bad type rule {other}"),
+                };
+                if !allowed.iter().any(|name| type_matches(name, value)) {
+                    errors.push(format!("{path}: expected {allowed:?}, got {value}"));
+                }
+            }
+            "const" => {
+                if value != rule {
+                    errors.push(format!("{path}: expected {rule}, got {value}"));
+                }
+            }
+            "enum" => {
+                let options = rule.as_array().expect("enum is an array");
+                if !options.contains(value) {
+                    errors.push(format!("{path}: {value} not in {rule}"));
+                }
+            }
+            "required" => {
+                if let Some(obj) = value.as_object() {
+                    for key in rule.as_array().expect("required is an array") {
+                        let key = key.as_str().expect("required key");
+                        if !obj.contains_key(key) {
+                            errors.push(format!("{path}: missing {key}"));
+                        }
+                    }
+                }
+            }
+            "minProperties" => {
+                let min = rule.as_u64().expect("minProperties is an integer");
+                if let Some(obj) = value.as_object()
+                    && (obj.len() as u64) < min
+                {
+                    errors.push(format!("{path}: fewer than {min} properties"));
+                }
+            }
+            "properties" | "additionalProperties" => {
+                let Some(obj) = value.as_object() else {
+                    continue;
+                };
+                let declared = keywords.get("properties").and_then(Value::as_object);
+                for (key, item) in obj {
+                    let item_path = format!("{path}/{key}");
+                    match declared.and_then(|props| props.get(key)) {
+                        Some(sub) if keyword == "properties" => {
+                            validate(root, sub, item, &item_path, errors);
+                        }
+                        None if keyword == "additionalProperties" => {
+                            validate(root, rule, item, &item_path, errors);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "items" => {
+                if let Some(items) = value.as_array() {
+                    for (idx, item) in items.iter().enumerate() {
+                        validate(root, rule, item, &format!("{path}/{idx}"), errors);
+                    }
+                }
+            }
+            "allOf" => {
+                for sub in rule.as_array().expect("allOf is an array") {
+                    validate(root, sub, value, path, errors);
+                }
+            }
+            "not" => {
+                let mut inner = Vec::new();
+                validate(root, rule, value, path, &mut inner);
+                if inner.is_empty() {
+                    errors.push(format!("{path}: matches a forbidden schema"));
+                }
+            }
+            "if" => {
+                let mut probe = Vec::new();
+                validate(root, rule, value, path, &mut probe);
+                let branch = if probe.is_empty() { "then" } else { "else" };
+                if let Some(sub) = keywords.get(branch) {
+                    validate(root, sub, value, path, errors);
+                }
+            }
+            other => panic!("schema keyword {other} is not covered by the test validator"),
+        }
+    }
+}
+
+fn schema_errors(doc: &Value) -> Vec<String> {
+    let root = schema();
+    let mut errors = Vec::new();
+    validate(&root, &root, doc, "", &mut errors);
+    errors
+}
+
+fn export_json(payload: &ExportPayload) -> Value {
+    let path = temp_export_path("schema_test", "json");
+    export_to_json(&path, payload).expect("JSON export failed");
+    let text = std::fs::read_to_string(&path).expect("failed to read JSON output");
+    let _ = std::fs::remove_file(&path);
+    serde_json::from_str(&text).expect("export is valid JSON")
+}
+
+fn cartesian_payload() -> ExportPayload {
+    ExportPayload {
+        points: vec![XYPoint { x: 0.5, y: 1.0 }, XYPoint { x: 1.5, y: -2.0 }],
+        x_unit: AxisUnit::Float,
+        y_unit: AxisUnit::Float,
+        x_label: "x".to_string(),
+        y_label: "y".to_string(),
+        title: None,
+        calibration_report: None,
+        coord_system: CoordSystem::Cartesian,
+        angle_unit: None,
+        extra_columns: vec![ExportExtraColumn::new("distance", vec![None, Some(1.0)])],
+        text_columns: vec![ExportTextColumn::new(
+            "flag",
+            vec![Some("check".to_string()), None],
+        )],
+        options: ExportOptions::default(),
+    }
+}
+
+fn assert_points_carry_axis_keys(doc: &Value) {
+    let x_label = doc["x_label"].as_str().expect("x_label");
+    let y_label = doc["y_label"].as_str().expect("y_label");
+    for point in doc["points"].as_array().expect("points") {
+        assert!(
+            point.get(x_label).is_some(),
+            "point without {x_label}: {point}"
+        );
+        assert!(
+            point.get(y_label).is_some(),
+            "point without {y_label}: {point}"
+        );
+    }
+}
+
+#[test]
+fn schema_version_constant_matches_shipped_schema() {
+    let root = schema();
+    assert_eq!(
+        root.pointer("/properties/schema_version/const"),
+        Some(&Value::from(EXPORT_SCHEMA_VERSION))
+    );
+}
+
+#[test]
+fn cartesian_export_matches_schema() {
+    let doc = export_json(&cartesian_payload());
+    assert_eq!(schema_errors(&doc), Vec::<String>::new());
+    assert_eq!(doc["schema_version"], Value::from(EXPORT_SCHEMA_VERSION));
+    assert_points_carry_axis_keys(&doc);
+}
+
+#[test]
+fn export_with_calibration_report_matches_schema() {
+    let mapping = |p2, v2, scale| {
+        AxisMapping::try_new(
+            egui::pos2(0.0, 100.0),
+            p2,
+            AxisValue::Float(1.0),
+            AxisValue::Float(v2),
+            scale,
+            AxisUnit::Float,
+        )
+        .expect("mapping")
+    };
+    let x = mapping(egui::pos2(200.0, 100.0), 11.0, ScaleKind::Linear);
+    let y = mapping(egui::pos2(0.0, 0.0), 100.0, ScaleKind::Log10);
+    let payload = ExportPayload {
+        calibration_report: Some(CalibrationReport::new(
+            &x,
+            &y,
+            [Some(ScaleKind::Linear), Some(ScaleKind::Linear)],
+        )),
+        ..cartesian_payload()
+    };
+    let doc = export_json(&payload);
+    assert_eq!(schema_errors(&doc), Vec::<String>::new());
+    assert_eq!(doc["calibration"]["y_grid_scale"], Value::from("linear"));
+}
+
+#[test]
+fn polar_export_matches_schema() {
+    let payload = ExportPayload {
+        x_label: "angle".to_string(),
+        y_label: "radius".to_string(),
+        coord_system: CoordSystem::Polar,
+        angle_unit: Some(AngleUnit::Degrees),
+        ..cartesian_payload()
+    };
+    let doc = export_json(&payload);
+    assert_eq!(schema_errors(&doc), Vec::<String>::new());
+    assert_points_carry_axis_keys(&doc);
+}
+
+#[test]
+fn smith_export_matches_schema() {
+    let payload = ExportPayload {
+        x_label: "r".to_string(),
+        y_label: "x".to_string(),
+        coord_system: CoordSystem::Smith,
+        ..cartesian_payload()
+    };
+    let doc = export_json(&payload);
+    assert_eq!(schema_errors(&doc), Vec::<String>::new());
+    assert_eq!(doc["coord_system"], Value::from("smith"));
+    assert_points_carry_axis_keys(&doc);
+}
+
+#[test]
+fn datetime_export_matches_schema_and_documented_format() {
+    let payload = ExportPayload {
+        points: vec![
+            XYPoint {
+                x: 1_700_000_000.0,
+                y: 1.0,
+            },
+            XYPoint {
+                x: 1_700_000_000.25,
+                y: 2.0,
+            },
+        ],
+        x_unit: AxisUnit::DateTime,
+        x_label: "time".to_string(),
+        ..cartesian_payload()
+    };
+    let doc = export_json(&payload);
+    assert_eq!(schema_errors(&doc), Vec::<String>::new());
+    for point in doc["points"].as_array().expect("points") {
+        let text = point["time"].as_str().expect("datetime is a string");
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+            .unwrap_or_else(|err| panic!("{text} does not follow the schema format: {err}"));
+    }
+}
+
+#[test]
+fn multi_curve_export_matches_schema() {
+    let curves = vec![
+        ("Upper".to_string(), cartesian_payload()),
+        ("Lower".to_string(), cartesian_payload()),
+    ];
+    let path = temp_export_path("schema_curves_test", "json");
+    export_curves_to_json(&path, curves).expect("JSON export failed");
+    let text = std::fs::read_to_string(&path).expect("failed to read JSON output");
+    let _ = std::fs::remove_file(&path);
+    let doc: Value = serde_json::from_str(&text).expect("export is valid JSON");
+
+    assert_eq!(schema_errors(&doc), Vec::<String>::new());
+    assert_eq!(doc["curves"], serde_json::json!(["Upper", "Lower"]));
+    let points = doc["points"].as_array().expect("points");
+    assert_eq!(points.len(), 4);
+    assert_eq!(points[3][CURVE_COLUMN_HEADER], Value::from("Lower"));
+}
+
+#[test]
+fn schema_rejects_contract_violations() {
+    let valid = export_json(&cartesian_payload());
+    let mutate = |f: &dyn Fn(&mut Map<String, Value>)| {
+        let mut doc = valid.clone();
+        f(doc.as_object_mut().expect("object root"));
+        doc
+    };
+
+    let cases = [
+        mutate(&|root| {
+            root.remove("schema_version");
+        }),
+        mutate(&|root| {
+            root.insert("schema_version".into(), Value::from(999));
+        }),
+        mutate(&|root| {
+            root.insert("unexpected".into(), Value::Null);
+        }),
+        mutate(&|root| {
+            root.insert("coord_system".into(), Value::from("polar"));
+        }),
+        mutate(&|root| {
+            root.insert("angle_unit".into(), Value::from("deg"));
+        }),
+        mutate(&|root| {
+            root.insert("x_unit".into(), Value::from("seconds"));
+        }),
+        mutate(&|root| {
+            root["points"][0]["x"] = Value::Bool(true);
+        }),
+        mutate(&|root| {
+            root.insert(
+                "calibration".into(),
+                serde_json::json!({ "axis_angle_deg": 90.0, "x_scale": "log", "y_scale": "linear" }),
+            );
+        }),
+    ];
+    for doc in &cases {
+        assert!(!schema_errors(doc).is_empty(), "schema accepted {doc}");
+    }
+}
@@ -0,0 +1,217 @@
+//! SQL script writer: `CREATE TABLE` plus batched `INSERT` statements.
+//!
+//! The dialect sticks to what `SQLite`, `PostgreSQL` and `DuckDB` all accept, so the script can be
+//! piped into `sqlite3 lab.db < curve.sql` or any other client without edits.
+//!
+//! Every table has a fixed layout: extra and text columns go to `curcat_values` as one row per
+//! cell, so exports with different columns can share one database.
+
+use super::{
+    ColumnFormats, ExportPayload, axis_value_from_scalar_for_export, metadata_pairs,
+    validate_extra_columns,
+};
+use crate::types::{AxisUnit, AxisValue, FloatFormat};
+use std::io::{BufWriter, Write};
+
+const SQL_POINTS_TABLE: &str = "curcat_points";
+const SQL_VALUES_TABLE: &str = "curcat_values";
+const SQL_METADATA_TABLE: &str = "curcat_metadata";
+/// `SQLite` rejects compound `VALUES` lists longer than 500 rows by default.
+const SQL_ROWS_PER_INSERT: usize = 500;
+
+/// Write the payload as an SQL script; the dataset is named after the file stem.
+pub fn export_to_sql(path: &std::path::Path, payload: &ExportPayload) -> anyhow::Result<()> {
+    let dataset = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("Data");
+    export_datasets_to_sql(path, &[(dataset, payload)])
+}
+
+/// Write every dataset into the shared tables, tagged by the `dataset` column.
+///
+/// Tables are created with `IF NOT EXISTS` and never change shape, so repeated exports append
+/// to the same database. An axis column is `TEXT` when a dataset has a date/time axis there,
+/// `REAL` otherwise; appending needs the same axis units as the export that created the table.
+pub fn export_datasets_to_sql(
+    path: &std::path::Path,
+    datasets: &[(&str, &ExportPayload)],
+) -> anyhow::Result<()> {
+    for (_, payload) in datasets {
+        if let Err(err) = validate_extra_columns(payload) {
+            anyhow::bail!(err);
+        }
+    }
+    let text_axes = [
+        datasets.iter().any(|(_, p)| p.x_unit == AxisUnit::DateTime),
+        datasets.iter().any(|(_, p)| p.y_unit == AxisUnit::DateTime),
+    ];
+    // A failed write must not leave half a script that a client would partly apply.
+    crate::project::write_atomic_with(path, |file| {
+        let mut out = BufWriter::new(file);
+        writeln!(out, "-- Generated by curcat {}.", env!("CARGO_PKG_VERSION"))?;
+        write_schema(&mut out, text_axes)?;
+        writeln!(out, "BEGIN TRANSACTION;")?;
+        for (name, payload) in datasets {
+            write_metadata(&mut out, name, payload)?;
+            write_points(&mut out, name, payload, text_axes)?;
+            write_values(&mut out, name, payload)?;
+        }
+        writeln!(out, "COMMIT;")?;
+        out.flush()?;
+        Ok(())
+    })
+}
+
+fn write_schema(out: &mut impl Write, text_axes: [bool; 2]) -> std::io::Result<()> {
+    let axis_type = |text: bool| if text { "TEXT" } else { "REAL" };
+    writeln!(
+        out,
+        "CREATE TABLE IF NOT EXISTS {SQL_POINTS_TABLE} (\n    dataset TEXT NOT NULL,\n    point INTEGER NOT NULL,\n    x {} NOT NULL,\n    y {} NOT NULL\n);",
+        axis_type(text_axes[0]),
+        axis_type(text_axes[1])
+    )?;
+    writeln!(
+        out,
+        "CREATE TABLE IF NOT EXISTS {SQL_VALUES_TABLE} (\n    dataset TEXT NOT NULL,\n    point INTEGER NOT NULL,\n    name TEXT NOT NULL,\n    value REAL,\n    text TEXT\n);"
+    )?;
+    writeln!(
+        out,
+        "CREATE TABLE IF NOT EXISTS {SQL_METADATA_TABLE} (\n    dataset TEXT NOT NULL,\n    key TEXT NOT NULL,\n    value TEXT NOT NULL\n);"
+    )
+}
+
+/// Write `rows` as `INSERT INTO {target} VALUES` statements of at most
+/// [`SQL_ROWS_PER_INSERT`] rows; no rows write nothing.
+fn write_inserts(
+    out: &mut impl Write,
+    target: &str,
+    rows: impl Iterator<Item = anyhow::Result<String>>,
+) -> anyhow::Result<()> {
+    let mut rows = rows.peekable();
+    let mut chunk = Vec::with_capacity(SQL_ROWS_PER_INSERT);
+    while rows.peek().is_some() {
+        chunk.clear();
+        for row in rows.by_ref().take(SQL_ROWS_PER_INSERT) {
+            chunk.push(row?);
+        }
+        writeln!(out, "INSERT INTO {target} VALUES")?;
+        writeln!(out, "    {};", chunk.join(",\n    "))?;
+    }
+    Ok(())
+}
+
+fn write_metadata(
+    out: &mut impl Write,
+    dataset: &str,
+    payload: &ExportPayload,
+) -> anyhow::Result<()> {
+    let dataset = quote_literal(dataset);
+    write_inserts(
+        out,
+        &format!("{SQL_METADATA_TABLE} (dataset, key, value)"),
+        metadata_pairs(payload).into_iter().map(|(key, value)| {
+            Ok(format!(
+                "({dataset}, {}, {})",
+                quote_literal(key),
+                quote_literal(&value)
+            ))
+        }),
+    )
+}
+
+fn write_points(
+    out: &mut impl Write,
+    dataset: &str,
+    payload: &ExportPayload,
+    text_axes: [bool; 2],
+) -> anyhow::Result<()> {
+    let formats = ColumnFormats::resolve(payload);
+    let dataset = quote_literal(dataset);
+    write_inserts(
+        out,
+        &format!("{SQL_POINTS_TABLE} (dataset, point, x, y)"),
+        payload.points.iter().enumerate().map(|(row_idx, p)| {
+            let x = axis_literal(payload.x_unit, p.x, "x", formats.x, text_axes[0])?;
+            let y = axis_literal(payload.y_unit, p.y, "y", formats.y, text_axes[1])?;
+            Ok(format!("({dataset}, {row_idx}, {x}, {y})"))
+        }),
+    )
+}
+
+/// One `curcat_values` row per present extra or text cell.
+fn write_values(
+    out: &mut impl Write,
+    dataset: &str,
+    payload: &ExportPayload,
+) -> anyhow::Result<()> {
+    let formats = ColumnFormats::resolve(payload);
+    let dataset = &quote_literal(dataset);
+    let numbers = payload
+        .extra_columns
+        .iter()
+        .zip(&formats.extras)
+        .flat_map(|(col, format)| {
+            let name = quote_literal(&col.header);
+            col.values
+                .iter()
+                .enumerate()
+                .filter_map(move |(row_idx, value)| {
+                    let value = value.filter(|v| v.is_finite())?;
+                    Some(format!(
+                        "({dataset}, {row_idx}, {name}, {}, NULL)",
+                        format.format(value)
+                    ))
+                })
+                .collect::<Vec<_>>()
+        });
+    let texts = payload.text_columns.iter().flat_map(|col| {
+        let name = quote_literal(&col.header);
+        col.values
+            .iter()
+            .enumerate()
+            .filter_map(move |(row_idx, value)| {
+                let text = value.as_deref()?;
+                Some(format!(
+                    "({dataset}, {row_idx}, {name}, NULL, {})",
+                    quote_literal(text)
+                ))
+            })
+            .collect::<Vec<_>>()
+    });
+    write_inserts(
+        out,
+        &format!("{SQL_VALUES_TABLE} (dataset, point, name, value, text)"),
+        numbers.chain(texts).map(Ok),
+    )
+}
+
+/// `scalar` as an SQL literal; numbers are quoted when the column is `TEXT`.
+fn axis_literal(
+    unit: AxisUnit,
+    scalar: f64,
+    axis: &str,
+    format: FloatFormat,
+    text_column: bool,
+) -> anyhow::Result<String> {
+    match axis_value_from_scalar_for_export(unit, scalar, axis)? {
+        av @ AxisValue::Float(value) if value.is_finite() => {
+            let number = av.format_with(format);
+            Ok(if text_column {
+                quote_literal(&number)
+            } else {
+                number
+            })
+        }
+        AxisValue::Float(value) => {
+            anyhow::bail!("SQL export cannot represent non-finite {axis} value {value}.")
+        }
+        // SQLite's date functions understand this layout directly.
+        AxisValue::DateTime(naive) => Ok(format!("'{}'", naive.format("%Y-%m-%d %H:%M:%S%.3f"))),
+    }
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
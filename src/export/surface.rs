@@ -0,0 +1,216 @@
+//! Curve family surface: curves tagged with a parameter value resampled onto one
+//! (x, parameter) grid, written as a CSV matrix or a `NetCDF` classic file.
+//!
+//! Each curve is interpolated along x within its own range; cells a curve does not reach are
+//! then interpolated across the parameter from the nearest curves on either side. The `NetCDF`
+//! file has two dimensions and three `double` variables.
+
+use super::netcdf::{Attribute, EPOCH_UNITS, Values, Variable, write_classic};
+use super::{ExportOptions, PARAMETER_COLUMN_HEADER};
+use crate::interp::{InterpAlgorithm, XYPoint, interpolate_within};
+use crate::types::{AxisUnit, AxisValue};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceFormat {
+    /// Parameters down the first column, x along the header row.
+    CsvMatrix,
+    NetCdf,
+}
+
+impl SurfaceFormat {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::CsvMatrix => "CSV matrix",
+            Self::NetCdf => "NetCDF",
+        }
+    }
+
+    pub const fn default_filename(self) -> &'static str {
+        match self {
+            Self::CsvMatrix => "surface.csv",
+            Self::NetCdf => "surface.nc",
+        }
+    }
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::CsvMatrix => "csv",
+            Self::NetCdf => "nc",
+        }
+    }
+
+    pub fn export(self, path: &std::path::Path, surface: &Surface) -> anyhow::Result<()> {
+        match self {
+            Self::CsvMatrix => export_surface_to_csv(path, surface),
+            Self::NetCdf => export_surface_to_netcdf(path, surface),
+        }
+    }
+}
+
+/// y sampled over an (x, parameter) grid.
+#[derive(Debug, Clone)]
+pub struct Surface {
+    /// Sample positions along x, ascending.
+    pub x: Vec<f64>,
+    /// Curve parameter values, ascending; one grid row each.
+    pub parameters: Vec<f64>,
+    /// y by parameter row, then x column; `None` where no curve brackets the cell.
+    pub values: Vec<Vec<Option<f64>>>,
+    pub x_unit: AxisUnit,
+    pub x_label: String,
+    pub y_label: String,
+    pub parameter_label: String,
+    pub title: Option<String>,
+    pub options: ExportOptions,
+}
+
+impl Surface {
+    /// Grid `family`, parameter values with points sorted by x, over `samples` x positions
+    /// spanning all of its curves.
+    ///
+    /// Curves with fewer than two points are left out. Labels start as `x`, `y` and
+    /// [`PARAMETER_COLUMN_HEADER`].
+    pub fn assemble(
+        mut family: Vec<(f64, Vec<XYPoint>)>,
+        samples: usize,
+        algo: InterpAlgorithm,
+    ) -> anyhow::Result<Self> {
+        family.retain(|(parameter, points)| parameter.is_finite() && points.len() >= 2);
+        if family.len() < 2 {
+            anyhow::bail!("A surface needs two or more curves with a parameter value and points.");
+        }
+        family.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some(pair) = family
+            .windows(2)
+            .find(|pair| pair[0].0.total_cmp(&pair[1].0) == Ordering::Equal)
+        {
+            anyhow::bail!("Two curves share the parameter value {}.", pair[0].0);
+        }
+        let lo = family
+            .iter()
+            .map(|(_, points)| points[0].x)
+            .fold(f64::INFINITY, f64::min);
+        let hi = family
+            .iter()
+            .map(|(_, points)| points[points.len() - 1].x)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let last = samples.max(2) - 1;
+        #[allow(clippy::cast_precision_loss)]
+        let x: Vec<f64> = (0..=last)
+            .map(|i| (hi - lo).mul_add(i as f64 / last as f64, lo))
+            .collect();
+        let parameters: Vec<f64> = family.iter().map(|(parameter, _)| *parameter).collect();
+        let mut values: Vec<_> = family
+            .iter()
+            .map(|(_, points)| interpolate_within(points, &x, algo))
+            .collect();
+        fill_across_parameters(&parameters, &mut values);
+        Ok(Self {
+            x,
+            parameters,
+            values,
+            x_unit: AxisUnit::Float,
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            parameter_label: PARAMETER_COLUMN_HEADER.to_string(),
+            title: None,
+            options: ExportOptions::default(),
+        })
+    }
+}
+
+/// Fill each empty cell linearly between the nearest rows below and above it that hold a
+/// value in the same column.
+fn fill_across_parameters(parameters: &[f64], values: &mut [Vec<Option<f64>>]) {
+    let columns = values.first().map_or(0, Vec::len);
+    for col in 0..columns {
+        let known: Vec<(f64, f64)> = parameters
+            .iter()
+            .zip(values.iter())
+            .filter_map(|(p, row)| Some((*p, row[col]?)))
+            .collect();
+        for (&p, row) in parameters.iter().zip(values.iter_mut()) {
+            if row[col].is_some() {
+                continue;
+            }
+            let below = known.iter().rev().find(|(q, _)| *q < p);
+            let above = known.iter().find(|(q, _)| *q > p);
+            if let (Some(&(p0, y0)), Some(&(p1, y1))) = (below, above) {
+                row[col] = Some((y1 - y0).mul_add((p - p0) / (p1 - p0), y0));
+            }
+        }
+    }
+}
+
+/// Write the grid with parameters down the first column and x along the header row.
+pub fn export_surface_to_csv(path: &std::path::Path, surface: &Surface) -> anyhow::Result<()> {
+    let options = surface.options;
+    let x_format = options.float_format(surface.x.iter().copied());
+    let y_format = options.float_format(surface.values.iter().flatten().flatten().copied());
+    let parameter_format = options.float_format(surface.parameters.iter().copied());
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(options.csv_delimiter)
+        .from_path(path)?;
+    let corner = format!("{} \\ {}", surface.parameter_label, surface.x_label);
+    wtr.write_record(std::iter::once(corner).chain(surface.x.iter().map(|&x| {
+        AxisValue::from_scalar_seconds(surface.x_unit, x)
+            .map_or_else(String::new, |value| value.format_with(x_format))
+    })))?;
+    for (&parameter, row) in surface.parameters.iter().zip(&surface.values) {
+        let cells = row
+            .iter()
+            .map(|y| y.map_or_else(String::new, |y| y_format.format_trimmed(y)));
+        wtr.write_record(std::iter::once(parameter_format.format_trimmed(parameter)).chain(cells))?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Write the grid as a `NetCDF` classic file with variables `x`, `parameter` and
+/// `y(parameter, x)`; empty cells hold the `_FillValue` NaN.
+pub fn export_surface_to_netcdf(path: &std::path::Path, surface: &Surface) -> anyhow::Result<()> {
+    let mut x_attributes = vec![Attribute::Text("long_name", &surface.x_label)];
+    if surface.x_unit == AxisUnit::DateTime {
+        x_attributes.push(Attribute::Text("units", EPOCH_UNITS));
+    }
+    let variables = [
+        Variable {
+            name: "x".to_string(),
+            dims: vec![0],
+            attributes: x_attributes,
+            values: Values::Double(surface.x.clone()),
+        },
+        Variable {
+            name: "parameter".to_string(),
+            dims: vec![1],
+            attributes: vec![Attribute::Text("long_name", &surface.parameter_label)],
+            values: Values::Double(surface.parameters.clone()),
+        },
+        Variable {
+            name: "y".to_string(),
+            dims: vec![1, 0],
+            attributes: vec![
+                Attribute::Text("long_name", &surface.y_label),
+                Attribute::Double("_FillValue", f64::NAN),
+            ],
+            values: Values::Double(
+                surface
+                    .values
+                    .iter()
+                    .flatten()
+                    .map(|y| y.unwrap_or(f64::NAN))
+                    .collect(),
+            ),
+        },
+    ];
+    let mut global = vec![Attribute::Text("source", "curcat")];
+    if let Some(title) = &surface.title {
+        global.push(Attribute::Text("title", title));
+    }
+    let dims = [
+        ("x".to_string(), surface.x.len()),
+        ("parameter".to_string(), surface.parameters.len()),
+    ];
+    write_classic(path, &dims, &global, &variables)
+}
@@ -0,0 +1,517 @@
+//! Least-squares model fitting for picked points.
+//!
+//! Polynomials are solved directly (Householder QR); exponential, power-law and
+//! logistic models start from a linearized fit and are refined with
+//! Levenberg–Marquardt on the original residuals.
+
+use crate::interp::XYPoint;
+use serde::{Deserialize, Serialize};
+
+/// Highest polynomial degree offered in the UI.
+pub const MAX_POLY_DEGREE: usize = 8;
+
+const LM_MAX_ITERATIONS: usize = 200;
+/// Damping increases tried per iteration before giving up on a step.
+const LM_MAX_DAMPING_STEPS: usize = 30;
+const LM_TOLERANCE: f64 = 1.0e-12;
+
+/// Model families available for fitting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FitModel {
+    Polynomial,
+    /// `y = a·e^(b·x)`; needs all y of one sign.
+    Exponential,
+    /// `y = a·x^b`; needs positive x and all y of one sign.
+    PowerLaw,
+    /// `y = L / (1 + e^(−k·(x − x0)))`, saturating at 0 and `L`.
+    Logistic,
+}
+
+impl FitModel {
+    /// Ordered list of models exposed in the UI.
+    pub const ALL: [Self; 4] = [
+        Self::Polynomial,
+        Self::Exponential,
+        Self::PowerLaw,
+        Self::Logistic,
+    ];
+
+    /// Human-readable model equation with named coefficients.
+    pub fn equation(self, degree: usize) -> String {
+        match self {
+            Self::Polynomial => {
+                let mut terms = vec!["c0".to_string()];
+                terms.extend((1..=degree).map(|p| match p {
+                    1 => "c1·x".to_string(),
+                    _ => format!("c{p}·x^{p}"),
+                }));
+                format!("y = {}", terms.join(" + "))
+            }
+            Self::Exponential => "y = a·e^(b·x)".to_string(),
+            Self::PowerLaw => "y = a·x^b".to_string(),
+            Self::Logistic => "y = L / (1 + e^(−k·(x − x0)))".to_string(),
+        }
+    }
+}
+
+/// A fitted model with its coefficients and goodness of fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveFit {
+    pub model: FitModel,
+    /// Polynomial coefficients from `c0` up; `[a, b]` or `[L, k, x0]` for the other models.
+    pub coefficients: Vec<f64>,
+    /// Coefficient of determination on the fitted points.
+    pub r_squared: f64,
+}
+
+impl CurveFit {
+    pub fn eval(&self, x: f64) -> f64 {
+        eval_model(self.model, &self.coefficients, x)
+    }
+
+    /// Coefficient names in the order of [`Self::coefficients`].
+    pub fn coefficient_names(&self) -> Vec<String> {
+        match self.model {
+            FitModel::Polynomial => (0..self.coefficients.len())
+                .map(|p| format!("c{p}"))
+                .collect(),
+            FitModel::Exponential | FitModel::PowerLaw => vec!["a".into(), "b".into()],
+            FitModel::Logistic => vec!["L".into(), "k".into(), "x0".into()],
+        }
+    }
+
+    /// `count` evenly spaced samples of the model over `[x_min, x_max]`.
+    pub fn sample(&self, x_min: f64, x_max: f64, count: usize) -> Vec<XYPoint> {
+        if count == 0 || !x_min.is_finite() || !x_max.is_finite() {
+            return Vec::new();
+        }
+        if count == 1 {
+            return vec![XYPoint {
+                x: x_min,
+                y: self.eval(x_min),
+            }];
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let step = (x_max - x_min) / (count - 1) as f64;
+        (0..count)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = if i + 1 == count {
+                    x_max
+                } else {
+                    step.mul_add(i as f64, x_min)
+                };
+                XYPoint { x, y: self.eval(x) }
+            })
+            .collect()
+    }
+}
+
+/// Fit `model` to `points` by least squares; `degree` applies to polynomials only.
+///
+/// Returns `None` when there are too few points for the model, the data is
+/// outside the model's domain, or the solve is singular.
+pub fn fit_curve(points: &[XYPoint], model: FitModel, degree: usize) -> Option<CurveFit> {
+    let points: Vec<XYPoint> = points
+        .iter()
+        .copied()
+        .filter(|p| p.x.is_finite() && p.y.is_finite())
+        .collect();
+    let coefficients = match model {
+        FitModel::Polynomial => fit_polynomial(&points, degree.clamp(1, MAX_POLY_DEGREE))?,
+        FitModel::Exponential => {
+            let initial = initial_exponential(&points)?;
+            refine(&points, model, initial)
+        }
+        FitModel::PowerLaw => {
+            let initial = initial_power_law(&points)?;
+            refine(&points, model, initial)
+        }
+        FitModel::Logistic => {
+            let initial = initial_logistic(&points)?;
+            refine(&points, model, initial)
+        }
+    };
+    if coefficients.iter().any(|c| !c.is_finite()) {
+        return None;
+    }
+    let r_squared = r_squared(&points, model, &coefficients);
+    Some(CurveFit {
+        model,
+        coefficients,
+        r_squared,
+    })
+}
+
+fn eval_model(model: FitModel, c: &[f64], x: f64) -> f64 {
+    match model {
+        FitModel::Polynomial => c.iter().rev().fold(0.0, |acc, &coef| acc.mul_add(x, coef)),
+        FitModel::Exponential => c[0] * (c[1] * x).exp(),
+        FitModel::PowerLaw => c[0] * x.powf(c[1]),
+        FitModel::Logistic => c[0] / (1.0 + (-c[1] * (x - c[2])).exp()),
+    }
+}
+
+/// Partial derivatives of the model with respect to each coefficient at `x`.
+fn model_gradient(model: FitModel, c: &[f64], x: f64, out: &mut [f64]) {
+    match model {
+        FitModel::Polynomial => {
+            let mut power = 1.0;
+            for slot in out.iter_mut() {
+                *slot = power;
+                power *= x;
+            }
+        }
+        FitModel::Exponential => {
+            let e = (c[1] * x).exp();
+            out[0] = e;
+            out[1] = c[0] * x * e;
+        }
+        FitModel::PowerLaw => {
+            let p = x.powf(c[1]);
+            out[0] = p;
+            out[1] = c[0] * p * x.ln();
+        }
+        FitModel::Logistic => {
+            let s = 1.0 / (1.0 + (-c[1] * (x - c[2])).exp());
+            let ds = c[0] * s * (1.0 - s);
+            out[0] = s;
+            out[1] = ds * (x - c[2]);
+            out[2] = -ds * c[1];
+        }
+    }
+}
+
+fn sum_squared_residuals(points: &[XYPoint], model: FitModel, c: &[f64]) -> f64 {
+    points
+        .iter()
+        .map(|p| {
+            let r = p.y - eval_model(model, c, p.x);
+            r * r
+        })
+        .sum()
+}
+
+fn r_squared(points: &[XYPoint], model: FitModel, c: &[f64]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let mean = points.iter().map(|p| p.y).sum::<f64>() / points.len() as f64;
+    let total: f64 = points.iter().map(|p| (p.y - mean) * (p.y - mean)).sum();
+    let residual = sum_squared_residuals(points, model, c);
+    if total <= f64::MIN_POSITIVE {
+        return if residual <= f64::EPSILON { 1.0 } else { 0.0 };
+    }
+    1.0 - residual / total
+}
+
+fn fit_polynomial(points: &[XYPoint], degree: usize) -> Option<Vec<f64>> {
+    let cols = degree + 1;
+    if points.len() < cols {
+        return None;
+    }
+    let rows: Vec<Vec<f64>> = points
+        .iter()
+        .map(|p| {
+            let mut row = vec![0.0; cols];
+            model_gradient(FitModel::Polynomial, &[], p.x, &mut row);
+            row
+        })
+        .collect();
+    let rhs: Vec<f64> = points.iter().map(|p| p.y).collect();
+    solve_least_squares(rows, rhs)
+}
+
+/// Straight-line fit `y = intercept + slope·x`, as `[intercept, slope]`.
+fn fit_line(xs: &[f64], ys: &[f64]) -> Option<[f64; 2]> {
+    let rows = xs.iter().map(|&x| vec![1.0, x]).collect();
+    let line = solve_least_squares(rows, ys.to_vec())?;
+    Some([line[0], line[1]])
+}
+
+/// Sign shared by every y, or `None` when some y is zero or the signs differ.
+fn common_sign(points: &[XYPoint]) -> Option<f64> {
+    let positive = points.first()?.y.is_sign_positive();
+    points
+        .iter()
+        .all(|p| p.y != 0.0 && p.y.is_sign_positive() == positive)
+        .then_some(if positive { 1.0 } else { -1.0 })
+}
+
+fn initial_exponential(points: &[XYPoint]) -> Option<Vec<f64>> {
+    if points.len() < 2 {
+        return None;
+    }
+    let sign = common_sign(points)?;
+    let xs: Vec<f64> = points.iter().map(|p| p.x).collect();
+    let ys: Vec<f64> = points.iter().map(|p| p.y.abs().ln()).collect();
+    let [intercept, slope] = fit_line(&xs, &ys)?;
+    Some(vec![sign * intercept.exp(), slope])
+}
+
+fn initial_power_law(points: &[XYPoint]) -> Option<Vec<f64>> {
+    if points.len() < 2 || points.iter().any(|p| p.x <= 0.0) {
+        return None;
+    }
+    let sign = common_sign(points)?;
+    let xs: Vec<f64> = points.iter().map(|p| p.x.ln()).collect();
+    let ys: Vec<f64> = points.iter().map(|p| p.y.abs().ln()).collect();
+    let [intercept, slope] = fit_line(&xs, &ys)?;
+    Some(vec![sign * intercept.exp(), slope])
+}
+
+fn initial_logistic(points: &[XYPoint]) -> Option<Vec<f64>> {
+    if points.len() < 3 {
+        return None;
+    }
+    let (lo, hi) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+            (lo.min(p.y), hi.max(p.y))
+        });
+    if hi <= 0.0 || hi - lo <= f64::EPSILON {
+        return None;
+    }
+    let ceiling = 0.05f64.mul_add(hi - lo, hi);
+    // Logit of the points strictly inside (0, L) is linear in x: k·x − k·x0.
+    let (xs, zs): (Vec<f64>, Vec<f64>) = points
+        .iter()
+        .filter(|p| p.y > 0.0)
+        .map(|p| (p.x, (p.y / (ceiling - p.y)).ln()))
+        .unzip();
+    let [intercept, slope] = fit_line(&xs, &zs)?;
+    if slope.abs() <= f64::EPSILON {
+        return None;
+    }
+    Some(vec![ceiling, slope, -intercept / slope])
+}
+
+/// Levenberg–Marquardt refinement; keeps `initial` when no step improves it.
+fn refine(points: &[XYPoint], model: FitModel, initial: Vec<f64>) -> Vec<f64> {
+    let n = initial.len();
+    let mut params = initial;
+    let mut cost = sum_squared_residuals(points, model, &params);
+    let mut lambda = 1.0e-3;
+    let mut grad = vec![0.0; n];
+    for _ in 0..LM_MAX_ITERATIONS {
+        let mut jtj = vec![vec![0.0; n]; n];
+        let mut jtr = vec![0.0; n];
+        for p in points {
+            model_gradient(model, &params, p.x, &mut grad);
+            let r = p.y - eval_model(model, &params, p.x);
+            for i in 0..n {
+                jtr[i] = grad[i].mul_add(r, jtr[i]);
+                for j in 0..n {
+                    jtj[i][j] = grad[i].mul_add(grad[j], jtj[i][j]);
+                }
+            }
+        }
+
+        let mut improved = false;
+        for _ in 0..LM_MAX_DAMPING_STEPS {
+            let mut damped = jtj.clone();
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += lambda * jtj[i][i].max(f64::MIN_POSITIVE);
+            }
+            let Some(step) = solve_square(damped, jtr.clone()) else {
+                lambda *= 10.0;
+                continue;
+            };
+            let candidate: Vec<f64> = params.iter().zip(&step).map(|(p, d)| p + d).collect();
+            let candidate_cost = sum_squared_residuals(points, model, &candidate);
+            if candidate_cost.is_finite() && candidate_cost < cost {
+                let gain = cost - candidate_cost;
+                params = candidate;
+                cost = candidate_cost;
+                lambda = (lambda * 0.1).max(1.0e-12);
+                improved = gain > LM_TOLERANCE * cost.max(f64::MIN_POSITIVE);
+                break;
+            }
+            lambda *= 10.0;
+        }
+        if !improved {
+            break;
+        }
+    }
+    params
+}
+
+/// Least-squares solution of `rows · c = rhs` via Householder QR.
+///
+/// Columns are scaled to unit norm first, so powers of large x (timestamps)
+/// do not swamp the low-order terms.
+fn solve_least_squares(mut rows: Vec<Vec<f64>>, mut rhs: Vec<f64>) -> Option<Vec<f64>> {
+    let m = rows.len();
+    let n = rows.first()?.len();
+    if m < n {
+        return None;
+    }
+    let mut scales = vec![0.0; n];
+    for (j, scale) in scales.iter_mut().enumerate() {
+        *scale = rows.iter().map(|row| row[j] * row[j]).sum::<f64>().sqrt();
+        if *scale <= f64::MIN_POSITIVE || !scale.is_finite() {
+            return None;
+        }
+        for row in &mut rows {
+            row[j] /= *scale;
+        }
+    }
+
+    for k in 0..n {
+        let norm = rows[k..]
+            .iter()
+            .map(|row| row[k] * row[k])
+            .sum::<f64>()
+            .sqrt();
+        if norm <= 1.0e-12 {
+            return None;
+        }
+        let alpha = if rows[k][k] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = rows[k..].iter().map(|row| row[k]).collect();
+        v[0] -= alpha;
+        let v_norm_sq: f64 = v.iter().map(|x| x * x).sum();
+        if v_norm_sq <= f64::MIN_POSITIVE {
+            continue;
+        }
+        for j in k..n {
+            let dot: f64 = v.iter().zip(&rows[k..]).map(|(vi, row)| vi * row[j]).sum();
+            let factor = 2.0 * dot / v_norm_sq;
+            for (vi, row) in v.iter().zip(rows[k..].iter_mut()) {
+                row[j] -= factor * vi;
+            }
+        }
+        let dot: f64 = v.iter().zip(&rhs[k..]).map(|(vi, b)| vi * b).sum();
+        let factor = 2.0 * dot / v_norm_sq;
+        for (vi, b) in v.iter().zip(rhs[k..].iter_mut()) {
+            *b -= factor * vi;
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for k in (0..n).rev() {
+        let tail: f64 = ((k + 1)..n).map(|j| rows[k][j] * solution[j]).sum();
+        solution[k] = (rhs[k] - tail) / rows[k][k];
+    }
+    for (value, scale) in solution.iter_mut().zip(&scales) {
+        *value /= scale;
+    }
+    Some(solution)
+}
+
+/// Solve a small square system by Gaussian elimination with partial pivoting.
+fn solve_square(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() <= f64::MIN_POSITIVE {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (pivot_row, pivot_b) = (a[col].clone(), b[col]);
+        for (row, rhs) in a.iter_mut().zip(b.iter_mut()).skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = factor.mul_add(-pivot, *value);
+            }
+            *rhs = factor.mul_add(-pivot_b, *rhs);
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = ((row + 1)..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sampled(f: impl Fn(f64) -> f64, xs: impl Iterator<Item = f64>) -> Vec<XYPoint> {
+        xs.map(|x| XYPoint { x, y: f(x) }).collect()
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64], tol: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= tol * e.abs().max(1.0),
+                "{actual:?} vs {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn polynomial_recovers_exact_coefficients() {
+        let xs = (0..20).map(|i| f64::from(i).mul_add(0.5, -3.0));
+        let points = sampled(|x| (0.5 * x * x).mul_add(x, 2.0f64.mul_add(-x, 1.0)), xs);
+        let fit = fit_curve(&points, FitModel::Polynomial, 3).expect("fit");
+        assert_close(&fit.coefficients, &[1.0, -2.0, 0.0, 0.5], 1.0e-9);
+        assert!((fit.r_squared - 1.0).abs() < 1.0e-12);
+        assert_eq!(fit.coefficient_names(), ["c0", "c1", "c2", "c3"]);
+        assert!(fit_curve(&points[..3], FitModel::Polynomial, 3).is_none());
+    }
+
+    #[test]
+    fn polynomial_handles_timestamp_scale_x() {
+        let xs = (0..30).map(|i| f64::from(i).mul_add(3600.0, 1.7e9));
+        let points = sampled(|x| 2.0e-6f64.mul_add(x - 1.7e9, 5.0), xs);
+        let fit = fit_curve(&points, FitModel::Polynomial, 1).expect("fit");
+        assert!((fit.eval(1.7e9 + 7200.0) - 2.0e-6f64.mul_add(7200.0, 5.0)).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn nonlinear_models_recover_their_parameters() {
+        let xs = || (1..25).map(|i| f64::from(i) * 0.25);
+        let exp = sampled(|x| 3.0 * (-0.7 * x).exp(), xs());
+        let fit = fit_curve(&exp, FitModel::Exponential, 0).expect("exp");
+        assert_close(&fit.coefficients, &[3.0, -0.7], 1.0e-6);
+
+        let power = sampled(|x| -1.5 * x.powf(1.8), xs());
+        let fit = fit_curve(&power, FitModel::PowerLaw, 0).expect("power");
+        assert_close(&fit.coefficients, &[-1.5, 1.8], 1.0e-6);
+
+        let logistic = sampled(|x| 10.0 / (1.0 + (-2.0 * (x - 3.0)).exp()), xs());
+        let fit = fit_curve(&logistic, FitModel::Logistic, 0).expect("logistic");
+        assert_close(&fit.coefficients, &[10.0, 2.0, 3.0], 1.0e-5);
+        assert!(fit.r_squared > 0.999_999);
+    }
+
+    #[test]
+    fn models_reject_data_outside_their_domain() {
+        let mixed = [
+            XYPoint { x: 1.0, y: 1.0 },
+            XYPoint { x: 2.0, y: -1.0 },
+            XYPoint { x: 3.0, y: 2.0 },
+        ];
+        assert!(fit_curve(&mixed, FitModel::Exponential, 0).is_none());
+        let negative_x = [XYPoint { x: -1.0, y: 1.0 }, XYPoint { x: 2.0, y: 3.0 }];
+        assert!(fit_curve(&negative_x, FitModel::PowerLaw, 0).is_none());
+        assert!(fit_curve(&negative_x, FitModel::Logistic, 0).is_none());
+    }
+
+    #[test]
+    fn noisy_data_scores_below_one() {
+        // Alternating ±3 noise around a straight line.
+        let points: Vec<XYPoint> = (0..40)
+            .map(|i| {
+                let x = f64::from(i);
+                let noise = if i % 2 == 0 { 3.0 } else { -3.0 };
+                XYPoint {
+                    x,
+                    y: 2.0f64.mul_add(x, noise),
+                }
+            })
+            .collect();
+        let fit = fit_curve(&points, FitModel::Polynomial, 1).expect("fit");
+        assert!(
+            fit.r_squared > 0.9 && fit.r_squared < 1.0,
+            "{}",
+            fit.r_squared
+        );
+        let samples = fit.sample(0.0, 39.0, 5);
+        assert_eq!(samples.len(), 5);
+        assert!((samples[4].x - 39.0).abs() < 1.0e-12);
+    }
+}
@@ -1,5 +1,8 @@
-use crate::interp::InterpAlgorithm;
-use crate::snap::{SnapFeatureSource, SnapThresholdKind};
+use crate::app::ErrorBarMode;
+use crate::fit::FitModel;
+use crate::image::StitchMode;
+use crate::interp::{InterpAlgorithm, SampleGrid, SmoothingFilter};
+use crate::snap::{ColorSampleMode, SnapFeatureSource, SnapThresholdKind};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -314,11 +317,496 @@ pub enum TextKey {
     LoadingImage,
     DropHint,
     Version,
+    FlaggedPoints,
+    FlaggedPointsHover,
+    NoFlaggedPoints,
+    SelectFlaggedPointHint,
+    NoCommentsYet,
+    CommentHint,
+    AddComment,
+    Unflag,
+    UnflagHover,
+    Flagged,
+    IncludeFlagAnnotations,
+    IncludeFlagAnnotationsHover,
+    ExportProfile,
+    ExportProfileHover,
+    NoExportProfile,
+    ProfileNameHint,
+    SaveProfile,
+    SaveProfileHover,
+    DeleteProfile,
+    DeleteProfileHover,
+    ExportWithProfile,
+    ExportWithProfileHover,
+    Decimals,
+    DecimalsHover,
+    CsvDelimiter,
+    CsvDelimiterHover,
+    DelimiterComma,
+    DelimiterSemicolon,
+    DelimiterTab,
+    DelimiterPipe,
+    CancelExportHover,
+    ExportOds,
+    ExportArrow,
+    ExportSql,
+    PastePoints,
+    PastePointsHover,
+    AppendPastedPoints,
+    AppendPastedPointsHover,
+    AppendPastedPointsDisabledHover,
+    ClearPastedPoints,
+    ClearPastedPointsHover,
+    RecordCaptureTimes,
+    RecordCaptureTimesHover,
+    IncludeCaptureTimes,
+    IncludeCaptureTimesHover,
+    CalibrationSandbox,
+    CalibrationSandboxHover,
+    CalibrationSandboxIntro,
+    ApplySandbox,
+    ApplySandboxHover,
+    ResetSandbox,
+    ResetSandboxHover,
+    FirstPoint,
+    LastPoint,
+    SandboxCurrent,
+    SandboxWhatIf,
+    SandboxDraftInvalid,
+    SignificantFigures,
+    SignificantFiguresHover,
+    FocusMode,
+    FocusModeHover,
+    UnsavedChangesWindow,
+    UnsavedChangesIntro,
+    UnsavedChangesCannotSave,
+    DiscardChanges,
+    DiscardChangesHover,
+    RestoreSessionWindow,
+    RestoreSessionIntro,
+    ReopenProject,
+    StartEmpty,
+    ProjectSettings,
+    ProjectSettingsHover,
+    KeepStylesInProject,
+    KeepStylesInProjectHover,
+    KeepAutoPlaceInProject,
+    KeepAutoPlaceInProjectHover,
+    KeepExportInProject,
+    KeepExportInProjectHover,
+    ColorSampling,
+    ColorSamplingHover,
+    ClickToUseColor,
+    Curves,
+    ActiveCurveHover,
+    CurveColorSwatchHover,
+    CurveNameHover,
+    CurvePointCountHover,
+    RemoveCurveHover,
+    AddCurve,
+    AddCurveHover,
+    Swatches,
+    NoSwatches,
+    ApplySwatchHover,
+    RemoveSwatchHover,
+    SwatchNameHint,
+    SaveSwatch,
+    SaveSwatchHover,
+    AutoCalibrate,
+    AutoCalibrateHover,
+    ProposedCalibration,
+    ProposedCalibrationHover,
+    AxisNotFound,
+    AcceptProposal,
+    AcceptProposalHover,
+    DiscardProposal,
+    DiscardProposalHover,
+    Affine,
+    CompleteCalibrationBeforeExportAffine,
+    SandboxAffineUnsupported,
+    ReferencePoints,
+    ReferencePointsHover,
+    AddReferencePoint,
+    AddReferencePointHover,
+    RemoveReferencePointHover,
+    PickReferencePointHover,
+    AffineFitError,
+    AffineFitHover,
+    AffineTooFewPoints,
+    AffineCollinearPoints,
+    AffineParallelAxes,
+    AffineEqualValues,
+    AffineInvalidValues,
+    AffineFitMissing,
+    LoadCurveMask,
+    LoadCurveMaskHover,
+    ClearCurveMask,
+    CurveMaskMissing,
+    OpenCurveMaskDialogTitle,
+    // Shown only in `--features segmentation` builds.
+    #[cfg_attr(not(feature = "segmentation"), allow(dead_code))]
+    RunSegmentationModel,
+    #[cfg_attr(not(feature = "segmentation"), allow(dead_code))]
+    RunSegmentationModelHover,
+    #[cfg_attr(not(feature = "segmentation"), allow(dead_code))]
+    OpenSegmentationModelDialogTitle,
+    #[cfg_attr(not(feature = "segmentation"), allow(dead_code))]
+    SegmentationModelFilter,
+    Perspective,
+    PerspectiveAction,
+    PerspectiveTogether,
+    IgnoreTextLabels,
+    IgnoreTextLabelsHover,
+    CheckContinuity,
+    CheckContinuityHover,
+    CurveFitSection,
+    FitModelLabel,
+    FitModelHover,
+    PolynomialDegree,
+    PolynomialDegreeHover,
+    FitNeedsCalibration,
+    FitUnavailable,
+    FittedCurve,
+    FittedCurveHover,
+    IncludeFitColumn,
+    IncludeFitColumnHover,
+    XAxisLooksInverted,
+    YAxisLooksInverted,
+    AxisLooksInvertedHover,
+    SwapAxisValues,
+    SwapAxisValuesHover,
+    ErrorBars,
+    ErrorBarsHover,
+    SwapEndpointsHover,
+    CopyXToY,
+    CopyXToYHover,
+    CopyCalibration,
+    CopyCalibrationHover,
+    PasteCalibration,
+    PasteCalibrationHover,
+    LevelImage,
+    LevelAction,
+    LevelTogether,
+    ResultPlot,
+    ResultPlotHover,
+    ResultPlotNeedsCalibration,
+    ExportParquet,
+    StitchScans,
+    StitchScansHover,
+    StitchScansWindow,
+    StitchScansIntro,
+    StitchAlignment,
+    StitchAlignmentHover,
+    AddScans,
+    StitchAction,
+    StitchActionHover,
+    CurvesFromLegend,
+    CurvesFromLegendHover,
+    SvgImportWindow,
+    SvgImportIntro,
+    SvgIntrinsicSize,
+    SvgRasterScale,
+    SvgRasterSize,
+    ImportSvg,
+    ChartTextWindow,
+    ChartTextMenu,
+    ChartTextMenuHover,
+    ChartTextIntro,
+    ChartTitle,
+    XAxisTitle,
+    YAxisTitle,
+    ChartDescription,
+    ReadChartText,
+    ReadChartTextHover,
+    SuppressGridLines,
+    SuppressGridLinesHover,
+    Preprocess,
+    PreprocessHover,
+    PreprocessWindow,
+    PreprocessIntro,
+    PreprocessEnabled,
+    PreprocessEnabledHover,
+    FlattenBackground,
+    FlattenBackgroundHover,
+    BackgroundCell,
+    Binarization,
+    BinarizationOff,
+    BinarizationGlobal,
+    BinarizationAdaptive,
+    AdaptiveWindow,
+    AdaptiveOffset,
+    AdaptiveOffsetHover,
+    KeepInkColor,
+    KeepInkColorHover,
+    ResetPreprocessing,
+    LoadImageToPreprocess,
+    FileDialogs,
+    FileDialogsHover,
+    FileDialogsAuto,
+    FileDialogsBuiltin,
+    FileDialogsNative,
+    FileDialogsPortal,
+    ExportHistory,
+    ExportHistoryHover,
+    ExportHistoryWindow,
+    ExportHistoryEmpty,
+    ExportedAt,
+    ExportFormat,
+    ExportedFile,
+    ExportedRows,
+    OpenExportedFile,
+    OpenExportFolder,
+    RepeatExport,
+    RepeatExportHover,
+    ClearExportHistory,
+    SecondaryYAxis,
+    SecondaryYAxisCalibrationHover,
+    CurveOnSecondaryYHover,
+    OverwriteBackup,
+    OverwriteBackupHover,
+    OverwriteBackupOff,
+    OverwriteBackupBak,
+    OverwriteBackupTimestamped,
+    InvertedAxis,
+    InvertedAxisHover,
+    ValuesGrow,
+    XAxisMarkedInverted,
+    YAxisMarkedInverted,
+    AxisMarkedInvertedHover,
+    ValidationRules,
+    ValidationRulesHover,
+    ValidationWindow,
+    ValidationIntro,
+    AddValidationRule,
+    RemoveValidationRule,
+    RuleAtLeast,
+    RuleAtMost,
+    RuleStrictlyIncreasing,
+    RuleStrictlyDecreasing,
+    RuleBlocks,
+    RuleWarns,
+    ValidationNotRun,
+    ValidationPassed,
+    ViolationCurve,
+    ViolationRow,
+    ViolationValue,
+    ViolationRule,
+    MoreViolations,
+    BridgeDashGaps,
+    BridgeDashGapsHover,
+    FitCalibrationToReference,
+    FitCalibrationToReferenceHover,
+    FitCalibrationToReferenceDisabledHover,
+    FittedToReference,
+    FittedToReferenceHover,
+    RmsResidual,
+    KeepFit,
+    RevertFit,
+    RevertFitHover,
+    PointList,
+    PointListHint,
+    PixelX,
+    PixelY,
+    MovePointUp,
+    MovePointDown,
+    InsertPointAfter,
+    DeletePointRow,
+    CurveParameterHint,
+    CurveParameterHover,
+    CurveParameterInvalid,
+    ParameterName,
+    ParameterNameHover,
+    LongFormatExport,
+    LongFormatExportHover,
+    SurfaceExport,
+    SurfaceExportHover,
+    SurfaceCsvMatrix,
+    SurfaceNetCdf,
+    Loupe,
+    LoupeHover,
+    ExportNetCdf,
+    FitWidth,
+    FitWidthHover,
+    ZoomToRegion,
+    ZoomToRegionHover,
+    ColumnTemplates,
+    ColumnTemplatesHover,
+    IncludeAxisSteps,
+    IncludeAxisStepsHover,
+    Smoothing,
+    SmoothingHover,
+    MarkSnapArea,
+    MarkSnapAreaHover,
+    ClearSnapArea,
+    DetectMarkers,
+    DetectMarkersHover,
+    DetectMarkersIntro,
+    AddMarkerPoints,
+    AddMarkerPointsHover,
+    SpectrumExport,
+    SpectrumExportHover,
+    LevelCrossingsSection,
+    CrossingLevel,
+    CrossingLevelHover,
+    ShowCrossings,
+    ShowCrossingsHover,
+    NoCrossings,
+    CrossingRising,
+    CrossingFalling,
+    CrossingsExport,
+    CrossingsExportHover,
+    StepBreakpoints,
+    StepBreakpointsHover,
+    UnstackLayers,
+    UnstackLayersHover,
+    GridSuggestsScale,
+    GridSuggestsScaleHover,
+    SmithChart,
+    CompleteCalibrationBeforeExportSmith,
+    SmithCenter,
+    PickSmithCenterHover,
+    SmithShort,
+    PickSmithShortHover,
+    ReferenceImpedance,
+    ReferenceImpedanceHover,
+    SmithChartRadius,
+    SmithFitMissing,
+    SmithZeroRadius,
+    SmithBadReference,
+    Resistance,
+    Reactance,
+    TimeInputFormat,
+    TimeInputFormatHover,
+    TimeInputFormatAuto,
+    UtcOffset,
+    UtcOffsetHover,
+    UtcOffsetInvalid,
+    TimeExportFormat,
+    TimeExportFormatHover,
+    TimeExportUnixSeconds,
+    TimeExportExcelSerial,
+    AxisName,
+    AxisNameHover,
+    SmoothingFilter,
+    SmoothingFilterHover,
+    LoessIterations,
+    LoessIterationsHover,
+    // Shown only in `--features profiling` builds.
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    Profiler,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerHover,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerRecord,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerRecordHover,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerPause,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerSlowestFrame,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerSlowestFrameHover,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerSaveTrace,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerSaveTraceHover,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerNoFrames,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerScope,
+    #[cfg_attr(not(feature = "profiling"), allow(dead_code))]
+    ProfilerCalls,
+    CurveStatsSection,
+    StatsNeedCalibration,
+    CurveMinimum,
+    CurveMaximum,
+    CurveMean,
+    CurveMeanHover,
+    ArcLength,
+    ArcLengthHover,
+    AreaBounds,
+    AreaBoundsHover,
+    WholeCurve,
+    WholeCurveHover,
+    AreaUnderCurve,
+    AreaUnderCurveHover,
+    AreaOutsideCurve,
+    CrossingsAtLevel,
+    CrossingsAtLevelHover,
+    ShowDetails,
+    CopyDetails,
+    SampleGridLabel,
+    SampleGridHover,
+    SampleXsHover,
+    GridFrom,
+    GridTo,
+    GridStep,
+    GridCurveRange,
+    GridCurveRangeHover,
+    GridLogNeedsPositive,
+    ExportXRange,
+    ExportXRangeHover,
+    ExportXRangeMissesCurve,
+    CropToPlot,
+    CropToPlotAction,
+    CropToPlotTogether,
+    PlotCropWindow,
+    PlotCropIntro,
+    PlotCropSize,
+    KeepWholeImage,
+    PlotCropAccept,
+    IncludeConfidence,
+    IncludeConfidenceHover,
+    ShowPointConfidence,
+    ShowPointConfidenceHover,
+    SelectPoints,
+    SelectPointsHover,
+    SelectedPointCountHover,
+    DeleteSelectedPoints,
+    DeleteSelectedPointsHover,
+    MoveSelectedPointsTo,
+    MoveSelectedPointsHover,
+    ClearPointSelection,
+    LockPointHover,
+    PointLockedByCurveHover,
+    LockCurveHover,
+    SplineSpan,
+    SplineSpanHover,
+    KeyboardShortcuts,
+    KeyboardShortcutsHover,
+    KeymapWindow,
+    KeymapIntro,
+    KeymapRecording,
+    KeymapUnbound,
+    KeymapRecordHover,
+    KeymapClearHover,
+    KeymapResetHover,
+    KeymapResetAll,
+    KeymapConflict,
+    ShortcutToggleSidePanel,
+    ShortcutPickX1,
+    ShortcutPickX2,
+    ShortcutPickY1,
+    ShortcutPickY2,
+    CalibrationQuality,
+    CalibrationQualityHover,
+    AxisAngle,
+    AxisAngleHover,
+    AxisAngleSkewed,
+    QualityScale,
+    QualityResolution,
+    QualityResolutionHover,
+    QualityError,
+    QualityErrorHover,
+    PixelsPerUnit,
+    PixelsPerDecade,
+    FileDialogFailed,
 }
 
 impl TextKey {
     #[cfg(test)]
-    pub const ALL: [Self; 270] = [
+    pub const ALL: [Self; 737] = [
         Self::File,
         Self::Appearance,
         Self::OpenImage,
@@ -589,6 +1077,473 @@ impl TextKey {
         Self::LoadingImage,
         Self::DropHint,
         Self::Version,
+        Self::FlaggedPoints,
+        Self::FlaggedPointsHover,
+        Self::NoFlaggedPoints,
+        Self::SelectFlaggedPointHint,
+        Self::NoCommentsYet,
+        Self::CommentHint,
+        Self::AddComment,
+        Self::Unflag,
+        Self::UnflagHover,
+        Self::Flagged,
+        Self::IncludeFlagAnnotations,
+        Self::IncludeFlagAnnotationsHover,
+        Self::ExportProfile,
+        Self::ExportProfileHover,
+        Self::NoExportProfile,
+        Self::ProfileNameHint,
+        Self::SaveProfile,
+        Self::SaveProfileHover,
+        Self::DeleteProfile,
+        Self::DeleteProfileHover,
+        Self::ExportWithProfile,
+        Self::ExportWithProfileHover,
+        Self::Decimals,
+        Self::DecimalsHover,
+        Self::CsvDelimiter,
+        Self::CsvDelimiterHover,
+        Self::DelimiterComma,
+        Self::DelimiterSemicolon,
+        Self::DelimiterTab,
+        Self::DelimiterPipe,
+        Self::CancelExportHover,
+        Self::ExportOds,
+        Self::ExportArrow,
+        Self::ExportSql,
+        Self::PastePoints,
+        Self::PastePointsHover,
+        Self::AppendPastedPoints,
+        Self::AppendPastedPointsHover,
+        Self::AppendPastedPointsDisabledHover,
+        Self::ClearPastedPoints,
+        Self::ClearPastedPointsHover,
+        Self::RecordCaptureTimes,
+        Self::RecordCaptureTimesHover,
+        Self::IncludeCaptureTimes,
+        Self::IncludeCaptureTimesHover,
+        Self::CalibrationSandbox,
+        Self::CalibrationSandboxHover,
+        Self::CalibrationSandboxIntro,
+        Self::ApplySandbox,
+        Self::ApplySandboxHover,
+        Self::ResetSandbox,
+        Self::ResetSandboxHover,
+        Self::FirstPoint,
+        Self::LastPoint,
+        Self::SandboxCurrent,
+        Self::SandboxWhatIf,
+        Self::SandboxDraftInvalid,
+        Self::SignificantFigures,
+        Self::SignificantFiguresHover,
+        Self::FocusMode,
+        Self::FocusModeHover,
+        Self::UnsavedChangesWindow,
+        Self::UnsavedChangesIntro,
+        Self::UnsavedChangesCannotSave,
+        Self::DiscardChanges,
+        Self::DiscardChangesHover,
+        Self::RestoreSessionWindow,
+        Self::RestoreSessionIntro,
+        Self::ReopenProject,
+        Self::StartEmpty,
+        Self::ProjectSettings,
+        Self::ProjectSettingsHover,
+        Self::KeepStylesInProject,
+        Self::KeepStylesInProjectHover,
+        Self::KeepAutoPlaceInProject,
+        Self::KeepAutoPlaceInProjectHover,
+        Self::KeepExportInProject,
+        Self::KeepExportInProjectHover,
+        Self::ColorSampling,
+        Self::ColorSamplingHover,
+        Self::ClickToUseColor,
+        Self::Curves,
+        Self::ActiveCurveHover,
+        Self::CurveColorSwatchHover,
+        Self::CurveNameHover,
+        Self::CurvePointCountHover,
+        Self::RemoveCurveHover,
+        Self::AddCurve,
+        Self::AddCurveHover,
+        Self::Swatches,
+        Self::NoSwatches,
+        Self::ApplySwatchHover,
+        Self::RemoveSwatchHover,
+        Self::SwatchNameHint,
+        Self::SaveSwatch,
+        Self::SaveSwatchHover,
+        Self::AutoCalibrate,
+        Self::AutoCalibrateHover,
+        Self::ProposedCalibration,
+        Self::ProposedCalibrationHover,
+        Self::AxisNotFound,
+        Self::AcceptProposal,
+        Self::AcceptProposalHover,
+        Self::DiscardProposal,
+        Self::DiscardProposalHover,
+        Self::Affine,
+        Self::CompleteCalibrationBeforeExportAffine,
+        Self::SandboxAffineUnsupported,
+        Self::ReferencePoints,
+        Self::ReferencePointsHover,
+        Self::AddReferencePoint,
+        Self::AddReferencePointHover,
+        Self::RemoveReferencePointHover,
+        Self::PickReferencePointHover,
+        Self::AffineFitError,
+        Self::AffineFitHover,
+        Self::AffineTooFewPoints,
+        Self::AffineCollinearPoints,
+        Self::AffineParallelAxes,
+        Self::AffineEqualValues,
+        Self::AffineInvalidValues,
+        Self::AffineFitMissing,
+        Self::LoadCurveMask,
+        Self::LoadCurveMaskHover,
+        Self::ClearCurveMask,
+        Self::CurveMaskMissing,
+        Self::OpenCurveMaskDialogTitle,
+        Self::RunSegmentationModel,
+        Self::RunSegmentationModelHover,
+        Self::OpenSegmentationModelDialogTitle,
+        Self::SegmentationModelFilter,
+        Self::Perspective,
+        Self::PerspectiveAction,
+        Self::PerspectiveTogether,
+        Self::IgnoreTextLabels,
+        Self::IgnoreTextLabelsHover,
+        Self::CheckContinuity,
+        Self::CheckContinuityHover,
+        Self::CurveFitSection,
+        Self::FitModelLabel,
+        Self::FitModelHover,
+        Self::PolynomialDegree,
+        Self::PolynomialDegreeHover,
+        Self::FitNeedsCalibration,
+        Self::FitUnavailable,
+        Self::FittedCurve,
+        Self::FittedCurveHover,
+        Self::IncludeFitColumn,
+        Self::IncludeFitColumnHover,
+        Self::XAxisLooksInverted,
+        Self::YAxisLooksInverted,
+        Self::AxisLooksInvertedHover,
+        Self::SwapAxisValues,
+        Self::SwapAxisValuesHover,
+        Self::ErrorBars,
+        Self::ErrorBarsHover,
+        Self::SwapEndpointsHover,
+        Self::CopyXToY,
+        Self::CopyXToYHover,
+        Self::CopyCalibration,
+        Self::CopyCalibrationHover,
+        Self::PasteCalibration,
+        Self::PasteCalibrationHover,
+        Self::LevelImage,
+        Self::LevelAction,
+        Self::LevelTogether,
+        Self::ResultPlot,
+        Self::ResultPlotHover,
+        Self::ResultPlotNeedsCalibration,
+        Self::ExportParquet,
+        Self::StitchScans,
+        Self::StitchScansHover,
+        Self::StitchScansWindow,
+        Self::StitchScansIntro,
+        Self::StitchAlignment,
+        Self::StitchAlignmentHover,
+        Self::AddScans,
+        Self::StitchAction,
+        Self::StitchActionHover,
+        Self::CurvesFromLegend,
+        Self::CurvesFromLegendHover,
+        Self::SvgImportWindow,
+        Self::SvgImportIntro,
+        Self::SvgIntrinsicSize,
+        Self::SvgRasterScale,
+        Self::SvgRasterSize,
+        Self::ImportSvg,
+        Self::ChartTextWindow,
+        Self::ChartTextMenu,
+        Self::ChartTextMenuHover,
+        Self::ChartTextIntro,
+        Self::ChartTitle,
+        Self::XAxisTitle,
+        Self::YAxisTitle,
+        Self::ChartDescription,
+        Self::ReadChartText,
+        Self::ReadChartTextHover,
+        Self::SuppressGridLines,
+        Self::SuppressGridLinesHover,
+        Self::Preprocess,
+        Self::PreprocessHover,
+        Self::PreprocessWindow,
+        Self::PreprocessIntro,
+        Self::PreprocessEnabled,
+        Self::PreprocessEnabledHover,
+        Self::FlattenBackground,
+        Self::FlattenBackgroundHover,
+        Self::BackgroundCell,
+        Self::Binarization,
+        Self::BinarizationOff,
+        Self::BinarizationGlobal,
+        Self::BinarizationAdaptive,
+        Self::AdaptiveWindow,
+        Self::AdaptiveOffset,
+        Self::AdaptiveOffsetHover,
+        Self::KeepInkColor,
+        Self::KeepInkColorHover,
+        Self::ResetPreprocessing,
+        Self::LoadImageToPreprocess,
+        Self::FileDialogs,
+        Self::FileDialogsHover,
+        Self::FileDialogsAuto,
+        Self::FileDialogsBuiltin,
+        Self::FileDialogsNative,
+        Self::FileDialogsPortal,
+        Self::ExportHistory,
+        Self::ExportHistoryHover,
+        Self::ExportHistoryWindow,
+        Self::ExportHistoryEmpty,
+        Self::ExportedAt,
+        Self::ExportFormat,
+        Self::ExportedFile,
+        Self::ExportedRows,
+        Self::OpenExportedFile,
+        Self::OpenExportFolder,
+        Self::RepeatExport,
+        Self::RepeatExportHover,
+        Self::ClearExportHistory,
+        Self::SecondaryYAxis,
+        Self::SecondaryYAxisCalibrationHover,
+        Self::CurveOnSecondaryYHover,
+        Self::OverwriteBackup,
+        Self::OverwriteBackupHover,
+        Self::OverwriteBackupOff,
+        Self::OverwriteBackupBak,
+        Self::OverwriteBackupTimestamped,
+        Self::InvertedAxis,
+        Self::InvertedAxisHover,
+        Self::ValuesGrow,
+        Self::XAxisMarkedInverted,
+        Self::YAxisMarkedInverted,
+        Self::AxisMarkedInvertedHover,
+        Self::ValidationRules,
+        Self::ValidationRulesHover,
+        Self::ValidationWindow,
+        Self::ValidationIntro,
+        Self::AddValidationRule,
+        Self::RemoveValidationRule,
+        Self::RuleAtLeast,
+        Self::RuleAtMost,
+        Self::RuleStrictlyIncreasing,
+        Self::RuleStrictlyDecreasing,
+        Self::RuleBlocks,
+        Self::RuleWarns,
+        Self::ValidationNotRun,
+        Self::ValidationPassed,
+        Self::ViolationCurve,
+        Self::ViolationRow,
+        Self::ViolationValue,
+        Self::ViolationRule,
+        Self::MoreViolations,
+        Self::BridgeDashGaps,
+        Self::BridgeDashGapsHover,
+        Self::FitCalibrationToReference,
+        Self::FitCalibrationToReferenceHover,
+        Self::FitCalibrationToReferenceDisabledHover,
+        Self::FittedToReference,
+        Self::FittedToReferenceHover,
+        Self::RmsResidual,
+        Self::KeepFit,
+        Self::RevertFit,
+        Self::RevertFitHover,
+        Self::PointList,
+        Self::PointListHint,
+        Self::PixelX,
+        Self::PixelY,
+        Self::MovePointUp,
+        Self::MovePointDown,
+        Self::InsertPointAfter,
+        Self::DeletePointRow,
+        Self::CurveParameterHint,
+        Self::CurveParameterHover,
+        Self::CurveParameterInvalid,
+        Self::ParameterName,
+        Self::ParameterNameHover,
+        Self::LongFormatExport,
+        Self::LongFormatExportHover,
+        Self::SurfaceExport,
+        Self::SurfaceExportHover,
+        Self::SurfaceCsvMatrix,
+        Self::SurfaceNetCdf,
+        Self::Loupe,
+        Self::LoupeHover,
+        Self::ExportNetCdf,
+        Self::FitWidth,
+        Self::FitWidthHover,
+        Self::ZoomToRegion,
+        Self::ZoomToRegionHover,
+        Self::ColumnTemplates,
+        Self::ColumnTemplatesHover,
+        Self::IncludeAxisSteps,
+        Self::IncludeAxisStepsHover,
+        Self::Smoothing,
+        Self::SmoothingHover,
+        Self::MarkSnapArea,
+        Self::MarkSnapAreaHover,
+        Self::ClearSnapArea,
+        Self::DetectMarkers,
+        Self::DetectMarkersHover,
+        Self::DetectMarkersIntro,
+        Self::AddMarkerPoints,
+        Self::AddMarkerPointsHover,
+        Self::SpectrumExport,
+        Self::SpectrumExportHover,
+        Self::LevelCrossingsSection,
+        Self::CrossingLevel,
+        Self::CrossingLevelHover,
+        Self::ShowCrossings,
+        Self::ShowCrossingsHover,
+        Self::NoCrossings,
+        Self::CrossingRising,
+        Self::CrossingFalling,
+        Self::CrossingsExport,
+        Self::CrossingsExportHover,
+        Self::StepBreakpoints,
+        Self::StepBreakpointsHover,
+        Self::UnstackLayers,
+        Self::UnstackLayersHover,
+        Self::GridSuggestsScale,
+        Self::GridSuggestsScaleHover,
+        Self::SmithChart,
+        Self::CompleteCalibrationBeforeExportSmith,
+        Self::SmithCenter,
+        Self::PickSmithCenterHover,
+        Self::SmithShort,
+        Self::PickSmithShortHover,
+        Self::ReferenceImpedance,
+        Self::ReferenceImpedanceHover,
+        Self::SmithChartRadius,
+        Self::SmithFitMissing,
+        Self::SmithZeroRadius,
+        Self::SmithBadReference,
+        Self::Resistance,
+        Self::Reactance,
+        Self::TimeInputFormat,
+        Self::TimeInputFormatHover,
+        Self::TimeInputFormatAuto,
+        Self::UtcOffset,
+        Self::UtcOffsetHover,
+        Self::UtcOffsetInvalid,
+        Self::TimeExportFormat,
+        Self::TimeExportFormatHover,
+        Self::TimeExportUnixSeconds,
+        Self::TimeExportExcelSerial,
+        Self::AxisName,
+        Self::AxisNameHover,
+        Self::SmoothingFilter,
+        Self::SmoothingFilterHover,
+        Self::LoessIterations,
+        Self::LoessIterationsHover,
+        Self::Profiler,
+        Self::ProfilerHover,
+        Self::ProfilerRecord,
+        Self::ProfilerRecordHover,
+        Self::ProfilerPause,
+        Self::ProfilerSlowestFrame,
+        Self::ProfilerSlowestFrameHover,
+        Self::ProfilerSaveTrace,
+        Self::ProfilerSaveTraceHover,
+        Self::ProfilerNoFrames,
+        Self::ProfilerScope,
+        Self::ProfilerCalls,
+        Self::CurveStatsSection,
+        Self::StatsNeedCalibration,
+        Self::CurveMinimum,
+        Self::CurveMaximum,
+        Self::CurveMean,
+        Self::CurveMeanHover,
+        Self::ArcLength,
+        Self::ArcLengthHover,
+        Self::AreaBounds,
+        Self::AreaBoundsHover,
+        Self::WholeCurve,
+        Self::WholeCurveHover,
+        Self::AreaUnderCurve,
+        Self::AreaUnderCurveHover,
+        Self::AreaOutsideCurve,
+        Self::CrossingsAtLevel,
+        Self::CrossingsAtLevelHover,
+        Self::ShowDetails,
+        Self::CopyDetails,
+        Self::SampleGridLabel,
+        Self::SampleGridHover,
+        Self::SampleXsHover,
+        Self::GridFrom,
+        Self::GridTo,
+        Self::GridStep,
+        Self::GridCurveRange,
+        Self::GridCurveRangeHover,
+        Self::GridLogNeedsPositive,
+        Self::ExportXRange,
+        Self::ExportXRangeHover,
+        Self::ExportXRangeMissesCurve,
+        Self::CropToPlot,
+        Self::CropToPlotAction,
+        Self::CropToPlotTogether,
+        Self::PlotCropWindow,
+        Self::PlotCropIntro,
+        Self::PlotCropSize,
+        Self::KeepWholeImage,
+        Self::PlotCropAccept,
+        Self::IncludeConfidence,
+        Self::IncludeConfidenceHover,
+        Self::ShowPointConfidence,
+        Self::ShowPointConfidenceHover,
+        Self::SelectPoints,
+        Self::SelectPointsHover,
+        Self::SelectedPointCountHover,
+        Self::DeleteSelectedPoints,
+        Self::DeleteSelectedPointsHover,
+        Self::MoveSelectedPointsTo,
+        Self::MoveSelectedPointsHover,
+        Self::ClearPointSelection,
+        Self::LockPointHover,
+        Self::PointLockedByCurveHover,
+        Self::LockCurveHover,
+        Self::SplineSpan,
+        Self::SplineSpanHover,
+        Self::KeyboardShortcuts,
+        Self::KeyboardShortcutsHover,
+        Self::KeymapWindow,
+        Self::KeymapIntro,
+        Self::KeymapRecording,
+        Self::KeymapUnbound,
+        Self::KeymapRecordHover,
+        Self::KeymapClearHover,
+        Self::KeymapResetHover,
+        Self::KeymapResetAll,
+        Self::KeymapConflict,
+        Self::ShortcutToggleSidePanel,
+        Self::ShortcutPickX1,
+        Self::ShortcutPickX2,
+        Self::ShortcutPickY1,
+        Self::ShortcutPickY2,
+        Self::CalibrationQuality,
+        Self::CalibrationQualityHover,
+        Self::AxisAngle,
+        Self::AxisAngleHover,
+        Self::AxisAngleSkewed,
+        Self::QualityScale,
+        Self::QualityResolution,
+        Self::QualityResolutionHover,
+        Self::QualityError,
+        Self::QualityErrorHover,
+        Self::PixelsPerUnit,
+        Self::PixelsPerDecade,
+        Self::FileDialogFailed,
     ];
 }
 
@@ -644,6 +1599,36 @@ impl I18n {
         }
     }
 
+    pub fn format_markers_found(self, count: usize) -> String {
+        match self.lang {
+            UiLanguage::En => format!("{count} markers to add; click false ones on the image."),
+            UiLanguage::Ru => {
+                format!("Маркеров к добавлению: {count}; щёлкните ложные на изображении.")
+            }
+        }
+    }
+
+    /// Samples of a user grid, and how many of its x values miss the curve.
+    pub fn format_grid_samples(self, samples: usize, outside: usize) -> String {
+        match (self.lang, outside) {
+            (UiLanguage::En, 0) => format!("{samples} samples"),
+            (UiLanguage::En, _) => {
+                format!("{samples} samples; {outside} x outside the curve skipped")
+            }
+            (UiLanguage::Ru, 0) => format!("Семплов: {samples}"),
+            (UiLanguage::Ru, _) => {
+                format!("Семплов: {samples}; {outside} X вне кривой пропущено")
+            }
+        }
+    }
+
+    pub fn format_unreadable_sample_x(self, entry: &str) -> String {
+        match self.lang {
+            UiLanguage::En => format!("Cannot read x value “{entry}”."),
+            UiLanguage::Ru => format!("Не удалось прочитать значение X «{entry}»."),
+        }
+    }
+
     pub fn format_sample_count_tuned(self, count: usize) -> String {
         match self.lang {
             UiLanguage::En => format!("Sample count auto-tuned to {count}."),
@@ -734,6 +1719,24 @@ impl I18n {
         format!("{}: {total}", self.text(TextKey::Placed))
     }
 
+    pub fn format_flagged_points(self, flagged: usize) -> String {
+        format!("{}: {flagged}", self.text(TextKey::Flagged))
+    }
+
+    pub fn format_swap_endpoints(self, p1: &str, p2: &str) -> String {
+        match self.lang {
+            UiLanguage::En => format!("Swap {p1} ↔ {p2}"),
+            UiLanguage::Ru => format!("Поменять {p1} ↔ {p2}"),
+        }
+    }
+
+    pub fn format_continuity_jumps(self, count: usize) -> String {
+        match self.lang {
+            UiLanguage::En => format!("Flagged {count} suspicious jumps for review."),
+            UiLanguage::Ru => format!("Отмечено подозрительных скачков: {count}."),
+        }
+    }
+
     pub fn format_calibrated_pairs(self, calibrated: usize) -> String {
         format!(
             "{}: {calibrated}",
@@ -766,6 +1769,21 @@ impl I18n {
         format!("{}: {len:.1} px", self.text(TextKey::YAxisLength))
     }
 
+    pub fn format_reference_points(self, count: usize) -> String {
+        format!("{}: {count}", self.text(TextKey::ReferencePoints))
+    }
+
+    pub fn format_reference_pairs(self, pairs: usize) -> String {
+        match self.lang {
+            UiLanguage::En => format!("{pairs} picked points matched to the reference"),
+            UiLanguage::Ru => format!("Точек, сопоставленных с опорными: {pairs}"),
+        }
+    }
+
+    pub fn format_affine_fit_error(self, rms: f64) -> String {
+        format!("{}: {rms:.2} px", self.text(TextKey::AffineFitError))
+    }
+
     pub fn format_axes_angle(self, actual: f32, delta: f32) -> String {
         match self.lang {
             UiLanguage::En => {
@@ -833,9 +1851,57 @@ impl I18n {
             (UiLanguage::En, InterpAlgorithm::Linear) => "Linear",
             (UiLanguage::En, InterpAlgorithm::StepHold) => "Step (previous)",
             (UiLanguage::En, InterpAlgorithm::NaturalCubic) => "Natural cubic spline",
+            (UiLanguage::En, InterpAlgorithm::Pchip) => "Monotone cubic (PCHIP)",
+            (UiLanguage::En, InterpAlgorithm::Akima) => "Akima spline",
+            (UiLanguage::En, InterpAlgorithm::SmoothingSpline) => {
+                "Smoothing spline (by confidence)"
+            }
             (UiLanguage::Ru, InterpAlgorithm::Linear) => "Линейная",
             (UiLanguage::Ru, InterpAlgorithm::StepHold) => "Ступенчатая (пред.)",
             (UiLanguage::Ru, InterpAlgorithm::NaturalCubic) => "Натуральный кубический сплайн",
+            (UiLanguage::Ru, InterpAlgorithm::Pchip) => "Монотонный кубический (PCHIP)",
+            (UiLanguage::Ru, InterpAlgorithm::Akima) => "Сплайн Акимы",
+            (UiLanguage::Ru, InterpAlgorithm::SmoothingSpline) => {
+                "Сглаживающий сплайн (по уверенности)"
+            }
+        }
+    }
+
+    pub const fn smoothing_filter_label(self, filter: SmoothingFilter) -> &'static str {
+        match (self.lang, filter) {
+            (UiLanguage::En, SmoothingFilter::MovingAverage) => "Moving average",
+            (UiLanguage::En, SmoothingFilter::SavitzkyGolay) => "Savitzky–Golay",
+            (UiLanguage::En, SmoothingFilter::Median) => "Median",
+            (UiLanguage::Ru, SmoothingFilter::MovingAverage) => "Скользящее среднее",
+            (UiLanguage::Ru, SmoothingFilter::SavitzkyGolay) => "Савицкий–Голей",
+            (UiLanguage::Ru, SmoothingFilter::Median) => "Медиана",
+            (_, SmoothingFilter::Loess) => "LOESS",
+        }
+    }
+
+    pub const fn sample_grid_label(self, grid: SampleGrid) -> &'static str {
+        match (self.lang, grid) {
+            (UiLanguage::En, SampleGrid::Even) => "Evenly spaced",
+            (UiLanguage::En, SampleGrid::List) => "Listed x values",
+            (UiLanguage::En, SampleGrid::Uniform) => "Fixed step",
+            (UiLanguage::En, SampleGrid::LogSpaced) => "Log-spaced",
+            (UiLanguage::Ru, SampleGrid::Even) => "Равномерно",
+            (UiLanguage::Ru, SampleGrid::List) => "Заданные значения X",
+            (UiLanguage::Ru, SampleGrid::Uniform) => "С фиксированным шагом",
+            (UiLanguage::Ru, SampleGrid::LogSpaced) => "Логарифмически",
+        }
+    }
+
+    pub const fn fit_model_label(self, model: FitModel) -> &'static str {
+        match (self.lang, model) {
+            (UiLanguage::En, FitModel::Polynomial) => "Polynomial",
+            (UiLanguage::En, FitModel::Exponential) => "Exponential",
+            (UiLanguage::En, FitModel::PowerLaw) => "Power law",
+            (UiLanguage::En, FitModel::Logistic) => "Logistic",
+            (UiLanguage::Ru, FitModel::Polynomial) => "Полином",
+            (UiLanguage::Ru, FitModel::Exponential) => "Экспонента",
+            (UiLanguage::Ru, FitModel::PowerLaw) => "Степенной закон",
+            (UiLanguage::Ru, FitModel::Logistic) => "Логистическая",
         }
     }
 
@@ -850,6 +1916,70 @@ impl I18n {
         }
     }
 
+    /// UI name of a registered snap scorer; unknown ids fall back to the id itself.
+    pub fn snap_scorer_label(self, id: &'static str) -> &'static str {
+        match (self.lang, id) {
+            (UiLanguage::En, "ridge") => "Ridge snap",
+            (UiLanguage::Ru, "ridge") => "Привязка к гребню",
+            (UiLanguage::En, "mask") => "Segmentation mask",
+            (UiLanguage::Ru, "mask") => "Маска сегментации",
+            _ => id,
+        }
+    }
+
+    pub fn snap_scorer_hover(self, id: &str) -> &'static str {
+        match (self.lang, id) {
+            (UiLanguage::En, "ridge") => {
+                "Snap to thin lines of the curve color, where the color mask peaks across the stroke. Works on hairlines that are too thin for centerline snap."
+            }
+            (UiLanguage::Ru, "ridge") => {
+                "Привязка к тонким линиям цвета кривой — туда, где цветовая маска достигает максимума поперёк штриха. Подходит для линий, слишком тонких для привязки к центру."
+            }
+            (UiLanguage::En, "mask") => {
+                "Snap to pixels marked as curve by a segmentation model. Load its output (a grayscale image, white = curve) below."
+            }
+            (UiLanguage::Ru, "mask") => {
+                "Привязка к пикселям, которые модель сегментации отметила как кривую. Загрузите её результат (полутоновое изображение, белое — кривая) ниже."
+            }
+            _ => "",
+        }
+    }
+
+    pub const fn error_bar_mode_label(self, mode: ErrorBarMode) -> &'static str {
+        match (self.lang, mode) {
+            (UiLanguage::En, ErrorBarMode::Off) => "Off",
+            (UiLanguage::En, ErrorBarMode::Asymmetric) => "Lower + upper",
+            (UiLanguage::En, ErrorBarMode::Symmetric) => "Symmetric",
+            (UiLanguage::Ru, ErrorBarMode::Off) => "Выкл.",
+            (UiLanguage::Ru, ErrorBarMode::Asymmetric) => "Нижняя + верхняя",
+            (UiLanguage::Ru, ErrorBarMode::Symmetric) => "Симметричные",
+        }
+    }
+
+    pub const fn color_sample_mode_label(self, mode: ColorSampleMode) -> &'static str {
+        match (self.lang, mode) {
+            (UiLanguage::En, ColorSampleMode::Pixel) => "Single pixel",
+            (UiLanguage::En, ColorSampleMode::Mean3x3) => "Mean 3×3",
+            (UiLanguage::En, ColorSampleMode::Mean5x5) => "Mean 5×5",
+            (UiLanguage::En, ColorSampleMode::Median3x3) => "Median 3×3",
+            (UiLanguage::En, ColorSampleMode::Median5x5) => "Median 5×5",
+            (UiLanguage::Ru, ColorSampleMode::Pixel) => "Один пиксель",
+            (UiLanguage::Ru, ColorSampleMode::Mean3x3) => "Среднее 3×3",
+            (UiLanguage::Ru, ColorSampleMode::Mean5x5) => "Среднее 5×5",
+            (UiLanguage::Ru, ColorSampleMode::Median3x3) => "Медиана 3×3",
+            (UiLanguage::Ru, ColorSampleMode::Median5x5) => "Медиана 5×5",
+        }
+    }
+
+    pub const fn stitch_mode_label(self, mode: StitchMode) -> &'static str {
+        match (self.lang, mode) {
+            (UiLanguage::En, StitchMode::Translation) => "Translation",
+            (UiLanguage::En, StitchMode::Features) => "Features",
+            (UiLanguage::Ru, StitchMode::Translation) => "Сдвиг",
+            (UiLanguage::Ru, StitchMode::Features) => "Особые точки",
+        }
+    }
+
     pub const fn snap_threshold_kind_label(self, kind: SnapThresholdKind) -> &'static str {
         match (self.lang, kind) {
             (_, SnapThresholdKind::Gradient) => self.text(TextKey::GradientOnly),
@@ -874,15 +2004,15 @@ const fn en_text(key: TextKey) -> &'static str {
         TextKey::File => "File",
         TextKey::Appearance => "Appearance",
         TextKey::OpenImage => "Open image…",
-        TextKey::OpenImageHover => {
-            "Open an image (Ctrl+O). You can also drag & drop into the center."
-        }
+        TextKey::OpenImageHover => "Open an image, or drag & drop one into the center",
         TextKey::PasteImage => "Paste image",
-        TextKey::PasteImageHover => "Paste image from clipboard (Ctrl+V)",
+        TextKey::PasteImageHover => {
+            "Paste an image, a copied image or project file, or an image path/URL from the clipboard"
+        }
         TextKey::LoadProject => "Load project…",
-        TextKey::LoadProjectHover => "Load a saved Curcat project (Ctrl+Shift+P)",
+        TextKey::LoadProjectHover => "Load a saved Curcat project",
         TextKey::SaveProject => "Save project",
-        TextKey::SaveProjectHover => "Save the current session as a Curcat project (Ctrl+S)",
+        TextKey::SaveProjectHover => "Save the current session as a Curcat project",
         TextKey::HideSide => "Hide side",
         TextKey::ShowSide => "Show side",
         TextKey::HideSidePanel => "Hide side panel",
@@ -890,15 +2020,15 @@ const fn en_text(key: TextKey) -> &'static str {
         TextKey::SidePanelPosition => "Side panel position",
         TextKey::Left => "Left",
         TextKey::Right => "Right",
-        TextKey::ToggleSidePanelHover => "Toggle side panel (Ctrl+B) and set position",
+        TextKey::ToggleSidePanelHover => "Toggle side panel and set position",
         TextKey::PointsStats => "Points stats",
-        TextKey::PointsStatsHover => "Show stats for picked points",
+        TextKey::PointsStatsHover => "Show stats and an editable list of the picked points",
         TextKey::Filters => "Filters",
-        TextKey::FiltersHover => "Show image filters (Ctrl+Shift+F)",
+        TextKey::FiltersHover => "Show image filters",
         TextKey::AutoTrace => "Auto-trace",
-        TextKey::AutoTraceHover => "Show auto-trace controls (Ctrl+Shift+T)",
+        TextKey::AutoTraceHover => "Show auto-trace controls",
         TextKey::ImageInfo => "Image info",
-        TextKey::ImageInfoHover => "Show file & image details (Ctrl+I)",
+        TextKey::ImageInfoHover => "Show file & image details",
         TextKey::TransformsTogether => "Transforms image, points, and calibration together.",
         TextKey::Rotate90Ccw => "Rotate 90° counter-clockwise.",
         TextKey::Rotate90Cw => "Rotate 90° clockwise.",
@@ -910,16 +2040,16 @@ const fn en_text(key: TextKey) -> &'static str {
         TextKey::ZoomHover => "Choose a preset zoom level",
         TextKey::ZoomPresetsHover => "Zoom presets plus Fit/Reset view actions",
         TextKey::Fit => "Fit",
-        TextKey::FitHover => "Fit the image into the viewport (Ctrl+F)",
+        TextKey::FitHover => "Fit the image into the viewport",
         TextKey::ResetView => "Reset view",
-        TextKey::ResetViewHover => "Reset zoom to 100% and pan to origin (Ctrl+R)",
+        TextKey::ResetViewHover => "Reset zoom to 100% and pan to origin",
         TextKey::PanWithMiddleButton => "Pan with middle mouse button",
         TextKey::MmbPan => "MMB pan",
         TextKey::MmbPanHover => "Enable/disable middle-button panning",
         TextKey::ClearPoints => "Clear points",
-        TextKey::ClearPointsHover => "Clear all points (Ctrl+Shift+D)",
+        TextKey::ClearPointsHover => "Clear all points",
         TextKey::Undo => "Undo",
-        TextKey::UndoHover => "Undo last point (Ctrl+Z)",
+        TextKey::UndoHover => "Undo last point",
         TextKey::LanguageSwitcherHover => "UI language",
         TextKey::PointInput => "Point input",
         TextKey::Free => "Free",
@@ -978,7 +2108,9 @@ const fn en_text(key: TextKey) -> &'static str {
         }
         TextKey::Calibration => "Calibration",
         TextKey::CoordinateSystem => "Coordinate system:",
-        TextKey::CoordinateSystemHover => "Choose between Cartesian (X/Y) or Polar (angle/radius)",
+        TextKey::CoordinateSystemHover => {
+            "Choose Cartesian (X/Y), Polar (angle/radius), or Affine (X/Y from reference points on skewed scans)"
+        }
         TextKey::Cartesian => "Cartesian",
         TextKey::Polar => "Polar",
         TextKey::CoordSystemForCalibrationExport => "Coordinate system for calibration and export",
@@ -1181,8 +2313,739 @@ const fn en_text(key: TextKey) -> &'static str {
         TextKey::PickedLabel => "Picked",
         TextKey::LoadingImageWithName => "Loading image",
         TextKey::LoadingImage => "Loading image…",
-        TextKey::DropHint => "Drop an image here, open a file, or paste from clipboard (Ctrl+V).",
+        TextKey::DropHint => "Drop an image here, open a file, or paste from clipboard",
         TextKey::Version => "Version",
+        TextKey::FlaggedPoints => "Flagged points",
+        TextKey::FlaggedPointsHover => {
+            "Review points marked as questionable (Alt+click a point to flag it)"
+        }
+        TextKey::NoFlaggedPoints => "No flagged points. Alt+click a point on the image to flag it.",
+        TextKey::SelectFlaggedPointHint => "Select a flagged point to read or add comments.",
+        TextKey::NoCommentsYet => "No comments yet.",
+        TextKey::CommentHint => "Write a comment…",
+        TextKey::AddComment => "Add comment",
+        TextKey::Unflag => "Unflag",
+        TextKey::UnflagHover => "Remove the flag and its comments from this point",
+        TextKey::Flagged => "Flagged",
+        TextKey::IncludeFlagAnnotations => "Include flag annotations",
+        TextKey::IncludeFlagAnnotationsHover => {
+            "Add a 'flag' text column with review comments of flagged points"
+        }
+        TextKey::ExportProfile => "Profile",
+        TextKey::ExportProfileHover => {
+            "Named export settings stored in curcat.toml; * marks settings changed since the active profile was applied, and Save profile keeps them"
+        }
+        TextKey::NoExportProfile => "(none)",
+        TextKey::ProfileNameHint => "Profile name",
+        TextKey::SaveProfile => "Save profile",
+        TextKey::SaveProfileHover => {
+            "Save the current export settings under this name (replaces a profile with the same name)"
+        }
+        TextKey::DeleteProfile => "Delete",
+        TextKey::DeleteProfileHover => "Delete the active export profile",
+        TextKey::ExportWithProfile => "Export with profile",
+        TextKey::ExportWithProfileHover => "Export using the format stored in the active profile",
+        TextKey::Decimals => "Decimals",
+        TextKey::DecimalsHover => "Number of fractional digits written for float values",
+        TextKey::CsvDelimiter => "CSV delimiter",
+        TextKey::CsvDelimiterHover => "Field separator used for CSV export",
+        TextKey::DelimiterComma => "Comma (,)",
+        TextKey::DelimiterSemicolon => "Semicolon (;)",
+        TextKey::DelimiterTab => "Tab",
+        TextKey::DelimiterPipe => "Pipe (|)",
+        TextKey::CancelExportHover => "Stop writing; the partially written file is removed",
+        TextKey::ExportOds => "Export ODS…",
+        TextKey::ExportArrow => "Export Arrow IPC…",
+        TextKey::ExportSql => "Export SQL script…",
+        TextKey::PastePoints => "Paste points",
+        TextKey::PastePointsHover => {
+            "Paste a two-column table (CSV/TSV) from the clipboard as reference data drawn over the image"
+        }
+        TextKey::AppendPastedPoints => "Add to points",
+        TextKey::AppendPastedPointsHover => {
+            "Turn the pasted reference into regular points to continue the curve"
+        }
+        TextKey::AppendPastedPointsDisabledHover => "Finish calibration to place pasted points",
+        TextKey::ClearPastedPoints => "Clear",
+        TextKey::ClearPastedPointsHover => "Remove the pasted reference overlay",
+        TextKey::RecordCaptureTimes => "Record capture times",
+        TextKey::RecordCaptureTimesHover => {
+            "Stamp each new point with the wall-clock time it was placed (for live strip-chart digitizing)"
+        }
+        TextKey::IncludeCaptureTimes => "Include capture time",
+        TextKey::IncludeCaptureTimesHover => {
+            "Add a captured_at column with the UTC time each point was placed; empty for points recorded without a timestamp"
+        }
+        TextKey::CalibrationSandbox => "Calibration sandbox",
+        TextKey::CalibrationSandboxHover => {
+            "Try other calibration values and compare the resulting data range before applying them"
+        }
+        TextKey::CalibrationSandboxIntro => {
+            "Edit values here to preview how the data would change. The real calibration stays untouched until you apply."
+        }
+        TextKey::ApplySandbox => "Apply",
+        TextKey::ApplySandboxHover => "Copy these values into the calibration",
+        TextKey::ResetSandbox => "Reset",
+        TextKey::ResetSandboxHover => {
+            "Discard the draft and start again from the current calibration"
+        }
+        TextKey::FirstPoint => "First point",
+        TextKey::LastPoint => "Last point",
+        TextKey::SandboxCurrent => "Current",
+        TextKey::SandboxWhatIf => "What-if",
+        TextKey::SandboxDraftInvalid => "Draft values do not form a valid calibration.",
+        TextKey::SignificantFigures => "Significant figures",
+        TextKey::SignificantFiguresHover => {
+            "Format each float column to this many significant figures of its value span; very large or small values switch to scientific notation. Replaces the fixed decimal count."
+        }
+        TextKey::FocusMode => "Focus mode (dim image)",
+        TextKey::FocusModeHover => {
+            "Dim and wash out the image except around the cursor and along the picked points, so faint curves are easier to follow."
+        }
+        TextKey::UnsavedChangesWindow => "Unsaved changes",
+        TextKey::UnsavedChangesIntro => {
+            "The current image has points, calibration or notes that are not saved to a project. Save them before continuing?"
+        }
+        TextKey::UnsavedChangesCannotSave => {
+            "This image was not opened from a file, so it cannot be saved as a project."
+        }
+        TextKey::DiscardChanges => "Discard",
+        TextKey::DiscardChangesHover => "Continue and lose the unsaved changes.",
+        TextKey::RestoreSessionWindow => "Restore last session",
+        TextKey::RestoreSessionIntro => "Reopen the project you were working on last time?",
+        TextKey::ReopenProject => "Reopen",
+        TextKey::StartEmpty => "Start empty",
+        TextKey::ProjectSettings => "Project settings",
+        TextKey::ProjectSettingsHover => {
+            "Config values saved into the project file; they win over curcat.toml when the project is opened"
+        }
+        TextKey::KeepStylesInProject => "Overlay styles",
+        TextKey::KeepStylesInProjectHover => {
+            "Store the curve, point, crosshair and highlight styles from the current config with the project"
+        }
+        TextKey::KeepAutoPlaceInProject => "Auto-place parameters",
+        TextKey::KeepAutoPlaceInProjectHover => {
+            "Store the current auto-place distances and timings with the project"
+        }
+        TextKey::KeepExportInProject => "Export settings",
+        TextKey::KeepExportInProjectHover => {
+            "Store the export section settings with the project and restore them on open"
+        }
+        TextKey::ColorSampling => "Sample",
+        TextKey::ColorSamplingHover => {
+            "How many pixels around the cursor the picker averages; larger windows steady the color on anti-aliased strokes."
+        }
+        TextKey::ClickToUseColor => "Click to use",
+        TextKey::Curves => "Curves",
+        TextKey::ActiveCurveHover => "New points are placed on the active curve.",
+        TextKey::CurveColorSwatchHover => {
+            "Color of the curve's points and segments. Right-click for saved swatches."
+        }
+        TextKey::CurveNameHover => "Curve name; used for export sheets and the curve column.",
+        TextKey::CurvePointCountHover => "Points on this curve.",
+        TextKey::RemoveCurveHover => "Delete the curve with its points.",
+        TextKey::AddCurve => "Add curve",
+        TextKey::AddCurveHover => "Start another curve from the same chart; it becomes active.",
+        TextKey::Swatches => "Swatches",
+        TextKey::NoSwatches => "No saved colors yet",
+        TextKey::ApplySwatchHover => "Use this saved color.",
+        TextKey::RemoveSwatchHover => "Remove this color from the project's swatches.",
+        TextKey::SwatchNameHint => "Name, e.g. Series A red",
+        TextKey::SaveSwatch => "Save color",
+        TextKey::SaveSwatchHover => {
+            "Save the color on the left under this name; an existing name is overwritten."
+        }
+        TextKey::AutoCalibrate => "Auto-calibrate",
+        TextKey::AutoCalibrateHover => {
+            "Find the axes, read their tick labels and fill X1/X2/Y1/Y2 for you to confirm."
+        }
+        TextKey::ProposedCalibration => "Proposed from tick labels",
+        TextKey::ProposedCalibrationHover => {
+            "Confidence combines how clearly the two labels were read with how many other labels agree; small labels count as less clearly read. Check the points on the image before accepting."
+        }
+        TextKey::AxisNotFound => "not found",
+        TextKey::AcceptProposal => "Accept",
+        TextKey::AcceptProposalHover => "Keep the proposed calibration.",
+        TextKey::DiscardProposal => "Discard",
+        TextKey::DiscardProposalHover => "Restore the calibration from before the proposal.",
+        TextKey::Affine => "Affine (3+ points)",
+        TextKey::CompleteCalibrationBeforeExportAffine => {
+            "Place at least three reference points before exporting to"
+        }
+        TextKey::SandboxAffineUnsupported => {
+            "The sandbox drafts two-point axis calibrations; switch to Cartesian or Polar to use it."
+        }
+        TextKey::ReferencePoints => "Reference points",
+        TextKey::ReferencePointsHover => {
+            "Each reference point pairs an image position with its X and Y values; the mapping is fitted to all complete rows."
+        }
+        TextKey::AddReferencePoint => "Add point",
+        TextKey::AddReferencePointHover => {
+            "Add another reference point; extra points average out picking errors."
+        }
+        TextKey::RemoveReferencePointHover => "Remove this reference point.",
+        TextKey::PickReferencePointHover => "Click the image to place this reference point.",
+        TextKey::AffineFitError => "Fit error (RMS)",
+        TextKey::AffineFitHover => {
+            "Distance between the reference points and where the fitted mapping puts their values."
+        }
+        TextKey::AffineTooFewPoints => "Need three complete reference points",
+        TextKey::AffineCollinearPoints => "Reference points lie on one line",
+        TextKey::AffineParallelAxes => "X and Y values change along the same direction",
+        TextKey::AffineEqualValues => "All X or all Y values are equal",
+        TextKey::AffineInvalidValues => "Values do not fit the axis unit or scale",
+        TextKey::AffineFitMissing => "Affine mapping not fitted yet",
+        TextKey::LoadCurveMask => "Load mask…",
+        TextKey::LoadCurveMaskHover => {
+            "Open a grayscale mask from a segmentation model. It is stretched to the image size; white means curve."
+        }
+        TextKey::ClearCurveMask => "Clear mask",
+        TextKey::CurveMaskMissing => "No mask loaded: this mode finds nothing until one is.",
+        TextKey::OpenCurveMaskDialogTitle => "Open curve mask",
+        TextKey::RunSegmentationModel => "Run model…",
+        TextKey::RunSegmentationModelHover => {
+            "Run an ONNX segmentation model on the image and use its output as the mask. The model takes RGB in 0–1 as [1, 3, H, W] and returns curve probability as [1, 1, H, W]."
+        }
+        TextKey::OpenSegmentationModelDialogTitle => "Open segmentation model",
+        TextKey::SegmentationModelFilter => "ONNX models",
+        TextKey::Perspective => "Perspective",
+        TextKey::PerspectiveAction => {
+            "Click the four corners of the plot area to straighten a photo taken at an angle."
+        }
+        TextKey::PerspectiveTogether => "Warps the image; points and calibration follow the warp.",
+        TextKey::IgnoreTextLabels => "Ignore text labels",
+        TextKey::IgnoreTextLabelsHover => {
+            "Leave tick labels, titles and other detected text out of the snap search"
+        }
+        TextKey::CheckContinuity => "Check continuity",
+        TextKey::CheckContinuityHover => {
+            "Flag points of the active curve where it jumps much further than between its neighbors"
+        }
+        TextKey::CurveFitSection => "Curve fit",
+        TextKey::FitModelLabel => "Model",
+        TextKey::FitModelHover => "Model fitted to the active curve's points by least squares",
+        TextKey::PolynomialDegree => "Degree",
+        TextKey::PolynomialDegreeHover => "Highest power of x in the polynomial",
+        TextKey::FitNeedsCalibration => "Complete the calibration to fit a model.",
+        TextKey::FitUnavailable => {
+            "The model cannot be fitted to these points (too few points, or values outside the model's domain)."
+        }
+        TextKey::FittedCurve => "Fitted curve",
+        TextKey::FittedCurveHover => {
+            "Export evenly spaced samples of the fitted model (see Curve fit)"
+        }
+        TextKey::IncludeFitColumn => "Add fitted column",
+        TextKey::IncludeFitColumnHover => {
+            "Add a 'fit' column with the fitted model evaluated at each exported x"
+        }
+        TextKey::XAxisLooksInverted => "⚠ X values decrease to the right",
+        TextKey::YAxisLooksInverted => "⚠ Y values decrease upward",
+        TextKey::AxisLooksInvertedHover => {
+            "The two entries may be swapped, which mirrors the exported data. If the chart really has a reversed axis, tick Inverted."
+        }
+        TextKey::SwapAxisValues => "Swap values",
+        TextKey::SwapAxisValuesHover => "Exchange the two values, keeping the picked pixels",
+        TextKey::ErrorBars => "Error bars",
+        TextKey::ErrorBarsHover => {
+            "After placing a point, click its error bar ends; symmetric bars take one click and mirror it. Bars export as y_err_lo / y_err_hi columns"
+        }
+        TextKey::SwapEndpointsHover => {
+            "Exchange the two calibration points together with their values; the mapping stays the same"
+        }
+        TextKey::CopyXToY => "X → Y",
+        TextKey::CopyXToYHover => {
+            "Copy the X axis unit, scale and values to Y (for square plots). If Y points are not picked yet, they are placed at X1 and at the X span rotated upward"
+        }
+        TextKey::CopyCalibration => "Copy calibration",
+        TextKey::CopyCalibrationHover => {
+            "Copy the whole calibration to the clipboard to paste into another project or Curcat window"
+        }
+        TextKey::PasteCalibration => "Paste calibration",
+        TextKey::PasteCalibrationHover => {
+            "Replace the calibration with one copied from another project"
+        }
+        TextKey::LevelImage => "Level",
+        TextKey::LevelAction => {
+            "Click two points that should be horizontal, such as the ends of the X axis, to rotate the image level."
+        }
+        TextKey::LevelTogether => {
+            "Rotates the image by a small angle; points and calibration follow the rotation."
+        }
+        TextKey::ResultPlot => "Result plot",
+        TextKey::ResultPlotHover => {
+            "Show a bottom panel plotting the picked points and the interpolated curve in calibrated coordinates"
+        }
+        TextKey::ResultPlotNeedsCalibration => {
+            "Complete the calibration to plot the digitized data."
+        }
+        TextKey::ExportParquet => "Export Parquet…",
+        TextKey::StitchScans => "Stitch scans…",
+        TextKey::StitchScansHover => "Join overlapping scans of one long chart into a single image",
+        TextKey::StitchScansWindow => "Stitch scans",
+        TextKey::StitchScansIntro => {
+            "Scans are joined in list order; each one must overlap the previous. The result is saved as a PNG next to the first scan and opened."
+        }
+        TextKey::StitchAlignment => "Alignment",
+        TextKey::StitchAlignmentHover => {
+            "Translation: match whole overlaps by correlation. Features: match corners, more robust to differing contrast and margins."
+        }
+        TextKey::AddScans => "Add scans…",
+        TextKey::StitchAction => "Stitch",
+        TextKey::StitchActionHover => "Needs at least two scans",
+        TextKey::CurvesFromLegend => "From legend",
+        TextKey::CurvesFromLegendHover => {
+            "Click the swatch of each legend entry: a curve gets its color and the label text as its name. Esc to finish."
+        }
+        TextKey::SvgImportWindow => "Import SVG",
+        TextKey::SvgImportIntro => {
+            "The vector image is rasterized once at the chosen scale. A larger scale gives finer detail for picking points; the scale is saved with the project."
+        }
+        TextKey::SvgIntrinsicSize => "Intrinsic size",
+        TextKey::SvgRasterScale => "Scale",
+        TextKey::SvgRasterSize => "Raster size",
+        TextKey::ImportSvg => "Import",
+        TextKey::ChartTextWindow => "Chart titles",
+        TextKey::ChartTextMenu => "Chart titles…",
+        TextKey::ChartTextMenuHover => {
+            "Edit the chart title, axis titles and description, or read them off the image"
+        }
+        TextKey::ChartTextIntro => {
+            "Axis titles name the x/y columns of exports; the title heads HTML reports. All of them are saved with the project."
+        }
+        TextKey::ChartTitle => "Title",
+        TextKey::XAxisTitle => "X axis",
+        TextKey::YAxisTitle => "Y axis",
+        TextKey::ChartDescription => "Description",
+        TextKey::ReadChartText => "Read",
+        TextKey::ReadChartTextHover => "Click the text on the image to read it",
+        TextKey::SuppressGridLines => "Suppress grid lines",
+        TextKey::SuppressGridLinesHover => {
+            "Fade long horizontal and vertical grid lines before snapping, so contrast snap does not lock onto them"
+        }
+        TextKey::Preprocess => "Preprocessing",
+        TextKey::PreprocessHover => "Clean up scanned figures: flatten the background and binarize",
+        TextKey::PreprocessWindow => "Scan preprocessing",
+        TextKey::PreprocessIntro => {
+            "Cleans up the working copy of the image before display filters and snapping; the file is not changed."
+        }
+        TextKey::PreprocessEnabled => "Apply preprocessing",
+        TextKey::PreprocessEnabledHover => "Turn off to compare with the original pixels",
+        TextKey::FlattenBackground => "Flatten background",
+        TextKey::FlattenBackgroundHover => {
+            "Divide out uneven lighting and paper tint so the background becomes white"
+        }
+        TextKey::BackgroundCell => "cell, px",
+        TextKey::Binarization => "Binarization:",
+        TextKey::BinarizationOff => "Off",
+        TextKey::BinarizationGlobal => "Global threshold",
+        TextKey::BinarizationAdaptive => "Adaptive (local mean)",
+        TextKey::AdaptiveWindow => "window radius, px",
+        TextKey::AdaptiveOffset => "offset",
+        TextKey::AdaptiveOffsetHover => {
+            "How much darker than the surrounding mean a pixel must be to count as ink"
+        }
+        TextKey::KeepInkColor => "Keep ink color",
+        TextKey::KeepInkColorHover => {
+            "Leave ink pixels their color instead of black, so color snapping still tells curves apart"
+        }
+        TextKey::ResetPreprocessing => "Reset preprocessing",
+        TextKey::LoadImageToPreprocess => "Load an image to preprocess it.",
+        TextKey::FileDialogs => "File dialogs",
+        TextKey::FileDialogsHover => {
+            "Which dialog opens and saves files: the built-in one, the system dialog with its quick-access locations, or the GNOME/KDE dialog shown through xdg-desktop-portal"
+        }
+        TextKey::FileDialogsAuto => "Automatic (portal in Flatpak/Snap)",
+        TextKey::FileDialogsBuiltin => "Built-in",
+        TextKey::FileDialogsNative => "System",
+        TextKey::FileDialogsPortal => "Desktop portal",
+        TextKey::ExportHistory => "Export history",
+        TextKey::ExportHistoryHover => {
+            "Exports made from this project: open the file or its folder, or write it again"
+        }
+        TextKey::ExportHistoryWindow => "Export history",
+        TextKey::ExportHistoryEmpty => "No exports from this project yet.",
+        TextKey::ExportedAt => "Time",
+        TextKey::ExportFormat => "Format",
+        TextKey::ExportedFile => "File",
+        TextKey::ExportedRows => "Rows",
+        TextKey::OpenExportedFile => "Open",
+        TextKey::OpenExportFolder => "Folder",
+        TextKey::RepeatExport => "Repeat",
+        TextKey::RepeatExportHover => {
+            "Write the current points to the same file with the settings of this export"
+        }
+        TextKey::ClearExportHistory => "Clear history",
+        TextKey::SecondaryYAxis => "Secondary Y axis (Y')",
+        TextKey::SecondaryYAxisCalibrationHover => {
+            "Second value scale of a dual-axis chart, usually on the right. Only curves assigned to Y' in the Curves section are read against it."
+        }
+        TextKey::CurveOnSecondaryYHover => {
+            "Read this curve against the secondary Y axis (Y') instead of the main one"
+        }
+        TextKey::OverwriteBackup => "Backup on overwrite",
+        TextKey::OverwriteBackupHover => {
+            "When an export replaces an existing file, move the previous version aside first"
+        }
+        TextKey::OverwriteBackupOff => "Off",
+        TextKey::OverwriteBackupBak => "name.ext.bak",
+        TextKey::OverwriteBackupTimestamped => "Timestamped copies",
+        TextKey::InvertedAxis => "Inverted",
+        TextKey::InvertedAxisHover => {
+            "The values on this chart fall along the axis (to the right on X, upward on Y), as on depth or pressure axes. Marks the reversed direction as intended; export checks that the values agree."
+        }
+        TextKey::ValuesGrow => "values grow",
+        TextKey::XAxisMarkedInverted => "⚠ X is marked inverted, but its values grow to the right",
+        TextKey::YAxisMarkedInverted => "⚠ Y is marked inverted, but its values grow upward",
+        TextKey::AxisMarkedInvertedHover => {
+            "Swap the values if they were entered the wrong way round, or clear Inverted. Export stays blocked until they agree."
+        }
+        TextKey::ValidationRules => "Validation rules",
+        TextKey::ValidationRulesHover => {
+            "Checks run on the exported rows before export; failing rules block the export or warn"
+        }
+        TextKey::ValidationWindow => "Validation rules",
+        TextKey::ValidationIntro => {
+            "Every exported row of every curve is checked before the file is written. Rules are saved with the project."
+        }
+        TextKey::AddValidationRule => "Add rule",
+        TextKey::RemoveValidationRule => "Remove rule",
+        TextKey::RuleAtLeast => "≥",
+        TextKey::RuleAtMost => "≤",
+        TextKey::RuleStrictlyIncreasing => "strictly increasing",
+        TextKey::RuleStrictlyDecreasing => "strictly decreasing",
+        TextKey::RuleBlocks => "Block export",
+        TextKey::RuleWarns => "Warn only",
+        TextKey::ValidationNotRun => "Rules are checked at the next export.",
+        TextKey::ValidationPassed => "The last export passed every rule.",
+        TextKey::ViolationCurve => "Curve",
+        TextKey::ViolationRow => "Row",
+        TextKey::ViolationValue => "Value",
+        TextKey::ViolationRule => "Rule",
+        TextKey::MoreViolations => "Rows not listed:",
+        TextKey::BridgeDashGaps => "Bridge dashed-line gaps",
+        TextKey::BridgeDashGapsHover => {
+            "When nothing matches within the radius, follow the line through nearby dashes or dots and snap onto it, so clicks between dashes still land on the curve"
+        }
+        TextKey::FitCalibrationToReference => "Fit calibration to reference",
+        TextKey::FitCalibrationToReferenceHover => {
+            "Slide the axis points along their axes (least squares) so the picked points of the active curve read the pasted reference values; useful when tick marks are smudged. Pick points only where the reference is known to be correct."
+        }
+        TextKey::FitCalibrationToReferenceDisabledHover => {
+            "Needs a finished Cartesian calibration and at least two picked points on the active curve"
+        }
+        TextKey::FittedToReference => "Calibration fitted to reference",
+        TextKey::FittedToReferenceHover => {
+            "The axis points were moved so the picked points match the pasted reference; the entered values did not change"
+        }
+        TextKey::RmsResidual => "RMS residual",
+        TextKey::KeepFit => "Keep",
+        TextKey::RevertFit => "Revert",
+        TextKey::RevertFitHover => "Restore the axis points from before the fit",
+        TextKey::PointList => "Point list",
+        TextKey::PointListHint => {
+            "Drag or type a value to move the point; pixel columns nudge it on the image."
+        }
+        TextKey::PixelX => "px X",
+        TextKey::PixelY => "px Y",
+        TextKey::MovePointUp => "Move up",
+        TextKey::MovePointDown => "Move down",
+        TextKey::InsertPointAfter => "Insert a point halfway to the next one",
+        TextKey::DeletePointRow => "Delete the point",
+        TextKey::CurveParameterHint => "param",
+        TextKey::CurveParameterHover => {
+            "Family parameter of this curve (e.g. its temperature); exported as an extra column once any curve has one."
+        }
+        TextKey::CurveParameterInvalid => {
+            "Not a number; the parameter cell of this curve stays empty."
+        }
+        TextKey::ParameterName => "Parameter name",
+        TextKey::ParameterNameHover => {
+            "Header of the exported parameter column; empty uses \"parameter\"."
+        }
+        TextKey::LongFormatExport => "Long format (one table)",
+        TextKey::LongFormatExportHover => {
+            "Stack all curves into one table with curve and parameter columns, also in Excel and JSON, which otherwise keep curves apart. Suited to surface fitting."
+        }
+        TextKey::SurfaceExport => "Surface:",
+        TextKey::SurfaceExportHover => {
+            "Grid the curves that have a parameter value over x and the parameter. Each curve is resampled with the sample count and interpolation above; cells between curves are interpolated across the parameter."
+        }
+        TextKey::SurfaceCsvMatrix => "CSV matrix",
+        TextKey::SurfaceNetCdf => "NetCDF",
+        TextKey::Loupe => "Loupe",
+        TextKey::LoupeHover => {
+            "Show a six-fold magnified round inset next to the cursor over the image, with a crosshair on the exact pixel a click lands in."
+        }
+        TextKey::ExportNetCdf => "Export NetCDF…",
+        TextKey::FitWidth => "Fit width",
+        TextKey::FitWidthHover => {
+            "Zoom so the image width fills the viewport, keeping the current row in view"
+        }
+        TextKey::ZoomToRegion => "Zoom to region",
+        TextKey::ZoomToRegionHover => {
+            "Drag a rectangle over the image to fill the viewport with it. Ctrl + drag does the same at any time; Esc cancels."
+        }
+        TextKey::ColumnTemplates => "Column templates:",
+        TextKey::ColumnTemplatesHover => {
+            "Reusable columns from [[export.columns]] in curcat.toml; the choice is kept in export profiles"
+        }
+        TextKey::IncludeAxisSteps => "Include dx, dy, step and slope",
+        TextKey::IncludeAxisStepsHover => {
+            "Step to the previous point in axis units; on log axes in decades (dlog10_x / dlog10_y), so slope on log-log axes is the power-law exponent"
+        }
+        TextKey::Smoothing => "Smoothing",
+        TextKey::SmoothingHover => {
+            "Smoothing window: this many rows on each side, 2n + 1 rows in all; previewed over the image and in the result plot. The picked points stay unchanged and only the export writes smoothed values (0 = off)"
+        }
+        TextKey::MarkSnapArea => "Snap area",
+        TextKey::MarkSnapAreaHover => {
+            "Drag a rectangle, or click the corners of a polygon and press Enter, around the plot; snapping and auto-trace ignore everything outside it."
+        }
+        TextKey::ClearSnapArea => "Clear area",
+        TextKey::DetectMarkers => "Detect markers",
+        TextKey::DetectMarkersHover => {
+            "Click one marker of the scatter series: every blob of its color and shape becomes a candidate point, inside the snap area if one is set."
+        }
+        TextKey::DetectMarkersIntro => {
+            "Scatter plots: find all markers like a clicked one at once."
+        }
+        TextKey::AddMarkerPoints => "Add points",
+        TextKey::AddMarkerPointsHover => {
+            "Add the remaining candidates to the active curve, ordered by x on the image."
+        }
+        TextKey::SpectrumExport => "Spectrum (FFT)",
+        TextKey::SpectrumExportHover => {
+            "Resample the curve evenly and export its one-sided spectrum: frequency (cycles per x unit), amplitude and phase in degrees"
+        }
+        TextKey::LevelCrossingsSection => "Level crossings",
+        TextKey::CrossingLevel => "Level",
+        TextKey::CrossingLevelHover => {
+            "Y level whose crossings by the curve are reported, in each curve's own Y units"
+        }
+        TextKey::ShowCrossings => "Mark on image",
+        TextKey::ShowCrossingsHover => {
+            "Draw a diamond at every crossing of every curve over the image"
+        }
+        TextKey::NoCrossings => "The curve does not cross this level.",
+        TextKey::CrossingRising => "rising",
+        TextKey::CrossingFalling => "falling",
+        TextKey::CrossingsExport => "Level crossings",
+        TextKey::CrossingsExportHover => {
+            "Export the X positions where the evenly resampled curve crosses the level, with the crossing direction"
+        }
+        TextKey::StepBreakpoints => "Exact step breakpoints",
+        TextKey::StepBreakpointsHover => {
+            "Export only the corners of the step curve: each jump as two rows at the same X, the held value and the new one"
+        }
+        TextKey::UnstackLayers => "Unstack layers",
+        TextKey::UnstackLayersHover => {
+            "Curves are the upper boundaries of stacked layers, bottom first: export each one less the curve before it"
+        }
+        TextKey::GridSuggestsScale => "Grid suggests",
+        TextKey::GridSuggestsScaleHover => {
+            "The spacing of the detected grid lines fits this scale; click to use it"
+        }
+        TextKey::SmithChart => "Smith chart",
+        TextKey::CompleteCalibrationBeforeExportSmith => {
+            "Place the Smith chart centre and short-circuit point before exporting to"
+        }
+        TextKey::SmithCenter => "Centre (z = 1)",
+        TextKey::PickSmithCenterHover => {
+            "Click the centre of the chart, where the r = 1 circle crosses the real axis."
+        }
+        TextKey::SmithShort => "Short circuit (z = 0)",
+        TextKey::PickSmithShortHover => {
+            "Click the short-circuit end of the real axis on the outer circle; it fixes the chart radius and rotation."
+        }
+        TextKey::ReferenceImpedance => "Z0, Ω",
+        TextKey::ReferenceImpedanceHover => {
+            "Reference impedance the chart is normalized to; export scales r and x by it into ohms."
+        }
+        TextKey::SmithChartRadius => "Chart radius",
+        TextKey::SmithFitMissing => "Place the chart centre and the short-circuit point",
+        TextKey::SmithZeroRadius => "The short-circuit point sits on the centre",
+        TextKey::SmithBadReference => "Reference impedance must be a positive number",
+        TextKey::Resistance => "Resistance r",
+        TextKey::Reactance => "Reactance x",
+        TextKey::TimeInputFormat => "Input format:",
+        TextKey::TimeInputFormatHover => {
+            "strptime-style pattern for the values of this axis, e.g. %d %b %Y %H:%M (%d day, %m month, %b month name, %Y year, %H:%M:%S time, %p AM/PM, %z offset). Leave empty to accept the built-in formats only; they are still tried when the pattern does not match."
+        }
+        TextKey::TimeInputFormatAuto => "auto",
+        TextKey::UtcOffset => "UTC offset:",
+        TextKey::UtcOffsetHover => {
+            "Time zone of the values typed without an offset, e.g. +03:00 or -0530; they are converted to UTC. Empty is UTC."
+        }
+        TextKey::UtcOffsetInvalid => "Not a UTC offset: use +HH:MM, -HHMM or +H.",
+        TextKey::TimeExportFormat => "Time format:",
+        TextKey::TimeExportFormatHover => {
+            "How date/time columns are written: ISO-8601 text in UTC (native date-time cells where the format has them), Unix seconds, or Excel serial days."
+        }
+        TextKey::TimeExportUnixSeconds => "Unix seconds",
+        TextKey::TimeExportExcelSerial => "Excel serial",
+        TextKey::AxisName => "Name",
+        TextKey::AxisNameHover => {
+            "Header of this axis's column in CSV, XLSX and other tables, and its key in JSON; left empty, the export uses the name shown in grey."
+        }
+        TextKey::SmoothingFilter => "Smoothing filter",
+        TextKey::SmoothingFilterHover => {
+            "Moving average: plain mean of the window. Savitzky–Golay: local quadratic fit, keeps peaks from flattening. Median: middle value, removes isolated spikes and keeps steps sharp. LOESS: local straight-line fits over the 2n + 1 rows nearest in x, for uneven, noisy scatter"
+        }
+        TextKey::LoessIterations => "Robust passes",
+        TextKey::LoessIterationsHover => {
+            "Refits that weigh rows down by how far they fall from the previous fit, so outliers and misclicks stop pulling the curve (0 = plain LOESS)"
+        }
+        TextKey::Profiler => "Profiler",
+        TextKey::ProfilerHover => {
+            "Time per frame of snapping, transforms, exports and the UI, with a trace file for bug reports."
+        }
+        TextKey::ProfilerRecord => "Record",
+        TextKey::ProfilerRecordHover => {
+            "Collect profiler scopes; recording costs a little time per scope."
+        }
+        TextKey::ProfilerPause => "Pause",
+        TextKey::ProfilerSlowestFrame => "Slowest frame",
+        TextKey::ProfilerSlowestFrameHover => {
+            "Show the slowest recent frame instead of the latest one."
+        }
+        TextKey::ProfilerSaveTrace => "Save trace",
+        TextKey::ProfilerSaveTraceHover => {
+            "Write the recorded frames to a .puffin file next to the exports; open it with puffin_viewer."
+        }
+        TextKey::ProfilerNoFrames => "No frames recorded yet.",
+        TextKey::ProfilerScope => "Scope",
+        TextKey::ProfilerCalls => "Calls",
+        TextKey::CurveStatsSection => "Curve statistics",
+        TextKey::StatsNeedCalibration => "Complete the calibration to see curve statistics.",
+        TextKey::CurveMinimum => "Minimum",
+        TextKey::CurveMaximum => "Maximum",
+        TextKey::CurveMean => "Mean",
+        TextKey::CurveMeanHover => {
+            "Mean of y over the x span of the active curve, as the export's interpolation and smoothing draw it."
+        }
+        TextKey::ArcLength => "Arc length",
+        TextKey::ArcLengthHover => "Length of the curve in axis units of both x and y.",
+        TextKey::AreaBounds => "Area from",
+        TextKey::AreaBoundsHover => {
+            "x bounds of the area under the curve; the curve's whole span until changed."
+        }
+        TextKey::WholeCurve => "Whole curve",
+        TextKey::WholeCurveHover => "Integrate over the whole x span of the curve again.",
+        TextKey::AreaUnderCurve => "Area",
+        TextKey::AreaUnderCurveHover => {
+            "Signed area between the curve and y = 0 within the bounds, by the trapezoid rule; negative below the axis."
+        }
+        TextKey::AreaOutsideCurve => "The bounds do not overlap the curve.",
+        TextKey::CrossingsAtLevel => "Crosses y =",
+        TextKey::CrossingsAtLevelHover => {
+            "x of the crossings of the level set in the level crossings section."
+        }
+        TextKey::ShowDetails => "Show details",
+        TextKey::CopyDetails => "Copy details",
+        TextKey::SampleGridLabel => "X grid",
+        TextKey::SampleGridHover => {
+            "Where the export samples the curve in x. X values outside the picked points are skipped, not extrapolated."
+        }
+        TextKey::SampleXsHover => {
+            "X values separated by commas, semicolons, spaces or new lines; date-times by commas, semicolons or new lines."
+        }
+        TextKey::GridFrom => "From",
+        TextKey::GridTo => "To",
+        TextKey::GridStep => "Step",
+        TextKey::GridCurveRange => "Curve range",
+        TextKey::GridCurveRangeHover => "Span the grid over the x range of the active curve again",
+        TextKey::GridLogNeedsPositive => "Log spacing needs a positive start and end.",
+        TextKey::ExportXRange => "Limit X range",
+        TextKey::ExportXRangeHover => {
+            "Export only the part of the curve between two x values. Interpolation still uses every picked point, so the rows at the bounds are exact. On cartesian charts the bounds can also be dragged on the image."
+        }
+        TextKey::ExportXRangeMissesCurve => "The range does not overlap the active curve.",
+        TextKey::CropToPlot => "Crop",
+        TextKey::CropToPlotAction => {
+            "Find the plot frame in the image and suggest a crop around it with its labels."
+        }
+        TextKey::CropToPlotTogether => "Crops the image; points and calibration move with it.",
+        TextKey::PlotCropWindow => "Crop to the plot?",
+        TextKey::PlotCropIntro => {
+            "The image looks like a screenshot with a plot in it. Crop away the window around the plot? The dimmed parts are dropped."
+        }
+        TextKey::PlotCropSize => "Cropped size",
+        TextKey::KeepWholeImage => "Keep whole image",
+        TextKey::PlotCropAccept => "Crop image",
+        TextKey::IncludeConfidence => "Include confidence",
+        TextKey::IncludeConfidenceHover => {
+            "Add a confidence column with each point's snap confidence from 0 to 1, for weighting fits downstream; 0 for points placed or moved by hand"
+        }
+        TextKey::ShowPointConfidence => "Fade unsure points",
+        TextKey::ShowPointConfidenceHover => {
+            "Draw each point more transparent the lower its snap confidence, judged by the snap feature strength and the contrast of the stroke where it was placed; hand-placed points are the faintest"
+        }
+        TextKey::SelectPoints => "Select points",
+        TextKey::SelectPointsHover => {
+            "Drag a rectangle over points of the active curve, or hold Alt and draw a lasso around them; the arrow keys then nudge them all and Delete removes them"
+        }
+        TextKey::SelectedPointCountHover => "Points selected",
+        TextKey::DeleteSelectedPoints => "Delete selected",
+        TextKey::DeleteSelectedPointsHover => "Remove the selected points from the curve (Delete)",
+        TextKey::MoveSelectedPointsTo => "Move to curve",
+        TextKey::MoveSelectedPointsHover => "Move the selected points to the end of another curve",
+        TextKey::ClearPointSelection => "Deselect",
+        TextKey::LockPointHover => {
+            "Lock the point so Shift-drag, arrow keys and Delete cannot move or remove it"
+        }
+        TextKey::PointLockedByCurveHover => {
+            "Locked with its whole curve; unlock the curve in the curves panel"
+        }
+        TextKey::LockCurveHover => {
+            "Lock every point of the curve against dragging, nudging and deletion; new points can still be added"
+        }
+        TextKey::SplineSpan => "Spline span",
+        TextKey::SplineSpanHover => {
+            "How many point spacings the smoothing spline averages over; longer spans give a smoother curve. Points are weighted by their snap confidence, so unsure and hand-placed points pull the curve less"
+        }
+        TextKey::KeyboardShortcuts => "Keyboard shortcuts…",
+        TextKey::KeyboardShortcutsHover => {
+            "Rebind the keys for opening, exporting, undo, the side panel and the pick modes"
+        }
+        TextKey::KeymapWindow => "Keyboard shortcuts",
+        TextKey::KeymapIntro => {
+            "Click a shortcut and press the new key combination; Esc cancels. Ctrl stands for Cmd on macOS. Changes are saved to the [keymap] section of the config."
+        }
+        TextKey::KeymapRecording => "Press keys…",
+        TextKey::KeymapUnbound => "Not set",
+        TextKey::KeymapRecordHover => "Click, then press the new key combination",
+        TextKey::KeymapClearHover => "Remove the shortcut",
+        TextKey::KeymapResetHover => "Restore the default shortcut",
+        TextKey::KeymapResetAll => "Reset all",
+        TextKey::KeymapConflict => "Also bound to",
+        TextKey::ShortcutToggleSidePanel => "Show/hide side panel",
+        TextKey::ShortcutPickX1 => "Pick X1",
+        TextKey::ShortcutPickX2 => "Pick X2",
+        TextKey::ShortcutPickY1 => "Pick Y1",
+        TextKey::ShortcutPickY2 => "Pick Y2",
+        TextKey::CalibrationQuality => "Calibration quality",
+        TextKey::CalibrationQualityHover => {
+            "How precisely points can be read with this calibration. The same figures are written into the metadata of exports."
+        }
+        TextKey::AxisAngle => "Axis angle",
+        TextKey::AxisAngleHover => {
+            "Angle between the X and Y calibration lines; 90° on a square chart"
+        }
+        TextKey::AxisAngleSkewed => {
+            "The axes are not square: straighten the image with Level or Perspective."
+        }
+        TextKey::QualityScale => "Scale",
+        TextKey::QualityResolution => "Resolution",
+        TextKey::QualityResolutionHover => "Image pixels per axis unit, or per decade on log axes",
+        TextKey::QualityError => "Reading error",
+        TextKey::QualityErrorHover => {
+            "Value error of a point picked half a pixel off along the axis; relative on log axes"
+        }
+        TextKey::PixelsPerUnit => "px/unit",
+        TextKey::PixelsPerDecade => "px/decade",
+        TextKey::FileDialogFailed => {
+            "The system file dialog failed; showing the built-in file dialog instead."
+        }
     }
 }
 
@@ -1196,15 +3059,15 @@ const fn ru_text(key: TextKey) -> Option<&'static str> {
         TextKey::File => Some("Файл"),
         TextKey::Appearance => Some("Вид"),
         TextKey::OpenImage => Some("Открыть изображение…"),
-        TextKey::OpenImageHover => {
-            Some("Открыть изображение (Ctrl+O). Можно также перетащить его в центр.")
-        }
+        TextKey::OpenImageHover => Some("Открыть изображение или перетащить его в центр"),
         TextKey::PasteImage => Some("Вставить изображение"),
-        TextKey::PasteImageHover => Some("Вставить изображение из буфера обмена (Ctrl+V)"),
+        TextKey::PasteImageHover => Some(
+            "Вставить из буфера обмена изображение, скопированный файл изображения или проекта либо путь/URL изображения",
+        ),
         TextKey::LoadProject => Some("Загрузить проект…"),
-        TextKey::LoadProjectHover => Some("Загрузить сохранённый проект Curcat (Ctrl+Shift+P)"),
+        TextKey::LoadProjectHover => Some("Загрузить сохранённый проект Curcat"),
         TextKey::SaveProject => Some("Сохранить проект"),
-        TextKey::SaveProjectHover => Some("Сохранить текущую сессию как проект Curcat (Ctrl+S)"),
+        TextKey::SaveProjectHover => Some("Сохранить текущую сессию как проект Curcat"),
         TextKey::HideSide => Some("Скрыть панель"),
         TextKey::ShowSide => Some("Показать панель"),
         TextKey::HideSidePanel => Some("Скрыть боковую панель"),
@@ -1213,16 +3076,16 @@ const fn ru_text(key: TextKey) -> Option<&'static str> {
         TextKey::Left => Some("Слева"),
         TextKey::Right => Some("Справа"),
         TextKey::ToggleSidePanelHover => {
-            Some("Показать/скрыть боковую панель (Ctrl+B) и выбрать её сторону")
+            Some("Показать/скрыть боковую панель и выбрать её сторону")
         }
         TextKey::PointsStats => Some("Статистика точек"),
-        TextKey::PointsStatsHover => Some("Показать статистику выбранных точек"),
+        TextKey::PointsStatsHover => Some("Показать статистику и редактируемый список точек"),
         TextKey::Filters => Some("Фильтры"),
-        TextKey::FiltersHover => Some("Показать фильтры изображения (Ctrl+Shift+F)"),
+        TextKey::FiltersHover => Some("Показать фильтры изображения"),
         TextKey::AutoTrace => Some("Авто-трассировка"),
-        TextKey::AutoTraceHover => Some("Показать настройки авто-трассировки (Ctrl+Shift+T)"),
+        TextKey::AutoTraceHover => Some("Показать настройки авто-трассировки"),
         TextKey::ImageInfo => Some("Инфо об изображении"),
-        TextKey::ImageInfoHover => Some("Показать информацию о файле и изображении (Ctrl+I)"),
+        TextKey::ImageInfoHover => Some("Показать информацию о файле и изображении"),
         TextKey::TransformsTogether => Some("Преобразует изображение, точки и калибровку вместе."),
         TextKey::Rotate90Ccw => Some("Повернуть на 90° против часовой стрелки."),
         TextKey::Rotate90Cw => Some("Повернуть на 90° по часовой стрелке."),
@@ -1234,18 +3097,16 @@ const fn ru_text(key: TextKey) -> Option<&'static str> {
         TextKey::ZoomHover => Some("Выбрать предустановленный масштаб"),
         TextKey::ZoomPresetsHover => Some("Предустановки масштаба и команды Вписать/Сбросить вид"),
         TextKey::Fit => Some("Вписать"),
-        TextKey::FitHover => Some("Вписать изображение в область просмотра (Ctrl+F)"),
+        TextKey::FitHover => Some("Вписать изображение в область просмотра"),
         TextKey::ResetView => Some("Сбросить вид"),
-        TextKey::ResetViewHover => {
-            Some("Сбросить масштаб до 100% и панорамирование к началу (Ctrl+R)")
-        }
+        TextKey::ResetViewHover => Some("Сбросить масштаб до 100% и панорамирование к началу"),
         TextKey::PanWithMiddleButton => Some("Панорамирование средней кнопкой мыши"),
         TextKey::MmbPan => Some("Панорам. СКМ"),
         TextKey::MmbPanHover => Some("Включить/выключить панорамирование средней кнопкой"),
         TextKey::ClearPoints => Some("Очистить точки"),
-        TextKey::ClearPointsHover => Some("Очистить все точки (Ctrl+Shift+D)"),
+        TextKey::ClearPointsHover => Some("Очистить все точки"),
         TextKey::Undo => Some("Отменить"),
-        TextKey::UndoHover => Some("Отменить последнюю точку (Ctrl+Z)"),
+        TextKey::UndoHover => Some("Отменить последнюю точку"),
         TextKey::LanguageSwitcherHover => Some("Язык интерфейса"),
         TextKey::PointInput => Some("Ввод точек"),
         TextKey::Free => Some("Свободно"),
@@ -1308,9 +3169,9 @@ const fn ru_text(key: TextKey) -> Option<&'static str> {
         }
         TextKey::Calibration => Some("Калибровка"),
         TextKey::CoordinateSystem => Some("Система координат:"),
-        TextKey::CoordinateSystemHover => {
-            Some("Выберите декартову (X/Y) или полярную (угол/радиус) систему")
-        }
+        TextKey::CoordinateSystemHover => Some(
+            "Выберите декартову (X/Y), полярную (угол/радиус) или аффинную (X/Y по опорным точкам на перекошенных сканах) систему",
+        ),
         TextKey::Cartesian => Some("Декартова"),
         TextKey::Polar => Some("Полярная"),
         TextKey::CoordSystemForCalibrationExport => {
@@ -1551,10 +3412,787 @@ const fn ru_text(key: TextKey) -> Option<&'static str> {
         TextKey::PickedLabel => Some("Выбрано"),
         TextKey::LoadingImageWithName => Some("Загрузка изображения"),
         TextKey::LoadingImage => Some("Загрузка изображения…"),
-        TextKey::DropHint => Some(
-            "Перетащите сюда изображение, откройте файл или вставьте из буфера обмена (Ctrl+V).",
-        ),
+        TextKey::DropHint => {
+            Some("Перетащите сюда изображение, откройте файл или вставьте из буфера обмена")
+        }
         TextKey::Version => Some("Версия"),
+        TextKey::FlaggedPoints => Some("Отмеченные точки"),
+        TextKey::FlaggedPointsHover => {
+            Some("Просмотр точек, отмеченных как сомнительные (Alt+клик по точке — отметить)")
+        }
+        TextKey::NoFlaggedPoints => {
+            Some("Нет отмеченных точек. Alt+клик по точке на изображении, чтобы отметить её.")
+        }
+        TextKey::SelectFlaggedPointHint => {
+            Some("Выберите отмеченную точку, чтобы прочитать или добавить комментарии.")
+        }
+        TextKey::NoCommentsYet => Some("Комментариев пока нет."),
+        TextKey::CommentHint => Some("Напишите комментарий…"),
+        TextKey::AddComment => Some("Добавить комментарий"),
+        TextKey::Unflag => Some("Снять отметку"),
+        TextKey::UnflagHover => Some("Снять отметку и удалить комментарии этой точки"),
+        TextKey::Flagged => Some("Отмечено"),
+        TextKey::IncludeFlagAnnotations => Some("Включить аннотации отметок"),
+        TextKey::IncludeFlagAnnotationsHover => {
+            Some("Добавить текстовую колонку 'flag' с комментариями отмеченных точек")
+        }
+        TextKey::ExportProfile => Some("Профиль"),
+        TextKey::ExportProfileHover => Some(
+            "Именованные настройки экспорта из curcat.toml; * отмечает настройки, изменённые после выбора активного профиля, а Save profile сохраняет их",
+        ),
+        TextKey::NoExportProfile => Some("(нет)"),
+        TextKey::ProfileNameHint => Some("Имя профиля"),
+        TextKey::SaveProfile => Some("Сохранить профиль"),
+        TextKey::SaveProfileHover => Some(
+            "Сохранить текущие настройки экспорта под этим именем (профиль с тем же именем будет заменён)",
+        ),
+        TextKey::DeleteProfile => Some("Удалить"),
+        TextKey::DeleteProfileHover => Some("Удалить активный профиль экспорта"),
+        TextKey::ExportWithProfile => Some("Экспорт по профилю"),
+        TextKey::ExportWithProfileHover => {
+            Some("Экспорт в формате, сохранённом в активном профиле")
+        }
+        TextKey::Decimals => Some("Знаков после запятой"),
+        TextKey::DecimalsHover => Some("Число знаков после запятой для вещественных значений"),
+        TextKey::CsvDelimiter => Some("Разделитель CSV"),
+        TextKey::CsvDelimiterHover => Some("Разделитель полей при экспорте в CSV"),
+        TextKey::DelimiterComma => Some("Запятая (,)"),
+        TextKey::DelimiterSemicolon => Some("Точка с запятой (;)"),
+        TextKey::DelimiterTab => Some("Табуляция"),
+        TextKey::DelimiterPipe => Some("Вертикальная черта (|)"),
+        TextKey::CancelExportHover => {
+            Some("Остановить запись; частично записанный файл будет удалён")
+        }
+        TextKey::ExportOds => Some("Экспорт ODS…"),
+        TextKey::ExportArrow => Some("Экспорт Arrow IPC…"),
+        TextKey::ExportSql => Some("Экспорт SQL-скрипта…"),
+        TextKey::PastePoints => Some("Вставить точки"),
+        TextKey::PastePointsHover => Some(
+            "Вставить таблицу из двух колонок (CSV/TSV) из буфера обмена как опорные данные поверх изображения",
+        ),
+        TextKey::AppendPastedPoints => Some("Добавить к точкам"),
+        TextKey::AppendPastedPointsHover => {
+            Some("Превратить вставленные данные в обычные точки, чтобы продолжить кривую")
+        }
+        TextKey::AppendPastedPointsDisabledHover => {
+            Some("Завершите калибровку, чтобы разместить вставленные точки")
+        }
+        TextKey::ClearPastedPoints => Some("Убрать"),
+        TextKey::ClearPastedPointsHover => Some("Убрать наложение вставленных данных"),
+        TextKey::RecordCaptureTimes => Some("Записывать время захвата"),
+        TextKey::RecordCaptureTimesHover => Some(
+            "Отмечать каждую новую точку временем её постановки (для оцифровки самописца в реальном времени)",
+        ),
+        TextKey::IncludeCaptureTimes => Some("Добавить время захвата"),
+        TextKey::IncludeCaptureTimesHover => Some(
+            "Добавить столбец captured_at со временем постановки точки (UTC); пусто для точек без отметки времени",
+        ),
+        TextKey::CalibrationSandbox => Some("Песочница калибровки"),
+        TextKey::CalibrationSandboxHover => {
+            Some("Попробовать другие значения калибровки и сравнить диапазон данных до применения")
+        }
+        TextKey::CalibrationSandboxIntro => Some(
+            "Меняйте значения здесь, чтобы увидеть, как изменятся данные. Настоящая калибровка не меняется, пока вы не нажмёте «Применить».",
+        ),
+        TextKey::ApplySandbox => Some("Применить"),
+        TextKey::ApplySandboxHover => Some("Перенести эти значения в калибровку"),
+        TextKey::ResetSandbox => Some("Сбросить"),
+        TextKey::ResetSandboxHover => {
+            Some("Отбросить черновик и начать заново с текущей калибровки")
+        }
+        TextKey::FirstPoint => Some("Первая точка"),
+        TextKey::LastPoint => Some("Последняя точка"),
+        TextKey::SandboxCurrent => Some("Сейчас"),
+        TextKey::SandboxWhatIf => Some("Если так"),
+        TextKey::SandboxDraftInvalid => {
+            Some("Черновые значения не образуют корректную калибровку.")
+        }
+        TextKey::SignificantFigures => Some("Значащие цифры"),
+        TextKey::SignificantFiguresHover => Some(
+            "Форматировать каждый столбец с заданным числом значащих цифр относительно его диапазона; очень большие и малые значения записываются в экспоненциальной форме. Заменяет фиксированное число знаков после запятой.",
+        ),
+        TextKey::FocusMode => Some("Режим фокуса (затемнение)"),
+        TextKey::FocusModeHover => Some(
+            "Затемнить и обесцветить изображение везде, кроме области вокруг курсора и вдоль поставленных точек, чтобы слабые кривые было легче отслеживать.",
+        ),
+        TextKey::UnsavedChangesWindow => Some("Несохранённые изменения"),
+        TextKey::UnsavedChangesIntro => Some(
+            "У текущего изображения есть точки, калибровка или заметки, не сохранённые в проект. Сохранить их перед продолжением?",
+        ),
+        TextKey::UnsavedChangesCannotSave => {
+            Some("Изображение открыто не из файла, поэтому его нельзя сохранить как проект.")
+        }
+        TextKey::DiscardChanges => Some("Не сохранять"),
+        TextKey::DiscardChangesHover => Some("Продолжить, потеряв несохранённые изменения."),
+        TextKey::RestoreSessionWindow => Some("Восстановить прошлую сессию"),
+        TextKey::RestoreSessionIntro => {
+            Some("Открыть проект, с которым вы работали в прошлый раз?")
+        }
+        TextKey::ReopenProject => Some("Открыть"),
+        TextKey::StartEmpty => Some("Начать заново"),
+        TextKey::ProjectSettings => Some("Настройки проекта"),
+        TextKey::ProjectSettingsHover => Some(
+            "Значения конфигурации, сохраняемые в файл проекта; при открытии проекта они важнее curcat.toml",
+        ),
+        TextKey::KeepStylesInProject => Some("Стили наложения"),
+        TextKey::KeepStylesInProjectHover => Some(
+            "Сохранить в проекте стили кривой, точек, перекрестия и подсветки из текущей конфигурации",
+        ),
+        TextKey::KeepAutoPlaceInProject => Some("Параметры авто-постановки"),
+        TextKey::KeepAutoPlaceInProjectHover => {
+            Some("Сохранить в проекте текущие расстояния и интервалы авто-постановки")
+        }
+        TextKey::KeepExportInProject => Some("Настройки экспорта"),
+        TextKey::KeepExportInProjectHover => {
+            Some("Сохранить в проекте настройки блока экспорта и восстанавливать их при открытии")
+        }
+        TextKey::ColorSampling => Some("Выборка"),
+        TextKey::ColorSamplingHover => Some(
+            "Сколько пикселей вокруг курсора усредняет пипетка; большее окно даёт устойчивый цвет на сглаженных линиях.",
+        ),
+        TextKey::ClickToUseColor => Some("Щелчок — применить"),
+        TextKey::Curves => Some("Кривые"),
+        TextKey::ActiveCurveHover => Some("Новые точки ставятся на активную кривую."),
+        TextKey::CurveColorSwatchHover => {
+            Some("Цвет точек и отрезков кривой. Правый клик — сохранённые образцы.")
+        }
+        TextKey::CurveNameHover => {
+            Some("Имя кривой; используется для листов экспорта и колонки curve.")
+        }
+        TextKey::CurvePointCountHover => Some("Точек на этой кривой."),
+        TextKey::RemoveCurveHover => Some("Удалить кривую вместе с её точками."),
+        TextKey::AddCurve => Some("Добавить кривую"),
+        TextKey::AddCurveHover => {
+            Some("Начать ещё одну кривую на том же графике; она станет активной.")
+        }
+        TextKey::Swatches => Some("Образцы"),
+        TextKey::NoSwatches => Some("Сохранённых цветов пока нет"),
+        TextKey::ApplySwatchHover => Some("Использовать этот сохранённый цвет."),
+        TextKey::RemoveSwatchHover => Some("Удалить цвет из образцов проекта."),
+        TextKey::SwatchNameHint => Some("Имя, например «Серия A, красный»"),
+        TextKey::SaveSwatch => Some("Сохранить цвет"),
+        TextKey::SaveSwatchHover => {
+            Some("Сохранить цвет слева под этим именем; существующее имя перезаписывается.")
+        }
+        TextKey::AutoCalibrate => Some("Автокалибровка"),
+        TextKey::AutoCalibrateHover => {
+            Some("Найти оси, прочитать подписи делений и заполнить X1/X2/Y1/Y2 для подтверждения.")
+        }
+        TextKey::ProposedCalibration => Some("Предложено по подписям делений"),
+        TextKey::ProposedCalibrationHover => Some(
+            "Уверенность учитывает, насколько чётко прочитаны две подписи и сколько остальных подписей с ними согласуются; мелкие подписи считаются прочитанными менее чётко. Проверьте точки на изображении перед подтверждением.",
+        ),
+        TextKey::AxisNotFound => Some("не найдена"),
+        TextKey::AcceptProposal => Some("Принять"),
+        TextKey::AcceptProposalHover => Some("Оставить предложенную калибровку."),
+        TextKey::DiscardProposal => Some("Отменить"),
+        TextKey::DiscardProposalHover => Some("Вернуть калибровку, бывшую до предложения."),
+        TextKey::Affine => Some("Аффинная (3+ точки)"),
+        TextKey::CompleteCalibrationBeforeExportAffine => {
+            Some("Задайте как минимум три опорные точки перед экспортом в")
+        }
+        TextKey::SandboxAffineUnsupported => Some(
+            "Песочница работает с двухточечной калибровкой осей; переключитесь на декартову или полярную систему.",
+        ),
+        TextKey::ReferencePoints => Some("Опорные точки"),
+        TextKey::ReferencePointsHover => Some(
+            "Каждая опорная точка связывает позицию на изображении со значениями X и Y; отображение подбирается по всем заполненным строкам.",
+        ),
+        TextKey::AddReferencePoint => Some("Добавить точку"),
+        TextKey::AddReferencePointHover => {
+            Some("Добавить ещё одну опорную точку; лишние точки усредняют ошибки выбора.")
+        }
+        TextKey::RemoveReferencePointHover => Some("Удалить эту опорную точку."),
+        TextKey::PickReferencePointHover => {
+            Some("Щёлкните по изображению, чтобы поставить эту опорную точку.")
+        }
+        TextKey::AffineFitError => Some("Ошибка подгонки (СКО)"),
+        TextKey::AffineFitHover => Some(
+            "Расстояние между опорными точками и положением их значений по подобранному отображению.",
+        ),
+        TextKey::AffineTooFewPoints => Some("Нужны три заполненные опорные точки"),
+        TextKey::AffineCollinearPoints => Some("Опорные точки лежат на одной прямой"),
+        TextKey::AffineParallelAxes => Some("Значения X и Y меняются в одном направлении"),
+        TextKey::AffineEqualValues => Some("Все значения X или все значения Y равны"),
+        TextKey::AffineInvalidValues => Some("Значения не подходят к единицам или шкале оси"),
+        TextKey::AffineFitMissing => Some("Аффинное отображение ещё не подобрано"),
+        TextKey::LoadCurveMask => Some("Загрузить маску…"),
+        TextKey::LoadCurveMaskHover => Some(
+            "Открыть полутоновую маску модели сегментации. Она растягивается до размера изображения; белое — кривая.",
+        ),
+        TextKey::ClearCurveMask => Some("Убрать маску"),
+        TextKey::CurveMaskMissing => {
+            Some("Маска не загружена: без неё этот режим ничего не находит.")
+        }
+        TextKey::OpenCurveMaskDialogTitle => Some("Открыть маску кривой"),
+        TextKey::RunSegmentationModel => Some("Запустить модель…"),
+        TextKey::RunSegmentationModelHover => Some(
+            "Запустить ONNX-модель сегментации на изображении и взять её выход как маску. Модель принимает RGB в 0–1 формы [1, 3, H, W] и возвращает вероятность кривой формы [1, 1, H, W].",
+        ),
+        TextKey::OpenSegmentationModelDialogTitle => Some("Открыть модель сегментации"),
+        TextKey::SegmentationModelFilter => Some("Модели ONNX"),
+        TextKey::Perspective => Some("Перспектива"),
+        TextKey::PerspectiveAction => Some(
+            "Щёлкните четыре угла области графика, чтобы выпрямить снимок, сделанный под углом.",
+        ),
+        TextKey::PerspectiveTogether => {
+            Some("Деформирует изображение; точки и калибровка следуют за ним.")
+        }
+        TextKey::IgnoreTextLabels => Some("Игнорировать подписи"),
+        TextKey::IgnoreTextLabelsHover => {
+            Some("Не притягиваться к подписям осей, заголовкам и другому найденному тексту")
+        }
+        TextKey::CheckContinuity => Some("Проверить непрерывность"),
+        TextKey::CheckContinuityHover => Some(
+            "Отметить точки активной кривой, где она скачет намного сильнее, чем между соседними точками",
+        ),
+        TextKey::CurveFitSection => Some("Аппроксимация"),
+        TextKey::FitModelLabel => Some("Модель"),
+        TextKey::FitModelHover => {
+            Some("Модель, подбираемая к точкам активной кривой методом наименьших квадратов")
+        }
+        TextKey::PolynomialDegree => Some("Степень"),
+        TextKey::PolynomialDegreeHover => Some("Наибольшая степень x в полиноме"),
+        TextKey::FitNeedsCalibration => Some("Завершите калибровку, чтобы подобрать модель."),
+        TextKey::FitUnavailable => Some(
+            "Модель не подбирается к этим точкам (слишком мало точек или значения вне области определения модели).",
+        ),
+        TextKey::FittedCurve => Some("Аппроксимирующая кривая"),
+        TextKey::FittedCurveHover => {
+            Some("Экспортировать равномерные отсчёты подобранной модели (см. «Аппроксимация»)")
+        }
+        TextKey::IncludeFitColumn => Some("Добавить колонку модели"),
+        TextKey::IncludeFitColumnHover => Some(
+            "Добавить колонку 'fit' со значением подобранной модели в каждой экспортируемой точке x",
+        ),
+        TextKey::XAxisLooksInverted => Some("⚠ Значения X убывают вправо"),
+        TextKey::YAxisLooksInverted => Some("⚠ Значения Y убывают вверх"),
+        TextKey::AxisLooksInvertedHover => Some(
+            "Возможно, значения перепутаны местами — тогда экспортируемые данные будут зеркальными. Если ось на графике действительно обратная, отметьте «Обратная».",
+        ),
+        TextKey::SwapAxisValues => Some("Поменять значения"),
+        TextKey::SwapAxisValuesHover => {
+            Some("Поменять местами два значения, сохранив выбранные пиксели")
+        }
+        TextKey::ErrorBars => Some("Планки погрешностей"),
+        TextKey::ErrorBarsHover => Some(
+            "После постановки точки щёлкните по концам её планки погрешности; для симметричной планки хватит одного щелчка. Планки экспортируются столбцами y_err_lo / y_err_hi",
+        ),
+        TextKey::SwapEndpointsHover => Some(
+            "Поменять местами обе точки калибровки вместе с их значениями; само преобразование не меняется",
+        ),
+        TextKey::CopyXToY => Some("X → Y"),
+        TextKey::CopyXToYHover => Some(
+            "Скопировать тип, шкалу и значения оси X в ось Y (для квадратных графиков). Если точки Y ещё не выбраны, они ставятся в X1 и на расстоянии длины оси X вверх",
+        ),
+        TextKey::CopyCalibration => Some("Копировать калибровку"),
+        TextKey::CopyCalibrationHover => Some(
+            "Скопировать всю калибровку в буфер обмена, чтобы вставить в другой проект или окно Curcat",
+        ),
+        TextKey::PasteCalibration => Some("Вставить калибровку"),
+        TextKey::PasteCalibrationHover => {
+            Some("Заменить калибровку скопированной из другого проекта")
+        }
+        TextKey::LevelImage => Some("Выровнять"),
+        TextKey::LevelAction => Some(
+            "Щёлкните две точки, которые должны лежать на горизонтали (например, концы оси X), чтобы повернуть изображение.",
+        ),
+        TextKey::LevelTogether => {
+            Some("Поворачивает изображение на небольшой угол; точки и калибровка следуют за ним.")
+        }
+        TextKey::ResultPlot => Some("График результата"),
+        TextKey::ResultPlotHover => Some(
+            "Показать внизу график отмеченных точек и интерполированной кривой в откалиброванных координатах",
+        ),
+        TextKey::ResultPlotNeedsCalibration => {
+            Some("Завершите калибровку, чтобы построить график оцифрованных данных.")
+        }
+        TextKey::ExportParquet => Some("Экспорт Parquet…"),
+        TextKey::StitchScans => Some("Склеить сканы…"),
+        TextKey::StitchScansHover => {
+            Some("Склеить перекрывающиеся сканы одного длинного графика в одно изображение")
+        }
+        TextKey::StitchScansWindow => Some("Склейка сканов"),
+        TextKey::StitchScansIntro => Some(
+            "Сканы склеиваются в порядке списка; каждый должен перекрываться с предыдущим. Результат сохраняется в PNG рядом с первым сканом и открывается.",
+        ),
+        TextKey::StitchAlignment => Some("Совмещение"),
+        TextKey::StitchAlignmentHover => Some(
+            "Сдвиг: сравнение перекрытий целиком по корреляции. Особые точки: сопоставление углов, устойчивее к разному контрасту и полям.",
+        ),
+        TextKey::AddScans => Some("Добавить сканы…"),
+        TextKey::StitchAction => Some("Склеить"),
+        TextKey::StitchActionHover => Some("Нужно не меньше двух сканов"),
+        TextKey::CurvesFromLegend => Some("Из легенды"),
+        TextKey::CurvesFromLegendHover => Some(
+            "Щёлкните образец каждой записи легенды: кривая получит его цвет и текст подписи в качестве имени. Esc — завершить.",
+        ),
+        TextKey::SvgImportWindow => Some("Импорт SVG"),
+        TextKey::SvgImportIntro => Some(
+            "Векторное изображение растеризуется один раз в выбранном масштабе. Крупный масштаб даёт больше деталей для расстановки точек; масштаб сохраняется в проекте.",
+        ),
+        TextKey::SvgIntrinsicSize => Some("Исходный размер"),
+        TextKey::SvgRasterScale => Some("Масштаб"),
+        TextKey::SvgRasterSize => Some("Размер растра"),
+        TextKey::ImportSvg => Some("Импортировать"),
+        TextKey::ChartTextWindow => Some("Подписи графика"),
+        TextKey::ChartTextMenu => Some("Подписи графика…"),
+        TextKey::ChartTextMenuHover => {
+            Some("Изменить заголовок, подписи осей и описание или распознать их на изображении")
+        }
+        TextKey::ChartTextIntro => Some(
+            "Подписи осей становятся именами столбцов x/y при экспорте, заголовок — заголовком HTML-отчёта. Всё сохраняется в проекте.",
+        ),
+        TextKey::ChartTitle => Some("Заголовок"),
+        TextKey::XAxisTitle => Some("Ось X"),
+        TextKey::YAxisTitle => Some("Ось Y"),
+        TextKey::ChartDescription => Some("Описание"),
+        TextKey::ReadChartText => Some("Распознать"),
+        TextKey::ReadChartTextHover => Some("Щёлкните текст на изображении, чтобы распознать его"),
+        TextKey::SuppressGridLines => Some("Подавлять линии сетки"),
+        TextKey::SuppressGridLinesHover => Some(
+            "Приглушать длинные горизонтальные и вертикальные линии сетки перед привязкой, чтобы контрастная привязка не цеплялась за них",
+        ),
+        TextKey::Preprocess => Some("Предобработка"),
+        TextKey::PreprocessHover => Some("Очистка сканов: выравнивание фона и бинаризация"),
+        TextKey::PreprocessWindow => Some("Предобработка скана"),
+        TextKey::PreprocessIntro => Some(
+            "Очищает рабочую копию изображения перед фильтрами отображения и привязкой; файл не изменяется.",
+        ),
+        TextKey::PreprocessEnabled => Some("Применять предобработку"),
+        TextKey::PreprocessEnabledHover => Some("Отключите, чтобы сравнить с исходными пикселями"),
+        TextKey::FlattenBackground => Some("Выровнять фон"),
+        TextKey::FlattenBackgroundHover => {
+            Some("Убрать неравномерное освещение и оттенок бумаги, чтобы фон стал белым")
+        }
+        TextKey::BackgroundCell => Some("ячейка, px"),
+        TextKey::Binarization => Some("Бинаризация:"),
+        TextKey::BinarizationOff => Some("Нет"),
+        TextKey::BinarizationGlobal => Some("Общий порог"),
+        TextKey::BinarizationAdaptive => Some("Адаптивная (локальное среднее)"),
+        TextKey::AdaptiveWindow => Some("радиус окна, px"),
+        TextKey::AdaptiveOffset => Some("смещение"),
+        TextKey::AdaptiveOffsetHover => Some(
+            "Насколько пиксель должен быть темнее среднего по окрестности, чтобы считаться линией",
+        ),
+        TextKey::KeepInkColor => Some("Сохранять цвет линий"),
+        TextKey::KeepInkColorHover => Some(
+            "Оставлять пикселям линий их цвет вместо чёрного, чтобы привязка по цвету различала кривые",
+        ),
+        TextKey::ResetPreprocessing => Some("Сбросить предобработку"),
+        TextKey::LoadImageToPreprocess => Some("Загрузите изображение для предобработки."),
+        TextKey::FileDialogs => Some("Файловые диалоги"),
+        TextKey::FileDialogsHover => Some(
+            "Каким диалогом открывать и сохранять файлы: встроенным, системным с его быстрым доступом к папкам или диалогом GNOME/KDE через xdg-desktop-portal",
+        ),
+        TextKey::FileDialogsAuto => Some("Автоматически (портал во Flatpak/Snap)"),
+        TextKey::FileDialogsBuiltin => Some("Встроенные"),
+        TextKey::FileDialogsNative => Some("Системные"),
+        TextKey::FileDialogsPortal => Some("Портал рабочего стола"),
+        TextKey::ExportHistory => Some("История экспорта"),
+        TextKey::ExportHistoryHover => {
+            Some("Экспорты из этого проекта: открыть файл или папку либо записать заново")
+        }
+        TextKey::ExportHistoryWindow => Some("История экспорта"),
+        TextKey::ExportHistoryEmpty => Some("Из этого проекта ещё ничего не экспортировалось."),
+        TextKey::ExportedAt => Some("Время"),
+        TextKey::ExportFormat => Some("Формат"),
+        TextKey::ExportedFile => Some("Файл"),
+        TextKey::ExportedRows => Some("Строк"),
+        TextKey::OpenExportedFile => Some("Открыть"),
+        TextKey::OpenExportFolder => Some("Папка"),
+        TextKey::RepeatExport => Some("Повторить"),
+        TextKey::RepeatExportHover => {
+            Some("Записать текущие точки в тот же файл с настройками этого экспорта")
+        }
+        TextKey::ClearExportHistory => Some("Очистить историю"),
+        TextKey::SecondaryYAxis => Some("Вторая ось Y (Y')"),
+        TextKey::SecondaryYAxisCalibrationHover => Some(
+            "Вторая шкала значений графика с двумя осями, обычно справа. По ней считываются только кривые, отнесённые к Y' в блоке «Кривые».",
+        ),
+        TextKey::CurveOnSecondaryYHover => {
+            Some("Считывать кривую по второй оси Y (Y') вместо основной")
+        }
+        TextKey::OverwriteBackup => Some("Копия при перезаписи"),
+        TextKey::OverwriteBackupHover => Some(
+            "Если экспорт заменяет существующий файл, сначала переместить прежнюю версию в копию",
+        ),
+        TextKey::OverwriteBackupOff => Some("Нет"),
+        TextKey::OverwriteBackupBak => Some("имя.ext.bak"),
+        TextKey::OverwriteBackupTimestamped => Some("Копии с датой"),
+        TextKey::InvertedAxis => Some("Обратная"),
+        TextKey::InvertedAxisHover => Some(
+            "Значения на графике убывают вдоль оси (вправо по X, вверх по Y), как на осях глубины или давления. Отмечает обратное направление как задуманное; перед экспортом проверяется, что значения с ним согласуются.",
+        ),
+        TextKey::ValuesGrow => Some("рост значений"),
+        TextKey::XAxisMarkedInverted => {
+            Some("⚠ Ось X отмечена как обратная, но значения растут вправо")
+        }
+        TextKey::YAxisMarkedInverted => {
+            Some("⚠ Ось Y отмечена как обратная, но значения растут вверх")
+        }
+        TextKey::AxisMarkedInvertedHover => Some(
+            "Поменяйте значения, если они введены наоборот, или снимите «Обратная». Пока они не согласуются, экспорт недоступен.",
+        ),
+        TextKey::ValidationRules => Some("Правила проверки"),
+        TextKey::ValidationRulesHover => Some(
+            "Проверки экспортируемых строк перед экспортом; нарушенное правило останавливает экспорт или только предупреждает",
+        ),
+        TextKey::ValidationWindow => Some("Правила проверки"),
+        TextKey::ValidationIntro => Some(
+            "Каждая экспортируемая строка каждой кривой проверяется до записи файла. Правила сохраняются в проекте.",
+        ),
+        TextKey::AddValidationRule => Some("Добавить правило"),
+        TextKey::RemoveValidationRule => Some("Удалить правило"),
+        TextKey::RuleAtLeast => Some("≥"),
+        TextKey::RuleAtMost => Some("≤"),
+        TextKey::RuleStrictlyIncreasing => Some("строго возрастает"),
+        TextKey::RuleStrictlyDecreasing => Some("строго убывает"),
+        TextKey::RuleBlocks => Some("Останавливать экспорт"),
+        TextKey::RuleWarns => Some("Только предупреждать"),
+        TextKey::ValidationNotRun => Some("Правила будут проверены при следующем экспорте."),
+        TextKey::ValidationPassed => Some("Последний экспорт прошёл все правила."),
+        TextKey::ViolationCurve => Some("Кривая"),
+        TextKey::ViolationRow => Some("Строка"),
+        TextKey::ViolationValue => Some("Значение"),
+        TextKey::ViolationRule => Some("Правило"),
+        TextKey::MoreViolations => Some("Не показано строк:"),
+        TextKey::BridgeDashGaps => Some("Перекрывать разрывы пунктира"),
+        TextKey::BridgeDashGapsHover => Some(
+            "Если в радиусе ничего не найдено, провести линию через соседние штрихи или точки и привязаться к ней, чтобы щелчок между штрихами всё равно попадал на кривую",
+        ),
+        TextKey::FitCalibrationToReference => Some("Подогнать калибровку по опорным"),
+        TextKey::FitCalibrationToReferenceHover => Some(
+            "Сдвинуть точки калибровки вдоль осей (МНК) так, чтобы точки активной кривой давали значения вставленных опорных данных; полезно, когда риски делений размыты. Ставьте точки только там, где опорные значения заведомо верны.",
+        ),
+        TextKey::FitCalibrationToReferenceDisabledHover => {
+            Some("Нужны завершённая декартова калибровка и хотя бы две точки на активной кривой")
+        }
+        TextKey::FittedToReference => Some("Калибровка подогнана по опорным"),
+        TextKey::FittedToReferenceHover => Some(
+            "Точки калибровки сдвинуты так, чтобы поставленные точки совпали со вставленными опорными данными; введённые значения не менялись",
+        ),
+        TextKey::RmsResidual => Some("СКО невязки"),
+        TextKey::KeepFit => Some("Оставить"),
+        TextKey::RevertFit => Some("Откатить"),
+        TextKey::RevertFitHover => Some("Вернуть точки калибровки, какими они были до подгонки"),
+        TextKey::PointList => Some("Список точек"),
+        TextKey::PointListHint => Some(
+            "Перетащите или введите значение, чтобы сдвинуть точку; столбцы пикселей двигают её на изображении.",
+        ),
+        TextKey::PixelX => Some("пикс. X"),
+        TextKey::PixelY => Some("пикс. Y"),
+        TextKey::MovePointUp => Some("Выше"),
+        TextKey::MovePointDown => Some("Ниже"),
+        TextKey::InsertPointAfter => Some("Вставить точку посередине до следующей"),
+        TextKey::DeletePointRow => Some("Удалить точку"),
+        TextKey::CurveParameterHint => Some("парам."),
+        TextKey::CurveParameterHover => Some(
+            "Параметр семейства для этой кривой (например, температура); экспортируется отдельной колонкой, если он задан хотя бы у одной кривой.",
+        ),
+        TextKey::CurveParameterInvalid => {
+            Some("Не число; ячейка параметра этой кривой останется пустой.")
+        }
+        TextKey::ParameterName => Some("Имя параметра"),
+        TextKey::ParameterNameHover => {
+            Some("Заголовок экспортируемой колонки параметра; если пусто — «parameter».")
+        }
+        TextKey::LongFormatExport => Some("Длинный формат (одна таблица)"),
+        TextKey::LongFormatExportHover => Some(
+            "Сложить все кривые в одну таблицу с колонками кривой и параметра — также в Excel и JSON, где иначе кривые разделены. Подходит для подгонки поверхностей.",
+        ),
+        TextKey::SurfaceExport => Some("Поверхность:"),
+        TextKey::SurfaceExportHover => Some(
+            "Свести кривые со значением параметра в сетку по x и параметру. Каждая кривая пересчитывается с числом точек и интерполяцией, заданными выше; ячейки между кривыми интерполируются по параметру.",
+        ),
+        TextKey::SurfaceCsvMatrix => Some("Матрица CSV"),
+        TextKey::SurfaceNetCdf => Some("NetCDF"),
+        TextKey::Loupe => Some("Лупа"),
+        TextKey::LoupeHover => Some(
+            "Показывать рядом с курсором круглую вставку с шестикратным увеличением и перекрестием на том пикселе, куда попадёт щелчок.",
+        ),
+        TextKey::ExportNetCdf => Some("Экспорт NetCDF…"),
+        TextKey::FitWidth => Some("По ширине"),
+        TextKey::FitWidthHover => Some(
+            "Подобрать масштаб так, чтобы ширина изображения заняла область просмотра, не теряя текущую строку",
+        ),
+        TextKey::ZoomToRegion => Some("Масштаб по области"),
+        TextKey::ZoomToRegionHover => Some(
+            "Обведите прямоугольник на изображении, чтобы он заполнил область просмотра. Ctrl + перетаскивание делает то же в любой момент; Esc отменяет.",
+        ),
+        TextKey::ColumnTemplates => Some("Шаблоны колонок:"),
+        TextKey::ColumnTemplatesHover => Some(
+            "Колонки из [[export.columns]] в curcat.toml; выбор сохраняется в профилях экспорта",
+        ),
+        TextKey::IncludeAxisSteps => Some("Добавить dx, dy, шаг и наклон"),
+        TextKey::IncludeAxisStepsHover => Some(
+            "Шаг до предыдущей точки в единицах осей; на логарифмических осях — в декадах (dlog10_x / dlog10_y), поэтому наклон в log-log — показатель степени",
+        ),
+        TextKey::Smoothing => Some("Сглаживание"),
+        TextKey::SmoothingHover => Some(
+            "Окно сглаживания: столько строк с каждой стороны, всего 2n + 1; предпросмотр — поверх изображения и на графике результата. Отмеченные точки не меняются, сглаженные значения пишет только экспорт (0 — выкл.)",
+        ),
+        TextKey::MarkSnapArea => Some("Область привязки"),
+        TextKey::MarkSnapAreaHover => Some(
+            "Обведите прямоугольник или щёлкните углы многоугольника вокруг графика и нажмите Enter; привязка и авто-трассировка не учитывают всё, что снаружи.",
+        ),
+        TextKey::ClearSnapArea => Some("Сбросить область"),
+        TextKey::DetectMarkers => Some("Найти маркеры"),
+        TextKey::DetectMarkersHover => Some(
+            "Щёлкните один маркер серии: все пятна того же цвета и формы станут кандидатами в точки — внутри области привязки, если она задана.",
+        ),
+        TextKey::DetectMarkersIntro => {
+            Some("Точечные графики: найти сразу все маркеры, похожие на выбранный.")
+        }
+        TextKey::AddMarkerPoints => Some("Добавить точки"),
+        TextKey::AddMarkerPointsHover => {
+            Some("Добавить оставшихся кандидатов в активную кривую по порядку x на изображении.")
+        }
+        TextKey::SpectrumExport => Some("Спектр (БПФ)"),
+        TextKey::SpectrumExportHover => Some(
+            "Равномерно передискретизировать кривую и экспортировать её односторонний спектр: частоту (циклов на единицу x), амплитуду и фазу в градусах",
+        ),
+        TextKey::LevelCrossingsSection => Some("Пересечения уровня"),
+        TextKey::CrossingLevel => Some("Уровень"),
+        TextKey::CrossingLevelHover => Some(
+            "Уровень Y, пересечения которого кривой выводятся, — в единицах оси Y каждой кривой",
+        ),
+        TextKey::ShowCrossings => Some("Отметить на изображении"),
+        TextKey::ShowCrossingsHover => {
+            Some("Рисовать ромб в каждом пересечении каждой кривой поверх изображения")
+        }
+        TextKey::NoCrossings => Some("Кривая не пересекает этот уровень."),
+        TextKey::CrossingRising => Some("рост"),
+        TextKey::CrossingFalling => Some("спад"),
+        TextKey::CrossingsExport => Some("Пересечения уровня"),
+        TextKey::CrossingsExportHover => Some(
+            "Экспорт позиций X, в которых равномерно пересчитанная кривая пересекает уровень, с направлением пересечения",
+        ),
+        TextKey::StepBreakpoints => Some("Точные изломы ступенек"),
+        TextKey::StepBreakpointsHover => Some(
+            "Экспортировать только углы ступенчатой кривой: каждый скачок — двумя строками с одним X, прежним и новым значением",
+        ),
+        TextKey::UnstackLayers => Some("Разделить слои"),
+        TextKey::UnstackLayersHover => Some(
+            "Кривые — верхние границы слоёв накопительной диаграммы снизу вверх: каждая экспортируется за вычетом предыдущей",
+        ),
+        TextKey::GridSuggestsScale => Some("Сетка подсказывает"),
+        TextKey::GridSuggestsScaleHover => {
+            Some("Шаг найденных линий сетки соответствует этой шкале; щёлкните, чтобы применить её")
+        }
+        TextKey::SmithChart => Some("Диаграмма Смита"),
+        TextKey::CompleteCalibrationBeforeExportSmith => {
+            Some("Задайте центр диаграммы Смита и точку короткого замыкания перед экспортом в")
+        }
+        TextKey::SmithCenter => Some("Центр (z = 1)"),
+        TextKey::PickSmithCenterHover => {
+            Some("Щёлкните центр диаграммы, где окружность r = 1 пересекает действительную ось.")
+        }
+        TextKey::SmithShort => Some("Короткое замыкание (z = 0)"),
+        TextKey::PickSmithShortHover => Some(
+            "Щёлкните конец действительной оси на внешней окружности, соответствующий короткому замыканию; он задаёт радиус и поворот диаграммы.",
+        ),
+        TextKey::ReferenceImpedance => Some("Z0, Ом"),
+        TextKey::ReferenceImpedanceHover => Some(
+            "Опорное сопротивление, к которому нормирована диаграмма; при экспорте r и x умножаются на него для перевода в омы.",
+        ),
+        TextKey::SmithChartRadius => Some("Радиус диаграммы"),
+        TextKey::SmithFitMissing => Some("Задайте центр диаграммы и точку короткого замыкания"),
+        TextKey::SmithZeroRadius => Some("Точка короткого замыкания совпадает с центром"),
+        TextKey::SmithBadReference => {
+            Some("Опорное сопротивление должно быть положительным числом")
+        }
+        TextKey::Resistance => Some("Активное сопротивление r"),
+        TextKey::Reactance => Some("Реактивное сопротивление x"),
+        TextKey::TimeInputFormat => Some("Формат ввода:"),
+        TextKey::TimeInputFormatHover => Some(
+            "Шаблон в стиле strptime для значений этой оси, например %d %b %Y %H:%M (%d день, %m месяц, %b название месяца, %Y год, %H:%M:%S время, %p AM/PM, %z смещение). Оставьте пустым, чтобы принимать только встроенные форматы; они пробуются и тогда, когда шаблон не подошёл.",
+        ),
+        TextKey::TimeInputFormatAuto => Some("авто"),
+        TextKey::UtcOffset => Some("Смещение UTC:"),
+        TextKey::UtcOffsetHover => Some(
+            "Часовой пояс значений, введённых без смещения, например +03:00 или -0530; они переводятся в UTC. Пусто — UTC.",
+        ),
+        TextKey::UtcOffsetInvalid => {
+            Some("Неверное смещение UTC: используйте +ЧЧ:ММ, -ЧЧММ или +Ч.")
+        }
+        TextKey::TimeExportFormat => Some("Формат времени:"),
+        TextKey::TimeExportFormatHover => Some(
+            "Как записываются столбцы даты/времени: текст ISO-8601 в UTC (собственный тип даты, где формат его поддерживает), секунды Unix или порядковые дни Excel.",
+        ),
+        TextKey::TimeExportUnixSeconds => Some("Секунды Unix"),
+        TextKey::TimeExportExcelSerial => Some("Дни Excel"),
+        TextKey::AxisName => Some("Название"),
+        TextKey::AxisNameHover => Some(
+            "Заголовок столбца этой оси в CSV, XLSX и других таблицах и её ключ в JSON; если пусто, экспорт использует имя, показанное серым.",
+        ),
+        TextKey::SmoothingFilter => Some("Фильтр сглаживания"),
+        TextKey::SmoothingFilterHover => Some(
+            "Скользящее среднее — обычное среднее по окну. Савицкий–Голей — локальная квадратичная аппроксимация, не сплющивает пики. Медиана — среднее по порядку значение, убирает одиночные выбросы и сохраняет резкие ступени. LOESS — локальные линейные аппроксимации по 2n + 1 ближайшим по X строкам, для неравномерных зашумлённых точек",
+        ),
+        TextKey::LoessIterations => Some("Робастные проходы"),
+        TextKey::LoessIterationsHover => Some(
+            "Повторные аппроксимации, в которых вес строки падает с её отклонением от предыдущей кривой: выбросы и промахи перестают тянуть кривую (0 — обычный LOESS)",
+        ),
+        TextKey::Profiler => Some("Профилировщик"),
+        TextKey::ProfilerHover => Some(
+            "Время кадра для привязки, преобразований, экспорта и интерфейса и файл трассы для отчёта об ошибке.",
+        ),
+        TextKey::ProfilerRecord => Some("Запись"),
+        TextKey::ProfilerRecordHover => {
+            Some("Собирать замеры профилировщика; запись немного замедляет каждый замер.")
+        }
+        TextKey::ProfilerPause => Some("Пауза"),
+        TextKey::ProfilerSlowestFrame => Some("Самый медленный кадр"),
+        TextKey::ProfilerSlowestFrameHover => {
+            Some("Показывать самый медленный из недавних кадров вместо последнего.")
+        }
+        TextKey::ProfilerSaveTrace => Some("Сохранить трассу"),
+        TextKey::ProfilerSaveTraceHover => {
+            Some("Записать кадры в файл .puffin рядом с экспортом; он открывается в puffin_viewer.")
+        }
+        TextKey::ProfilerNoFrames => Some("Кадры ещё не записаны."),
+        TextKey::ProfilerScope => Some("Замер"),
+        TextKey::ProfilerCalls => Some("Вызовы"),
+        TextKey::CurveStatsSection => Some("Статистика кривой"),
+        TextKey::StatsNeedCalibration => {
+            Some("Завершите калибровку, чтобы увидеть статистику кривой.")
+        }
+        TextKey::CurveMinimum => Some("Минимум"),
+        TextKey::CurveMaximum => Some("Максимум"),
+        TextKey::CurveMean => Some("Среднее"),
+        TextKey::CurveMeanHover => Some(
+            "Среднее y по диапазону x активной кривой, какой её рисуют интерполяция и сглаживание экспорта.",
+        ),
+        TextKey::ArcLength => Some("Длина дуги"),
+        TextKey::ArcLengthHover => Some("Длина кривой в единицах осей x и y."),
+        TextKey::AreaBounds => Some("Площадь от"),
+        TextKey::AreaBoundsHover => {
+            Some("Границы по x для площади под кривой; пока не изменены — весь диапазон кривой.")
+        }
+        TextKey::WholeCurve => Some("Вся кривая"),
+        TextKey::WholeCurveHover => Some("Снова считать площадь по всему диапазону x кривой."),
+        TextKey::AreaUnderCurve => Some("Площадь"),
+        TextKey::AreaUnderCurveHover => Some(
+            "Площадь со знаком между кривой и y = 0 в границах, по формуле трапеций; ниже оси — отрицательная.",
+        ),
+        TextKey::AreaOutsideCurve => Some("Границы не пересекаются с кривой."),
+        TextKey::CrossingsAtLevel => Some("Пересекает y ="),
+        TextKey::CrossingsAtLevelHover => {
+            Some("x пересечений уровня, заданного в разделе пересечений уровня.")
+        }
+        TextKey::ShowDetails => Some("Подробности"),
+        TextKey::CopyDetails => Some("Скопировать"),
+        TextKey::SampleGridLabel => Some("Сетка X"),
+        TextKey::SampleGridHover => Some(
+            "В каких точках по X экспорт берёт значения кривой. Значения X за пределами отмеченных точек пропускаются, а не экстраполируются.",
+        ),
+        TextKey::SampleXsHover => Some(
+            "Значения X через запятую, точку с запятой, пробел или с новой строки; даты — через запятую, точку с запятой или с новой строки.",
+        ),
+        TextKey::GridFrom => Some("От"),
+        TextKey::GridTo => Some("До"),
+        TextKey::GridStep => Some("Шаг"),
+        TextKey::GridCurveRange => Some("Диапазон кривой"),
+        TextKey::GridCurveRangeHover => Some("Снова растянуть сетку на диапазон X активной кривой"),
+        TextKey::GridLogNeedsPositive => {
+            Some("Для логарифмической сетки начало и конец должны быть положительными.")
+        }
+        TextKey::ExportXRange => Some("Ограничить диапазон X"),
+        TextKey::ExportXRangeHover => Some(
+            "Экспортировать только часть кривой между двумя значениями X. Интерполяция по-прежнему использует все отмеченные точки, поэтому строки на границах точные. На декартовых графиках границы можно перетаскивать прямо на изображении.",
+        ),
+        TextKey::ExportXRangeMissesCurve => Some("Диапазон не пересекается с активной кривой."),
+        TextKey::CropToPlot => Some("Обрезать"),
+        TextKey::CropToPlotAction => Some(
+            "Найти рамку графика на изображении и предложить обрезку вокруг неё вместе с подписями.",
+        ),
+        TextKey::CropToPlotTogether => {
+            Some("Обрезает изображение; точки и калибровка сдвигаются вместе с ним.")
+        }
+        TextKey::PlotCropWindow => Some("Обрезать по графику?"),
+        TextKey::PlotCropIntro => Some(
+            "Изображение похоже на снимок экрана с графиком. Обрезать окно вокруг графика? Затемнённые части будут удалены.",
+        ),
+        TextKey::PlotCropSize => Some("Размер после обрезки"),
+        TextKey::KeepWholeImage => Some("Оставить целиком"),
+        TextKey::PlotCropAccept => Some("Обрезать изображение"),
+        TextKey::IncludeConfidence => Some("Добавить уверенность"),
+        TextKey::IncludeConfidenceHover => Some(
+            "Добавить столбец confidence с уверенностью привязки каждой точки от 0 до 1 — для взвешивания при дальнейшей обработке; 0 для точек, поставленных или сдвинутых вручную",
+        ),
+        TextKey::ShowPointConfidence => Some("Бледнить неуверенные точки"),
+        TextKey::ShowPointConfidenceHover => Some(
+            "Рисовать точку тем прозрачнее, чем ниже уверенность привязки — по силе признака привязки и контрасту линии в месте постановки; точки, поставленные вручную, самые бледные",
+        ),
+        TextKey::SelectPoints => Some("Выбрать точки"),
+        TextKey::SelectPointsHover => Some(
+            "Обведите точки активной кривой прямоугольником или, удерживая Alt, лассо; затем стрелки сдвигают их все, а Delete удаляет",
+        ),
+        TextKey::SelectedPointCountHover => Some("Выбрано точек"),
+        TextKey::DeleteSelectedPoints => Some("Удалить выбранные"),
+        TextKey::DeleteSelectedPointsHover => Some("Удалить выбранные точки с кривой (Delete)"),
+        TextKey::MoveSelectedPointsTo => Some("Перенести в кривую"),
+        TextKey::MoveSelectedPointsHover => Some("Перенести выбранные точки в конец другой кривой"),
+        TextKey::ClearPointSelection => Some("Снять выбор"),
+        TextKey::LockPointHover => Some(
+            "Заблокировать точку, чтобы Shift-перетаскивание, стрелки и Delete не могли её сдвинуть или удалить",
+        ),
+        TextKey::PointLockedByCurveHover => {
+            Some("Заблокирована вместе со всей кривой; снимите блокировку кривой в панели кривых")
+        }
+        TextKey::LockCurveHover => Some(
+            "Заблокировать все точки кривой от перетаскивания, сдвига и удаления; новые точки добавлять можно",
+        ),
+        TextKey::SplineSpan => Some("Ширина сплайна"),
+        TextKey::SplineSpanHover => Some(
+            "Сколько интервалов между точками усредняет сглаживающий сплайн; чем шире, тем глаже кривая. Точки взвешиваются по уверенности привязки, поэтому неуверенные и поставленные вручную точки меньше тянут кривую",
+        ),
+        TextKey::KeyboardShortcuts => Some("Сочетания клавиш…"),
+        TextKey::KeyboardShortcutsHover => Some(
+            "Переназначить клавиши открытия, экспорта, отмены, боковой панели и режимов выбора",
+        ),
+        TextKey::KeymapWindow => Some("Сочетания клавиш"),
+        TextKey::KeymapIntro => Some(
+            "Нажмите на сочетание, затем новые клавиши; Esc отменяет. Ctrl на macOS означает Cmd. Изменения сохраняются в раздел [keymap] конфигурации.",
+        ),
+        TextKey::KeymapRecording => Some("Нажмите клавиши…"),
+        TextKey::KeymapUnbound => Some("Не задано"),
+        TextKey::KeymapRecordHover => Some("Нажмите, затем введите новое сочетание клавиш"),
+        TextKey::KeymapClearHover => Some("Убрать сочетание"),
+        TextKey::KeymapResetHover => Some("Вернуть сочетание по умолчанию"),
+        TextKey::KeymapResetAll => Some("Сбросить все"),
+        TextKey::KeymapConflict => Some("Также назначено"),
+        TextKey::ShortcutToggleSidePanel => Some("Показать/скрыть боковую панель"),
+        TextKey::ShortcutPickX1 => Some("Выбрать X1"),
+        TextKey::ShortcutPickX2 => Some("Выбрать X2"),
+        TextKey::ShortcutPickY1 => Some("Выбрать Y1"),
+        TextKey::ShortcutPickY2 => Some("Выбрать Y2"),
+        TextKey::CalibrationQuality => Some("Качество калибровки"),
+        TextKey::CalibrationQualityHover => Some(
+            "Насколько точно читаются точки при этой калибровке. Те же значения записываются в метаданные экспорта.",
+        ),
+        TextKey::AxisAngle => Some("Угол между осями"),
+        TextKey::AxisAngleHover => {
+            Some("Угол между линиями калибровки X и Y; 90° у прямоугольного графика")
+        }
+        TextKey::AxisAngleSkewed => Some(
+            "Оси не перпендикулярны: исправьте изображение кнопкой «Выровнять» или «Перспектива».",
+        ),
+        TextKey::QualityScale => Some("Шкала"),
+        TextKey::QualityResolution => Some("Разрешение"),
+        TextKey::QualityResolutionHover => {
+            Some("Пикселей изображения на единицу оси, на логарифмической оси — на декаду")
+        }
+        TextKey::QualityError => Some("Погрешность отсчёта"),
+        TextKey::QualityErrorHover => Some(
+            "Ошибка значения точки, поставленной на полпикселя мимо вдоль оси; на логарифмической оси — относительная",
+        ),
+        TextKey::PixelsPerUnit => Some("пикс./ед."),
+        TextKey::PixelsPerDecade => Some("пикс./декаду"),
+        TextKey::FileDialogFailed => {
+            Some("Системный файловый диалог не открылся; показан встроенный диалог.")
+        }
     }
 }
 
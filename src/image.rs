@@ -1,19 +1,28 @@
 mod filters;
 mod load;
 mod meta;
+mod preprocess;
+mod sniff;
+mod stitch;
 mod transform;
 
 pub use filters::{ImageFilters, apply_image_filters};
+#[cfg(test)]
+pub use load::render_svg;
 pub use load::{
     ImageDecodeOptions, ImageLimitInfo, ImageLoadOutcome, ImageLoadPolicy, decode_image_from_bytes,
     decode_image_from_bytes_with_options, decode_image_from_clipboard_rgba,
     decode_image_from_clipboard_rgba_with_options, decode_image_from_path,
-    decode_image_from_path_with_options,
+    decode_image_from_path_with_options, is_svg_path, probe_svg_size, svg_scaled_size,
 };
 pub use meta::{
     ImageMeta, describe_aspect_ratio, format_system_time, human_readable_bytes, total_pixel_count,
 };
+pub use preprocess::{Binarization, Preprocessing, apply_preprocessing};
+pub use sniff::FormatMismatch;
+pub use stitch::{StitchMode, stitch_images};
 pub use transform::{
-    ImageTransformOp, ImageTransformRecord, LoadedImage, flip_color_image_horizontal,
-    flip_color_image_vertical, rotate_color_image_ccw, rotate_color_image_cw,
+    ImageTransformOp, ImageTransformRecord, LoadedImage, PerspectiveWarpRecord,
+    flip_color_image_horizontal, flip_color_image_vertical, level_quad, order_quad_corners,
+    rotate_color_image_ccw, rotate_color_image_cw, warp_perspective,
 };
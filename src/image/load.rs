@@ -1,8 +1,9 @@
+use super::sniff::FormatMismatch;
 use crate::config::{AppConfig, ImageLimits};
 use crate::util::u32_to_f32;
 use anyhow::Context as _;
 use egui::ColorImage;
-use image::{ImageReader, imageops::FilterType};
+use image::{ImageFormat, ImageReader, imageops::FilterType};
 use resvg::{tiny_skia, usvg};
 use std::io::{BufRead, Cursor, Read, Seek};
 use std::path::Path;
@@ -29,6 +30,9 @@ pub enum ImageLoadPolicy {
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ImageDecodeOptions {
     pub svg_min_render_size: Option<[u32; 2]>,
+    /// Rasterize SVG sources at this percentage of their intrinsic size instead of
+    /// upscaling them to `svg_min_render_size`.
+    pub svg_scale_percent: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -188,43 +192,69 @@ fn decode_image_data(
     options: ImageDecodeOptions,
 ) -> anyhow::Result<ImageLoadOutcome> {
     let cfg_limits = cfg.effective_image_limits();
-    let force_svg = path_hint
-        .and_then(Path::extension)
-        .and_then(|ext| ext.to_str())
-        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("svgz"));
+    let force_svg = path_hint.is_some_and(is_svg_path);
 
+    let mut svg_failure = None;
     if force_svg || looks_like_svg(bytes) {
         match parse_svg_tree(bytes, resources_dir) {
             Ok(tree) => {
                 return decode_svg_tree(&tree, &cfg_limits, policy, options);
             }
             Err(err) if force_svg => {
-                return Err(err.context("Failed to parse SVG data"));
+                svg_failure = Some(err.context("Failed to parse SVG data"));
             }
             Err(_) => {}
         }
     }
 
-    decode_raster_bytes(bytes, cfg_limits, policy)
+    // The contents decide first; the extension is the fallback for signature-less formats
+    // (TGA) and for contents that start like one format but are another.
+    let sniffed = image::guess_format(bytes).ok();
+    let named = path_hint.and_then(|path| ImageFormat::from_path(path).ok());
+    let mut raster_failure = None;
+    for format in [sniffed, named.filter(|named| Some(*named) != sniffed)]
+        .into_iter()
+        .flatten()
+    {
+        match decode_raster_bytes(bytes, format, &cfg_limits, policy) {
+            Ok(outcome) => return Ok(outcome),
+            Err(err) => {
+                raster_failure.get_or_insert(err);
+            }
+        }
+    }
+
+    let failure = raster_failure.or(svg_failure).unwrap_or_else(|| {
+        anyhow::Error::new(image::ImageError::Unsupported(
+            image::error::UnsupportedError::from_format_and_kind(
+                image::error::ImageFormatHint::Unknown,
+                image::error::UnsupportedErrorKind::Format(image::error::ImageFormatHint::Unknown),
+            ),
+        ))
+        .context("Failed to detect image format")
+    });
+    Err(FormatMismatch::explain(path_hint, bytes, failure)
+        .map_or_else(|err| err, anyhow::Error::new))
 }
 
 fn decode_raster_bytes(
     bytes: &[u8],
-    cfg_limits: ImageLimits,
+    format: ImageFormat,
+    cfg_limits: &ImageLimits,
     policy: ImageLoadPolicy,
 ) -> anyhow::Result<ImageLoadOutcome> {
-    let (width, height) = raster_dimensions(bytes)?;
+    let (width, height) = raster_dimensions(bytes, format)?;
     let metrics = source_metrics(width, height)?;
-    let decision = decide_plan(metrics, &cfg_limits, policy)?;
+    let decision = decide_plan(metrics, cfg_limits, policy)?;
 
     match decision {
         PlanDecision::Prompt(info) => Ok(ImageLoadOutcome::NeedsLimitDecision(info)),
         PlanDecision::Proceed(plan) => {
             let decode_limits = match plan {
-                DecodePlan::OriginalConfig => cfg_limits,
+                DecodePlan::OriginalConfig => cfg_limits.clone(),
                 DecodePlan::OriginalHard | DecodePlan::ResizeConfig(_) => HARD_LIMITS.clone(),
             };
-            let decoded = decode_raster_with_limits(bytes, &decode_limits)?;
+            let decoded = decode_raster_with_limits(bytes, format, &decode_limits)?;
             let output = match plan {
                 DecodePlan::ResizeConfig([target_w, target_h]) => {
                     dynamic_image_to_color_with_resize(&decoded, target_w, target_h)
@@ -244,9 +274,11 @@ fn decode_svg_tree(
     policy: ImageLoadPolicy,
     options: ImageDecodeOptions,
 ) -> anyhow::Result<ImageLoadOutcome> {
-    let source_size = tree.size().to_int_size();
-    let source_w = source_size.width();
-    let source_h = source_size.height();
+    let intrinsic = tree.size().to_int_size();
+    let intrinsic = [intrinsic.width(), intrinsic.height()];
+    let [source_w, source_h] = options
+        .svg_scale_percent
+        .map_or(intrinsic, |percent| svg_scaled_size(intrinsic, percent));
     let metrics = source_metrics(source_w, source_h)?;
     let decision = decide_plan(metrics, cfg_limits, policy)?;
 
@@ -258,14 +290,44 @@ fn decode_svg_tree(
                 DecodePlan::OriginalHard => ([source_w, source_h], &HARD_LIMITS),
                 DecodePlan::ResizeConfig(size) => (size, cfg_limits),
             };
-            let target =
-                upscale_svg_target_for_ui(base_target, options.svg_min_render_size, render_limits);
+            let target = if options.svg_scale_percent.is_some() {
+                base_target
+            } else {
+                upscale_svg_target_for_ui(base_target, options.svg_min_render_size, render_limits)
+            };
             let color = render_svg_to_color_image(tree, target[0], target[1], render_limits)?;
             Ok(ImageLoadOutcome::Ready(color))
         }
     }
 }
 
+/// Intrinsic size of `bytes` if they hold an SVG document, without rendering it.
+///
+/// `path_hint` forces SVG detection by extension, like the decoder does.
+pub fn probe_svg_size(bytes: &[u8], path_hint: Option<&Path>) -> Option<[u32; 2]> {
+    if !path_hint.is_some_and(is_svg_path) && !looks_like_svg(bytes) {
+        return None;
+    }
+    let tree = usvg::Tree::from_data(bytes, &usvg::Options::default()).ok()?;
+    let size = tree.size().to_int_size();
+    Some([size.width(), size.height()])
+}
+
+/// Raster size of an SVG with intrinsic `size` rendered at `percent` scale.
+pub fn svg_scaled_size(size: [u32; 2], percent: u32) -> [u32; 2] {
+    size.map(|side| {
+        let scaled = (u64::from(side) * u64::from(percent) + 50) / 100;
+        u32::try_from(scaled.max(1)).unwrap_or(u32::MAX)
+    })
+}
+
+/// Whether `path` has an `.svg` or `.svgz` extension.
+pub fn is_svg_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("svgz"))
+}
+
 fn parse_svg_tree(bytes: &[u8], resources_dir: Option<&Path>) -> anyhow::Result<usvg::Tree> {
     let mut options = usvg::Options {
         resources_dir: resources_dir.map(Path::to_path_buf),
@@ -275,6 +337,15 @@ fn parse_svg_tree(bytes: &[u8], resources_dir: Option<&Path>) -> anyhow::Result<
     usvg::Tree::from_data(bytes, &options).context("Invalid SVG content")
 }
 
+/// Rasterize `svg` at its intrinsic size, its text set in the system fonts.
+#[cfg(test)]
+pub fn render_svg(svg: &str) -> ColorImage {
+    let tree = parse_svg_tree(svg.as_bytes(), None).expect("valid SVG");
+    let size = tree.size().to_int_size();
+    render_svg_to_color_image(&tree, size.width(), size.height(), &HARD_LIMITS)
+        .expect("SVG renders")
+}
+
 fn shared_fontdb() -> Arc<usvg::fontdb::Database> {
     static FONT_DB: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
     FONT_DB
@@ -475,25 +546,24 @@ const fn fits_limits_u64(
         && rgba_bytes <= limits.alloc_bytes
 }
 
-fn raster_dimensions(bytes: &[u8]) -> anyhow::Result<(u32, u32)> {
-    let mut reader = ImageReader::new(Cursor::new(bytes))
-        .with_guessed_format()
-        .context("Failed to detect image format")?;
+fn raster_dimensions(bytes: &[u8], format: ImageFormat) -> anyhow::Result<(u32, u32)> {
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
     reader.no_limits();
     reader
         .into_dimensions()
-        .context("Failed to read image dimensions")
+        .with_context(|| format!("Failed to read {format:?} image dimensions"))
 }
 
 fn decode_raster_with_limits(
     bytes: &[u8],
+    format: ImageFormat,
     limits: &ImageLimits,
 ) -> anyhow::Result<image::DynamicImage> {
-    let mut reader = ImageReader::new(Cursor::new(bytes))
-        .with_guessed_format()
-        .context("Failed to detect image format")?;
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
     apply_raster_limits(&mut reader, limits);
-    reader.decode().context("Failed to decode image data")
+    reader
+        .decode()
+        .with_context(|| format!("Failed to decode {format:?} image data"))
 }
 
 fn apply_raster_limits<R>(reader: &mut ImageReader<R>, limits: &ImageLimits)
@@ -704,7 +774,7 @@ fn compute_autoscale_size(metrics: SourceMetrics, limits: &ImageLimits) -> Optio
     }
 }
 
-fn looks_like_svg(bytes: &[u8]) -> bool {
+pub(super) fn looks_like_svg(bytes: &[u8]) -> bool {
     if bytes.starts_with(&[0x1f, 0x8b]) {
         return true;
     }
@@ -904,6 +974,7 @@ mod tests {
             ImageLoadPolicy::AskUser,
             ImageDecodeOptions {
                 svg_min_render_size: Some([1_200, 800]),
+                ..ImageDecodeOptions::default()
             },
         )
         .unwrap();
@@ -923,6 +994,7 @@ mod tests {
             ImageLoadPolicy::AskUser,
             ImageDecodeOptions {
                 svg_min_render_size: Some([2_400, 1_800]),
+                ..ImageDecodeOptions::default()
             },
         )
         .unwrap();
@@ -931,4 +1003,102 @@ mod tests {
         };
         assert_eq!(color.size, [1_000, 500]);
     }
+
+    #[test]
+    fn svg_scale_overrides_viewport_upscale() {
+        let cfg = cfg_with_limits(10_000, 200_000_000, 1_000_000_000);
+        let svg = svg_bytes(400, 200);
+        let outcome = decode_image_from_bytes_with_options(
+            &cfg,
+            &svg,
+            ImageLoadPolicy::AskUser,
+            ImageDecodeOptions {
+                svg_min_render_size: Some([2_400, 1_800]),
+                svg_scale_percent: Some(150),
+            },
+        )
+        .unwrap();
+        let ImageLoadOutcome::Ready(color) = outcome else {
+            panic!("Expected ready image");
+        };
+        assert_eq!(color.size, [600, 300]);
+    }
+
+    #[test]
+    fn svg_scale_beyond_limits_prompts() {
+        let cfg = cfg_with_limits(1_000, 1_000_000, 200_000_000);
+        let svg = svg_bytes(400, 200);
+        let outcome = decode_image_from_bytes_with_options(
+            &cfg,
+            &svg,
+            ImageLoadPolicy::AskUser,
+            ImageDecodeOptions {
+                svg_scale_percent: Some(400),
+                ..ImageDecodeOptions::default()
+            },
+        )
+        .unwrap();
+        let ImageLoadOutcome::NeedsLimitDecision(info) = outcome else {
+            panic!("Expected limit prompt");
+        };
+        assert_eq!([info.source_width, info.source_height], [1_600, 800]);
+    }
+
+    #[test]
+    fn probes_svg_size_without_rendering() {
+        assert_eq!(probe_svg_size(&svg_bytes(9, 7), None), Some([9, 7]));
+        assert_eq!(probe_svg_size(b"\x89PNG\r\n", None), None);
+        assert_eq!(svg_scaled_size([9, 7], 250), [23, 18]);
+        assert_eq!(svg_scaled_size([9, 7], 1), [1, 1]);
+    }
+
+    fn encoded(format: ImageFormat) -> Vec<u8> {
+        let image = image::RgbaImage::from_pixel(6, 4, image::Rgba([10, 20, 30, 255]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).unwrap();
+        bytes.into_inner()
+    }
+
+    fn decode_named(name: &str, bytes: &[u8]) -> anyhow::Result<ImageLoadOutcome> {
+        let dir = std::env::temp_dir().join(format!("curcat_load_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        let outcome =
+            decode_image_from_path(&AppConfig::default(), &path, ImageLoadPolicy::AskUser);
+        let _ = std::fs::remove_file(&path);
+        outcome
+    }
+
+    #[test]
+    fn misnamed_and_signatureless_rasters_fall_back_to_another_decoder() {
+        let png = encoded(ImageFormat::Png);
+        for name in ["plot.svg", "plot.jpg", "plot"] {
+            let outcome = decode_named(name, &png).unwrap();
+            assert!(
+                matches!(outcome, ImageLoadOutcome::Ready(ref c) if c.size == [6, 4]),
+                "{name}"
+            );
+        }
+        // TGA has no signature: only the extension can name it.
+        let tga = encoded(ImageFormat::Tga);
+        assert!(matches!(
+            decode_named("plot.tga", &tga).unwrap(),
+            ImageLoadOutcome::Ready(_)
+        ));
+        assert!(decode_named("plot", &tga).is_err());
+    }
+
+    #[test]
+    fn undecodable_files_report_the_container_they_hold() {
+        let err = decode_named("plot.png", b"%PDF-1.7\n1 0 obj").unwrap_err();
+        let mismatch = err.downcast_ref::<FormatMismatch>().expect("mismatch");
+        assert_eq!(mismatch.named, Some("PNG"));
+        assert_eq!(mismatch.actual.map(|c| c.mime), Some("application/pdf"));
+
+        let mut truncated = encoded(ImageFormat::Png);
+        truncated.truncate(40);
+        let err = decode_named("plot.png", &truncated).unwrap_err();
+        assert!(err.downcast_ref::<FormatMismatch>().is_none(), "{err:#}");
+    }
 }
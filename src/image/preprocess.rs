@@ -0,0 +1,335 @@
+//! Scan cleanup run on the working pixels before display filters and snapping.
+//!
+//! Background flattening divides every pixel by a smooth estimate of the paper around it, so
+//! uneven lighting, yellowed paper and texture turn into plain white. Binarization then
+//! separates ink from paper, either against one global level or against the local mean, and
+//! paints everything that is not ink white; JPEG speckle around the strokes goes with it.
+
+use egui::{Color32, ColorImage};
+use rayon::prelude::*;
+
+/// How ink is told apart from paper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Binarization {
+    #[default]
+    Off,
+    /// One luminance level for the whole image.
+    Global,
+    /// Darker than the mean of the surrounding window by more than an offset.
+    Adaptive,
+}
+
+/// Scan preprocessing settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Preprocessing {
+    /// Master switch, so the cleanup can be compared against the original at a click.
+    pub enabled: bool,
+    pub flatten_background: bool,
+    /// Size of the cells the paper brightness is estimated in, in pixels.
+    pub background_cell: u32,
+    pub binarization: Binarization,
+    /// Luminance below which a pixel is ink, for [`Binarization::Global`].
+    pub threshold: f32,
+    /// Half-size of the averaging window, for [`Binarization::Adaptive`].
+    pub window_radius: u32,
+    /// How much darker than the local mean ink must be, for [`Binarization::Adaptive`].
+    pub offset: f32,
+    /// Keep the color of ink pixels instead of painting them black, so color snap still works.
+    pub keep_ink_color: bool,
+}
+
+impl Default for Preprocessing {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            flatten_background: true,
+            background_cell: 32,
+            binarization: Binarization::Off,
+            threshold: 0.6,
+            window_radius: 15,
+            offset: 0.08,
+            keep_ink_color: true,
+        }
+    }
+}
+
+impl Preprocessing {
+    pub fn sanitized(self) -> Self {
+        Self {
+            background_cell: self.background_cell.clamp(8, 256),
+            threshold: self.threshold.clamp(0.0, 1.0),
+            window_radius: self.window_radius.clamp(2, 64),
+            offset: self.offset.clamp(0.0, 0.5),
+            ..self
+        }
+    }
+
+    pub fn is_identity(self) -> bool {
+        !self.enabled || (!self.flatten_background && self.binarization == Binarization::Off)
+    }
+}
+
+/// Apply the preprocessing settings to a base image.
+pub fn apply_preprocessing(base: &ColorImage, settings: Preprocessing) -> ColorImage {
+    if base.pixels.is_empty() || settings.is_identity() {
+        return base.clone();
+    }
+    let settings = settings.sanitized();
+    let mut image = if settings.flatten_background {
+        flatten_background(base, settings.background_cell as usize)
+    } else {
+        base.clone()
+    };
+    match settings.binarization {
+        Binarization::Off => {}
+        Binarization::Global => {
+            let threshold = settings.threshold;
+            let ink: Vec<bool> = luminance(&image)
+                .into_iter()
+                .map(|l| l < threshold)
+                .collect();
+            paint_binary(&mut image, &ink, settings.keep_ink_color);
+        }
+        Binarization::Adaptive => {
+            let ink = adaptive_ink(&image, settings.window_radius as usize, settings.offset);
+            paint_binary(&mut image, &ink, settings.keep_ink_color);
+        }
+    }
+    image
+}
+
+/// Divide each pixel by the paper color estimated around it.
+#[allow(clippy::cast_precision_loss)]
+fn flatten_background(image: &ColorImage, cell: usize) -> ColorImage {
+    let [width, height] = image.size;
+    let cells = paper_cells(image, cell);
+    let (cols, rows) = (width.div_ceil(cell), height.div_ceil(cell));
+    // Cell estimates sit at cell centers; pixels between them interpolate bilinearly.
+    let axis = |pos: usize, count: usize| {
+        let t = ((pos as f32 + 0.5) / cell as f32 - 0.5).clamp(0.0, (count - 1) as f32);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let lo = t.floor() as usize;
+        (lo, (lo + 1).min(count - 1), t - t.floor())
+    };
+
+    let mut out = image.clone();
+    out.pixels
+        .par_chunks_mut(width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let (r0, r1, ty) = axis(y, rows);
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let (c0, c1, tx) = axis(x, cols);
+                let mut paper = [0.0_f32; 3];
+                for (channel, value) in paper.iter_mut().enumerate() {
+                    let at = |r: usize, c: usize| cells[r * cols + c][channel];
+                    let top = (at(r0, c1) - at(r0, c0)).mul_add(tx, at(r0, c0));
+                    let bottom = (at(r1, c1) - at(r1, c0)).mul_add(tx, at(r1, c0));
+                    *value = (bottom - top).mul_add(ty, top);
+                }
+                let [r, g, b, a] = pixel.to_array();
+                let flat = |v: u8, paper: f32| float_to_u8(f32::from(v) / 255.0 / paper.max(0.01));
+                *pixel = Color32::from_rgba_unmultiplied(
+                    flat(r, paper[0]),
+                    flat(g, paper[1]),
+                    flat(b, paper[2]),
+                    a,
+                );
+            }
+        });
+    out
+}
+
+/// Paper color of each `cell`×`cell` block: the 90th percentile of every channel, high enough
+/// to skip the ink in a block and low enough to ignore a few bright specks.
+fn paper_cells(image: &ColorImage, cell: usize) -> Vec<[f32; 3]> {
+    let [width, height] = image.size;
+    let (cols, rows) = (width.div_ceil(cell), height.div_ceil(cell));
+    (0..rows * cols)
+        .into_par_iter()
+        .map(|idx| {
+            let (cx, cy) = (idx % cols, idx / cols);
+            let mut histograms = [[0_u32; 256]; 3];
+            let mut count = 0_u32;
+            for y in cy * cell..((cy + 1) * cell).min(height) {
+                for x in cx * cell..((cx + 1) * cell).min(width) {
+                    let [r, g, b, _] = image.pixels[y * width + x].to_array();
+                    histograms[0][usize::from(r)] += 1;
+                    histograms[1][usize::from(g)] += 1;
+                    histograms[2][usize::from(b)] += 1;
+                    count += 1;
+                }
+            }
+            let rank = count - count / 10;
+            histograms.map(|histogram| {
+                let mut seen = 0;
+                let level = histogram
+                    .iter()
+                    .position(|&n| {
+                        seen += n;
+                        seen >= rank
+                    })
+                    .unwrap_or(255);
+                f32::from(u8::try_from(level).unwrap_or(u8::MAX)) / 255.0
+            })
+        })
+        .collect()
+}
+
+/// Pixels darker than the mean of their `(2·radius + 1)²` window by more than `offset`.
+#[allow(clippy::cast_precision_loss)]
+fn adaptive_ink(image: &ColorImage, radius: usize, offset: f32) -> Vec<bool> {
+    let [width, height] = image.size;
+    let luma = luminance(image);
+    // Summed-area table with a zero first row and column.
+    let stride = width + 1;
+    let mut integral = vec![0.0_f64; stride * (height + 1)];
+    for y in 0..height {
+        let mut row_sum = 0.0;
+        for x in 0..width {
+            row_sum += f64::from(luma[y * width + x]);
+            integral[(y + 1) * stride + x + 1] = integral[y * stride + x + 1] + row_sum;
+        }
+    }
+    (0..width * height)
+        .into_par_iter()
+        .map(|idx| {
+            let (x, y) = (idx % width, idx / width);
+            let (x0, x1) = (x.saturating_sub(radius), (x + radius + 1).min(width));
+            let (y0, y1) = (y.saturating_sub(radius), (y + radius + 1).min(height));
+            let sum = integral[y1 * stride + x1]
+                - integral[y0 * stride + x1]
+                - integral[y1 * stride + x0]
+                + integral[y0 * stride + x0];
+            #[allow(clippy::cast_possible_truncation)]
+            let mean = (sum / ((x1 - x0) * (y1 - y0)) as f64) as f32;
+            luma[idx] < mean - offset
+        })
+        .collect()
+}
+
+/// Paint paper white and ink black, or leave ink its own color.
+fn paint_binary(image: &mut ColorImage, ink: &[bool], keep_ink_color: bool) {
+    for (pixel, &is_ink) in image.pixels.iter_mut().zip(ink) {
+        let alpha = pixel.a();
+        if !is_ink {
+            *pixel = Color32::from_rgba_unmultiplied(255, 255, 255, alpha);
+        } else if !keep_ink_color {
+            *pixel = Color32::from_rgba_unmultiplied(0, 0, 0, alpha);
+        }
+    }
+}
+
+#[allow(clippy::suboptimal_flops)]
+fn luminance(image: &ColorImage) -> Vec<f32> {
+    image
+        .pixels
+        .iter()
+        .map(|pixel| {
+            let [r, g, b, _] = pixel.to_array();
+            (0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b)) / 255.0
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn float_to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVE: Color32 = Color32::from_rgb(30, 60, 200);
+
+    /// A blue line across paper that darkens from left to right, with salt-and-pepper specks.
+    fn uneven_scan() -> ColorImage {
+        let (width, height) = (240, 120);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let shade = 250 - u8::try_from(x * 90 / width).unwrap_or(90);
+                let speck = if (x * 7 + y * 13) % 29 == 0 { 12 } else { 0 };
+                let paper = shade - speck;
+                pixels.push(Color32::from_rgb(paper, paper, paper - 10));
+            }
+        }
+        let mut image = ColorImage::new([width, height], pixels);
+        for x in 0..width {
+            for y in 59..62 {
+                let [r, g, b, _] = CURVE.to_array();
+                let shade = 250 - u8::try_from(x * 90 / width).unwrap_or(90);
+                let dim = |c: u8| u8::try_from(u32::from(c) * u32::from(shade) / 250).unwrap_or(c);
+                image.pixels[y * width + x] = Color32::from_rgb(dim(r), dim(g), dim(b));
+            }
+        }
+        image
+    }
+
+    fn settings(binarization: Binarization) -> Preprocessing {
+        Preprocessing {
+            enabled: true,
+            binarization,
+            ..Preprocessing::default()
+        }
+    }
+
+    #[test]
+    fn flattening_evens_out_the_paper() {
+        let image = uneven_scan();
+        let width = image.size[0];
+        let out = apply_preprocessing(&image, settings(Binarization::Off));
+        let (left, right) = (out.pixels[20 * width + 10], out.pixels[20 * width + 225]);
+        assert!(left.r() >= 245 && right.r() >= 245, "{left:?} {right:?}");
+        // The curve keeps its hue on both sides.
+        for x in [10, 225] {
+            let curve = out.pixels[60 * width + x];
+            assert!(curve.b() > 150 && curve.r() < 80, "{curve:?} at x = {x}");
+        }
+    }
+
+    #[test]
+    fn binarization_whitens_paper_and_keeps_the_ink() {
+        let image = uneven_scan();
+        let [width, height] = image.size;
+        for binarization in [Binarization::Global, Binarization::Adaptive] {
+            let out = apply_preprocessing(&image, settings(binarization));
+            for y in (0..height).filter(|y| !(57..64).contains(y)) {
+                for x in 0..width {
+                    assert_eq!(
+                        out.pixels[y * width + x],
+                        Color32::WHITE,
+                        "{binarization:?} paper at ({x}, {y})"
+                    );
+                }
+            }
+            for x in 0..width {
+                assert_ne!(
+                    out.pixels[60 * width + x],
+                    Color32::WHITE,
+                    "{binarization:?}"
+                );
+            }
+        }
+        let black = Preprocessing {
+            keep_ink_color: false,
+            ..settings(Binarization::Adaptive)
+        };
+        assert_eq!(
+            apply_preprocessing(&image, black).pixels[60 * width + 100],
+            Color32::BLACK
+        );
+    }
+
+    #[test]
+    fn disabled_settings_leave_the_image_alone() {
+        let image = uneven_scan();
+        let off = Preprocessing {
+            enabled: false,
+            ..settings(Binarization::Adaptive)
+        };
+        assert!(off.is_identity());
+        assert_eq!(apply_preprocessing(&image, off).pixels, image.pixels);
+    }
+}
@@ -0,0 +1,216 @@
+//! What a file actually contains, from its leading bytes, next to what its extension claims.
+//!
+//! Decoding picks the format from the contents and falls back to the extension; when both fail,
+//! [`FormatMismatch`] tells the user which container was found instead of a bare decode error.
+
+use super::load::looks_like_svg;
+use image::ImageFormat;
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+
+/// A file format recognised from its signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Container {
+    pub name: &'static str,
+    pub mime: &'static str,
+    /// Whether this build can open it.
+    pub supported: bool,
+}
+
+/// Signatures of formats people commonly rename to an image extension by mistake.
+const FOREIGN_SIGNATURES: &[(&[u8], Container)] = &[
+    (b"%PDF", foreign("PDF", "application/pdf")),
+    (b"%!PS", foreign("PostScript", "application/postscript")),
+    (b"8BPS", foreign("Photoshop", "image/vnd.adobe.photoshop")),
+    (b"\xff\x0a", foreign("JPEG XL", "image/jxl")),
+    (b"\0\0\0\x0cJXL ", foreign("JPEG XL", "image/jxl")),
+    (b"PK\x03\x04", foreign("ZIP archive", "application/zip")),
+];
+
+/// HEIF brands of the ISO media `ftyp` box; AVIF is recognised by the `image` crate itself.
+const HEIF_BRANDS: &[&[u8; 4]] = &[b"heic", b"heix", b"hevc", b"hevx", b"mif1", b"msf1"];
+
+const fn foreign(name: &'static str, mime: &'static str) -> Container {
+    Container {
+        name,
+        mime,
+        supported: false,
+    }
+}
+
+/// Display name of a raster format.
+pub fn format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "PNG",
+        ImageFormat::Jpeg => "JPEG",
+        ImageFormat::Gif => "GIF",
+        ImageFormat::WebP => "WebP",
+        ImageFormat::Tiff => "TIFF",
+        ImageFormat::Bmp => "BMP",
+        ImageFormat::Ico => "ICO",
+        ImageFormat::Tga => "TGA",
+        ImageFormat::Pnm => "PNM",
+        ImageFormat::Hdr => "HDR",
+        ImageFormat::Dds => "DDS",
+        ImageFormat::Avif => "AVIF",
+        ImageFormat::OpenExr => "OpenEXR",
+        ImageFormat::Qoi => "QOI",
+        _ => format.extensions_str().first().copied().unwrap_or("image"),
+    }
+}
+
+/// The format `path`'s extension names, when it is an image extension.
+pub fn named_format(path: &Path) -> Option<&'static str> {
+    if super::is_svg_path(path) {
+        return Some("SVG");
+    }
+    ImageFormat::from_path(path).ok().map(format_name)
+}
+
+/// The container `bytes` hold, by signature; `None` when nothing known matches.
+pub fn sniff_container(bytes: &[u8]) -> Option<Container> {
+    if let Ok(format) = image::guess_format(bytes) {
+        return Some(Container {
+            name: format_name(format),
+            mime: format.to_mime_type(),
+            supported: format.reading_enabled(),
+        });
+    }
+    if let Some((_, container)) = FOREIGN_SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+    {
+        return Some(*container);
+    }
+    if bytes.get(4..8) == Some(b"ftyp")
+        && bytes
+            .get(8..12)
+            .is_some_and(|brand| HEIF_BRANDS.iter().any(|heif| brand == *heif))
+    {
+        return Some(foreign("HEIC", "image/heic"));
+    }
+    if looks_like_svg(bytes) {
+        return Some(Container {
+            name: "SVG",
+            mime: "image/svg+xml",
+            supported: true,
+        });
+    }
+    let head: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .skip_while(u8::is_ascii_whitespace)
+        .take(16)
+        .map(|byte| byte.to_ascii_lowercase())
+        .collect();
+    (head.starts_with(b"<!doctype html") || head.starts_with(b"<html"))
+        .then_some(foreign("HTML page", "text/html"))
+}
+
+/// Every decoder failed and the contents are not what the extension says, or not an image
+/// this build opens.
+#[derive(Debug, Error)]
+pub struct FormatMismatch {
+    /// Format the file name claims.
+    pub named: Option<&'static str>,
+    /// Container found in the contents.
+    pub actual: Option<Container>,
+    #[source]
+    source: anyhow::Error,
+}
+
+impl FormatMismatch {
+    /// `source` explained by the contents; handed back as is when they are what the name
+    /// promises.
+    pub fn explain(
+        path_hint: Option<&Path>,
+        bytes: &[u8],
+        source: anyhow::Error,
+    ) -> Result<Self, anyhow::Error> {
+        let named = path_hint.and_then(named_format);
+        let actual = sniff_container(bytes);
+        let mismatched = match (named, actual) {
+            (_, Some(actual)) if !actual.supported => true,
+            (Some(named), actual) => actual.is_none_or(|actual| actual.name != named),
+            (None, _) => false,
+        };
+        if mismatched {
+            Ok(Self {
+                named,
+                actual,
+                source,
+            })
+        } else {
+            Err(source)
+        }
+    }
+}
+
+impl fmt::Display for FormatMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.named, self.actual) {
+            (Some(named), Some(actual)) => write!(
+                f,
+                "file named as {named} contains {} ({})",
+                actual.name, actual.mime
+            ),
+            (Some(named), None) => {
+                write!(
+                    f,
+                    "file named as {named} does not contain a known image format"
+                )
+            }
+            (None, Some(actual)) => write!(
+                f,
+                "file contains {} ({}), which is not a supported image",
+                actual.name, actual.mime
+            ),
+            (None, None) => f.write_str("file is not a known image format"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_images_and_common_impostors() {
+        let png = sniff_container(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR").expect("png");
+        assert_eq!(
+            (png.name, png.mime, png.supported),
+            ("PNG", "image/png", true)
+        );
+        assert_eq!(sniff_container(b"%PDF-1.7\n%").map(|c| c.name), Some("PDF"));
+        assert_eq!(
+            sniff_container(b"\0\0\0\x18ftypheic\0\0\0\0").map(|c| c.mime),
+            Some("image/heic")
+        );
+        assert_eq!(
+            sniff_container(b"\n <!DOCTYPE html><html>").map(|c| c.name),
+            Some("HTML page")
+        );
+        assert_eq!(sniff_container(b"just some text"), None);
+    }
+
+    #[test]
+    fn mismatch_needs_contents_other_than_the_name() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        let source = || anyhow::anyhow!("decode failed");
+        let broken = FormatMismatch::explain(Some(Path::new("plot.png")), png, source());
+        assert!(broken.is_err(), "a broken PNG named .png is just damaged");
+
+        let renamed = FormatMismatch::explain(Some(Path::new("plot.jpg")), png, source())
+            .expect("PNG named as JPEG");
+        assert_eq!(renamed.named, Some("JPEG"));
+        assert_eq!(
+            renamed.to_string(),
+            "file named as JPEG contains PNG (image/png)"
+        );
+
+        let pdf = FormatMismatch::explain(None, b"%PDF-1.4", source()).expect("PDF bytes");
+        assert_eq!(pdf.actual.map(|c| c.supported), Some(false));
+        assert!(FormatMismatch::explain(None, b"???", source()).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Stitching of overlapping scans of one chart into a single image.
+//!
+//! Consecutive scans are aligned pairwise by a pure translation, which is what a flatbed
+//! or sheet-fed scanner produces when a long strip chart is scanned in parts. The offset is
+//! found either by normalized cross-correlation over a coarse-to-fine image pyramid, or by
+//! matching corner features and taking the offset most matches agree on; both end with a
+//! full-resolution correlation refinement. Later scans are drawn over earlier ones.
+
+use crate::profiling::profile_function;
+use egui::{Color32, ColorImage};
+
+/// Largest side of the coarsest pyramid level searched exhaustively.
+const COARSE_MAX_DIM: usize = 64;
+/// Largest side of the level features are detected on.
+const FEATURE_MAX_DIM: usize = 1024;
+/// Smallest overlap considered, as a fraction of the smaller scan's area.
+const MIN_OVERLAP_FRACTION: f64 = 0.05;
+/// Search radius, in pixels, around an offset carried over from a coarser estimate.
+const REFINE_RADIUS: isize = 2;
+/// Correlation an accepted alignment must reach.
+const MIN_MATCH_SCORE: f64 = 0.5;
+/// Overlaps with less intensity variance than this are blank paper and cannot be matched.
+const MIN_VARIANCE: f64 = 1.0e-6;
+const HARRIS_K: f64 = 0.04;
+const HARRIS_WINDOW_RADIUS: usize = 2;
+/// Corners weaker than this fraction of the strongest one are ignored.
+const HARRIS_REL_THRESHOLD: f64 = 0.01;
+const MAX_FEATURES: usize = 400;
+const NON_MAX_RADIUS: usize = 3;
+const PATCH_RADIUS: usize = 5;
+/// Lowe's ratio test: the best match must be clearly closer than the second best.
+const MATCH_RATIO: f64 = 0.8;
+const INLIER_RADIUS_PX: f64 = 2.0;
+const MIN_INLIERS: usize = 5;
+
+/// How consecutive scans are aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StitchMode {
+    /// Correlate the images directly; best for clean scans with plain overlaps.
+    #[default]
+    Translation,
+    /// Match corner features; copes better with exposure changes and large offsets.
+    Features,
+}
+
+impl StitchMode {
+    pub const ALL: [Self; 2] = [Self::Translation, Self::Features];
+}
+
+/// Stitch `parts`, in order, into one image; each part must overlap the previous one.
+pub fn stitch_images(parts: &[ColorImage], mode: StitchMode) -> anyhow::Result<ColorImage> {
+    profile_function!();
+    if parts.len() < 2 {
+        anyhow::bail!("Stitching needs at least two scans.");
+    }
+    let grays: Vec<Gray> = parts.iter().map(Gray::from_color).collect();
+    let mut origins = vec![[0isize, 0isize]];
+    for (idx, pair) in grays.windows(2).enumerate() {
+        let Some(offset) = align_pair(&pair[0], &pair[1], mode) else {
+            anyhow::bail!(
+                "Scans {} and {} could not be aligned; check that they overlap.",
+                idx + 1,
+                idx + 2
+            );
+        };
+        let prev = origins[idx];
+        origins.push([prev[0] + offset[0], prev[1] + offset[1]]);
+    }
+    Ok(compose(parts, &origins))
+}
+
+/// Offset of `b`'s top-left corner in `a`'s pixel frame.
+fn align_pair(a: &Gray, b: &Gray, mode: StitchMode) -> Option<[isize; 2]> {
+    let guess = match mode {
+        StitchMode::Translation => pyramid_offset(a, b)?,
+        StitchMode::Features => feature_offset(a, b)?,
+    };
+    let (offset, score) = refine_offset(a, b, guess, REFINE_RADIUS)?;
+    (score >= MIN_MATCH_SCORE).then_some(offset)
+}
+
+fn compose(parts: &[ColorImage], origins: &[[isize; 2]]) -> ColorImage {
+    let extent = |axis: usize| {
+        let lo = origins.iter().map(|o| o[axis]).min().unwrap_or(0);
+        let hi = parts
+            .iter()
+            .zip(origins)
+            .map(|(part, o)| o[axis] + to_isize(part.size[axis]))
+            .max()
+            .unwrap_or(0);
+        (lo, hi)
+    };
+    let (x0, x1) = extent(0);
+    let (y0, y1) = extent(1);
+    let width = (x1 - x0).unsigned_abs();
+    let height = (y1 - y0).unsigned_abs();
+    let mut pixels = vec![Color32::WHITE; width * height];
+    for (part, origin) in parts.iter().zip(origins) {
+        let left = (origin[0] - x0).unsigned_abs();
+        let top = (origin[1] - y0).unsigned_abs();
+        let [w, h] = part.size;
+        for row in 0..h {
+            let dst = (top + row) * width + left;
+            pixels[dst..dst + w].copy_from_slice(&part.pixels[row * w..(row + 1) * w]);
+        }
+    }
+    ColorImage::new([width, height], pixels)
+}
+
+/// Luminance image in `0.0..=1.0` used for alignment.
+#[derive(Clone)]
+struct Gray {
+    width: usize,
+    height: usize,
+    data: Vec<f32>,
+}
+
+impl Gray {
+    fn from_color(image: &ColorImage) -> Self {
+        let data = image
+            .pixels
+            .iter()
+            .map(|c| {
+                let [r, g, b, _] = c.to_array();
+                (0.0722f32.mul_add(
+                    f32::from(b),
+                    0.2126f32.mul_add(f32::from(r), 0.7152 * f32::from(g)),
+                )) / 255.0
+            })
+            .collect();
+        Self {
+            width: image.size[0],
+            height: image.size[1],
+            data,
+        }
+    }
+
+    fn at(&self, x: usize, y: usize) -> f32 {
+        self.data[y * self.width + x]
+    }
+
+    /// Half-size image made of 2×2 box averages.
+    fn downsample(&self) -> Self {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = (2 * x, 2 * y);
+                let (nx, ny) = ((sx + 1).min(self.width - 1), (sy + 1).min(self.height - 1));
+                let sum = self.at(sx, sy) + self.at(nx, sy) + self.at(sx, ny) + self.at(nx, ny);
+                data.push(sum * 0.25);
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    const fn max_dim(&self) -> usize {
+        if self.width > self.height {
+            self.width
+        } else {
+            self.height
+        }
+    }
+}
+
+/// Halve both images together until the larger fits `max_dim`; returns the levels, finest first.
+fn pyramid(a: &Gray, b: &Gray, max_dim: usize) -> Vec<(Gray, Gray)> {
+    let mut levels = vec![(a.clone(), b.clone())];
+    loop {
+        let (la, lb) = levels.last().expect("pyramid has a level");
+        if la.max_dim().max(lb.max_dim()) <= max_dim || la.width.min(la.height) < 16 {
+            return levels;
+        }
+        let next = (la.downsample(), lb.downsample());
+        levels.push(next);
+    }
+}
+
+/// Exhaustive correlation search on the coarsest level, refined level by level.
+fn pyramid_offset(a: &Gray, b: &Gray) -> Option<[isize; 2]> {
+    let levels = pyramid(a, b, COARSE_MAX_DIM);
+    let (ca, cb) = levels.last()?;
+    let mut best: Option<([isize; 2], f64)> = None;
+    for dy in -to_isize(cb.height) + 1..to_isize(ca.height) {
+        for dx in -to_isize(cb.width) + 1..to_isize(ca.width) {
+            if let Some(score) = correlation(ca, cb, [dx, dy])
+                && best.is_none_or(|(_, s)| score > s)
+            {
+                best = Some(([dx, dy], score));
+            }
+        }
+    }
+    let mut offset = best?.0;
+    for (la, lb) in levels
+        .iter()
+        .rev()
+        .skip(1)
+        .take(levels.len().saturating_sub(2))
+    {
+        offset = refine_offset(la, lb, [offset[0] * 2, offset[1] * 2], REFINE_RADIUS)?.0;
+    }
+    // The finest level is refined by the caller.
+    Some(if levels.len() > 1 {
+        [offset[0] * 2, offset[1] * 2]
+    } else {
+        offset
+    })
+}
+
+/// Best-correlated offset within `radius` pixels of `guess`.
+fn refine_offset(
+    a: &Gray,
+    b: &Gray,
+    guess: [isize; 2],
+    radius: isize,
+) -> Option<([isize; 2], f64)> {
+    let mut best: Option<([isize; 2], f64)> = None;
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            let offset = [guess[0] + dx, guess[1] + dy];
+            if let Some(score) = correlation(a, b, offset)
+                && best.is_none_or(|(_, s)| score > s)
+            {
+                best = Some((offset, score));
+            }
+        }
+    }
+    best
+}
+
+/// Normalized cross-correlation of the overlap when `b` sits at `offset` in `a`'s frame.
+fn correlation(a: &Gray, b: &Gray, offset: [isize; 2]) -> Option<f64> {
+    let [dx, dy] = offset;
+    let x0 = dx.max(0);
+    let y0 = dy.max(0);
+    let x1 = to_isize(a.width).min(dx + to_isize(b.width));
+    let y1 = to_isize(a.height).min(dy + to_isize(b.height));
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+    let (width, height) = ((x1 - x0).unsigned_abs(), (y1 - y0).unsigned_abs());
+    let smaller = (a.width * a.height).min(b.width * b.height);
+    if (width * height) < min_overlap(smaller) {
+        return None;
+    }
+    let (ax, ay) = (x0.unsigned_abs(), y0.unsigned_abs());
+    let (bx, by) = ((x0 - dx).unsigned_abs(), (y0 - dy).unsigned_abs());
+    let (mut sa, mut sb, mut saa, mut sbb, mut sab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for row in 0..height {
+        let ra = &a.data[(ay + row) * a.width + ax..][..width];
+        let rb = &b.data[(by + row) * b.width + bx..][..width];
+        for (&va, &vb) in ra.iter().zip(rb) {
+            let (va, vb) = (f64::from(va), f64::from(vb));
+            sa += va;
+            sb += vb;
+            saa = va.mul_add(va, saa);
+            sbb = vb.mul_add(vb, sbb);
+            sab = va.mul_add(vb, sab);
+        }
+    }
+    let n = usize_to_f64(width * height);
+    let var_a = sa.mul_add(-sa / n, saa) / n;
+    let var_b = sb.mul_add(-sb / n, sbb) / n;
+    if var_a < MIN_VARIANCE || var_b < MIN_VARIANCE {
+        return None;
+    }
+    Some(sa.mul_add(-sb / n, sab) / n / (var_a * var_b).sqrt())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn min_overlap(area: usize) -> usize {
+    (usize_to_f64(area) * MIN_OVERLAP_FRACTION).ceil() as usize
+}
+
+struct Feature {
+    x: usize,
+    y: usize,
+    /// Zero-mean, unit-length patch around the corner.
+    descriptor: Vec<f64>,
+}
+
+/// Offset most matched corner pairs agree on, scaled back to full resolution.
+fn feature_offset(a: &Gray, b: &Gray) -> Option<[isize; 2]> {
+    let levels = pyramid(a, b, FEATURE_MAX_DIM);
+    let scale = 1usize << (levels.len() - 1);
+    let (la, lb) = levels.last()?;
+    let fa = detect_features(la);
+    let fb = detect_features(lb);
+    let matches = match_features(&fa, &fb);
+
+    let mut best: Option<(usize, [f64; 2])> = None;
+    for &(ia, ib) in &matches {
+        let candidate = feature_delta(&fa[ia], &fb[ib]);
+        let inliers: Vec<[f64; 2]> = matches
+            .iter()
+            .map(|&(ja, jb)| feature_delta(&fa[ja], &fb[jb]))
+            .filter(|d| (d[0] - candidate[0]).hypot(d[1] - candidate[1]) <= INLIER_RADIUS_PX)
+            .collect();
+        if best.is_none_or(|(count, _)| inliers.len() > count) {
+            let n = usize_to_f64(inliers.len());
+            let mean = inliers
+                .iter()
+                .fold([0.0, 0.0], |acc, d| [acc[0] + d[0] / n, acc[1] + d[1] / n]);
+            best = Some((inliers.len(), mean));
+        }
+    }
+    let (count, mean) = best?;
+    if count < MIN_INLIERS {
+        return None;
+    }
+    let scale = usize_to_f64(scale);
+    Some([round_isize(mean[0] * scale), round_isize(mean[1] * scale)])
+}
+
+fn feature_delta(a: &Feature, b: &Feature) -> [f64; 2] {
+    [
+        usize_to_f64(a.x) - usize_to_f64(b.x),
+        usize_to_f64(a.y) - usize_to_f64(b.y),
+    ]
+}
+
+/// Harris corners with non-maximum suppression, strongest first.
+fn detect_features(gray: &Gray) -> Vec<Feature> {
+    let (width, height) = (gray.width, gray.height);
+    let margin = PATCH_RADIUS.max(HARRIS_WINDOW_RADIUS + 1);
+    if width <= 2 * margin || height <= 2 * margin {
+        return Vec::new();
+    }
+    let mut ixx = vec![0.0f64; width * height];
+    let mut iyy = vec![0.0f64; width * height];
+    let mut ixy = vec![0.0f64; width * height];
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let gx = f64::from(gray.at(x + 1, y) - gray.at(x - 1, y)) * 0.5;
+            let gy = f64::from(gray.at(x, y + 1) - gray.at(x, y - 1)) * 0.5;
+            ixx[y * width + x] = gx * gx;
+            iyy[y * width + x] = gy * gy;
+            ixy[y * width + x] = gx * gy;
+        }
+    }
+    let (sxx, syy, sxy) = (
+        integral(&ixx, width, height),
+        integral(&iyy, width, height),
+        integral(&ixy, width, height),
+    );
+    let radius = HARRIS_WINDOW_RADIUS;
+    let mut response = vec![0.0f64; width * height];
+    for y in margin..height - margin {
+        for x in margin..width - margin {
+            let (gxx, gyy, gxy) = (
+                box_sum(&sxx, width, x - radius, y - radius, x + radius, y + radius),
+                box_sum(&syy, width, x - radius, y - radius, x + radius, y + radius),
+                box_sum(&sxy, width, x - radius, y - radius, x + radius, y + radius),
+            );
+            let trace = gxx + gyy;
+            response[y * width + x] =
+                (HARRIS_K * trace).mul_add(-trace, gxx.mul_add(gyy, -gxy * gxy));
+        }
+    }
+    let strongest = response.iter().copied().fold(0.0, f64::max);
+    if strongest <= 0.0 {
+        return Vec::new();
+    }
+    let threshold = strongest * HARRIS_REL_THRESHOLD;
+    let mut corners = Vec::new();
+    for y in margin..height - margin {
+        for x in margin..width - margin {
+            let value = response[y * width + x];
+            if value < threshold {
+                continue;
+            }
+            let is_peak = (y.saturating_sub(NON_MAX_RADIUS)..=(y + NON_MAX_RADIUS).min(height - 1))
+                .all(|ny| {
+                    (x.saturating_sub(NON_MAX_RADIUS)..=(x + NON_MAX_RADIUS).min(width - 1)).all(
+                        |nx| {
+                            let other = response[ny * width + nx];
+                            match other.total_cmp(&value) {
+                                std::cmp::Ordering::Less => true,
+                                // Plateaus keep only their first pixel.
+                                std::cmp::Ordering::Equal => (ny, nx) >= (y, x),
+                                std::cmp::Ordering::Greater => false,
+                            }
+                        },
+                    )
+                });
+            if is_peak {
+                corners.push((value, x, y));
+            }
+        }
+    }
+    corners.sort_by(|l, r| r.0.total_cmp(&l.0));
+    corners
+        .into_iter()
+        .take(MAX_FEATURES)
+        .filter_map(|(_, x, y)| {
+            let descriptor = patch_descriptor(gray, x, y)?;
+            Some(Feature { x, y, descriptor })
+        })
+        .collect()
+}
+
+fn patch_descriptor(g: &Gray, x: usize, y: usize) -> Option<Vec<f64>> {
+    let mut patch = Vec::with_capacity((2 * PATCH_RADIUS + 1).pow(2));
+    for py in y - PATCH_RADIUS..=y + PATCH_RADIUS {
+        for px in x - PATCH_RADIUS..=x + PATCH_RADIUS {
+            patch.push(f64::from(g.at(px, py)));
+        }
+    }
+    let mean = patch.iter().sum::<f64>() / usize_to_f64(patch.len());
+    for v in &mut patch {
+        *v -= mean;
+    }
+    let norm = patch.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm < MIN_VARIANCE.sqrt() {
+        return None;
+    }
+    for v in &mut patch {
+        *v /= norm;
+    }
+    Some(patch)
+}
+
+/// Mutual-ratio-test matches between two feature sets, as index pairs.
+fn match_features(fa: &[Feature], fb: &[Feature]) -> Vec<(usize, usize)> {
+    let distance = |a: &Feature, b: &Feature| {
+        let dot: f64 = a
+            .descriptor
+            .iter()
+            .zip(&b.descriptor)
+            .map(|(x, y)| x * y)
+            .sum();
+        2.0f64.mul_add(-dot, 2.0).max(0.0)
+    };
+    let mut matches = Vec::new();
+    for (ia, a) in fa.iter().enumerate() {
+        let mut first = (f64::INFINITY, usize::MAX);
+        let mut second = f64::INFINITY;
+        for (ib, b) in fb.iter().enumerate() {
+            let d = distance(a, b);
+            if d < first.0 {
+                second = first.0;
+                first = (d, ib);
+            } else if d < second {
+                second = d;
+            }
+        }
+        if first.1 != usize::MAX && first.0 < MATCH_RATIO * MATCH_RATIO * second {
+            matches.push((ia, first.1));
+        }
+    }
+    matches
+}
+
+/// Summed-area table with a zero row and column in front.
+fn integral(values: &[f64], w: usize, h: usize) -> Vec<f64> {
+    let mut table = vec![0.0; (w + 1) * (h + 1)];
+    for y in 0..h {
+        let mut row = 0.0;
+        for x in 0..w {
+            row += values[y * w + x];
+            table[(y + 1) * (w + 1) + x + 1] = table[y * (w + 1) + x + 1] + row;
+        }
+    }
+    table
+}
+
+/// Sum over the inclusive pixel box `[x0, x1] × [y0, y1]`.
+fn box_sum(table: &[f64], w: usize, x0: usize, y0: usize, x1: usize, y1: usize) -> f64 {
+    let stride = w + 1;
+    table[(y1 + 1) * stride + x1 + 1] - table[y0 * stride + x1 + 1] - table[(y1 + 1) * stride + x0]
+        + table[y0 * stride + x0]
+}
+
+fn to_isize(value: usize) -> isize {
+    isize::try_from(value).unwrap_or(isize::MAX)
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn usize_to_f64(value: usize) -> f64 {
+    value as f64
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const fn round_isize(value: f64) -> isize {
+    value.round() as isize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic chart-like texture: gray rectangles and thin dark lines on white.
+    fn texture(width: usize, height: usize) -> ColorImage {
+        let mut state = 0x2545_f491_u32;
+        let mut next = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            usize::try_from(state).unwrap_or_default() % bound
+        };
+        let mut image = ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+        for _ in 0..(width * height / 400) {
+            let (x, y) = (next(width), next(height));
+            let (w, h) = (3 + next(20), 3 + next(20));
+            let shade = u8::try_from(40 + next(180)).unwrap_or(128);
+            for yy in y..(y + h).min(height) {
+                for xx in x..(x + w).min(width) {
+                    image.pixels[yy * width + xx] = Color32::from_gray(shade);
+                }
+            }
+        }
+        image
+    }
+
+    fn crop(image: &ColorImage, x: usize, y: usize, w: usize, h: usize) -> ColorImage {
+        let mut pixels = Vec::with_capacity(w * h);
+        for row in y..y + h {
+            pixels.extend_from_slice(&image.pixels[row * image.size[0] + x..][..w]);
+        }
+        ColorImage::new([w, h], pixels)
+    }
+
+    #[test]
+    fn both_modes_recover_the_original_strip() {
+        let chart = texture(420, 140);
+        // Three scans drifting slightly downwards, as a hand-fed strip would.
+        let parts = [
+            crop(&chart, 0, 0, 180, 130),
+            crop(&chart, 130, 6, 170, 130),
+            crop(&chart, 250, 10, 170, 130),
+        ];
+        for mode in StitchMode::ALL {
+            let stitched = stitch_images(&parts, mode).expect("scans align");
+            assert_eq!(stitched.size, [420, 140], "{mode:?}");
+            let expected = crop(&chart, 250, 10, 170, 130);
+            assert_eq!(crop(&stitched, 250, 10, 170, 130).pixels, expected.pixels);
+            assert_eq!(stitched.pixels[0], chart.pixels[0]);
+        }
+    }
+
+    #[test]
+    fn scans_without_overlap_are_rejected() {
+        let chart = texture(400, 100);
+        let parts = [
+            crop(&chart, 0, 0, 150, 100),
+            ColorImage::new([150, 100], vec![Color32::WHITE; 150 * 100]),
+        ];
+        for mode in StitchMode::ALL {
+            assert!(stitch_images(&parts, mode).is_err(), "{mode:?}");
+        }
+        assert!(stitch_images(&parts[..1], StitchMode::Translation).is_err());
+    }
+}
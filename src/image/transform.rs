@@ -1,7 +1,9 @@
-use egui::{Color32, ColorImage, Context, TextureHandle, TextureOptions};
+use egui::{Color32, ColorImage, Context, Pos2, TextureHandle, TextureOptions, pos2};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::util::safe_usize_to_f32;
+
 /// Minimum pixel count before parallelizing per-pixel transforms.
 const PARALLEL_PIXEL_THRESHOLD: usize = 262_144; // 512x512
 
@@ -27,7 +29,7 @@ pub enum ImageTransformOp {
 }
 
 /// Accumulated rotation/flip state for the loaded image.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ImageTransformRecord {
     pub rotation_quarters: u8,
     pub reflected: bool,
@@ -75,6 +77,18 @@ impl ImageTransformRecord {
     }
 }
 
+/// Projective warp applied to straighten a photographed plot.
+///
+/// Warps reset the accumulated rotation/flip state, so each record keeps the
+/// orientation that was active when its corners were picked.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerspectiveWarpRecord {
+    /// Rotation/flip applied before the warp.
+    pub prior: ImageTransformRecord,
+    /// Plot corners in source pixels: top-left, top-right, bottom-right, bottom-left.
+    pub corners: [[f32; 2]; 4],
+}
+
 /// Image data plus the egui texture handle that mirrors its pixels.
 pub struct LoadedImage {
     pub size: [usize; 2],
@@ -208,6 +222,210 @@ pub fn flip_color_image_vertical(image: &mut ColorImage) {
     *image = ColorImage::new([width, height], flipped_pixels);
 }
 
+/// Plane projective transform stored as a row-major 3x3 matrix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography([f64; 9]);
+
+impl Homography {
+    /// Map the unit square `(0,0) (1,0) (1,1) (0,1)` onto a convex quad given in the same order.
+    fn square_to_quad(quad: [Pos2; 4]) -> Option<Self> {
+        if !is_convex_quad(quad) {
+            return None;
+        }
+        let [x0, x1, x2, x3] = quad.map(|p| f64::from(p.x));
+        let [y0, y1, y2, y3] = quad.map(|p| f64::from(p.y));
+        let (dx1, dx2, dx3) = (x1 - x2, x3 - x2, x0 - x1 + x2 - x3);
+        let (dy1, dy2, dy3) = (y1 - y2, y3 - y2, y0 - y1 + y2 - y3);
+        let det = dx1.mul_add(dy2, -(dx2 * dy1));
+        if det.abs() < 1e-9 {
+            return None;
+        }
+        let g = dx3.mul_add(dy2, -(dx2 * dy3)) / det;
+        let h = dx1.mul_add(dy3, -(dx3 * dy1)) / det;
+        Some(Self([
+            g.mul_add(x1, x1 - x0),
+            h.mul_add(x3, x3 - x0),
+            x0,
+            g.mul_add(y1, y1 - y0),
+            h.mul_add(y3, y3 - y0),
+            y0,
+            g,
+            h,
+            1.0,
+        ]))
+    }
+
+    /// Map output pixels of a `size` image onto the quad, corner pixel to corner.
+    pub fn rect_to_quad(size: [usize; 2], quad: [Pos2; 4]) -> Option<Self> {
+        let span = |len: usize| -> Option<f64> {
+            let len = u32::try_from(len.checked_sub(1)?).ok()?;
+            (len > 0).then(|| f64::from(len))
+        };
+        let (sx, sy) = (span(size[0])?, span(size[1])?);
+        let m = Self::square_to_quad(quad)?.0;
+        Some(Self([
+            m[0] / sx,
+            m[1] / sy,
+            m[2],
+            m[3] / sx,
+            m[4] / sy,
+            m[5],
+            m[6] / sx,
+            m[7] / sy,
+            m[8],
+        ]))
+    }
+
+    #[allow(clippy::many_single_char_names)]
+    pub fn inverse(&self) -> Option<Self> {
+        let [a, b, c, d, e, f, g, h, i] = self.0;
+        let co = [
+            e.mul_add(i, -(f * h)),
+            c.mul_add(h, -(b * i)),
+            b.mul_add(f, -(c * e)),
+            f.mul_add(g, -(d * i)),
+            a.mul_add(i, -(c * g)),
+            c.mul_add(d, -(a * f)),
+            d.mul_add(h, -(e * g)),
+            b.mul_add(g, -(a * h)),
+            a.mul_add(e, -(b * d)),
+        ];
+        let det = a.mul_add(co[0], b.mul_add(co[3], c * co[6]));
+        (det.abs() > 1e-12).then(|| Self(co.map(|v| v / det)))
+    }
+
+    /// Transform a point; `None` when it maps to infinity.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn apply(&self, pos: Pos2) -> Option<Pos2> {
+        let m = &self.0;
+        let (x, y) = (f64::from(pos.x), f64::from(pos.y));
+        let w = m[6].mul_add(x, m[7].mul_add(y, m[8]));
+        if w.abs() < 1e-12 {
+            return None;
+        }
+        Some(pos2(
+            (m[0].mul_add(x, m[1].mul_add(y, m[2])) / w) as f32,
+            (m[3].mul_add(x, m[4].mul_add(y, m[5])) / w) as f32,
+        ))
+    }
+}
+
+fn is_convex_quad(quad: [Pos2; 4]) -> bool {
+    let turns = (0..4).map(|i| {
+        let (a, b, c) = (quad[i], quad[(i + 1) % 4], quad[(i + 2) % 4]);
+        (b - a).x.mul_add((c - b).y, -((b - a).y * (c - b).x))
+    });
+    let mut sign = 0.0f32;
+    for turn in turns {
+        if turn.abs() < 1e-3 || turn * sign < 0.0 {
+            return false;
+        }
+        sign = turn;
+    }
+    true
+}
+
+/// Order four clicked corners as top-left, top-right, bottom-right, bottom-left.
+///
+/// Corners are sorted clockwise (in screen coordinates) around their centroid,
+/// starting from the one nearest the image's top-left.
+pub fn order_quad_corners(mut corners: [Pos2; 4]) -> [Pos2; 4] {
+    let center = corners
+        .iter()
+        .fold(egui::Vec2::ZERO, |acc, p| acc + p.to_vec2() / 4.0)
+        .to_pos2();
+    corners.sort_by(|a, b| {
+        let angle = |p: &Pos2| (p.y - center.y).atan2(p.x - center.x);
+        angle(a).total_cmp(&angle(b))
+    });
+    let start = (0..4)
+        .min_by(|&a, &b| {
+            let key = |p: Pos2| p.x + p.y;
+            key(corners[a]).total_cmp(&key(corners[b]))
+        })
+        .unwrap_or(0);
+    corners.rotate_left(start);
+    corners
+}
+
+/// Output size that keeps the longer of each pair of opposite quad edges.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::suboptimal_flops
+)]
+pub fn perspective_output_size(quad: [Pos2; 4]) -> [usize; 2] {
+    let width = quad[0].distance(quad[1]).max(quad[3].distance(quad[2]));
+    let height = quad[0].distance(quad[3]).max(quad[1].distance(quad[2]));
+    [
+        (width.round() as usize + 1).max(2),
+        (height.round() as usize + 1).max(2),
+    ]
+}
+
+/// Source quad whose warp turns an image of `size` by `-angle` radians about
+/// its centre, keeping the size; a line at `angle` comes out horizontal.
+pub fn level_quad(size: [usize; 2], angle: f32) -> [Pos2; 4] {
+    let (w, h) = (
+        safe_usize_to_f32(size[0].saturating_sub(1)),
+        safe_usize_to_f32(size[1].saturating_sub(1)),
+    );
+    let center = pos2(w * 0.5, h * 0.5);
+    let (sin, cos) = angle.sin_cos();
+    [pos2(0.0, 0.0), pos2(w, 0.0), pos2(w, h), pos2(0.0, h)].map(|corner| {
+        let d = corner - center;
+        center + egui::vec2(cos.mul_add(d.x, -sin * d.y), sin.mul_add(d.x, cos * d.y))
+    })
+}
+
+/// Bilinear sample with edge clamping; `pos` is in pixel-center coordinates.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn sample_bilinear(image: &ColorImage, pos: Pos2) -> Color32 {
+    let [width, height] = image.size;
+    let x = pos.x.clamp(0.0, (width - 1) as f32);
+    let y = pos.y.clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+    let px = |x: usize, y: usize| image.pixels[y * width + x].to_array().map(f32::from);
+    let (c00, c10, c01, c11) = (px(x0, y0), px(x1, y0), px(x0, y1), px(x1, y1));
+    let mut out = [0u8; 4];
+    for (ch, value) in out.iter_mut().enumerate() {
+        let top = (c10[ch] - c00[ch]).mul_add(fx, c00[ch]);
+        let bottom = (c11[ch] - c01[ch]).mul_add(fx, c01[ch]);
+        *value = (bottom - top).mul_add(fy, top).round().clamp(0.0, 255.0) as u8;
+    }
+    Color32::from_rgba_premultiplied(out[0], out[1], out[2], out[3])
+}
+
+/// Warp the quad with corners `top-left, top-right, bottom-right, bottom-left`
+/// onto an upright rectangle.
+///
+/// Returns the warped image and the homography from output to source pixels,
+/// or `None` for an empty image or a degenerate or self-intersecting quad.
+pub fn warp_perspective(image: &ColorImage, quad: [Pos2; 4]) -> Option<(ColorImage, Homography)> {
+    if image.size[0] == 0 || image.size[1] == 0 {
+        return None;
+    }
+    let size = perspective_output_size(quad);
+    let to_source = Homography::rect_to_quad(size, quad)?;
+    let width = size[0];
+    let pixels = map_pixels(size[0] * size[1], |idx| {
+        let dest = pos2(
+            safe_usize_to_f32(idx % width),
+            safe_usize_to_f32(idx / width),
+        );
+        to_source
+            .apply(dest)
+            .map_or(Color32::TRANSPARENT, |src| sample_bilinear(image, src))
+    });
+    Some((ColorImage::new(size, pixels), to_source))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,4 +491,119 @@ mod tests {
         assert_eq!(image.size, [3, 2]);
         assert_eq!(ids_from_image(&image.pixels), vec![4, 5, 6, 1, 2, 3]);
     }
+
+    #[test]
+    fn homography_maps_output_corners_onto_the_quad() {
+        let quad = [
+            pos2(10.0, 12.0),
+            pos2(90.0, 4.0),
+            pos2(110.0, 70.0),
+            pos2(2.0, 60.0),
+        ];
+        let size = [101, 51];
+        let h = Homography::rect_to_quad(size, quad).expect("convex quad");
+        let rect = [
+            pos2(0.0, 0.0),
+            pos2(100.0, 0.0),
+            pos2(100.0, 50.0),
+            pos2(0.0, 50.0),
+        ];
+        let inverse = h.inverse().expect("invertible");
+        for (corner, expected) in rect.into_iter().zip(quad) {
+            let mapped = h.apply(corner).expect("finite");
+            assert!(
+                mapped.distance(expected) < 1e-3,
+                "{mapped:?} vs {expected:?}"
+            );
+            let back = inverse.apply(mapped).expect("finite");
+            assert!(back.distance(corner) < 1e-3);
+        }
+        // A bow-tie is not a plot outline.
+        let crossed = [quad[0], quad[2], quad[1], quad[3]];
+        assert!(Homography::rect_to_quad(size, crossed).is_none());
+    }
+
+    #[test]
+    fn order_quad_corners_accepts_any_click_order() {
+        let ordered = [
+            pos2(10.0, 12.0),
+            pos2(90.0, 4.0),
+            pos2(110.0, 70.0),
+            pos2(2.0, 60.0),
+        ];
+        let clicked = [ordered[2], ordered[0], ordered[3], ordered[1]];
+        assert_eq!(order_quad_corners(clicked), ordered);
+    }
+
+    #[test]
+    fn warp_perspective_straightens_a_skewed_rectangle() {
+        // A dark quad on white, as a photo of a plot frame taken at an angle.
+        let (w, h) = (120, 90);
+        let quad = [
+            pos2(20.0, 15.0),
+            pos2(100.0, 25.0),
+            pos2(95.0, 80.0),
+            pos2(10.0, 70.0),
+        ];
+        let inside = |p: Pos2| {
+            (0..4).all(|i| {
+                let (a, b) = (quad[i], quad[(i + 1) % 4]);
+                (b - a).x.mul_add((p - a).y, -((b - a).y * (p - a).x)) >= 0.0
+            })
+        };
+        let pixels = (0..w * h)
+            .map(|idx| {
+                let p = pos2(safe_usize_to_f32(idx % w), safe_usize_to_f32(idx / w));
+                if inside(p) {
+                    Color32::BLACK
+                } else {
+                    Color32::WHITE
+                }
+            })
+            .collect();
+        let image = ColorImage::new([w, h], pixels);
+
+        let (warped, to_source) = warp_perspective(&image, quad).expect("warp");
+        assert_eq!(warped.size, perspective_output_size(quad));
+        let [ow, oh] = warped.size;
+        // Everything away from the border lies inside the quad.
+        for y in 2..oh - 2 {
+            for x in 2..ow - 2 {
+                assert_eq!(warped.pixels[y * ow + x], Color32::BLACK, "({x}, {y})");
+            }
+        }
+        let corner = to_source.apply(pos2(0.0, 0.0)).expect("finite");
+        assert!(corner.distance(quad[0]) < 1e-3);
+    }
+
+    #[test]
+    fn level_quad_rotates_a_tilted_line_flat() {
+        let (w, h) = (101, 61);
+        // A one-pixel dark line rising 10 px over 80 px.
+        let angle = (-10.0f32).atan2(80.0);
+        let pixels = (0..w * h)
+            .map(|idx| {
+                let p = pos2(safe_usize_to_f32(idx % w), safe_usize_to_f32(idx / w));
+                let along = angle.tan().mul_add(p.x - 50.0, 30.0);
+                if (p.y - along).abs() < 0.6 {
+                    Color32::BLACK
+                } else {
+                    Color32::WHITE
+                }
+            })
+            .collect();
+        let image = ColorImage::new([w, h], pixels);
+
+        let quad = level_quad([w, h], angle);
+        let (level, _) = warp_perspective(&image, quad).expect("warp");
+        assert_eq!(level.size, [w, h]);
+        let darkest_row = |x: usize| {
+            (0..h)
+                .min_by_key(|&y| level.pixels[y * w + x].r())
+                .expect("rows")
+        };
+        for x in [25, 50, 75] {
+            assert_eq!(darkest_row(x), 30, "column {x}");
+        }
+    }
 }
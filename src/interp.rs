@@ -1,26 +1,92 @@
 //! Interpolation utilities for resampling picked points.
 
+use serde::{Deserialize, Serialize};
+
 /// A 2D point in numeric axis space.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct XYPoint {
     pub x: f64,
     pub y: f64,
 }
 
 /// Supported interpolation algorithms for curve export.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InterpAlgorithm {
     Linear,
     StepHold,
     NaturalCubic,
+    /// Monotone piecewise cubic (Fritsch–Carlson); never overshoots the data.
+    Pchip,
+    /// Akima spline; follows local trends and damps wiggles near outliers.
+    Akima,
+    /// Cubic smoothing spline; passes near the points rather than through them, pulled less
+    /// by low-weight ones. See [`smoothing_spline`].
+    SmoothingSpline,
+}
+
+/// Filters for smoothing exported rows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmoothingFilter {
+    #[default]
+    MovingAverage,
+    /// Local quadratic fit; keeps peak heights and widths better than the average.
+    SavitzkyGolay,
+    /// Middle value of the window; removes isolated spikes and keeps steps sharp.
+    Median,
+    /// Local linear fits over the nearest rows in x, with robust reweighting; see [`loess`].
+    Loess,
+}
+
+impl SmoothingFilter {
+    /// Ordered list of filters exposed in the UI.
+    pub const ALL: [Self; 4] = [
+        Self::MovingAverage,
+        Self::SavitzkyGolay,
+        Self::Median,
+        Self::Loess,
+    ];
+}
+
+/// Where interpolated and fitted exports sample the curve in x.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SampleGrid {
+    /// A sample count spread evenly over the curve's x range.
+    #[default]
+    Even,
+    /// X values typed by the user.
+    List,
+    /// From a start to a stop x in fixed steps.
+    Uniform,
+    /// A sample count spread evenly in log10 x between a start and a stop.
+    LogSpaced,
+}
+
+impl SampleGrid {
+    /// Ordered list of grids exposed in the UI.
+    pub const ALL: [Self; 4] = [Self::Even, Self::List, Self::Uniform, Self::LogSpaced];
 }
 
+/// Span of [`InterpAlgorithm::SmoothingSpline`] where no weights or span are given, in point
+/// spacings.
+pub const DEFAULT_SPLINE_SPAN: usize = 3;
+/// Weight a smoothing-spline point is raised to, so a zero weight leaves the solve regular.
+const MIN_SPLINE_WEIGHT: f64 = 1.0e-6;
+
 const MIN_REF_SAMPLES: usize = 16;
 const MIN_ABS_TOLERANCE: f64 = 1.0e-9;
 
 impl InterpAlgorithm {
     /// Ordered list of algorithms exposed in the UI.
-    pub const ALL: [Self; 3] = [Self::Linear, Self::StepHold, Self::NaturalCubic];
+    pub const ALL: [Self; 6] = [
+        Self::Linear,
+        Self::StepHold,
+        Self::NaturalCubic,
+        Self::Pchip,
+        Self::Akima,
+        Self::SmoothingSpline,
+    ];
 }
 
 /// Resample already-sorted points into `samples` using the chosen algorithm.
@@ -40,11 +106,506 @@ pub fn interpolate_sorted(
     }
 
     let sample_xs = build_sample_positions(points, samples);
+    interpolate_at(points, &sample_xs, algo)
+}
+
+/// Values of already-sorted `points` at each of `xs`; `None` outside the points' x range.
+pub fn interpolate_within(
+    points: &[XYPoint],
+    xs: &[f64],
+    algo: InterpAlgorithm,
+) -> Vec<Option<f64>> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return vec![None; xs.len()];
+    };
+    let range = first.x..=last.x;
+    let inside: Vec<f64> = xs.iter().copied().filter(|x| range.contains(x)).collect();
+    let mut values = interpolate_at(points, &inside, algo).into_iter();
+    xs.iter()
+        .map(|x| {
+            if range.contains(x) {
+                values.next().map(|p| p.y)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// `from`, `from + step`, … up to `to` in either direction, at most `max` values.
+///
+/// Empty when the step is not positive or a bound is not finite; `to` is kept when the steps
+/// land on it up to rounding.
+pub fn uniform_grid(from: f64, to: f64, step: f64, max: usize) -> Vec<f64> {
+    if !(from.is_finite() && to.is_finite() && step.is_finite() && step > 0.0) {
+        return Vec::new();
+    }
+    let span = to - from;
+    let steps = (span.abs() / step + 1.0e-9).floor();
+    let signed_step = step.copysign(span);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = (steps as usize).saturating_add(1).min(max);
+    (0..count)
+        .map(|i| usize_to_f64(i).mul_add(signed_step, from))
+        .collect()
+}
+
+/// `count` values evenly spaced in log10 from `start` to `stop`; empty unless both are positive.
+pub fn log_grid(start: f64, stop: f64, count: usize) -> Vec<f64> {
+    if !(start > 0.0 && stop > 0.0 && start.is_finite() && stop.is_finite()) {
+        return Vec::new();
+    }
+    if count <= 1 {
+        return vec![start; count];
+    }
+    let (lo, hi) = (start.log10(), stop.log10());
+    let denom = usize_to_f64(count - 1);
+    (0..count)
+        .map(|i| {
+            if i + 1 == count {
+                stop
+            } else {
+                10.0_f64.powf((hi - lo).mul_add(usize_to_f64(i) / denom, lo))
+            }
+        })
+        .collect()
+}
+
+/// Already-sorted `points` resampled at `xs`, in ascending x; values outside the points' x range
+/// are dropped rather than extrapolated, and repeated values are written once.
+pub fn interpolate_on_grid(points: &[XYPoint], xs: &[f64], algo: InterpAlgorithm) -> Vec<XYPoint> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let mut inside: Vec<f64> = xs
+        .iter()
+        .copied()
+        .filter(|x| (first.x..=last.x).contains(x))
+        .collect();
+    inside.sort_by(f64::total_cmp);
+    inside.dedup();
+    if points.len() == 1 {
+        return inside.iter().map(|&x| XYPoint { x, y: first.y }).collect();
+    }
+    interpolate_at(points, &inside, algo)
+}
+
+fn interpolate_at(points: &[XYPoint], sample_xs: &[f64], algo: InterpAlgorithm) -> Vec<XYPoint> {
     match algo {
-        InterpAlgorithm::Linear => interpolate_linear(points, &sample_xs),
-        InterpAlgorithm::StepHold => interpolate_step(points, &sample_xs),
-        InterpAlgorithm::NaturalCubic => interpolate_cubic(points, &sample_xs),
+        InterpAlgorithm::Linear => interpolate_linear(points, sample_xs),
+        InterpAlgorithm::StepHold => interpolate_step(points, sample_xs),
+        InterpAlgorithm::NaturalCubic => {
+            interpolate_cubic(points, sample_xs, build_natural_cubic_segments)
+        }
+        InterpAlgorithm::Pchip => interpolate_cubic(points, sample_xs, build_pchip_segments),
+        InterpAlgorithm::Akima => interpolate_cubic(points, sample_xs, build_akima_segments),
+        InterpAlgorithm::SmoothingSpline => {
+            let nodes = smoothing_spline(points, &[], usize_to_f64(DEFAULT_SPLINE_SPAN));
+            interpolate_cubic(&nodes, sample_xs, build_natural_cubic_segments)
+        }
+    }
+}
+
+/// Centred moving average of y over up to `radius` neighbours on each side; x is kept.
+///
+/// Near the ends the window shrinks to stay centred, so the first and last points keep their
+/// values and the curve does not drift toward the interior. Rows are weighted alike whatever
+/// their spacing in x.
+pub fn smooth_moving_average(points: &[XYPoint], radius: usize) -> Vec<XYPoint> {
+    let last = points.len().saturating_sub(1);
+    points
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            let reach = radius.min(idx).min(last - idx);
+            let window = &points[idx - reach..=idx + reach];
+            #[allow(clippy::cast_precision_loss)]
+            let y = window.iter().map(|q| q.y).sum::<f64>() / window.len() as f64;
+            XYPoint { x: p.x, y }
+        })
+        .collect()
+}
+
+/// Smooth y with `filter` over up to `radius` neighbours on each side; x is kept.
+///
+/// The window filters use the centred window of [`smooth_moving_average`], shrinking near the
+/// ends. LOESS fits over the `2 · radius + 1` rows nearest in x and reweights
+/// `robust_iterations` times; the other filters ignore that count.
+pub fn smooth(
+    points: &[XYPoint],
+    filter: SmoothingFilter,
+    radius: usize,
+    robust_iterations: usize,
+) -> Vec<XYPoint> {
+    match filter {
+        SmoothingFilter::MovingAverage => smooth_moving_average(points, radius),
+        SmoothingFilter::SavitzkyGolay => smooth_windows(points, radius, savitzky_golay_centre),
+        SmoothingFilter::Median => smooth_windows(points, radius, median),
+        SmoothingFilter::Loess => loess(points, 2 * radius + 1, robust_iterations),
+    }
+}
+
+/// LOWESS (Cleveland, 1979): at each point, a straight line fitted by weighted least squares
+/// to the `neighbours` points nearest in x; x is kept.
+///
+/// Weights fall off with the tricube of the distance in x over the distance to the nearest
+/// point outside the neighbourhood, so uneven spacing is taken into account and the order of
+/// `points` does not matter. Each robust iteration scales the weights by the bisquare of the
+/// residuals over six median absolute residuals, so outliers stop pulling the curve. Fewer
+/// than three neighbours leave the points as they are.
+pub fn loess(points: &[XYPoint], neighbours: usize, robust_iterations: usize) -> Vec<XYPoint> {
+    let n = points.len();
+    let k = neighbours.min(n);
+    if k < 3 {
+        return points.to_vec();
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| points[a].x.total_cmp(&points[b].x));
+    let xs: Vec<f64> = order.iter().map(|&i| points[i].x).collect();
+    let ys: Vec<f64> = order.iter().map(|&i| points[i].y).collect();
+
+    let mut robustness = vec![1.0; n];
+    let mut fitted = loess_pass(&xs, &ys, k, &robustness, &ys);
+    for _ in 0..robust_iterations {
+        let residuals: Vec<f64> = ys.iter().zip(&fitted).map(|(y, f)| (y - f).abs()).collect();
+        let mad = median_of(residuals.clone());
+        if mad <= f64::EPSILON * ys.iter().fold(1.0, |m: f64, y| m.max(y.abs())) {
+            break;
+        }
+        for (weight, residual) in robustness.iter_mut().zip(&residuals) {
+            let u = residual / (6.0 * mad);
+            *weight = if u < 1.0 { (1.0 - u * u).powi(2) } else { 0.0 };
+        }
+        fitted = loess_pass(&xs, &ys, k, &robustness, &fitted);
+    }
+
+    let mut out = points.to_vec();
+    for (&idx, y) in order.iter().zip(fitted) {
+        out[idx].y = y;
+    }
+    out
+}
+
+/// Bandwidth over the distance to the farthest neighbour when the neighbourhood holds every
+/// point and none is left to bound it.
+const LOESS_LAST_NEIGHBOUR_MARGIN: f64 = 1.1;
+
+/// One LOWESS pass over points sorted by x with per-point `robustness` weights.
+///
+/// Where every neighbour has lost its weight the value of `previous` is kept: falling back to
+/// the point itself would give an outlier a zero residual and its full weight back.
+fn loess_pass(xs: &[f64], ys: &[f64], k: usize, robustness: &[f64], previous: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut lo = 0;
+    (0..n)
+        .map(|idx| {
+            let x0 = xs[idx];
+            // The k nearest points in sorted order form a window; slide it right while the
+            // point past its end is closer than its first point.
+            while lo + k < n && xs[lo + k] - x0 < x0 - xs[lo] {
+                lo += 1;
+            }
+            let window = lo..lo + k;
+            // Weights reach zero at the nearest point left out, so every neighbour counts.
+            let outside = [lo.checked_sub(1), Some(lo + k).filter(|&j| j < n)]
+                .into_iter()
+                .flatten()
+                .map(|j| (xs[j] - x0).abs())
+                .reduce(f64::min);
+            let farthest = (x0 - xs[lo]).max(xs[lo + k - 1] - x0);
+            let reach = outside.unwrap_or(farthest * LOESS_LAST_NEIGHBOUR_MARGIN);
+            let (mut sw, mut sx, mut sy) = (0.0, 0.0, 0.0);
+            let weights: Vec<f64> = window
+                .clone()
+                .map(|j| {
+                    let t = if reach > 0.0 {
+                        (xs[j] - x0).abs() / reach
+                    } else {
+                        0.0
+                    };
+                    let w = if t < 1.0 {
+                        (1.0 - t.powi(3)).powi(3)
+                    } else {
+                        0.0
+                    } * robustness[j];
+                    sw += w;
+                    sx += w * xs[j];
+                    sy += w * ys[j];
+                    w
+                })
+                .collect();
+            if sw <= 0.0 {
+                return previous[idx];
+            }
+            let (mx, my) = (sx / sw, sy / sw);
+            let (mut sxx, mut sxy) = (0.0, 0.0);
+            for (j, w) in window.zip(weights) {
+                let dx = xs[j] - mx;
+                sxx = (w * dx).mul_add(dx, sxx);
+                sxy = (w * dx).mul_add(ys[j] - my, sxy);
+            }
+            if sxx <= f64::EPSILON * reach * reach * sw {
+                my
+            } else {
+                (sxy / sxx).mul_add(x0 - mx, my)
+            }
+        })
+        .collect()
+}
+
+/// Apply `centre_value` to the centred window of every point, as the moving average does.
+fn smooth_windows(
+    points: &[XYPoint],
+    radius: usize,
+    centre_value: fn(&[XYPoint]) -> f64,
+) -> Vec<XYPoint> {
+    let last = points.len().saturating_sub(1);
+    points
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            let reach = radius.min(idx).min(last - idx);
+            XYPoint {
+                x: p.x,
+                y: centre_value(&points[idx - reach..=idx + reach]),
+            }
+        })
+        .collect()
+}
+
+/// Value at the centre of a quadratic least-squares fit over an odd `window` of rows taken
+/// as evenly spaced; windows of three rows or fewer are passed through.
+///
+/// With half-width `m` the weight of the row `j` steps from the centre is
+/// `3(3m² + 3m − 1 − 5j²) / ((2m − 1)(2m + 1)(2m + 3))`.
+#[allow(clippy::cast_precision_loss)]
+fn savitzky_golay_centre(window: &[XYPoint]) -> f64 {
+    let m = window.len() / 2;
+    if m < 2 {
+        return window[m].y;
+    }
+    let mf = m as f64;
+    let base = 3.0f64.mul_add(mf * mf + mf, -1.0);
+    let norm = (2.0f64.mul_add(mf, -1.0)) * (2.0f64.mul_add(mf, 1.0)) * (2.0f64.mul_add(mf, 3.0));
+    window
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            let j = idx.abs_diff(m) as f64;
+            3.0 * 5.0f64.mul_add(-j * j, base) / norm * p.y
+        })
+        .sum()
+}
+
+/// Median of the window's y values.
+fn median(window: &[XYPoint]) -> f64 {
+    median_of(window.iter().map(|p| p.y).collect())
+}
+
+fn median_of(mut ys: Vec<f64>) -> f64 {
+    ys.sort_by(f64::total_cmp);
+    let mid = ys.len() / 2;
+    if ys.len() % 2 == 1 {
+        ys[mid]
+    } else {
+        f64::midpoint(ys[mid - 1], ys[mid])
+    }
+}
+
+/// Corners of the step-hold curve through `points`, which are sorted by x.
+///
+/// Each value holds until the next point, where the curve jumps: the jump shows up as two rows
+/// at the same x, the held value and then the new one. Points that keep the held value add no
+/// rows, apart from the last, which closes the curve.
+// Jumps are exact: a value read again from the same step is equal, not just close.
+#[allow(clippy::float_cmp)]
+pub fn step_breakpoints(points: &[XYPoint]) -> Vec<XYPoint> {
+    let Some((&first, rest)) = points.split_first() else {
+        return Vec::new();
+    };
+    let mut corners = vec![first];
+    let mut held = first.y;
+    for p in rest {
+        if p.y != held {
+            corners.push(XYPoint { x: p.x, y: held });
+            corners.push(*p);
+            held = p.y;
+        }
+    }
+    if let Some(&last) = points.last()
+        && corners.last() != Some(&last)
+    {
+        corners.push(last);
+    }
+    corners
+}
+
+/// `rows` with the curve through `below` subtracted, recovering one layer of a stacked chart
+/// from its upper boundary.
+///
+/// `below` is sorted by x and read with `algo`; past its ends its end values hold. `rows` may
+/// come in any order. Of two consecutive rows at the same x, a jump, the first is matched with
+/// `below` just left of that x, so stacked steps that jump together come apart cleanly.
+#[allow(clippy::float_cmp)]
+pub fn subtract_below(rows: &[XYPoint], below: &[XYPoint], algo: InterpAlgorithm) -> Vec<XYPoint> {
+    let (Some(first), Some(last)) = (below.first(), below.last()) else {
+        return rows.to_vec();
+    };
+    let xs: Vec<f64> = rows
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| {
+            let x = if rows.get(idx + 1).is_some_and(|next| next.x == p.x) {
+                p.x.next_down()
+            } else {
+                p.x
+            };
+            x.clamp(first.x, last.x)
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..xs.len()).collect();
+    order.sort_by(|&a, &b| xs[a].total_cmp(&xs[b]));
+    let sorted: Vec<f64> = order.iter().map(|&idx| xs[idx]).collect();
+    let mut lower = vec![0.0; xs.len()];
+    for (&idx, low) in order.iter().zip(interpolate_within(below, &sorted, algo)) {
+        lower[idx] = low.unwrap_or(0.0);
     }
+    rows.iter()
+        .zip(lower)
+        .map(|(p, low)| XYPoint {
+            x: p.x,
+            y: p.y - low,
+        })
+        .collect()
+}
+
+/// Place where a curve passes through a y level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelCrossing {
+    pub x: f64,
+    /// The curve goes from below the level to above it.
+    pub rising: bool,
+}
+
+/// Crossings of `level` by the polyline through `points`, which are sorted by x.
+///
+/// Between samples on both sides of the level the crossing is interpolated linearly. A curve
+/// that runs along the level and then leaves it on the other side crosses in the middle of that
+/// run; one that only touches the level and turns back does not cross at all.
+pub fn level_crossings(points: &[XYPoint], level: f64) -> Vec<LevelCrossing> {
+    let mut crossings = Vec::new();
+    // Last sample off the level, and the x span of the samples on it since then.
+    let mut off: Option<(f64, f64)> = None;
+    let mut on_level: Option<(f64, f64)> = None;
+    for p in points.iter().filter(|p| p.x.is_finite() && p.y.is_finite()) {
+        let d = p.y - level;
+        if d == 0.0 {
+            on_level = Some(on_level.map_or((p.x, p.x), |(start, _)| (start, p.x)));
+            continue;
+        }
+        if let Some((x0, d0)) = off
+            && (d0 < 0.0) != (d < 0.0)
+        {
+            let x = on_level.map_or_else(
+                || (p.x - x0).mul_add(d0 / (d0 - d), x0),
+                |(start, end)| f64::midpoint(start, end),
+            );
+            crossings.push(LevelCrossing { x, rising: d > 0.0 });
+        }
+        off = Some((p.x, d));
+        on_level = None;
+    }
+    crossings
+}
+
+/// Extremes, mean and length of the polyline through points sorted by x.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveStats {
+    /// Sample with the smallest y; the first one on ties.
+    pub min: XYPoint,
+    /// Sample with the largest y; the first one on ties.
+    pub max: XYPoint,
+    /// Mean of y over the x span, each segment weighted by its width; the plain mean of the
+    /// samples when they all share one x.
+    pub mean: f64,
+    /// Length of the polyline in data units of both axes.
+    pub arc_length: f64,
+}
+
+/// Statistics of the polyline through `points`, which are sorted by x; `None` without a finite
+/// sample.
+pub fn curve_stats(points: &[XYPoint]) -> Option<CurveStats> {
+    let finite: Vec<XYPoint> = points
+        .iter()
+        .copied()
+        .filter(|p| p.x.is_finite() && p.y.is_finite())
+        .collect();
+    let first = *finite.first()?;
+    let (mut min, mut max) = (first, first);
+    let (mut area, mut arc_length) = (0.0, 0.0);
+    for pair in finite.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        area = (b.x - a.x).mul_add(f64::midpoint(a.y, b.y), area);
+        arc_length += (b.x - a.x).hypot(b.y - a.y);
+    }
+    for p in &finite {
+        if p.y < min.y {
+            min = *p;
+        }
+        if p.y > max.y {
+            max = *p;
+        }
+    }
+    let span = finite[finite.len() - 1].x - first.x;
+    #[allow(clippy::cast_precision_loss)]
+    let mean = if span > 0.0 {
+        area / span
+    } else {
+        finite.iter().map(|p| p.y).sum::<f64>() / finite.len() as f64
+    };
+    Some(CurveStats {
+        min,
+        max,
+        mean,
+        arc_length,
+    })
+}
+
+/// Signed area between the polyline through `points` (sorted by x) and y = 0, from x = `from`
+/// to x = `to`, by the trapezoid rule.
+///
+/// The bounds are clipped to the curve's x span, with the curve interpolated linearly at a bound
+/// that falls between samples; swapped bounds negate the area. `None` when the bounds and the
+/// curve do not overlap.
+pub fn area_under(points: &[XYPoint], from: f64, to: f64) -> Option<f64> {
+    if !from.is_finite() || !to.is_finite() {
+        return None;
+    }
+    let (lo, hi, sign) = if from <= to {
+        (from, to, 1.0)
+    } else {
+        (to, from, -1.0)
+    };
+    let finite: Vec<XYPoint> = points
+        .iter()
+        .copied()
+        .filter(|p| p.x.is_finite() && p.y.is_finite())
+        .collect();
+    let (first, last) = (finite.first()?.x, finite.last()?.x);
+    if hi < first || lo > last {
+        return None;
+    }
+    let mut area = 0.0;
+    for pair in finite.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (x0, x1) = (a.x.max(lo), b.x.min(hi));
+        if x1 <= x0 {
+            continue;
+        }
+        let at = |x: f64| (b.y - a.y).mul_add((x - a.x) / (b.x - a.x), a.y);
+        area = (x1 - x0).mul_add(f64::midpoint(at(x0), at(x1)), area);
+    }
+    Some(sign * area)
 }
 
 /// Heuristic auto-selection of sample count for exporting an interpolated curve.
@@ -84,11 +645,7 @@ pub fn auto_sample_count(
     }
 
     let ref_xs = build_sample_positions(points, ref_samples);
-    let ref_curve = match algo {
-        InterpAlgorithm::Linear => interpolate_linear(points, &ref_xs),
-        InterpAlgorithm::StepHold => interpolate_step(points, &ref_xs),
-        InterpAlgorithm::NaturalCubic => interpolate_cubic(points, &ref_xs),
-    };
+    let ref_curve = interpolate_at(points, &ref_xs, algo);
 
     // Compute Y-range on the reference curve to derive an absolute tolerance.
     let mut y_min = ref_curve[0].y;
@@ -163,30 +720,33 @@ pub fn auto_sample_count(
 }
 
 fn build_sample_positions(points: &[XYPoint], samples: usize) -> Vec<f64> {
-    if samples == 0 {
+    match (points.first(), points.last()) {
+        (Some(first), Some(last)) => even_grid(first.x, last.x, samples),
+        _ => Vec::new(),
+    }
+}
+
+/// `count` values evenly spaced from `from` to `to`, both included.
+pub fn even_grid(from: f64, to: f64, count: usize) -> Vec<f64> {
+    if count == 0 {
         return vec![];
     }
-    let mut xs = Vec::with_capacity(samples);
-    let (Some(first), Some(last)) = (points.first(), points.last()) else {
-        return xs;
-    };
-    let x_min = first.x;
-    let x_max = last.x;
-    if (x_max - x_min).abs() <= f64::EPSILON {
-        xs.resize(samples, x_min);
+    let mut xs = Vec::with_capacity(count);
+    if (to - from).abs() <= f64::EPSILON {
+        xs.resize(count, from);
         return xs;
     }
-    let denom = samples.saturating_sub(1);
+    let denom = count.saturating_sub(1);
     let step = if denom == 0 {
         0.0
     } else {
-        (x_max - x_min) / usize_to_f64(denom)
+        (to - from) / usize_to_f64(denom)
     };
-    for i in 0..samples {
-        if i + 1 == samples {
-            xs.push(x_max);
+    for i in 0..count {
+        if i + 1 == count {
+            xs.push(to);
         } else {
-            xs.push(x_min + step * usize_to_f64(i));
+            xs.push(from + step * usize_to_f64(i));
         }
     }
     xs
@@ -232,12 +792,16 @@ fn interpolate_step(points: &[XYPoint], sample_xs: &[f64]) -> Vec<XYPoint> {
 }
 
 #[allow(clippy::suboptimal_flops)]
-fn interpolate_cubic(points: &[XYPoint], sample_xs: &[f64]) -> Vec<XYPoint> {
+fn interpolate_cubic(
+    points: &[XYPoint],
+    sample_xs: &[f64],
+    build_segments: fn(&[XYPoint]) -> Option<Vec<CubicSegment>>,
+) -> Vec<XYPoint> {
     let unique = unique_by_x(points);
     if unique.len() < 2 {
         return interpolate_linear(points, sample_xs);
     }
-    let Some(segments) = build_natural_cubic_segments(&unique) else {
+    let Some(segments) = build_segments(&unique) else {
         return interpolate_linear(&unique, sample_xs);
     };
 
@@ -350,6 +914,240 @@ fn build_natural_cubic_segments(points: &[XYPoint]) -> Option<Vec<CubicSegment>>
     Some(segments)
 }
 
+/// Interval widths and secant slopes; `None` when two points share an `x`.
+fn secants(points: &[XYPoint]) -> Option<(Vec<f64>, Vec<f64>)> {
+    points
+        .windows(2)
+        .map(|pair| {
+            let width = pair[1].x - pair[0].x;
+            (width.abs() > f64::EPSILON).then(|| (width, (pair[1].y - pair[0].y) / width))
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|pairs| pairs.into_iter().unzip())
+}
+
+/// Cubic Hermite segments through `points` with the given node slopes.
+fn hermite_segments(
+    points: &[XYPoint],
+    widths: &[f64],
+    secant: &[f64],
+    slopes: &[f64],
+) -> Vec<CubicSegment> {
+    (0..widths.len())
+        .map(|i| {
+            let (h, delta) = (widths[i], secant[i]);
+            let (m0, m1) = (slopes[i], slopes[i + 1]);
+            CubicSegment {
+                x: points[i].x,
+                a: points[i].y,
+                b: m0,
+                c: (3.0f64.mul_add(delta, -2.0 * m0) - m1) / h,
+                d: 2.0f64.mul_add(-delta, m0 + m1) / (h * h),
+            }
+        })
+        .collect()
+}
+
+/// Monotone cubic slopes after Fritsch and Carlson, with the
+/// shape-preserving three-point end conditions used by `SciPy`.
+fn build_pchip_segments(points: &[XYPoint]) -> Option<Vec<CubicSegment>> {
+    let (widths, secant) = secants(points)?;
+    let n = secant.len();
+    if n == 1 {
+        return Some(hermite_segments(points, &widths, &secant, &[secant[0]; 2]));
+    }
+    let mut slopes = vec![0.0; n + 1];
+    for i in 1..n {
+        let (d0, d1) = (secant[i - 1], secant[i]);
+        if d0 * d1 <= 0.0 {
+            continue;
+        }
+        // Weighted harmonic mean of the neighbouring secants.
+        let w0 = 2.0f64.mul_add(widths[i], widths[i - 1]);
+        let w1 = 2.0f64.mul_add(widths[i - 1], widths[i]);
+        slopes[i] = (w0 + w1) / (w0 / d0 + w1 / d1);
+    }
+    slopes[0] = pchip_end_slope(widths[0], widths[1], secant[0], secant[1]);
+    slopes[n] = pchip_end_slope(widths[n - 1], widths[n - 2], secant[n - 1], secant[n - 2]);
+    Some(hermite_segments(points, &widths, &secant, &slopes))
+}
+
+fn pchip_end_slope(h0: f64, h1: f64, d0: f64, d1: f64) -> f64 {
+    let sign = |v: f64| v.partial_cmp(&0.0);
+    let slope = 2.0f64.mul_add(h0, h1).mul_add(d0, -h0 * d1) / (h0 + h1);
+    if sign(slope) != sign(d0) {
+        0.0
+    } else if sign(d0) != sign(d1) && slope.abs() > 3.0 * d0.abs() {
+        3.0 * d0
+    } else {
+        slope
+    }
+}
+
+/// Akima (1970) slopes: each node takes the secant on the side that varies less.
+fn build_akima_segments(points: &[XYPoint]) -> Option<Vec<CubicSegment>> {
+    let (widths, secant) = secants(points)?;
+    let n = secant.len();
+    // Two extrapolated secants on each side, so `m[i + 2]` is `secant[i]`.
+    let mut m = Vec::with_capacity(n + 4);
+    let (first, second) = (secant[0], secant.get(1).copied().unwrap_or(secant[0]));
+    let before = 2.0f64.mul_add(first, -second);
+    m.push(2.0f64.mul_add(before, -first));
+    m.push(before);
+    m.extend_from_slice(&secant);
+    let (last, prev) = (secant[n - 1], secant[n.saturating_sub(2)]);
+    let after = 2.0f64.mul_add(last, -prev);
+    m.push(after);
+    m.push(2.0f64.mul_add(after, -last));
+
+    let slopes: Vec<f64> = (0..=n)
+        .map(|i| {
+            let right = (m[i + 3] - m[i + 2]).abs();
+            let left = (m[i + 1] - m[i]).abs();
+            if right + left <= f64::EPSILON {
+                0.5 * (m[i + 1] + m[i + 2])
+            } else {
+                right.mul_add(m[i + 1], left * m[i + 2]) / (right + left)
+            }
+        })
+        .collect();
+    Some(hermite_segments(points, &widths, &secant, &slopes))
+}
+
+/// Nodes of the cubic smoothing spline (Reinsch, 1967) of already-sorted `points`: the natural
+/// cubic through them is the curve `g` minimising `Σ wᵢ·(yᵢ − g(xᵢ))² + λ·∫ g''²`.
+///
+/// `weights` pair with `points`, missing ones counting as 1; only their ratios matter. `span`
+/// sets λ as about how many average point spacings the spline averages over: near 0 it
+/// interpolates, and long spans flatten it toward the weighted least-squares line. Points
+/// sharing an x merge into their weighted mean; fewer than three distinct x are not smoothed.
+#[allow(clippy::suboptimal_flops)]
+pub fn smoothing_spline(points: &[XYPoint], weights: &[f64], span: f64) -> Vec<XYPoint> {
+    let mut nodes: Vec<XYPoint> = Vec::with_capacity(points.len());
+    let mut node_weights: Vec<f64> = Vec::with_capacity(points.len());
+    for (idx, p) in points.iter().enumerate() {
+        let w = weights
+            .get(idx)
+            .copied()
+            .filter(|w| w.is_finite())
+            .map_or(1.0, |w| w.max(MIN_SPLINE_WEIGHT));
+        if let (Some(last), Some(last_w)) = (nodes.last_mut(), node_weights.last_mut())
+            && (last.x - p.x).abs() <= f64::EPSILON
+        {
+            last.y = (last.y * *last_w + p.y * w) / (*last_w + w);
+            *last_w += w;
+            continue;
+        }
+        nodes.push(*p);
+        node_weights.push(w);
+    }
+    let n = nodes.len();
+    if n < 3 || !span.is_finite() || span <= 0.0 {
+        return nodes;
+    }
+
+    let h: Vec<f64> = nodes.windows(2).map(|pair| pair[1].x - pair[0].x).collect();
+    let mean_h = (nodes[n - 1].x - nodes[0].x) / usize_to_f64(n - 1);
+    let lambda = span.powi(4) * mean_h.powi(3);
+    let mean_w = node_weights.iter().sum::<f64>() / usize_to_f64(n);
+    let inverse_w: Vec<f64> = node_weights.iter().map(|w| mean_w / w).collect();
+    // Column k of the second-difference matrix Q has its three entries in rows k..=k + 2.
+    let q = |k: usize| {
+        let (left, right) = (1.0 / h[k], 1.0 / h[k + 1]);
+        [left, -left - right, right]
+    };
+
+    // Band of R + λ·Qᵀ·W⁻¹·Q, with R the tridiagonal Gram matrix of the second derivatives.
+    let m = n - 2;
+    let mut diag = vec![0.0; m];
+    let mut off1 = vec![0.0; m];
+    let mut off2 = vec![0.0; m];
+    let mut rhs = vec![0.0; m];
+    for k in 0..m {
+        let qk = q(k);
+        diag[k] = (h[k] + h[k + 1]) / 3.0
+            + lambda
+                * (0..3)
+                    .map(|j| qk[j] * qk[j] * inverse_w[k + j])
+                    .sum::<f64>();
+        if k + 1 < m {
+            let next = q(k + 1);
+            off1[k] = h[k + 1] / 6.0
+                + lambda
+                    * (qk[1] * next[0] * inverse_w[k + 1] + qk[2] * next[1] * inverse_w[k + 2]);
+        }
+        if k + 2 < m {
+            off2[k] = lambda * qk[2] * q(k + 2)[0] * inverse_w[k + 2];
+        }
+        rhs[k] = (0..3).map(|j| qk[j] * nodes[k + j].y).sum();
+    }
+    let Some(gamma) = solve_symmetric_band(&diag, &off1, &off2, rhs) else {
+        return nodes;
+    };
+
+    // g = y − λ·W⁻¹·Q·γ
+    for (r, node) in nodes.iter_mut().enumerate() {
+        let pull: f64 = (r.saturating_sub(2)..=r.min(m - 1))
+            .map(|k| q(k)[r - k] * gamma[k])
+            .sum();
+        node.y -= lambda * inverse_w[r] * pull;
+    }
+    nodes
+}
+
+/// Solve the symmetric positive-definite system with `diag` and the first and second
+/// off-diagonals `off1` and `off2` by a band LDL factorisation; `None` when a pivot is not
+/// positive.
+#[allow(clippy::suboptimal_flops)]
+fn solve_symmetric_band(
+    diag: &[f64],
+    off1: &[f64],
+    off2: &[f64],
+    mut rhs: Vec<f64>,
+) -> Option<Vec<f64>> {
+    let m = diag.len();
+    let mut pivots = vec![0.0; m];
+    let mut below1 = vec![0.0; m];
+    let mut below2 = vec![0.0; m];
+    for k in 0..m {
+        let mut pivot = diag[k];
+        let mut lower = off1[k];
+        if k >= 1 {
+            pivot -= below1[k - 1] * below1[k - 1] * pivots[k - 1];
+            lower -= below2[k - 1] * below1[k - 1] * pivots[k - 1];
+        }
+        if k >= 2 {
+            pivot -= below2[k - 2] * below2[k - 2] * pivots[k - 2];
+        }
+        if !pivot.is_finite() || pivot <= 0.0 {
+            return None;
+        }
+        pivots[k] = pivot;
+        below1[k] = lower / pivot;
+        below2[k] = off2[k] / pivot;
+    }
+    for k in 0..m {
+        if k >= 1 {
+            rhs[k] -= below1[k - 1] * rhs[k - 1];
+        }
+        if k >= 2 {
+            rhs[k] -= below2[k - 2] * rhs[k - 2];
+        }
+    }
+    for (value, pivot) in rhs.iter_mut().zip(&pivots) {
+        *value /= pivot;
+    }
+    for k in (0..m).rev() {
+        if k + 1 < m {
+            rhs[k] -= below1[k] * rhs[k + 1];
+        }
+        if k + 2 < m {
+            rhs[k] -= below2[k] * rhs[k + 2];
+        }
+    }
+    Some(rhs)
+}
+
 const fn usize_to_f64(value: usize) -> f64 {
     #[allow(clippy::cast_precision_loss)]
     {
@@ -378,6 +1176,230 @@ mod tests {
         assert!(approx_eq(out[2].y, 10.0, 1.0e-9));
     }
 
+    #[test]
+    fn user_grids_step_both_ways_and_space_decades_evenly() {
+        let up = uniform_grid(0.0, 1.0, 0.25, 100);
+        assert_eq!(up.len(), 5);
+        assert!(approx_eq(up[4], 1.0, 1.0e-12));
+        let down = uniform_grid(1.0, 0.0, 0.3, 100);
+        assert_eq!(down.len(), 4);
+        assert!(approx_eq(down[3], 0.1, 1.0e-12));
+        assert_eq!(uniform_grid(0.0, 1.0, 0.0, 100), Vec::<f64>::new());
+        assert_eq!(uniform_grid(0.0, 1.0e6, 1.0, 10).len(), 10);
+
+        let decades = log_grid(1.0, 1000.0, 4);
+        for (x, expected) in decades.iter().zip([1.0, 10.0, 100.0, 1000.0]) {
+            assert!(approx_eq(*x, expected, 1.0e-9), "{decades:?}");
+        }
+        assert!(log_grid(0.0, 10.0, 4).is_empty());
+    }
+
+    #[test]
+    fn grid_interpolation_sorts_and_skips_x_outside_the_curve() {
+        let points = vec![XYPoint { x: 0.0, y: 0.0 }, XYPoint { x: 10.0, y: 20.0 }];
+        let out = interpolate_on_grid(
+            &points,
+            &[7.5, -1.0, 2.0, 2.0, 11.0],
+            InterpAlgorithm::Linear,
+        );
+        let xs: Vec<f64> = out.iter().map(|p| p.x).collect();
+        assert_eq!(xs, [2.0, 7.5]);
+        assert!(approx_eq(out[0].y, 4.0, 1.0e-12));
+        assert!(approx_eq(out[1].y, 15.0, 1.0e-12));
+    }
+
+    #[test]
+    fn moving_average_keeps_ends_and_flattens_noise() {
+        let points: Vec<XYPoint> = [0.0, 3.0, 0.0, 3.0, 0.0, 3.0]
+            .into_iter()
+            .zip([0.0, 1.0, 2.0, 4.0, 8.0, 9.0])
+            .map(|(y, x)| XYPoint { x, y })
+            .collect();
+        let out = smooth_moving_average(&points, 2);
+        let ys: Vec<f64> = out.iter().map(|p| p.y).collect();
+        assert_eq!(ys, [0.0, 1.0, 1.2, 1.8, 2.0, 3.0]);
+        let xs: Vec<f64> = out.iter().map(|p| p.x).collect();
+        assert_eq!(xs, [0.0, 1.0, 2.0, 4.0, 8.0, 9.0]);
+        assert_eq!(smooth_moving_average(&points, 0), points);
+        assert!(smooth_moving_average(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn savitzky_golay_keeps_quadratics_and_median_drops_spikes() {
+        let parabola: Vec<XYPoint> = (0..9)
+            .map(|i| {
+                let x = f64::from(i);
+                XYPoint {
+                    x,
+                    y: 0.5f64.mul_add(x * x, -2.0 * x) + 1.0,
+                }
+            })
+            .collect();
+        for (out, p) in smooth(&parabola, SmoothingFilter::SavitzkyGolay, 3, 0)
+            .iter()
+            .zip(&parabola)
+        {
+            assert!(approx_eq(out.y, p.y, 1.0e-9), "{out:?} vs {p:?}");
+        }
+        // The classic five-point weights (−3, 12, 17, 12, −3) / 35.
+        let spike: Vec<XYPoint> = [0.0, 0.0, 35.0, 0.0, 0.0]
+            .into_iter()
+            .zip([0.0, 1.0, 2.0, 3.0, 4.0])
+            .map(|(y, x)| XYPoint { x, y })
+            .collect();
+        let out = smooth(&spike, SmoothingFilter::SavitzkyGolay, 2, 0);
+        assert!(approx_eq(out[2].y, 17.0, 1.0e-9));
+
+        let out = smooth(&spike, SmoothingFilter::Median, 1, 0);
+        let ys: Vec<f64> = out.iter().map(|p| p.y).collect();
+        assert_eq!(ys, [0.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(smooth(&spike, SmoothingFilter::Median, 0, 0), spike);
+        assert!(smooth(&[], SmoothingFilter::SavitzkyGolay, 3, 0).is_empty());
+    }
+
+    #[test]
+    fn loess_follows_lines_and_robust_passes_ignore_outliers() {
+        // Uneven x, given out of order: a straight line comes back as it is.
+        let line: Vec<XYPoint> = [3.0, 0.0, 1.0, 7.5, 2.0, 4.0, 6.0, 9.0]
+            .into_iter()
+            .map(|x| XYPoint {
+                x,
+                y: 2.0f64.mul_add(x, 1.0),
+            })
+            .collect();
+        for (out, p) in loess(&line, 5, 2).iter().zip(&line) {
+            assert!(approx_eq(out.x, p.x, 0.0));
+            assert!(approx_eq(out.y, p.y, 1.0e-9), "{out:?} vs {p:?}");
+        }
+
+        // One wild point on a flat line: the plain fit bends towards it, the robust one not.
+        let mut flat: Vec<XYPoint> = (0..15)
+            .map(|i| XYPoint {
+                x: f64::from(i),
+                y: if i % 2 == 0 { 0.1 } else { -0.1 },
+            })
+            .collect();
+        flat[7].y = 50.0;
+        assert!(loess(&flat, 7, 0)[6].y > 5.0);
+        let robust = loess(&flat, 7, 3);
+        assert!(robust[6].y.abs() < 0.2, "{:?}", robust[6]);
+        assert!(robust[7].y.abs() < 0.2, "{:?}", robust[7]);
+        assert_eq!(loess(&flat, 2, 3), flat);
+    }
+
+    #[test]
+    fn step_breakpoints_keep_only_the_jumps() {
+        let points: Vec<XYPoint> = [(0.0, 1.0), (1.0, 1.0), (2.0, 3.0), (4.0, 2.0), (5.0, 2.0)]
+            .into_iter()
+            .map(|(x, y)| XYPoint { x, y })
+            .collect();
+        let corners: Vec<(f64, f64)> = step_breakpoints(&points)
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect();
+        assert_eq!(
+            corners,
+            [
+                (0.0, 1.0),
+                (2.0, 1.0),
+                (2.0, 3.0),
+                (4.0, 3.0),
+                (4.0, 2.0),
+                (5.0, 2.0)
+            ]
+        );
+        assert!(step_breakpoints(&[]).is_empty());
+    }
+
+    #[test]
+    fn subtracting_the_layer_below_recovers_stacked_series() {
+        let xy = |pairs: &[(f64, f64)]| -> Vec<XYPoint> {
+            pairs.iter().map(|&(x, y)| XYPoint { x, y }).collect()
+        };
+        let below = xy(&[(0.0, 1.0), (10.0, 3.0)]);
+        // Unsorted rows, one past the end of the lower curve.
+        let rows = xy(&[(5.0, 6.0), (0.0, 4.0), (12.0, 7.0)]);
+        let layer = subtract_below(&rows, &below, InterpAlgorithm::Linear);
+        let ys: Vec<f64> = layer.iter().map(|p| p.y).collect();
+        assert_eq!(ys, [4.0, 3.0, 4.0]);
+
+        // Steps jumping together at x = 2: 1 → 3 below, 4 → 7 on top.
+        let below = xy(&[(0.0, 1.0), (2.0, 3.0), (4.0, 3.0)]);
+        let top = step_breakpoints(&xy(&[(0.0, 4.0), (2.0, 7.0), (4.0, 7.0)]));
+        let layer = subtract_below(&top, &below, InterpAlgorithm::StepHold);
+        let ys: Vec<f64> = layer.iter().map(|p| p.y).collect();
+        assert_eq!(ys, [3.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn level_crossings_interpolate_between_samples_and_skip_touches() {
+        let points: Vec<XYPoint> = [-1.0, 3.0, 1.0, 1.0, 1.0, -2.0, 1.0, 0.0]
+            .into_iter()
+            .enumerate()
+            .map(|(x, y)| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = x as f64;
+                XYPoint { x, y }
+            })
+            .collect();
+        let out = level_crossings(&points, 1.0);
+        // Up between x = 0 and 1, down after the run at x = 2..4, touch at x = 6 skipped.
+        assert_eq!(
+            out,
+            [
+                LevelCrossing {
+                    x: 0.5,
+                    rising: true
+                },
+                LevelCrossing {
+                    x: 3.0,
+                    rising: false
+                },
+            ]
+        );
+        let zero = level_crossings(&points, 0.0);
+        let xs: Vec<f64> = zero.iter().map(|c| c.x).collect();
+        assert_eq!(xs.len(), 3);
+        assert!(approx_eq(xs[0], 0.25, 1.0e-12));
+        assert!(approx_eq(xs[1], 4.0 + 1.0 / 3.0, 1.0e-12));
+        assert!(approx_eq(xs[2], 5.0 + 2.0 / 3.0, 1.0e-12));
+        assert!(level_crossings(&points[..1], 0.0).is_empty());
+    }
+
+    #[test]
+    fn curve_stats_weight_the_mean_by_x_and_measure_the_polyline() {
+        // Flat at 1 over [0, 3], then a rise to 5 over [3, 6].
+        let points: Vec<XYPoint> = [(0.0, 1.0), (3.0, 1.0), (6.0, 5.0), (6.0, f64::NAN)]
+            .into_iter()
+            .map(|(x, y)| XYPoint { x, y })
+            .collect();
+        let stats = curve_stats(&points).expect("stats");
+        assert_eq!(stats.min, XYPoint { x: 0.0, y: 1.0 });
+        assert_eq!(stats.max, XYPoint { x: 6.0, y: 5.0 });
+        assert!((stats.mean - 2.0).abs() < 1e-12);
+        assert!((stats.arc_length - 8.0).abs() < 1e-12);
+        assert!(curve_stats(&points[3..]).is_none());
+        let single = curve_stats(&points[..1]).expect("one sample");
+        assert!((single.mean - 1.0).abs() < 1e-12);
+        assert!(single.arc_length.abs() < 1e-12);
+    }
+
+    #[test]
+    fn area_under_clips_to_the_curve_and_interpolates_at_the_bounds() {
+        // y = x over [0, 4].
+        let points: Vec<XYPoint> = [0.0, 2.0, 4.0]
+            .into_iter()
+            .map(|x| XYPoint { x, y: x })
+            .collect();
+        let close = |a: Option<f64>, b: f64| a.is_some_and(|a| (a - b).abs() < 1e-12);
+        assert!(close(area_under(&points, 0.0, 4.0), 8.0));
+        assert!(close(area_under(&points, 1.0, 3.0), 4.0));
+        assert!(close(area_under(&points, 3.0, 1.0), -4.0));
+        assert!(close(area_under(&points, -5.0, 1.0), 0.5));
+        assert!(area_under(&points, 5.0, 6.0).is_none());
+        assert!(area_under(&points, f64::NAN, 1.0).is_none());
+    }
+
     #[test]
     fn interpolate_step_basic() {
         let points = vec![XYPoint { x: 0.0, y: 0.0 }, XYPoint { x: 10.0, y: 10.0 }];
@@ -391,6 +1413,13 @@ mod tests {
         assert!(approx_eq(out[2].y, 10.0, 1.0e-9));
     }
 
+    #[test]
+    fn interpolate_within_leaves_positions_outside_the_points_empty() {
+        let points = vec![XYPoint { x: 2.0, y: 4.0 }, XYPoint { x: 6.0, y: 12.0 }];
+        let out = interpolate_within(&points, &[0.0, 2.0, 5.0, 6.0, 8.0], InterpAlgorithm::Linear);
+        assert_eq!(out, vec![None, Some(4.0), Some(10.0), Some(12.0), None]);
+    }
+
     #[test]
     fn auto_sample_count_linear_returns_min() {
         let points = vec![XYPoint { x: 0.0, y: 0.0 }, XYPoint { x: 10.0, y: 10.0 }];
@@ -408,4 +1437,135 @@ mod tests {
         );
         assert_eq!(out, min_samples);
     }
+
+    /// Evaluate `algo` at the given `xs` on the data points.
+    fn eval(points: &[XYPoint], xs: &[f64], algo: InterpAlgorithm) -> Vec<f64> {
+        interpolate_at(points, xs, algo)
+            .into_iter()
+            .map(|p| p.y)
+            .collect()
+    }
+
+    fn xy(values: &[(f64, f64)]) -> Vec<XYPoint> {
+        values.iter().map(|&(x, y)| XYPoint { x, y }).collect()
+    }
+
+    #[test]
+    fn pchip_matches_known_values() {
+        // Hand-computed with the Fritsch–Carlson slopes: m = [1.5, 0, 0, 1.5].
+        let points = xy(&[(0.0, 0.0), (1.0, 1.0), (2.0, 1.0), (3.0, 2.0)]);
+        let ys = eval(&points, &[0.0, 0.5, 1.5, 2.5, 3.0], InterpAlgorithm::Pchip);
+        for (y, expected) in ys.into_iter().zip([0.0, 0.6875, 1.0, 1.3125, 2.0]) {
+            assert!(approx_eq(y, expected, 1.0e-12), "{y} vs {expected}");
+        }
+        // Two points degrade to a straight line.
+        let line = xy(&[(0.0, 1.0), (4.0, 3.0)]);
+        assert!(approx_eq(
+            eval(&line, &[1.0], InterpAlgorithm::Pchip)[0],
+            1.5,
+            1.0e-12
+        ));
+    }
+
+    #[test]
+    fn akima_matches_known_values() {
+        // Slopes at both ends of the rise are zero, so flat runs stay flat.
+        let points = xy(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (3.0, 1.0),
+            (4.0, 1.0),
+            (5.0, 1.0),
+        ]);
+        let ys = eval(&points, &[1.5, 2.25, 2.5, 4.5], InterpAlgorithm::Akima);
+        for (y, expected) in ys.into_iter().zip([0.0, 0.15625, 0.5, 1.0]) {
+            assert!(approx_eq(y, expected, 1.0e-12), "{y} vs {expected}");
+        }
+        // Straight lines are reproduced exactly, even with uneven spacing.
+        let line = xy(&[(0.0, 2.0), (1.0, 4.0), (3.0, 8.0), (4.0, 10.0)]);
+        let ys = eval(&line, &[0.5, 2.0, 3.7], InterpAlgorithm::Akima);
+        for (y, expected) in ys.into_iter().zip([3.0, 6.0, 9.4]) {
+            assert!(approx_eq(y, expected, 1.0e-12), "{y} vs {expected}");
+        }
+    }
+
+    #[test]
+    fn smoothing_spline_spans_from_interpolation_to_the_least_squares_line() {
+        // Straight lines have no curvature to smooth away, whatever the span or spacing.
+        let line = xy(&[(0.0, 1.0), (1.0, 3.0), (3.0, 7.0), (3.5, 8.0), (6.0, 13.0)]);
+        for node in smoothing_spline(&line, &[], 4.0) {
+            assert!(
+                approx_eq(node.y, 2.0f64.mul_add(node.x, 1.0), 1.0e-9),
+                "{node:?}"
+            );
+        }
+
+        let points = xy(&[
+            (0.0, 0.0),
+            (1.0, 2.0),
+            (2.0, 1.0),
+            (3.0, 3.0),
+            (4.0, 2.0),
+            (5.0, 4.0),
+            (6.0, 3.0),
+        ]);
+        for (node, p) in smoothing_spline(&points, &[], 1.0e-3).iter().zip(&points) {
+            assert!(approx_eq(node.y, p.y, 1.0e-6), "{node:?}");
+        }
+        // Least squares: slope 14 / 28 through the means (3, 15 / 7).
+        for node in smoothing_spline(&points, &[], 1.0e3) {
+            let expected = 0.5f64.mul_add(node.x - 3.0, 15.0 / 7.0);
+            assert!(approx_eq(node.y, expected, 1.0e-6), "{node:?}");
+        }
+        let smoothed = eval(&points, &[2.5], InterpAlgorithm::SmoothingSpline)[0];
+        assert!((1.0..3.0).contains(&smoothed), "{smoothed}");
+    }
+
+    #[test]
+    fn smoothing_spline_is_pulled_less_by_low_weight_points() {
+        let mut points: Vec<XYPoint> = (0..11)
+            .map(|x| XYPoint {
+                x: f64::from(x),
+                y: 0.0,
+            })
+            .collect();
+        points[5].y = 10.0;
+        let full = smoothing_spline(&points, &[], 2.0);
+        let mut weights = vec![1.0; points.len()];
+        weights[5] = 0.05;
+        let faint = smoothing_spline(&points, &weights, 2.0);
+        assert!(
+            faint[5].y < 0.5 * full[5].y,
+            "{} vs {}",
+            faint[5].y,
+            full[5].y
+        );
+        assert!(full[5].y < 10.0);
+
+        // Repeated x merge into one node at their weighted mean.
+        let repeated = xy(&[(0.0, 0.0), (1.0, 1.0), (1.0, 4.0), (2.0, 2.0)]);
+        let merged = smoothing_spline(&repeated, &[1.0, 1.0, 2.0, 1.0], 0.0);
+        assert_eq!(merged.len(), 3);
+        assert!(approx_eq(merged[1].y, 3.0, 1.0e-12));
+    }
+
+    #[test]
+    fn pchip_does_not_overshoot_steps() {
+        let points = xy(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.5, 5.0),
+            (3.0, 10.0),
+            (4.0, 10.0),
+            (5.0, 10.0),
+        ]);
+        let xs = build_sample_positions(&points, 201);
+        let ys = eval(&points, &xs, InterpAlgorithm::Pchip);
+        assert!(ys.iter().all(|&y| (-1.0e-9..=10.0 + 1.0e-9).contains(&y)));
+        assert!(ys.windows(2).all(|w| w[1] >= w[0] - 1.0e-9));
+        let natural = eval(&points, &xs, InterpAlgorithm::NaturalCubic);
+        assert!(natural.iter().any(|&y| !(0.0..=10.0).contains(&y)));
+    }
 }
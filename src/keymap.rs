@@ -0,0 +1,281 @@
+//! Keyboard shortcuts: the actions a key can trigger, their default bindings and the
+//! `[keymap]` config section that rebinds them.
+//!
+//! Bindings are written like `"Ctrl+Shift+C"`: modifiers joined by `+` before one key name.
+//! `Ctrl` is the command key, so it stands for Cmd on macOS, and an empty binding leaves the
+//! action without a shortcut.
+
+use egui::{Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Actions a keyboard shortcut can trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    ToggleSidePanel,
+    OpenImage,
+    PasteImage,
+    PastePoints,
+    OpenProject,
+    SaveProject,
+    ExportCsv,
+    ExportJson,
+    ExportRon,
+    ExportExcel,
+    ExportOds,
+    ExportArrow,
+    ExportParquet,
+    ExportSql,
+    ExportNetCdf,
+    ExportHtml,
+    ExportXml,
+    ExportMarkdown,
+    Undo,
+    ClearPoints,
+    ImageInfo,
+    ImageFilters,
+    AutoTrace,
+    FitView,
+    FitWidth,
+    ResetView,
+    PickX1,
+    PickX2,
+    PickY1,
+    PickY2,
+    SelectPoints,
+}
+
+const CTRL: Modifiers = Modifiers::COMMAND;
+const CTRL_SHIFT: Modifiers = Modifiers::COMMAND.plus(Modifiers::SHIFT);
+
+impl ShortcutAction {
+    /// Ordered list of actions shown in the shortcut settings.
+    pub const ALL: [Self; 31] = [
+        Self::ToggleSidePanel,
+        Self::OpenImage,
+        Self::PasteImage,
+        Self::PastePoints,
+        Self::OpenProject,
+        Self::SaveProject,
+        Self::ExportCsv,
+        Self::ExportJson,
+        Self::ExportRon,
+        Self::ExportExcel,
+        Self::ExportOds,
+        Self::ExportArrow,
+        Self::ExportParquet,
+        Self::ExportSql,
+        Self::ExportNetCdf,
+        Self::ExportHtml,
+        Self::ExportXml,
+        Self::ExportMarkdown,
+        Self::Undo,
+        Self::ClearPoints,
+        Self::ImageInfo,
+        Self::ImageFilters,
+        Self::AutoTrace,
+        Self::FitView,
+        Self::FitWidth,
+        Self::ResetView,
+        Self::PickX1,
+        Self::PickX2,
+        Self::PickY1,
+        Self::PickY2,
+        Self::SelectPoints,
+    ];
+
+    /// Binding used while the config does not rebind the action; pick modes start unbound.
+    pub const fn default_shortcut(self) -> Option<KeyboardShortcut> {
+        let (modifiers, key) = match self {
+            Self::ToggleSidePanel => (CTRL, Key::B),
+            Self::OpenImage => (CTRL, Key::O),
+            Self::PasteImage => (CTRL, Key::V),
+            Self::PastePoints => (CTRL_SHIFT, Key::V),
+            Self::OpenProject => (CTRL_SHIFT, Key::P),
+            Self::SaveProject => (CTRL, Key::S),
+            Self::ExportCsv => (CTRL_SHIFT, Key::C),
+            Self::ExportJson => (CTRL_SHIFT, Key::J),
+            Self::ExportRon => (CTRL_SHIFT, Key::R),
+            Self::ExportExcel => (CTRL_SHIFT, Key::E),
+            Self::ExportOds => (CTRL_SHIFT, Key::L),
+            Self::ExportArrow => (CTRL_SHIFT, Key::A),
+            Self::ExportParquet => (CTRL_SHIFT, Key::K),
+            Self::ExportSql => (CTRL_SHIFT, Key::Q),
+            Self::ExportNetCdf => (CTRL_SHIFT, Key::N),
+            Self::ExportHtml => (CTRL_SHIFT, Key::H),
+            Self::ExportXml => (CTRL_SHIFT, Key::X),
+            Self::ExportMarkdown => (CTRL_SHIFT, Key::M),
+            Self::Undo => (CTRL, Key::Z),
+            Self::ClearPoints => (CTRL_SHIFT, Key::D),
+            Self::ImageInfo => (CTRL, Key::I),
+            Self::ImageFilters => (CTRL_SHIFT, Key::F),
+            Self::AutoTrace => (CTRL_SHIFT, Key::T),
+            Self::FitView => (CTRL, Key::F),
+            Self::FitWidth => (CTRL, Key::W),
+            Self::ResetView => (CTRL, Key::R),
+            Self::PickX1 | Self::PickX2 | Self::PickY1 | Self::PickY2 | Self::SelectPoints => {
+                return None;
+            }
+        };
+        Some(KeyboardShortcut::new(modifiers, key))
+    }
+}
+
+/// Parse a binding like `"Ctrl+Shift+C"`; `None` when it is empty or names an unknown key.
+///
+/// Modifiers are matched ignoring case, and `Cmd` and `Command` read as `Ctrl`.
+pub fn parse_shortcut(text: &str) -> Option<KeyboardShortcut> {
+    let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+    let key_name = parts.pop().filter(|name| !name.is_empty())?;
+    let mut modifiers = Modifiers::NONE;
+    for part in parts {
+        let modifier = match part.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" | "cmd" | "command" => CTRL,
+            "shift" => Modifiers::SHIFT,
+            "alt" | "option" => Modifiers::ALT,
+            _ => return None,
+        };
+        modifiers = modifiers.plus(modifier);
+    }
+    let key =
+        Key::from_name(key_name).or_else(|| Key::from_name(&key_name.to_ascii_uppercase()))?;
+    Some(KeyboardShortcut::new(modifiers, key))
+}
+
+/// `shortcut` written the way [`parse_shortcut`] reads it, modifiers in Ctrl, Alt, Shift order.
+pub fn format_shortcut(shortcut: KeyboardShortcut) -> String {
+    let mut parts = Vec::with_capacity(4);
+    let modifiers = shortcut.modifiers;
+    if modifiers.command || modifiers.ctrl || modifiers.mac_cmd {
+        parts.push("Ctrl");
+    }
+    if modifiers.alt {
+        parts.push("Alt");
+    }
+    if modifiers.shift {
+        parts.push("Shift");
+    }
+    parts.push(shortcut.logical_key.name());
+    parts.join("+")
+}
+
+/// `modifiers` as pressed, folded to the ones a binding can name.
+pub const fn binding_modifiers(modifiers: Modifiers) -> Modifiers {
+    let mut folded = Modifiers::NONE;
+    if modifiers.command || modifiers.ctrl || modifiers.mac_cmd {
+        folded = folded.plus(CTRL);
+    }
+    if modifiers.alt {
+        folded = folded.plus(Modifiers::ALT);
+    }
+    if modifiers.shift {
+        folded = folded.plus(Modifiers::SHIFT);
+    }
+    folded
+}
+
+/// Rebound shortcuts (`[keymap]`), by action; actions left out keep their default bindings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeymapConfig {
+    pub bindings: BTreeMap<ShortcutAction, String>,
+}
+
+impl KeymapConfig {
+    /// Shortcut that triggers `action`; an empty or unreadable binding leaves it unbound.
+    pub fn shortcut(&self, action: ShortcutAction) -> Option<KeyboardShortcut> {
+        self.bindings
+            .get(&action)
+            .map_or_else(|| action.default_shortcut(), |text| parse_shortcut(text))
+    }
+
+    /// Bind `action` to `shortcut`, or unbind it with `None`; its default is stored as no entry.
+    pub fn rebind(&mut self, action: ShortcutAction, shortcut: Option<KeyboardShortcut>) {
+        if shortcut == action.default_shortcut() {
+            self.bindings.remove(&action);
+        } else {
+            let text = shortcut.map(format_shortcut).unwrap_or_default();
+            self.bindings.insert(action, text);
+        }
+    }
+
+    /// Other actions bound to the same shortcut as `action`.
+    pub fn conflicts(&self, action: ShortcutAction) -> Vec<ShortcutAction> {
+        let Some(shortcut) = self.shortcut(action) else {
+            return Vec::new();
+        };
+        ShortcutAction::ALL
+            .into_iter()
+            .filter(|&other| other != action && self.shortcut(other) == Some(shortcut))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_read_back_as_written() {
+        let shortcut = parse_shortcut("ctrl + alt + c").expect("binding");
+        assert_eq!(
+            shortcut,
+            KeyboardShortcut::new(CTRL.plus(Modifiers::ALT), Key::C)
+        );
+        assert_eq!(format_shortcut(shortcut), "Ctrl+Alt+C");
+        for text in ["Ctrl+Shift+F5", "Alt+Delete", "Plus", "Ctrl+Up"] {
+            let parsed = parse_shortcut(text).expect(text);
+            assert_eq!(format_shortcut(parsed), text);
+        }
+        assert_eq!(parse_shortcut(""), None);
+        assert_eq!(parse_shortcut("Ctrl+"), None);
+        assert_eq!(parse_shortcut("Hyper+C"), None);
+        assert_eq!(parse_shortcut("Ctrl+Nope"), None);
+    }
+
+    #[test]
+    fn the_config_rebinds_unbinds_and_reports_clashes() {
+        let mut keymap: KeymapConfig = toml::from_str(
+            r#"
+export_csv = "Ctrl+Alt+C"
+undo = ""
+pick_x1 = "Ctrl+B"
+"#,
+        )
+        .expect("keymap");
+        assert_eq!(
+            keymap.shortcut(ShortcutAction::ExportCsv),
+            parse_shortcut("Ctrl+Alt+C")
+        );
+        assert_eq!(keymap.shortcut(ShortcutAction::Undo), None);
+        assert_eq!(
+            keymap.shortcut(ShortcutAction::ExportJson),
+            ShortcutAction::ExportJson.default_shortcut()
+        );
+        assert_eq!(
+            keymap.conflicts(ShortcutAction::PickX1),
+            [ShortcutAction::ToggleSidePanel]
+        );
+
+        keymap.rebind(
+            ShortcutAction::Undo,
+            ShortcutAction::Undo.default_shortcut(),
+        );
+        keymap.rebind(ShortcutAction::PickX1, None);
+        keymap.rebind(ShortcutAction::ExportJson, None);
+        assert_eq!(
+            toml::to_string(&keymap).expect("encode"),
+            "export_csv = \"Ctrl+Alt+C\"\nexport_json = \"\"\n"
+        );
+        assert!(keymap.conflicts(ShortcutAction::ToggleSidePanel).is_empty());
+    }
+
+    #[test]
+    fn default_bindings_do_not_clash() {
+        let keymap = KeymapConfig::default();
+        for action in ShortcutAction::ALL {
+            assert!(keymap.conflicts(action).is_empty(), "{action:?}");
+        }
+    }
+}
@@ -1,35 +1,80 @@
 #![feature(portable_simd)]
 
 mod app;
+mod cache;
+mod calibration_report;
 mod config;
+mod error;
 mod export;
+mod fit;
 mod i18n;
 mod image;
 mod interp;
+mod keymap;
+mod native_dialog;
+mod ocr;
+mod portable;
+mod portal;
+mod profiling;
 mod project;
 mod snap;
+mod spectrum;
 mod types;
 mod util;
+mod validation;
+mod window_geometry;
 
 use app::CurcatApp;
 use std::path::PathBuf;
+use window_geometry::{GEOMETRY_FLAG, WindowGeometry};
 
 fn main() -> eframe::Result<()> {
-    let initial_image_path: Option<PathBuf> = std::env::args_os().nth(1).map(PathBuf::from);
+    let mut portable_flag = false;
+    let mut view_only = false;
+    let mut initial_path: Option<PathBuf> = None;
+    let mut geometry_text: Option<String> = None;
+    let mut args = std::env::args_os().skip(1);
+    while let Some(arg) = args.next() {
+        let inline_geometry = arg
+            .to_str()
+            .and_then(|arg| arg.strip_prefix(GEOMETRY_FLAG)?.strip_prefix('='));
+        if arg == portable::PORTABLE_FLAG {
+            portable_flag = true;
+        } else if arg == app::VIEW_FLAG {
+            view_only = true;
+        } else if arg == profiling::PROFILE_FLAG {
+            profiling::start_recording();
+        } else if let Some(text) = inline_geometry {
+            geometry_text = Some(text.to_string());
+        } else if arg == GEOMETRY_FLAG {
+            geometry_text = args.next().map(|text| text.to_string_lossy().into_owned());
+        } else if initial_path.is_none() {
+            initial_path = Some(PathBuf::from(arg));
+        }
+    }
+    portable::init(portable_flag);
+    let geometry = geometry_text.and_then(|text| {
+        WindowGeometry::parse(&text)
+            .inspect_err(|err| eprintln!("{err}"))
+            .ok()
+    });
+    let window_config = config::AppConfig::load().window;
+    let (viewport, persist_window) = window_geometry::main_viewport(&window_config, geometry);
     let native_options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 800.0])
-            .with_min_inner_size([800.0, 600.0]),
+        viewport,
+        persist_window,
+        persistence_path: portable::persistence_file(),
         ..Default::default()
     };
     eframe::run_native(
         "Curcat — Graph Digitizer",
         native_options,
         Box::new(move |cc| {
-            Ok(Box::new(CurcatApp::new_with_initial_path(
-                &cc.egui_ctx,
-                initial_image_path.as_deref(),
-            )))
+            let mut app = CurcatApp::new_with_initial_path(&cc.egui_ctx, initial_path.as_deref());
+            if view_only {
+                app.enter_view_mode();
+            }
+            Ok(Box::new(app))
         }),
     )
 }
@@ -0,0 +1,64 @@
+//! The operating system's own file dialogs through [`rfd`]: the Windows and macOS dialogs with
+//! their quick-access locations, and on Linux and the BSDs the desktop's dialog, which `rfd`
+//! reaches through xdg-desktop-portal.
+//!
+//! Builds without the `native-dialogs` feature have none and keep the built-in dialog.
+
+use crate::portal::{FileRequest, PendingRequest};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+/// Whether this build has the system file dialogs.
+pub const fn available() -> bool {
+    cfg!(feature = "native-dialogs")
+}
+
+/// Show `request` over `parent` and return without waiting for the user.
+///
+/// Call it on the main thread: macOS creates its dialogs there. Only the answer is awaited
+/// on a background thread. `request.parent_window` is unused; `parent` takes its place.
+#[cfg(feature = "native-dialogs")]
+pub fn start(
+    request: &FileRequest,
+    parent: &(impl HasWindowHandle + HasDisplayHandle),
+) -> PendingRequest {
+    use std::future::Future;
+    use std::pin::Pin;
+
+    type Picked = Pin<Box<dyn Future<Output = Option<Vec<rfd::FileHandle>>> + Send>>;
+
+    let mut dialog = rfd::AsyncFileDialog::new()
+        .set_title(&request.title)
+        .set_parent(parent);
+    for (label, extensions) in &request.filters {
+        dialog = dialog.add_filter(label, extensions);
+    }
+    if let Some(folder) = &request.current_folder {
+        dialog = dialog.set_directory(folder);
+    }
+    if let Some(name) = &request.current_name {
+        dialog = dialog.set_file_name(name);
+    }
+    // The dialog is shown when its future is created, so that happens here and not on the
+    // waiting thread.
+    let picked: Picked = if request.save {
+        let file = dialog.save_file();
+        Box::pin(async move { file.await.map(|file| vec![file]) })
+    } else if request.multiple {
+        Box::pin(dialog.pick_files())
+    } else {
+        let file = dialog.pick_file();
+        Box::pin(async move { file.await.map(|file| vec![file]) })
+    };
+    PendingRequest::spawn(move || {
+        Ok(pollster::block_on(picked)
+            .map(|files| files.iter().map(|file| file.path().to_path_buf()).collect()))
+    })
+}
+
+#[cfg(not(feature = "native-dialogs"))]
+pub fn start(
+    _request: &FileRequest,
+    _parent: &(impl HasWindowHandle + HasDisplayHandle),
+) -> PendingRequest {
+    PendingRequest::spawn(|| anyhow::bail!("this build has no system file dialogs"))
+}
@@ -0,0 +1,19 @@
+//! Axis auto-calibration: detect the axis lines and their tick marks, read the tick labels
+//! with a small built-in digit recognizer, and propose two calibration points per axis.
+//! The same ink components also locate text regions that snapping should ignore, and a
+//! letter font reads legend labels for curve names and the chart's title and axis titles.
+
+mod axes;
+mod calibrate;
+mod caption;
+mod glyphs;
+mod ink;
+mod legend;
+mod text;
+
+pub use calibrate::{AxisProposal, propose_calibration};
+pub use caption::{CaptionOrientation, read_caption};
+#[cfg(test)]
+pub use glyphs::{stamp_text, text_width};
+pub use legend::read_legend_label;
+pub use text::detect_text_regions;
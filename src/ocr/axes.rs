@@ -0,0 +1,295 @@
+//! Axis line and tick mark detection on the ink mask.
+
+use super::ink::{InkMask, PixelBox};
+
+/// An axis line must run for at least this share of the image.
+const MIN_AXIS_SHARE: f32 = 0.3;
+/// Tick marks are this many pixels long at most (measured away from the axis).
+const MAX_TICK_LEN: usize = 25;
+const MIN_TICK_LEN: usize = 2;
+/// Gaps up to this long (a curve drawn across the axis) do not break an axis line.
+const MAX_LINE_GAP: usize = 3;
+/// Tick labels are searched this far beyond the tick ends.
+const LABEL_REACH: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisKind {
+    /// Horizontal axis with labels below it.
+    X,
+    /// Vertical axis with labels to its left.
+    Y,
+}
+
+impl AxisKind {
+    const fn along_len(self, mask: &InkMask) -> usize {
+        match self {
+            Self::X => mask.width(),
+            Self::Y => mask.height(),
+        }
+    }
+
+    const fn across_len(self, mask: &InkMask) -> usize {
+        match self {
+            Self::X => mask.height(),
+            Self::Y => mask.width(),
+        }
+    }
+
+    fn ink(self, mask: &InkMask, along: usize, across: usize) -> bool {
+        match self {
+            Self::X => mask.is_ink(along, across),
+            Self::Y => mask.is_ink(across, along),
+        }
+    }
+}
+
+/// A detected axis line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisLine {
+    pub kind: AxisKind,
+    /// Rows (X) or columns (Y) covered by the line, max exclusive.
+    pub band: (usize, usize),
+    /// Extent along the line, max exclusive.
+    pub span: (usize, usize),
+}
+
+impl AxisLine {
+    /// Continuous image coordinate of the line's center across its thickness.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn center(self) -> f32 {
+        (self.band.0 + self.band.1) as f32 * 0.5
+    }
+
+    /// Index `steps` pixels beyond the line on the label side.
+    const fn outward(self, steps: usize) -> Option<usize> {
+        match self.kind {
+            AxisKind::X => Some(self.band.1 + steps),
+            AxisKind::Y => self.band.0.checked_sub(1 + steps),
+        }
+    }
+
+    /// Index `steps` pixels beyond the line on the plot side.
+    const fn inward(self, steps: usize) -> Option<usize> {
+        match self.kind {
+            AxisKind::X => self.band.0.checked_sub(1 + steps),
+            AxisKind::Y => Some(self.band.1 + steps),
+        }
+    }
+}
+
+/// A tick mark and the region its label is expected in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    /// Continuous image coordinate along the axis.
+    pub at: f32,
+    /// Search window for the label, in image pixels.
+    pub label_window: PixelBox,
+}
+
+/// Longest run of ink at `across`, bridging short gaps, as `(start, len)` along the axis.
+fn longest_run(mask: &InkMask, kind: AxisKind, across: usize) -> (usize, usize) {
+    let mut best = (0, 0);
+    // (run start, last ink pixel)
+    let mut run: Option<(usize, usize)> = None;
+    for along in 0..kind.along_len(mask) {
+        if !kind.ink(mask, along, across) {
+            continue;
+        }
+        run = match run {
+            Some((start, last)) if along - last <= MAX_LINE_GAP + 1 => Some((start, along)),
+            _ => Some((along, along)),
+        };
+        if let Some((start, last)) = run
+            && last + 1 - start > best.1
+        {
+            best = (start, last + 1 - start);
+        }
+    }
+    best
+}
+
+/// Candidate axis lines in preference order: bottom-up for X, left to right for Y.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn axis_candidates(mask: &InkMask, kind: AxisKind) -> Vec<AxisLine> {
+    let along_len = kind.along_len(mask);
+    let min_len = ((along_len as f32 * MIN_AXIS_SHARE) as usize).max(20);
+    let mut lines: Vec<AxisLine> = Vec::new();
+    for across in 0..kind.across_len(mask) {
+        let (start, len) = longest_run(mask, kind, across);
+        if len < min_len {
+            continue;
+        }
+        let span = (start, start + len);
+        match lines.last_mut() {
+            Some(line) if line.band.1 == across => {
+                line.band.1 = across + 1;
+                line.span = (line.span.0.min(span.0), line.span.1.max(span.1));
+            }
+            _ => lines.push(AxisLine {
+                kind,
+                band: (across, across + 1),
+                span,
+            }),
+        }
+    }
+    if kind == AxisKind::X {
+        lines.reverse();
+    }
+    lines
+}
+
+/// Ink length of the perpendicular mark at `along`, stepping with `step` away from the line.
+fn mark_len(
+    mask: &InkMask,
+    line: AxisLine,
+    along: usize,
+    step: impl Fn(usize) -> Option<usize>,
+) -> usize {
+    (0..=MAX_TICK_LEN)
+        .take_while(|&k| step(k).is_some_and(|across| line.kind.ink(mask, along, across)))
+        .count()
+}
+
+/// Tick marks along `line`, outward ones preferred, with a label window for each.
+///
+/// Returns an empty list when fewer than two ticks are found on either side.
+pub fn find_ticks(mask: &InkMask, line: AxisLine) -> Vec<Tick> {
+    let thickness = line.band.1 - line.band.0;
+    let max_tick_width = (3 * thickness).max(6);
+    for outward in [true, false] {
+        let mut groups: Vec<(usize, usize, usize)> = Vec::new();
+        // Ticks centered on the line's ends stick out past it by half their width.
+        let along_end = (line.span.1 + thickness).min(line.kind.along_len(mask));
+        for along in line.span.0.saturating_sub(thickness)..along_end {
+            let len = if outward {
+                mark_len(mask, line, along, |k| line.outward(k))
+            } else {
+                mark_len(mask, line, along, |k| line.inward(k))
+            };
+            if !(MIN_TICK_LEN..=MAX_TICK_LEN).contains(&len) {
+                continue;
+            }
+            match groups.last_mut() {
+                Some(group) if group.1 == along => {
+                    group.1 = along + 1;
+                    group.2 = group.2.max(len);
+                }
+                _ => groups.push((along, along + 1, len)),
+            }
+        }
+        groups.retain(|&(start, end, _)| end - start <= max_tick_width);
+        // Ticks share one length; shorter or longer marks are glyphs or plot content.
+        let mut lens: Vec<usize> = groups.iter().map(|g| g.2).collect();
+        lens.sort_unstable();
+        if let Some(&median) = lens.get(lens.len() / 2) {
+            groups.retain(|g| 2 * g.2 >= median && 2 * g.2 <= 3 * median);
+        }
+        if groups.len() < 2 {
+            continue;
+        }
+        let centers: Vec<usize> = groups.iter().map(|g| g.0 + g.1).collect();
+        return groups
+            .iter()
+            .enumerate()
+            .map(|(idx, &(start, end, len))| {
+                // Doubled coordinates keep half-pixel tick centers exact.
+                let gap = [idx.checked_sub(1), Some(idx + 1)]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|other| centers.get(other))
+                    .map(|&c| c.abs_diff(centers[idx]) / 2)
+                    .min()
+                    .unwrap_or(LABEL_REACH);
+                let half = (gap / 2).max(4);
+                let skip = if outward { len } else { 0 };
+                label_window(line, (start, end), half, skip)
+            })
+            .collect();
+    }
+    Vec::new()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn label_window(line: AxisLine, tick: (usize, usize), half: usize, skip: usize) -> Tick {
+    let center2 = tick.0 + tick.1;
+    let along = (
+        (center2 / 2).saturating_sub(half),
+        center2.div_ceil(2) + half,
+    );
+    let near = line.outward(skip).unwrap_or(0);
+    let far = line.outward(skip + LABEL_REACH).unwrap_or(0);
+    let across = (near.min(far), near.max(far) + 1);
+    let label_window = match line.kind {
+        AxisKind::X => PixelBox {
+            x0: along.0,
+            y0: across.0,
+            x1: along.1,
+            y1: across.1,
+        },
+        AxisKind::Y => PixelBox {
+            x0: across.0,
+            y0: along.0,
+            x1: across.1,
+            y1: along.1,
+        },
+    };
+    Tick {
+        at: center2 as f32 * 0.5,
+        label_window,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Color32, ColorImage};
+
+    fn paint(image: &mut ColorImage, x: std::ops::Range<usize>, y: std::ops::Range<usize>) {
+        for row in y {
+            for col in x.clone() {
+                image.pixels[row * image.size[0] + col] = Color32::BLACK;
+            }
+        }
+    }
+
+    #[test]
+    fn finds_framed_axes_and_their_outward_ticks() {
+        let (w, h) = (200, 120);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        // Plot frame: the top and right edges have no ticks.
+        paint(&mut image, 30..190, 10..12);
+        paint(&mut image, 188..190, 10..100);
+        paint(&mut image, 30..190, 98..100);
+        paint(&mut image, 30..32, 10..100);
+        for x in [31, 80, 130] {
+            paint(&mut image, x..x + 2, 100..105);
+        }
+        for y in [20, 60] {
+            paint(&mut image, 25..30, y..y + 2);
+        }
+        let mask = InkMask::from_image(&image);
+
+        let x_axis = axis_candidates(&mask, AxisKind::X)[0];
+        assert_eq!(x_axis.band, (98, 100));
+        assert!((x_axis.center() - 99.0).abs() < 1e-6);
+        let ticks = find_ticks(&mask, x_axis);
+        let at: Vec<f32> = ticks.iter().map(|t| t.at).collect();
+        assert_eq!(at, vec![32.0, 81.0, 131.0]);
+        assert_eq!(ticks[1].label_window.y0, 105);
+
+        let y_axis = axis_candidates(&mask, AxisKind::Y)[0];
+        assert_eq!(y_axis.band, (30, 32));
+        let ticks = find_ticks(&mask, y_axis);
+        let at: Vec<f32> = ticks.iter().map(|t| t.at).collect();
+        assert_eq!(at, vec![21.0, 61.0]);
+        assert_eq!(ticks[0].label_window.x1, 25);
+
+        let top = axis_candidates(&mask, AxisKind::X)[1];
+        assert_eq!(top.band, (10, 12));
+        assert!(find_ticks(&mask, top).is_empty());
+    }
+}
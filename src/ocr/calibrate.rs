@@ -0,0 +1,489 @@
+//! Reading tick labels and turning them into two-point axis calibrations.
+
+use super::axes::{self, AxisKind, AxisLine, Tick};
+use super::glyphs;
+use super::ink::{InkMask, PixelBox};
+use crate::types::{AxisUnit, ScaleKind, parse_axis_value};
+use egui::{ColorImage, Pos2, pos2};
+
+/// Labels read with less confidence than this are ignored.
+const MIN_LABEL_CONFIDENCE: f32 = 0.5;
+/// A label agrees with a calibration when it lies within this share of the pair's value span.
+const AGREEMENT_TOLERANCE: f64 = 0.02;
+/// Support given to an axis on which only two labels were read, so nothing cross-checks them.
+const UNCHECKED_SUPPORT: f32 = 0.6;
+/// Axis line candidates tried per axis before giving up.
+const MAX_AXIS_CANDIDATES: usize = 6;
+
+/// Two calibration points proposed for one axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisProposal {
+    pub unit: AxisUnit,
+    pub scale: ScaleKind,
+    pub p1: Pos2,
+    pub p2: Pos2,
+    pub v1_text: String,
+    pub v2_text: String,
+    /// `0..=1`: how well both labels were read, times the share of other labels that agree.
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CalibrationProposal {
+    pub x: Option<AxisProposal>,
+    pub y: Option<AxisProposal>,
+}
+
+/// A tick label read from the image.
+#[derive(Debug, Clone, PartialEq)]
+struct TickLabel {
+    at: f32,
+    text: String,
+    unit: AxisUnit,
+    /// Axis scalar (seconds for date-times).
+    value: f64,
+    confidence: f32,
+}
+
+/// Find the axes, read their tick labels and propose a calibration for each axis found.
+pub fn propose_calibration(image: &ColorImage) -> CalibrationProposal {
+    let mask = InkMask::from_image(image);
+    CalibrationProposal {
+        x: propose_axis(&mask, AxisKind::X),
+        y: propose_axis(&mask, AxisKind::Y),
+    }
+}
+
+fn propose_axis(mask: &InkMask, kind: AxisKind) -> Option<AxisProposal> {
+    axes::axis_candidates(mask, kind)
+        .into_iter()
+        .take(MAX_AXIS_CANDIDATES)
+        .find_map(|line| {
+            let labels: Vec<TickLabel> = axes::find_ticks(mask, line)
+                .iter()
+                .filter_map(|tick| read_label(mask, kind, tick))
+                .collect();
+            fit_axis(line, &labels)
+        })
+}
+
+/// Distance from `at` to the range `lo..hi`, zero inside it.
+#[allow(clippy::cast_precision_loss)]
+fn range_distance(at: f32, lo: usize, hi: usize) -> f32 {
+    (lo as f32 - at).max(at - hi as f32).max(0.0)
+}
+
+/// Read the text line nearest to `tick` inside its label window.
+#[allow(clippy::cast_precision_loss)]
+fn read_label(mask: &InkMask, kind: AxisKind, tick: &Tick) -> Option<TickLabel> {
+    let window = tick.label_window;
+    let mut blobs = mask.blobs(window);
+    // Seed with the blob lined up with the tick, nearest to the axis.
+    let seed_key = |b: &PixelBox| match kind {
+        AxisKind::X => (
+            range_distance(tick.at, b.x0, b.x1),
+            (b.y0 - window.y0) as f32,
+        ),
+        AxisKind::Y => (
+            range_distance(tick.at, b.y0, b.y1),
+            (window.x1 - b.x1) as f32,
+        ),
+    };
+    let (seed_idx, _) = blobs
+        .iter()
+        .map(seed_key)
+        .enumerate()
+        .filter(|(_, (along, _))| *along <= 2.0)
+        .min_by(|(_, a), (_, b)| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)))?;
+    let mut line = vec![blobs.swap_remove(seed_idx)];
+    let mut bounds = line[0];
+    // Grow along the text line: same rows, gaps no wider than most of a character.
+    while let Some(idx) = blobs.iter().position(|b| {
+        let gap =
+            b.x0.saturating_sub(bounds.x1)
+                .max(bounds.x0.saturating_sub(b.x1)) as f32;
+        let tolerance = (0.8 * bounds.height().max(b.height()) as f32).max(2.0);
+        bounds.vertical_overlap(*b) >= 0.5 && gap <= tolerance
+    }) {
+        let blob = blobs.swap_remove(idx);
+        bounds = bounds.union(blob);
+        line.push(blob);
+    }
+    line.sort_by_key(|b| b.x0);
+    // Marks stacked over each other (the dot inside a dotted zero, a thin stroke that broke
+    // apart when anti-aliased) form one character.
+    let mut chars: Vec<PixelBox> = Vec::new();
+    for blob in line {
+        match chars.last_mut() {
+            Some(last) if last.horizontal_overlap(blob) >= 0.5 => *last = last.union(blob),
+            _ => chars.push(blob),
+        }
+    }
+
+    let mut text = String::new();
+    let mut confidence = 1.0_f32;
+    for blob in chars {
+        let glyph = glyphs::recognize(mask, blob, bounds)?;
+        text.push(glyph.ch);
+        confidence = confidence.min(glyph.score);
+    }
+    let (unit, value) = parse_label(&text)?;
+    Some(TickLabel {
+        at: tick.at,
+        text,
+        unit,
+        value,
+        confidence,
+    })
+}
+
+/// Numbers first; anything else that parses as a date is a date-time label.
+fn parse_label(text: &str) -> Option<(AxisUnit, f64)> {
+    if !text.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    [AxisUnit::Float, AxisUnit::DateTime]
+        .into_iter()
+        .find_map(|unit| Some((unit, parse_axis_value(text, unit)?.to_scalar_seconds())))
+}
+
+/// Pick the two labels most other labels agree with and turn them into an axis proposal.
+#[allow(clippy::cast_precision_loss)]
+fn fit_axis(line: AxisLine, labels: &[TickLabel]) -> Option<AxisProposal> {
+    let float_count = labels.iter().filter(|l| l.unit == AxisUnit::Float).count();
+    let unit = if float_count * 2 >= labels.len() {
+        AxisUnit::Float
+    } else {
+        AxisUnit::DateTime
+    };
+    let labels: Vec<&TickLabel> = labels
+        .iter()
+        .filter(|l| l.unit == unit && l.confidence >= MIN_LABEL_CONFIDENCE)
+        .collect();
+    if labels.len() < 2 {
+        return None;
+    }
+    let log_possible = unit == AxisUnit::Float && labels.iter().all(|l| l.value > 0.0);
+
+    // (agreeing labels, pixel span, scale, i, j)
+    let mut best: Option<(usize, f32, ScaleKind, usize, usize)> = None;
+    for scale in [ScaleKind::Linear, ScaleKind::Log10] {
+        if scale == ScaleKind::Log10 && !log_possible {
+            continue;
+        }
+        let t = |v: f64| match scale {
+            ScaleKind::Linear => v,
+            ScaleKind::Log10 => v.log10(),
+        };
+        for i in 0..labels.len() {
+            for j in i + 1..labels.len() {
+                let (a, b) = (labels[i], labels[j]);
+                let span_px = (b.at - a.at).abs();
+                let span_v = t(b.value) - t(a.value);
+                if span_px < 4.0 || span_v.abs() <= f64::EPSILON {
+                    continue;
+                }
+                let per_px = span_v / f64::from(b.at - a.at);
+                let agree = labels
+                    .iter()
+                    .filter(|l| {
+                        let predicted = per_px.mul_add(f64::from(l.at - a.at), t(a.value));
+                        (predicted - t(l.value)).abs() <= AGREEMENT_TOLERANCE * span_v.abs()
+                    })
+                    .count();
+                let better = best.is_none_or(|(best_agree, best_span, ..)| {
+                    agree > best_agree || (agree == best_agree && span_px > best_span)
+                });
+                if better {
+                    best = Some((agree, span_px, scale, i, j));
+                }
+            }
+        }
+    }
+    let (agree, _, scale, i, j) = best?;
+    let (first, second) = if labels[i].value <= labels[j].value {
+        (labels[i], labels[j])
+    } else {
+        (labels[j], labels[i])
+    };
+    let support = if labels.len() > 2 {
+        agree as f32 / labels.len() as f32
+    } else {
+        UNCHECKED_SUPPORT
+    };
+    let point = |at: f32| match line.kind {
+        AxisKind::X => pos2(at, line.center()),
+        AxisKind::Y => pos2(line.center(), at),
+    };
+    Some(AxisProposal {
+        unit,
+        scale,
+        p1: point(first.at),
+        p2: point(second.at),
+        v1_text: first.text.clone(),
+        v2_text: second.text.clone(),
+        confidence: (first.confidence + second.confidence) * 0.5 * support,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Color32;
+    use std::fmt::Write as _;
+
+    const LEFT: usize = 90;
+    const BOTTOM: usize = 300;
+    const SCALE: usize = 2;
+
+    fn paint(image: &mut ColorImage, x: std::ops::Range<usize>, y: std::ops::Range<usize>) {
+        for row in y {
+            for col in x.clone() {
+                image.pixels[row * image.size[0] + col] = Color32::BLACK;
+            }
+        }
+    }
+
+    /// Axes meeting at (`LEFT`, `BOTTOM`) with labelled outward ticks every `step` pixels.
+    fn chart(x_labels: &[&str], x_step: usize, y_labels: &[&str], y_step: usize) -> ColorImage {
+        let right = LEFT + x_step * (x_labels.len() - 1);
+        let top = BOTTOM - y_step * (y_labels.len() - 1);
+        let size = [right + 90, BOTTOM + 40];
+        let mut image = ColorImage::new(size, vec![Color32::WHITE; size[0] * size[1]]);
+        paint(&mut image, LEFT - 1..right + 20, BOTTOM - 1..BOTTOM + 1);
+        paint(&mut image, LEFT - 1..LEFT + 1, top - 20..BOTTOM + 1);
+        for (idx, label) in x_labels.iter().enumerate() {
+            let x = LEFT + idx * x_step;
+            paint(&mut image, x - 1..x + 1, BOTTOM + 1..BOTTOM + 6);
+            let origin = [x - glyphs::text_width(label, SCALE) / 2, BOTTOM + 10];
+            glyphs::stamp_text(&mut image, label, origin, SCALE, Color32::BLACK);
+        }
+        for (idx, label) in y_labels.iter().enumerate() {
+            let y = BOTTOM - idx * y_step;
+            paint(&mut image, LEFT - 6..LEFT - 1, y - 1..y + 1);
+            let origin = [LEFT - 10 - glyphs::text_width(label, SCALE), y - 7];
+            glyphs::stamp_text(&mut image, label, origin, SCALE, Color32::BLACK);
+        }
+        image
+    }
+
+    /// [`chart`] drawn as SVG, its labels set `font_size` pixels high in `family`.
+    fn font_chart(
+        family: &str,
+        font_size: usize,
+        (x_labels, x_step): (&[&str], usize),
+        (y_labels, y_step): (&[&str], usize),
+    ) -> ColorImage {
+        let right = LEFT + x_step * (x_labels.len() - 1);
+        let top = BOTTOM - y_step * (y_labels.len() - 1);
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">
+                <rect width="100%" height="100%" fill="white"/>
+                <g stroke="black" stroke-width="2" fill="none">
+                <path d="M{LEFT} {} V{BOTTOM} H{}"/>"#,
+            right + 90,
+            BOTTOM + 40,
+            top - 20,
+            right + 20,
+        );
+        for idx in 0..x_labels.len() {
+            let _ = write!(svg, r#"<path d="M{} {BOTTOM} v6"/>"#, LEFT + idx * x_step);
+        }
+        for idx in 0..y_labels.len() {
+            let _ = write!(svg, r#"<path d="M{LEFT} {} h-6"/>"#, BOTTOM - idx * y_step);
+        }
+        let _ = write!(
+            svg,
+            r#"</g><g font-family="{family}" font-size="{font_size}">"#
+        );
+        for (idx, label) in x_labels.iter().enumerate() {
+            let _ = write!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="middle">{label}</text>"#,
+                LEFT + idx * x_step,
+                BOTTOM + 10 + font_size,
+            );
+        }
+        for (idx, label) in y_labels.iter().enumerate() {
+            let _ = write!(
+                svg,
+                r#"<text x="{}" y="{}" text-anchor="end">{label}</text>"#,
+                LEFT - 10,
+                BOTTOM - idx * y_step + font_size * 7 / 20,
+            );
+        }
+        crate::image::render_svg(&(svg + "</g></svg>"))
+    }
+
+    /// Label of the tick nearest to `offset` pixels from the axes' corner.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn label_at<'a>(labels: &[&'a str], step: usize, offset: f32) -> &'a str {
+        labels[(offset / step as f32).round() as usize]
+    }
+
+    #[test]
+    fn proposes_both_axes_from_labelled_ticks() {
+        let image = chart(
+            &["0", "2.5", "5", "7.5", "10"],
+            100,
+            &["-1", "0", "1", "2"],
+            80,
+        );
+        let proposal = propose_calibration(&image);
+
+        let x = proposal.x.expect("x axis");
+        assert_eq!((x.unit, x.scale), (AxisUnit::Float, ScaleKind::Linear));
+        assert_eq!((x.v1_text.as_str(), x.v2_text.as_str()), ("0", "10"));
+        assert_eq!(x.p1, pos2(90.0, 300.0));
+        assert_eq!(x.p2, pos2(490.0, 300.0));
+        assert!(x.confidence > 0.9, "x confidence {}", x.confidence);
+
+        let y = proposal.y.expect("y axis");
+        assert_eq!((y.v1_text.as_str(), y.v2_text.as_str()), ("-1", "2"));
+        assert_eq!(y.p1, pos2(90.0, 300.0));
+        assert_eq!(y.p2, pos2(90.0, 60.0));
+        assert!(y.confidence > 0.9, "y confidence {}", y.confidence);
+    }
+
+    #[test]
+    fn log_and_date_axes_are_recognized() {
+        let image = chart(
+            &["2024-01-01", "2024-01-11", "2024-01-21"],
+            150,
+            &["1", "10", "100", "1000"],
+            60,
+        );
+        let proposal = propose_calibration(&image);
+        let x = proposal.x.expect("x axis");
+        assert_eq!(x.unit, AxisUnit::DateTime);
+        assert_eq!(x.v2_text, "2024-01-21");
+        let y = proposal.y.expect("y axis");
+        assert_eq!(y.scale, ScaleKind::Log10);
+        assert_eq!((y.v1_text.as_str(), y.v2_text.as_str()), ("1", "1000"));
+    }
+
+    #[test]
+    fn a_label_that_disagrees_lowers_confidence_but_not_the_fit() {
+        let image = chart(&["0", "1", "2", "9", "4"], 80, &["0", "5"], 100);
+        let proposal = propose_calibration(&image);
+        let x = proposal.x.expect("x axis");
+        assert_eq!((x.v1_text.as_str(), x.v2_text.as_str()), ("0", "4"));
+        assert!(
+            (x.confidence - 0.8).abs() < 0.05,
+            "x confidence {}",
+            x.confidence
+        );
+        let y = proposal.y.expect("y axis");
+        assert!((y.confidence - UNCHECKED_SUPPORT).abs() < 0.05);
+    }
+
+    #[test]
+    fn labels_set_in_a_real_font_are_read() {
+        for family in ["DejaVu Sans", "DejaVu Serif"] {
+            let image = font_chart(
+                family,
+                16,
+                (&["0", "2.5", "5", "7.5", "10"], 100),
+                (&["-1", "0", "1", "2"], 80),
+            );
+            let proposal = propose_calibration(&image);
+
+            let x = proposal.x.expect("x axis");
+            assert_eq!(
+                (x.v1_text.as_str(), x.v2_text.as_str()),
+                ("0", "10"),
+                "{family}"
+            );
+            assert_eq!((x.p1, x.p2), (pos2(90.0, 300.0), pos2(490.0, 300.0)));
+            assert!(
+                x.confidence >= MIN_LABEL_CONFIDENCE,
+                "{family} x {}",
+                x.confidence
+            );
+
+            let y = proposal.y.expect("y axis");
+            assert_eq!(
+                (y.v1_text.as_str(), y.v2_text.as_str()),
+                ("-1", "2"),
+                "{family}"
+            );
+            assert_eq!((y.p1, y.p2), (pos2(90.0, 300.0), pos2(90.0, 60.0)));
+            assert!(
+                y.confidence >= MIN_LABEL_CONFIDENCE,
+                "{family} y {}",
+                y.confidence
+            );
+        }
+    }
+
+    #[test]
+    fn small_font_labels_are_proposed_right_or_as_unsure() {
+        let label_sets: [(&[&str], usize, &[&str], usize); 3] = [
+            (
+                &["0", "2.5", "5", "7.5", "10"],
+                100,
+                &["-1", "0", "1", "2"],
+                80,
+            ),
+            (
+                &["0", "20", "40", "60", "80", "100"],
+                80,
+                &["1", "10", "100", "1000"],
+                80,
+            ),
+            (
+                &["2024-01-01", "2024-01-11", "2024-01-21"],
+                200,
+                &["0.1", "0.2", "0.3", "0.4", "0.5", "0.6"],
+                48,
+            ),
+        ];
+        for family in ["DejaVu Sans", "DejaVu Serif", "DejaVu Sans Mono"] {
+            for ((x_labels, x_step, y_labels, y_step), font_size) in label_sets
+                .into_iter()
+                .flat_map(|set| [(set, 10), (set, 12)])
+            {
+                let image = font_chart(family, font_size, (x_labels, x_step), (y_labels, y_step));
+                let proposal = propose_calibration(&image);
+                // A proposal that is not reported as unsure names the labels at its points.
+                let confident = |axis: Option<AxisProposal>| {
+                    axis.filter(|a| a.confidence >= MIN_LABEL_CONFIDENCE)
+                };
+                if let Some(x) = confident(proposal.x) {
+                    assert_eq!(
+                        x.v1_text,
+                        label_at(x_labels, x_step, x.p1.x - 90.0),
+                        "{family}"
+                    );
+                    assert_eq!(
+                        x.v2_text,
+                        label_at(x_labels, x_step, x.p2.x - 90.0),
+                        "{family}"
+                    );
+                }
+                if let Some(y) = confident(proposal.y) {
+                    assert_eq!(
+                        y.v1_text,
+                        label_at(y_labels, y_step, 300.0 - y.p1.y),
+                        "{family}"
+                    );
+                    assert_eq!(
+                        y.v2_text,
+                        label_at(y_labels, y_step, 300.0 - y.p2.y),
+                        "{family}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn images_without_axes_yield_no_proposal() {
+        let image = ColorImage::new([120, 80], vec![Color32::WHITE; 120 * 80]);
+        assert_eq!(propose_calibration(&image), CalibrationProposal::default());
+    }
+}
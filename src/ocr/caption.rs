@@ -0,0 +1,236 @@
+//! Chart captions: the title and the axis titles, read from a click on their text.
+//!
+//! The ink marks around the clicked point are chained left and right into one line, as
+//! legend labels are chained right of their swatch, and read with the same letter templates.
+//! Y axis titles usually run bottom to top, so that neighbourhood is turned upright first.
+//!
+//! Captions share the legend's limits: plain sans-serif text from about 24 pixels is mostly
+//! read right, anything else is a suggestion to check.
+
+use super::ink::{InkMask, PixelBox};
+use super::legend::{
+    LABEL_GAP_RATIO, LABEL_SEARCH_WIDTH, MAX_GLYPH_SIZE, MIN_GLYPH_SIZE, read_line,
+};
+use egui::ColorImage;
+
+/// A click at most this far beside the nearest character still selects its line.
+const CLICK_SLACK: usize = 8;
+
+/// Reading direction of a caption.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptionOrientation {
+    Horizontal,
+    /// Rotated a quarter turn counter-clockwise, as most Y axis titles are.
+    BottomToTop,
+}
+
+/// Text of the caption line under or next to pixel `(x, y)`.
+///
+/// Returns `None` when no character is near `(x, y)` or the line is mostly illegible.
+pub fn read_caption(
+    image: &ColorImage,
+    x: usize,
+    y: usize,
+    orientation: CaptionOrientation,
+) -> Option<String> {
+    let [width, height] = image.size;
+    if x >= width || y >= height {
+        return None;
+    }
+    match orientation {
+        CaptionOrientation::Horizontal => {
+            let mask = InkMask::from_image(image);
+            read_line(&mask, &line_glyphs(&mask, x, y)?)
+        }
+        CaptionOrientation::BottomToTop => {
+            let (upright, [ux, uy]) = upright_neighbourhood(image, x, y);
+            let mask = InkMask::from_image(&upright);
+            read_line(&mask, &line_glyphs(&mask, ux, uy)?)
+        }
+    }
+}
+
+/// The part of `image` a bottom-to-top caption through `(x, y)` can cover, turned a
+/// quarter clockwise, and where `(x, y)` lands in it.
+fn upright_neighbourhood(image: &ColorImage, x: usize, y: usize) -> (ColorImage, [usize; 2]) {
+    let [width, height] = image.size;
+    let (x0, x1) = (
+        x.saturating_sub(2 * MAX_GLYPH_SIZE),
+        (x + 2 * MAX_GLYPH_SIZE + 1).min(width),
+    );
+    let (y0, y1) = (
+        y.saturating_sub(LABEL_SEARCH_WIDTH),
+        (y + LABEL_SEARCH_WIDTH + 1).min(height),
+    );
+    let (upright_w, upright_h) = (y1 - y0, x1 - x0);
+    let mut pixels = Vec::with_capacity(upright_w * upright_h);
+    for uy in 0..upright_h {
+        for ux in 0..upright_w {
+            pixels.push(image.pixels[(y1 - 1 - ux) * width + x0 + uy]);
+        }
+    }
+    let upright = ColorImage::new([upright_w, upright_h], pixels);
+    (upright, [y1 - 1 - y, x - x0])
+}
+
+/// Character boxes of the line through `(x, y)`, left to right.
+#[allow(clippy::cast_precision_loss)]
+fn line_glyphs(mask: &InkMask, x: usize, y: usize) -> Option<Vec<PixelBox>> {
+    let window = PixelBox {
+        x0: x.saturating_sub(LABEL_SEARCH_WIDTH),
+        y0: y.saturating_sub(MAX_GLYPH_SIZE),
+        x1: x + LABEL_SEARCH_WIDTH + 1,
+        y1: y + MAX_GLYPH_SIZE + 1,
+    };
+    let mut blobs: Vec<PixelBox> = mask
+        .blobs(window)
+        .into_iter()
+        .filter(|b| b.width().max(b.height()) <= MAX_GLYPH_SIZE)
+        .collect();
+    blobs.sort_by_key(|b| b.x0);
+
+    let beside = |b: &PixelBox| b.x0.saturating_sub(x).max((x + 1).saturating_sub(b.x1));
+    let seed = blobs
+        .iter()
+        .copied()
+        .filter(|b| b.y0 <= y && y < b.y1 && b.height() >= MIN_GLYPH_SIZE)
+        .min_by_key(beside)
+        .filter(|b| beside(b) <= CLICK_SLACK)?;
+    // Room for ascenders and descenders even when the clicked character is lowercase.
+    let reach = seed.height() * 3 / 4;
+    let (band_top, band_bottom) = (seed.y0.saturating_sub(reach), seed.y1 + reach);
+    let line: Vec<PixelBox> = blobs
+        .into_iter()
+        .filter(|b| b.y0 >= band_top && b.y1 <= band_bottom)
+        .collect();
+    let seed_idx = line.iter().position(|b| *b == seed)?;
+
+    let mut line_height = seed.height();
+    let (mut end, mut right) = (seed_idx + 1, seed.x1);
+    for blob in &line[end..] {
+        if blob.x0.saturating_sub(right) as f32 > LABEL_GAP_RATIO * line_height as f32 {
+            break;
+        }
+        right = right.max(blob.x1);
+        line_height = line_height.max(blob.height());
+        end += 1;
+    }
+    let (mut start, mut left) = (seed_idx, seed.x0);
+    for blob in line[..seed_idx].iter().rev() {
+        if left.saturating_sub(blob.x1) as f32 > LABEL_GAP_RATIO * line_height as f32 {
+            break;
+        }
+        left = left.min(blob.x0);
+        line_height = line_height.max(blob.height());
+        start -= 1;
+    }
+
+    let mut glyphs: Vec<PixelBox> = Vec::new();
+    for &blob in &line[start..end] {
+        match glyphs.last_mut() {
+            Some(last) if last.horizontal_overlap(blob) >= 0.5 => *last = last.union(blob),
+            _ => glyphs.push(blob),
+        }
+    }
+    Some(glyphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::legend::stamp_label;
+    use egui::Color32;
+
+    /// A copy of `image` turned a quarter counter-clockwise.
+    fn rotated_counter_clockwise(image: &ColorImage) -> ColorImage {
+        let [width, height] = image.size;
+        let mut pixels = vec![Color32::WHITE; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                pixels[(width - 1 - x) * height + y] = image.pixels[y * width + x];
+            }
+        }
+        ColorImage::new([height, width], pixels)
+    }
+
+    fn blank(width: usize, height: usize) -> ColorImage {
+        ColorImage::new([width, height], vec![Color32::WHITE; width * height])
+    }
+
+    /// `caption` set `font_size` pixels high in `DejaVu Sans`, once with its baseline at
+    /// (40, 60) and once turned bottom to top with its baseline at x = 60 from y = 560 up.
+    fn font_captions(caption: &str, font_size: usize) -> ColorImage {
+        crate::image::render_svg(&format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="600" height="600">
+                <rect width="100%" height="100%" fill="white"/>
+                <text x="40" y="60" font-family="DejaVu Sans" font-size="{font_size}">{caption}</text>
+                <text transform="translate(60 560) rotate(-90)" font-family="DejaVu Sans"
+                    font-size="{font_size}">{caption}</text>
+            </svg>"#
+        ))
+    }
+
+    #[test]
+    fn a_click_anywhere_on_the_title_reads_the_whole_line() {
+        let mut image = blank(500, 100);
+        stamp_label(
+            &mut image,
+            "Phase shift vs Time (s)",
+            [40, 30],
+            3,
+            Color32::BLACK,
+        );
+        for x in [45, 200, 380] {
+            assert_eq!(
+                read_caption(&image, x, 40, CaptionOrientation::Horizontal).as_deref(),
+                Some("Phase shift vs Time (s)")
+            );
+        }
+        assert_eq!(
+            read_caption(&image, 250, 90, CaptionOrientation::Horizontal),
+            None
+        );
+    }
+
+    #[test]
+    fn bottom_to_top_axis_titles_are_turned_upright() {
+        let mut image = blank(300, 80);
+        stamp_label(&mut image, "Voltage, mV", [30, 20], 2, Color32::BLACK);
+        let rotated = rotated_counter_clockwise(&image);
+        // Pixel (60, 26) of the horizontal label after the quarter turn.
+        let (x, y) = (26, 300 - 1 - 60);
+        assert_eq!(
+            read_caption(&rotated, x, y, CaptionOrientation::BottomToTop).as_deref(),
+            Some("Voltage, mV")
+        );
+    }
+
+    #[test]
+    fn captions_set_in_a_real_sans_font_are_read() {
+        for font_size in [24, 32] {
+            for caption in ["Time, s", "Voltage, mV", "Temperature", "Frequency, Hz"] {
+                let image = font_captions(caption, font_size);
+                // Inside the first letter, a third of the font size off the baseline.
+                let (along, across) = (font_size / 4, font_size / 3);
+                let horizontal = read_caption(
+                    &image,
+                    40 + along,
+                    60 - across,
+                    CaptionOrientation::Horizontal,
+                );
+                let upright = read_caption(
+                    &image,
+                    60 - across,
+                    560 - along,
+                    CaptionOrientation::BottomToTop,
+                );
+                assert_eq!(
+                    horizontal.as_deref(),
+                    Some(caption),
+                    "font size {font_size}"
+                );
+                assert_eq!(upright.as_deref(), Some(caption), "font size {font_size}");
+            }
+        }
+    }
+}
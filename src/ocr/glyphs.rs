@@ -0,0 +1,342 @@
+//! Character recognition by template matching against a built-in 5×7 font.
+//!
+//! Digits and `/` are resampled onto the 5×7 grid (narrow glyphs such as `1` keep their aspect
+//! ratio and are centered) and compared cell by cell with every template. `.` and `-` are too
+//! small for that, so they are told apart by their size and height within the text line.
+//!
+//! The templates are a bitmap font, and anti-aliased digits in a real font match them only
+//! roughly: labels set in a plain sans or serif font at 12 pixels and up are usually read
+//! right but score well below text in the template font, and some digits (`6`, `8`, `9`) can
+//! score too low for calibration to use their label. Smaller text blurs into the wrong digits
+//! on the grid, so scores shrink with glyph height under [`RELIABLE_GLYPH_HEIGHT`] and such
+//! reads show up as unsure rather than wrong.
+
+use super::ink::{InkMask, PixelBox};
+
+const GRID_W: usize = 5;
+pub(super) const GRID_H: usize = 7;
+/// Samples per grid cell side when measuring ink coverage.
+const CELL_SAMPLES: usize = 3;
+/// Template distance below which the runner-up makes a match ambiguous.
+const AMBIGUITY_MARGIN: f32 = 0.08;
+/// Marks shorter than this share of the line height are punctuation.
+const SMALL_MARK_HEIGHT: f32 = 0.45;
+/// Digits shorter than this many pixels score lower in proportion: anti-aliased font
+/// glyphs that small blur into each other on the grid.
+const RELIABLE_GLYPH_HEIGHT: f32 = 10.0;
+
+pub(super) const TEMPLATES: [(char, [&str; GRID_H]); 11] = [
+    (
+        '0',
+        [
+            ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '1',
+        [
+            "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
+        ],
+    ),
+    (
+        '2',
+        [
+            ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
+        ],
+    ),
+    (
+        '3',
+        [
+            ".###.", "#...#", "....#", "..##.", "....#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '4',
+        [
+            "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
+        ],
+    ),
+    (
+        '5',
+        [
+            "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '6',
+        [
+            "..##.", ".#...", "#....", "####.", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '7',
+        [
+            "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
+        ],
+    ),
+    (
+        '8',
+        [
+            ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        '9',
+        [
+            ".###.", "#...#", "#...#", ".####", "....#", "...#.", ".##..",
+        ],
+    ),
+    (
+        '/',
+        [
+            "....#", "...#.", "...#.", "..#..", ".#...", ".#...", "#....",
+        ],
+    ),
+];
+
+/// One recognized character.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    pub ch: char,
+    /// Match quality in `0..=1`.
+    pub score: f32,
+}
+
+/// Recognize the ink blob `glyph` on a text line whose bounds are `line`.
+///
+/// Returns `None` for marks that match no supported character.
+#[allow(clippy::cast_precision_loss)]
+pub fn recognize(mask: &InkMask, glyph: PixelBox, line: PixelBox) -> Option<Glyph> {
+    let (w, h) = (glyph.width() as f32, glyph.height() as f32);
+    let line_h = line.height() as f32;
+    if h < SMALL_MARK_HEIGHT * line_h {
+        let fill = mask.fill(glyph);
+        let rel_y = (glyph.center_y() - line.y0 as f32) / line_h;
+        if w >= 1.5 * h && (0.25..=0.75).contains(&rel_y) {
+            return Some(Glyph {
+                ch: '-',
+                score: fill,
+            });
+        }
+        if w <= 2.0 * h && h <= 2.0 * w && rel_y >= 0.65 {
+            return Some(Glyph {
+                ch: '.',
+                score: (fill + 0.2).min(1.0),
+            });
+        }
+        return None;
+    }
+
+    let coverage = coverage_grid(mask, glyph);
+    let mut best = (f32::INFINITY, ' ');
+    let mut second = f32::INFINITY;
+    for (ch, rows) in TEMPLATES {
+        let distance = template_distance(&coverage, &rows);
+        if distance < best.0 {
+            second = best.0;
+            best = (distance, ch);
+        } else if distance < second {
+            second = distance;
+        }
+    }
+    let margin = ((second - best.0) / AMBIGUITY_MARGIN).min(1.0);
+    let legible = (h / RELIABLE_GLYPH_HEIGHT).min(1.0);
+    Some(Glyph {
+        ch: best.1,
+        score: (1.0 - best.0) * 0.5f32.mul_add(margin, 0.5) * legible,
+    })
+}
+
+/// Ink coverage of each 5×7 grid cell after fitting the glyph onto the grid.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn coverage_grid(mask: &InkMask, glyph: PixelBox) -> [[f32; GRID_W]; GRID_H] {
+    let (w, h) = (glyph.width() as f32, glyph.height() as f32);
+    let cells_wide = GRID_H as f32 * w / h;
+    let (span, offset) = if cells_wide >= 3.5 {
+        (GRID_W, 0)
+    } else {
+        let span = (cells_wide.round() as usize).clamp(1, GRID_W);
+        (span, (GRID_W - span) / 2)
+    };
+    let samples = CELL_SAMPLES as f32;
+    let mut grid = [[0.0; GRID_W]; GRID_H];
+    for (row, cells) in grid.iter_mut().enumerate() {
+        for (col, cell) in cells.iter_mut().enumerate().skip(offset).take(span) {
+            let mut hits = 0_u32;
+            for sy in 0..CELL_SAMPLES {
+                for sx in 0..CELL_SAMPLES {
+                    let fx = ((col - offset) as f32 + (sx as f32 + 0.5) / samples) / span as f32;
+                    let fy = (row as f32 + (sy as f32 + 0.5) / samples) / GRID_H as f32;
+                    let x = glyph.x0 + (fx * w) as usize;
+                    let y = glyph.y0 + (fy * h) as usize;
+                    hits += u32::from(mask.is_ink(x, y));
+                }
+            }
+            *cell = hits as f32 / (samples * samples);
+        }
+    }
+    grid
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn template_distance(coverage: &[[f32; GRID_W]; GRID_H], rows: &[&str; GRID_H]) -> f32 {
+    let total: f32 = coverage
+        .iter()
+        .zip(rows)
+        .flat_map(|(cells, row)| {
+            cells
+                .iter()
+                .zip(row.bytes())
+                .map(|(&cov, cell)| (cov - f32::from(u8::from(cell == b'#'))).abs())
+        })
+        .sum();
+    total / (GRID_W * GRID_H) as f32
+}
+
+/// Width in pixels of `text` painted by [`stamp_text`].
+#[cfg(test)]
+pub fn text_width(text: &str, scale: usize) -> usize {
+    let cells: usize = text
+        .chars()
+        .map(|ch| if ch == '.' { 2 } else { GRID_W + 1 })
+        .sum();
+    cells.saturating_sub(1) * scale
+}
+
+/// Paint `text` in the template font with `scale`-pixel cells, top-left corner at `origin`.
+#[cfg(test)]
+pub fn stamp_text(
+    image: &mut egui::ColorImage,
+    text: &str,
+    origin: [usize; 2],
+    scale: usize,
+    color: egui::Color32,
+) {
+    let mut paint_cell = |col: usize, row: usize| {
+        for dy in 0..scale {
+            for dx in 0..scale {
+                let (x, y) = (col * scale + dx, row * scale + dy);
+                let (x, y) = (origin[0] + x, origin[1] + y);
+                if x < image.size[0] && y < image.size[1] {
+                    image.pixels[y * image.size[0] + x] = color;
+                }
+            }
+        }
+    };
+    let mut cursor = 0;
+    for ch in text.chars() {
+        match ch {
+            '.' => {
+                paint_cell(cursor, GRID_H - 1);
+                cursor += 2;
+                continue;
+            }
+            '-' => (0..GRID_W).for_each(|col| paint_cell(cursor + col, GRID_H / 2)),
+            _ => {
+                let (_, rows) = TEMPLATES
+                    .iter()
+                    .find(|(t, _)| *t == ch)
+                    .unwrap_or_else(|| panic!("no template for {ch:?}"));
+                for (row, pattern) in rows.iter().enumerate() {
+                    for (col, cell) in pattern.bytes().enumerate() {
+                        if cell == b'#' {
+                            paint_cell(cursor + col, row);
+                        }
+                    }
+                }
+            }
+        }
+        cursor += GRID_W + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Color32, ColorImage};
+
+    fn read_line(text: &str, scale: usize, bold: bool) -> Vec<Glyph> {
+        let size = [text_width(text, scale) + 8, GRID_H * scale + 8];
+        let mut image = ColorImage::new(size, vec![Color32::WHITE; size[0] * size[1]]);
+        stamp_text(&mut image, text, [4, 4], scale, Color32::BLACK);
+        if bold {
+            // Thicken every stroke by one pixel to the right, like a heavier font weight.
+            for y in 0..size[1] {
+                for x in (1..size[0]).rev() {
+                    if image.pixels[y * size[0] + x - 1] == Color32::BLACK {
+                        image.pixels[y * size[0] + x] = Color32::BLACK;
+                    }
+                }
+            }
+        }
+        read_image(&image)
+    }
+
+    /// Recognize every ink mark of `image` as one line of text.
+    fn read_image(image: &ColorImage) -> Vec<Glyph> {
+        let mask = InkMask::from_image(image);
+        let mut blobs = mask.blobs(PixelBox {
+            x0: 0,
+            y0: 0,
+            x1: image.size[0],
+            y1: image.size[1],
+        });
+        blobs.sort_by_key(|b| b.x0);
+        let line = blobs.iter().copied().reduce(PixelBox::union).expect("ink");
+        blobs
+            .into_iter()
+            .map(|b| recognize(&mask, b, line).expect("recognized glyph"))
+            .collect()
+    }
+
+    #[test]
+    fn every_character_is_read_back_at_several_sizes() {
+        for scale in [1, 2, 3, 5] {
+            let glyphs = read_line("-0123456789/.5", scale, false);
+            let text: String = glyphs.iter().map(|g| g.ch).collect();
+            assert_eq!(text, "-0123456789/.5", "scale {scale}");
+            if scale == 1 {
+                // Read right, but seven pixels are below the height digits are trusted at.
+                assert!(glyphs.iter().all(|g| g.score > 0.6));
+                assert!(glyphs.iter().any(|g| g.score < 0.8));
+            } else {
+                assert!(glyphs.iter().all(|g| g.score > 0.9), "scale {scale}");
+            }
+        }
+    }
+
+    #[test]
+    fn heavier_strokes_still_match_the_right_digit() {
+        let glyphs = read_line("0123456789", 3, true);
+        let text: String = glyphs.iter().map(|g| g.ch).collect();
+        assert_eq!(text, "0123456789");
+        assert!(glyphs.iter().all(|g| g.score > 0.5));
+    }
+
+    #[test]
+    fn digits_set_in_a_real_font_are_read_with_less_certainty() {
+        for font_size in [18, 24] {
+            let image = crate::image::render_svg(&format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">
+                    <rect width="100%" height="100%" fill="white"/>
+                    <text x="4" y="{}" font-family="DejaVu Sans" font-size="{font_size}">-0123456789/.5</text>
+                </svg>"#,
+                font_size * 12,
+                font_size * 2,
+                font_size * 3 / 2,
+            ));
+            let glyphs = read_image(&image);
+            let text: String = glyphs.iter().map(|g| g.ch).collect();
+            assert_eq!(text, "-0123456789/.5", "font size {font_size}");
+            // Anti-aliased outlines fit the bitmap templates worse than their own font does.
+            assert!(
+                glyphs.iter().any(|g| g.score < 0.8),
+                "font size {font_size}"
+            );
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Binary ink mask of dark, near-neutral pixels and connected-component search on it.
+
+use egui::ColorImage;
+
+/// Pixels darker than this luma count as ink.
+const INK_LUMA_MAX: f32 = 128.0;
+/// Pixels whose channels spread further than this are colored plot content, not axis ink.
+const INK_CHROMA_MAX: u8 = 80;
+
+/// Axis-aligned pixel box: `x0..x1` × `y0..y1`, max edges exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelBox {
+    pub x0: usize,
+    pub y0: usize,
+    pub x1: usize,
+    pub y1: usize,
+}
+
+impl PixelBox {
+    pub const fn width(self) -> usize {
+        self.x1 - self.x0
+    }
+
+    pub const fn height(self) -> usize {
+        self.y1 - self.y0
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn center_y(self) -> f32 {
+        (self.y0 + self.y1) as f32 * 0.5
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            x0: self.x0.min(other.x0),
+            y0: self.y0.min(other.y0),
+            x1: self.x1.max(other.x1),
+            y1: self.y1.max(other.y1),
+        }
+    }
+
+    /// Rows shared with `other`, as a share of the shorter box.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn vertical_overlap(self, other: Self) -> f32 {
+        let shared = self.y1.min(other.y1).saturating_sub(self.y0.max(other.y0));
+        shared as f32 / self.height().min(other.height()).max(1) as f32
+    }
+
+    /// Columns shared with `other`, as a share of the narrower box.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn horizontal_overlap(self, other: Self) -> f32 {
+        let shared = self.x1.min(other.x1).saturating_sub(self.x0.max(other.x0));
+        shared as f32 / self.width().min(other.width()).max(1) as f32
+    }
+}
+
+pub struct InkMask {
+    width: usize,
+    height: usize,
+    ink: Vec<bool>,
+}
+
+impl InkMask {
+    pub fn from_image(image: &ColorImage) -> Self {
+        let ink = image
+            .pixels
+            .iter()
+            .map(|c| {
+                let [r, g, b, a] = c.to_array();
+                let chroma = r.max(g).max(b) - r.min(g).min(b);
+                let luma = 0.114f32.mul_add(
+                    f32::from(b),
+                    0.299f32.mul_add(f32::from(r), 0.587 * f32::from(g)),
+                );
+                a >= 128 && chroma <= INK_CHROMA_MAX && luma < INK_LUMA_MAX
+            })
+            .collect();
+        Self {
+            width: image.size[0],
+            height: image.size[1],
+            ink,
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is ink; out-of-bounds pixels are background.
+    pub fn is_ink(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.ink[y * self.width + x]
+    }
+
+    /// Share of ink pixels inside `b`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fill(&self, b: PixelBox) -> f32 {
+        let ink = (b.y0..b.y1)
+            .flat_map(|y| (b.x0..b.x1).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.is_ink(x, y))
+            .count();
+        ink as f32 / (b.width() * b.height()).max(1) as f32
+    }
+
+    /// Bounding boxes of the 8-connected ink components inside `window`, clipped to it.
+    pub fn blobs(&self, window: PixelBox) -> Vec<PixelBox> {
+        let x1 = window.x1.min(self.width);
+        let y1 = window.y1.min(self.height);
+        if window.x0 >= x1 || window.y0 >= y1 {
+            return Vec::new();
+        }
+        let w = x1 - window.x0;
+        let mut seen = vec![false; w * (y1 - window.y0)];
+        let mut blobs = Vec::new();
+        let mut stack = Vec::new();
+        for y in window.y0..y1 {
+            for x in window.x0..x1 {
+                let idx = (y - window.y0) * w + (x - window.x0);
+                if seen[idx] || !self.is_ink(x, y) {
+                    continue;
+                }
+                seen[idx] = true;
+                stack.push((x, y));
+                let mut bounds = PixelBox {
+                    x0: x,
+                    y0: y,
+                    x1: x + 1,
+                    y1: y + 1,
+                };
+                while let Some((cx, cy)) = stack.pop() {
+                    bounds = bounds.union(PixelBox {
+                        x0: cx,
+                        y0: cy,
+                        x1: cx + 1,
+                        y1: cy + 1,
+                    });
+                    for ny in cy.saturating_sub(1)..=(cy + 1).min(y1 - 1) {
+                        for nx in cx.saturating_sub(1)..=(cx + 1).min(x1 - 1) {
+                            if nx < window.x0 || ny < window.y0 {
+                                continue;
+                            }
+                            let nidx = (ny - window.y0) * w + (nx - window.x0);
+                            if !seen[nidx] && self.is_ink(nx, ny) {
+                                seen[nidx] = true;
+                                stack.push((nx, ny));
+                            }
+                        }
+                    }
+                }
+                blobs.push(bounds);
+            }
+        }
+        blobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Color32;
+
+    #[test]
+    fn only_dark_neutral_pixels_are_ink_and_blobs_split_by_gaps() {
+        let mut image = ColorImage::new([8, 4], vec![Color32::WHITE; 32]);
+        for (x, y) in [(1, 1), (2, 2), (5, 1), (5, 2)] {
+            image.pixels[y * 8 + x] = Color32::BLACK;
+        }
+        image.pixels[8 + 7] = Color32::from_rgb(200, 20, 20);
+        let mask = InkMask::from_image(&image);
+        assert!(mask.is_ink(1, 1));
+        assert!(!mask.is_ink(7, 1), "saturated red is plot content");
+        assert!(!mask.is_ink(9, 9));
+
+        let blobs = mask.blobs(PixelBox {
+            x0: 0,
+            y0: 0,
+            x1: 8,
+            y1: 4,
+        });
+        assert_eq!(
+            blobs,
+            vec![
+                PixelBox {
+                    x0: 1,
+                    y0: 1,
+                    x1: 3,
+                    y1: 3
+                },
+                PixelBox {
+                    x0: 5,
+                    y0: 1,
+                    x1: 6,
+                    y1: 3
+                },
+            ]
+        );
+    }
+}
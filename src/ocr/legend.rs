@@ -0,0 +1,820 @@
+//! Legend labels: the text printed to the right of a legend swatch.
+//!
+//! The swatch is flood-filled from the clicked pixel, and the ink marks just right of it on
+//! the same line are chained into a label until a gap wider than a few letters. Marks stacked
+//! over each other (the dot of `i`, the bars of `=`) form one character. Each character is
+//! matched against letter, digit and punctuation templates of the 5×7 font; besides the
+//! shape, its extent relative to the line's baseline and cap height tells `o` from `O` and
+//! `p` from `P`. Characters that fit a letter and a digit equally well (`0`/`O`, `1`/`l`)
+//! follow the rest of their word.
+//!
+//! The templates are a bitmap font, with second shapes for the letters sans-serif fonts draw
+//! differently. Labels in a plain sans-serif font from about 24 pixels are mostly read right;
+//! smaller text and serif or monospaced fonts are misread often, and a misread matches the
+//! templates about as closely as a right read does. A label therefore carries no confidence
+//! and is only a suggestion for the curve name.
+
+use super::glyphs::{GRID_H, TEMPLATES as DIGITS};
+use super::ink::{InkMask, PixelBox};
+use egui::{Color32, ColorImage};
+use std::cmp::Ordering;
+
+/// Swatch pixels differ from the clicked color by at most this much per channel.
+const SWATCH_TOLERANCE: u8 = 48;
+/// A fill larger than this in either direction is not a swatch (plot area, background).
+const MAX_SWATCH_SPAN: usize = 160;
+/// Furthest the label may reach right of the swatch, in pixels.
+pub(super) const LABEL_SEARCH_WIDTH: usize = 640;
+/// Character height range in pixels.
+pub(super) const MIN_GLYPH_SIZE: usize = 5;
+pub(super) const MAX_GLYPH_SIZE: usize = 48;
+/// The label starts within this many character heights of the swatch.
+const LEAD_GAP_RATIO: f32 = 3.0;
+/// A gap wider than this many character heights ends the label (next entry of a row legend).
+pub(super) const LABEL_GAP_RATIO: f32 = 1.5;
+/// Gaps wider than this share of the cap height separate words.
+const SPACE_GAP_RATIO: f32 = 0.35;
+/// Samples per template cell side when measuring ink coverage.
+const CELL_SAMPLES: usize = 3;
+/// Weight of the aspect-ratio mismatch (log ratio) in the match distance.
+const ASPECT_WEIGHT: f32 = 0.15;
+/// Weight of the top/bottom mismatch against the line metrics, in cap heights.
+const ZONE_WEIGHT: f32 = 0.5;
+/// Candidates this close to the best match are equally plausible.
+const AMBIGUITY_MARGIN: f32 = 0.05;
+/// Labels whose characters match worse than this on average are not reported.
+const MAX_MEAN_DISTANCE: f32 = 0.3;
+
+/// Letters and punctuation as `(char, first row, rows)`: rows count from the cap line, so
+/// x-height letters start at row 2 and rows from [`GRID_H`] on are descenders.
+const LETTERS: [(char, usize, &[&str]); 59] = [
+    (
+        'A',
+        0,
+        &[
+            ".###.", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'B',
+        0,
+        &[
+            "####.", "#...#", "#...#", "####.", "#...#", "#...#", "####.",
+        ],
+    ),
+    (
+        'C',
+        0,
+        &[
+            ".###.", "#...#", "#....", "#....", "#....", "#...#", ".###.",
+        ],
+    ),
+    (
+        'D',
+        0,
+        &[
+            "####.", "#...#", "#...#", "#...#", "#...#", "#...#", "####.",
+        ],
+    ),
+    (
+        'E',
+        0,
+        &[
+            "#####", "#....", "#....", "####.", "#....", "#....", "#####",
+        ],
+    ),
+    (
+        'F',
+        0,
+        &[
+            "#####", "#....", "#....", "####.", "#....", "#....", "#....",
+        ],
+    ),
+    (
+        'G',
+        0,
+        &[
+            ".###.", "#...#", "#....", "#.###", "#...#", "#...#", ".####",
+        ],
+    ),
+    (
+        'H',
+        0,
+        &[
+            "#...#", "#...#", "#...#", "#####", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'I',
+        0,
+        &[
+            ".###.", "..#..", "..#..", "..#..", "..#..", "..#..", ".###.",
+        ],
+    ),
+    (
+        'J',
+        0,
+        &[
+            "..###", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##..",
+        ],
+    ),
+    (
+        'K',
+        0,
+        &[
+            "#...#", "#..#.", "#.#..", "##...", "#.#..", "#..#.", "#...#",
+        ],
+    ),
+    (
+        'L',
+        0,
+        &[
+            "#....", "#....", "#....", "#....", "#....", "#....", "#####",
+        ],
+    ),
+    (
+        'M',
+        0,
+        &[
+            "#...#", "##.##", "#.#.#", "#.#.#", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'N',
+        0,
+        &[
+            "#...#", "#...#", "##..#", "#.#.#", "#..##", "#...#", "#...#",
+        ],
+    ),
+    (
+        'O',
+        0,
+        &[
+            ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        'P',
+        0,
+        &[
+            "####.", "#...#", "#...#", "####.", "#....", "#....", "#....",
+        ],
+    ),
+    (
+        'Q',
+        0,
+        &[
+            ".###.", "#...#", "#...#", "#...#", "#.#.#", "#..#.", ".##.#",
+        ],
+    ),
+    (
+        'R',
+        0,
+        &[
+            "####.", "#...#", "#...#", "####.", "#.#..", "#..#.", "#...#",
+        ],
+    ),
+    (
+        'S',
+        0,
+        &[
+            ".####", "#....", "#....", ".###.", "....#", "....#", "####.",
+        ],
+    ),
+    (
+        'T',
+        0,
+        &[
+            "#####", "..#..", "..#..", "..#..", "..#..", "..#..", "..#..",
+        ],
+    ),
+    (
+        'U',
+        0,
+        &[
+            "#...#", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.",
+        ],
+    ),
+    (
+        'V',
+        0,
+        &[
+            "#...#", "#...#", "#...#", "#...#", "#...#", ".#.#.", "..#..",
+        ],
+    ),
+    (
+        'W',
+        0,
+        &[
+            "#...#", "#...#", "#...#", "#.#.#", "#.#.#", "#.#.#", ".#.#.",
+        ],
+    ),
+    (
+        'X',
+        0,
+        &[
+            "#...#", "#...#", ".#.#.", "..#..", ".#.#.", "#...#", "#...#",
+        ],
+    ),
+    (
+        'Y',
+        0,
+        &[
+            "#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "..#..",
+        ],
+    ),
+    (
+        'Z',
+        0,
+        &[
+            "#####", "....#", "...#.", "..#..", ".#...", "#....", "#####",
+        ],
+    ),
+    ('a', 2, &[".###.", "....#", ".####", "#...#", ".####"]),
+    (
+        'b',
+        0,
+        &[
+            "#....", "#....", "#.##.", "##..#", "#...#", "#...#", "####.",
+        ],
+    ),
+    ('c', 2, &[".###.", "#....", "#....", "#...#", ".###."]),
+    (
+        'd',
+        0,
+        &[
+            "....#", "....#", ".##.#", "#..##", "#...#", "#...#", ".####",
+        ],
+    ),
+    ('e', 2, &[".###.", "#...#", "#####", "#....", ".###."]),
+    (
+        'f',
+        0,
+        &[
+            "..##.", ".#..#", ".#...", "###..", ".#...", ".#...", ".#...",
+        ],
+    ),
+    (
+        'g',
+        2,
+        &[
+            ".####", "#...#", "#...#", "#...#", ".####", "....#", ".###.",
+        ],
+    ),
+    (
+        'h',
+        0,
+        &[
+            "#....", "#....", "#.##.", "##..#", "#...#", "#...#", "#...#",
+        ],
+    ),
+    (
+        'i',
+        0,
+        &[
+            "..#..", ".....", ".##..", "..#..", "..#..", "..#..", ".###.",
+        ],
+    ),
+    (
+        'j',
+        0,
+        &[
+            "...#.", ".....", "..##.", "...#.", "...#.", "...#.", "...#.", "#..#.", ".##..",
+        ],
+    ),
+    (
+        'k',
+        0,
+        &[
+            "#....", "#....", "#..#.", "#.#..", "##...", "#.#..", "#..#.",
+        ],
+    ),
+    (
+        'l',
+        0,
+        &[
+            ".##..", "..#..", "..#..", "..#..", "..#..", "..#..", ".###.",
+        ],
+    ),
+    ('m', 2, &["##.#.", "#.#.#", "#.#.#", "#...#", "#...#"]),
+    ('n', 2, &["#.##.", "##..#", "#...#", "#...#", "#...#"]),
+    ('o', 2, &[".###.", "#...#", "#...#", "#...#", ".###."]),
+    (
+        'p',
+        2,
+        &[
+            "####.", "#...#", "#...#", "#...#", "####.", "#....", "#....",
+        ],
+    ),
+    (
+        'q',
+        2,
+        &[
+            ".####", "#...#", "#...#", "#...#", ".####", "....#", "....#",
+        ],
+    ),
+    ('r', 2, &["#.##.", "##..#", "#....", "#....", "#...."]),
+    ('s', 2, &[".####", "#....", ".###.", "....#", "####."]),
+    (
+        't',
+        1,
+        &[".#...", "####.", ".#...", ".#...", ".#..#", "..##."],
+    ),
+    ('u', 2, &["#...#", "#...#", "#...#", "#..##", ".##.#"]),
+    ('v', 2, &["#...#", "#...#", "#...#", ".#.#.", "..#.."]),
+    ('w', 2, &["#...#", "#...#", "#.#.#", "#.#.#", ".#.#."]),
+    ('x', 2, &["#...#", ".#.#.", "..#..", ".#.#.", "#...#"]),
+    (
+        'y',
+        2,
+        &[
+            "#...#", "#...#", "#...#", ".####", "....#", "#...#", ".###.",
+        ],
+    ),
+    ('z', 2, &["#####", "...#.", "..#..", ".#...", "#####"]),
+    ('-', 3, &["#####"]),
+    ('.', 6, &["#"]),
+    ('=', 2, &["#####", ".....", "#####"]),
+    (',', 6, &[".#", "#."]),
+    ('(', 0, &["..#", ".#.", "#..", "#..", "#..", ".#.", "..#"]),
+    (')', 0, &["#..", ".#.", "..#", "..#", "..#", ".#.", "#.."]),
+    (
+        '/',
+        0,
+        &[
+            "....#", "....#", "...#.", "..#..", ".#...", "#....", "#....",
+        ],
+    ),
+];
+
+/// Second shapes for letters that sans-serif fonts draw differently from the 5×7 font: bare
+/// strokes for `l`, `i` and `j`, a `t` nearly as tall as the capitals, a pointed `A`, a
+/// slanted `V` and a `Q` whose tail drops below the baseline.
+const SANS_LETTERS: [(char, usize, &[&str]); 7] = [
+    ('l', 0, &["#", "#", "#", "#", "#", "#", "#"]),
+    ('i', 0, &["#", ".", "#", "#", "#", "#", "#"]),
+    (
+        'j',
+        0,
+        &[".#", "..", ".#", ".#", ".#", ".#", ".#", ".#", "#."],
+    ),
+    (
+        't',
+        0,
+        &[".#..", ".#..", "####", ".#..", ".#..", ".#..", "..##"],
+    ),
+    (
+        'A',
+        0,
+        &[
+            "..#..", ".#.#.", ".#.#.", "#...#", "#####", "#...#", "#...#",
+        ],
+    ),
+    (
+        'V',
+        0,
+        &[
+            "#...#", "#...#", ".#.#.", ".#.#.", ".#.#.", "..#..", "..#..",
+        ],
+    ),
+    (
+        'Q',
+        0,
+        &[
+            ".###.", "#...#", "#...#", "#...#", "#...#", "#...#", ".###.", "...#.", "....#",
+        ],
+    ),
+];
+
+/// A template cropped to its ink, with where it sits relative to the baseline.
+struct Shape {
+    ch: char,
+    cols: usize,
+    rows: usize,
+    cells: Vec<bool>,
+    /// Top edge above the baseline, in cap heights.
+    top: f32,
+    /// Bottom edge below the baseline, in cap heights (negative above it).
+    bottom: f32,
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn shapes() -> Vec<Shape> {
+    let digits = DIGITS.iter().map(|(ch, rows)| (*ch, 0, rows.as_slice()));
+    digits
+        .chain(LETTERS.iter().chain(&SANS_LETTERS).copied())
+        .map(|(ch, top, rows)| {
+            let (first, last) = ink_columns(rows);
+            let cols = last - first + 1;
+            let cells = rows
+                .iter()
+                .flat_map(|row| row.bytes().skip(first).take(cols).map(|cell| cell == b'#'))
+                .collect();
+            let grid = GRID_H as f32;
+            Shape {
+                ch,
+                cols,
+                rows: rows.len(),
+                cells,
+                top: (grid - top as f32) / grid,
+                bottom: (top + rows.len()) as f32 / grid - 1.0,
+            }
+        })
+        .collect()
+}
+
+/// How well one template fits a character box.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    ch: char,
+    /// Shape, aspect and zone mismatch together; lower is better.
+    distance: f32,
+    /// Mismatch of the box's top and bottom against the template's, in cap heights.
+    zone: f32,
+}
+
+/// First and last column with ink in any row.
+fn ink_columns(rows: &[&str]) -> (usize, usize) {
+    let col0 = rows.iter().filter_map(|row| row.find('#')).min();
+    let col1 = rows.iter().filter_map(|row| row.rfind('#')).max();
+    (col0.unwrap_or(0), col1.unwrap_or(0))
+}
+
+/// Text of the legend label right of the swatch under pixel `(x, y)`.
+///
+/// Returns `None` when `(x, y)` is not on a swatch-sized patch of color or no legible text
+/// follows it.
+pub fn read_legend_label(image: &ColorImage, x: usize, y: usize) -> Option<String> {
+    let swatch = swatch_bounds(image, x, y)?;
+    let mask = InkMask::from_image(image);
+    let glyphs = label_glyphs(&mask, swatch);
+    read_line(&mask, &glyphs)
+}
+
+/// Bounds of the 4-connected patch of the clicked color, if it is swatch-sized.
+fn swatch_bounds(image: &ColorImage, x: usize, y: usize) -> Option<PixelBox> {
+    let [width, height] = image.size;
+    if x >= width || y >= height {
+        return None;
+    }
+    let target = image.pixels[y * width + x];
+    let similar = |c: Color32| {
+        let (a, b) = (c.to_array(), target.to_array());
+        (0..3).all(|i| a[i].abs_diff(b[i]) <= SWATCH_TOLERANCE)
+    };
+    let mut seen = vec![false; width * height];
+    let mut stack = vec![(x, y)];
+    seen[y * width + x] = true;
+    let mut bounds = PixelBox {
+        x0: x,
+        y0: y,
+        x1: x + 1,
+        y1: y + 1,
+    };
+    while let Some((cx, cy)) = stack.pop() {
+        bounds = bounds.union(PixelBox {
+            x0: cx,
+            y0: cy,
+            x1: cx + 1,
+            y1: cy + 1,
+        });
+        if bounds.width() > MAX_SWATCH_SPAN || bounds.height() > MAX_SWATCH_SPAN {
+            return None;
+        }
+        let neighbours = [
+            (cx.wrapping_sub(1), cy),
+            (cx + 1, cy),
+            (cx, cy.wrapping_sub(1)),
+            (cx, cy + 1),
+        ];
+        for (nx, ny) in neighbours {
+            if nx < width && ny < height && !seen[ny * width + nx] {
+                seen[ny * width + nx] = true;
+                if similar(image.pixels[ny * width + nx]) {
+                    stack.push((nx, ny));
+                }
+            }
+        }
+    }
+    Some(bounds)
+}
+
+/// Character boxes of the label right of `swatch`, left to right.
+#[allow(clippy::cast_precision_loss)]
+fn label_glyphs(mask: &InkMask, swatch: PixelBox) -> Vec<PixelBox> {
+    let cy = usize::midpoint(swatch.y0, swatch.y1);
+    let window = PixelBox {
+        x0: swatch.x1,
+        y0: cy.saturating_sub(MAX_GLYPH_SIZE),
+        x1: swatch.x1 + LABEL_SEARCH_WIDTH,
+        y1: cy + MAX_GLYPH_SIZE + 1,
+    };
+    let mut blobs: Vec<PixelBox> = mask
+        .blobs(window)
+        .into_iter()
+        .filter(|b| b.width().max(b.height()) <= MAX_GLYPH_SIZE)
+        .collect();
+    blobs.sort_by_key(|b| b.x0);
+
+    let Some(first) = blobs
+        .iter()
+        .copied()
+        .find(|b| b.y0 <= cy && cy < b.y1 && b.height() >= MIN_GLYPH_SIZE)
+    else {
+        return Vec::new();
+    };
+    let lead = (first.x0 - swatch.x1) as f32;
+    if lead > LEAD_GAP_RATIO.mul_add(first.height() as f32, 2.0) {
+        return Vec::new();
+    }
+    // Room for ascenders, descenders and dots around the first character.
+    let half = first.height() / 2;
+    let (band_top, band_bottom) = (first.y0.saturating_sub(half), first.y1 + half);
+
+    let mut glyphs: Vec<PixelBox> = Vec::new();
+    let mut right = first.x0;
+    let mut line_height = first.height();
+    for blob in blobs {
+        if blob.x0 < first.x0 || blob.y0 < band_top || blob.y1 > band_bottom {
+            continue;
+        }
+        let gap = blob.x0.saturating_sub(right) as f32;
+        if gap > LABEL_GAP_RATIO * line_height as f32 {
+            break;
+        }
+        right = right.max(blob.x1);
+        line_height = line_height.max(blob.height());
+        match glyphs.last_mut() {
+            Some(last) if last.horizontal_overlap(blob) >= 0.5 => *last = last.union(blob),
+            _ => glyphs.push(blob),
+        }
+    }
+    glyphs
+}
+
+/// Recognize a line of character boxes; `None` if it is empty or mostly illegible.
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn read_line(mask: &InkMask, glyphs: &[PixelBox]) -> Option<String> {
+    let max_height = glyphs.iter().map(|g| g.height()).max()?;
+    let tall: Vec<PixelBox> = glyphs
+        .iter()
+        .copied()
+        .filter(|g| g.height() * 5 > max_height * 2)
+        .collect();
+    let mut bottoms: Vec<usize> = tall.iter().map(|g| g.y1).collect();
+    bottoms.sort_unstable();
+    let baseline = bottoms[bottoms.len() / 2];
+    let tolerance = (max_height / 10).max(1);
+    let cap_top = tall
+        .iter()
+        .filter(|g| g.y1.abs_diff(baseline) <= tolerance)
+        .map(|g| g.y0)
+        .min()?;
+    let cap_height = baseline.saturating_sub(cap_top).max(1) as f32;
+
+    let shapes = shapes();
+    let mut ranked = Vec::with_capacity(glyphs.len());
+    let mut words = vec![0];
+    let mut right = glyphs[0].x1;
+    for glyph in glyphs {
+        if glyph.x0.saturating_sub(right) as f32 > SPACE_GAP_RATIO * cap_height {
+            words.push(ranked.len());
+        }
+        right = right.max(glyph.x1);
+        let top = (baseline as f32 - glyph.y0 as f32) / cap_height;
+        let bottom = (glyph.y1 as f32 - baseline as f32) / cap_height;
+        let mut candidates: Vec<Candidate> = shapes
+            .iter()
+            .map(|shape| match_shape(mask, *glyph, shape, top, bottom))
+            .collect();
+        candidates.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        ranked.push(candidates);
+    }
+    let mean = ranked.iter().map(|c| c[0].distance).sum::<f32>() / ranked.len() as f32;
+    if mean > MAX_MEAN_DISTANCE {
+        return None;
+    }
+
+    words.push(ranked.len());
+    let mut text = String::new();
+    for bounds in words.windows(2) {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.extend(word_text(&ranked[bounds[0]..bounds[1]]));
+    }
+    Some(text)
+}
+
+/// Best characters of one word: of the equally plausible matches, the one whose height fits
+/// the line best (`s` over `S`), with letter/digit ties settled by the word's other characters.
+fn word_text(ranked: &[Vec<Candidate>]) -> impl Iterator<Item = char> + '_ {
+    let plausible = |candidates: &[Candidate]| {
+        let best = candidates[0].distance;
+        let mut options: Vec<Candidate> = candidates
+            .iter()
+            .take_while(|c| c.distance <= best + AMBIGUITY_MARGIN)
+            .copied()
+            .collect();
+        options.sort_by(|a, b| a.zone.total_cmp(&b.zone));
+        options.into_iter().map(|c| c.ch).collect::<Vec<_>>()
+    };
+    let (mut letters, mut digits) = (0_usize, 0_usize);
+    for candidates in ranked {
+        if let [ch] = plausible(candidates)[..] {
+            letters += usize::from(ch.is_alphabetic());
+            digits += usize::from(ch.is_ascii_digit());
+        }
+    }
+    ranked.iter().map(move |candidates| {
+        let options = plausible(candidates);
+        let preferred = match digits.cmp(&letters) {
+            Ordering::Greater => options.iter().find(|ch| ch.is_ascii_digit()),
+            Ordering::Less => options.iter().find(|ch| ch.is_alphabetic()),
+            Ordering::Equal => None,
+        };
+        preferred.copied().unwrap_or(options[0])
+    })
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn match_shape(mask: &InkMask, glyph: PixelBox, shape: &Shape, top: f32, bottom: f32) -> Candidate {
+    let (w, h) = (glyph.width() as f32, glyph.height() as f32);
+    let samples = CELL_SAMPLES as f32;
+    let mut total = 0.0;
+    for row in 0..shape.rows {
+        for col in 0..shape.cols {
+            let mut hits = 0_u32;
+            for sy in 0..CELL_SAMPLES {
+                for sx in 0..CELL_SAMPLES {
+                    let fx = (col as f32 + (sx as f32 + 0.5) / samples) / shape.cols as f32;
+                    let fy = (row as f32 + (sy as f32 + 0.5) / samples) / shape.rows as f32;
+                    let x = glyph.x0 + (fx * w) as usize;
+                    let y = glyph.y0 + (fy * h) as usize;
+                    hits += u32::from(mask.is_ink(x, y));
+                }
+            }
+            let coverage = hits as f32 / (samples * samples);
+            let cell = f32::from(u8::from(shape.cells[row * shape.cols + col]));
+            total += (coverage - cell).abs();
+        }
+    }
+    let shape_distance = total / (shape.cols * shape.rows) as f32;
+    let aspect = ((w / h) / (shape.cols as f32 / shape.rows as f32))
+        .ln()
+        .abs();
+    let zone = (top - shape.top).abs() + (bottom - shape.bottom).abs();
+    Candidate {
+        ch: shape.ch,
+        distance: ZONE_WEIGHT.mul_add(zone, ASPECT_WEIGHT.mul_add(aspect, shape_distance)),
+        zone,
+    }
+}
+
+/// Paint `text` in the proportional legend font with `scale`-pixel cells; `origin` is the
+/// top-left corner of the first character's cap height.
+#[cfg(test)]
+pub fn stamp_label(
+    image: &mut ColorImage,
+    text: &str,
+    origin: [usize; 2],
+    scale: usize,
+    color: Color32,
+) {
+    let mut cursor = 0;
+    for ch in text.chars() {
+        if ch == ' ' {
+            cursor += 3;
+            continue;
+        }
+        let (top, rows) = DIGITS
+            .iter()
+            .map(|(t, rows)| (*t, 0, rows.as_slice()))
+            .chain(LETTERS.iter().copied())
+            .find_map(|(t, top, rows)| (t == ch).then_some((top, rows)))
+            .unwrap_or_else(|| panic!("no template for {ch:?}"));
+        let (col0, col1) = ink_columns(rows);
+        for (row, pattern) in rows.iter().enumerate() {
+            for (col, cell) in pattern.bytes().enumerate().skip(col0).take(col1 - col0 + 1) {
+                if cell != b'#' {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let x = origin[0] + (cursor + col - col0) * scale + dx;
+                        let y = origin[1] + (top + row) * scale + dy;
+                        if x < image.size[0] && y < image.size[1] {
+                            image.pixels[y * image.size[0] + x] = color;
+                        }
+                    }
+                }
+            }
+        }
+        cursor += col1 - col0 + 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color32 = Color32::from_rgb(220, 40, 40);
+
+    /// A white image with a red line swatch at `(x, y)` followed by `label`.
+    fn legend_entry(image: &mut ColorImage, [x, y]: [usize; 2], label: &str, scale: usize) {
+        for sy in y..y + scale {
+            for sx in x..x + 8 * scale {
+                image.pixels[sy * image.size[0] + sx] = RED;
+            }
+        }
+        let cap = GRID_H * scale;
+        let origin = [x + 11 * scale, (y + scale / 2).saturating_sub(cap / 2)];
+        stamp_label(image, label, origin, scale, Color32::BLACK);
+    }
+
+    fn blank(width: usize, height: usize) -> ColorImage {
+        ColorImage::new([width, height], vec![Color32::WHITE; width * height])
+    }
+
+    /// A red line swatch at (20, 60) followed by `label` set `font_size` pixels high in
+    /// `DejaVu Sans`.
+    fn font_legend_entry(label: &str, font_size: usize) -> ColorImage {
+        crate::image::render_svg(&format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="600" height="120">
+                <rect width="100%" height="100%" fill="white"/>
+                <path d="M20 60 h{}" stroke="rgb(220,40,40)" stroke-width="3"/>
+                <text x="{}" y="{}" font-family="DejaVu Sans" font-size="{font_size}">{label}</text>
+            </svg>"#,
+            2 * font_size,
+            20 + 2 * font_size + font_size / 2,
+            60 + font_size * 7 / 20,
+        ))
+    }
+
+    #[test]
+    fn labels_are_read_back_at_several_sizes() {
+        for scale in [2, 3, 4] {
+            for label in ["Sample A", "T = 300 K", "run 12", "Quartz jig", "0.5 mg-l"] {
+                let mut image = blank(600, 120);
+                legend_entry(&mut image, [20, 60], label, scale);
+                let read = read_legend_label(&image, 22, 60);
+                assert_eq!(read.as_deref(), Some(label), "scale {scale}");
+            }
+        }
+    }
+
+    #[test]
+    fn labels_set_in_a_real_sans_font_are_read() {
+        for font_size in [24, 32] {
+            for label in [
+                "T = 300 K",
+                "Data",
+                "Fit",
+                "Voltage, mV",
+                "Series 2",
+                "0.5 mg-l",
+                "Time, s",
+                "Temperature",
+            ] {
+                let image = font_legend_entry(label, font_size);
+                let read = read_legend_label(&image, 22, 60);
+                assert_eq!(read.as_deref(), Some(label), "font size {font_size}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_letter_is_told_apart() {
+        for label in [
+            "ABCDEFGHIJKLM",
+            "NOPQRSTUVWXYZ",
+            "The abcdefghijklm",
+            "The nopqrstuvwxyz",
+        ] {
+            let mut image = blank(600, 120);
+            legend_entry(&mut image, [20, 60], label, 3);
+            assert_eq!(read_legend_label(&image, 22, 60).as_deref(), Some(label));
+        }
+    }
+
+    #[test]
+    fn the_label_stops_before_the_next_entry_of_a_row_legend() {
+        let mut image = blank(600, 80);
+        legend_entry(&mut image, [20, 40], "Fit", 2);
+        legend_entry(&mut image, [140, 40], "Data", 2);
+        assert_eq!(read_legend_label(&image, 24, 40).as_deref(), Some("Fit"));
+        assert_eq!(read_legend_label(&image, 144, 40).as_deref(), Some("Data"));
+    }
+
+    #[test]
+    fn background_and_unlabelled_swatches_give_nothing() {
+        let mut image = blank(300, 80);
+        legend_entry(&mut image, [20, 40], "", 2);
+        assert_eq!(read_legend_label(&image, 24, 40), None);
+        assert_eq!(read_legend_label(&image, 200, 10), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Text-like regions: rows or columns of glyph-sized ink components.
+//!
+//! Tick labels and titles are dark, near-neutral marks a few pixels to a few
+//! dozen pixels tall, set close together. Lines, axes and curves form long
+//! components and filled markers are too solid, so neither passes as a glyph.
+
+use super::ink::{InkMask, PixelBox};
+use crate::profiling::profile_function;
+use egui::{ColorImage, Rect, pos2};
+
+/// Glyph height range in pixels.
+const MIN_GLYPH_SIZE: usize = 5;
+const MAX_GLYPH_SIZE: usize = 48;
+/// Glyphs are at most this much wider than tall (or taller than wide when rotated).
+const MAX_GLYPH_ASPECT: usize = 3;
+/// Strokes cover this share of a glyph's box; filled markers cover more.
+const GLYPH_FILL: std::ops::RangeInclusive<f32> = 0.1..=0.7;
+/// Neighbours closer than this share of the larger mark belong to the same word.
+const MAX_GAP_RATIO: f32 = 1.0;
+/// Lone marks count as text only when this much narrower than tall, like a single digit.
+const MAX_SINGLE_ASPECT: f32 = 0.8;
+/// Margin added around each region to cover anti-aliasing.
+const REGION_PADDING: f32 = 2.0;
+
+#[derive(Clone, Copy)]
+struct Mark {
+    bounds: PixelBox,
+    /// Shaped like a glyph rather than a tick, dot or dash.
+    glyph: bool,
+}
+
+fn classify(mask: &InkMask, bounds: PixelBox) -> Option<Mark> {
+    let (w, h) = (bounds.width(), bounds.height());
+    let long = w.max(h);
+    if long > MAX_GLYPH_SIZE || long > MAX_GLYPH_ASPECT * w.min(h).max(2) {
+        return None;
+    }
+    let glyph = long >= MIN_GLYPH_SIZE && GLYPH_FILL.contains(&mask.fill(bounds));
+    Some(Mark { bounds, glyph })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn adjacent(a: PixelBox, b: PixelBox) -> bool {
+    let gap = |lo0: usize, hi0: usize, lo1: usize, hi1: usize| {
+        lo1.saturating_sub(hi0).max(lo0.saturating_sub(hi1)) as f32
+    };
+    let size = a.width().max(a.height()).max(b.width()).max(b.height()) as f32;
+    let max_gap = size * MAX_GAP_RATIO;
+    let in_row = a.vertical_overlap(b) >= 0.5 && gap(a.x0, a.x1, b.x0, b.x1) <= max_gap;
+    let in_column = a.horizontal_overlap(b) >= 0.5 && gap(a.y0, a.y1, b.y0, b.y1) <= max_gap;
+    in_row || in_column
+}
+
+fn find_root(parent: &mut [usize], mut idx: usize) -> usize {
+    while parent[idx] != idx {
+        parent[idx] = parent[parent[idx]];
+        idx = parent[idx];
+    }
+    idx
+}
+
+/// Boxes around text-like clusters of ink, padded and clipped to the image, in pixels.
+#[allow(clippy::cast_precision_loss)]
+pub fn detect_text_regions(image: &ColorImage) -> Vec<Rect> {
+    profile_function!();
+    let mask = InkMask::from_image(image);
+    let whole = PixelBox {
+        x0: 0,
+        y0: 0,
+        x1: mask.width(),
+        y1: mask.height(),
+    };
+    let mut marks: Vec<Mark> = mask
+        .blobs(whole)
+        .into_iter()
+        .filter_map(|b| classify(&mask, b))
+        .collect();
+    marks.sort_by_key(|m| m.bounds.x0);
+
+    let mut parent: Vec<usize> = (0..marks.len()).collect();
+    for i in 0..marks.len() {
+        let reach = marks[i].bounds.x1 + MAX_GLYPH_SIZE;
+        for j in (i + 1)..marks.len() {
+            if marks[j].bounds.x0 > reach {
+                break;
+            }
+            if adjacent(marks[i].bounds, marks[j].bounds) {
+                let (ri, rj) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                parent[rj] = ri;
+            }
+        }
+    }
+
+    // Per cluster: bounds, member count, glyph count.
+    let mut clusters: Vec<Option<(PixelBox, usize, usize)>> = vec![None; marks.len()];
+    for (idx, mark) in marks.iter().enumerate() {
+        let root = find_root(&mut parent, idx);
+        let entry = clusters[root].get_or_insert((mark.bounds, 0, 0));
+        entry.0 = entry.0.union(mark.bounds);
+        entry.1 += 1;
+        entry.2 += usize::from(mark.glyph);
+    }
+    let (width, height) = (mask.width() as f32, mask.height() as f32);
+    clusters
+        .into_iter()
+        .flatten()
+        .filter(|&(bounds, members, glyphs)| {
+            glyphs > 0
+                && (members > 1
+                    || (bounds.width() as f32) <= bounds.height() as f32 * MAX_SINGLE_ASPECT)
+        })
+        .map(|(b, _, _)| {
+            Rect::from_min_max(
+                pos2(
+                    (b.x0 as f32 - REGION_PADDING).max(0.0),
+                    (b.y0 as f32 - REGION_PADDING).max(0.0),
+                ),
+                pos2(
+                    (b.x1 as f32 + REGION_PADDING).min(width),
+                    (b.y1 as f32 + REGION_PADDING).min(height),
+                ),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr::stamp_text;
+    use egui::Color32;
+
+    #[test]
+    fn labels_are_found_but_lines_and_markers_are_not() {
+        let (w, h) = (160, 100);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        stamp_text(&mut image, "250", [10, 10], 2, Color32::BLACK);
+        stamp_text(&mut image, "7", [120, 12], 2, Color32::from_gray(40));
+        // A dark curve, an axis line and a filled square marker.
+        for x in 0..w {
+            let y = 60 + (x / 8) % 6;
+            image.pixels[y * w + x] = Color32::BLACK;
+            image.pixels[95 * w + x] = Color32::BLACK;
+        }
+        for y in 30..38 {
+            for x in 70..78 {
+                image.pixels[y * w + x] = Color32::BLACK;
+            }
+        }
+
+        let regions = detect_text_regions(&image);
+        assert_eq!(regions.len(), 2, "{regions:?}");
+        let label = regions.iter().find(|r| r.min.x < 20.0).expect("\"250\"");
+        assert!(label.contains(pos2(12.0, 12.0)) && label.contains(pos2(36.0, 22.0)));
+        assert!(regions.iter().any(|r| r.contains(pos2(122.0, 16.0))));
+        for r in &regions {
+            assert!(r.max.y < 50.0, "curve or axis masked: {r:?}");
+            assert!(!r.contains(pos2(74.0, 34.0)), "marker masked: {r:?}");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Portable mode: config, recents and caches live beside the executable.
+//!
+//! Enabled by the `--portable` flag or a `curcat.portable` marker file next to the binary,
+//! for running from removable media on machines without a writable profile directory.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Command-line flag that turns portable mode on for one launch.
+pub const PORTABLE_FLAG: &str = "--portable";
+/// Marker file that turns portable mode on permanently for a copy of the binary.
+const PORTABLE_MARKER: &str = "curcat.portable";
+
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Decide portable mode once at startup, before the config is loaded.
+pub fn init(flag: bool) {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let _ = PORTABLE_ROOT.set(resolve_root(flag, exe_dir.as_deref()));
+}
+
+/// Directory holding all files in portable mode, or `None` when using the profile dirs.
+pub fn root() -> Option<&'static Path> {
+    PORTABLE_ROOT.get().and_then(Option::as_deref)
+}
+
+/// Decoded-image and snap-map cache directory in portable mode.
+pub fn cache_dir() -> Option<PathBuf> {
+    root().map(|root| root.join("cache"))
+}
+
+/// File where eframe keeps window and UI state in portable mode.
+pub fn persistence_file() -> Option<PathBuf> {
+    root().map(|root| root.join("data").join("app.ron"))
+}
+
+fn resolve_root(flag: bool, exe_dir: Option<&Path>) -> Option<PathBuf> {
+    let exe_dir = exe_dir?;
+    (flag || exe_dir.join(PORTABLE_MARKER).is_file()).then(|| exe_dir.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_root_needs_flag_or_marker() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let dir = std::env::temp_dir().join(format!("curcat_portable_{nanos}"));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+
+        assert_eq!(resolve_root(false, Some(&dir)), None);
+        assert_eq!(resolve_root(true, Some(&dir)), Some(dir.clone()));
+        assert_eq!(resolve_root(true, None), None);
+
+        std::fs::write(dir.join(PORTABLE_MARKER), b"").expect("write marker");
+        assert_eq!(resolve_root(false, Some(&dir)), Some(dir.clone()));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
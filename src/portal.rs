@@ -0,0 +1,488 @@
+//! File chooser through the XDG desktop portal: the desktop's own dialog, with its bookmarks
+//! and recent places, and the only way out of a Flatpak or Snap sandbox.
+//!
+//! Inside a sandbox the built-in file browser only sees the sandbox's own filesystem; the
+//! portal shows the desktop's dialog on the host and grants access to whatever the user
+//! picks. Requests go through [`ashpd`] on a background thread; builds without the `portal`
+//! feature, and Windows and macOS, have no portal.
+
+use anyhow::anyhow;
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// Whether Curcat runs inside a Flatpak or Snap sandbox.
+pub fn sandboxed() -> bool {
+    std::env::var_os("FLATPAK_ID").is_some()
+        || Path::new("/.flatpak-info").exists()
+        || std::env::var_os("SNAP").is_some()
+}
+
+/// Whether this build can reach a desktop portal at all.
+pub const fn available() -> bool {
+    cfg!(all(feature = "portal", unix, not(target_os = "macos")))
+}
+
+/// One file chooser request: open one or several files, or name a file to save.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    not(all(feature = "portal", unix, not(target_os = "macos"))),
+    allow(dead_code)
+)]
+pub struct FileRequest {
+    pub title: String,
+    pub save: bool,
+    pub multiple: bool,
+    /// Filter label and the extensions it admits, the first one preselected.
+    pub filters: Vec<(String, Vec<String>)>,
+    /// Suggested file name when saving.
+    pub current_name: Option<String>,
+    pub current_folder: Option<PathBuf>,
+    /// Window the dialog belongs to, in the portal's `x11:XID` form; empty when unknown.
+    ///
+    /// With a parent the desktop opens the dialog over Curcat and on its monitor instead of
+    /// wherever it places new windows.
+    pub parent_window: String,
+}
+
+/// A dialog shown outside the app; the answer arrives once the user closes it.
+#[derive(Debug)]
+pub struct PendingRequest {
+    rx: Receiver<anyhow::Result<Option<Vec<PathBuf>>>>,
+}
+
+impl PendingRequest {
+    /// Wait for `answer` on a background thread.
+    pub fn spawn(
+        answer: impl FnOnce() -> anyhow::Result<Option<Vec<PathBuf>>> + Send + 'static,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(answer());
+        });
+        Self { rx }
+    }
+
+    /// Picked paths, `Ok(None)` when cancelled, or `None` while the dialog is still open.
+    pub fn try_outcome(&self) -> Option<anyhow::Result<Option<Vec<PathBuf>>>> {
+        match self.rx.try_recv() {
+            Ok(outcome) => Some(outcome),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(anyhow!("file dialog thread ended"))),
+        }
+    }
+}
+
+/// Portal identifier of `window`: `x11:XID` on X11.
+///
+/// Wayland needs an `xdg-foreign` export handle that winit does not provide, so there the
+/// identifier is empty and the compositor places the dialog itself.
+pub fn parent_window_id(window: &impl HasWindowHandle) -> String {
+    match window.window_handle().map(|handle| handle.as_raw()) {
+        Ok(RawWindowHandle::Xlib(handle)) => format!("x11:{:x}", handle.window),
+        Ok(RawWindowHandle::Xcb(handle)) => format!("x11:{:x}", handle.window.get()),
+        _ => String::new(),
+    }
+}
+
+/// Ask the portal to show `request` and return without waiting for the user.
+pub fn start(request: FileRequest) -> PendingRequest {
+    PendingRequest::spawn(move || run(&request))
+}
+
+#[cfg(all(feature = "portal", unix, not(target_os = "macos")))]
+fn run(request: &FileRequest) -> anyhow::Result<Option<Vec<PathBuf>>> {
+    ashpd::zbus::block_on(imp::request_files(request, None))
+}
+
+#[cfg(not(all(feature = "portal", unix, not(target_os = "macos"))))]
+fn run(_request: &FileRequest) -> anyhow::Result<Option<Vec<PathBuf>>> {
+    anyhow::bail!("this build has no desktop portal support")
+}
+
+/// Local paths of the `file://` URIs a portal answered with.
+#[cfg_attr(
+    not(all(feature = "portal", unix, not(target_os = "macos"))),
+    allow(dead_code)
+)]
+fn uris_to_paths<'a>(uris: impl IntoIterator<Item = &'a str>) -> anyhow::Result<Vec<PathBuf>> {
+    uris.into_iter()
+        .map(|uri| {
+            url::Url::parse(uri)
+                .ok()
+                .filter(|url| url.scheme() == "file")
+                .and_then(|url| url.to_file_path().ok())
+                .ok_or_else(|| anyhow!("not a local file: {uri}"))
+        })
+        .collect()
+}
+
+#[cfg(all(feature = "portal", unix, not(target_os = "macos")))]
+mod imp {
+    use super::FileRequest;
+    use anyhow::Context as _;
+    use ashpd::desktop::ResponseError;
+    use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
+    use ashpd::{WindowIdentifier, zbus};
+    use std::path::{Path, PathBuf};
+
+    /// Portal identifier of the parent window, from its `x11:XID` form.
+    fn parent_identifier(parent_window: &str) -> Option<WindowIdentifier> {
+        let xid = parent_window.strip_prefix("x11:")?;
+        std::os::raw::c_ulong::from_str_radix(xid, 16)
+            .ok()
+            .map(WindowIdentifier::from_xid)
+    }
+
+    /// Filters with `*.ext` and `*.EXT` patterns, since portal globs are case-sensitive.
+    fn file_filters(request: &FileRequest) -> Vec<FileFilter> {
+        request
+            .filters
+            .iter()
+            .map(|(label, extensions)| {
+                extensions
+                    .iter()
+                    .fold(FileFilter::new(label), |filter, ext| {
+                        filter
+                            .glob(&format!("*.{ext}"))
+                            .glob(&format!("*.{}", ext.to_ascii_uppercase()))
+                    })
+            })
+            .collect()
+    }
+
+    /// Show `request` over `connection` (the session bus when `None`) and wait for the answer.
+    pub(super) async fn request_files(
+        request: &FileRequest,
+        connection: Option<zbus::Connection>,
+    ) -> anyhow::Result<Option<Vec<PathBuf>>> {
+        let identifier = parent_identifier(&request.parent_window);
+        let filters = file_filters(request);
+        let current_filter = filters.first().cloned();
+        let current_folder = request.current_folder.as_deref();
+        let response = if request.save {
+            SelectedFiles::save_file()
+                .identifier(identifier)
+                .title(request.title.as_str())
+                .modal(true)
+                .current_name(request.current_name.as_deref())
+                .current_folder::<&Path>(current_folder)?
+                .filters(filters)
+                .current_filter(current_filter)
+                .connection(connection)
+                .send()
+                .await
+        } else {
+            SelectedFiles::open_file()
+                .identifier(identifier)
+                .title(request.title.as_str())
+                .modal(true)
+                .multiple(request.multiple)
+                .current_folder::<&Path>(current_folder)?
+                .filters(filters)
+                .current_filter(current_filter)
+                .connection(connection)
+                .send()
+                .await
+        }
+        .context("the desktop portal did not open a file chooser")?
+        .response();
+        match response {
+            Ok(files) => {
+                super::uris_to_paths(files.uris().iter().map(ashpd::Uri::as_str)).map(Some)
+            }
+            Err(ashpd::Error::Response(ResponseError::Cancelled)) => Ok(None),
+            Err(err) => Err(err).context("the desktop portal file chooser failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portal_answers_yield_local_paths() {
+        assert_eq!(
+            uris_to_paths(["file:///home/me/My%20Chart.png", "file:///tmp/b.svg"]).unwrap(),
+            [
+                PathBuf::from("/home/me/My Chart.png"),
+                PathBuf::from("/tmp/b.svg")
+            ]
+        );
+        assert!(uris_to_paths(["https://example.com/a.png"]).is_err());
+        assert!(uris_to_paths(["not a uri"]).is_err());
+    }
+
+    #[cfg(all(feature = "portal", unix, not(target_os = "macos")))]
+    mod session_bus {
+        use super::super::{FileRequest, imp};
+        use std::collections::HashMap;
+        use std::path::PathBuf;
+        use std::sync::{Arc, Mutex};
+        use zbus::{message::Header, object_server::SignalEmitter, zvariant};
+
+        /// A private `dbus-daemon --session`, or `None` where it is not installed.
+        struct PrivateBus {
+            daemon: std::process::Child,
+            address: String,
+        }
+
+        impl PrivateBus {
+            fn start() -> Option<Self> {
+                use std::io::BufRead as _;
+                let mut daemon = std::process::Command::new("dbus-daemon")
+                    .args(["--session", "--nofork", "--print-address"])
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::null())
+                    .spawn()
+                    .ok()?;
+                let mut address = String::new();
+                std::io::BufReader::new(daemon.stdout.take()?)
+                    .read_line(&mut address)
+                    .ok()?;
+                Some(Self {
+                    daemon,
+                    address: address.trim().to_string(),
+                })
+            }
+
+            fn connect(&self) -> zbus::Connection {
+                zbus::block_on(
+                    zbus::connection::Builder::address(self.address.as_str())
+                        .unwrap()
+                        .build(),
+                )
+                .unwrap()
+            }
+        }
+
+        impl Drop for PrivateBus {
+            fn drop(&mut self) {
+                let _ = self.daemon.kill();
+                let _ = self.daemon.wait();
+            }
+        }
+
+        /// What the fake portal saw of one call.
+        #[derive(Debug, Default)]
+        struct Seen {
+            method: String,
+            parent_window: String,
+            title: String,
+            options: HashMap<String, zvariant::OwnedValue>,
+        }
+
+        /// A `FileChooser` portal that answers every request with `code` and `uris`.
+        struct FakePortal {
+            code: u32,
+            uris: Vec<String>,
+            seen: Arc<Mutex<Seen>>,
+        }
+
+        impl FakePortal {
+            async fn answer(
+                &self,
+                method: &str,
+                parent_window: &str,
+                title: &str,
+                options: HashMap<String, zvariant::OwnedValue>,
+                header: &Header<'_>,
+                emitter: &SignalEmitter<'_>,
+            ) -> zbus::fdo::Result<zvariant::OwnedObjectPath> {
+                let token = options
+                    .get("handle_token")
+                    .and_then(|token| String::try_from(token.try_clone().ok()?).ok())
+                    .unwrap_or_default();
+                let sender = header
+                    .sender()
+                    .map(|name| name.trim_start_matches(':').replace('.', "_"))
+                    .unwrap_or_default();
+                *self.seen.lock().unwrap() = Seen {
+                    method: method.to_string(),
+                    parent_window: parent_window.to_string(),
+                    title: title.to_string(),
+                    options,
+                };
+                let handle = zvariant::OwnedObjectPath::try_from(format!(
+                    "/org/freedesktop/portal/desktop/request/{sender}/{token}"
+                ))
+                .map_err(|err| zbus::fdo::Error::Failed(err.to_string()))?;
+                let results: HashMap<&str, zvariant::Value<'_>> =
+                    HashMap::from([("uris", zvariant::Value::from(self.uris.clone()))]);
+                emitter
+                    .connection()
+                    .emit_signal(
+                        None::<&str>,
+                        &handle,
+                        "org.freedesktop.portal.Request",
+                        "Response",
+                        &(self.code, results),
+                    )
+                    .await?;
+                Ok(handle)
+            }
+        }
+
+        #[zbus::interface(name = "org.freedesktop.portal.FileChooser")]
+        impl FakePortal {
+            #[zbus(property, name = "version")]
+            #[allow(clippy::unused_self)]
+            fn version(&self) -> u32 {
+                4
+            }
+
+            async fn open_file(
+                &self,
+                parent_window: &str,
+                title: &str,
+                options: HashMap<String, zvariant::OwnedValue>,
+                #[zbus(header)] header: Header<'_>,
+                #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+            ) -> zbus::fdo::Result<zvariant::OwnedObjectPath> {
+                self.answer("OpenFile", parent_window, title, options, &header, &emitter)
+                    .await
+            }
+
+            async fn save_file(
+                &self,
+                parent_window: &str,
+                title: &str,
+                options: HashMap<String, zvariant::OwnedValue>,
+                #[zbus(header)] header: Header<'_>,
+                #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+            ) -> zbus::fdo::Result<zvariant::OwnedObjectPath> {
+                self.answer("SaveFile", parent_window, title, options, &header, &emitter)
+                    .await
+            }
+        }
+
+        /// Serve a [`FakePortal`] on `bus`; the returned connection keeps it registered.
+        fn serve(
+            bus: &PrivateBus,
+            code: u32,
+            uris: &[&str],
+        ) -> (zbus::Connection, Arc<Mutex<Seen>>) {
+            let seen = Arc::new(Mutex::new(Seen::default()));
+            let portal = FakePortal {
+                code,
+                uris: uris.iter().map(ToString::to_string).collect(),
+                seen: Arc::clone(&seen),
+            };
+            let connection = zbus::block_on(async {
+                zbus::connection::Builder::address(bus.address.as_str())?
+                    .name("org.freedesktop.portal.Desktop")?
+                    .serve_at("/org/freedesktop/portal/desktop", portal)?
+                    .build()
+                    .await
+            })
+            .unwrap();
+            (connection, seen)
+        }
+
+        fn request(save: bool) -> FileRequest {
+            FileRequest {
+                title: "Open Images".to_string(),
+                save,
+                multiple: !save,
+                filters: vec![
+                    (
+                        "Images".to_string(),
+                        vec!["png".to_string(), "svg".to_string()],
+                    ),
+                    ("*.tiff".to_string(), vec!["tiff".to_string()]),
+                ],
+                current_name: save.then(|| "curve.csv".to_string()),
+                current_folder: Some(std::env::temp_dir()),
+                parent_window: "x11:2c00004".to_string(),
+            }
+        }
+
+        #[test]
+        fn open_requests_reach_the_portal_and_picked_files_come_back() {
+            let Some(bus) = PrivateBus::start() else {
+                return;
+            };
+            let (_portal, seen) = serve(
+                &bus,
+                0,
+                &["file:///home/me/My%20Chart.png", "file:///tmp/b.svg"],
+            );
+            let picked =
+                zbus::block_on(imp::request_files(&request(false), Some(bus.connect()))).unwrap();
+            assert_eq!(
+                picked,
+                Some(vec![
+                    PathBuf::from("/home/me/My Chart.png"),
+                    PathBuf::from("/tmp/b.svg")
+                ])
+            );
+            let seen = std::mem::take(&mut *seen.lock().unwrap());
+            assert_eq!(seen.method, "OpenFile");
+            assert_eq!(seen.parent_window, "x11:2c00004");
+            assert_eq!(seen.title, "Open Images");
+            assert_eq!(
+                seen.options.get("multiple").map(|v| bool::try_from(&**v)),
+                Some(Ok(true))
+            );
+            let filters = seen.options.get("filters").expect("filters sent");
+            let filters =
+                <Vec<(String, Vec<(u32, String)>)>>::try_from(filters.try_clone().unwrap())
+                    .unwrap();
+            assert_eq!(filters[0].0, "Images");
+            assert_eq!(
+                filters[0].1,
+                [
+                    (0, "*.png".to_string()),
+                    (0, "*.PNG".to_string()),
+                    (0, "*.svg".to_string()),
+                    (0, "*.SVG".to_string()),
+                ]
+            );
+            assert!(seen.options.contains_key("current_folder"));
+        }
+
+        #[test]
+        fn save_requests_carry_the_name_and_cancelling_picks_nothing() {
+            let Some(bus) = PrivateBus::start() else {
+                return;
+            };
+            let (_portal, seen) = serve(&bus, 1, &[]);
+            let picked =
+                zbus::block_on(imp::request_files(&request(true), Some(bus.connect()))).unwrap();
+            assert_eq!(picked, None);
+            let seen = std::mem::take(&mut *seen.lock().unwrap());
+            assert_eq!(seen.method, "SaveFile");
+            assert_eq!(
+                seen.options
+                    .get("current_name")
+                    .and_then(|v| String::try_from(v.try_clone().ok()?).ok())
+                    .as_deref(),
+                Some("curve.csv")
+            );
+        }
+
+        #[test]
+        fn malformed_answers_are_errors() {
+            let Some(bus) = PrivateBus::start() else {
+                return;
+            };
+            for (code, uris) in [(0, &["https://example.com/a.png"][..]), (2, &[])] {
+                let (portal, _) = serve(&bus, code, uris);
+                let answer =
+                    zbus::block_on(imp::request_files(&request(false), Some(bus.connect())));
+                assert!(answer.is_err(), "code {code}: {answer:?}");
+                drop(portal);
+            }
+        }
+
+        #[test]
+        fn a_bus_without_a_portal_is_an_error() {
+            let Some(bus) = PrivateBus::start() else {
+                return;
+            };
+            let answer = zbus::block_on(imp::request_files(&request(false), Some(bus.connect())));
+            assert!(answer.is_err());
+        }
+    }
+}
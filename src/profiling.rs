@@ -0,0 +1,46 @@
+//! Optional [puffin] scopes over the heavy paths: snap map builds, image transforms and
+//! filters, exports and the main UI panels.
+//!
+//! They are compiled in with `--features profiling`; without it the macros expand to nothing, so
+//! call sites need no `cfg`. Scopes record only while recording is on, either from launch with
+//! [`PROFILE_FLAG`] or from the profiler window, and background threads report into the frame
+//! that is current when their outermost scope ends.
+
+#[cfg(feature = "profiling")]
+pub use puffin;
+
+/// Command-line flag that starts recording profiler scopes at launch.
+pub const PROFILE_FLAG: &str = "--profile";
+
+/// Time the rest of the enclosing block as a scope named `$name`.
+macro_rules! profile_scope {
+    ($name:expr) => {
+        #[cfg(feature = "profiling")]
+        $crate::profiling::puffin::profile_scope!($name);
+    };
+}
+
+/// Time the rest of the enclosing function as a scope named after it.
+macro_rules! profile_function {
+    () => {
+        #[cfg(feature = "profiling")]
+        $crate::profiling::puffin::profile_function!();
+    };
+}
+
+pub(crate) use {profile_function, profile_scope};
+
+/// Close the previous profiler frame; called once at the start of every UI frame.
+#[cfg_attr(not(feature = "profiling"), allow(clippy::missing_const_for_fn))]
+pub fn new_frame() {
+    #[cfg(feature = "profiling")]
+    puffin::GlobalProfiler::lock().new_frame();
+}
+
+/// Record scopes from now on, for [`PROFILE_FLAG`].
+pub fn start_recording() {
+    #[cfg(feature = "profiling")]
+    puffin::set_scopes_on(true);
+    #[cfg(not(feature = "profiling"))]
+    eprintln!("{PROFILE_FLAG} needs a build with `--features profiling`.");
+}
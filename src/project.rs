@@ -4,12 +4,14 @@ mod model;
 mod path;
 
 pub use checksum::compute_image_crc32;
-pub use io::{load_project, save_project};
+pub use io::{encode_payload, load_project, save_project};
 pub use model::{
-    AxisCalibrationRecord, CalibrationRecord, ImagePathSource, PointRecord, PolarCalibrationRecord,
-    ProjectLoadOutcome, ProjectPayload, ProjectWarning, ResolvedImage,
+    AffineCalibrationRecord, AffineReferenceRecord, AxisCalibrationRecord, AxisTimeRecord,
+    CalibrationRecord, CurveRecord, ExportHistoryRecord, ImagePathSource, InvertedAxesRecord,
+    PointFlagRecord, PointRecord, PolarCalibrationRecord, ProjectLoadOutcome, ProjectPayload,
+    ProjectWarning, ResolvedImage, SmithCalibrationRecord, SwatchRecord, TimeInputsRecord,
 };
-pub use path::make_relative_image_path;
+pub use path::{make_relative_image_path, write_atomic, write_atomic_with};
 
 #[cfg(test)]
 mod tests;
@@ -4,19 +4,20 @@ use lz4_flex::block::{compress_prepend_size, decompress_size_prepended};
 use std::fs;
 use std::path::Path;
 
-use super::model::{ProjectLoadOutcome, ProjectPayload, ProjectPayloadV1};
+use super::model::{ProjectLoadOutcome, ProjectPayload, ProjectPayloadV1, ProjectPayloadV2};
 use super::path::{resolve_image_path, write_atomic};
 
 /// Magic signature prefix for project files
 pub const PROJECT_MAGIC: &[u8; 6] = b"CURCAT";
 /// Current binary project format version.
-pub const PROJECT_VERSION: u32 = 2;
+pub const PROJECT_VERSION: u32 = 3;
 
 fn bincode_config() -> impl Config {
     standard().with_little_endian()
 }
 
-fn encode_payload(payload: &ProjectPayload) -> anyhow::Result<Vec<u8>> {
+/// Bytes of `payload` as stored in a project file, before compression.
+pub fn encode_payload(payload: &ProjectPayload) -> anyhow::Result<Vec<u8>> {
     bincode::serde::encode_to_vec(payload, bincode_config())
         .context("Failed to serialize project payload")
 }
@@ -35,6 +36,13 @@ fn decode_payload_v1(bytes: &[u8]) -> anyhow::Result<ProjectPayloadV1> {
     Ok(payload)
 }
 
+fn decode_payload_v2(bytes: &[u8]) -> anyhow::Result<ProjectPayloadV2> {
+    let (payload, _): (ProjectPayloadV2, usize) =
+        bincode::serde::decode_from_slice(bytes, bincode_config())
+            .context("Failed to deserialize v2 project payload")?;
+    Ok(payload)
+}
+
 /// Save a project with compression and an atomic temp-file swap.
 pub fn save_project(path: &Path, payload: &ProjectPayload) -> anyhow::Result<()> {
     let encoded = encode_payload(payload)?;
@@ -66,10 +74,13 @@ pub fn load_project(path: &Path) -> anyhow::Result<ProjectLoadOutcome> {
     let decompressed =
         decompress_size_prepended(compressed).context("Failed to decompress project payload")?;
     let payload = match version {
-        1 => ProjectPayload::from(decode_payload_v1(&decompressed)?),
+        1 => ProjectPayload::from(ProjectPayloadV2::from(decode_payload_v1(&decompressed)?)),
+        2 => ProjectPayload::from(decode_payload_v2(&decompressed)?),
         PROJECT_VERSION => decode_payload(&decompressed)?,
         _ => {
-            bail!("Unsupported project version {version}. Supported versions: 1, {PROJECT_VERSION}")
+            bail!(
+                "Unsupported project version {version}. Supported versions: 1, 2, {PROJECT_VERSION}"
+            )
         }
     };
     let (chosen_image, warnings) = resolve_image_path(path, &payload)?;
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::image::ImageTransformRecord;
-use crate::types::{AngleDirection, AngleUnit, AxisUnit, CoordSystem, ScaleKind};
+use crate::config::{ConfigOverrides, ExportProfile};
+use crate::image::{ImageTransformRecord, PerspectiveWarpRecord};
+use crate::types::{AngleDirection, AngleUnit, AxisUnit, CoordSystem, ScaleKind, YAxis};
+use crate::validation::ValidationRule;
 
 /// Saved calibration data for a single axis.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +63,70 @@ impl Default for PolarCalibrationRecord {
     }
 }
 
+/// Saved reference point of the affine calibration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AffineReferenceRecord {
+    /// Position in image pixels, if placed.
+    pub pixel: Option<[f32; 2]>,
+    /// Raw text entered for the X value.
+    pub x_text: String,
+    /// Raw text entered for the Y value.
+    pub y_text: String,
+}
+
+/// Saved affine calibration: axis settings plus the reference points.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffineCalibrationRecord {
+    pub x_unit: AxisUnit,
+    pub x_scale: ScaleKind,
+    pub y_unit: AxisUnit,
+    pub y_scale: ScaleKind,
+    /// Reference point rows in display order.
+    pub points: Vec<AffineReferenceRecord>,
+}
+
+impl Default for AffineCalibrationRecord {
+    fn default() -> Self {
+        Self {
+            x_unit: AxisUnit::Float,
+            x_scale: ScaleKind::Linear,
+            y_unit: AxisUnit::Float,
+            y_scale: ScaleKind::Linear,
+            points: vec![AffineReferenceRecord::default(); 3],
+        }
+    }
+}
+
+/// Saved Smith chart calibration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmithCalibrationRecord {
+    /// Chart centre in image pixels, if placed.
+    pub center: Option<[f32; 2]>,
+    /// Short-circuit point in image pixels, if placed.
+    pub short: Option<[f32; 2]>,
+    /// Raw text entered for the reference impedance; empty uses the default.
+    pub reference_text: String,
+}
+
+/// Saved input format and UTC offset of an axis' `DateTime` values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AxisTimeRecord {
+    /// strptime-style pattern; empty uses the built-in formats.
+    pub pattern: String,
+    /// UTC offset text for values entered without one; empty is UTC.
+    pub offset_text: String,
+}
+
+/// Saved `DateTime` input settings of every axis that takes typed values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimeInputsRecord {
+    pub x: AxisTimeRecord,
+    pub y: AxisTimeRecord,
+    pub secondary_y: AxisTimeRecord,
+    pub affine_x: AxisTimeRecord,
+    pub affine_y: AxisTimeRecord,
+}
+
 /// Full calibration across both axes plus overlay flags.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalibrationRecord {
@@ -72,10 +138,40 @@ pub struct CalibrationRecord {
     pub y: AxisCalibrationRecord,
     /// Polar calibration (origin, radius, angle).
     pub polar: PolarCalibrationRecord,
+    /// Affine calibration (reference points).
+    #[serde(default)]
+    pub affine: AffineCalibrationRecord,
     /// Whether angle snapping is enabled while picking calibration points.
     pub calibration_angle_snap: bool,
     /// Whether to draw calibration lines/labels on the image.
     pub show_calibration_segments: bool,
+    /// Secondary Y axis, for curves read against a second value scale.
+    #[serde(default)]
+    pub secondary_y: AxisCalibrationRecord,
+    /// Cartesian axes marked as running against the screen direction.
+    #[serde(default)]
+    pub inverted_axes: InvertedAxesRecord,
+    /// Smith chart calibration (centre, short-circuit point, reference impedance).
+    #[serde(default)]
+    pub smith: SmithCalibrationRecord,
+    /// Input formats and UTC offsets of `DateTime` axes.
+    #[serde(default)]
+    pub time_inputs: TimeInputsRecord,
+}
+
+/// Which Cartesian axes were declared reversed: values fall to the right or upward.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvertedAxesRecord {
+    pub x: bool,
+    pub y: bool,
+    pub secondary_y: bool,
+}
+
+/// Review flag stored with a point.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointFlagRecord {
+    /// Comment thread in the order it was written.
+    pub comments: Vec<String>,
 }
 
 /// Saved point with pixel coordinates and computed values.
@@ -87,9 +183,66 @@ pub struct PointRecord {
     pub x_numeric: Option<f64>,
     /// Numeric Y value (if calibration available).
     pub y_numeric: Option<f64>,
+    /// Review flag, if the point was marked as questionable.
+    #[serde(default)]
+    pub flag: Option<PointFlagRecord>,
+    /// Wall-clock capture time as Unix milliseconds (UTC), if recording was enabled.
+    #[serde(default)]
+    pub captured_at_ms: Option<i64>,
+    /// Pixel positions of the two error bar ends, if picked.
+    #[serde(default)]
+    pub error_bar: Option<[[f32; 2]; 2]>,
+    /// Snap confidence from 0 to 1; 0 for points placed or moved by hand.
+    #[serde(default)]
+    pub confidence: f32,
+    /// Locked against dragging, nudging and deletion.
+    #[serde(default)]
+    pub locked: bool,
 }
 
-/// Version 1 project payload (before compression).
+/// Stored curve: a named series of points with its overlay color.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveRecord {
+    /// Name shown in the curves panel and used for export sheets.
+    pub name: String,
+    /// RGBA color of the curve overlay; `None` follows the configured styles.
+    pub color: Option<[u8; 4]>,
+    /// Points of the curve in placement order.
+    pub points: Vec<PointRecord>,
+    /// Y axis the curve's values are read against.
+    #[serde(default)]
+    pub y_axis: YAxis,
+    /// Raw text of the family parameter value (e.g. the curve's temperature); may be empty.
+    #[serde(default)]
+    pub parameter_text: String,
+    /// Locks every point of the curve, whatever their own locks.
+    #[serde(default)]
+    pub locked: bool,
+}
+
+/// Named color of the project's swatch library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwatchRecord {
+    /// Name shown in swatch menus, e.g. "Series A red".
+    pub name: String,
+    /// RGBA color.
+    pub color: [u8; 4],
+}
+
+/// A finished export, kept so it can be found, opened or written again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportHistoryRecord {
+    /// File that was written.
+    pub path: PathBuf,
+    /// Format and export settings used; the profile name is left empty.
+    pub settings: ExportProfile,
+    /// Data rows written, summed over all curves.
+    pub rows: usize,
+    /// Unix milliseconds (UTC) when the file was finished.
+    pub exported_at_ms: i64,
+}
+
+/// Current project payload (before compression).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectPayload {
     /// Absolute path to the source image.
@@ -98,12 +251,19 @@ pub struct ProjectPayload {
     pub relative_image_path: Option<PathBuf>,
     /// CRC32 checksum of the image file.
     pub image_crc32: u32,
+    /// Perspective warps replayed before `transform`, oldest first.
+    #[serde(default)]
+    pub perspective: Vec<PerspectiveWarpRecord>,
     /// Stored image transform state.
     pub transform: ImageTransformRecord,
     /// Calibration data for both axes.
     pub calibration: CalibrationRecord,
-    /// Stored points.
-    pub points: Vec<PointRecord>,
+    /// Stored curves with their points.
+    #[serde(default)]
+    pub curves: Vec<CurveRecord>,
+    /// Index into `curves` of the curve that receives new points.
+    #[serde(default)]
+    pub active_curve: usize,
     /// Last zoom level.
     pub zoom: f32,
     /// Last pan offset of the scroll area.
@@ -112,6 +272,41 @@ pub struct ProjectPayload {
     pub title: Option<String>,
     /// Reserved project description.
     pub description: Option<String>,
+    /// Styles, auto-place and export settings that override the global config.
+    #[serde(default)]
+    pub config_overrides: ConfigOverrides,
+    /// Named colors saved for reuse as snap targets and curve colors.
+    #[serde(default)]
+    pub swatches: Vec<SwatchRecord>,
+    /// Scale an SVG source was rasterized at, in percent; `None` for raster images.
+    #[serde(default)]
+    pub svg_scale_percent: Option<u32>,
+    /// X and Y axis titles; empty titles keep the default column names.
+    #[serde(default)]
+    pub axis_titles: [String; 2],
+    /// Secondary Y axis title; empty keeps the `y2` column name.
+    #[serde(default)]
+    pub secondary_y_title: String,
+    /// Exports made from this project, oldest first.
+    #[serde(default)]
+    pub export_history: Vec<ExportHistoryRecord>,
+    /// Data checks run on exported rows before they are written.
+    #[serde(default)]
+    pub validation_rules: Vec<ValidationRule>,
+    /// Header of the exported family parameter column; empty uses `parameter`.
+    #[serde(default)]
+    pub parameter_name: String,
+}
+
+/// Version 2 calibration payload (before affine calibration).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationRecordV2 {
+    pub coord_system: CoordSystem,
+    pub x: AxisCalibrationRecord,
+    pub y: AxisCalibrationRecord,
+    pub polar: PolarCalibrationRecord,
+    pub calibration_angle_snap: bool,
+    pub show_calibration_segments: bool,
 }
 
 /// Version 1 calibration payload (cartesian only).
@@ -123,6 +318,14 @@ pub struct CalibrationRecordV1 {
     pub show_calibration_segments: bool,
 }
 
+/// Point record of versions 1 and 2 (before review flags).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointRecordV2 {
+    pub pixel: [f32; 2],
+    pub x_numeric: Option<f64>,
+    pub y_numeric: Option<f64>,
+}
+
 /// Version 1 project payload (before polar support).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectPayloadV1 {
@@ -131,21 +334,21 @@ pub struct ProjectPayloadV1 {
     pub image_crc32: u32,
     pub transform: ImageTransformRecord,
     pub calibration: CalibrationRecordV1,
-    pub points: Vec<PointRecord>,
+    pub points: Vec<PointRecordV2>,
     pub zoom: f32,
     pub pan: [f32; 2],
     pub title: Option<String>,
     pub description: Option<String>,
 }
 
-impl From<ProjectPayloadV1> for ProjectPayload {
+impl From<ProjectPayloadV1> for ProjectPayloadV2 {
     fn from(v1: ProjectPayloadV1) -> Self {
         Self {
             absolute_image_path: v1.absolute_image_path,
             relative_image_path: v1.relative_image_path,
             image_crc32: v1.image_crc32,
             transform: v1.transform,
-            calibration: CalibrationRecord {
+            calibration: CalibrationRecordV2 {
                 coord_system: CoordSystem::Cartesian,
                 x: v1.calibration.x,
                 y: v1.calibration.y,
@@ -162,6 +365,81 @@ impl From<ProjectPayloadV1> for ProjectPayload {
     }
 }
 
+/// Version 2 project payload (a single curve of plain points).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectPayloadV2 {
+    pub absolute_image_path: PathBuf,
+    pub relative_image_path: Option<PathBuf>,
+    pub image_crc32: u32,
+    pub transform: ImageTransformRecord,
+    pub calibration: CalibrationRecordV2,
+    pub points: Vec<PointRecordV2>,
+    pub zoom: f32,
+    pub pan: [f32; 2],
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+impl From<ProjectPayloadV2> for ProjectPayload {
+    fn from(v2: ProjectPayloadV2) -> Self {
+        let points = v2
+            .points
+            .into_iter()
+            .map(|point| PointRecord {
+                pixel: point.pixel,
+                x_numeric: point.x_numeric,
+                y_numeric: point.y_numeric,
+                flag: None,
+                captured_at_ms: None,
+                error_bar: None,
+                confidence: 0.0,
+                locked: false,
+            })
+            .collect();
+        Self {
+            absolute_image_path: v2.absolute_image_path,
+            relative_image_path: v2.relative_image_path,
+            image_crc32: v2.image_crc32,
+            perspective: Vec::new(),
+            transform: v2.transform,
+            calibration: CalibrationRecord {
+                coord_system: v2.calibration.coord_system,
+                x: v2.calibration.x,
+                y: v2.calibration.y,
+                polar: v2.calibration.polar,
+                affine: AffineCalibrationRecord::default(),
+                calibration_angle_snap: v2.calibration.calibration_angle_snap,
+                show_calibration_segments: v2.calibration.show_calibration_segments,
+                secondary_y: AxisCalibrationRecord::default(),
+                inverted_axes: InvertedAxesRecord::default(),
+                smith: SmithCalibrationRecord::default(),
+                time_inputs: TimeInputsRecord::default(),
+            },
+            curves: vec![CurveRecord {
+                name: "Curve 1".to_string(),
+                color: None,
+                points,
+                y_axis: YAxis::Primary,
+                parameter_text: String::new(),
+                locked: false,
+            }],
+            active_curve: 0,
+            zoom: v2.zoom,
+            pan: v2.pan,
+            title: v2.title,
+            description: v2.description,
+            config_overrides: ConfigOverrides::default(),
+            swatches: Vec::new(),
+            svg_scale_percent: None,
+            axis_titles: Default::default(),
+            secondary_y_title: String::new(),
+            export_history: Vec::new(),
+            validation_rules: Vec::new(),
+            parameter_name: String::new(),
+        }
+    }
+}
+
 /// Where the image path was resolved from.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ImagePathSource {
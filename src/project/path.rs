@@ -72,20 +72,37 @@ fn sync_parent_dir(path: &Path) -> io::Result<()> {
     }
 }
 
-pub(super) fn write_atomic(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+/// Write through a synced temp file and a rename, so readers never see a partial file.
+pub fn write_atomic(path: &Path, data: &[u8]) -> anyhow::Result<()> {
+    write_atomic_with(path, |file| {
+        file.write_all(data)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    })
+}
+
+/// [`write_atomic`] with `write` filling the temp file, for output produced piece by piece.
+///
+/// When `write` fails the temp file is removed and `path` keeps its previous contents.
+pub fn write_atomic_with(
+    path: &Path,
+    write: impl FnOnce(&mut fs::File) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
     let parent = path.parent().unwrap_or_else(|| Path::new("."));
     fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
     let tmp_path = build_temp_path(path);
-    {
-        let mut file = fs::OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&tmp_path)
-            .with_context(|| format!("Failed to create temp file {}", tmp_path.display()))?;
-        file.write_all(data)
-            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
-        file.sync_all()
-            .with_context(|| format!("Failed to sync {}", tmp_path.display()))?;
+    let written = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp_path)
+        .with_context(|| format!("Failed to create temp file {}", tmp_path.display()))
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()
+                .with_context(|| format!("Failed to sync {}", tmp_path.display()))
+        });
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
     }
     if let Err(err) = replace_file(&tmp_path, path)
         .with_context(|| format!("Failed to replace {} with temp file", path.display()))
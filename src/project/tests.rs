@@ -3,8 +3,10 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::image::{ImageTransformOp, ImageTransformRecord};
-use crate::types::{AxisUnit, CoordSystem, ScaleKind};
+use crate::config::{ConfigOverrides, ExportProfile, HexColor, StrokeStyle};
+use crate::image::{ImageTransformOp, ImageTransformRecord, PerspectiveWarpRecord};
+use crate::types::{AxisUnit, CoordSystem, ScaleKind, YAxis};
+use crate::validation::{RuleCheck, RuleColumn, RuleSeverity, ValidationRule};
 
 fn unique_temp_dir(label: &str) -> PathBuf {
     let nanos = SystemTime::now()
@@ -15,46 +17,185 @@ fn unique_temp_dir(label: &str) -> PathBuf {
     dir
 }
 
+fn sample_calibration() -> CalibrationRecord {
+    CalibrationRecord {
+        coord_system: CoordSystem::Cartesian,
+        x: AxisCalibrationRecord {
+            unit: AxisUnit::Float,
+            scale: ScaleKind::Linear,
+            p1: Some([0.0, 0.0]),
+            p2: Some([10.0, 0.0]),
+            v1_text: "0".to_string(),
+            v2_text: "10".to_string(),
+        },
+        y: AxisCalibrationRecord {
+            unit: AxisUnit::Float,
+            scale: ScaleKind::Linear,
+            p1: Some([0.0, 0.0]),
+            p2: Some([0.0, 10.0]),
+            v1_text: "0".to_string(),
+            v2_text: "10".to_string(),
+        },
+        secondary_y: AxisCalibrationRecord {
+            unit: AxisUnit::Float,
+            scale: ScaleKind::Log10,
+            p1: Some([10.0, 0.0]),
+            p2: Some([10.0, 10.0]),
+            v1_text: "1".to_string(),
+            v2_text: "1000".to_string(),
+        },
+        inverted_axes: InvertedAxesRecord {
+            y: true,
+            ..InvertedAxesRecord::default()
+        },
+        polar: PolarCalibrationRecord::default(),
+        affine: AffineCalibrationRecord {
+            points: vec![
+                AffineReferenceRecord {
+                    pixel: Some([12.0, 40.0]),
+                    x_text: "0".to_string(),
+                    y_text: "0".to_string(),
+                },
+                AffineReferenceRecord::default(),
+                AffineReferenceRecord::default(),
+                AffineReferenceRecord::default(),
+            ],
+            ..AffineCalibrationRecord::default()
+        },
+        smith: SmithCalibrationRecord {
+            center: Some([50.0, 50.0]),
+            short: Some([10.0, 50.0]),
+            reference_text: "75".to_string(),
+        },
+        time_inputs: TimeInputsRecord {
+            x: AxisTimeRecord {
+                pattern: "%d %b %Y".to_string(),
+                offset_text: "+03:00".to_string(),
+            },
+            ..TimeInputsRecord::default()
+        },
+        calibration_angle_snap: false,
+        show_calibration_segments: true,
+    }
+}
+
 fn sample_payload(image_path: &Path, image_crc32: u32) -> ProjectPayload {
     ProjectPayload {
         absolute_image_path: image_path.to_path_buf(),
         relative_image_path: image_path.file_name().map(PathBuf::from),
         image_crc32,
+        perspective: vec![PerspectiveWarpRecord {
+            prior: ImageTransformRecord {
+                rotation_quarters: 1,
+                reflected: false,
+            },
+            corners: [[4.0, 6.0], [90.0, 2.0], [96.0, 70.0], [1.0, 64.0]],
+        }],
         transform: ImageTransformRecord::identity(),
-        calibration: CalibrationRecord {
-            coord_system: CoordSystem::Cartesian,
-            x: AxisCalibrationRecord {
-                unit: AxisUnit::Float,
-                scale: ScaleKind::Linear,
-                p1: Some([0.0, 0.0]),
-                p2: Some([10.0, 0.0]),
-                v1_text: "0".to_string(),
-                v2_text: "10".to_string(),
+        calibration: sample_calibration(),
+        curves: vec![
+            CurveRecord {
+                name: "Curve 1".to_string(),
+                color: None,
+                points: sample_points(),
+                y_axis: YAxis::Primary,
+                parameter_text: "25".to_string(),
+                locked: false,
             },
-            y: AxisCalibrationRecord {
-                unit: AxisUnit::Float,
-                scale: ScaleKind::Linear,
-                p1: Some([0.0, 0.0]),
-                p2: Some([0.0, 10.0]),
-                v1_text: "0".to_string(),
-                v2_text: "10".to_string(),
+            CurveRecord {
+                name: "Reference".to_string(),
+                color: Some([66, 135, 245, 255]),
+                points: vec![PointRecord {
+                    pixel: [5.0, 6.0],
+                    x_numeric: Some(5.0),
+                    y_numeric: Some(6.0),
+                    flag: None,
+                    captured_at_ms: None,
+                    error_bar: Some([[5.0, 4.0], [5.0, 9.5]]),
+                    confidence: 0.0,
+                    locked: false,
+                }],
+                y_axis: YAxis::Secondary,
+                parameter_text: String::new(),
+                locked: true,
             },
-            polar: PolarCalibrationRecord::default(),
-            calibration_angle_snap: false,
-            show_calibration_segments: true,
-        },
-        points: vec![PointRecord {
-            pixel: [1.0, 2.0],
-            x_numeric: Some(1.0),
-            y_numeric: Some(2.0),
-        }],
+        ],
+        active_curve: 1,
         zoom: 1.0,
         pan: [0.0, 0.0],
         title: Some("Test".to_string()),
         description: Some("Project roundtrip".to_string()),
+        config_overrides: ConfigOverrides {
+            curve_line: Some(StrokeStyle {
+                color: HexColor::from_rgb(10, 20, 30),
+                thickness: 4.5,
+            }),
+            ..ConfigOverrides::default()
+        },
+        swatches: vec![SwatchRecord {
+            name: "Series A red".to_string(),
+            color: [220, 40, 40, 255],
+        }],
+        svg_scale_percent: Some(200),
+        axis_titles: ["Time, s".to_string(), String::new()],
+        secondary_y_title: "Pressure [kPa]".to_string(),
+        export_history: vec![ExportHistoryRecord {
+            path: PathBuf::from("/tmp/curves.csv"),
+            settings: ExportProfile::default(),
+            rows: 42,
+            exported_at_ms: 1_790_000_000_000,
+        }],
+        validation_rules: vec![
+            ValidationRule::default(),
+            ValidationRule {
+                column: RuleColumn::X,
+                check: RuleCheck::StrictlyIncreasing,
+                severity: RuleSeverity::Warn,
+            },
+        ],
+        parameter_name: "T, °C".to_string(),
+    }
+}
+
+/// Calibration as stored by version 2, which had no affine section.
+fn legacy_calibration(calibration: CalibrationRecord) -> super::model::CalibrationRecordV2 {
+    super::model::CalibrationRecordV2 {
+        coord_system: calibration.coord_system,
+        x: calibration.x,
+        y: calibration.y,
+        polar: calibration.polar,
+        calibration_angle_snap: calibration.calibration_angle_snap,
+        show_calibration_segments: calibration.show_calibration_segments,
     }
 }
 
+fn sample_points() -> Vec<PointRecord> {
+    vec![
+        PointRecord {
+            pixel: [1.0, 2.0],
+            x_numeric: Some(1.0),
+            y_numeric: Some(2.0),
+            flag: None,
+            captured_at_ms: None,
+            error_bar: None,
+            confidence: 0.92,
+            locked: true,
+        },
+        PointRecord {
+            pixel: [3.0, 4.0],
+            x_numeric: Some(3.0),
+            y_numeric: Some(4.0),
+            flag: Some(PointFlagRecord {
+                comments: vec!["Looks off the curve".to_string()],
+            }),
+            captured_at_ms: Some(1_700_000_000_123),
+            error_bar: None,
+            confidence: 0.0,
+            locked: false,
+        },
+    ]
+}
+
 #[test]
 fn save_and_load_roundtrip() {
     let dir = unique_temp_dir("roundtrip");
@@ -68,7 +209,65 @@ fn save_and_load_roundtrip() {
     let outcome = load_project(&project_path).expect("load project");
     assert!(outcome.warnings.is_empty());
     assert_eq!(outcome.payload.image_crc32, payload.image_crc32);
-    assert_eq!(outcome.payload.points.len(), payload.points.len());
+    assert_eq!(outcome.payload.curves.len(), 2);
+    assert_eq!(outcome.payload.active_curve, 1);
+    let first = &outcome.payload.curves[0];
+    assert_eq!(first.points.len(), payload.curves[0].points.len());
+    assert_eq!(first.points[1].flag, payload.curves[0].points[1].flag);
+    assert_eq!(first.points[1].captured_at_ms, Some(1_700_000_000_123));
+    assert!((first.points[0].confidence - 0.92).abs() < 1e-6);
+    assert_eq!(
+        first.points.iter().map(|p| p.locked).collect::<Vec<_>>(),
+        [true, false]
+    );
+    assert!(!first.locked);
+    let second = &outcome.payload.curves[1];
+    assert_eq!(second.name, "Reference");
+    assert!(second.locked);
+    assert_eq!(second.color, Some([66, 135, 245, 255]));
+    assert_eq!(second.points.len(), 1);
+    assert_eq!(second.points[0].error_bar, Some([[5.0, 4.0], [5.0, 9.5]]));
+    assert!(first.points[0].error_bar.is_none());
+    assert_eq!(first.y_axis, YAxis::Primary);
+    assert_eq!(second.y_axis, YAxis::Secondary);
+    let secondary_y = &outcome.payload.calibration.secondary_y;
+    assert_eq!(secondary_y.scale, ScaleKind::Log10);
+    assert_eq!(secondary_y.p2, Some([10.0, 10.0]));
+    assert_eq!(secondary_y.v2_text, "1000");
+    assert_eq!(
+        outcome.payload.calibration.inverted_axes,
+        payload.calibration.inverted_axes
+    );
+    let line = outcome
+        .payload
+        .config_overrides
+        .curve_line
+        .expect("curve line override");
+    assert!((line.thickness - 4.5).abs() < f32::EPSILON);
+    assert_eq!(line.color, HexColor::from_rgb(10, 20, 30));
+    assert!(outcome.payload.config_overrides.export.is_none());
+    assert_eq!(outcome.payload.swatches.len(), 1);
+    assert_eq!(outcome.payload.swatches[0].name, "Series A red");
+    assert_eq!(outcome.payload.swatches[0].color, [220, 40, 40, 255]);
+    assert_eq!(outcome.payload.svg_scale_percent, Some(200));
+    assert_eq!(outcome.payload.axis_titles, payload.axis_titles);
+    assert_eq!(outcome.payload.secondary_y_title, "Pressure [kPa]");
+    assert_eq!(outcome.payload.export_history, payload.export_history);
+    assert_eq!(outcome.payload.validation_rules, payload.validation_rules);
+    assert_eq!(outcome.payload.parameter_name, "T, °C");
+    assert_eq!(outcome.payload.curves[0].parameter_text, "25");
+    let affine = &outcome.payload.calibration.affine;
+    assert_eq!(affine.points.len(), 4);
+    assert_eq!(affine.points[0].pixel, Some([12.0, 40.0]));
+    assert_eq!(affine.points[0].x_text, "0");
+    let smith = &outcome.payload.calibration.smith;
+    assert_eq!(smith.center, Some([50.0, 50.0]));
+    assert_eq!(smith.short, Some([10.0, 50.0]));
+    assert_eq!(smith.reference_text, "75");
+    let time_x = &outcome.payload.calibration.time_inputs.x;
+    assert_eq!(time_x.pattern, "%d %b %Y");
+    assert_eq!(time_x.offset_text, "+03:00");
+    assert_eq!(outcome.payload.perspective, payload.perspective);
     assert_eq!(outcome.chosen_image.path, image_path);
     assert!(outcome.chosen_image.checksum_matches);
 }
@@ -146,7 +345,7 @@ fn load_v1_migrates_to_cartesian() {
             calibration_angle_snap: false,
             show_calibration_segments: true,
         },
-        points: vec![PointRecord {
+        points: vec![super::model::PointRecordV2 {
             pixel: [1.0, 2.0],
             x_numeric: Some(1.0),
             y_numeric: Some(2.0),
@@ -179,3 +378,87 @@ fn load_v1_migrates_to_cartesian() {
     );
     assert!(outcome.payload.calibration.polar.origin.is_none());
 }
+
+#[test]
+fn load_v2_migrates_points_into_a_single_curve() {
+    let dir = unique_temp_dir("v2");
+    let image_path = dir.join("image.bin");
+    fs::write(&image_path, b"image-bytes").expect("write image");
+    let crc = compute_image_crc32(&image_path).expect("checksum");
+    let current = sample_payload(&image_path, crc);
+
+    let payload_v2 = super::model::ProjectPayloadV2 {
+        absolute_image_path: current.absolute_image_path,
+        relative_image_path: current.relative_image_path,
+        image_crc32: current.image_crc32,
+        transform: current.transform,
+        calibration: legacy_calibration(current.calibration),
+        points: vec![super::model::PointRecordV2 {
+            pixel: [5.0, 6.0],
+            x_numeric: Some(5.0),
+            y_numeric: None,
+        }],
+        zoom: current.zoom,
+        pan: current.pan,
+        title: current.title,
+        description: current.description,
+    };
+
+    let encoded = bincode::serde::encode_to_vec(
+        &payload_v2,
+        bincode::config::standard().with_little_endian(),
+    )
+    .expect("encode v2");
+    let compressed = lz4_flex::block::compress_prepend_size(&encoded);
+    let mut buffer = Vec::with_capacity(super::io::PROJECT_MAGIC.len() + 4 + compressed.len());
+    buffer.extend_from_slice(super::io::PROJECT_MAGIC);
+    buffer.extend_from_slice(&2u32.to_le_bytes());
+    buffer.extend_from_slice(&compressed);
+
+    let project_path = dir.join("project_v2.curcat");
+    fs::write(&project_path, &buffer).expect("write v2 project");
+
+    let outcome = load_project(&project_path).expect("load v2");
+    assert_eq!(outcome.version, 2);
+    let payload = outcome.payload;
+    assert_eq!(payload.curves.len(), 1);
+    assert_eq!(payload.active_curve, 0);
+    let curve = &payload.curves[0];
+    assert_eq!(curve.name, "Curve 1");
+    assert_eq!(
+        (curve.color, curve.y_axis, curve.locked),
+        (None, YAxis::Primary, false)
+    );
+    assert_eq!(curve.points.len(), 1);
+    let point = &curve.points[0];
+    assert!((point.pixel[0] - 5.0).abs() < f32::EPSILON);
+    assert_eq!(point.x_numeric, Some(5.0));
+    assert!(point.flag.is_none() && point.captured_at_ms.is_none() && point.error_bar.is_none());
+    assert!(point.confidence.abs() < f32::EPSILON && !point.locked);
+    assert_eq!(payload.calibration.x.v2_text, "10");
+    assert_eq!(payload.calibration.affine.points.len(), 3);
+    assert_eq!(
+        payload.calibration.inverted_axes,
+        InvertedAxesRecord::default()
+    );
+    assert!(payload.perspective.is_empty() && payload.swatches.is_empty());
+    assert!(payload.export_history.is_empty() && payload.validation_rules.is_empty());
+    assert_eq!(payload.svg_scale_percent, None);
+    assert_eq!(payload.axis_titles, [String::new(), String::new()]);
+}
+
+#[test]
+fn load_rejects_unknown_versions() {
+    let dir = unique_temp_dir("unknown_version");
+    let mut buffer = super::io::PROJECT_MAGIC.to_vec();
+    buffer.extend_from_slice(&(super::io::PROJECT_VERSION + 1).to_le_bytes());
+    buffer.extend_from_slice(&lz4_flex::block::compress_prepend_size(b"payload"));
+    let project_path = dir.join("project_future.curcat");
+    fs::write(&project_path, &buffer).expect("write project");
+
+    let error = load_project(&project_path).expect_err("unknown version");
+    assert_eq!(
+        error.to_string(),
+        "Unsupported project version 4. Supported versions: 1, 2, 3"
+    );
+}
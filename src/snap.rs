@@ -2,10 +2,27 @@
 
 mod behavior;
 mod color;
+mod frame;
+mod grid;
 mod maps;
+mod markers;
+mod mask;
 mod palette;
+mod scorers;
 mod search;
+#[cfg(feature = "segmentation")]
+mod segment;
 
-pub use behavior::{SnapBehavior, SnapFeatureSource, SnapThresholdKind};
+pub use behavior::{
+    CenterlineScorer, ContrastScorer, SnapBehavior, SnapFeatureSource, SnapThresholdKind,
+};
+pub use color::{ColorSampleMode, sample_color_area};
+pub use frame::detect_plot_frame;
+pub use grid::{detect_grid_lines, spacing_scale, suppress_grid_lines};
 pub use maps::SnapMapCache;
+pub use markers::{detect_markers, inside_polygon, sample_marker};
+pub use mask::CurveMask;
 pub use palette::derive_snap_overlay_palette;
+pub use scorers::SNAP_SCORERS;
+#[cfg(feature = "segmentation")]
+pub use segment::SegmentationModel;
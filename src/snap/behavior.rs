@@ -1,3 +1,5 @@
+use super::maps::SnapMapLevel;
+
 /// Image feature source used to score candidate pixels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SnapFeatureSource {
@@ -18,53 +20,100 @@ pub enum SnapThresholdKind {
     Score,
 }
 
-/// Behavior configuration for snapping (contrast or centerline).
+/// Map values around one candidate pixel of a snap level.
+///
+/// Offsets past the level edge are clamped to the nearest edge pixel.
+#[derive(Clone, Copy)]
+pub struct SnapSample<'a> {
+    level: &'a SnapMapLevel,
+    x: i32,
+    y: i32,
+}
+
+impl<'a> SnapSample<'a> {
+    pub(super) const fn new(level: &'a SnapMapLevel, x: i32, y: i32) -> Self {
+        Self { level, x, y }
+    }
+
+    /// Luma gradient magnitude at the candidate (0–255).
+    pub fn gradient(&self) -> f32 {
+        self.gradient_at(0, 0)
+    }
+
+    /// Similarity to the target curve color at the candidate (0–1).
+    pub fn color_similarity(&self) -> f32 {
+        self.color_similarity_at(0, 0)
+    }
+
+    /// Curve probability from the attached segmentation mask (0–1); 0 without a mask.
+    pub fn curve_probability(&self) -> f32 {
+        self.level.curve_probability_at(self.x, self.y)
+    }
+
+    pub fn gradient_at(&self, dx: i32, dy: i32) -> f32 {
+        self.level.gradient_at(self.x + dx, self.y + dy)
+    }
+
+    pub fn color_similarity_at(&self, dx: i32, dy: i32) -> f32 {
+        self.level.color_similarity_at(self.x + dx, self.y + dy)
+    }
+}
+
+/// Scoring strategy used by the snap search.
+///
+/// The search visits every pixel in the radius, drops the ones with zero
+/// strength or a failed threshold and weighs the rest by closeness to the
+/// cursor. Strengths are on a 0–255 scale.
+pub trait SnapBehavior: Send + Sync {
+    fn feature_strength(&self, sample: &SnapSample<'_>) -> f32;
+
+    fn threshold_passes(&self, sample: &SnapSample<'_>, feature_strength: f32) -> bool;
+}
+
+/// Snap to edges: high luma gradient, color match, or a blend of both.
 #[derive(Debug, Clone, Copy)]
-pub enum SnapBehavior {
-    Contrast {
-        feature_source: SnapFeatureSource,
-        threshold_kind: SnapThresholdKind,
-        threshold: f32,
-    },
-    Centerline {
-        threshold: f32,
-    },
+pub struct ContrastScorer {
+    pub feature_source: SnapFeatureSource,
+    pub threshold_kind: SnapThresholdKind,
+    pub threshold: f32,
 }
 
-impl SnapBehavior {
-    pub(super) fn feature_strength(self, gradient: f32, color_similarity: f32) -> f32 {
-        match self {
-            Self::Contrast { feature_source, .. } => match feature_source {
-                SnapFeatureSource::LumaGradient => gradient.clamp(0.0, 255.0),
-                SnapFeatureSource::ColorMatch => (color_similarity * 255.0).clamp(0.0, 255.0),
-                SnapFeatureSource::Hybrid => {
-                    let grad_strength = gradient.clamp(0.0, 255.0);
-                    let color_strength = (color_similarity * 255.0).clamp(0.0, 255.0);
-                    0.6f32.mul_add(grad_strength, 0.4 * color_strength)
-                }
-            },
-            Self::Centerline { .. } => {
-                let color_strength = (color_similarity * 255.0).clamp(0.0, 255.0);
-                if color_strength <= f32::EPSILON {
-                    return 0.0;
-                }
-                let grad_norm = (gradient / 255.0).clamp(0.0, 1.0);
-                color_strength * (1.0 - grad_norm)
-            }
+impl SnapBehavior for ContrastScorer {
+    fn feature_strength(&self, sample: &SnapSample<'_>) -> f32 {
+        let grad_strength = sample.gradient().clamp(0.0, 255.0);
+        let color_strength = (sample.color_similarity() * 255.0).clamp(0.0, 255.0);
+        match self.feature_source {
+            SnapFeatureSource::LumaGradient => grad_strength,
+            SnapFeatureSource::ColorMatch => color_strength,
+            SnapFeatureSource::Hybrid => 0.6f32.mul_add(grad_strength, 0.4 * color_strength),
+        }
+    }
+
+    fn threshold_passes(&self, sample: &SnapSample<'_>, feature_strength: f32) -> bool {
+        match self.threshold_kind {
+            SnapThresholdKind::Gradient => sample.gradient() >= self.threshold,
+            SnapThresholdKind::Score => feature_strength >= self.threshold,
         }
     }
+}
 
-    pub(super) fn threshold_passes(self, gradient: f32, feature_strength: f32) -> bool {
-        match self {
-            Self::Contrast {
-                threshold_kind,
-                threshold,
-                ..
-            } => match threshold_kind {
-                SnapThresholdKind::Gradient => gradient >= threshold,
-                SnapThresholdKind::Score => feature_strength >= threshold,
-            },
-            Self::Centerline { threshold } => feature_strength >= threshold,
+/// Snap to the flat middle of a colored stroke, away from its edges.
+#[derive(Debug, Clone, Copy)]
+pub struct CenterlineScorer {
+    pub threshold: f32,
+}
+
+impl SnapBehavior for CenterlineScorer {
+    fn feature_strength(&self, sample: &SnapSample<'_>) -> f32 {
+        let color_strength = (sample.color_similarity() * 255.0).clamp(0.0, 255.0);
+        if color_strength <= f32::EPSILON {
+            return 0.0;
         }
+        let grad_norm = (sample.gradient() / 255.0).clamp(0.0, 1.0);
+        color_strength * (1.0 - grad_norm)
+    }
+
+    fn threshold_passes(&self, _sample: &SnapSample<'_>, feature_strength: f32) -> bool {
+        feature_strength >= self.threshold
     }
 }
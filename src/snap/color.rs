@@ -1,4 +1,4 @@
-use egui::Color32;
+use egui::{Color32, ColorImage};
 
 #[allow(clippy::suboptimal_flops)]
 pub(super) fn color_luminance(color: Color32) -> f32 {
@@ -17,3 +17,103 @@ pub(super) fn color_similarity_value(color: Color32, target: Color32, tolerance:
     let tol = tolerance.max(1.0);
     ((tol - diff).max(0.0) / tol).clamp(0.0, 1.0)
 }
+
+/// How the curve color picker reduces the neighbourhood under the cursor to one color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSampleMode {
+    Pixel,
+    Mean3x3,
+    Mean5x5,
+    Median3x3,
+    Median5x5,
+}
+
+impl ColorSampleMode {
+    /// Ordered list of sampling modes exposed in the UI.
+    pub const ALL: [Self; 5] = [
+        Self::Pixel,
+        Self::Mean3x3,
+        Self::Mean5x5,
+        Self::Median3x3,
+        Self::Median5x5,
+    ];
+
+    /// Pixels sampled on each side of the center pixel.
+    pub const fn half_window(self) -> usize {
+        match self {
+            Self::Pixel => 0,
+            Self::Mean3x3 | Self::Median3x3 => 1,
+            Self::Mean5x5 | Self::Median5x5 => 2,
+        }
+    }
+}
+
+/// Sample `image` around pixel `(x, y)`; the window is clipped at the image border.
+///
+/// Channels are reduced independently, so a median can mix channels of different pixels;
+/// on anti-aliased strokes that still lands closer to the stroke color than a single pixel.
+pub fn sample_color_area(
+    image: &ColorImage,
+    x: usize,
+    y: usize,
+    mode: ColorSampleMode,
+) -> Option<Color32> {
+    let [w, h] = image.size;
+    if x >= w || y >= h {
+        return None;
+    }
+    let half = mode.half_window();
+    let mut channels: [Vec<u8>; 4] = Default::default();
+    for sy in y.saturating_sub(half)..=(y + half).min(h - 1) {
+        for sx in x.saturating_sub(half)..=(x + half).min(w - 1) {
+            let rgba = image.pixels[sy * w + sx].to_array();
+            for (channel, value) in channels.iter_mut().zip(rgba) {
+                channel.push(value);
+            }
+        }
+    }
+    let reduce = |values: &mut Vec<u8>| -> u8 {
+        match mode {
+            ColorSampleMode::Median3x3 | ColorSampleMode::Median5x5 => {
+                values.sort_unstable();
+                values[values.len() / 2]
+            }
+            ColorSampleMode::Pixel | ColorSampleMode::Mean3x3 | ColorSampleMode::Mean5x5 => {
+                let sum: usize = values.iter().map(|&v| usize::from(v)).sum();
+                let mean = (sum + values.len() / 2) / values.len();
+                u8::try_from(mean).unwrap_or(u8::MAX)
+            }
+        }
+    };
+    let [red, green, blue, alpha] = channels.each_mut().map(reduce);
+    Some(Color32::from_rgba_premultiplied(red, green, blue, alpha))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_sampling_smooths_antialiased_edges() {
+        // A red 3 px stroke with one washed-out anti-aliased pixel in the middle.
+        let mut pixels = vec![Color32::WHITE; 5 * 5];
+        for y in 0..5 {
+            for x in 1..4 {
+                pixels[y * 5 + x] = Color32::from_rgb(200, 0, 0);
+            }
+        }
+        pixels[2 * 5 + 2] = Color32::from_rgb(240, 180, 180);
+        let image = ColorImage::new([5, 5], pixels);
+
+        let pixel = sample_color_area(&image, 2, 2, ColorSampleMode::Pixel);
+        assert_eq!(pixel, Some(Color32::from_rgb(240, 180, 180)));
+        let median = sample_color_area(&image, 2, 2, ColorSampleMode::Median3x3);
+        assert_eq!(median, Some(Color32::from_rgb(200, 0, 0)));
+        let mean = sample_color_area(&image, 2, 2, ColorSampleMode::Mean3x3).expect("mean");
+        assert_eq!(mean.to_array(), [204, 20, 20, 255]);
+        // The corner window is clipped to the 3×3 pixels inside the image.
+        let corner = sample_color_area(&image, 0, 0, ColorSampleMode::Mean5x5).expect("corner");
+        assert_eq!(corner.to_array(), [223, 105, 105, 255]);
+        assert!(sample_color_area(&image, 5, 0, ColorSampleMode::Pixel).is_none());
+    }
+}
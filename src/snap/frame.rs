@@ -0,0 +1,331 @@
+//! Plot frame detection: the box a chart's axes draw, found in a screenshot so the window
+//! around the chart can be cropped away.
+//!
+//! A frame side is a long thin line: a run of one color that differs from the pixels a few
+//! rows (or columns) away on both sides. Tick marks crossing a side are bridged. The left and
+//! bottom sides must meet in a corner; the top and right ones are optional, as many charts draw
+//! only the two axes.
+//!
+//! The crop grows outward from the frame over tick labels and titles, up to a blank gap or a
+//! line mostly in ink, which is taken for the toolbar or panel border of the window.
+
+use super::grid::{color_distance, dominant_color};
+use crate::profiling::profile_function;
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, ColorImage, Rect, pos2};
+use rayon::prelude::*;
+
+/// Shortest frame side, in pixels.
+const MIN_SIDE: usize = 48;
+/// Distance across a line at which pixels are compared with it.
+const LINE_REACH: usize = 3;
+/// Longest stretch of a side crossed by tick marks or grid lines.
+const MAX_BRIDGE: usize = 3;
+/// Largest summed RGB difference along a side.
+const RUN_TOLERANCE: u32 = 60;
+/// Smallest summed RGB difference counted as contrast, across a side or from the background.
+const INK_CONTRAST: u32 = 45;
+/// Largest distance, in pixels, between the ends of the left and bottom sides at their corner.
+const CORNER_SLACK: usize = 6;
+/// Largest share of the image a frame may cover; a bigger one leaves nothing to crop.
+const MAX_FRAME_SHARE: f32 = 0.9;
+/// Largest share of the image a crop may keep to be worth suggesting.
+const MAX_CROP_SHARE: f32 = 0.95;
+/// Blank rows or columns past a frame side that end the crop there.
+const MIN_GAP: usize = 24;
+/// Share of a row or column in ink that makes it part of the surrounding window.
+const BARRIER_SHARE: f32 = 0.6;
+/// How far along a side, past its ends, labels are looked for, as a share of its length.
+const LABEL_OVERHANG: f32 = 0.1;
+/// Blank pixels kept around the crop's content.
+const PADDING: usize = 4;
+
+/// A chart found in a screenshot, in image pixels; both rects run from the first pixel to the
+/// last one they include.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlotFrame {
+    /// The box of the axes.
+    pub frame: Rect,
+    /// The frame with its tick labels and titles: the suggested crop.
+    pub crop: Rect,
+}
+
+/// A frame side candidate: the row (or column) it lies on and its extent along it, end
+/// exclusive.
+struct Segment {
+    at: usize,
+    start: usize,
+    end: usize,
+}
+
+/// Side runs along one row or column of `len` pixels, as `(start, end)`, end exclusive.
+///
+/// `across(i)` gives the two pixels `LINE_REACH` away on either side of pixel `i`.
+fn side_runs(
+    len: usize,
+    pixel: impl Fn(usize) -> Color32,
+    across: impl Fn(usize) -> (Color32, Color32),
+) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    // First and last pixel of the current run that stand out on both sides, and its color.
+    let mut run: Option<(usize, usize, Color32)> = None;
+    let mut close = |run: Option<(usize, usize, Color32)>| {
+        if let Some((start, last, _)) = run
+            && last + 1 - start >= MIN_SIDE
+        {
+            runs.push((start, last + 1));
+        }
+    };
+    for i in 0..len {
+        let color = pixel(i);
+        let (a, b) = across(i);
+        let on_line =
+            color_distance(color, a) >= INK_CONTRAST && color_distance(color, b) >= INK_CONTRAST;
+        match run {
+            Some((start, last, run_color))
+                if color_distance(color, run_color) <= RUN_TOLERANCE
+                    && (on_line || i - last <= MAX_BRIDGE) =>
+            {
+                if on_line {
+                    run = Some((start, i, run_color));
+                }
+            }
+            _ => {
+                close(run);
+                run = on_line.then_some((i, i, color));
+            }
+        }
+    }
+    close(run);
+    runs
+}
+
+/// Side candidates along the rows and along the columns of `image`.
+fn side_segments(image: &ColorImage) -> (Vec<Segment>, Vec<Segment>) {
+    let [width, height] = image.size;
+    let at = |x: usize, y: usize| image.pixels[y * width + x];
+    let horizontal = (LINE_REACH..height - LINE_REACH)
+        .into_par_iter()
+        .flat_map_iter(|y| {
+            side_runs(
+                width,
+                |x| at(x, y),
+                |x| (at(x, y - LINE_REACH), at(x, y + LINE_REACH)),
+            )
+            .into_iter()
+            .map(move |(start, end)| Segment { at: y, start, end })
+        })
+        .collect();
+    let vertical = (LINE_REACH..width - LINE_REACH)
+        .into_par_iter()
+        .flat_map_iter(|x| {
+            side_runs(
+                height,
+                |y| at(x, y),
+                |y| (at(x - LINE_REACH, y), at(x + LINE_REACH, y)),
+            )
+            .into_iter()
+            .map(move |(start, end)| Segment { at: x, start, end })
+        })
+        .collect();
+    (horizontal, vertical)
+}
+
+/// Most frequent color inside `frame`, sampled every other pixel.
+fn frame_background(image: &ColorImage, [x0, y0, x1, y1]: [usize; 4]) -> Color32 {
+    let width = image.size[0];
+    let samples: Vec<Color32> = (y0..=y1)
+        .step_by(2)
+        .flat_map(|y| {
+            (x0..=x1)
+                .step_by(2)
+                .map(move |x| image.pixels[y * width + x])
+        })
+        .collect();
+    dominant_color(&samples)
+}
+
+/// How many lines past a frame side the crop takes in, at most `limit`.
+///
+/// `ink_share(step)` is the share of ink on the line `step` lines out. The last line with ink
+/// before a blank gap, a line mostly in ink or the image edge is kept, with some padding.
+fn content_reach(limit: usize, ink_share: impl Fn(usize) -> f32) -> usize {
+    let mut reach = 0;
+    for step in 1..=limit {
+        let share = ink_share(step);
+        // The first lines out may still be the frame's own top or right side.
+        if share >= BARRIER_SHARE && step > LINE_REACH {
+            return (reach + PADDING).min(step - 1);
+        }
+        if share > 0.0 {
+            reach = step;
+        } else if step - reach >= MIN_GAP {
+            break;
+        }
+    }
+    (reach + PADDING).min(limit)
+}
+
+/// The plot frame of a screenshot and the crop around it; `None` when no frame is found or
+/// the crop would keep about the whole image.
+pub fn detect_plot_frame(image: &ColorImage) -> Option<PlotFrame> {
+    profile_function!();
+    let [width, height] = image.size;
+    if width < MIN_SIDE + 2 * LINE_REACH || height < MIN_SIDE + 2 * LINE_REACH {
+        return None;
+    }
+    let image_area = safe_usize_to_f32(width * height);
+    let (horizontal, vertical) = side_segments(image);
+    let [x0, y0, x1, y1] = vertical
+        .iter()
+        .flat_map(|left| {
+            horizontal
+                .iter()
+                .filter(|bottom| {
+                    left.at.abs_diff(bottom.start) <= CORNER_SLACK
+                        && bottom.at.abs_diff(left.end) <= CORNER_SLACK
+                })
+                .map(|bottom| [left.at, left.start, bottom.end - 1, bottom.at])
+        })
+        .filter(|[x0, y0, x1, y1]| {
+            x1 > x0
+                && y1 > y0
+                && safe_usize_to_f32((x1 - x0) * (y1 - y0)) <= MAX_FRAME_SHARE * image_area
+        })
+        .max_by_key(|[x0, y0, x1, y1]| (x1 - x0) * (y1 - y0))?;
+
+    let background = frame_background(image, [x0, y0, x1, y1]);
+    let is_ink = |x: usize, y: usize| {
+        color_distance(image.pixels[y * width + x], background) >= INK_CONTRAST
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let overhang = |len: usize| (safe_usize_to_f32(len) * LABEL_OVERHANG) as usize;
+    let (over_x, over_y) = (overhang(x1 - x0), overhang(y1 - y0));
+    let xs = x0.saturating_sub(over_x)..=(x1 + over_x).min(width - 1);
+    let ys = y0.saturating_sub(over_y)..=(y1 + over_y).min(height - 1);
+    let row_share = |y: usize| {
+        safe_usize_to_f32(xs.clone().filter(|&x| is_ink(x, y)).count())
+            / safe_usize_to_f32(xs.clone().count())
+    };
+    let column_share = |x: usize| {
+        safe_usize_to_f32(ys.clone().filter(|&y| is_ink(x, y)).count())
+            / safe_usize_to_f32(ys.clone().count())
+    };
+    let left = content_reach(x0, |step| column_share(x0 - step));
+    let right = content_reach(width - 1 - x1, |step| column_share(x1 + step));
+    let top = content_reach(y0, |step| row_share(y0 - step));
+    let bottom = content_reach(height - 1 - y1, |step| row_share(y1 + step));
+    let crop = [x0 - left, y0 - top, x1 + right, y1 + bottom];
+    let kept = safe_usize_to_f32((crop[2] - crop[0] + 1) * (crop[3] - crop[1] + 1));
+    if kept > MAX_CROP_SHARE * image_area {
+        return None;
+    }
+    let rect = |[x0, y0, x1, y1]: [usize; 4]| {
+        Rect::from_min_max(
+            pos2(safe_usize_to_f32(x0), safe_usize_to_f32(y0)),
+            pos2(safe_usize_to_f32(x1), safe_usize_to_f32(y1)),
+        )
+    };
+    Some(PlotFrame {
+        frame: rect([x0, y0, x1, y1]),
+        crop: rect(crop),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: usize = 480;
+    const HEIGHT: usize = 360;
+
+    fn fill(
+        image: &mut ColorImage,
+        xs: std::ops::Range<usize>,
+        ys: std::ops::Range<usize>,
+        color: Color32,
+    ) {
+        for y in ys {
+            for x in xs.clone() {
+                image.pixels[y * WIDTH + x] = color;
+            }
+        }
+    }
+
+    /// Axes from (120, 80) to (420, 290) with inward ticks, tick labels, and a title.
+    fn chart(boxed: bool) -> ColorImage {
+        let mut image = ColorImage::new([WIDTH, HEIGHT], vec![Color32::WHITE; WIDTH * HEIGHT]);
+        let ink = Color32::BLACK;
+        fill(&mut image, 120..121, 80..291, ink);
+        fill(&mut image, 120..421, 290..291, ink);
+        if boxed {
+            fill(&mut image, 420..421, 80..291, ink);
+            fill(&mut image, 120..421, 80..81, ink);
+        }
+        for x in (120..=420).step_by(50) {
+            fill(&mut image, x..x + 1, 286..290, ink);
+            fill(&mut image, x - 5..x + 5, 298..306, ink);
+        }
+        for y in (90..=290).step_by(50) {
+            fill(&mut image, 121..125, y..y + 1, ink);
+            fill(&mut image, 96..112, y - 4..y + 4, ink);
+        }
+        fill(&mut image, 220..320, 56..66, ink);
+        image
+    }
+
+    /// The chart inside a window: a toolbar above, a side panel on the left and a status bar
+    /// line below.
+    fn screenshot() -> ColorImage {
+        let mut image = chart(true);
+        fill(&mut image, 0..WIDTH, 0..28, Color32::from_gray(70));
+        fill(&mut image, 0..50, 28..HEIGHT, Color32::from_gray(235));
+        fill(&mut image, 50..51, 28..HEIGHT, Color32::from_gray(150));
+        fill(&mut image, 0..WIDTH, 340..341, Color32::from_gray(150));
+        image
+    }
+
+    fn assert_near(actual: Rect, expected: Rect) {
+        let off = (actual.min - expected.min)
+            .length()
+            .max((actual.max - expected.max).length());
+        assert!(off <= 1.0, "{actual:?} is not {expected:?}");
+    }
+
+    #[test]
+    fn the_crop_keeps_labels_and_title_and_drops_the_window() {
+        let found = detect_plot_frame(&screenshot()).expect("frame found");
+        assert_near(
+            found.frame,
+            Rect::from_min_max(pos2(120.0, 80.0), pos2(420.0, 290.0)),
+        );
+        assert_eq!(
+            found.crop,
+            Rect::from_min_max(pos2(92.0, 52.0), pos2(428.0, 309.0))
+        );
+    }
+
+    #[test]
+    fn two_axes_without_a_box_make_a_frame() {
+        let found = detect_plot_frame(&chart(false)).expect("frame found");
+        assert_near(
+            found.frame,
+            Rect::from_min_max(pos2(120.0, 80.0), pos2(420.0, 290.0)),
+        );
+    }
+
+    #[test]
+    fn blank_images_and_charts_filling_the_image_get_no_crop() {
+        let blank = ColorImage::new([WIDTH, HEIGHT], vec![Color32::WHITE; WIDTH * HEIGHT]);
+        assert_eq!(detect_plot_frame(&blank), None);
+        let mut full = blank;
+        fill(&mut full, 4..5, 4..HEIGHT - 4, Color32::BLACK);
+        fill(
+            &mut full,
+            4..WIDTH - 4,
+            HEIGHT - 5..HEIGHT - 4,
+            Color32::BLACK,
+        );
+        assert_eq!(detect_plot_frame(&full), None);
+    }
+}
@@ -0,0 +1,377 @@
+//! Grid-line suppression: fade horizontal and vertical rulings before snap maps are built.
+//!
+//! A ruling is a row or column mostly covered by long runs of one ink color. Curves crossing
+//! it break the runs, so only the ruling itself is painted over with the background and
+//! crossings keep their contrast. The faint anti-aliased fringe beside a ruling goes with it;
+//! even a light edge would still pass the contrast snap threshold.
+//!
+//! The same rulings, located by their centres, tell a linear axis from a log10 one by their
+//! spacing.
+
+use crate::profiling::profile_function;
+use crate::types::ScaleKind;
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, ColorImage};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Shortest stretch of constant color that counts toward a ruling.
+const MIN_RUN: usize = 16;
+/// Share of a row or column that its runs must cover to be treated as a ruling.
+const MIN_COVERAGE: f32 = 0.5;
+/// Largest summed RGB difference between a run's first pixel and the rest of it.
+const RUN_TOLERANCE: u32 = 60;
+/// Smallest summed RGB difference from the background that counts as ink.
+const INK_CONTRAST: u32 = 45;
+/// Fewest grid lines along an axis that [`spacing_scale`] reads a scale from.
+const MIN_SCALE_LINES: usize = 4;
+/// Relative deviation from a multiple of the smallest gap still counted as even spacing.
+const EVEN_GAP_TOLERANCE: f32 = 0.12;
+/// Mean distance, in decades, of the lines from the 1..9 pattern still counted as a log grid.
+const LOG_FIT_TOLERANCE: f32 = 0.008;
+/// Fewest different leading digits the lines of a log grid must cover.
+const MIN_LOG_DIGITS: usize = 4;
+
+/// Centres of the grid lines of a chart, in image pixels.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GridLines {
+    /// x of each vertical line, left to right.
+    pub vertical: Vec<f32>,
+    /// y of each horizontal line, top to bottom.
+    pub horizontal: Vec<f32>,
+}
+
+/// Ruling runs of one row or column as `(start, end)`, end exclusive.
+type Runs = Vec<(usize, usize)>;
+
+/// Ruling runs of every row and every column of `image`.
+fn ruling_lines(
+    image: &ColorImage,
+    is_ink: impl Fn(Color32) -> bool + Sync,
+) -> (Vec<Runs>, Vec<Runs>) {
+    let [width, height] = image.size;
+    let row_runs = image
+        .pixels
+        .par_chunks(width)
+        .map(|row| ruling_runs(row.iter().copied(), width, &is_ink))
+        .collect();
+    let column_runs = (0..width)
+        .into_par_iter()
+        .map(|x| {
+            ruling_runs(
+                (0..height).map(|y| image.pixels[y * width + x]),
+                height,
+                &is_ink,
+            )
+        })
+        .collect();
+    (row_runs, column_runs)
+}
+
+/// Grid lines of `image`: rows and columns mostly covered by long runs of one ink color, a
+/// line several pixels thick counted once at its centre.
+pub fn detect_grid_lines(image: &ColorImage) -> GridLines {
+    let [width, height] = image.size;
+    if width < MIN_RUN || height < MIN_RUN {
+        return GridLines::default();
+    }
+    let background = dominant_color(&image.pixels);
+    let (row_runs, column_runs) = ruling_lines(image, |color| {
+        color_distance(color, background) >= INK_CONTRAST
+    });
+    let centres = |runs: &[Runs]| {
+        let mut centres: Vec<f32> = Vec::new();
+        let mut band: Option<(usize, usize)> = None;
+        for (idx, line) in runs.iter().enumerate() {
+            band = match band {
+                _ if line.is_empty() => {
+                    centres.extend(band.map(band_centre));
+                    None
+                }
+                Some((start, end)) if end == idx => Some((start, idx + 1)),
+                _ => Some((idx, idx + 1)),
+            };
+        }
+        centres.extend(band.map(band_centre));
+        centres
+    };
+    GridLines {
+        vertical: centres(&column_runs),
+        horizontal: centres(&row_runs),
+    }
+}
+
+/// Axis scale the spacing of grid lines at `lines` suggests, or `None` when it is unclear.
+///
+/// Evenly spaced lines, allowing a few missing ones, read as linear. Lines in the repeating
+/// 1, 2, …, 9 pattern of a log decade, running either way, read as log10. A log axis ruled only
+/// at whole decades is evenly spaced and reads as linear.
+pub fn spacing_scale(lines: &[f32]) -> Option<ScaleKind> {
+    if lines.len() < MIN_SCALE_LINES {
+        return None;
+    }
+    let gaps: Vec<f32> = lines.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let smallest = gaps.iter().copied().fold(f32::INFINITY, f32::min);
+    if smallest <= 0.0 {
+        return None;
+    }
+    let multiples: Vec<f32> = gaps.iter().map(|gap| gap / smallest).collect();
+    let even = multiples
+        .iter()
+        .all(|m| (m - m.round()).abs() <= EVEN_GAP_TOLERANCE * m.round())
+        && multiples.iter().filter(|&&m| m < 1.5).count() * 2 >= multiples.len();
+    if even {
+        return Some(ScaleKind::Linear);
+    }
+    let mirrored: Vec<f32> = lines.iter().rev().map(|at| -at).collect();
+    (fits_log_decades(lines) || fits_log_decades(&mirrored)).then_some(ScaleKind::Log10)
+}
+
+/// Whether ascending `lines` sit at `log10(d)` of a decade for digits `d`, values growing
+/// along the axis.
+///
+/// The widest gap is the one from 1 to 2, which fixes both the decade length and its origin.
+fn fits_log_decades(lines: &[f32]) -> bool {
+    let Some((origin_idx, widest)) = lines
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return false;
+    };
+    let decade = widest / 2f32.log10();
+    let origin = lines[origin_idx];
+    let mut digits = [false; 9];
+    let mut residual = 0.0;
+    for &at in lines {
+        let t = (at - origin) / decade;
+        let within = t - t.floor();
+        let (digit, error) = (1..=10u8)
+            .map(|digit| (digit, (within - f32::from(digit).log10()).abs()))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((1, 0.0));
+        // The line at 10 starts the next decade over at digit 1.
+        digits[usize::from(digit % 10).max(1) - 1] = true;
+        residual += error;
+    }
+    residual / safe_usize_to_f32(lines.len()) <= LOG_FIT_TOLERANCE
+        && digits.iter().filter(|&&seen| seen).count() >= MIN_LOG_DIGITS
+}
+
+/// Continuous coordinate of the middle of the pixel band `start..end`.
+fn band_centre((start, end): (usize, usize)) -> f32 {
+    safe_usize_to_f32(start + end) * 0.5
+}
+
+/// Copy of `image` with grid lines painted over with the background.
+pub fn suppress_grid_lines(image: &ColorImage) -> ColorImage {
+    profile_function!();
+    let [width, height] = image.size;
+    if width < MIN_RUN || height < MIN_RUN {
+        return image.clone();
+    }
+    let background = dominant_color(&image.pixels);
+    let is_ink = |color: Color32| color_distance(color, background) >= INK_CONTRAST;
+    let (row_runs, column_runs) = ruling_lines(image, is_ink);
+
+    let mut out = image.clone();
+    let mut erase = |idx: usize, ruling: bool| {
+        if ruling || !is_ink(image.pixels[idx]) {
+            out.pixels[idx] = background;
+        }
+    };
+    for (y, runs) in row_runs.iter().enumerate() {
+        for &(start, end) in runs {
+            for x in start..end {
+                erase(y * width + x, true);
+                for fringe in [y.wrapping_sub(1), y + 1]
+                    .into_iter()
+                    .filter(|&f| f < height)
+                {
+                    erase(fringe * width + x, false);
+                }
+            }
+        }
+    }
+    for (x, runs) in column_runs.iter().enumerate() {
+        for &(start, end) in runs {
+            for y in start..end {
+                erase(y * width + x, true);
+                for fringe in [x.wrapping_sub(1), x + 1]
+                    .into_iter()
+                    .filter(|&f| f < width)
+                {
+                    erase(y * width + fringe, false);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Long constant-color ink runs of one line, or none when they cover too little of it.
+#[allow(clippy::cast_precision_loss)]
+fn ruling_runs(
+    line: impl Iterator<Item = Color32>,
+    len: usize,
+    is_ink: impl Fn(Color32) -> bool,
+) -> Runs {
+    let mut runs = Vec::new();
+    let mut current: Option<(usize, Color32)> = None;
+    let close = |runs: &mut Runs, start: usize, end: usize| {
+        if end - start >= MIN_RUN {
+            runs.push((start, end));
+        }
+    };
+    for (idx, color) in line.enumerate() {
+        let continues = current.is_some_and(|(_, first)| {
+            is_ink(color) && color_distance(color, first) <= RUN_TOLERANCE
+        });
+        if continues {
+            continue;
+        }
+        if let Some((start, _)) = current.take() {
+            close(&mut runs, start, idx);
+        }
+        if is_ink(color) {
+            current = Some((idx, color));
+        }
+    }
+    if let Some((start, _)) = current {
+        close(&mut runs, start, len);
+    }
+    let covered: usize = runs.iter().map(|(start, end)| end - start).sum();
+    if (covered as f32) < MIN_COVERAGE * len as f32 {
+        runs.clear();
+    }
+    runs
+}
+
+/// Most frequent color, quantized to 4 bits per channel, taken as the chart background.
+pub(super) fn dominant_color(pixels: &[Color32]) -> Color32 {
+    let mut counts: HashMap<[u8; 3], (usize, [u64; 3])> = HashMap::new();
+    for pixel in pixels {
+        let [r, g, b, _] = pixel.to_array();
+        let entry = counts.entry([r >> 4, g >> 4, b >> 4]).or_default();
+        entry.0 += 1;
+        entry.1[0] += u64::from(r);
+        entry.1[1] += u64::from(g);
+        entry.1[2] += u64::from(b);
+    }
+    counts
+        .into_values()
+        .max_by_key(|(count, _)| *count)
+        .map_or(Color32::WHITE, |(count, sums)| {
+            let mean = |sum: u64| u8::try_from(sum / count as u64).unwrap_or(u8::MAX);
+            Color32::from_rgb(mean(sums[0]), mean(sums[1]), mean(sums[2]))
+        })
+}
+
+pub(super) fn color_distance(a: Color32, b: Color32) -> u32 {
+    let [ar, ag, ab, _] = a.to_array();
+    let [br, bg, bb, _] = b.to_array();
+    u32::from(ar.abs_diff(br)) + u32::from(ag.abs_diff(bg)) + u32::from(ab.abs_diff(bb))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: Color32 = Color32::from_rgb(190, 190, 190);
+    const CURVE: Color32 = Color32::from_rgb(200, 30, 30);
+
+    /// White chart with gray rulings every 25 px, one with a faint fringe, and a red diagonal.
+    fn gridded_chart() -> ColorImage {
+        let (width, height) = (200, 150);
+        let mut image = ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+        for y in (10..height).step_by(25) {
+            for x in 0..width {
+                image.pixels[y * width + x] = GRID;
+            }
+        }
+        for x in (10..width).step_by(25) {
+            for y in 0..height {
+                image.pixels[y * width + x] = GRID;
+            }
+        }
+        for x in 0..width {
+            image.pixels[36 * width + x] = Color32::from_gray(235);
+        }
+        for x in 0..width {
+            let y = x * 3 / 4;
+            for dy in 0..2 {
+                image.pixels[(y + dy).min(height - 1) * width + x] = CURVE;
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn rulings_fade_and_the_curve_keeps_its_color() {
+        let image = gridded_chart();
+        let width = image.size[0];
+        let out = suppress_grid_lines(&image);
+        // A ruling pixel well away from the curve.
+        assert_eq!(out.pixels[35 * width + 150], Color32::WHITE);
+        assert_eq!(out.pixels[120 * width + 60], Color32::WHITE);
+        // The faint fringe beside a ruling goes too.
+        assert_eq!(out.pixels[36 * width + 150], Color32::WHITE);
+        // Every curve pixel, including where it crosses a ruling, is untouched.
+        for x in 0..width {
+            let y = x * 3 / 4;
+            assert_eq!(out.pixels[y * width + x], CURVE, "curve at x = {x}");
+        }
+    }
+
+    #[test]
+    fn short_strokes_and_sparse_marks_are_left_alone() {
+        let (width, height) = (200, 60);
+        let mut image = ColorImage::new([width, height], vec![Color32::WHITE; width * height]);
+        // A flat stretch of curve over a third of the width is not a ruling.
+        for x in 20..90 {
+            image.pixels[30 * width + x] = CURVE;
+        }
+        assert_eq!(suppress_grid_lines(&image).pixels, image.pixels);
+    }
+
+    #[test]
+    fn grid_lines_are_found_at_their_centres() {
+        let mut image = gridded_chart();
+        let width = image.size[0];
+        // Thicken one vertical ruling to two pixels.
+        for y in 0..image.size[1] {
+            image.pixels[y * width + 61] = GRID;
+        }
+        let lines = detect_grid_lines(&image);
+        assert_eq!(
+            lines.vertical,
+            [10.5, 35.5, 61.0, 85.5, 110.5, 135.5, 160.5, 185.5]
+        );
+        // The faint anti-aliasing row under the second ruling widens it by a pixel.
+        assert_eq!(lines.horizontal, [10.5, 36.0, 60.5, 85.5, 110.5, 135.5]);
+    }
+
+    #[test]
+    fn grid_spacing_tells_linear_from_log_axes() {
+        // Two decades at 120 px each, every digit ruled, and the same grid running upward.
+        let log: Vec<f32> = [0.0, 1.0]
+            .into_iter()
+            .flat_map(|decade: f32| {
+                (1..=9u8).map(move |d| 120.0f32.mul_add(decade + f32::from(d).log10(), 20.0))
+            })
+            .chain([260.0])
+            .collect();
+        assert_eq!(spacing_scale(&log), Some(ScaleKind::Log10));
+        let upward: Vec<f32> = log.iter().rev().map(|y| 300.0 - y).collect();
+        assert_eq!(spacing_scale(&upward), Some(ScaleKind::Log10));
+        // Only 1, 2 and 5 ruled covers too few digits to be sure.
+        let sparse = [20.0, 56.1, 103.9, 140.0, 176.1, 223.9, 260.0];
+        assert_eq!(spacing_scale(&sparse), None);
+
+        let even = [10.5, 35.5, 61.0, 85.5, 135.5, 160.5];
+        assert_eq!(spacing_scale(&even), Some(ScaleKind::Linear));
+        assert_eq!(spacing_scale(&even[..3]), None);
+        assert_eq!(spacing_scale(&[0.0, 10.0, 30.0, 33.0, 70.0]), None);
+    }
+}
@@ -1,12 +1,14 @@
-use egui::{Color32, ColorImage, Pos2, pos2};
+use egui::{Color32, ColorImage, Pos2, Rect, pos2};
 use rayon::prelude::*;
 use std::simd::num::SimdFloat;
 use std::simd::{Simd, StdFloat};
 
-use super::behavior::SnapBehavior;
+use super::behavior::{SnapBehavior, SnapSample};
 use super::color::{color_luminance, color_similarity_value};
-use super::search::{refine_snap_position, search_in_level};
-use crate::util::{clamp_index, u32_to_f32};
+use super::mask::CurveMask;
+use super::search::{bridge_gap, refine_snap_position, search_in_level};
+use crate::profiling::profile_function;
+use crate::util::{clamp_index, safe_usize_to_f32, saturating_f32_to_i32, u32_to_f32};
 
 const SNAP_MAP_SIMD_LANES: usize = 8;
 const SNAP_BASE_PAR_CHUNK: usize = 4096;
@@ -14,6 +16,8 @@ const LUMA_R_COEFF: f32 = 0.2126;
 const LUMA_G_COEFF: f32 = 0.7152;
 const LUMA_B_COEFF: f32 = 0.0722;
 type F32x8 = Simd<f32, SNAP_MAP_SIMD_LANES>;
+/// Pixels around a snapped point searched for the contrast of its stroke.
+const CONFIDENCE_REACH: i32 = 2;
 
 #[allow(clippy::suboptimal_flops)]
 fn compute_luma_similarity_chunk(
@@ -100,6 +104,70 @@ fn downsample_cell_checked(
     }
 }
 
+/// 2×2 box average of `values` into a plane of `dst` size; edge cells average what exists.
+#[allow(clippy::cast_precision_loss)]
+fn downsample_plane(values: &[f32], src: [usize; 2], dst: [usize; 2]) -> Vec<f32> {
+    let mut out = vec![0.0; dst[0] * dst[1]];
+    for (y, row) in out.chunks_mut(dst[0]).enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            let mut count = 0usize;
+            for sy in (y * 2)..(y * 2 + 2).min(src[1]) {
+                for sx in (x * 2)..(x * 2 + 2).min(src[0]) {
+                    sum += values[sy * src[0] + sx];
+                    count += 1;
+                }
+            }
+            if count > 0 {
+                *cell = sum / count as f32;
+            }
+        }
+    }
+    out
+}
+
+/// Pixels of a `size` plane whose centres lie outside `polygon`, by even-odd scanline fill.
+fn outside_polygon(polygon: &[Pos2], size: [usize; 2]) -> Vec<bool> {
+    let [width, height] = size;
+    let mut outside = vec![true; width * height];
+    let mut crossings = Vec::new();
+    for (y, row) in outside.chunks_mut(width).enumerate() {
+        let yc = safe_usize_to_f32(y) + 0.5;
+        crossings.clear();
+        for (i, a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            if (a.y <= yc) != (b.y <= yc) {
+                crossings.push((b.x - a.x).mul_add((yc - a.y) / (b.y - a.y), a.x));
+            }
+        }
+        crossings.sort_by(f32::total_cmp);
+        for span in crossings.chunks_exact(2) {
+            // Pixel x is inside when its centre x + 0.5 lies within the span.
+            let clip = |v: f32| clamp_index(saturating_f32_to_i32(v), width + 1);
+            let (x0, x1) = (
+                clip((span[0] - 0.5).ceil()),
+                clip((span[1] - 0.5).floor() + 1.0),
+            );
+            if x0 < x1 {
+                row[x0..x1].fill(false);
+            }
+        }
+    }
+    outside
+}
+
+/// 2×2 AND of `values` into a plane of `dst` size; edge cells use what exists.
+fn downsample_all(values: &[bool], src: [usize; 2], dst: [usize; 2]) -> Vec<bool> {
+    let mut out = vec![false; dst[0] * dst[1]];
+    for (y, row) in out.chunks_mut(dst[0]).enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            *cell = ((y * 2)..(y * 2 + 2).min(src[1]))
+                .all(|sy| ((x * 2)..(x * 2 + 2).min(src[0])).all(|sx| values[sy * src[0] + sx]));
+        }
+    }
+    out
+}
+
 #[allow(clippy::suboptimal_flops)]
 fn compute_gradient_row(row: &mut [f32], lum: &[f32], row_base: usize, width: usize) {
     let max_gradient = F32x8::splat(255.0);
@@ -131,12 +199,20 @@ pub(super) struct SnapMapLevel {
     scale: u32,
     gradient: Vec<f32>,
     color_similarity: Vec<f32>,
+    /// Curve probability from a segmentation mask; empty when none is attached.
+    curve_probability: Vec<f32>,
+    /// Pixels the search skips, such as tick labels; empty when nothing is excluded.
+    excluded: Vec<bool>,
 }
 
 /// Cached multi-resolution maps for fast snapping searches.
 #[derive(Debug, Clone)]
 pub struct SnapMapCache {
     levels: Vec<SnapMapLevel>,
+    /// Regions last passed to [`Self::set_exclusions`].
+    exclusions: Vec<Rect>,
+    /// Polygon the search is confined to; empty when the whole image is searched.
+    search_area: Vec<Pos2>,
 }
 
 impl SnapMapCache {
@@ -144,6 +220,7 @@ impl SnapMapCache {
     ///
     /// Returns `None` when the image is empty.
     pub fn build(image: &ColorImage, target: Color32, tolerance: f32) -> Option<Self> {
+        profile_function!();
         if image.size[0] == 0 || image.size[1] == 0 {
             return None;
         }
@@ -159,7 +236,11 @@ impl SnapMapCache {
                 break;
             }
         }
-        Some(Self { levels })
+        Some(Self {
+            levels,
+            exclusions: Vec::new(),
+            search_area: Vec::new(),
+        })
     }
 
     /// Find the best snap candidate near `pixel_hint` within `radius`.
@@ -170,7 +251,7 @@ impl SnapMapCache {
         &self,
         pixel_hint: Pos2,
         radius: f32,
-        behavior: SnapBehavior,
+        behavior: &dyn SnapBehavior,
     ) -> Option<Pos2> {
         if self.levels.is_empty() {
             return None;
@@ -190,11 +271,199 @@ impl SnapMapCache {
         let refined_candidate =
             search_in_level(base_level, coarse_base_pos, refine_radius, behavior)
                 .map_or(coarse_base_pos, |cand| cand.pos);
-        Some(refine_snap_position(
-            base_level,
-            refined_candidate,
-            behavior,
-        ))
+        let snapped = refine_snap_position(base_level, refined_candidate, behavior);
+        // The coarse fallback can land on an excluded pixel when the base level found nothing.
+        let (x, y) = (snapped.x.round(), snapped.y.round());
+        (!base_level.is_excluded(saturating_f32_to_i32(x), saturating_f32_to_i32(y)))
+            .then_some(snapped)
+    }
+
+    /// Like [`Self::find_point`], but a cursor in the gap of a dashed or dotted stroke lands
+    /// on the line through the neighbouring dashes instead of finding nothing.
+    pub fn find_point_across_gaps(
+        &self,
+        pixel_hint: Pos2,
+        radius: f32,
+        behavior: &dyn SnapBehavior,
+    ) -> Option<Pos2> {
+        self.find_point(pixel_hint, radius, behavior).or_else(|| {
+            let base_level = self.levels.first()?;
+            bridge_gap(base_level, pixel_hint, radius.max(1.0), behavior)
+        })
+    }
+
+    /// How surely a point snapped to `pixel` lies on a curve, from 0 to 1.
+    ///
+    /// The geometric mean of the feature strength at the pixel and the strongest gradient
+    /// within [`CONFIDENCE_REACH`] of it, so a strong feature on a faint stroke still scores
+    /// low. Looking around the pixel lets centerline snaps, which sit where the gradient is
+    /// flat, see the edges of their stroke.
+    pub fn confidence_at(&self, pixel: Pos2, behavior: &dyn SnapBehavior) -> f32 {
+        let Some(base) = self.levels.first() else {
+            return 0.0;
+        };
+        let x = saturating_f32_to_i32(pixel.x.round());
+        let y = saturating_f32_to_i32(pixel.y.round());
+        let strength = behavior.feature_strength(&SnapSample::new(base, x, y)) / 255.0;
+        let contrast = (-CONFIDENCE_REACH..=CONFIDENCE_REACH)
+            .flat_map(|dy| (-CONFIDENCE_REACH..=CONFIDENCE_REACH).map(move |dx| (dx, dy)))
+            .map(|(dx, dy)| base.gradient_at(x + dx, y + dy))
+            .fold(0.0, f32::max)
+            / 255.0;
+        (strength.clamp(0.0, 1.0) * contrast.clamp(0.0, 1.0)).sqrt()
+    }
+
+    /// Serialize every level as little-endian `u32` headers followed by the raw maps.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let payload: usize = self.levels.iter().map(|l| 12 + l.gradient.len() * 8).sum();
+        let mut out = Vec::with_capacity(4 + payload);
+        let to_u32 = |value: usize| u32::try_from(value).unwrap_or(u32::MAX);
+        out.extend_from_slice(&to_u32(self.levels.len()).to_le_bytes());
+        for level in &self.levels {
+            out.extend_from_slice(&to_u32(level.size[0]).to_le_bytes());
+            out.extend_from_slice(&to_u32(level.size[1]).to_le_bytes());
+            out.extend_from_slice(&level.scale.to_le_bytes());
+            for value in level.gradient.iter().chain(&level.color_similarity) {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// Inverse of [`Self::to_bytes`]; `None` for truncated or inconsistent data.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        fn take_u32(bytes: &mut &[u8]) -> Option<u32> {
+            let (head, rest) = bytes.split_first_chunk::<4>()?;
+            *bytes = rest;
+            Some(u32::from_le_bytes(*head))
+        }
+        fn take_f32s(bytes: &mut &[u8], len: usize) -> Option<Vec<f32>> {
+            let (head, rest) = bytes.split_at_checked(len.checked_mul(4)?)?;
+            *bytes = rest;
+            Some(
+                head.chunks_exact(4)
+                    .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                    .collect(),
+            )
+        }
+
+        let mut rest = bytes;
+        let count = take_u32(&mut rest)?;
+        let mut levels = Vec::new();
+        for _ in 0..count {
+            let width = usize::try_from(take_u32(&mut rest)?).ok()?;
+            let height = usize::try_from(take_u32(&mut rest)?).ok()?;
+            let scale = take_u32(&mut rest)?;
+            let len = width.checked_mul(height)?;
+            let gradient = take_f32s(&mut rest, len)?;
+            let color_similarity = take_f32s(&mut rest, len)?;
+            levels.push(SnapMapLevel {
+                size: [width, height],
+                scale,
+                gradient,
+                color_similarity,
+                curve_probability: Vec::new(),
+                excluded: Vec::new(),
+            });
+        }
+        (!levels.is_empty() && rest.is_empty()).then_some(Self {
+            levels,
+            exclusions: Vec::new(),
+            search_area: Vec::new(),
+        })
+    }
+
+    /// Attach a curve-probability mask to every level, or drop it with `None`.
+    ///
+    /// A mask whose size differs from the base level is ignored; returns whether it was attached.
+    /// The mask is not part of [`Self::to_bytes`], since it does not derive from the pixels.
+    pub fn set_curve_mask(&mut self, mask: Option<&CurveMask>) -> bool {
+        let Some(mask) = mask else {
+            for level in &mut self.levels {
+                level.curve_probability = Vec::new();
+            }
+            return true;
+        };
+        if self
+            .levels
+            .first()
+            .is_none_or(|base| base.size != mask.size())
+        {
+            return false;
+        }
+        let mut values = mask.values().to_vec();
+        for idx in 0..self.levels.len() {
+            if idx > 0 {
+                values =
+                    downsample_plane(&values, self.levels[idx - 1].size, self.levels[idx].size);
+            }
+            self.levels[idx].curve_probability.clone_from(&values);
+        }
+        true
+    }
+
+    /// Exclude pixel regions (such as detected text) from the search on every level.
+    ///
+    /// A coarse cell is excluded only when all the pixels it covers are, so a
+    /// curve running past a label can still be found and then refined at full
+    /// resolution. An empty slice clears the exclusions; like the curve mask
+    /// they are not part of [`Self::to_bytes`].
+    pub fn set_exclusions(&mut self, regions: &[Rect]) {
+        self.exclusions = regions.to_vec();
+        self.rebuild_excluded();
+    }
+
+    /// Confine the search to the image polygon `vertices`, or search everywhere again with an
+    /// empty slice (or one with fewer than three vertices).
+    ///
+    /// Pixels whose centres fall outside the polygon are excluded just like the regions of
+    /// [`Self::set_exclusions`], and the two combine.
+    pub fn set_search_area(&mut self, vertices: &[Pos2]) {
+        self.search_area = if vertices.len() >= 3 {
+            vertices.to_vec()
+        } else {
+            Vec::new()
+        };
+        self.rebuild_excluded();
+    }
+
+    fn rebuild_excluded(&mut self) {
+        let Some(base) = self.levels.first() else {
+            return;
+        };
+        if self.exclusions.is_empty() && self.search_area.is_empty() {
+            for level in &mut self.levels {
+                level.excluded = Vec::new();
+            }
+            return;
+        }
+        let [width, height] = base.size;
+        let mut excluded = if self.search_area.is_empty() {
+            vec![false; width * height]
+        } else {
+            outside_polygon(&self.search_area, base.size)
+        };
+        let clip = |v: f32, max: usize| clamp_index(saturating_f32_to_i32(v), max + 1);
+        for region in &self.exclusions {
+            let (x0, x1) = (
+                clip(region.min.x.floor(), width),
+                clip(region.max.x.ceil(), width),
+            );
+            let (y0, y1) = (
+                clip(region.min.y.floor(), height),
+                clip(region.max.y.ceil(), height),
+            );
+            for row in excluded.chunks_mut(width).take(y1).skip(y0) {
+                row[x0..x1].fill(true);
+            }
+        }
+        for idx in 0..self.levels.len() {
+            if idx > 0 {
+                let (src, dst) = (self.levels[idx - 1].size, self.levels[idx].size);
+                excluded = downsample_all(&excluded, src, dst);
+            }
+            self.levels[idx].excluded.clone_from(&excluded);
+        }
     }
 
     fn level_for_radius(&self, radius: f32) -> (usize, &SnapMapLevel) {
@@ -252,6 +521,8 @@ impl SnapMapLevel {
             scale: 1,
             gradient,
             color_similarity,
+            curve_probability: Vec::new(),
+            excluded: Vec::new(),
         }
     }
 
@@ -347,6 +618,8 @@ impl SnapMapLevel {
             scale: prev.scale * 2,
             gradient,
             color_similarity,
+            curve_probability: Vec::new(),
+            excluded: Vec::new(),
         })
     }
 
@@ -359,6 +632,24 @@ impl SnapMapLevel {
         self.gradient[yi * self.size[0] + xi]
     }
 
+    pub(super) fn curve_probability_at(&self, x: i32, y: i32) -> f32 {
+        if self.curve_probability.is_empty() {
+            return 0.0;
+        }
+        let xi = clamp_index(x, self.size[0]);
+        let yi = clamp_index(y, self.size[1]);
+        self.curve_probability[yi * self.size[0] + xi]
+    }
+
+    pub(super) fn is_excluded(&self, x: i32, y: i32) -> bool {
+        if self.excluded.is_empty() {
+            return false;
+        }
+        let xi = clamp_index(x, self.size[0]);
+        let yi = clamp_index(y, self.size[1]);
+        self.excluded[yi * self.size[0] + xi]
+    }
+
     pub(super) fn color_similarity_at(&self, x: i32, y: i32) -> f32 {
         if self.color_similarity.is_empty() {
             return 0.0;
@@ -434,6 +725,8 @@ mod tests {
             scale: 1,
             gradient,
             color_similarity,
+            curve_probability: Vec::new(),
+            excluded: Vec::new(),
         }
     }
 
@@ -461,6 +754,8 @@ mod tests {
             scale: prev.scale * 2,
             gradient,
             color_similarity,
+            curve_probability: Vec::new(),
+            excluded: Vec::new(),
         })
     }
 
@@ -506,4 +801,138 @@ mod tests {
             1.0e-6
         ));
     }
+
+    #[test]
+    fn excluded_text_does_not_attract_snaps() {
+        use crate::snap::{ContrastScorer, SnapFeatureSource, SnapThresholdKind};
+        use egui::pos2;
+
+        let (w, h) = (120, 60);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        crate::ocr::stamp_text(&mut image, "42", [20, 20], 2, Color32::BLACK);
+        for y in 0..h {
+            image.pixels[y * w + 80] = Color32::BLACK;
+        }
+        let scorer = ContrastScorer {
+            feature_source: SnapFeatureSource::ColorMatch,
+            threshold_kind: SnapThresholdKind::Score,
+            threshold: 128.0,
+        };
+        let mut maps = SnapMapCache::build(&image, Color32::BLACK, 40.0).expect("snap maps");
+        assert!(maps.find_point(pos2(26.0, 26.0), 8.0, &scorer).is_some());
+
+        maps.set_exclusions(&crate::ocr::detect_text_regions(&image));
+        assert!(maps.find_point(pos2(26.0, 26.0), 8.0, &scorer).is_none());
+        let line = maps
+            .find_point(pos2(76.0, 30.0), 8.0, &scorer)
+            .expect("curve stays snappable");
+        assert!((line.x - 80.0).abs() < 0.6, "x = {}", line.x);
+
+        maps.set_exclusions(&[]);
+        assert!(maps.find_point(pos2(26.0, 26.0), 8.0, &scorer).is_some());
+    }
+
+    #[test]
+    fn search_area_confines_snaps_to_the_polygon() {
+        use crate::snap::{ContrastScorer, SnapFeatureSource, SnapThresholdKind};
+        use egui::pos2;
+
+        let (w, h) = (120, 60);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        for y in 0..h {
+            image.pixels[y * w + 30] = Color32::BLACK;
+            image.pixels[y * w + 90] = Color32::BLACK;
+        }
+        let scorer = ContrastScorer {
+            feature_source: SnapFeatureSource::ColorMatch,
+            threshold_kind: SnapThresholdKind::Score,
+            threshold: 128.0,
+        };
+        let mut maps = SnapMapCache::build(&image, Color32::BLACK, 40.0).expect("snap maps");
+        assert!(maps.find_point(pos2(88.0, 30.0), 8.0, &scorer).is_some());
+
+        // A triangle over the left line only.
+        maps.set_search_area(&[pos2(10.0, 5.0), pos2(60.0, 30.0), pos2(10.0, 55.0)]);
+        let left = maps
+            .find_point(pos2(27.0, 30.0), 8.0, &scorer)
+            .expect("line inside the area");
+        assert!((left.x - 30.0).abs() < 0.6, "x = {}", left.x);
+        assert!(maps.find_point(pos2(88.0, 30.0), 8.0, &scorer).is_none());
+        assert!(maps.find_point(pos2(30.0, 2.0), 2.0, &scorer).is_none());
+
+        maps.set_exclusions(&[Rect::from_min_max(pos2(20.0, 20.0), pos2(40.0, 40.0))]);
+        assert!(maps.find_point(pos2(30.0, 30.0), 4.0, &scorer).is_none());
+        maps.set_search_area(&[]);
+        assert!(maps.find_point(pos2(88.0, 30.0), 8.0, &scorer).is_some());
+        assert!(maps.find_point(pos2(30.0, 30.0), 4.0, &scorer).is_none());
+    }
+
+    #[test]
+    fn confidence_is_higher_on_a_dark_stroke_than_on_a_faint_one() {
+        use crate::snap::CenterlineScorer;
+        use egui::pos2;
+
+        let (w, h) = (80, 40);
+        let faint = Color32::from_gray(215);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        for y in 0..h {
+            for dx in 0..3 {
+                image.pixels[y * w + 19 + dx] = Color32::BLACK;
+                image.pixels[y * w + 59 + dx] = faint;
+            }
+        }
+        let scorer = CenterlineScorer { threshold: 60.0 };
+        let dark = SnapMapCache::build(&image, Color32::BLACK, 40.0).expect("snap maps");
+        let light = SnapMapCache::build(&image, faint, 40.0).expect("snap maps");
+
+        let on_dark = dark.confidence_at(pos2(20.0, 20.0), &scorer);
+        let on_faint = light.confidence_at(pos2(60.0, 20.0), &scorer);
+        assert!(on_dark > 0.9, "dark stroke: {on_dark}");
+        assert!(on_faint < 0.5, "faint stroke: {on_faint}");
+        assert!(dark.confidence_at(pos2(40.0, 20.0), &scorer) < 1e-3);
+    }
+
+    #[test]
+    fn gaps_of_a_dashed_stroke_are_bridged_along_the_line() {
+        use crate::snap::CenterlineScorer;
+        use egui::pos2;
+
+        // Dashes 6 px long with 10 px gaps along y = 20 + x / 2, three pixels thick.
+        let (w, h) = (160, 100);
+        let blue = Color32::from_rgb(30, 60, 200);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        for x in (0..120).filter(|x| x % 16 < 6) {
+            let y = 20 + x / 2;
+            for dy in 0..3 {
+                image.pixels[(y + dy - 1) * w + x] = blue;
+            }
+        }
+        let line_y = |x: f32| 0.5f32.mul_add(x, 20.0);
+        let scorer = CenterlineScorer { threshold: 60.0 };
+        let maps = SnapMapCache::build(&image, blue, 40.0).expect("snap maps");
+
+        let in_gap = pos2(42.5, 43.0);
+        assert!(maps.find_point(in_gap, 4.0, &scorer).is_none());
+        let bridged = maps
+            .find_point_across_gaps(in_gap, 4.0, &scorer)
+            .expect("gap bridged");
+        assert!(
+            (bridged.y - line_y(bridged.x)).abs() < 0.8,
+            "{bridged:?} off the line"
+        );
+        assert!(bridged.distance(in_gap) < 2.5, "{bridged:?}");
+
+        // Beyond the last dash there is nothing to bridge to.
+        let past_end = pos2(127.0, line_y(127.0));
+        assert!(
+            maps.find_point_across_gaps(past_end, 4.0, &scorer)
+                .is_none()
+        );
+        // On a dash the plain search wins.
+        let on_dash = pos2(34.0, 38.0);
+        assert_eq!(
+            maps.find_point_across_gaps(on_dash, 4.0, &scorer),
+            maps.find_point(on_dash, 4.0, &scorer)
+        );
+    }
 }
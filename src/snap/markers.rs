@@ -0,0 +1,227 @@
+//! Scatter markers: 8-connected blobs of the marker color shaped like a sample marker.
+
+use super::color::color_similarity_value;
+use crate::util::safe_usize_to_f32;
+use egui::{Color32, ColorImage, Pos2, pos2};
+
+/// Largest ratio between a marker's bounding-box side and the sample's, either way.
+const SIZE_RATIO: f32 = 1.6;
+/// How far the share of its box a marker covers may differ from the sample's.
+const FILL_TOLERANCE: f32 = 0.2;
+/// Pixels around a click searched for the marker color when the click misses the marker.
+const SAMPLE_REACH: usize = 3;
+
+/// Size and fill of a sample marker that detected markers are compared against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarkerShape {
+    pub width: usize,
+    pub height: usize,
+    /// Share of the bounding box the marker pixels cover: about 0.8 for a disc, 1 for a square.
+    pub fill: f32,
+}
+
+impl MarkerShape {
+    fn matches(self, other: Self) -> bool {
+        let side = |a: usize, b: usize| {
+            let (a, b) = (safe_usize_to_f32(a), safe_usize_to_f32(b));
+            a.max(b) <= a.min(b) * SIZE_RATIO
+        };
+        side(self.width, other.width)
+            && side(self.height, other.height)
+            && (self.fill - other.fill).abs() <= FILL_TOLERANCE
+    }
+}
+
+struct Blob {
+    min: [usize; 2],
+    max: [usize; 2],
+    count: usize,
+    sum: [usize; 2],
+}
+
+impl Blob {
+    fn shape(&self) -> MarkerShape {
+        let width = self.max[0] - self.min[0] + 1;
+        let height = self.max[1] - self.min[1] + 1;
+        MarkerShape {
+            width,
+            height,
+            fill: safe_usize_to_f32(self.count) / safe_usize_to_f32(width * height),
+        }
+    }
+
+    /// Mean of the pixel centres, which sit half a pixel into each pixel.
+    fn centroid(&self) -> Pos2 {
+        let count = safe_usize_to_f32(self.count);
+        pos2(
+            safe_usize_to_f32(self.sum[0]) / count + 0.5,
+            safe_usize_to_f32(self.sum[1]) / count + 0.5,
+        )
+    }
+}
+
+/// Pixels within `tolerance` of `target`, row by row.
+fn color_mask(image: &ColorImage, target: Color32, tolerance: f32) -> Vec<bool> {
+    image
+        .pixels
+        .iter()
+        .map(|&color| color_similarity_value(color, target, tolerance) > 0.0)
+        .collect()
+}
+
+/// The blob of `mask` holding `start`, marking its pixels in `seen`.
+fn flood(mask: &[bool], size: [usize; 2], seen: &mut [bool], start: [usize; 2]) -> Blob {
+    let [width, height] = size;
+    let mut blob = Blob {
+        min: start,
+        max: start,
+        count: 0,
+        sum: [0, 0],
+    };
+    let mut stack = vec![start];
+    seen[start[1] * width + start[0]] = true;
+    while let Some([x, y]) = stack.pop() {
+        blob.min = [blob.min[0].min(x), blob.min[1].min(y)];
+        blob.max = [blob.max[0].max(x), blob.max[1].max(y)];
+        blob.count += 1;
+        blob.sum = [blob.sum[0] + x, blob.sum[1] + y];
+        for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+            for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                let idx = ny * width + nx;
+                if mask[idx] && !seen[idx] {
+                    seen[idx] = true;
+                    stack.push([nx, ny]);
+                }
+            }
+        }
+    }
+    blob
+}
+
+/// Even-odd test of `point` against `polygon`.
+pub fn inside_polygon(polygon: &[Pos2], point: Pos2) -> bool {
+    let mut inside = false;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y <= point.y) != (b.y <= point.y)
+            && point.x < (b.x - a.x).mul_add((point.y - a.y) / (b.y - a.y), a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Shape of the marker of color `target` at pixel `(x, y)`, or within a few pixels of it.
+pub fn sample_marker(
+    image: &ColorImage,
+    x: usize,
+    y: usize,
+    target: Color32,
+    tolerance: f32,
+) -> Option<MarkerShape> {
+    let [width, height] = image.size;
+    if x >= width || y >= height {
+        return None;
+    }
+    let mask = color_mask(image, target, tolerance);
+    let start = (0..=SAMPLE_REACH).find_map(|reach| {
+        let rows = y.saturating_sub(reach)..=(y + reach).min(height - 1);
+        rows.flat_map(|ny| {
+            (x.saturating_sub(reach)..=(x + reach).min(width - 1)).map(move |nx| [nx, ny])
+        })
+        .find(|&[nx, ny]| mask[ny * width + nx])
+    })?;
+    let mut seen = vec![false; mask.len()];
+    Some(flood(&mask, image.size, &mut seen, start).shape())
+}
+
+/// Centroids of the markers of color `target` shaped like `shape`, sorted by x.
+///
+/// With three or more `area` vertices only markers centred inside that polygon count. Markers
+/// that touch each other merge into a larger blob and are left out.
+pub fn detect_markers(
+    image: &ColorImage,
+    target: Color32,
+    tolerance: f32,
+    shape: MarkerShape,
+    area: &[Pos2],
+) -> Vec<Pos2> {
+    let width = image.size[0];
+    let mask = color_mask(image, target, tolerance);
+    let mut seen = vec![false; mask.len()];
+    let mut centroids = Vec::new();
+    for (idx, &on) in mask.iter().enumerate() {
+        if !on || seen[idx] {
+            continue;
+        }
+        let blob = flood(&mask, image.size, &mut seen, [idx % width, idx / width]);
+        let centroid = blob.centroid();
+        if blob.shape().matches(shape) && (area.len() < 3 || inside_polygon(area, centroid)) {
+            centroids.push(centroid);
+        }
+    }
+    centroids.sort_by(|a, b| a.x.total_cmp(&b.x));
+    centroids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamp_disc(image: &mut ColorImage, center: [usize; 2], radius: usize, color: Color32) {
+        let width = image.size[0];
+        let reach = radius * radius;
+        for y in center[1] - radius..=center[1] + radius {
+            for x in center[0] - radius..=center[0] + radius {
+                let (dx, dy) = (x.abs_diff(center[0]), y.abs_diff(center[1]));
+                if dx * dx + dy * dy <= reach {
+                    image.pixels[y * width + x] = color;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn markers_like_the_sample_are_found_at_their_centres() {
+        let (w, h) = (160, 80);
+        let red = Color32::from_rgb(220, 40, 40);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        for center in [[20, 20], [60, 50], [100, 30], [140, 60]] {
+            stamp_disc(&mut image, center, 3, red);
+        }
+        // A legend swatch line and a lone stray pixel in the same color.
+        for x in 10..40 {
+            image.pixels[74 * w + x] = red;
+        }
+        image.pixels[5 * w + 150] = red;
+        // A blue marker of the same shape.
+        stamp_disc(&mut image, [80, 10], 3, Color32::from_rgb(40, 40, 220));
+
+        let shape = sample_marker(&image, 58, 51, red, 40.0).expect("sample marker");
+        assert_eq!((shape.width, shape.height), (7, 7));
+        let found = detect_markers(&image, red, 40.0, shape, &[]);
+        let expected = [
+            pos2(20.5, 20.5),
+            pos2(60.5, 50.5),
+            pos2(100.5, 30.5),
+            pos2(140.5, 60.5),
+        ];
+        assert_eq!(found.len(), expected.len(), "found {found:?}");
+        for (got, want) in found.iter().zip(expected) {
+            assert!(got.distance(want) < 1e-3, "{got:?} != {want:?}");
+        }
+
+        let left_half = [
+            pos2(0.0, 0.0),
+            pos2(80.0, 0.0),
+            pos2(80.0, 80.0),
+            pos2(0.0, 80.0),
+        ];
+        assert_eq!(
+            detect_markers(&image, red, 40.0, shape, &left_half).len(),
+            2
+        );
+        assert!(sample_marker(&image, 120, 10, red, 40.0).is_none());
+    }
+}
@@ -0,0 +1,103 @@
+//! Curve-probability masks produced by a segmentation model.
+//!
+//! The mask is a grayscale image aligned with the chart: white where the model
+//! sees curve ink, black elsewhere. It becomes an extra snap feature plane.
+//!
+//! Masks come from an image another program wrote, or from an ONNX model run in-process
+//! by `segmentation` builds (see `segment.rs`).
+
+use anyhow::Context as _;
+use image::imageops::{self, FilterType};
+use std::path::Path;
+
+/// Per-pixel curve probability (0–1), row-major.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveMask {
+    size: [usize; 2],
+    values: Vec<f32>,
+}
+
+impl CurveMask {
+    /// Build from 8-bit gray values; `None` when the length does not match `size`.
+    pub fn from_gray(size: [usize; 2], gray: &[u8]) -> Option<Self> {
+        (gray.len() == size[0].checked_mul(size[1])?).then(|| Self {
+            size,
+            values: gray.iter().map(|&v| f32::from(v) / 255.0).collect(),
+        })
+    }
+
+    /// Decode a mask image and stretch it to `size` (model outputs are often smaller).
+    pub fn load(path: &Path, size: [usize; 2]) -> anyhow::Result<Self> {
+        let decoded = image::open(path)
+            .with_context(|| format!("Failed to decode mask {}", path.display()))?
+            .to_luma8();
+        Self::stretched(&decoded, size)
+    }
+
+    /// Stretch probabilities (0–1) of a `source`-sized model output to `size`.
+    #[cfg_attr(not(feature = "segmentation"), allow(dead_code))]
+    pub fn from_probabilities(
+        source: [usize; 2],
+        values: &[f32],
+        size: [usize; 2],
+    ) -> anyhow::Result<Self> {
+        let width = u32::try_from(source[0]).context("Model output too wide")?;
+        let height = u32::try_from(source[1]).context("Model output too tall")?;
+        // Clamped to 0–255 before the cast, so nothing truncates.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let gray = values
+            .iter()
+            .map(|&p| (p.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect();
+        let gray = image::GrayImage::from_raw(width, height, gray)
+            .context("Model output size mismatch")?;
+        Self::stretched(&gray, size)
+    }
+
+    fn stretched(gray: &image::GrayImage, size: [usize; 2]) -> anyhow::Result<Self> {
+        let width = u32::try_from(size[0]).context("Image too wide for a mask")?;
+        let height = u32::try_from(size[1]).context("Image too tall for a mask")?;
+        if gray.dimensions() == (width, height) {
+            return Self::from_gray(size, gray.as_raw()).context("Mask size mismatch");
+        }
+        let gray = imageops::resize(gray, width, height, FilterType::Triangle);
+        Self::from_gray(size, gray.as_raw()).context("Mask size mismatch")
+    }
+
+    pub const fn size(&self) -> [usize; 2] {
+        self.size
+    }
+
+    pub(super) fn values(&self) -> &[f32] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_stretches_a_smaller_mask_to_the_image() {
+        let dir = std::env::temp_dir().join(format!("curcat_mask_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("mask.png");
+        // Left half curve, right half background.
+        let small =
+            image::GrayImage::from_fn(4, 2, |x, _| image::Luma([if x < 2 { 255 } else { 0 }]));
+        small.save(&path).expect("write mask");
+
+        let mask = CurveMask::load(&path, [8, 4]).expect("load mask");
+        assert_eq!(mask.size(), [8, 4]);
+        assert!((mask.values()[0] - 1.0).abs() < 1e-6);
+        assert!(mask.values()[7].abs() < 1e-6);
+        assert!(CurveMask::from_gray([3, 3], &[0; 8]).is_none());
+
+        let probs = CurveMask::from_probabilities([2, 1], &[1.0, 0.0], [4, 2]).expect("stretch");
+        assert_eq!(probs.size(), [4, 2]);
+        assert!((probs.values()[0] - 1.0).abs() < 1e-6);
+        assert!(probs.values()[3].abs() < 1e-6);
+        assert!(CurveMask::from_probabilities([2, 2], &[0.5; 3], [4, 4]).is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
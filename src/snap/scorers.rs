@@ -0,0 +1,184 @@
+//! Registry of snap scorers beyond the built-in contrast and centerline modes.
+//!
+//! Adding a strategy takes a [`SnapBehavior`] impl and an entry in
+//! [`SNAP_SCORERS`]; the point input panel lists every entry with a threshold
+//! slider, and the search loop only sees the trait.
+
+use std::ops::RangeInclusive;
+
+use super::behavior::{SnapBehavior, SnapSample};
+
+/// A selectable snap scorer.
+pub struct SnapScorerEntry {
+    /// Stable identifier; also the key for the UI label.
+    pub id: &'static str,
+    pub threshold_range: RangeInclusive<f32>,
+    pub default_threshold: f32,
+    /// Whether the scorer reads [`SnapSample::curve_probability`] and needs a mask loaded.
+    pub needs_curve_mask: bool,
+    pub build: fn(threshold: f32) -> Box<dyn SnapBehavior>,
+}
+
+/// Registered scorers in UI order.
+pub const SNAP_SCORERS: &[SnapScorerEntry] = &[
+    SnapScorerEntry {
+        id: "ridge",
+        threshold_range: 0.0..=255.0,
+        default_threshold: 60.0,
+        needs_curve_mask: false,
+        build: |threshold| Box::new(RidgeScorer { threshold }),
+    },
+    SnapScorerEntry {
+        id: "mask",
+        threshold_range: 0.0..=255.0,
+        default_threshold: 128.0,
+        needs_curve_mask: true,
+        build: |threshold| Box::new(MaskScorer { threshold }),
+    },
+];
+
+/// Second-difference steps of the ridge test; wider strokes need longer steps.
+const RIDGE_STEPS: [i32; 3] = [1, 2, 3];
+
+/// Snap to thin lines of the curve color: pixels where the color mask peaks
+/// across the stroke, found from the Hessian of the color similarity.
+///
+/// Unlike the centerline scorer it does not need a flat stroke interior, so
+/// it holds on hairlines and anti-aliased strokes one or two pixels wide.
+#[derive(Debug, Clone, Copy)]
+pub struct RidgeScorer {
+    pub threshold: f32,
+}
+
+impl RidgeScorer {
+    /// Curvature across the ridge at `step`, where 1.0 is an ideal peak.
+    fn ridge_response(sample: &SnapSample<'_>, step: i32) -> f32 {
+        let center = sample.color_similarity();
+        let sxx = 2.0f32.mul_add(
+            -center,
+            sample.color_similarity_at(step, 0) + sample.color_similarity_at(-step, 0),
+        );
+        let syy = 2.0f32.mul_add(
+            -center,
+            sample.color_similarity_at(0, step) + sample.color_similarity_at(0, -step),
+        );
+        let sxy = (sample.color_similarity_at(step, step)
+            - sample.color_similarity_at(step, -step)
+            - sample.color_similarity_at(-step, step)
+            + sample.color_similarity_at(-step, -step))
+            * 0.25;
+        let half_diff = (sxx - syy) * 0.5;
+        let lambda_min = (sxx + syy).mul_add(0.5, -half_diff.hypot(sxy));
+        (-lambda_min * 0.5).max(0.0)
+    }
+}
+
+impl SnapBehavior for RidgeScorer {
+    fn feature_strength(&self, sample: &SnapSample<'_>) -> f32 {
+        let similarity = sample.color_similarity();
+        if similarity <= f32::EPSILON {
+            return 0.0;
+        }
+        let ridge = RIDGE_STEPS
+            .iter()
+            .map(|&step| Self::ridge_response(sample, step))
+            .fold(0.0f32, f32::max)
+            .min(1.0);
+        255.0 * similarity * ridge
+    }
+
+    fn threshold_passes(&self, _sample: &SnapSample<'_>, feature_strength: f32) -> bool {
+        feature_strength >= self.threshold
+    }
+}
+
+/// Snap to pixels a segmentation model marked as curve.
+///
+/// The model sees past gridlines, text and other series, which the color and
+/// gradient planes cannot tell apart from the curve on cluttered charts.
+#[derive(Debug, Clone, Copy)]
+pub struct MaskScorer {
+    pub threshold: f32,
+}
+
+impl SnapBehavior for MaskScorer {
+    fn feature_strength(&self, sample: &SnapSample<'_>) -> f32 {
+        (sample.curve_probability() * 255.0).clamp(0.0, 255.0)
+    }
+
+    fn threshold_passes(&self, _sample: &SnapSample<'_>, feature_strength: f32) -> bool {
+        feature_strength >= self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snap::{CurveMask, SnapMapCache};
+    use egui::{Color32, ColorImage, pos2};
+
+    /// White canvas with a filled red disk and a red hairline to its right.
+    fn disk_and_hairline() -> ColorImage {
+        let (w, h) = (80, 40);
+        let mut image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        for y in 0..h {
+            for x in 0..w {
+                let (dx, dy) = (x.abs_diff(20), y.abs_diff(20));
+                if dx * dx + dy * dy <= 100 || x == 50 {
+                    image.pixels[y * w + x] = Color32::from_rgb(220, 30, 30);
+                }
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn registered_ids_are_unique() {
+        for (idx, entry) in SNAP_SCORERS.iter().enumerate() {
+            assert!(SNAP_SCORERS[..idx].iter().all(|other| other.id != entry.id));
+            assert!(entry.threshold_range.contains(&entry.default_threshold));
+        }
+    }
+
+    #[test]
+    fn mask_scorer_follows_the_mask_only() {
+        let (w, h) = (64, 32);
+        let image = ColorImage::new([w, h], vec![Color32::WHITE; w * h]);
+        let mut maps = SnapMapCache::build(&image, Color32::BLACK, 40.0).expect("snap maps");
+        let mask_entry = SNAP_SCORERS.iter().find(|e| e.id == "mask").expect("mask");
+        let scorer = (mask_entry.build)(mask_entry.default_threshold);
+        assert!(
+            maps.find_point(pos2(30.0, 16.0), 8.0, scorer.as_ref())
+                .is_none()
+        );
+
+        let gray: Vec<u8> = (0..w * h)
+            .map(|i| if i % w == 34 { 230 } else { 0 })
+            .collect();
+        let mask = CurveMask::from_gray([w, h], &gray).expect("mask");
+        assert!(maps.set_curve_mask(Some(&mask)));
+        let snapped = maps
+            .find_point(pos2(30.0, 16.0), 8.0, scorer.as_ref())
+            .expect("masked column");
+        assert!((snapped.x - 34.0).abs() < 0.6, "x = {}", snapped.x);
+        assert!(!maps.set_curve_mask(Some(&CurveMask::from_gray([2, 2], &[0; 4]).expect("tiny"))));
+    }
+
+    #[test]
+    fn ridge_follows_hairlines_but_not_filled_areas() {
+        let image = disk_and_hairline();
+        let maps =
+            SnapMapCache::build(&image, Color32::from_rgb(220, 30, 30), 40.0).expect("snap maps");
+        let ridge = (SNAP_SCORERS[0].build)(SNAP_SCORERS[0].default_threshold);
+
+        let snapped = maps
+            .find_point(pos2(46.0, 20.0), 6.0, ridge.as_ref())
+            .expect("hairline");
+        assert!((snapped.x - 50.0).abs() < 0.6, "x = {}", snapped.x);
+        // The middle of the disk is flat color with no ridge to lock onto.
+        assert!(
+            maps.find_point(pos2(20.0, 20.0), 4.0, ridge.as_ref())
+                .is_none()
+        );
+    }
+}
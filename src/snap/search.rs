@@ -1,6 +1,6 @@
-use egui::{Pos2, pos2};
+use egui::{Pos2, Vec2, pos2};
 
-use super::behavior::SnapBehavior;
+use super::behavior::{SnapBehavior, SnapSample};
 use super::maps::SnapMapLevel;
 use crate::util::{i32_to_f32, saturating_f32_to_i32};
 
@@ -15,7 +15,7 @@ pub(super) fn search_in_level(
     level: &SnapMapLevel,
     center: Pos2,
     radius: f32,
-    behavior: SnapBehavior,
+    behavior: &dyn SnapBehavior,
 ) -> Option<SnapCandidate> {
     if radius <= 0.0 || level.size[0] < 3 || level.size[1] < 3 {
         return None;
@@ -42,16 +42,15 @@ pub(super) fn search_in_level(
             let dx = xf - center_x;
             let dy = yf - center_y;
             let dist_sq = dx * dx + dy * dy;
-            if dist_sq > radius_sq {
+            if dist_sq > radius_sq || level.is_excluded(x, y) {
                 continue;
             }
-            let gradient = level.gradient_at(x, y);
-            let color_similarity = level.color_similarity_at(x, y);
-            let feature_strength = behavior.feature_strength(gradient, color_similarity);
+            let sample = SnapSample::new(level, x, y);
+            let feature_strength = behavior.feature_strength(&sample);
             if feature_strength <= 0.0 {
                 continue;
             }
-            if !behavior.threshold_passes(gradient, feature_strength) {
+            if !behavior.threshold_passes(&sample, feature_strength) {
                 continue;
             }
             let dist = dist_sq.sqrt();
@@ -78,7 +77,7 @@ pub(super) fn search_in_level(
 pub(super) fn refine_snap_position(
     level: &SnapMapLevel,
     approx: Pos2,
-    behavior: SnapBehavior,
+    behavior: &dyn SnapBehavior,
 ) -> Pos2 {
     if level.size[0] < 3 || level.size[1] < 3 {
         return approx;
@@ -99,8 +98,10 @@ pub(super) fn refine_snap_position(
         for dx in -1..=1 {
             let px = (ax + dx).clamp(0, width - 1);
             let py = (ay + dy).clamp(0, height - 1);
-            let strength = behavior
-                .feature_strength(level.gradient_at(px, py), level.color_similarity_at(px, py));
+            if level.is_excluded(px, py) {
+                continue;
+            }
+            let strength = behavior.feature_strength(&SnapSample::new(level, px, py));
             if strength <= 0.0 {
                 continue;
             }
@@ -118,3 +119,83 @@ pub(super) fn refine_snap_position(
         approx
     }
 }
+
+/// Stroke pixels within this many search radii are used to find the line through a gap.
+const GAP_REACH: f32 = 3.0;
+/// Fewest stroke pixels that still describe a line.
+const GAP_MIN_PIXELS: usize = 6;
+/// Largest ratio of the stroke spread across the line to its spread along it.
+const GAP_MAX_ASPECT: f32 = 0.35;
+
+/// Point on a dashed or dotted stroke across the gap around `center`.
+///
+/// Stroke pixels in a wider window get a weighted principal axis; when they form a line with
+/// stroke on both sides of the cursor, the cursor is projected onto that line. `None` when the
+/// pixels do not look like a line, the cursor is past its end, or farther than `radius` away.
+#[allow(clippy::suboptimal_flops)]
+pub(super) fn bridge_gap(
+    level: &SnapMapLevel,
+    center: Pos2,
+    radius: f32,
+    behavior: &dyn SnapBehavior,
+) -> Option<Pos2> {
+    let width = i32::try_from(level.size[0]).ok()?;
+    let height = i32::try_from(level.size[1]).ok()?;
+    if radius <= 0.0 || width < 3 || height < 3 {
+        return None;
+    }
+    let reach = saturating_f32_to_i32((radius * GAP_REACH).ceil());
+    let cx = saturating_f32_to_i32(center.x.round());
+    let cy = saturating_f32_to_i32(center.y.round());
+    let mut pixels = Vec::new();
+    for y in (cy - reach).max(1)..=(cy + reach).min(height - 2) {
+        for x in (cx - reach).max(1)..=(cx + reach).min(width - 2) {
+            if (x - cx).pow(2) + (y - cy).pow(2) > reach * reach || level.is_excluded(x, y) {
+                continue;
+            }
+            let sample = SnapSample::new(level, x, y);
+            let strength = behavior.feature_strength(&sample);
+            if strength > 0.0 && behavior.threshold_passes(&sample, strength) {
+                pixels.push((pos2(i32_to_f32(x), i32_to_f32(y)), strength));
+            }
+        }
+    }
+    if pixels.len() < GAP_MIN_PIXELS {
+        return None;
+    }
+
+    let total: f32 = pixels.iter().map(|(_, w)| w).sum();
+    let mean = pixels
+        .iter()
+        .fold(Vec2::ZERO, |acc, (p, w)| acc + p.to_vec2() * *w)
+        / total;
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (p, w) in &pixels {
+        let d = p.to_vec2() - mean;
+        sxx += w * d.x * d.x;
+        sxy += w * d.x * d.y;
+        syy += w * d.y * d.y;
+    }
+    // Eigenvalues of the 2×2 covariance; the larger one belongs to the line direction.
+    let half_trace = f32::midpoint(sxx, syy);
+    let spread = ((sxx - syy) / 2.0).hypot(sxy);
+    let (along, across) = (half_trace + spread, (half_trace - spread).max(0.0));
+    if along <= f32::EPSILON || (across / along).sqrt() > GAP_MAX_ASPECT {
+        return None;
+    }
+    let angle = 0.5 * (2.0 * sxy).atan2(sxx - syy);
+    let dir = Vec2::angled(angle);
+
+    let t_cursor = (center.to_vec2() - mean).dot(dir);
+    let (before, after) = pixels
+        .iter()
+        .fold((false, false), |(before, after), (p, _)| {
+            let t = (p.to_vec2() - mean).dot(dir) - t_cursor;
+            (before || t < -1.0, after || t > 1.0)
+        });
+    if !(before && after) {
+        return None;
+    }
+    let on_line = (mean + dir * t_cursor).to_pos2();
+    (on_line.distance(center) <= radius).then_some(on_line)
+}
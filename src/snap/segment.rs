@@ -0,0 +1,361 @@
+//! In-process curve segmentation with an ONNX model, run by [`tract_onnx`].
+//!
+//! The model takes one `[1, 3, H, W]` float input, RGB scaled to 0–1, and returns one
+//! `[1, 1, H, W]` (or `[1, H, W]`, `[H, W]`) map of curve probability. A model that returns
+//! logits says so with the ONNX metadata entry `curcat.output = logits`, and its output goes
+//! through a sigmoid; without it the output must already lie in 0–1. Models with a fixed input
+//! size see the chart stretched to it. Others get it scaled down to at most
+//! [`MAX_INPUT_SIDE`] pixels on a side and padded with its edge pixels to multiples of 32, as
+//! U-Net style encoders expect; the padding is cropped off the output. The map is stretched
+//! back to the chart as a [`CurveMask`].
+
+use super::mask::CurveMask;
+use anyhow::{Context as _, bail};
+use egui::ColorImage;
+use image::imageops::{self, FilterType};
+use std::path::Path;
+use tract_onnx::prelude::*;
+use tract_onnx::tract_hir::infer::Factoid as _;
+use tract_onnx::tract_hir::internal::DimLike as _;
+
+/// Longest side of the chart as fed to a model without a fixed input size.
+const MAX_INPUT_SIDE: usize = 1024;
+/// Dynamic input sizes are padded to a multiple of this.
+const INPUT_ALIGN: usize = 32;
+/// ONNX metadata key naming the model's output activation.
+const OUTPUT_METADATA: &str = "onnx.metadata_props.curcat.output";
+
+/// What the model's output values are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputActivation {
+    /// Curve probability in 0–1, the default.
+    Probability,
+    /// Raw scores, turned into probability with a sigmoid.
+    Logits,
+}
+
+/// Where the chart sits in the model input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InputLayout {
+    /// Size (`[width, height]`) the chart is resized to.
+    chart: [usize; 2],
+    /// Size of the whole input; the chart is in its top left corner, the rest is padding.
+    padded: [usize; 2],
+}
+
+/// A loaded segmentation model, ready to run on any chart size.
+#[derive(Debug, Clone)]
+pub struct SegmentationModel {
+    model: InferenceModel,
+    activation: OutputActivation,
+}
+
+impl SegmentationModel {
+    /// Parse the ONNX model at `path`.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let model = tract_onnx::onnx()
+            .model_for_path(path)
+            .with_context(|| format!("Failed to read model {}", path.display()))?;
+        Self::from_model(model)
+    }
+
+    fn from_model(model: InferenceModel) -> anyhow::Result<Self> {
+        if model.inputs.len() != 1 {
+            bail!(
+                "Model must take one image input, not {}",
+                model.inputs.len()
+            );
+        }
+        let activation = match model.properties.get(OUTPUT_METADATA) {
+            None => OutputActivation::Probability,
+            Some(value) => match value.try_as_plain_ram()?.to_scalar::<String>()?.as_str() {
+                "probability" => OutputActivation::Probability,
+                "logits" => OutputActivation::Logits,
+                other => bail!(
+                    "Unknown curcat.output \"{other}\" in the model, expected \"probability\" or \"logits\""
+                ),
+            },
+        };
+        Ok(Self { model, activation })
+    }
+
+    /// Where a `size` chart goes in the model input.
+    fn input_layout(&self, size: [usize; 2]) -> anyhow::Result<InputLayout> {
+        let fact = self.model.input_fact(0)?;
+        let dim = |axis: usize| {
+            fact.shape
+                .dim(axis)
+                .and_then(|dim| dim.concretize())
+                .and_then(|dim| dim.to_usize().ok())
+        };
+        if let (Some(height), Some(width)) = (dim(2), dim(3)) {
+            return Ok(InputLayout {
+                chart: [width, height],
+                padded: [width, height],
+            });
+        }
+        let longest = size[0].max(size[1]).max(1);
+        let scaled = |extent: usize| {
+            (extent * MAX_INPUT_SIDE.min(longest))
+                .div_ceil(longest)
+                .max(1)
+        };
+        let chart = [scaled(size[0]), scaled(size[1])];
+        Ok(InputLayout {
+            chart,
+            padded: chart.map(|extent| extent.next_multiple_of(INPUT_ALIGN)),
+        })
+    }
+
+    /// Run the model on `image` and return its mask at the image size.
+    pub fn run(&self, image: &ColorImage) -> anyhow::Result<CurveMask> {
+        let InputLayout {
+            chart: [chart_width, chart_height],
+            padded: [width, height],
+        } = self.input_layout(image.size)?;
+        let rgb = image::RgbImage::from_raw(
+            u32::try_from(image.size[0]).context("Image too wide for the model")?,
+            u32::try_from(image.size[1]).context("Image too tall for the model")?,
+            image
+                .pixels
+                .iter()
+                .flat_map(|pixel| [pixel.r(), pixel.g(), pixel.b()])
+                .collect(),
+        )
+        .context("Image size mismatch")?;
+        let rgb = imageops::resize(
+            &rgb,
+            u32::try_from(chart_width)?,
+            u32::try_from(chart_height)?,
+            FilterType::Triangle,
+        );
+        // The padding repeats the chart's edge pixels, so it adds no edges of its own.
+        let input: Tensor =
+            tract_ndarray::Array4::from_shape_fn((1, 3, height, width), |(_, c, y, x)| {
+                let (x, y) = (x.min(chart_width - 1), y.min(chart_height - 1));
+                f32::from(rgb.as_raw()[(y * chart_width + x) * 3 + c]) / 255.0
+            })
+            .into();
+        let plan = self
+            .model
+            .clone()
+            .with_input_fact(
+                0,
+                InferenceFact::dt_shape(f32::datum_type(), tvec![1, 3, height, width]),
+            )?
+            .into_optimized()
+            .context("Model does not fit the chart")?
+            .into_runnable()?;
+        let outputs = plan.run(tvec!(input.into())).context("Model run failed")?;
+        let output = outputs
+            .first()
+            .context("Model has no output")?
+            .cast_to::<f32>()?;
+        let output = output.to_plain_array_view::<f32>()?;
+        let shape = output.shape();
+        let [leading @ .., out_height, out_width] = shape else {
+            bail!("Model output {shape:?} is not an image");
+        };
+        if leading.iter().product::<usize>() != 1 {
+            bail!("Model output {shape:?} has more than one channel");
+        }
+        // The output may be smaller than the input; its padding shrinks with it.
+        let crop = [
+            (out_width * chart_width).div_ceil(width).max(1),
+            (out_height * chart_height).div_ceil(height).max(1),
+        ];
+        let mut values: Vec<f32> = output
+            .rows()
+            .into_iter()
+            .take(crop[1])
+            .flat_map(|row| row.into_iter().take(crop[0]).copied().collect::<Vec<_>>())
+            .collect();
+        if values.iter().any(|value| !value.is_finite()) {
+            bail!("Model output is not finite");
+        }
+        match self.activation {
+            OutputActivation::Probability => {
+                if values.iter().any(|value| !(0.0..=1.0).contains(value)) {
+                    bail!(
+                        "Model output is outside 0–1; a model that returns logits needs the metadata curcat.output = logits"
+                    );
+                }
+            }
+            OutputActivation::Logits => {
+                for value in &mut values {
+                    *value = 1.0 / (1.0 + (-*value).exp());
+                }
+            }
+        }
+        CurveMask::from_probabilities(crop, &values, image.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Color32;
+    use tract_onnx::pb::{
+        self, AttributeProto, GraphProto, ModelProto, NodeProto, OperatorSetIdProto,
+        StringStringEntryProto, TypeProto, ValueInfoProto, tensor_shape_proto, type_proto,
+    };
+
+    /// Value info of a float tensor; `None` dimensions are symbolic.
+    fn float_value(name: &str, dims: &[Option<i64>]) -> ValueInfoProto {
+        let dim = dims
+            .iter()
+            .enumerate()
+            .map(|(i, dim)| tensor_shape_proto::Dimension {
+                value: Some(dim.map_or_else(
+                    || tensor_shape_proto::dimension::Value::DimParam(format!("d{i}")),
+                    tensor_shape_proto::dimension::Value::DimValue,
+                )),
+                ..Default::default()
+            })
+            .collect();
+        ValueInfoProto {
+            name: name.to_string(),
+            r#type: Some(TypeProto {
+                value: Some(type_proto::Value::TensorType(type_proto::Tensor {
+                    elem_type: pb::tensor_proto::DataType::Float as i32,
+                    shape: Some(pb::TensorShapeProto { dim }),
+                })),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// A model whose "probability" is the mean of the RGB channels, then `op` if given, with
+    /// `activation` as its `curcat.output` metadata.
+    fn brightness_model(
+        height: Option<i64>,
+        width: Option<i64>,
+        op: Option<&str>,
+        activation: Option<&str>,
+    ) -> anyhow::Result<SegmentationModel> {
+        let mut nodes = vec![NodeProto {
+            input: vec!["image".to_string()],
+            output: vec!["mean".to_string()],
+            op_type: "ReduceMean".to_string(),
+            attribute: vec![AttributeProto {
+                name: "axes".to_string(),
+                ints: vec![1],
+                r#type: pb::attribute_proto::AttributeType::Ints as i32,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        let mut output = "mean";
+        if let Some(op) = op {
+            nodes.push(NodeProto {
+                input: vec![output.to_string()],
+                output: vec!["mask".to_string()],
+                op_type: op.to_string(),
+                ..Default::default()
+            });
+            output = "mask";
+        }
+        let proto = ModelProto {
+            ir_version: 8,
+            opset_import: vec![OperatorSetIdProto {
+                domain: String::new(),
+                version: 13,
+            }],
+            graph: Some(GraphProto {
+                node: nodes,
+                input: vec![float_value("image", &[Some(1), Some(3), height, width])],
+                output: vec![float_value(output, &[Some(1), Some(1), height, width])],
+                ..Default::default()
+            }),
+            metadata_props: activation
+                .map(|value| StringStringEntryProto {
+                    key: "curcat.output".to_string(),
+                    value: value.to_string(),
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let model = tract_onnx::onnx()
+            .model_for_proto_model(&proto)
+            .expect("parse model");
+        SegmentationModel::from_model(model)
+    }
+
+    /// Black chart with white pixels where `white` says so.
+    fn chart(size: [usize; 2], white: impl Fn(usize, usize) -> bool) -> ColorImage {
+        let pixels = (0..size[0] * size[1])
+            .map(|i| {
+                if white(i % size[0], i / size[0]) {
+                    Color32::WHITE
+                } else {
+                    Color32::BLACK
+                }
+            })
+            .collect();
+        ColorImage::new(size, pixels)
+    }
+
+    #[test]
+    fn dynamic_models_see_a_padded_chart_and_the_padding_is_cropped() {
+        let model = brightness_model(None, None, None, None).expect("model");
+        assert_eq!(
+            model.input_layout([2048, 512]).unwrap(),
+            InputLayout {
+                chart: [1024, 256],
+                padded: [1024, 256]
+            }
+        );
+        assert_eq!(
+            model.input_layout([1000, 300]).unwrap(),
+            InputLayout {
+                chart: [1000, 300],
+                padded: [1024, 320]
+            }
+        );
+
+        // A curve along the right and bottom edges stays there: stretching the padded output
+        // back over the chart would pull it inwards.
+        let size = [40, 10];
+        let mask = model
+            .run(&chart(size, |x, y| x == 39 || y == 9))
+            .expect("run");
+        assert_eq!(mask.size(), size);
+        let at = |x: usize, y: usize| mask.values()[y * size[0] + x];
+        assert!(at(39, 0) > 0.99);
+        assert!(at(0, 9) > 0.99);
+        assert!(at(37, 0) < 0.01);
+        assert!(at(20, 7) < 0.01);
+    }
+
+    #[test]
+    fn fixed_models_get_their_input_size_and_declared_logits_are_squashed() {
+        // Negating the brightness leaves every value at or below zero, as logits may be.
+        let model =
+            brightness_model(Some(8), Some(16), Some("Neg"), Some("logits")).expect("model");
+        assert_eq!(
+            model.input_layout([40, 10]).unwrap(),
+            InputLayout {
+                chart: [16, 8],
+                padded: [16, 8]
+            }
+        );
+
+        let mask = model.run(&chart([40, 10], |x, _| x < 20)).expect("run");
+        assert_eq!(mask.size(), [40, 10]);
+        // sigmoid(-1) on the white half, sigmoid(0) on the black one.
+        assert!((mask.values()[0] - 0.269).abs() < 0.01);
+        assert!((mask.values()[39] - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn undeclared_logits_and_unknown_activations_are_rejected() {
+        let model = brightness_model(None, None, Some("Neg"), None).expect("model");
+        let err = model.run(&chart([40, 10], |x, _| x < 20)).unwrap_err();
+        assert!(err.to_string().contains("curcat.output = logits"), "{err}");
+
+        let err = brightness_model(None, None, None, Some("softmax")).unwrap_err();
+        assert!(err.to_string().contains("softmax"), "{err}");
+    }
+}
@@ -0,0 +1,228 @@
+//! One-sided amplitude and phase spectrum of evenly spaced samples.
+//!
+//! Power-of-two lengths go through an iterative radix-2 FFT; other lengths use Bluestein's
+//! chirp transform on top of it, so any sample count costs `O(n log n)`.
+
+use crate::interp::XYPoint;
+use std::f64::consts::PI;
+
+/// Relative spread of the sample steps above which the samples do not count as even.
+const EVEN_STEP_TOLERANCE: f64 = 1.0e-6;
+
+/// One frequency bin of [`amplitude_spectrum`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumBin {
+    /// Cycles per unit of x.
+    pub frequency: f64,
+    /// Amplitude of the sinusoid at this frequency; the mean at zero frequency.
+    pub amplitude: f64,
+    /// Phase of a cosine at this frequency, in degrees within (-180, 180].
+    pub phase_deg: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    const ZERO: Self = Self { re: 0.0, im: 0.0 };
+
+    fn from_angle(angle: f64) -> Self {
+        let (im, re) = angle.sin_cos();
+        Self { re, im }
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            re: self.re.mul_add(other.re, -self.im * other.im),
+            im: self.re.mul_add(other.im, self.im * other.re),
+        }
+    }
+
+    const fn conj(self) -> Self {
+        Self {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
+    fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+}
+
+/// In-place radix-2 FFT; `data.len()` must be a power of two. `inverse` flips the sign of
+/// the exponent and leaves the result unscaled.
+fn fft_radix2(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        #[allow(clippy::cast_precision_loss)]
+        let step = Complex::from_angle(sign * 2.0 * PI / len as f64);
+        for chunk in data.chunks_mut(len) {
+            let mut w = Complex { re: 1.0, im: 0.0 };
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+                let t = b.mul(w);
+                *b = Complex {
+                    re: a.re - t.re,
+                    im: a.im - t.im,
+                };
+                *a = Complex {
+                    re: a.re + t.re,
+                    im: a.im + t.im,
+                };
+                w = w.mul(step);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Discrete Fourier transform of `values` of any length.
+fn dft(values: &[f64]) -> Vec<Complex> {
+    let n = values.len();
+    let mut data: Vec<Complex> = values.iter().map(|&re| Complex { re, im: 0.0 }).collect();
+    if n.is_power_of_two() {
+        fft_radix2(&mut data, false);
+        return data;
+    }
+    // Bluestein: X_k = conj(w_k) · Σ (x_j · conj(w_j)) · w_(k−j) with w_m = e^(iπm²/n).
+    #[allow(clippy::cast_precision_loss)]
+    let chirp: Vec<Complex> = (0..n)
+        .map(|m| Complex::from_angle(PI * ((m * m) % (2 * n)) as f64 / n as f64))
+        .collect();
+    let size = (2 * n - 1).next_power_of_two();
+    let mut a = vec![Complex::ZERO; size];
+    for (slot, (x, w)) in a.iter_mut().zip(data.iter().zip(&chirp)) {
+        *slot = x.mul(w.conj());
+    }
+    let mut b = vec![Complex::ZERO; size];
+    b[0] = chirp[0];
+    for m in 1..n {
+        b[m] = chirp[m];
+        b[size - m] = chirp[m];
+    }
+    fft_radix2(&mut a, false);
+    fft_radix2(&mut b, false);
+    for (x, y) in a.iter_mut().zip(&b) {
+        *x = x.mul(*y);
+    }
+    fft_radix2(&mut a, true);
+    #[allow(clippy::cast_precision_loss)]
+    let scale = 1.0 / size as f64;
+    a.iter()
+        .zip(&chirp)
+        .map(|(x, w)| {
+            let x = Complex {
+                re: x.re * scale,
+                im: x.im * scale,
+            };
+            x.mul(w.conj())
+        })
+        .collect()
+}
+
+/// Spectrum of `samples`, which must be sorted by x and evenly spaced, from zero frequency up
+/// to the Nyquist frequency.
+///
+/// Amplitudes are one-sided: a sinusoid of amplitude `a` shows up as `a` in its bin. The
+/// samples are used as they are, without mean removal or a window.
+pub fn amplitude_spectrum(samples: &[XYPoint]) -> Result<Vec<SpectrumBin>, String> {
+    let n = samples.len();
+    if n < 2 {
+        return Err("A spectrum needs two or more samples.".to_string());
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let step = (samples[n - 1].x - samples[0].x) / (n - 1) as f64;
+    let even = step.is_finite()
+        && step > 0.0
+        && samples
+            .windows(2)
+            .all(|pair| ((pair[1].x - pair[0].x) - step).abs() <= step * EVEN_STEP_TOLERANCE);
+    if !even {
+        return Err("A spectrum needs evenly spaced samples of increasing x.".to_string());
+    }
+    let values: Vec<f64> = samples.iter().map(|p| p.y).collect();
+    let transform = dft(&values);
+    #[allow(clippy::cast_precision_loss)]
+    let (n_f, span) = (n as f64, n as f64 * step);
+    Ok(transform
+        .iter()
+        .take(n / 2 + 1)
+        .enumerate()
+        .map(|(k, value)| {
+            // Bins other than zero and Nyquist also stand for their negative frequency.
+            let one_sided = if k == 0 || 2 * k == n { 1.0 } else { 2.0 };
+            #[allow(clippy::cast_precision_loss)]
+            SpectrumBin {
+                frequency: k as f64 / span,
+                amplitude: one_sided * value.abs() / n_f,
+                phase_deg: value.im.atan2(value.re).to_degrees(),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sampled(n: usize, step: f64, f: impl Fn(f64) -> f64) -> Vec<XYPoint> {
+        (0..n)
+            .map(|i| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = i as f64 * step;
+                XYPoint { x, y: f(x) }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sinusoids_show_their_amplitude_and_phase() {
+        // 1.5 + 2·cos(2π·5x + 30°) + 0.5·sin(2π·12x) over one second, for both FFT paths.
+        for n in [64, 100] {
+            #[allow(clippy::cast_precision_loss)]
+            let step = 1.0 / n as f64;
+            let samples = sampled(n, step, |x| {
+                let w = 2.0 * PI * x;
+                0.5f64.mul_add(
+                    (12.0 * w).sin(),
+                    2.0f64.mul_add(5.0f64.mul_add(w, 30f64.to_radians()).cos(), 1.5),
+                )
+            });
+            let bins = amplitude_spectrum(&samples).expect("spectrum");
+            assert_eq!(bins.len(), n / 2 + 1);
+            assert!((bins[5].frequency - 5.0).abs() < 1e-9);
+            assert!((bins[0].amplitude - 1.5).abs() < 1e-9, "n = {n}");
+            assert!((bins[5].amplitude - 2.0).abs() < 1e-9, "n = {n}");
+            assert!((bins[5].phase_deg - 30.0).abs() < 1e-6, "n = {n}");
+            assert!((bins[12].amplitude - 0.5).abs() < 1e-9, "n = {n}");
+            assert!((bins[12].phase_deg + 90.0).abs() < 1e-6, "n = {n}");
+            assert!(bins[7].amplitude < 1e-9, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn uneven_samples_are_rejected() {
+        let mut samples = sampled(16, 0.5, f64::sin);
+        samples[3].x += 0.1;
+        assert!(amplitude_spectrum(&samples).is_err());
+        assert!(amplitude_spectrum(&samples[..1]).is_err());
+    }
+}
@@ -4,13 +4,19 @@ mod axis;
 mod coord;
 mod mapping;
 
-pub use axis::{AxisUnit, AxisValue, parse_axis_value};
-pub use coord::{AngleDirection, AngleUnit, CoordSystem, ScaleKind};
-pub use mapping::{AxisMapping, PolarMapping, PolarMappingParams};
+pub use axis::{
+    AxisUnit, AxisValue, FloatFormat, TimeInput, parse_axis_value, parse_axis_value_with,
+    parse_utc_offset,
+};
+pub use coord::{AngleDirection, AngleUnit, CoordSystem, ScaleKind, YAxis};
+pub use mapping::{
+    AffineMapping, AffineMappingError, AffineReference, AxisMapping, AxisMappingError,
+    PolarMapping, PolarMappingParams, SmithMapping, SmithMappingError, reflection_of,
+};
 
 #[cfg(test)]
 mod tests {
-    use super::mapping::{AxisMappingError, PolarMappingError};
+    use super::mapping::PolarMappingError;
     use super::*;
     use chrono::{DateTime, NaiveDate, Utc};
     use egui::Pos2;
@@ -22,6 +28,34 @@ mod tests {
         assert_eq!(AxisValue::Float(-0.0).format(), "0");
     }
 
+    #[test]
+    fn float_format_adapts_to_span_and_switches_to_scientific() {
+        assert_eq!(
+            FloatFormat::adaptive(2000.0, 1234.5, 4),
+            FloatFormat::Fixed(0)
+        );
+        assert_eq!(FloatFormat::adaptive(2.0, 1.2345, 4), FloatFormat::Fixed(3));
+        assert_eq!(
+            FloatFormat::adaptive(2.0, 1.2345, 4).format_trimmed(1.2345),
+            "1.234"
+        );
+        assert_eq!(
+            FloatFormat::adaptive(1.0e7, 12_345_678.0, 4).format_trimmed(12_345_678.0),
+            "1.235e7"
+        );
+        assert_eq!(
+            FloatFormat::adaptive(1.0e-6, 5.0e-7, 3).format_trimmed(5.25e-7),
+            "5.2e-7"
+        );
+        assert_eq!(FloatFormat::adaptive(0.0, 0.0, 4), FloatFormat::Fixed(3));
+        assert_eq!(FloatFormat::Fixed(2).format(1.5), "1.50");
+        assert!((FloatFormat::Scientific(2).round(12_345.0) - 12_300.0).abs() < 1e-9);
+        assert_eq!(
+            AxisValue::Float(2.5).format_with(FloatFormat::Fixed(3)),
+            AxisValue::Float(2.5).format_with_decimals(3)
+        );
+    }
+
     #[test]
     fn parse_axis_value_accepts_date_and_timezone() {
         let AxisValue::DateTime(date_only) =
@@ -48,6 +82,45 @@ mod tests {
         assert_eq!(with_tz, expected_tz);
     }
 
+    #[test]
+    fn time_input_reads_custom_patterns_in_the_axis_offset() {
+        let at = |h, m| {
+            NaiveDate::from_ymd_opt(2024, 3, 5)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .map(AxisValue::DateTime)
+        };
+        let time = TimeInput {
+            pattern: "%d %b %Y %H:%M".to_string(),
+            offset: parse_utc_offset("+03:00").expect("offset"),
+        };
+        assert_eq!(
+            parse_axis_value_with("05 Mar 2024 12:30", AxisUnit::DateTime, &time),
+            at(9, 30)
+        );
+        // Built-in formats still apply; an explicit offset wins over the axis one.
+        assert_eq!(
+            parse_axis_value_with("2024-03-05 12:30", AxisUnit::DateTime, &time),
+            at(9, 30)
+        );
+        assert_eq!(
+            parse_axis_value_with("2024-03-05T12:30:00+01:00", AxisUnit::DateTime, &time),
+            at(11, 30)
+        );
+        assert_eq!(
+            parse_axis_value("05 Mar 2024 12:30", AxisUnit::DateTime),
+            None
+        );
+
+        let minutes = |text| parse_utc_offset(text).map(|o| o.local_minus_utc() / 60);
+        assert_eq!(minutes(""), Some(0));
+        assert_eq!(minutes("Z"), Some(0));
+        assert_eq!(minutes("-0530"), Some(-330));
+        assert_eq!(minutes("UTC+2"), Some(120));
+        assert_eq!(minutes("+15"), None);
+        assert_eq!(minutes("3"), None);
+    }
+
     #[test]
     fn from_scalar_seconds_rounds_nanos_across_second() {
         let value = AxisValue::from_scalar_seconds(AxisUnit::DateTime, 1.999_999_999_6)
@@ -75,6 +148,38 @@ mod tests {
         assert!((value - 10.0).abs() < 1.0e-6);
     }
 
+    #[test]
+    fn log_decade_ticks_follow_the_log_scale_across_the_bounds() {
+        // 1 at x = 10 and 100 at x = 110: one decade every 50 pixels.
+        let mapping = AxisMapping {
+            p1: Pos2::new(10.0, 20.0),
+            p2: Pos2::new(110.0, 20.0),
+            v1: AxisValue::Float(1.0),
+            v2: AxisValue::Float(100.0),
+            scale: ScaleKind::Log10,
+            unit: AxisUnit::Float,
+        };
+        let bounds = egui::Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(200.0, 40.0));
+        let ticks = mapping.log_decade_ticks(bounds);
+        // 0.7 … 0.9 left of x = 10, then 1 … 900, then 1000 … 6000 up to x = 200.
+        assert_eq!(ticks.len(), 3 + 27 + 6);
+        assert_eq!(
+            ticks[..4].iter().map(|t| t.1).collect::<Vec<_>>(),
+            [7, 8, 9, 1]
+        );
+        for &(point, digit) in &ticks {
+            let decade = (f64::from(point.x) - 10.0) / 50.0 - f64::from(digit).log10();
+            assert!((decade - decade.round()).abs() < 1e-5, "{point:?} {digit}");
+            assert!((point.y - 20.0).abs() < 1e-3);
+        }
+
+        let linear = AxisMapping {
+            scale: ScaleKind::Linear,
+            ..mapping
+        };
+        assert!(linear.log_decade_ticks(bounds).is_empty());
+    }
+
     #[test]
     fn axis_mapping_log10_rejects_nonpositive_values() {
         let mapping = AxisMapping {
@@ -208,4 +313,234 @@ mod tests {
             Err(PolarMappingError::EqualAngleValues)
         );
     }
+
+    #[test]
+    fn axis_mapping_pixel_at_inverts_skewed_axes() {
+        let x_mapping = AxisMapping {
+            p1: Pos2::new(10.0, 200.0),
+            p2: Pos2::new(210.0, 190.0),
+            v1: AxisValue::Float(1.0),
+            v2: AxisValue::Float(1000.0),
+            scale: ScaleKind::Log10,
+            unit: AxisUnit::Float,
+        };
+        let y_mapping = AxisMapping {
+            p1: Pos2::new(12.0, 200.0),
+            p2: Pos2::new(20.0, 20.0),
+            v1: AxisValue::Float(0.0),
+            v2: AxisValue::Float(50.0),
+            scale: ScaleKind::Linear,
+            unit: AxisUnit::Float,
+        };
+        let pixel = AxisMapping::pixel_at(&x_mapping, &y_mapping, 31.6, 12.5).expect("pixel");
+        let x = x_mapping.numeric_at(pixel).expect("x");
+        let y = y_mapping.numeric_at(pixel).expect("y");
+        assert!((x - 31.6).abs() < 1.0e-3, "x = {x}");
+        assert!((y - 12.5).abs() < 1.0e-3, "y = {y}");
+
+        assert!(AxisMapping::pixel_at(&x_mapping, &y_mapping, -1.0, 12.5).is_none());
+        assert!(AxisMapping::pixel_at(&x_mapping, &x_mapping, 10.0, 10.0).is_none());
+    }
+
+    #[test]
+    fn axis_mapping_refit_moves_points_to_match_samples() {
+        // Ticks entered at 10 and 110 px, while the true 0 and 10 sit at 12 and 108 px.
+        let entered = AxisMapping {
+            p1: Pos2::new(10.0, 50.0),
+            p2: Pos2::new(110.0, 50.0),
+            v1: AxisValue::Float(1.0),
+            v2: AxisValue::Float(1000.0),
+            scale: ScaleKind::Log10,
+            unit: AxisUnit::Float,
+        };
+        let truth = AxisMapping {
+            p1: Pos2::new(12.0, 50.0),
+            p2: Pos2::new(108.0, 50.0),
+            ..entered.clone()
+        };
+        let samples: Vec<(Pos2, f64)> = [20.0, 55.0, 90.0]
+            .into_iter()
+            .map(|x| {
+                let pixel = Pos2::new(x, 47.0);
+                (pixel, truth.numeric_at(pixel).expect("value"))
+            })
+            .collect();
+        let refit = entered.refit_to_samples(&samples).expect("refit");
+        assert!(refit.p1.distance(truth.p1) < 1.0e-3, "{:?}", refit.p1);
+        assert!(refit.p2.distance(truth.p2) < 1.0e-3, "{:?}", refit.p2);
+        assert_eq!(refit.v2, entered.v2);
+
+        assert!(entered.refit_to_samples(&samples[..1]).is_none());
+        let reversed: Vec<(Pos2, f64)> = samples
+            .iter()
+            .zip(samples.iter().rev())
+            .map(|(&(pixel, _), &(_, value))| (pixel, value))
+            .collect();
+        assert!(entered.refit_to_samples(&reversed).is_none());
+    }
+
+    #[test]
+    fn polar_mapping_pixel_at_round_trips_both_directions() {
+        for (direction, angle_pixel2) in [
+            (AngleDirection::Ccw, std::f64::consts::FRAC_PI_2),
+            (AngleDirection::Cw, -std::f64::consts::FRAC_PI_2),
+        ] {
+            let mapping = PolarMapping::try_new(PolarMappingParams {
+                origin: Pos2::new(100.0, 100.0),
+                radius_distance1: 10.0,
+                radius_distance2: 50.0,
+                radius_value1: 1.0,
+                radius_value2: 5.0,
+                radius_scale: ScaleKind::Linear,
+                angle_pixel1: 0.0,
+                angle_pixel2,
+                angle_value1: 0.0,
+                angle_value2: 90.0,
+                angle_unit: AngleUnit::Degrees,
+                angle_direction: direction,
+            })
+            .expect("valid mapping");
+            let pixel = mapping.pixel_at(135.0, 3.0).expect("pixel");
+            let angle = mapping.angle_at(pixel).expect("angle");
+            let radius = mapping.radius_at(pixel).expect("radius");
+            assert!(
+                (angle - 135.0).abs() < 1.0e-3,
+                "{direction:?}: angle = {angle}"
+            );
+            assert!(
+                (radius - 3.0).abs() < 1.0e-3,
+                "{direction:?}: radius = {radius}"
+            );
+        }
+    }
+
+    /// Pixel of chart point `(x, y)` on a scan rotated by 0.2 rad and sheared along X.
+    fn skewed_pixel(x: f64, y: f64) -> Pos2 {
+        let (sin, cos) = 0.2f64.sin_cos();
+        let (u, v) = (40.0f64.mul_add(x, 12.0 * y), -30.0 * y);
+        #[allow(clippy::cast_possible_truncation)]
+        Pos2::new(
+            (cos.mul_add(u, -(sin * v)) + 100.0) as f32,
+            (sin.mul_add(u, cos * v) + 400.0) as f32,
+        )
+    }
+
+    fn reference(x: f64, y: f64, pixel_value: (f64, f64)) -> AffineReference {
+        AffineReference {
+            pixel: skewed_pixel(pixel_value.0, pixel_value.1),
+            x: AxisValue::Float(x),
+            y: AxisValue::Float(y),
+        }
+    }
+
+    #[test]
+    fn affine_mapping_recovers_rotated_and_sheared_axes() {
+        let refs: Vec<AffineReference> = [(0.0, 0.0), (10.0, 0.0), (0.0, 8.0)]
+            .into_iter()
+            .map(|(x, y)| reference(x, y, (x, y)))
+            .collect();
+        let linear = (ScaleKind::Linear, AxisUnit::Float);
+        let mapping = AffineMapping::fit(&refs, linear, linear).expect("fit");
+        assert!(mapping.rms_residual < 1.0e-3);
+        for (x, y) in [(5.0, 4.0), (-2.0, 9.5), (12.0, 1.0)] {
+            let pixel = skewed_pixel(x, y);
+            let fx = mapping.x.numeric_at(pixel).expect("x");
+            let fy = mapping.y.numeric_at(pixel).expect("y");
+            assert!((fx - x).abs() < 1.0e-3, "x = {fx}");
+            assert!((fy - y).abs() < 1.0e-3, "y = {fy}");
+            let back = AxisMapping::pixel_at(&mapping.x, &mapping.y, x, y).expect("pixel");
+            assert!(back.distance(pixel) < 1.0e-2);
+        }
+    }
+
+    #[test]
+    fn affine_mapping_fits_log_axes_and_reports_residual() {
+        // Log10 X decades at pixel steps 0, 1, 2; the last reference is off by 2 px.
+        let refs = vec![
+            reference(1.0, 0.0, (0.0, 0.0)),
+            reference(100.0, 0.0, (2.0, 0.0)),
+            reference(1.0, 8.0, (0.0, 8.0)),
+            reference(10.0, 4.0, (1.0 + 2.0 / 40.0, 4.0)),
+        ];
+        let mapping = AffineMapping::fit(
+            &refs,
+            (ScaleKind::Log10, AxisUnit::Float),
+            (ScaleKind::Linear, AxisUnit::Float),
+        )
+        .expect("fit");
+        let x = mapping.x.numeric_at(skewed_pixel(0.5, 2.0)).expect("x");
+        assert!((x.log10() - 0.5).abs() < 0.05, "x = {x}");
+        assert!((0.1..2.0).contains(&mapping.rms_residual));
+    }
+
+    #[test]
+    fn affine_mapping_rejects_degenerate_references() {
+        let linear = (ScaleKind::Linear, AxisUnit::Float);
+        let line: Vec<AffineReference> = [0.0, 1.0, 2.0]
+            .into_iter()
+            .map(|t| reference(t, t, (t, t)))
+            .collect();
+        assert_eq!(
+            AffineMapping::fit(&line[..2], linear, linear),
+            Err(AffineMappingError::TooFewPoints)
+        );
+        assert_eq!(
+            AffineMapping::fit(&line, linear, linear),
+            Err(AffineMappingError::CollinearPoints)
+        );
+        let flat: Vec<AffineReference> = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]
+            .into_iter()
+            .map(|(x, y)| reference(5.0, y, (x, y)))
+            .collect();
+        assert_eq!(
+            AffineMapping::fit(&flat, linear, linear),
+            Err(AffineMappingError::Axis(AxisMappingError::EqualValues))
+        );
+        let parallel: Vec<AffineReference> = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]
+            .into_iter()
+            .map(|(x, y)| reference(x + y, x + y, (x, y)))
+            .collect();
+        assert_eq!(
+            AffineMapping::fit(&parallel, linear, linear),
+            Err(AffineMappingError::ParallelAxes)
+        );
+    }
+
+    #[test]
+    fn smith_mapping_reads_impedance_on_a_rotated_chart() {
+        // Radius 100 px, the real axis turned 90° so the short-circuit point is at the bottom.
+        let center = Pos2::new(200.0, 200.0);
+        let mapping =
+            SmithMapping::try_new(center, Pos2::new(200.0, 300.0), 50.0).expect("mapping");
+        assert!((mapping.radius() - 100.0).abs() < 1e-9);
+        let close = |got: Option<(f64, f64)>, want: (f64, f64)| {
+            let (r, x) = got.expect("impedance");
+            assert!(
+                (r - want.0).abs() < 1e-6 && (x - want.1).abs() < 1e-6,
+                "{r}, {x}"
+            );
+        };
+        close(mapping.impedance_at(center), (1.0, 0.0));
+        close(mapping.impedance_at(Pos2::new(200.0, 300.0)), (0.0, 0.0));
+        // Γ = 1/3 on the real axis is r = 2; Γ = j on the rim is x = 1, left of the axis here.
+        close(
+            mapping.impedance_at(Pos2::new(200.0, 200.0 - 100.0 / 3.0)),
+            (2.0, 0.0),
+        );
+        close(mapping.impedance_at(Pos2::new(100.0, 200.0)), (0.0, 1.0));
+        assert_eq!(mapping.impedance_at(Pos2::new(200.0, 100.0)), None);
+
+        for (r, x) in [(0.5, -0.8), (1.0, 2.0), (3.0, 0.25)] {
+            let pixel = mapping.pixel_at(r, x).expect("pixel");
+            close(mapping.impedance_at(pixel), (r, x));
+        }
+        assert_eq!(
+            SmithMapping::try_new(center, center, 50.0),
+            Err(SmithMappingError::ZeroRadius)
+        );
+        assert_eq!(
+            SmithMapping::try_new(center, Pos2::new(100.0, 200.0), 0.0),
+            Err(SmithMappingError::NonPositiveReference)
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! Axis value parsing, formatting, and conversion helpers.
 
-use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 const NANOS_PER_SEC: f64 = 1_000_000_000.0;
@@ -35,7 +35,7 @@ const DATETIME_FORMATS: [&str; 12] = [
 const DATE_FORMATS: [&str; 5] = ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y", "%d/%m/%Y", "%m/%d/%Y"];
 
 /// Units used for axis values.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AxisUnit {
     /// Plain numeric axis values.
     Float,
@@ -101,16 +101,122 @@ impl AxisValue {
     /// Floats are trimmed to remove trailing zeros; date-times are formatted as
     /// `YYYY-MM-DD HH:MM:SS[.fraction]`.
     pub fn format(&self) -> String {
+        self.format_with_decimals(DEFAULT_FLOAT_DECIMALS)
+    }
+
+    /// Format like [`Self::format`], but with a caller-chosen number of float decimals.
+    pub fn format_with_decimals(&self, decimals: usize) -> String {
+        self.format_with(FloatFormat::Fixed(decimals))
+    }
+
+    /// Format like [`Self::format`], rendering floats with `float_format`.
+    pub fn format_with(&self, float_format: FloatFormat) -> String {
         match self {
-            Self::Float(v) => format_float(*v),
+            Self::Float(v) => float_format.format_trimmed(*v),
             Self::DateTime(dt) => format_datetime(dt),
         }
     }
 }
 
-fn format_float(value: f64) -> String {
+/// How float values are rendered: fixed fractional digits or scientific notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Fixed number of fractional digits.
+    Fixed(usize),
+    /// Scientific notation with this many fractional mantissa digits.
+    Scientific(usize),
+}
+
+impl FloatFormat {
+    /// Most digits worth printing for an `f64`.
+    const MAX_DIGITS: i32 = 15;
+    /// Magnitudes outside `SCIENTIFIC_BELOW..SCIENTIFIC_FROM` switch to scientific notation.
+    const SCIENTIFIC_FROM: f64 = 1.0e6;
+    const SCIENTIFIC_BELOW: f64 = 1.0e-4;
+
+    /// Precision that resolves `significant` digits of `span` for values up to `magnitude`.
+    ///
+    /// A value of 1234.5 on an axis spanning 0..2000 with four significant figures prints as
+    /// `1234`, while the same figures on a 0..2 axis print `1.235`. A zero or non-finite span
+    /// falls back to the magnitude itself.
+    pub fn adaptive(span: f64, magnitude: f64, significant: usize) -> Self {
+        let significant = i32::try_from(significant.max(1)).unwrap_or(Self::MAX_DIGITS);
+        let magnitude = magnitude.abs();
+        let span = span.abs();
+        let reference = if span.is_finite() && span > 0.0 {
+            span
+        } else {
+            magnitude
+        };
+        if !reference.is_finite() || reference == 0.0 {
+            return Self::Fixed(Self::digits(significant - 1));
+        }
+        let span_exp = decimal_exponent(reference);
+        let scientific = magnitude.is_finite()
+            && (magnitude >= Self::SCIENTIFIC_FROM
+                || (magnitude > 0.0 && magnitude < Self::SCIENTIFIC_BELOW));
+        if scientific {
+            let value_exp = decimal_exponent(magnitude);
+            Self::Scientific(Self::digits(significant - 1 + value_exp - span_exp))
+        } else {
+            Self::Fixed(Self::digits(significant - 1 - span_exp))
+        }
+    }
+
+    fn digits(digits: i32) -> usize {
+        usize::try_from(digits.clamp(0, Self::MAX_DIGITS)).unwrap_or(0)
+    }
+
+    /// Render `value`, keeping trailing zeros so a column lines up.
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Self::Fixed(decimals) => format!("{value:.decimals$}"),
+            Self::Scientific(digits) => format!("{value:.digits$e}"),
+        }
+    }
+
+    /// Render `value` with trailing fractional zeros (and `-0`) trimmed.
+    pub fn format_trimmed(self, value: f64) -> String {
+        match self {
+            Self::Fixed(decimals) => format_float(value, decimals),
+            Self::Scientific(digits) => {
+                let text = format!("{value:.digits$e}");
+                let Some((mantissa, exponent)) = text.split_once('e') else {
+                    return text;
+                };
+                let mantissa = trim_fraction(mantissa.to_string());
+                format!("{mantissa}e{exponent}")
+            }
+        }
+    }
+
+    /// `value` rounded to the digits this format would print.
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            Self::Fixed(decimals) => {
+                let scale = 10_f64.powi(i32::try_from(decimals).unwrap_or(i32::MAX));
+                (value * scale).round() / scale
+            }
+            Self::Scientific(_) => self.format(value).parse().unwrap_or(value),
+        }
+    }
+}
+
+/// Power of ten of the leading digit, e.g. `2` for 250.0 and `-3` for 0.004.
+fn decimal_exponent(value: f64) -> i32 {
+    #[allow(clippy::cast_possible_truncation)]
+    {
+        value.abs().log10().floor() as i32
+    }
+}
+
+fn format_float(value: f64, decimals: usize) -> String {
     // Format with fixed decimals first, then trim trailing zeros and "-0".
-    let mut text = format!("{value:.DEFAULT_FLOAT_DECIMALS$}");
+    let text = trim_fraction(format!("{value:.decimals$}"));
+    if text == "-0" { "0".to_string() } else { text }
+}
+
+fn trim_fraction(mut text: String) -> String {
     if let Some(dot) = text.find('.') {
         let mut end = text.len();
         while end > dot + 1 && text.as_bytes()[end - 1] == b'0' {
@@ -122,7 +228,7 @@ fn format_float(value: f64) -> String {
             text.truncate(dot);
         }
     }
-    if text == "-0" { "0".to_string() } else { text }
+    text
 }
 
 const fn int_to_f64(value: i64) -> f64 {
@@ -170,39 +276,118 @@ fn format_datetime(dt: &NaiveDateTime) -> String {
     }
 }
 
+/// How `DateTime` text typed for an axis is read.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimeInput {
+    /// strptime-style pattern (`%d %b %Y %H:%M`) tried before the built-in formats; empty
+    /// uses only the built-in ones.
+    pub pattern: String,
+    /// Offset of the local time in values written without one.
+    pub offset: FixedOffset,
+}
+
+impl Default for TimeInput {
+    fn default() -> Self {
+        Self {
+            pattern: String::new(),
+            offset: FixedOffset::east_opt(0).expect("zero offset"),
+        }
+    }
+}
+
+/// Parse a UTC offset such as `+03:00`, `-0530`, `+3`, `UTC+2` or `Z`; empty reads as UTC.
+pub fn parse_utc_offset(input: &str) -> Option<FixedOffset> {
+    let s = input.trim();
+    let s = s
+        .strip_prefix("UTC")
+        .or_else(|| s.strip_prefix("GMT"))
+        .unwrap_or(s)
+        .trim();
+    if s.is_empty() || s.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+    let (sign, rest) = if let Some(rest) = s.strip_prefix('+') {
+        (1, rest)
+    } else {
+        (-1, s.strip_prefix('-')?)
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if rest.len() == 4 && rest.is_ascii() => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours: i32 = hours.parse().ok().filter(|h| (0..=14).contains(h))?;
+    let minutes: i32 = minutes.parse().ok().filter(|m| (0..60).contains(m))?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
 /// Parse a string into an axis value using the given unit.
 ///
 /// For `DateTime`, multiple common formats are accepted (RFC3339, with/without
 /// timezone offsets, or date-only). Timezone inputs are converted to UTC.
 pub fn parse_axis_value(input: &str, unit: AxisUnit) -> Option<AxisValue> {
+    parse_axis_value_with(input, unit, &TimeInput::default())
+}
+
+/// Like [`parse_axis_value`], reading date-times with `time`: its pattern is tried first, and
+/// values without an offset of their own are taken in its offset.
+pub fn parse_axis_value_with(input: &str, unit: AxisUnit, time: &TimeInput) -> Option<AxisValue> {
     match unit {
         AxisUnit::Float => input.trim().parse::<f64>().ok().map(AxisValue::Float),
-        AxisUnit::DateTime => parse_datetime(input).map(AxisValue::DateTime),
+        AxisUnit::DateTime => parse_datetime(input, time).map(AxisValue::DateTime),
     }
 }
 
-fn parse_datetime(input: &str) -> Option<NaiveDateTime> {
+fn parse_datetime(input: &str, time: &TimeInput) -> Option<NaiveDateTime> {
     let s = input.trim();
     if s.is_empty() {
         return None;
     }
+    let local_to_utc = |local: NaiveDateTime| {
+        local.checked_sub_signed(chrono::TimeDelta::seconds(i64::from(
+            time.offset.local_minus_utc(),
+        )))
+    };
+    let pattern = time.pattern.trim();
+    if !pattern.is_empty() {
+        if let Ok(dt) = chrono::DateTime::parse_from_str(s, pattern) {
+            return Some(dt.naive_utc());
+        }
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, pattern) {
+            return local_to_utc(dt);
+        }
+        if let Ok(d) = NaiveDate::parse_from_str(s, pattern) {
+            return local_to_utc(d.and_hms_opt(0, 0, 0)?);
+        }
+    }
+    parse_builtin_datetime(s).and_then(|(dt, has_offset)| {
+        if has_offset {
+            Some(dt)
+        } else {
+            local_to_utc(dt)
+        }
+    })
+}
+
+/// `s` read with the built-in formats, and whether it carried its own offset.
+fn parse_builtin_datetime(s: &str) -> Option<(NaiveDateTime, bool)> {
     // Try RFC3339 first, then timezone-aware formats, then naive date/time.
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
-        return Some(dt.naive_utc());
+        return Some((dt.naive_utc(), true));
     }
     for fmt in TZ_FORMATS {
         if let Ok(dt) = chrono::DateTime::parse_from_str(s, fmt) {
-            return Some(dt.naive_utc());
+            return Some((dt.naive_utc(), true));
         }
     }
     for fmt in DATETIME_FORMATS {
         if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
-            return Some(dt);
+            return Some((dt, false));
         }
     }
     for fmt in DATE_FORMATS {
         if let Ok(d) = NaiveDate::parse_from_str(s, fmt) {
-            return d.and_hms_opt(0, 0, 0);
+            return d.and_hms_opt(0, 0, 0).map(|dt| (dt, false));
         }
     }
     None
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Scale type for an axis.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ScaleKind {
     /// Linear interpolation in value space.
     Linear,
@@ -10,16 +10,40 @@ pub enum ScaleKind {
 }
 
 /// Coordinate system for calibration and export.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CoordSystem {
     /// Cartesian coordinate system (x, y).
     Cartesian,
     /// Polar coordinate system (angle, radius).
     Polar,
+    /// Cartesian (x, y) fitted to three or more reference points, for rotated or skewed scans.
+    Affine,
+    /// Smith chart of an RF plot: normalized impedance (r, x) from the chart centre and rim.
+    Smith,
+}
+
+/// Y axis a Cartesian curve is read against, on charts with two value scales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum YAxis {
+    /// Main Y axis, usually on the left.
+    #[default]
+    Primary,
+    /// Secondary Y axis, usually on the right.
+    Secondary,
+}
+
+impl YAxis {
+    /// Short label used in export columns.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+        }
+    }
 }
 
 /// Angle unit for polar calibration/export.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AngleUnit {
     /// Degrees (0-360).
     Degrees,
@@ -38,7 +62,7 @@ impl AngleUnit {
 }
 
 /// Direction of increasing polar angle.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AngleDirection {
     /// Counter-clockwise angle increase.
     Ccw,
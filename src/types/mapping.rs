@@ -1,6 +1,6 @@
 //! Axis and polar mapping helpers for calibration.
 
-use egui::Pos2;
+use egui::{Pos2, Rect};
 
 use super::axis::{AxisUnit, AxisValue};
 use super::coord::{AngleDirection, AngleUnit, ScaleKind};
@@ -34,6 +34,9 @@ pub struct AxisMapping {
 }
 
 impl AxisMapping {
+    /// Widest range, in decades, that [`Self::log_decade_ticks`] marks.
+    pub const MAX_TICK_DECADES: f64 = 12.0;
+
     /// Build a validated axis mapping.
     pub fn try_new(
         p1: Pos2,
@@ -137,6 +140,160 @@ impl AxisMapping {
         self.numeric_at(p)
             .and_then(|s| AxisValue::from_scalar_seconds(self.unit, s))
     }
+
+    /// Span that sets display precision near `value`: the calibrated range on linear axes,
+    /// and `value` itself on log axes, where resolution grows with the value.
+    pub fn display_span(&self, value: f64) -> f64 {
+        display_span(
+            self.scale,
+            self.v1.to_scalar_seconds(),
+            self.v2.to_scalar_seconds(),
+            value,
+        )
+    }
+
+    /// Parameter t (0..1 between the calibration points) at which the axis reaches `value`.
+    ///
+    /// Inverse of [`Self::numeric_at_t`]; `None` when the value is outside the scale domain.
+    pub fn t_of_numeric(&self, value: f64) -> Option<f64> {
+        if self.scale == ScaleKind::Log10 && self.unit == AxisUnit::DateTime {
+            return None;
+        }
+        t_at_value(
+            self.scale,
+            self.v1.to_scalar_seconds(),
+            self.v2.to_scalar_seconds(),
+            value,
+        )
+    }
+
+    /// Points on the axis line inside `bounds` at the decade ticks 1·10ᵏ, 2·10ᵏ … 9·10ᵏ of a
+    /// log axis, each with its leading digit; empty on linear axes.
+    ///
+    /// Lined up against the printed ticks they show whether the chart really is logarithmic.
+    /// Ranges of more than [`Self::MAX_TICK_DECADES`] decades give no ticks, as they would
+    /// only blur into the axis line.
+    pub fn log_decade_ticks(&self, bounds: Rect) -> Vec<(Pos2, u8)> {
+        if self.scale != ScaleKind::Log10 || self.unit != AxisUnit::Float {
+            return Vec::new();
+        }
+        let corners = [
+            bounds.left_top(),
+            bounds.right_top(),
+            bounds.left_bottom(),
+            bounds.right_bottom(),
+        ];
+        let (t_min, t_max) = corners
+            .iter()
+            .map(|&corner| self.t_of_point(corner))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), t| {
+                (lo.min(t), hi.max(t))
+            });
+        let (Some(a), Some(b)) = (self.numeric_at_t(t_min), self.numeric_at_t(t_max)) else {
+            return Vec::new();
+        };
+        let (first, last) = (a.min(b).log10().floor(), a.max(b).log10().floor());
+        if !(first.is_finite() && last.is_finite()) || last - first > Self::MAX_TICK_DECADES {
+            return Vec::new();
+        }
+        let d = self.p2 - self.p1;
+        let mut ticks = Vec::new();
+        #[allow(clippy::cast_possible_truncation)]
+        let decades = first as i32..=last as i32;
+        for decade in decades {
+            for digit in 1..=9_u8 {
+                let value = f64::from(digit) * 10f64.powi(decade);
+                let Some(t) = self
+                    .t_of_numeric(value)
+                    .filter(|t| (t_min..=t_max).contains(t))
+                else {
+                    continue;
+                };
+                let point = finite_pos(
+                    t.mul_add(f64::from(d.x), f64::from(self.p1.x)),
+                    t.mul_add(f64::from(d.y), f64::from(self.p1.y)),
+                );
+                if let Some(point) = point.filter(|p| bounds.contains(*p)) {
+                    ticks.push((point, digit));
+                }
+            }
+        }
+        ticks
+    }
+
+    /// Copy with both calibration points slid along the axis line so that the pixels of
+    /// `samples` read their known values as closely as possible (least squares in `t`).
+    ///
+    /// The entered values stay; only the positions they are pinned to move, as needed when
+    /// tick marks are smudged. `None` with fewer than two usable samples, samples that share
+    /// one position, or a fit that would reverse the axis.
+    pub fn refit_to_samples(&self, samples: &[(Pos2, f64)]) -> Option<Self> {
+        let pairs: Vec<(f64, f64)> = samples
+            .iter()
+            .filter_map(|&(pixel, value)| Some((self.t_of_point(pixel), self.t_of_numeric(value)?)))
+            .collect();
+        if pairs.len() < 2 {
+            return None;
+        }
+        let n = int_to_f64(pairs.len());
+        let mean_pixel = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_value = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+        let (mut spp, mut spv) = (0.0, 0.0);
+        for &(tp, tv) in &pairs {
+            spp = (tp - mean_pixel).mul_add(tp - mean_pixel, spp);
+            spv = (tp - mean_pixel).mul_add(tv - mean_value, spv);
+        }
+        if spp <= f64::EPSILON {
+            return None;
+        }
+        // Reference positions as t_value = a + b·t_pixel; the new points sit where that
+        // line reaches 0 and 1.
+        let slope = spv / spp;
+        if !slope.is_finite() || slope <= f64::EPSILON {
+            return None;
+        }
+        let intercept = slope.mul_add(-mean_pixel, mean_value);
+        let d = self.p2 - self.p1;
+        let at = |t: f64| {
+            finite_pos(
+                t.mul_add(f64::from(d.x), f64::from(self.p1.x)),
+                t.mul_add(f64::from(d.y), f64::from(self.p1.y)),
+            )
+        };
+        Some(Self {
+            p1: at(-intercept / slope)?,
+            p2: at((1.0 - intercept) / slope)?,
+            ..self.clone()
+        })
+    }
+
+    /// Pixel position whose projections onto both axes give `(x, y)`.
+    ///
+    /// Solves the two projection equations used by [`Self::t_of_point`], so skewed or rotated
+    /// axes round-trip exactly. Returns `None` for parallel axes.
+    pub fn pixel_at(x_mapping: &Self, y_mapping: &Self, x: f64, y: f64) -> Option<Pos2> {
+        let tx = x_mapping.t_of_numeric(x)?;
+        let ty = y_mapping.t_of_numeric(y)?;
+        let (dx, x1) = (x_mapping.p2 - x_mapping.p1, x_mapping.p1);
+        let (dy, y1) = (y_mapping.p2 - y_mapping.p1, y_mapping.p1);
+        let (dxx, dxy) = (f64::from(dx.x), f64::from(dx.y));
+        let (dyx, dyy) = (f64::from(dy.x), f64::from(dy.y));
+        let det = dxx.mul_add(dyy, -(dxy * dyx));
+        if det.abs() <= f64::EPSILON {
+            return None;
+        }
+        let a = tx.mul_add(
+            dxx.mul_add(dxx, dxy * dxy),
+            dxx.mul_add(f64::from(x1.x), dxy * f64::from(x1.y)),
+        );
+        let b = ty.mul_add(
+            dyx.mul_add(dyx, dyy * dyy),
+            dyx.mul_add(f64::from(y1.x), dyy * f64::from(y1.y)),
+        );
+        let px = a.mul_add(dyy, -(b * dxy)) / det;
+        let py = dxx.mul_add(b, -(dyx * a)) / det;
+        finite_pos(px, py)
+    }
 }
 
 /// Validation errors for polar mappings.
@@ -240,6 +397,16 @@ impl PolarMapping {
         numeric_at_t(self.radius_scale, self.radius_v1, self.radius_v2, t)
     }
 
+    /// Display-precision span for a radius value; see [`AxisMapping::display_span`].
+    pub fn radius_display_span(&self, value: f64) -> f64 {
+        display_span(self.radius_scale, self.radius_v1, self.radius_v2, value)
+    }
+
+    /// Display-precision span for angles: the calibrated angle range.
+    pub fn angle_display_span(&self) -> f64 {
+        (self.angle_v2 - self.angle_v1).abs()
+    }
+
     /// Compute the angle value at a pixel position.
     ///
     /// Returns `None` if the point coincides with the origin (undefined angle).
@@ -255,12 +422,333 @@ impl PolarMapping {
         Some((self.angle_v2 - self.angle_v1).mul_add(t, self.angle_v1))
     }
 
+    /// Pixel position for an (angle, radius) pair; inverse of [`Self::angle_at`]/[`Self::radius_at`].
+    ///
+    /// Returns `None` when the radius maps inside the origin or outside the log domain.
+    pub fn pixel_at(&self, angle: f64, radius: f64) -> Option<Pos2> {
+        let t_r = t_at_value(self.radius_scale, self.radius_v1, self.radius_v2, radius)?;
+        let dist = (self.radius_d2 - self.radius_d1).mul_add(t_r, self.radius_d1);
+        if dist < 0.0 {
+            return None;
+        }
+        let t_a = (angle - self.angle_v1) / (self.angle_v2 - self.angle_v1);
+        let delta = t_a * self.angle_span;
+        let raw = match self.angle_direction {
+            AngleDirection::Ccw => self.angle_a1 + delta,
+            AngleDirection::Cw => self.angle_a1 - delta,
+        };
+        finite_pos(
+            dist.mul_add(raw.cos(), f64::from(self.origin.x)),
+            dist.mul_add(raw.sin(), f64::from(self.origin.y)),
+        )
+    }
+
     /// Metadata about the angle units used for `angle_v1/angle_v2` values.
     pub const fn angle_unit(&self) -> AngleUnit {
         self.angle_unit
     }
 }
 
+/// Validation errors for affine calibrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AffineMappingError {
+    TooFewPoints,
+    CollinearPoints,
+    /// The fitted X and Y value gradients are parallel, so pixels cannot be recovered.
+    ParallelAxes,
+    Axis(AxisMappingError),
+}
+
+/// Reference point of an affine calibration: a pixel and the values it stands for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineReference {
+    pub pixel: Pos2,
+    pub x: AxisValue,
+    pub y: AxisValue,
+}
+
+/// Full 2-D affine calibration fitted by least squares to three or more reference points.
+///
+/// Each axis value (its log10 on log axes) is an affine function of the pixel position,
+/// which handles rotated and sheared scans. The fit is expressed as two [`AxisMapping`]s
+/// whose segments run along the value gradients, so projecting a pixel onto them gives the
+/// fitted values and everything built on cartesian mappings works unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineMapping {
+    pub x: AxisMapping,
+    pub y: AxisMapping,
+    /// RMS distance in pixels between the reference points and their fitted positions.
+    pub rms_residual: f64,
+}
+
+impl AffineMapping {
+    /// Fit the mapping to `refs`; the axes take `(scale, unit)` pairs.
+    pub fn fit(
+        refs: &[AffineReference],
+        x_axis: (ScaleKind, AxisUnit),
+        y_axis: (ScaleKind, AxisUnit),
+    ) -> Result<Self, AffineMappingError> {
+        if refs.len() < 3 {
+            return Err(AffineMappingError::TooFewPoints);
+        }
+        let pixels: Vec<(f64, f64)> = refs
+            .iter()
+            .map(|r| (f64::from(r.pixel.x), f64::from(r.pixel.y)))
+            .collect();
+        let frame = PixelFrame::new(&pixels).ok_or(AffineMappingError::CollinearPoints)?;
+        let x = fit_affine_axis(&frame, refs.iter().map(|r| &r.x), x_axis)?;
+        let y = fit_affine_axis(&frame, refs.iter().map(|r| &r.y), y_axis)?;
+
+        let mut sum_sq = 0.0;
+        for r in refs {
+            let fitted =
+                AxisMapping::pixel_at(&x, &y, r.x.to_scalar_seconds(), r.y.to_scalar_seconds())
+                    .ok_or(AffineMappingError::ParallelAxes)?;
+            sum_sq += f64::from(fitted.distance_sq(r.pixel));
+        }
+        Ok(Self {
+            x,
+            y,
+            rms_residual: (sum_sq / int_to_f64(refs.len())).sqrt(),
+        })
+    }
+}
+
+/// Centered second moments of the reference pixels, shared by both axis fits.
+struct PixelFrame {
+    pixels: Vec<(f64, f64)>,
+    mean: (f64, f64),
+    suu: f64,
+    suv: f64,
+    svv: f64,
+    det: f64,
+}
+
+impl PixelFrame {
+    /// `None` when the pixels are coincident or lie on one line.
+    fn new(pixels: &[(f64, f64)]) -> Option<Self> {
+        let n = int_to_f64(pixels.len());
+        let mean = (
+            pixels.iter().map(|p| p.0).sum::<f64>() / n,
+            pixels.iter().map(|p| p.1).sum::<f64>() / n,
+        );
+        let (mut suu, mut suv, mut svv) = (0.0, 0.0, 0.0);
+        for &(px, py) in pixels {
+            let (u, v) = (px - mean.0, py - mean.1);
+            suu = u.mul_add(u, suu);
+            suv = u.mul_add(v, suv);
+            svv = v.mul_add(v, svv);
+        }
+        let det = suu.mul_add(svv, -(suv * suv));
+        // Relative test: a thin triangle is as unusable as a degenerate one.
+        let spread = suu + svv;
+        (spread > f64::EPSILON && det > 1.0e-6 * spread * spread).then(|| Self {
+            pixels: pixels.to_vec(),
+            mean,
+            suu,
+            suv,
+            svv,
+            det,
+        })
+    }
+
+    /// RMS distance of the pixels from their mean.
+    fn radius(&self) -> f64 {
+        ((self.suu + self.svv) / int_to_f64(self.pixels.len())).sqrt()
+    }
+}
+
+/// Least-squares plane through `values` over the frame, as an axis mapping.
+fn fit_affine_axis<'a>(
+    frame: &PixelFrame,
+    values: impl Iterator<Item = &'a AxisValue>,
+    (scale, unit): (ScaleKind, AxisUnit),
+) -> Result<AxisMapping, AffineMappingError> {
+    let scalars = values
+        .map(|value| affine_scalar(scale, unit, value))
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(AffineMappingError::Axis)?;
+    let mean = scalars.iter().sum::<f64>() / int_to_f64(scalars.len());
+    let (mut sus, mut svs) = (0.0, 0.0);
+    for (&(px, py), &s) in frame.pixels.iter().zip(&scalars) {
+        sus = (px - frame.mean.0).mul_add(s - mean, sus);
+        svs = (py - frame.mean.1).mul_add(s - mean, svs);
+    }
+    let gx = frame.svv.mul_add(sus, -(frame.suv * svs)) / frame.det;
+    let gy = frame.suu.mul_add(svs, -(frame.suv * sus)) / frame.det;
+    let grad = gx.hypot(gy);
+    // Anchor the segment one frame radius either side of the mean, so it spans the
+    // reference points whatever the value range.
+    let reach = frame.radius();
+    let delta = grad * reach;
+    if !delta.is_finite() || delta <= f64::EPSILON * mean.abs().max(1.0) {
+        return Err(AffineMappingError::Axis(AxisMappingError::EqualValues));
+    }
+    let (ux, uy) = (gx / grad * reach, gy / grad * reach);
+    let p1 = finite_pos(frame.mean.0 - ux, frame.mean.1 - uy)
+        .ok_or(AffineMappingError::Axis(AxisMappingError::NonFiniteValue))?;
+    let p2 = finite_pos(frame.mean.0 + ux, frame.mean.1 + uy)
+        .ok_or(AffineMappingError::Axis(AxisMappingError::NonFiniteValue))?;
+    let value = |s: f64| {
+        let s = if scale == ScaleKind::Log10 {
+            10f64.powf(s)
+        } else {
+            s
+        };
+        AxisValue::from_scalar_seconds(unit, s)
+            .ok_or(AffineMappingError::Axis(AxisMappingError::NonFiniteValue))
+    };
+    AxisMapping::try_new(
+        p1,
+        p2,
+        value(mean - delta)?,
+        value(mean + delta)?,
+        scale,
+        unit,
+    )
+    .map_err(AffineMappingError::Axis)
+}
+
+/// Value in the space the fit is linear in: scalar seconds, or their log10 on log axes.
+fn affine_scalar(
+    scale: ScaleKind,
+    unit: AxisUnit,
+    value: &AxisValue,
+) -> Result<f64, AxisMappingError> {
+    match (unit, value) {
+        (AxisUnit::Float, AxisValue::Float(v)) => {
+            if !v.is_finite() {
+                return Err(AxisMappingError::NonFiniteValue);
+            }
+            match scale {
+                ScaleKind::Linear => Ok(*v),
+                ScaleKind::Log10 if *v > 0.0 => Ok(v.log10()),
+                ScaleKind::Log10 => Err(AxisMappingError::LogScaleRequiresPositiveValues),
+            }
+        }
+        (AxisUnit::DateTime, AxisValue::DateTime(_)) => match scale {
+            ScaleKind::Linear => Ok(value.to_scalar_seconds()),
+            ScaleKind::Log10 => Err(AxisMappingError::LogScaleUnsupportedForDateTime),
+        },
+        _ => Err(AxisMappingError::UnitValueMismatch),
+    }
+}
+
+/// Validation errors for Smith chart calibrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmithMappingError {
+    NonFiniteInput,
+    /// The short-circuit point sits on the centre, so the chart has no size.
+    ZeroRadius,
+    NonPositiveReference,
+}
+
+/// Smith chart calibration from pixels to normalized impedance `z = r + jx`.
+///
+/// The chart centre is the matched point `z = 1` and the short-circuit point `z = 0` marks
+/// the left end of the real axis on the outer circle, which fixes the radius and any rotation
+/// of the scan. A pixel stands for the reflection coefficient `Γ` of its offset from the
+/// centre in chart radii, with positive reactance above the real axis, and
+/// `z = (1 + Γ) / (1 − Γ)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmithMapping {
+    pub center: Pos2,
+    /// Pixel offset from the centre to the open-circuit point `Γ = 1`; its length is the
+    /// chart radius.
+    open: (f64, f64),
+    /// Reference impedance in ohms that normalized values are scaled by.
+    pub reference_ohms: f64,
+}
+
+impl SmithMapping {
+    /// Build a validated mapping from the chart centre, its short-circuit point and `Z0`.
+    pub fn try_new(
+        center: Pos2,
+        short: Pos2,
+        reference_ohms: f64,
+    ) -> Result<Self, SmithMappingError> {
+        if !center.x.is_finite()
+            || !center.y.is_finite()
+            || !short.x.is_finite()
+            || !short.y.is_finite()
+            || !reference_ohms.is_finite()
+        {
+            return Err(SmithMappingError::NonFiniteInput);
+        }
+        if reference_ohms <= 0.0 {
+            return Err(SmithMappingError::NonPositiveReference);
+        }
+        let open = (f64::from(center.x - short.x), f64::from(center.y - short.y));
+        if open.0.hypot(open.1) <= f64::EPSILON {
+            return Err(SmithMappingError::ZeroRadius);
+        }
+        Ok(Self {
+            center,
+            open,
+            reference_ohms,
+        })
+    }
+
+    /// Chart radius in pixels.
+    pub fn radius(&self) -> f64 {
+        self.open.0.hypot(self.open.1)
+    }
+
+    /// Reflection coefficient `(re, im)` at a pixel position.
+    pub fn reflection_at(&self, p: Pos2) -> (f64, f64) {
+        let dx = f64::from(p.x - self.center.x);
+        let dy = f64::from(p.y - self.center.y);
+        let (ox, oy) = self.open;
+        let norm = ox.mul_add(ox, oy * oy);
+        // Screen y grows downward, so "up" from the real axis is `(oy, -ox)`.
+        (
+            dx.mul_add(ox, dy * oy) / norm,
+            dx.mul_add(oy, -dy * ox) / norm,
+        )
+    }
+
+    /// Normalized impedance `(r, x)` at a pixel position.
+    ///
+    /// Returns `None` at the open-circuit point, where the impedance is infinite.
+    pub fn impedance_at(&self, p: Pos2) -> Option<(f64, f64)> {
+        let (re, im) = self.reflection_at(p);
+        let denom = (1.0 - re).mul_add(1.0 - re, im * im);
+        if denom <= f64::EPSILON {
+            return None;
+        }
+        Some((
+            re.mul_add(-re, im.mul_add(-im, 1.0)) / denom,
+            2.0 * im / denom,
+        ))
+    }
+
+    /// Pixel position of the normalized impedance `(r, x)`; inverse of [`Self::impedance_at`].
+    pub fn pixel_at(&self, r: f64, x: f64) -> Option<Pos2> {
+        let (re, im) = reflection_of(r, x)?;
+        let (ox, oy) = self.open;
+        finite_pos(
+            re.mul_add(ox, im * oy) + f64::from(self.center.x),
+            re.mul_add(oy, -im * ox) + f64::from(self.center.y),
+        )
+    }
+}
+
+/// Reflection coefficient `(re, im)` of the normalized impedance `(r, x)`, `Γ = (z − 1) / (z + 1)`.
+///
+/// Returns `None` for non-finite input and at the pole `z = −1`.
+pub fn reflection_of(r: f64, x: f64) -> Option<(f64, f64)> {
+    let denom = (r + 1.0).mul_add(r + 1.0, x * x);
+    if !denom.is_finite() || denom <= f64::EPSILON {
+        return None;
+    }
+    Some((r.mul_add(r, x.mul_add(x, -1.0)) / denom, 2.0 * x / denom))
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn int_to_f64(n: usize) -> f64 {
+    n as f64
+}
+
 fn normalize_angle_rad(angle: f64) -> f64 {
     // Normalize to the [0, 2*pi) range.
     angle.rem_euclid(std::f64::consts::TAU)
@@ -274,6 +762,13 @@ fn angle_delta(start: f64, end: f64, direction: AngleDirection) -> f64 {
     }
 }
 
+fn display_span(scale: ScaleKind, v1: f64, v2: f64, value: f64) -> f64 {
+    match scale {
+        ScaleKind::Linear => (v2 - v1).abs(),
+        ScaleKind::Log10 => value.abs(),
+    }
+}
+
 fn numeric_at_t(scale: ScaleKind, v1: f64, v2: f64, t: f64) -> Option<f64> {
     match scale {
         ScaleKind::Linear => Some((v2 - v1).mul_add(t, v1)),
@@ -288,3 +783,26 @@ fn numeric_at_t(scale: ScaleKind, v1: f64, v2: f64, t: f64) -> Option<f64> {
         }
     }
 }
+
+/// Inverse of [`numeric_at_t`]: the parameter t at which the scale reaches `value`.
+fn t_at_value(scale: ScaleKind, v1: f64, v2: f64, value: f64) -> Option<f64> {
+    if !value.is_finite() {
+        return None;
+    }
+    let t = match scale {
+        ScaleKind::Linear => (value - v1) / (v2 - v1),
+        ScaleKind::Log10 => {
+            if v1 <= 0.0 || v2 <= 0.0 || value <= 0.0 {
+                return None;
+            }
+            let l1 = v1.log10();
+            (value.log10() - l1) / (v2.log10() - l1)
+        }
+    };
+    t.is_finite().then_some(t)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn finite_pos(x: f64, y: f64) -> Option<Pos2> {
+    (x.is_finite() && y.is_finite()).then(|| Pos2::new(x as f32, y as f32))
+}
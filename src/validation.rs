@@ -0,0 +1,179 @@
+//! Per-project data checks run on export payloads before anything is written.
+//!
+//! Rules look at the exported rows, so they see interpolated or fitted samples exactly as they
+//! will land in the file. A failing rule either blocks the export or only warns, as set per
+//! rule.
+
+use crate::interp::XYPoint;
+use serde::{Deserialize, Serialize};
+
+/// Exported column a rule looks at: the first (`x`, or angle in polar mode) or the second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleColumn {
+    X,
+    Y,
+}
+
+impl RuleColumn {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::X => "x",
+            Self::Y => "y",
+        }
+    }
+
+    const fn value(self, point: XYPoint) -> f64 {
+        match self {
+            Self::X => point.x,
+            Self::Y => point.y,
+        }
+    }
+}
+
+/// Condition every exported row must meet.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RuleCheck {
+    AtLeast(f64),
+    AtMost(f64),
+    /// Each row above the previous one of the same curve.
+    StrictlyIncreasing,
+    /// Each row below the previous one of the same curve.
+    StrictlyDecreasing,
+}
+
+/// What a failing rule does to the export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleSeverity {
+    #[default]
+    Block,
+    Warn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ValidationRule {
+    pub column: RuleColumn,
+    pub check: RuleCheck,
+    pub severity: RuleSeverity,
+}
+
+impl Default for ValidationRule {
+    fn default() -> Self {
+        Self {
+            column: RuleColumn::Y,
+            check: RuleCheck::AtLeast(0.0),
+            severity: RuleSeverity::Block,
+        }
+    }
+}
+
+impl ValidationRule {
+    /// `y ≥ 0`, `x ↑` and the like, for violation lists.
+    pub fn summary(&self) -> String {
+        let column = self.column.label();
+        match self.check {
+            RuleCheck::AtLeast(bound) => format!("{column} ≥ {bound}"),
+            RuleCheck::AtMost(bound) => format!("{column} ≤ {bound}"),
+            RuleCheck::StrictlyIncreasing => format!("{column} ↑"),
+            RuleCheck::StrictlyDecreasing => format!("{column} ↓"),
+        }
+    }
+
+    fn holds(&self, value: f64, previous: Option<f64>) -> bool {
+        match self.check {
+            RuleCheck::AtLeast(bound) => value >= bound,
+            RuleCheck::AtMost(bound) => value <= bound,
+            RuleCheck::StrictlyIncreasing => previous.is_none_or(|prev| value > prev),
+            RuleCheck::StrictlyDecreasing => previous.is_none_or(|prev| value < prev),
+        }
+    }
+}
+
+/// One exported row that breaks a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Index into the rule list.
+    pub rule: usize,
+    pub curve: String,
+    /// Zero-based row within the curve.
+    pub row: usize,
+    pub value: f64,
+}
+
+/// Every row of the named `curves` that breaks one of `rules`, rule by rule.
+pub fn check_rules(rules: &[ValidationRule], curves: &[(&str, &[XYPoint])]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for (rule_idx, rule) in rules.iter().enumerate() {
+        for &(name, points) in curves {
+            let mut previous = None;
+            for (row, point) in points.iter().enumerate() {
+                let value = rule.column.value(*point);
+                if !rule.holds(value, previous) {
+                    violations.push(Violation {
+                        rule: rule_idx,
+                        curve: name.to_string(),
+                        row,
+                        value,
+                    });
+                }
+                previous = Some(value);
+            }
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(values: &[(f64, f64)]) -> Vec<XYPoint> {
+        values.iter().map(|&(x, y)| XYPoint { x, y }).collect()
+    }
+
+    #[test]
+    fn rows_breaking_a_rule_are_listed_per_curve() {
+        let (a, b) = (
+            points(&[(0.0, 1.0), (1.0, -0.5), (2.0, 2.0)]),
+            points(&[(0.0, 3.0), (0.0, -1.0), (-1.0, 0.0)]),
+        );
+        let curves = [("a", a.as_slice()), ("b", b.as_slice())];
+        let rules = [
+            ValidationRule::default(),
+            ValidationRule {
+                column: RuleColumn::X,
+                check: RuleCheck::StrictlyIncreasing,
+                severity: RuleSeverity::Warn,
+            },
+        ];
+        let found: Vec<_> = check_rules(&rules, &curves)
+            .into_iter()
+            .map(|v| (v.rule, v.curve, v.row))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (0, "a".to_string(), 1),
+                (0, "b".to_string(), 1),
+                (1, "b".to_string(), 1),
+                (1, "b".to_string(), 2),
+            ]
+        );
+        assert_eq!(rules[0].summary(), "y ≥ 0");
+        assert_eq!(rules[1].summary(), "x ↑");
+    }
+
+    #[test]
+    fn monotonic_checks_restart_with_each_curve() {
+        let (a, b) = (
+            points(&[(3.0, 0.0), (2.0, 0.0)]),
+            points(&[(5.0, 0.0), (4.0, 0.0)]),
+        );
+        let curves = [("a", a.as_slice()), ("b", b.as_slice())];
+        let rule = ValidationRule {
+            column: RuleColumn::X,
+            check: RuleCheck::StrictlyDecreasing,
+            severity: RuleSeverity::Block,
+        };
+        assert!(check_rules(&[rule], &curves).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! Initial main window geometry: the `[window]` config, the remembered geometry of the last
+//! run, and the `--geometry WIDTHxHEIGHT[+X+Y]` command-line override.
+//!
+//! eframe stores the window size, position and maximized state in its persistence file and
+//! restores them over the configured size; an override wins over both for one launch.
+
+use crate::config::WindowConfig;
+use egui::{Pos2, Vec2, ViewportBuilder};
+
+/// Command-line flag that sets the window size and, optionally, its position.
+pub const GEOMETRY_FLAG: &str = "--geometry";
+
+/// Window size in points and outer position in screen points, X11 `-geometry` style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowGeometry {
+    pub size: Vec2,
+    pub position: Option<Pos2>,
+}
+
+impl WindowGeometry {
+    /// Parse `1600x900` or `1600x900+40+20`. A `-` offset is a plain negative coordinate, not a
+    /// distance from the right or bottom edge as in X11: the screen size is unknown until the
+    /// window opens.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid {GEOMETRY_FLAG} '{text}': expected WIDTHxHEIGHT[+X+Y].");
+        let text = text.trim();
+        let split = text
+            .get(1..)
+            .and_then(|rest| rest.find(['+', '-']))
+            .map_or(text.len(), |idx| idx + 1);
+        let (dims, offsets) = text.split_at(split);
+        let (width, height) = dims.split_once(['x', 'X']).ok_or_else(invalid)?;
+        let length = |value: &str| {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .ok_or_else(invalid)
+        };
+        let size = Vec2::new(length(width)?, length(height)?);
+        if offsets.is_empty() {
+            return Ok(Self {
+                size,
+                position: None,
+            });
+        }
+        let second = offsets
+            .get(1..)
+            .and_then(|rest| rest.find(['+', '-']))
+            .map(|idx| idx + 1)
+            .ok_or_else(invalid)?;
+        let (x, y) = offsets.split_at(second);
+        let offset = |value: &str| {
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(invalid)
+        };
+        Ok(Self {
+            size,
+            position: Some(Pos2::new(offset(x)?, offset(y)?)),
+        })
+    }
+}
+
+/// Viewport for the main window and whether eframe should restore and save its geometry.
+///
+/// The override is not saved, so the next launch without it returns to the remembered
+/// geometry.
+pub fn main_viewport(
+    config: &WindowConfig,
+    geometry: Option<WindowGeometry>,
+) -> (ViewportBuilder, bool) {
+    let builder = ViewportBuilder::default().with_min_inner_size(WindowConfig::MIN_SIZE);
+    match geometry {
+        Some(geometry) => {
+            let builder = builder.with_inner_size(geometry.size);
+            let builder = match geometry.position {
+                Some(position) => builder.with_position(position),
+                None => builder,
+            };
+            (builder, false)
+        }
+        None => (
+            builder.with_inner_size(config.size_sanitized()),
+            config.remember,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geometry_parses_size_and_optional_position() {
+        assert_eq!(
+            WindowGeometry::parse("1600x900"),
+            Ok(WindowGeometry {
+                size: Vec2::new(1600.0, 900.0),
+                position: None,
+            })
+        );
+        assert_eq!(
+            WindowGeometry::parse("1024X768+40-10"),
+            Ok(WindowGeometry {
+                size: Vec2::new(1024.0, 768.0),
+                position: Some(Pos2::new(40.0, -10.0)),
+            })
+        );
+        for bad in ["", "1600", "x900", "0x900", "1600x900+40", "1600x900+a+b"] {
+            assert!(WindowGeometry::parse(bad).is_err(), "{bad}");
+        }
+    }
+}